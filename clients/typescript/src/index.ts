@@ -0,0 +1,66 @@
+/**
+ * Typed client for the Veritas Q Truth API.
+ *
+ * `schema.ts` is generated from the server's OpenAPI spec
+ * (`veritas-server openapi`) by `npm run generate`; request paths, bodies
+ * and responses are checked against it at compile time.
+ */
+
+import createClient, { type Client, type Middleware } from "openapi-fetch";
+
+import type { components, paths } from "./schema.js";
+
+export type { components, paths };
+
+/** Schemas of the API (request and response bodies) */
+export type Schemas = components["schemas"];
+
+/** Body of every error response: a message and a stable `code` */
+export type ApiErrorBody = Schemas["ErrorResponse"];
+
+/** Bearer token, or a function returning the current one */
+export type TokenSource = string | (() => string | undefined | Promise<string | undefined>);
+
+export interface VeritasClientOptions {
+  /** Server URL, e.g. `https://api.veritas-q.io` */
+  baseUrl: string;
+  /** Clerk session token, self-hosted account token or OAuth access token */
+  token?: TokenSource;
+  /** API key of a machine client (`X-API-Key`) */
+  apiKey?: string;
+  /** Custom fetch implementation */
+  fetch?: typeof globalThis.fetch;
+}
+
+/** Header carrying API keys */
+export const API_KEY_HEADER = "X-API-Key";
+
+/**
+ * Create a client sending the configured credentials with every request.
+ *
+ * ```ts
+ * const veritas = createVeritasClient({ baseUrl, token: () => session.getToken() });
+ * const { data, error } = await veritas.GET("/api/v1/seals/{seal_id}", {
+ *   params: { path: { seal_id } },
+ * });
+ * ```
+ */
+export function createVeritasClient(options: VeritasClientOptions): Client<paths> {
+  const client = createClient<paths>({ baseUrl: options.baseUrl, fetch: options.fetch });
+
+  const credentials: Middleware = {
+    async onRequest({ request }) {
+      const token = typeof options.token === "function" ? await options.token() : options.token;
+      if (token) {
+        request.headers.set("Authorization", `Bearer ${token}`);
+      }
+      if (options.apiKey) {
+        request.headers.set(API_KEY_HEADER, options.apiKey);
+      }
+      return request;
+    },
+  };
+  client.use(credentials);
+
+  return client;
+}
@@ -0,0 +1,385 @@
+#!/usr/bin/env python3
+"""Generate the interoperability test vectors in conformance/.
+
+The vectors let other implementations (the JS verifier, the mobile SDKs,
+third-party verifiers) check they read and verify seals exactly like
+veritas-core: seals in CBOR and JSON, signing keys and their identifiers,
+C2PA manifest stores, and the expected result of verifying each of them.
+See conformance/README.md for the format.
+
+Seals are built independently of the Rust code, with the CBOR encoder of
+scripts/generate-seal-fixtures.py. Signing keys are derived from fixed seeds,
+so `vectors.json` is regenerated identically; seal files embed randomized
+ML-DSA signatures and are never overwritten. Each group of files derived
+from the same seal is written together, only when none of them exists.
+
+Requires Python `cryptography` >= 48 built against OpenSSL 3.5 (ML-DSA).
+
+Usage: ./scripts/generate-conformance-vectors.py [output_dir]
+"""
+
+import base64
+import copy
+import hashlib
+import importlib.util
+import json
+import sys
+from pathlib import Path
+
+from cryptography.hazmat.primitives.asymmetric import mldsa
+
+_spec = importlib.util.spec_from_file_location(
+    "seal_fixtures", Path(__file__).with_name("generate-seal-fixtures.py")
+)
+fx = importlib.util.module_from_spec(_spec)
+_spec.loader.exec_module(fx)
+
+OUTPUT_DIR = Path(sys.argv[1] if len(sys.argv) > 1 else "conformance")
+
+FORMAT_VERSION = 1
+SEAL_VERSION = 16
+ISSUER = "did:web:veritas-q.com"
+# Signature results are checked one day after capture
+VERIFIED_AT = fx.CAPTURE_TS + 86_400_000
+# One byte of segment 1 (bytes 8..16) changed
+SEGMENT_EDITED = fx.CONTENT.replace(b"Q seal", b"Q Seal")
+# Multicodec varint of ML-DSA-65 public keys (0x1211)
+MLDSA65_PUB_MULTICODEC = bytes([0x91, 0x24])
+BASE58_ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def test_key(name):
+    """Deterministic ML-DSA-65 key pair, for test vectors only."""
+    seed = hashlib.sha3_256(b"veritas conformance key " + name.encode()).digest()
+    return mldsa.MLDSA65PrivateKey.from_seed_bytes(seed)
+
+
+def public_bytes(key):
+    return key.public_key().public_bytes_raw()
+
+
+def base58(data):
+    number = int.from_bytes(data, "big")
+    encoded = ""
+    while number:
+        number, digit = divmod(number, 58)
+        encoded = BASE58_ALPHABET[digit] + encoded
+    zeros = len(data) - len(data.lstrip(b"\0"))
+    return "1" * zeros + encoded
+
+
+def did_key(key):
+    return "did:key:z" + base58(MLDSA65_PUB_MULTICODEC + public_bytes(key))
+
+
+def did_key_id(did):
+    """DID URL of the key of a did:key DID."""
+    return f"{did}#{did[len('did:key:'):]}"
+
+
+def json_seal(fields):
+    """Seal as veritas_core::VeritasSeal::from_json reads it."""
+    return json.dumps(fields, default=list, separators=(",", ":")).encode()
+
+
+def c2pa_manifest_store(fields=None):
+    """C2PA manifest store as rendered to JSON by the C2PA reader, with the
+    quantum seal assertion of `fields` (none if `fields` is None)."""
+    assertions = [{"label": "c2pa.actions", "data": {"actions": [{"action": "c2pa.created"}]}}]
+    if fields is not None:
+        assertions.append(
+            {
+                "label": "veritas.quantum_seal",
+                "data": {
+                    "version": 1,
+                    "qrng_entropy": bytes(fields["qrng_entropy"]).hex(),
+                    "qrng_source": "LFD_CLOUD",
+                    "entropy_timestamp": fields["entropy_timestamp"],
+                    "capture_timestamp": fields["capture_timestamp_utc"],
+                    "ml_dsa_signature": base64.b64encode(bytes(fields["signature"])).decode(),
+                    "ml_dsa_public_key": base64.b64encode(bytes(fields["public_key"])).decode(),
+                    "content_hash": bytes(fields["content_hash"]["crypto_hash"]).hex(),
+                },
+            }
+        )
+    label = "urn:uuid:6f1d2c3b-4a59-4e8f-9b7c-0d1e2f3a4b5c"
+    store = {
+        "active_manifest": label,
+        "manifests": {
+            label: {
+                "claim_generator": "veritas-q/0.1.0",
+                "title": "conformance.jpg",
+                "format": "image/jpeg",
+                "instance_id": "xmp:iid:0a1b2c3d-4e5f-4a6b-8c7d-9e0f1a2b3c4d",
+                "assertions": assertions,
+                "signature_info": {"issuer": "Veritas Q Conformance", "alg": "Es256"},
+            }
+        },
+    }
+    return (json.dumps(store, indent=2) + "\n").encode()
+
+
+def tampered(fields, **changes):
+    altered = copy.deepcopy(fields)
+    altered.update(changes)
+    return fx.cbor(altered)
+
+
+def flip_byte(data, index):
+    altered = bytearray(data)
+    altered[index] ^= 0x01
+    return fx.Array(altered)
+
+
+def segment_leaves(content, size):
+    return [
+        hashlib.sha3_256(
+            b"\x00veritas-segment-v1" + i.to_bytes(4, "big") + content[start : start + size]
+        ).digest()
+        for i, start in enumerate(range(0, len(content), size))
+    ]
+
+
+def key_vector(name, key, did):
+    public_key = public_bytes(key)
+    key_id = hashlib.sha3_256(public_key).digest()[:8].hex()
+    return {
+        "id": name,
+        "public_key": public_key.hex(),
+        "key_id": key_id,
+        "did_key": did,
+        "did_key_id": did_key_id(did),
+        "did_web_key_id": f"{ISSUER}#{key_id}",
+    }
+
+
+def seal_vectors(signer):
+    content = fx.CONTENT.hex()
+    key_id = fx.key_id(signer)
+
+    def vector(id, description, seal, expected, content=content):
+        return {
+            "id": id,
+            "description": description,
+            "seal": seal,
+            "content": content,
+            "expected": expected,
+        }
+
+    def valid(version=SEAL_VERSION, key_id=None, issuer_did=None, **extra):
+        return {
+            "parse": True,
+            "version": version,
+            "key_id": key_id,
+            "issuer_did": issuer_did,
+            "signature": "signature_valid",
+            "content": "content_authentic",
+            **extra,
+        }
+
+    def failed(result, key_id=None):
+        return valid(key_id=key_id, signature=result, content=result)
+
+    return [
+        vector(
+            "registered-cbor",
+            "Current seal with a registry key ID, in CBOR",
+            "seals/registered.cbor",
+            valid(key_id=key_id),
+        ),
+        vector(
+            "registered-json",
+            "The same seal, in JSON",
+            "seals/registered.json",
+            valid(key_id=key_id),
+        ),
+        vector(
+            "content-modified",
+            "Valid seal, verified against other content",
+            "seals/registered.cbor",
+            valid(key_id=key_id, content="content_modified"),
+            content=(fx.CONTENT + b"!").hex(),
+        ),
+        vector(
+            "did-web-key-id",
+            "Key ID that is a did:web DID URL",
+            "seals/did_web_key_id.cbor",
+            valid(key_id=f"{ISSUER}#{key_id}", issuer_did=ISSUER),
+        ),
+        vector(
+            "oversized-key-id",
+            "Key ID longer than 256 bytes (a did:key DID URL): rejected when parsing",
+            "seals/oversized_key_id.cbor",
+            {"parse": False},
+        ),
+        vector(
+            "tampered-signature",
+            "One bit of the ML-DSA signature flipped",
+            "seals/tampered_signature.cbor",
+            failed("invalid_signature", key_id),
+        ),
+        vector(
+            "tampered-field",
+            "Signed field (capture location) changed after signing",
+            "seals/tampered_field.cbor",
+            failed("payload_mismatch", key_id),
+        ),
+        vector(
+            "wrong-public-key",
+            "Public key replaced by another valid key",
+            "seals/wrong_public_key.cbor",
+            failed("invalid_signature", key_id),
+        ),
+        vector(
+            "truncated-public-key",
+            "Public key one byte short: rejected when parsing",
+            "seals/truncated_public_key.cbor",
+            {"parse": False},
+        ),
+        vector(
+            "validity-window",
+            "Seal valid from capture until 2100-01-01, bounds included",
+            "seals/validity_window.cbor",
+            valid(
+                signature_at=[
+                    {"timestamp_ms": fx.CAPTURE_TS - 1, "signature": "expired"},
+                    {"timestamp_ms": fx.CAPTURE_TS, "signature": "signature_valid"},
+                    {"timestamp_ms": fx.NOT_AFTER, "signature": "signature_valid"},
+                    {"timestamp_ms": fx.NOT_AFTER + 1, "signature": "expired"},
+                ]
+            ),
+        ),
+        vector(
+            "segment-digests",
+            "Segmented seal (8-byte segments) with per-segment digests",
+            "seals/segment_digests.cbor",
+            valid(),
+        ),
+        vector(
+            "segment-modified",
+            "Segmented seal, one byte of segment 1 changed",
+            "seals/segment_digests.cbor",
+            valid(content="segments_modified", tampered_segments=[1]),
+            content=SEGMENT_EDITED.hex(),
+        ),
+        vector(
+            "threshold",
+            "2-of-3 threshold seal with two co-signatures",
+            "seals/threshold.cbor",
+            valid(),
+        ),
+        vector(
+            "threshold-not-met",
+            "2-of-3 threshold seal with a single co-signature",
+            "seals/threshold_not_met.cbor",
+            failed("threshold_not_met"),
+        ),
+        vector(
+            "legacy-v1",
+            "Seal written before the version field existed (read as version 1)",
+            "seals/legacy_v1.cbor",
+            valid(version=1),
+        ),
+    ]
+
+
+def main():
+    signer, other = test_key("signer"), test_key("other")
+    signer_did = did_key(signer)
+
+    signer_key_id = fx.key_id(signer)
+    registered = fx.seal_fields(SEAL_VERSION, registered=True, key=signer)
+    did_web = fx.seal_fields(
+        SEAL_VERSION, registered=True, key=signer, signing_key_id=f"{ISSUER}#{signer_key_id}"
+    )
+    # did:key DID URLs are far longer than seal key IDs may be
+    oversized_key_id = fx.seal_fields(
+        SEAL_VERSION,
+        registered=True,
+        key=signer,
+        signing_key_id=did_key_id(signer_did),
+    )
+    validity = fx.seal_fields(SEAL_VERSION, validity=(fx.CAPTURE_TS, fx.NOT_AFTER))
+    segmented = fx.seal_fields(SEAL_VERSION, segments=fx.segment_manifest(8, 250, digests=True))
+    threshold = fx.seal_fields(SEAL_VERSION, threshold=(2, 3))
+
+    groups = [
+        {
+            "seals/registered.cbor": fx.cbor(registered),
+            "seals/registered.json": json_seal(registered),
+            "seals/tampered_signature.cbor": tampered(
+                registered, signature=flip_byte(registered["signature"], 100)
+            ),
+            "seals/tampered_field.cbor": tampered(registered, capture_location="u09tvw1"),
+            "seals/wrong_public_key.cbor": tampered(
+                registered, public_key=fx.Array(public_bytes(other))
+            ),
+            "seals/truncated_public_key.cbor": tampered(
+                registered, public_key=fx.Array(registered["public_key"][:-1])
+            ),
+            "c2pa/quantum_seal.json": c2pa_manifest_store(registered),
+        },
+        {"seals/did_web_key_id.cbor": fx.cbor(did_web)},
+        {"seals/oversized_key_id.cbor": fx.cbor(oversized_key_id)},
+        {"seals/validity_window.cbor": fx.cbor(validity)},
+        {"seals/segment_digests.cbor": fx.cbor(segmented)},
+        {
+            "seals/threshold.cbor": fx.cbor(threshold),
+            "seals/threshold_not_met.cbor": tampered(
+                threshold, partial_signatures=threshold["partial_signatures"][:1]
+            ),
+        },
+        {"seals/legacy_v1.cbor": fx.seal(None)},
+        {"c2pa/no_quantum_seal.json": c2pa_manifest_store()},
+    ]
+
+    # Independent check of the segment the edit lands in
+    leaves = zip(segment_leaves(fx.CONTENT, 8), segment_leaves(SEGMENT_EDITED, 8))
+    assert [i for i, (a, b) in enumerate(leaves) if a != b] == [1]
+
+    vectors = {
+        "format_version": FORMAT_VERSION,
+        "description": "Veritas Q interoperability test vectors, see README.md",
+        "verified_at": VERIFIED_AT,
+        "keys": [
+            key_vector("signer", signer, signer_did),
+            key_vector("other", other, did_key(other)),
+        ],
+        "seals": seal_vectors(signer),
+        "c2pa": [
+            {
+                "id": "c2pa-quantum-seal",
+                "description": "Manifest store with the quantum seal assertion of a seal",
+                "manifest_store": "c2pa/quantum_seal.json",
+                "seal": "seals/registered.cbor",
+            },
+            {
+                "id": "c2pa-no-quantum-seal",
+                "description": "Manifest store without a Veritas assertion",
+                "manifest_store": "c2pa/no_quantum_seal.json",
+                "seal": None,
+            },
+        ],
+    }
+
+    for group in groups:
+        paths = {OUTPUT_DIR / name: data for name, data in group.items()}
+        existing = [path for path in paths if path.exists()]
+        if len(existing) == len(paths):
+            for path in paths:
+                print(f"kept {path}")
+            continue
+        if existing:
+            sys.exit(f"{existing[0]} exists but not the rest of its group, remove it first")
+        for path, data in paths.items():
+            path.parent.mkdir(parents=True, exist_ok=True)
+            path.write_bytes(data)
+            print(f"wrote {path} ({len(data)} bytes)")
+
+    manifest = OUTPUT_DIR / "vectors.json"
+    manifest.write_text(json.dumps(vectors, indent=2) + "\n")
+    print(f"wrote {manifest}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,149 @@
+#!/usr/bin/env python3
+"""Generate the seed corpus of the veritas-core fuzz targets.
+
+Seeds start the fuzzers from valid inputs of every seal format version, so
+they reach deep into parsing and verification instead of stopping at the
+first byte. They are derived from the seal fixtures in
+veritas-core/tests/fixtures and committed under veritas-core/fuzz/seeds/:
+
+- fuzz_from_cbor, fuzz_verify: the CBOR fixtures as-is
+- fuzz_from_json: the same seals as JSON (what the WASM verifier accepts)
+- fuzz_c2pa_assertion: C2PA manifest store JSON carrying each seal as a
+  `veritas.quantum_seal` assertion
+
+Seeds are rewritten on every run. Inputs found by the fuzzers stay in the
+ignored fuzz/corpus/ directories; add interesting ones here by hand.
+
+Usage: ./scripts/generate-fuzz-seeds.py [fixtures_dir] [seeds_dir]
+"""
+
+import base64
+import json
+import struct
+import sys
+from pathlib import Path
+
+FIXTURES_DIR = Path(sys.argv[1] if len(sys.argv) > 1 else "veritas-core/tests/fixtures")
+SEEDS_DIR = Path(sys.argv[2] if len(sys.argv) > 2 else "veritas-core/fuzz/seeds")
+
+
+def decode_cbor(data):
+    """Decode the subset of CBOR produced by ciborium for seals."""
+
+    def item(pos):
+        head = data[pos]
+        major, info = head >> 5, head & 0x1F
+        pos += 1
+        if major == 7:
+            if info == 20:
+                return False, pos
+            if info == 21:
+                return True, pos
+            if info == 22:
+                return None, pos
+            if info == 27:
+                return struct.unpack(">d", data[pos : pos + 8])[0], pos + 8
+            raise ValueError(f"unsupported simple value {info}")
+
+        if info < 24:
+            value = info
+        else:
+            size = {24: 1, 25: 2, 26: 4, 27: 8}[info]
+            value = int.from_bytes(data[pos : pos + size], "big")
+            pos += size
+
+        if major == 0:
+            return value, pos
+        if major == 1:
+            return -1 - value, pos
+        if major == 2:
+            return list(data[pos : pos + value]), pos + value
+        if major == 3:
+            return data[pos : pos + value].decode(), pos + value
+        if major == 4:
+            items = []
+            for _ in range(value):
+                element, pos = item(pos)
+                items.append(element)
+            return items, pos
+        if major == 5:
+            entries = {}
+            for _ in range(value):
+                key, pos = item(pos)
+                entries[key], pos = item(pos)
+            return entries, pos
+        raise ValueError(f"unsupported major type {major}")
+
+    value, end = item(0)
+    if end != len(data):
+        raise ValueError("trailing bytes")
+    return value
+
+
+def qrng_source(source):
+    """QrngSource as written in the C2PA assertion."""
+    if isinstance(source, dict):
+        return f"DEVICE_HARDWARE:{source['DeviceHardware']['device_id']}"
+    return {
+        "Mock": "MOCK",
+        "AnuCloud": "ANU_CLOUD",
+        "LfdCloud": "LFD_CLOUD",
+        "IdQuantiqueCloud": "ID_QUANTIQUE_CLOUD",
+    }[source]
+
+
+def manifest_store(seal):
+    """C2PA manifest store JSON with the seal as a Veritas assertion."""
+    assertion = {
+        "version": 1,
+        "qrng_entropy": bytes(seal["qrng_entropy"]).hex(),
+        "qrng_source": qrng_source(seal["qrng_source"]),
+        "entropy_timestamp": seal["entropy_timestamp"],
+        "capture_timestamp": seal["capture_timestamp_utc"],
+        "ml_dsa_signature": base64.b64encode(bytes(seal["signature"])).decode(),
+        "ml_dsa_public_key": base64.b64encode(bytes(seal["public_key"])).decode(),
+        "content_hash": bytes(seal["content_hash"]["crypto_hash"]).hex(),
+    }
+    return {
+        "active_manifest": "urn:uuid:00000000-0000-4000-8000-000000000000",
+        "manifests": {
+            "urn:uuid:00000000-0000-4000-8000-000000000000": {
+                "claim_generator": "Veritas Q",
+                "assertions": [
+                    {"label": "c2pa.actions", "data": {"actions": [{"action": "c2pa.created"}]}},
+                    {"label": "veritas.quantum_seal", "data": assertion},
+                ],
+            }
+        },
+    }
+
+
+def write(target, name, data):
+    directory = SEEDS_DIR / target
+    directory.mkdir(parents=True, exist_ok=True)
+    (directory / name).write_bytes(data)
+
+
+def main():
+    fixtures = sorted(FIXTURES_DIR.glob("*.cbor"))
+    if not fixtures:
+        sys.exit(f"no fixtures in {FIXTURES_DIR}")
+
+    for fixture in fixtures:
+        raw = fixture.read_bytes()
+        seal = decode_cbor(raw)
+        stem = fixture.stem
+
+        write("fuzz_from_cbor", f"{stem}.cbor", raw)
+        write("fuzz_verify", f"{stem}.cbor", raw)
+        write("fuzz_from_json", f"{stem}.json", json.dumps(seal, separators=(",", ":")).encode())
+        write(
+            "fuzz_c2pa_assertion",
+            f"{stem}.json",
+            json.dumps(manifest_store(seal), separators=(",", ":")).encode(),
+        )
+        print(f"seeded {stem}")
+
+
+if __name__ == "__main__":
+    main()
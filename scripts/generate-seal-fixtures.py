@@ -0,0 +1,468 @@
+#!/usr/bin/env python3
+"""Generate fixture seals for every historical seal format version.
+
+The fixtures are loaded by veritas-core/tests/seal_compat.rs to make sure new
+releases keep reading, verifying and migrating old evidence. They are
+committed to the repository. Existing files are never overwritten (keys are
+random, so regenerating would change them); the script only writes fixtures
+that are missing, e.g. after adding a new version below.
+
+The CBOR encoding mirrors what ciborium produces for the Rust structs:
+definite-length maps in field declaration order, byte arrays as arrays of
+integers, `None` as null and enums as externally tagged variants.
+
+Requires Python `cryptography` >= 48 built against OpenSSL 3.5 (ML-DSA).
+
+Usage: ./scripts/generate-seal-fixtures.py [output_dir]
+"""
+
+import hashlib
+import struct
+import sys
+from pathlib import Path
+
+from cryptography.hazmat.primitives.asymmetric import mldsa
+
+OUTPUT_DIR = Path(sys.argv[1] if len(sys.argv) > 1 else "veritas-core/tests/fixtures")
+
+# Shared by all fixtures so the compat tests can check content binding
+CONTENT = b"Veritas Q seal format fixture"
+CAPTURE_TS = 1_767_225_600_000  # 2026-01-01T00:00:00Z
+# Far enough out that the v11 fixture keeps verifying
+NOT_AFTER = 4_102_444_800_000  # 2100-01-01T00:00:00Z
+ENTROPY = bytes(range(1, 33))
+
+
+class Array(list):
+    """Byte string that serde serializes as a sequence of integers."""
+
+
+def _head(major, value):
+    if value < 24:
+        return bytes([major << 5 | value])
+    if value < 0x100:
+        return bytes([major << 5 | 24, value])
+    if value < 0x10000:
+        return bytes([major << 5 | 25]) + struct.pack(">H", value)
+    if value < 0x100000000:
+        return bytes([major << 5 | 26]) + struct.pack(">I", value)
+    return bytes([major << 5 | 27]) + struct.pack(">Q", value)
+
+
+def cbor(value):
+    if value is None:
+        return b"\xf6"
+    if isinstance(value, bool):
+        return b"\xf5" if value else b"\xf4"
+    if isinstance(value, int):
+        return _head(0, value)
+    if isinstance(value, str):
+        raw = value.encode()
+        return _head(3, len(raw)) + raw
+    if isinstance(value, (bytes, Array)):
+        return _head(4, len(value)) + b"".join(_head(0, b) for b in value)
+    if isinstance(value, list):
+        return _head(4, len(value)) + b"".join(cbor(v) for v in value)
+    if isinstance(value, dict):
+        out = _head(5, len(value))
+        for key, item in value.items():
+            out += cbor(key) + cbor(item)
+        return out
+    raise TypeError(f"unsupported type {type(value)}")
+
+
+def signable(seal):
+    """SignablePayload: the seal without version, signature, key and anchor."""
+    payload = {
+        "capture_timestamp_utc": seal["capture_timestamp_utc"],
+        "capture_location": seal["capture_location"],
+        "device_attestation": seal["device_attestation"],
+        "qrng_entropy": seal["qrng_entropy"],
+        "qrng_source": seal["qrng_source"],
+        "entropy_timestamp": seal["entropy_timestamp"],
+    }
+    for optional in (
+        "qrng_conditioning",
+        "raw_entropy_hash",
+        "entropy_certificate",
+        "qrng_policy",
+    ):
+        if optional in seal:
+            payload[optional] = seal[optional]
+    payload["content_hash"] = seal["content_hash"]
+    payload["media_type"] = seal["media_type"]
+    for optional in (
+        "threshold_policy",
+        "not_before",
+        "not_after",
+        "capture_nonce",
+        "liveness",
+        "key_id",
+    ):
+        if optional in seal:
+            payload[optional] = seal[optional]
+    return cbor(payload)
+
+
+def entropy_certificate(source, entropy, issued_at):
+    key = mldsa.MLDSA65PrivateKey.generate()
+    payload = cbor(
+        {
+            "domain": "veritas-entropy-certificate-v1",
+            "source": source,
+            "entropy": entropy,
+            "issued_at": issued_at,
+        }
+    )
+    return {
+        "source": source,
+        "entropy": entropy,
+        "issued_at": issued_at,
+        "algorithm": "MlDsa65",
+        "signer_public_key": Array(key.public_key().public_bytes_raw()),
+        "signature": Array(key.sign(payload)),
+    }
+
+
+def countersignature(fields, role, timestamp):
+    seal_hash = hashlib.sha3_256(
+        b"veritas-seal-hash-v1" + bytes(fields["public_key"]) + bytes(fields["signature"])
+    ).digest()
+    key = mldsa.MLDSA65PrivateKey.generate()
+    payload = cbor(
+        {
+            "domain": "veritas-countersignature-v1",
+            "seal_hash": seal_hash,
+            "role": role,
+            "timestamp": timestamp,
+        }
+    )
+    return {
+        "role": role,
+        "signer_public_key": Array(key.public_key().public_bytes_raw()),
+        "timestamp": timestamp,
+        "signature": Array(key.sign(payload)),
+    }
+
+
+def ai_detection(fields, verdict, timestamp):
+    seal_hash = hashlib.sha3_256(
+        b"veritas-seal-hash-v1" + bytes(fields["public_key"]) + bytes(fields["signature"])
+    ).digest()
+    key = mldsa.MLDSA65PrivateKey.generate()
+    payload = cbor(
+        {
+            "domain": "veritas-ai-detection-v1",
+            "seal_hash": seal_hash,
+            "verdict": verdict,
+            "timestamp": timestamp,
+        }
+    )
+    return {
+        "verdict": verdict,
+        "timestamp": timestamp,
+        "signer_public_key": Array(key.public_key().public_bytes_raw()),
+        "signature": Array(key.sign(payload)),
+    }
+
+
+DISCLOSURE_FIELDS = [
+    "capture_timestamp_utc",
+    "capture_location",
+    "device_attestation",
+    "qrng_entropy",
+    "qrng_source",
+    "entropy_timestamp",
+    "qrng_conditioning",
+    "entropy_certificate",
+    "content_hash",
+    "media_type",
+    "threshold_policy",
+]
+
+
+def merkle_root(level):
+    while len(level) > 1:
+        pairs = [level[i : i + 2] for i in range(0, len(level), 2)]
+        level = [
+            hashlib.sha3_256(b"\x01" + p[0] + p[1]).digest() if len(p) == 2 else p[0]
+            for p in pairs
+        ]
+    return level[0]
+
+
+def segment_manifest(size, duration_ms, digests=False):
+    """ContentHash.segments for CONTENT cut into `size`-byte segments."""
+    chunks = [CONTENT[i : i + size] for i in range(0, len(CONTENT), size)]
+    leaves = [
+        hashlib.sha3_256(
+            b"\x00veritas-segment-v1" + i.to_bytes(4, "big") + chunk
+        ).digest()
+        for i, chunk in enumerate(chunks)
+    ]
+    manifest = {
+        "segment_size": size,
+        "segment_duration_ms": duration_ms,
+        "segment_count": len(chunks),
+        "total_size": len(CONTENT),
+        "root": merkle_root(leaves),
+    }
+    if digests:
+        manifest["digests"] = [int.from_bytes(leaf[:8], "big") for leaf in leaves]
+    return manifest
+
+
+def disclosure_seal(version, redact):
+    """Selective-disclosure seal with the `redact` fields removed."""
+    values = {
+        "capture_timestamp_utc": CAPTURE_TS,
+        "capture_location": "u09tvw0",
+        "device_attestation": None,
+        "qrng_entropy": ENTROPY,
+        "qrng_source": "LfdCloud",
+        "entropy_timestamp": CAPTURE_TS + 120,
+        "qrng_conditioning": None,
+        "entropy_certificate": None,
+        "content_hash": {
+            "crypto_hash": hashlib.sha3_256(CONTENT).digest(),
+            "perceptual_hash": None,
+        },
+        "media_type": "Image",
+        "threshold_policy": None,
+    }
+
+    leaves, openings = [], []
+    for name in DISCLOSURE_FIELDS:
+        salt = hashlib.sha3_256(b"fixture salt " + name.encode()).digest()
+        leaf = hashlib.sha3_256(
+            b"\x00veritas-disclosure-v1"
+            + bytes([len(name)])
+            + name.encode()
+            + salt
+            + cbor(values[name])
+        ).digest()
+        leaves.append(leaf)
+        if name in redact:
+            openings.append({"Redacted": {"commitment": leaf}})
+            values[name] = None
+        else:
+            openings.append({"Disclosed": {"salt": salt}})
+
+    fields = {"version": version}
+    for name in DISCLOSURE_FIELDS[:6]:
+        fields[name] = values[name]
+    fields["content_hash"] = values["content_hash"]
+    fields["media_type"] = values["media_type"]
+    fields["disclosure"] = {"fields": openings}
+
+    key = mldsa.MLDSA65PrivateKey.generate()
+    message = cbor({"scheme": "veritas-disclosure-v1", "root": merkle_root(leaves)})
+    fields["signature"] = Array(key.sign(message) + message)
+    fields["public_key"] = Array(key.public_key().public_bytes_raw())
+    fields["blockchain_anchor"] = None
+    return cbor(fields)
+
+
+def liveness_code(nonce):
+    digest = hashlib.sha3_256(b"veritas-liveness-v1" + nonce).digest()
+    return f"{int.from_bytes(digest[:4], 'big') % 1_000_000:06}"
+
+
+def key_id(key):
+    """Registry ID of a signing key, as derived by veritas_core::key_id_for."""
+    return hashlib.sha3_256(key.public_key().public_bytes_raw()).digest()[:8].hex()
+
+
+def seal(*args, **options):
+    """CBOR of a signed seal, see `seal_fields`."""
+    return cbor(seal_fields(*args, **options))
+
+
+def seal_fields(
+    version,
+    extra=None,
+    countersigned_by=(),
+    threshold=None,
+    segments=None,
+    canonical_hash=None,
+    media_type="Image",
+    validity=None,
+    region_hashes=None,
+    ai_verdicts=(),
+    registered=False,
+    key=None,
+    signing_key_id=None,
+):
+    """Fields of a signed seal, in serialization order.
+
+    `key` signs the seal (a random key by default). Registered seals carry
+    `signing_key_id`, the registry ID of the key by default.
+    """
+    fields = {}
+    if version is not None:
+        fields["version"] = version
+    fields.update(
+        {
+            "capture_timestamp_utc": CAPTURE_TS,
+            "capture_location": "u09tvw0",
+            "device_attestation": None,
+            "qrng_entropy": ENTROPY,
+            "qrng_source": "LfdCloud",
+            "entropy_timestamp": CAPTURE_TS + 120,
+        }
+    )
+    fields.update(extra or {})
+    content_hash = {
+        "crypto_hash": hashlib.sha3_256(CONTENT).digest(),
+        "perceptual_hash": None,
+    }
+    if segments is not None:
+        content_hash["segments"] = segments
+    if canonical_hash is not None:
+        content_hash["canonical_hash"] = canonical_hash
+    if region_hashes is not None:
+        content_hash["region_hashes"] = region_hashes
+    fields.update({"content_hash": content_hash, "media_type": media_type})
+    if validity is not None:
+        fields["not_before"], fields["not_after"] = validity
+
+    cosigners = []
+    if threshold is not None:
+        k, n = threshold
+        cosigners = [mldsa.MLDSA65PrivateKey.generate() for _ in range(n)]
+        policy = {
+            "threshold": k,
+            "signers": [Array(c.public_key().public_bytes_raw()) for c in cosigners],
+        }
+        # Serialized after public_key, but signed after media_type
+        signed = dict(fields, threshold_policy=policy)
+    else:
+        signed = fields
+
+    key = key or mldsa.MLDSA65PrivateKey.generate()
+    if registered:
+        fields["key_id"] = signed["key_id"] = signing_key_id or key_id(key)
+    message = signable(signed)
+    # pqcrypto SignedMessage layout: signature || message
+    fields["signature"] = Array(key.sign(message) + message)
+    fields["public_key"] = Array(key.public_key().public_bytes_raw())
+    if threshold is not None:
+        fields["threshold_policy"] = policy
+        threshold_message = b"veritas-threshold-v1" + message
+        fields["partial_signatures"] = [
+            {"signer_index": i, "signature": Array(cosigners[i].sign(threshold_message))}
+            for i in range(k)
+        ]
+    fields["blockchain_anchor"] = None
+    if countersigned_by:
+        fields["countersignatures"] = [
+            countersignature(fields, role, CAPTURE_TS + 60_000 * (i + 1))
+            for i, role in enumerate(countersigned_by)
+        ]
+    if ai_verdicts:
+        fields["ai_detections"] = [
+            ai_detection(fields, verdict, CAPTURE_TS + 120_000 * (i + 1))
+            for i, verdict in enumerate(ai_verdicts)
+        ]
+    return fields
+
+
+def main():
+    OUTPUT_DIR.mkdir(parents=True, exist_ok=True)
+
+    certified_entropy = bytes(range(101, 133))
+    live_nonce = hashlib.sha3_256(b"fixture live capture").digest()
+    fixtures = {
+        # v1 seals written before the version field existed
+        "seal_v1_legacy.cbor": seal(None),
+        "seal_v1.cbor": seal(1),
+        "seal_v2.cbor": seal(
+            2,
+            {
+                "qrng_conditioning": "Sha3OsMix",
+                "entropy_certificate": entropy_certificate(
+                    "LfdCloud", certified_entropy, CAPTURE_TS + 100
+                ),
+            },
+        ),
+        "seal_v3.cbor": seal(3, countersigned_by=("Notary", {"Custom": "archivist"})),
+        "seal_v4.cbor": seal(4, threshold=(2, 3)),
+        "seal_v5_redacted.cbor": disclosure_seal(5, redact={"capture_location"}),
+        "seal_v6_segmented.cbor": seal(6, segments=segment_manifest(8, 250)),
+        "seal_v7_segment_digests.cbor": seal(
+            7, segments=segment_manifest(8, 250, digests=True)
+        ),
+        # CONTENT is not a PDF, so verification falls back to crypto_hash
+        "seal_v8_document.cbor": seal(
+            8,
+            canonical_hash=hashlib.sha3_256(b"fixture canonical form").digest(),
+            media_type="Document",
+        ),
+        "seal_v9_custom_media.cbor": seal(9, media_type={"Custom": "model/gltf-binary"}),
+        "seal_v10_pooled_entropy.cbor": seal(
+            10, {"qrng_policy": {"Pooled": {"max_age_secs": 300}}}
+        ),
+        "seal_v11_validity.cbor": seal(11, validity=(CAPTURE_TS, NOT_AFTER)),
+        # CONTENT is not an image, so the tiles are arbitrary
+        "seal_v12_regions.cbor": seal(
+            12,
+            region_hashes={
+                "grid": 4,
+                "tiles": [0x0123456789ABCDEF * (i + 1) % 2**64 for i in range(16)],
+            },
+        ),
+        "seal_v13_ai_detection.cbor": seal(
+            13,
+            ai_verdicts=(
+                {
+                    "detector": "synthid",
+                    "detector_version": "2.0",
+                    "score_bps": 9_400,
+                    "verdict": "AiGenerated",
+                },
+                {
+                    "detector": "in-house-classifier",
+                    "detector_version": "0.3.1",
+                    "score_bps": 5_100,
+                    "verdict": "Inconclusive",
+                },
+            ),
+        ),
+        "seal_v14_capture_nonce.cbor": seal(
+            14, {"capture_nonce": hashlib.sha3_256(b"fixture capture session").digest()}
+        ),
+        "seal_v15_liveness.cbor": seal(
+            15,
+            {
+                "capture_nonce": live_nonce,
+                "liveness": {
+                    "code": liveness_code(live_nonce),
+                    "issued_at": CAPTURE_TS - 9_000,
+                    "responded_at": CAPTURE_TS - 4_000,
+                },
+            },
+        ),
+        "seal_v16_key_id.cbor": seal(16, registered=True),
+        "seal_v17_raw_entropy_hash.cbor": seal(
+            17,
+            {
+                "qrng_conditioning": "Sha3OsMix",
+                "raw_entropy_hash": hashlib.sha3_256(certified_entropy).digest(),
+                "entropy_certificate": entropy_certificate(
+                    "LfdCloud", certified_entropy, CAPTURE_TS + 100
+                ),
+            },
+        ),
+    }
+
+    for name, data in fixtures.items():
+        path = OUTPUT_DIR / name
+        if path.exists():
+            print(f"kept {path}")
+            continue
+        path.write_bytes(data)
+        print(f"wrote {path} ({len(data)} bytes)")
+
+
+if __name__ == "__main__":
+    main()
@@ -60,9 +60,9 @@ subjectKeyIdentifier = hash
 authorityKeyIdentifier = keyid:always
 EOF
 
-# Generate ECDSA P-256 private key
+# Generate ECDSA P-256 private key (PKCS#8, as JWS seal exports require)
 echo "1. Generating ECDSA P-256 private key..."
-openssl ecparam -name prime256v1 -genkey -noout -out "$KEY_FILE"
+openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:P-256 -out "$KEY_FILE"
 
 # Generate CSR
 echo "2. Generating certificate signing request..."
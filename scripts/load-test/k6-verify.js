@@ -104,13 +104,13 @@ function randomChoice(arr) {
 // ============================================================================
 
 /**
- * Test POST /verify (vérification classique)
+ * Test POST /api/v1/verify (vérification classique)
  */
 function testVerify() {
   const fd = createImageFormData();
   fd.append('seal_data', TEST_SEAL_DATA);
 
-  const res = http.post(`${API_URL}/verify`, fd.body(), {
+  const res = http.post(`${API_URL}/api/v1/verify`, fd.body(), {
     headers: { 'Content-Type': fd.contentType },
     tags: { name: 'verify' },
   });
@@ -132,12 +132,12 @@ function testVerify() {
 }
 
 /**
- * Test POST /c2pa/verify (vérification C2PA)
+ * Test POST /api/v1/c2pa/verify (vérification C2PA)
  */
 function testC2paVerify() {
   const fd = createImageFormData();
 
-  const res = http.post(`${API_URL}/c2pa/verify`, fd.body(), {
+  const res = http.post(`${API_URL}/api/v1/c2pa/verify`, fd.body(), {
     headers: { 'Content-Type': fd.contentType },
     tags: { name: 'c2pa_verify' },
   });
@@ -159,7 +159,7 @@ function testC2paVerify() {
 }
 
 /**
- * Test POST /resolve (résolution soft binding)
+ * Test POST /api/v1/resolve (résolution soft binding)
  */
 function testResolve() {
   const payload = JSON.stringify({
@@ -169,7 +169,7 @@ function testResolve() {
     include_seal_data: false,
   });
 
-  const res = http.post(`${API_URL}/resolve`, payload, {
+  const res = http.post(`${API_URL}/api/v1/resolve`, payload, {
     headers: { 'Content-Type': 'application/json' },
     tags: { name: 'resolve' },
   });
@@ -191,7 +191,7 @@ function testResolve() {
 }
 
 /**
- * Test POST /resolve avec image_data
+ * Test POST /api/v1/resolve avec image_data
  */
 function testResolveWithImage() {
   const payload = JSON.stringify({
@@ -201,7 +201,7 @@ function testResolveWithImage() {
     include_seal_data: false,
   });
 
-  const res = http.post(`${API_URL}/resolve`, payload, {
+  const res = http.post(`${API_URL}/api/v1/resolve`, payload, {
     headers: { 'Content-Type': 'application/json' },
     tags: { name: 'resolve_image' },
   });
@@ -0,0 +1,31 @@
+#!/bin/bash
+# Performance check - starts a release server with mock entropy and no
+# database, then load tests it with veritas-loadtest.
+# Usage: ./scripts/loadtest.sh [veritas-loadtest options]
+#   e.g. ./scripts/loadtest.sh --requests 2000 --concurrency 32 --mix seal=1,verify=4
+
+set -e
+
+PORT="${LOADTEST_PORT:-3900}"
+
+echo "🔨 Building release binaries..."
+cargo build --release -p veritas-server -p veritas-loadtest
+
+echo "🚀 Starting server on port $PORT..."
+env -u DATABASE_URL \
+    PORT="$PORT" \
+    ALLOW_MOCK_QRNG=true \
+    STARTUP_QRNG_CHECK=false \
+    RATE_LIMIT_ENABLED=false \
+    RUST_LOG=warn \
+    ./target/release/veritas-server > /dev/null &
+SERVER_PID=$!
+trap 'kill $SERVER_PID 2>/dev/null' EXIT
+
+for _ in $(seq 1 50); do
+    curl -sf "http://127.0.0.1:$PORT/health" > /dev/null && break
+    sleep 0.2
+done
+
+echo "📈 Running load test..."
+./target/release/veritas-loadtest --server "http://127.0.0.1:$PORT" --mock "$@"
@@ -1,29 +1,34 @@
-//! Anchor command - publish seal hash to Solana blockchain.
+//! Anchor command - publish seal hash to Solana or Bitcoin (OpenTimestamps).
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
 use colored::Colorize;
 use solana_client::rpc_client::RpcClient;
 #[allow(deprecated)]
 use solana_sdk::system_instruction;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
     message::Message,
     native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
-    signature::{Keypair, Signer},
+    signature::{read_keypair_file, Keypair, Signer},
     transaction::Transaction,
 };
 use spl_memo::build_memo;
 use tracing::{debug, info, warn};
-use veritas_core::VeritasSeal;
+use veritas_core::anchor::{solana_memo, OpenTimestampsConfig};
+use veritas_core::{
+    verify_anchors, AnchorProvider, AnchorStatus, AnchorVerifier, BlockchainAnchor, OpenTimestamps,
+    SolanaVerifier, VeritasError, VeritasSeal,
+};
 
 use crate::utils::load_seal;
-
-/// Solana Devnet RPC endpoint.
-const DEVNET_RPC_URL: &str = "https://api.devnet.solana.com";
+use crate::{AnchorChain, SolanaNetwork};
 
 /// Airdrop amount in SOL.
 const AIRDROP_SOL: u64 = 1;
@@ -31,32 +36,295 @@ const AIRDROP_SOL: u64 = 1;
 /// Maximum retries for airdrop.
 const AIRDROP_RETRIES: u32 = 3;
 
+impl SolanaNetwork {
+    /// Public RPC endpoint of the cluster.
+    fn default_rpc_url(self) -> &'static str {
+        match self {
+            Self::Mainnet => "https://api.mainnet-beta.solana.com",
+            Self::Devnet => "https://api.devnet.solana.com",
+            Self::Testnet => "https://api.testnet.solana.com",
+        }
+    }
+
+    /// Chain identifier recorded in the seal's blockchain anchor.
+    fn chain(self) -> &'static str {
+        match self {
+            Self::Mainnet => "solana-mainnet",
+            Self::Devnet => "solana-devnet",
+            Self::Testnet => "solana-testnet",
+        }
+    }
+
+    fn display_name(self) -> &'static str {
+        match self {
+            Self::Mainnet => "Solana Mainnet",
+            Self::Devnet => "Solana Devnet",
+            Self::Testnet => "Solana Testnet",
+        }
+    }
+
+    /// Explorer link for a transaction on the cluster.
+    fn explorer_url(self, tx_id: &str) -> String {
+        match self {
+            Self::Mainnet => format!("https://explorer.solana.com/tx/{tx_id}"),
+            Self::Devnet => format!("https://explorer.solana.com/tx/{tx_id}?cluster=devnet"),
+            Self::Testnet => format!("https://explorer.solana.com/tx/{tx_id}?cluster=testnet"),
+        }
+    }
+}
+
+/// Account paying the transaction fee.
+pub enum FeePayer {
+    /// Throwaway keypair funded by an airdrop (devnet and testnet only)
+    Ephemeral,
+    /// Keypair file, as written by `solana-keygen`
+    Keystore(PathBuf),
+    /// Ledger hardware wallet key `m/44'/501'[/ACCOUNT'[/CHANGE']]`
+    Ledger { key: Option<String> },
+}
+
+impl FeePayer {
+    fn describe(&self) -> String {
+        match self {
+            Self::Ephemeral => "ephemeral (airdrop)".to_string(),
+            Self::Keystore(path) => format!("keypair file {}", path.display()),
+            Self::Ledger { key: Some(key) } => format!("Ledger (key {key})"),
+            Self::Ledger { key: None } => "Ledger".to_string(),
+        }
+    }
+}
+
+/// Options of the anchor command.
+pub struct AnchorOptions {
+    pub seal_path: PathBuf,
+    pub update_seal: bool,
+    pub chain: AnchorChain,
+    pub network: SolanaNetwork,
+    /// RPC endpoint replacing the network's public one
+    pub rpc_url: Option<String>,
+    pub payer: FeePayer,
+    /// Priority fee in micro-lamports per compute unit (0 = none)
+    pub priority_fee: u64,
+    /// Compute units requested for the transaction (None = runtime default)
+    pub compute_unit_limit: Option<u32>,
+    /// Refuse to send if the fee would exceed this many lamports
+    pub max_fee: Option<u64>,
+    /// OpenTimestamps calendars (empty = public calendars)
+    pub calendars: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// Anchors seals on Solana with a memo transaction.
+pub struct SolanaAnchor {
+    network: SolanaNetwork,
+    rpc_url: String,
+    payer: FeePayer,
+    priority_fee: u64,
+    compute_unit_limit: Option<u32>,
+    max_fee: Option<u64>,
+}
+
+impl SolanaAnchor {
+    fn client(&self) -> RpcClient {
+        info!(url = %self.rpc_url, network = self.network.display_name(), "Connecting to Solana");
+        RpcClient::new_with_timeout_and_commitment(
+            self.rpc_url.clone(),
+            Duration::from_secs(30),
+            CommitmentConfig::confirmed(),
+        )
+    }
+
+    /// Send the memo transaction, returning its signature.
+    async fn send_memo(&self, memo_text: &str) -> Result<String> {
+        let client = self.client();
+
+        // The burner keypair is funded before any other signer is loaded:
+        // a Ledger signer cannot be held across the airdrop wait.
+        let burner = matches!(self.payer, FeePayer::Ephemeral).then(Keypair::new);
+        if let Some(burner) = &burner {
+            let pubkey = burner.pubkey();
+            debug!(pubkey = %pubkey, "Generated burner keypair");
+
+            info!(amount = AIRDROP_SOL, "Requesting SOL airdrop");
+            request_airdrop_with_retry(&client, &pubkey, AIRDROP_SOL).await?;
+
+            debug!("Waiting for airdrop confirmation");
+            wait_for_balance(&client, &pubkey, AIRDROP_SOL * LAMPORTS_PER_SOL).await?;
+        }
+        let payer: Box<dyn Signer> = match burner {
+            Some(burner) => Box::new(burner),
+            None => load_fee_payer(&self.payer)?,
+        };
+        let payer_pubkey = payer.pubkey();
+        debug!(pubkey = %payer_pubkey, "Loaded fee payer");
+
+        // Memo, carried by a minimal transfer (0 SOL to self)
+        let mut instructions =
+            compute_budget_instructions(self.priority_fee, self.compute_unit_limit);
+        instructions.push(system_instruction::transfer(
+            &payer_pubkey,
+            &payer_pubkey,
+            0,
+        ));
+        instructions.push(build_memo(memo_text.as_bytes(), &[&payer_pubkey]));
+
+        let recent_blockhash = client
+            .get_latest_blockhash()
+            .context("Failed to get recent blockhash")?;
+        let message =
+            Message::new_with_blockhash(&instructions, Some(&payer_pubkey), &recent_blockhash);
+
+        let fee = client
+            .get_fee_for_message(&message)
+            .context("Failed to estimate transaction fee")?;
+        info!(fee_lamports = fee, "Estimated transaction fee");
+        if let Some(max_fee) = self.max_fee {
+            if fee > max_fee {
+                bail!("Transaction fee of {fee} lamports exceeds --max-fee {max_fee}");
+            }
+        }
+        if !matches!(self.payer, FeePayer::Ephemeral) {
+            let balance = client
+                .get_balance(&payer_pubkey)
+                .context("Failed to get fee payer balance")?;
+            if balance < fee {
+                bail!(
+                    "Fee payer {} has {} lamports, the transaction needs {}",
+                    payer_pubkey,
+                    balance,
+                    fee
+                );
+            }
+        }
+
+        // Build and send the transaction (a Ledger asks for confirmation here)
+        info!("Signing transaction");
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction
+            .try_sign(&[payer.as_ref()], recent_blockhash)
+            .context("Failed to sign transaction")?;
+
+        info!("Sending transaction");
+        let signature = client
+            .send_and_confirm_transaction(&transaction)
+            .context("Failed to send transaction")?;
+        Ok(signature.to_string())
+    }
+}
+
+#[async_trait]
+impl AnchorProvider for SolanaAnchor {
+    async fn anchor(&self, digest: &[u8; 32]) -> veritas_core::Result<BlockchainAnchor> {
+        let tx_id = self
+            .send_memo(&solana_memo(digest))
+            .await
+            .map_err(|e| VeritasError::AnchorError(format!("Solana: {e:#}")))?;
+        Ok(BlockchainAnchor {
+            chain: self.network.chain().to_string(),
+            tx_id,
+            block_height: 0, // We don't fetch this for simplicity
+            proof: None,
+            batch: None,
+        })
+    }
+}
+
+#[async_trait]
+impl AnchorVerifier for SolanaAnchor {
+    fn chain(&self) -> &str {
+        self.network.chain()
+    }
+
+    async fn verify(
+        &self,
+        seal: &VeritasSeal,
+        anchor: &BlockchainAnchor,
+    ) -> veritas_core::Result<AnchorStatus> {
+        SolanaVerifier::new(self.network.chain(), &self.rpc_url)?
+            .verify(seal, anchor)
+            .await
+    }
+}
+
+/// OpenTimestamps settings, with `calendars` replacing the public ones.
+fn open_timestamps_config(calendars: Vec<String>) -> OpenTimestampsConfig {
+    let mut config = OpenTimestampsConfig::default();
+    if !calendars.is_empty() {
+        config.calendars = calendars;
+    }
+    config
+}
+
+fn open_timestamps(config: OpenTimestampsConfig) -> Result<OpenTimestamps> {
+    OpenTimestamps::with_config(config).context("Failed to create OpenTimestamps client")
+}
+
 /// Execute the anchor command.
-pub async fn execute(
-    seal_path: PathBuf,
-    update_seal: bool,
-    dry_run: bool,
-    quiet: bool,
-) -> Result<()> {
+pub async fn execute(options: AnchorOptions, quiet: bool) -> Result<()> {
+    let AnchorOptions {
+        seal_path,
+        update_seal,
+        chain,
+        network,
+        rpc_url,
+        payer,
+        priority_fee,
+        compute_unit_limit,
+        max_fee,
+        calendars,
+        dry_run,
+    } = options;
+
+    let solana = chain == AnchorChain::Solana;
+    let ephemeral = matches!(payer, FeePayer::Ephemeral);
+    if solana && ephemeral && network == SolanaNetwork::Mainnet {
+        bail!("Mainnet has no airdrops: pay fees with --payer <KEYPAIR> or --ledger");
+    }
+    let rpc_url = rpc_url.unwrap_or_else(|| network.default_rpc_url().to_string());
+    let ots_config = open_timestamps_config(calendars);
+
     // Load and parse the seal
     info!(path = %seal_path.display(), "Loading seal");
-    let seal = load_seal(&seal_path)?;
+    let mut seal = load_seal(&seal_path)?;
 
-    // Compute the seal hash (hash of the content hash + signature prefix)
-    let seal_hash = compute_seal_hash(&seal);
-    info!(hash = %&seal_hash[..16], "Computed seal hash");
+    let digest = seal.anchor_digest();
+    info!(hash = %hex::encode(&digest[..8]), "Computed anchor digest");
+    let memo_text = solana_memo(&digest);
 
     // Dry run: show what would be done and exit
     if dry_run {
-        let memo_text = format!("VERITAS-Q:{}", seal_hash);
         println!("{}", "[DRY RUN] Would perform the following:".cyan().bold());
         println!();
         println!("   {} {}", "Seal file:".dimmed(), seal_path.display());
-        println!("   {} {}", "Seal hash:".dimmed(), &seal_hash[..16]);
-        println!("   {} Solana Devnet", "Network:".dimmed());
-        println!("   {} {}", "RPC URL:".dimmed(), DEVNET_RPC_URL);
-        println!("   {} {} SOL", "Airdrop:".dimmed(), AIRDROP_SOL);
-        println!("   {} {}", "Memo:".dimmed(), memo_text);
+        println!("   {} {}", "Seal hash:".dimmed(), hex::encode(&digest[..8]));
+        if solana {
+            println!("   {} {}", "Network:".dimmed(), network.display_name());
+            println!("   {} {}", "RPC URL:".dimmed(), rpc_url);
+            println!("   {} {}", "Fee payer:".dimmed(), payer.describe());
+            if ephemeral {
+                println!("   {} {} SOL", "Airdrop:".dimmed(), AIRDROP_SOL);
+            }
+            if priority_fee > 0 {
+                println!(
+                    "   {} {} micro-lamports/CU",
+                    "Priority fee:".dimmed(),
+                    priority_fee
+                );
+            }
+            if let Some(limit) = compute_unit_limit {
+                println!("   {} {}", "Compute units:".dimmed(), limit);
+            }
+            if let Some(max_fee) = max_fee {
+                println!("   {} {} lamports", "Max fee:".dimmed(), max_fee);
+            }
+            println!("   {} {}", "Memo:".dimmed(), memo_text);
+        } else {
+            println!("   {} Bitcoin (OpenTimestamps)", "Network:".dimmed());
+            println!("   {} {}", "Digest:".dimmed(), hex::encode(digest));
+            for calendar in &ots_config.calendars {
+                println!("   {} {}", "Calendar:".dimmed(), calendar);
+            }
+        }
         println!(
             "   {} {}",
             "Update seal:".dimmed(),
@@ -65,64 +333,59 @@ pub async fn execute(
         return Ok(());
     }
 
-    // Generate a burner keypair
-    let payer = Keypair::new();
-    debug!(pubkey = %payer.pubkey(), "Generated burner keypair");
-
-    // Connect to Devnet
-    info!(url = DEVNET_RPC_URL, "Connecting to Solana Devnet");
-    let client = RpcClient::new_with_timeout_and_commitment(
-        DEVNET_RPC_URL.to_string(),
-        Duration::from_secs(30),
-        CommitmentConfig::confirmed(),
-    );
-
-    // Request airdrop
-    info!(amount = AIRDROP_SOL, "Requesting SOL airdrop");
-    request_airdrop_with_retry(&client, &payer.pubkey(), AIRDROP_SOL).await?;
-
-    // Wait for airdrop to confirm
-    debug!("Waiting for airdrop confirmation");
-    wait_for_balance(&client, &payer.pubkey(), AIRDROP_SOL * LAMPORTS_PER_SOL).await?;
-
-    // Build the memo instruction
-    let memo_text = format!("VERITAS-Q:{}", seal_hash);
-    let memo_ix = build_memo(memo_text.as_bytes(), &[&payer.pubkey()]);
-
-    // Build a minimal transfer instruction (0 SOL to self, just to carry the memo)
-    let transfer_ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 0);
-
-    // Build and send the transaction
-    info!("Sending transaction");
-    let recent_blockhash = client
-        .get_latest_blockhash()
-        .context("Failed to get recent blockhash")?;
-
-    let message = Message::new(&[transfer_ix, memo_ix], Some(&payer.pubkey()));
-    let transaction = Transaction::new(&[&payer], message, recent_blockhash);
-
-    let signature = client
-        .send_and_confirm_transaction(&transaction)
-        .context("Failed to send transaction")?;
-
-    let tx_id = signature.to_string();
-    let explorer_url = format!("https://explorer.solana.com/tx/{}?cluster=devnet", tx_id);
-
-    info!(tx_id = %tx_id, "Transaction confirmed");
+    let provider: Box<dyn AnchorProvider> = match chain {
+        AnchorChain::Solana => Box::new(SolanaAnchor {
+            network,
+            rpc_url,
+            payer,
+            priority_fee,
+            compute_unit_limit,
+            max_fee,
+        }),
+        AnchorChain::Bitcoin => Box::new(open_timestamps(ots_config)?),
+    };
+    let anchor = provider.anchor(&digest).await?;
+    info!(chain = %anchor.chain, tx_id = %anchor.tx_id, "Seal anchored");
 
     // Success output
     if !quiet {
         println!();
-        println!("{}", "Anchored to Solana Devnet!".green().bold());
-        println!();
-        println!("   {} {}", "Transaction:".dimmed(), tx_id);
-        println!("   {} {}", "Explorer:".dimmed(), explorer_url.cyan());
-        println!("   {} {}", "Memo:".dimmed(), memo_text);
+        match chain {
+            AnchorChain::Solana => {
+                println!(
+                    "{}",
+                    format!("Anchored to {}!", network.display_name())
+                        .green()
+                        .bold()
+                );
+                println!();
+                println!("   {} {}", "Transaction:".dimmed(), anchor.tx_id);
+                println!(
+                    "   {} {}",
+                    "Explorer:".dimmed(),
+                    network.explorer_url(&anchor.tx_id).cyan()
+                );
+                println!("   {} {}", "Memo:".dimmed(), memo_text);
+            }
+            AnchorChain::Bitcoin => {
+                println!("{}", "Submitted to OpenTimestamps!".green().bold());
+                println!();
+                println!("   {} {}", "Calendar:".dimmed(), anchor.tx_id);
+                println!("   {} {}", "Digest:".dimmed(), hex::encode(digest));
+                println!(
+                    "   {} {}",
+                    "Status:".dimmed(),
+                    "Pending until the calendar's Bitcoin transaction confirms (a few hours)"
+                        .yellow()
+                );
+            }
+        }
     }
 
     // Optionally update the seal file
     if update_seal {
-        update_seal_with_anchor(&seal_path, &seal, &tx_id)?;
+        seal.add_anchor(anchor);
+        save_seal(&seal_path, &seal)?;
         info!(path = %seal_path.display(), "Updated seal with blockchain anchor");
         if !quiet {
             println!();
@@ -133,16 +396,129 @@ pub async fn execute(
     Ok(())
 }
 
-/// Compute a hash representing the seal (content hash + first 8 bytes of signature).
-fn compute_seal_hash(seal: &VeritasSeal) -> String {
-    use sha3::{Digest, Sha3_256};
+/// Check every anchor of a seal on its chain, printing one line per anchor.
+///
+/// Fails if an anchor does not commit to the seal. Pending and shallow
+/// anchors (fewer than `min_confirmations`), chains without a verifier and
+/// unreachable services are reported but do not fail.
+pub async fn check_anchors(seal: &VeritasSeal, min_confirmations: u64, quiet: bool) -> Result<()> {
+    let solana =
+        SolanaVerifier::public_clusters().context("Failed to create Solana RPC clients")?;
+    let bitcoin = open_timestamps(OpenTimestampsConfig::default())?;
+    let mut verifiers: Vec<&dyn AnchorVerifier> = solana
+        .iter()
+        .map(|verifier| verifier as &dyn AnchorVerifier)
+        .collect();
+    verifiers.push(&bitcoin);
+
+    let results = verify_anchors(seal, &verifiers, min_confirmations).await;
+    if results.is_empty() && !quiet {
+        println!("   {} {}", "Anchors:".dimmed(), "none".yellow());
+    }
+
+    let mut mismatched = Vec::new();
+    for (anchor, status) in results {
+        let status = match status {
+            Ok(AnchorStatus::Confirmed {
+                block_height,
+                confirmations,
+            }) => format!("confirmed (block {block_height}, {confirmations} confirmations)").green(),
+            Ok(AnchorStatus::Shallow {
+                block_height,
+                confirmations,
+            }) => format!(
+                "shallow (block {block_height}, {confirmations} of {min_confirmations} confirmations)"
+            )
+            .yellow(),
+            Ok(AnchorStatus::Pending) => "pending".yellow(),
+            Ok(AnchorStatus::Mismatch(reason)) => {
+                mismatched.push(anchor.chain.clone());
+                format!("MISMATCH ({reason})").red()
+            }
+            Ok(AnchorStatus::Unsupported) => "unsupported chain".yellow(),
+            Err(e) => {
+                warn!(chain = %anchor.chain, error = %e, "Anchor check failed");
+                format!("not checked ({e})").yellow()
+            }
+        };
+        if !quiet {
+            println!("   {} {} {}", "Anchor:".dimmed(), anchor.chain, status);
+        }
+    }
+
+    if !mismatched.is_empty() {
+        bail!(
+            "Anchor verification failed: {} anchor does not match the seal",
+            mismatched.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Compute budget instructions for the requested priority fee and limit.
+fn compute_budget_instructions(
+    priority_fee: u64,
+    compute_unit_limit: Option<u32>,
+) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    if let Some(limit) = compute_unit_limit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+    if priority_fee > 0 {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+            priority_fee,
+        ));
+    }
+    instructions
+}
 
-    let mut hasher = Sha3_256::new();
-    hasher.update(seal.content_hash.crypto_hash);
-    hasher.update(&seal.signature[..std::cmp::min(seal.signature.len(), 32)]);
-    let result = hasher.finalize();
+/// Signer for a funded fee payer.
+fn load_fee_payer(payer: &FeePayer) -> Result<Box<dyn Signer>> {
+    match payer {
+        FeePayer::Ephemeral => bail!("Ephemeral fee payers are funded by airdrop"),
+        FeePayer::Keystore(path) => {
+            let keypair = read_keypair_file(path).map_err(|e| {
+                anyhow::anyhow!("Failed to read Solana keypair {}: {}", path.display(), e)
+            })?;
+            Ok(Box::new(keypair))
+        }
+        FeePayer::Ledger { key } => ledger_signer(key.as_deref()),
+    }
+}
+
+/// Signer backed by the first Ledger found over USB (Solana app open).
+#[cfg(feature = "ledger")]
+fn ledger_signer(key: Option<&str>) -> Result<Box<dyn Signer>> {
+    use solana_remote_wallet::locator::Locator;
+    use solana_remote_wallet::remote_keypair::generate_remote_keypair;
+    use solana_remote_wallet::remote_wallet::maybe_wallet_manager;
+    use solana_sdk::derivation_path::DerivationPath;
+
+    let derivation_path = match key {
+        Some(key) => DerivationPath::from_key_str(key)
+            .map_err(|e| anyhow::anyhow!("Invalid Ledger key {key}: {e}"))?,
+        None => DerivationPath::default(),
+    };
+    let wallet_manager = maybe_wallet_manager()
+        .map_err(|e| anyhow::anyhow!("Failed to access hardware wallets: {e}"))?
+        .context("No Ledger found: connect and unlock it, then open the Solana app")?;
+    let locator = Locator::new_from_path("usb://ledger")
+        .map_err(|e| anyhow::anyhow!("Invalid Ledger locator: {e}"))?;
+
+    let keypair = generate_remote_keypair(
+        locator,
+        derivation_path,
+        &wallet_manager,
+        false,
+        "fee payer",
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to use Ledger: {e}"))?;
+    Ok(Box::new(keypair))
+}
 
-    hex::encode(&result[..16]) // 128-bit hash as hex
+#[cfg(not(feature = "ledger"))]
+fn ledger_signer(_key: Option<&str>) -> Result<Box<dyn Signer>> {
+    bail!("Ledger support is not built in: rebuild veritas-cli with --features ledger")
 }
 
 /// Request airdrop with retries.
@@ -192,28 +568,17 @@ async fn wait_for_balance(client: &RpcClient, pubkey: &Pubkey, min_lamports: u64
     bail!("Timeout waiting for airdrop to confirm")
 }
 
-/// Update the seal file with the blockchain anchor.
-fn update_seal_with_anchor(seal_path: &PathBuf, seal: &VeritasSeal, tx_id: &str) -> Result<()> {
-    use veritas_core::BlockchainAnchor;
-
-    // Create updated seal with anchor
-    let mut updated_seal = seal.clone();
-    updated_seal.blockchain_anchor = Some(BlockchainAnchor {
-        chain: "solana-devnet".to_string(),
-        tx_id: tx_id.to_string(),
-        block_height: 0, // We don't fetch this for simplicity
-    });
-
-    // Determine format and save
+/// Write the seal back in the format it was read in.
+fn save_seal(seal_path: &Path, seal: &VeritasSeal) -> Result<()> {
     let seal_bytes = std::fs::read(seal_path)?;
     if VeritasSeal::from_cbor(&seal_bytes).is_ok() {
         // CBOR format
-        let cbor = updated_seal.to_cbor()?;
+        let cbor = seal.to_cbor()?;
         std::fs::write(seal_path, cbor)?;
         debug!(format = "cbor", "Saved updated seal");
     } else {
         // JSON format
-        let json = serde_json::to_string_pretty(&updated_seal)?;
+        let json = seal.to_json_canonical()?;
         std::fs::write(seal_path, json)?;
         debug!(format = "json", "Saved updated seal");
     }
@@ -7,26 +7,41 @@ use std::path::PathBuf;
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use veritas_core::c2pa::{
     extract_quantum_seal, verify_c2pa_manifest, VeritasManifestBuilder, VeritasSigner,
+    TEST_CERTIFICATE_ORG,
 };
 use veritas_core::VeritasSeal;
 
 use crate::utils::build_seal_path;
 
+/// Options for the C2PA embed command.
+pub struct EmbedOptions {
+    pub input: PathBuf,
+    pub output: Option<PathBuf>,
+    pub seal_path: Option<PathBuf>,
+    pub key_path: Option<PathBuf>,
+    pub cert_path: Option<PathBuf>,
+    /// Sign with an ephemeral key and test certificate chain
+    pub self_signed: bool,
+    pub dry_run: bool,
+}
+
 /// Execute the C2PA embed command.
 ///
 /// Embeds a Veritas seal into a media file as a C2PA manifest.
-pub async fn execute_embed(
-    input: PathBuf,
-    output: Option<PathBuf>,
-    seal_path: Option<PathBuf>,
-    key_path: Option<PathBuf>,
-    cert_path: Option<PathBuf>,
-    dry_run: bool,
-    quiet: bool,
-) -> Result<()> {
+pub async fn execute_embed(options: EmbedOptions, quiet: bool) -> Result<()> {
+    let EmbedOptions {
+        input,
+        output,
+        seal_path,
+        key_path,
+        cert_path,
+        self_signed,
+        dry_run,
+    } = options;
+
     // Determine seal path
     let seal_file = seal_path.unwrap_or_else(|| build_seal_path(&input));
 
@@ -52,22 +67,29 @@ pub async fn execute_embed(
         println!("   {} {}", "Input file:".dimmed(), input.display());
         println!("   {} {}", "Seal file:".dimmed(), seal_file.display());
         println!("   {} {}", "Output file:".dimmed(), output_path.display());
-        println!(
-            "   {} {}",
-            "Signing key:".dimmed(),
-            key_path
-                .as_ref()
-                .map(|p| p.display().to_string())
-                .unwrap_or_else(|| "from C2PA_SIGNING_KEY env".to_string())
-        );
-        println!(
-            "   {} {}",
-            "Certificate:".dimmed(),
-            cert_path
-                .as_ref()
-                .map(|p| p.display().to_string())
-                .unwrap_or_else(|| "from C2PA_SIGNING_CERT env".to_string())
-        );
+        if self_signed {
+            println!(
+                "   {} ephemeral, self-signed test chain (TEST ONLY)",
+                "Signing key:".dimmed()
+            );
+        } else {
+            println!(
+                "   {} {}",
+                "Signing key:".dimmed(),
+                key_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "from C2PA_SIGNING_KEY env".to_string())
+            );
+            println!(
+                "   {} {}",
+                "Certificate:".dimmed(),
+                cert_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "from C2PA_SIGNING_CERT env".to_string())
+            );
+        }
         return Ok(());
     }
 
@@ -81,12 +103,10 @@ pub async fn execute_embed(
     info!(seal_path = %seal_file.display(), "Loaded seal");
 
     // Create signer
-    let signer = match (key_path, cert_path) {
-        (Some(key), Some(cert)) => VeritasSigner::from_files(&key, &cert)
-            .with_context(|| "Failed to load signing credentials from files")?,
-        (None, None) => VeritasSigner::from_env()
-            .with_context(|| "Failed to load signing credentials from environment. Set C2PA_SIGNING_KEY and C2PA_SIGNING_CERT")?,
-        _ => bail!("Both --key and --cert must be provided together, or neither (use env vars)"),
+    let signer = if self_signed {
+        VeritasSigner::ephemeral_test().context("Failed to generate test certificate")?
+    } else {
+        load_signer(key_path, cert_path)?
     };
 
     // Build and embed manifest
@@ -95,7 +115,21 @@ pub async fn execute_embed(
         .embed_in_file(&input, &output_path, signer)
         .with_context(|| "Failed to embed C2PA manifest")?;
 
-    info!(output = %output_path.display(), "C2PA manifest embedded");
+    info!(output = %output_path.display(), self_signed, "C2PA manifest embedded");
+
+    if self_signed {
+        warn!("C2PA manifest signed with a test certificate");
+        if !quiet {
+            eprintln!(
+                "{}",
+                format!(
+                    "TEST ONLY: signed by an ephemeral \"{TEST_CERTIFICATE_ORG}\" certificate, \
+                     C2PA validators will not trust it"
+                )
+                .yellow()
+            );
+        }
+    }
 
     if !quiet {
         println!();
@@ -111,6 +145,18 @@ pub async fn execute_embed(
     Ok(())
 }
 
+/// Load the C2PA signing key and certificate chain from files, or from the
+/// environment when no paths are given.
+fn load_signer(key_path: Option<PathBuf>, cert_path: Option<PathBuf>) -> Result<VeritasSigner> {
+    Ok(match (key_path, cert_path) {
+        (Some(key), Some(cert)) => VeritasSigner::from_files(&key, &cert)
+            .with_context(|| "Failed to load signing credentials from files")?,
+        (None, None) => VeritasSigner::from_env()
+            .with_context(|| "Failed to load signing credentials from environment. Set C2PA_SIGNING_KEY and C2PA_SIGNING_CERT")?,
+        _ => bail!("Both --key and --cert must be provided together, or neither (use env vars)"),
+    })
+}
+
 /// Execute the C2PA extract command.
 ///
 /// Extracts a Veritas seal from a C2PA manifest in a media file.
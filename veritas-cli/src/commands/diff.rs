@@ -0,0 +1,71 @@
+//! Diff command implementation.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use tracing::info;
+
+use crate::utils::{is_stdio, load_seal};
+
+/// Execute the diff command.
+pub async fn execute(left_path: PathBuf, right_path: PathBuf, quiet: bool) -> Result<()> {
+    if left_path == right_path && is_stdio(&left_path) {
+        bail!("Only one seal can be read from standard input");
+    }
+
+    info!(path = %left_path.display(), "Loading seal");
+    let left = load_seal(&left_path)?;
+    info!(path = %right_path.display(), "Loading seal");
+    let right = load_seal(&right_path)?;
+
+    let diff = left.diff(&right);
+
+    if quiet {
+        return Ok(());
+    }
+
+    println!();
+    if diff.is_empty() {
+        println!("{}", "Seals are identical".green().bold());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("{} field(s) differ", diff.differences.len())
+            .yellow()
+            .bold()
+    );
+    println!();
+    println!("   {} {}", "---".red(), left_path.display());
+    println!("   {} {}", "+++".green(), right_path.display());
+    for difference in &diff.differences {
+        println!();
+        println!("   {}", difference.field.bold());
+        println!(
+            "     {} {}",
+            "-".red(),
+            difference.left.as_deref().unwrap_or("(absent)")
+        );
+        println!(
+            "     {} {}",
+            "+".green(),
+            difference.right.as_deref().unwrap_or("(absent)")
+        );
+    }
+
+    println!();
+    println!(
+        "   {} {}",
+        "Same content:".dimmed(),
+        if diff.same_content() { "yes" } else { "no" }
+    );
+    println!(
+        "   {} {}",
+        "Same signer:".dimmed(),
+        if diff.same_signer() { "yes" } else { "no" }
+    );
+
+    Ok(())
+}
@@ -1,5 +1,10 @@
 pub mod anchor;
 #[cfg(feature = "c2pa")]
 pub mod c2pa;
+pub mod diff;
+pub mod redact;
+pub mod report;
 pub mod seal;
 pub mod verify;
+pub mod watch;
+pub mod watermark;
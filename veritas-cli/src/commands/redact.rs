@@ -0,0 +1,152 @@
+//! Redact command implementation.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use tracing::{debug, info};
+use veritas_core::SealField;
+
+use crate::utils::{build_redacted_path, load_seal};
+use crate::OutputFormat;
+
+/// Fields removed when none are given on the command line.
+const DEFAULT_REDACTED_FIELDS: [SealField; 2] =
+    [SealField::CaptureLocation, SealField::DeviceAttestation];
+
+/// Options for the redact command.
+pub struct RedactOptions {
+    pub seal: PathBuf,
+    pub fields: Vec<SealField>,
+    pub output: Option<PathBuf>,
+    pub format: OutputFormat,
+    pub dry_run: bool,
+}
+
+/// Execute the redact command.
+pub async fn execute(options: RedactOptions, quiet: bool) -> Result<()> {
+    let RedactOptions {
+        seal: seal_path,
+        mut fields,
+        output,
+        format,
+        dry_run,
+    } = options;
+
+    if fields.is_empty() {
+        fields = DEFAULT_REDACTED_FIELDS.to_vec();
+    }
+    fields.sort();
+    fields.dedup();
+
+    if let Some(field) = fields.iter().find(|field| !field.is_redactable()) {
+        bail!(
+            "Field '{}' cannot be redacted (redactable: {})",
+            field,
+            redactable_field_names()
+        );
+    }
+
+    let output_path = output.unwrap_or_else(|| build_redacted_path(&seal_path));
+    if output_path == seal_path {
+        bail!("Refusing to overwrite the original seal; choose a different --output");
+    }
+
+    info!(path = %seal_path.display(), "Loading seal");
+    let seal = load_seal(&seal_path)?;
+
+    if !seal.supports_selective_disclosure() {
+        bail!(
+            "Seal was not created with selective disclosure; re-seal with `veritas seal --disclosable`"
+        );
+    }
+
+    if dry_run {
+        println!("{}", "[DRY RUN] Would perform the following:".cyan().bold());
+        println!();
+        println!("   {} {}", "Seal:".dimmed(), seal_path.display());
+        println!("   {} {}", "Redact:".dimmed(), field_names(&fields));
+        println!("   {} {:?}", "Output format:".dimmed(), format);
+        println!("   {} {}", "Redacted seal:".dimmed(), output_path.display());
+        return Ok(());
+    }
+
+    let redacted = seal.redact(&fields).context("Failed to redact seal")?;
+
+    // The copy must still verify on its own before it is handed to anyone
+    let result = redacted
+        .verify_detailed()
+        .context("Failed to verify redacted seal")?;
+    if !result.is_valid() {
+        bail!(
+            "Redacted seal verification failed: {}",
+            result.description()
+        );
+    }
+
+    match format {
+        OutputFormat::Json => {
+            let json = redacted
+                .to_json_canonical()
+                .context("Failed to serialize seal to JSON")?;
+            std::fs::write(&output_path, json).context("Failed to write redacted seal file")?;
+        }
+        OutputFormat::Cbor => {
+            let cbor = redacted
+                .to_cbor()
+                .context("Failed to serialize seal to CBOR")?;
+            std::fs::write(&output_path, cbor).context("Failed to write redacted seal file")?;
+        }
+    }
+    debug!(format = ?format, "Serialized redacted seal");
+
+    info!(path = %output_path.display(), "Redacted seal saved");
+
+    if !quiet {
+        let redacted_fields = redacted
+            .disclosure
+            .as_ref()
+            .map(|disclosure| disclosure.redacted_fields())
+            .unwrap_or_default();
+
+        println!();
+        println!("{}", "Seal redacted, signature still valid!".green().bold());
+        println!();
+        println!("   {} {}", "Redacted seal:".dimmed(), output_path.display());
+        println!(
+            "   {} {}",
+            "Removed:".dimmed(),
+            field_names(&redacted_fields)
+        );
+        println!(
+            "   {} {}",
+            "Seal hash:".dimmed(),
+            &hex::encode(redacted.seal_hash())[..16]
+        );
+        if !redacted.countersignatures.is_empty() {
+            println!(
+                "   {} {} kept",
+                "Counter-signatures:".dimmed(),
+                redacted.countersignatures.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn field_names(fields: &[SealField]) -> String {
+    fields
+        .iter()
+        .map(|field| field.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn redactable_field_names() -> String {
+    let redactable: Vec<SealField> = SealField::ALL
+        .into_iter()
+        .filter(|field| field.is_redactable())
+        .collect();
+    field_names(&redactable)
+}
@@ -0,0 +1,88 @@
+//! Report command implementation.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use tracing::{debug, info};
+use veritas_core::report::VerificationReport;
+use veritas_core::ContentVerificationResult;
+
+use crate::utils::{build_report_path, load_seal_for_file};
+use crate::ReportFormat;
+
+/// Options for the report command.
+pub struct ReportOptions {
+    pub file: PathBuf,
+    pub seal: Option<PathBuf>,
+    pub format: ReportFormat,
+    pub output: Option<PathBuf>,
+    pub verify_url: String,
+}
+
+/// Execute the report command.
+///
+/// Verifies the file against its seal and writes a verification certificate.
+/// A failed verification is reported in the certificate, not as an error.
+pub async fn execute(options: ReportOptions, quiet: bool) -> Result<()> {
+    let ReportOptions {
+        file,
+        seal: seal_path,
+        format,
+        output,
+        verify_url,
+    } = options;
+
+    let content =
+        std::fs::read(&file).with_context(|| format!("Failed to read file: {}", file.display()))?;
+    info!(path = %file.display(), bytes = content.len(), "Read file");
+
+    let seal = load_seal_for_file(&file, &content, seal_path)?;
+
+    debug!("Verifying seal and content");
+    let result = seal
+        .verify_content(&content)
+        .context("Verification failed")?;
+
+    // The verify page looks seals up by content hash
+    let mut report = VerificationReport::from_seal(&seal)
+        .with_content_result(&result)
+        .with_verify_url(format!(
+            "{}/{}",
+            verify_url.trim_end_matches('/'),
+            seal.content_hash.crypto_hash_hex()
+        ));
+    if let Some(name) = file.file_name() {
+        report = report.with_file_name(name.to_string_lossy());
+    }
+    if matches!(result, ContentVerificationResult::ContentModified { .. }) {
+        if let Some(regions) = seal.region_comparison(&content) {
+            report = report.with_regions(regions);
+        }
+    }
+
+    let (bytes, extension) = match format {
+        ReportFormat::Pdf => (report.to_pdf(), "pdf"),
+        ReportFormat::Html => (report.to_html().into_bytes(), "html"),
+    };
+    let output_path = output.unwrap_or_else(|| build_report_path(&file, extension));
+    std::fs::write(&output_path, bytes)
+        .with_context(|| format!("Failed to write report: {}", output_path.display()))?;
+
+    info!(path = %output_path.display(), outcome = report.outcome.label(), "Report saved");
+
+    if !quiet {
+        let outcome = if report.outcome.is_success() {
+            report.outcome.label().green().bold()
+        } else {
+            report.outcome.label().red().bold()
+        };
+        println!();
+        println!("{}", "Verification report created".green().bold());
+        println!();
+        println!("   {} {}", "Outcome:".dimmed(), outcome);
+        println!("   {} {}", "Report:".dimmed(), output_path.display());
+    }
+
+    Ok(())
+}
@@ -5,112 +5,241 @@ use std::path::{Path, PathBuf};
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use pqcrypto_mldsa::mldsa65;
-use pqcrypto_traits::sign::{PublicKey, SecretKey};
+use pqcrypto_traits::sign::PublicKey;
 use tracing::{debug, info, warn};
+use veritas_core::keyfile::LEGACY_KEYPAIR_FILE_SIZE;
 use veritas_core::{
-    generate_keypair, LfdQrng, MediaType, MockQrng, QuantumEntropySource, SealBuilder, VeritasSeal,
-    ZeroizingSecretKey, MLDSA65_PUBLIC_KEY_BYTES, MLDSA65_SECRET_KEY_BYTES,
+    did_key, generate_keypair, KeypairFile, KeypairFileError, LfdQrng, MediaType, MockQrng,
+    QrngConditioning, QuantumEntropySource, SealBuilder, SegmentLayout, VerifiableCredential,
+    VeritasSeal, Zeroizing, ZeroizingSecretKey, MLDSA65_PUBLIC_KEY_BYTES,
 };
 
-use crate::utils::build_seal_path;
-use crate::OutputFormat;
+use crate::utils::{
+    build_embedded_path, build_qr_path, build_seal_path, build_vc_path, format_timestamp, is_stdio,
+    read_stdin, write_output,
+};
+use crate::{OutputFormat, QrFormat};
 
-/// Keypair file format: public key (1952 bytes) || secret key (4032 bytes)
-const KEYPAIR_FILE_SIZE: usize = MLDSA65_PUBLIC_KEY_BYTES + MLDSA65_SECRET_KEY_BYTES;
+/// Environment variable holding the passphrase of encrypted keypair files
+const KEYPAIR_PASSPHRASE_ENV: &str = "VERITAS_KEYPAIR_PASSPHRASE";
 
 /// Detect media type from file extension.
-fn detect_media_type(path: &Path) -> MediaType {
-    match path
-        .extension()
+pub(crate) fn detect_media_type(path: &Path) -> MediaType {
+    path.extension()
         .and_then(|ext| ext.to_str())
-        .map(|s| s.to_lowercase())
-        .as_deref()
-    {
-        Some("jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "tiff" | "svg") => MediaType::Image,
-        Some("mp4" | "mov" | "avi" | "mkv" | "webm" | "flv" | "wmv") => MediaType::Video,
-        Some("mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a") => MediaType::Audio,
-        _ => MediaType::Image, // Default to image
-    }
+        .map_or(MediaType::Generic, MediaType::from_extension)
 }
 
-/// Load an ML-DSA-65 keypair from a file.
-fn load_keypair(path: &Path) -> Result<(mldsa65::PublicKey, ZeroizingSecretKey)> {
-    let data = std::fs::read(path)
-        .with_context(|| format!("Failed to read keypair file: {}", path.display()))?;
+/// Load an ML-DSA-65 keypair file, accepting the headerless legacy format.
+pub(crate) fn load_keypair(path: &Path, quiet: bool) -> Result<KeypairFile> {
+    let data = Zeroizing::new(
+        std::fs::read(path)
+            .with_context(|| format!("Failed to read keypair file: {}", path.display()))?,
+    );
 
-    if data.len() != KEYPAIR_FILE_SIZE {
-        bail!(
-            "Invalid keypair file size: expected {} bytes, got {}",
-            KEYPAIR_FILE_SIZE,
-            data.len()
-        );
+    if !KeypairFile::is_keypair_file(&data) && data.len() == LEGACY_KEYPAIR_FILE_SIZE {
+        return load_legacy_keypair(path, &data, quiet);
+    }
+
+    let passphrase = keypair_passphrase();
+    match KeypairFile::decode(&data, passphrase.as_deref().map(String::as_str)) {
+        Ok(keypair) => Ok(keypair),
+        Err(KeypairFileError::PassphraseRequired) => bail!(
+            "Keypair file {} is encrypted: set {} to its passphrase",
+            path.display(),
+            KEYPAIR_PASSPHRASE_ENV
+        ),
+        Err(e) => Err(e).with_context(|| format!("Invalid keypair file: {}", path.display())),
     }
+}
 
+/// Load a keypair file written before the versioned format (public key
+/// followed by secret key, no checksum).
+fn load_legacy_keypair(path: &Path, data: &[u8], quiet: bool) -> Result<KeypairFile> {
     let public_key = mldsa65::PublicKey::from_bytes(&data[..MLDSA65_PUBLIC_KEY_BYTES])
         .map_err(|_| anyhow::anyhow!("Invalid public key in keypair file"))?;
-
-    let secret_key = mldsa65::SecretKey::from_bytes(&data[MLDSA65_PUBLIC_KEY_BYTES..])
+    let secret_key = ZeroizingSecretKey::from_bytes(&data[MLDSA65_PUBLIC_KEY_BYTES..])
         .map_err(|_| anyhow::anyhow!("Invalid secret key in keypair file"))?;
+    let keypair = KeypairFile::new(public_key, secret_key)
+        .with_context(|| format!("Invalid keypair file: {}", path.display()))?;
 
-    Ok((public_key, ZeroizingSecretKey::new(secret_key)))
+    warn!(path = %path.display(), "Legacy keypair file without checksum");
+    if !quiet {
+        eprintln!(
+            "{}",
+            format!(
+                "Keypair file {} uses the legacy format without checksum; \
+                 convert it with --keypair {} --save-keypair <NEW_PATH>",
+                path.display(),
+                path.display()
+            )
+            .yellow()
+        );
+    }
+    Ok(keypair)
 }
 
-/// Save an ML-DSA-65 keypair to a file.
-fn save_keypair(
-    path: &Path,
-    public_key: &mldsa65::PublicKey,
-    secret_key: &ZeroizingSecretKey,
-) -> Result<()> {
-    let mut data = Vec::with_capacity(KEYPAIR_FILE_SIZE);
-    data.extend_from_slice(public_key.as_bytes());
-    data.extend_from_slice(secret_key.as_inner().as_bytes());
+/// Save an ML-DSA-65 keypair file, encrypted if a passphrase is set.
+fn save_keypair(path: &Path, keypair: &KeypairFile) -> Result<()> {
+    let passphrase = keypair_passphrase();
+    keypair
+        .save(path, passphrase.as_deref().map(String::as_str))
+        .with_context(|| format!("Failed to write keypair file: {}", path.display()))
+}
 
-    std::fs::write(path, &data)
-        .with_context(|| format!("Failed to write keypair file: {}", path.display()))?;
+/// Passphrase for keypair files, from the environment (never the command
+/// line, where other users could read it).
+fn keypair_passphrase() -> Option<Zeroizing<String>> {
+    std::env::var(KEYPAIR_PASSPHRASE_ENV)
+        .ok()
+        .filter(|passphrase| !passphrase.is_empty())
+        .map(Zeroizing::new)
+}
 
-    // Set restrictive permissions on Unix
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let permissions = std::fs::Permissions::from_mode(0o600);
-        std::fs::set_permissions(path, permissions)?;
+/// QR code label requested with `--qr`.
+pub struct QrLabel {
+    pub format: QrFormat,
+    /// Encode the compact seal digest instead of the verify URL
+    pub digest: bool,
+    pub verify_url: String,
+}
+
+impl QrLabel {
+    /// Text encoded in the QR code for `seal`.
+    fn payload(&self, seal: &VeritasSeal) -> String {
+        if self.digest {
+            veritas_core::qr::seal_digest(seal)
+        } else {
+            // The verify page looks seals up by content hash
+            veritas_core::qr::verify_url(&self.verify_url, &seal.content_hash.crypto_hash_hex())
+        }
     }
 
-    Ok(())
+    fn extension(&self) -> &'static str {
+        match self.format {
+            QrFormat::Png => "png",
+            QrFormat::Svg => "svg",
+        }
+    }
+}
+
+/// `--vc` issuer standing for the did:key of the signing key.
+pub const SIGNING_KEY_DID: &str = "did:key";
+
+/// Options for the seal command.
+pub struct SealOptions {
+    /// File to seal; `None` reads the content from standard input
+    pub file: Option<PathBuf>,
+    /// Seal output path (`-` for standard output), `<FILE>.veritas` if unset
+    pub seal_out: Option<PathBuf>,
+    pub format: OutputFormat,
+    pub media_type: Option<MediaType>,
+    pub use_mock: bool,
+    pub mix_entropy: bool,
+    pub disclosable: bool,
+    pub segment_size: Option<u32>,
+    /// Validity window (Unix ms)
+    pub not_before: Option<u64>,
+    pub not_after: Option<u64>,
+    pub embed: bool,
+    pub qr: Option<QrLabel>,
+    /// DID issuing the Verifiable Credential written with `--vc`
+    /// ([`SIGNING_KEY_DID`] = the did:key of the signing key)
+    pub vc_issuer: Option<String>,
+    pub keypair_path: Option<PathBuf>,
+    pub save_keypair_path: Option<PathBuf>,
+    pub dry_run: bool,
 }
 
 /// Execute the seal command.
-pub async fn execute(
-    file: PathBuf,
-    format: OutputFormat,
-    use_mock: bool,
-    keypair_path: Option<PathBuf>,
-    save_keypair_path: Option<PathBuf>,
-    dry_run: bool,
-    quiet: bool,
-) -> Result<()> {
-    // Read the file content
-    let content =
-        std::fs::read(&file).with_context(|| format!("Failed to read file: {}", file.display()))?;
-
-    info!(path = %file.display(), bytes = content.len(), "Read file");
-
-    // Detect media type
-    let media_type = detect_media_type(&file);
-    debug!(media_type = ?media_type, "Detected media type");
-
-    // Determine output path
-    let seal_path = build_seal_path(&file);
+pub async fn execute(options: SealOptions, quiet: bool) -> Result<()> {
+    let SealOptions {
+        file,
+        seal_out,
+        format,
+        media_type,
+        use_mock,
+        mix_entropy,
+        disclosable,
+        segment_size,
+        not_before,
+        not_after,
+        embed,
+        qr,
+        vc_issuer,
+        keypair_path,
+        save_keypair_path,
+        dry_run,
+    } = options;
+
+    // Read the content, from the file or standard input
+    let content = match &file {
+        Some(file) => std::fs::read(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?,
+        None => read_stdin()?,
+    };
+    let input = file
+        .as_ref()
+        .map_or_else(|| "<stdin>".to_string(), |f| f.display().to_string());
+
+    info!(path = %input, bytes = content.len(), "Read file");
+
+    // Detect media type unless given explicitly (stdin has no extension)
+    let media_type = media_type
+        .or_else(|| file.as_deref().map(detect_media_type))
+        .unwrap_or(MediaType::Generic);
+    debug!(media_type = %media_type, "Detected media type");
+
+    if embed && !veritas_core::document::is_pdf(&content) {
+        bail!("--embed requires a PDF file: {input}");
+    }
+
+    // Determine output paths (--embed, --qr and --vc need a file next to
+    // which to write)
+    let seal_path = match (seal_out, &file) {
+        (Some(path), _) => path,
+        (None, Some(file)) => build_seal_path(file),
+        (None, None) => bail!("--stdin requires --seal-out <PATH> (- for standard output)"),
+    };
+    let embedded_path = file.as_deref().filter(|_| embed).map(build_embedded_path);
+    let qr_path = file
+        .as_deref()
+        .zip(qr.as_ref())
+        .map(|(file, label)| build_qr_path(file, label.extension()));
+    let vc_path = file
+        .as_deref()
+        .filter(|_| vc_issuer.is_some())
+        .map(build_vc_path);
+    if (embed && embedded_path.is_none())
+        || (qr.is_some() && qr_path.is_none())
+        || (vc_issuer.is_some() && vc_path.is_none())
+    {
+        bail!("--embed, --qr and --vc cannot be used with --stdin");
+    }
 
     // Dry run: show what would be done and exit
     if dry_run {
         println!("{}", "[DRY RUN] Would perform the following:".cyan().bold());
         println!();
-        println!("   {} {}", "Input file:".dimmed(), file.display());
+        println!("   {} {}", "Input file:".dimmed(), input);
         println!("   {} {} bytes", "File size:".dimmed(), content.len());
-        println!("   {} {:?}", "Media type:".dimmed(), media_type);
+        println!("   {} {}", "Media type:".dimmed(), media_type);
         println!("   {} {:?}", "Output format:".dimmed(), format);
         println!("   {} {}", "Seal output:".dimmed(), seal_path.display());
+        if let Some(path) = &embedded_path {
+            println!("   {} {}", "Sealed PDF:".dimmed(), path.display());
+        }
+        if let Some(path) = &qr_path {
+            println!("   {} {}", "QR code:".dimmed(), path.display());
+        }
+        if let (Some(path), Some(issuer)) = (&vc_path, &vc_issuer) {
+            println!(
+                "   {} {} (issuer {})",
+                "Credential:".dimmed(),
+                path.display(),
+                issuer
+            );
+        }
         println!(
             "   {} {}",
             "QRNG source:".dimmed(),
@@ -120,6 +249,22 @@ pub async fn execute(
                 "Auto (best available)"
             }
         );
+        if mix_entropy {
+            println!(
+                "   {} SHA3 mix with OS randomness",
+                "Conditioning:".dimmed()
+            );
+        }
+        if disclosable {
+            println!(
+                "   {} enabled (redactable)",
+                "Selective disclosure:".dimmed()
+            );
+        }
+        if let Some(size) = segment_size {
+            println!("   {} {} bytes per segment", "Segmented:".dimmed(), size);
+        }
+        print_validity(not_before, not_after);
         println!(
             "   {} {}",
             "Keypair:".dimmed(),
@@ -137,84 +282,99 @@ pub async fn execute(
         return Ok(());
     }
 
+    // Standard output carries the seal itself, keep it free of messages
+    let to_stdout = is_stdio(&seal_path);
+
     // Load or generate keypair
-    let (public_key, secret_key) = if let Some(kp_path) = &keypair_path {
+    let keypair = if let Some(kp_path) = &keypair_path {
         info!(path = %kp_path.display(), "Loading keypair from file");
-        load_keypair(kp_path)?
+        load_keypair(kp_path, quiet)?
     } else {
         let (pk, sk) = generate_keypair();
         debug!("Generated new ML-DSA-65 keypair");
+        KeypairFile::new(pk, sk)?
+    };
 
-        // Save keypair if requested (only when generating new)
-        if let Some(save_path) = &save_keypair_path {
-            save_keypair(save_path, &pk, &sk)?;
-            info!(path = %save_path.display(), "Saved keypair to file");
-            if !quiet {
-                println!(
-                    "{}",
-                    format!("Keypair saved to: {}", save_path.display()).dimmed()
-                );
-            }
+    // Save keypair if requested (a loaded one is re-saved, e.g. to convert
+    // a legacy file or change its passphrase)
+    if let Some(save_path) = &save_keypair_path {
+        save_keypair(save_path, &keypair)?;
+        info!(path = %save_path.display(), "Saved keypair to file");
+        if !quiet && !to_stdout {
+            println!(
+                "{}",
+                format!("Keypair saved to: {}", save_path.display()).dimmed()
+            );
         }
-
-        (pk, sk)
+    }
+    let KeypairFile {
+        public_key,
+        secret_key,
+        ..
+    } = keypair;
+
+    let settings = BuildSettings {
+        conditioning: mix_entropy.then_some(QrngConditioning::Sha3OsMix),
+        disclosable,
+        segments: segment_size.map(SegmentLayout::new),
+        not_before,
+        not_after,
     };
 
-    // Get quantum entropy and create seal
-    let seal = if use_mock {
-        warn!("Using MOCK entropy (not quantum-safe!)");
-        if !quiet {
-            eprintln!("{}", "Using MOCK entropy (not quantum-safe!)".yellow());
-        }
-        let qrng = MockQrng::default();
-        create_seal(content, media_type, &qrng, &public_key, &secret_key).await?
-    } else {
-        // Auto-select QRNG: try ID Quantique first (if API key set), fall back to LfD
-        use veritas_core::qrng::IdQuantiqueConfig;
+    // Kept for --embed, the builder takes ownership of the content
+    let original = embed.then(|| content.clone());
 
-        if let Ok(idq_config) = IdQuantiqueConfig::from_env() {
-            info!("Auto-selected ID Quantique QRNG provider");
-            let provider = veritas_core::qrng::IdQuantiqueQrng::new(idq_config)
-                .context("Failed to create ID Quantique QRNG")?;
-            if !quiet {
-                eprintln!(
-                    "{}",
-                    format!("Using QRNG: {}", provider.source_id()).dimmed()
-                );
-            }
-            create_seal(content, media_type, &provider, &public_key, &secret_key).await?
-        } else {
-            info!("Auto-selected LfD QRNG provider (Germany)");
-            let provider = LfdQrng::new().context("Failed to create LfD QRNG")?;
-            if !quiet {
-                eprintln!(
-                    "{}",
-                    format!("Using QRNG: {}", provider.source_id()).dimmed()
-                );
-            }
-            create_seal(content, media_type, &provider, &public_key, &secret_key).await?
-        }
-    };
+    // Get quantum entropy and create seal
+    let qrng = select_qrng(use_mock, quiet)?;
+    let seal = create_seal(
+        content,
+        media_type,
+        qrng.as_ref(),
+        &settings,
+        &public_key,
+        &secret_key,
+    )
+    .await?;
 
     // Serialize and save
-    match format {
-        OutputFormat::Json => {
-            let json =
-                serde_json::to_string_pretty(&seal).context("Failed to serialize seal to JSON")?;
-            std::fs::write(&seal_path, json).context("Failed to write seal file")?;
-            debug!(format = "json", "Serialized seal");
-        }
-        OutputFormat::Cbor => {
-            let cbor = seal.to_cbor().context("Failed to serialize seal to CBOR")?;
-            std::fs::write(&seal_path, cbor).context("Failed to write seal file")?;
-            debug!(format = "cbor", "Serialized seal");
-        }
+    write_seal(&seal_path, &seal, format)?;
+    info!(path = %seal_path.display(), "Seal saved");
+
+    if let (Some(path), Some(original)) = (&embedded_path, &original) {
+        let sealed_pdf = veritas_core::document::embed_seal_reference(original, &seal)
+            .context("Failed to embed seal into PDF")?;
+        std::fs::write(path, sealed_pdf)
+            .with_context(|| format!("Failed to write sealed PDF: {}", path.display()))?;
+        info!(path = %path.display(), "Sealed PDF saved");
     }
 
-    info!(path = %seal_path.display(), "Seal saved");
+    if let (Some(path), Some(label)) = (&qr_path, &qr) {
+        let code = veritas_core::qr::SealQrCode::encode(&label.payload(&seal))
+            .context("Failed to encode QR code")?;
+        let image = match label.format {
+            QrFormat::Png => code.to_png(veritas_core::qr::DEFAULT_PNG_SCALE),
+            QrFormat::Svg => code.to_svg().into_bytes(),
+        };
+        std::fs::write(path, image)
+            .with_context(|| format!("Failed to write QR code: {}", path.display()))?;
+        info!(path = %path.display(), "QR code saved");
+    }
+
+    let vc_issuer = vc_issuer.map(|issuer| match issuer.as_str() {
+        SIGNING_KEY_DID => did_key(&seal.public_key),
+        _ => issuer,
+    });
+    if let (Some(path), Some(issuer)) = (&vc_path, &vc_issuer) {
+        let credential = VerifiableCredential::from_seal(&seal, issuer)
+            .and_then(|credential| credential.to_json_pretty())
+            .context("Failed to export seal as a Verifiable Credential")?;
+        std::fs::write(path, credential)
+            .with_context(|| format!("Failed to write credential: {}", path.display()))?;
+        info!(path = %path.display(), "Verifiable Credential saved");
+    }
 
     // Print success message (user-facing output)
-    if !quiet {
+    if !quiet && !to_stdout {
         let content_hash = hex::encode(seal.content_hash.crypto_hash);
         let qrng_source = format!("{:?}", seal.qrng_source);
 
@@ -222,6 +382,15 @@ pub async fn execute(
         println!("{}", "File sealed with Quantum Entropy!".green().bold());
         println!();
         println!("   {} {}", "Seal saved:".dimmed(), seal_path.display());
+        if let Some(path) = &embedded_path {
+            println!("   {} {}", "Sealed PDF:".dimmed(), path.display());
+        }
+        if let Some(path) = &qr_path {
+            println!("   {} {}", "QR code:".dimmed(), path.display());
+        }
+        if let Some(path) = &vc_path {
+            println!("   {} {}", "Credential:".dimmed(), path.display());
+        }
         println!("   {} {}", "Content hash:".dimmed(), &content_hash[..16]);
         println!("   {} {}", "QRNG source:".dimmed(), qrng_source);
         println!(
@@ -229,6 +398,15 @@ pub async fn execute(
             "Signature size:".dimmed(),
             seal.signature.len()
         );
+        if let Some(segments) = &seal.content_hash.segments {
+            println!(
+                "   {} {} x {} bytes",
+                "Segments:".dimmed(),
+                segments.segment_count,
+                segments.segment_size
+            );
+        }
+        print_validity(seal.not_before, seal.not_after);
         if keypair_path.is_some() {
             println!("   {} {}", "Keypair:".dimmed(), "loaded from file".cyan());
         }
@@ -237,15 +415,112 @@ pub async fn execute(
     Ok(())
 }
 
-async fn create_seal<Q: QuantumEntropySource>(
+/// Print the validity window, if the seal has one.
+pub(crate) fn print_validity(not_before: Option<u64>, not_after: Option<u64>) {
+    if let Some(not_before) = not_before {
+        println!(
+            "   {} {}",
+            "Valid from:".dimmed(),
+            format_timestamp(not_before)
+        );
+    }
+    if let Some(not_after) = not_after {
+        println!(
+            "   {} {}",
+            "Valid until:".dimmed(),
+            format_timestamp(not_after)
+        );
+    }
+}
+
+/// Pick the QRNG: mock entropy when asked, else a local hardware device (if
+/// configured), then ID Quantique (if an API key is set), falling back to LfD.
+/// Real providers run behind the continuous health tests.
+pub(crate) fn select_qrng(use_mock: bool, quiet: bool) -> Result<Box<dyn QuantumEntropySource>> {
+    use veritas_core::qrng::{DeviceQrngConfig, HealthCheckedQrng, IdQuantiqueConfig};
+
+    if use_mock {
+        warn!("Using MOCK entropy (not quantum-safe!)");
+        if !quiet {
+            eprintln!("{}", "Using MOCK entropy (not quantum-safe!)".yellow());
+        }
+        return Ok(Box::new(MockQrng::default()));
+    }
+
+    let provider: Box<dyn QuantumEntropySource> =
+        if let Ok(device_config) = DeviceQrngConfig::from_env() {
+            info!("Auto-selected hardware QRNG device");
+            Box::new(HealthCheckedQrng::new(
+                veritas_core::qrng::DeviceQrng::with_config(device_config)
+                    .context("Failed to open hardware QRNG device")?,
+            ))
+        } else if let Ok(idq_config) = IdQuantiqueConfig::from_env() {
+            info!("Auto-selected ID Quantique QRNG provider");
+            Box::new(HealthCheckedQrng::new(
+                veritas_core::qrng::IdQuantiqueQrng::new(idq_config)
+                    .context("Failed to create ID Quantique QRNG")?,
+            ))
+        } else {
+            info!("Auto-selected LfD QRNG provider (Germany)");
+            Box::new(HealthCheckedQrng::new(
+                LfdQrng::new().context("Failed to create LfD QRNG")?,
+            ))
+        };
+    if !quiet {
+        eprintln!(
+            "{}",
+            format!("Using QRNG: {}", provider.source_id()).dimmed()
+        );
+    }
+    Ok(provider)
+}
+
+/// Write a seal file in the given format (`-` for standard output).
+pub(crate) fn write_seal(path: &Path, seal: &VeritasSeal, format: OutputFormat) -> Result<()> {
+    let bytes = match format {
+        OutputFormat::Json => seal
+            .to_json_canonical()
+            .context("Failed to serialize seal to JSON")?
+            .into_bytes(),
+        OutputFormat::Cbor => seal.to_cbor().context("Failed to serialize seal to CBOR")?,
+    };
+    debug!(format = ?format, "Serialized seal");
+    write_output(path, &bytes).context("Failed to write seal file")
+}
+
+/// Optional seal builder settings taken from the command line.
+#[derive(Default)]
+pub(crate) struct BuildSettings {
+    pub conditioning: Option<QrngConditioning>,
+    pub disclosable: bool,
+    pub segments: Option<SegmentLayout>,
+    pub not_before: Option<u64>,
+    pub not_after: Option<u64>,
+}
+
+pub(crate) async fn create_seal<Q: QuantumEntropySource + ?Sized>(
     content: Vec<u8>,
     media_type: MediaType,
     qrng: &Q,
+    settings: &BuildSettings,
     public_key: &mldsa65::PublicKey,
     secret_key: &ZeroizingSecretKey,
 ) -> Result<VeritasSeal> {
     // Create the seal using secure builder
-    let seal = SealBuilder::new(content, media_type)
+    let mut builder = SealBuilder::new(content, media_type);
+    if let Some(policy) = settings.conditioning {
+        builder = builder.with_conditioning(policy);
+    }
+    if settings.disclosable {
+        builder = builder.with_selective_disclosure();
+    }
+    if let Some(layout) = settings.segments {
+        builder = builder.with_segments(layout);
+    }
+    if settings.not_before.is_some() || settings.not_after.is_some() {
+        builder = builder.with_validity(settings.not_before, settings.not_after);
+    }
+    let seal = builder
         .build_secure(qrng, secret_key, public_key)
         .await
         .context("Failed to create seal")?;
@@ -4,25 +4,47 @@ use std::path::PathBuf;
 
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use tracing::{debug, error, info};
-use veritas_core::ContentVerificationResult;
+use tracing::{debug, error, info, warn};
+use veritas_core::{
+    ct_eq, ContentHash, ContentVerificationResult, TamperedSegment, VerificationResult,
+};
 
-use crate::utils::{build_seal_path, format_timestamp, load_seal};
+use crate::utils::{format_timestamp, is_stdio, load_seal, load_seal_for_file, read_stdin};
 
 /// Execute the verify command.
-pub async fn execute(file: PathBuf, seal_path: Option<PathBuf>, quiet: bool) -> Result<()> {
-    // Determine seal path
-    let seal_path = seal_path.unwrap_or_else(|| build_seal_path(&file));
-
-    // Read the original file
-    let content =
-        std::fs::read(&file).with_context(|| format!("Failed to read file: {}", file.display()))?;
-
-    info!(path = %file.display(), bytes = content.len(), "Read file");
-
-    // Load and parse the seal
-    info!(path = %seal_path.display(), "Loading seal");
-    let seal = load_seal(&seal_path)?;
+///
+/// Without `file`, the content is read from standard input and `seal_path`
+/// is required. With `check_anchors`, the seal's blockchain anchors are also
+/// checked, requiring that many confirmations. Seals made with mock entropy
+/// are flagged, or rejected with `reject_mock`.
+pub async fn execute(
+    file: Option<PathBuf>,
+    seal_path: Option<PathBuf>,
+    check_anchors: Option<u64>,
+    reject_mock: bool,
+    quiet: bool,
+) -> Result<()> {
+    // Load and parse the seal, falling back to one embedded in a PDF
+    let (content, seal) = match &file {
+        Some(file) => {
+            let content = std::fs::read(file)
+                .with_context(|| format!("Failed to read file: {}", file.display()))?;
+            info!(path = %file.display(), bytes = content.len(), "Read file");
+            let seal = load_seal_for_file(file, &content, seal_path)?;
+            (content, seal)
+        }
+        None => {
+            let Some(seal_path) = seal_path else {
+                bail!("--stdin requires --seal <PATH>");
+            };
+            if is_stdio(&seal_path) {
+                bail!("--stdin cannot be combined with a seal read from standard input");
+            }
+            let content = read_stdin()?;
+            info!(bytes = content.len(), "Read standard input");
+            (content, load_seal(&seal_path)?)
+        }
+    };
 
     // Verify signature and content in one call
     debug!("Verifying ML-DSA signature");
@@ -31,7 +53,36 @@ pub async fn execute(file: PathBuf, seal_path: Option<PathBuf>, quiet: bool) ->
         .context("Verification failed")?;
 
     match result {
+        ContentVerificationResult::Authentic if reject_mock && seal.uses_mock_entropy() => {
+            error!("Seal was made with mock entropy");
+
+            if !quiet {
+                println!();
+                println!("{}", "╔════════════════════════════════════════╗".red());
+                println!(
+                    "{}",
+                    "║              REJECTED                  ║".red().bold()
+                );
+                println!("{}", "╚════════════════════════════════════════╝".red());
+                println!();
+                println!(
+                    "   {} {}",
+                    "Signature:".dimmed(),
+                    "Valid (ML-DSA-65)".green()
+                );
+                println!("   {} {}", "Content:".dimmed(), "Matches original".green());
+                println!(
+                    "   {} {}",
+                    "QRNG source:".dimmed(),
+                    "Mock - not quantum-safe".red()
+                );
+            }
+            bail!("Verification failed: seal rejected, made with mock entropy (not quantum-safe)")
+        }
         ContentVerificationResult::Authentic => {
+            if seal.uses_mock_entropy() {
+                warn!("Seal was made with mock entropy - not quantum-safe");
+            }
             info!(
                 qrng_source = ?seal.qrng_source,
                 timestamp = seal.capture_timestamp_utc,
@@ -52,19 +103,50 @@ pub async fn execute(file: PathBuf, seal_path: Option<PathBuf>, quiet: bool) ->
                     "Signature:".dimmed(),
                     "Valid (ML-DSA-65)".green()
                 );
-                println!("   {} {}", "Content:".dimmed(), "Matches original".green());
-                println!("   {} {:?}", "QRNG source:".dimmed(), seal.qrng_source);
+                if ct_eq(
+                    &ContentHash::from_bytes(&content).crypto_hash,
+                    &seal.content_hash.crypto_hash,
+                ) {
+                    println!("   {} {}", "Content:".dimmed(), "Matches original".green());
+                } else {
+                    println!(
+                        "   {} {}",
+                        "Content:".dimmed(),
+                        "Matches original (document re-saved since sealing)".green()
+                    );
+                }
+                if seal.uses_mock_entropy() {
+                    println!(
+                        "   {} {}",
+                        "QRNG source:".dimmed(),
+                        "Mock - not quantum-safe, for testing only".yellow()
+                    );
+                } else {
+                    println!("   {} {:?}", "QRNG source:".dimmed(), seal.qrng_source);
+                }
                 println!(
                     "   {} {}",
                     "Sealed at:".dimmed(),
                     format_timestamp(seal.capture_timestamp_utc)
                 );
+                super::seal::print_validity(seal.not_before, seal.not_after);
+                if let Some(disclosure) = &seal.disclosure {
+                    let redacted = disclosure.redacted_fields();
+                    if !redacted.is_empty() {
+                        let names: Vec<_> = redacted.iter().map(|field| field.name()).collect();
+                        println!("   {} {}", "Redacted:".dimmed(), names.join(", "));
+                    }
+                }
+            }
+            if let Some(min_confirmations) = check_anchors {
+                super::anchor::check_anchors(&seal, min_confirmations, quiet).await?;
             }
             Ok(())
         }
         ContentVerificationResult::ContentModified {
             expected_hash,
             actual_hash,
+            tampered_segments,
         } => {
             error!(
                 expected = hex::encode(&expected_hash[..8]),
@@ -93,9 +175,38 @@ pub async fn execute(file: PathBuf, seal_path: Option<PathBuf>, quiet: bool) ->
                     hex::encode(&expected_hash[..8])
                 );
                 println!("   {} {}", "Got:".dimmed(), hex::encode(&actual_hash[..8]));
+                if let Some(segments) = &tampered_segments {
+                    print_tampered_segments(segments);
+                }
             }
             bail!("Verification failed: content has been modified")
         }
+        ContentVerificationResult::SignatureFailed(VerificationResult::Expired) => {
+            error!(
+                not_before = ?seal.not_before,
+                not_after = ?seal.not_after,
+                "Seal is outside its validity window"
+            );
+
+            if !quiet {
+                println!();
+                println!("{}", "╔════════════════════════════════════════╗".yellow());
+                println!(
+                    "{}",
+                    "║               EXPIRED                  ║".yellow().bold()
+                );
+                println!("{}", "╚════════════════════════════════════════╝".yellow());
+                println!();
+                println!("   {} {}", "Signature:".dimmed(), "Valid".green());
+                println!(
+                    "   {} {}",
+                    "Validity:".dimmed(),
+                    "Outside the seal's validity window".red()
+                );
+                super::seal::print_validity(seal.not_before, seal.not_after);
+            }
+            bail!("Verification failed: seal is outside its validity window")
+        }
         ContentVerificationResult::SignatureFailed(sig_result) => {
             error!(reason = %sig_result.description(), "Signature verification failed");
 
@@ -118,3 +229,39 @@ pub async fn execute(file: PathBuf, seal_path: Option<PathBuf>, quiet: bool) ->
         }
     }
 }
+
+/// Number of altered segments listed before the rest are summarized.
+const MAX_LISTED_SEGMENTS: usize = 10;
+
+fn print_tampered_segments(segments: &[TamperedSegment]) {
+    println!(
+        "   {} {}",
+        "Tampered segments:".dimmed(),
+        segments.len().to_string().red()
+    );
+    for segment in segments.iter().take(MAX_LISTED_SEGMENTS) {
+        let mut line = format!(
+            "#{} bytes {}..{}",
+            segment.index, segment.byte_range.start, segment.byte_range.end
+        );
+        if let Some(time) = &segment.time_range_ms {
+            line.push_str(&format!(
+                " ({} - {})",
+                format_offset(time.start),
+                format_offset(time.end)
+            ));
+        }
+        println!("     {}", line.red());
+    }
+    if segments.len() > MAX_LISTED_SEGMENTS {
+        println!(
+            "     {}",
+            format!("... and {} more", segments.len() - MAX_LISTED_SEGMENTS).dimmed()
+        );
+    }
+}
+
+/// Format a playback offset as `m:ss.mmm`.
+fn format_offset(ms: u64) -> String {
+    format!("{}:{:02}.{:03}", ms / 60_000, (ms / 1000) % 60, ms % 1000)
+}
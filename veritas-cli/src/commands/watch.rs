@@ -0,0 +1,460 @@
+//! Watch command - seal new files as they appear in a directory.
+//!
+//! Meant for tethered capture: point a camera's transfer target (e.g. its
+//! DCIM folder) at the watched directory and every new file is sealed next to
+//! it with one keypair, and optionally uploaded to a Veritas server. A ledger
+//! of sealed files (JSON lines) is kept in the directory so restarts skip
+//! files sealed before.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use notify::{EventKind, RecursiveMode, Watcher};
+use pqcrypto_mldsa::mldsa65;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use veritas_core::{
+    generate_keypair, KeypairFile, QrngConditioning, QuantumEntropySource, ZeroizingSecretKey,
+};
+
+use super::seal::{
+    create_seal, detect_media_type, load_keypair, select_qrng, write_seal, BuildSettings,
+};
+use crate::utils::build_seal_path;
+use crate::OutputFormat;
+
+/// Ledger file name, in the watched directory by default.
+pub const LEDGER_FILE_NAME: &str = ".veritas-ledger.jsonl";
+
+/// Default quiet period before a file counts as completely written.
+pub const DEFAULT_SETTLE_MS: u64 = 1000;
+
+/// Extensions of files never sealed: seals, and partial downloads or copies.
+const IGNORED_EXTENSIONS: [&str; 5] = ["veritas", "tmp", "part", "partial", "crdownload"];
+
+/// Options for the watch command.
+pub struct WatchOptions {
+    pub dir: PathBuf,
+    pub recursive: bool,
+    /// Also seal files already in the directory (and not in the ledger)
+    pub existing: bool,
+    /// Seal the files already in the directory, then exit
+    pub once: bool,
+    pub format: OutputFormat,
+    pub use_mock: bool,
+    pub mix_entropy: bool,
+    pub keypair_path: Option<PathBuf>,
+    /// Veritas server the files are also uploaded to (`POST /api/v1/seal`)
+    pub upload_url: Option<String>,
+    /// Bearer token for the uploads
+    pub token: Option<String>,
+    pub ledger_path: Option<PathBuf>,
+    pub settle: Duration,
+}
+
+/// One sealed file in the ledger.
+#[derive(Debug, Serialize, Deserialize)]
+struct LedgerEntry {
+    path: PathBuf,
+    /// SHA3-256 of the file when sealed (hex)
+    content_hash: String,
+    seal_path: PathBuf,
+    sealed_at: DateTime<Utc>,
+    /// Seal ID returned by the server, when uploaded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seal_id: Option<String>,
+}
+
+/// Append-only record of the files sealed in a directory.
+struct Ledger {
+    file: File,
+    /// Files sealed so far
+    sealed: HashSet<PathBuf>,
+}
+
+impl Ledger {
+    /// Open (or create) a ledger, loading the files it records.
+    fn open(path: &Path) -> Result<Self> {
+        let mut sealed = HashSet::new();
+        if path.exists() {
+            let reader = BufReader::new(
+                File::open(path)
+                    .with_context(|| format!("Failed to read ledger: {}", path.display()))?,
+            );
+            for (number, line) in reader.lines().enumerate() {
+                let line = line.context("Failed to read ledger")?;
+                match serde_json::from_str::<LedgerEntry>(&line) {
+                    Ok(entry) => {
+                        sealed.insert(entry.path);
+                    }
+                    Err(e) => warn!(line = number + 1, error = %e, "Skipping ledger entry"),
+                }
+            }
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open ledger: {}", path.display()))?;
+        Ok(Self { file, sealed })
+    }
+
+    fn record(&mut self, entry: LedgerEntry) -> Result<()> {
+        let mut line = serde_json::to_string(&entry).context("Failed to encode ledger entry")?;
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .context("Failed to write ledger")?;
+        self.sealed.insert(entry.path);
+        Ok(())
+    }
+}
+
+/// Everything needed to seal one file.
+struct Sealer {
+    qrng: Box<dyn QuantumEntropySource>,
+    public_key: mldsa65::PublicKey,
+    secret_key: ZeroizingSecretKey,
+    settings: BuildSettings,
+    format: OutputFormat,
+    upload: Option<Uploader>,
+    ledger: Ledger,
+    ledger_path: PathBuf,
+    quiet: bool,
+    sealed: usize,
+}
+
+impl Sealer {
+    /// Seal `path` unless it is not a candidate or already in the ledger.
+    async fn seal(&mut self, path: &Path) -> Result<()> {
+        if !is_candidate(path, &self.ledger_path) || self.ledger.sealed.contains(path) {
+            return Ok(());
+        }
+        let content = std::fs::read(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+        // Kept for the upload, the builder takes ownership of the content
+        let upload_content = self.upload.is_some().then(|| content.clone());
+        let media_type = detect_media_type(path);
+        let seal = create_seal(
+            content,
+            media_type,
+            self.qrng.as_ref(),
+            &self.settings,
+            &self.public_key,
+            &self.secret_key,
+        )
+        .await?;
+        let seal_path = build_seal_path(path);
+        write_seal(&seal_path, &seal, self.format)?;
+        info!(path = %seal_path.display(), "Seal saved");
+
+        let seal_id = match (&self.upload, upload_content) {
+            (Some(uploader), Some(content)) => match uploader.upload(path, content).await {
+                Ok(seal_id) => Some(seal_id),
+                Err(e) => {
+                    warn!(path = %path.display(), error = %format!("{e:#}"), "Upload failed");
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        self.ledger.record(LedgerEntry {
+            path: path.to_path_buf(),
+            content_hash: seal.content_hash.crypto_hash_hex(),
+            seal_path: seal_path.clone(),
+            sealed_at: Utc::now(),
+            seal_id: seal_id.clone(),
+        })?;
+        self.sealed += 1;
+
+        if !self.quiet {
+            match seal_id {
+                Some(seal_id) => println!(
+                    "{} {} {}",
+                    "Sealed".green(),
+                    path.display(),
+                    format!("(uploaded: {seal_id})").dimmed()
+                ),
+                None => println!("{} {}", "Sealed".green(), path.display()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Seal `path`, logging failures instead of stopping the watch.
+    async fn seal_or_warn(&mut self, path: &Path) {
+        if let Err(e) = self.seal(path).await {
+            warn!(path = %path.display(), error = %format!("{e:#}"), "Failed to seal file");
+            if !self.quiet {
+                eprintln!("{} {}: {:#}", "Failed".red(), path.display(), e);
+            }
+        }
+    }
+}
+
+/// Uploads sealed files to a Veritas server.
+struct Uploader {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+/// Fields of the server's seal response used here.
+#[derive(Deserialize)]
+struct UploadResponse {
+    seal_id: String,
+}
+
+impl Uploader {
+    fn new(base_url: &str, token: Option<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .context("Failed to create HTTP client")?;
+        Ok(Self {
+            client,
+            url: format!("{}/api/v1/seal", base_url.trim_end_matches('/')),
+            token,
+        })
+    }
+
+    /// Upload a file to `POST /api/v1/seal`, returning the server's seal ID.
+    async fn upload(&self, path: &Path, content: Vec<u8>) -> Result<String> {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let form = reqwest::multipart::Form::new().part(
+            "file",
+            reqwest::multipart::Part::bytes(content).file_name(file_name),
+        );
+        let mut request = self.client.post(&self.url).multipart(form);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response: UploadResponse = request
+            .send()
+            .await
+            .context("Failed to reach the server")?
+            .error_for_status()
+            .context("Server refused the upload")?
+            .json()
+            .await
+            .context("Invalid server response")?;
+        Ok(response.seal_id)
+    }
+}
+
+/// Returns true for regular files that should be sealed: not hidden (which
+/// also covers the ledger), not a seal, not a partial copy.
+fn is_candidate(path: &Path, ledger_path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    if name.starts_with('.') || path == ledger_path {
+        return false;
+    }
+    let ignored = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IGNORED_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+    !ignored && path.is_file()
+}
+
+/// Files in `dir` (and its subdirectories if `recursive`), sorted.
+fn list_files(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+        for entry in entries {
+            let path = entry.context("Failed to read directory")?.path();
+            if path.is_dir() {
+                if recursive {
+                    dirs.push(path);
+                }
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Execute the watch command.
+pub async fn execute(options: WatchOptions, quiet: bool) -> Result<()> {
+    let WatchOptions {
+        dir,
+        recursive,
+        existing,
+        once,
+        format,
+        use_mock,
+        mix_entropy,
+        keypair_path,
+        upload_url,
+        token,
+        ledger_path,
+        settle,
+    } = options;
+
+    let dir = dir
+        .canonicalize()
+        .with_context(|| format!("Directory not found: {}", dir.display()))?;
+    if !dir.is_dir() {
+        anyhow::bail!("Not a directory: {}", dir.display());
+    }
+    let ledger_path = ledger_path.unwrap_or_else(|| dir.join(LEDGER_FILE_NAME));
+    let ledger = Ledger::open(&ledger_path)?;
+    debug!(entries = ledger.sealed.len(), "Loaded ledger");
+
+    // One keypair for the whole session
+    let keypair = match &keypair_path {
+        Some(path) => {
+            info!(path = %path.display(), "Loading keypair from file");
+            load_keypair(path, quiet)?
+        }
+        None => {
+            let (pk, sk) = generate_keypair();
+            KeypairFile::new(pk, sk)?
+        }
+    };
+    let KeypairFile {
+        public_key,
+        secret_key,
+        ..
+    } = keypair;
+
+    let mut sealer = Sealer {
+        qrng: select_qrng(use_mock, quiet)?,
+        public_key,
+        secret_key,
+        settings: BuildSettings {
+            conditioning: mix_entropy.then_some(QrngConditioning::Sha3OsMix),
+            ..BuildSettings::default()
+        },
+        format,
+        upload: upload_url
+            .as_deref()
+            .map(|url| Uploader::new(url, token))
+            .transpose()?,
+        ledger,
+        ledger_path,
+        quiet,
+        sealed: 0,
+    };
+
+    if existing || once {
+        for path in list_files(&dir, recursive)? {
+            sealer.seal_or_warn(&path).await;
+        }
+    }
+    if once {
+        if !quiet {
+            println!("{} file(s) sealed", sealer.sealed);
+        }
+        return Ok(());
+    }
+
+    // Watch events are forwarded from notify's thread
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let _ = tx.send(event);
+    })
+    .context("Failed to start file watcher")?;
+    let mode = if recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher
+        .watch(&dir, mode)
+        .with_context(|| format!("Failed to watch directory: {}", dir.display()))?;
+
+    if !quiet {
+        println!(
+            "{} {} {}",
+            "Watching".cyan().bold(),
+            dir.display(),
+            "(Ctrl-C to stop)".dimmed()
+        );
+    }
+
+    // A file is sealed once no event touched it for `settle`, so files
+    // still being copied are not sealed half-written
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    let mut tick = tokio::time::interval((settle / 4).max(Duration::from_millis(50)));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some(event) = event else { break };
+                match event {
+                    Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                        for path in event.paths {
+                            pending.insert(path, Instant::now());
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!(error = %e, "File watcher error"),
+                }
+            }
+            _ = tick.tick() => {
+                let settled: Vec<PathBuf> = pending
+                    .iter()
+                    .filter(|(_, seen)| seen.elapsed() >= settle)
+                    .map(|(path, _)| path.clone())
+                    .collect();
+                for path in settled {
+                    pending.remove(&path);
+                    sealer.seal_or_warn(&path).await;
+                }
+            }
+            _ = &mut ctrl_c => break,
+        }
+    }
+
+    if !quiet {
+        println!();
+        println!("{} file(s) sealed", sealer.sealed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seals_and_partial_files_are_not_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = dir.path().join(LEDGER_FILE_NAME);
+        for name in [
+            "photo.jpg",
+            "photo.jpg.veritas",
+            "clip.MP4.part",
+            ".hidden.jpg",
+        ] {
+            std::fs::write(dir.path().join(name), b"x").unwrap();
+        }
+        std::fs::write(&ledger, b"").unwrap();
+
+        let candidates: Vec<_> = list_files(dir.path(), false)
+            .unwrap()
+            .into_iter()
+            .filter(|path| is_candidate(path, &ledger))
+            .collect();
+        assert_eq!(candidates, [dir.path().join("photo.jpg")]);
+    }
+}
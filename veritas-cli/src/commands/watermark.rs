@@ -0,0 +1,107 @@
+//! Watermark command implementation.
+//!
+//! Embeds a seal ID in an image as an invisible watermark, and reads it back.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use tracing::info;
+use uuid::Uuid;
+use veritas_core::watermark::{embed_watermark_bytes, extract_watermark_bytes};
+
+/// Execute the watermark embed command.
+///
+/// Writes a copy of `input` carrying `seal_id`. JPEG stays JPEG; other
+/// formats are written as PNG.
+pub async fn execute_embed(
+    input: PathBuf,
+    seal_id: Uuid,
+    output: Option<PathBuf>,
+    quiet: bool,
+) -> Result<()> {
+    let content = std::fs::read(&input)
+        .with_context(|| format!("Failed to read file: {}", input.display()))?;
+
+    let marked = embed_watermark_bytes(&content, seal_id.as_bytes())
+        .with_context(|| format!("Failed to watermark {}", input.display()))?;
+
+    let output_path =
+        output.unwrap_or_else(|| build_watermark_path(&input, marked.format.extension()));
+    std::fs::write(&output_path, &marked.data)
+        .with_context(|| format!("Failed to write image: {}", output_path.display()))?;
+
+    info!(output = %output_path.display(), seal_id = %seal_id, "Watermark embedded");
+
+    if !quiet {
+        println!();
+        println!("{}", "Watermark embedded!".green().bold());
+        println!();
+        println!("   {} {}", "Seal ID:".dimmed(), seal_id);
+        println!("   {} {}", "Output file:".dimmed(), output_path.display());
+        println!(
+            "   {}",
+            "Read it back with: veritas watermark extract <file>".dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Execute the watermark extract command.
+///
+/// Prints the seal ID carried by `file`; fails if it carries none. In quiet
+/// mode only the seal ID is printed.
+pub async fn execute_extract(file: PathBuf, quiet: bool) -> Result<()> {
+    let content =
+        std::fs::read(&file).with_context(|| format!("Failed to read file: {}", file.display()))?;
+
+    let Some(watermark) = extract_watermark_bytes(&content) else {
+        bail!("No watermark found in {}", file.display());
+    };
+    let seal_id = Uuid::from_bytes(watermark.payload);
+
+    info!(file = %file.display(), seal_id = %seal_id, "Watermark extracted");
+
+    if quiet {
+        println!("{}", seal_id);
+    } else {
+        println!();
+        println!("{}", "Watermark found".green().bold());
+        println!();
+        println!("   {} {}", "Seal ID:".dimmed(), seal_id);
+        println!(
+            "   {} {:.0}%",
+            "Confidence:".dimmed(),
+            watermark.confidence * 100.0
+        );
+    }
+
+    Ok(())
+}
+
+/// Default output path: `<stem>_watermarked.<extension>` next to `input`.
+fn build_watermark_path(input: &Path, extension: &str) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    input.with_file_name(format!("{}_watermarked.{}", stem, extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_watermark_path() {
+        assert_eq!(
+            build_watermark_path(Path::new("/tmp/photo.jpeg"), "jpg"),
+            PathBuf::from("/tmp/photo_watermarked.jpg")
+        );
+        assert_eq!(
+            build_watermark_path(Path::new("scan.gif"), "png"),
+            PathBuf::from("scan_watermarked.png")
+        );
+    }
+}
@@ -60,6 +60,7 @@ impl ExitCode {
             if message.contains("Failed to read file") || message.contains("Failed to read seal") {
                 INPUT_ERROR
             } else if message.contains("verification failed")
+                || message.starts_with("Verification failed")
                 || message.contains("has been modified")
                 || message.contains("TAMPERED")
             {
@@ -68,6 +69,7 @@ impl ExitCode {
                 || message.contains("network")
                 || message.contains("Solana")
                 || message.contains("airdrop")
+                || message.contains("Anchor error")
             {
                 NETWORK_ERROR
             } else if message.contains("Failed to write") || message.contains("serialize") {
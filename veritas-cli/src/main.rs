@@ -21,6 +21,48 @@ pub enum OutputFormat {
     Json,
 }
 
+/// Output format for verification reports.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ReportFormat {
+    /// Printable PDF certificate
+    #[default]
+    Pdf,
+    /// Standalone HTML page
+    Html,
+}
+
+/// Image format for QR code labels.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum QrFormat {
+    /// PNG image
+    #[default]
+    Png,
+    /// SVG vector image (scales for any label size)
+    Svg,
+}
+
+/// Solana cluster for anchoring.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum SolanaNetwork {
+    /// Mainnet-beta (fees paid in real SOL)
+    Mainnet,
+    /// Devnet (free airdrops, default)
+    #[default]
+    Devnet,
+    /// Testnet (free airdrops)
+    Testnet,
+}
+
+/// Chain a seal is anchored on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum AnchorChain {
+    /// Solana memo transaction (default)
+    #[default]
+    Solana,
+    /// Bitcoin, through OpenTimestamps calendars (free, confirms in hours)
+    Bitcoin,
+}
+
 /// Color output mode.
 #[derive(Clone, Copy, Default, ValueEnum)]
 pub enum ColorMode {
@@ -39,16 +81,36 @@ pub enum ColorMode {
 #[command(after_help = "Examples:
   veritas seal image.jpg              Seal a file with quantum entropy
   veritas seal --mock image.jpg       Seal with mock entropy (testing)
+  veritas seal --segmented video.mp4  Seal a long recording clip by clip
+  veritas seal --qr=svg image.jpg     Seal and write a QR code label
+  veritas seal --vc did:web:example.com image.jpg
+                                      Also export the seal as a W3C
+                                      Verifiable Credential
+  veritas seal --not-after 2026-12-31 badge.jpg
+                                      Seal that stops verifying after a date
   veritas verify image.jpg            Verify a sealed file
+  veritas seal --stdin --seal-out -   Seal piped content, seal to stdout
+  veritas verify --stdin --seal f.veritas
+                                      Verify piped content
+  veritas report image.jpg            Write a verification certificate (PDF)
+  veritas redact image.jpg.veritas    Strip location/device from a seal
+  veritas diff a.veritas b.veritas    Show which fields of two seals differ
   veritas anchor image.jpg.veritas    Anchor seal to Solana
+  veritas watch ~/DCIM                Seal new files as they appear
+  veritas watermark embed --seal-id <UUID> image.jpg
+                                      Hide a seal ID in the image pixels
+  veritas watermark extract image_watermarked.jpg
+                                      Read the seal ID back
   veritas c2pa embed -i image.jpg     Embed seal as C2PA manifest
+  veritas c2pa embed --self-signed -i image.jpg
+                                      Embed with a test certificate (no PKI)
   veritas c2pa verify image_c2pa.jpg  Verify C2PA manifest
 
 Exit codes:
   0   Success
   1   General error
   64  Usage error (invalid arguments)
-  65  Verification failed (tampered content)
+  65  Verification failed (tampered, expired or rejected seal)
   66  Input error (file not found)
   69  Network error (QRNG/blockchain unavailable)
   74  I/O error (cannot write output)")]
@@ -74,22 +136,105 @@ enum Commands {
     /// Seal a file with quantum entropy and post-quantum signature
     Seal {
         /// Path to the file to seal
-        #[arg(value_name = "FILE")]
-        file: PathBuf,
+        #[arg(value_name = "FILE", required_unless_present = "stdin")]
+        file: Option<PathBuf>,
+
+        /// Read the content to seal from standard input (needs --seal-out)
+        #[arg(long, conflicts_with_all = ["file", "embed", "qr", "vc"], requires = "seal_out")]
+        stdin: bool,
+
+        /// Write the seal to this path instead of <FILE>.veritas
+        /// (- for standard output)
+        #[arg(long, value_name = "PATH")]
+        seal_out: Option<PathBuf>,
 
         /// Output format for the seal file
         #[arg(short, long, default_value = "cbor", value_enum)]
         format: OutputFormat,
 
+        /// Media type (image, video, audio, document, generic or a MIME type);
+        /// detected from the file extension by default
+        #[arg(long, value_name = "TYPE", value_parser = parse_media_type)]
+        media_type: Option<veritas_core::MediaType>,
+
         /// Use mock QRNG instead of real quantum entropy (for testing)
         #[arg(long)]
         r#mock: bool,
 
+        /// Mix QRNG output with local OS randomness (SHA3 conditioning)
+        #[arg(long)]
+        mix_entropy: bool,
+
+        /// Sign salted field commitments so the seal can be redacted later
+        #[arg(long)]
+        disclosable: bool,
+
+        /// Hash the file as fixed-size segments so extracted clips can be
+        /// verified against the seal (for long recordings)
+        #[arg(long)]
+        segmented: bool,
+
+        /// Segment size in bytes for --segmented
+        #[arg(
+            long,
+            value_name = "BYTES",
+            default_value_t = veritas_core::segments::DEFAULT_SEGMENT_SIZE,
+            value_parser = clap::value_parser!(u32).range(1..),
+            requires = "segmented"
+        )]
+        segment_size: u32,
+
+        /// Seal does not verify before this time (RFC 3339 or YYYY-MM-DD, UTC)
+        #[arg(long, value_name = "TIME", value_parser = parse_timestamp)]
+        not_before: Option<u64>,
+
+        /// Seal does not verify after this time (RFC 3339 or YYYY-MM-DD, UTC),
+        /// e.g. for time-boxed credentials
+        #[arg(long, value_name = "TIME", value_parser = parse_timestamp)]
+        not_after: Option<u64>,
+
+        /// Also write <FILE>.sealed.pdf carrying the seal in its XMP metadata
+        /// (PDF only; verifies after viewer re-saves)
+        #[arg(long)]
+        embed: bool,
+
+        /// Also write a QR code label (<FILE>.qr.png or --qr=svg) for the
+        /// online verify page
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            value_enum,
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "png"
+        )]
+        qr: Option<QrFormat>,
+
+        /// Encode the compact seal digest instead of the verify URL in the QR code
+        #[arg(long, requires = "qr")]
+        qr_digest: bool,
+
+        /// Base URL of the online verify page encoded in the QR code
+        #[arg(
+            long,
+            value_name = "URL",
+            default_value = veritas_core::report::DEFAULT_VERIFY_BASE_URL
+        )]
+        verify_url: String,
+
+        /// Also write <FILE>.vc.json, the seal as a W3C Verifiable Credential
+        /// issued by this DID (e.g. did:web:example.com, or did:key for the
+        /// did:key of the signing key)
+        #[arg(long, value_name = "DID", value_parser = parse_did)]
+        vc: Option<String>,
+
         /// Path to existing ML-DSA-65 keypair file to use for signing
+        /// (encrypted files need VERITAS_KEYPAIR_PASSPHRASE)
         #[arg(long, value_name = "PATH")]
         keypair: Option<PathBuf>,
 
-        /// Save the generated keypair to this path (ignored if --keypair is set)
+        /// Save the keypair to this path, encrypted if VERITAS_KEYPAIR_PASSPHRASE
+        /// is set (with --keypair: re-save the loaded keypair)
         #[arg(long, value_name = "PATH")]
         save_keypair: Option<PathBuf>,
 
@@ -100,6 +245,42 @@ enum Commands {
 
     /// Verify a sealed file's authenticity
     Verify {
+        /// Path to the original file
+        #[arg(value_name = "FILE", required_unless_present = "stdin")]
+        file: Option<PathBuf>,
+
+        /// Path to the seal file (defaults to <FILE>.veritas, - for
+        /// standard input)
+        #[arg(value_name = "SEAL")]
+        seal: Option<PathBuf>,
+
+        /// Read the original content from standard input (needs --seal)
+        #[arg(long, conflicts_with = "file", requires = "seal_file")]
+        stdin: bool,
+
+        /// Path to the seal file, as an option (for use with --stdin)
+        #[arg(long = "seal", value_name = "PATH", conflicts_with = "seal")]
+        seal_file: Option<PathBuf>,
+
+        /// Also check each blockchain anchor of the seal on its chain
+        #[arg(long)]
+        anchors: bool,
+
+        /// Confirmations an anchor's block needs to count as confirmed
+        #[arg(long, value_name = "N", default_value_t = 1, requires = "anchors")]
+        min_confirmations: u64,
+
+        /// Fail on seals made with mock entropy (not quantum-safe) instead of
+        /// warning
+        #[arg(long)]
+        reject_mock: bool,
+    },
+
+    /// Write a human-readable verification certificate (PDF or HTML)
+    ///
+    /// Verifies the file against its seal and renders the outcome, seal
+    /// details, anchor link and a QR code to the online verify page.
+    Report {
         /// Path to the original file
         #[arg(value_name = "FILE")]
         file: PathBuf,
@@ -107,9 +288,68 @@ enum Commands {
         /// Path to the seal file (defaults to <FILE>.veritas)
         #[arg(value_name = "SEAL")]
         seal: Option<PathBuf>,
+
+        /// Report format
+        #[arg(short, long, default_value = "pdf", value_enum)]
+        format: ReportFormat,
+
+        /// Output file (default: <FILE>.report.pdf or <FILE>.report.html)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Base URL of the online verify page encoded in the QR code
+        #[arg(
+            long,
+            value_name = "URL",
+            default_value = veritas_core::report::DEFAULT_VERIFY_BASE_URL
+        )]
+        verify_url: String,
+    },
+
+    /// Produce a copy of a seal with private fields removed
+    ///
+    /// The copy stays verifiable: removed fields are replaced by their
+    /// commitments. Only seals created with `seal --disclosable` can be redacted.
+    Redact {
+        /// Path to the seal file (.veritas)
+        #[arg(value_name = "SEAL")]
+        seal: PathBuf,
+
+        /// Field to remove (repeatable; default: capture_location and device_attestation)
+        #[arg(long = "field", value_name = "FIELD")]
+        fields: Vec<veritas_core::SealField>,
+
+        /// Output file (default: <SEAL> with a .redacted suffix)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Output format for the redacted seal
+        #[arg(short, long, default_value = "cbor", value_enum)]
+        format: OutputFormat,
+
+        /// Show what would be removed without writing the redacted seal
+        #[arg(short = 'n', long)]
+        dry_run: bool,
     },
 
-    /// Anchor a seal's hash to the Solana blockchain (Devnet)
+    /// Show which fields of two seals differ
+    ///
+    /// Compares timestamps, hashes, entropy, keys, signatures and anchors.
+    /// Neither seal is verified.
+    Diff {
+        /// First seal file (- for standard input)
+        #[arg(value_name = "SEAL_A")]
+        left: PathBuf,
+
+        /// Second seal file (- for standard input)
+        #[arg(value_name = "SEAL_B")]
+        right: PathBuf,
+    },
+
+    /// Anchor a seal's hash to Solana (Devnet by default) or Bitcoin
+    ///
+    /// A seal keeps one anchor per chain: anchoring on a second chain adds
+    /// to the first instead of replacing it.
     Anchor {
         /// Path to the seal file (.veritas)
         #[arg(value_name = "SEAL")]
@@ -119,11 +359,127 @@ enum Commands {
         #[arg(long)]
         update_seal: bool,
 
+        /// Chain to anchor on
+        #[arg(long, value_enum, default_value_t = AnchorChain::Solana)]
+        chain: AnchorChain,
+
+        /// OpenTimestamps calendar URL for --chain bitcoin (repeatable;
+        /// default: the public calendars)
+        #[arg(long = "calendar", value_name = "URL")]
+        calendars: Vec<String>,
+
+        /// Solana cluster to anchor on
+        #[arg(long, value_enum, default_value_t = SolanaNetwork::Devnet)]
+        network: SolanaNetwork,
+
+        /// RPC endpoint (default: the network's public endpoint)
+        #[arg(long, value_name = "URL", env = "SOLANA_RPC_URL")]
+        rpc_url: Option<String>,
+
+        /// Pay fees from this Solana keypair file (solana-keygen JSON)
+        /// instead of an airdropped burner keypair
+        #[arg(long, value_name = "KEYPAIR", conflicts_with = "ledger")]
+        payer: Option<PathBuf>,
+
+        /// Pay fees from a Ledger hardware wallet (Solana app open)
+        #[arg(long)]
+        ledger: bool,
+
+        /// Ledger key as ACCOUNT[/CHANGE], i.e. m/44'/501'/ACCOUNT'/CHANGE'
+        #[arg(long, value_name = "KEY", requires = "ledger")]
+        ledger_key: Option<String>,
+
+        /// Priority fee in micro-lamports per compute unit
+        #[arg(long, value_name = "MICRO_LAMPORTS", default_value_t = 0)]
+        priority_fee: u64,
+
+        /// Compute unit limit of the transaction (lowers the priority fee paid)
+        #[arg(long, value_name = "UNITS")]
+        compute_unit_limit: Option<u32>,
+
+        /// Abort if the transaction fee exceeds this many lamports
+        #[arg(long, value_name = "LAMPORTS")]
+        max_fee: Option<u64>,
+
         /// Show what would be done without sending the transaction
         #[arg(short = 'n', long)]
         dry_run: bool,
     },
 
+    /// Seal new files as they appear in a directory
+    ///
+    /// Each new file is sealed next to it (<FILE>.veritas) once it has been
+    /// left untouched for --settle-ms. Sealed files are recorded in a ledger
+    /// (<DIR>/.veritas-ledger.jsonl), so restarts skip them.
+    Watch {
+        /// Directory to watch
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+
+        /// Also watch subdirectories
+        #[arg(short, long)]
+        recursive: bool,
+
+        /// Also seal files already in the directory and not in the ledger
+        #[arg(long)]
+        existing: bool,
+
+        /// Seal the files already in the directory, then exit
+        #[arg(long)]
+        once: bool,
+
+        /// Output format for the seal files
+        #[arg(short, long, default_value = "cbor", value_enum)]
+        format: OutputFormat,
+
+        /// Use mock QRNG instead of real quantum entropy (for testing)
+        #[arg(long)]
+        r#mock: bool,
+
+        /// Mix QRNG output with local OS randomness (SHA3 conditioning)
+        #[arg(long)]
+        mix_entropy: bool,
+
+        /// Path to the ML-DSA-65 keypair file signing every seal
+        /// (default: a keypair generated for the session)
+        #[arg(long, value_name = "PATH")]
+        keypair: Option<PathBuf>,
+
+        /// Also upload each file to this Veritas server (POST /api/v1/seal)
+        #[arg(long, value_name = "URL")]
+        upload: Option<String>,
+
+        /// Bearer token for --upload
+        #[arg(
+            long,
+            value_name = "TOKEN",
+            env = "VERITAS_API_TOKEN",
+            requires = "upload"
+        )]
+        token: Option<String>,
+
+        /// Ledger of sealed files (default: <DIR>/.veritas-ledger.jsonl)
+        #[arg(long, value_name = "FILE")]
+        ledger: Option<PathBuf>,
+
+        /// Time a file must be left untouched before it is sealed
+        #[arg(
+            long,
+            value_name = "MS",
+            default_value_t = commands::watch::DEFAULT_SETTLE_MS
+        )]
+        settle_ms: u64,
+    },
+
+    /// Invisible watermarks carrying a seal ID (embed, extract)
+    ///
+    /// The watermark survives moderate JPEG recompression, so a copy of the
+    /// image stripped of its seal and metadata still points back to the seal.
+    Watermark {
+        #[command(subcommand)]
+        command: WatermarkCommands,
+    },
+
     /// C2PA manifest operations (embed, extract, verify)
     #[cfg(feature = "c2pa")]
     C2pa {
@@ -132,6 +488,33 @@ enum Commands {
     },
 }
 
+/// Watermark subcommands
+#[derive(Subcommand)]
+enum WatermarkCommands {
+    /// Embed a seal ID in an image as an invisible watermark
+    Embed {
+        /// Image to watermark (JPEG, PNG, GIF or WebP)
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Seal ID to embed (the UUID returned by the server)
+        #[arg(long, value_name = "UUID")]
+        seal_id: uuid::Uuid,
+
+        /// Output file (default: <FILE stem>_watermarked.jpg, or .png for
+        /// non-JPEG input)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Read the seal ID watermarked in an image
+    Extract {
+        /// Image to read
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+}
+
 /// C2PA subcommands
 #[cfg(feature = "c2pa")]
 #[derive(Subcommand)]
@@ -158,6 +541,11 @@ enum C2paCommands {
         #[arg(long, value_name = "FILE")]
         cert: Option<PathBuf>,
 
+        /// Sign with an ephemeral key and self-signed test certificate chain
+        /// (TEST ONLY: not trusted by C2PA validators)
+        #[arg(long, conflicts_with_all = ["key", "cert"])]
+        self_signed: bool,
+
         /// Show what would be done without embedding
         #[arg(short = 'n', long)]
         dry_run: bool,
@@ -182,6 +570,38 @@ enum C2paCommands {
     },
 }
 
+/// Parse `--media-type`: a media type name or a MIME type.
+fn parse_media_type(value: &str) -> Result<veritas_core::MediaType, String> {
+    veritas_core::MediaType::parse(value)
+        .ok_or_else(|| format!("unknown media type '{value}' (expected a type name or MIME type)"))
+}
+
+/// Parse `--vc`: a DID such as `did:web:example.com`, or `did:key` alone.
+fn parse_did(value: &str) -> Result<String, String> {
+    if value == commands::seal::SIGNING_KEY_DID || veritas_core::is_valid_did(value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "invalid DID '{value}' (expected did:<method>:<id>, e.g. did:web:example.com)"
+        ))
+    }
+}
+
+/// Parse `--not-before`/`--not-after`: an RFC 3339 time or a date (midnight
+/// UTC), as Unix milliseconds.
+fn parse_timestamp(value: &str) -> Result<u64, String> {
+    let time = chrono::DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&chrono::Utc))
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        })
+        .map_err(|_| {
+            format!("invalid time '{value}' (expected e.g. 2026-12-31 or 2026-12-31T18:00:00Z)")
+        })?;
+    u64::try_from(time.timestamp_millis()).map_err(|_| format!("time '{value}' is before 1970"))
+}
+
 fn setup_logging(verbose: u8, quiet: bool, color: ColorMode) {
     let level = if quiet {
         Level::ERROR
@@ -230,29 +650,176 @@ async fn main() -> ExitCode {
     let result = match cli.command {
         Commands::Seal {
             file,
+            stdin: _,
+            seal_out,
             format,
+            media_type,
             r#mock,
+            mix_entropy,
+            disclosable,
+            segmented,
+            segment_size,
+            not_before,
+            not_after,
+            embed,
+            qr,
+            qr_digest,
+            verify_url,
+            vc,
             keypair,
             save_keypair,
             dry_run,
         } => {
-            commands::seal::execute(
+            let options = commands::seal::SealOptions {
                 file,
+                seal_out,
                 format,
-                r#mock,
-                keypair,
-                save_keypair,
+                media_type,
+                use_mock: r#mock,
+                mix_entropy,
+                disclosable,
+                segment_size: segmented.then_some(segment_size),
+                not_before,
+                not_after,
+                embed,
+                qr: qr.map(|format| commands::seal::QrLabel {
+                    format,
+                    digest: qr_digest,
+                    verify_url,
+                }),
+                vc_issuer: vc,
+                keypair_path: keypair,
+                save_keypair_path: save_keypair,
                 dry_run,
+            };
+            commands::seal::execute(options, cli.quiet).await
+        }
+        Commands::Verify {
+            file,
+            seal,
+            stdin: _,
+            seal_file,
+            anchors,
+            min_confirmations,
+            reject_mock,
+        } => {
+            commands::verify::execute(
+                file,
+                seal.or(seal_file),
+                anchors.then_some(min_confirmations),
+                reject_mock,
                 cli.quiet,
             )
             .await
         }
-        Commands::Verify { file, seal } => commands::verify::execute(file, seal, cli.quiet).await,
+        Commands::Report {
+            file,
+            seal,
+            format,
+            output,
+            verify_url,
+        } => {
+            let options = commands::report::ReportOptions {
+                file,
+                seal,
+                format,
+                output,
+                verify_url,
+            };
+            commands::report::execute(options, cli.quiet).await
+        }
+        Commands::Redact {
+            seal,
+            fields,
+            output,
+            format,
+            dry_run,
+        } => {
+            let options = commands::redact::RedactOptions {
+                seal,
+                fields,
+                output,
+                format,
+                dry_run,
+            };
+            commands::redact::execute(options, cli.quiet).await
+        }
+        Commands::Diff { left, right } => commands::diff::execute(left, right, cli.quiet).await,
         Commands::Anchor {
             seal,
             update_seal,
+            chain,
+            calendars,
+            network,
+            rpc_url,
+            payer,
+            ledger,
+            ledger_key,
+            priority_fee,
+            compute_unit_limit,
+            max_fee,
             dry_run,
-        } => commands::anchor::execute(seal, update_seal, dry_run, cli.quiet).await,
+        } => {
+            let payer = match (payer, ledger) {
+                (Some(path), _) => commands::anchor::FeePayer::Keystore(path),
+                (None, true) => commands::anchor::FeePayer::Ledger { key: ledger_key },
+                (None, false) => commands::anchor::FeePayer::Ephemeral,
+            };
+            let options = commands::anchor::AnchorOptions {
+                seal_path: seal,
+                update_seal,
+                chain,
+                network,
+                rpc_url,
+                payer,
+                priority_fee,
+                compute_unit_limit,
+                max_fee,
+                calendars,
+                dry_run,
+            };
+            commands::anchor::execute(options, cli.quiet).await
+        }
+        Commands::Watch {
+            dir,
+            recursive,
+            existing,
+            once,
+            format,
+            r#mock,
+            mix_entropy,
+            keypair,
+            upload,
+            token,
+            ledger,
+            settle_ms,
+        } => {
+            let options = commands::watch::WatchOptions {
+                dir,
+                recursive,
+                existing,
+                once,
+                format,
+                use_mock: r#mock,
+                mix_entropy,
+                keypair_path: keypair,
+                upload_url: upload,
+                token,
+                ledger_path: ledger,
+                settle: std::time::Duration::from_millis(settle_ms),
+            };
+            commands::watch::execute(options, cli.quiet).await
+        }
+        Commands::Watermark { command } => match command {
+            WatermarkCommands::Embed {
+                file,
+                seal_id,
+                output,
+            } => commands::watermark::execute_embed(file, seal_id, output, cli.quiet).await,
+            WatermarkCommands::Extract { file } => {
+                commands::watermark::execute_extract(file, cli.quiet).await
+            }
+        },
         #[cfg(feature = "c2pa")]
         Commands::C2pa { command } => match command {
             C2paCommands::Embed {
@@ -261,10 +828,19 @@ async fn main() -> ExitCode {
                 seal,
                 key,
                 cert,
+                self_signed,
                 dry_run,
             } => {
-                commands::c2pa::execute_embed(input, output, seal, key, cert, dry_run, cli.quiet)
-                    .await
+                let options = commands::c2pa::EmbedOptions {
+                    input,
+                    output,
+                    seal_path: seal,
+                    key_path: key,
+                    cert_path: cert,
+                    self_signed,
+                    dry_run,
+                };
+                commands::c2pa::execute_embed(options, cli.quiet).await
             }
             C2paCommands::Extract { file, output } => {
                 commands::c2pa::execute_extract(file, output, cli.quiet).await
@@ -1,11 +1,44 @@
 //! Common utility functions shared across CLI commands.
 
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
 use chrono::{TimeZone, Utc};
-use tracing::debug;
-use veritas_core::VeritasSeal;
+use tracing::{debug, info};
+use veritas_core::{document, VeritasSeal};
+
+/// Path argument standing for standard input or output.
+pub const STDIO_PATH: &str = "-";
+
+/// Returns true if `path` is `-` (standard input or output).
+pub fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == STDIO_PATH
+}
+
+/// Read all of standard input.
+pub fn read_stdin() -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    std::io::stdin()
+        .lock()
+        .read_to_end(&mut data)
+        .context("Failed to read standard input")?;
+    Ok(data)
+}
+
+/// Write `data` to `path`, or to standard output if `path` is `-`.
+pub fn write_output(path: &Path, data: &[u8]) -> Result<()> {
+    if is_stdio(path) {
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(data)
+            .and_then(|()| stdout.flush())
+            .context("Failed to write standard output")
+    } else {
+        std::fs::write(path, data)
+            .with_context(|| format!("Failed to write file: {}", path.display()))
+    }
+}
 
 /// Build the seal output path from the original file path.
 ///
@@ -17,10 +50,43 @@ pub fn build_seal_path(file: &Path) -> PathBuf {
     ))
 }
 
-/// Load and parse a seal file, trying CBOR first then JSON.
+/// Build the output path for a PDF carrying its own seal.
+///
+/// Transforms `file.pdf` into `file.sealed.pdf`.
+pub fn build_embedded_path(file: &Path) -> PathBuf {
+    file.with_extension(format!(
+        "sealed.{}",
+        file.extension().and_then(|e| e.to_str()).unwrap_or("pdf")
+    ))
+}
+
+/// Build the output path for a redacted copy of a seal.
+///
+/// Transforms `file.ext.veritas` into `file.ext.redacted.veritas`.
+pub fn build_redacted_path(seal: &Path) -> PathBuf {
+    match seal.file_stem() {
+        Some(stem) if seal.extension().is_some_and(|ext| ext == "veritas") => {
+            let mut name = stem.to_os_string();
+            name.push(".redacted.veritas");
+            seal.with_file_name(name)
+        }
+        _ => {
+            let mut name = seal.as_os_str().to_os_string();
+            name.push(".redacted");
+            PathBuf::from(name)
+        }
+    }
+}
+
+/// Load and parse a seal file (`-` for standard input), trying CBOR first
+/// then JSON.
 pub fn load_seal(path: &Path) -> Result<VeritasSeal> {
-    let seal_bytes = std::fs::read(path)
-        .with_context(|| format!("Failed to read seal file: {}", path.display()))?;
+    let seal_bytes = if is_stdio(path) {
+        read_stdin()?
+    } else {
+        std::fs::read(path)
+            .with_context(|| format!("Failed to read seal file: {}", path.display()))?
+    };
 
     let seal = if let Ok(seal) = VeritasSeal::from_cbor(&seal_bytes) {
         debug!(format = "cbor", "Parsed seal");
@@ -35,6 +101,62 @@ pub fn load_seal(path: &Path) -> Result<VeritasSeal> {
     Ok(seal)
 }
 
+/// Load the seal for a file: `seal_path` if given, else `<FILE>.veritas`,
+/// falling back to a seal embedded in the file when it is a PDF.
+pub fn load_seal_for_file(
+    file: &Path,
+    content: &[u8],
+    seal_path: Option<PathBuf>,
+) -> Result<VeritasSeal> {
+    if let Some(seal_path) = seal_path {
+        info!(path = %seal_path.display(), "Loading seal");
+        return load_seal(&seal_path);
+    }
+
+    let seal_path = build_seal_path(file);
+    if !seal_path.exists() && document::is_pdf(content) {
+        info!("Loading seal embedded in PDF");
+        document::extract_seal_reference(content)
+            .context("Failed to read seal embedded in PDF")?
+            .with_context(|| {
+                format!(
+                    "No seal file at {} and no seal embedded in the PDF",
+                    seal_path.display()
+                )
+            })
+    } else {
+        info!(path = %seal_path.display(), "Loading seal");
+        load_seal(&seal_path)
+    }
+}
+
+/// Build the output path for a verification report.
+///
+/// Transforms `file.ext` into `file.ext.report.<extension>`.
+pub fn build_report_path(file: &Path, extension: &str) -> PathBuf {
+    let mut name = file.as_os_str().to_os_string();
+    name.push(format!(".report.{}", extension));
+    PathBuf::from(name)
+}
+
+/// Build the output path for a QR code label.
+///
+/// Transforms `file.ext` into `file.ext.qr.<extension>`.
+pub fn build_qr_path(file: &Path, extension: &str) -> PathBuf {
+    let mut name = file.as_os_str().to_os_string();
+    name.push(format!(".qr.{}", extension));
+    PathBuf::from(name)
+}
+
+/// Build the output path for a Verifiable Credential export.
+///
+/// Transforms `file.ext` into `file.ext.vc.json`.
+pub fn build_vc_path(file: &Path) -> PathBuf {
+    let mut name = file.as_os_str().to_os_string();
+    name.push(".vc.json");
+    PathBuf::from(name)
+}
+
 /// Format a Unix timestamp (milliseconds) as a human-readable UTC string.
 pub fn format_timestamp(timestamp_ms: u64) -> String {
     let secs = (timestamp_ms / 1000) as i64;
@@ -65,6 +187,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_embedded_path() {
+        assert_eq!(
+            build_embedded_path(Path::new("docs/report.pdf")),
+            PathBuf::from("docs/report.sealed.pdf")
+        );
+        assert_eq!(
+            build_embedded_path(Path::new("report")),
+            PathBuf::from("report.sealed.pdf")
+        );
+    }
+
+    #[test]
+    fn test_build_redacted_path() {
+        assert_eq!(
+            build_redacted_path(Path::new("image.jpg.veritas")),
+            PathBuf::from("image.jpg.redacted.veritas")
+        );
+        assert_eq!(
+            build_redacted_path(Path::new("dir/seal.cbor")),
+            PathBuf::from("dir/seal.cbor.redacted")
+        );
+    }
+
+    #[test]
+    fn test_build_report_path() {
+        assert_eq!(
+            build_report_path(Path::new("photos/image.jpg"), "pdf"),
+            PathBuf::from("photos/image.jpg.report.pdf")
+        );
+        assert_eq!(
+            build_report_path(Path::new("noext"), "html"),
+            PathBuf::from("noext.report.html")
+        );
+    }
+
+    #[test]
+    fn test_build_qr_path() {
+        assert_eq!(
+            build_qr_path(Path::new("photos/image.jpg"), "svg"),
+            PathBuf::from("photos/image.jpg.qr.svg")
+        );
+    }
+
+    #[test]
+    fn test_build_vc_path() {
+        assert_eq!(
+            build_vc_path(Path::new("photos/image.jpg")),
+            PathBuf::from("photos/image.jpg.vc.json")
+        );
+    }
+
+    #[test]
+    fn test_is_stdio() {
+        assert!(is_stdio(Path::new("-")));
+        assert!(!is_stdio(Path::new("./-")));
+        assert!(!is_stdio(Path::new("image.jpg.veritas")));
+    }
+
     #[test]
     fn test_format_timestamp() {
         // 2024-01-15 12:30:45.123 UTC
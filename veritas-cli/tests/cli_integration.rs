@@ -81,7 +81,105 @@ fn test_anchor_help_shows_options() {
         .assert()
         .success()
         .stdout(predicate::str::contains("--update-seal"))
-        .stdout(predicate::str::contains("--dry-run"));
+        .stdout(predicate::str::contains("--dry-run"))
+        .stdout(predicate::str::contains("--network"))
+        .stdout(predicate::str::contains("--ledger"))
+        .stdout(predicate::str::contains("--priority-fee"))
+        .stdout(predicate::str::contains("--chain"));
+}
+
+#[test]
+fn test_anchor_mainnet_requires_fee_payer() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"anchored content").unwrap();
+    veritas()
+        .args(["seal", "--mock", test_file.to_str().unwrap()])
+        .assert()
+        .success();
+    let seal_path = temp.path().join("photo.jpg.veritas");
+
+    veritas()
+        .args(["anchor", "--network", "mainnet", "--dry-run"])
+        .arg(&seal_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--payer"));
+
+    veritas()
+        .env_remove("SOLANA_RPC_URL")
+        .args([
+            "anchor",
+            "--network",
+            "mainnet",
+            "--payer",
+            "payer.json",
+            "--priority-fee",
+            "5000",
+            "--dry-run",
+        ])
+        .arg(&seal_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Solana Mainnet"))
+        .stdout(predicate::str::contains(
+            "https://api.mainnet-beta.solana.com",
+        ))
+        .stdout(predicate::str::contains("keypair file payer.json"))
+        .stdout(predicate::str::contains("5000 micro-lamports/CU"));
+}
+
+#[test]
+fn test_anchor_bitcoin_dry_run_and_verify_without_anchors() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"timestamped content").unwrap();
+    veritas()
+        .args(["seal", "--mock", test_file.to_str().unwrap()])
+        .assert()
+        .success();
+    let seal_path = temp.path().join("photo.jpg.veritas");
+
+    veritas()
+        .args([
+            "anchor",
+            "--chain",
+            "bitcoin",
+            "--calendar",
+            "https://calendar.example.org",
+            "--dry-run",
+        ])
+        .arg(&seal_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Bitcoin (OpenTimestamps)"))
+        .stdout(predicate::str::contains("https://calendar.example.org"));
+
+    // No anchors: nothing to look up, verification still succeeds
+    veritas()
+        .args([
+            "verify",
+            "--anchors",
+            "--min-confirmations",
+            "6",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AUTHENTIC"))
+        .stdout(predicate::str::contains("none"));
+
+    // A confirmation depth only applies to anchor checks
+    veritas()
+        .args([
+            "verify",
+            "--min-confirmations",
+            "6",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--anchors"));
 }
 
 // ============================================================================
@@ -206,6 +304,102 @@ fn test_seal_creates_seal_file() {
     );
 }
 
+#[test]
+fn test_seal_validity_window() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("badge.jpg");
+    fs::write(&test_file, b"event badge").unwrap();
+    let file = test_file.to_str().unwrap();
+
+    veritas()
+        .args(["seal", "--mock", "--not-after", "2001-01-01", file])
+        .assert()
+        .failure();
+
+    veritas()
+        .args([
+            "seal",
+            "--mock",
+            "--not-after",
+            "2099-12-31T23:59:59Z",
+            file,
+        ])
+        .assert()
+        .success();
+    veritas()
+        .args(["verify", file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Valid until:"));
+
+    // Not valid yet
+    veritas()
+        .args(["seal", "--mock", "--not-before", "2099-01-01", file])
+        .assert()
+        .success();
+    veritas()
+        .args(["verify", file])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("EXPIRED"))
+        .stderr(predicate::str::contains("validity window"));
+}
+
+#[test]
+fn test_verify_flags_and_rejects_mock_entropy() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"mock sealed photo").unwrap();
+    let file = test_file.to_str().unwrap();
+
+    veritas().args(["seal", "--mock", file]).assert().success();
+
+    veritas()
+        .args(["verify", file])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("not quantum-safe"));
+
+    veritas()
+        .args(["verify", "--reject-mock", file])
+        .assert()
+        .code(65)
+        .stdout(predicate::str::contains("REJECTED"))
+        .stderr(predicate::str::contains("mock entropy"));
+}
+
+#[test]
+fn test_watch_once_seals_each_file_once() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("a.jpg"), b"first photo").unwrap();
+    fs::write(temp.path().join("b.jpg"), b"second photo").unwrap();
+    fs::write(temp.path().join("c.jpg.part"), b"still copying").unwrap();
+
+    veritas()
+        .args(["watch", "--once", "--mock", temp.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 file(s) sealed"));
+
+    assert!(temp.path().join("a.jpg.veritas").exists());
+    assert!(temp.path().join("b.jpg.veritas").exists());
+    assert!(!temp.path().join("c.jpg.part.veritas").exists());
+    let ledger = fs::read_to_string(temp.path().join(".veritas-ledger.jsonl")).unwrap();
+    assert_eq!(ledger.lines().count(), 2);
+
+    // Files in the ledger are not sealed again
+    veritas()
+        .args(["watch", "--once", "--mock", temp.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 file(s) sealed"));
+
+    veritas()
+        .args(["verify", temp.path().join("a.jpg").to_str().unwrap()])
+        .assert()
+        .success();
+}
+
 #[test]
 fn test_seal_verify_roundtrip_authentic() {
     let temp = TempDir::new().unwrap();
@@ -250,6 +444,548 @@ fn test_seal_verify_roundtrip_tampered() {
         .stdout(predicate::str::contains("TAMPERED"));
 }
 
+#[test]
+fn test_seal_and_verify_through_pipes() {
+    let temp = TempDir::new().unwrap();
+    let content = b"generated artifact, never written to disk";
+
+    // The seal is the only thing on stdout
+    let output = veritas()
+        .args(["seal", "--mock", "--stdin", "--seal-out", "-"])
+        .write_stdin(content.to_vec())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let seal_path = temp.path().join("artifact.veritas");
+    fs::write(&seal_path, &output.stdout).unwrap();
+
+    veritas()
+        .args(["verify", "--stdin", "--seal", seal_path.to_str().unwrap()])
+        .write_stdin(content.to_vec())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AUTHENTIC"));
+
+    veritas()
+        .args(["verify", "--stdin", "--seal", seal_path.to_str().unwrap()])
+        .write_stdin(b"tampered artifact".to_vec())
+        .assert()
+        .code(65)
+        .stdout(predicate::str::contains("TAMPERED"));
+
+    // A file can also be checked against a seal read from stdin
+    let test_file = temp.path().join("artifact.bin");
+    fs::write(&test_file, content).unwrap();
+    veritas()
+        .args(["verify", test_file.to_str().unwrap(), "-"])
+        .write_stdin(output.stdout)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_seal_stdin_requires_seal_out() {
+    veritas()
+        .args(["seal", "--mock", "--stdin"])
+        .write_stdin(b"content".to_vec())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--seal-out"));
+}
+
+#[cfg(feature = "c2pa")]
+#[test]
+fn test_c2pa_embed_self_signed_needs_no_credentials() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"jpeg bytes").unwrap();
+    veritas()
+        .args(["seal", "--mock", test_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    veritas()
+        .env_remove("C2PA_SIGNING_KEY")
+        .env_remove("C2PA_SIGNING_CERT")
+        .args([
+            "c2pa",
+            "embed",
+            "--dry-run",
+            "--self-signed",
+            "-i",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TEST ONLY"));
+
+    // A test certificate never mixes with real credentials
+    veritas()
+        .args([
+            "c2pa",
+            "embed",
+            "--self-signed",
+            "--key",
+            "key.pem",
+            "-i",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_report_writes_pdf_and_html() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"Report content").unwrap();
+
+    veritas()
+        .args(["seal", "--mock", test_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    veritas()
+        .args(["report", test_file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AUTHENTIC"));
+    let pdf = fs::read(temp.path().join("photo.jpg.report.pdf")).unwrap();
+    assert!(pdf.starts_with(b"%PDF-"));
+
+    // Tampered content still gets a report, which says so
+    fs::write(&test_file, b"Modified content").unwrap();
+    let html_path = temp.path().join("report.html");
+    veritas()
+        .args([
+            "report",
+            "--format",
+            "html",
+            "--output",
+            html_path.to_str().unwrap(),
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MODIFIED"));
+    let html = fs::read_to_string(&html_path).unwrap();
+    assert!(html.contains("MODIFIED"));
+    assert!(html.contains("https://veritas-q.com/verify/"));
+}
+
+#[test]
+fn test_seal_writes_qr_code() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("evidence.jpg");
+    fs::write(&test_file, b"Evidence content").unwrap();
+
+    veritas()
+        .args(["seal", "--mock", "--qr", test_file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("QR code:"));
+    let png = fs::read(temp.path().join("evidence.jpg.qr.png")).unwrap();
+    assert!(png.starts_with(b"\x89PNG"));
+
+    veritas()
+        .args([
+            "seal",
+            "--mock",
+            "--qr=svg",
+            "--qr-digest",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let svg = fs::read_to_string(temp.path().join("evidence.jpg.qr.svg")).unwrap();
+    assert!(svg.starts_with("<svg"));
+}
+
+#[test]
+fn test_seal_writes_verifiable_credential() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("evidence.jpg");
+    fs::write(&test_file, b"Evidence content").unwrap();
+
+    veritas()
+        .args([
+            "seal",
+            "--mock",
+            "--vc",
+            "did:web:newsroom.example",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Credential:"));
+
+    let json = fs::read_to_string(temp.path().join("evidence.jpg.vc.json")).unwrap();
+    let credential: veritas_core::VerifiableCredential = serde_json::from_str(&json).unwrap();
+    assert_eq!(credential.issuer, "did:web:newsroom.example");
+    let seal = fs::read(temp.path().join("evidence.jpg.veritas")).unwrap();
+    assert_eq!(credential.seal().unwrap().to_cbor().unwrap(), seal);
+
+    veritas()
+        .args(["seal", "--mock", "--vc", "newsroom.example"])
+        .arg(&test_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid DID"));
+
+    // did:key alone issues the credential as the signing key itself
+    let self_issued = temp.path().join("self-issued.jpg");
+    fs::write(&self_issued, b"Self-issued content").unwrap();
+    veritas()
+        .args(["seal", "--mock", "--vc", "did:key"])
+        .arg(&self_issued)
+        .assert()
+        .success();
+    let json = fs::read_to_string(temp.path().join("self-issued.jpg.vc.json")).unwrap();
+    let credential: veritas_core::VerifiableCredential = serde_json::from_str(&json).unwrap();
+    let seal = credential.seal().unwrap();
+    assert_eq!(credential.issuer, veritas_core::did_key(&seal.public_key));
+    let document = veritas_core::DidDocument::for_did_key(&credential.issuer).unwrap();
+    let key = document
+        .assertion_key(&credential.proof.verification_method)
+        .expect("verification method is the did:key");
+    assert_eq!(key.public_key().unwrap(), seal.public_key);
+}
+
+#[test]
+fn test_redact_keeps_seal_verifiable() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"photo taken at a confidential location").unwrap();
+
+    veritas()
+        .args([
+            "seal",
+            "--mock",
+            "--disclosable",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let seal_path = temp.path().join("photo.jpg.veritas");
+    veritas()
+        .args(["redact", seal_path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("capture_location"))
+        .stdout(predicate::str::contains("device_attestation"));
+
+    let redacted_path = temp.path().join("photo.jpg.redacted.veritas");
+    assert!(redacted_path.exists(), "Redacted seal should be created");
+
+    veritas()
+        .args([
+            "verify",
+            test_file.to_str().unwrap(),
+            redacted_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AUTHENTIC"));
+}
+
+#[test]
+fn test_redact_requires_disclosable_seal() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"plain seal").unwrap();
+
+    veritas()
+        .args(["seal", "--mock", test_file.to_str().unwrap()])
+        .assert()
+        .success();
+
+    veritas()
+        .args([
+            "redact",
+            temp.path().join("photo.jpg.veritas").to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("selective disclosure"));
+}
+
+#[test]
+fn test_redact_rejects_signed_fields() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"content hash must stay").unwrap();
+
+    veritas()
+        .args([
+            "seal",
+            "--mock",
+            "--disclosable",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    veritas()
+        .args([
+            "redact",
+            "--field",
+            "content_hash",
+            temp.path().join("photo.jpg.veritas").to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be redacted"));
+}
+
+#[test]
+fn test_diff_reports_differing_fields() {
+    let temp = TempDir::new().unwrap();
+    let first = temp.path().join("first.jpg");
+    let second = temp.path().join("second.jpg");
+    fs::write(&first, b"first take").unwrap();
+    fs::write(&second, b"second take").unwrap();
+
+    for file in [&first, &second] {
+        veritas()
+            .args(["seal", "--mock", file.to_str().unwrap()])
+            .assert()
+            .success();
+    }
+
+    let first_seal = temp.path().join("first.jpg.veritas");
+    let second_seal = temp.path().join("second.jpg.veritas");
+    veritas()
+        .args([
+            "diff",
+            first_seal.to_str().unwrap(),
+            second_seal.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("content_hash.crypto_hash"))
+        .stdout(predicate::str::contains("public_key"));
+
+    veritas()
+        .args([
+            "diff",
+            first_seal.to_str().unwrap(),
+            first_seal.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Seals are identical"));
+}
+
+#[test]
+fn test_watermark_embed_and_extract() {
+    let temp = TempDir::new().unwrap();
+    let photo = temp.path().join("photo.png");
+    image::RgbImage::from_fn(256, 256, |x, y| {
+        image::Rgb([60 + (x % 128) as u8, 70 + (y % 128) as u8, 120])
+    })
+    .save(&photo)
+    .unwrap();
+    let seal_id = "550e8400-e29b-41d4-a716-446655440000";
+
+    veritas()
+        .args([
+            "watermark",
+            "embed",
+            "--seal-id",
+            seal_id,
+            photo.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Watermark embedded"));
+
+    let marked = temp.path().join("photo_watermarked.png");
+    veritas()
+        .args(["--quiet", "watermark", "extract", marked.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(format!("{seal_id}\n"));
+
+    // The original carries no watermark
+    veritas()
+        .args(["watermark", "extract", photo.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No watermark found"));
+}
+
+#[test]
+fn test_seal_segmented_records_segments() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("bodycam.mp4");
+    fs::write(&test_file, vec![0x42; 100]).unwrap();
+
+    veritas()
+        .args([
+            "seal",
+            "--mock",
+            "--segmented",
+            "--segment-size",
+            "32",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Segments:"))
+        .stdout(predicate::str::contains("4 x 32 bytes"));
+
+    veritas()
+        .args(["verify", test_file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AUTHENTIC"));
+}
+
+#[test]
+fn test_verify_segmented_reports_tampered_segments() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("bodycam.mp4");
+    fs::write(&test_file, vec![0x42; 100]).unwrap();
+
+    veritas()
+        .args([
+            "seal",
+            "--mock",
+            "--segmented",
+            "--segment-size",
+            "32",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let mut tampered = vec![0x42; 100];
+    tampered[40] = 0;
+    fs::write(&test_file, tampered).unwrap();
+
+    veritas()
+        .args(["verify", test_file.to_str().unwrap()])
+        .assert()
+        .code(65)
+        .stdout(predicate::str::contains("Tampered segments:"))
+        .stdout(predicate::str::contains("#1 bytes 32..64"));
+}
+
+#[test]
+fn test_seal_with_custom_media_type() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("scene.bin");
+    fs::write(&test_file, b"binary scene data").unwrap();
+
+    veritas()
+        .args([
+            "seal",
+            "--mock",
+            "--format",
+            "json",
+            "--media-type",
+            "model/gltf-binary",
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let seal = fs::read_to_string(temp.path().join("scene.bin.veritas")).unwrap();
+    assert!(seal.contains(r#""media_type":{"Custom":"model/gltf-binary"}"#));
+
+    veritas()
+        .args(["verify", test_file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AUTHENTIC"));
+}
+
+#[test]
+fn test_unknown_media_type_rejected() {
+    veritas()
+        .args(["seal", "--media-type", "hologram", "file.bin"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown media type"));
+}
+
+#[test]
+fn test_segment_size_requires_segmented() {
+    veritas()
+        .args(["seal", "--segment-size", "32", "file.mp4"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--segmented"));
+}
+
+/// Single-page PDF with one content stream.
+fn sample_pdf() -> Vec<u8> {
+    let content = "BT /F1 24 Tf 72 720 Td (Sealed report) Tj ET";
+    let bodies = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /Contents 4 0 R >>".to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{content}\nendstream",
+            content.len()
+        ),
+    ];
+
+    let mut pdf = b"%PDF-1.7\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, body) in bodies.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{body}\nendobj\n", i + 1).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(b"xref\n0 5\n0000000000 65535 f\r\n");
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n\r\n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!("trailer\n<< /Size 5 /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n").as_bytes(),
+    );
+    pdf
+}
+
+#[test]
+fn test_seal_embed_pdf_verifies_without_seal_file() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("report.pdf");
+    fs::write(&test_file, sample_pdf()).unwrap();
+
+    veritas()
+        .args(["seal", "--mock", "--embed", test_file.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Sealed PDF:"));
+
+    // The sealed copy carries its own seal
+    let sealed_pdf = temp.path().join("report.sealed.pdf");
+    assert!(sealed_pdf.exists());
+    veritas()
+        .args(["verify", sealed_pdf.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AUTHENTIC"))
+        .stdout(predicate::str::contains("re-saved"));
+}
+
+#[test]
+fn test_seal_embed_requires_pdf() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("photo.jpg");
+    fs::write(&test_file, b"not a pdf").unwrap();
+
+    veritas()
+        .args(["seal", "--mock", "--embed", test_file.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires a PDF"));
+}
+
 #[test]
 fn test_seal_with_json_format() {
     let temp = TempDir::new().unwrap();
@@ -337,9 +1073,17 @@ fn test_save_and_load_keypair() {
 
     assert!(keypair_path.exists(), "Keypair file should be created");
 
-    // Keypair should be 5984 bytes (1952 + 4032)
-    let keypair_size = fs::metadata(&keypair_path).unwrap().len();
-    assert_eq!(keypair_size, 5984, "Keypair file should be 5984 bytes");
+    // Versioned container: 16-byte header, keys (1952 + 4032), SHA3-256 checksum
+    let keypair_data = fs::read(&keypair_path).unwrap();
+    assert!(
+        keypair_data.starts_with(b"VQKP"),
+        "Keypair file should have a header"
+    );
+    assert_eq!(
+        keypair_data.len(),
+        6032,
+        "Keypair file should be 6032 bytes"
+    );
 
     // Seal second file using the saved keypair
     veritas()
@@ -355,6 +1099,117 @@ fn test_save_and_load_keypair() {
         .stdout(predicate::str::contains("loaded from file"));
 }
 
+/// Seal `file` with a new keypair saved to `keypair_path`.
+fn save_keypair(file: &std::path::Path, keypair_path: &std::path::Path, passphrase: Option<&str>) {
+    let mut cmd = veritas();
+    cmd.env_remove("VERITAS_KEYPAIR_PASSPHRASE");
+    if let Some(passphrase) = passphrase {
+        cmd.env("VERITAS_KEYPAIR_PASSPHRASE", passphrase);
+    }
+    cmd.args([
+        "seal",
+        "--mock",
+        "--save-keypair",
+        keypair_path.to_str().unwrap(),
+        file.to_str().unwrap(),
+    ])
+    .assert()
+    .success();
+}
+
+#[test]
+fn test_corrupted_keypair_is_rejected() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("file.jpg");
+    let keypair_path = temp.path().join("my.keypair");
+    fs::write(&test_file, b"File content").unwrap();
+    save_keypair(&test_file, &keypair_path, None);
+
+    let mut data = fs::read(&keypair_path).unwrap();
+    data[4000] ^= 0x01;
+    fs::write(&keypair_path, &data).unwrap();
+
+    veritas()
+        .env_remove("VERITAS_KEYPAIR_PASSPHRASE")
+        .args([
+            "seal",
+            "--mock",
+            "--keypair",
+            keypair_path.to_str().unwrap(),
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("checksum mismatch"));
+}
+
+#[test]
+fn test_wrong_algorithm_keypair_is_rejected() {
+    use sha3::{Digest, Sha3_256};
+
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("file.jpg");
+    let keypair_path = temp.path().join("my.keypair");
+    fs::write(&test_file, b"File content").unwrap();
+    save_keypair(&test_file, &keypair_path, None);
+
+    // Relabel the algorithm and fix up the checksum
+    let mut data = fs::read(&keypair_path).unwrap();
+    data[5] = 7;
+    let end = data.len() - 32;
+    let checksum = Sha3_256::digest(&data[..end]);
+    data[end..].copy_from_slice(&checksum);
+    fs::write(&keypair_path, &data).unwrap();
+
+    veritas()
+        .env_remove("VERITAS_KEYPAIR_PASSPHRASE")
+        .args([
+            "seal",
+            "--mock",
+            "--keypair",
+            keypair_path.to_str().unwrap(),
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unsupported key algorithm 7"));
+}
+
+#[test]
+fn test_encrypted_keypair_needs_passphrase() {
+    let temp = TempDir::new().unwrap();
+    let test_file = temp.path().join("file.jpg");
+    let keypair_path = temp.path().join("my.keypair");
+    fs::write(&test_file, b"File content").unwrap();
+    save_keypair(&test_file, &keypair_path, Some("correct horse"));
+
+    let seal_with = |passphrase: Option<&str>| {
+        let mut cmd = veritas();
+        cmd.env_remove("VERITAS_KEYPAIR_PASSPHRASE");
+        if let Some(passphrase) = passphrase {
+            cmd.env("VERITAS_KEYPAIR_PASSPHRASE", passphrase);
+        }
+        cmd.args([
+            "seal",
+            "--mock",
+            "--keypair",
+            keypair_path.to_str().unwrap(),
+            test_file.to_str().unwrap(),
+        ])
+        .assert()
+    };
+
+    seal_with(None)
+        .failure()
+        .stderr(predicate::str::contains("VERITAS_KEYPAIR_PASSPHRASE"));
+    seal_with(Some("battery staple"))
+        .failure()
+        .stderr(predicate::str::contains("wrong passphrase"));
+    seal_with(Some("correct horse"))
+        .success()
+        .stdout(predicate::str::contains("loaded from file"));
+}
+
 // ============================================================================
 // Quiet and Verbose Mode Tests
 // ============================================================================
@@ -365,7 +365,7 @@ fn test_e2e_media_type_detection_by_extension() {
         ("video.webm", "video"),
         ("audio.mp3", "audio"),
         ("audio.wav", "audio"),
-        ("document.pdf", "generic"),
+        ("document.pdf", "document"),
         ("data.bin", "generic"),
     ];
 
@@ -0,0 +1,68 @@
+//! Benchmark of batch seal verification, sequential vs parallel.
+//!
+//! Run with: cargo run --release -p veritas-core --example verify_throughput [seals]
+
+use std::time::{Duration, Instant};
+
+use veritas_core::{
+    generate_keypair, verify_seals_parallel, MediaType, MockQrng, SealBuilder, VeritasSeal,
+};
+
+/// Seals verified per run when no count is given
+const DEFAULT_SEALS: usize = 512;
+
+#[tokio::main]
+async fn main() {
+    let count = std::env::args()
+        .nth(1)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(DEFAULT_SEALS);
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+    println!("=== Seal Verification Throughput ===\n");
+    println!("Creating {} seals...", count);
+
+    let (public_key, secret_key) = generate_keypair();
+    let qrng = MockQrng::default();
+    let mut seals: Vec<VeritasSeal> = Vec::with_capacity(count);
+    for i in 0..count {
+        let seal = SealBuilder::new(
+            format!("benchmark content {i}").into_bytes(),
+            MediaType::Image,
+        )
+        .build_secure(&qrng, &secret_key, &public_key)
+        .await
+        .expect("Failed to create seal");
+        seals.push(seal);
+    }
+
+    let start = Instant::now();
+    let sequential: Vec<_> = seals.iter().map(VeritasSeal::verify_detailed).collect();
+    let sequential_time = start.elapsed();
+
+    let start = Instant::now();
+    let parallel = verify_seals_parallel(&seals);
+    let parallel_time = start.elapsed();
+
+    assert!(sequential
+        .iter()
+        .chain(&parallel)
+        .all(|result| result.as_ref().is_ok_and(|r| r.is_valid())));
+
+    println!("\nCores: {}\n", threads);
+    report("Sequential", count, sequential_time);
+    report("Parallel", count, parallel_time);
+    println!(
+        "\nSpeedup: {:.2}x",
+        sequential_time.as_secs_f64() / parallel_time.as_secs_f64()
+    );
+}
+
+fn report(label: &str, count: usize, elapsed: Duration) {
+    println!(
+        "{:<12} {:>8.1} ms  {:>10.0} seals/s",
+        label,
+        elapsed.as_secs_f64() * 1000.0,
+        count as f64 / elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,27 @@
+#![no_main]
+
+//! Fuzz target for Veritas quantum seal assertion extraction from C2PA
+//!
+//! The assertion is read from manifests embedded in uploaded media, so both
+//! the C2PA container parsing and the JSON assertion lookup see
+//! attacker-controlled bytes.
+//!
+//! Run with: cargo +nightly fuzz run fuzz_c2pa_assertion \
+//!   fuzz/corpus/fuzz_c2pa_assertion fuzz/seeds/fuzz_c2pa_assertion
+
+use std::io::Cursor;
+
+use libfuzzer_sys::fuzz_target;
+use veritas_core::c2pa::{extract_quantum_seal_from_stream, quantum_seal_from_manifest_json};
+
+fuzz_target!(|data: &[u8]| {
+    // Manifest store JSON, as rendered by the C2PA reader
+    if let Ok(json) = std::str::from_utf8(data) {
+        if let Ok(assertion) = quantum_seal_from_manifest_json(json) {
+            let _ = assertion.to_blockchain_anchor();
+        }
+    }
+
+    // Raw media with an embedded manifest
+    let _ = extract_quantum_seal_from_stream("image/jpeg", Cursor::new(data));
+});
@@ -7,7 +7,7 @@
 //! - Memory safety issues
 //! - Logic errors in validation
 //!
-//! Run with: cargo +nightly fuzz run fuzz_from_cbor
+//! Run with: cargo +nightly fuzz run fuzz_from_cbor fuzz/corpus/fuzz_from_cbor fuzz/seeds/fuzz_from_cbor
 
 use libfuzzer_sys::fuzz_target;
 use veritas_core::VeritasSeal;
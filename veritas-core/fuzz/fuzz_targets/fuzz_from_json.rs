@@ -0,0 +1,26 @@
+#![no_main]
+
+//! Fuzz target for JSON seal parsing, as done by the WASM verifier
+//!
+//! veritas-wasm accepts seal files in CBOR or JSON and verifies them against
+//! the user's file in the browser. This target exercises the JSON path:
+//! - Panics from malformed or unvalidated seals (e.g. segment manifests)
+//! - Oversized input handling
+//! - Content verification of whatever parses
+//!
+//! Run with: cargo +nightly fuzz run fuzz_from_json fuzz/corpus/fuzz_from_json fuzz/seeds/fuzz_from_json
+
+use libfuzzer_sys::fuzz_target;
+use veritas_core::VeritasSeal;
+
+/// Content the fuzzed seals claim to cover
+const CONTENT: &[u8] = b"Veritas Q seal format fixture";
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(seal) = VeritasSeal::from_json(data) {
+        // Matching and tampered content: the tampered path localizes segments
+        let _ = seal.verify_content(CONTENT);
+        let _ = seal.verify_content(&CONTENT[1..]);
+        let _ = seal.to_json_canonical();
+    }
+});
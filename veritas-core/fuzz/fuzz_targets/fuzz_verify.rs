@@ -5,7 +5,7 @@
 //! This target exercises the verification path with potentially malformed seals.
 //! It creates seals from arbitrary CBOR data and attempts verification.
 //!
-//! Run with: cargo +nightly fuzz run fuzz_verify
+//! Run with: cargo +nightly fuzz run fuzz_verify fuzz/corpus/fuzz_verify fuzz/seeds/fuzz_verify
 
 use libfuzzer_sys::fuzz_target;
 use veritas_core::VeritasSeal;
@@ -17,5 +17,7 @@ fuzz_target!(|data: &[u8]| {
         // This should never panic even with garbage data
         let _ = seal.verify();
         let _ = seal.verify_detailed();
+        // Content mismatch localizes tampered segments of segmented seals
+        let _ = seal.verify_content(data);
     }
 });
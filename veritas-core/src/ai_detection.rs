@@ -0,0 +1,373 @@
+//! Signed verdicts of AI-content detectors.
+//!
+//! A seal proves who sealed the content and when, not whether a camera or a
+//! generator produced it. Detectors (SynthID, vendor classifiers, in-house
+//! models) answer that second question; [`AiContentDetector`] is the hook for
+//! plugging one in, and its [`AiDetectionVerdict`] is attached to the seal as
+//! an [`AiDetectionAssertion`] signed by whoever ran the detector.
+//!
+//! Like counter-signatures, assertions sign the [`VeritasSeal::seal_hash`] and
+//! live outside the sealed payload, so they can be added after sealing
+//! without invalidating it. They are carried into C2PA manifests as a
+//! `veritas.ai_detection` assertion.
+
+use chrono::Utc;
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::{MediaType, VeritasSeal};
+
+/// Domain separation tag for AI-detection payloads.
+const AI_DETECTION_DOMAIN: &str = "veritas-ai-detection-v1";
+
+/// Scores are stored in basis points: 10000 means certainly AI-generated.
+pub const MAX_AI_SCORE_BPS: u16 = 10_000;
+
+/// Longest accepted detector name or version.
+pub const MAX_DETECTOR_LABEL_LEN: usize = 128;
+
+/// Detector conclusion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AiVerdict {
+    /// Likely captured or made by a person
+    Human,
+    /// Likely produced or substantially altered by a generative model
+    AiGenerated,
+    /// The detector could not decide
+    Inconclusive,
+}
+
+impl std::fmt::Display for AiVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Human => write!(f, "human"),
+            Self::AiGenerated => write!(f, "ai_generated"),
+            Self::Inconclusive => write!(f, "inconclusive"),
+        }
+    }
+}
+
+/// Output of an AI-content detector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AiDetectionVerdict {
+    /// Detector or model name (e.g. `synthid`)
+    pub detector: String,
+    /// Detector or model version
+    pub detector_version: String,
+    /// Likelihood the content is AI-generated, in basis points (0-10000)
+    pub score_bps: u16,
+    /// Conclusion drawn by the detector
+    pub verdict: AiVerdict,
+}
+
+impl AiDetectionVerdict {
+    /// Likelihood the content is AI-generated, from 0.0 to 1.0.
+    pub fn score(&self) -> f64 {
+        f64::from(self.score_bps) / f64::from(MAX_AI_SCORE_BPS)
+    }
+
+    /// Check the score range and label lengths.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.score_bps > MAX_AI_SCORE_BPS {
+            return Err(VeritasError::InvalidSeal(format!(
+                "AI-detection score {} exceeds {MAX_AI_SCORE_BPS} basis points",
+                self.score_bps
+            )));
+        }
+        for (name, value) in [
+            ("detector", &self.detector),
+            ("detector_version", &self.detector_version),
+        ] {
+            if value.is_empty() || value.len() > MAX_DETECTOR_LABEL_LEN {
+                return Err(VeritasError::InvalidSeal(format!(
+                    "AI-detection {name} must be 1 to {MAX_DETECTOR_LABEL_LEN} bytes"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An external AI-content detector.
+///
+/// Implementations wrap a detection model or service; the crate ships none.
+pub trait AiContentDetector {
+    /// Classify `content` of the given media type.
+    fn detect(&self, content: &[u8], media_type: &MediaType) -> Result<AiDetectionVerdict>;
+}
+
+/// A detector verdict about a sealed content, signed by the party that ran
+/// the detector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AiDetectionAssertion {
+    /// What the detector concluded
+    pub verdict: AiDetectionVerdict,
+    /// When the verdict was signed (Unix timestamp ms)
+    pub timestamp: u64,
+    /// Asserting party's ML-DSA-65 public key
+    pub signer_public_key: Vec<u8>,
+    /// Detached ML-DSA-65 signature over the assertion payload
+    pub signature: Vec<u8>,
+}
+
+/// Signed portion of an AI-detection assertion.
+#[derive(Serialize)]
+struct AiDetectionPayload<'a> {
+    domain: &'static str,
+    seal_hash: &'a [u8; 32],
+    verdict: &'a AiDetectionVerdict,
+    timestamp: u64,
+}
+
+impl AiDetectionAssertion {
+    /// Sign a verdict about a seal hash at the given time.
+    pub fn sign(
+        seal_hash: &[u8; 32],
+        verdict: AiDetectionVerdict,
+        timestamp: u64,
+        secret_key: &mldsa65::SecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<Self> {
+        verdict.validate()?;
+        let payload = payload_bytes(seal_hash, &verdict, timestamp)?;
+        let signature = mldsa65::detached_sign(&payload, secret_key);
+
+        Ok(Self {
+            verdict,
+            timestamp,
+            signer_public_key: public_key.as_bytes().to_vec(),
+            signature: signature.as_bytes().to_vec(),
+        })
+    }
+
+    /// Verify this assertion against a seal.
+    ///
+    /// Returns `Ok(false)` for malformed keys, signatures or verdicts, or when
+    /// the assertion was made about a different seal.
+    pub fn verify(&self, seal: &VeritasSeal) -> Result<bool> {
+        if self.verdict.validate().is_err() {
+            return Ok(false);
+        }
+        let payload = payload_bytes(&seal.seal_hash(), &self.verdict, self.timestamp)?;
+
+        let Ok(public_key) = mldsa65::PublicKey::from_bytes(&self.signer_public_key) else {
+            return Ok(false);
+        };
+        let Ok(signature) = mldsa65::DetachedSignature::from_bytes(&self.signature) else {
+            return Ok(false);
+        };
+        Ok(mldsa65::verify_detached_signature(&signature, &payload, &public_key).is_ok())
+    }
+
+    /// SHA3-256 fingerprint of the asserting key (hex).
+    pub fn signer_fingerprint(&self) -> String {
+        hex::encode(Sha3_256::digest(&self.signer_public_key))
+    }
+}
+
+fn payload_bytes(
+    seal_hash: &[u8; 32],
+    verdict: &AiDetectionVerdict,
+    timestamp: u64,
+) -> Result<Vec<u8>> {
+    let payload = AiDetectionPayload {
+        domain: AI_DETECTION_DOMAIN,
+        seal_hash,
+        verdict,
+        timestamp,
+    };
+    let mut bytes = Vec::with_capacity(160);
+    ciborium::into_writer(&payload, &mut bytes)
+        .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+    Ok(bytes)
+}
+
+impl VeritasSeal {
+    /// Run `detector` on the sealed content and attach its verdict, signed
+    /// now by the given key.
+    ///
+    /// The seal must verify against `content`, so a verdict is never signed
+    /// about other content. Returns the assertion that was added.
+    pub fn detect_ai_content(
+        &mut self,
+        detector: &dyn AiContentDetector,
+        content: &[u8],
+        secret_key: &mldsa65::SecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<&AiDetectionAssertion> {
+        if !self.verify_content(content)?.is_authentic() {
+            return Err(VeritasError::InvalidSeal(
+                "content does not match this seal".into(),
+            ));
+        }
+        let verdict = detector.detect(content, &self.media_type)?;
+        self.attach_ai_detection(verdict, secret_key, public_key)
+    }
+
+    /// Sign a detector verdict now and attach it to the seal.
+    ///
+    /// Older seals are migrated to the current format first (the original
+    /// signature stays valid).
+    pub fn attach_ai_detection(
+        &mut self,
+        verdict: AiDetectionVerdict,
+        secret_key: &mldsa65::SecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<&AiDetectionAssertion> {
+        let timestamp = u64::try_from(Utc::now().timestamp_millis()).map_err(|_| {
+            VeritasError::InvalidTimestamp {
+                reason: "timestamp before Unix epoch".into(),
+            }
+        })?;
+        let assertion = AiDetectionAssertion::sign(
+            &self.seal_hash(),
+            verdict,
+            timestamp,
+            secret_key,
+            public_key,
+        )?;
+        self.add_ai_detection(assertion)
+    }
+
+    /// Attach an assertion signed elsewhere (e.g. by a detection service).
+    ///
+    /// The assertion must verify against this seal.
+    pub fn add_ai_detection(
+        &mut self,
+        assertion: AiDetectionAssertion,
+    ) -> Result<&AiDetectionAssertion> {
+        if !assertion.verify(self)? {
+            return Err(VeritasError::InvalidSeal(
+                "AI-detection assertion does not verify against this seal".into(),
+            ));
+        }
+
+        crate::schema::migrate(self)?;
+        self.ai_detections.push(assertion);
+        Ok(self.ai_detections.last().expect("just pushed"))
+    }
+
+    /// Verify every AI-detection assertion on the seal.
+    ///
+    /// Returns one result per entry in `ai_detections`, in order.
+    pub fn verify_ai_detections(&self) -> Result<Vec<bool>> {
+        self.ai_detections
+            .iter()
+            .map(|assertion| assertion.verify(self))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, generate_keypair_raw, SealBuilder};
+
+    const CONTENT: &[u8] = b"possibly generated";
+
+    struct FixedDetector(u16);
+
+    impl AiContentDetector for FixedDetector {
+        fn detect(&self, _content: &[u8], _media_type: &MediaType) -> Result<AiDetectionVerdict> {
+            Ok(AiDetectionVerdict {
+                detector: "fixed".into(),
+                detector_version: "1.0".into(),
+                score_bps: self.0,
+                verdict: if self.0 >= 5_000 {
+                    AiVerdict::AiGenerated
+                } else {
+                    AiVerdict::Human
+                },
+            })
+        }
+    }
+
+    async fn sample_seal() -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(CONTENT.to_vec(), MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal")
+    }
+
+    #[tokio::test]
+    async fn test_detect_and_verify() {
+        let mut seal = sample_seal().await;
+        let (pk, sk) = generate_keypair_raw();
+
+        let assertion = seal
+            .detect_ai_content(&FixedDetector(9_250), CONTENT, &sk, &pk)
+            .unwrap();
+        assert_eq!(assertion.verdict.verdict, AiVerdict::AiGenerated);
+        assert!((assertion.verdict.score() - 0.925).abs() < f64::EPSILON);
+
+        assert!(seal.verify().unwrap());
+        assert_eq!(seal.verify_ai_detections().unwrap(), vec![true]);
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(restored.ai_detections, seal.ai_detections);
+        assert_eq!(restored.verify_ai_detections().unwrap(), vec![true]);
+    }
+
+    #[tokio::test]
+    async fn test_detect_rejects_other_content() {
+        let mut seal = sample_seal().await;
+        let (pk, sk) = generate_keypair_raw();
+
+        assert!(seal
+            .detect_ai_content(&FixedDetector(0), b"other", &sk, &pk)
+            .is_err());
+        assert!(seal.ai_detections.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tampered_verdict_detected() {
+        let mut seal = sample_seal().await;
+        let (pk, sk) = generate_keypair_raw();
+        seal.detect_ai_content(&FixedDetector(9_000), CONTENT, &sk, &pk)
+            .unwrap();
+
+        seal.ai_detections[0].verdict.verdict = AiVerdict::Human;
+        assert_eq!(seal.verify_ai_detections().unwrap(), vec![false]);
+
+        seal.ai_detections[0].verdict.verdict = AiVerdict::AiGenerated;
+        seal.ai_detections[0].verdict.score_bps = 100;
+        assert_eq!(seal.verify_ai_detections().unwrap(), vec![false]);
+    }
+
+    #[tokio::test]
+    async fn test_assertion_bound_to_seal() {
+        let mut seal = sample_seal().await;
+        let other = sample_seal().await;
+        let (pk, sk) = generate_keypair_raw();
+        let verdict = FixedDetector(10)
+            .detect(CONTENT, &MediaType::Image)
+            .unwrap();
+
+        let foreign = AiDetectionAssertion::sign(&other.seal_hash(), verdict, 1, &sk, &pk).unwrap();
+        assert!(!foreign.verify(&seal).unwrap());
+        assert!(seal.add_ai_detection(foreign).is_err());
+    }
+
+    #[test]
+    fn test_verdict_validation() {
+        let mut verdict = AiDetectionVerdict {
+            detector: "synthid".into(),
+            detector_version: "2".into(),
+            score_bps: MAX_AI_SCORE_BPS,
+            verdict: AiVerdict::Inconclusive,
+        };
+        assert!(verdict.validate().is_ok());
+
+        verdict.score_bps = MAX_AI_SCORE_BPS + 1;
+        assert!(verdict.validate().is_err());
+
+        verdict.score_bps = 0;
+        verdict.detector = String::new();
+        assert!(verdict.validate().is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Batched anchoring: one transaction for many seals.
+//!
+//! Anchoring each seal costs one transaction. An [`AnchorBatch`] instead
+//! hashes the anchor digests of many seals into a Merkle tree and only the
+//! root is published. Each seal then carries a [`BatchProof`] in its
+//! [`BlockchainAnchor`]: the root and the sibling path from its digest. The
+//! path is checked offline; only the root needs a lookup on chain, and that
+//! lookup is the same for every seal of the batch.
+//!
+//! Leaves are `SHA3-256(0x00 || "veritas-anchor-batch-v1" || digest)`, so a
+//! batch root can never be mistaken for the digest of a single seal.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::constant_time::ct_eq;
+use crate::merkle;
+pub use crate::merkle::ProofStep;
+use crate::seal::{BlockchainAnchor, VeritasSeal};
+
+/// Domain separation tag for batch leaves.
+const BATCH_DOMAIN: &str = "veritas-anchor-batch-v1";
+
+/// Inclusion of a seal in an anchored batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchProof {
+    /// Merkle root published on chain
+    pub root: [u8; 32],
+    /// Sibling path from the seal's leaf to the root
+    pub path: Vec<ProofStep>,
+}
+
+impl BatchProof {
+    /// Returns true if the path leads from the seal's anchor digest to the
+    /// root (no network access needed).
+    pub fn includes(&self, seal: &VeritasSeal) -> bool {
+        ct_eq(
+            &merkle::root_from_path(batch_leaf(&seal.anchor_digest()), &self.path),
+            &self.root,
+        )
+    }
+}
+
+/// Anchor digests of several seals, committed to by one Merkle root.
+#[derive(Debug, Clone)]
+pub struct AnchorBatch {
+    /// Tree levels, from the leaves up to the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl AnchorBatch {
+    /// Batch the given anchor digests, in order. Returns `None` when empty.
+    pub fn new(digests: &[[u8; 32]]) -> Option<Self> {
+        if digests.is_empty() {
+            return None;
+        }
+        let leaves: Vec<[u8; 32]> = digests.iter().map(batch_leaf).collect();
+        Some(Self {
+            levels: merkle::levels(&leaves),
+        })
+    }
+
+    /// Number of digests in the batch.
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Always false: empty batches cannot be built.
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Digest to publish on chain.
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Proof of inclusion of the digest at `index`.
+    pub fn proof(&self, index: usize) -> Option<BatchProof> {
+        (index < self.len()).then(|| BatchProof {
+            root: self.root(),
+            path: merkle::path_in(&self.levels, index),
+        })
+    }
+
+    /// Anchor for the seal at `index`, given the anchor of the root.
+    pub fn anchor_for(
+        &self,
+        index: usize,
+        root_anchor: &BlockchainAnchor,
+    ) -> Option<BlockchainAnchor> {
+        let proof = self.proof(index)?;
+        Some(BlockchainAnchor {
+            batch: Some(proof),
+            ..root_anchor.clone()
+        })
+    }
+}
+
+/// Leaf hash of an anchor digest.
+fn batch_leaf(digest: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update([0x00]);
+    hasher.update(BATCH_DOMAIN.as_bytes());
+    hasher.update(digest);
+    hasher.finalize().into()
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::{generate_keypair, MediaType, SealBuilder};
+
+    async fn seals(count: usize) -> Vec<VeritasSeal> {
+        let (public_key, secret_key) = generate_keypair();
+        let mut seals = Vec::new();
+        for i in 0..count {
+            let seal = SealBuilder::new(format!("content {i}").into_bytes(), MediaType::Generic)
+                .build_secure(&MockQrng::default(), &secret_key, &public_key)
+                .await
+                .unwrap();
+            seals.push(seal);
+        }
+        seals
+    }
+
+    #[tokio::test]
+    async fn test_batch_proofs_verify_offline() {
+        let seals = seals(5).await;
+        let digests: Vec<[u8; 32]> = seals.iter().map(VeritasSeal::anchor_digest).collect();
+        let batch = AnchorBatch::new(&digests).unwrap();
+        assert_eq!(batch.len(), 5);
+        assert!(AnchorBatch::new(&[]).is_none());
+        assert!(batch.proof(5).is_none());
+
+        let root_anchor = BlockchainAnchor {
+            chain: "bitcoin".into(),
+            tx_id: "https://alice.btc.calendar.opentimestamps.org".into(),
+            block_height: 0,
+            proof: Some(vec![0x08]),
+            batch: None,
+        };
+        for (index, seal) in seals.iter().enumerate() {
+            let anchor = batch.anchor_for(index, &root_anchor).unwrap();
+            assert_eq!(anchor.tx_id, root_anchor.tx_id);
+            assert_eq!(anchor.committed_digest(seal), Some(batch.root()));
+            // A proof only fits its own seal
+            let other = &seals[(index + 1) % seals.len()];
+            assert!(!anchor.batch.as_ref().unwrap().includes(other));
+            assert_eq!(anchor.committed_digest(other), None);
+        }
+
+        // A batch of one still commits to the leaf, not the bare digest
+        let single = AnchorBatch::new(&digests[..1]).unwrap();
+        assert_ne!(single.root(), digests[0]);
+        assert!(single.proof(0).unwrap().includes(&seals[0]));
+        assert_eq!(root_anchor.committed_digest(&seals[0]), Some(digests[0]));
+    }
+}
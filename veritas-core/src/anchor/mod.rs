@@ -0,0 +1,192 @@
+//! Anchoring seals on public ledgers.
+//!
+//! An anchor publishes [`crate::VeritasSeal::anchor_digest`] on a ledger nobody
+//! controls, proving the seal existed by the time the block was produced.
+//! Trusting a single chain makes it a single point of trust, so a seal can
+//! carry one anchor per chain ([`crate::VeritasSeal::anchors`]) and each one is
+//! checked on its own.
+//!
+//! Each chain has an [`AnchorVerifier`] checking its anchors, and an
+//! [`AnchorProvider`] when seals can also be anchored from here (`network`
+//! feature):
+//!
+//! - **Solana** - memo transaction carrying the digest, sent by `veritas-cli`
+//!   and checked over JSON-RPC (`SolanaVerifier`)
+//! - **Bitcoin** - OpenTimestamps calendars commit to the digest in a Bitcoin
+//!   transaction (`OpenTimestamps`)
+//!
+//! Seals can also be anchored in batches ([`AnchorBatch`]): one transaction
+//! commits to the Merkle root of many anchor digests, and each seal keeps its
+//! path to the root in [`BatchProof`], checked offline.
+//!
+//! An anchor only counts once its block is buried deep enough that the chain
+//! will not drop it: [`verify_anchors`] reports shallower ones as
+//! [`AnchorStatus::Shallow`].
+
+mod batch;
+#[cfg(feature = "network")]
+mod opentimestamps;
+#[cfg(feature = "network")]
+mod solana;
+pub use batch::{AnchorBatch, BatchProof, ProofStep};
+#[cfg(feature = "network")]
+pub use opentimestamps::{OpenTimestamps, OpenTimestampsConfig, BITCOIN_CHAIN};
+#[cfg(feature = "network")]
+pub use solana::{solana_memo, SolanaVerifier, SOLANA_CLUSTERS};
+
+#[cfg(feature = "network")]
+use async_trait::async_trait;
+
+#[cfg(feature = "network")]
+use crate::error::Result;
+#[cfg(feature = "network")]
+use crate::seal::{BlockchainAnchor, VeritasSeal};
+
+/// Outcome of checking one anchor against its chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnchorStatus {
+    /// The chain commits to the seal's digest in this block, buried under
+    /// `confirmations` blocks (counting its own)
+    Confirmed {
+        block_height: u64,
+        confirmations: u64,
+    },
+    /// Confirmed, but with fewer confirmations than required
+    Shallow {
+        block_height: u64,
+        confirmations: u64,
+    },
+    /// Submitted but not in a block yet (OpenTimestamps batches digests
+    /// into a Bitcoin transaction every few hours)
+    Pending,
+    /// The anchor does not commit to this seal's digest
+    Mismatch(String),
+    /// No provider handles the anchor's chain
+    Unsupported,
+}
+
+impl AnchorStatus {
+    /// Returns true if the anchor is confirmed on chain.
+    pub fn is_confirmed(&self) -> bool {
+        matches!(self, Self::Confirmed { .. })
+    }
+
+    /// Short lowercase name of the status (`confirmed`, `shallow`, ...).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Confirmed { .. } => "confirmed",
+            Self::Shallow { .. } => "shallow",
+            Self::Pending => "pending",
+            Self::Mismatch(_) => "mismatch",
+            Self::Unsupported => "unsupported",
+        }
+    }
+
+    /// Block height and confirmations, once the anchor is in a block.
+    pub fn depth(&self) -> Option<(u64, u64)> {
+        match *self {
+            Self::Confirmed {
+                block_height,
+                confirmations,
+            }
+            | Self::Shallow {
+                block_height,
+                confirmations,
+            } => Some((block_height, confirmations)),
+            _ => None,
+        }
+    }
+
+    /// Demote a confirmation with fewer than `min_confirmations` to
+    /// [`AnchorStatus::Shallow`].
+    pub fn require_confirmations(self, min_confirmations: u64) -> Self {
+        match self {
+            Self::Confirmed {
+                block_height,
+                confirmations,
+            } if confirmations < min_confirmations => Self::Shallow {
+                block_height,
+                confirmations,
+            },
+            status => status,
+        }
+    }
+}
+
+/// A ledger whose anchors can be checked.
+///
+/// Implementations must be thread-safe (`Send + Sync`).
+#[cfg(feature = "network")]
+#[async_trait]
+pub trait AnchorVerifier: Send + Sync {
+    /// Chain identifier recorded in [`BlockchainAnchor::chain`].
+    fn chain(&self) -> &str;
+
+    /// Check that `anchor` commits to the anchor digest of `seal`, and how
+    /// deep its block is.
+    async fn verify(&self, seal: &VeritasSeal, anchor: &BlockchainAnchor) -> Result<AnchorStatus>;
+}
+
+/// A ledger that seals can be anchored on.
+#[cfg(feature = "network")]
+#[async_trait]
+pub trait AnchorProvider: AnchorVerifier {
+    /// Publish `digest` (a seal's [`VeritasSeal::anchor_digest`], or the
+    /// root of an [`AnchorBatch`]) and return the anchor to record with
+    /// [`VeritasSeal::add_anchor`].
+    async fn anchor(&self, digest: &[u8; 32]) -> Result<BlockchainAnchor>;
+}
+
+/// Check every anchor of `seal` with the verifier for its chain.
+///
+/// Anchors are checked one after the other; those on a chain no verifier
+/// handles are reported as [`AnchorStatus::Unsupported`], and those with
+/// fewer than `min_confirmations` as [`AnchorStatus::Shallow`].
+#[cfg(feature = "network")]
+pub async fn verify_anchors<'a>(
+    seal: &'a VeritasSeal,
+    verifiers: &[&dyn AnchorVerifier],
+    min_confirmations: u64,
+) -> Vec<(&'a BlockchainAnchor, Result<AnchorStatus>)> {
+    let mut results = Vec::new();
+    for anchor in seal.anchors() {
+        let status = match verifiers.iter().find(|v| v.chain() == anchor.chain) {
+            Some(verifier) => verifier
+                .verify(seal, anchor)
+                .await
+                .map(|status| status.require_confirmations(min_confirmations)),
+            None => Ok(AnchorStatus::Unsupported),
+        };
+        results.push((anchor, status));
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shallow_confirmations_are_demoted() {
+        let confirmed = AnchorStatus::Confirmed {
+            block_height: 840_000,
+            confirmations: 3,
+        };
+        assert_eq!(confirmed.clone().require_confirmations(3), confirmed);
+        assert!(confirmed.is_confirmed());
+
+        let shallow = confirmed.require_confirmations(6);
+        assert_eq!(
+            shallow,
+            AnchorStatus::Shallow {
+                block_height: 840_000,
+                confirmations: 3
+            }
+        );
+        assert!(!shallow.is_confirmed());
+        assert_eq!(shallow.as_str(), "shallow");
+        assert_eq!(shallow.depth(), Some((840_000, 3)));
+
+        assert_eq!(AnchorStatus::Pending.require_confirmations(6).depth(), None);
+    }
+}
@@ -0,0 +1,552 @@
+//! Bitcoin anchoring through OpenTimestamps calendars.
+//!
+//! A calendar collects digests, commits to all of them in one Bitcoin
+//! transaction every few hours, and hands back a *timestamp*: the operations
+//! (appends, prepends, hashes) leading from the digest to a commitment, ending
+//! in attestations. Right after submission the only attestation is "pending
+//! at this calendar". Once the transaction is mined the calendar serves the
+//! rest of the path, which ends in a Bitcoin attestation for the block whose
+//! Merkle root it produces.
+//!
+//! [`OpenTimestamps`] stores the pending timestamp in
+//! [`BlockchainAnchor::proof`]. Verification replays it, fetches the rest of
+//! the path from the calendar while it is pending, and compares the result
+//! with the block's Merkle root from an Esplora API, whose chain tip gives the
+//! number of confirmations. Only the configured
+//! calendars are contacted, whatever URLs a timestamp names.
+//!
+//! The encoding is the one of python-opentimestamps (`Timestamp.serialize`).
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use tracing::{debug, info, instrument, warn};
+
+use super::{AnchorProvider, AnchorStatus, AnchorVerifier};
+use crate::error::{Result, VeritasError};
+use crate::seal::{BlockchainAnchor, VeritasSeal};
+
+/// Chain identifier of OpenTimestamps anchors.
+pub const BITCOIN_CHAIN: &str = "bitcoin";
+
+/// Public calendars run by the OpenTimestamps project and Eternity Wall.
+const DEFAULT_CALENDARS: &[&str] = &[
+    "https://alice.btc.calendar.opentimestamps.org",
+    "https://bob.btc.calendar.opentimestamps.org",
+    "https://finney.calendar.eternitywall.com",
+];
+
+/// Default Esplora API for Bitcoin block headers.
+const DEFAULT_ESPLORA_URL: &str = "https://blockstream.info/api";
+
+/// Media type of serialized timestamps.
+const OTS_MEDIA_TYPE: &str = "application/vnd.opentimestamps.v1";
+
+/// Largest timestamp accepted from a calendar or a seal.
+const MAX_PROOF_LEN: usize = 64 * 1024;
+
+/// Longest message or operand while replaying a timestamp.
+const MAX_MESSAGE_LEN: usize = 4096;
+
+/// Deepest nesting of operations while replaying a timestamp.
+const MAX_DEPTH: usize = 256;
+
+/// Longest calendar URI in a pending attestation.
+const MAX_URI_LEN: usize = 1000;
+
+const ATTESTATION_TAG: u8 = 0x00;
+const FORK_TAG: u8 = 0xff;
+const BITCOIN_ATTESTATION: [u8; 8] = [0x05, 0x88, 0x96, 0x0d, 0x73, 0xd7, 0x19, 0x01];
+const PENDING_ATTESTATION: [u8; 8] = [0x83, 0xdf, 0xe3, 0x0d, 0x2e, 0xf9, 0x0c, 0x8e];
+
+/// Configuration for the OpenTimestamps provider.
+#[derive(Debug, Clone)]
+pub struct OpenTimestampsConfig {
+    /// Calendars to submit digests to and fetch completed timestamps from.
+    pub calendars: Vec<String>,
+    /// Esplora API base URL used to look up block Merkle roots.
+    pub esplora_url: String,
+    /// Request timeout.
+    pub timeout: Duration,
+}
+
+impl Default for OpenTimestampsConfig {
+    fn default() -> Self {
+        let calendars = std::env::var("OPENTIMESTAMPS_CALENDARS")
+            .map(|urls| {
+                urls.split(',')
+                    .map(str::trim)
+                    .filter(|url| !url.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_else(|_| {
+                DEFAULT_CALENDARS
+                    .iter()
+                    .map(|url| url.to_string())
+                    .collect()
+            });
+        Self {
+            calendars,
+            esplora_url: std::env::var("ESPLORA_URL")
+                .unwrap_or_else(|_| DEFAULT_ESPLORA_URL.to_string()),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Block fields returned by Esplora's `/block/:hash`.
+#[derive(Debug, Deserialize)]
+struct EsploraBlock {
+    merkle_root: String,
+}
+
+/// Anchors seals in Bitcoin through OpenTimestamps calendars.
+pub struct OpenTimestamps {
+    client: Client,
+    config: OpenTimestampsConfig,
+}
+
+impl OpenTimestamps {
+    /// Create a provider using the public calendars and Esplora API.
+    pub fn new() -> Result<Self> {
+        Self::with_config(OpenTimestampsConfig::default())
+    }
+
+    /// Create a provider with custom calendars and Esplora API.
+    #[instrument(level = "debug", skip_all, fields(calendars = config.calendars.len()))]
+    pub fn with_config(config: OpenTimestampsConfig) -> Result<Self> {
+        debug!("Creating OpenTimestamps client");
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .https_only(true)
+            .build()
+            .map_err(|e| VeritasError::AnchorError(format!("Failed to create HTTP client: {e}")))?;
+        Ok(Self { client, config })
+    }
+
+    /// Submit a digest to one calendar, returning its pending timestamp.
+    async fn submit(&self, calendar: &str, digest: &[u8]) -> Result<Vec<u8>> {
+        let response = self
+            .client
+            .post(format!("{}/digest", calendar.trim_end_matches('/')))
+            .header(reqwest::header::ACCEPT, OTS_MEDIA_TYPE)
+            .body(digest.to_vec())
+            .send()
+            .await?
+            .error_for_status()?;
+        let proof = read_proof(response).await?;
+
+        // Refuse a timestamp that does not start from our digest
+        replay(&proof, digest)?;
+        Ok(proof)
+    }
+
+    /// Fetch the completed timestamp of a pending commitment, if the
+    /// calendar's Bitcoin transaction is mined.
+    async fn fetch_upgrade(&self, calendar: &str, commitment: &[u8]) -> Result<Option<Vec<u8>>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/timestamp/{}",
+                calendar.trim_end_matches('/'),
+                hex::encode(commitment)
+            ))
+            .header(reqwest::header::ACCEPT, OTS_MEDIA_TYPE)
+            .send()
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(read_proof(response.error_for_status()?).await?))
+    }
+
+    /// Merkle root of the Bitcoin block at `height`, in internal byte order.
+    async fn block_merkle_root(&self, height: u64) -> Result<Vec<u8>> {
+        let base = self.config.esplora_url.trim_end_matches('/');
+        let hash = self
+            .client
+            .get(format!("{base}/block-height/{height}"))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let hash = hash.trim();
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(VeritasError::AnchorError(format!(
+                "invalid block hash for height {height}"
+            )));
+        }
+
+        let block: EsploraBlock = self
+            .client
+            .get(format!("{base}/block/{hash}"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let mut root = hex::decode(&block.merkle_root)
+            .map_err(|e| VeritasError::AnchorError(format!("invalid Merkle root: {e}")))?;
+        // Esplora displays hashes byte-reversed
+        root.reverse();
+        Ok(root)
+    }
+
+    /// Height of the best Bitcoin block.
+    async fn tip_height(&self) -> Result<u64> {
+        let base = self.config.esplora_url.trim_end_matches('/');
+        self.client
+            .get(format!("{base}/blocks/tip/height"))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?
+            .trim()
+            .parse()
+            .map_err(|e| VeritasError::AnchorError(format!("invalid tip height: {e}")))
+    }
+
+    fn is_configured_calendar(&self, uri: &str) -> bool {
+        self.config
+            .calendars
+            .iter()
+            .any(|calendar| calendar.trim_end_matches('/') == uri.trim_end_matches('/'))
+    }
+}
+
+#[async_trait]
+impl AnchorProvider for OpenTimestamps {
+    async fn anchor(&self, digest: &[u8; 32]) -> Result<BlockchainAnchor> {
+        for calendar in &self.config.calendars {
+            match self.submit(calendar, digest).await {
+                Ok(proof) => {
+                    info!(calendar = %calendar, "Digest submitted to OpenTimestamps");
+                    return Ok(BlockchainAnchor {
+                        chain: BITCOIN_CHAIN.to_string(),
+                        tx_id: calendar.clone(),
+                        block_height: 0,
+                        proof: Some(proof),
+                        batch: None,
+                    });
+                }
+                Err(e) => warn!(calendar = %calendar, error = %e, "Calendar submission failed"),
+            }
+        }
+        Err(VeritasError::AnchorError(
+            "no OpenTimestamps calendar accepted the digest".into(),
+        ))
+    }
+}
+
+#[async_trait]
+impl AnchorVerifier for OpenTimestamps {
+    fn chain(&self) -> &str {
+        BITCOIN_CHAIN
+    }
+
+    async fn verify(&self, seal: &VeritasSeal, anchor: &BlockchainAnchor) -> Result<AnchorStatus> {
+        let Some(proof) = &anchor.proof else {
+            return Ok(AnchorStatus::Mismatch("anchor carries no timestamp".into()));
+        };
+        let Some(digest) = anchor.committed_digest(seal) else {
+            return Ok(AnchorStatus::Mismatch(
+                "seal is not in the anchored batch".into(),
+            ));
+        };
+        let attestations = match replay(proof, &digest) {
+            Ok(attestations) => attestations,
+            Err(e) => return Ok(AnchorStatus::Mismatch(e.to_string())),
+        };
+
+        let mut confirmations = Vec::new();
+        let mut pending = Vec::new();
+        for (commitment, attestation) in attestations {
+            match attestation {
+                Attestation::Bitcoin { height } => confirmations.push((commitment, height)),
+                Attestation::Pending { uri } => pending.push((commitment, uri)),
+                Attestation::Unknown => {}
+            }
+        }
+
+        // Complete pending paths from the calendars we trust
+        if confirmations.is_empty() {
+            for (commitment, uri) in pending {
+                if !self.is_configured_calendar(&uri) {
+                    debug!(calendar = %uri, "Skipping calendar outside the configuration");
+                    continue;
+                }
+                let Some(upgrade) = self.fetch_upgrade(&uri, &commitment).await? else {
+                    continue;
+                };
+                match replay(&upgrade, &commitment) {
+                    Ok(attestations) => confirmations.extend(attestations.into_iter().filter_map(
+                        |(commitment, attestation)| match attestation {
+                            Attestation::Bitcoin { height } => Some((commitment, height)),
+                            _ => None,
+                        },
+                    )),
+                    Err(e) => warn!(calendar = %uri, error = %e, "Invalid timestamp upgrade"),
+                }
+            }
+        }
+        if confirmations.is_empty() {
+            return Ok(AnchorStatus::Pending);
+        }
+
+        for (commitment, height) in confirmations {
+            if commitment == self.block_merkle_root(height).await? {
+                let tip = self.tip_height().await?;
+                return Ok(AnchorStatus::Confirmed {
+                    block_height: height,
+                    confirmations: tip.saturating_sub(height) + 1,
+                });
+            }
+        }
+        Ok(AnchorStatus::Mismatch(
+            "timestamp does not match the Bitcoin block's Merkle root".into(),
+        ))
+    }
+}
+
+/// Read a timestamp body, refusing oversized ones.
+async fn read_proof(response: reqwest::Response) -> Result<Vec<u8>> {
+    let proof = response.bytes().await?;
+    if proof.len() > MAX_PROOF_LEN {
+        return Err(invalid("timestamp too large"));
+    }
+    Ok(proof.to_vec())
+}
+
+/// Attestation at the end of a timestamp path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Attestation {
+    /// The commitment is the Merkle root of the block at this height
+    Bitcoin { height: u64 },
+    /// The calendar at this URI will commit to it in a Bitcoin transaction
+    Pending { uri: String },
+    /// Attestation for another chain
+    Unknown,
+}
+
+/// Replay a serialized timestamp from `message`, returning each attestation
+/// with the commitment it attests.
+fn replay(proof: &[u8], message: &[u8]) -> Result<Vec<(Vec<u8>, Attestation)>> {
+    let mut reader = Reader { bytes: proof };
+    let mut attestations = Vec::new();
+    replay_timestamp(&mut reader, message, 0, &mut attestations)?;
+    if !reader.bytes.is_empty() {
+        return Err(invalid("trailing bytes after timestamp"));
+    }
+    Ok(attestations)
+}
+
+fn replay_timestamp(
+    reader: &mut Reader<'_>,
+    message: &[u8],
+    depth: usize,
+    attestations: &mut Vec<(Vec<u8>, Attestation)>,
+) -> Result<()> {
+    if depth > MAX_DEPTH {
+        return Err(invalid("timestamp nested too deeply"));
+    }
+    loop {
+        let tag = reader.byte()?;
+        if tag != FORK_TAG {
+            return replay_item(reader, tag, message, depth, attestations);
+        }
+        let tag = reader.byte()?;
+        replay_item(reader, tag, message, depth, attestations)?;
+    }
+}
+
+/// Replay one attestation, or one operation and the timestamp following it.
+fn replay_item(
+    reader: &mut Reader<'_>,
+    tag: u8,
+    message: &[u8],
+    depth: usize,
+    attestations: &mut Vec<(Vec<u8>, Attestation)>,
+) -> Result<()> {
+    if tag == ATTESTATION_TAG {
+        let attestation = read_attestation(reader)?;
+        attestations.push((message.to_vec(), attestation));
+        return Ok(());
+    }
+
+    let result = match tag {
+        0x08 => Sha256::digest(message).to_vec(),
+        0x67 => Keccak256::digest(message).to_vec(),
+        0xf0 => [message, reader.varbytes(MAX_MESSAGE_LEN)?].concat(),
+        0xf1 => [reader.varbytes(MAX_MESSAGE_LEN)?, message].concat(),
+        0xf2 => message.iter().rev().copied().collect(),
+        0xf3 => hex::encode(message).into_bytes(),
+        0x02 | 0x03 => return Err(invalid("SHA-1 and RIPEMD-160 operations are not supported")),
+        _ => return Err(invalid(format!("unknown operation 0x{tag:02x}"))),
+    };
+    if result.len() > MAX_MESSAGE_LEN {
+        return Err(invalid("operation result too long"));
+    }
+    replay_timestamp(reader, &result, depth + 1, attestations)
+}
+
+fn read_attestation(reader: &mut Reader<'_>) -> Result<Attestation> {
+    let tag = reader.take(8)?;
+    let mut payload = Reader {
+        bytes: reader.varbytes(MAX_MESSAGE_LEN)?,
+    };
+    let attestation = if tag == BITCOIN_ATTESTATION {
+        Attestation::Bitcoin {
+            height: payload.varuint()?,
+        }
+    } else if tag == PENDING_ATTESTATION {
+        let uri = std::str::from_utf8(payload.varbytes(MAX_URI_LEN)?)
+            .map_err(|_| invalid("calendar URI is not UTF-8"))?;
+        Attestation::Pending {
+            uri: uri.to_string(),
+        }
+    } else {
+        return Ok(Attestation::Unknown);
+    };
+    if !payload.bytes.is_empty() {
+        return Err(invalid("trailing bytes in attestation"));
+    }
+    Ok(attestation)
+}
+
+fn invalid(reason: impl std::fmt::Display) -> VeritasError {
+    VeritasError::AnchorError(format!("invalid OpenTimestamps proof: {reason}"))
+}
+
+/// Cursor over a serialized timestamp.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(invalid("unexpected end of timestamp"));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Unsigned LEB128 integer.
+    fn varuint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("integer too large"))
+    }
+
+    /// Length-prefixed bytes, non-empty and at most `max_len` long.
+    fn varbytes(&mut self, max_len: usize) -> Result<&'a [u8]> {
+        let len = self.varuint()?;
+        if len == 0 || len > max_len as u64 {
+            return Err(invalid(format!("field length {len} out of range")));
+        }
+        self.take(len as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = "https://alice.btc.calendar.opentimestamps.org";
+
+    fn varuint(mut value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn varbytes(bytes: &[u8]) -> Vec<u8> {
+        [varuint(bytes.len() as u64), bytes.to_vec()].concat()
+    }
+
+    /// digest -> append(nonce) -> sha256 -> {pending at CALENDAR, Bitcoin at height}
+    fn sample_proof(nonce: &[u8], height: u64) -> Vec<u8> {
+        let mut proof = vec![0xf0];
+        proof.extend(varbytes(nonce));
+        proof.push(0x08);
+        proof.extend([FORK_TAG, ATTESTATION_TAG]);
+        proof.extend(PENDING_ATTESTATION);
+        proof.extend(varbytes(&varbytes(CALENDAR.as_bytes())));
+        proof.push(ATTESTATION_TAG);
+        proof.extend(BITCOIN_ATTESTATION);
+        proof.extend(varbytes(&varuint(height)));
+        proof
+    }
+
+    #[test]
+    fn test_replay_reaches_each_attestation() {
+        let digest = [7u8; 32];
+        let nonce = [0xaa; 16];
+        let attestations = replay(&sample_proof(&nonce, 358_391), &digest).unwrap();
+
+        let commitment = Sha256::digest([&digest[..], &nonce[..]].concat()).to_vec();
+        assert_eq!(
+            attestations,
+            vec![
+                (
+                    commitment.clone(),
+                    Attestation::Pending {
+                        uri: CALENDAR.to_string()
+                    }
+                ),
+                (commitment, Attestation::Bitcoin { height: 358_391 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replay_rejects_malformed_proofs() {
+        let proof = sample_proof(&[1, 2, 3], 1);
+
+        assert!(replay(&proof[..proof.len() - 1], &[0u8; 32]).is_err());
+        assert!(replay(&[proof.clone(), vec![0]].concat(), &[0u8; 32]).is_err());
+        assert!(replay(&[0x02], &[0u8; 32]).is_err());
+        assert!(replay(&[0x42], &[0u8; 32]).is_err());
+        // Operand longer than the replay limit
+        let mut oversized = vec![0xf0];
+        oversized.extend(varbytes(&vec![0; MAX_MESSAGE_LEN + 1]));
+        assert!(replay(&oversized, &[0u8; 32]).is_err());
+        // Chain of operations deeper than the limit
+        assert!(replay(&vec![0x08; MAX_DEPTH + 2], &[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_varuint_roundtrip() {
+        for value in [0, 1, 127, 128, 300, 358_391, u64::MAX] {
+            let encoded = varuint(value);
+            let mut reader = Reader { bytes: &encoded };
+            assert_eq!(reader.varuint().unwrap(), value);
+            assert!(reader.bytes.is_empty());
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Solana anchor checks over JSON-RPC.
+//!
+//! A Solana anchor is a transaction whose memo is [`solana_memo`]; the memo
+//! program logs it, so the transaction's log messages show whether it carries
+//! the seal's digest. [`SolanaVerifier`] looks the transaction up with
+//! `getTransaction` and counts confirmations from the cluster's current slot.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tracing::{debug, instrument};
+
+use super::{AnchorStatus, AnchorVerifier};
+use crate::error::{Result, VeritasError};
+use crate::seal::{BlockchainAnchor, VeritasSeal};
+
+/// Chain identifiers of Solana anchors and the cluster's public RPC endpoint.
+pub const SOLANA_CLUSTERS: [(&str, &str); 3] = [
+    ("solana-mainnet", "https://api.mainnet-beta.solana.com"),
+    ("solana-devnet", "https://api.devnet.solana.com"),
+    ("solana-testnet", "https://api.testnet.solana.com"),
+];
+
+/// Longest base58 encoding of a 64-byte transaction signature.
+const MAX_SIGNATURE_LEN: usize = 88;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Memo published for an anchor digest (a seal's, or a batch root): its
+/// first 128 bits.
+pub fn solana_memo(digest: &[u8; 32]) -> String {
+    format!("VERITAS-Q:{}", hex::encode(&digest[..16]))
+}
+
+/// JSON-RPC response envelope.
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+/// Fields of a `getTransaction` result.
+#[derive(Debug, Deserialize)]
+struct RpcTransaction {
+    slot: u64,
+    meta: Option<RpcTransactionMeta>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransactionMeta {
+    err: Option<serde_json::Value>,
+    #[serde(default)]
+    log_messages: Option<Vec<String>>,
+}
+
+/// Checks Solana anchors of one cluster.
+pub struct SolanaVerifier {
+    client: Client,
+    chain: String,
+    rpc_url: String,
+}
+
+impl SolanaVerifier {
+    /// Create a verifier for the anchors on `chain`, queried through `rpc_url`.
+    #[instrument(level = "debug", skip(rpc_url))]
+    pub fn new(chain: &str, rpc_url: &str) -> Result<Self> {
+        debug!("Creating Solana RPC client");
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| VeritasError::AnchorError(format!("Failed to create HTTP client: {e}")))?;
+        Ok(Self {
+            client,
+            chain: chain.to_string(),
+            rpc_url: rpc_url.to_string(),
+        })
+    }
+
+    /// Verifiers for every cluster in [`SOLANA_CLUSTERS`], through their
+    /// public RPC endpoints.
+    pub fn public_clusters() -> Result<Vec<Self>> {
+        SOLANA_CLUSTERS
+            .iter()
+            .map(|(chain, rpc_url)| Self::new(chain, rpc_url))
+            .collect()
+    }
+
+    /// Call a JSON-RPC method; `None` when the node has no result for it.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<Option<T>> {
+        let response: RpcResponse<T> = self
+            .client
+            .post(&self.rpc_url)
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(error) = response.error {
+            return Err(VeritasError::AnchorError(format!(
+                "Solana {method}: {}",
+                error.message
+            )));
+        }
+        Ok(response.result)
+    }
+}
+
+#[async_trait]
+impl AnchorVerifier for SolanaVerifier {
+    fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    async fn verify(&self, seal: &VeritasSeal, anchor: &BlockchainAnchor) -> Result<AnchorStatus> {
+        let signature = anchor.tx_id.as_str();
+        if signature.is_empty()
+            || signature.len() > MAX_SIGNATURE_LEN
+            || !signature.chars().all(|c| BASE58_ALPHABET.contains(c))
+        {
+            return Ok(AnchorStatus::Mismatch(
+                "invalid transaction signature".into(),
+            ));
+        }
+
+        let config = serde_json::json!({
+            "encoding": "json",
+            "commitment": "confirmed",
+            "maxSupportedTransactionVersion": 0,
+        });
+        let Some(transaction) = self
+            .call::<RpcTransaction>("getTransaction", serde_json::json!([signature, config]))
+            .await?
+        else {
+            return Ok(AnchorStatus::Mismatch("transaction not found".into()));
+        };
+        let Some(meta) = transaction.meta else {
+            return Ok(AnchorStatus::Pending);
+        };
+        if meta.err.is_some() {
+            return Ok(AnchorStatus::Mismatch("transaction failed".into()));
+        }
+
+        let Some(digest) = anchor.committed_digest(seal) else {
+            return Ok(AnchorStatus::Mismatch(
+                "seal is not in the anchored batch".into(),
+            ));
+        };
+        let memo = solana_memo(&digest);
+        if !meta
+            .log_messages
+            .iter()
+            .flatten()
+            .any(|line| line.contains(&memo))
+        {
+            return Ok(AnchorStatus::Mismatch(
+                "transaction memo does not carry the seal digest".into(),
+            ));
+        }
+
+        let slot: u64 = self
+            .call("getSlot", serde_json::json!([{"commitment": "confirmed"}]))
+            .await?
+            .ok_or_else(|| VeritasError::AnchorError("Solana getSlot: no result".into()))?;
+        Ok(AnchorStatus::Confirmed {
+            block_height: transaction.slot,
+            confirmations: slot.saturating_sub(transaction.slot) + 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::{generate_keypair, MediaType, SealBuilder};
+
+    #[tokio::test]
+    async fn test_malformed_signatures_are_mismatches() {
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"anchored".to_vec(), MediaType::Generic)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        let memo = solana_memo(&seal.anchor_digest());
+        assert_eq!(memo.len(), "VERITAS-Q:".len() + 32);
+        assert!(memo.starts_with("VERITAS-Q:"));
+
+        // Rejected before any request reaches the node
+        let verifier = SolanaVerifier::new("solana-devnet", "http://127.0.0.1:9").unwrap();
+        let too_long = "1".repeat(MAX_SIGNATURE_LEN + 1);
+        for tx_id in ["", "0OIl", "a\"}, \"method\": \"x", too_long.as_str()] {
+            let anchor = BlockchainAnchor {
+                chain: "solana-devnet".into(),
+                tx_id: tx_id.into(),
+                block_height: 0,
+                proof: None,
+                batch: None,
+            };
+            assert_eq!(
+                verifier.verify(&seal, &anchor).await.unwrap(),
+                AnchorStatus::Mismatch("invalid transaction signature".into())
+            );
+        }
+    }
+}
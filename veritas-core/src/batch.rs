@@ -0,0 +1,115 @@
+//! Batch verification across CPU cores.
+//!
+//! ML-DSA-65 verification is CPU-bound and independent per seal, so a batch
+//! is split into one chunk per available core and verified on scoped threads.
+//! Where threads are unavailable (e.g. Wasm) the batch is verified in order on
+//! the calling thread.
+//!
+//! These functions block until the whole batch is verified; async callers
+//! should run them off the runtime threads (e.g. `tokio::task::spawn_blocking`).
+
+use std::thread;
+
+use crate::error::Result;
+use crate::seal::{ContentVerificationResult, VerificationResult, VeritasSeal};
+
+/// Verify the signatures of many seals in parallel.
+///
+/// Returns one result per seal, in order, as [`VeritasSeal::verify_detailed`]
+/// would.
+pub fn verify_seals_parallel(seals: &[VeritasSeal]) -> Vec<Result<VerificationResult>> {
+    parallel_map(seals, VeritasSeal::verify_detailed)
+}
+
+/// Verify many seals against their content in parallel.
+///
+/// Returns one result per `(seal, content)` pair, in order, as
+/// [`VeritasSeal::verify_content`] would.
+pub fn verify_contents_parallel(
+    items: &[(&VeritasSeal, &[u8])],
+) -> Vec<Result<ContentVerificationResult>> {
+    parallel_map(items, |(seal, content)| seal.verify_content(content))
+}
+
+/// Apply `f` to every item, one chunk of items per available core.
+fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(threads);
+    let f = &f;
+    thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, MediaType, SealBuilder};
+
+    async fn sample_seals(count: usize) -> Vec<VeritasSeal> {
+        let (public_key, secret_key) = generate_keypair();
+        let mut seals = Vec::with_capacity(count);
+        for i in 0..count {
+            seals.push(
+                SealBuilder::new(format!("content {i}").into_bytes(), MediaType::Image)
+                    .build_secure(&MockQrng::default(), &secret_key, &public_key)
+                    .await
+                    .expect("Failed to create seal"),
+            );
+        }
+        seals
+    }
+
+    #[tokio::test]
+    async fn test_parallel_results_match_sequential() {
+        let mut seals = sample_seals(9).await;
+        seals[4].capture_timestamp_utc += 1;
+        seals[7].signature[100] ^= 0xFF;
+
+        let parallel = verify_seals_parallel(&seals);
+        assert_eq!(parallel.len(), seals.len());
+        for (seal, result) in seals.iter().zip(parallel) {
+            assert_eq!(result.unwrap(), seal.verify_detailed().unwrap());
+        }
+        assert!(verify_seals_parallel(&[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_content_verification_keeps_order() {
+        let seals = sample_seals(4).await;
+        let contents: Vec<Vec<u8>> = (0..4)
+            .map(|i| format!("content {}", if i == 2 { 99 } else { i }).into_bytes())
+            .collect();
+        let items: Vec<(&VeritasSeal, &[u8])> = seals
+            .iter()
+            .zip(&contents)
+            .map(|(seal, content)| (seal, content.as_slice()))
+            .collect();
+
+        let results = verify_contents_parallel(&items);
+        for (i, result) in results.into_iter().enumerate() {
+            assert_eq!(
+                matches!(result.unwrap(), ContentVerificationResult::Authentic),
+                i != 2
+            );
+        }
+    }
+}
@@ -14,6 +14,9 @@ pub const VERITAS_ASSERTION_LABEL: &str = "veritas.quantum_seal";
 /// Version of the Veritas assertion schema
 pub const VERITAS_ASSERTION_VERSION: usize = 1;
 
+/// Label for the AI-detection assertion in C2PA manifests
+pub const VERITAS_AI_DETECTION_LABEL: &str = "veritas.ai_detection";
+
 /// Custom C2PA assertion containing the Veritas quantum seal data.
 ///
 /// This assertion is embedded within a C2PA manifest to provide:
@@ -72,6 +75,79 @@ pub struct BlockchainAnchorInfo {
     pub block_height: u64,
 }
 
+/// Custom C2PA assertion carrying the signed AI-detection verdicts of a seal.
+///
+/// Sits next to the quantum seal assertion so a single manifest shows both
+/// who sealed the media and whether detectors consider it AI-generated.
+/// Each verdict is signed over `seal_hash` (see [`VeritasSeal::seal_hash`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiDetectionC2paAssertion {
+    /// Schema version for forward compatibility
+    pub version: usize,
+
+    /// Hash of the seal the verdicts were signed over
+    #[serde(with = "hex_bytes")]
+    pub seal_hash: [u8; 32],
+
+    /// Detector verdicts, in the order they were attached
+    pub detections: Vec<AiDetectionInfo>,
+}
+
+/// One signed detector verdict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiDetectionInfo {
+    /// Detector or model name
+    pub detector: String,
+    /// Detector or model version
+    pub detector_version: String,
+    /// Likelihood of AI generation, from 0.0 to 1.0
+    pub score: f64,
+    /// Likelihood of AI generation in basis points, as signed
+    pub score_bps: u16,
+    /// `human`, `ai_generated` or `inconclusive`
+    pub verdict: String,
+    /// When the verdict was signed (Unix milliseconds)
+    pub timestamp: u64,
+    /// ML-DSA-65 public key of the asserting party
+    #[serde(with = "base64_bytes")]
+    pub signer_public_key: Vec<u8>,
+    /// ML-DSA-65 signature over the verdict
+    #[serde(with = "base64_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl AiDetectionC2paAssertion {
+    /// Build the assertion for a seal, or `None` if it carries no verdicts.
+    pub fn from_seal(seal: &VeritasSeal) -> Option<Self> {
+        if seal.ai_detections.is_empty() {
+            return None;
+        }
+        Some(Self {
+            version: VERITAS_ASSERTION_VERSION,
+            seal_hash: seal.seal_hash(),
+            detections: seal
+                .ai_detections
+                .iter()
+                .map(|a| AiDetectionInfo {
+                    detector: a.verdict.detector.clone(),
+                    detector_version: a.verdict.detector_version.clone(),
+                    score: a.verdict.score(),
+                    score_bps: a.verdict.score_bps,
+                    verdict: a.verdict.verdict.to_string(),
+                    timestamp: a.timestamp,
+                    signer_public_key: a.signer_public_key.clone(),
+                    signature: a.signature.clone(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Get the assertion label for C2PA
+    pub fn label() -> &'static str {
+        VERITAS_AI_DETECTION_LABEL
+    }
+}
+
 impl From<&VeritasSeal> for QuantumSealAssertion {
     fn from(seal: &VeritasSeal) -> Self {
         Self {
@@ -104,6 +180,8 @@ impl QuantumSealAssertion {
             chain: format!("{}-{}", a.chain, a.network),
             tx_id: a.transaction_id.clone(),
             block_height: a.block_height,
+            proof: None,
+            batch: None,
         })
     }
 
@@ -224,4 +302,30 @@ mod tests {
         assert_eq!(parsed.qrng_source, assertion.qrng_source);
         assert_eq!(parsed.ml_dsa_signature, assertion.ml_dsa_signature);
     }
+
+    #[test]
+    fn test_ai_detection_assertion_serialization() {
+        let assertion = AiDetectionC2paAssertion {
+            version: 1,
+            seal_hash: [0x11; 32],
+            detections: vec![AiDetectionInfo {
+                detector: "synthid".to_string(),
+                detector_version: "2".to_string(),
+                score: 0.97,
+                score_bps: 9700,
+                verdict: "ai_generated".to_string(),
+                timestamp: 1704067200000,
+                signer_public_key: vec![1, 2, 3],
+                signature: vec![4, 5, 6],
+            }],
+        };
+
+        let json = serde_json::to_value(&assertion).expect("serialize");
+        assert_eq!(json["seal_hash"], "11".repeat(32));
+        assert_eq!(json["detections"][0]["signature"], "BAUG");
+
+        let parsed: AiDetectionC2paAssertion = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(parsed.detections[0].score_bps, 9700);
+        assert_eq!(parsed.detections[0].signer_public_key, vec![1, 2, 3]);
+    }
 }
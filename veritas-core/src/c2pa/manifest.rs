@@ -8,7 +8,10 @@ use std::path::Path;
 
 use c2pa::{Builder, CallbackSigner, Reader, SigningAlg};
 
-use super::assertion::{QuantumSealAssertion, VERITAS_ASSERTION_LABEL};
+use super::assertion::{
+    AiDetectionC2paAssertion, QuantumSealAssertion, VERITAS_AI_DETECTION_LABEL,
+    VERITAS_ASSERTION_LABEL,
+};
 use super::error::{C2paError, C2paResult};
 use super::signer::VeritasSigner;
 use crate::seal::VeritasSeal;
@@ -36,6 +39,7 @@ fn certs_to_pem_chain(der_certs: &[Vec<u8>]) -> Vec<u8> {
 /// This builder creates C2PA-compliant manifests that include:
 /// - Standard C2PA claims and assertions
 /// - A custom `veritas.quantum_seal` assertion containing the post-quantum signature
+/// - A custom `veritas.ai_detection` assertion when the seal carries AI-detection verdicts
 pub struct VeritasManifestBuilder {
     seal: VeritasSeal,
     claim_generator: String,
@@ -67,7 +71,7 @@ impl VeritasManifestBuilder {
         let quantum_assertion = QuantumSealAssertion::from(&self.seal);
 
         // Build the manifest definition as JSON
-        let manifest_def = serde_json::json!({
+        let mut manifest_def = serde_json::json!({
             "claim_generator": self.claim_generator,
             "claim_generator_info": [{
                 "name": "Veritas Q",
@@ -99,6 +103,15 @@ impl VeritasManifestBuilder {
             ]
         });
 
+        if let Some(ai_detection) = AiDetectionC2paAssertion::from_seal(&self.seal) {
+            if let Some(assertions) = manifest_def["assertions"].as_array_mut() {
+                assertions.push(serde_json::json!({
+                    "label": VERITAS_AI_DETECTION_LABEL,
+                    "data": ai_detection
+                }));
+            }
+        }
+
         serde_json::to_string_pretty(&manifest_def)
             .map_err(|e| C2paError::Serialization(e.to_string()))
     }
@@ -208,9 +221,16 @@ pub fn extract_quantum_seal_from_stream<R: Read + Seek + Send>(
         .ok_or(C2paError::NoVeritasSealFound)?;
 
     // Find the Veritas quantum seal assertion by examining the JSON representation
-    let json = reader.json();
+    quantum_seal_from_manifest_json(&reader.json())
+}
+
+/// Find the Veritas quantum seal assertion in a C2PA manifest store, as
+/// rendered to JSON by the C2PA reader.
+///
+/// The JSON comes from the media file, so it is treated as untrusted.
+pub fn quantum_seal_from_manifest_json(json: &str) -> C2paResult<QuantumSealAssertion> {
     let json_value: serde_json::Value =
-        serde_json::from_str(&json).map_err(|e| C2paError::Serialization(e.to_string()))?;
+        serde_json::from_str(json).map_err(|e| C2paError::Serialization(e.to_string()))?;
 
     // Navigate to find the veritas.quantum_seal assertion
     if let Some(manifests) = json_value.get("manifests").and_then(|m| m.as_object()) {
@@ -235,6 +255,35 @@ pub fn extract_quantum_seal_from_stream<R: Read + Seek + Send>(
     Err(C2paError::NoVeritasSealFound)
 }
 
+/// Find the AI-detection assertion in a C2PA manifest store rendered to JSON,
+/// or `None` if the manifest has none.
+///
+/// The verdicts are only trustworthy once verified against the seal.
+pub fn ai_detection_from_manifest_json(json: &str) -> C2paResult<Option<AiDetectionC2paAssertion>> {
+    let json_value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| C2paError::Serialization(e.to_string()))?;
+
+    let Some(manifests) = json_value.get("manifests").and_then(|m| m.as_object()) else {
+        return Ok(None);
+    };
+    for manifest_obj in manifests.values() {
+        let Some(assertions) = manifest_obj.get("assertions").and_then(|a| a.as_array()) else {
+            continue;
+        };
+        for assertion in assertions {
+            if assertion.get("label").and_then(|l| l.as_str()) != Some(VERITAS_AI_DETECTION_LABEL) {
+                continue;
+            }
+            if let Some(data) = assertion.get("data") {
+                return serde_json::from_value(data.clone())
+                    .map(Some)
+                    .map_err(|e| C2paError::Serialization(e.to_string()));
+            }
+        }
+    }
+    Ok(None)
+}
+
 /// Verify a C2PA manifest and return validation status.
 pub fn verify_c2pa_manifest(path: &Path) -> C2paResult<C2paValidationResult> {
     let format = get_format_from_path(path)?;
@@ -357,4 +406,84 @@ mod tests {
         );
         assert!(get_format_from_path(Path::new("test.xyz")).is_err());
     }
+
+    #[test]
+    fn test_quantum_seal_from_manifest_json() {
+        let store = |data: serde_json::Value| {
+            serde_json::json!({
+                "active_manifest": "urn:uuid:1",
+                "manifests": {
+                    "urn:uuid:1": {
+                        "assertions": [
+                            { "label": "c2pa.actions", "data": {} },
+                            { "label": VERITAS_ASSERTION_LABEL, "data": data }
+                        ]
+                    }
+                }
+            })
+            .to_string()
+        };
+        let data = serde_json::json!({
+            "version": 1,
+            "qrng_entropy": "01".repeat(32),
+            "qrng_source": "MOCK",
+            "entropy_timestamp": 1,
+            "capture_timestamp": 2,
+            "ml_dsa_signature": "AAEC",
+            "ml_dsa_public_key": "AwQF",
+            "content_hash": "ab".repeat(32),
+        });
+
+        let assertion = quantum_seal_from_manifest_json(&store(data.clone())).unwrap();
+        assert_eq!(assertion.qrng_entropy, [1; 32]);
+        assert_eq!(assertion.ml_dsa_signature, vec![0, 1, 2]);
+
+        let mut short_hash = data;
+        short_hash["content_hash"] = "abcd".into();
+        assert!(matches!(
+            quantum_seal_from_manifest_json(&store(short_hash)),
+            Err(C2paError::Serialization(_))
+        ));
+        assert!(matches!(
+            quantum_seal_from_manifest_json(r#"{"manifests":{}}"#),
+            Err(C2paError::NoVeritasSealFound)
+        ));
+        assert!(quantum_seal_from_manifest_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_ai_detection_from_manifest_json() {
+        let data = serde_json::json!({
+            "version": 1,
+            "seal_hash": "22".repeat(32),
+            "detections": [{
+                "detector": "synthid",
+                "detector_version": "2",
+                "score": 0.12,
+                "score_bps": 1200,
+                "verdict": "human",
+                "timestamp": 3,
+                "signer_public_key": "AAEC",
+                "signature": "AwQF"
+            }]
+        });
+        let store = serde_json::json!({
+            "manifests": {
+                "urn:uuid:1": {
+                    "assertions": [
+                        { "label": VERITAS_ASSERTION_LABEL, "data": {} },
+                        { "label": VERITAS_AI_DETECTION_LABEL, "data": data }
+                    ]
+                }
+            }
+        })
+        .to_string();
+
+        let assertion = ai_detection_from_manifest_json(&store).unwrap().unwrap();
+        assert_eq!(assertion.seal_hash, [0x22; 32]);
+        assert_eq!(assertion.detections[0].verdict, "human");
+        assert!(ai_detection_from_manifest_json(r#"{"manifests":{}}"#)
+            .unwrap()
+            .is_none());
+    }
 }
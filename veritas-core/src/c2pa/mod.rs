@@ -31,10 +31,13 @@ mod error;
 mod manifest;
 mod signer;
 
-pub use assertion::{BlockchainAnchorInfo, QuantumSealAssertion};
+pub use assertion::{
+    AiDetectionC2paAssertion, AiDetectionInfo, BlockchainAnchorInfo, QuantumSealAssertion,
+};
 pub use error::{C2paError, C2paResult};
 pub use manifest::{
-    extract_quantum_seal, extract_quantum_seal_from_stream, verify_c2pa_manifest,
-    C2paValidationResult, VeritasManifestBuilder,
+    ai_detection_from_manifest_json, extract_quantum_seal, extract_quantum_seal_from_stream,
+    quantum_seal_from_manifest_json, verify_c2pa_manifest, C2paValidationResult,
+    VeritasManifestBuilder,
 };
-pub use signer::VeritasSigner;
+pub use signer::{generate_test_certificate, VeritasSigner, TEST_CERTIFICATE_ORG};
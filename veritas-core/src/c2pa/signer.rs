@@ -7,6 +7,7 @@ use openssl::hash::MessageDigest;
 use openssl::pkey::{PKey, Private};
 use openssl::sign::Signer;
 use openssl::x509::X509;
+use zeroize::Zeroizing;
 
 use super::error::{C2paError, C2paResult};
 
@@ -61,7 +62,7 @@ impl VeritasSigner {
         let cert_path = std::env::var("C2PA_SIGNING_CERT")
             .map_err(|_| C2paError::MissingEnvVar("C2PA_SIGNING_CERT"))?;
 
-        let key_pem = std::fs::read(&key_path)?;
+        let key_pem = Zeroizing::new(std::fs::read(&key_path)?);
         let cert_pem = std::fs::read(&cert_path)?;
 
         Self::from_pem(&key_pem, &cert_pem)
@@ -77,12 +78,23 @@ impl VeritasSigner {
         key_path: impl AsRef<std::path::Path>,
         cert_path: impl AsRef<std::path::Path>,
     ) -> C2paResult<Self> {
-        let key_pem = std::fs::read(key_path)?;
+        let key_pem = Zeroizing::new(std::fs::read(key_path)?);
         let cert_pem = std::fs::read(cert_path)?;
 
         Self::from_pem(&key_pem, &cert_pem)
     }
 
+    /// Create a signer with an ephemeral key and test certificate chain
+    /// (see [`generate_test_certificate`]).
+    ///
+    /// **WARNING**: For development only! Manifests signed this way are not
+    /// trusted by C2PA validators.
+    pub fn ephemeral_test() -> C2paResult<Self> {
+        let (key_pem, cert_pem) = generate_test_certificate()?;
+        let key_pem = Zeroizing::new(key_pem);
+        Self::from_pem(&key_pem, &cert_pem)
+    }
+
     /// Sign data using ECDSA P-256 with SHA-256.
     pub fn sign(&self, data: &[u8]) -> c2pa::Result<Vec<u8>> {
         let mut signer = Signer::new(MessageDigest::sha256(), &self.private_key)
@@ -111,70 +123,111 @@ impl VeritasSigner {
     }
 }
 
-/// Generate a self-signed certificate for testing purposes.
+/// Organization and common name prefix of the certificates made by
+/// [`generate_test_certificate`], so they can be told apart at a glance.
+pub const TEST_CERTIFICATE_ORG: &str = "Veritas Q TEST ONLY";
+
+/// Generate an ephemeral ES256 key and a test certificate chain for it.
 ///
-/// **WARNING**: Do not use in production! Self-signed certificates
-/// will not be trusted by C2PA validators.
-#[cfg(test)]
+/// The chain holds a C2PA signing certificate for the key, issued by a
+/// self-signed root; both are new on every call. Returns the PEM-encoded
+/// private key and chain (leaf certificate first).
+///
+/// **WARNING**: Do not use in production! Test certificates will not be
+/// trusted by C2PA validators.
 pub fn generate_test_certificate() -> C2paResult<(Vec<u8>, Vec<u8>)> {
-    use openssl::asn1::Asn1Time;
-    use openssl::bn::BigNum;
+    use openssl::x509::extension::{
+        AuthorityKeyIdentifier, BasicConstraints, ExtendedKeyUsage, KeyUsage, SubjectKeyIdentifier,
+    };
+
+    // Root CA, self-signed
+    let root_key = generate_es256_key()?;
+    let root_name = test_certificate_name("Root CA")?;
+    let mut root_builder = test_certificate_builder(&root_name, &root_name, &root_key)?;
+    root_builder.append_extension(BasicConstraints::new().critical().ca().build()?)?;
+    root_builder.append_extension(
+        KeyUsage::new()
+            .critical()
+            .key_cert_sign()
+            .crl_sign()
+            .build()?,
+    )?;
+    let subject_key_id =
+        SubjectKeyIdentifier::new().build(&root_builder.x509v3_context(None, None))?;
+    root_builder.append_extension(subject_key_id)?;
+    root_builder.sign(&root_key, MessageDigest::sha256())?;
+    let root = root_builder.build();
+
+    // Signing certificate, with the profile C2PA requires of claim signers
+    let private_key = generate_es256_key()?;
+    let leaf_name = test_certificate_name("Signer")?;
+    let mut leaf_builder = test_certificate_builder(&leaf_name, &root_name, &private_key)?;
+    leaf_builder.append_extension(BasicConstraints::new().critical().build()?)?;
+    leaf_builder.append_extension(KeyUsage::new().critical().digital_signature().build()?)?;
+    leaf_builder.append_extension(ExtendedKeyUsage::new().email_protection().build()?)?;
+    let subject_key_id =
+        SubjectKeyIdentifier::new().build(&leaf_builder.x509v3_context(Some(&root), None))?;
+    leaf_builder.append_extension(subject_key_id)?;
+    let authority_key_id = AuthorityKeyIdentifier::new()
+        .keyid(true)
+        .build(&leaf_builder.x509v3_context(Some(&root), None))?;
+    leaf_builder.append_extension(authority_key_id)?;
+    leaf_builder.sign(&root_key, MessageDigest::sha256())?;
+    let leaf = leaf_builder.build();
+
+    let key_pem = private_key.private_key_to_pem_pkcs8()?;
+    let mut chain_pem = leaf.to_pem()?;
+    chain_pem.extend(root.to_pem()?);
+
+    Ok((key_pem, chain_pem))
+}
+
+/// Generate an EC P-256 key pair.
+fn generate_es256_key() -> C2paResult<PKey<Private>> {
     use openssl::ec::{EcGroup, EcKey};
     use openssl::nid::Nid;
-    use openssl::x509::extension::{BasicConstraints, KeyUsage};
-    use openssl::x509::{X509Builder, X509NameBuilder};
 
-    // Generate EC P-256 key pair
     let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
-    let ec_key = EcKey::generate(&group)?;
-    let private_key = PKey::from_ec_key(ec_key)?;
-
-    // Build certificate
-    let mut x509_builder = X509Builder::new()?;
-    x509_builder.set_version(2)?;
-
-    // Serial number
-    let serial = BigNum::from_u32(1)?;
-    let serial = serial.to_asn1_integer()?;
-    x509_builder.set_serial_number(&serial)?;
+    Ok(PKey::from_ec_key(EcKey::generate(&group)?)?)
+}
 
-    // Subject/Issuer name
-    let mut name_builder = X509NameBuilder::new()?;
+/// Distinguished name of a test certificate.
+fn test_certificate_name(role: &str) -> C2paResult<openssl::x509::X509Name> {
+    let mut name_builder = openssl::x509::X509NameBuilder::new()?;
     name_builder.append_entry_by_text("C", "US")?;
-    name_builder.append_entry_by_text("O", "Veritas Q Test")?;
-    name_builder.append_entry_by_text("CN", "Veritas Q Test CA")?;
-    let name = name_builder.build();
-    x509_builder.set_subject_name(&name)?;
-    x509_builder.set_issuer_name(&name)?;
+    name_builder.append_entry_by_text("O", TEST_CERTIFICATE_ORG)?;
+    name_builder.append_entry_by_text("CN", &format!("{TEST_CERTIFICATE_ORG} {role}"))?;
+    Ok(name_builder.build())
+}
 
-    // Validity
-    let not_before = Asn1Time::days_from_now(0)?;
-    let not_after = Asn1Time::days_from_now(365)?;
-    x509_builder.set_not_before(&not_before)?;
-    x509_builder.set_not_after(&not_after)?;
+/// Certificate builder with a random serial, one year of validity and the
+/// given names and public key; extensions and signature are left to the
+/// caller.
+fn test_certificate_builder(
+    subject: &openssl::x509::X509NameRef,
+    issuer: &openssl::x509::X509NameRef,
+    key: &PKey<Private>,
+) -> C2paResult<openssl::x509::X509Builder> {
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::{BigNum, MsbOption};
 
-    // Public key
-    x509_builder.set_pubkey(&private_key)?;
+    let mut x509_builder = openssl::x509::X509Builder::new()?;
+    x509_builder.set_version(2)?;
 
-    // Extensions
-    x509_builder.append_extension(BasicConstraints::new().critical().ca().build()?)?;
-    x509_builder.append_extension(
-        KeyUsage::new()
-            .critical()
-            .key_cert_sign()
-            .digital_signature()
-            .build()?,
-    )?;
+    // Serial number
+    let mut serial = BigNum::new()?;
+    serial.rand(127, MsbOption::MAYBE_ZERO, false)?;
+    x509_builder.set_serial_number(&serial.to_asn1_integer()?)?;
 
-    // Sign with own key (self-signed)
-    x509_builder.sign(&private_key, MessageDigest::sha256())?;
-    let cert = x509_builder.build();
+    x509_builder.set_subject_name(subject)?;
+    x509_builder.set_issuer_name(issuer)?;
 
-    // Convert to PEM
-    let key_pem = private_key.private_key_to_pem_pkcs8()?;
-    let cert_pem = cert.to_pem()?;
+    // Validity
+    x509_builder.set_not_before(&Asn1Time::days_from_now(0)?)?;
+    x509_builder.set_not_after(&Asn1Time::days_from_now(365)?)?;
 
-    Ok((key_pem, cert_pem))
+    x509_builder.set_pubkey(key)?;
+    Ok(x509_builder)
 }
 
 #[cfg(test)]
@@ -200,8 +253,35 @@ mod tests {
         let signer = VeritasSigner::from_pem(&key_pem, &cert_pem).expect("create signer");
 
         let certs = signer.certs().expect("get certs");
-        assert_eq!(certs.len(), 1);
-        assert!(!certs[0].is_empty());
+        assert_eq!(certs.len(), 2);
+        assert!(certs.iter().all(|cert| !cert.is_empty()));
+    }
+
+    #[test]
+    fn test_ephemeral_test_chain() {
+        let signer = VeritasSigner::ephemeral_test().expect("create signer");
+        let [leaf, root] = signer.certificate_chain.as_slice() else {
+            panic!("expected leaf and root certificates");
+        };
+
+        // The leaf is issued by the root and both say they are for testing
+        assert!(leaf.verify(&root.public_key().unwrap()).unwrap());
+        assert!(root.verify(&root.public_key().unwrap()).unwrap());
+        for cert in [leaf, root] {
+            let org = cert
+                .subject_name()
+                .entries_by_nid(openssl::nid::Nid::ORGANIZATIONNAME)
+                .next()
+                .unwrap();
+            assert_eq!(
+                org.data().as_utf8().unwrap().to_string(),
+                TEST_CERTIFICATE_ORG
+            );
+        }
+
+        // Every call uses a new key
+        let other = VeritasSigner::ephemeral_test().expect("create signer");
+        assert_ne!(signer.certs().unwrap(), other.certs().unwrap());
     }
 
     #[test]
@@ -0,0 +1,143 @@
+//! Canonical JSON serialization (RFC 8785, JSON Canonicalization Scheme).
+//!
+//! CBOR is the primary seal format, but JSON exports are consumed by tools in
+//! other languages and sometimes signed themselves. Those use cases need the
+//! same logical value to always produce the same bytes:
+//!
+//! - object members sorted by key (UTF-16 code unit order, as JCS requires)
+//! - no insignificant whitespace
+//! - minimal string escaping
+//!
+//! Seals only contain integers, so the JCS number rules reduce to plain
+//! decimal integers; floats are written in their shortest round-trip form.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{Result, VeritasError};
+
+/// Serialize a value to canonical JSON bytes.
+pub fn to_canonical_json<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let value =
+        serde_json::to_value(value).map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+    let mut out = Vec::with_capacity(1024);
+    write_value(&value, &mut out);
+    Ok(out)
+}
+
+fn write_value(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.extend_from_slice(b"null"),
+        Value::Bool(true) => out.extend_from_slice(b"true"),
+        Value::Bool(false) => out.extend_from_slice(b"false"),
+        Value::Number(n) => write_number(n, out),
+        Value::String(s) => write_string(s, out),
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_value(item, out);
+            }
+            out.push(b']');
+        }
+        Value::Object(map) => {
+            // Sort explicitly: serde_json's map order depends on crate features.
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push(b'{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_string(key, out);
+                out.push(b':');
+                write_value(item, out);
+            }
+            out.push(b'}');
+        }
+    }
+}
+
+fn write_number(n: &serde_json::Number, out: &mut Vec<u8>) {
+    if let Some(i) = n.as_i64() {
+        out.extend_from_slice(i.to_string().as_bytes());
+    } else if let Some(u) = n.as_u64() {
+        out.extend_from_slice(u.to_string().as_bytes());
+    } else if let Some(f) = n.as_f64() {
+        if f.fract() == 0.0 && f.abs() < 1e21 {
+            out.extend_from_slice(format!("{}", f as i128).as_bytes());
+        } else {
+            out.extend_from_slice(format!("{f}").as_bytes());
+        }
+    }
+}
+
+fn write_string(s: &str, out: &mut Vec<u8>) {
+    out.push(b'"');
+    for c in s.chars() {
+        match c {
+            '"' => out.extend_from_slice(b"\\\""),
+            '\\' => out.extend_from_slice(b"\\\\"),
+            '\u{08}' => out.extend_from_slice(b"\\b"),
+            '\u{0C}' => out.extend_from_slice(b"\\f"),
+            '\n' => out.extend_from_slice(b"\\n"),
+            '\r' => out.extend_from_slice(b"\\r"),
+            '\t' => out.extend_from_slice(b"\\t"),
+            c if (c as u32) < 0x20 => {
+                out.extend_from_slice(format!("\\u{:04x}", c as u32).as_bytes());
+            }
+            c => {
+                let mut buf = [0u8; 4];
+                out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+    }
+    out.push(b'"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn canonical(value: &Value) -> String {
+        String::from_utf8(to_canonical_json(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_keys_sorted_and_compact() {
+        let value = json!({ "b": 1, "a": [true, null], "c": { "z": "x", "y": -2 } });
+        assert_eq!(
+            canonical(&value),
+            r#"{"a":[true,null],"b":1,"c":{"y":-2,"z":"x"}}"#
+        );
+    }
+
+    #[test]
+    fn test_string_escaping() {
+        let value = json!("quote\" backslash\\ tab\t ctrl\u{1f} é");
+        assert_eq!(
+            canonical(&value),
+            "\"quote\\\" backslash\\\\ tab\\t ctrl\\u001f é\""
+        );
+    }
+
+    #[test]
+    fn test_integral_float_written_as_integer() {
+        assert_eq!(canonical(&json!(1.0)), "1");
+        assert_eq!(canonical(&json!(1.5)), "1.5");
+    }
+
+    #[test]
+    fn test_same_value_same_bytes() {
+        let a = json!({ "x": 1, "y": 2 });
+        let b: Value = serde_json::from_str(r#"{ "y": 2,   "x": 1 }"#).unwrap();
+        assert_eq!(
+            to_canonical_json(&a).unwrap(),
+            to_canonical_json(&b).unwrap()
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! High-level client for applications.
+//!
+//! [`Veritas`] bundles an entropy source and a signing keypair so sealing
+//! and verifying take one call each, without wiring a QRNG, keys and a
+//! [`SealBuilder`] by hand:
+//!
+//! ```no_run
+//! use veritas_core::client::Veritas;
+//!
+//! # async fn example() -> veritas_core::client::ClientResult<()> {
+//! // Best available QRNG, fresh signing keypair
+//! let veritas = Veritas::new()?;
+//!
+//! let seal = veritas.seal_file("photo.jpg").await?;
+//! std::fs::write("photo.jpg.veritas", seal.to_cbor()?)?;
+//!
+//! assert!(veritas.verify_file("photo.jpg", &seal)?.is_authentic());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The QRNG is picked as by [`QrngProviderConfig::Auto`]. Keys are ephemeral
+//! unless loaded with [`Veritas::with_keypair`] or, with the `keyfile`
+//! feature, kept in a keypair file by [`Veritas::with_keystore`].
+
+use std::path::Path;
+use std::sync::Arc;
+
+use pqcrypto_mldsa::mldsa65;
+use thiserror::Error;
+
+use crate::error::VeritasError;
+use crate::qrng::{
+    QrngConditioning, QrngProviderConfig, QrngProviderFactory, QuantumEntropySource,
+};
+use crate::seal::{
+    generate_keypair, ContentVerificationResult, MediaType, SealBuilder, VeritasSeal,
+};
+use crate::secret::ZeroizingSecretKey;
+
+#[cfg(feature = "keyfile")]
+use crate::keyfile::{KeypairFile, KeypairFileError};
+
+/// Result type for [`Veritas`] operations
+pub type ClientResult<T> = Result<T, ClientError>;
+
+/// Errors from [`Veritas`] operations
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// IO error
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Sealing or verification error
+    #[error(transparent)]
+    Veritas(#[from] VeritasError),
+
+    /// The keystore could not be read or written
+    #[cfg(feature = "keyfile")]
+    #[error("Keystore error: {0}")]
+    Keystore(#[from] KeypairFileError),
+}
+
+/// Seals and verifies content with one entropy source and signing keypair.
+pub struct Veritas {
+    qrng: Arc<dyn QuantumEntropySource>,
+    public_key: mldsa65::PublicKey,
+    secret_key: ZeroizingSecretKey,
+    conditioning: Option<QrngConditioning>,
+}
+
+impl Veritas {
+    /// Create a client with the best available QRNG and a new, ephemeral
+    /// keypair.
+    pub fn new() -> ClientResult<Self> {
+        let (public_key, secret_key) = generate_keypair();
+        Self::with_keypair(public_key, secret_key)
+    }
+
+    /// Create a client with the best available QRNG, signing with the given
+    /// keypair.
+    pub fn with_keypair(
+        public_key: mldsa65::PublicKey,
+        secret_key: ZeroizingSecretKey,
+    ) -> ClientResult<Self> {
+        Ok(Self {
+            qrng: QrngProviderFactory::create(QrngProviderConfig::Auto)?,
+            public_key,
+            secret_key,
+            conditioning: None,
+        })
+    }
+
+    /// Create a client with the best available QRNG, signing with the
+    /// keypair file at `path`; a new keypair is saved there if the file
+    /// does not exist.
+    ///
+    /// The secret key is encrypted with `passphrase` when one is given.
+    #[cfg(feature = "keyfile")]
+    pub fn with_keystore(path: impl AsRef<Path>, passphrase: Option<&str>) -> ClientResult<Self> {
+        let path = path.as_ref();
+        let keypair = if path.exists() {
+            KeypairFile::load(path, passphrase)?
+        } else {
+            let (public_key, secret_key) = generate_keypair();
+            let keypair = KeypairFile::new(public_key, secret_key)?;
+            keypair.save(path, passphrase)?;
+            tracing::info!(path = %path.display(), "Created keystore");
+            keypair
+        };
+        Self::with_keypair(keypair.public_key, keypair.secret_key)
+    }
+
+    /// Use `qrng` as the entropy source instead of the auto-selected one.
+    pub fn with_qrng(mut self, qrng: Arc<dyn QuantumEntropySource>) -> Self {
+        self.qrng = qrng;
+        self
+    }
+
+    /// Mix QRNG output with local OS randomness before use.
+    pub fn with_conditioning(mut self, policy: QrngConditioning) -> Self {
+        self.conditioning = Some(policy);
+        self
+    }
+
+    /// Public key that seals are signed with.
+    pub fn public_key(&self) -> &mldsa65::PublicKey {
+        &self.public_key
+    }
+
+    /// Seal `content` as `media_type`.
+    pub async fn seal_bytes(
+        &self,
+        content: Vec<u8>,
+        media_type: MediaType,
+    ) -> ClientResult<VeritasSeal> {
+        let mut builder = SealBuilder::new(content, media_type);
+        if let Some(policy) = self.conditioning {
+            builder = builder.with_conditioning(policy);
+        }
+        Ok(builder
+            .build_secure(self.qrng.as_ref(), &self.secret_key, &self.public_key)
+            .await?)
+    }
+
+    /// Seal the file at `path`, with the media type inferred from its
+    /// extension.
+    pub async fn seal_file(&self, path: impl AsRef<Path>) -> ClientResult<VeritasSeal> {
+        let path = path.as_ref();
+        let content = std::fs::read(path)?;
+        let media_type = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map_or(MediaType::Generic, MediaType::from_extension);
+        self.seal_bytes(content, media_type).await
+    }
+
+    /// Verify the seal's signature and that `content` is what was sealed.
+    ///
+    /// Any valid seal is accepted, whoever signed it; compare
+    /// [`VeritasSeal::public_key`] to trust a given signer only.
+    pub fn verify_bytes(
+        &self,
+        content: &[u8],
+        seal: &VeritasSeal,
+    ) -> ClientResult<ContentVerificationResult> {
+        Ok(seal.verify_content(content)?)
+    }
+
+    /// Verify the seal against the file at `path`.
+    pub fn verify_file(
+        &self,
+        path: impl AsRef<Path>,
+        seal: &VeritasSeal,
+    ) -> ClientResult<ContentVerificationResult> {
+        let content = std::fs::read(path)?;
+        self.verify_bytes(&content, seal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use pqcrypto_traits::sign::PublicKey;
+
+    /// Fresh path in the temp directory, removed first if left over.
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("veritas-client-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn client() -> Veritas {
+        Veritas::new()
+            .unwrap()
+            .with_qrng(Arc::new(MockQrng::default()))
+    }
+
+    #[tokio::test]
+    async fn test_seal_and_verify_file() {
+        let path = temp_path("photo.JPG");
+        std::fs::write(&path, b"pixels").unwrap();
+
+        let veritas = client();
+        let seal = veritas.seal_file(&path).await.unwrap();
+        assert_eq!(seal.media_type, MediaType::Image);
+        assert_eq!(seal.public_key, veritas.public_key().as_bytes());
+        assert!(veritas.verify_file(&path, &seal).unwrap().is_authentic());
+
+        std::fs::write(&path, b"edited pixels").unwrap();
+        assert!(!veritas.verify_file(&path, &seal).unwrap().is_authentic());
+        assert!(matches!(
+            veritas.seal_file(temp_path("missing.jpg")).await,
+            Err(ClientError::Io(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_bytes_accepts_other_signers() {
+        let seal = client()
+            .seal_bytes(b"report".to_vec(), MediaType::Generic)
+            .await
+            .unwrap();
+        let verifier = client();
+        assert!(verifier
+            .verify_bytes(b"report", &seal)
+            .unwrap()
+            .is_authentic());
+        assert!(!verifier
+            .verify_bytes(b"rapport", &seal)
+            .unwrap()
+            .is_authentic());
+    }
+
+    #[cfg(feature = "keyfile")]
+    #[tokio::test]
+    async fn test_keystore_keeps_keypair() {
+        let path = temp_path("keystore.key");
+
+        let first = Veritas::with_keystore(&path, Some("passphrase")).unwrap();
+        assert!(path.exists());
+        let second = Veritas::with_keystore(&path, Some("passphrase")).unwrap();
+        assert_eq!(
+            first.public_key().as_bytes(),
+            second.public_key().as_bytes()
+        );
+        assert!(matches!(
+            Veritas::with_keystore(&path, Some("wrong")),
+            Err(ClientError::Keystore(KeypairFileError::WrongPassphrase))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
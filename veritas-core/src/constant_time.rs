@@ -0,0 +1,40 @@
+//! Constant-time comparison of hashes, MACs and signed payloads.
+//!
+//! `==` on byte slices returns at the first differing byte, so its timing
+//! tells a caller how many leading bytes of a forged digest were right.
+//! Compare secret-dependent bytes with [`ct_eq`] instead. Lengths are not
+//! treated as secret: slices of different lengths are unequal straight away.
+
+use subtle::ConstantTimeEq;
+
+/// Returns true if `a` and `b` are equal, in time independent of their contents.
+///
+/// # Example
+///
+/// ```
+/// use veritas_core::{ct_eq, ContentHash};
+///
+/// let sealed = ContentHash::from_bytes(b"original");
+/// let received = ContentHash::from_bytes(b"original");
+/// assert!(ct_eq(&sealed.crypto_hash, &received.crypto_hash));
+/// ```
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq() {
+        let digest = [0xAB; 32];
+        let mut tampered = digest;
+        tampered[31] ^= 1;
+
+        assert!(ct_eq(&digest, &digest));
+        assert!(!ct_eq(&digest, &tampered));
+        assert!(!ct_eq(&digest, &digest[..31]));
+        assert!(ct_eq(&[], &[]));
+    }
+}
@@ -0,0 +1,259 @@
+//! Counter-signatures on existing seals.
+//!
+//! A notary, editor or witness can endorse a seal after the fact by signing
+//! its [`VeritasSeal::seal_hash`]. Counter-signatures are stored next to the
+//! original signature, outside the signed payload, so adding one never
+//! invalidates the original seal.
+//!
+//! Each counter-signature is an ML-DSA-65 detached signature over a CBOR
+//! payload binding the seal hash, the signer role and the signing time.
+
+use chrono::Utc;
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::VeritasSeal;
+
+/// Domain separation tag for counter-signature payloads.
+const COUNTERSIGNATURE_DOMAIN: &str = "veritas-countersignature-v1";
+
+/// Capacity in which a counter-signer endorses a seal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CounterSignerRole {
+    /// Notary attesting the seal existed at signing time
+    Notary,
+    /// Editor approving the media for publication
+    Editor,
+    /// Witness present at capture
+    Witness,
+    /// Application-defined role
+    Custom(String),
+}
+
+impl std::fmt::Display for CounterSignerRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Notary => write!(f, "notary"),
+            Self::Editor => write!(f, "editor"),
+            Self::Witness => write!(f, "witness"),
+            Self::Custom(role) => write!(f, "{role}"),
+        }
+    }
+}
+
+/// Endorsement of an existing seal by an additional signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CounterSignature {
+    /// Role of the counter-signer
+    pub role: CounterSignerRole,
+    /// Counter-signer ML-DSA-65 public key
+    pub signer_public_key: Vec<u8>,
+    /// When the counter-signature was made (Unix timestamp ms)
+    pub timestamp: u64,
+    /// Detached ML-DSA-65 signature over the counter-signature payload
+    pub signature: Vec<u8>,
+}
+
+/// Signed portion of a counter-signature.
+#[derive(Serialize)]
+struct CounterSignaturePayload<'a> {
+    domain: &'static str,
+    seal_hash: &'a [u8; 32],
+    role: &'a CounterSignerRole,
+    timestamp: u64,
+}
+
+impl CounterSignature {
+    /// Counter-sign a seal hash at the given time.
+    pub fn sign(
+        seal_hash: &[u8; 32],
+        role: CounterSignerRole,
+        timestamp: u64,
+        secret_key: &mldsa65::SecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<Self> {
+        let payload = payload_bytes(seal_hash, &role, timestamp)?;
+        let signature = mldsa65::detached_sign(&payload, secret_key);
+
+        Ok(Self {
+            role,
+            signer_public_key: public_key.as_bytes().to_vec(),
+            timestamp,
+            signature: signature.as_bytes().to_vec(),
+        })
+    }
+
+    /// Verify this counter-signature against a seal.
+    ///
+    /// Returns `Ok(false)` for malformed keys or signatures, or when the
+    /// counter-signature was made over a different seal.
+    pub fn verify(&self, seal: &VeritasSeal) -> Result<bool> {
+        let payload = payload_bytes(&seal.seal_hash(), &self.role, self.timestamp)?;
+
+        let Ok(public_key) = mldsa65::PublicKey::from_bytes(&self.signer_public_key) else {
+            return Ok(false);
+        };
+        let Ok(signature) = mldsa65::DetachedSignature::from_bytes(&self.signature) else {
+            return Ok(false);
+        };
+        Ok(mldsa65::verify_detached_signature(&signature, &payload, &public_key).is_ok())
+    }
+
+    /// SHA3-256 fingerprint of the counter-signer key (hex).
+    pub fn signer_fingerprint(&self) -> String {
+        hex::encode(Sha3_256::digest(&self.signer_public_key))
+    }
+}
+
+fn payload_bytes(
+    seal_hash: &[u8; 32],
+    role: &CounterSignerRole,
+    timestamp: u64,
+) -> Result<Vec<u8>> {
+    let payload = CounterSignaturePayload {
+        domain: COUNTERSIGNATURE_DOMAIN,
+        seal_hash,
+        role,
+        timestamp,
+    };
+    let mut bytes = Vec::with_capacity(128);
+    ciborium::into_writer(&payload, &mut bytes)
+        .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+    Ok(bytes)
+}
+
+impl VeritasSeal {
+    /// Add a counter-signature made now by the given key.
+    ///
+    /// Older seals are migrated to the current format first (the original
+    /// signature stays valid). Returns the counter-signature that was added.
+    pub fn countersign(
+        &mut self,
+        role: CounterSignerRole,
+        secret_key: &mldsa65::SecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<&CounterSignature> {
+        let timestamp = u64::try_from(Utc::now().timestamp_millis()).map_err(|_| {
+            VeritasError::InvalidTimestamp {
+                reason: "timestamp before Unix epoch".into(),
+            }
+        })?;
+        let countersignature =
+            CounterSignature::sign(&self.seal_hash(), role, timestamp, secret_key, public_key)?;
+        self.add_countersignature(countersignature)
+    }
+
+    /// Attach a counter-signature produced elsewhere (e.g. by a remote notary).
+    ///
+    /// The counter-signature must verify against this seal.
+    pub fn add_countersignature(
+        &mut self,
+        countersignature: CounterSignature,
+    ) -> Result<&CounterSignature> {
+        if !countersignature.verify(self)? {
+            return Err(VeritasError::InvalidSeal(
+                "counter-signature does not verify against this seal".into(),
+            ));
+        }
+
+        crate::schema::migrate(self)?;
+        self.countersignatures.push(countersignature);
+        Ok(self.countersignatures.last().expect("just pushed"))
+    }
+
+    /// Verify every counter-signature on the seal.
+    ///
+    /// Returns one result per entry in `countersignatures`, in order. The
+    /// original seal signature is checked separately with [`VeritasSeal::verify`].
+    pub fn verify_countersignatures(&self) -> Result<Vec<bool>> {
+        self.countersignatures
+            .iter()
+            .map(|countersignature| countersignature.verify(self))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, generate_keypair_raw, MediaType, SealBuilder};
+
+    async fn sample_seal() -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(b"countersigned".to_vec(), MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal")
+    }
+
+    #[tokio::test]
+    async fn test_countersign_keeps_original_valid() {
+        let mut seal = sample_seal().await;
+        let (notary_pk, notary_sk) = generate_keypair_raw();
+        let (editor_pk, editor_sk) = generate_keypair_raw();
+
+        seal.countersign(CounterSignerRole::Notary, &notary_sk, &notary_pk)
+            .unwrap();
+        seal.countersign(
+            CounterSignerRole::Custom("archivist".into()),
+            &editor_sk,
+            &editor_pk,
+        )
+        .unwrap();
+
+        assert!(seal.verify().unwrap());
+        assert_eq!(seal.verify_countersignatures().unwrap(), vec![true, true]);
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(restored.countersignatures, seal.countersignatures);
+        assert_eq!(
+            restored.verify_countersignatures().unwrap(),
+            vec![true, true]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_countersignature_bound_to_seal() {
+        let mut seal = sample_seal().await;
+        let other = sample_seal().await;
+        let (pk, sk) = generate_keypair_raw();
+
+        let foreign =
+            CounterSignature::sign(&other.seal_hash(), CounterSignerRole::Editor, 1, &sk, &pk)
+                .unwrap();
+        assert!(!foreign.verify(&seal).unwrap());
+        assert!(seal.add_countersignature(foreign).is_err());
+        assert!(seal.countersignatures.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tampered_countersignature_detected() {
+        let mut seal = sample_seal().await;
+        let (pk, sk) = generate_keypair_raw();
+        seal.countersign(CounterSignerRole::Witness, &sk, &pk)
+            .unwrap();
+
+        seal.countersignatures[0].role = CounterSignerRole::Notary;
+        assert_eq!(seal.verify_countersignatures().unwrap(), vec![false]);
+
+        seal.countersignatures[0].role = CounterSignerRole::Witness;
+        seal.countersignatures[0].timestamp += 1;
+        assert_eq!(seal.verify_countersignatures().unwrap(), vec![false]);
+    }
+
+    #[tokio::test]
+    async fn test_countersign_migrates_old_seal() {
+        let mut seal = sample_seal().await;
+        seal.version = 1;
+        let (pk, sk) = generate_keypair_raw();
+
+        seal.countersign(CounterSignerRole::Notary, &sk, &pk)
+            .unwrap();
+        assert!(seal.is_latest_version());
+        assert!(seal.verify().unwrap());
+    }
+}
@@ -0,0 +1,277 @@
+//! W3C Verifiable Credential export of seals.
+//!
+//! Wraps a seal as a Verifiable Credential (VC Data Model 2.0) for tools of
+//! the decentralized identity ecosystem: the issuer is a DID, the
+//! `credentialSubject` carries the content hashes and timestamps, and the
+//! `proof` references the seal's ML-DSA-65 signature.
+//!
+//! No ML-DSA cryptosuite is standardized for Data Integrity proofs yet, so
+//! the proof does not sign the credential itself. It carries the signed seal
+//! (CBOR, multibase base64url): verifiers decode it with
+//! [`VerifiableCredential::seal`], which checks the credential matches it,
+//! then verify the seal like any other. Terms outside the VC 2.0 context fall
+//! under its issuer-dependent vocabulary.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::did::{did_key_id, is_valid_did};
+use crate::error::{Result, VeritasError};
+use crate::seal::VeritasSeal;
+
+/// JSON-LD context of the VC Data Model 2.0.
+pub const VC_CONTEXT_V2: &str = "https://www.w3.org/ns/credentials/v2";
+
+/// Credential type of exported seals.
+pub const SEAL_CREDENTIAL_TYPE: &str = "VeritasSealCredential";
+
+/// Proof type referencing the seal signature.
+pub const SEAL_PROOF_TYPE: &str = "VeritasSealSignature2026";
+
+/// Multibase prefix of base64url without padding.
+const MULTIBASE_BASE64URL: char = 'u';
+
+/// A seal wrapped as a W3C Verifiable Credential.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    /// JSON-LD contexts
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    /// `VerifiableCredential` and [`SEAL_CREDENTIAL_TYPE`]
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    /// DID of the issuer
+    pub issuer: String,
+    /// Start of the seal's validity window, or its capture time (RFC 3339)
+    pub valid_from: String,
+    /// End of the seal's validity window (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<String>,
+    /// The sealed content
+    pub credential_subject: SealSubject,
+    /// Reference to the seal signature
+    pub proof: SealProof,
+}
+
+/// Sealed content, as the subject of a credential.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SealSubject {
+    /// `urn:sha3-256:` URN of the content hash
+    pub id: String,
+    /// Media type of the content
+    pub media_type: String,
+    /// SHA3-256 of the content (hex)
+    pub content_hash: String,
+    /// Perceptual hash (hex, images only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub perceptual_hash: Option<String>,
+    /// Hash of the canonical form of documents (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_hash: Option<String>,
+    /// Capture time (RFC 3339)
+    pub captured_at: String,
+    /// Time the QRNG entropy was fetched (RFC 3339)
+    pub entropy_timestamp: String,
+    /// QRNG entropy bound into the seal (hex)
+    pub qrng_entropy: String,
+    /// QRNG source
+    pub qrng_source: String,
+}
+
+/// Proof of a credential: the seal and its ML-DSA-65 signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SealProof {
+    /// [`SEAL_PROOF_TYPE`]
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    /// Time the seal was signed (RFC 3339)
+    pub created: String,
+    /// Always `assertionMethod`
+    pub proof_purpose: String,
+    /// DID URL of the signing key: the seal's key ID when it is one, else
+    /// the key ID (or the default ID of the public key) under the issuer
+    pub verification_method: String,
+    /// Signature algorithm (`ML-DSA-65`)
+    pub signature_algorithm: String,
+    /// ML-DSA-65 public key (multibase base64url)
+    pub public_key_multibase: String,
+    /// ML-DSA-65 signature of the seal (multibase base64url)
+    pub proof_value: String,
+    /// The signed seal as CBOR (multibase base64url)
+    pub seal: String,
+}
+
+impl VerifiableCredential {
+    /// Wrap `seal` as a credential issued by the DID `issuer`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `issuer` is not a DID or a seal timestamp is out of range.
+    pub fn from_seal(seal: &VeritasSeal, issuer: &str) -> Result<Self> {
+        if !is_valid_did(issuer) {
+            return Err(VeritasError::InvalidDid(format!(
+                "credential issuer '{issuer}' is not a DID"
+            )));
+        }
+        let content_hash = hex::encode(seal.content_hash.crypto_hash);
+        // Key IDs that are DID URLs already name their key
+        let verification_method = match &seal.key_id {
+            Some(key_id) if is_valid_did(key_id.split('#').next().unwrap_or_default()) => {
+                key_id.clone()
+            }
+            Some(key_id) => format!("{issuer}#{key_id}"),
+            None => did_key_id(issuer, &seal.public_key),
+        };
+
+        Ok(Self {
+            context: vec![VC_CONTEXT_V2.to_string()],
+            types: vec![
+                "VerifiableCredential".to_string(),
+                SEAL_CREDENTIAL_TYPE.to_string(),
+            ],
+            issuer: issuer.to_string(),
+            valid_from: rfc3339(seal.not_before.unwrap_or(seal.capture_timestamp_utc))?,
+            valid_until: seal.not_after.map(rfc3339).transpose()?,
+            credential_subject: SealSubject {
+                id: format!("urn:sha3-256:{content_hash}"),
+                media_type: seal.media_type.to_string(),
+                content_hash,
+                perceptual_hash: seal.content_hash.perceptual_hash.as_ref().map(hex::encode),
+                canonical_hash: seal.content_hash.canonical_hash.map(hex::encode),
+                captured_at: rfc3339(seal.capture_timestamp_utc)?,
+                entropy_timestamp: rfc3339(seal.entropy_timestamp)?,
+                qrng_entropy: hex::encode(seal.qrng_entropy),
+                qrng_source: seal.qrng_source.to_string(),
+            },
+            proof: SealProof {
+                proof_type: SEAL_PROOF_TYPE.to_string(),
+                created: rfc3339(seal.capture_timestamp_utc)?,
+                proof_purpose: "assertionMethod".to_string(),
+                verification_method,
+                signature_algorithm: "ML-DSA-65".to_string(),
+                public_key_multibase: multibase(&seal.public_key),
+                proof_value: multibase(&seal.signature),
+                seal: multibase(&seal.to_cbor()?),
+            },
+        })
+    }
+
+    /// Decode the seal carried by the proof, checking the rest of the
+    /// credential matches it.
+    ///
+    /// The seal's signature is not checked: verify the returned seal (and
+    /// the content) as usual.
+    pub fn seal(&self) -> Result<VeritasSeal> {
+        let cbor = self
+            .proof
+            .seal
+            .strip_prefix(MULTIBASE_BASE64URL)
+            .and_then(|encoded| URL_SAFE_NO_PAD.decode(encoded).ok())
+            .ok_or_else(|| {
+                VeritasError::InvalidSeal("credential proof seal is not base64url".into())
+            })?;
+        let seal = VeritasSeal::from_cbor(&cbor)?;
+        if Self::from_seal(&seal, &self.issuer)? != *self {
+            return Err(VeritasError::InvalidSeal(
+                "credential does not match the seal in its proof".into(),
+            ));
+        }
+        Ok(seal)
+    }
+
+    /// Serialize the credential to indented JSON.
+    pub fn to_json_pretty(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))
+    }
+}
+
+fn multibase(bytes: &[u8]) -> String {
+    format!("{MULTIBASE_BASE64URL}{}", URL_SAFE_NO_PAD.encode(bytes))
+}
+
+fn rfc3339(timestamp_ms: u64) -> Result<String> {
+    i64::try_from(timestamp_ms)
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .ok_or_else(|| VeritasError::InvalidTimestamp {
+            reason: format!("{timestamp_ms} ms is out of range"),
+        })
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, MediaType, SealBuilder};
+    use crate::signing_keys::key_id_for;
+    use pqcrypto_traits::sign::PublicKey;
+
+    async fn sealed() -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(b"Credential".to_vec(), MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_credential_round_trip() {
+        let seal = sealed().await;
+        let credential = VerifiableCredential::from_seal(&seal, "did:web:veritas-q.com").unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&credential.to_json_pretty().unwrap())
+            .expect("credential is JSON");
+        assert_eq!(json["@context"][0], VC_CONTEXT_V2);
+        assert_eq!(json["type"][1], SEAL_CREDENTIAL_TYPE);
+        assert_eq!(json["issuer"], "did:web:veritas-q.com");
+        assert_eq!(
+            json["credentialSubject"]["contentHash"],
+            hex::encode(seal.content_hash.crypto_hash)
+        );
+        assert!(json.get("validUntil").is_none());
+        let method = json["proof"]["verificationMethod"].as_str().unwrap();
+        assert_eq!(
+            method,
+            format!("did:web:veritas-q.com#{}", key_id_for(&seal.public_key))
+        );
+
+        let parsed: VerifiableCredential = serde_json::from_value(json).unwrap();
+        let decoded = parsed.seal().unwrap();
+        assert_eq!(decoded.signature, seal.signature);
+        assert!(decoded.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_credential_must_match_its_seal() {
+        let seal = sealed().await;
+        let credential = VerifiableCredential::from_seal(&seal, "did:web:veritas-q.com").unwrap();
+
+        let mut altered = credential.clone();
+        altered.credential_subject.content_hash = hex::encode([0u8; 32]);
+        assert!(altered.seal().is_err());
+
+        let mut corrupt = credential;
+        corrupt.proof.seal.replace_range(..1, "z");
+        assert!(corrupt.seal().is_err());
+
+        assert!(VerifiableCredential::from_seal(&seal, "veritas-q.com").is_err());
+
+        // A key ID that is a DID URL names the key itself
+        let (public_key, secret_key) = generate_keypair();
+        let key_id = crate::did::did_key_id("did:web:newsroom.example", public_key.as_bytes());
+        let issued = SealBuilder::new(b"Issued".to_vec(), MediaType::Image)
+            .with_key_id(key_id.clone())
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        let credential = VerifiableCredential::from_seal(&issued, "did:web:newsroom.example");
+        assert_eq!(credential.unwrap().proof.verification_method, key_id);
+    }
+}
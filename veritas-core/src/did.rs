@@ -0,0 +1,483 @@
+//! Decentralized identifiers (DIDs) of seal signers.
+//!
+//! A `did:key` DID embeds an ML-DSA-65 public key (multicodec
+//! `mldsa-65-pub`, base58btc) and resolves offline, which suits signers
+//! without a domain. A `did:web` DID names a domain publishing a DID document
+//! over HTTPS (`https://<domain>/.well-known/did.json`) that lists the
+//! signer's keys ([`DidDocument`]).
+//!
+//! Seals reference their issuer by carrying a DID URL as their key ID
+//! (`did:web:example.com#<key ID>`, see [`did_key_id`]). Anyone can then
+//! resolve the issuer's DID document and check the seal's key against it
+//! with [`DidDocument::resolve_signing_key`], without asking the issuer's
+//! API. DID documents carry no validity periods: retired keys stay listed,
+//! revoked ones are removed.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::VeritasSeal;
+use crate::signing_keys::{key_id_for, KeyResolution, SigningKey, SigningKeyStatus};
+
+/// Multicodec code of ML-DSA-65 public keys (`mldsa-65-pub`).
+pub const MLDSA65_PUB_MULTICODEC: u64 = 0x1211;
+
+/// JSON-LD context of DID documents.
+pub const DID_CONTEXT_V1: &str = "https://www.w3.org/ns/did/v1";
+
+/// JSON-LD context of `Multikey` verification methods.
+pub const MULTIKEY_CONTEXT_V1: &str = "https://w3id.org/security/multikey/v1";
+
+/// Path of a `did:web` document on a domain without a path.
+pub const DID_WEB_WELL_KNOWN_PATH: &str = "/.well-known/did.json";
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Multibase prefix of base58btc.
+const MULTIBASE_BASE58BTC: char = 'z';
+
+/// Whether `did` is a DID: `did:<method>:<method-specific-id>`.
+pub fn is_valid_did(did: &str) -> bool {
+    let Some((method, id)) = did
+        .strip_prefix("did:")
+        .and_then(|rest| rest.split_once(':'))
+    else {
+        return false;
+    };
+    !method.is_empty()
+        && method
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        && !id.is_empty()
+        && !id.ends_with(':')
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"._-:%".contains(&b))
+}
+
+/// `did:key` DID of an ML-DSA-65 public key.
+pub fn did_key(public_key: &[u8]) -> String {
+    format!("did:key:{}", public_key_multibase(public_key))
+}
+
+/// ML-DSA-65 public key of a `did:key` DID (a fragment is ignored).
+pub fn did_key_public_key(did: &str) -> Result<Vec<u8>> {
+    let did = did.split_once('#').map_or(did, |(did, _)| did);
+    let multibase = did
+        .strip_prefix("did:key:")
+        .ok_or_else(|| VeritasError::InvalidDid(format!("'{did}' is not a did:key DID")))?;
+    decode_public_key_multibase(multibase)
+}
+
+/// DID URL naming `public_key` as a key of `did`, for use as a seal's key
+/// ID: the key's multibase for `did:key` DIDs, its default key ID
+/// ([`key_id_for`]) otherwise.
+pub fn did_key_id(did: &str, public_key: &[u8]) -> String {
+    if did.starts_with("did:key:") {
+        format!("{did}#{}", public_key_multibase(public_key))
+    } else {
+        format!("{did}#{}", key_id_for(public_key))
+    }
+}
+
+/// HTTPS URL of the document of a `did:web` DID.
+///
+/// `did:web:example.com` resolves to
+/// `https://example.com/.well-known/did.json`,
+/// `did:web:example.com:issuers:news` to
+/// `https://example.com/issuers/news/did.json`, and a port is written
+/// `%3A` (`did:web:localhost%3A8443`).
+pub fn did_web_document_url(did: &str) -> Result<String> {
+    let id = did
+        .strip_prefix("did:web:")
+        .filter(|_| is_valid_did(did))
+        .ok_or_else(|| VeritasError::InvalidDid(format!("'{did}' is not a did:web DID")))?;
+    let mut segments = id.split(':');
+    let host = segments.next().unwrap_or_default().replace("%3A", ":");
+    let path: Vec<&str> = segments.collect();
+    if path.is_empty() {
+        Ok(format!("https://{host}{DID_WEB_WELL_KNOWN_PATH}"))
+    } else {
+        Ok(format!("https://{host}/{}/did.json", path.join("/")))
+    }
+}
+
+impl VeritasSeal {
+    /// DID of the seal's issuer, when its key ID is a DID URL.
+    pub fn issuer_did(&self) -> Option<&str> {
+        self.key_id
+            .as_deref()
+            .and_then(|key_id| key_id.split_once('#'))
+            .map(|(did, _)| did)
+            .filter(|did| is_valid_did(did))
+    }
+}
+
+/// A key listed in a DID document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationMethod {
+    /// DID URL of the key (`<DID>#<key ID>`)
+    pub id: String,
+    /// Always `Multikey`
+    #[serde(rename = "type")]
+    pub method_type: String,
+    /// DID controlling the key
+    pub controller: String,
+    /// Multicodec-prefixed public key (multibase base58btc)
+    pub public_key_multibase: String,
+}
+
+impl VerificationMethod {
+    /// Decoded ML-DSA-65 public key.
+    pub fn public_key(&self) -> Result<Vec<u8>> {
+        decode_public_key_multibase(&self.public_key_multibase)
+    }
+}
+
+/// A DID document listing the keys that sign an issuer's seals.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    /// JSON-LD contexts
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    /// The DID
+    pub id: String,
+    /// Keys of the DID
+    #[serde(default)]
+    pub verification_method: Vec<VerificationMethod>,
+    /// DID URLs of the keys vouching for seals and credentials
+    #[serde(default)]
+    pub assertion_method: Vec<String>,
+}
+
+impl DidDocument {
+    /// Document of `did` listing the registered `keys` that are not revoked.
+    ///
+    /// Keys whose ID is not already a DID URL of `did` are listed as
+    /// `<DID>#<key ID>`.
+    pub fn new(did: &str, keys: &[SigningKey]) -> Result<Self> {
+        if !is_valid_did(did) {
+            return Err(VeritasError::InvalidDid(format!("'{did}' is not a DID")));
+        }
+        let verification_method: Vec<VerificationMethod> = keys
+            .iter()
+            .filter(|key| key.status != SigningKeyStatus::Revoked)
+            .map(|key| VerificationMethod {
+                id: if key.key_id.starts_with(&format!("{did}#")) {
+                    key.key_id.clone()
+                } else {
+                    format!("{did}#{}", key.key_id)
+                },
+                method_type: "Multikey".to_string(),
+                controller: did.to_string(),
+                public_key_multibase: public_key_multibase(&key.public_key),
+            })
+            .collect();
+        Ok(Self {
+            context: vec![DID_CONTEXT_V1.to_string(), MULTIKEY_CONTEXT_V1.to_string()],
+            id: did.to_string(),
+            assertion_method: verification_method.iter().map(|m| m.id.clone()).collect(),
+            verification_method,
+        })
+    }
+
+    /// Document of a `did:key` DID, derived from the DID itself.
+    pub fn for_did_key(did: &str) -> Result<Self> {
+        let public_key = did_key_public_key(did)?;
+        let did = did_key(&public_key);
+        let id = did_key_id(&did, &public_key);
+        Ok(Self {
+            context: vec![DID_CONTEXT_V1.to_string(), MULTIKEY_CONTEXT_V1.to_string()],
+            verification_method: vec![VerificationMethod {
+                id: id.clone(),
+                method_type: "Multikey".to_string(),
+                controller: did.clone(),
+                public_key_multibase: public_key_multibase(&public_key),
+            }],
+            assertion_method: vec![id],
+            id: did,
+        })
+    }
+
+    /// Key of the document with DID URL `id`, if it may vouch for seals.
+    pub fn assertion_key(&self, id: &str) -> Option<&VerificationMethod> {
+        if !self.assertion_method.iter().any(|method| method == id) {
+            return None;
+        }
+        self.verification_method
+            .iter()
+            .find(|method| method.id == id)
+    }
+
+    /// Check the key that signed `seal` against this document, like
+    /// [`VeritasSeal::resolve_signing_key`] against a registry.
+    ///
+    /// Seals whose key ID is not a DID URL of this document resolve as
+    /// [`KeyResolution::Unknown`]. Only meaningful once the signature has
+    /// been verified.
+    pub fn resolve_signing_key(&self, seal: &VeritasSeal) -> KeyResolution {
+        let Some(key_id) = &seal.key_id else {
+            return KeyResolution::Embedded;
+        };
+        match self
+            .assertion_key(key_id)
+            .map(VerificationMethod::public_key)
+        {
+            None => KeyResolution::Unknown,
+            Some(Ok(public_key)) if public_key == seal.public_key => KeyResolution::Trusted,
+            Some(_) => KeyResolution::KeyMismatch,
+        }
+    }
+
+    /// Resolve `did`: offline for `did:key`, by fetching its document over
+    /// HTTPS for `did:web`.
+    #[cfg(feature = "network")]
+    pub async fn resolve(did: &str) -> Result<Self> {
+        if did.starts_with("did:key:") {
+            return Self::for_did_key(did);
+        }
+        let url = did_web_document_url(did)?;
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()?;
+        let document: Self = client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if document.id != did {
+            return Err(VeritasError::InvalidDid(format!(
+                "document at {url} is for '{}', not '{did}'",
+                document.id
+            )));
+        }
+        Ok(document)
+    }
+}
+
+/// [`MLDSA65_PUB_MULTICODEC`] as an unsigned varint, prefixing the keys.
+fn multicodec_prefix() -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(3);
+    let mut code = MLDSA65_PUB_MULTICODEC;
+    while code >= 0x80 {
+        prefix.push((code as u8 & 0x7f) | 0x80);
+        code >>= 7;
+    }
+    prefix.push(code as u8);
+    prefix
+}
+
+/// Multicodec-prefixed ML-DSA-65 public key, multibase base58btc.
+fn public_key_multibase(public_key: &[u8]) -> String {
+    let mut bytes = multicodec_prefix();
+    bytes.extend_from_slice(public_key);
+    format!("{MULTIBASE_BASE58BTC}{}", base58_encode(&bytes))
+}
+
+fn decode_public_key_multibase(multibase: &str) -> Result<Vec<u8>> {
+    let prefix = multicodec_prefix();
+    multibase
+        .strip_prefix(MULTIBASE_BASE58BTC)
+        .and_then(base58_decode)
+        .and_then(|bytes| bytes.strip_prefix(prefix.as_slice()).map(<[u8]>::to_vec))
+        .ok_or_else(|| {
+            VeritasError::InvalidDid(format!(
+                "'{multibase}' is not a base58btc ML-DSA-65 public key"
+            ))
+        })
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    // Base-58 digits, least significant first
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let mut encoded = "1".repeat(zeros);
+    encoded.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|&digit| char::from(BASE58_ALPHABET[usize::from(digit)])),
+    );
+    encoded
+}
+
+fn base58_decode(encoded: &str) -> Option<Vec<u8>> {
+    let zeros = encoded.bytes().take_while(|&b| b == b'1').count();
+    // Bytes, least significant first
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len() * 733 / 1000 + 1);
+    for c in encoded.bytes().skip(zeros) {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let mut decoded = vec![0; zeros];
+    decoded.extend(bytes.iter().rev());
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base58_round_trip() {
+        // Bitcoin base58 test vectors
+        assert_eq!(base58_encode(b"Hello World!"), "2NEpo7TZRRrLZSi2U");
+        assert_eq!(base58_encode(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]), "11233QC4");
+        for bytes in [&b""[..], &[0], &[0, 1, 2], b"Hello World!"] {
+            assert_eq!(base58_decode(&base58_encode(bytes)).unwrap(), bytes);
+        }
+        assert!(base58_decode("0OIl").is_none());
+    }
+
+    #[test]
+    fn test_is_valid_did() {
+        assert!(is_valid_did("did:web:veritas-q.com"));
+        assert!(is_valid_did("did:web:example.com%3A8443:issuers:1"));
+        assert!(is_valid_did(
+            "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+        ));
+        assert!(!is_valid_did("did:web:"));
+        assert!(!is_valid_did("did:Web:example.com"));
+        assert!(!is_valid_did("https://example.com"));
+        assert!(!is_valid_did("did:web:exa mple.com"));
+    }
+
+    #[test]
+    fn test_did_key_round_trip() {
+        let public_key = vec![7u8; crate::MLDSA65_PUBLIC_KEY_BYTES];
+        assert_eq!(multicodec_prefix(), [0x91, 0x24]);
+        let did = did_key(&public_key);
+        assert!(did.starts_with("did:key:z"));
+        assert_eq!(did_key_public_key(&did).unwrap(), public_key);
+        let key_id = did_key_id(&did, &public_key);
+        assert_eq!(did_key_public_key(&key_id).unwrap(), public_key);
+
+        let document = DidDocument::for_did_key(&did).unwrap();
+        assert_eq!(document.id, did);
+        assert_eq!(
+            document
+                .assertion_key(&key_id)
+                .unwrap()
+                .public_key()
+                .unwrap(),
+            public_key
+        );
+
+        assert!(did_key_public_key("did:web:example.com").is_err());
+        assert!(
+            did_key_public_key("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").is_err()
+        );
+    }
+
+    #[test]
+    fn test_did_web_document_url() {
+        assert_eq!(
+            did_web_document_url("did:web:veritas-q.com").unwrap(),
+            "https://veritas-q.com/.well-known/did.json"
+        );
+        assert_eq!(
+            did_web_document_url("did:web:example.com%3A8443:issuers:news").unwrap(),
+            "https://example.com:8443/issuers/news/did.json"
+        );
+        assert!(did_web_document_url("did:key:z6Mk").is_err());
+    }
+
+    #[test]
+    fn test_document_lists_unrevoked_keys() {
+        let did = "did:web:veritas-q.com";
+        let active = SigningKey {
+            key_id: did_key_id(did, &[1; 32]),
+            ..SigningKey::new(vec![1; 32], 2_000)
+        };
+        let retired = SigningKey {
+            status: SigningKeyStatus::Retired,
+            ..SigningKey::new(vec![2; 32], 1_000)
+        };
+        let revoked = SigningKey {
+            status: SigningKeyStatus::Revoked,
+            ..SigningKey::new(vec![3; 32], 0)
+        };
+        let document = DidDocument::new(did, &[active.clone(), retired.clone(), revoked]).unwrap();
+
+        assert_eq!(document.verification_method.len(), 2);
+        assert_eq!(document.verification_method[0].id, active.key_id);
+        assert_eq!(
+            document.verification_method[1].id,
+            format!("{did}#{}", retired.key_id)
+        );
+        assert_eq!(document.assertion_method.len(), 2);
+        let json = serde_json::to_value(&document).unwrap();
+        assert_eq!(json["verificationMethod"][0]["type"], "Multikey");
+        assert!(DidDocument::new("veritas-q.com", &[]).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "network"))]
+mod seal_tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, MediaType, SealBuilder};
+    use pqcrypto_traits::sign::PublicKey;
+
+    #[tokio::test]
+    async fn test_resolve_signing_key_from_document() {
+        let did = "did:web:veritas-q.com";
+        let (public_key, secret_key) = generate_keypair();
+        let key_id = did_key_id(did, public_key.as_bytes());
+        let seal = SealBuilder::new(b"Issued".to_vec(), MediaType::Image)
+            .with_key_id(key_id.clone())
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        assert_eq!(seal.issuer_did(), Some(did));
+
+        let registered = SigningKey {
+            key_id,
+            ..SigningKey::new(public_key.as_bytes().to_vec(), 0)
+        };
+        let document = DidDocument::new(did, &[registered.clone()]).unwrap();
+        assert_eq!(document.resolve_signing_key(&seal), KeyResolution::Trusted);
+
+        let (other_key, _) = generate_keypair();
+        let impostor = SigningKey {
+            public_key: other_key.as_bytes().to_vec(),
+            ..registered.clone()
+        };
+        let document = DidDocument::new(did, &[impostor]).unwrap();
+        assert_eq!(
+            document.resolve_signing_key(&seal),
+            KeyResolution::KeyMismatch
+        );
+
+        let revoked = SigningKey {
+            status: SigningKeyStatus::Revoked,
+            ..registered
+        };
+        let document = DidDocument::new(did, &[revoked]).unwrap();
+        assert_eq!(document.resolve_signing_key(&seal), KeyResolution::Unknown);
+    }
+}
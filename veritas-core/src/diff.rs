@@ -0,0 +1,319 @@
+//! Field-by-field comparison of two seals.
+//!
+//! [`VeritasSeal::diff`] reports which fields of two seals differ, e.g. to
+//! tell a re-sealed file from a re-signed or re-anchored copy of the same
+//! seal. Values are rendered as short strings: hashes and entropy in hex,
+//! keys and signatures by their SHA3-256 fingerprint, timestamps in Unix
+//! milliseconds.
+
+use serde::Serialize;
+use sha3::{Digest, Sha3_256};
+
+use crate::seal::VeritasSeal;
+
+/// A field whose value differs between two seals.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDifference {
+    /// Field path, e.g. `capture_timestamp_utc`, `content_hash.crypto_hash`
+    /// or `anchors.bitcoin`
+    pub field: String,
+    /// Value in the first seal (`None` = absent)
+    pub left: Option<String>,
+    /// Value in the second seal (`None` = absent)
+    pub right: Option<String>,
+}
+
+/// Differences between two seals, in seal field order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SealDiff {
+    pub differences: Vec<FieldDifference>,
+}
+
+impl SealDiff {
+    /// Whether every compared field is identical.
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// The difference for `field`, if it differs.
+    pub fn get(&self, field: &str) -> Option<&FieldDifference> {
+        self.differences.iter().find(|d| d.field == field)
+    }
+
+    /// Whether both seals bind the same content (same SHA3-256 hash).
+    pub fn same_content(&self) -> bool {
+        self.get("content_hash.crypto_hash").is_none()
+    }
+
+    /// Whether both seals are signed with the same key.
+    pub fn same_signer(&self) -> bool {
+        self.get("public_key").is_none()
+    }
+
+    fn compare(&mut self, field: &str, left: Option<String>, right: Option<String>) {
+        if left != right {
+            self.differences.push(FieldDifference {
+                field: field.to_string(),
+                left,
+                right,
+            });
+        }
+    }
+}
+
+impl VeritasSeal {
+    /// Compare this seal with `other`, field by field.
+    ///
+    /// Neither seal is verified. Anchors are compared per chain, so the order
+    /// in which they were added does not matter.
+    pub fn diff(&self, other: &VeritasSeal) -> SealDiff {
+        let mut diff = SealDiff::default();
+        let fields: [(&str, fn(&VeritasSeal) -> Option<String>); 23] = [
+            ("version", |s| Some(s.version.to_string())),
+            ("capture_timestamp_utc", |s| {
+                Some(s.capture_timestamp_utc.to_string())
+            }),
+            ("capture_location", |s| s.capture_location.clone()),
+            ("device_attestation", |s| {
+                s.device_attestation.as_ref().map(|attestation| {
+                    format!(
+                        "{} ({}, token {})",
+                        attestation.device_id,
+                        attestation.tee_type,
+                        fingerprint(&attestation.attestation_token)
+                    )
+                })
+            }),
+            ("qrng_entropy", |s| Some(hex::encode(s.qrng_entropy))),
+            ("qrng_source", |s| Some(s.qrng_source.to_string())),
+            ("entropy_timestamp", |s| {
+                Some(s.entropy_timestamp.to_string())
+            }),
+            ("qrng_conditioning", |s| {
+                s.qrng_conditioning.as_ref().map(ToString::to_string)
+            }),
+            ("raw_entropy_hash", |s| s.raw_entropy_hash.map(hex::encode)),
+            ("entropy_certificate", |s| {
+                s.entropy_certificate
+                    .as_ref()
+                    .map(|certificate| format!("signer {}", certificate.signer_fingerprint()))
+            }),
+            ("qrng_policy", |s| {
+                s.qrng_policy.as_ref().map(ToString::to_string)
+            }),
+            ("content_hash.crypto_hash", |s| {
+                Some(hex::encode(s.content_hash.crypto_hash))
+            }),
+            ("content_hash.perceptual_hash", |s| {
+                s.content_hash.perceptual_hash.as_ref().map(hex::encode)
+            }),
+            ("content_hash.segments", |s| {
+                s.content_hash.segments.as_ref().map(|segments| {
+                    format!(
+                        "{} x {} bytes, root {}",
+                        segments.segment_count,
+                        segments.segment_size,
+                        hex::encode(segments.root)
+                    )
+                })
+            }),
+            ("content_hash.canonical_hash", |s| {
+                s.content_hash.canonical_hash.map(hex::encode)
+            }),
+            ("content_hash.region_hashes", |s| {
+                s.content_hash.region_hashes.as_ref().map(|regions| {
+                    let tiles: Vec<_> = regions
+                        .tiles
+                        .iter()
+                        .map(|tile| format!("{tile:016x}"))
+                        .collect();
+                    format!("{0}x{0} tiles {1}", regions.grid, tiles.join(" "))
+                })
+            }),
+            ("media_type", |s| Some(s.media_type.to_string())),
+            ("not_before", |s| s.not_before.map(|t| t.to_string())),
+            ("not_after", |s| s.not_after.map(|t| t.to_string())),
+            ("capture_nonce", |s| s.capture_nonce.map(hex::encode)),
+            ("liveness", |s| {
+                s.liveness.as_ref().map(|liveness| {
+                    format!(
+                        "{} ({}-{})",
+                        liveness.code, liveness.issued_at, liveness.responded_at
+                    )
+                })
+            }),
+            ("disclosure", |s| {
+                s.disclosure.as_ref().map(|disclosure| {
+                    let redacted = disclosure.redacted_fields();
+                    if redacted.is_empty() {
+                        "nothing redacted".to_string()
+                    } else {
+                        let names: Vec<_> = redacted.iter().map(|field| field.name()).collect();
+                        format!("redacted {}", names.join(", "))
+                    }
+                })
+            }),
+            ("public_key", |s| Some(fingerprint(&s.public_key))),
+            ("key_id", |s| s.key_id.clone()),
+            ("signature", |s| Some(fingerprint(&s.signature))),
+            ("threshold_policy", |s| {
+                s.threshold_policy.as_ref().map(|policy| {
+                    let signers: Vec<_> = policy
+                        .signers
+                        .iter()
+                        .map(|key| fingerprint(key.as_slice()))
+                        .collect();
+                    format!("{} of [{}]", policy.threshold, signers.join(", "))
+                })
+            }),
+            ("partial_signatures", |s| {
+                (!s.partial_signatures.is_empty()).then(|| {
+                    let signers: Vec<_> = s
+                        .partial_signatures
+                        .iter()
+                        .map(|partial| partial.signer_index.to_string())
+                        .collect();
+                    format!("co-signers {}", signers.join(", "))
+                })
+            }),
+            ("countersignatures", |s| {
+                (!s.countersignatures.is_empty()).then(|| {
+                    let signers: Vec<_> = s
+                        .countersignatures
+                        .iter()
+                        .map(|cs| format!("{} {}", cs.role, cs.signer_fingerprint()))
+                        .collect();
+                    signers.join(", ")
+                })
+            }),
+            ("ai_detections", |s| {
+                (!s.ai_detections.is_empty()).then(|| {
+                    let verdicts: Vec<_> = s
+                        .ai_detections
+                        .iter()
+                        .map(|a| {
+                            format!(
+                                "{} {} {} ({} bps)",
+                                a.verdict.detector,
+                                a.verdict.detector_version,
+                                a.verdict.verdict,
+                                a.verdict.score_bps
+                            )
+                        })
+                        .collect();
+                    verdicts.join(", ")
+                })
+            }),
+        ];
+        for (field, value) in fields {
+            diff.compare(field, value(self), value(other));
+        }
+
+        let mut chains: Vec<&str> = self
+            .anchors()
+            .chain(other.anchors())
+            .map(|anchor| anchor.chain.as_str())
+            .collect();
+        chains.sort_unstable();
+        chains.dedup();
+        for chain in chains {
+            diff.compare(
+                &format!("anchors.{chain}"),
+                anchor_on(self, chain),
+                anchor_on(other, chain),
+            );
+        }
+
+        diff
+    }
+}
+
+/// SHA3-256 fingerprint (hex) of a key, signature or token.
+fn fingerprint(bytes: &[u8]) -> String {
+    hex::encode(Sha3_256::digest(bytes))
+}
+
+/// The seal's anchor on `chain`, as transaction ID and block height.
+fn anchor_on(seal: &VeritasSeal, chain: &str) -> Option<String> {
+    seal.anchors()
+        .find(|anchor| anchor.chain == chain)
+        .map(|anchor| format!("{} (block {})", anchor.tx_id, anchor.block_height))
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, BlockchainAnchor, MediaType, SealBuilder};
+
+    async fn sample_seal(content: &[u8]) -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(content.to_vec(), MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal")
+    }
+
+    fn anchor(chain: &str, tx_id: &str) -> BlockchainAnchor {
+        BlockchainAnchor {
+            chain: chain.into(),
+            tx_id: tx_id.into(),
+            block_height: 0,
+            proof: None,
+            batch: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_identical_seals_is_empty() {
+        let seal = sample_seal(b"same").await;
+        let diff = seal.diff(&seal.clone());
+        assert!(diff.is_empty());
+        assert!(diff.same_content());
+        assert!(diff.same_signer());
+    }
+
+    #[tokio::test]
+    async fn test_diff_reports_changed_fields() {
+        let first = sample_seal(b"original").await;
+        let second = sample_seal(b"edited").await;
+
+        let diff = first.diff(&second);
+        assert!(!diff.same_content());
+        assert!(!diff.same_signer());
+        assert!(diff.get("signature").is_some());
+        assert!(diff.get("media_type").is_none());
+
+        let hash = diff.get("content_hash.crypto_hash").unwrap();
+        assert_eq!(
+            hash.left.as_deref(),
+            Some(hex::encode(first.content_hash.crypto_hash).as_str())
+        );
+        assert_eq!(
+            hash.right.as_deref(),
+            Some(hex::encode(second.content_hash.crypto_hash).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_compares_anchors_per_chain() {
+        let seal = sample_seal(b"anchored").await;
+        let mut left = seal.clone();
+        let mut right = seal;
+        left.add_anchor(anchor("solana-devnet", "sig1"));
+        left.add_anchor(anchor("bitcoin", "https://calendar"));
+        right.add_anchor(anchor("bitcoin", "https://calendar"));
+        right.add_anchor(anchor("solana-devnet", "sig2"));
+
+        let diff = left.diff(&right);
+        assert_eq!(diff.differences.len(), 1);
+        let solana = diff.get("anchors.solana-devnet").unwrap();
+        assert_eq!(solana.left.as_deref(), Some("sig1 (block 0)"));
+        assert_eq!(solana.right.as_deref(), Some("sig2 (block 0)"));
+
+        right.additional_anchors.clear();
+        let diff = left.diff(&right);
+        assert_eq!(diff.get("anchors.solana-devnet").unwrap().right, None);
+        assert!(diff.same_signer());
+    }
+}
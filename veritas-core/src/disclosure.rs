@@ -0,0 +1,610 @@
+//! Selective disclosure of seal fields.
+//!
+//! A seal built with [`SealBuilder::with_selective_disclosure`] does not sign
+//! its fields directly. Each field is turned into a salted commitment
+//! (`SHA3-256(field name || salt || CBOR value)`), the commitments form a small
+//! Merkle tree, and only the root is signed. Because the salt is random, a
+//! commitment reveals nothing about the value behind it.
+//!
+//! That makes two things possible without breaking the signature:
+//!
+//! - [`VeritasSeal::redact`] drops privacy-sensitive fields (location, device
+//!   identity) and keeps only their commitments.
+//! - [`VeritasSeal::disclosure_proof`] extracts a single field with its Merkle
+//!   path, e.g. to prove the capture time to an editor without handing over
+//!   the rest of the seal.
+//!
+//! [`SealBuilder::with_selective_disclosure`]: crate::SealBuilder::with_selective_disclosure
+
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::{PublicKey, SignedMessage};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::constant_time::ct_eq;
+use crate::error::{Result, VeritasError};
+use crate::merkle;
+use crate::seal::VeritasSeal;
+
+pub use crate::merkle::ProofStep;
+
+/// Domain separation tag for leaves and for the signed root payload.
+const DISCLOSURE_DOMAIN: &str = "veritas-disclosure-v1";
+
+/// CBOR encoding of `None`.
+const CBOR_NULL: &[u8] = &[0xf6];
+
+/// Seal fields committed to in the disclosure tree, in leaf order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SealField {
+    CaptureTimestamp,
+    CaptureLocation,
+    DeviceAttestation,
+    QrngEntropy,
+    QrngSource,
+    EntropyTimestamp,
+    QrngConditioning,
+    EntropyCertificate,
+    ContentHash,
+    MediaType,
+    ThresholdPolicy,
+    QrngPolicy,
+    NotBefore,
+    NotAfter,
+    CaptureNonce,
+    Liveness,
+    KeyId,
+    RawEntropyHash,
+}
+
+impl SealField {
+    /// All committed fields, in leaf order.
+    ///
+    /// New fields are appended, so the openings of older seals are a prefix.
+    pub const ALL: [SealField; 18] = [
+        Self::CaptureTimestamp,
+        Self::CaptureLocation,
+        Self::DeviceAttestation,
+        Self::QrngEntropy,
+        Self::QrngSource,
+        Self::EntropyTimestamp,
+        Self::QrngConditioning,
+        Self::EntropyCertificate,
+        Self::ContentHash,
+        Self::MediaType,
+        Self::ThresholdPolicy,
+        Self::QrngPolicy,
+        Self::NotBefore,
+        Self::NotAfter,
+        Self::CaptureNonce,
+        Self::Liveness,
+        Self::KeyId,
+        Self::RawEntropyHash,
+    ];
+
+    /// Fields committed by seals before version 10 (without `qrng_policy`).
+    /// Later versions append fields, so older seals commit a prefix of
+    /// [`SealField::ALL`].
+    const LEGACY_COUNT: usize = 11;
+
+    /// Seal field name, as used in serialized seals and leaf hashes.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CaptureTimestamp => "capture_timestamp_utc",
+            Self::CaptureLocation => "capture_location",
+            Self::DeviceAttestation => "device_attestation",
+            Self::QrngEntropy => "qrng_entropy",
+            Self::QrngSource => "qrng_source",
+            Self::EntropyTimestamp => "entropy_timestamp",
+            Self::QrngConditioning => "qrng_conditioning",
+            Self::EntropyCertificate => "entropy_certificate",
+            Self::ContentHash => "content_hash",
+            Self::MediaType => "media_type",
+            Self::ThresholdPolicy => "threshold_policy",
+            Self::QrngPolicy => "qrng_policy",
+            Self::NotBefore => "not_before",
+            Self::NotAfter => "not_after",
+            Self::CaptureNonce => "capture_nonce",
+            Self::Liveness => "liveness",
+            Self::KeyId => "key_id",
+            Self::RawEntropyHash => "raw_entropy_hash",
+        }
+    }
+
+    /// Whether the field can be removed from a seal by [`VeritasSeal::redact`].
+    ///
+    /// Only optional fields qualify: the others are needed to check content
+    /// binding and entropy, and have no "absent" representation.
+    pub fn is_redactable(self) -> bool {
+        matches!(
+            self,
+            Self::CaptureLocation | Self::DeviceAttestation | Self::EntropyCertificate
+        )
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|f| *f == self)
+            .expect("every field is listed in ALL")
+    }
+}
+
+impl std::fmt::Display for SealField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for SealField {
+    type Err = VeritasError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name() == s)
+            .ok_or_else(|| VeritasError::InvalidSeal(format!("unknown seal field '{s}'")))
+    }
+}
+
+/// How one field is opened in a disclosure seal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldOpening {
+    /// The value is present in the seal; this is its salt
+    Disclosed { salt: [u8; 32] },
+    /// The value was removed; only its leaf commitment remains
+    Redacted { commitment: [u8; 32] },
+}
+
+/// Per-field openings for a selectively disclosable seal.
+///
+/// Holds one entry per [`SealField::ALL`], in the same order (seals before
+/// version 10 have no entry for `qrng_policy`, seals before version 11 none
+/// for `not_before` and `not_after`, seals before version 14 none for
+/// `capture_nonce`, seals before version 15 none for `liveness`, seals before
+/// version 16 none for `key_id`, seals before version 17 none for
+/// `raw_entropy_hash`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectiveDisclosure {
+    /// One opening per committed field
+    pub fields: Vec<FieldOpening>,
+}
+
+impl SelectiveDisclosure {
+    /// Fresh random salts for every field.
+    #[cfg(feature = "network")]
+    pub(crate) fn generate() -> Result<Self> {
+        let fields = SealField::ALL
+            .iter()
+            .map(|_| {
+                let mut salt = [0u8; 32];
+                getrandom::fill(&mut salt).map_err(|e| {
+                    VeritasError::SerializationError(format!("failed to generate salt: {e}"))
+                })?;
+                Ok(FieldOpening::Disclosed { salt })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { fields })
+    }
+
+    /// Fields that have been redacted.
+    pub fn redacted_fields(&self) -> Vec<SealField> {
+        SealField::ALL
+            .into_iter()
+            .zip(&self.fields)
+            .filter(|(_, opening)| matches!(opening, FieldOpening::Redacted { .. }))
+            .map(|(field, _)| field)
+            .collect()
+    }
+
+    /// Check the opening list matches the field list, redactions are allowed,
+    /// and redacted fields are really absent from the seal.
+    pub(crate) fn validate(&self, seal: &VeritasSeal) -> Result<()> {
+        let committed = self.fields.len();
+        if !(SealField::LEGACY_COUNT..=SealField::ALL.len()).contains(&committed) {
+            return Err(VeritasError::InvalidSeal(format!(
+                "selective disclosure must open {} fields, got {}",
+                SealField::ALL.len(),
+                committed
+            )));
+        }
+        // Fields newer than the seal's openings would not be covered by the signature
+        for &field in &SealField::ALL[committed..] {
+            if field_value(seal, field)? != CBOR_NULL {
+                return Err(VeritasError::InvalidSeal(format!(
+                    "field {field} is set but not committed"
+                )));
+            }
+        }
+        for field in self.redacted_fields() {
+            if !field.is_redactable() {
+                return Err(VeritasError::InvalidSeal(format!(
+                    "field {field} cannot be redacted"
+                )));
+            }
+            // A value next to a redaction would not be covered by the signature
+            if field_value(seal, field)? != CBOR_NULL {
+                return Err(VeritasError::InvalidSeal(format!(
+                    "redacted field {field} still has a value"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Signed payload of a disclosure seal.
+#[derive(Serialize)]
+struct RootPayload<'a> {
+    scheme: &'static str,
+    root: &'a [u8; 32],
+}
+
+/// Standalone proof for a single seal field.
+///
+/// Contains the seal's signed message and public key, so it verifies on its
+/// own without the rest of the seal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldProof {
+    /// Disclosed field
+    pub field: SealField,
+    /// CBOR encoding of the field value
+    pub value: Vec<u8>,
+    /// Leaf salt
+    pub salt: [u8; 32],
+    /// Merkle path from the leaf to the signed root
+    pub path: Vec<ProofStep>,
+    /// Seal signature (ML-DSA-65 signed message over the root)
+    pub signature: Vec<u8>,
+    /// Seal public key
+    pub public_key: Vec<u8>,
+}
+
+impl FieldProof {
+    /// Verify the proof: the leaf must hash up to the root signed by `public_key`.
+    ///
+    /// Returns `Ok(false)` for any mismatch or malformed key/signature.
+    pub fn verify(&self) -> Result<bool> {
+        let leaf = leaf_hash(self.field, &self.salt, &self.value);
+        let expected = root_payload_bytes(&merkle::root_from_path(leaf, &self.path))?;
+
+        let Ok(public_key) = mldsa65::PublicKey::from_bytes(&self.public_key) else {
+            return Ok(false);
+        };
+        let Ok(signed_message) = mldsa65::SignedMessage::from_bytes(&self.signature) else {
+            return Ok(false);
+        };
+        Ok(mldsa65::open(&signed_message, &public_key)
+            .is_ok_and(|message| ct_eq(&message, &expected)))
+    }
+
+    /// Decode the disclosed value (e.g. `u64` for the capture timestamp).
+    pub fn value<T: DeserializeOwned>(&self) -> Result<T> {
+        ciborium::from_reader(self.value.as_slice())
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))
+    }
+}
+
+impl VeritasSeal {
+    /// Returns true if the seal was built with selective disclosure.
+    pub fn supports_selective_disclosure(&self) -> bool {
+        self.disclosure.is_some()
+    }
+
+    /// Copy of the seal with the given fields removed.
+    ///
+    /// Redacted fields keep their commitment, so the copy still verifies
+    /// (including counter-signatures). Fails if the seal was not built with
+    /// selective disclosure or a field is not [redactable](SealField::is_redactable).
+    pub fn redact(&self, fields: &[SealField]) -> Result<VeritasSeal> {
+        let disclosure = self.disclosure.as_ref().ok_or_else(no_disclosure)?;
+        let mut redacted = self.clone();
+        let mut openings = disclosure.fields.clone();
+
+        for &field in fields {
+            if !field.is_redactable() {
+                return Err(VeritasError::InvalidSeal(format!(
+                    "field {field} cannot be redacted"
+                )));
+            }
+            let opening = &mut openings[field.index()];
+            if let FieldOpening::Disclosed { salt } = opening {
+                let commitment = leaf_hash(field, salt, &field_value(self, field)?);
+                *opening = FieldOpening::Redacted { commitment };
+            }
+            match field {
+                SealField::CaptureLocation => redacted.capture_location = None,
+                SealField::DeviceAttestation => redacted.device_attestation = None,
+                SealField::EntropyCertificate => redacted.entropy_certificate = None,
+                _ => unreachable!("checked by is_redactable"),
+            }
+        }
+
+        redacted.disclosure = Some(SelectiveDisclosure { fields: openings });
+        Ok(redacted)
+    }
+
+    /// Extract a standalone, verifiable proof for one disclosed field.
+    pub fn disclosure_proof(&self, field: SealField) -> Result<FieldProof> {
+        let disclosure = self.disclosure.as_ref().ok_or_else(no_disclosure)?;
+        let salt = match disclosure.fields.get(field.index()) {
+            Some(FieldOpening::Disclosed { salt }) => *salt,
+            Some(FieldOpening::Redacted { .. }) => {
+                return Err(VeritasError::InvalidSeal(format!(
+                    "field {field} has been redacted"
+                )))
+            }
+            None => {
+                return Err(VeritasError::InvalidSeal(format!(
+                    "field {field} is not committed in this seal version"
+                )))
+            }
+        };
+
+        let leaves = leaves(self, disclosure)?;
+        Ok(FieldProof {
+            field,
+            value: field_value(self, field)?,
+            salt,
+            path: merkle::path(&leaves, field.index()),
+            signature: self.signature.clone(),
+            public_key: self.public_key.clone(),
+        })
+    }
+}
+
+/// Signed payload bytes for a disclosure seal (CBOR of the Merkle root).
+pub(crate) fn committed_payload_bytes(
+    seal: &VeritasSeal,
+    disclosure: &SelectiveDisclosure,
+) -> Result<Vec<u8>> {
+    disclosure.validate(seal)?;
+    let root = merkle::root(&leaves(seal, disclosure)?);
+    root_payload_bytes(&root)
+}
+
+fn root_payload_bytes(root: &[u8; 32]) -> Result<Vec<u8>> {
+    let payload = RootPayload {
+        scheme: DISCLOSURE_DOMAIN,
+        root,
+    };
+    let mut bytes = Vec::with_capacity(128);
+    ciborium::into_writer(&payload, &mut bytes)
+        .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+    Ok(bytes)
+}
+
+fn no_disclosure() -> VeritasError {
+    VeritasError::InvalidSeal("seal was not created with selective disclosure".into())
+}
+
+/// CBOR encoding of a field's current value in the seal.
+fn field_value(seal: &VeritasSeal, field: SealField) -> Result<Vec<u8>> {
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(64);
+        ciborium::into_writer(value, &mut bytes)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    match field {
+        SealField::CaptureTimestamp => encode(&seal.capture_timestamp_utc),
+        SealField::CaptureLocation => encode(&seal.capture_location),
+        SealField::DeviceAttestation => encode(&seal.device_attestation),
+        SealField::QrngEntropy => encode(&seal.qrng_entropy),
+        SealField::QrngSource => encode(&seal.qrng_source),
+        SealField::EntropyTimestamp => encode(&seal.entropy_timestamp),
+        SealField::QrngConditioning => encode(&seal.qrng_conditioning),
+        SealField::EntropyCertificate => encode(&seal.entropy_certificate),
+        SealField::ContentHash => encode(&seal.content_hash),
+        SealField::MediaType => encode(&seal.media_type),
+        SealField::ThresholdPolicy => encode(&seal.threshold_policy),
+        SealField::QrngPolicy => encode(&seal.qrng_policy),
+        SealField::NotBefore => encode(&seal.not_before),
+        SealField::NotAfter => encode(&seal.not_after),
+        SealField::CaptureNonce => encode(&seal.capture_nonce),
+        SealField::Liveness => encode(&seal.liveness),
+        SealField::KeyId => encode(&seal.key_id),
+        SealField::RawEntropyHash => encode(&seal.raw_entropy_hash),
+    }
+}
+
+fn leaves(seal: &VeritasSeal, disclosure: &SelectiveDisclosure) -> Result<Vec<[u8; 32]>> {
+    SealField::ALL
+        .into_iter()
+        .zip(&disclosure.fields)
+        .map(|(field, opening)| match opening {
+            FieldOpening::Disclosed { salt } => {
+                Ok(leaf_hash(field, salt, &field_value(seal, field)?))
+            }
+            FieldOpening::Redacted { commitment } => Ok(*commitment),
+        })
+        .collect()
+}
+
+fn leaf_hash(field: SealField, salt: &[u8; 32], value: &[u8]) -> [u8; 32] {
+    let name = field.name();
+    let mut hasher = Sha3_256::new();
+    hasher.update([0x00]);
+    hasher.update(DISCLOSURE_DOMAIN);
+    hasher.update([name.len() as u8]);
+    hasher.update(name);
+    hasher.update(salt);
+    hasher.update(value);
+    hasher.finalize().into()
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, generate_keypair_raw, DeviceAttestation, MediaType};
+    use crate::{CounterSignerRole, SealBuilder};
+
+    async fn disclosure_seal() -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(b"sensitive footage".to_vec(), MediaType::Video)
+            .with_location("u09tvw0".into())
+            .with_attestation(DeviceAttestation {
+                device_id: "source-phone".into(),
+                tee_type: "ARM_TRUSTZONE".into(),
+                attestation_token: vec![9; 16],
+            })
+            .with_selective_disclosure()
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal")
+    }
+
+    #[tokio::test]
+    async fn test_disclosure_seal_verifies_and_roundtrips() {
+        let seal = disclosure_seal().await;
+        assert!(seal.supports_selective_disclosure());
+        assert!(seal.verify().unwrap());
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_redacted_seal_still_verifies() {
+        let mut seal = disclosure_seal().await;
+        let (pk, sk) = generate_keypair_raw();
+        seal.countersign(CounterSignerRole::Editor, &sk, &pk)
+            .unwrap();
+
+        let redacted = seal
+            .redact(&[SealField::CaptureLocation, SealField::DeviceAttestation])
+            .unwrap();
+
+        assert!(redacted.capture_location.is_none());
+        assert!(redacted.device_attestation.is_none());
+        assert_eq!(
+            redacted.disclosure.as_ref().unwrap().redacted_fields(),
+            vec![SealField::CaptureLocation, SealField::DeviceAttestation]
+        );
+        assert!(redacted.verify().unwrap());
+        assert_eq!(redacted.verify_countersignatures().unwrap(), vec![true]);
+        assert!(redacted
+            .verify_content(b"sensitive footage")
+            .unwrap()
+            .is_authentic());
+
+        // Redacting twice is harmless
+        assert!(redacted
+            .redact(&[SealField::CaptureLocation])
+            .unwrap()
+            .verify()
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_tampering_with_disclosed_field_detected() {
+        let mut seal = disclosure_seal().await;
+        seal.capture_timestamp_utc += 1;
+        assert!(!seal.verify().unwrap());
+
+        // Re-adding a fake value behind a redaction is also detected
+        let mut redacted = disclosure_seal()
+            .await
+            .redact(&[SealField::CaptureLocation])
+            .unwrap();
+        redacted.disclosure.as_mut().unwrap().fields[SealField::CaptureLocation.index()] =
+            FieldOpening::Disclosed { salt: [0; 32] };
+        redacted.capture_location = Some("gbsuv7z".into());
+        assert!(!redacted.verify().unwrap());
+
+        // A value smuggled in next to a redaction is rejected
+        let mut redacted = disclosure_seal()
+            .await
+            .redact(&[SealField::CaptureLocation])
+            .unwrap();
+        redacted.capture_location = Some("gbsuv7z".into());
+        assert!(!redacted.verify().unwrap());
+        assert!(VeritasSeal::from_cbor(&redacted.to_cbor().unwrap()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validity_window_committed() {
+        let (public_key, secret_key) = generate_keypair();
+        let mut seal = SealBuilder::new(b"press pass".to_vec(), MediaType::Image)
+            .with_validity(None, Some(u64::MAX))
+            .with_selective_disclosure()
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        assert!(seal.verify().unwrap());
+        let proof = seal.disclosure_proof(SealField::NotAfter).unwrap();
+        assert_eq!(proof.value::<Option<u64>>().unwrap(), Some(u64::MAX));
+
+        seal.not_after = Some(u64::MAX - 1);
+        assert!(!seal.verify().unwrap());
+
+        // Openings from before version 11 cannot carry a window
+        let mut legacy = disclosure_seal().await;
+        legacy
+            .disclosure
+            .as_mut()
+            .unwrap()
+            .fields
+            .truncate(SealField::NotBefore.index());
+        legacy.not_after = Some(u64::MAX);
+        assert!(!legacy.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_only_optional_fields_redactable() {
+        let seal = disclosure_seal().await;
+        assert!(seal.redact(&[SealField::ContentHash]).is_err());
+        assert!(seal.redact(&[SealField::CaptureTimestamp]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_field_proofs_verify_standalone() {
+        let seal = disclosure_seal()
+            .await
+            .redact(&[SealField::CaptureLocation])
+            .unwrap();
+
+        for field in SealField::ALL {
+            if field == SealField::CaptureLocation {
+                assert!(seal.disclosure_proof(field).is_err());
+                continue;
+            }
+            let proof = seal.disclosure_proof(field).unwrap();
+            assert!(proof.verify().unwrap(), "{field}");
+        }
+
+        let proof = seal.disclosure_proof(SealField::CaptureTimestamp).unwrap();
+        assert_eq!(proof.value::<u64>().unwrap(), seal.capture_timestamp_utc);
+
+        let mut forged = proof.clone();
+        forged.value = {
+            let mut bytes = Vec::new();
+            ciborium::into_writer(&(seal.capture_timestamp_utc - 60_000), &mut bytes).unwrap();
+            bytes
+        };
+        assert!(!forged.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_plain_seal_has_no_disclosure() {
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"plain".to_vec(), MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        assert!(!seal.supports_selective_disclosure());
+        assert!(seal.redact(&[SealField::CaptureLocation]).is_err());
+        assert!(seal.disclosure_proof(SealField::MediaType).is_err());
+    }
+
+    #[test]
+    fn test_field_names_parse() {
+        for field in SealField::ALL {
+            assert_eq!(field.name().parse::<SealField>().unwrap(), field);
+        }
+        assert!("nonsense".parse::<SealField>().is_err());
+    }
+}
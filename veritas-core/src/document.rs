@@ -0,0 +1,690 @@
+//! Format-aware hashing for sealed documents.
+//!
+//! PDF viewers rewrite files all the time: filling a form field or simply
+//! saving appends an incremental update with a new cross-reference section,
+//! trailer and metadata. The raw SHA3 of the file then no longer matches even
+//! though no page changed.
+//!
+//! [`canonical_pdf_hash`] therefore hashes only the data streams of the
+//! document (page contents, images, fonts), taking the latest revision of each
+//! object and skipping XMP metadata, cross-reference streams and object
+//! streams. Dictionaries outside streams (document info, catalog, annotation
+//! dictionaries) are not covered, and a full rewrite that renumbers objects or
+//! re-compresses streams changes the hash.
+//!
+//! [`embed_seal_reference`] stores a seal in the document's XMP metadata as an
+//! incremental update. This leaves the canonical hash unchanged, so a PDF can
+//! carry its own seal and still verify.
+
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sha3::{Digest, Sha3_256};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::VeritasSeal;
+
+/// Domain separation tag for the canonical PDF hash.
+const PDF_DOMAIN: &str = "veritas-pdf-v1";
+
+/// XMP namespace for embedded seals.
+pub const XMP_NAMESPACE: &str = "https://veritas-q.io/ns/seal/1.0/";
+
+/// Stream types rewritten by viewers without changing the document content.
+const IGNORED_STREAM_TYPES: [&[u8]; 3] = [b"Metadata", b"XRef", b"ObjStm"];
+
+/// The PDF header may be preceded by up to this many bytes of junk.
+const HEADER_SEARCH_LIMIT: usize = 1024;
+
+/// Returns true if the content looks like a PDF file.
+pub fn is_pdf(content: &[u8]) -> bool {
+    find(&content[..content.len().min(HEADER_SEARCH_LIMIT)], b"%PDF-").is_some()
+}
+
+/// Hash the content streams of a PDF, ignoring incremental-save metadata.
+pub fn canonical_pdf_hash(pdf: &[u8]) -> Result<[u8; 32]> {
+    if !is_pdf(pdf) {
+        return Err(VeritasError::InvalidDocument("not a PDF file".into()));
+    }
+
+    // Incremental updates append new revisions of an object: the last wins
+    let mut latest = BTreeMap::new();
+    for object in objects(pdf) {
+        latest.insert(object.number, object);
+    }
+
+    let mut hasher = Sha3_256::new();
+    hasher.update(PDF_DOMAIN);
+    let mut streams = 0usize;
+    for object in latest.values() {
+        let (Some(dict), Some(data)) = (object.dict, object.stream) else {
+            continue;
+        };
+        let stream_type = dict_get(dict, b"Type").and_then(name);
+        if stream_type.is_some_and(|t| IGNORED_STREAM_TYPES.contains(&t)) {
+            continue;
+        }
+        hasher.update(object.number.to_be_bytes());
+        hasher.update((data.len() as u64).to_be_bytes());
+        hasher.update(data);
+        streams += 1;
+    }
+
+    if streams == 0 {
+        return Err(VeritasError::InvalidDocument(
+            "PDF has no content streams".into(),
+        ));
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Append an incremental update storing `seal` in the PDF's XMP metadata.
+///
+/// An existing uncompressed XMP packet is extended; otherwise a new packet
+/// replaces the catalog's metadata reference. Files whose catalog lives in a
+/// compressed object stream are not supported.
+pub fn embed_seal_reference(pdf: &[u8], seal: &VeritasSeal) -> Result<Vec<u8>> {
+    if !is_pdf(pdf) {
+        return Err(VeritasError::InvalidDocument("not a PDF file".into()));
+    }
+
+    let prev = last_startxref(pdf)
+        .ok_or_else(|| VeritasError::InvalidDocument("missing startxref".into()))?;
+    let trailer = trailer_dict(pdf, prev)
+        .ok_or_else(|| VeritasError::InvalidDocument("cannot read trailer".into()))?;
+    let (root, root_generation) = dict_get(trailer, b"Root")
+        .and_then(reference)
+        .ok_or_else(|| VeritasError::InvalidDocument("trailer has no /Root".into()))?;
+    let size = dict_get(trailer, b"Size")
+        .and_then(integer)
+        .and_then(|size| u32::try_from(size).ok())
+        .ok_or_else(|| VeritasError::InvalidDocument("trailer has no valid /Size".into()))?;
+
+    let objects = objects(pdf);
+    let latest = |number: u32| objects.iter().rev().find(|o| o.number == number);
+    let catalog = latest(root).and_then(|o| o.dict).ok_or_else(|| {
+        VeritasError::InvalidDocument(
+            "catalog not found (compressed object streams are not supported)".into(),
+        )
+    })?;
+
+    let existing_xmp = dict_get(catalog, b"Metadata")
+        .and_then(reference)
+        .and_then(|(number, _)| latest(number))
+        .filter(|o| o.dict.is_some_and(|d| dict_get(d, b"Filter").is_none()))
+        .and_then(|o| o.stream);
+    let xmp = xmp_packet(existing_xmp, seal)?;
+
+    let mut out = pdf.to_vec();
+    if !out.ends_with(b"\n") {
+        out.push(b'\n');
+    }
+
+    let metadata_offset = out.len();
+    out.extend_from_slice(
+        format!(
+            "{size} 0 obj\n<< /Type /Metadata /Subtype /XML /Length {} >>\nstream\n",
+            xmp.len()
+        )
+        .as_bytes(),
+    );
+    out.extend_from_slice(&xmp);
+    out.extend_from_slice(b"\nendstream\nendobj\n");
+
+    let catalog_offset = out.len();
+    out.extend_from_slice(format!("{root} {root_generation} obj\n").as_bytes());
+    out.extend_from_slice(&dict_with(
+        catalog,
+        b"Metadata",
+        format!("{size} 0 R").as_bytes(),
+    ));
+    out.extend_from_slice(b"\nendobj\n");
+
+    let xref_offset = out.len();
+    out.extend_from_slice(
+        format!(
+            "xref\n{root} 1\n{catalog_offset:010} {root_generation:05} n\r\n{size} 1\n{metadata_offset:010} 00000 n\r\n"
+        )
+        .as_bytes(),
+    );
+    let mut new_trailer = format!(
+        "trailer\n<< /Size {} /Root {root} {root_generation} R /Prev {prev}",
+        size + 1
+    )
+    .into_bytes();
+    for key in [b"Info".as_slice(), b"ID".as_slice()] {
+        if let Some(value) = dict_get(trailer, key) {
+            new_trailer.extend_from_slice(b" /");
+            new_trailer.extend_from_slice(key);
+            new_trailer.push(b' ');
+            new_trailer.extend_from_slice(value);
+        }
+    }
+    out.extend_from_slice(&new_trailer);
+    out.extend_from_slice(format!(" >>\nstartxref\n{xref_offset}\n%%EOF\n").as_bytes());
+
+    Ok(out)
+}
+
+/// Extract the most recently embedded seal from a PDF, if any.
+pub fn extract_seal_reference(pdf: &[u8]) -> Result<Option<VeritasSeal>> {
+    const OPEN: &[u8] = b"<veritas:Seal>";
+    const CLOSE: &[u8] = b"</veritas:Seal>";
+
+    let Some(start) = rfind(pdf, OPEN).map(|i| i + OPEN.len()) else {
+        return Ok(None);
+    };
+    let end = find(&pdf[start..], CLOSE)
+        .map(|i| start + i)
+        .ok_or_else(|| VeritasError::InvalidDocument("unterminated embedded seal".into()))?;
+
+    let encoded: Vec<u8> = pdf[start..end]
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    let cbor = BASE64
+        .decode(encoded)
+        .map_err(|e| VeritasError::InvalidDocument(format!("invalid embedded seal: {e}")))?;
+    VeritasSeal::from_cbor(&cbor).map(Some)
+}
+
+fn xmp_packet(existing: Option<&[u8]>, seal: &VeritasSeal) -> Result<Vec<u8>> {
+    let description = format!(
+        "<rdf:Description rdf:about=\"\" xmlns:veritas=\"{XMP_NAMESPACE}\">\n\
+         <veritas:SealHash>{}</veritas:SealHash>\n\
+         <veritas:Seal>{}</veritas:Seal>\n\
+         </rdf:Description>\n",
+        hex::encode(seal.seal_hash()),
+        BASE64.encode(seal.to_cbor()?)
+    );
+
+    if let Some(existing) = existing {
+        if let Some(at) = rfind(existing, b"</rdf:RDF>") {
+            let mut packet = existing[..at].to_vec();
+            packet.extend_from_slice(description.as_bytes());
+            packet.extend_from_slice(&existing[at..]);
+            return Ok(packet);
+        }
+    }
+
+    Ok(format!(
+        "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+         <x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n\
+         <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n\
+         {description}\
+         </rdf:RDF>\n\
+         </x:xmpmeta>\n\
+         <?xpacket end=\"w\"?>"
+    )
+    .into_bytes())
+}
+
+// ---------------------------------------------------------------------------
+// Minimal PDF object scanner
+
+/// An indirect object found in the file body.
+struct PdfObject<'a> {
+    number: u32,
+    /// Object dictionary, including the `<<` and `>>` delimiters
+    dict: Option<&'a [u8]>,
+    /// Raw (still encoded) stream data
+    stream: Option<&'a [u8]>,
+}
+
+/// All `N G obj` definitions, in file order.
+fn objects(pdf: &[u8]) -> Vec<PdfObject<'_>> {
+    let mut objects = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = find(&pdf[pos..], b"obj") {
+        let keyword = pos + found;
+        pos = keyword + 3;
+        if pdf.get(pos).is_some_and(|&b| is_regular(b)) {
+            continue;
+        }
+        let Some(number) = object_number(pdf, keyword) else {
+            continue;
+        };
+
+        let body = skip_whitespace(pdf, pos);
+        let dict = pdf[body..]
+            .starts_with(b"<<")
+            .then(|| value_end(pdf, body))
+            .flatten()
+            .map(|end| &pdf[body..end]);
+
+        let mut stream = None;
+        if let Some(dict) = dict {
+            let after = skip_whitespace(pdf, body + dict.len());
+            if pdf[after..].starts_with(b"stream") {
+                if let Some((data, end)) = stream_data(pdf, after + b"stream".len(), dict) {
+                    stream = Some(data);
+                    // Binary stream data may contain anything, including "obj"
+                    pos = end;
+                }
+            }
+        }
+
+        objects.push(PdfObject {
+            number,
+            dict,
+            stream,
+        });
+    }
+
+    objects
+}
+
+/// Parse the `N G` before an `obj` keyword at `keyword`.
+fn object_number(pdf: &[u8], keyword: usize) -> Option<u32> {
+    let generation_end = rskip_whitespace(pdf, keyword);
+    if generation_end == keyword {
+        return None;
+    }
+    let generation_start = rskip_digits(pdf, generation_end);
+    let number_end = rskip_whitespace(pdf, generation_start);
+    if generation_start == generation_end || number_end == generation_start {
+        return None;
+    }
+    let number_start = rskip_digits(pdf, number_end);
+    if number_start == number_end || (number_start > 0 && is_regular(pdf[number_start - 1])) {
+        return None;
+    }
+    std::str::from_utf8(&pdf[number_start..number_end])
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Locate stream data following the `stream` keyword.
+///
+/// Returns the data and the position after `endstream`.
+fn stream_data<'a>(pdf: &'a [u8], mut start: usize, dict: &[u8]) -> Option<(&'a [u8], usize)> {
+    // The keyword is followed by CRLF or LF (a lone CR is tolerated)
+    if pdf[start..].starts_with(b"\r\n") {
+        start += 2;
+    } else if matches!(pdf.get(start), Some(b'\n' | b'\r')) {
+        start += 1;
+    }
+
+    // Trust a direct /Length when it lands on `endstream`
+    if let Some(length) = dict_get(dict, b"Length").and_then(integer) {
+        let end = usize::try_from(length)
+            .ok()
+            .and_then(|l| start.checked_add(l));
+        if let Some(end) = end.filter(|&end| end <= pdf.len()) {
+            let keyword = skip_whitespace(pdf, end);
+            if pdf[keyword..].starts_with(b"endstream") {
+                return Some((&pdf[start..end], keyword + b"endstream".len()));
+            }
+        }
+    }
+
+    let keyword = start + find(&pdf[start..], b"endstream")?;
+    let mut end = keyword;
+    if pdf[..end].ends_with(b"\r\n") {
+        end -= 2;
+    } else if end > start && matches!(pdf[end - 1], b'\n' | b'\r') {
+        end -= 1;
+    }
+    Some((&pdf[start..end.max(start)], keyword + b"endstream".len()))
+}
+
+/// Offset recorded by the last `startxref`.
+fn last_startxref(pdf: &[u8]) -> Option<usize> {
+    let at = rfind(pdf, b"startxref")? + b"startxref".len();
+    let start = skip_whitespace(pdf, at);
+    let end = start
+        + pdf[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+    std::str::from_utf8(&pdf[start..end]).ok()?.parse().ok()
+}
+
+/// Trailer dictionary of the cross-reference section at `offset`: either the
+/// `trailer` of a classic table or the dictionary of a cross-reference stream.
+fn trailer_dict(pdf: &[u8], offset: usize) -> Option<&[u8]> {
+    let section = pdf.get(offset..)?;
+    let start = if section.starts_with(b"xref") {
+        offset + find(section, b"trailer")? + b"trailer".len()
+    } else {
+        offset + find(section, b"obj")? + b"obj".len()
+    };
+    let start = skip_whitespace(pdf, start);
+    if !pdf[start..].starts_with(b"<<") {
+        return None;
+    }
+    value_end(pdf, start).map(|end| &pdf[start..end])
+}
+
+/// Rebuild `dict` with `key` set to `value`.
+fn dict_with(dict: &[u8], key: &[u8], value: &[u8]) -> Vec<u8> {
+    let mut out = b"<<".to_vec();
+    for (name, existing) in dict_entries(dict) {
+        if name == key {
+            continue;
+        }
+        out.extend_from_slice(b" /");
+        out.extend_from_slice(name);
+        out.push(b' ');
+        out.extend_from_slice(existing);
+    }
+    out.extend_from_slice(b" /");
+    out.extend_from_slice(key);
+    out.push(b' ');
+    out.extend_from_slice(value);
+    out.extend_from_slice(b" >>");
+    out
+}
+
+/// Raw value of a top-level dictionary entry.
+fn dict_get<'a>(dict: &'a [u8], key: &[u8]) -> Option<&'a [u8]> {
+    dict_entries(dict)
+        .into_iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| value)
+}
+
+/// Top-level `(key, raw value)` pairs of a dictionary.
+fn dict_entries(dict: &[u8]) -> Vec<(&[u8], &[u8])> {
+    let mut entries = Vec::new();
+    let mut pos = 2;
+
+    loop {
+        pos = skip_whitespace(dict, pos);
+        if dict.get(pos) != Some(&b'/') {
+            break;
+        }
+        let key_end = token_end(dict, pos + 1);
+        let key = &dict[pos + 1..key_end];
+
+        let value_start = skip_whitespace(dict, key_end);
+        let Some(value_end) = value_end(dict, value_start) else {
+            break;
+        };
+        entries.push((key, &dict[value_start..value_end]));
+        pos = value_end;
+    }
+
+    entries
+}
+
+/// End of the PDF value starting at `pos`.
+fn value_end(data: &[u8], pos: usize) -> Option<usize> {
+    match *data.get(pos)? {
+        b'/' => Some(token_end(data, pos + 1)),
+        b'<' if data.get(pos + 1) == Some(&b'<') => {
+            let mut cursor = pos + 2;
+            loop {
+                cursor = skip_whitespace(data, cursor);
+                if data[cursor..].starts_with(b">>") {
+                    return Some(cursor + 2);
+                }
+                cursor = value_end(data, cursor)?;
+            }
+        }
+        b'<' => Some(pos + find(&data[pos..], b">")? + 1),
+        b'[' => {
+            let mut cursor = pos + 1;
+            loop {
+                cursor = skip_whitespace(data, cursor);
+                if *data.get(cursor)? == b']' {
+                    return Some(cursor + 1);
+                }
+                cursor = value_end(data, cursor)?;
+            }
+        }
+        b'(' => literal_string_end(data, pos),
+        b'>' | b']' | b')' => None,
+        _ => {
+            let end = token_end(data, pos);
+            if end == pos {
+                return None;
+            }
+            // An indirect reference `N G R` is a single value
+            Some(reference_end(data, pos, end).unwrap_or(end))
+        }
+    }
+}
+
+/// If `N` at `start..end` begins an `N G R` reference, its end.
+fn reference_end(data: &[u8], start: usize, end: usize) -> Option<usize> {
+    if !data[start..end].iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let generation = skip_whitespace(data, end);
+    let generation_end = token_end(data, generation);
+    if generation_end == generation
+        || !data[generation..generation_end]
+            .iter()
+            .all(u8::is_ascii_digit)
+    {
+        return None;
+    }
+    let keyword = skip_whitespace(data, generation_end);
+    (data.get(keyword) == Some(&b'R') && token_end(data, keyword) == keyword + 1)
+        .then_some(keyword + 1)
+}
+
+fn literal_string_end(data: &[u8], pos: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut cursor = pos;
+    while cursor < data.len() {
+        match data[cursor] {
+            b'\\' => cursor += 1,
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(cursor + 1);
+                }
+            }
+            _ => {}
+        }
+        cursor += 1;
+    }
+    None
+}
+
+fn name(value: &[u8]) -> Option<&[u8]> {
+    value.strip_prefix(b"/")
+}
+
+fn integer(value: &[u8]) -> Option<u64> {
+    std::str::from_utf8(value).ok()?.parse().ok()
+}
+
+fn reference(value: &[u8]) -> Option<(u32, u16)> {
+    let text = std::str::from_utf8(value).ok()?;
+    let mut parts = text.split_ascii_whitespace();
+    let number = parts.next()?.parse().ok()?;
+    let generation = parts.next()?.parse().ok()?;
+    (parts.next()? == "R" && parts.next().is_none()).then_some((number, generation))
+}
+
+fn token_end(data: &[u8], pos: usize) -> usize {
+    pos + data[pos.min(data.len())..]
+        .iter()
+        .take_while(|&&b| is_regular(b))
+        .count()
+}
+
+fn skip_whitespace(data: &[u8], mut pos: usize) -> usize {
+    while pos < data.len() {
+        match data[pos] {
+            b if is_whitespace(b) => pos += 1,
+            // Comments run to the end of the line
+            b'%' => {
+                while pos < data.len() && !matches!(data[pos], b'\r' | b'\n') {
+                    pos += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+    pos
+}
+
+fn rskip_whitespace(data: &[u8], mut pos: usize) -> usize {
+    while pos > 0 && is_whitespace(data[pos - 1]) {
+        pos -= 1;
+    }
+    pos
+}
+
+fn rskip_digits(data: &[u8], mut pos: usize) -> usize {
+    while pos > 0 && data[pos - 1].is_ascii_digit() {
+        pos -= 1;
+    }
+    pos
+}
+
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b'\0' | b'\t' | b'\n' | b'\x0c' | b'\r' | b' ')
+}
+
+fn is_regular(b: u8) -> bool {
+    !is_whitespace(b) && !b"()<>[]{}/%".contains(&b)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a small PDF with a classic cross-reference table.
+    fn sample_pdf(page_text: &str) -> Vec<u8> {
+        let content = format!("BT /F1 24 Tf 72 720 Td ({page_text}) Tj ET");
+        let bodies = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R >>".to_string(),
+            format!(
+                "<< /Length {} >>\nstream\n{content}\nendstream",
+                content.len()
+            ),
+            "<< /Title (Report) /ModDate (D:20260101000000Z) >>".to_string(),
+        ];
+
+        let mut pdf = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, body) in bodies.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n{body}\nendobj\n", i + 1).as_bytes());
+        }
+        let xref = pdf.len();
+        pdf.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f\r\n", bodies.len() + 1).as_bytes(),
+        );
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{offset:010} 00000 n\r\n").as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+                bodies.len() + 1
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+
+    /// Simulate a viewer save: a new document info dictionary appended as an
+    /// incremental update.
+    fn resave(pdf: &[u8]) -> Vec<u8> {
+        let prev = last_startxref(pdf).unwrap();
+        let mut out = pdf.to_vec();
+        let offset = out.len();
+        out.extend_from_slice(
+            b"5 0 obj\n<< /Title (Report) /ModDate (D:20260301120000Z) /Producer (Viewer) >>\nendobj\n",
+        );
+        let xref = out.len();
+        out.extend_from_slice(
+            format!(
+                "xref\n5 1\n{offset:010} 00000 n\r\ntrailer\n<< /Size 6 /Root 1 0 R /Info 5 0 R /Prev {prev} >>\nstartxref\n{xref}\n%%EOF\n"
+            )
+            .as_bytes(),
+        );
+        out
+    }
+
+    #[test]
+    fn test_canonical_hash_ignores_incremental_save() {
+        let pdf = sample_pdf("Hello");
+        let resaved = resave(&pdf);
+
+        assert_ne!(pdf, resaved);
+        assert_eq!(
+            canonical_pdf_hash(&pdf).unwrap(),
+            canonical_pdf_hash(&resaved).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_canonical_hash_detects_content_change() {
+        assert_ne!(
+            canonical_pdf_hash(&sample_pdf("Hello")).unwrap(),
+            canonical_pdf_hash(&sample_pdf("Jello")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_pdf() {
+        assert!(!is_pdf(b"GIF89a"));
+        assert!(canonical_pdf_hash(b"plain text").is_err());
+        assert!(canonical_pdf_hash(b"%PDF-1.7\n%%EOF\n").is_err());
+    }
+
+    #[test]
+    fn test_dict_entries() {
+        let dict = b"<< /Type /Page /Kids [3 0 R (a]b)] /Parent 2 0 R /Sub << /X 1 >> /N 7 >>";
+        assert_eq!(dict_get(dict, b"Type"), Some(b"/Page".as_slice()));
+        assert_eq!(dict_get(dict, b"Kids"), Some(b"[3 0 R (a]b)]".as_slice()));
+        assert_eq!(dict_get(dict, b"Parent").and_then(reference), Some((2, 0)));
+        assert_eq!(dict_get(dict, b"Sub"), Some(b"<< /X 1 >>".as_slice()));
+        assert_eq!(dict_get(dict, b"N").and_then(integer), Some(7));
+        assert_eq!(dict_get(dict, b"X"), None);
+    }
+
+    #[cfg(feature = "network")]
+    #[tokio::test]
+    async fn test_embedded_seal_survives_viewer_save() {
+        use crate::qrng::MockQrng;
+        use crate::seal::{generate_keypair, MediaType, SealBuilder};
+
+        let pdf = sample_pdf("Hello");
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(pdf.clone(), MediaType::Document)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        assert!(extract_seal_reference(&pdf).unwrap().is_none());
+
+        let sealed = embed_seal_reference(&pdf, &seal).unwrap();
+        assert!(sealed.starts_with(&pdf));
+        let embedded = extract_seal_reference(&sealed).unwrap().unwrap();
+        assert_eq!(embedded.seal_hash(), seal.seal_hash());
+
+        assert!(embedded.verify_content(&sealed).unwrap().is_authentic());
+        assert!(embedded
+            .verify_content(&resave(&sealed))
+            .unwrap()
+            .is_authentic());
+
+        let tampered = sample_pdf("Jello");
+        assert!(!embedded.verify_content(&tampered).unwrap().is_authentic());
+    }
+}
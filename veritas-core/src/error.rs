@@ -1,10 +1,22 @@
 use thiserror::Error;
 
-/// Current seal format version.
-pub const CURRENT_SEAL_VERSION: u8 = 1;
+/// Current seal format version (see [`crate::schema`] for the history).
+pub const CURRENT_SEAL_VERSION: u8 = 17;
 
-/// Maximum allowed seal size in bytes (16KB).
-pub const MAX_SEAL_SIZE: usize = 16_384;
+/// Maximum allowed seal size in bytes (128KB).
+///
+/// A plain seal is ~11KB of CBOR. An entropy certificate carries a second
+/// ML-DSA-65 key and signature, and is counted twice (once as a field, once
+/// inside the signed message), bringing a certified seal to ~41KB. Each
+/// counter-signature adds another ~10KB.
+pub const MAX_SEAL_SIZE: usize = 131_072;
+
+/// Maximum allowed size of a JSON-encoded seal in bytes (1MB).
+///
+/// JSON spells every byte of a key or signature as a decimal number, so a
+/// seal takes about four times its CBOR size, and twice that again when
+/// pretty-printed.
+pub const MAX_JSON_SEAL_SIZE: usize = 8 * MAX_SEAL_SIZE;
 
 #[derive(Error, Debug)]
 pub enum VeritasError {
@@ -39,10 +51,23 @@ pub enum VeritasError {
     #[error("Invalid timestamp: {reason}")]
     InvalidTimestamp { reason: String },
 
+    #[error("Invalid document: {0}")]
+    InvalidDocument(String),
+
+    #[error("Anchor error: {0}")]
+    AnchorError(String),
+
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+
     #[cfg(feature = "perceptual-hash")]
     #[error("Perceptual hash error: {0}")]
     PerceptualHashError(String),
 
+    #[cfg(feature = "perceptual-hash")]
+    #[error("Watermark error: {0}")]
+    WatermarkError(String),
+
     #[cfg(feature = "network")]
     #[error("HTTP request error: {0}")]
     HttpError(#[from] reqwest::Error),
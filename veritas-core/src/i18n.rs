@@ -0,0 +1,333 @@
+//! Localized verification messages.
+//!
+//! Every message shown to end users about a verification has a stable
+//! [`MessageCode`] (`content_modified`, `mock_entropy`, ...) that clients can
+//! key on, and a text in each supported [`Locale`]. The `description()`
+//! methods of [`VerificationResult`](crate::VerificationResult),
+//! [`ContentVerificationResult`](crate::ContentVerificationResult) and
+//! [`PolicyViolation`](crate::PolicyViolation) return the English text;
+//! their `description_in()` counterparts take a locale.
+//!
+//! # Example
+//!
+//! ```
+//! use veritas_core::i18n::{Locale, MessageCode};
+//!
+//! let locale = Locale::from_accept_language("fr-CH, fr;q=0.9, en;q=0.8");
+//! assert_eq!(locale, Some(Locale::Fr));
+//! assert_eq!(
+//!     MessageCode::MockEntropy.message(Locale::Fr),
+//!     "Le sceau a été créé avec une entropie simulée - non résistant au quantique"
+//! );
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Language of verification messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// English
+    #[default]
+    En,
+    /// French
+    Fr,
+}
+
+impl Locale {
+    /// All supported locales
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Fr];
+
+    /// Language subtag (`en`, `fr`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Fr => "fr",
+        }
+    }
+
+    /// Locale of a language tag such as `fr`, `fr-CA` or `en_US`
+    /// (case-insensitive), or `None` if the language is not supported.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_']).next()?;
+        Self::ALL
+            .into_iter()
+            .find(|locale| language.eq_ignore_ascii_case(locale.as_str()))
+    }
+
+    /// Preferred supported locale of an `Accept-Language` header, by quality
+    /// value, or `None` if it lists none of them.
+    pub fn from_accept_language(header: &str) -> Option<Self> {
+        let mut best: Option<(Self, f32)> = None;
+        for range in header.split(',') {
+            let mut parts = range.split(';');
+            let Some(locale) = parts.next().and_then(Self::parse) else {
+                continue;
+            };
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            // Ranges with equal quality keep the header's order
+            if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((locale, quality));
+            }
+        }
+        best.map(|(locale, _)| locale)
+    }
+}
+
+/// Stable identifier of a verification message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageCode {
+    /// The signature is valid
+    SignatureValid,
+    /// The ML-DSA signature does not verify
+    InvalidSignature,
+    /// The signed payload differs from the seal's data
+    PayloadMismatch,
+    /// The seal's public key is malformed
+    InvalidPublicKey,
+    /// The signature is malformed
+    MalformedSignature,
+    /// The entropy certificate does not verify
+    InvalidEntropyCertificate,
+    /// Too few valid co-signatures for the threshold policy
+    ThresholdNotMet,
+    /// The seal is outside its validity window
+    Expired,
+    /// Signature valid and content unchanged
+    ContentAuthentic,
+    /// The content hash differs from the sealed one
+    ContentModified,
+    /// Segments of a segmented recording differ (`{count}` of them)
+    SegmentsModified,
+    /// The content hash differs but the image looks the same (re-encoded)
+    LikelyRecompressed,
+    /// The policy requires a device attestation and the seal has none
+    MissingDeviceAttestation,
+    /// The seal was made with the mock QRNG
+    MockEntropy,
+    /// No anchor of the seal is confirmed
+    AnchorNotConfirmed,
+    /// The signer's key registry does not vouch for the seal's key
+    UntrustedSigningKey,
+}
+
+impl MessageCode {
+    /// Code as serialized (`content_modified`, ...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SignatureValid => "signature_valid",
+            Self::InvalidSignature => "invalid_signature",
+            Self::PayloadMismatch => "payload_mismatch",
+            Self::InvalidPublicKey => "invalid_public_key",
+            Self::MalformedSignature => "malformed_signature",
+            Self::InvalidEntropyCertificate => "invalid_entropy_certificate",
+            Self::ThresholdNotMet => "threshold_not_met",
+            Self::Expired => "expired",
+            Self::ContentAuthentic => "content_authentic",
+            Self::ContentModified => "content_modified",
+            Self::SegmentsModified => "segments_modified",
+            Self::LikelyRecompressed => "likely_recompressed",
+            Self::MissingDeviceAttestation => "missing_device_attestation",
+            Self::MockEntropy => "mock_entropy",
+            Self::AnchorNotConfirmed => "anchor_not_confirmed",
+            Self::UntrustedSigningKey => "untrusted_signing_key",
+        }
+    }
+
+    /// Text of the message in `locale`.
+    ///
+    /// [`MessageCode::SegmentsModified`] contains a `{count}` placeholder.
+    pub fn message(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::En => self.english(),
+            Locale::Fr => self.french(),
+        }
+    }
+
+    fn english(&self) -> &'static str {
+        match self {
+            Self::SignatureValid => "Signature is valid",
+            Self::InvalidSignature => "Signature verification failed - seal may be forged",
+            Self::PayloadMismatch => "Payload mismatch - seal data may have been modified",
+            Self::InvalidPublicKey => "Public key in seal is malformed",
+            Self::MalformedSignature => "Signature format is invalid",
+            Self::InvalidEntropyCertificate => {
+                "Entropy certificate is invalid - entropy origin cannot be confirmed"
+            }
+            Self::ThresholdNotMet => "Not enough valid co-signatures for the threshold policy",
+            Self::Expired => "Seal is outside its validity window - expired or not yet valid",
+            Self::ContentAuthentic => "Content is authentic - signature valid and hash matches",
+            Self::ContentModified => "Content has been modified since sealing - hash mismatch",
+            Self::SegmentsModified => {
+                "Content has been modified since sealing - {count} segment(s) differ"
+            }
+            Self::LikelyRecompressed => {
+                "Content differs from the sealed file but looks the same - likely recompressed \
+                 or resized"
+            }
+            Self::MissingDeviceAttestation => "Seal has no device attestation",
+            Self::MockEntropy => "Seal was made with mock entropy - not quantum-safe",
+            Self::AnchorNotConfirmed => "No blockchain anchor of the seal is confirmed",
+            Self::UntrustedSigningKey => {
+                "Signing key is revoked, not registered under this ID or was not valid at \
+                 capture time"
+            }
+        }
+    }
+
+    fn french(&self) -> &'static str {
+        match self {
+            Self::SignatureValid => "La signature est valide",
+            Self::InvalidSignature => {
+                "Échec de la vérification de la signature - le sceau est peut-être falsifié"
+            }
+            Self::PayloadMismatch => {
+                "Données signées différentes - le sceau a peut-être été modifié"
+            }
+            Self::InvalidPublicKey => "La clé publique du sceau est mal formée",
+            Self::MalformedSignature => "Le format de la signature est invalide",
+            Self::InvalidEntropyCertificate => {
+                "Le certificat d'entropie est invalide - l'origine de l'entropie ne peut pas \
+                 être confirmée"
+            }
+            Self::ThresholdNotMet => {
+                "Pas assez de co-signatures valides pour la politique de seuil"
+            }
+            Self::Expired => {
+                "Le sceau est hors de sa période de validité - expiré ou pas encore valide"
+            }
+            Self::ContentAuthentic => {
+                "Le contenu est authentique - signature valide et empreinte identique"
+            }
+            Self::ContentModified => {
+                "Le contenu a été modifié depuis le scellement - empreinte différente"
+            }
+            Self::SegmentsModified => {
+                "Le contenu a été modifié depuis le scellement - {count} segment(s) différent(s)"
+            }
+            Self::LikelyRecompressed => {
+                "Le contenu diffère du fichier scellé mais semble identique - probablement \
+                 recompressé ou redimensionné"
+            }
+            Self::MissingDeviceAttestation => "Le sceau n'a pas d'attestation d'appareil",
+            Self::MockEntropy => {
+                "Le sceau a été créé avec une entropie simulée - non résistant au quantique"
+            }
+            Self::AnchorNotConfirmed => "Aucun ancrage blockchain du sceau n'est confirmé",
+            Self::UntrustedSigningKey => {
+                "La clé de signature est révoquée, n'est pas enregistrée sous cet identifiant \
+                 ou n'était pas valide lors de la capture"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for MessageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_CODES: [MessageCode; 16] = [
+        MessageCode::SignatureValid,
+        MessageCode::InvalidSignature,
+        MessageCode::PayloadMismatch,
+        MessageCode::InvalidPublicKey,
+        MessageCode::MalformedSignature,
+        MessageCode::InvalidEntropyCertificate,
+        MessageCode::ThresholdNotMet,
+        MessageCode::Expired,
+        MessageCode::ContentAuthentic,
+        MessageCode::ContentModified,
+        MessageCode::SegmentsModified,
+        MessageCode::LikelyRecompressed,
+        MessageCode::MissingDeviceAttestation,
+        MessageCode::MockEntropy,
+        MessageCode::AnchorNotConfirmed,
+        MessageCode::UntrustedSigningKey,
+    ];
+
+    #[test]
+    fn test_locale_negotiation() {
+        assert_eq!(Locale::parse("fr"), Some(Locale::Fr));
+        assert_eq!(Locale::parse(" FR-ca "), Some(Locale::Fr));
+        assert_eq!(Locale::parse("en_US"), Some(Locale::En));
+        assert_eq!(Locale::parse("de"), None);
+        assert_eq!(Locale::parse(""), None);
+
+        assert_eq!(
+            Locale::from_accept_language("fr-FR,fr;q=0.9"),
+            Some(Locale::Fr)
+        );
+        assert_eq!(
+            Locale::from_accept_language("de-DE, fr;q=0.5, en;q=0.7"),
+            Some(Locale::En)
+        );
+        assert_eq!(Locale::from_accept_language("en, fr"), Some(Locale::En));
+        assert_eq!(
+            Locale::from_accept_language("fr;q=0, en;q=0.1"),
+            Some(Locale::En)
+        );
+        assert_eq!(Locale::from_accept_language("de, *;q=0.5"), None);
+    }
+
+    #[test]
+    fn test_every_code_is_translated() {
+        for code in ALL_CODES {
+            let json = serde_json::to_string(&code).unwrap();
+            assert_eq!(json, format!("\"{}\"", code.as_str()));
+            for locale in Locale::ALL {
+                assert!(!code.message(locale).is_empty(), "{} in {:?}", code, locale);
+            }
+            assert_ne!(code.message(Locale::En), code.message(Locale::Fr));
+        }
+        assert!(MessageCode::SegmentsModified
+            .message(Locale::Fr)
+            .contains("{count}"));
+    }
+
+    #[test]
+    fn test_localized_descriptions() {
+        use crate::{
+            ContentVerificationResult, PolicyViolation, TamperedSegment, VerificationResult,
+        };
+
+        let modified = ContentVerificationResult::ContentModified {
+            expected_hash: [0; 32],
+            actual_hash: [1; 32],
+            tampered_segments: Some(vec![TamperedSegment {
+                index: 3,
+                byte_range: 0..10,
+                time_range_ms: None,
+            }]),
+        };
+        assert_eq!(modified.code(), MessageCode::SegmentsModified);
+        assert_eq!(
+            modified.description(),
+            "Content has been modified since sealing - 1 segment(s) differ"
+        );
+        assert_eq!(
+            modified.description_in(Locale::Fr),
+            "Le contenu a été modifié depuis le scellement - 1 segment(s) différent(s)"
+        );
+
+        let forged = ContentVerificationResult::SignatureFailed(VerificationResult::Expired);
+        assert_eq!(forged.code(), MessageCode::Expired);
+        assert_eq!(
+            forged.description_in(Locale::Fr),
+            VerificationResult::Expired.description_in(Locale::Fr)
+        );
+        assert_eq!(
+            PolicyViolation::MockEntropy.description(),
+            "Seal was made with mock entropy - not quantum-safe"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! JWS (JSON Web Signature) claims of seals.
+//!
+//! Lets seals flow through JOSE tooling: [`SealClaims`] are the seal as JWT
+//! claims (registered claims for the issuer, subject and validity window,
+//! the seal fields as private claims), signed ES256 by the exporter into a
+//! compact JWS. JOSE has no ML-DSA algorithm yet, so the seal's own
+//! ML-DSA-65 signature travels as a claim, with the signed seal itself
+//! (CBOR): verifiers check the ES256 signature with their JWT library, then
+//! decode the seal with [`SealClaims::seal`], which checks the claims match
+//! it, and verify the seal like any other.
+//!
+//! The claims are derived from the seal alone, so exporting a seal twice
+//! yields the same payload.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::VeritasSeal;
+
+/// Media type of compact JWS seal exports.
+pub const JWT_MEDIA_TYPE: &str = "application/jwt";
+
+/// A seal as JWT claims.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealClaims {
+    /// Issuer (the exporter's DID)
+    pub iss: String,
+    /// `urn:sha3-256:` URN of the content hash
+    pub sub: String,
+    /// Capture time (Unix seconds)
+    pub iat: u64,
+    /// Start of the seal's validity window (Unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<u64>,
+    /// End of the seal's validity window (Unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    /// Media type of the content
+    pub media_type: String,
+    /// SHA3-256 of the content (hex)
+    pub content_hash: String,
+    /// Perceptual hash (hex, images only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub perceptual_hash: Option<String>,
+    /// Hash of the canonical form of documents (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_hash: Option<String>,
+    /// Capture time (Unix ms)
+    pub capture_timestamp: u64,
+    /// Time the QRNG entropy was fetched (Unix ms)
+    pub entropy_timestamp: u64,
+    /// QRNG entropy bound into the seal (hex)
+    pub qrng_entropy: String,
+    /// QRNG source
+    pub qrng_source: String,
+    /// Registry ID of the signing key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    /// ML-DSA-65 public key (base64url)
+    pub ml_dsa_public_key: String,
+    /// ML-DSA-65 signature of the seal (base64url)
+    pub ml_dsa_signature: String,
+    /// The signed seal as CBOR (base64url)
+    pub seal: String,
+}
+
+impl SealClaims {
+    /// Claims of `seal`, issued by `issuer`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the seal cannot be serialized.
+    pub fn from_seal(seal: &VeritasSeal, issuer: &str) -> Result<Self> {
+        let content_hash = hex::encode(seal.content_hash.crypto_hash);
+        Ok(Self {
+            iss: issuer.to_string(),
+            sub: format!("urn:sha3-256:{content_hash}"),
+            iat: seal.capture_timestamp_utc / 1000,
+            nbf: seal.not_before.map(|ms| ms / 1000),
+            exp: seal.not_after.map(|ms| ms / 1000),
+            media_type: seal.media_type.to_string(),
+            content_hash,
+            perceptual_hash: seal.content_hash.perceptual_hash.as_ref().map(hex::encode),
+            canonical_hash: seal.content_hash.canonical_hash.map(hex::encode),
+            capture_timestamp: seal.capture_timestamp_utc,
+            entropy_timestamp: seal.entropy_timestamp,
+            qrng_entropy: hex::encode(seal.qrng_entropy),
+            qrng_source: seal.qrng_source.to_string(),
+            key_id: seal.key_id.clone(),
+            ml_dsa_public_key: URL_SAFE_NO_PAD.encode(&seal.public_key),
+            ml_dsa_signature: URL_SAFE_NO_PAD.encode(&seal.signature),
+            seal: URL_SAFE_NO_PAD.encode(seal.to_cbor()?),
+        })
+    }
+
+    /// Claims of a compact JWS (`header.payload.signature`).
+    ///
+    /// The JWS signature is **not** checked: verify the token with a JWT
+    /// library first, or treat the claims as untrusted.
+    pub fn from_compact_jws(token: &str) -> Result<Self> {
+        let invalid = |reason: &str| VeritasError::InvalidSeal(format!("invalid JWS: {reason}"));
+        let mut parts = token.trim().split('.');
+        let (Some(_header), Some(payload), Some(_signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid("expected three dot-separated parts"));
+        };
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| invalid("payload is not base64url"))?;
+        serde_json::from_slice(&payload).map_err(|e| invalid(&e.to_string()))
+    }
+
+    /// Decode the seal carried by the claims, checking the other claims
+    /// match it.
+    ///
+    /// The seal's signature is not checked: verify the returned seal (and
+    /// the content) as usual.
+    pub fn seal(&self) -> Result<VeritasSeal> {
+        let cbor = URL_SAFE_NO_PAD
+            .decode(&self.seal)
+            .map_err(|_| VeritasError::InvalidSeal("JWS seal claim is not base64url".into()))?;
+        let seal = VeritasSeal::from_cbor(&cbor)?;
+        if Self::from_seal(&seal, &self.iss)? != *self {
+            return Err(VeritasError::InvalidSeal(
+                "JWS claims do not match the seal they carry".into(),
+            ));
+        }
+        Ok(seal)
+    }
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, MediaType, SealBuilder};
+
+    async fn sealed() -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(b"JWS".to_vec(), MediaType::Image)
+            .with_validity(Some(1_767_225_600_000), Some(4_102_444_800_500))
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap()
+    }
+
+    /// Compact JWS with `claims` as payload and a dummy signature.
+    fn compact(claims: &SealClaims) -> String {
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims).unwrap());
+        format!("eyJhbGciOiJFUzI1NiJ9.{payload}.c2ln")
+    }
+
+    #[tokio::test]
+    async fn test_claims_round_trip() {
+        let seal = sealed().await;
+        let claims = SealClaims::from_seal(&seal, "did:web:veritas-q.com").unwrap();
+        assert_eq!(claims.iat, seal.capture_timestamp_utc / 1000);
+        assert_eq!(claims.nbf, Some(1_767_225_600));
+        assert_eq!(claims.exp, Some(4_102_444_800));
+
+        let json = serde_json::to_value(&claims).unwrap();
+        assert_eq!(json["iss"], "did:web:veritas-q.com");
+        assert!(json.get("key_id").is_none());
+
+        let parsed = SealClaims::from_compact_jws(&compact(&claims)).unwrap();
+        assert_eq!(parsed, claims);
+        let decoded = parsed.seal().unwrap();
+        assert_eq!(decoded.signature, seal.signature);
+        assert!(decoded.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_claims_must_match_their_seal() {
+        let seal = sealed().await;
+        let claims = SealClaims::from_seal(&seal, "did:web:veritas-q.com").unwrap();
+
+        let mut altered = claims.clone();
+        altered.content_hash = hex::encode([0u8; 32]);
+        assert!(altered.seal().is_err());
+
+        let mut extended = claims;
+        extended.exp = None;
+        assert!(extended.seal().is_err());
+
+        assert!(SealClaims::from_compact_jws("not-a-jws").is_err());
+        assert!(SealClaims::from_compact_jws("a.!!.c").is_err());
+    }
+}
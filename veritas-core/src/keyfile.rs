@@ -0,0 +1,455 @@
+//! Versioned keypair files with integrity protection.
+//!
+//! A keypair file stores an ML-DSA-65 signing keypair with enough metadata
+//! to reject a file that was truncated, corrupted or written for another
+//! algorithm before any key is used. The secret key can be encrypted under
+//! a passphrase.
+//!
+//! # Layout (version 1)
+//!
+//! | Offset | Size | Field |
+//! |--------|------|-------|
+//! | 0      | 4    | Magic `VQKP` |
+//! | 4      | 1    | Format version (1) |
+//! | 5      | 1    | Algorithm (1 = ML-DSA-65) |
+//! | 6      | 1    | Secret key encryption (0 = none, 1 = Argon2id + XChaCha20-Poly1305) |
+//! | 7      | 1    | Reserved (0) |
+//! | 8      | 8    | Creation time, Unix seconds (little-endian) |
+//! | 16     | 1952 | Public key |
+//! | 1968   | 4032 | Secret key, or when encrypted: salt (16), Argon2id memory KiB, passes, lanes (u32 LE each), nonce (24), ciphertext with tag (4048) |
+//! | end-32 | 32   | SHA3-256 of all preceding bytes |
+//!
+//! The encryption authenticates the header and public key as associated
+//! data, so an encrypted file also cannot be re-labelled or paired with
+//! another public key.
+
+use std::io::Write;
+use std::path::Path;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::PublicKey;
+use sha3::digest::ExtendableOutput;
+use sha3::{Digest, Sha3_256, Shake256};
+use thiserror::Error;
+
+use crate::constant_time::ct_eq;
+use crate::seal::{MLDSA65_PUBLIC_KEY_BYTES, MLDSA65_SECRET_KEY_BYTES};
+use crate::secret::{Zeroizing, ZeroizingSecretKey};
+
+/// First bytes of every keypair file
+pub const KEYPAIR_FILE_MAGIC: &[u8; 4] = b"VQKP";
+
+/// Current keypair file format version
+pub const KEYPAIR_FILE_VERSION: u8 = 1;
+
+/// Algorithm ID of ML-DSA-65 keypairs
+pub const ALGORITHM_MLDSA65: u8 = 1;
+
+/// Size of a keypair file written by CLI versions before the container:
+/// public key followed by secret key, nothing else.
+pub const LEGACY_KEYPAIR_FILE_SIZE: usize = MLDSA65_PUBLIC_KEY_BYTES + MLDSA65_SECRET_KEY_BYTES;
+
+const ENCRYPTION_NONE: u8 = 0;
+const ENCRYPTION_ARGON2ID_XCHACHA20POLY1305: u8 = 1;
+
+const HEADER_LEN: usize = 16;
+const CHECKSUM_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const TAG_LEN: usize = 16;
+const KDF_PARAMS_LEN: usize = 12;
+const ENCRYPTED_SECRET_LEN: usize =
+    SALT_LEN + KDF_PARAMS_LEN + NONCE_LEN + MLDSA65_SECRET_KEY_BYTES + TAG_LEN;
+
+/// Largest Argon2id memory cost accepted when reading a file (1 GiB)
+const MAX_KDF_MEMORY_KIB: u32 = 1 << 20;
+
+/// Errors reading or writing keypair files
+#[derive(Debug, Error)]
+pub enum KeypairFileError {
+    /// IO error
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The data does not start with the keypair file magic
+    #[error("not a Veritas keypair file")]
+    NotAKeypairFile,
+
+    /// Written by a newer (or unknown) format version
+    #[error("unsupported keypair file version {0} (supported: 1)")]
+    UnsupportedVersion(u8),
+
+    /// The checksum does not match: the file was truncated or corrupted
+    #[error("keypair file checksum mismatch (file is corrupted or truncated)")]
+    ChecksumMismatch,
+
+    /// The keypair is for another signature algorithm
+    #[error("unsupported key algorithm {0} (expected ML-DSA-65)")]
+    UnsupportedAlgorithm(u8),
+
+    /// The secret key is encrypted with an unknown scheme
+    #[error("unsupported secret key encryption {0}")]
+    UnsupportedEncryption(u8),
+
+    /// The file size does not match its header
+    #[error("invalid keypair file length: expected {expected} bytes, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+
+    /// A key or encryption parameter could not be decoded
+    #[error("malformed keypair file: {0}")]
+    Malformed(&'static str),
+
+    /// The secret key is encrypted and no passphrase was given
+    #[error("keypair file is encrypted; a passphrase is required")]
+    PassphraseRequired,
+
+    /// The passphrase does not decrypt the secret key
+    #[error("wrong passphrase for keypair file")]
+    WrongPassphrase,
+
+    /// The secret key does not belong to the public key
+    #[error("secret key does not match public key")]
+    KeyMismatch,
+
+    /// Randomness or key derivation failed while encrypting
+    #[error("failed to encrypt secret key: {0}")]
+    Encryption(String),
+}
+
+/// An ML-DSA-65 keypair with its creation time
+pub struct KeypairFile {
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+    pub public_key: mldsa65::PublicKey,
+    pub secret_key: ZeroizingSecretKey,
+}
+
+impl KeypairFile {
+    /// Pair keys created now, checking that they belong together.
+    pub fn new(
+        public_key: mldsa65::PublicKey,
+        secret_key: ZeroizingSecretKey,
+    ) -> Result<Self, KeypairFileError> {
+        if !keys_match(&public_key, &secret_key) {
+            return Err(KeypairFileError::KeyMismatch);
+        }
+        Ok(Self {
+            created_at: chrono::Utc::now().timestamp().max(0) as u64,
+            public_key,
+            secret_key,
+        })
+    }
+
+    /// Returns true if `bytes` start like a keypair file (any version).
+    pub fn is_keypair_file(bytes: &[u8]) -> bool {
+        bytes.starts_with(KEYPAIR_FILE_MAGIC)
+    }
+
+    /// Encode the keypair, encrypting the secret key if `passphrase` is given.
+    pub fn encode(&self, passphrase: Option<&str>) -> Result<Zeroizing<Vec<u8>>, KeypairFileError> {
+        let encryption = match passphrase {
+            Some(_) => ENCRYPTION_ARGON2ID_XCHACHA20POLY1305,
+            None => ENCRYPTION_NONE,
+        };
+        let mut data = Zeroizing::new(Vec::with_capacity(file_len(encryption)));
+        data.extend_from_slice(KEYPAIR_FILE_MAGIC);
+        data.extend_from_slice(&[KEYPAIR_FILE_VERSION, ALGORITHM_MLDSA65, encryption, 0]);
+        data.extend_from_slice(&self.created_at.to_le_bytes());
+        data.extend_from_slice(self.public_key.as_bytes());
+
+        match passphrase {
+            Some(passphrase) => {
+                let mut salt = [0u8; SALT_LEN];
+                let mut nonce = [0u8; NONCE_LEN];
+                getrandom::fill(&mut salt)
+                    .and_then(|()| getrandom::fill(&mut nonce))
+                    .map_err(|e| KeypairFileError::Encryption(e.to_string()))?;
+                let params = Params::default();
+                let cipher = cipher(passphrase, &salt, &params)?;
+                let ciphertext = cipher
+                    .encrypt(
+                        XNonce::from_slice(&nonce),
+                        Payload {
+                            msg: self.secret_key.as_bytes(),
+                            aad: &data,
+                        },
+                    )
+                    .map_err(|e| KeypairFileError::Encryption(e.to_string()))?;
+
+                data.extend_from_slice(&salt);
+                for value in [params.m_cost(), params.t_cost(), params.p_cost()] {
+                    data.extend_from_slice(&value.to_le_bytes());
+                }
+                data.extend_from_slice(&nonce);
+                data.extend_from_slice(&ciphertext);
+            }
+            None => data.extend_from_slice(self.secret_key.as_bytes()),
+        }
+
+        let checksum = Sha3_256::digest(data.as_slice());
+        data.extend_from_slice(&checksum);
+        Ok(data)
+    }
+
+    /// Decode and check a keypair file, decrypting the secret key with
+    /// `passphrase` if it is encrypted.
+    pub fn decode(bytes: &[u8], passphrase: Option<&str>) -> Result<Self, KeypairFileError> {
+        if !Self::is_keypair_file(bytes) || bytes.len() < HEADER_LEN + CHECKSUM_LEN {
+            return Err(KeypairFileError::NotAKeypairFile);
+        }
+        if bytes[4] != KEYPAIR_FILE_VERSION {
+            return Err(KeypairFileError::UnsupportedVersion(bytes[4]));
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if !ct_eq(&Sha3_256::digest(body), checksum) {
+            return Err(KeypairFileError::ChecksumMismatch);
+        }
+        if bytes[5] != ALGORITHM_MLDSA65 {
+            return Err(KeypairFileError::UnsupportedAlgorithm(bytes[5]));
+        }
+        let encryption = bytes[6];
+        if encryption > ENCRYPTION_ARGON2ID_XCHACHA20POLY1305 {
+            return Err(KeypairFileError::UnsupportedEncryption(encryption));
+        }
+        if bytes.len() != file_len(encryption) {
+            return Err(KeypairFileError::InvalidLength {
+                expected: file_len(encryption),
+                actual: bytes.len(),
+            });
+        }
+
+        let mut created_at = [0u8; 8];
+        created_at.copy_from_slice(&body[8..HEADER_LEN]);
+        let (authenticated, secret) = body.split_at(HEADER_LEN + MLDSA65_PUBLIC_KEY_BYTES);
+        let public_key = mldsa65::PublicKey::from_bytes(&authenticated[HEADER_LEN..])
+            .map_err(|_| KeypairFileError::Malformed("invalid public key"))?;
+
+        let secret_key = if encryption == ENCRYPTION_NONE {
+            ZeroizingSecretKey::from_bytes(secret)
+        } else {
+            let passphrase = passphrase.ok_or(KeypairFileError::PassphraseRequired)?;
+            let (salt, rest) = secret.split_at(SALT_LEN);
+            let (params, rest) = rest.split_at(KDF_PARAMS_LEN);
+            let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+            let cost = |i: usize| {
+                let mut value = [0u8; 4];
+                value.copy_from_slice(&params[i * 4..i * 4 + 4]);
+                u32::from_le_bytes(value)
+            };
+            if cost(0) > MAX_KDF_MEMORY_KIB {
+                return Err(KeypairFileError::Malformed(
+                    "key derivation memory too large",
+                ));
+            }
+            let params = Params::new(cost(0), cost(1), cost(2), Some(32))
+                .map_err(|_| KeypairFileError::Malformed("invalid key derivation parameters"))?;
+
+            let plaintext = Zeroizing::new(
+                cipher(passphrase, salt, &params)?
+                    .decrypt(
+                        XNonce::from_slice(nonce),
+                        Payload {
+                            msg: ciphertext,
+                            aad: authenticated,
+                        },
+                    )
+                    .map_err(|_| KeypairFileError::WrongPassphrase)?,
+            );
+            ZeroizingSecretKey::from_bytes(&plaintext)
+        }
+        .map_err(|_| KeypairFileError::Malformed("invalid secret key"))?;
+
+        if !keys_match(&public_key, &secret_key) {
+            return Err(KeypairFileError::KeyMismatch);
+        }
+        Ok(Self {
+            created_at: u64::from_le_bytes(created_at),
+            public_key,
+            secret_key,
+        })
+    }
+
+    /// Write the keypair to `path`, readable by the owner only (Unix).
+    pub fn save(&self, path: &Path, passphrase: Option<&str>) -> Result<(), KeypairFileError> {
+        let data = self.encode(passphrase)?;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+            // An existing file keeps its mode on open; restrict it first
+            if path.exists() {
+                std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+            }
+        }
+
+        let mut file = options.open(path)?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Read and check the keypair file at `path`.
+    pub fn load(path: &Path, passphrase: Option<&str>) -> Result<Self, KeypairFileError> {
+        let data = Zeroizing::new(std::fs::read(path)?);
+        Self::decode(&data, passphrase)
+    }
+}
+
+// Prevent Debug from leaking key material
+impl std::fmt::Debug for KeypairFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeypairFile")
+            .field("created_at", &self.created_at)
+            .field("secret_key", &self.secret_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Size of a version 1 file with the given secret key encryption
+fn file_len(encryption: u8) -> usize {
+    let secret_len = match encryption {
+        ENCRYPTION_NONE => MLDSA65_SECRET_KEY_BYTES,
+        _ => ENCRYPTED_SECRET_LEN,
+    };
+    HEADER_LEN + MLDSA65_PUBLIC_KEY_BYTES + secret_len + CHECKSUM_LEN
+}
+
+/// XChaCha20-Poly1305 keyed by Argon2id over the passphrase
+fn cipher(
+    passphrase: &str,
+    salt: &[u8],
+    params: &Params,
+) -> Result<XChaCha20Poly1305, KeypairFileError> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut_slice())
+        .map_err(|e| KeypairFileError::Encryption(e.to_string()))?;
+    Ok(XChaCha20Poly1305::new(Key::from_slice(key.as_slice())))
+}
+
+/// ML-DSA secret keys embed `tr = SHAKE256(public key)` after the two
+/// 32-byte seeds (FIPS 204 skEncode), which ties them to their public key.
+fn keys_match(public_key: &mldsa65::PublicKey, secret_key: &ZeroizingSecretKey) -> bool {
+    let mut tr = [0u8; 64];
+    Shake256::digest_xof(public_key.as_bytes(), &mut tr);
+    ct_eq(&secret_key.as_bytes()[64..128], &tr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::generate_keypair;
+
+    fn sample() -> KeypairFile {
+        let (public_key, secret_key) = generate_keypair();
+        KeypairFile::new(public_key, secret_key).unwrap()
+    }
+
+    /// Recompute the checksum after editing a file
+    fn reseal(data: &mut [u8]) {
+        let end = data.len() - CHECKSUM_LEN;
+        let checksum = Sha3_256::digest(&data[..end]);
+        data[end..].copy_from_slice(&checksum);
+    }
+
+    #[test]
+    fn test_plain_roundtrip() {
+        let keypair = sample();
+        let data = keypair.encode(None).unwrap();
+        assert_eq!(data.len(), file_len(ENCRYPTION_NONE));
+        assert!(KeypairFile::is_keypair_file(&data));
+
+        let decoded = KeypairFile::decode(&data, None).unwrap();
+        assert_eq!(decoded.created_at, keypair.created_at);
+        assert_eq!(decoded.public_key.as_bytes(), keypair.public_key.as_bytes());
+        assert_eq!(decoded.secret_key.as_bytes(), keypair.secret_key.as_bytes());
+    }
+
+    #[test]
+    fn test_encrypted_roundtrip() {
+        let keypair = sample();
+        let data = keypair.encode(Some("correct horse")).unwrap();
+        assert_eq!(data.len(), file_len(ENCRYPTION_ARGON2ID_XCHACHA20POLY1305));
+        assert!(!data
+            .windows(64)
+            .any(|window| window == &keypair.secret_key.as_bytes()[..64]));
+
+        let decoded = KeypairFile::decode(&data, Some("correct horse")).unwrap();
+        assert_eq!(decoded.secret_key.as_bytes(), keypair.secret_key.as_bytes());
+        assert!(matches!(
+            KeypairFile::decode(&data, None),
+            Err(KeypairFileError::PassphraseRequired)
+        ));
+        assert!(matches!(
+            KeypairFile::decode(&data, Some("battery staple")),
+            Err(KeypairFileError::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn test_rejects_corrupted_files() {
+        let data = sample().encode(None).unwrap();
+
+        let mut flipped = data.to_vec();
+        flipped[3000] ^= 0x01;
+        assert!(matches!(
+            KeypairFile::decode(&flipped, None),
+            Err(KeypairFileError::ChecksumMismatch)
+        ));
+        assert!(matches!(
+            KeypairFile::decode(&data[..data.len() - 1], None),
+            Err(KeypairFileError::ChecksumMismatch)
+        ));
+        assert!(matches!(
+            KeypairFile::decode(&data[LEGACY_KEYPAIR_FILE_SIZE..], None),
+            Err(KeypairFileError::NotAKeypairFile)
+        ));
+    }
+
+    #[test]
+    fn test_rejects_wrong_algorithm_and_version() {
+        let data = sample().encode(None).unwrap();
+
+        let mut other_algorithm = data.to_vec();
+        other_algorithm[5] = 2;
+        reseal(&mut other_algorithm);
+        assert!(matches!(
+            KeypairFile::decode(&other_algorithm, None),
+            Err(KeypairFileError::UnsupportedAlgorithm(2))
+        ));
+
+        let mut newer = data.to_vec();
+        newer[4] = KEYPAIR_FILE_VERSION + 1;
+        assert!(matches!(
+            KeypairFile::decode(&newer, None),
+            Err(KeypairFileError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_mismatched_keys() {
+        let (public_key, _) = generate_keypair();
+        let (_, secret_key) = generate_keypair();
+        assert!(matches!(
+            KeypairFile::new(public_key, secret_key),
+            Err(KeypairFileError::KeyMismatch)
+        ));
+
+        // A checksummed file pairing the keys of two keypairs
+        let mut data = sample().encode(None).unwrap().to_vec();
+        let other = sample().encode(None).unwrap();
+        let public_key = HEADER_LEN..HEADER_LEN + MLDSA65_PUBLIC_KEY_BYTES;
+        data[public_key.clone()].copy_from_slice(&other[public_key]);
+        reseal(&mut data);
+        assert!(matches!(
+            KeypairFile::decode(&data, None),
+            Err(KeypairFileError::KeyMismatch)
+        ));
+    }
+}
@@ -36,25 +36,95 @@
 //! # }
 //! ```
 
+pub mod ai_detection;
+pub mod anchor;
+pub mod batch;
+pub mod canonical;
+#[cfg(feature = "network")]
+pub mod client;
+pub mod constant_time;
+pub mod countersign;
+pub mod credential;
+pub mod did;
+pub mod diff;
+pub mod disclosure;
+pub mod document;
 pub mod error;
+pub mod i18n;
+pub mod jws;
+pub mod liveness;
+mod merkle;
+pub mod policy;
 pub mod qrng;
+pub mod regions;
+pub mod schema;
 pub mod seal;
+pub mod secret;
+pub mod segments;
+pub mod session;
+pub mod signing_keys;
+pub mod threshold;
 #[cfg(feature = "perceptual-hash")]
 pub mod watermark;
+pub mod webhook;
 
 #[cfg(feature = "c2pa")]
 pub mod c2pa;
 
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+
+#[cfg(feature = "qr")]
+pub mod qr;
+
+#[cfg(feature = "report")]
+pub mod report;
+
+#[cfg(feature = "keyfile")]
+pub mod keyfile;
+
 // Re-export main types for convenience
-pub use error::{Result, VeritasError, CURRENT_SEAL_VERSION, MAX_SEAL_SIZE};
-pub use qrng::QrngSource;
+pub use ai_detection::{AiContentDetector, AiDetectionAssertion, AiDetectionVerdict, AiVerdict};
+pub use anchor::{AnchorBatch, AnchorStatus, BatchProof};
+pub use batch::{verify_contents_parallel, verify_seals_parallel};
+pub use constant_time::ct_eq;
+pub use countersign::{CounterSignature, CounterSignerRole};
+pub use credential::VerifiableCredential;
+pub use did::{did_key, is_valid_did, DidDocument};
+pub use diff::{FieldDifference, SealDiff};
+pub use disclosure::{FieldProof, SealField, SelectiveDisclosure};
+pub use error::{Result, VeritasError, CURRENT_SEAL_VERSION, MAX_JSON_SEAL_SIZE, MAX_SEAL_SIZE};
+pub use i18n::{Locale, MessageCode};
+pub use jws::SealClaims;
+pub use liveness::{liveness_code, LivenessChallenge};
+pub use policy::{MockQrngHandling, PolicyVerification, PolicyViolation, VerificationPolicy};
+pub use qrng::{
+    CertificateStatus, EntropyCertificate, QrngConditioning, QrngPolicy, QrngSource,
+    TrustedProviderKeys,
+};
+pub use regions::{
+    ChangedRegion, RegionComparison, RegionHashes, DEFAULT_REGION_GRID, REGION_CHANGE_THRESHOLD,
+};
 pub use seal::{
-    generate_keypair, generate_keypair_raw, BlockchainAnchor, ContentHash,
-    ContentVerificationResult, DeviceAttestation, MediaType, VerificationResult, VeritasSeal,
-    ZeroizingSecretKey, MLDSA65_PUBLIC_KEY_BYTES, MLDSA65_SECRET_KEY_BYTES,
-    MLDSA65_SIGNATURE_BYTES,
+    generate_keypair, generate_keypair_raw, BlockchainAnchor, ContentGrade, ContentHash,
+    ContentHasher, ContentVerificationResult, DeviceAttestation, MediaType, VerificationResult,
+    VeritasSeal, MLDSA65_PUBLIC_KEY_BYTES, MLDSA65_SECRET_KEY_BYTES, MLDSA65_SIGNATURE_BYTES,
+    RECOMPRESSION_MAX_DISTANCE,
 };
+pub use secret::{Zeroizing, ZeroizingSecretKey};
+
+pub use segments::{
+    ClipProof, ClipVerificationResult, SegmentLayout, SegmentManifest, TamperedSegment,
+};
+#[cfg(feature = "network")]
+pub use session::SealSession;
+pub use session::SessionCheckpoint;
+pub use signing_keys::{key_id_for, KeyResolution, SigningKey, SigningKeyStatus, MAX_KEY_ID_LEN};
+pub use threshold::{PartialSignature, ThresholdCollector, ThresholdPolicy, ThresholdStatus};
+pub use webhook::{sign_webhook, verify_webhook, WebhookError};
 
+#[cfg(feature = "network")]
+pub use client::Veritas;
 #[cfg(feature = "network")]
 pub use seal::SealBuilder;
 
@@ -63,15 +133,22 @@ pub use qrng::MockQrng;
 
 // Network-dependent exports (not available in Wasm)
 #[cfg(feature = "network")]
+pub use anchor::{verify_anchors, AnchorProvider, AnchorVerifier, OpenTimestamps, SolanaVerifier};
+#[cfg(feature = "network")]
 pub use qrng::{AnuQrng, LfdQrng, QuantumEntropySource};
 
 // Perceptual hashing exports (soft binding)
 #[cfg(feature = "perceptual-hash")]
 pub use watermark::{
-    compute_phash, hamming_distance, HashAlgorithm, PerceptualHash, PerceptualHasher,
-    PERCEPTUAL_HASH_SIZE,
+    compute_phash, embed_watermark_bytes, extract_watermark_bytes, hamming_distance,
+    ExtractedWatermark, HashAlgorithm, PerceptualHash, PerceptualHasher, WatermarkFormat,
+    WatermarkedImage, PERCEPTUAL_HASH_SIZE, WATERMARK_PAYLOAD_SIZE,
 };
 
+// Keypair files (CLI key storage)
+#[cfg(feature = "keyfile")]
+pub use keyfile::{KeypairFile, KeypairFileError};
+
 #[cfg(all(test, feature = "network"))]
 mod tests {
     use super::*;
@@ -0,0 +1,161 @@
+//! Challenge-response liveness for trusted captures.
+//!
+//! A capture nonce (see [`crate::SealBuilder::with_capture_nonce`]) proves the
+//! seal was made in a server-issued session, not that the media was captured
+//! live in it: a device could seal an old file within the session. The
+//! liveness challenge closes that gap. When the session starts the server
+//! derives a short code from the nonce ([`liveness_code`]) and records when
+//! it issued it; the capture app shows the code in frame or writes it into
+//! the frame metadata, and records when it did so. The seal carries the code
+//! and both times as a [`LivenessChallenge`], signed with the rest of the
+//! payload.
+//!
+//! Since the code is derived from the signed nonce, verifiers recompute it
+//! themselves, and the response time shows the capture answered a challenge
+//! that did not exist more than [`MAX_LIVENESS_RESPONSE_MS`] earlier.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::VeritasSeal;
+
+/// Domain separation tag for deriving challenge codes.
+const LIVENESS_DOMAIN: &str = "veritas-liveness-v1";
+
+/// Number of decimal digits in a challenge code.
+pub const LIVENESS_CODE_DIGITS: usize = 6;
+
+/// Longest accepted delay between issuing a challenge and the capture
+/// answering it (2 minutes, the lifetime of a capture nonce).
+pub const MAX_LIVENESS_RESPONSE_MS: u64 = 120_000;
+
+/// Challenge code for a capture session: [`LIVENESS_CODE_DIGITS`] decimal
+/// digits derived from the session's capture nonce.
+pub fn liveness_code(capture_nonce: &[u8; 32]) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(LIVENESS_DOMAIN.as_bytes());
+    hasher.update(capture_nonce);
+    let digest = hasher.finalize();
+    let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+    format!(
+        "{:0width$}",
+        value % 10u32.pow(LIVENESS_CODE_DIGITS as u32),
+        width = LIVENESS_CODE_DIGITS
+    )
+}
+
+/// A liveness challenge and the capture's answer to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LivenessChallenge {
+    /// Code shown in frame or written into the frame metadata
+    pub code: String,
+    /// When the server issued the challenge (Unix timestamp ms)
+    pub issued_at: u64,
+    /// When the capture answered it (Unix timestamp ms, server clock: the
+    /// app adds its elapsed monotonic time to `issued_at`)
+    pub responded_at: u64,
+}
+
+impl LivenessChallenge {
+    /// Delay between the challenge and the answer, in milliseconds.
+    pub fn response_time_ms(&self) -> u64 {
+        self.responded_at.saturating_sub(self.issued_at)
+    }
+
+    /// Check the challenge against the capture nonce and capture time of
+    /// the seal recording it.
+    pub fn check(
+        &self,
+        capture_nonce: Option<&[u8; 32]>,
+        capture_timestamp_utc: u64,
+    ) -> Result<()> {
+        let Some(capture_nonce) = capture_nonce else {
+            return Err(VeritasError::InvalidSeal(
+                "liveness challenge without a capture nonce".into(),
+            ));
+        };
+        if self.code != liveness_code(capture_nonce) {
+            return Err(VeritasError::InvalidSeal(
+                "liveness code does not match the capture nonce".into(),
+            ));
+        }
+        if self.responded_at < self.issued_at || self.responded_at > capture_timestamp_utc {
+            return Err(VeritasError::InvalidTimestamp {
+                reason: "liveness response outside the capture session".into(),
+            });
+        }
+        if self.response_time_ms() > MAX_LIVENESS_RESPONSE_MS {
+            return Err(VeritasError::InvalidTimestamp {
+                reason: format!(
+                    "liveness response took {} ms (max {MAX_LIVENESS_RESPONSE_MS} ms)",
+                    self.response_time_ms()
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl VeritasSeal {
+    /// Check the seal's liveness challenge, if it has one.
+    ///
+    /// Returns `None` for seals without a challenge. Only meaningful once the
+    /// signature has been verified.
+    pub fn verify_liveness(&self) -> Option<bool> {
+        self.liveness.as_ref().map(|liveness| {
+            liveness
+                .check(self.capture_nonce.as_ref(), self.capture_timestamp_utc)
+                .is_ok()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONCE: [u8; 32] = [7; 32];
+
+    fn answered(issued_at: u64, responded_at: u64) -> LivenessChallenge {
+        LivenessChallenge {
+            code: liveness_code(&NONCE),
+            issued_at,
+            responded_at,
+        }
+    }
+
+    #[test]
+    fn test_liveness_code_is_derived_from_nonce() {
+        let code = liveness_code(&NONCE);
+        assert_eq!(code.len(), LIVENESS_CODE_DIGITS);
+        assert!(code.bytes().all(|b| b.is_ascii_digit()));
+        assert_eq!(code, liveness_code(&NONCE));
+        assert_ne!(code, liveness_code(&[8; 32]));
+    }
+
+    #[test]
+    fn test_liveness_check() {
+        let capture = 1_700_000_010_000;
+        assert!(answered(1_700_000_000_000, 1_700_000_004_000)
+            .check(Some(&NONCE), capture)
+            .is_ok());
+
+        // Wrong session, or none at all
+        let challenge = answered(1_700_000_000_000, 1_700_000_004_000);
+        assert!(challenge.check(Some(&[8; 32]), capture).is_err());
+        assert!(challenge.check(None, capture).is_err());
+
+        // Answered before it was issued, after sealing, or too late
+        assert!(answered(1_700_000_005_000, 1_700_000_004_000)
+            .check(Some(&NONCE), capture)
+            .is_err());
+        assert!(answered(1_700_000_000_000, capture + 1)
+            .check(Some(&NONCE), capture)
+            .is_err());
+        let late = capture + MAX_LIVENESS_RESPONSE_MS;
+        assert!(answered(capture - MAX_LIVENESS_RESPONSE_MS - 1, capture)
+            .check(Some(&NONCE), late)
+            .is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! Binary SHA3-256 Merkle tree shared by commitment-based seal features.
+//!
+//! Callers hash their own leaves (prefixed with `0x00` and a domain tag);
+//! interior nodes are `SHA3-256(0x01 || left || right)`. A level with an odd
+//! number of nodes carries its last node up unchanged instead of duplicating
+//! it, so paths may be shorter than the tree height.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// One step of a Merkle path, from the leaf up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    /// Sibling node hash
+    pub sibling: [u8; 32],
+    /// True if the sibling is the left child
+    pub sibling_is_left: bool,
+}
+
+pub(crate) fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Hash pairs level by level; an unpaired last node is carried up unchanged.
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks(2)"),
+        })
+        .collect()
+}
+
+/// Root of a non-empty list of leaves.
+pub(crate) fn root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    debug_assert!(!leaves.is_empty(), "Merkle tree needs at least one leaf");
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Sibling path from leaf `index` to the root.
+pub(crate) fn path(leaves: &[[u8; 32]], index: usize) -> Vec<ProofStep> {
+    path_in(&levels(leaves), index)
+}
+
+/// Every level of the tree, from the leaves up to the root.
+pub(crate) fn levels(leaves: &[[u8; 32]]) -> Vec<Vec<[u8; 32]>> {
+    debug_assert!(!leaves.is_empty(), "Merkle tree needs at least one leaf");
+    let mut levels = vec![leaves.to_vec()];
+    while levels[levels.len() - 1].len() > 1 {
+        levels.push(next_level(&levels[levels.len() - 1]));
+    }
+    levels
+}
+
+/// Sibling path from leaf `index` to the root, given the tree's [`levels`].
+pub(crate) fn path_in(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<ProofStep> {
+    let mut path = Vec::new();
+    for level in &levels[..levels.len() - 1] {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            path.push(ProofStep {
+                sibling: level[sibling],
+                sibling_is_left: sibling < index,
+            });
+        }
+        index /= 2;
+    }
+    path
+}
+
+/// Recompute the root from a leaf and its path.
+pub(crate) fn root_from_path(leaf: [u8; 32], path: &[ProofStep]) -> [u8; 32] {
+    path.iter().fold(leaf, |node, step| {
+        if step.sibling_is_left {
+            node_hash(&step.sibling, &node)
+        } else {
+            node_hash(&node, &step.sibling)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn test_every_path_reaches_root() {
+        for count in 1..=11 {
+            let leaves = leaves(count);
+            let expected = root(&leaves);
+            for (index, leaf) in leaves.iter().enumerate() {
+                assert_eq!(root_from_path(*leaf, &path(&leaves, index)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrong_leaf_or_position_fails() {
+        let leaves = leaves(5);
+        let expected = root(&leaves);
+        assert_ne!(root_from_path([9; 32], &path(&leaves, 2)), expected);
+        assert_ne!(root_from_path(leaves[1], &path(&leaves, 0)), expected);
+    }
+}
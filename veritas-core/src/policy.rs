@@ -0,0 +1,321 @@
+//! Verification policies: what a consumer requires of a seal beyond a valid
+//! signature over unmodified content.
+//!
+//! A newsroom may only accept seals made on attested hardware with real
+//! quantum entropy and confirmed on chain, while a preview tool accepts any
+//! authentic seal. [`VerificationPolicy`] states those requirements and
+//! [`VeritasSeal::verify_with_policy`] checks them together with the
+//! signature and content. Three presets cover the common cases:
+//!
+//! | Preset     | Device attestation | Mock QRNG | Confirmed anchor |
+//! |------------|--------------------|-----------|------------------|
+//! | `strict`   | required           | rejected  | required         |
+//! | `standard` | -                  | rejected  | -                |
+//! | `lenient`  | -                  | warning   | -                |
+//!
+//! Mock entropy is deterministic, so a mock seal proves nothing about when
+//! it was made. Policies never accept one silently: at least a
+//! [`PolicyViolation::MockEntropy`] warning is reported unless the policy
+//! explicitly uses [`MockQrngHandling::Accept`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::anchor::AnchorStatus;
+use crate::error::Result;
+use crate::i18n::{Locale, MessageCode};
+use crate::qrng::QrngSource;
+use crate::seal::{ContentVerificationResult, VeritasSeal};
+
+/// Requirements a seal must meet to be accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerificationPolicy {
+    /// Require a device attestation (seal made on attested hardware)
+    pub require_device_attestation: bool,
+    /// What to do with seals whose entropy came from the mock QRNG
+    pub mock_qrng: MockQrngHandling,
+    /// Require at least one anchor confirmed on chain
+    pub require_confirmed_anchor: bool,
+}
+
+impl VerificationPolicy {
+    /// Names of the presets, for [`VerificationPolicy::preset`].
+    pub const PRESETS: [&'static str; 3] = ["strict", "standard", "lenient"];
+
+    /// Attested hardware, real quantum entropy and a confirmed anchor.
+    pub fn strict() -> Self {
+        Self {
+            require_device_attestation: true,
+            mock_qrng: MockQrngHandling::Reject,
+            require_confirmed_anchor: true,
+        }
+    }
+
+    /// Real quantum entropy; attestation and anchors optional.
+    pub fn standard() -> Self {
+        Self {
+            require_device_attestation: false,
+            mock_qrng: MockQrngHandling::Reject,
+            require_confirmed_anchor: false,
+        }
+    }
+
+    /// Any authentic seal, with a warning for mock entropy.
+    pub fn lenient() -> Self {
+        Self {
+            require_device_attestation: false,
+            mock_qrng: MockQrngHandling::Warn,
+            require_confirmed_anchor: false,
+        }
+    }
+
+    /// Preset by name (`strict`, `standard` or `lenient`, case-insensitive).
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "strict" => Some(Self::strict()),
+            "standard" => Some(Self::standard()),
+            "lenient" => Some(Self::lenient()),
+            _ => None,
+        }
+    }
+
+    /// Check the seal's provenance against the policy.
+    ///
+    /// Only the requirements are checked, not the signature or content.
+    /// `anchors` are the statuses of the seal's anchors checked on chain
+    /// (see [`anchor`](crate::anchor)); when they were not checked,
+    /// pass an empty slice and a required confirmed anchor is reported missing.
+    pub fn evaluate(&self, seal: &VeritasSeal, anchors: &[AnchorStatus]) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+        if self.require_device_attestation && seal.device_attestation.is_none() {
+            violations.push(PolicyViolation::MissingDeviceAttestation);
+        }
+        if self.mock_qrng == MockQrngHandling::Reject && seal.uses_mock_entropy() {
+            violations.push(PolicyViolation::MockEntropy);
+        }
+        if self.require_confirmed_anchor && !anchors.iter().any(AnchorStatus::is_confirmed) {
+            violations.push(PolicyViolation::AnchorNotConfirmed);
+        }
+        violations
+    }
+
+    /// Weaknesses of the seal the policy tolerates but reports.
+    pub fn warnings(&self, seal: &VeritasSeal) -> Vec<PolicyViolation> {
+        let mut warnings = Vec::new();
+        if self.mock_qrng == MockQrngHandling::Warn && seal.uses_mock_entropy() {
+            warnings.push(PolicyViolation::MockEntropy);
+        }
+        warnings
+    }
+}
+
+impl Default for VerificationPolicy {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// What a [`VerificationPolicy`] does with seals made with the mock QRNG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MockQrngHandling {
+    /// Accept them like any other seal
+    Accept,
+    /// Accept them with a [`PolicyViolation::MockEntropy`] warning
+    Warn,
+    /// Reject them with a [`PolicyViolation::MockEntropy`] violation
+    Reject,
+}
+
+impl MockQrngHandling {
+    /// Parse `accept`, `warn` or `reject` (case-insensitive).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "accept" => Some(Self::Accept),
+            "warn" => Some(Self::Warn),
+            "reject" => Some(Self::Reject),
+            _ => None,
+        }
+    }
+}
+
+/// A requirement of a [`VerificationPolicy`] the seal does not meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyViolation {
+    /// The policy requires a device attestation and the seal has none
+    MissingDeviceAttestation,
+    /// The seal's entropy came from the mock QRNG
+    MockEntropy,
+    /// The policy requires a confirmed anchor and none was confirmed
+    AnchorNotConfirmed,
+    /// The signer's key registry contradicts the seal's signing key (see
+    /// [`crate::signing_keys`])
+    UntrustedSigningKey,
+}
+
+impl PolicyViolation {
+    /// Stable code of the violation, see [`crate::i18n`].
+    pub fn code(&self) -> MessageCode {
+        match self {
+            Self::MissingDeviceAttestation => MessageCode::MissingDeviceAttestation,
+            Self::MockEntropy => MessageCode::MockEntropy,
+            Self::AnchorNotConfirmed => MessageCode::AnchorNotConfirmed,
+            Self::UntrustedSigningKey => MessageCode::UntrustedSigningKey,
+        }
+    }
+
+    /// Returns a human-readable description of the violation.
+    pub fn description(&self) -> &'static str {
+        self.description_in(Locale::En)
+    }
+
+    /// Returns a human-readable description of the violation in `locale`.
+    pub fn description_in(&self, locale: Locale) -> &'static str {
+        self.code().message(locale)
+    }
+}
+
+/// Outcome of [`VeritasSeal::verify_with_policy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyVerification {
+    /// Signature and content verification
+    pub result: ContentVerificationResult,
+    /// Policy requirements the seal does not meet
+    pub violations: Vec<PolicyViolation>,
+    /// Weaknesses the policy tolerates (see [`VerificationPolicy::warnings`])
+    pub warnings: Vec<PolicyViolation>,
+}
+
+impl PolicyVerification {
+    /// Returns true if the content is authentic and the seal meets the policy.
+    pub fn is_accepted(&self) -> bool {
+        self.result.is_authentic() && self.violations.is_empty()
+    }
+}
+
+impl VeritasSeal {
+    /// Whether the seal's entropy came from the mock QRNG (testing only, not
+    /// quantum-safe).
+    pub fn uses_mock_entropy(&self) -> bool {
+        self.qrng_source == QrngSource::Mock
+    }
+
+    /// Verify the seal and content, then check the seal against `policy`.
+    ///
+    /// `anchors` are the on-chain statuses of the seal's anchors, as for
+    /// [`VerificationPolicy::evaluate`].
+    pub fn verify_with_policy(
+        &self,
+        content: &[u8],
+        policy: &VerificationPolicy,
+        anchors: &[AnchorStatus],
+    ) -> Result<PolicyVerification> {
+        Ok(PolicyVerification {
+            result: self.verify_content(content)?,
+            violations: policy.evaluate(self, anchors),
+            warnings: policy.warnings(self),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, DeviceAttestation, MediaType, SealBuilder};
+
+    const CONTENT: &[u8] = b"policy test";
+
+    async fn mock_seal() -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(CONTENT.to_vec(), MediaType::Image)
+            .with_attestation(DeviceAttestation {
+                device_id: "device-1".into(),
+                tee_type: "ARM_TRUSTZONE".into(),
+                attestation_token: vec![1, 2, 3],
+            })
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal")
+    }
+
+    #[test]
+    fn test_presets_by_name() {
+        for name in VerificationPolicy::PRESETS {
+            assert!(VerificationPolicy::preset(name).is_some());
+        }
+        assert_eq!(
+            VerificationPolicy::preset("STRICT"),
+            Some(VerificationPolicy::strict())
+        );
+        assert_eq!(VerificationPolicy::preset("paranoid"), None);
+        assert_eq!(
+            VerificationPolicy::default(),
+            VerificationPolicy::standard()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_with_policy_presets() {
+        let seal = mock_seal().await;
+
+        let lenient = seal
+            .verify_with_policy(CONTENT, &VerificationPolicy::lenient(), &[])
+            .unwrap();
+        assert!(lenient.is_accepted());
+        assert_eq!(lenient.warnings, [PolicyViolation::MockEntropy]);
+
+        let standard = seal
+            .verify_with_policy(CONTENT, &VerificationPolicy::standard(), &[])
+            .unwrap();
+        assert!(standard.result.is_authentic());
+        assert_eq!(standard.violations, [PolicyViolation::MockEntropy]);
+        assert!(standard.warnings.is_empty());
+        assert!(!standard.is_accepted());
+
+        let confirmed = AnchorStatus::Confirmed {
+            block_height: 100,
+            confirmations: 6,
+        };
+        let strict = VerificationPolicy {
+            mock_qrng: MockQrngHandling::Accept,
+            ..VerificationPolicy::strict()
+        };
+        assert_eq!(
+            strict.evaluate(&seal, &[AnchorStatus::Pending]),
+            [PolicyViolation::AnchorNotConfirmed]
+        );
+        let accepted = seal
+            .verify_with_policy(CONTENT, &strict, &[AnchorStatus::Pending, confirmed])
+            .unwrap();
+        assert!(accepted.is_accepted());
+        assert!(accepted.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_policy_does_not_accept_tampered_content() {
+        let seal = mock_seal().await;
+        let verification = seal
+            .verify_with_policy(b"edited", &VerificationPolicy::lenient(), &[])
+            .unwrap();
+        assert!(verification.violations.is_empty());
+        assert!(!verification.is_accepted());
+    }
+
+    #[test]
+    fn test_policy_deserializes_with_defaults() {
+        let policy: VerificationPolicy =
+            serde_json::from_str(r#"{"require_device_attestation": true}"#).unwrap();
+        assert!(policy.require_device_attestation);
+        assert_eq!(policy.mock_qrng, MockQrngHandling::Reject);
+
+        let policy: VerificationPolicy = serde_json::from_str(r#"{"mock_qrng": "warn"}"#).unwrap();
+        assert_eq!(policy.mock_qrng, MockQrngHandling::Warn);
+        assert_eq!(
+            MockQrngHandling::parse("ACCEPT"),
+            Some(MockQrngHandling::Accept)
+        );
+        assert_eq!(MockQrngHandling::parse("ignore"), None);
+    }
+}
@@ -0,0 +1,1236 @@
+//! Protobuf serialization of seals (`protobuf` feature).
+//!
+//! The message definitions live in `proto/veritas_seal.proto`. The prost
+//! structs below are maintained by hand so the crate builds without `protoc`;
+//! field tags must match the `.proto` file.
+//!
+//! Conversion is lossless: a seal decoded from protobuf re-encodes to the
+//! same CBOR and verifies with the original signature.
+
+use crate::ai_detection::{AiDetectionAssertion, AiDetectionVerdict, AiVerdict};
+use crate::anchor::{BatchProof, ProofStep};
+use crate::countersign::{CounterSignature, CounterSignerRole};
+use crate::disclosure::{FieldOpening, SelectiveDisclosure};
+use crate::error::{Result, VeritasError, MAX_SEAL_SIZE};
+use crate::liveness::LivenessChallenge;
+use crate::qrng::{
+    CertificateAlgorithm, EntropyCertificate, QrngConditioning, QrngPolicy, QrngSource,
+};
+use crate::regions::RegionHashes;
+use crate::seal::{BlockchainAnchor, ContentHash, DeviceAttestation, MediaType, VeritasSeal};
+use crate::segments::SegmentManifest;
+use crate::threshold::{PartialSignature, ThresholdPolicy};
+
+/// Generated-style prost messages for `veritas.v1`.
+pub mod proto {
+    /// `veritas.v1.VeritasSeal`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct VeritasSeal {
+        #[prost(uint32, tag = "1")]
+        pub version: u32,
+        #[prost(uint64, tag = "2")]
+        pub capture_timestamp_utc: u64,
+        #[prost(string, optional, tag = "3")]
+        pub capture_location: Option<String>,
+        #[prost(message, optional, tag = "4")]
+        pub device_attestation: Option<DeviceAttestation>,
+        #[prost(bytes = "vec", tag = "5")]
+        pub qrng_entropy: Vec<u8>,
+        #[prost(message, optional, tag = "6")]
+        pub qrng_source: Option<QrngSource>,
+        #[prost(uint64, tag = "7")]
+        pub entropy_timestamp: u64,
+        #[prost(message, optional, tag = "8")]
+        pub content_hash: Option<ContentHash>,
+        #[prost(enumeration = "MediaType", tag = "9")]
+        pub media_type: i32,
+        #[prost(bytes = "vec", tag = "10")]
+        pub signature: Vec<u8>,
+        #[prost(bytes = "vec", tag = "11")]
+        pub public_key: Vec<u8>,
+        #[prost(message, optional, tag = "12")]
+        pub blockchain_anchor: Option<BlockchainAnchor>,
+        #[prost(enumeration = "QrngConditioning", tag = "13")]
+        pub qrng_conditioning: i32,
+        #[prost(message, optional, tag = "14")]
+        pub entropy_certificate: Option<EntropyCertificate>,
+        #[prost(message, repeated, tag = "15")]
+        pub countersignatures: Vec<CounterSignature>,
+        #[prost(message, optional, tag = "16")]
+        pub threshold_policy: Option<ThresholdPolicy>,
+        #[prost(message, repeated, tag = "17")]
+        pub partial_signatures: Vec<PartialSignature>,
+        #[prost(message, repeated, tag = "18")]
+        pub disclosure: Vec<FieldOpening>,
+        #[prost(string, tag = "19")]
+        pub custom_media_type: String,
+        #[prost(message, optional, tag = "20")]
+        pub qrng_policy: Option<QrngPolicy>,
+        #[prost(message, repeated, tag = "21")]
+        pub additional_anchors: Vec<BlockchainAnchor>,
+        #[prost(uint64, optional, tag = "22")]
+        pub not_before: Option<u64>,
+        #[prost(uint64, optional, tag = "23")]
+        pub not_after: Option<u64>,
+        #[prost(message, repeated, tag = "24")]
+        pub ai_detections: Vec<AiDetectionAssertion>,
+        #[prost(bytes = "vec", optional, tag = "25")]
+        pub capture_nonce: Option<Vec<u8>>,
+        #[prost(message, optional, tag = "26")]
+        pub liveness: Option<LivenessChallenge>,
+        #[prost(string, optional, tag = "27")]
+        pub key_id: Option<String>,
+        #[prost(bytes = "vec", optional, tag = "28")]
+        pub raw_entropy_hash: Option<Vec<u8>>,
+    }
+
+    /// `veritas.v1.DeviceAttestation`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct DeviceAttestation {
+        #[prost(string, tag = "1")]
+        pub device_id: String,
+        #[prost(string, tag = "2")]
+        pub tee_type: String,
+        #[prost(bytes = "vec", tag = "3")]
+        pub attestation_token: Vec<u8>,
+    }
+
+    /// `veritas.v1.ContentHash`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ContentHash {
+        #[prost(bytes = "vec", tag = "1")]
+        pub crypto_hash: Vec<u8>,
+        #[prost(bytes = "vec", optional, tag = "2")]
+        pub perceptual_hash: Option<Vec<u8>>,
+        #[prost(message, optional, tag = "3")]
+        pub segments: Option<SegmentManifest>,
+        #[prost(bytes = "vec", optional, tag = "4")]
+        pub canonical_hash: Option<Vec<u8>>,
+        #[prost(message, optional, tag = "5")]
+        pub region_hashes: Option<RegionHashes>,
+    }
+
+    /// `veritas.v1.RegionHashes`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct RegionHashes {
+        #[prost(uint32, tag = "1")]
+        pub grid: u32,
+        #[prost(uint64, repeated, tag = "2")]
+        pub tiles: Vec<u64>,
+    }
+
+    /// `veritas.v1.SegmentManifest`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SegmentManifest {
+        #[prost(uint32, tag = "1")]
+        pub segment_size: u32,
+        #[prost(uint32, optional, tag = "2")]
+        pub segment_duration_ms: Option<u32>,
+        #[prost(uint32, tag = "3")]
+        pub segment_count: u32,
+        #[prost(uint64, tag = "4")]
+        pub total_size: u64,
+        #[prost(bytes = "vec", tag = "5")]
+        pub root: Vec<u8>,
+        #[prost(uint64, repeated, tag = "6")]
+        pub digests: Vec<u64>,
+    }
+
+    /// `veritas.v1.MediaType`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum MediaType {
+        Unspecified = 0,
+        Image = 1,
+        Video = 2,
+        Audio = 3,
+        Document = 4,
+        Generic = 5,
+        Custom = 6,
+    }
+
+    /// `veritas.v1.QrngSourceKind`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum QrngSourceKind {
+        Unspecified = 0,
+        IdQuantiqueCloud = 1,
+        AnuCloud = 2,
+        LfdCloud = 3,
+        DeviceHardware = 4,
+        Mock = 5,
+    }
+
+    /// `veritas.v1.QrngSource`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QrngSource {
+        #[prost(enumeration = "QrngSourceKind", tag = "1")]
+        pub kind: i32,
+        #[prost(string, tag = "2")]
+        pub device_id: String,
+    }
+
+    /// `veritas.v1.QrngConditioning`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum QrngConditioning {
+        Raw = 0,
+        Sha3OsMix = 1,
+    }
+
+    /// `veritas.v1.QrngPolicyKind`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum QrngPolicyKind {
+        Unspecified = 0,
+        FreshPerSeal = 1,
+        Pooled = 2,
+        Mixed = 3,
+    }
+
+    /// `veritas.v1.QrngPolicy`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct QrngPolicy {
+        #[prost(enumeration = "QrngPolicyKind", tag = "1")]
+        pub kind: i32,
+        #[prost(uint64, tag = "2")]
+        pub max_age_secs: u64,
+    }
+
+    /// `veritas.v1.CertificateAlgorithm`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum CertificateAlgorithm {
+        Unspecified = 0,
+        MlDsa65 = 1,
+    }
+
+    /// `veritas.v1.EntropyCertificate`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct EntropyCertificate {
+        #[prost(message, optional, tag = "1")]
+        pub source: Option<QrngSource>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub entropy: Vec<u8>,
+        #[prost(uint64, tag = "3")]
+        pub issued_at: u64,
+        #[prost(enumeration = "CertificateAlgorithm", tag = "4")]
+        pub algorithm: i32,
+        #[prost(bytes = "vec", tag = "5")]
+        pub signer_public_key: Vec<u8>,
+        #[prost(bytes = "vec", tag = "6")]
+        pub signature: Vec<u8>,
+    }
+
+    /// `veritas.v1.CounterSignerRoleKind`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum CounterSignerRoleKind {
+        Unspecified = 0,
+        Notary = 1,
+        Editor = 2,
+        Witness = 3,
+        Custom = 4,
+    }
+
+    /// `veritas.v1.CounterSignerRole`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct CounterSignerRole {
+        #[prost(enumeration = "CounterSignerRoleKind", tag = "1")]
+        pub kind: i32,
+        #[prost(string, tag = "2")]
+        pub custom: String,
+    }
+
+    /// `veritas.v1.CounterSignature`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct CounterSignature {
+        #[prost(message, optional, tag = "1")]
+        pub role: Option<CounterSignerRole>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub signer_public_key: Vec<u8>,
+        #[prost(uint64, tag = "3")]
+        pub timestamp: u64,
+        #[prost(bytes = "vec", tag = "4")]
+        pub signature: Vec<u8>,
+    }
+
+    /// `veritas.v1.AiVerdict`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum AiVerdict {
+        Unspecified = 0,
+        Human = 1,
+        AiGenerated = 2,
+        Inconclusive = 3,
+    }
+
+    /// `veritas.v1.AiDetectionAssertion`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct AiDetectionAssertion {
+        #[prost(string, tag = "1")]
+        pub detector: String,
+        #[prost(string, tag = "2")]
+        pub detector_version: String,
+        #[prost(uint32, tag = "3")]
+        pub score_bps: u32,
+        #[prost(enumeration = "AiVerdict", tag = "4")]
+        pub verdict: i32,
+        #[prost(uint64, tag = "5")]
+        pub timestamp: u64,
+        #[prost(bytes = "vec", tag = "6")]
+        pub signer_public_key: Vec<u8>,
+        #[prost(bytes = "vec", tag = "7")]
+        pub signature: Vec<u8>,
+    }
+
+    /// `veritas.v1.LivenessChallenge`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct LivenessChallenge {
+        #[prost(string, tag = "1")]
+        pub code: String,
+        #[prost(uint64, tag = "2")]
+        pub issued_at: u64,
+        #[prost(uint64, tag = "3")]
+        pub responded_at: u64,
+    }
+
+    /// `veritas.v1.ThresholdPolicy`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ThresholdPolicy {
+        #[prost(uint32, tag = "1")]
+        pub threshold: u32,
+        #[prost(bytes = "vec", repeated, tag = "2")]
+        pub signers: Vec<Vec<u8>>,
+    }
+
+    /// `veritas.v1.PartialSignature`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct PartialSignature {
+        #[prost(uint32, tag = "1")]
+        pub signer_index: u32,
+        #[prost(bytes = "vec", tag = "2")]
+        pub signature: Vec<u8>,
+    }
+
+    /// `veritas.v1.FieldOpening`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct FieldOpening {
+        #[prost(bytes = "vec", tag = "1")]
+        pub salt: Vec<u8>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub commitment: Vec<u8>,
+    }
+
+    /// `veritas.v1.BlockchainAnchor`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct BlockchainAnchor {
+        #[prost(string, tag = "1")]
+        pub chain: String,
+        #[prost(string, tag = "2")]
+        pub tx_id: String,
+        #[prost(uint64, tag = "3")]
+        pub block_height: u64,
+        #[prost(bytes = "vec", optional, tag = "4")]
+        pub proof: Option<Vec<u8>>,
+        #[prost(message, optional, tag = "5")]
+        pub batch: Option<AnchorBatchProof>,
+    }
+
+    /// `veritas.v1.AnchorBatchProof`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct AnchorBatchProof {
+        #[prost(bytes = "vec", tag = "1")]
+        pub root: Vec<u8>,
+        #[prost(message, repeated, tag = "2")]
+        pub path: Vec<ProofStep>,
+    }
+
+    /// `veritas.v1.ProofStep`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ProofStep {
+        #[prost(bytes = "vec", tag = "1")]
+        pub sibling: Vec<u8>,
+        #[prost(bool, tag = "2")]
+        pub sibling_is_left: bool,
+    }
+}
+
+impl VeritasSeal {
+    /// Serialize the seal to protobuf bytes (`veritas.v1.VeritasSeal`).
+    pub fn to_protobuf(&self) -> Result<Vec<u8>> {
+        use prost::Message;
+
+        Ok(proto::VeritasSeal::from(self).encode_to_vec())
+    }
+
+    /// Deserialize a seal from protobuf bytes.
+    ///
+    /// Applies the same size and structure checks as [`VeritasSeal::from_cbor`].
+    pub fn from_protobuf(bytes: &[u8]) -> Result<Self> {
+        use prost::Message;
+
+        if bytes.len() > MAX_SEAL_SIZE {
+            return Err(VeritasError::SealTooLarge {
+                size: bytes.len(),
+                max: MAX_SEAL_SIZE,
+            });
+        }
+
+        let message = proto::VeritasSeal::decode(bytes)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+        let seal = Self::try_from(message)?;
+        seal.validate_structure()?;
+        Ok(seal)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rust -> protobuf
+// ---------------------------------------------------------------------------
+
+impl From<&VeritasSeal> for proto::VeritasSeal {
+    fn from(seal: &VeritasSeal) -> Self {
+        Self {
+            version: u32::from(seal.version),
+            capture_timestamp_utc: seal.capture_timestamp_utc,
+            capture_location: seal.capture_location.clone(),
+            device_attestation: seal.device_attestation.as_ref().map(|a| {
+                proto::DeviceAttestation {
+                    device_id: a.device_id.clone(),
+                    tee_type: a.tee_type.clone(),
+                    attestation_token: a.attestation_token.clone(),
+                }
+            }),
+            qrng_entropy: seal.qrng_entropy.to_vec(),
+            qrng_source: Some(source_to_proto(&seal.qrng_source)),
+            entropy_timestamp: seal.entropy_timestamp,
+            content_hash: Some(proto::ContentHash {
+                crypto_hash: seal.content_hash.crypto_hash.to_vec(),
+                perceptual_hash: seal.content_hash.perceptual_hash.clone(),
+                segments: seal
+                    .content_hash
+                    .segments
+                    .as_ref()
+                    .map(|m| proto::SegmentManifest {
+                        segment_size: m.segment_size,
+                        segment_duration_ms: m.segment_duration_ms,
+                        segment_count: m.segment_count,
+                        total_size: m.total_size,
+                        root: m.root.to_vec(),
+                        digests: m.digests.clone(),
+                    }),
+                canonical_hash: seal.content_hash.canonical_hash.map(|h| h.to_vec()),
+                region_hashes: seal.content_hash.region_hashes.as_ref().map(|r| {
+                    proto::RegionHashes {
+                        grid: u32::from(r.grid),
+                        tiles: r.tiles.clone(),
+                    }
+                }),
+            }),
+            media_type: media_type_to_proto(&seal.media_type) as i32,
+            custom_media_type: match &seal.media_type {
+                MediaType::Custom(mime) => mime.clone(),
+                _ => String::new(),
+            },
+            not_before: seal.not_before,
+            not_after: seal.not_after,
+            capture_nonce: seal.capture_nonce.map(|nonce| nonce.to_vec()),
+            liveness: seal
+                .liveness
+                .as_ref()
+                .map(|liveness| proto::LivenessChallenge {
+                    code: liveness.code.clone(),
+                    issued_at: liveness.issued_at,
+                    responded_at: liveness.responded_at,
+                }),
+            signature: seal.signature.clone(),
+            public_key: seal.public_key.clone(),
+            key_id: seal.key_id.clone(),
+            blockchain_anchor: seal.blockchain_anchor.as_ref().map(Into::into),
+            additional_anchors: seal.additional_anchors.iter().map(Into::into).collect(),
+            qrng_conditioning: match seal.qrng_conditioning {
+                None => proto::QrngConditioning::Raw,
+                Some(QrngConditioning::Sha3OsMix) => proto::QrngConditioning::Sha3OsMix,
+            } as i32,
+            raw_entropy_hash: seal.raw_entropy_hash.map(|hash| hash.to_vec()),
+            entropy_certificate: seal.entropy_certificate.as_ref().map(|c| {
+                proto::EntropyCertificate {
+                    source: Some(source_to_proto(&c.source)),
+                    entropy: c.entropy.to_vec(),
+                    issued_at: c.issued_at,
+                    algorithm: match c.algorithm {
+                        CertificateAlgorithm::MlDsa65 => proto::CertificateAlgorithm::MlDsa65,
+                    } as i32,
+                    signer_public_key: c.signer_public_key.clone(),
+                    signature: c.signature.clone(),
+                }
+            }),
+            qrng_policy: seal.qrng_policy.map(|policy| {
+                let kind = match policy {
+                    QrngPolicy::FreshPerSeal => proto::QrngPolicyKind::FreshPerSeal,
+                    QrngPolicy::Pooled { .. } => proto::QrngPolicyKind::Pooled,
+                    QrngPolicy::Mixed { .. } => proto::QrngPolicyKind::Mixed,
+                };
+                proto::QrngPolicy {
+                    kind: kind as i32,
+                    max_age_secs: policy.max_age_secs().unwrap_or(0),
+                }
+            }),
+            countersignatures: seal
+                .countersignatures
+                .iter()
+                .map(|c| proto::CounterSignature {
+                    role: Some(role_to_proto(&c.role)),
+                    signer_public_key: c.signer_public_key.clone(),
+                    timestamp: c.timestamp,
+                    signature: c.signature.clone(),
+                })
+                .collect(),
+            ai_detections: seal
+                .ai_detections
+                .iter()
+                .map(|a| proto::AiDetectionAssertion {
+                    detector: a.verdict.detector.clone(),
+                    detector_version: a.verdict.detector_version.clone(),
+                    score_bps: u32::from(a.verdict.score_bps),
+                    verdict: match a.verdict.verdict {
+                        AiVerdict::Human => proto::AiVerdict::Human,
+                        AiVerdict::AiGenerated => proto::AiVerdict::AiGenerated,
+                        AiVerdict::Inconclusive => proto::AiVerdict::Inconclusive,
+                    } as i32,
+                    timestamp: a.timestamp,
+                    signer_public_key: a.signer_public_key.clone(),
+                    signature: a.signature.clone(),
+                })
+                .collect(),
+            threshold_policy: seal
+                .threshold_policy
+                .as_ref()
+                .map(|p| proto::ThresholdPolicy {
+                    threshold: u32::from(p.threshold),
+                    signers: p.signers.clone(),
+                }),
+            partial_signatures: seal
+                .partial_signatures
+                .iter()
+                .map(|p| proto::PartialSignature {
+                    signer_index: u32::from(p.signer_index),
+                    signature: p.signature.clone(),
+                })
+                .collect(),
+            disclosure: seal
+                .disclosure
+                .iter()
+                .flat_map(|d| &d.fields)
+                .map(|opening| match opening {
+                    FieldOpening::Disclosed { salt } => proto::FieldOpening {
+                        salt: salt.to_vec(),
+                        commitment: Vec::new(),
+                    },
+                    FieldOpening::Redacted { commitment } => proto::FieldOpening {
+                        salt: Vec::new(),
+                        commitment: commitment.to_vec(),
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&BlockchainAnchor> for proto::BlockchainAnchor {
+    fn from(anchor: &BlockchainAnchor) -> Self {
+        Self {
+            chain: anchor.chain.clone(),
+            tx_id: anchor.tx_id.clone(),
+            block_height: anchor.block_height,
+            proof: anchor.proof.clone(),
+            batch: anchor.batch.as_ref().map(|batch| proto::AnchorBatchProof {
+                root: batch.root.to_vec(),
+                path: batch
+                    .path
+                    .iter()
+                    .map(|step| proto::ProofStep {
+                        sibling: step.sibling.to_vec(),
+                        sibling_is_left: step.sibling_is_left,
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+fn role_to_proto(role: &CounterSignerRole) -> proto::CounterSignerRole {
+    let (kind, custom) = match role {
+        CounterSignerRole::Notary => (proto::CounterSignerRoleKind::Notary, String::new()),
+        CounterSignerRole::Editor => (proto::CounterSignerRoleKind::Editor, String::new()),
+        CounterSignerRole::Witness => (proto::CounterSignerRoleKind::Witness, String::new()),
+        CounterSignerRole::Custom(role) => (proto::CounterSignerRoleKind::Custom, role.clone()),
+    };
+    proto::CounterSignerRole {
+        kind: kind as i32,
+        custom,
+    }
+}
+
+fn source_to_proto(source: &QrngSource) -> proto::QrngSource {
+    let (kind, device_id) = match source {
+        QrngSource::IdQuantiqueCloud => (proto::QrngSourceKind::IdQuantiqueCloud, String::new()),
+        QrngSource::AnuCloud => (proto::QrngSourceKind::AnuCloud, String::new()),
+        QrngSource::LfdCloud => (proto::QrngSourceKind::LfdCloud, String::new()),
+        QrngSource::DeviceHardware { device_id } => {
+            (proto::QrngSourceKind::DeviceHardware, device_id.clone())
+        }
+        QrngSource::Mock => (proto::QrngSourceKind::Mock, String::new()),
+    };
+    proto::QrngSource {
+        kind: kind as i32,
+        device_id,
+    }
+}
+
+fn media_type_to_proto(media_type: &MediaType) -> proto::MediaType {
+    match media_type {
+        MediaType::Image => proto::MediaType::Image,
+        MediaType::Video => proto::MediaType::Video,
+        MediaType::Audio => proto::MediaType::Audio,
+        MediaType::Document => proto::MediaType::Document,
+        MediaType::Generic => proto::MediaType::Generic,
+        MediaType::Custom(_) => proto::MediaType::Custom,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// protobuf -> Rust
+// ---------------------------------------------------------------------------
+
+fn invalid(msg: impl Into<String>) -> VeritasError {
+    VeritasError::InvalidSeal(msg.into())
+}
+
+fn to_array32(bytes: &[u8], field: &str) -> Result<[u8; 32]> {
+    bytes
+        .try_into()
+        .map_err(|_| invalid(format!("{field} must be 32 bytes, got {}", bytes.len())))
+}
+
+fn anchor_from_proto(anchor: proto::BlockchainAnchor) -> Result<BlockchainAnchor> {
+    let batch = anchor
+        .batch
+        .map(|batch| -> Result<BatchProof> {
+            Ok(BatchProof {
+                root: to_array32(&batch.root, "anchor batch root")?,
+                path: batch
+                    .path
+                    .iter()
+                    .map(|step| -> Result<ProofStep> {
+                        Ok(ProofStep {
+                            sibling: to_array32(&step.sibling, "anchor batch sibling")?,
+                            sibling_is_left: step.sibling_is_left,
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            })
+        })
+        .transpose()?;
+    Ok(BlockchainAnchor {
+        chain: anchor.chain,
+        tx_id: anchor.tx_id,
+        block_height: anchor.block_height,
+        proof: anchor.proof,
+        batch,
+    })
+}
+
+fn source_from_proto(source: Option<proto::QrngSource>) -> Result<QrngSource> {
+    let source = source.ok_or_else(|| invalid("missing qrng_source"))?;
+    match proto::QrngSourceKind::try_from(source.kind) {
+        Ok(proto::QrngSourceKind::IdQuantiqueCloud) => Ok(QrngSource::IdQuantiqueCloud),
+        Ok(proto::QrngSourceKind::AnuCloud) => Ok(QrngSource::AnuCloud),
+        Ok(proto::QrngSourceKind::LfdCloud) => Ok(QrngSource::LfdCloud),
+        Ok(proto::QrngSourceKind::DeviceHardware) => Ok(QrngSource::DeviceHardware {
+            device_id: source.device_id,
+        }),
+        Ok(proto::QrngSourceKind::Mock) => Ok(QrngSource::Mock),
+        Ok(proto::QrngSourceKind::Unspecified) | Err(_) => {
+            Err(invalid(format!("unknown qrng source kind {}", source.kind)))
+        }
+    }
+}
+
+fn role_from_proto(role: Option<proto::CounterSignerRole>) -> Result<CounterSignerRole> {
+    let role = role.ok_or_else(|| invalid("missing counter-signer role"))?;
+    match proto::CounterSignerRoleKind::try_from(role.kind) {
+        Ok(proto::CounterSignerRoleKind::Notary) => Ok(CounterSignerRole::Notary),
+        Ok(proto::CounterSignerRoleKind::Editor) => Ok(CounterSignerRole::Editor),
+        Ok(proto::CounterSignerRoleKind::Witness) => Ok(CounterSignerRole::Witness),
+        Ok(proto::CounterSignerRoleKind::Custom) => Ok(CounterSignerRole::Custom(role.custom)),
+        Ok(proto::CounterSignerRoleKind::Unspecified) | Err(_) => Err(invalid(format!(
+            "unknown counter-signer role {}",
+            role.kind
+        ))),
+    }
+}
+
+impl TryFrom<proto::VeritasSeal> for VeritasSeal {
+    type Error = VeritasError;
+
+    fn try_from(message: proto::VeritasSeal) -> Result<Self> {
+        let version = u8::try_from(message.version)
+            .map_err(|_| invalid(format!("version {} out of range", message.version)))?;
+
+        let media_type = match proto::MediaType::try_from(message.media_type) {
+            Ok(proto::MediaType::Image) => MediaType::Image,
+            Ok(proto::MediaType::Video) => MediaType::Video,
+            Ok(proto::MediaType::Audio) => MediaType::Audio,
+            Ok(proto::MediaType::Document) => MediaType::Document,
+            Ok(proto::MediaType::Generic) => MediaType::Generic,
+            Ok(proto::MediaType::Custom) => MediaType::Custom(message.custom_media_type),
+            Ok(proto::MediaType::Unspecified) | Err(_) => {
+                return Err(invalid(format!(
+                    "unknown media type {}",
+                    message.media_type
+                )))
+            }
+        };
+
+        let qrng_conditioning = match proto::QrngConditioning::try_from(message.qrng_conditioning) {
+            Ok(proto::QrngConditioning::Raw) => None,
+            Ok(proto::QrngConditioning::Sha3OsMix) => Some(QrngConditioning::Sha3OsMix),
+            Err(_) => {
+                return Err(invalid(format!(
+                    "unknown qrng conditioning {}",
+                    message.qrng_conditioning
+                )))
+            }
+        };
+
+        let qrng_policy = message
+            .qrng_policy
+            .map(|p| match proto::QrngPolicyKind::try_from(p.kind) {
+                Ok(proto::QrngPolicyKind::FreshPerSeal) => Ok(QrngPolicy::FreshPerSeal),
+                Ok(proto::QrngPolicyKind::Pooled) => Ok(QrngPolicy::Pooled {
+                    max_age_secs: p.max_age_secs,
+                }),
+                Ok(proto::QrngPolicyKind::Mixed) => Ok(QrngPolicy::Mixed {
+                    max_age_secs: p.max_age_secs,
+                }),
+                Ok(proto::QrngPolicyKind::Unspecified) | Err(_) => {
+                    Err(invalid(format!("unknown qrng policy {}", p.kind)))
+                }
+            })
+            .transpose()?;
+
+        let content_hash = message
+            .content_hash
+            .ok_or_else(|| invalid("missing content_hash"))?;
+        let segments = content_hash
+            .segments
+            .map(|m| -> Result<SegmentManifest> {
+                Ok(SegmentManifest {
+                    segment_size: m.segment_size,
+                    segment_duration_ms: m.segment_duration_ms,
+                    segment_count: m.segment_count,
+                    total_size: m.total_size,
+                    root: to_array32(&m.root, "segments.root")?,
+                    digests: m.digests,
+                })
+            })
+            .transpose()?;
+        let region_hashes = content_hash
+            .region_hashes
+            .map(|r| -> Result<RegionHashes> {
+                Ok(RegionHashes {
+                    grid: u8::try_from(r.grid)
+                        .map_err(|_| invalid(format!("region grid {} too large", r.grid)))?,
+                    tiles: r.tiles,
+                })
+            })
+            .transpose()?;
+
+        let entropy_certificate = message
+            .entropy_certificate
+            .map(|c| -> Result<EntropyCertificate> {
+                let algorithm = match proto::CertificateAlgorithm::try_from(c.algorithm) {
+                    Ok(proto::CertificateAlgorithm::MlDsa65) => CertificateAlgorithm::MlDsa65,
+                    Ok(proto::CertificateAlgorithm::Unspecified) | Err(_) => {
+                        return Err(invalid(format!(
+                            "unknown certificate algorithm {}",
+                            c.algorithm
+                        )))
+                    }
+                };
+                Ok(EntropyCertificate {
+                    source: source_from_proto(c.source)?,
+                    entropy: to_array32(&c.entropy, "certificate entropy")?,
+                    issued_at: c.issued_at,
+                    algorithm,
+                    signer_public_key: c.signer_public_key,
+                    signature: c.signature,
+                })
+            })
+            .transpose()?;
+
+        let countersignatures = message
+            .countersignatures
+            .into_iter()
+            .map(|c| -> Result<CounterSignature> {
+                Ok(CounterSignature {
+                    role: role_from_proto(c.role)?,
+                    signer_public_key: c.signer_public_key,
+                    timestamp: c.timestamp,
+                    signature: c.signature,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let ai_detections = message
+            .ai_detections
+            .into_iter()
+            .map(|a| -> Result<AiDetectionAssertion> {
+                let verdict = match proto::AiVerdict::try_from(a.verdict) {
+                    Ok(proto::AiVerdict::Human) => AiVerdict::Human,
+                    Ok(proto::AiVerdict::AiGenerated) => AiVerdict::AiGenerated,
+                    Ok(proto::AiVerdict::Inconclusive) => AiVerdict::Inconclusive,
+                    Ok(proto::AiVerdict::Unspecified) | Err(_) => {
+                        return Err(invalid(format!("unknown AI verdict {}", a.verdict)))
+                    }
+                };
+                Ok(AiDetectionAssertion {
+                    verdict: AiDetectionVerdict {
+                        detector: a.detector,
+                        detector_version: a.detector_version,
+                        score_bps: u16::try_from(a.score_bps).map_err(|_| {
+                            invalid(format!("AI-detection score {} out of range", a.score_bps))
+                        })?,
+                        verdict,
+                    },
+                    timestamp: a.timestamp,
+                    signer_public_key: a.signer_public_key,
+                    signature: a.signature,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let threshold_policy = message
+            .threshold_policy
+            .map(|p| -> Result<ThresholdPolicy> {
+                Ok(ThresholdPolicy {
+                    threshold: u8::try_from(p.threshold)
+                        .map_err(|_| invalid(format!("threshold {} out of range", p.threshold)))?,
+                    signers: p.signers,
+                })
+            })
+            .transpose()?;
+
+        let partial_signatures = message
+            .partial_signatures
+            .into_iter()
+            .map(|p| -> Result<PartialSignature> {
+                Ok(PartialSignature {
+                    signer_index: u8::try_from(p.signer_index).map_err(|_| {
+                        invalid(format!("signer index {} out of range", p.signer_index))
+                    })?,
+                    signature: p.signature,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // An empty list means the seal signs its fields directly
+        let disclosure = if message.disclosure.is_empty() {
+            None
+        } else {
+            let fields = message
+                .disclosure
+                .into_iter()
+                .map(|o| match (o.salt.is_empty(), o.commitment.is_empty()) {
+                    (false, true) => Ok(FieldOpening::Disclosed {
+                        salt: to_array32(&o.salt, "disclosure salt")?,
+                    }),
+                    (true, false) => Ok(FieldOpening::Redacted {
+                        commitment: to_array32(&o.commitment, "disclosure commitment")?,
+                    }),
+                    _ => Err(invalid(
+                        "field opening needs exactly one of salt or commitment",
+                    )),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Some(SelectiveDisclosure { fields })
+        };
+
+        Ok(VeritasSeal {
+            version,
+            capture_timestamp_utc: message.capture_timestamp_utc,
+            capture_location: message.capture_location,
+            device_attestation: message.device_attestation.map(|a| DeviceAttestation {
+                device_id: a.device_id,
+                tee_type: a.tee_type,
+                attestation_token: a.attestation_token,
+            }),
+            qrng_entropy: to_array32(&message.qrng_entropy, "qrng_entropy")?,
+            qrng_source: source_from_proto(message.qrng_source)?,
+            entropy_timestamp: message.entropy_timestamp,
+            qrng_conditioning,
+            raw_entropy_hash: message
+                .raw_entropy_hash
+                .map(|hash| to_array32(&hash, "raw_entropy_hash"))
+                .transpose()?,
+            entropy_certificate,
+            qrng_policy,
+            content_hash: ContentHash {
+                crypto_hash: to_array32(&content_hash.crypto_hash, "crypto_hash")?,
+                perceptual_hash: content_hash.perceptual_hash,
+                segments,
+                canonical_hash: content_hash
+                    .canonical_hash
+                    .map(|h| to_array32(&h, "canonical_hash"))
+                    .transpose()?,
+                region_hashes,
+            },
+            media_type,
+            not_before: message.not_before,
+            not_after: message.not_after,
+            capture_nonce: message
+                .capture_nonce
+                .map(|nonce| to_array32(&nonce, "capture_nonce"))
+                .transpose()?,
+            liveness: message.liveness.map(|liveness| LivenessChallenge {
+                code: liveness.code,
+                issued_at: liveness.issued_at,
+                responded_at: liveness.responded_at,
+            }),
+            disclosure,
+            signature: message.signature,
+            public_key: message.public_key,
+            key_id: message.key_id,
+            blockchain_anchor: message
+                .blockchain_anchor
+                .map(anchor_from_proto)
+                .transpose()?,
+            additional_anchors: message
+                .additional_anchors
+                .into_iter()
+                .map(anchor_from_proto)
+                .collect::<Result<_>>()?,
+            threshold_policy,
+            partial_signatures,
+            countersignatures,
+            ai_detections,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, SealBuilder};
+
+    async fn sample_seal(media_type: MediaType) -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(b"protobuf round trip".to_vec(), media_type)
+            .with_location("u4pruydqqvj".to_string())
+            .with_attestation(DeviceAttestation {
+                device_id: "device-1".into(),
+                tee_type: "ARM_TRUSTZONE".into(),
+                attestation_token: vec![1, 2, 3],
+            })
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal")
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_matches_cbor() {
+        for media_type in [
+            MediaType::Image,
+            MediaType::Video,
+            MediaType::Audio,
+            MediaType::Document,
+            MediaType::Generic,
+            MediaType::Custom("model/gltf-binary".into()),
+        ] {
+            let mut seal = sample_seal(media_type).await;
+            seal.blockchain_anchor = Some(BlockchainAnchor {
+                chain: "solana-devnet".into(),
+                tx_id: "abc".into(),
+                block_height: 42,
+                proof: None,
+                batch: None,
+            });
+            seal.additional_anchors.push(BlockchainAnchor {
+                chain: "bitcoin".into(),
+                tx_id: "https://alice.btc.calendar.opentimestamps.org".into(),
+                block_height: 0,
+                proof: Some(vec![0xf0, 1, 0xaa, 0x08]),
+                batch: Some(BatchProof {
+                    root: [0x5a; 32],
+                    path: vec![
+                        ProofStep {
+                            sibling: [1; 32],
+                            sibling_is_left: true,
+                        },
+                        ProofStep {
+                            sibling: [2; 32],
+                            sibling_is_left: false,
+                        },
+                    ],
+                }),
+            });
+
+            let bytes = seal.to_protobuf().unwrap();
+            let restored = VeritasSeal::from_protobuf(&bytes).unwrap();
+
+            assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+            assert!(restored.verify().unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_conditioning() {
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"mixed".to_vec(), MediaType::Audio)
+            .with_conditioning(QrngConditioning::Sha3OsMix)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_qrng_policy() {
+        let (public_key, secret_key) = generate_keypair();
+        for policy in [
+            QrngPolicy::FreshPerSeal,
+            QrngPolicy::Pooled { max_age_secs: 300 },
+            QrngPolicy::Mixed { max_age_secs: 60 },
+        ] {
+            let seal = SealBuilder::new(b"pooled".to_vec(), MediaType::Image)
+                .with_qrng_policy(policy)
+                .build_secure(&MockQrng::default(), &secret_key, &public_key)
+                .await
+                .unwrap();
+
+            let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+            assert_eq!(restored.qrng_policy, Some(policy));
+            assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+            assert!(restored.verify().unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_capture_nonce() {
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"trusted capture".to_vec(), MediaType::Image)
+            .with_capture_nonce([0x5A; 32])
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.capture_nonce, Some([0x5A; 32]));
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_liveness() {
+        let (public_key, secret_key) = generate_keypair();
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let liveness = LivenessChallenge {
+            code: crate::liveness::liveness_code(&[0x5A; 32]),
+            issued_at: now - 5_000,
+            responded_at: now - 1_000,
+        };
+        let seal = SealBuilder::new(b"live capture".to_vec(), MediaType::Video)
+            .with_capture_nonce([0x5A; 32])
+            .with_liveness(liveness.clone())
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.liveness, Some(liveness));
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_raw_entropy_hash() {
+        let mut seal = sample_seal(MediaType::Image).await;
+        seal.qrng_conditioning = Some(QrngConditioning::Sha3OsMix);
+        seal.raw_entropy_hash = Some([0x3C; 32]);
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.raw_entropy_hash, Some([0x3C; 32]));
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_key_id() {
+        use pqcrypto_traits::sign::PublicKey;
+
+        let (public_key, secret_key) = generate_keypair();
+        let key_id = crate::signing_keys::key_id_for(public_key.as_bytes());
+        let seal = SealBuilder::new(b"registered key".to_vec(), MediaType::Image)
+            .with_key_id(key_id.clone())
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.key_id, Some(key_id));
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_validity_window() {
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"time-boxed".to_vec(), MediaType::Image)
+            .with_validity(None, Some(u64::MAX))
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(
+            (restored.not_before, restored.not_after),
+            (None, Some(u64::MAX))
+        );
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[cfg(feature = "perceptual-hash")]
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_region_hashes() {
+        let image = image::RgbImage::from_fn(128, 128, |x, y| image::Rgb([x as u8, y as u8, 0]));
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgb8(image)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(png, MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        assert!(seal.content_hash.region_hashes.is_some());
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(
+            restored.content_hash.region_hashes,
+            seal.content_hash.region_hashes
+        );
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_countersignatures() {
+        let mut seal = sample_seal(MediaType::Image).await;
+        let (pk, sk) = crate::seal::generate_keypair_raw();
+        seal.countersign(CounterSignerRole::Notary, &sk, &pk)
+            .unwrap();
+        seal.countersign(CounterSignerRole::Custom("desk".into()), &sk, &pk)
+            .unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert_eq!(
+            restored.verify_countersignatures().unwrap(),
+            vec![true, true]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_with_ai_detections() {
+        let mut seal = sample_seal(MediaType::Image).await;
+        let (pk, sk) = crate::seal::generate_keypair_raw();
+        let verdict = AiDetectionVerdict {
+            detector: "synthid".into(),
+            detector_version: "2.1".into(),
+            score_bps: 9_700,
+            verdict: AiVerdict::AiGenerated,
+        };
+        seal.attach_ai_detection(verdict, &sk, &pk).unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert_eq!(restored.verify_ai_detections().unwrap(), vec![true]);
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_threshold_seal() {
+        let (public_key, secret_key) = generate_keypair();
+        let (cosigner_pk, cosigner_sk) = crate::seal::generate_keypair_raw();
+        let mut seal = SealBuilder::new(b"threshold".to_vec(), MediaType::Image)
+            .with_threshold(ThresholdPolicy::new(1, &[cosigner_pk]).unwrap())
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        seal.cosign(&cosigner_sk, &cosigner_pk).unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_redacted_seal() {
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"disclosure".to_vec(), MediaType::Image)
+            .with_location("u4pruydqqvj".to_string())
+            .with_selective_disclosure()
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap()
+            .redact(&[crate::SealField::CaptureLocation])
+            .unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_protobuf_roundtrip_segmented_seal() {
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(vec![7; 300], MediaType::Video)
+            .with_segments(crate::SegmentLayout::new(64).with_duration_ms(1_000))
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        let restored = VeritasSeal::from_protobuf(&seal.to_protobuf().unwrap()).unwrap();
+        assert_eq!(restored.to_cbor().unwrap(), seal.to_cbor().unwrap());
+        assert!(restored.verify().unwrap());
+    }
+
+    #[test]
+    fn test_qrng_source_roundtrip() {
+        let sources = [
+            QrngSource::IdQuantiqueCloud,
+            QrngSource::AnuCloud,
+            QrngSource::LfdCloud,
+            QrngSource::DeviceHardware {
+                device_id: "quantis-pcie-0".into(),
+            },
+            QrngSource::Mock,
+        ];
+        for source in sources {
+            let restored = source_from_proto(Some(source_to_proto(&source))).unwrap();
+            assert_eq!(restored, source);
+        }
+    }
+
+    #[test]
+    fn test_from_protobuf_rejects_garbage() {
+        assert!(VeritasSeal::from_protobuf(&[0xFF, 0xFF, 0xFF]).is_err());
+        assert!(VeritasSeal::from_protobuf(&[]).is_err());
+    }
+}
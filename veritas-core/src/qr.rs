@@ -0,0 +1,257 @@
+//! QR codes for seal verification links.
+//!
+//! Encodes a verification URL or a compact [`seal_digest`] as a QR code and
+//! renders it as SVG or PNG, for printing on physical evidence labels. The
+//! PNG is written directly (1-bit grayscale, uncompressed deflate blocks),
+//! so no image codec is needed.
+
+use std::fmt::Write as _;
+
+use qrcode::{Color, QrCode};
+use sha3::{Digest, Sha3_256};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::VeritasSeal;
+
+/// Prefix of compact seal digests (`vq1:<content hash>:<signature tag>`).
+pub const SEAL_DIGEST_PREFIX: &str = "vq1";
+
+/// Width of the blank border around the code, in modules.
+pub const QUIET_ZONE: usize = 4;
+
+/// Default size of one module in PNG output, in pixels.
+pub const DEFAULT_PNG_SCALE: u32 = 8;
+
+/// Maximum size of one module in PNG output, in pixels.
+pub const MAX_PNG_SCALE: u32 = 32;
+
+/// Build the verify page URL for a seal identifier (seal ID or content hash).
+pub fn verify_url(base_url: &str, id: &str) -> String {
+    format!("{}/{}", base_url.trim_end_matches('/'), id)
+}
+
+/// Compact digest identifying a seal: its content hash and the first 8 bytes
+/// of the SHA3-256 of its signature.
+///
+/// Short enough for a small printed code, and enough to look the seal up
+/// and tell it apart from another seal of the same content.
+pub fn seal_digest(seal: &VeritasSeal) -> String {
+    seal_digest_from_parts(&seal.content_hash.crypto_hash_hex(), &seal.signature)
+}
+
+/// [`seal_digest`] from a hex content hash and the raw signature.
+pub fn seal_digest_from_parts(content_hash_hex: &str, signature: &[u8]) -> String {
+    let signature_hash = Sha3_256::digest(signature);
+    format!(
+        "{}:{}:{}",
+        SEAL_DIGEST_PREFIX,
+        content_hash_hex.to_ascii_lowercase(),
+        hex::encode(&signature_hash[..8])
+    )
+}
+
+/// A QR code ready to be rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SealQrCode {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+impl SealQrCode {
+    /// Encode `data` as a QR code.
+    pub fn encode(data: &str) -> Result<Self> {
+        let code = QrCode::new(data.as_bytes()).map_err(|e| {
+            VeritasError::SerializationError(format!("QR code encoding failed: {}", e))
+        })?;
+        let modules = code
+            .to_colors()
+            .into_iter()
+            .map(|color| color == Color::Dark)
+            .collect();
+        Ok(Self {
+            width: code.width(),
+            modules,
+        })
+    }
+
+    /// Number of modules per side, excluding the quiet zone.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Whether the module at column `x`, row `y` is dark.
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.width && self.modules[y * self.width + x]
+    }
+
+    /// Coordinates `(x, y)` of the dark modules, row by row.
+    pub fn dark_modules(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.modules
+            .iter()
+            .enumerate()
+            .filter(|(_, dark)| **dark)
+            .map(|(i, _)| (i % self.width, i / self.width))
+    }
+
+    /// Render as a standalone SVG image with a quiet zone, one unit per module.
+    pub fn to_svg(&self) -> String {
+        let mut path = String::new();
+        for (x, y) in self.dark_modules() {
+            let _ = write!(path, "M{} {}h1v1h-1z", x + QUIET_ZONE, y + QUIET_ZONE);
+        }
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {0} {0}\" shape-rendering=\"crispEdges\">\
+             <rect width=\"{0}\" height=\"{0}\" fill=\"#fff\"/><path d=\"{1}\" fill=\"#000\"/></svg>",
+            self.width + 2 * QUIET_ZONE,
+            path
+        )
+    }
+
+    /// Render as a black-and-white PNG with a quiet zone, `scale` pixels per
+    /// module (clamped to 1..=[`MAX_PNG_SCALE`]).
+    pub fn to_png(&self, scale: u32) -> Vec<u8> {
+        let scale = scale.clamp(1, MAX_PNG_SCALE) as usize;
+        let size = (self.width + 2 * QUIET_ZONE) * scale;
+        let row_bytes = size.div_ceil(8);
+
+        // Filter byte (none) followed by packed pixels, 1 = white
+        let mut raw = Vec::with_capacity(size * (row_bytes + 1));
+        for py in 0..size {
+            raw.push(0);
+            let start = raw.len();
+            raw.resize(start + row_bytes, 0);
+            for px in 0..size {
+                let (x, y) = (px / scale, py / scale);
+                let dark = x >= QUIET_ZONE
+                    && y >= QUIET_ZONE
+                    && self.is_dark(x - QUIET_ZONE, y - QUIET_ZONE);
+                if !dark {
+                    raw[start + px / 8] |= 0x80 >> (px % 8);
+                }
+            }
+        }
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&(size as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(size as u32).to_be_bytes());
+        // Bit depth 1, grayscale, deflate, adaptive filtering, no interlace
+        ihdr.extend_from_slice(&[1, 0, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_png_chunk(&mut png, b"IHDR", &ihdr);
+        write_png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn write_png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65_535;
+
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(u8::from(last));
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_contains_modules() {
+        let code = SealQrCode::encode("https://veritas-q.com/verify/abc").unwrap();
+        let svg = code.to_svg();
+        let side = code.width() + 2 * QUIET_ZONE;
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(&format!("viewBox=\"0 0 {0} {0}\"", side)));
+        // Finder pattern in the top-left corner
+        assert!(code.is_dark(0, 0));
+        assert!(svg.contains("M4 4h1v1h-1z"));
+    }
+
+    #[test]
+    fn test_png_structure() {
+        let code = SealQrCode::encode("vq1:abcd:0123456789abcdef").unwrap();
+        let png = code.to_png(2);
+        let side = ((code.width() + 2 * QUIET_ZONE) * 2) as u32;
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(png[16..20], side.to_be_bytes());
+        assert_eq!(png[20..24], side.to_be_bytes());
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        let stream = zlib_stored(&[]);
+        assert_eq!(stream, [0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_seal_digest_format() {
+        let digest = seal_digest_from_parts(&"AB".repeat(32), b"signature");
+        let parts: Vec<&str> = digest.split(':').collect();
+
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0], SEAL_DIGEST_PREFIX);
+        assert_eq!(parts[1], "ab".repeat(32));
+        assert_eq!(parts[2].len(), 16);
+        assert_ne!(digest, seal_digest_from_parts(&"ab".repeat(32), b"other"));
+        assert_eq!(
+            verify_url("https://veritas-q.com/verify/", "abc"),
+            "https://veritas-q.com/verify/abc"
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Proof-of-origin certificates for QRNG entropy.
+//!
+//! A QRNG provider (or a trusted local signer in front of a hardware device)
+//! can sign the exact entropy block it hands out. The certificate travels in
+//! the seal, so an auditor can later confirm that the seal entropy was issued
+//! by a specific QRNG key rather than produced locally. Of the built-in
+//! providers, only ID Quantique returns certificates.
+//!
+//! Certificates are signed with ML-DSA-65 (detached) over a CBOR payload that
+//! binds the entropy, the QRNG source and the issue time. Anyone can sign a
+//! well-formed certificate with their own key, so [`EntropyCertificate::verify`]
+//! takes the provider keys the auditor trusts ([`TrustedProviderKeys`]) and
+//! reports certificates from other signers as
+//! [`CertificateStatus::UntrustedSigner`].
+
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use super::QrngSource;
+use crate::error::{Result, VeritasError};
+
+/// Domain separation tag for certificate signatures.
+const CERTIFICATE_DOMAIN: &str = "veritas-entropy-certificate-v1";
+
+/// Signature algorithm used by an entropy certificate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CertificateAlgorithm {
+    /// FIPS 204 ML-DSA-65, detached signature
+    MlDsa65,
+}
+
+/// Provider keys trusted to sign entropy certificates, per QRNG source.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProviderKeys {
+    keys: Vec<(QrngSource, Vec<u8>)>,
+}
+
+impl TrustedProviderKeys {
+    /// Empty set: every certificate is reported as untrusted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust `public_key` (ML-DSA-65) to sign certificates for `source`.
+    pub fn with_key(mut self, source: QrngSource, public_key: Vec<u8>) -> Self {
+        self.keys.push((source, public_key));
+        self
+    }
+
+    /// Returns true if `public_key` is trusted for `source`.
+    pub fn is_trusted(&self, source: &QrngSource, public_key: &[u8]) -> bool {
+        self.keys
+            .iter()
+            .any(|(trusted_source, key)| trusted_source == source && key == public_key)
+    }
+}
+
+/// Outcome of checking an entropy certificate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertificateStatus {
+    /// Validly signed by a key trusted for the certificate's source
+    Trusted,
+    /// Validly signed, but by a key that is not trusted for the source, so it
+    /// proves nothing about where the entropy came from
+    UntrustedSigner,
+    /// Validly signed, but the certified block cannot be tied to the seal
+    /// entropy (conditioned seals before version 17, see
+    /// [`crate::VeritasSeal::raw_entropy_hash`])
+    Unbound,
+    /// Malformed or forged, or certifying other entropy than the seal's
+    Invalid,
+}
+
+impl CertificateStatus {
+    /// Returns true only for [`CertificateStatus::Trusted`].
+    #[inline]
+    pub fn is_trusted(self) -> bool {
+        matches!(self, Self::Trusted)
+    }
+}
+
+/// Provider-signed statement that a block of entropy came from a QRNG.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntropyCertificate {
+    /// QRNG source that issued the entropy
+    pub source: QrngSource,
+    /// The certified entropy block (raw provider output)
+    pub entropy: [u8; 32],
+    /// Issue time reported by the provider (Unix timestamp ms)
+    pub issued_at: u64,
+    /// Signature algorithm
+    pub algorithm: CertificateAlgorithm,
+    /// Provider signing key
+    pub signer_public_key: Vec<u8>,
+    /// Detached signature over the certificate payload
+    pub signature: Vec<u8>,
+}
+
+/// Signed portion of a certificate.
+#[derive(Serialize)]
+struct CertificatePayload<'a> {
+    domain: &'static str,
+    source: &'a QrngSource,
+    entropy: &'a [u8; 32],
+    issued_at: u64,
+}
+
+impl EntropyCertificate {
+    /// Issue a certificate for an entropy block.
+    ///
+    /// Used by providers that sign locally (e.g. a hardware QRNG behind an
+    /// HSM) and by tests.
+    pub fn sign(
+        source: QrngSource,
+        entropy: [u8; 32],
+        issued_at: u64,
+        secret_key: &mldsa65::SecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<Self> {
+        let payload = Self::payload_bytes(&source, &entropy, issued_at)?;
+        let signature = mldsa65::detached_sign(&payload, secret_key);
+
+        Ok(Self {
+            source,
+            entropy,
+            issued_at,
+            algorithm: CertificateAlgorithm::MlDsa65,
+            signer_public_key: public_key.as_bytes().to_vec(),
+            signature: signature.as_bytes().to_vec(),
+        })
+    }
+
+    /// Verify the certificate signature and check its signer against the
+    /// `trusted` provider keys.
+    ///
+    /// Malformed keys or signatures are reported as
+    /// [`CertificateStatus::Invalid`]; `Err` only if the payload cannot be
+    /// serialized.
+    pub fn verify(&self, trusted: &TrustedProviderKeys) -> Result<CertificateStatus> {
+        if !self.signature_is_valid()? {
+            return Ok(CertificateStatus::Invalid);
+        }
+        if trusted.is_trusted(&self.source, &self.signer_public_key) {
+            Ok(CertificateStatus::Trusted)
+        } else {
+            Ok(CertificateStatus::UntrustedSigner)
+        }
+    }
+
+    /// Check the signature against the embedded key only, whoever it belongs to.
+    pub(crate) fn signature_is_valid(&self) -> Result<bool> {
+        let payload = Self::payload_bytes(&self.source, &self.entropy, self.issued_at)?;
+
+        match self.algorithm {
+            CertificateAlgorithm::MlDsa65 => {
+                let Ok(public_key) = mldsa65::PublicKey::from_bytes(&self.signer_public_key) else {
+                    return Ok(false);
+                };
+                let Ok(signature) = mldsa65::DetachedSignature::from_bytes(&self.signature) else {
+                    return Ok(false);
+                };
+                Ok(mldsa65::verify_detached_signature(&signature, &payload, &public_key).is_ok())
+            }
+        }
+    }
+
+    /// SHA3-256 fingerprint of the signer key (hex), for comparison with the
+    /// provider's published key.
+    pub fn signer_fingerprint(&self) -> String {
+        hex::encode(Sha3_256::digest(&self.signer_public_key))
+    }
+
+    /// SHA3-256 of the certified block, as recorded in
+    /// [`crate::VeritasSeal::raw_entropy_hash`].
+    pub fn entropy_hash(&self) -> [u8; 32] {
+        Sha3_256::digest(self.entropy).into()
+    }
+
+    fn payload_bytes(source: &QrngSource, entropy: &[u8; 32], issued_at: u64) -> Result<Vec<u8>> {
+        let payload = CertificatePayload {
+            domain: CERTIFICATE_DOMAIN,
+            source,
+            entropy,
+            issued_at,
+        };
+        let mut bytes = Vec::with_capacity(128);
+        ciborium::into_writer(&payload, &mut bytes)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+        Ok(bytes)
+    }
+}
+
+/// Entropy together with an optional proof-of-origin certificate.
+#[derive(Debug, Clone)]
+pub struct AttestedEntropy {
+    /// 256 bits of entropy
+    pub entropy: [u8; 32],
+    /// Provider certificate, if the provider supports attestation
+    pub certificate: Option<EntropyCertificate>,
+    /// When the block was drawn from the provider (Unix ms), if it was drawn
+    /// ahead of the request into a pool; `None` for a fresh draw
+    pub drawn_at: Option<u64>,
+}
+
+impl AttestedEntropy {
+    /// Entropy without a certificate.
+    pub fn unattested(entropy: [u8; 32]) -> Self {
+        Self {
+            entropy,
+            certificate: None,
+            drawn_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_certificate() -> EntropyCertificate {
+        let (pk, sk) = mldsa65::keypair();
+        EntropyCertificate::sign(
+            QrngSource::IdQuantiqueCloud,
+            [0x5A; 32],
+            1_700_000_000_000,
+            &sk,
+            &pk,
+        )
+        .unwrap()
+    }
+
+    fn trusting(cert: &EntropyCertificate) -> TrustedProviderKeys {
+        TrustedProviderKeys::new().with_key(cert.source.clone(), cert.signer_public_key.clone())
+    }
+
+    #[test]
+    fn test_certificate_roundtrip_verifies() {
+        let cert = signed_certificate();
+        assert_eq!(
+            cert.verify(&trusting(&cert)).unwrap(),
+            CertificateStatus::Trusted
+        );
+        assert_eq!(cert.signer_fingerprint().len(), 64);
+    }
+
+    #[test]
+    fn test_certificate_from_untrusted_signer_is_not_trusted() {
+        let cert = signed_certificate();
+        let other_provider = trusting(&signed_certificate());
+        assert_eq!(
+            cert.verify(&other_provider).unwrap(),
+            CertificateStatus::UntrustedSigner
+        );
+        assert_eq!(
+            cert.verify(&TrustedProviderKeys::new()).unwrap(),
+            CertificateStatus::UntrustedSigner
+        );
+
+        // A provider key is only trusted for its own source
+        let other_source = TrustedProviderKeys::new()
+            .with_key(QrngSource::LfdCloud, cert.signer_public_key.clone());
+        assert!(!cert.verify(&other_source).unwrap().is_trusted());
+    }
+
+    #[test]
+    fn test_certificate_detects_tampering() {
+        let mut cert = signed_certificate();
+        let trusted = trusting(&cert);
+        cert.entropy[0] ^= 0x01;
+        assert_eq!(cert.verify(&trusted).unwrap(), CertificateStatus::Invalid);
+
+        let mut cert = signed_certificate();
+        let trusted = TrustedProviderKeys::new()
+            .with_key(QrngSource::LfdCloud, cert.signer_public_key.clone());
+        cert.source = QrngSource::LfdCloud;
+        assert_eq!(cert.verify(&trusted).unwrap(), CertificateStatus::Invalid);
+
+        let mut cert = signed_certificate();
+        let trusted = trusting(&cert);
+        cert.issued_at += 1;
+        assert_eq!(cert.verify(&trusted).unwrap(), CertificateStatus::Invalid);
+    }
+
+    #[test]
+    fn test_certificate_malformed_key_is_invalid() {
+        let mut cert = signed_certificate();
+        cert.signer_public_key.truncate(10);
+        assert_eq!(
+            cert.verify(&trusting(&cert)).unwrap(),
+            CertificateStatus::Invalid
+        );
+
+        let mut cert = signed_certificate();
+        cert.signature.clear();
+        assert_eq!(
+            cert.verify(&trusting(&cert)).unwrap(),
+            CertificateStatus::Invalid
+        );
+    }
+}
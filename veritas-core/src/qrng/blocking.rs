@@ -0,0 +1,217 @@
+//! Blocking QRNG clients for callers without an async runtime.
+//!
+//! Python bindings and simple command-line tools often have no Tokio
+//! runtime. [`BlockingEntropySource`] is the blocking counterpart of
+//! [`QuantumEntropySource`](super::QuantumEntropySource), fed to
+//! [`SealBuilder::build_blocking`](crate::SealBuilder::build_blocking).
+//! [`BlockingLfdQrng`] fetches entropy with `reqwest::blocking`, with the
+//! same TLS 1.3 and certificate pinning as [`LfdQrng`](super::LfdQrng).
+
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tracing::{debug, info, instrument, warn};
+
+use super::http_client::{hex_to_bytes, is_transient_error, is_transient_status};
+use super::{AttestedEntropy, LfdQrngConfig, MockQrng, QrngSource};
+use crate::error::{Result, VeritasError};
+
+/// Blocking source of quantum entropy.
+///
+/// Implementations must be thread-safe (`Send + Sync`).
+pub trait BlockingEntropySource: Send + Sync {
+    /// Fetch 256 bits (32 bytes) of quantum random entropy, blocking the
+    /// calling thread.
+    fn get_entropy(&self) -> Result<[u8; 32]>;
+
+    /// Fetch entropy together with a provider-signed certificate of origin.
+    ///
+    /// Providers that do not sign their output return the entropy without a
+    /// certificate (the default implementation).
+    fn get_attested_entropy(&self) -> Result<AttestedEntropy> {
+        Ok(AttestedEntropy::unattested(self.get_entropy()?))
+    }
+
+    /// Returns the source identifier for attestation.
+    fn source_id(&self) -> QrngSource;
+}
+
+impl BlockingEntropySource for MockQrng {
+    fn get_entropy(&self) -> Result<[u8; 32]> {
+        self.get_entropy_sync()
+    }
+
+    fn source_id(&self) -> QrngSource {
+        MockQrng::source_id(self)
+    }
+}
+
+/// Initial wait before retrying a transient failure (doubles each retry).
+const INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Longest wait between retries.
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Blocking LfD Quantum Random Number Generator client.
+///
+/// Blocking counterpart of [`LfdQrng`](super::LfdQrng); must not be used
+/// from inside an async runtime (`reqwest::blocking` panics there).
+pub struct BlockingLfdQrng {
+    client: Client,
+    api_url: String,
+    max_retries: u32,
+}
+
+impl BlockingLfdQrng {
+    /// Create a new blocking LfD QRNG client with default settings.
+    pub fn new() -> Result<Self> {
+        Self::with_config(LfdQrngConfig::default())
+    }
+
+    /// Create a new blocking LfD QRNG client with custom configuration.
+    #[instrument(level = "debug", skip_all, fields(api_url = %config.api_url))]
+    pub fn with_config(config: LfdQrngConfig) -> Result<Self> {
+        debug!("Creating blocking LfD QRNG client");
+        let cert = reqwest::Certificate::from_pem(include_bytes!("certs/isrg_root_x1.pem"))
+            .map_err(|e| {
+                VeritasError::QrngError(format!("Failed to load pinned certificate: {e}"))
+            })?;
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .https_only(true)
+            .min_tls_version(reqwest::tls::Version::TLS_1_3)
+            .tls_built_in_root_certs(false)
+            .add_root_certificate(cert)
+            .build()
+            .map_err(|e| VeritasError::QrngError(format!("Failed to create HTTP client: {e}")))?;
+        info!("Blocking LfD QRNG client created successfully");
+        Ok(Self {
+            client,
+            api_url: format!("{}?length=32&format=HEX", config.api_url),
+            max_retries: config.max_retries,
+        })
+    }
+}
+
+/// Response structure from LfD QRNG API.
+#[derive(Debug, Deserialize)]
+struct LfdResponse {
+    qrn: String,
+}
+
+impl BlockingEntropySource for BlockingLfdQrng {
+    #[instrument(level = "info", skip(self), fields(source = "lfd"))]
+    fn get_entropy(&self) -> Result<[u8; 32]> {
+        let resp: LfdResponse =
+            fetch_json(&self.client, &self.api_url, "LfD QRNG", self.max_retries)?;
+        if resp.qrn.is_empty() {
+            return Err(VeritasError::QrngError(
+                "LfD QRNG API returned empty data".into(),
+            ));
+        }
+        hex_to_bytes(&resp.qrn, "LfD API")
+    }
+
+    fn source_id(&self) -> QrngSource {
+        QrngSource::LfdCloud
+    }
+}
+
+/// GET `url` and parse its JSON body, retrying transient failures up to
+/// `max_retries` times with exponential backoff.
+fn fetch_json<R: DeserializeOwned>(
+    client: &Client,
+    url: &str,
+    provider_name: &str,
+    max_retries: u32,
+) -> Result<R> {
+    let mut retry_interval = INITIAL_RETRY_INTERVAL;
+    let mut attempt = 0;
+    loop {
+        let start = Instant::now();
+        let (err, transient) = match client.get(url).send() {
+            Ok(response) if response.status().is_success() => {
+                debug!(
+                    latency_ms = start.elapsed().as_millis() as u64,
+                    "Request completed successfully"
+                );
+                return response.json().map_err(|e| {
+                    VeritasError::QrngError(format!(
+                        "Failed to parse {provider_name} response: {e}"
+                    ))
+                });
+            }
+            Ok(response) => {
+                let status = response.status();
+                (
+                    VeritasError::QrngError(format!(
+                        "{provider_name} API returned status: {status}"
+                    )),
+                    is_transient_status(status),
+                )
+            }
+            Err(e) => {
+                let transient = is_transient_error(&e);
+                (
+                    VeritasError::QrngError(format!("{provider_name} request failed: {e}")),
+                    transient,
+                )
+            }
+        };
+
+        if !transient || attempt >= max_retries {
+            warn!(error = %err, attempt, "Entropy request failed, aborting");
+            return Err(err);
+        }
+        warn!(
+            error = %err,
+            retry_after_ms = retry_interval.as_millis() as u64,
+            "Retry scheduled"
+        );
+        std::thread::sleep(retry_interval);
+        retry_interval = (retry_interval * 2).min(MAX_RETRY_INTERVAL);
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_client() {
+        let qrng = BlockingLfdQrng::new().unwrap();
+        assert_eq!(qrng.source_id(), QrngSource::LfdCloud);
+    }
+
+    #[test]
+    fn test_mock_matches_async_mock() {
+        let qrng = MockQrng::new(7);
+        let attested = BlockingEntropySource::get_attested_entropy(&qrng).unwrap();
+        assert_eq!(attested.entropy, qrng.get_entropy_sync().unwrap());
+        assert!(attested.certificate.is_none());
+    }
+
+    #[test]
+    fn test_unreachable_endpoint_fails() {
+        let qrng = BlockingLfdQrng::with_config(LfdQrngConfig {
+            api_url: "https://127.0.0.1:9/qrng".into(),
+            timeout: Duration::from_secs(1),
+            max_retries: 1,
+        })
+        .unwrap();
+        assert!(matches!(
+            qrng.get_entropy(),
+            Err(VeritasError::QrngError(_))
+        ));
+    }
+
+    #[test]
+    #[ignore = "requires network access to LfD QRNG API"]
+    fn test_lfd_real_api() {
+        let qrng = BlockingLfdQrng::new().unwrap();
+        assert_eq!(qrng.get_entropy().unwrap().len(), 32);
+    }
+}
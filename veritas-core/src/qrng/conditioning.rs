@@ -0,0 +1,106 @@
+//! Entropy conditioning: mixing QRNG output with local OS randomness.
+//!
+//! A single QRNG provider is a single point of trust. When conditioning is
+//! enabled, the 32 bytes stored in the seal are not the raw provider output but
+//! a SHA3-256 extraction over the QRNG block and an equal-sized block from the
+//! operating system CSPRNG. An attacker now has to control *both* sources to
+//! predict or bias the seal entropy.
+//!
+//! The policy used is recorded in the seal (`qrng_conditioning`) and covered
+//! by the signature, so verifiers can tell raw and mixed entropy apart.
+
+use serde::{Deserialize, Serialize};
+
+/// Domain separation tag for the SHA3 extractor.
+#[cfg(feature = "network")]
+const CONDITIONING_DOMAIN: &[u8] = b"veritas-qrng-conditioning-v1";
+
+/// Entropy conditioning policy applied before sealing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QrngConditioning {
+    /// `SHA3-256(domain || qrng_entropy || os_entropy)` with 32 bytes from the
+    /// operating system CSPRNG.
+    Sha3OsMix,
+}
+
+impl std::fmt::Display for QrngConditioning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sha3OsMix => write!(f, "SHA3-256 mix with OS randomness"),
+        }
+    }
+}
+
+/// Apply a conditioning policy to a block of QRNG entropy.
+#[cfg(feature = "network")]
+pub fn condition_entropy(
+    qrng_entropy: &[u8; 32],
+    policy: QrngConditioning,
+) -> crate::error::Result<[u8; 32]> {
+    use zeroize::Zeroize;
+
+    match policy {
+        QrngConditioning::Sha3OsMix => {
+            let mut os_entropy = [0u8; 32];
+            getrandom::fill(&mut os_entropy).map_err(|e| {
+                crate::error::VeritasError::QrngError(format!(
+                    "Failed to read OS randomness for conditioning: {e}"
+                ))
+            })?;
+
+            let mixed = mix_sha3(qrng_entropy, &os_entropy);
+            os_entropy.zeroize();
+            Ok(mixed)
+        }
+    }
+}
+
+/// SHA3-256 extractor over both inputs.
+#[cfg(feature = "network")]
+fn mix_sha3(qrng_entropy: &[u8; 32], os_entropy: &[u8; 32]) -> [u8; 32] {
+    use sha3::{Digest, Sha3_256};
+
+    let mut hasher = Sha3_256::new();
+    hasher.update(CONDITIONING_DOMAIN);
+    hasher.update(qrng_entropy);
+    hasher.update(os_entropy);
+
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.finalize());
+    out
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix_depends_on_both_inputs() {
+        let qrng = [0x11; 32];
+        let os_a = [0x22; 32];
+        let os_b = [0x33; 32];
+
+        let a = mix_sha3(&qrng, &os_a);
+        assert_eq!(a, mix_sha3(&qrng, &os_a));
+        assert_ne!(a, mix_sha3(&qrng, &os_b));
+        assert_ne!(a, mix_sha3(&[0x12; 32], &os_a));
+    }
+
+    #[test]
+    fn test_condition_entropy_is_not_raw_output() {
+        let qrng = [7u8; 32];
+        let first = condition_entropy(&qrng, QrngConditioning::Sha3OsMix).unwrap();
+        let second = condition_entropy(&qrng, QrngConditioning::Sha3OsMix).unwrap();
+
+        assert_ne!(first, qrng);
+        // Fresh OS randomness on every call
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_conditioning_serde_roundtrip() {
+        let json = serde_json::to_string(&QrngConditioning::Sha3OsMix).unwrap();
+        let parsed: QrngConditioning = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, QrngConditioning::Sha3OsMix);
+    }
+}
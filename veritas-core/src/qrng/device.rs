@@ -0,0 +1,233 @@
+//! Local hardware QRNG exposed as a character device.
+//!
+//! Quantis PCIe cards and ID Quantique USB modules ship with kernel drivers
+//! that expose raw quantum entropy as a character device (e.g. `/dev/qrandom0`).
+//! Reading from the device directly lets air-gapped deployments seal media
+//! without depending on a cloud QRNG.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tracing::{debug, info, instrument, warn};
+
+use super::{QrngSource, QuantumEntropySource};
+use crate::error::{Result, VeritasError};
+
+/// Default device node created by the Quantis driver.
+const DEFAULT_DEVICE_PATH: &str = "/dev/qrandom0";
+
+/// Configuration for a local hardware QRNG.
+#[derive(Debug, Clone)]
+pub struct DeviceQrngConfig {
+    /// Path to the character device (or any readable file of raw entropy).
+    pub device_path: PathBuf,
+    /// Identifier recorded in the seal as `QrngSource::DeviceHardware`.
+    ///
+    /// Defaults to the device path when unset.
+    pub device_id: Option<String>,
+    /// Maximum time to wait for the device to return 32 bytes.
+    pub timeout: Duration,
+}
+
+impl Default for DeviceQrngConfig {
+    fn default() -> Self {
+        Self {
+            device_path: std::env::var("QRNG_DEVICE_PATH")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from(DEFAULT_DEVICE_PATH)),
+            device_id: std::env::var("QRNG_DEVICE_ID").ok(),
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+impl DeviceQrngConfig {
+    /// Create configuration from environment variables.
+    ///
+    /// Required: `QRNG_DEVICE_PATH`
+    /// Optional: `QRNG_DEVICE_ID` (defaults to the device path)
+    pub fn from_env() -> Result<Self> {
+        if std::env::var("QRNG_DEVICE_PATH").is_err() {
+            return Err(VeritasError::QrngError(
+                "QRNG_DEVICE_PATH environment variable not set".into(),
+            ));
+        }
+        Ok(Self::default())
+    }
+}
+
+/// Hardware QRNG client reading from a local character device.
+pub struct DeviceQrng {
+    device_path: PathBuf,
+    device_id: String,
+    timeout: Duration,
+}
+
+impl DeviceQrng {
+    /// Open the default device (`QRNG_DEVICE_PATH` or `/dev/qrandom0`).
+    pub fn new() -> Result<Self> {
+        Self::with_config(DeviceQrngConfig::default())
+    }
+
+    /// Create a client for a specific device.
+    ///
+    /// Fails if the device cannot be opened for reading, so misconfiguration
+    /// is reported at startup rather than on the first seal.
+    #[instrument(level = "debug", skip_all, fields(device = %config.device_path.display()))]
+    pub fn with_config(config: DeviceQrngConfig) -> Result<Self> {
+        debug!("Opening hardware QRNG device");
+        File::open(&config.device_path).map_err(|e| {
+            VeritasError::QrngError(format!(
+                "Cannot open QRNG device {}: {e}",
+                config.device_path.display()
+            ))
+        })?;
+
+        let device_id = config
+            .device_id
+            .unwrap_or_else(|| config.device_path.display().to_string());
+
+        info!(device_id = %device_id, "Hardware QRNG device opened");
+        Ok(Self {
+            device_path: config.device_path,
+            device_id,
+            timeout: config.timeout,
+        })
+    }
+
+    /// Path of the underlying device.
+    pub fn device_path(&self) -> &Path {
+        &self.device_path
+    }
+
+    /// Blocking read of exactly 32 bytes from the device.
+    fn read_block(path: &Path) -> Result<[u8; 32]> {
+        let mut file = File::open(path).map_err(|e| {
+            VeritasError::QrngError(format!("Cannot open QRNG device {}: {e}", path.display()))
+        })?;
+
+        let mut entropy = [0u8; 32];
+        file.read_exact(&mut entropy).map_err(|e| {
+            VeritasError::QrngError(format!(
+                "Failed to read 32 bytes from QRNG device {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        Ok(entropy)
+    }
+}
+
+#[async_trait]
+impl QuantumEntropySource for DeviceQrng {
+    #[instrument(level = "info", skip(self), fields(source = "device", device_id = %self.device_id))]
+    async fn get_entropy(&self) -> Result<[u8; 32]> {
+        let path = self.device_path.clone();
+        let read = tokio::task::spawn_blocking(move || Self::read_block(&path));
+
+        match tokio::time::timeout(self.timeout, read).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) => Err(VeritasError::QrngError(format!(
+                "QRNG device read task failed: {e}"
+            ))),
+            Err(_) => {
+                warn!(
+                    timeout_ms = self.timeout.as_millis() as u64,
+                    "QRNG device read timed out"
+                );
+                Err(VeritasError::QrngError(format!(
+                    "QRNG device {} did not respond within {:?}",
+                    self.device_path.display(),
+                    self.timeout
+                )))
+            }
+        }
+    }
+
+    fn source_id(&self) -> QrngSource {
+        QrngSource::DeviceHardware {
+            device_id: self.device_id.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a fake "device" file filled with distinct bytes.
+    fn fake_device(name: &str, len: usize) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("veritas-device-qrng-{name}-{}", std::process::id()));
+        let data: Vec<u8> = (0..len).map(|i| (i * 37 + 11) as u8).collect();
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_missing_device_is_rejected() {
+        let config = DeviceQrngConfig {
+            device_path: PathBuf::from("/nonexistent/qrandom-veritas"),
+            device_id: None,
+            timeout: Duration::from_secs(1),
+        };
+        assert!(DeviceQrng::with_config(config).is_err());
+    }
+
+    #[test]
+    fn test_device_id_defaults_to_path() {
+        let path = fake_device("id", 64);
+        let qrng = DeviceQrng::with_config(DeviceQrngConfig {
+            device_path: path.clone(),
+            device_id: None,
+            timeout: Duration::from_secs(1),
+        })
+        .unwrap();
+
+        assert_eq!(
+            qrng.source_id(),
+            QrngSource::DeviceHardware {
+                device_id: path.display().to_string()
+            }
+        );
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_reads_entropy_from_device() {
+        let path = fake_device("read", 64);
+        let qrng = DeviceQrng::with_config(DeviceQrngConfig {
+            device_path: path.clone(),
+            device_id: Some("quantis-usb-001".into()),
+            timeout: Duration::from_secs(1),
+        })
+        .unwrap();
+
+        let entropy = qrng.get_entropy().await.unwrap();
+        assert_eq!(entropy[0], 11);
+        assert_eq!(
+            qrng.source_id(),
+            QrngSource::DeviceHardware {
+                device_id: "quantis-usb-001".into()
+            }
+        );
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_short_read_is_an_error() {
+        let path = fake_device("short", 16);
+        let qrng = DeviceQrng::with_config(DeviceQrngConfig {
+            device_path: path.clone(),
+            device_id: None,
+            timeout: Duration::from_secs(1),
+        })
+        .unwrap();
+
+        assert!(qrng.get_entropy().await.is_err());
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,516 @@
+//! Continuous health tests for QRNG output (NIST SP 800-90B, section 4.4).
+//!
+//! [`validate_entropy`](super::validate_entropy) only inspects a single
+//! 32-byte block. The tests in this module run continuously over every byte a
+//! provider returns, so slow failures (a stuck bit, a biased detector) are
+//! caught even when each individual block still looks plausible.
+//!
+//! - **Repetition Count Test** (section 4.4.1) - flags a run of identical
+//!   samples that is too long for the assumed min-entropy.
+//! - **Adaptive Proportion Test** (section 4.4.2) - splits the stream into
+//!   non-overlapping windows and flags a window whose first sample recurs too
+//!   often within it.
+//!
+//! Both cutoffs follow from the assessed min-entropy per sample and the window
+//! size, see [`HealthTestConfig::for_min_entropy`].
+//!
+//! Failures are latched: once a test has failed the monitor reports
+//! [`QrngHealthStatus::Degraded`] until [`EntropyHealthMonitor::reset`] is
+//! called, and [`HealthCheckedQrng`] refuses to hand out entropy meanwhile.
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use super::{AttestedEntropy, QrngHealthStatus, QrngSource, QuantumEntropySource};
+use crate::error::{Result, VeritasError};
+
+/// Min-entropy per byte assumed by the default thresholds, in bits.
+///
+/// Deliberately conservative for cloud QRNGs that claim full entropy.
+pub const DEFAULT_MIN_ENTROPY_BITS: f64 = 6.0;
+
+/// Adaptive Proportion Test window for non-binary samples (SP 800-90B).
+const ADAPTIVE_WINDOW: usize = 512;
+
+/// Targeted false-positive probability per test, α = 2^-20 (SP 800-90B).
+const FALSE_POSITIVE_BITS: f64 = 20.0;
+
+/// Thresholds for the continuous health tests.
+///
+/// Samples are bytes. Use [`HealthTestConfig::for_min_entropy`] to derive the
+/// cutoffs from a source's assessed min-entropy; the default assumes
+/// [`DEFAULT_MIN_ENTROPY_BITS`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthTestConfig {
+    /// Repetition Count Test cutoff: fail when this many identical bytes
+    /// appear consecutively.
+    pub repetition_cutoff: usize,
+    /// Adaptive Proportion Test window size in bytes.
+    pub adaptive_window: usize,
+    /// Adaptive Proportion Test cutoff: fail when the first byte of a window
+    /// appears this many times within that window.
+    pub adaptive_cutoff: usize,
+}
+
+impl Default for HealthTestConfig {
+    fn default() -> Self {
+        Self::derive(DEFAULT_MIN_ENTROPY_BITS)
+    }
+}
+
+impl HealthTestConfig {
+    /// Cutoffs for a source with `min_entropy` bits of min-entropy per byte
+    /// (H), as specified by SP 800-90B section 4.4 for α = 2^-20 and a window
+    /// of W = 512 bytes: `1 + ⌈20 / H⌉` for the Repetition Count Test and
+    /// `1 + CRITBINOM(W, 2^-H, 1 - α)` for the Adaptive Proportion Test.
+    pub fn for_min_entropy(min_entropy: f64) -> Result<Self> {
+        if !(min_entropy > 0.0 && min_entropy <= 8.0) {
+            return Err(VeritasError::QrngError(format!(
+                "Min-entropy per byte must be in (0, 8] bits, got {min_entropy}"
+            )));
+        }
+        Ok(Self::derive(min_entropy))
+    }
+
+    fn derive(min_entropy: f64) -> Self {
+        Self {
+            repetition_cutoff: 1 + (FALSE_POSITIVE_BITS / min_entropy).ceil() as usize,
+            adaptive_window: ADAPTIVE_WINDOW,
+            adaptive_cutoff: 1 + critical_binomial(ADAPTIVE_WINDOW, (-min_entropy).exp2()),
+        }
+    }
+
+    /// Check that the thresholds describe a usable test.
+    pub fn validate(&self) -> Result<()> {
+        if self.repetition_cutoff < 2 {
+            return Err(VeritasError::QrngError(
+                "Repetition count cutoff must be at least 2".into(),
+            ));
+        }
+        if self.adaptive_window == 0 {
+            return Err(VeritasError::QrngError(
+                "Adaptive proportion window must not be empty".into(),
+            ));
+        }
+        if self.adaptive_cutoff < 2 || self.adaptive_cutoff > self.adaptive_window {
+            return Err(VeritasError::QrngError(format!(
+                "Adaptive proportion cutoff must be between 2 and {}",
+                self.adaptive_window
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Smallest `k` such that a Binomial(`trials`, `p`) variable exceeds `k` with
+/// probability at most α (SP 800-90B's `CRITBINOM(trials, p, 1 - α)`).
+fn critical_binomial(trials: usize, p: f64) -> usize {
+    // Probabilities in log space: (1 - p)^trials underflows for low entropy
+    let (ln_p, ln_q) = (p.ln(), (-p).ln_1p());
+    let mut ln_choose = 0.0;
+    let mut pmf = Vec::with_capacity(trials + 1);
+    for k in 0..=trials {
+        if k > 0 {
+            ln_choose += ((trials - k + 1) as f64).ln() - (k as f64).ln();
+        }
+        pmf.push((ln_choose + k as f64 * ln_p + (trials - k) as f64 * ln_q).exp());
+    }
+
+    // Sum the upper tail P(X >= k) from the top, where the terms are smallest
+    let alpha = (-FALSE_POSITIVE_BITS).exp2();
+    let mut tail = 0.0;
+    for (k, probability) in pmf.iter().enumerate().skip(1).rev() {
+        tail += probability;
+        if tail > alpha {
+            return k;
+        }
+    }
+    0
+}
+
+/// Mutable state shared by both tests.
+#[derive(Debug, Default)]
+struct HealthState {
+    last_sample: Option<u8>,
+    run_length: usize,
+    /// First sample of the current Adaptive Proportion Test window
+    window_sample: Option<u8>,
+    /// Occurrences of `window_sample` in the window so far
+    window_count: usize,
+    /// Samples in the window so far
+    window_len: usize,
+    failure: Option<String>,
+}
+
+/// Runs the continuous health tests over a stream of entropy bytes.
+#[derive(Debug)]
+pub struct EntropyHealthMonitor {
+    config: HealthTestConfig,
+    state: Mutex<HealthState>,
+}
+
+impl EntropyHealthMonitor {
+    /// Create a monitor with the default thresholds.
+    pub fn new() -> Self {
+        Self {
+            config: HealthTestConfig::default(),
+            state: Mutex::new(HealthState::default()),
+        }
+    }
+
+    /// Create a monitor with custom thresholds.
+    pub fn with_config(config: HealthTestConfig) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            state: Mutex::new(HealthState::default()),
+        })
+    }
+
+    /// The thresholds in use.
+    pub fn config(&self) -> &HealthTestConfig {
+        &self.config
+    }
+
+    /// Feed freshly fetched entropy through both tests.
+    ///
+    /// Returns the resulting health status. A failure detected now or earlier
+    /// is reported as [`QrngHealthStatus::Degraded`].
+    pub fn observe(&self, entropy: &[u8]) -> QrngHealthStatus {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        for &sample in entropy {
+            // Repetition Count Test
+            if state.last_sample == Some(sample) {
+                state.run_length += 1;
+            } else {
+                state.last_sample = Some(sample);
+                state.run_length = 1;
+            }
+            if state.run_length >= self.config.repetition_cutoff && state.failure.is_none() {
+                state.failure = Some(format!(
+                    "Repetition count test failed: byte 0x{sample:02x} repeated {} times",
+                    state.run_length
+                ));
+            }
+
+            // Adaptive Proportion Test (non-overlapping windows)
+            state.window_len += 1;
+            match state.window_sample {
+                None => {
+                    state.window_sample = Some(sample);
+                    state.window_count = 1;
+                }
+                Some(first) if first == sample => state.window_count += 1,
+                Some(_) => {}
+            }
+            if state.window_count >= self.config.adaptive_cutoff && state.failure.is_none() {
+                state.failure = Some(format!(
+                    "Adaptive proportion test failed: byte 0x{sample:02x} seen {} times in {} samples",
+                    state.window_count, state.window_len
+                ));
+            }
+            if state.window_len == self.config.adaptive_window {
+                state.window_sample = None;
+                state.window_count = 0;
+                state.window_len = 0;
+            }
+        }
+
+        Self::status_of(&state)
+    }
+
+    /// Current health status without feeding new samples.
+    pub fn status(&self) -> QrngHealthStatus {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        Self::status_of(&state)
+    }
+
+    /// Clear all test state, including a latched failure.
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        *state = HealthState::default();
+    }
+
+    fn status_of(state: &HealthState) -> QrngHealthStatus {
+        match &state.failure {
+            Some(reason) => QrngHealthStatus::Degraded {
+                reason: reason.clone(),
+            },
+            None => QrngHealthStatus::Healthy,
+        }
+    }
+}
+
+impl Default for EntropyHealthMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps any entropy source and runs the continuous health tests on its output.
+///
+/// Once a test fails, every request (including the one that tripped it)
+/// returns [`VeritasError::QrngError`] until [`reset`](Self::reset) is
+/// called, so a failing source cannot feed seals.
+pub struct HealthCheckedQrng<S> {
+    inner: S,
+    monitor: EntropyHealthMonitor,
+}
+
+impl<S: QuantumEntropySource> HealthCheckedQrng<S> {
+    /// Wrap a source using the default thresholds.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            monitor: EntropyHealthMonitor::new(),
+        }
+    }
+
+    /// Wrap a source using custom thresholds.
+    pub fn with_config(inner: S, config: HealthTestConfig) -> Result<Self> {
+        Ok(Self {
+            inner,
+            monitor: EntropyHealthMonitor::with_config(config)?,
+        })
+    }
+
+    /// Health status derived from all entropy fetched so far.
+    pub fn health(&self) -> QrngHealthStatus {
+        self.monitor.status()
+    }
+
+    /// Clear a latched failure (e.g. after maintenance of the source).
+    pub fn reset(&self) {
+        self.monitor.reset();
+    }
+
+    /// Access the underlying monitor.
+    pub fn monitor(&self) -> &EntropyHealthMonitor {
+        &self.monitor
+    }
+
+    /// Access the wrapped source.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Refuse to serve while degraded, before asking the source.
+    fn ensure_healthy(&self) -> Result<()> {
+        match self.monitor.status() {
+            QrngHealthStatus::Degraded { reason } => Err(degraded(reason)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Run the tests on a fresh block, refusing it if they fail.
+    fn check(&self, entropy: &[u8; 32]) -> Result<()> {
+        match self.monitor.observe(entropy) {
+            QrngHealthStatus::Degraded { reason } => {
+                warn!(source = %self.inner.source_id(), %reason, "QRNG health test failure");
+                Err(degraded(reason))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn degraded(reason: String) -> VeritasError {
+    VeritasError::QrngError(format!("QRNG failed its health tests: {reason}"))
+}
+
+#[async_trait]
+impl<S: QuantumEntropySource> QuantumEntropySource for HealthCheckedQrng<S> {
+    async fn get_entropy(&self) -> Result<[u8; 32]> {
+        self.ensure_healthy()?;
+        let entropy = self.inner.get_entropy().await?;
+        self.check(&entropy)?;
+        Ok(entropy)
+    }
+
+    async fn get_attested_entropy(&self) -> Result<AttestedEntropy> {
+        self.ensure_healthy()?;
+        let attested = self.inner.get_attested_entropy().await?;
+        self.check(&attested.entropy)?;
+        Ok(attested)
+    }
+
+    fn source_id(&self) -> QrngSource {
+        self.inner.source_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+
+    /// Deterministic, well-spread byte stream for the happy path.
+    fn spread_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 167 + 13) as u8).collect()
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(HealthTestConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_rejects_bad_thresholds() {
+        let config = HealthTestConfig {
+            repetition_cutoff: 1,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = HealthTestConfig {
+            adaptive_window: 16,
+            adaptive_cutoff: 32,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_healthy_stream() {
+        let monitor = EntropyHealthMonitor::new();
+        assert_eq!(
+            monitor.observe(&spread_bytes(4096)),
+            QrngHealthStatus::Healthy
+        );
+    }
+
+    #[test]
+    fn test_repetition_count_failure() {
+        let monitor = EntropyHealthMonitor::new();
+        let mut data = spread_bytes(32);
+        data[10..15].fill(0x42);
+
+        let status = monitor.observe(&data);
+        match status {
+            QrngHealthStatus::Degraded { reason } => assert!(reason.contains("Repetition")),
+            other => panic!("expected Degraded, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_repetition_run_spans_blocks() {
+        let monitor = EntropyHealthMonitor::new();
+        assert_eq!(monitor.observe(&[1, 2, 7, 7, 7]), QrngHealthStatus::Healthy);
+        assert!(matches!(
+            monitor.observe(&[7, 7, 3]),
+            QrngHealthStatus::Degraded { .. }
+        ));
+    }
+
+    #[test]
+    fn test_adaptive_proportion_failure() {
+        let monitor = EntropyHealthMonitor::new();
+        // Interleave so the repetition test never trips.
+        let data: Vec<u8> = (0..64)
+            .map(|i| if i % 2 == 0 { 0xAA } else { i as u8 })
+            .collect();
+
+        match monitor.observe(&data) {
+            QrngHealthStatus::Degraded { reason } => assert!(reason.contains("Adaptive")),
+            other => panic!("expected Degraded, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_cutoffs_follow_sp800_90b() {
+        // SP 800-90B Table 2 (W = 512) and the RCT formula, α = 2^-20
+        for (min_entropy, repetition, adaptive) in
+            [(1.0, 21, 311), (2.0, 11, 177), (4.0, 6, 62), (8.0, 4, 13)]
+        {
+            let config = HealthTestConfig::for_min_entropy(min_entropy).unwrap();
+            assert_eq!(config.repetition_cutoff, repetition, "H = {min_entropy}");
+            assert_eq!(config.adaptive_window, 512);
+            assert_eq!(config.adaptive_cutoff, adaptive, "H = {min_entropy}");
+        }
+        assert_eq!(
+            HealthTestConfig::default(),
+            HealthTestConfig::for_min_entropy(DEFAULT_MIN_ENTROPY_BITS).unwrap()
+        );
+
+        assert!(HealthTestConfig::for_min_entropy(0.0).is_err());
+        assert!(HealthTestConfig::for_min_entropy(8.5).is_err());
+    }
+
+    #[test]
+    fn test_adaptive_proportion_counts_first_sample_only() {
+        let config = HealthTestConfig {
+            repetition_cutoff: 5,
+            adaptive_window: 8,
+            adaptive_cutoff: 4,
+        };
+        let monitor = EntropyHealthMonitor::with_config(config).unwrap();
+        // 0x02 appears 4 times, but the window starts with 0x01
+        assert_eq!(
+            monitor.observe(&[1, 2, 9, 2, 9, 2, 9, 2]),
+            QrngHealthStatus::Healthy
+        );
+    }
+
+    #[test]
+    fn test_adaptive_windows_do_not_overlap() {
+        let config = HealthTestConfig {
+            repetition_cutoff: 5,
+            adaptive_window: 8,
+            adaptive_cutoff: 4,
+        };
+        let monitor = EntropyHealthMonitor::with_config(config.clone()).unwrap();
+        // 0x00 fills 5 of the 8 samples straddling the window boundary, but
+        // only the second window starts with it and holds it 3 times
+        let data = [1, 2, 3, 0, 4, 0, 5, 0, 0, 6, 0, 7, 0, 8, 9, 10];
+        assert_eq!(monitor.observe(&data), QrngHealthStatus::Healthy);
+
+        let monitor = EntropyHealthMonitor::with_config(config).unwrap();
+        assert!(matches!(
+            monitor.observe(&[0, 1, 0, 2, 0, 3, 0, 4]),
+            QrngHealthStatus::Degraded { .. }
+        ));
+    }
+
+    #[test]
+    fn test_failure_is_latched_until_reset() {
+        let monitor = EntropyHealthMonitor::new();
+        monitor.observe(&[9; 8]);
+        monitor.observe(&spread_bytes(256));
+        assert!(matches!(
+            monitor.status(),
+            QrngHealthStatus::Degraded { .. }
+        ));
+
+        monitor.reset();
+        assert_eq!(monitor.status(), QrngHealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_health_checked_qrng_detects_stuck_source() {
+        // MockQrng returns the same block on every call, so with a 256-byte
+        // window every value in the block is seen at least 8 times.
+        let config = HealthTestConfig {
+            repetition_cutoff: 5,
+            adaptive_window: 256,
+            adaptive_cutoff: 8,
+        };
+        let qrng = HealthCheckedQrng::with_config(MockQrng::default(), config).unwrap();
+        assert_eq!(qrng.source_id(), QrngSource::Mock);
+
+        // The block's first byte occurs once per block
+        for _ in 0..7 {
+            qrng.get_entropy().await.unwrap();
+        }
+        assert_eq!(qrng.health(), QrngHealthStatus::Healthy);
+        assert!(qrng.get_entropy().await.is_err());
+        assert!(matches!(qrng.health(), QrngHealthStatus::Degraded { .. }));
+
+        // Refused until reset, even though the source still answers
+        assert!(qrng.get_attested_entropy().await.is_err());
+        qrng.reset();
+        assert!(qrng.get_entropy().await.is_ok());
+    }
+}
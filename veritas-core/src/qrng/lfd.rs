@@ -3,6 +3,9 @@
 //! Uses the free public API at https://lfdr.de/qrng_api/
 //! Backed by an ID Quantique QRNG PCIe device.
 //!
+//! The API does not sign its output, so LfD entropy comes without an entropy
+//! certificate (see [`super::EntropyCertificate`]).
+//!
 //! Certificate pinning to ISRG Root X1 (Let's Encrypt root CA).
 
 use async_trait::async_trait;
@@ -10,6 +10,7 @@
 //! - **LfD QRNG** - LfD Germany (default fallback, free, backed by ID Quantique hardware)
 //! - **ID Quantique** - Production-grade quantum entropy (requires API key)
 //! - **ANU QRNG** - Australian National University (deprecated - SSL expired)
+//! - **Device** - Local hardware QRNG via character device (air-gapped deployments)
 //! - **Mock** - Deterministic mock for testing (always available, no network required)
 //!
 //! ## Quick Start
@@ -29,20 +30,53 @@
 mod mock;
 pub use mock::MockQrng;
 
+// Entropy certificates are verified offline, so they are always available
+mod attestation;
+pub use attestation::{
+    AttestedEntropy, CertificateAlgorithm, CertificateStatus, EntropyCertificate,
+    TrustedProviderKeys,
+};
+
+// Conditioning policy is recorded in seals, so the type is always available
+mod conditioning;
+#[cfg(feature = "network")]
+pub use conditioning::condition_entropy;
+pub use conditioning::QrngConditioning;
+
+// Reuse policy is recorded in seals, so the type is always available
+mod policy;
+pub use policy::QrngPolicy;
+
 #[cfg(feature = "network")]
 mod anu;
+#[cfg(feature = "blocking")]
+mod blocking;
+#[cfg(feature = "network")]
+mod device;
+#[cfg(feature = "network")]
+mod health;
 #[cfg(feature = "network")]
 mod http_client;
 #[cfg(feature = "network")]
 mod lfd;
 #[cfg(feature = "network")]
+mod pool;
+#[cfg(feature = "network")]
 mod provider;
 
 #[cfg(feature = "network")]
 pub use anu::{AnuQrng, AnuQrngConfig};
+#[cfg(feature = "blocking")]
+pub use blocking::{BlockingEntropySource, BlockingLfdQrng};
+#[cfg(feature = "network")]
+pub use device::{DeviceQrng, DeviceQrngConfig};
+#[cfg(feature = "network")]
+pub use health::{EntropyHealthMonitor, HealthCheckedQrng, HealthTestConfig};
 #[cfg(feature = "network")]
 pub use lfd::{LfdQrng, LfdQrngConfig};
 #[cfg(feature = "network")]
+pub use pool::EntropyPool;
+#[cfg(feature = "network")]
 pub use provider::{
     IdQuantiqueConfig, IdQuantiqueQrng, QrngCapabilities, QrngHealthStatus, QrngProviderConfig,
     QrngProviderFactory,
@@ -67,6 +101,15 @@ pub trait QuantumEntropySource: Send + Sync {
     /// asynchronously. Implementations should handle retries internally.
     async fn get_entropy(&self) -> Result<[u8; 32]>;
 
+    /// Fetch entropy together with a provider-signed certificate of origin.
+    ///
+    /// Providers that do not sign their output (all built-in providers except
+    /// ID Quantique) return the entropy without a certificate (the default
+    /// implementation).
+    async fn get_attested_entropy(&self) -> Result<AttestedEntropy> {
+        Ok(AttestedEntropy::unattested(self.get_entropy().await?))
+    }
+
     /// Returns the source identifier for attestation.
     fn source_id(&self) -> QrngSource;
 }
@@ -97,6 +140,7 @@ pub enum QrngSource {
 /// - Rejects repeating 2-byte patterns (e.g., 0xAB 0xCD repeated)
 ///
 /// This is NOT a full NIST SP 800-90B test — it only catches obvious failures.
+/// See [`EntropyHealthMonitor`] for continuous health testing across blocks.
 #[cfg(feature = "network")]
 pub fn validate_entropy(entropy: &[u8; 32]) -> crate::error::Result<()> {
     // Check all-zero
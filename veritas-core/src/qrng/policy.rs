@@ -0,0 +1,117 @@
+//! Entropy reuse policy: fresh QRNG draws per seal or a pre-drawn pool.
+//!
+//! Some compliance regimes require every seal to use entropy drawn from the
+//! QRNG for that seal; others accept blocks drawn ahead of time, as long as
+//! each block is used once and is not too old. Pooling takes the provider
+//! round-trip out of the sealing latency and rides out short outages.
+//!
+//! The policy is recorded in the seal (`qrng_policy`) and covered by the
+//! signature. [`SealBuilder`](crate::SealBuilder) enforces it: entropy drawn
+//! ahead of time (see [`AttestedEntropy::drawn_at`]) is refused under
+//! [`QrngPolicy::FreshPerSeal`], and when older than `max_age_secs` otherwise.
+
+use serde::{Deserialize, Serialize};
+
+use super::AttestedEntropy;
+use crate::error::{Result, VeritasError};
+
+/// How seal entropy may be obtained from the QRNG.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QrngPolicy {
+    /// A fresh QRNG draw for every seal (the behavior of unrecorded seals).
+    #[default]
+    FreshPerSeal,
+    /// Blocks drawn ahead into a pool, each used once, at most
+    /// `max_age_secs` old when sealing.
+    Pooled { max_age_secs: u64 },
+    /// A fresh draw when the provider answers, otherwise a pooled block at
+    /// most `max_age_secs` old.
+    Mixed { max_age_secs: u64 },
+}
+
+impl QrngPolicy {
+    /// Maximum age of pooled entropy (`None` = pooled entropy not allowed).
+    pub fn max_age_secs(&self) -> Option<u64> {
+        match self {
+            Self::FreshPerSeal => None,
+            Self::Pooled { max_age_secs } | Self::Mixed { max_age_secs } => Some(*max_age_secs),
+        }
+    }
+
+    /// Check that entropy obtained at `now_ms` (Unix ms) satisfies the policy.
+    pub fn check(&self, entropy: &AttestedEntropy, now_ms: u64) -> Result<()> {
+        let Some(drawn_at) = entropy.drawn_at else {
+            return Ok(());
+        };
+        let Some(max_age_secs) = self.max_age_secs() else {
+            return Err(VeritasError::QrngError(
+                "QRNG policy requires fresh entropy per seal, got pooled entropy".into(),
+            ));
+        };
+
+        let age_ms = now_ms.saturating_sub(drawn_at);
+        if age_ms > max_age_secs * 1000 {
+            return Err(VeritasError::QrngError(format!(
+                "Pooled entropy is {}s old, QRNG policy allows {}s",
+                age_ms / 1000,
+                max_age_secs
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for QrngPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FreshPerSeal => write!(f, "fresh entropy per seal"),
+            Self::Pooled { max_age_secs } => {
+                write!(f, "pooled entropy (max age {max_age_secs}s)")
+            }
+            Self::Mixed { max_age_secs } => {
+                write!(
+                    f,
+                    "fresh entropy, pooled fallback (max age {max_age_secs}s)"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pooled(drawn_at: u64) -> AttestedEntropy {
+        AttestedEntropy {
+            drawn_at: Some(drawn_at),
+            ..AttestedEntropy::unattested([7; 32])
+        }
+    }
+
+    #[test]
+    fn test_fresh_entropy_satisfies_every_policy() {
+        let fresh = AttestedEntropy::unattested([7; 32]);
+        for policy in [
+            QrngPolicy::FreshPerSeal,
+            QrngPolicy::Pooled { max_age_secs: 60 },
+            QrngPolicy::Mixed { max_age_secs: 60 },
+        ] {
+            assert!(policy.check(&fresh, 1_000_000).is_ok(), "{policy}");
+        }
+    }
+
+    #[test]
+    fn test_pooled_entropy_age_limit() {
+        let now = 1_000_000;
+        assert!(QrngPolicy::FreshPerSeal.check(&pooled(now), now).is_err());
+
+        let policy = QrngPolicy::Pooled { max_age_secs: 60 };
+        assert!(policy.check(&pooled(now - 60_000), now).is_ok());
+        assert!(policy.check(&pooled(now - 60_001), now).is_err());
+
+        let policy = QrngPolicy::Mixed { max_age_secs: 10 };
+        assert!(policy.check(&pooled(now - 5_000), now).is_ok());
+        assert!(policy.check(&pooled(now - 11_000), now).is_err());
+    }
+}
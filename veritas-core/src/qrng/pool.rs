@@ -0,0 +1,241 @@
+//! Entropy pool serving blocks according to a [`QrngPolicy`].
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::warn;
+
+use super::{AttestedEntropy, QrngPolicy, QrngSource, QuantumEntropySource};
+use crate::error::Result;
+
+/// Wraps an entropy source and draws blocks ahead according to a policy.
+///
+/// - [`QrngPolicy::FreshPerSeal`]: every request goes to the source.
+/// - [`QrngPolicy::Pooled`]: requests are served from the pool; when it runs
+///   dry, `size` blocks are drawn at once (the first one answers the request).
+/// - [`QrngPolicy::Mixed`]: requests go to the source, and one extra block is
+///   drawn into the pool while it holds fewer than `size`; the pool is only
+///   used when the source fails.
+///
+/// Each pooled block is handed out once and discarded once older than the
+/// policy's `max_age_secs`. Pooled blocks carry their draw time in
+/// [`AttestedEntropy::drawn_at`] so the seal builder can enforce the policy.
+pub struct EntropyPool {
+    inner: Arc<dyn QuantumEntropySource>,
+    policy: QrngPolicy,
+    size: usize,
+    blocks: Mutex<VecDeque<AttestedEntropy>>,
+}
+
+impl EntropyPool {
+    /// Pool up to `size` blocks (at least 1) from `inner`.
+    pub fn new(inner: Arc<dyn QuantumEntropySource>, policy: QrngPolicy, size: usize) -> Self {
+        Self {
+            inner,
+            policy,
+            size: size.max(1),
+            blocks: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Policy applied by this pool.
+    pub fn policy(&self) -> QrngPolicy {
+        self.policy
+    }
+
+    /// Unexpired blocks currently pooled.
+    pub fn available(&self) -> usize {
+        let mut blocks = self.blocks.lock().unwrap_or_else(|e| e.into_inner());
+        self.discard_expired(&mut blocks, now_ms());
+        blocks.len()
+    }
+
+    /// Take the oldest unexpired block, if any.
+    fn take(&self, now_ms: u64) -> Option<AttestedEntropy> {
+        let mut blocks = self.blocks.lock().unwrap_or_else(|e| e.into_inner());
+        self.discard_expired(&mut blocks, now_ms);
+        blocks.pop_front()
+    }
+
+    fn discard_expired(&self, blocks: &mut VecDeque<AttestedEntropy>, now_ms: u64) {
+        let max_age_ms = self.policy.max_age_secs().unwrap_or(0) * 1000;
+        blocks.retain(|block| {
+            block
+                .drawn_at
+                .is_some_and(|drawn_at| now_ms.saturating_sub(drawn_at) <= max_age_ms)
+        });
+    }
+
+    /// Draw `count` blocks into the pool, stopping at the first failure.
+    async fn fill(&self, count: usize) -> Result<()> {
+        for _ in 0..count {
+            let mut block = self.inner.get_attested_entropy().await?;
+            block.drawn_at = Some(now_ms());
+            self.blocks
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push_back(block);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl QuantumEntropySource for EntropyPool {
+    async fn get_entropy(&self) -> Result<[u8; 32]> {
+        Ok(self.get_attested_entropy().await?.entropy)
+    }
+
+    async fn get_attested_entropy(&self) -> Result<AttestedEntropy> {
+        match self.policy {
+            QrngPolicy::FreshPerSeal => self.inner.get_attested_entropy().await,
+            QrngPolicy::Pooled { .. } => {
+                if let Some(block) = self.take(now_ms()) {
+                    return Ok(block);
+                }
+                let fresh = self.inner.get_attested_entropy().await?;
+                if let Err(e) = self.fill(self.size - 1).await {
+                    warn!(source = %self.inner.source_id(), error = %e, "QRNG pool refill incomplete");
+                }
+                Ok(fresh)
+            }
+            QrngPolicy::Mixed { .. } => match self.inner.get_attested_entropy().await {
+                Ok(fresh) => {
+                    if self.available() < self.size {
+                        if let Err(e) = self.fill(1).await {
+                            warn!(source = %self.inner.source_id(), error = %e, "QRNG pool top-up failed");
+                        }
+                    }
+                    Ok(fresh)
+                }
+                Err(e) => match self.take(now_ms()) {
+                    Some(block) => {
+                        warn!(source = %self.inner.source_id(), error = %e, "QRNG draw failed, using pooled entropy");
+                        Ok(block)
+                    }
+                    None => Err(e),
+                },
+            },
+        }
+    }
+
+    fn source_id(&self) -> QrngSource {
+        self.inner.source_id()
+    }
+}
+
+fn now_ms() -> u64 {
+    u64::try_from(Utc::now().timestamp_millis()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::error::VeritasError;
+
+    /// Counts draws and fails on demand, with a distinct block per draw.
+    #[derive(Default)]
+    struct CountingQrng {
+        draws: AtomicUsize,
+        failing: AtomicBool,
+    }
+
+    #[async_trait]
+    impl QuantumEntropySource for CountingQrng {
+        async fn get_entropy(&self) -> Result<[u8; 32]> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(VeritasError::QrngError("provider down".into()));
+            }
+            let n = self.draws.fetch_add(1, Ordering::SeqCst);
+            Ok([n as u8 + 1; 32])
+        }
+
+        fn source_id(&self) -> QrngSource {
+            QrngSource::Mock
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fresh_policy_always_draws() {
+        let source = Arc::new(CountingQrng::default());
+        let pool = EntropyPool::new(source.clone(), QrngPolicy::FreshPerSeal, 8);
+
+        for _ in 0..3 {
+            assert!(pool
+                .get_attested_entropy()
+                .await
+                .unwrap()
+                .drawn_at
+                .is_none());
+        }
+        assert_eq!(source.draws.load(Ordering::SeqCst), 3);
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pooled_policy_draws_in_batches() {
+        let source = Arc::new(CountingQrng::default());
+        let pool = EntropyPool::new(source.clone(), QrngPolicy::Pooled { max_age_secs: 60 }, 4);
+
+        let first = pool.get_attested_entropy().await.unwrap();
+        assert!(first.drawn_at.is_none());
+        assert_eq!(source.draws.load(Ordering::SeqCst), 4);
+
+        // The next three requests are served from the pool, each block once
+        let mut seen = vec![first.entropy];
+        for _ in 0..3 {
+            let block = pool.get_attested_entropy().await.unwrap();
+            assert!(block.drawn_at.is_some());
+            assert!(!seen.contains(&block.entropy));
+            seen.push(block.entropy);
+        }
+        assert_eq!(source.draws.load(Ordering::SeqCst), 4);
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_mixed_policy_falls_back_to_pool() {
+        let source = Arc::new(CountingQrng::default());
+        let pool = EntropyPool::new(source.clone(), QrngPolicy::Mixed { max_age_secs: 60 }, 1);
+
+        assert!(pool
+            .get_attested_entropy()
+            .await
+            .unwrap()
+            .drawn_at
+            .is_none());
+        assert_eq!(pool.available(), 1);
+
+        source.failing.store(true, Ordering::SeqCst);
+        assert!(pool
+            .get_attested_entropy()
+            .await
+            .unwrap()
+            .drawn_at
+            .is_some());
+        assert!(pool.get_attested_entropy().await.is_err());
+    }
+
+    #[test]
+    fn test_expired_blocks_are_discarded() {
+        let pool = EntropyPool::new(
+            Arc::new(CountingQrng::default()),
+            QrngPolicy::Pooled { max_age_secs: 10 },
+            4,
+        );
+        let now = 1_000_000;
+        for drawn_at in [now - 20_000, now - 5_000] {
+            pool.blocks.lock().unwrap().push_back(AttestedEntropy {
+                drawn_at: Some(drawn_at),
+                ..AttestedEntropy::unattested([1; 32])
+            });
+        }
+
+        assert_eq!(pool.take(now).unwrap().drawn_at, Some(now - 5_000));
+        assert!(pool.take(now).is_none());
+    }
+}
@@ -8,12 +8,18 @@
 //! - `AnuQrng` - Australian National University (development)
 //! - `LfdQrng` - LfD Germany (default fallback, free)
 //! - `IdQuantiqueQrng` - ID Quantique (production)
+//! - `DeviceQrng` - Local hardware QRNG (air-gapped)
 //! - `MockQrng` - Deterministic mock (testing only)
+//!
+//! Every provider except the mock is wrapped in [`HealthCheckedQrng`], so a
+//! source failing the continuous health tests stops feeding seals.
 
 use std::sync::Arc;
 
 use super::{
-    AnuQrng, AnuQrngConfig, LfdQrng, LfdQrngConfig, MockQrng, QrngSource, QuantumEntropySource,
+    AnuQrng, AnuQrngConfig, AttestedEntropy, CertificateAlgorithm, DeviceQrng, DeviceQrngConfig,
+    EntropyCertificate, HealthCheckedQrng, LfdQrng, LfdQrngConfig, MockQrng, QrngSource,
+    QuantumEntropySource,
 };
 use crate::error::{Result, VeritasError};
 
@@ -29,6 +35,9 @@ pub enum QrngProviderConfig {
     /// ID Quantique (production)
     IdQuantique(IdQuantiqueConfig),
 
+    /// Local hardware QRNG (character device)
+    Device(DeviceQrngConfig),
+
     /// Mock provider (testing only)
     Mock { seed: u64 },
 
@@ -126,19 +135,26 @@ pub struct QrngProviderFactory;
 
 impl QrngProviderFactory {
     /// Create a QRNG provider from configuration.
+    ///
+    /// Real providers come wrapped in [`HealthCheckedQrng`]; the mock is
+    /// returned as is since its deterministic output would trip the tests.
     pub fn create(config: QrngProviderConfig) -> Result<Arc<dyn QuantumEntropySource>> {
         match config {
             QrngProviderConfig::Anu(anu_config) => {
                 let provider = AnuQrng::with_config(anu_config)?;
-                Ok(Arc::new(provider))
+                Ok(Arc::new(HealthCheckedQrng::new(provider)))
             }
             QrngProviderConfig::Lfd(lfd_config) => {
                 let provider = LfdQrng::with_config(lfd_config)?;
-                Ok(Arc::new(provider))
+                Ok(Arc::new(HealthCheckedQrng::new(provider)))
             }
             QrngProviderConfig::IdQuantique(idq_config) => {
                 let provider = IdQuantiqueQrng::new(idq_config)?;
-                Ok(Arc::new(provider))
+                Ok(Arc::new(HealthCheckedQrng::new(provider)))
+            }
+            QrngProviderConfig::Device(device_config) => {
+                let provider = DeviceQrng::with_config(device_config)?;
+                Ok(Arc::new(HealthCheckedQrng::new(provider)))
             }
             QrngProviderConfig::Mock { seed } => {
                 let provider = MockQrng::new(seed);
@@ -151,9 +167,15 @@ impl QrngProviderFactory {
     /// Auto-select the best available provider.
     ///
     /// Priority:
-    /// 1. ID Quantique (if QRNG_API_KEY is set)
-    /// 2. LfD QRNG (Germany, free, backed by ID Quantique hardware)
+    /// 1. Local hardware device (if QRNG_DEVICE_PATH is set)
+    /// 2. ID Quantique (if QRNG_API_KEY is set)
+    /// 3. LfD QRNG (Germany, free, backed by ID Quantique hardware)
     fn create_auto() -> Result<Arc<dyn QuantumEntropySource>> {
+        if let Ok(device_config) = DeviceQrngConfig::from_env() {
+            tracing::info!("Auto-selected hardware QRNG device");
+            return Self::create(QrngProviderConfig::Device(device_config));
+        }
+
         if let Ok(idq_config) = IdQuantiqueConfig::from_env() {
             tracing::info!("Auto-selected ID Quantique QRNG provider");
             return Self::create(QrngProviderConfig::IdQuantique(idq_config));
@@ -202,6 +224,17 @@ struct EntropyRequest {
 #[derive(Debug, Deserialize)]
 struct EntropyResponse {
     entropy: Vec<String>, // Base64 encoded
+    /// Provider signature over the returned block (when attestation is enabled)
+    #[serde(default)]
+    certificate: Option<CertificateResponse>,
+}
+
+/// Entropy certificate as returned by the provider.
+#[derive(Debug, Deserialize)]
+struct CertificateResponse {
+    issued_at: u64,
+    public_key: String, // Base64 encoded
+    signature: String,  // Base64 encoded
 }
 
 /// QRNG Open API capabilities response.
@@ -333,7 +366,7 @@ impl IdQuantiqueQrng {
     /// Fetch entropy (single attempt).
     async fn fetch_entropy_once(
         &self,
-    ) -> std::result::Result<[u8; 32], backoff::Error<VeritasError>> {
+    ) -> std::result::Result<AttestedEntropy, backoff::Error<VeritasError>> {
         let url = format!("{}/entropy", self.config.api_url);
         let start = Instant::now();
 
@@ -399,32 +432,37 @@ impl IdQuantiqueQrng {
         let mut result = [0u8; 32];
         result.copy_from_slice(&bytes);
 
+        let certificate = entropy_response
+            .certificate
+            .map(|cert| -> Result<EntropyCertificate> {
+                Ok(EntropyCertificate {
+                    source: QrngSource::IdQuantiqueCloud,
+                    entropy: result,
+                    issued_at: cert.issued_at,
+                    algorithm: CertificateAlgorithm::MlDsa65,
+                    signer_public_key: base64_decode(&cert.public_key)?,
+                    signature: base64_decode(&cert.signature)?,
+                })
+            })
+            .transpose()
+            .map_err(backoff::Error::permanent)?;
+
         let latency_ms = start.elapsed().as_millis();
         debug!(
             latency_ms = latency_ms as u64,
+            attested = certificate.is_some(),
             "Entropy fetched successfully"
         );
 
-        Ok(result)
+        Ok(AttestedEntropy {
+            entropy: result,
+            certificate,
+            drawn_at: None,
+        })
     }
-}
 
-/// Decode base64 string to bytes.
-fn base64_decode(encoded: &str) -> Result<Vec<u8>> {
-    use base64::Engine;
-    base64::engine::general_purpose::STANDARD
-        .decode(encoded)
-        .map_err(|e| VeritasError::QrngError(format!("Base64 decode error: {e}")))
-}
-
-#[async_trait]
-impl QuantumEntropySource for IdQuantiqueQrng {
-    #[instrument(
-        level = "info",
-        skip(self),
-        fields(source = "idquantique", max_retries = self.config.max_retries)
-    )]
-    async fn get_entropy(&self) -> Result<[u8; 32]> {
+    /// Fetch entropy with retries on transient errors.
+    async fn fetch_entropy_with_retry(&self) -> Result<AttestedEntropy> {
         let start = Instant::now();
         debug!("Fetching quantum entropy from ID Quantique");
 
@@ -461,6 +499,37 @@ impl QuantumEntropySource for IdQuantiqueQrng {
 
         result
     }
+}
+
+/// Decode base64 string to bytes.
+fn base64_decode(encoded: &str) -> Result<Vec<u8>> {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| VeritasError::QrngError(format!("Base64 decode error: {e}")))
+}
+
+#[async_trait]
+impl QuantumEntropySource for IdQuantiqueQrng {
+    #[instrument(
+        level = "info",
+        skip(self),
+        fields(source = "idquantique", max_retries = self.config.max_retries)
+    )]
+    async fn get_entropy(&self) -> Result<[u8; 32]> {
+        self.fetch_entropy_with_retry()
+            .await
+            .map(|attested| attested.entropy)
+    }
+
+    #[instrument(
+        level = "info",
+        skip(self),
+        fields(source = "idquantique", max_retries = self.config.max_retries)
+    )]
+    async fn get_attested_entropy(&self) -> Result<AttestedEntropy> {
+        self.fetch_entropy_with_retry().await
+    }
 
     fn source_id(&self) -> QrngSource {
         QrngSource::IdQuantiqueCloud
@@ -525,4 +594,18 @@ mod tests {
         let entropy = provider.get_entropy().await.unwrap();
         assert_eq!(entropy.len(), 32);
     }
+
+    #[test]
+    fn test_entropy_response_certificate_is_optional() {
+        let plain: EntropyResponse = serde_json::from_str(r#"{"entropy":["AAAA"]}"#).unwrap();
+        assert!(plain.certificate.is_none());
+
+        let attested: EntropyResponse = serde_json::from_str(
+            r#"{"entropy":["AAAA"],"certificate":{"issued_at":1,"public_key":"AQID","signature":"BAUG"}}"#,
+        )
+        .unwrap();
+        let cert = attested.certificate.unwrap();
+        assert_eq!(cert.issued_at, 1);
+        assert_eq!(base64_decode(&cert.public_key).unwrap(), vec![1, 2, 3]);
+    }
 }
@@ -0,0 +1,251 @@
+//! Region-of-interest hashing for localized image edits.
+//!
+//! The global perceptual hash tells how far an image drifted from the sealed
+//! one, not where. Image seals therefore also carry [`RegionHashes`]: a
+//! Blockhash64 of every tile of a grid laid over the image (4x4 by default).
+//! Comparing the tiles of a verified image with the sealed ones reports which
+//! regions changed (a swapped face, a removed object) and feeds the tamper
+//! heatmap of the verification report.
+//!
+//! Tiles are cut in proportion to the image size, so a resized copy lines up
+//! with the sealed grid. Re-encoding moves every tile a few bits; an edit
+//! moves the tiles it touches far past [`REGION_CHANGE_THRESHOLD`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VeritasError};
+
+/// Default tiles per row and per column.
+pub const DEFAULT_REGION_GRID: u8 = 4;
+
+/// Largest accepted grid, keeping seals small.
+pub const MAX_REGION_GRID: u8 = 16;
+
+/// Tile distance, in bits of the 64-bit hash, above which a region counts as
+/// changed.
+pub const REGION_CHANGE_THRESHOLD: u32 = 12;
+
+/// Images are only tiled when every tile is at least this many pixels wide
+/// and high; smaller tiles hash to noise.
+#[cfg(feature = "perceptual-hash")]
+const MIN_TILE_PIXELS: u32 = 16;
+
+/// Perceptual hashes of the tiles of an image.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionHashes {
+    /// Tiles per row and per column
+    pub grid: u8,
+    /// Blockhash64 of each tile (big-endian), row by row from the top left
+    pub tiles: Vec<u64>,
+}
+
+impl RegionHashes {
+    /// Hash the tiles of an encoded image (JPEG, PNG, GIF or WebP), or
+    /// `None` if it is not an image or too small for `grid` tiles.
+    #[cfg(feature = "perceptual-hash")]
+    pub fn from_bytes(image_data: &[u8], grid: u8) -> Option<Self> {
+        let image = image::load_from_memory(image_data).ok()?;
+        Self::from_image(&image, grid)
+    }
+
+    /// Hash the tiles of a decoded image, or `None` if it is too small for
+    /// `grid` tiles.
+    #[cfg(feature = "perceptual-hash")]
+    pub fn from_image(image: &image::DynamicImage, grid: u8) -> Option<Self> {
+        use image::GenericImageView;
+
+        let (width, height) = image.dimensions();
+        let cells = u32::from(grid);
+        if grid == 0
+            || grid > MAX_REGION_GRID
+            || width < cells * MIN_TILE_PIXELS
+            || height < cells * MIN_TILE_PIXELS
+        {
+            return None;
+        }
+
+        let edge = |size: u32, i: u32| (u64::from(size) * u64::from(i) / u64::from(cells)) as u32;
+        let mut tiles = Vec::with_capacity((cells * cells) as usize);
+        for row in 0..cells {
+            let (top, bottom) = (edge(height, row), edge(height, row + 1));
+            for column in 0..cells {
+                let (left, right) = (edge(width, column), edge(width, column + 1));
+                let tile = image.crop_imm(left, top, right - left, bottom - top);
+                let hash: [u8; 8] = blockhash::blockhash64(&tile).into();
+                tiles.push(u64::from_be_bytes(hash));
+            }
+        }
+        Some(Self { grid, tiles })
+    }
+
+    /// Compare with the tiles of another image hashed on the same grid, or
+    /// `None` if the grids differ.
+    pub fn compare(&self, other: &Self) -> Option<RegionComparison> {
+        if self.grid != other.grid || self.tiles.len() != other.tiles.len() {
+            return None;
+        }
+        let distances = self
+            .tiles
+            .iter()
+            .zip(&other.tiles)
+            .map(|(a, b)| (a ^ b).count_ones())
+            .collect();
+        Some(RegionComparison {
+            grid: self.grid,
+            distances,
+        })
+    }
+
+    /// Check the tile count matches the grid.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.grid == 0 || self.grid > MAX_REGION_GRID {
+            return Err(VeritasError::InvalidSeal(format!(
+                "region grid must be 1 to {MAX_REGION_GRID}, got {}",
+                self.grid
+            )));
+        }
+        let expected = usize::from(self.grid) * usize::from(self.grid);
+        if self.tiles.len() != expected {
+            return Err(VeritasError::InvalidSeal(format!(
+                "region hashes list {} tiles for a {}x{} grid",
+                self.tiles.len(),
+                self.grid,
+                self.grid
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Per-tile distances between a sealed image and a verified one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionComparison {
+    /// Tiles per row and per column
+    pub grid: u8,
+    /// Perceptual distance of each tile in bits (0-64), row by row
+    pub distances: Vec<u32>,
+}
+
+impl RegionComparison {
+    /// Regions farther than `threshold` bits from the sealed image, row by
+    /// row.
+    pub fn changed_regions(&self, threshold: u32) -> Vec<ChangedRegion> {
+        let grid = usize::from(self.grid.max(1));
+        self.distances
+            .iter()
+            .enumerate()
+            .filter(|(_, distance)| **distance > threshold)
+            .map(|(i, &distance)| ChangedRegion {
+                row: (i / grid) as u8,
+                column: (i % grid) as u8,
+                distance,
+            })
+            .collect()
+    }
+
+    /// Distance of the tile at `row`, `column`.
+    pub fn distance(&self, row: u8, column: u8) -> Option<u32> {
+        if row >= self.grid || column >= self.grid {
+            return None;
+        }
+        let index = usize::from(row) * usize::from(self.grid) + usize::from(column);
+        self.distances.get(index).copied()
+    }
+}
+
+/// A region of the image that differs from the sealed one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangedRegion {
+    /// Tile row, from the top
+    pub row: u8,
+    /// Tile column, from the left
+    pub column: u8,
+    /// Perceptual distance to the sealed tile, in bits
+    pub distance: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_and_changed_regions() {
+        let sealed = RegionHashes {
+            grid: 2,
+            tiles: vec![0, u64::MAX, 0xFF, 0],
+        };
+        let verified = RegionHashes {
+            grid: 2,
+            tiles: vec![0b11, u64::MAX, 0xFF, u64::MAX],
+        };
+
+        let comparison = sealed.compare(&verified).unwrap();
+        assert_eq!(comparison.distances, vec![2, 0, 0, 64]);
+        assert_eq!(comparison.distance(1, 1), Some(64));
+        assert_eq!(comparison.distance(2, 0), None);
+        assert_eq!(
+            comparison.changed_regions(REGION_CHANGE_THRESHOLD),
+            vec![ChangedRegion {
+                row: 1,
+                column: 1,
+                distance: 64
+            }]
+        );
+
+        let other_grid = RegionHashes {
+            grid: 1,
+            tiles: vec![0],
+        };
+        assert!(sealed.compare(&other_grid).is_none());
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = RegionHashes {
+            grid: 2,
+            tiles: vec![0; 4],
+        };
+        assert!(valid.validate().is_ok());
+
+        let short = RegionHashes {
+            grid: 2,
+            tiles: vec![0; 3],
+        };
+        assert!(short.validate().is_err());
+
+        let empty = RegionHashes {
+            grid: 0,
+            tiles: Vec::new(),
+        };
+        assert!(empty.validate().is_err());
+    }
+
+    #[cfg(feature = "perceptual-hash")]
+    #[test]
+    fn test_edit_is_localized() {
+        use image::{DynamicImage, Rgb, RgbImage};
+
+        let mut pixels = RgbImage::from_fn(256, 256, |x, y| {
+            Rgb([x as u8, y as u8, ((x / 16 + y / 16) % 2 * 200) as u8])
+        });
+        let original = RegionHashes::from_image(&DynamicImage::ImageRgb8(pixels.clone()), 4)
+            .expect("image large enough");
+        assert_eq!(original.tiles.len(), 16);
+
+        // Paint over the top-left tile
+        for y in 0..64 {
+            for x in 0..64 {
+                pixels.put_pixel(x, y, Rgb([255, 255, 255]));
+            }
+        }
+        let edited = RegionHashes::from_image(&DynamicImage::ImageRgb8(pixels), 4).unwrap();
+        let changed = original
+            .compare(&edited)
+            .unwrap()
+            .changed_regions(REGION_CHANGE_THRESHOLD);
+        assert!(changed.iter().any(|r| (r.row, r.column) == (0, 0)));
+        assert!(changed.iter().all(|r| r.row == 0 && r.column == 0));
+
+        assert!(RegionHashes::from_image(&DynamicImage::new_rgb8(32, 32), 4).is_none());
+    }
+}
@@ -0,0 +1,823 @@
+//! Human-readable verification reports.
+//!
+//! Renders a [`VerificationReport`] as a self-contained HTML page or a
+//! single-page PDF certificate: seal details, verification outcome, trust
+//! tier, blockchain anchor link, and a QR code pointing to the online verify
+//! page. The PDF is written directly with the standard Type 1 fonts, so no
+//! font files or external renderer are needed.
+
+use std::fmt::Write as _;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::qr::SealQrCode;
+use crate::regions::{RegionComparison, REGION_CHANGE_THRESHOLD};
+use crate::seal::{BlockchainAnchor, ContentVerificationResult, VerificationResult, VeritasSeal};
+
+/// Default base URL of the online verify page.
+pub const DEFAULT_VERIFY_BASE_URL: &str = "https://veritas-q.com/verify";
+
+/// Outcome shown at the top of a report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportOutcome {
+    /// Signature valid and content matches the seal
+    Authentic,
+    /// Signature valid; the content itself was not checked
+    SignatureValid,
+    /// Signature valid but the content changed since sealing
+    ContentModified,
+    /// The seal failed verification
+    Invalid(String),
+    /// The seal could not be verified
+    Unverified(String),
+}
+
+impl ReportOutcome {
+    /// Short label for the outcome banner.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Authentic => "AUTHENTIC",
+            Self::SignatureValid => "SIGNATURE VALID",
+            Self::ContentModified => "MODIFIED",
+            Self::Invalid(_) => "INVALID",
+            Self::Unverified(_) => "NOT VERIFIED",
+        }
+    }
+
+    /// One-sentence explanation of the outcome.
+    pub fn description(&self) -> String {
+        match self {
+            Self::Authentic => "The signature is valid and the content matches the seal.".into(),
+            Self::SignatureValid => {
+                "The seal signature is valid. The content itself was not checked.".into()
+            }
+            Self::ContentModified => {
+                "The signature is valid but the content has been modified since sealing.".into()
+            }
+            Self::Invalid(reason) | Self::Unverified(reason) => reason.clone(),
+        }
+    }
+
+    /// Whether the outcome supports the authenticity of the media.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Authentic | Self::SignatureValid)
+    }
+
+    /// Banner color as RGB components in `0.0..=1.0`.
+    fn color(&self) -> (f32, f32, f32) {
+        match self {
+            Self::Authentic | Self::SignatureValid => (0.09, 0.55, 0.29),
+            Self::ContentModified | Self::Invalid(_) => (0.75, 0.15, 0.15),
+            Self::Unverified(_) => (0.45, 0.45, 0.45),
+        }
+    }
+}
+
+impl From<&VerificationResult> for ReportOutcome {
+    fn from(result: &VerificationResult) -> Self {
+        if result.is_valid() {
+            Self::SignatureValid
+        } else {
+            Self::Invalid(result.description().to_string())
+        }
+    }
+}
+
+impl From<&ContentVerificationResult> for ReportOutcome {
+    fn from(result: &ContentVerificationResult) -> Self {
+        match result {
+            ContentVerificationResult::Authentic => Self::Authentic,
+            ContentVerificationResult::ContentModified { .. } => Self::ContentModified,
+            ContentVerificationResult::SignatureFailed(result) => Self::from(result),
+        }
+    }
+}
+
+/// Link to the blockchain transaction anchoring a seal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorLink {
+    /// Chain identifier (e.g. "solana-devnet")
+    pub chain: String,
+    /// Transaction ID
+    pub tx_id: String,
+    /// Block explorer URL, for chains with a known explorer
+    pub url: Option<String>,
+}
+
+impl From<&BlockchainAnchor> for AnchorLink {
+    fn from(anchor: &BlockchainAnchor) -> Self {
+        let url = anchor.chain.starts_with("solana").then(|| {
+            let cluster = anchor
+                .chain
+                .strip_prefix("solana-")
+                .filter(|cluster| *cluster != "mainnet");
+            match cluster {
+                Some(cluster) => format!(
+                    "https://explorer.solana.com/tx/{}?cluster={}",
+                    anchor.tx_id, cluster
+                ),
+                None => format!("https://explorer.solana.com/tx/{}", anchor.tx_id),
+            }
+        });
+        Self {
+            chain: anchor.chain.clone(),
+            tx_id: anchor.tx_id.clone(),
+            url,
+        }
+    }
+}
+
+/// Everything shown on a verification certificate.
+#[derive(Debug, Clone)]
+pub struct VerificationReport {
+    /// Seal identifier, when the seal is known to a server
+    pub seal_id: Option<String>,
+    /// Name of the verified file
+    pub file_name: Option<String>,
+    /// Verification outcome
+    pub outcome: ReportOutcome,
+    /// Media type of the sealed content
+    pub media_type: String,
+    /// Capture time recorded in the seal
+    pub sealed_at: DateTime<Utc>,
+    /// SHA3-256 content hash (hex)
+    pub content_hash: String,
+    /// Perceptual hash (hex), images only
+    pub perceptual_hash: Option<String>,
+    /// QRNG source of the seal entropy
+    pub qrng_source: String,
+    /// Capture location, as recorded
+    pub location: Option<String>,
+    /// Whether the seal carries a device attestation
+    pub has_device_attestation: bool,
+    /// Trust tier label (e.g. "Tier 3 - Hardware Secure")
+    pub trust_tier: Option<String>,
+    /// Reasons behind the trust tier
+    pub trust_rationale: Vec<String>,
+    /// Blockchain anchor, if the seal was anchored
+    pub anchor: Option<AnchorLink>,
+    /// Online verify page, encoded in the QR code
+    pub verify_url: Option<String>,
+    /// Per-region distances to the sealed image, drawn as a tamper heatmap
+    pub regions: Option<RegionComparison>,
+    /// When the report was generated
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Signature algorithm shown on reports.
+const SIGNATURE_ALGORITHM: &str = "ML-DSA-65 (FIPS 204)";
+
+impl VerificationReport {
+    /// Build a report from a seal, checking its signature only.
+    ///
+    /// Use [`VerificationReport::with_content_result`] to report on the
+    /// content as well.
+    pub fn from_seal(seal: &VeritasSeal) -> Self {
+        let outcome = match seal.verify_detailed() {
+            Ok(result) => ReportOutcome::from(&result),
+            Err(e) => ReportOutcome::Invalid(e.to_string()),
+        };
+        let sealed_at = i64::try_from(seal.capture_timestamp_utc)
+            .ok()
+            .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+            .unwrap_or_default();
+
+        Self {
+            seal_id: None,
+            file_name: None,
+            outcome,
+            media_type: seal.media_type.to_string(),
+            sealed_at,
+            content_hash: seal.content_hash.crypto_hash_hex(),
+            perceptual_hash: seal.content_hash.perceptual_hash_hex(),
+            qrng_source: seal.qrng_source.to_string(),
+            location: seal.capture_location.clone(),
+            has_device_attestation: seal.device_attestation.is_some(),
+            trust_tier: None,
+            trust_rationale: Vec::new(),
+            anchor: seal.blockchain_anchor.as_ref().map(AnchorLink::from),
+            verify_url: None,
+            regions: None,
+            generated_at: Utc::now(),
+        }
+    }
+
+    /// Set the outcome from a full content verification.
+    pub fn with_content_result(mut self, result: &ContentVerificationResult) -> Self {
+        self.outcome = ReportOutcome::from(result);
+        self
+    }
+
+    /// Set the seal identifier.
+    pub fn with_seal_id(mut self, seal_id: impl Into<String>) -> Self {
+        self.seal_id = Some(seal_id.into());
+        self
+    }
+
+    /// Set the verified file name.
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Set the online verify page encoded in the QR code.
+    pub fn with_verify_url(mut self, verify_url: impl Into<String>) -> Self {
+        self.verify_url = Some(verify_url.into());
+        self
+    }
+
+    /// Set the per-region comparison drawn as a tamper heatmap.
+    pub fn with_regions(mut self, regions: RegionComparison) -> Self {
+        self.regions = Some(regions);
+        self
+    }
+
+    /// Summary of the region comparison, e.g. "3 of 16 regions differ".
+    fn regions_caption(regions: &RegionComparison) -> String {
+        let changed = regions.changed_regions(REGION_CHANGE_THRESHOLD).len();
+        let total = regions.distances.len();
+        match changed {
+            0 => format!("None of the {} regions differ from the sealed image", total),
+            1 => format!("1 of {} regions differs from the sealed image", total),
+            _ => format!(
+                "{} of {} regions differ from the sealed image",
+                changed, total
+            ),
+        }
+    }
+
+    /// Labelled detail rows, in display order. The flag marks hashes and
+    /// identifiers shown in a monospace font.
+    fn rows(&self) -> Vec<(&'static str, String, bool)> {
+        let mut rows = Vec::new();
+        if let Some(seal_id) = &self.seal_id {
+            rows.push(("Seal ID", seal_id.clone(), true));
+        }
+        if let Some(file_name) = &self.file_name {
+            rows.push(("File", file_name.clone(), false));
+        }
+        rows.push(("Media type", self.media_type.clone(), false));
+        rows.push((
+            "Sealed at",
+            self.sealed_at.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            false,
+        ));
+        rows.push(("Content hash", self.content_hash.clone(), true));
+        if let Some(perceptual_hash) = &self.perceptual_hash {
+            rows.push(("Perceptual hash", perceptual_hash.clone(), true));
+        }
+        rows.push(("Signature", SIGNATURE_ALGORITHM.to_string(), false));
+        rows.push(("QRNG source", self.qrng_source.clone(), false));
+        if let Some(location) = &self.location {
+            rows.push(("Location", location.clone(), false));
+        }
+        rows.push((
+            "Device attestation",
+            if self.has_device_attestation {
+                "Present"
+            } else {
+                "None"
+            }
+            .to_string(),
+            false,
+        ));
+        if let Some(trust_tier) = &self.trust_tier {
+            rows.push(("Trust tier", trust_tier.clone(), false));
+        }
+        for reason in &self.trust_rationale {
+            rows.push(("", format!("- {}", reason), false));
+        }
+        if let Some(anchor) = &self.anchor {
+            rows.push(("Anchor", format!("{} {}", anchor.chain, anchor.tx_id), true));
+        }
+        rows
+    }
+
+    /// Render the report as a standalone HTML page.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let (r, g, b) = self.outcome.color();
+        let banner = format!(
+            "#{:02x}{:02x}{:02x}",
+            (r * 255.0) as u8,
+            (g * 255.0) as u8,
+            (b * 255.0) as u8
+        );
+
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        let _ = writeln!(
+            html,
+            "<title>Veritas Q Verification Certificate - {}</title>",
+            escape_html(&self.content_hash)
+        );
+        html.push_str(
+            "<style>\
+             body{font-family:Helvetica,Arial,sans-serif;max-width:760px;margin:2em auto;color:#1a1a1a}\
+             .banner{color:#fff;padding:1em 1.25em;border-radius:6px}\
+             .banner h2{margin:0}.banner p{margin:.25em 0 0}\
+             table{border-collapse:collapse;width:100%;margin:1.5em 0}\
+             th{text-align:left;vertical-align:top;width:11em;padding:.35em 0}\
+             td{padding:.35em 0;word-break:break-all}.mono{font-family:Menlo,Consolas,monospace;font-size:.9em}\
+             .qr{display:flex;gap:1em;align-items:center}.qr svg{width:140px;height:140px}\
+             .regions{display:flex;gap:1em;align-items:center;margin-bottom:1.5em}\
+             .regions svg{width:140px;height:140px;border:1px solid #ccc}\
+             footer{color:#777;font-size:.8em;margin-top:2em}\
+             </style>\n</head>\n<body>\n",
+        );
+        html.push_str("<h1>Veritas Q Verification Certificate</h1>\n");
+        let _ = writeln!(
+            html,
+            "<div class=\"banner\" style=\"background:{}\"><h2>{}</h2><p>{}</p></div>",
+            banner,
+            self.outcome.label(),
+            escape_html(&self.outcome.description())
+        );
+
+        html.push_str("<table>\n");
+        for (label, value, mono) in self.rows() {
+            let value = match (label, &self.anchor) {
+                ("Anchor", Some(AnchorLink { url: Some(url), .. })) => format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(url),
+                    escape_html(&value)
+                ),
+                _ => escape_html(&value),
+            };
+            let _ = writeln!(
+                html,
+                "<tr><th>{}</th><td{}>{}</td></tr>",
+                escape_html(label),
+                if mono { " class=\"mono\"" } else { "" },
+                value
+            );
+        }
+        html.push_str("</table>\n");
+
+        if let Some(regions) = &self.regions {
+            let grid = regions.grid.max(1);
+            let _ = write!(
+                html,
+                "<div class=\"regions\"><svg xmlns=\"http://www.w3.org/2000/svg\" \
+                 viewBox=\"0 0 {0} {0}\" shape-rendering=\"crispEdges\">",
+                grid
+            );
+            for (i, distance) in regions.distances.iter().enumerate() {
+                let (r, g, b) = region_color(*distance);
+                let _ = write!(
+                    html,
+                    "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" \
+                     fill=\"#{:02x}{:02x}{:02x}\"><title>{} bits</title></rect>",
+                    i % usize::from(grid),
+                    i / usize::from(grid),
+                    (r * 255.0) as u8,
+                    (g * 255.0) as u8,
+                    (b * 255.0) as u8,
+                    distance
+                );
+            }
+            let _ = writeln!(
+                html,
+                "</svg><p><strong>Tamper heatmap</strong><br>{}</p></div>",
+                escape_html(&Self::regions_caption(regions))
+            );
+        }
+
+        if let Some(url) = &self.verify_url {
+            html.push_str("<div class=\"qr\">");
+            if let Ok(code) = SealQrCode::encode(url) {
+                html.push_str(&code.to_svg());
+            }
+            let _ = writeln!(
+                html,
+                "<p>Scan to verify online:<br><a href=\"{0}\">{0}</a></p></div>",
+                escape_html(url)
+            );
+        }
+
+        let _ = writeln!(
+            html,
+            "<footer>Generated {} by Veritas Q {}</footer>\n</body>\n</html>",
+            self.generated_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            env!("CARGO_PKG_VERSION")
+        );
+        html
+    }
+
+    /// Render the report as a single-page A4 PDF.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut page = PdfPage::default();
+
+        page.text(
+            FONT_BOLD,
+            20.0,
+            MARGIN,
+            790.0,
+            "Veritas Q Verification Certificate",
+        );
+
+        // Outcome banner
+        page.fill_rect(
+            self.outcome.color(),
+            MARGIN,
+            712.0,
+            PAGE_WIDTH - 2.0 * MARGIN,
+            56.0,
+        );
+        page.fill_color((1.0, 1.0, 1.0));
+        page.text(FONT_BOLD, 16.0, MARGIN + 14.0, 746.0, self.outcome.label());
+        let description = self.outcome.description();
+        for (i, line) in wrap(&description, 95).iter().take(2).enumerate() {
+            page.text(
+                FONT_REGULAR,
+                9.0,
+                MARGIN + 14.0,
+                730.0 - 11.0 * i as f32,
+                line,
+            );
+        }
+        page.fill_color((0.0, 0.0, 0.0));
+
+        // Detail rows, leaving room for the heatmap when there is one
+        let bottom_row = if self.regions.is_some() { 340.0 } else { 200.0 };
+        let mut y = 685.0;
+        'rows: for (label, value, mono) in self.rows() {
+            let (font, size, width) = if mono {
+                (FONT_MONO, 8.5, 64)
+            } else {
+                (FONT_REGULAR, 10.0, 70)
+            };
+            page.text(FONT_BOLD, 10.0, MARGIN, y, label);
+            for line in wrap(&value, width) {
+                if y < bottom_row {
+                    break 'rows;
+                }
+                page.text(font, size, MARGIN + 120.0, y, &line);
+                y -= 14.0;
+            }
+            y -= 2.0;
+        }
+
+        // Tamper heatmap, one cell per region
+        if let Some(regions) = &self.regions {
+            let size = 110.0;
+            let grid = regions.grid.max(1);
+            let cell = size / f32::from(grid);
+            let (left, bottom) = (MARGIN, 205.0);
+            for (i, distance) in regions.distances.iter().enumerate() {
+                let (row, col) = (i / usize::from(grid), i % usize::from(grid));
+                page.fill_rect(
+                    region_color(*distance),
+                    left + col as f32 * cell,
+                    bottom + size - (row as f32 + 1.0) * cell,
+                    cell,
+                    cell,
+                );
+            }
+            page.fill_color((0.0, 0.0, 0.0));
+            page.text(
+                FONT_BOLD,
+                10.0,
+                left + size + 16.0,
+                bottom + 70.0,
+                "Tamper heatmap",
+            );
+            page.text(
+                FONT_REGULAR,
+                9.0,
+                left + size + 16.0,
+                bottom + 56.0,
+                &Self::regions_caption(regions),
+            );
+        }
+
+        // QR code to the online verify page
+        if let Some(url) = &self.verify_url {
+            if let Ok(code) = SealQrCode::encode(url) {
+                let size = 110.0;
+                let module = size / code.width() as f32;
+                let (left, bottom) = (MARGIN, 70.0);
+                for (col, row) in code.dark_modules() {
+                    page.fill_rect(
+                        (0.0, 0.0, 0.0),
+                        left + col as f32 * module,
+                        bottom + size - (row as f32 + 1.0) * module,
+                        module,
+                        module,
+                    );
+                }
+                page.text(
+                    FONT_BOLD,
+                    10.0,
+                    left + size + 16.0,
+                    bottom + 70.0,
+                    "Scan to verify online",
+                );
+                for (i, line) in wrap(url, 60).iter().take(3).enumerate() {
+                    page.text(
+                        FONT_MONO,
+                        8.5,
+                        left + size + 16.0,
+                        bottom + 54.0 - 12.0 * i as f32,
+                        line,
+                    );
+                }
+            }
+        }
+
+        page.fill_color((0.45, 0.45, 0.45));
+        page.text(
+            FONT_REGULAR,
+            8.0,
+            MARGIN,
+            40.0,
+            &format!(
+                "Generated {} by Veritas Q {}",
+                self.generated_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                env!("CARGO_PKG_VERSION")
+            ),
+        );
+
+        page.finish()
+    }
+}
+
+/// Heatmap color of a region: pale for an unchanged tile, shading to red as
+/// its distance grows, full red past the change threshold.
+fn region_color(distance: u32) -> (f32, f32, f32) {
+    if distance > REGION_CHANGE_THRESHOLD {
+        return (0.75, 0.15, 0.15);
+    }
+    let t = distance as f32 / REGION_CHANGE_THRESHOLD as f32;
+    (0.93 - 0.1 * t, 0.95 - 0.5 * t, 0.93 - 0.5 * t)
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Split `text` into lines of at most `width` characters, breaking at
+/// spaces where possible.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ') {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..width).collect());
+        }
+        let word: String = word.into_iter().collect();
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+
+const FONT_REGULAR: &str = "F1";
+const FONT_BOLD: &str = "F2";
+const FONT_MONO: &str = "F3";
+
+/// Content stream of a single PDF page using the standard fonts.
+#[derive(Default)]
+struct PdfPage {
+    content: Vec<u8>,
+}
+
+impl PdfPage {
+    fn text(&mut self, font: &str, size: f32, x: f32, y: f32, text: &str) {
+        self.content
+            .extend_from_slice(format!("BT /{} {} Tf {} {} Td (", font, size, x, y).as_bytes());
+        self.content.extend(encode_pdf_text(text));
+        self.content.extend_from_slice(b") Tj ET\n");
+    }
+
+    fn fill_color(&mut self, (r, g, b): (f32, f32, f32)) {
+        self.content
+            .extend_from_slice(format!("{} {} {} rg\n", r, g, b).as_bytes());
+    }
+
+    fn fill_rect(&mut self, color: (f32, f32, f32), x: f32, y: f32, width: f32, height: f32) {
+        self.fill_color(color);
+        self.content
+            .extend_from_slice(format!("{} {} {} {} re f\n", x, y, width, height).as_bytes());
+    }
+
+    /// Wrap the content stream in a complete PDF document.
+    fn finish(self) -> Vec<u8> {
+        let font = |name: &str| {
+            format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                name
+            )
+            .into_bytes()
+        };
+        let mut stream = format!("<< /Length {} >>\nstream\n", self.content.len()).into_bytes();
+        stream.extend(self.content);
+        stream.extend_from_slice(b"\nendstream");
+
+        let objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 4 0 R /F2 5 0 R /F3 6 0 R >> >> /Contents 7 0 R >>",
+                PAGE_WIDTH, PAGE_HEIGHT
+            )
+            .into_bytes(),
+            font("Helvetica"),
+            font("Helvetica-Bold"),
+            font("Courier"),
+            stream,
+        ];
+
+        let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = pdf.len();
+        pdf.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+        );
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+}
+
+/// Encode text for a PDF string literal in WinAnsiEncoding, escaping
+/// delimiters and replacing unsupported characters with `?`.
+fn encode_pdf_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                bytes.push(c as u8);
+            }
+            '\u{2013}' => bytes.push(0x96),
+            '\u{2014}' => bytes.push(0x97),
+            '\u{2192}' => bytes.extend_from_slice(b"->"),
+            ' '..='~' | '\u{a0}'..='\u{ff}' => bytes.push(c as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> VerificationReport {
+        VerificationReport {
+            seal_id: Some("550e8400-e29b-41d4-a716-446655440000".into()),
+            file_name: Some("photo <1>.jpg".into()),
+            outcome: ReportOutcome::Authentic,
+            media_type: "image".into(),
+            sealed_at: Utc.timestamp_millis_opt(1_704_067_200_000).unwrap(),
+            content_hash: "ab".repeat(32),
+            perceptual_hash: Some("a1b2c3d4e5f67890".into()),
+            qrng_source: "LfD QRNG (Germany)".into(),
+            location: Some("u09tvw".into()),
+            has_device_attestation: true,
+            trust_tier: Some("Tier 3 - Hardware Secure".into()),
+            trust_rationale: vec!["device attestation verified (packed format)".into()],
+            anchor: Some(AnchorLink::from(&BlockchainAnchor {
+                chain: "solana-devnet".into(),
+                tx_id: "5xTx".into(),
+                block_height: 1,
+                proof: None,
+                batch: None,
+            })),
+            verify_url: Some(format!(
+                "{}/550e8400-e29b-41d4-a716-446655440000",
+                DEFAULT_VERIFY_BASE_URL
+            )),
+            regions: None,
+            generated_at: Utc.timestamp_millis_opt(1_704_070_800_000).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_html_report_contents() {
+        let html = report().to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("AUTHENTIC"));
+        assert!(html.contains("photo &lt;1&gt;.jpg"));
+        assert!(html.contains(&"ab".repeat(32)));
+        assert!(html.contains("Tier 3 - Hardware Secure"));
+        assert!(html.contains("https://explorer.solana.com/tx/5xTx?cluster=devnet"));
+        assert!(html.contains("<svg"));
+        assert!(html.contains("2024-01-01 00:00:00 UTC"));
+    }
+
+    #[test]
+    fn test_pdf_report_structure() {
+        let pdf = report().to_pdf();
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("(AUTHENTIC) Tj"));
+        assert!(text.contains("(photo <1>.jpg) Tj"));
+        assert!(text.contains(" re f"));
+
+        // The xref offset points at the xref table
+        let startxref: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|offset| offset.parse().ok())
+            .unwrap();
+        assert!(pdf[startxref..].starts_with(b"xref"));
+    }
+
+    #[test]
+    fn test_tamper_heatmap() {
+        let mut distances = vec![0; 16];
+        distances[5] = 30;
+        distances[6] = 20;
+        let report = report().with_regions(RegionComparison { grid: 4, distances });
+
+        let html = report.to_html();
+        assert!(html.contains("Tamper heatmap"));
+        assert!(html.contains("2 of 16 regions differ"));
+        assert_eq!(html.matches("<rect x=").count(), 16);
+        assert!(html.contains("fill=\"#bf2626\"><title>30 bits</title>"));
+
+        let text = String::from_utf8_lossy(&report.to_pdf()).into_owned();
+        assert!(text.contains("(Tamper heatmap) Tj"));
+        assert!(text.contains("(2 of 16 regions differ from the sealed image) Tj"));
+        assert!(!report().to_html().contains("Tamper heatmap"));
+    }
+
+    #[test]
+    fn test_outcome_from_verification() {
+        assert_eq!(
+            ReportOutcome::from(&ContentVerificationResult::Authentic),
+            ReportOutcome::Authentic
+        );
+        assert_eq!(
+            ReportOutcome::from(&VerificationResult::Valid),
+            ReportOutcome::SignatureValid
+        );
+        let invalid = ReportOutcome::from(&VerificationResult::InvalidSignature);
+        assert!(!invalid.is_success());
+        assert_eq!(invalid.label(), "INVALID");
+    }
+
+    #[test]
+    fn test_pdf_text_encoding() {
+        assert_eq!(encode_pdf_text("a(b)\\"), b"a\\(b\\)\\\\");
+        assert_eq!(
+            encode_pdf_text("caf\u{e9} \u{2014} \u{1F600}"),
+            b"caf\xe9 \x97 ?"
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("one two three", 7), vec!["one two", "three"]);
+        assert_eq!(wrap("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(wrap("", 10), vec![""]);
+    }
+}
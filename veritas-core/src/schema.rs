@@ -0,0 +1,291 @@
+//! Seal format versions and migration between them.
+//!
+//! Each value of [`VeritasSeal::version`] maps to a [`SealSchema`] that states
+//! which fields a seal of that version may carry:
+//!
+//! | Version | Changes |
+//! |---------|---------|
+//! | 1 | Initial format (seals without a `version` field are treated as v1) |
+//! | 2 | Optional `qrng_conditioning` and `entropy_certificate` |
+//! | 3 | `countersignatures` |
+//! | 4 | `threshold_policy` and `partial_signatures` |
+//! | 5 | `disclosure` (signature over salted field commitments) |
+//! | 6 | `content_hash.segments` (Merkle tree over fixed-size segments) |
+//! | 7 | `content_hash.segments.digests` (per-segment tamper localization) |
+//! | 8 | `MediaType::Document` and `content_hash.canonical_hash` |
+//! | 9 | `MediaType::Generic` and `MediaType::Custom` |
+//! | 10 | `qrng_policy` (fresh, pooled or mixed entropy) |
+//! | 11 | `not_before` and `not_after` (validity window) |
+//! | 12 | `content_hash.region_hashes` (per-tile perceptual hashes) |
+//! | 13 | `ai_detections` (signed AI-content detector verdicts) |
+//! | 14 | `capture_nonce` (trusted capture session) |
+//! | 15 | `liveness` (capture liveness challenge) |
+//! | 16 | `key_id` (signing-key registry ID) |
+//! | 17 | `raw_entropy_hash` (binds entropy certificates of conditioned seals) |
+//!
+//! Anchors (`blockchain_anchor`, `additional_anchors`) are attached after
+//! signing and allowed at every version.
+//!
+//! The version byte is not part of the signed payload, and new fields are
+//! omitted from that payload when absent. Migrating an old seal therefore only
+//! rewrites its version and never invalidates the signature.
+//!
+//! Adding a version: add a variant, extend `from_version`/`version`, describe
+//! the allowed fields in `validate_fields`, add the one-step upgrade in
+//! `upgrade`, and commit a fixture for it (see `scripts/generate-seal-fixtures.py`).
+
+use crate::error::{Result, VeritasError, CURRENT_SEAL_VERSION};
+use crate::seal::{MediaType, VeritasSeal};
+
+/// Known seal format versions, oldest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum SealSchema {
+    V1,
+    V2,
+    V3,
+    V4,
+    V5,
+    V6,
+    V7,
+    V8,
+    V9,
+    V10,
+    V11,
+    V12,
+    V13,
+    V14,
+    V15,
+    V16,
+    V17,
+}
+
+impl SealSchema {
+    /// Schema written by this version of the library.
+    pub(crate) const LATEST: Self = Self::V17;
+
+    /// Resolve the schema for a seal's version byte.
+    pub(crate) fn from_version(version: u8) -> Result<Self> {
+        match version {
+            1 => Ok(Self::V1),
+            2 => Ok(Self::V2),
+            3 => Ok(Self::V3),
+            4 => Ok(Self::V4),
+            5 => Ok(Self::V5),
+            6 => Ok(Self::V6),
+            7 => Ok(Self::V7),
+            8 => Ok(Self::V8),
+            9 => Ok(Self::V9),
+            10 => Ok(Self::V10),
+            11 => Ok(Self::V11),
+            12 => Ok(Self::V12),
+            13 => Ok(Self::V13),
+            14 => Ok(Self::V14),
+            15 => Ok(Self::V15),
+            16 => Ok(Self::V16),
+            17 => Ok(Self::V17),
+            _ => Err(VeritasError::UnsupportedSealVersion(
+                version,
+                CURRENT_SEAL_VERSION,
+            )),
+        }
+    }
+
+    /// Version byte stored in seals of this schema.
+    pub(crate) fn version(self) -> u8 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+            Self::V3 => 3,
+            Self::V4 => 4,
+            Self::V5 => 5,
+            Self::V6 => 6,
+            Self::V7 => 7,
+            Self::V8 => 8,
+            Self::V9 => 9,
+            Self::V10 => 10,
+            Self::V11 => 11,
+            Self::V12 => 12,
+            Self::V13 => 13,
+            Self::V14 => 14,
+            Self::V15 => 15,
+            Self::V16 => 16,
+            Self::V17 => 17,
+        }
+    }
+
+    /// Reject fields that did not exist in this schema.
+    ///
+    /// A seal carrying fields newer than its version was either produced by a
+    /// buggy encoder or had its version byte rewritten, so it is refused
+    /// rather than guessed at.
+    pub(crate) fn validate_fields(self, seal: &VeritasSeal) -> Result<()> {
+        if self < Self::V2
+            && (seal.qrng_conditioning.is_some() || seal.entropy_certificate.is_some())
+        {
+            return Err(unexpected_field(
+                self,
+                "qrng_conditioning or entropy_certificate",
+            ));
+        }
+        if self < Self::V3 && !seal.countersignatures.is_empty() {
+            return Err(unexpected_field(self, "countersignatures"));
+        }
+        if self < Self::V4
+            && (seal.threshold_policy.is_some() || !seal.partial_signatures.is_empty())
+        {
+            return Err(unexpected_field(
+                self,
+                "threshold_policy or partial_signatures",
+            ));
+        }
+        if self < Self::V5 && seal.disclosure.is_some() {
+            return Err(unexpected_field(self, "disclosure"));
+        }
+        if self < Self::V6 && seal.content_hash.segments.is_some() {
+            return Err(unexpected_field(self, "content_hash.segments"));
+        }
+        if self < Self::V7
+            && seal
+                .content_hash
+                .segments
+                .as_ref()
+                .is_some_and(|segments| !segments.digests.is_empty())
+        {
+            return Err(unexpected_field(self, "content_hash.segments.digests"));
+        }
+        if self < Self::V8
+            && (seal.media_type == MediaType::Document
+                || seal.content_hash.canonical_hash.is_some())
+        {
+            return Err(unexpected_field(
+                self,
+                "Document media type or content_hash.canonical_hash",
+            ));
+        }
+        if self < Self::V9 && matches!(seal.media_type, MediaType::Generic | MediaType::Custom(_)) {
+            return Err(unexpected_field(self, "Generic or Custom media type"));
+        }
+        if self < Self::V10 && seal.qrng_policy.is_some() {
+            return Err(unexpected_field(self, "qrng_policy"));
+        }
+        if self < Self::V11 && (seal.not_before.is_some() || seal.not_after.is_some()) {
+            return Err(unexpected_field(self, "not_before or not_after"));
+        }
+        if self < Self::V12 && seal.content_hash.region_hashes.is_some() {
+            return Err(unexpected_field(self, "content_hash.region_hashes"));
+        }
+        if self < Self::V13 && !seal.ai_detections.is_empty() {
+            return Err(unexpected_field(self, "ai_detections"));
+        }
+        if self < Self::V14 && seal.capture_nonce.is_some() {
+            return Err(unexpected_field(self, "capture_nonce"));
+        }
+        if self < Self::V15 && seal.liveness.is_some() {
+            return Err(unexpected_field(self, "liveness"));
+        }
+        if self < Self::V16 && seal.key_id.is_some() {
+            return Err(unexpected_field(self, "key_id"));
+        }
+        if self < Self::V17 && seal.raw_entropy_hash.is_some() {
+            return Err(unexpected_field(self, "raw_entropy_hash"));
+        }
+        Ok(())
+    }
+
+    /// Upgrade a seal by one version, returning the new schema.
+    ///
+    /// Returns `None` when the seal is already at the latest schema.
+    fn upgrade(self, seal: &mut VeritasSeal) -> Option<Self> {
+        let next = match self {
+            // Every step so far only added optional fields
+            Self::V1 => Self::V2,
+            Self::V2 => Self::V3,
+            Self::V3 => Self::V4,
+            Self::V4 => Self::V5,
+            Self::V5 => Self::V6,
+            Self::V6 => Self::V7,
+            Self::V7 => Self::V8,
+            Self::V8 => Self::V9,
+            Self::V9 => Self::V10,
+            Self::V10 => Self::V11,
+            Self::V11 => Self::V12,
+            Self::V12 => Self::V13,
+            Self::V13 => Self::V14,
+            Self::V14 => Self::V15,
+            Self::V15 => Self::V16,
+            Self::V16 => Self::V17,
+            Self::V17 => return None,
+        };
+        seal.version = next.version();
+        Some(next)
+    }
+}
+
+fn unexpected_field(schema: SealSchema, field: &str) -> VeritasError {
+    VeritasError::InvalidSeal(format!(
+        "version {} seals cannot carry {field}",
+        schema.version()
+    ))
+}
+
+/// Bring a seal of any supported version up to [`SealSchema::LATEST`] in place.
+pub(crate) fn migrate(seal: &mut VeritasSeal) -> Result<()> {
+    let mut schema = SealSchema::from_version(seal.version)?;
+    schema.validate_fields(seal)?;
+
+    while let Some(next) = schema.upgrade(seal) {
+        schema = next;
+    }
+
+    debug_assert_eq!(schema, SealSchema::LATEST);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_matches_current_version() {
+        assert_eq!(SealSchema::LATEST.version(), CURRENT_SEAL_VERSION);
+        assert_eq!(
+            SealSchema::from_version(CURRENT_SEAL_VERSION).unwrap(),
+            SealSchema::LATEST
+        );
+    }
+
+    #[test]
+    fn test_version_roundtrip() {
+        for schema in [
+            SealSchema::V1,
+            SealSchema::V2,
+            SealSchema::V3,
+            SealSchema::V4,
+            SealSchema::V5,
+            SealSchema::V6,
+            SealSchema::V7,
+            SealSchema::V8,
+            SealSchema::V9,
+            SealSchema::V10,
+            SealSchema::V11,
+            SealSchema::V12,
+            SealSchema::V13,
+            SealSchema::V14,
+            SealSchema::V15,
+            SealSchema::V16,
+            SealSchema::V17,
+        ] {
+            assert_eq!(SealSchema::from_version(schema.version()).unwrap(), schema);
+        }
+    }
+
+    #[test]
+    fn test_unknown_versions_rejected() {
+        for version in [0, CURRENT_SEAL_VERSION + 1, u8::MAX] {
+            assert!(matches!(
+                SealSchema::from_version(version),
+                Err(VeritasError::UnsupportedSealVersion(v, CURRENT_SEAL_VERSION)) if v == version
+            ));
+        }
+    }
+}
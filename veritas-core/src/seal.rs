@@ -2,19 +2,40 @@ use pqcrypto_mldsa::mldsa65;
 use pqcrypto_traits::sign::{PublicKey, SecretKey as SecretKeyTrait, SignedMessage};
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
-use zeroize::Zeroize;
 
-use crate::error::{Result, VeritasError, CURRENT_SEAL_VERSION, MAX_SEAL_SIZE};
-use crate::qrng::QrngSource;
+use crate::ai_detection::AiDetectionAssertion;
+use crate::anchor::BatchProof;
+use crate::constant_time::ct_eq;
+use crate::countersign::CounterSignature;
+use crate::disclosure::SelectiveDisclosure;
+use crate::error::{Result, VeritasError, CURRENT_SEAL_VERSION, MAX_JSON_SEAL_SIZE, MAX_SEAL_SIZE};
+use crate::i18n::{Locale, MessageCode};
+use crate::liveness::LivenessChallenge;
 #[cfg(feature = "network")]
 use crate::qrng::QuantumEntropySource;
+use crate::qrng::{
+    CertificateStatus, EntropyCertificate, QrngConditioning, QrngPolicy, QrngSource,
+    TrustedProviderKeys,
+};
+#[cfg(feature = "perceptual-hash")]
+use crate::regions::RegionComparison;
+use crate::regions::RegionHashes;
+use crate::schema::SealSchema;
+use crate::secret::ZeroizingSecretKey;
 #[cfg(feature = "network")]
+use crate::segments::SegmentLayout;
+use crate::segments::{SegmentManifest, TamperedSegment};
+use crate::signing_keys::validate_key_id;
+use crate::threshold::{PartialSignature, ThresholdPolicy};
 use chrono::Utc;
 
 /// Maximum allowed difference between entropy and capture timestamps (in seconds).
 #[cfg(feature = "network")]
 const MAX_ENTROPY_TIMESTAMP_DRIFT_SECS: u64 = 5;
 
+/// Domain separation tag for [`VeritasSeal::seal_hash`].
+const SEAL_HASH_DOMAIN: &[u8] = b"veritas-seal-hash-v1";
+
 // ML-DSA-65 (FIPS 204) cryptographic sizes
 /// ML-DSA-65 public key size in bytes.
 pub const MLDSA65_PUBLIC_KEY_BYTES: usize = 1952;
@@ -23,63 +44,6 @@ pub const MLDSA65_SECRET_KEY_BYTES: usize = 4032;
 /// ML-DSA-65 detached signature size in bytes.
 pub const MLDSA65_SIGNATURE_BYTES: usize = 3309;
 
-/// Wrapper for ML-DSA-65 secret key that zeroizes memory on drop.
-///
-/// # Security
-///
-/// Uses `unsafe` to obtain mutable access to the key's backing memory
-/// for zeroization. This is necessary because `pqcrypto-mldsa` does not
-/// expose a `as_bytes_mut()` method on `SecretKey`.
-///
-/// # Safety
-///
-/// The unsafe block reinterprets the immutable key bytes as mutable for
-/// zeroization only during `Drop`. This is safe because:
-/// 1. We have exclusive (`&mut self`) access during Drop
-/// 2. The key will not be used after Drop completes
-/// 3. The zeroize operation does not change the type or layout
-pub struct ZeroizingSecretKey {
-    /// The actual key used for signing operations
-    key: mldsa65::SecretKey,
-}
-
-impl ZeroizingSecretKey {
-    /// Create a new zeroizing wrapper from an ML-DSA-65 secret key.
-    pub fn new(key: mldsa65::SecretKey) -> Self {
-        Self { key }
-    }
-
-    /// Get a reference to the underlying secret key for signing.
-    pub fn as_inner(&self) -> &mldsa65::SecretKey {
-        &self.key
-    }
-}
-
-impl Drop for ZeroizingSecretKey {
-    fn drop(&mut self) {
-        // SAFETY: We have exclusive &mut self access during Drop.
-        // The key bytes pointer is valid for the key's lifetime and we are
-        // the sole owner. After Drop, the memory is freed anyway, but we
-        // zeroize to prevent the key lingering in freed memory pages.
-        let key_bytes = self.key.as_bytes();
-        let len = key_bytes.len();
-        let ptr = key_bytes.as_ptr() as *mut u8;
-        unsafe {
-            let slice = std::slice::from_raw_parts_mut(ptr, len);
-            slice.zeroize();
-        }
-    }
-}
-
-// Prevent Debug from leaking key material
-impl std::fmt::Debug for ZeroizingSecretKey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ZeroizingSecretKey")
-            .field("key", &"[REDACTED]")
-            .finish()
-    }
-}
-
 /// Result of seal verification with detailed failure information.
 ///
 /// This enum provides more granular information about verification failures
@@ -96,6 +60,13 @@ pub enum VerificationResult {
     InvalidPublicKey,
     /// Signature format is malformed
     MalformedSignature,
+    /// Seal signature is valid but the attached entropy certificate is not
+    InvalidEntropyCertificate,
+    /// Threshold seal has fewer valid co-signatures than its policy requires
+    ThresholdNotMet,
+    /// Seal signature is valid but the seal is outside its validity window
+    /// (`not_before`/`not_after`)
+    Expired,
 }
 
 impl VerificationResult {
@@ -105,16 +76,29 @@ impl VerificationResult {
         matches!(self, Self::Valid)
     }
 
-    /// Returns a human-readable description of the result.
-    pub fn description(&self) -> &'static str {
+    /// Stable code of the result, see [`crate::i18n`].
+    pub fn code(&self) -> MessageCode {
         match self {
-            Self::Valid => "Signature is valid",
-            Self::InvalidSignature => "Signature verification failed - seal may be forged",
-            Self::PayloadMismatch => "Payload mismatch - seal data may have been modified",
-            Self::InvalidPublicKey => "Public key in seal is malformed",
-            Self::MalformedSignature => "Signature format is invalid",
+            Self::Valid => MessageCode::SignatureValid,
+            Self::InvalidSignature => MessageCode::InvalidSignature,
+            Self::PayloadMismatch => MessageCode::PayloadMismatch,
+            Self::InvalidPublicKey => MessageCode::InvalidPublicKey,
+            Self::MalformedSignature => MessageCode::MalformedSignature,
+            Self::InvalidEntropyCertificate => MessageCode::InvalidEntropyCertificate,
+            Self::ThresholdNotMet => MessageCode::ThresholdNotMet,
+            Self::Expired => MessageCode::Expired,
         }
     }
+
+    /// Returns a human-readable description of the result.
+    pub fn description(&self) -> &'static str {
+        self.description_in(Locale::En)
+    }
+
+    /// Returns a human-readable description of the result in `locale`.
+    pub fn description_in(&self, locale: Locale) -> &'static str {
+        self.code().message(locale)
+    }
 }
 
 /// Result of full content verification.
@@ -126,6 +110,8 @@ pub enum ContentVerificationResult {
     ContentModified {
         expected_hash: [u8; 32],
         actual_hash: [u8; 32],
+        /// Altered segments, for segmented seals with per-segment digests
+        tampered_segments: Option<Vec<TamperedSegment>>,
     },
     /// Signature verification failed
     SignatureFailed(VerificationResult),
@@ -138,14 +124,103 @@ impl ContentVerificationResult {
         matches!(self, Self::Authentic)
     }
 
+    /// Stable code of the result, see [`crate::i18n`].
+    pub fn code(&self) -> MessageCode {
+        match self {
+            Self::Authentic => MessageCode::ContentAuthentic,
+            Self::ContentModified {
+                tampered_segments: Some(segments),
+                ..
+            } if !segments.is_empty() => MessageCode::SegmentsModified,
+            Self::ContentModified { .. } => MessageCode::ContentModified,
+            Self::SignatureFailed(result) => result.code(),
+        }
+    }
+
     /// Returns a human-readable description of the result.
     pub fn description(&self) -> String {
+        self.description_in(Locale::En)
+    }
+
+    /// Returns a human-readable description of the result in `locale`.
+    pub fn description_in(&self, locale: Locale) -> String {
+        let message = self.code().message(locale);
         match self {
-            Self::Authentic => "Content is authentic - signature valid and hash matches".into(),
-            Self::ContentModified { .. } => {
-                "Content has been modified since sealing - hash mismatch".into()
-            }
-            Self::SignatureFailed(result) => result.description().into(),
+            Self::ContentModified {
+                tampered_segments: Some(segments),
+                ..
+            } => message.replace("{count}", &segments.len().to_string()),
+            _ => message.to_string(),
+        }
+    }
+}
+
+/// Largest perceptual hash distance, in bits of the 64-bit hash, at which
+/// modified content is graded [`ContentGrade::LikelyRecompressed`].
+pub const RECOMPRESSION_MAX_DISTANCE: u32 = 10;
+
+/// Graded outcome of content verification.
+///
+/// An image recompressed or resized after sealing fails the cryptographic
+/// hash just like an edited one; the perceptual hash tells them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentGrade {
+    /// Content unchanged since sealing
+    Authentic,
+    /// Content differs but looks the same as the sealed image
+    LikelyRecompressed {
+        /// Perceptual hash distance to the sealed image, in bits
+        distance: u32,
+    },
+    /// Content modified since sealing
+    Modified,
+}
+
+impl ContentGrade {
+    /// Grade as serialized (`authentic`, `likely_recompressed`, `modified`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Authentic => "authentic",
+            Self::LikelyRecompressed { .. } => "likely_recompressed",
+            Self::Modified => "modified",
+        }
+    }
+
+    /// Stable code of the grade, see [`crate::i18n`].
+    pub fn code(&self) -> MessageCode {
+        match self {
+            Self::Authentic => MessageCode::ContentAuthentic,
+            Self::LikelyRecompressed { .. } => MessageCode::LikelyRecompressed,
+            Self::Modified => MessageCode::ContentModified,
+        }
+    }
+}
+
+impl ContentVerificationResult {
+    /// Grade of the result, given the perceptual distance between the sealed
+    /// and the verified content (`None` if unknown), or `None` if the
+    /// signature failed.
+    ///
+    /// Modified content within `max_distance` bits is likely recompressed,
+    /// unless segments of a segmented recording pinpoint an edit.
+    pub fn grade(
+        &self,
+        perceptual_distance: Option<u32>,
+        max_distance: u32,
+    ) -> Option<ContentGrade> {
+        match self {
+            Self::Authentic => Some(ContentGrade::Authentic),
+            Self::ContentModified {
+                tampered_segments: Some(segments),
+                ..
+            } if !segments.is_empty() => Some(ContentGrade::Modified),
+            Self::ContentModified { .. } => Some(match perceptual_distance {
+                Some(distance) if distance <= max_distance => {
+                    ContentGrade::LikelyRecompressed { distance }
+                }
+                _ => ContentGrade::Modified,
+            }),
+            Self::SignatureFailed(_) => None,
         }
     }
 }
@@ -168,6 +243,17 @@ pub struct ContentHash {
     pub crypto_hash: [u8; 32],
     /// Optional perceptual hash for images/video (for robustness to re-encoding)
     pub perceptual_hash: Option<Vec<u8>>,
+    /// Segment tree for long recordings (see [`crate::segments`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<SegmentManifest>,
+    /// Hash of the format-aware canonical form, for documents that are
+    /// routinely re-saved (see [`crate::document`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_hash: Option<[u8; 32]>,
+    /// Perceptual hashes of the tiles of an image, to localize edits (see
+    /// [`crate::regions`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region_hashes: Option<RegionHashes>,
 }
 
 impl ContentHash {
@@ -183,7 +269,27 @@ impl ContentHash {
         Self {
             crypto_hash,
             perceptual_hash: None,
+            segments: None,
+            canonical_hash: None,
+            region_hashes: None,
+        }
+    }
+
+    /// Create a content hash by streaming from a reader (cryptographic hash only).
+    ///
+    /// Produces the same hash as [`ContentHash::from_bytes`] without holding
+    /// the whole content in memory.
+    pub fn from_reader<R: std::io::Read>(mut reader: R) -> std::io::Result<Self> {
+        let mut hasher = ContentHasher::new();
+        let mut buffer = [0u8; 64 * 1024];
+        loop {
+            let n = reader.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
         }
+        Ok(hasher.finalize())
     }
 
     /// Create a content hash with both cryptographic and perceptual hashes.
@@ -207,6 +313,9 @@ impl ContentHash {
         Self {
             crypto_hash,
             perceptual_hash,
+            segments: None,
+            canonical_hash: None,
+            region_hashes: None,
         }
     }
 
@@ -226,23 +335,210 @@ impl ContentHash {
     }
 }
 
+/// Incremental [`ContentHash`] computation, for content that arrives in
+/// chunks (a browser `File.stream()`, a network body).
+///
+/// Feeding every chunk in order produces the same hash as
+/// [`ContentHash::from_bytes`] over the whole content.
+#[derive(Clone, Default)]
+pub struct ContentHasher {
+    hasher: Sha3_256,
+    bytes_hashed: u64,
+}
+
+impl ContentHasher {
+    /// Start hashing new content.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash the next chunk of content.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+        self.bytes_hashed += chunk.len() as u64;
+    }
+
+    /// Number of bytes hashed so far.
+    pub fn bytes_hashed(&self) -> u64 {
+        self.bytes_hashed
+    }
+
+    /// Content hash of everything fed so far (cryptographic hash only).
+    pub fn finalize(self) -> ContentHash {
+        ContentHash {
+            crypto_hash: self.hasher.finalize().into(),
+            perceptual_hash: None,
+            segments: None,
+            canonical_hash: None,
+            region_hashes: None,
+        }
+    }
+}
+
 /// Media type being sealed.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Serialized as the variant name (`"Image"`), or `{"Custom": "<mime>"}` for
+/// custom types, so adding variants never changes how existing seals encode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MediaType {
     Image,
     Video,
     Audio,
+    /// Office documents; PDFs also get a canonical hash that survives re-saving
+    Document,
+    /// Arbitrary bytes with no format-specific handling
+    Generic,
+    /// Application-defined type, stored as a MIME type (e.g. `model/gltf-binary`)
+    Custom(String),
+}
+
+/// Maximum length of a [`MediaType::Custom`] MIME type (RFC 6838 limits each
+/// of type and subtype to 127 characters).
+pub const MAX_CUSTOM_MEDIA_TYPE_LEN: usize = 255;
+
+impl MediaType {
+    /// Infer the media type from a MIME type such as `image/png`.
+    ///
+    /// Parameters (`; charset=...`) are ignored. Well-formed MIME types
+    /// without a dedicated variant become [`MediaType::Custom`]; anything
+    /// else, including `application/octet-stream`, is [`MediaType::Generic`].
+    pub fn from_mime(mime: &str) -> Self {
+        let essence = mime
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match essence.split_once('/') {
+            Some(("image", _)) => Self::Image,
+            Some(("video", _)) => Self::Video,
+            Some(("audio", _)) => Self::Audio,
+            Some(("application", "pdf")) => Self::Document,
+            Some(("application", "octet-stream")) => Self::Generic,
+            Some(_) if is_valid_custom_mime(&essence) => Self::Custom(essence),
+            _ => Self::Generic,
+        }
+    }
+
+    /// Infer the media type from a file extension such as `jpg` (case
+    /// insensitive); unknown extensions are [`MediaType::Generic`].
+    pub fn from_extension(extension: &str) -> Self {
+        match extension.to_ascii_lowercase().as_str() {
+            "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "tiff" | "svg" => Self::Image,
+            "mp4" | "mov" | "avi" | "mkv" | "webm" | "flv" | "wmv" => Self::Video,
+            "mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a" => Self::Audio,
+            "pdf" => Self::Document,
+            "txt" | "md" => Self::Custom("text/plain".into()),
+            "csv" => Self::Custom("text/csv".into()),
+            "json" => Self::Custom("application/json".into()),
+            "xml" => Self::Custom("application/xml".into()),
+            "glb" => Self::Custom("model/gltf-binary".into()),
+            "gltf" => Self::Custom("model/gltf+json".into()),
+            _ => Self::Generic,
+        }
+    }
+
+    /// Parse a media type name (`image`, `video`, `audio`, `document`,
+    /// `generic`) or a MIME type, as accepted by the CLI and server.
+    ///
+    /// Returns `None` for anything else.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "image" => Some(Self::Image),
+            "video" => Some(Self::Video),
+            "audio" => Some(Self::Audio),
+            "document" => Some(Self::Document),
+            "generic" => Some(Self::Generic),
+            _ if value.contains('/') => Some(Self::from_mime(&value)),
+            _ => None,
+        }
+    }
+
+    /// MIME type to assume when the content type is otherwise unknown.
+    pub fn default_mime(&self) -> &str {
+        match self {
+            Self::Image => "image/jpeg",
+            Self::Video => "video/mp4",
+            Self::Audio => "audio/mpeg",
+            Self::Document => "application/pdf",
+            Self::Generic => "application/octet-stream",
+            Self::Custom(mime) => mime,
+        }
+    }
+
+    /// Reject custom types that are not a well-formed MIME type.
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self {
+            Self::Custom(mime) if !is_valid_custom_mime(mime) => Err(VeritasError::InvalidSeal(
+                format!("invalid custom media type '{mime}'"),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for MediaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Image => write!(f, "image"),
+            Self::Video => write!(f, "video"),
+            Self::Audio => write!(f, "audio"),
+            Self::Document => write!(f, "document"),
+            Self::Generic => write!(f, "generic"),
+            Self::Custom(mime) => write!(f, "{mime}"),
+        }
+    }
+}
+
+/// Check a lowercase `type/subtype` against the RFC 6838 character set.
+fn is_valid_custom_mime(mime: &str) -> bool {
+    let restricted = |part: &str| {
+        !part.is_empty()
+            && part.len() <= 127
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$&-^_.+".contains(&b))
+    };
+    mime.len() <= MAX_CUSTOM_MEDIA_TYPE_LEN
+        && mime
+            .split_once('/')
+            .is_some_and(|(kind, subtype)| restricted(kind) && restricted(subtype))
 }
 
 /// Blockchain anchor reference for immutable timestamping.
+///
+/// Anchors publish [`VeritasSeal::anchor_digest`]; see [`crate::anchor`] for
+/// the chains that can carry and check them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockchainAnchor {
-    /// Chain identifier (e.g., "solana-mainnet", "solana-devnet")
+    /// Chain identifier (e.g., "solana-mainnet", "solana-devnet", "bitcoin")
     pub chain: String,
-    /// Transaction ID on the blockchain
+    /// Transaction ID on the blockchain (OpenTimestamps: the calendar URL)
     pub tx_id: String,
-    /// Block height when anchored
+    /// Block height when anchored (0 = not yet known)
     pub block_height: u64,
+    /// Chain-specific inclusion proof (OpenTimestamps: the serialized
+    /// timestamp of the committed digest)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<Vec<u8>>,
+    /// Path to the batch root the transaction commits to, when the seal was
+    /// anchored in a batch (see [`crate::anchor::AnchorBatch`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<BatchProof>,
+}
+
+impl BlockchainAnchor {
+    /// Digest the transaction must commit to for `seal`: the seal's anchor
+    /// digest, or the batch root for batched anchors.
+    ///
+    /// Returns `None` when the batch proof does not include the seal.
+    pub fn committed_digest(&self, seal: &VeritasSeal) -> Option<[u8; 32]> {
+        match &self.batch {
+            None => Some(seal.anchor_digest()),
+            Some(batch) => batch.includes(seal).then_some(batch.root),
+        }
+    }
 }
 
 /// The Veritas Seal - core data structure for authenticated media.
@@ -271,6 +567,20 @@ pub struct VeritasSeal {
     pub qrng_source: QrngSource,
     /// When entropy was generated (Unix timestamp ms)
     pub entropy_timestamp: u64,
+    /// Conditioning applied to the QRNG output (`None` = raw provider output)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qrng_conditioning: Option<QrngConditioning>,
+    /// SHA3-256 of the raw QRNG block, for conditioned seals with an entropy
+    /// certificate (seal version 17+): binds the certificate to `qrng_entropy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_entropy_hash: Option<[u8; 32]>,
+    /// Provider-signed proof that the entropy came from the QRNG
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entropy_certificate: Option<EntropyCertificate>,
+    /// Whether the entropy had to be a fresh draw or could come from a pool
+    /// (`None` = not recorded, seals before version 10)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qrng_policy: Option<QrngPolicy>,
 
     // === Content Binding ===
     /// Perceptual hash + cryptographic hash
@@ -278,18 +588,66 @@ pub struct VeritasSeal {
     /// Image, Video, Audio
     pub media_type: MediaType,
 
+    // === Validity Window ===
+    /// Seal does not verify before this time (Unix timestamp ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<u64>,
+    /// Seal does not verify after this time (Unix timestamp ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_after: Option<u64>,
+
+    // === Trusted Capture ===
+    /// Single-use nonce issued by the sealing server to the capturing device
+    /// before capture, proving the seal was made in that capture session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_nonce: Option<[u8; 32]>,
+    /// Liveness challenge answered by the capture (requires `capture_nonce`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub liveness: Option<LivenessChallenge>,
+
+    // === Selective Disclosure ===
+    /// Salted per-field commitments; when set, the signature covers their
+    /// Merkle root instead of the fields themselves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disclosure: Option<SelectiveDisclosure>,
+
     // === Post-Quantum Signature ===
     /// FIPS 204 ML-DSA-65 signature (128-bit security)
     pub signature: Vec<u8>,
     /// ML-DSA-65 public key
     pub public_key: Vec<u8>,
+    /// ID of `public_key` in the signer's key registry (covered by
+    /// `signature`; `None` = one-off key, see [`crate::signing_keys`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+
+    // === Threshold Signing ===
+    /// k-of-n co-signing requirement (covered by `signature`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold_policy: Option<ThresholdPolicy>,
+    /// Co-signatures collected for `threshold_policy`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partial_signatures: Vec<PartialSignature>,
 
     // === Anchoring ===
     /// Optional blockchain anchor for public verification
     pub blockchain_anchor: Option<BlockchainAnchor>,
+    /// Anchors on further chains, so the timestamp does not rest on one chain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_anchors: Vec<BlockchainAnchor>,
+
+    // === Endorsements ===
+    /// Counter-signatures by notaries, editors, etc. (not covered by `signature`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub countersignatures: Vec<CounterSignature>,
+    /// Signed verdicts of AI-content detectors (not covered by `signature`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ai_detections: Vec<AiDetectionAssertion>,
 }
 
 /// Default version for deserializing legacy seals without version field.
+///
+/// Those seals predate versioning and always use the v1 schema.
 fn default_version() -> u8 {
     1
 }
@@ -302,6 +660,17 @@ pub struct SealBuilder {
     media_type: MediaType,
     capture_location: Option<String>,
     device_attestation: Option<DeviceAttestation>,
+    qrng_conditioning: Option<QrngConditioning>,
+    qrng_policy: Option<QrngPolicy>,
+    threshold_policy: Option<ThresholdPolicy>,
+    not_before: Option<u64>,
+    not_after: Option<u64>,
+    capture_nonce: Option<[u8; 32]>,
+    liveness: Option<LivenessChallenge>,
+    key_id: Option<String>,
+    selective_disclosure: bool,
+    segments: Option<SegmentLayout>,
+    content_hash: Option<ContentHash>,
 }
 
 #[cfg(feature = "network")]
@@ -313,6 +682,28 @@ impl SealBuilder {
             media_type,
             capture_location: None,
             device_attestation: None,
+            qrng_conditioning: None,
+            qrng_policy: None,
+            threshold_policy: None,
+            not_before: None,
+            not_after: None,
+            capture_nonce: None,
+            liveness: None,
+            key_id: None,
+            selective_disclosure: false,
+            segments: None,
+            content_hash: None,
+        }
+    }
+
+    /// Create a seal builder over an already computed content hash, for
+    /// content too large to hold in memory (see [`ContentHash::from_reader`]).
+    ///
+    /// The hash is sealed as is: [`SealBuilder::with_segments`] has no effect.
+    pub fn from_content_hash(content_hash: ContentHash, media_type: MediaType) -> Self {
+        Self {
+            content_hash: Some(content_hash),
+            ..Self::new(Vec::new(), media_type)
         }
     }
 
@@ -328,6 +719,80 @@ impl SealBuilder {
         self
     }
 
+    /// Condition the QRNG output before sealing (e.g. mix with OS randomness).
+    ///
+    /// The policy is recorded in the seal's `qrng_conditioning` field.
+    pub fn with_conditioning(mut self, policy: QrngConditioning) -> Self {
+        self.qrng_conditioning = Some(policy);
+        self
+    }
+
+    /// Allow entropy drawn ahead into a pool (see [`crate::qrng::EntropyPool`]).
+    ///
+    /// The policy is recorded in the seal's `qrng_policy` field. Without it,
+    /// the builder only accepts a fresh draw, like [`QrngPolicy::FreshPerSeal`].
+    pub fn with_qrng_policy(mut self, policy: QrngPolicy) -> Self {
+        self.qrng_policy = Some(policy);
+        self
+    }
+
+    /// Require co-signatures from `k` of `n` keys (see [`crate::threshold`]).
+    ///
+    /// The built seal does not verify until enough co-signers have signed.
+    pub fn with_threshold(mut self, policy: ThresholdPolicy) -> Self {
+        self.threshold_policy = Some(policy);
+        self
+    }
+
+    /// Limit the time during which the seal verifies, e.g. for time-boxed
+    /// credentials (Unix timestamps in ms, `None` = unbounded).
+    ///
+    /// Outside the window, [`VeritasSeal::verify_detailed`] reports
+    /// [`VerificationResult::Expired`].
+    pub fn with_validity(mut self, not_before: Option<u64>, not_after: Option<u64>) -> Self {
+        self.not_before = not_before;
+        self.not_after = not_after;
+        self
+    }
+
+    /// Bind the seal to a trusted capture session by signing the nonce the
+    /// server issued to the capturing device.
+    pub fn with_capture_nonce(mut self, nonce: [u8; 32]) -> Self {
+        self.capture_nonce = Some(nonce);
+        self
+    }
+
+    /// Record the capture's answer to the session's liveness challenge
+    /// (see [`crate::liveness`]); building fails unless it matches the
+    /// capture nonce and was answered before sealing.
+    pub fn with_liveness(mut self, liveness: LivenessChallenge) -> Self {
+        self.liveness = Some(liveness);
+        self
+    }
+
+    /// Record the registry ID of the signing key (see
+    /// [`crate::signing_keys`]), so verifiers can check the key was valid
+    /// when the seal was made.
+    pub fn with_key_id(mut self, key_id: impl Into<String>) -> Self {
+        self.key_id = Some(key_id.into());
+        self
+    }
+
+    /// Sign per-field commitments instead of the fields themselves, so the
+    /// seal can later be redacted or disclosed field by field
+    /// (see [`crate::disclosure`]).
+    pub fn with_selective_disclosure(mut self) -> Self {
+        self.selective_disclosure = true;
+        self
+    }
+
+    /// Hash the content as fixed-size segments so clips can be verified
+    /// on their own (see [`crate::segments`]).
+    pub fn with_segments(mut self, layout: SegmentLayout) -> Self {
+        self.segments = Some(layout);
+        self
+    }
+
     /// Build and sign the seal using the provided QRNG source and signing key.
     ///
     /// Accepts either a raw `mldsa65::SecretKey` or a `ZeroizingSecretKey` wrapper.
@@ -337,18 +802,110 @@ impl SealBuilder {
         secret_key: &mldsa65::SecretKey,
         public_key: &mldsa65::PublicKey,
     ) -> Result<VeritasSeal> {
+        let content_hash = self.content_hash()?;
+        self.build_with_content_hash(content_hash, qrng, secret_key, public_key)
+            .await
+    }
+
+    /// Hash the builder content (with perceptual hash for images if the
+    /// feature is enabled, and the segment tree if requested).
+    fn content_hash(&self) -> Result<ContentHash> {
+        if let Some(content_hash) = &self.content_hash {
+            return Ok(content_hash.clone());
+        }
+
+        #[cfg(feature = "perceptual-hash")]
+        let mut content_hash = if self.media_type == MediaType::Image {
+            ContentHash::from_bytes_with_phash(&self.content)
+        } else {
+            ContentHash::from_bytes(&self.content)
+        };
+
+        #[cfg(not(feature = "perceptual-hash"))]
+        let mut content_hash = ContentHash::from_bytes(&self.content);
+
+        if let Some(layout) = self.segments {
+            content_hash.segments = Some(SegmentManifest::compute(&self.content, layout)?);
+        }
+
+        if self.media_type == MediaType::Document && crate::document::is_pdf(&self.content) {
+            content_hash.canonical_hash = Some(crate::document::canonical_pdf_hash(&self.content)?);
+        }
+
+        #[cfg(feature = "perceptual-hash")]
+        if self.media_type == MediaType::Image {
+            content_hash.region_hashes =
+                RegionHashes::from_bytes(&self.content, crate::regions::DEFAULT_REGION_GRID);
+        }
+
+        Ok(content_hash)
+    }
+
+    /// Sign a seal over an already computed content hash, ignoring the
+    /// builder content (used by streaming sessions that never hold the
+    /// whole recording).
+    pub(crate) async fn build_with_content_hash<Q: QuantumEntropySource + ?Sized>(
+        self,
+        content_hash: ContentHash,
+        qrng: &Q,
+        secret_key: &mldsa65::SecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<VeritasSeal> {
+        self.media_type.validate()?;
+
         let now = Utc::now();
         let capture_timestamp_utc =
             u64::try_from(now.timestamp_millis()).map_err(|_| VeritasError::InvalidTimestamp {
                 reason: "timestamp before Unix epoch".into(),
             })?;
 
-        // Fetch quantum entropy
-        let qrng_entropy = qrng.get_entropy().await?;
+        if let Some(not_after) = self.not_after {
+            if self
+                .not_before
+                .is_some_and(|not_before| not_before > not_after)
+            {
+                return Err(VeritasError::InvalidTimestamp {
+                    reason: "validity window ends before it starts".into(),
+                });
+            }
+            if not_after < capture_timestamp_utc {
+                return Err(VeritasError::InvalidTimestamp {
+                    reason: "validity window ends before the capture time".into(),
+                });
+            }
+        }
+
+        if let Some(liveness) = &self.liveness {
+            liveness.check(self.capture_nonce.as_ref(), capture_timestamp_utc)?;
+        }
+
+        if let Some(key_id) = &self.key_id {
+            validate_key_id(key_id)?;
+        }
+
+        // Fetch quantum entropy (with a certificate of origin if the provider signs it)
+        let attested = qrng.get_attested_entropy().await?;
+        self.qrng_policy
+            .unwrap_or_default()
+            .check(&attested, capture_timestamp_utc)?;
+        let qrng_entropy = attested.entropy;
+        let entropy_certificate = attested.certificate;
 
         // Validate entropy quality (reject degenerate patterns)
         crate::qrng::validate_entropy(&qrng_entropy)?;
 
+        // Conditioning hides the certified block, so keep its hash
+        let raw_entropy_hash = match (&self.qrng_conditioning, &entropy_certificate) {
+            (Some(_), Some(_)) => Some(Sha3_256::digest(qrng_entropy).into()),
+            _ => None,
+        };
+
+        // Optionally condition the raw QRNG output
+        let qrng_entropy = match self.qrng_conditioning {
+            Some(policy) => crate::qrng::condition_entropy(&qrng_entropy, policy)?,
+            None => qrng_entropy,
+        };
+
         let entropy_timestamp = u64::try_from(Utc::now().timestamp_millis()).map_err(|_| {
             VeritasError::InvalidTimestamp {
                 reason: "entropy timestamp before Unix epoch".into(),
@@ -365,42 +922,14 @@ impl SealBuilder {
             });
         }
 
-        // Create content hash (with perceptual hash for images if feature enabled)
-        #[cfg(feature = "perceptual-hash")]
-        let content_hash = if self.media_type == MediaType::Image {
-            ContentHash::from_bytes_with_phash(&self.content)
+        let disclosure = if self.selective_disclosure {
+            Some(SelectiveDisclosure::generate()?)
         } else {
-            ContentHash::from_bytes(&self.content)
-        };
-
-        #[cfg(not(feature = "perceptual-hash"))]
-        let content_hash = ContentHash::from_bytes(&self.content);
-
-        // Get QRNG source identifier
-        let qrng_source = qrng.source_id();
-
-        // Create the signable payload (everything except signature)
-        let signable = SignablePayload {
-            capture_timestamp_utc,
-            capture_location: &self.capture_location,
-            device_attestation: &self.device_attestation,
-            qrng_entropy: &qrng_entropy,
-            qrng_source: &qrng_source,
-            entropy_timestamp,
-            content_hash: &content_hash,
-            media_type: self.media_type,
+            None
         };
 
-        // Serialize signable payload to CBOR for signing (pre-allocate buffer)
-        let mut signable_bytes = Vec::with_capacity(512);
-        ciborium::into_writer(&signable, &mut signable_bytes)
-            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
-
-        // Sign with ML-DSA-65
-        let signed_message = mldsa65::sign(&signable_bytes, secret_key);
-        let signature = signed_message.as_bytes().to_vec();
-
-        Ok(VeritasSeal {
+        // Assemble the seal, then sign its payload (everything except signature)
+        let mut seal = VeritasSeal {
             version: CURRENT_SEAL_VERSION,
             capture_timestamp_utc,
             capture_location: self.capture_location,
@@ -408,12 +937,34 @@ impl SealBuilder {
             qrng_entropy,
             qrng_source: qrng.source_id(),
             entropy_timestamp,
+            qrng_conditioning: self.qrng_conditioning,
+            raw_entropy_hash,
+            entropy_certificate,
+            qrng_policy: self.qrng_policy,
             content_hash,
             media_type: self.media_type,
-            signature,
+            not_before: self.not_before,
+            not_after: self.not_after,
+            capture_nonce: self.capture_nonce,
+            liveness: self.liveness,
+            disclosure,
+            signature: Vec::new(),
             public_key: public_key.as_bytes().to_vec(),
+            key_id: self.key_id,
+            threshold_policy: self.threshold_policy,
+            partial_signatures: Vec::new(),
             blockchain_anchor: None,
-        })
+            additional_anchors: Vec::new(),
+            countersignatures: Vec::new(),
+            ai_detections: Vec::new(),
+        };
+
+        // Sign with ML-DSA-65
+        let signable_bytes = seal.signable_bytes()?;
+        let signed_message = mldsa65::sign(&signable_bytes, secret_key);
+        seal.signature = signed_message.as_bytes().to_vec();
+
+        Ok(seal)
     }
 
     /// Build and sign the seal using a zeroizing secret key wrapper.
@@ -430,6 +981,57 @@ impl SealBuilder {
     }
 }
 
+#[cfg(feature = "blocking")]
+impl SealBuilder {
+    /// Build and sign the seal without an async runtime, drawing entropy
+    /// from a blocking source (see [`crate::qrng::BlockingEntropySource`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime; use
+    /// [`SealBuilder::build_secure`] there.
+    pub fn build_blocking<Q: crate::qrng::BlockingEntropySource + ?Sized>(
+        self,
+        qrng: &Q,
+        secret_key: &ZeroizingSecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<VeritasSeal> {
+        // Entropy is fetched up front, so building never waits on I/O and
+        // a current-thread runtime suffices
+        let prefetched = PrefetchedEntropy {
+            attested: qrng.get_attested_entropy()?,
+            source: qrng.source_id(),
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .map_err(|e| VeritasError::SignatureError(format!("Failed to start runtime: {e}")))?;
+        runtime.block_on(self.build_secure(&prefetched, secret_key, public_key))
+    }
+}
+
+/// Entropy drawn from a blocking source before building a seal.
+#[cfg(feature = "blocking")]
+struct PrefetchedEntropy {
+    attested: crate::qrng::AttestedEntropy,
+    source: QrngSource,
+}
+
+#[cfg(feature = "blocking")]
+#[async_trait::async_trait]
+impl QuantumEntropySource for PrefetchedEntropy {
+    async fn get_entropy(&self) -> Result<[u8; 32]> {
+        Ok(self.attested.entropy)
+    }
+
+    async fn get_attested_entropy(&self) -> Result<crate::qrng::AttestedEntropy> {
+        Ok(self.attested.clone())
+    }
+
+    fn source_id(&self) -> QrngSource {
+        self.source.clone()
+    }
+}
+
 /// Internal structure for the signable portion of a seal.
 #[derive(Serialize)]
 struct SignablePayload<'a> {
@@ -439,8 +1041,29 @@ struct SignablePayload<'a> {
     qrng_entropy: &'a [u8; 32],
     qrng_source: &'a QrngSource,
     entropy_timestamp: u64,
+    // Omitted when absent so seals created before conditioning still verify
+    #[serde(skip_serializing_if = "Option::is_none")]
+    qrng_conditioning: Option<&'a QrngConditioning>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw_entropy_hash: Option<&'a [u8; 32]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entropy_certificate: Option<&'a EntropyCertificate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    qrng_policy: Option<&'a QrngPolicy>,
     content_hash: &'a ContentHash,
-    media_type: MediaType,
+    media_type: &'a MediaType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threshold_policy: Option<&'a ThresholdPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    not_before: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    not_after: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    capture_nonce: Option<&'a [u8; 32]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    liveness: Option<&'a LivenessChallenge>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key_id: Option<&'a str>,
 }
 
 impl VeritasSeal {
@@ -458,23 +1081,30 @@ impl VeritasSeal {
     ///
     /// Unlike [`verify`], this method distinguishes between different
     /// failure modes (invalid signature, payload mismatch, malformed keys).
+    /// The validity window is checked against the current time; see
+    /// [`verify_detailed_at`](Self::verify_detailed_at).
+    ///
+    /// An entropy certificate is checked for a valid signature and a match
+    /// with the seal entropy, not for who signed it: use
+    /// [`entropy_certificate_status`](Self::entropy_certificate_status) to
+    /// tell whether it comes from a trusted provider.
     pub fn verify_detailed(&self) -> Result<VerificationResult> {
-        // Reconstruct the signable payload (no clone needed - use reference)
-        let signable = SignablePayload {
-            capture_timestamp_utc: self.capture_timestamp_utc,
-            capture_location: &self.capture_location,
-            device_attestation: &self.device_attestation,
-            qrng_entropy: &self.qrng_entropy,
-            qrng_source: &self.qrng_source,
-            entropy_timestamp: self.entropy_timestamp,
-            content_hash: &self.content_hash,
-            media_type: self.media_type,
-        };
+        let now = u64::try_from(Utc::now().timestamp_millis()).unwrap_or(0);
+        self.verify_detailed_at(now)
+    }
 
-        // Serialize to CBOR (pre-allocate buffer)
-        let mut signable_bytes = Vec::with_capacity(512);
-        ciborium::into_writer(&signable, &mut signable_bytes)
-            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+    /// Like [`verify_detailed`](Self::verify_detailed), checking the validity
+    /// window at `timestamp_ms` (Unix timestamp ms) instead of now, e.g. to
+    /// tell whether a seal was valid when it was presented.
+    pub fn verify_detailed_at(&self, timestamp_ms: u64) -> Result<VerificationResult> {
+        // Malformed openings cannot reproduce the signed commitment root
+        if let Some(disclosure) = &self.disclosure {
+            if disclosure.validate(self).is_err() {
+                return Ok(VerificationResult::PayloadMismatch);
+            }
+        }
+
+        let signable_bytes = self.signable_bytes()?;
 
         // Reconstruct public key
         let public_key = match mldsa65::PublicKey::from_bytes(&self.public_key) {
@@ -491,13 +1121,121 @@ impl VeritasSeal {
         // Verify ML-DSA signature
         match mldsa65::open(&signed_message, &public_key) {
             Ok(verified_message) => {
-                if verified_message == signable_bytes {
-                    Ok(VerificationResult::Valid)
-                } else {
-                    Ok(VerificationResult::PayloadMismatch)
+                if !ct_eq(&verified_message, &signable_bytes) {
+                    return Ok(VerificationResult::PayloadMismatch);
                 }
             }
-            Err(_) => Ok(VerificationResult::InvalidSignature),
+            Err(_) => return Ok(VerificationResult::InvalidSignature),
+        }
+
+        // Verify the entropy certificate of origin, if present
+        if let Some(certificate) = &self.entropy_certificate {
+            if self.certifies_entropy(certificate) == Some(false)
+                || !certificate.signature_is_valid()?
+            {
+                return Ok(VerificationResult::InvalidEntropyCertificate);
+            }
+        }
+
+        // Threshold seals also need k valid co-signatures
+        if self.threshold_policy.is_some() {
+            let status = self.threshold_status()?;
+            if !status.is_some_and(|s| s.is_met()) {
+                return Ok(VerificationResult::ThresholdNotMet);
+            }
+        }
+
+        if !self.is_valid_at(timestamp_ms) {
+            return Ok(VerificationResult::Expired);
+        }
+
+        Ok(VerificationResult::Valid)
+    }
+
+    /// Returns true if `timestamp_ms` (Unix timestamp ms) falls inside the
+    /// seal's validity window, bounds included. Seals without a window are
+    /// valid at any time.
+    ///
+    /// Only meaningful once the signature has been verified.
+    pub fn is_valid_at(&self, timestamp_ms: u64) -> bool {
+        self.not_before
+            .is_none_or(|not_before| timestamp_ms >= not_before)
+            && self
+                .not_after
+                .is_none_or(|not_after| timestamp_ms <= not_after)
+    }
+
+    /// Reconstruct the CBOR payload covered by the seal signature.
+    pub(crate) fn signable_bytes(&self) -> Result<Vec<u8>> {
+        if let Some(disclosure) = &self.disclosure {
+            return crate::disclosure::committed_payload_bytes(self, disclosure);
+        }
+
+        // No clone needed - the payload borrows from the seal
+        let signable = SignablePayload {
+            capture_timestamp_utc: self.capture_timestamp_utc,
+            capture_location: &self.capture_location,
+            device_attestation: &self.device_attestation,
+            qrng_entropy: &self.qrng_entropy,
+            qrng_source: &self.qrng_source,
+            entropy_timestamp: self.entropy_timestamp,
+            qrng_conditioning: self.qrng_conditioning.as_ref(),
+            raw_entropy_hash: self.raw_entropy_hash.as_ref(),
+            entropy_certificate: self.entropy_certificate.as_ref(),
+            qrng_policy: self.qrng_policy.as_ref(),
+            content_hash: &self.content_hash,
+            media_type: &self.media_type,
+            threshold_policy: self.threshold_policy.as_ref(),
+            not_before: self.not_before,
+            not_after: self.not_after,
+            capture_nonce: self.capture_nonce.as_ref(),
+            liveness: self.liveness.as_ref(),
+            key_id: self.key_id.as_deref(),
+        };
+
+        // Serialize to CBOR (pre-allocate buffer)
+        let mut signable_bytes = Vec::with_capacity(512);
+        ciborium::into_writer(&signable, &mut signable_bytes)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+        Ok(signable_bytes)
+    }
+
+    /// Check the entropy certificate against the `trusted` provider keys.
+    ///
+    /// Returns `None` for seals without a certificate. The certificate is only
+    /// [`CertificateStatus::Trusted`] if a key trusted for the seal's QRNG
+    /// source signed it and it certifies this seal's entropy: `qrng_entropy`
+    /// itself, or the block hashed into `raw_entropy_hash` for conditioned
+    /// seals. Only check this once the seal signature is verified.
+    pub fn entropy_certificate_status(
+        &self,
+        trusted: &TrustedProviderKeys,
+    ) -> Result<Option<CertificateStatus>> {
+        let Some(certificate) = &self.entropy_certificate else {
+            return Ok(None);
+        };
+        let status = match (
+            self.certifies_entropy(certificate),
+            certificate.verify(trusted)?,
+        ) {
+            (Some(false), _) | (_, CertificateStatus::Invalid) => CertificateStatus::Invalid,
+            (None, _) => CertificateStatus::Unbound,
+            (Some(true), status) => status,
+        };
+        Ok(Some(status))
+    }
+
+    /// Whether `certificate` certifies this seal's entropy, or `None` if that
+    /// cannot be told: conditioned seals before version 17 do not record the
+    /// hash of the raw block.
+    fn certifies_entropy(&self, certificate: &EntropyCertificate) -> Option<bool> {
+        if certificate.source != self.qrng_source {
+            return Some(false);
+        }
+        match (&self.qrng_conditioning, &self.raw_entropy_hash) {
+            (None, _) => Some(ct_eq(&certificate.entropy, &self.qrng_entropy)),
+            (Some(_), Some(hash)) => Some(ct_eq(&certificate.entropy_hash(), hash)),
+            (Some(_), None) => None,
         }
     }
 
@@ -516,16 +1254,162 @@ impl VeritasSeal {
         // Then verify content hash
         let actual_hash = ContentHash::from_bytes(content);
 
-        if self.content_hash.crypto_hash == actual_hash.crypto_hash {
+        if ct_eq(&self.content_hash.crypto_hash, &actual_hash.crypto_hash)
+            || self.matches_canonical_form(content)
+        {
             Ok(ContentVerificationResult::Authentic)
         } else {
+            let tampered_segments = self
+                .content_hash
+                .segments
+                .as_ref()
+                .and_then(|manifest| manifest.tampered_segments(content));
             Ok(ContentVerificationResult::ContentModified {
                 expected_hash: self.content_hash.crypto_hash,
                 actual_hash: actual_hash.crypto_hash,
+                tampered_segments,
+            })
+        }
+    }
+
+    /// Verify like [`verify_content`](Self::verify_content) and grade the
+    /// result: modified images within `max_distance` bits of the sealed
+    /// perceptual hash (see [`RECOMPRESSION_MAX_DISTANCE`]) are graded
+    /// [`ContentGrade::LikelyRecompressed`] rather than modified.
+    ///
+    /// The perceptual hash is only computed when the content hash differs.
+    #[cfg(feature = "perceptual-hash")]
+    pub fn verify_content_graded(
+        &self,
+        content: &[u8],
+        max_distance: u32,
+    ) -> Result<(ContentVerificationResult, Option<ContentGrade>)> {
+        let result = self.verify_content(content)?;
+        let distance = match result {
+            ContentVerificationResult::ContentModified { .. } => self.perceptual_distance(content),
+            _ => None,
+        };
+        let grade = result.grade(distance, max_distance);
+        Ok((result, grade))
+    }
+
+    /// Compare the tiles of `content` with the sealed image, locating the
+    /// regions that changed, or `None` if the seal has no region hashes or
+    /// `content` is not an image.
+    #[cfg(feature = "perceptual-hash")]
+    pub fn region_comparison(&self, content: &[u8]) -> Option<RegionComparison> {
+        let sealed = self.content_hash.region_hashes.as_ref()?;
+        sealed.compare(&RegionHashes::from_bytes(content, sealed.grid)?)
+    }
+
+    /// Perceptual hash distance between the sealed image and `content`, in
+    /// bits, or `None` if the seal has no perceptual hash or `content` is
+    /// not an image.
+    #[cfg(feature = "perceptual-hash")]
+    pub fn perceptual_distance(&self, content: &[u8]) -> Option<u32> {
+        use crate::watermark::{compute_phash, hamming_distance};
+
+        let sealed = self.content_hash.perceptual_hash.as_deref()?;
+        hamming_distance(sealed, &compute_phash(content)?)
+    }
+
+    /// Verify the seal's signature and that `content_hash` (the SHA3-256 of
+    /// the content, e.g. from a [`ContentHasher`]) is the sealed hash.
+    ///
+    /// For content too large to hold in memory. Unlike
+    /// [`verify_content`](Self::verify_content), this cannot recognise a
+    /// re-saved PDF by its canonical form nor locate tampered segments: a
+    /// mismatch is reported as [`ContentVerificationResult::ContentModified`]
+    /// without segments.
+    pub fn verify_content_hash(
+        &self,
+        content_hash: &[u8; 32],
+    ) -> Result<ContentVerificationResult> {
+        let sig_result = self.verify_detailed()?;
+        if !sig_result.is_valid() {
+            return Ok(ContentVerificationResult::SignatureFailed(sig_result));
+        }
+
+        if ct_eq(&self.content_hash.crypto_hash, content_hash) {
+            Ok(ContentVerificationResult::Authentic)
+        } else {
+            Ok(ContentVerificationResult::ContentModified {
+                expected_hash: self.content_hash.crypto_hash,
+                actual_hash: *content_hash,
+                tampered_segments: None,
             })
         }
     }
 
+    /// Returns true if `content` differs from the sealed bytes only in ways
+    /// the seal's canonical hash ignores (e.g. a PDF saved again by a viewer).
+    pub fn matches_canonical_form(&self, content: &[u8]) -> bool {
+        match self.content_hash.canonical_hash {
+            Some(expected) if crate::document::is_pdf(content) => {
+                crate::document::canonical_pdf_hash(content)
+                    .is_ok_and(|hash| ct_eq(&hash, &expected))
+            }
+            _ => false,
+        }
+    }
+
+    /// SHA3-256 hash identifying the signed seal.
+    ///
+    /// Covers the signer key and the signed message (which embeds the signed
+    /// payload), but not the version, anchor or counter-signatures, so it is
+    /// stable across migration and endorsement.
+    pub fn seal_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(SEAL_HASH_DOMAIN);
+        hasher.update(&self.public_key);
+        hasher.update(&self.signature);
+
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&hasher.finalize());
+        hash
+    }
+
+    /// SHA3-256 digest published when anchoring the seal.
+    ///
+    /// Covers the content hash and the first 32 signature bytes. Solana memos
+    /// carry its first 16 bytes as hex; OpenTimestamps timestamps all of it.
+    pub fn anchor_digest(&self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.content_hash.crypto_hash);
+        hasher.update(&self.signature[..self.signature.len().min(32)]);
+
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&hasher.finalize());
+        digest
+    }
+
+    /// All anchors of the seal: `blockchain_anchor` first, then
+    /// `additional_anchors`.
+    pub fn anchors(&self) -> impl Iterator<Item = &BlockchainAnchor> {
+        self.blockchain_anchor
+            .iter()
+            .chain(self.additional_anchors.iter())
+    }
+
+    /// Record an anchor, replacing any previous anchor on the same chain.
+    ///
+    /// The first anchor goes to `blockchain_anchor`, which older verifiers
+    /// read; anchors on other chains go to `additional_anchors`.
+    pub fn add_anchor(&mut self, anchor: BlockchainAnchor) {
+        match &mut self.blockchain_anchor {
+            None => self.blockchain_anchor = Some(anchor),
+            Some(primary) if primary.chain == anchor.chain => *primary = anchor,
+            Some(_) => match self
+                .additional_anchors
+                .iter_mut()
+                .find(|existing| existing.chain == anchor.chain)
+            {
+                Some(existing) => *existing = anchor,
+                None => self.additional_anchors.push(anchor),
+            },
+        }
+    }
+
     /// Serialize the seal to CBOR bytes.
     pub fn to_cbor(&self) -> Result<Vec<u8>> {
         let mut bytes = Vec::with_capacity(4096);
@@ -534,6 +1418,21 @@ impl VeritasSeal {
         Ok(bytes)
     }
 
+    /// Serialize the seal to canonical JSON (RFC 8785).
+    ///
+    /// Keys are sorted and whitespace is stripped, so the same seal always
+    /// produces the same bytes regardless of platform or library version.
+    pub fn to_json_canonical(&self) -> Result<String> {
+        let bytes = crate::canonical::to_canonical_json(self)?;
+        String::from_utf8(bytes).map_err(|e| VeritasError::SerializationError(e.to_string()))
+    }
+
+    /// Serialize the seal to indented, human-readable JSON.
+    pub fn to_json_pretty(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))
+    }
+
     /// Deserialize a seal from CBOR bytes.
     ///
     /// # Security
@@ -552,34 +1451,95 @@ impl VeritasSeal {
         let seal: Self = ciborium::from_reader(bytes)
             .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
 
-        // Validate version compatibility
-        if seal.version > CURRENT_SEAL_VERSION {
-            return Err(VeritasError::UnsupportedSealVersion(
-                seal.version,
-                CURRENT_SEAL_VERSION,
-            ));
+        seal.validate_structure()?;
+        Ok(seal)
+    }
+
+    /// Deserialize a seal from JSON bytes (canonical or pretty-printed).
+    ///
+    /// # Security
+    ///
+    /// Applies the same checks as [`from_cbor`](Self::from_cbor), with a size
+    /// limit of [`MAX_JSON_SEAL_SIZE`].
+    pub fn from_json(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > MAX_JSON_SEAL_SIZE {
+            return Err(VeritasError::SealTooLarge {
+                size: bytes.len(),
+                max: MAX_JSON_SEAL_SIZE,
+            });
         }
 
+        let seal: Self = serde_json::from_slice(bytes)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+
+        seal.validate_structure()?;
+        Ok(seal)
+    }
+
+    /// Upgrade a seal decoded from an older format to [`CURRENT_SEAL_VERSION`].
+    ///
+    /// Only the `version` field changes: it is not covered by the signature,
+    /// so a migrated seal verifies exactly like the original. Seals already
+    /// at the current version are returned unchanged.
+    pub fn migrate_to_latest(mut self) -> Result<Self> {
+        crate::schema::migrate(&mut self)?;
+        Ok(self)
+    }
+
+    /// Returns true if the seal uses the current format version.
+    pub fn is_latest_version(&self) -> bool {
+        self.version == CURRENT_SEAL_VERSION
+    }
+
+    /// Validate version and cryptographic field sizes of a decoded seal.
+    ///
+    /// Shared by every deserialization path so all formats enforce the same rules.
+    pub(crate) fn validate_structure(&self) -> Result<()> {
+        // Validate version compatibility and version-specific fields
+        SealSchema::from_version(self.version)?.validate_fields(self)?;
+
         // Validate cryptographic field sizes (ML-DSA-65)
-        if seal.public_key.len() != MLDSA65_PUBLIC_KEY_BYTES {
+        if self.public_key.len() != MLDSA65_PUBLIC_KEY_BYTES {
             return Err(VeritasError::InvalidSeal(format!(
                 "invalid public key size: expected {} bytes, got {}",
                 MLDSA65_PUBLIC_KEY_BYTES,
-                seal.public_key.len()
+                self.public_key.len()
             )));
         }
 
         // Note: signature size varies because SignedMessage includes the message
         // Minimum size is MLDSA65_SIGNATURE_BYTES (detached signature)
-        if seal.signature.len() < MLDSA65_SIGNATURE_BYTES {
+        if self.signature.len() < MLDSA65_SIGNATURE_BYTES {
             return Err(VeritasError::InvalidSeal(format!(
                 "signature too short: minimum {} bytes, got {}",
                 MLDSA65_SIGNATURE_BYTES,
-                seal.signature.len()
+                self.signature.len()
             )));
         }
 
-        Ok(seal)
+        self.media_type.validate()?;
+
+        if let Some(key_id) = &self.key_id {
+            validate_key_id(key_id)?;
+        }
+
+        if let Some(policy) = &self.threshold_policy {
+            policy.validate()?;
+        }
+
+        if let Some(disclosure) = &self.disclosure {
+            disclosure.validate(self)?;
+        }
+
+        if let Some(segments) = &self.content_hash.segments {
+            segments.validate()?;
+        }
+
+        if let Some(regions) = &self.content_hash.region_hashes {
+            regions.validate()?;
+        }
+
+        Ok(())
     }
 }
 
@@ -588,8 +1548,10 @@ impl VeritasSeal {
 /// Returns the public key and a zeroizing wrapper for the secret key.
 /// The secret key will be securely erased from memory when dropped.
 pub fn generate_keypair() -> (mldsa65::PublicKey, ZeroizingSecretKey) {
-    let (pk, sk) = mldsa65::keypair();
-    (pk, ZeroizingSecretKey::new(sk))
+    let (pk, mut sk) = mldsa65::keypair();
+    let secret_key = ZeroizingSecretKey::new(sk);
+    crate::secret::wipe(&mut sk);
+    (pk, secret_key)
 }
 
 /// Generate a new ML-DSA-65 keypair returning raw keys (for testing).
@@ -705,6 +1667,48 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_migrate_v1_seal_keeps_signature() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+
+        let mut seal = SealBuilder::new(b"Test".to_vec(), MediaType::Image)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        seal.version = 1;
+        assert!(!seal.is_latest_version());
+
+        let migrated = seal.migrate_to_latest().expect("Migration failed");
+        assert!(migrated.is_latest_version());
+        assert_eq!(migrated.version, CURRENT_SEAL_VERSION);
+        assert!(migrated.verify().unwrap());
+
+        // Migrating again is a no-op
+        let again = migrated.clone().migrate_to_latest().unwrap();
+        assert_eq!(again.to_cbor().unwrap(), migrated.to_cbor().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_v1_seal_with_v2_fields_rejected() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+
+        let mut seal = SealBuilder::new(b"Test".to_vec(), MediaType::Image)
+            .with_conditioning(QrngConditioning::Sha3OsMix)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        seal.version = 1;
+
+        let cbor = seal.to_cbor().unwrap();
+        assert!(matches!(
+            VeritasSeal::from_cbor(&cbor),
+            Err(VeritasError::InvalidSeal(_))
+        ));
+        assert!(seal.migrate_to_latest().is_err());
+    }
+
     #[tokio::test]
     async fn test_verify_detailed_returns_valid() {
         let qrng = MockQrng::default();
@@ -822,6 +1826,51 @@ mod tests {
         assert!(!result.is_authentic());
     }
 
+    #[test]
+    fn test_content_grade() {
+        let modified = |tampered_segments| ContentVerificationResult::ContentModified {
+            expected_hash: [0; 32],
+            actual_hash: [1; 32],
+            tampered_segments,
+        };
+
+        assert_eq!(
+            ContentVerificationResult::Authentic.grade(None, RECOMPRESSION_MAX_DISTANCE),
+            Some(ContentGrade::Authentic)
+        );
+        assert_eq!(
+            modified(None).grade(Some(3), RECOMPRESSION_MAX_DISTANCE),
+            Some(ContentGrade::LikelyRecompressed { distance: 3 })
+        );
+        assert_eq!(
+            modified(None).grade(
+                Some(RECOMPRESSION_MAX_DISTANCE + 1),
+                RECOMPRESSION_MAX_DISTANCE
+            ),
+            Some(ContentGrade::Modified)
+        );
+        assert_eq!(
+            modified(None).grade(None, RECOMPRESSION_MAX_DISTANCE),
+            Some(ContentGrade::Modified)
+        );
+
+        // Tampered segments pinpoint an edit whatever the distance
+        let segments = vec![TamperedSegment {
+            index: 0,
+            byte_range: 0..10,
+            time_range_ms: None,
+        }];
+        assert_eq!(
+            modified(Some(segments)).grade(Some(0), RECOMPRESSION_MAX_DISTANCE),
+            Some(ContentGrade::Modified)
+        );
+        assert_eq!(
+            ContentVerificationResult::SignatureFailed(VerificationResult::InvalidSignature)
+                .grade(Some(0), RECOMPRESSION_MAX_DISTANCE),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_verify_content_signature_failed() {
         let qrng = MockQrng::default();
@@ -884,4 +1933,677 @@ mod tests {
 
         assert!(matches!(result, Err(VeritasError::InvalidSeal(_))));
     }
+
+    #[tokio::test]
+    async fn test_conditioned_seal_records_policy_and_verifies() {
+        let qrng = MockQrng::default();
+        let raw_entropy = qrng.get_entropy_sync().unwrap();
+        let (public_key, secret_key) = generate_keypair();
+
+        let seal = SealBuilder::new(b"Mixed".to_vec(), MediaType::Image)
+            .with_conditioning(QrngConditioning::Sha3OsMix)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        assert_eq!(seal.qrng_conditioning, Some(QrngConditioning::Sha3OsMix));
+        assert_ne!(seal.qrng_entropy, raw_entropy);
+        assert!(seal.verify().expect("Verification failed"));
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(
+            restored.qrng_conditioning,
+            Some(QrngConditioning::Sha3OsMix)
+        );
+        assert!(restored.verify().expect("Verification failed"));
+    }
+
+    #[tokio::test]
+    async fn test_stripping_conditioning_fails_verification() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+
+        let mut seal = SealBuilder::new(b"Mixed".to_vec(), MediaType::Image)
+            .with_conditioning(QrngConditioning::Sha3OsMix)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        seal.qrng_conditioning = None;
+        assert!(!seal.verify().expect("Verification call failed"));
+    }
+
+    #[tokio::test]
+    async fn test_unconditioned_seal_omits_field() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+
+        let seal = SealBuilder::new(b"Raw".to_vec(), MediaType::Image)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        assert_eq!(seal.qrng_entropy, qrng.get_entropy_sync().unwrap());
+        let json = serde_json::to_value(&seal).unwrap();
+        assert!(json.get("qrng_conditioning").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_qrng_policy_recorded_and_signed() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+        let policy = QrngPolicy::Pooled { max_age_secs: 300 };
+
+        let mut seal = SealBuilder::new(b"Pooled".to_vec(), MediaType::Image)
+            .with_qrng_policy(policy)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        assert_eq!(seal.qrng_policy, Some(policy));
+        assert!(seal.verify().unwrap());
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(restored.qrng_policy, Some(policy));
+
+        // Claiming a stricter policy breaks the signature
+        seal.qrng_policy = Some(QrngPolicy::FreshPerSeal);
+        assert!(!seal.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_validity_window_enforced() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+        let not_before = Utc::now().timestamp_millis() as u64;
+        let not_after = not_before + 60_000;
+
+        let mut seal = SealBuilder::new(b"Press pass".to_vec(), MediaType::Image)
+            .with_validity(Some(not_before), Some(not_after))
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        assert_eq!(
+            (seal.not_before, seal.not_after),
+            (Some(not_before), Some(not_after))
+        );
+        assert!(seal.verify().unwrap());
+
+        for (timestamp, expected) in [
+            (not_before - 1, VerificationResult::Expired),
+            (not_before, VerificationResult::Valid),
+            (not_after, VerificationResult::Valid),
+            (not_after + 1, VerificationResult::Expired),
+        ] {
+            assert_eq!(seal.verify_detailed_at(timestamp).unwrap(), expected);
+        }
+        assert_eq!(
+            seal.verify_content(b"Press pass").unwrap(),
+            ContentVerificationResult::Authentic
+        );
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(restored.not_after, Some(not_after));
+
+        // Extending the window breaks the signature
+        seal.not_after = None;
+        assert_eq!(
+            seal.verify_detailed_at(not_after + 1).unwrap(),
+            VerificationResult::PayloadMismatch
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validity_window_rejected_when_inverted_or_past() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+        let now = Utc::now().timestamp_millis() as u64;
+
+        for (not_before, not_after) in [(Some(now + 10_000), Some(now + 5_000)), (None, Some(1))] {
+            let result = SealBuilder::new(b"Window".to_vec(), MediaType::Image)
+                .with_validity(not_before, not_after)
+                .build_secure(&qrng, &secret_key, &public_key)
+                .await;
+            assert!(matches!(result, Err(VeritasError::InvalidTimestamp { .. })));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capture_nonce_is_signed() {
+        let (public_key, secret_key) = generate_keypair();
+        let mut seal = SealBuilder::new(b"Trusted capture".to_vec(), MediaType::Image)
+            .with_capture_nonce([7; 32])
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        assert!(seal.verify().unwrap());
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(restored.capture_nonce, Some([7; 32]));
+        assert!(restored.verify().unwrap());
+
+        // Replaying the media under another session's nonce breaks the signature
+        seal.capture_nonce = Some([8; 32]);
+        assert!(!seal.verify().unwrap());
+        seal.capture_nonce = None;
+        assert!(!seal.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_liveness_is_checked_and_signed() {
+        let (public_key, secret_key) = generate_keypair();
+        let now = Utc::now().timestamp_millis() as u64;
+        let liveness = LivenessChallenge {
+            code: crate::liveness::liveness_code(&[7; 32]),
+            issued_at: now - 3_000,
+            responded_at: now - 2_000,
+        };
+
+        // The challenge must belong to the capture session
+        for nonce in [None, Some([8; 32])] {
+            let mut builder = SealBuilder::new(b"Live capture".to_vec(), MediaType::Image)
+                .with_liveness(liveness.clone());
+            if let Some(nonce) = nonce {
+                builder = builder.with_capture_nonce(nonce);
+            }
+            assert!(builder
+                .build_secure(&MockQrng::default(), &secret_key, &public_key)
+                .await
+                .is_err());
+        }
+
+        let mut seal = SealBuilder::new(b"Live capture".to_vec(), MediaType::Image)
+            .with_capture_nonce([7; 32])
+            .with_liveness(liveness)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        assert!(seal.verify().unwrap());
+        assert_eq!(seal.verify_liveness(), Some(true));
+
+        // Claiming a faster answer breaks the signature
+        seal.liveness.as_mut().unwrap().responded_at = now - 2_900;
+        assert!(!seal.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_key_id_is_signed() {
+        let (public_key, secret_key) = generate_keypair();
+        let key_id = crate::signing_keys::key_id_for(public_key.as_bytes());
+
+        assert!(SealBuilder::new(b"Registered".to_vec(), MediaType::Image)
+            .with_key_id("")
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .is_err());
+
+        let mut seal = SealBuilder::new(b"Registered".to_vec(), MediaType::Image)
+            .with_key_id(key_id.clone())
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        assert_eq!(seal.key_id.as_deref(), Some(key_id.as_str()));
+        assert!(seal.verify().unwrap());
+
+        // Pointing the seal at another registry entry breaks the signature
+        seal.key_id = Some("0000000000000000".to_string());
+        assert!(!seal.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_pooled_entropy_requires_policy() {
+        let pool = crate::qrng::EntropyPool::new(
+            std::sync::Arc::new(MockQrng::default()),
+            QrngPolicy::Pooled { max_age_secs: 300 },
+            2,
+        );
+        let (public_key, secret_key) = generate_keypair();
+
+        // The first request draws the batch and gets a fresh block
+        SealBuilder::new(b"First".to_vec(), MediaType::Image)
+            .build_secure(&pool, &secret_key, &public_key)
+            .await
+            .expect("fresh block accepted");
+
+        // The second one gets a pooled block, refused by the default policy
+        let result = SealBuilder::new(b"Second".to_vec(), MediaType::Image)
+            .build_secure(&pool, &secret_key, &public_key)
+            .await;
+        assert!(matches!(result, Err(VeritasError::QrngError(_))));
+    }
+
+    /// Test source that signs every block it returns.
+    struct AttestingQrng {
+        public_key: mldsa65::PublicKey,
+        secret_key: mldsa65::SecretKey,
+    }
+
+    impl AttestingQrng {
+        fn new() -> Self {
+            let (public_key, secret_key) = mldsa65::keypair();
+            Self {
+                public_key,
+                secret_key,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl QuantumEntropySource for AttestingQrng {
+        async fn get_entropy(&self) -> Result<[u8; 32]> {
+            MockQrng::default().get_entropy_sync()
+        }
+
+        async fn get_attested_entropy(&self) -> Result<crate::qrng::AttestedEntropy> {
+            let entropy = self.get_entropy().await?;
+            let certificate = EntropyCertificate::sign(
+                self.source_id(),
+                entropy,
+                1_700_000_000_000,
+                &self.secret_key,
+                &self.public_key,
+            )?;
+            Ok(crate::qrng::AttestedEntropy {
+                entropy,
+                certificate: Some(certificate),
+                drawn_at: None,
+            })
+        }
+
+        fn source_id(&self) -> QrngSource {
+            QrngSource::IdQuantiqueCloud
+        }
+    }
+
+    #[tokio::test]
+    async fn test_attested_seal_stores_and_verifies_certificate() {
+        let qrng = AttestingQrng::new();
+        let (public_key, secret_key) = generate_keypair();
+
+        let seal = SealBuilder::new(b"Attested".to_vec(), MediaType::Image)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        let certificate = seal.entropy_certificate.as_ref().expect("certificate");
+        assert_eq!(certificate.entropy, seal.qrng_entropy);
+        assert!(seal.raw_entropy_hash.is_none());
+        assert_eq!(seal.verify_detailed().unwrap(), VerificationResult::Valid);
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(
+            restored.verify_detailed().unwrap(),
+            VerificationResult::Valid
+        );
+    }
+
+    #[tokio::test]
+    async fn test_entropy_certificate_status_requires_trusted_provider() {
+        let qrng = AttestingQrng::new();
+        let (public_key, secret_key) = generate_keypair();
+
+        let seal = SealBuilder::new(b"Attested".to_vec(), MediaType::Image)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        let trusted = TrustedProviderKeys::new().with_key(
+            QrngSource::IdQuantiqueCloud,
+            qrng.public_key.as_bytes().to_vec(),
+        );
+        assert_eq!(
+            seal.entropy_certificate_status(&trusted).unwrap(),
+            Some(CertificateStatus::Trusted)
+        );
+
+        // A self-made certificate passes the signature check but is not trusted
+        assert_eq!(
+            seal.entropy_certificate_status(&TrustedProviderKeys::new())
+                .unwrap(),
+            Some(CertificateStatus::UntrustedSigner)
+        );
+
+        let unattested = SealBuilder::new(b"Raw".to_vec(), MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        assert_eq!(
+            unattested.entropy_certificate_status(&trusted).unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_attested_seal_with_conditioning_verifies() {
+        let qrng = AttestingQrng::new();
+        let (public_key, secret_key) = generate_keypair();
+
+        let seal = SealBuilder::new(b"Attested".to_vec(), MediaType::Image)
+            .with_conditioning(QrngConditioning::Sha3OsMix)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        let certificate = seal.entropy_certificate.as_ref().expect("certificate");
+        assert_ne!(certificate.entropy, seal.qrng_entropy);
+        assert_eq!(seal.raw_entropy_hash, Some(certificate.entropy_hash()));
+        assert_eq!(seal.verify_detailed().unwrap(), VerificationResult::Valid);
+
+        let trusted = TrustedProviderKeys::new().with_key(
+            QrngSource::IdQuantiqueCloud,
+            qrng.public_key.as_bytes().to_vec(),
+        );
+        assert_eq!(
+            seal.entropy_certificate_status(&trusted).unwrap(),
+            Some(CertificateStatus::Trusted)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conditioned_seal_rejects_certificate_for_other_entropy() {
+        let qrng = AttestingQrng::new();
+        let (public_key, secret_key) = generate_keypair();
+
+        let mut seal = SealBuilder::new(b"Attested".to_vec(), MediaType::Image)
+            .with_conditioning(QrngConditioning::Sha3OsMix)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        // A validly signed certificate for another block, re-signed into the seal
+        let other = EntropyCertificate::sign(
+            QrngSource::IdQuantiqueCloud,
+            [0x42; 32],
+            1_700_000_000_000,
+            &qrng.secret_key,
+            &qrng.public_key,
+        )
+        .unwrap();
+        seal.entropy_certificate = Some(other);
+        let signable_bytes = seal.signable_bytes().unwrap();
+        seal.signature = mldsa65::sign(&signable_bytes, secret_key.as_inner())
+            .as_bytes()
+            .to_vec();
+
+        assert_eq!(
+            seal.verify_detailed().unwrap(),
+            VerificationResult::InvalidEntropyCertificate
+        );
+        let trusted = TrustedProviderKeys::new().with_key(
+            QrngSource::IdQuantiqueCloud,
+            qrng.public_key.as_bytes().to_vec(),
+        );
+        assert_eq!(
+            seal.entropy_certificate_status(&trusted).unwrap(),
+            Some(CertificateStatus::Invalid)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forged_entropy_certificate_detected() {
+        let qrng = AttestingQrng::new();
+        let (public_key, secret_key) = generate_keypair();
+
+        // Re-sign the seal itself over a certificate whose signature is broken,
+        // so only the certificate check can catch it.
+        let mut seal = SealBuilder::new(b"Attested".to_vec(), MediaType::Image)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        let mut certificate = seal.entropy_certificate.take().unwrap();
+        certificate.signature[0] ^= 0xFF;
+
+        let signable = SignablePayload {
+            capture_timestamp_utc: seal.capture_timestamp_utc,
+            capture_location: &seal.capture_location,
+            device_attestation: &seal.device_attestation,
+            qrng_entropy: &seal.qrng_entropy,
+            qrng_source: &seal.qrng_source,
+            entropy_timestamp: seal.entropy_timestamp,
+            qrng_conditioning: None,
+            raw_entropy_hash: None,
+            entropy_certificate: Some(&certificate),
+            qrng_policy: None,
+            content_hash: &seal.content_hash,
+            media_type: &seal.media_type,
+            threshold_policy: None,
+            not_before: None,
+            not_after: None,
+            capture_nonce: None,
+            liveness: None,
+            key_id: None,
+        };
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&signable, &mut bytes).unwrap();
+        seal.signature = mldsa65::sign(&bytes, secret_key.as_inner())
+            .as_bytes()
+            .to_vec();
+        seal.entropy_certificate = Some(certificate);
+
+        assert_eq!(
+            seal.verify_detailed().unwrap(),
+            VerificationResult::InvalidEntropyCertificate
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_canonical_is_stable_and_roundtrips() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+
+        let seal = SealBuilder::new(b"Canonical".to_vec(), MediaType::Audio)
+            .with_location("u4pruydqqvj".to_string())
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        let canonical = seal.to_json_canonical().expect("Failed to serialize");
+        assert!(!canonical.contains(' ') && !canonical.contains('\n'));
+        assert!(canonical.starts_with(r#"{"blockchain_anchor":null,"capture_location":"#));
+
+        // Re-serializing a parsed copy yields identical bytes
+        let restored: VeritasSeal = serde_json::from_str(&canonical).unwrap();
+        assert_eq!(restored.to_json_canonical().unwrap(), canonical);
+        assert!(restored.verify().unwrap());
+
+        let pretty = seal.to_json_pretty().unwrap();
+        let from_pretty: VeritasSeal = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(from_pretty.to_json_canonical().unwrap(), canonical);
+    }
+
+    #[tokio::test]
+    async fn test_from_json_validates_like_cbor() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"JSON".to_vec(), MediaType::Image)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+
+        let restored = VeritasSeal::from_json(seal.to_json_pretty().unwrap().as_bytes()).unwrap();
+        assert!(restored.verify().unwrap());
+
+        // serde alone accepts a seal with a truncated key; from_json does not
+        let mut value = serde_json::to_value(&seal).unwrap();
+        value["public_key"] = serde_json::json!([1, 2, 3]);
+        let truncated = serde_json::to_vec(&value).unwrap();
+        assert!(serde_json::from_slice::<VeritasSeal>(&truncated).is_ok());
+        assert!(matches!(
+            VeritasSeal::from_json(&truncated),
+            Err(VeritasError::InvalidSeal(_))
+        ));
+
+        assert!(matches!(
+            VeritasSeal::from_json(&vec![b' '; MAX_JSON_SEAL_SIZE + 1]),
+            Err(VeritasError::SealTooLarge { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_anchors_one_per_chain_outside_signature() {
+        let qrng = MockQrng::default();
+        let (public_key, secret_key) = generate_keypair();
+        let mut seal = SealBuilder::new(b"Anchored".to_vec(), MediaType::Image)
+            .build_secure(&qrng, &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal");
+        let digest = seal.anchor_digest();
+        let anchor = |chain: &str, tx_id: &str| BlockchainAnchor {
+            chain: chain.into(),
+            tx_id: tx_id.into(),
+            block_height: 0,
+            proof: None,
+            batch: None,
+        };
+
+        seal.add_anchor(anchor("solana-devnet", "first"));
+        seal.add_anchor(anchor("bitcoin", "https://calendar.example"));
+        seal.add_anchor(anchor("solana-devnet", "second"));
+        let anchors: Vec<_> = seal.anchors().map(|a| a.tx_id.as_str()).collect();
+        assert_eq!(anchors, ["second", "https://calendar.example"]);
+        assert_eq!(seal.additional_anchors.len(), 1);
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(restored.anchors().count(), 2);
+        assert_eq!(restored.anchor_digest(), digest);
+        assert!(restored.verify().unwrap());
+    }
+
+    #[test]
+    fn test_media_type_cbor_encoding_is_stable() {
+        let encode = |media_type: &MediaType| {
+            let mut bytes = Vec::new();
+            ciborium::into_writer(media_type, &mut bytes).unwrap();
+            bytes
+        };
+
+        // Unit variants stay plain text strings, as in seals written before v9
+        assert_eq!(encode(&MediaType::Image), b"\x65Image");
+        assert_eq!(encode(&MediaType::Generic), b"\x67Generic");
+
+        let custom = MediaType::Custom("model/gltf-binary".into());
+        let bytes = encode(&custom);
+        assert_eq!(&bytes[..8], b"\xa1\x66Custom");
+        let decoded: MediaType = ciborium::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, custom);
+    }
+
+    #[test]
+    fn test_media_type_from_mime() {
+        assert_eq!(MediaType::from_mime("image/png"), MediaType::Image);
+        assert_eq!(MediaType::from_mime("Video/MP4"), MediaType::Video);
+        assert_eq!(
+            MediaType::from_mime("audio/ogg; codecs=opus"),
+            MediaType::Audio
+        );
+        assert_eq!(MediaType::from_mime("application/pdf"), MediaType::Document);
+        assert_eq!(
+            MediaType::from_mime("application/octet-stream"),
+            MediaType::Generic
+        );
+        assert_eq!(
+            MediaType::from_mime("model/gltf-binary"),
+            MediaType::Custom("model/gltf-binary".into())
+        );
+        assert_eq!(MediaType::from_mime("not a mime"), MediaType::Generic);
+        assert_eq!(MediaType::from_mime("text/<script>"), MediaType::Generic);
+
+        assert_eq!(MediaType::parse("Generic"), Some(MediaType::Generic));
+        assert_eq!(
+            MediaType::parse("text/csv"),
+            Some(MediaType::Custom("text/csv".into()))
+        );
+        assert_eq!(MediaType::parse("hologram"), None);
+        assert_eq!(MediaType::Generic.to_string(), "generic");
+        assert_eq!(
+            MediaType::Custom("model/gltf-binary".into()).default_mime(),
+            "model/gltf-binary"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_custom_media_type_rejected() {
+        let (public_key, secret_key) = generate_keypair();
+        let result = SealBuilder::new(b"x".to_vec(), MediaType::Custom("bad type".into()))
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await;
+        assert!(matches!(result, Err(VeritasError::InvalidSeal(_))));
+
+        let seal = SealBuilder::new(b"x".to_vec(), MediaType::Custom("text/csv".into()))
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        let reloaded = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert_eq!(reloaded.media_type, MediaType::Custom("text/csv".into()));
+        assert!(reloaded.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_seal_from_streamed_content_hash() {
+        let content = vec![0x5Au8; 200 * 1024];
+        let content_hash = ContentHash::from_reader(content.as_slice()).unwrap();
+        assert_eq!(
+            content_hash.crypto_hash,
+            ContentHash::from_bytes(&content).crypto_hash
+        );
+
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::from_content_hash(content_hash, MediaType::Video)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        assert!(seal.verify().unwrap());
+        assert!(seal.verify_content(&content).unwrap().is_authentic());
+    }
+
+    #[tokio::test]
+    async fn test_verify_content_hash_from_chunks() {
+        let content = vec![0xA5u8; 100 * 1024];
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(content.clone(), MediaType::Video)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        let mut hasher = ContentHasher::new();
+        for chunk in content.chunks(7000) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.bytes_hashed(), content.len() as u64);
+        let content_hash = hasher.finalize().crypto_hash;
+        assert!(seal
+            .verify_content_hash(&content_hash)
+            .unwrap()
+            .is_authentic());
+
+        let mut hasher = ContentHasher::new();
+        hasher.update(&content[1..]);
+        let result = seal
+            .verify_content_hash(&hasher.finalize().crypto_hash)
+            .unwrap();
+        assert!(matches!(
+            result,
+            ContentVerificationResult::ContentModified {
+                tampered_segments: None,
+                ..
+            }
+        ));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_build_blocking_without_runtime() {
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"no tokio here".to_vec(), MediaType::Generic)
+            .build_blocking(&MockQrng::default(), &secret_key, &public_key)
+            .unwrap();
+        assert_eq!(seal.qrng_source, QrngSource::Mock);
+        assert_eq!(
+            seal.qrng_entropy,
+            MockQrng::default().get_entropy_sync().unwrap()
+        );
+        assert!(seal
+            .verify_content(b"no tokio here")
+            .unwrap()
+            .is_authentic());
+    }
 }
@@ -0,0 +1,178 @@
+//! Secret key storage that wipes key material.
+//!
+//! `pqcrypto` secret keys are plain `Copy` byte arrays: every move leaves a
+//! copy behind, and dropping one frees the bytes without clearing them.
+//! [`ZeroizingSecretKey`] moves the key to a heap page of its own once, wipes
+//! the value it was given, and zeroizes the page in place on drop. Signing
+//! borrows the key from there, so it is not copied again.
+//!
+//! With the `mlock` feature (Unix), the page is also locked in RAM so the key
+//! is never written to swap. Locking can fail when `RLIMIT_MEMLOCK` is too
+//! low; the key is then used unlocked and [`ZeroizingSecretKey::is_locked`]
+//! reports it.
+//!
+//! Buffers holding encoded keys (keypair files, PEM) belong in
+//! [`Zeroizing`], which wipes them on drop.
+
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::SecretKey as SecretKeyTrait;
+use zeroize::Zeroize;
+pub use zeroize::Zeroizing;
+
+use crate::error::{Result, VeritasError};
+
+/// Page-aligned storage so a key never shares a page with other data
+/// (which `munlock` would unlock along with it).
+#[repr(C, align(4096))]
+struct KeyPage(mldsa65::SecretKey);
+
+/// ML-DSA-65 secret key that zeroizes its memory on drop.
+///
+/// The key lives on the heap and is only ever borrowed. `as_inner` hands out
+/// a reference for signing; copying the key out of it (it is `Copy`) defeats
+/// the wrapper.
+pub struct ZeroizingSecretKey {
+    page: Box<KeyPage>,
+    locked: bool,
+}
+
+impl ZeroizingSecretKey {
+    /// Move an ML-DSA-65 secret key to zeroizing storage.
+    ///
+    /// `key` is wiped once copied; copies the caller made before are not.
+    pub fn new(mut key: mldsa65::SecretKey) -> Self {
+        let page = Box::new(KeyPage(key));
+        wipe(&mut key);
+        let locked = lock(&page);
+        Self { page, locked }
+    }
+
+    /// Decode a secret key from its byte encoding (e.g. a keypair file).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        mldsa65::SecretKey::from_bytes(bytes)
+            .map(Self::new)
+            .map_err(|_| VeritasError::SignatureError("invalid ML-DSA-65 secret key".into()))
+    }
+
+    /// Get a reference to the underlying secret key for signing.
+    pub fn as_inner(&self) -> &mldsa65::SecretKey {
+        &self.page.0
+    }
+
+    /// Byte encoding of the key, e.g. to write a keypair file.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.page.0.as_bytes()
+    }
+
+    /// Returns true if the key's memory is locked in RAM (`mlock` feature).
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+impl Drop for ZeroizingSecretKey {
+    fn drop(&mut self) {
+        wipe(&mut self.page.0);
+        if self.locked {
+            unlock(&self.page);
+        }
+    }
+}
+
+// Prevent Debug from leaking key material
+impl std::fmt::Debug for ZeroizingSecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZeroizingSecretKey")
+            .field("key", &"[REDACTED]")
+            .field("locked", &self.locked)
+            .finish()
+    }
+}
+
+/// Overwrite a key with zeros in place.
+pub(crate) fn wipe(key: &mut mldsa65::SecretKey) {
+    // SAFETY: `SecretKey` is a bare byte array, so any bytes form a valid
+    // value, and the slice covers exactly the key we borrow mutably.
+    let bytes = unsafe {
+        std::slice::from_raw_parts_mut(
+            (key as *mut mldsa65::SecretKey).cast::<u8>(),
+            std::mem::size_of::<mldsa65::SecretKey>(),
+        )
+    };
+    bytes.zeroize();
+}
+
+#[cfg(all(feature = "mlock", unix))]
+fn lock(page: &KeyPage) -> bool {
+    // SAFETY: the range is the page's own allocation, alive for the call.
+    let locked = unsafe {
+        libc::mlock(
+            (page as *const KeyPage).cast(),
+            std::mem::size_of::<KeyPage>(),
+        )
+    } == 0;
+    if !locked {
+        tracing::warn!(
+            error = %std::io::Error::last_os_error(),
+            "Failed to lock secret key in memory (check RLIMIT_MEMLOCK)"
+        );
+    }
+    locked
+}
+
+#[cfg(not(all(feature = "mlock", unix)))]
+fn lock(_page: &KeyPage) -> bool {
+    false
+}
+
+#[cfg(all(feature = "mlock", unix))]
+fn unlock(page: &KeyPage) {
+    // SAFETY: same range as locked in `lock`, still allocated.
+    unsafe {
+        libc::munlock(
+            (page as *const KeyPage).cast(),
+            std::mem::size_of::<KeyPage>(),
+        );
+    }
+}
+
+#[cfg(not(all(feature = "mlock", unix)))]
+fn unlock(_page: &KeyPage) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wipe_clears_key_bytes() {
+        let (_, mut key) = mldsa65::keypair();
+        assert!(key.as_bytes().iter().any(|&b| b != 0));
+
+        wipe(&mut key);
+        assert!(key.as_bytes().iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_key_round_trips_through_bytes() {
+        let (public_key, raw) = mldsa65::keypair();
+        let secret_key = ZeroizingSecretKey::from_bytes(raw.as_bytes()).unwrap();
+        assert_eq!(secret_key.as_bytes(), raw.as_bytes());
+
+        let signed = mldsa65::sign(b"payload", secret_key.as_inner());
+        assert_eq!(mldsa65::open(&signed, &public_key).unwrap(), b"payload");
+
+        assert!(ZeroizingSecretKey::from_bytes(&raw.as_bytes()[1..]).is_err());
+        assert!(!format!("{secret_key:?}").contains(&hex::encode(&raw.as_bytes()[..8])));
+    }
+
+    #[test]
+    fn test_key_gets_its_own_page() {
+        let secret_key = ZeroizingSecretKey::new(mldsa65::keypair().1);
+        let address = secret_key.as_inner() as *const mldsa65::SecretKey as usize;
+        assert_eq!(address % 4096, 0);
+        assert_eq!(std::mem::size_of::<KeyPage>(), 4096);
+        if cfg!(not(all(feature = "mlock", unix))) {
+            assert!(!secret_key.is_locked());
+        }
+    }
+}
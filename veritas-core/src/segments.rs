@@ -0,0 +1,566 @@
+//! Segment-level sealing for long recordings.
+//!
+//! Bodycam and surveillance footage is often shared as short excerpts. A seal
+//! built with [`SealBuilder::with_segments`] splits the content into
+//! fixed-size segments, hashes each one into a Merkle tree and records the
+//! root in the signed [`ContentHash`]. An extracted clip (a run of whole
+//! segments) can then be checked against the original seal with a
+//! [`ClipProof`] carrying the sibling hashes, without the rest of the file.
+//!
+//! Segments are cut by byte count. For constant-bitrate recordings the
+//! optional `segment_duration_ms` maps segment indices back to timestamps.
+//!
+//! The manifest also carries a short digest of every segment, so verifying a
+//! tampered copy of the full recording reports which segments were altered
+//! rather than only a hash mismatch. An edit that changes the length of the
+//! content shifts every later segment, and all of them are reported.
+//!
+//! [`SealBuilder::with_segments`]: crate::SealBuilder::with_segments
+//! [`ContentHash`]: crate::ContentHash
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::constant_time::ct_eq;
+use crate::error::{Result, VeritasError};
+use crate::merkle::{self, ProofStep};
+use crate::seal::{VerificationResult, VeritasSeal};
+
+/// Domain separation tag for segment leaves.
+const SEGMENT_DOMAIN: &str = "veritas-segment-v1";
+
+/// Default segment size (1 MiB).
+pub const DEFAULT_SEGMENT_SIZE: u32 = 1024 * 1024;
+
+/// Recordings with more segments than this are sealed without per-segment
+/// digests, keeping the seal under [`crate::MAX_SEAL_SIZE`].
+pub const MAX_INDEXED_SEGMENTS: u32 = 4096;
+
+/// How content is cut into segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentLayout {
+    /// Segment size in bytes (the last segment may be shorter)
+    pub segment_size: u32,
+    /// Nominal playback duration of one segment, if known
+    pub segment_duration_ms: Option<u32>,
+}
+
+impl Default for SegmentLayout {
+    fn default() -> Self {
+        Self {
+            segment_size: DEFAULT_SEGMENT_SIZE,
+            segment_duration_ms: None,
+        }
+    }
+}
+
+impl SegmentLayout {
+    /// Fixed-size segments without timing information.
+    pub fn new(segment_size: u32) -> Self {
+        Self {
+            segment_size,
+            segment_duration_ms: None,
+        }
+    }
+
+    /// Record the playback duration covered by each segment.
+    pub fn with_duration_ms(mut self, duration_ms: u32) -> Self {
+        self.segment_duration_ms = Some(duration_ms);
+        self
+    }
+}
+
+/// Signed description of a segmented recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentManifest {
+    /// Segment size in bytes
+    pub segment_size: u32,
+    /// Nominal playback duration of one segment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment_duration_ms: Option<u32>,
+    /// Number of segments
+    pub segment_count: u32,
+    /// Total content size in bytes
+    pub total_size: u64,
+    /// Merkle root over the segment hashes
+    pub root: [u8; 32],
+    /// First 8 bytes (big-endian) of each segment hash, for tamper
+    /// localization; empty above [`MAX_INDEXED_SEGMENTS`] segments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub digests: Vec<u64>,
+}
+
+impl SegmentManifest {
+    /// Segment the content and compute the Merkle root.
+    pub fn compute(content: &[u8], layout: SegmentLayout) -> Result<Self> {
+        if layout.segment_size == 0 {
+            return Err(VeritasError::InvalidSeal(
+                "segment size must be non-zero".into(),
+            ));
+        }
+        if content.is_empty() {
+            return Err(VeritasError::InvalidSeal(
+                "cannot segment empty content".into(),
+            ));
+        }
+
+        let leaves = segment_leaves(content, layout.segment_size);
+        Self::from_leaves(layout, content.len() as u64, &leaves)
+    }
+
+    /// Manifest for already hashed segments.
+    pub(crate) fn from_leaves(
+        layout: SegmentLayout,
+        total_size: u64,
+        leaves: &[[u8; 32]],
+    ) -> Result<Self> {
+        let segment_count = u32::try_from(leaves.len())
+            .map_err(|_| VeritasError::InvalidSeal("too many segments".into()))?;
+        let digests = if segment_count <= MAX_INDEXED_SEGMENTS {
+            leaves.iter().map(short_digest).collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            segment_size: layout.segment_size,
+            segment_duration_ms: layout.segment_duration_ms,
+            segment_count,
+            total_size,
+            root: merkle::root(leaves),
+            digests,
+        })
+    }
+
+    /// Byte range of a segment in the original content.
+    pub fn byte_range(&self, index: u32) -> Option<Range<u64>> {
+        if index >= self.segment_count {
+            return None;
+        }
+        let start = u64::from(index) * u64::from(self.segment_size);
+        let end = (start + u64::from(self.segment_size)).min(self.total_size);
+        Some(start..end)
+    }
+
+    /// Playback time range of a segment (ms from start), if durations are known.
+    pub fn time_range_ms(&self, index: u32) -> Option<Range<u64>> {
+        let duration = u64::from(self.segment_duration_ms?);
+        if index >= self.segment_count {
+            return None;
+        }
+        let start = u64::from(index) * duration;
+        Some(start..start + duration)
+    }
+
+    /// Segments of `content` that differ from the sealed recording.
+    ///
+    /// Segments missing from `content` or appended beyond the sealed length
+    /// are reported too. Returns `None` if the manifest has no per-segment
+    /// digests.
+    pub fn tampered_segments(&self, content: &[u8]) -> Option<Vec<TamperedSegment>> {
+        if self.digests.is_empty() {
+            return None;
+        }
+
+        let actual: Vec<u64> = segment_leaves(content, self.segment_size)
+            .iter()
+            .map(short_digest)
+            .collect();
+        let size = u64::from(self.segment_size);
+        let end_of_content = self.total_size.max(content.len() as u64);
+
+        let tampered = (0..self.digests.len().max(actual.len()))
+            .filter(|&i| self.digests.get(i) != actual.get(i))
+            .map(|i| {
+                let index = i as u32;
+                let start = u64::from(index) * size;
+                TamperedSegment {
+                    index,
+                    byte_range: start..(start + size).min(end_of_content),
+                    time_range_ms: self.segment_duration_ms.map(|duration| {
+                        let start = u64::from(index) * u64::from(duration);
+                        start..start + u64::from(duration)
+                    }),
+                }
+            })
+            .collect();
+        Some(tampered)
+    }
+
+    /// Check the counts are consistent with the sizes.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.segment_size == 0 || self.total_size == 0 {
+            return Err(VeritasError::InvalidSeal(
+                "segment manifest has zero size".into(),
+            ));
+        }
+        let expected = self.total_size.div_ceil(u64::from(self.segment_size));
+        if u64::from(self.segment_count) != expected {
+            return Err(VeritasError::InvalidSeal(format!(
+                "segment manifest lists {} segments, sizes imply {expected}",
+                self.segment_count
+            )));
+        }
+        if !self.digests.is_empty() && self.digests.len() != self.segment_count as usize {
+            return Err(VeritasError::InvalidSeal(format!(
+                "segment manifest has {} digests for {} segments",
+                self.digests.len(),
+                self.segment_count
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A segment that does not match the sealed recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TamperedSegment {
+    /// Segment index
+    pub index: u32,
+    /// Byte range of the segment
+    pub byte_range: Range<u64>,
+    /// Playback time range (ms from start), if segment durations are known
+    pub time_range_ms: Option<Range<u64>>,
+}
+
+/// Proof that a clip is a run of segments from a sealed recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipProof {
+    /// Index of the first segment in the clip
+    pub first_segment: u32,
+    /// Merkle path for each segment of the clip, in order
+    pub paths: Vec<Vec<ProofStep>>,
+}
+
+impl ClipProof {
+    /// Segment indices covered by the clip.
+    pub fn segments(&self) -> Range<u32> {
+        let count = u32::try_from(self.paths.len()).unwrap_or(u32::MAX);
+        self.first_segment..self.first_segment.saturating_add(count)
+    }
+}
+
+/// Result of verifying a clip against a segmented seal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipVerificationResult {
+    /// Seal is valid and every segment of the clip matches
+    Authentic,
+    /// Seal signature verification failed
+    SignatureFailed(VerificationResult),
+    /// The listed segments do not match the sealed recording
+    SegmentsModified { segments: Vec<u32> },
+}
+
+impl ClipVerificationResult {
+    /// Returns true if the clip is authentic.
+    pub fn is_authentic(&self) -> bool {
+        matches!(self, Self::Authentic)
+    }
+}
+
+impl VeritasSeal {
+    /// Returns true if the seal was built with segment hashing.
+    pub fn is_segmented(&self) -> bool {
+        self.content_hash.segments.is_some()
+    }
+
+    /// Build a proof for the clip made of `segments` of the original content.
+    ///
+    /// `content` must be the full recording the seal was made over; the clip
+    /// itself is `content[manifest.byte_range(first)..byte_range(last)]`.
+    pub fn clip_proof(&self, content: &[u8], segments: Range<u32>) -> Result<ClipProof> {
+        let manifest = self.segment_manifest()?;
+        if segments.is_empty() || segments.end > manifest.segment_count {
+            return Err(VeritasError::InvalidSeal(format!(
+                "segment range {segments:?} outside 0..{}",
+                manifest.segment_count
+            )));
+        }
+
+        let leaves = segment_leaves(content, manifest.segment_size);
+        if leaves.is_empty() || !ct_eq(&merkle::root(&leaves), &manifest.root) {
+            return Err(VeritasError::InvalidSeal(
+                "content does not match the sealed segment tree".into(),
+            ));
+        }
+
+        Ok(ClipProof {
+            first_segment: segments.start,
+            paths: segments
+                .map(|index| merkle::path(&leaves, index as usize))
+                .collect(),
+        })
+    }
+
+    /// Extract the bytes of a clip from the original content.
+    pub fn clip_bytes<'a>(&self, content: &'a [u8], segments: Range<u32>) -> Result<&'a [u8]> {
+        let manifest = self.segment_manifest()?;
+        let range = match (
+            manifest.byte_range(segments.start),
+            segments
+                .end
+                .checked_sub(1)
+                .and_then(|last| manifest.byte_range(last)),
+        ) {
+            (Some(first), Some(last)) if first.start < last.end => first.start..last.end,
+            _ => {
+                return Err(VeritasError::InvalidSeal(format!(
+                    "segment range {segments:?} outside 0..{}",
+                    manifest.segment_count
+                )))
+            }
+        };
+        content
+            .get(range.start as usize..range.end as usize)
+            .ok_or_else(|| VeritasError::InvalidSeal("content shorter than the seal".into()))
+    }
+
+    /// Verify the seal and that `clip` is the run of segments named by `proof`.
+    pub fn verify_clip(&self, clip: &[u8], proof: &ClipProof) -> Result<ClipVerificationResult> {
+        let sig_result = self.verify_detailed()?;
+        if !sig_result.is_valid() {
+            return Ok(ClipVerificationResult::SignatureFailed(sig_result));
+        }
+
+        let manifest = self.segment_manifest()?;
+        let range = proof.segments();
+        if proof.paths.is_empty() || range.end > manifest.segment_count {
+            return Err(VeritasError::InvalidSeal(format!(
+                "clip proof covers {range:?}, seal has {} segments",
+                manifest.segment_count
+            )));
+        }
+
+        let mut modified = Vec::new();
+        let mut offset = 0usize;
+        for (index, path) in range.clone().zip(&proof.paths) {
+            let expected_len = manifest
+                .byte_range(index)
+                .map_or(0, |r| (r.end - r.start) as usize);
+            let end = offset.saturating_add(expected_len);
+            let matches = clip.get(offset..end).is_some_and(|segment| {
+                ct_eq(
+                    &merkle::root_from_path(leaf_hash(index, segment), path),
+                    &manifest.root,
+                )
+            });
+            if !matches {
+                modified.push(index);
+            }
+            offset = end;
+        }
+        // Trailing bytes beyond the last segment are an alteration of that segment
+        if offset < clip.len() && !modified.contains(&(range.end - 1)) {
+            modified.push(range.end - 1);
+        }
+
+        if modified.is_empty() {
+            Ok(ClipVerificationResult::Authentic)
+        } else {
+            Ok(ClipVerificationResult::SegmentsModified { segments: modified })
+        }
+    }
+
+    fn segment_manifest(&self) -> Result<&SegmentManifest> {
+        self.content_hash
+            .segments
+            .as_ref()
+            .ok_or_else(|| VeritasError::InvalidSeal("seal is not segmented".into()))
+    }
+}
+
+pub(crate) fn segment_leaves(content: &[u8], segment_size: u32) -> Vec<[u8; 32]> {
+    // Manifests deserialized without validation may carry a zero size
+    if segment_size == 0 {
+        return Vec::new();
+    }
+    content
+        .chunks(segment_size as usize)
+        .enumerate()
+        .map(|(index, segment)| leaf_hash(index as u32, segment))
+        .collect()
+}
+
+fn short_digest(leaf: &[u8; 32]) -> u64 {
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&leaf[..8]);
+    u64::from_be_bytes(prefix)
+}
+
+pub(crate) fn leaf_hash(index: u32, segment: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update([0x00]);
+    hasher.update(SEGMENT_DOMAIN);
+    hasher.update(index.to_be_bytes());
+    hasher.update(segment);
+    hasher.finalize().into()
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, ContentVerificationResult, MediaType};
+    use crate::SealBuilder;
+
+    /// 10 segments of 64 bytes, the last one 40 bytes long.
+    fn recording() -> Vec<u8> {
+        (0..616u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    async fn segmented_seal(content: &[u8]) -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(content.to_vec(), MediaType::Video)
+            .with_segments(SegmentLayout::new(64).with_duration_ms(2_000))
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal")
+    }
+
+    #[tokio::test]
+    async fn test_segmented_seal_verifies_full_content() {
+        let content = recording();
+        let seal = segmented_seal(&content).await;
+
+        assert!(seal.is_segmented());
+        let manifest = seal.content_hash.segments.as_ref().unwrap();
+        assert_eq!(manifest.segment_count, 10);
+        assert_eq!(manifest.byte_range(9), Some(576..616));
+        assert_eq!(manifest.time_range_ms(3), Some(6_000..8_000));
+        assert!(seal.verify_content(&content).unwrap().is_authentic());
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_clip_verifies_against_original_seal() {
+        let content = recording();
+        let seal = segmented_seal(&content).await;
+
+        for segments in [0..1, 2..5, 7..10, 0..10] {
+            let proof = seal.clip_proof(&content, segments.clone()).unwrap();
+            let clip = seal.clip_bytes(&content, segments).unwrap();
+            assert!(seal.verify_clip(clip, &proof).unwrap().is_authentic());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tampered_clip_reports_segment() {
+        let content = recording();
+        let seal = segmented_seal(&content).await;
+        let proof = seal.clip_proof(&content, 2..5).unwrap();
+        let mut clip = seal.clip_bytes(&content, 2..5).unwrap().to_vec();
+
+        clip[64 + 10] ^= 0xff;
+        assert_eq!(
+            seal.verify_clip(&clip, &proof).unwrap(),
+            ClipVerificationResult::SegmentsModified { segments: vec![3] }
+        );
+
+        // A clip passed off as coming from elsewhere in the recording fails
+        let mut shifted = proof.clone();
+        shifted.first_segment = 3;
+        let clip = seal.clip_bytes(&content, 2..5).unwrap();
+        assert!(!seal.verify_clip(clip, &shifted).unwrap().is_authentic());
+    }
+
+    #[tokio::test]
+    async fn test_truncated_clip_detected() {
+        let content = recording();
+        let seal = segmented_seal(&content).await;
+        let proof = seal.clip_proof(&content, 7..10).unwrap();
+        let clip = seal.clip_bytes(&content, 7..10).unwrap();
+
+        assert_eq!(
+            seal.verify_clip(&clip[..clip.len() - 1], &proof).unwrap(),
+            ClipVerificationResult::SegmentsModified { segments: vec![9] }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clip_proof_requires_matching_content() {
+        let content = recording();
+        let seal = segmented_seal(&content).await;
+
+        let mut other = content.clone();
+        other[0] ^= 1;
+        assert!(seal.clip_proof(&other, 0..1).is_err());
+        assert!(seal.clip_proof(&content, 5..11).is_err());
+        assert!(seal.clip_proof(&content, 4..4).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_content_localizes_tampering() {
+        let content = recording();
+        let seal = segmented_seal(&content).await;
+
+        let mut tampered = content.clone();
+        tampered[64 * 3 + 5] ^= 0xff;
+        tampered[64 * 7] ^= 0xff;
+        match seal.verify_content(&tampered).unwrap() {
+            ContentVerificationResult::ContentModified {
+                tampered_segments, ..
+            } => {
+                let segments = tampered_segments.unwrap();
+                let indices: Vec<u32> = segments.iter().map(|s| s.index).collect();
+                assert_eq!(indices, vec![3, 7]);
+                assert_eq!(segments[0].byte_range, 192..256);
+                assert_eq!(segments[0].time_range_ms, Some(6_000..8_000));
+            }
+            other => panic!("expected ContentModified, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_length_changes_reported() {
+        let content = recording();
+        let manifest = SegmentManifest::compute(&content, SegmentLayout::new(64)).unwrap();
+
+        // Truncation drops the last segment, shortening changes it
+        let truncated = manifest.tampered_segments(&content[..600]).unwrap();
+        assert_eq!(truncated.len(), 1);
+        assert_eq!(truncated[0].index, 9);
+        let cut = manifest.tampered_segments(&content[..500]).unwrap();
+        assert_eq!(
+            cut.iter().map(|s| s.index).collect::<Vec<_>>(),
+            vec![7, 8, 9]
+        );
+
+        // Appended data shows up as extra segments past the sealed length
+        let mut extended = content.clone();
+        extended.extend_from_slice(&[0; 50]);
+        let appended = manifest.tampered_segments(&extended).unwrap();
+        assert_eq!(
+            appended.iter().map(|s| s.index).collect::<Vec<_>>(),
+            vec![9, 10]
+        );
+        assert_eq!(appended[1].byte_range, 640..666);
+        assert!(appended[1].time_range_ms.is_none());
+    }
+
+    #[test]
+    fn test_manifest_validation() {
+        let manifest = SegmentManifest::compute(&recording(), SegmentLayout::new(64)).unwrap();
+        assert!(manifest.validate().is_ok());
+
+        let mut wrong_count = manifest.clone();
+        wrong_count.segment_count = 9;
+        assert!(wrong_count.validate().is_err());
+
+        let mut missing_digest = manifest.clone();
+        missing_digest.digests.pop();
+        assert!(missing_digest.validate().is_err());
+
+        assert!(SegmentManifest::compute(b"", SegmentLayout::default()).is_err());
+        assert!(SegmentManifest::compute(b"data", SegmentLayout::new(0)).is_err());
+
+        // Unvalidated manifests must not panic when checking content
+        let mut zero_size = manifest.clone();
+        zero_size.segment_size = 0;
+        assert!(zero_size.validate().is_err());
+        assert_eq!(zero_size.tampered_segments(&recording()).unwrap().len(), 10);
+    }
+}
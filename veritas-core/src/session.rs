@@ -0,0 +1,411 @@
+//! Streaming seal sessions for live recordings.
+//!
+//! A [`SealSession`] seals content that arrives in chunks (a live broadcast,
+//! a bodycam upload in progress). Chunks are cut into segments as they
+//! arrive and folded into the same segment tree used by
+//! [`crate::segments`], so the session never holds more than one segment in
+//! memory.
+//!
+//! While the stream is running the session emits signed
+//! [`SessionCheckpoint`]s over the segments received so far. A viewer can
+//! check a checkpoint long before the recording ends; once it does end,
+//! [`SealSession::finish`] produces an ordinary segmented seal signed by the
+//! same key.
+
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "network")]
+use sha3::{Digest, Sha3_256};
+
+use crate::constant_time::ct_eq;
+use crate::error::{Result, VeritasError};
+use crate::merkle;
+#[cfg(feature = "network")]
+use crate::qrng::QuantumEntropySource;
+#[cfg(feature = "network")]
+use crate::seal::{ContentHash, MediaType, SealBuilder, VeritasSeal};
+#[cfg(feature = "network")]
+use crate::secret::ZeroizingSecretKey;
+use crate::segments::segment_leaves;
+#[cfg(feature = "network")]
+use crate::segments::{leaf_hash, SegmentLayout, SegmentManifest};
+
+/// Domain separation tag for checkpoint payloads.
+const CHECKPOINT_DOMAIN: &str = "veritas-session-checkpoint-v1";
+
+/// Signed statement about the segments a session has received so far.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionCheckpoint {
+    /// Random identifier shared by all checkpoints of a session
+    pub session_id: [u8; 16],
+    /// Checkpoint number within the session, starting at 0
+    pub sequence: u32,
+    /// When the checkpoint was signed (Unix timestamp ms)
+    pub timestamp: u64,
+    /// Segment size in bytes
+    pub segment_size: u32,
+    /// Number of complete segments covered
+    pub segment_count: u32,
+    /// Merkle root over the covered segments
+    pub root: [u8; 32],
+    /// Session ML-DSA-65 public key (also signs the final seal)
+    pub public_key: Vec<u8>,
+    /// Detached ML-DSA-65 signature over the checkpoint payload
+    pub signature: Vec<u8>,
+}
+
+/// Signed portion of a checkpoint.
+#[derive(Serialize)]
+struct CheckpointPayload<'a> {
+    domain: &'static str,
+    session_id: &'a [u8; 16],
+    sequence: u32,
+    timestamp: u64,
+    segment_size: u32,
+    segment_count: u32,
+    root: &'a [u8; 32],
+}
+
+impl SessionCheckpoint {
+    /// Verify the checkpoint signature.
+    ///
+    /// Returns `Ok(false)` for malformed keys or signatures.
+    pub fn verify(&self) -> Result<bool> {
+        let Ok(public_key) = mldsa65::PublicKey::from_bytes(&self.public_key) else {
+            return Ok(false);
+        };
+        let Ok(signature) = mldsa65::DetachedSignature::from_bytes(&self.signature) else {
+            return Ok(false);
+        };
+        Ok(
+            mldsa65::verify_detached_signature(&signature, &self.payload_bytes()?, &public_key)
+                .is_ok(),
+        )
+    }
+
+    /// Returns true if `content` starts with exactly the segments this
+    /// checkpoint covers (e.g. the final recording, or a partial download).
+    pub fn covers(&self, content: &[u8]) -> bool {
+        let covered = u64::from(self.segment_count) * u64::from(self.segment_size);
+        if self.segment_count == 0 || (content.len() as u64) < covered {
+            return false;
+        }
+        let leaves = segment_leaves(&content[..covered as usize], self.segment_size);
+        ct_eq(&merkle::root(&leaves), &self.root)
+    }
+
+    /// Returns true if the final seal was produced by the same session key.
+    #[cfg(feature = "network")]
+    pub fn same_signer(&self, seal: &VeritasSeal) -> bool {
+        self.public_key == seal.public_key
+    }
+
+    fn payload_bytes(&self) -> Result<Vec<u8>> {
+        let payload = CheckpointPayload {
+            domain: CHECKPOINT_DOMAIN,
+            session_id: &self.session_id,
+            sequence: self.sequence,
+            timestamp: self.timestamp,
+            segment_size: self.segment_size,
+            segment_count: self.segment_count,
+            root: &self.root,
+        };
+        let mut bytes = Vec::with_capacity(160);
+        ciborium::into_writer(&payload, &mut bytes)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// CBOR encoding of the checkpoint.
+    pub fn to_cbor(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(6 * 1024);
+        ciborium::into_writer(self, &mut bytes)
+            .map_err(|e| VeritasError::SerializationError(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Decode a checkpoint from CBOR.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        ciborium::from_reader(bytes).map_err(|e| VeritasError::SerializationError(e.to_string()))
+    }
+}
+
+/// Incremental seal over content received in chunks.
+#[cfg(feature = "network")]
+pub struct SealSession {
+    session_id: [u8; 16],
+    media_type: MediaType,
+    layout: SegmentLayout,
+    checkpoint_every: u32,
+    content_hasher: Sha3_256,
+    pending: Vec<u8>,
+    leaves: Vec<[u8; 32]>,
+    total_size: u64,
+    next_sequence: u32,
+    public_key: mldsa65::PublicKey,
+    secret_key: ZeroizingSecretKey,
+}
+
+#[cfg(feature = "network")]
+impl SealSession {
+    /// Start a session that signs checkpoints and the final seal with the
+    /// given key.
+    pub fn new(
+        media_type: MediaType,
+        layout: SegmentLayout,
+        public_key: mldsa65::PublicKey,
+        secret_key: ZeroizingSecretKey,
+    ) -> Result<Self> {
+        if layout.segment_size == 0 {
+            return Err(VeritasError::InvalidSeal(
+                "segment size must be non-zero".into(),
+            ));
+        }
+
+        let mut session_id = [0u8; 16];
+        getrandom::fill(&mut session_id).map_err(|e| {
+            VeritasError::SerializationError(format!("failed to generate session id: {e}"))
+        })?;
+
+        Ok(Self {
+            session_id,
+            media_type,
+            layout,
+            checkpoint_every: 0,
+            content_hasher: Sha3_256::new(),
+            pending: Vec::new(),
+            leaves: Vec::new(),
+            total_size: 0,
+            next_sequence: 0,
+            public_key,
+            secret_key,
+        })
+    }
+
+    /// Emit a checkpoint from [`append`](Self::append) every `segments`
+    /// completed segments (0 disables automatic checkpoints).
+    pub fn with_checkpoint_every(mut self, segments: u32) -> Self {
+        self.checkpoint_every = segments;
+        self
+    }
+
+    /// Random session identifier (also stored in every checkpoint).
+    pub fn session_id(&self) -> [u8; 16] {
+        self.session_id
+    }
+
+    /// Total bytes appended so far.
+    pub fn bytes_received(&self) -> u64 {
+        self.total_size
+    }
+
+    /// Number of complete segments received so far.
+    pub fn segments_completed(&self) -> u32 {
+        self.leaves.len() as u32
+    }
+
+    /// Feed the next chunk of the stream.
+    ///
+    /// Returns the checkpoints that became due while processing the chunk
+    /// (usually none or one).
+    pub fn append(&mut self, mut chunk: &[u8]) -> Result<Vec<SessionCheckpoint>> {
+        let segment_size = self.layout.segment_size as usize;
+        let mut checkpoints = Vec::new();
+
+        self.content_hasher.update(chunk);
+        self.total_size += chunk.len() as u64;
+
+        while !chunk.is_empty() {
+            let take = (segment_size - self.pending.len()).min(chunk.len());
+            self.pending.extend_from_slice(&chunk[..take]);
+            chunk = &chunk[take..];
+
+            if self.pending.len() == segment_size {
+                self.complete_segment()?;
+                if self.checkpoint_every > 0
+                    && self
+                        .segments_completed()
+                        .is_multiple_of(self.checkpoint_every)
+                {
+                    checkpoints.push(self.checkpoint()?);
+                }
+            }
+        }
+
+        Ok(checkpoints)
+    }
+
+    /// Sign a checkpoint over every complete segment received so far.
+    pub fn checkpoint(&mut self) -> Result<SessionCheckpoint> {
+        if self.leaves.is_empty() {
+            return Err(VeritasError::InvalidSeal(
+                "no complete segment to checkpoint yet".into(),
+            ));
+        }
+
+        let mut checkpoint = SessionCheckpoint {
+            session_id: self.session_id,
+            sequence: self.next_sequence,
+            timestamp: now_ms()?,
+            segment_size: self.layout.segment_size,
+            segment_count: self.segments_completed(),
+            root: merkle::root(&self.leaves),
+            public_key: self.public_key.as_bytes().to_vec(),
+            signature: Vec::new(),
+        };
+        let signature =
+            mldsa65::detached_sign(&checkpoint.payload_bytes()?, self.secret_key.as_inner());
+        checkpoint.signature = signature.as_bytes().to_vec();
+
+        self.next_sequence += 1;
+        Ok(checkpoint)
+    }
+
+    /// Build the final segmented seal over everything appended so far.
+    ///
+    /// The seal is signed by the session key, so it can be matched to the
+    /// checkpoints with [`SessionCheckpoint::same_signer`]. The session is
+    /// left untouched, so a failed entropy fetch can simply be retried.
+    pub async fn finish<Q: QuantumEntropySource + ?Sized>(&self, qrng: &Q) -> Result<VeritasSeal> {
+        if self.total_size == 0 {
+            return Err(VeritasError::InvalidSeal(
+                "cannot seal an empty stream".into(),
+            ));
+        }
+
+        // The trailing partial segment becomes the (shorter) last leaf
+        let mut leaves = self.leaves.clone();
+        if !self.pending.is_empty() {
+            let index = u32::try_from(leaves.len())
+                .map_err(|_| VeritasError::InvalidSeal("too many segments".into()))?;
+            leaves.push(leaf_hash(index, &self.pending));
+        }
+
+        let content_hash = ContentHash {
+            crypto_hash: self.content_hasher.clone().finalize().into(),
+            perceptual_hash: None,
+            segments: Some(SegmentManifest::from_leaves(
+                self.layout,
+                self.total_size,
+                &leaves,
+            )?),
+            canonical_hash: None,
+            region_hashes: None,
+        };
+
+        SealBuilder::new(Vec::new(), self.media_type.clone())
+            .build_with_content_hash(
+                content_hash,
+                qrng,
+                self.secret_key.as_inner(),
+                &self.public_key,
+            )
+            .await
+    }
+
+    fn complete_segment(&mut self) -> Result<()> {
+        let index = u32::try_from(self.leaves.len())
+            .map_err(|_| VeritasError::InvalidSeal("too many segments".into()))?;
+        self.leaves.push(leaf_hash(index, &self.pending));
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "network")]
+fn now_ms() -> Result<u64> {
+    u64::try_from(chrono::Utc::now().timestamp_millis()).map_err(|_| {
+        VeritasError::InvalidTimestamp {
+            reason: "timestamp before Unix epoch".into(),
+        }
+    })
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::generate_keypair;
+
+    fn stream() -> Vec<u8> {
+        (0..1000u32).map(|i| (i * 7 % 256) as u8).collect()
+    }
+
+    fn session() -> SealSession {
+        let (public_key, secret_key) = generate_keypair();
+        SealSession::new(
+            MediaType::Video,
+            SegmentLayout::new(100),
+            public_key,
+            secret_key,
+        )
+        .unwrap()
+        .with_checkpoint_every(3)
+    }
+
+    #[tokio::test]
+    async fn test_session_matches_one_shot_seal() {
+        let content = stream();
+        let mut session = session();
+
+        let mut checkpoints = Vec::new();
+        for chunk in content.chunks(37) {
+            checkpoints.extend(session.append(chunk).unwrap());
+        }
+        assert_eq!(session.bytes_received(), 1000);
+        assert_eq!(session.segments_completed(), 10);
+
+        // Checkpoints after segments 3, 6 and 9
+        let counts: Vec<u32> = checkpoints.iter().map(|c| c.segment_count).collect();
+        assert_eq!(counts, vec![3, 6, 9]);
+
+        let seal = session.finish(&MockQrng::default()).await.unwrap();
+        assert!(seal.verify().unwrap());
+        assert!(seal.verify_content(&content).unwrap().is_authentic());
+
+        // Same tree as sealing the whole recording at once
+        let expected = SegmentManifest::compute(&content, SegmentLayout::new(100)).unwrap();
+        assert_eq!(seal.content_hash.segments.as_ref(), Some(&expected));
+
+        for (sequence, checkpoint) in checkpoints.iter().enumerate() {
+            assert_eq!(checkpoint.sequence, sequence as u32);
+            assert!(checkpoint.verify().unwrap());
+            assert!(checkpoint.covers(&content));
+            assert!(checkpoint.same_signer(&seal));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_detects_tampering() {
+        let content = stream();
+        let mut session = session();
+        session.append(&content[..450]).unwrap();
+        let checkpoint = session.checkpoint().unwrap();
+        assert_eq!(checkpoint.segment_count, 4);
+
+        let mut altered = content.clone();
+        altered[120] ^= 1;
+        assert!(!checkpoint.covers(&altered));
+        assert!(!checkpoint.covers(&content[..350]));
+
+        let mut forged = checkpoint.clone();
+        forged.segment_count = 3;
+        assert!(!forged.verify().unwrap());
+
+        let restored = SessionCheckpoint::from_cbor(&checkpoint.to_cbor().unwrap()).unwrap();
+        assert_eq!(restored, checkpoint);
+    }
+
+    #[tokio::test]
+    async fn test_empty_session_rejected() {
+        let mut session = session();
+        assert!(session.checkpoint().is_err());
+        assert!(session.finish(&MockQrng::default()).await.is_err());
+
+        // A partial first segment is enough for the final seal
+        session.append(b"short").unwrap();
+        let seal = session.finish(&MockQrng::default()).await.unwrap();
+        assert!(seal.verify_content(b"short").unwrap().is_authentic());
+    }
+}
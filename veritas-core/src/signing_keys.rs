@@ -0,0 +1,276 @@
+//! Signing-key registry entries and key rotation.
+//!
+//! Seals embed the public key that signed them, so any seal verifies on its
+//! own, but that only proves *some* key signed it. A signer using long-lived
+//! keys publishes them in a registry ([`SigningKey`]: ID, public key,
+//! validity, status) and writes the key's ID into each seal (`key_id`,
+//! seal version 16, covered by the signature). Verifiers resolve the ID
+//! against the registry with [`VeritasSeal::resolve_signing_key`] to learn
+//! whether the signer vouched for the key at capture time.
+//!
+//! Rotation keeps old seals valid: the new key becomes `Active` and the
+//! previous one `Retired` with a `valid_until` slightly in the future, so
+//! the two validity periods overlap while every replica switches keys.
+//! Retired keys stay in the registry and keep vouching for the seals made
+//! within their validity; only a `Revoked` key (e.g. after a compromise)
+//! stops vouching for its seals. Seals without a key ID were signed with a
+//! one-off key and are checked against their embedded key alone.
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::VeritasSeal;
+
+/// Longest accepted key ID, in bytes (room for a DID URL, see
+/// [`crate::did`]).
+pub const MAX_KEY_ID_LEN: usize = 256;
+
+/// Registry ID of a public key: the first 8 bytes of its SHA3-256, in hex.
+///
+/// Registries may use other IDs; this is the one signers use by default.
+pub fn key_id_for(public_key: &[u8]) -> String {
+    hex::encode(&Sha3_256::digest(public_key)[..8])
+}
+
+/// Check a key ID is 1 to [`MAX_KEY_ID_LEN`] bytes long.
+pub(crate) fn validate_key_id(key_id: &str) -> Result<()> {
+    if key_id.is_empty() || key_id.len() > MAX_KEY_ID_LEN {
+        return Err(VeritasError::InvalidSeal(format!(
+            "key_id must be 1 to {MAX_KEY_ID_LEN} bytes, got {}",
+            key_id.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Lifecycle status of a registered signing key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningKeyStatus {
+    /// Signing new seals
+    Active,
+    /// Replaced by a newer key; still vouches for seals made within its
+    /// validity
+    Retired,
+    /// Withdrawn; vouches for no seal
+    Revoked,
+}
+
+impl SigningKeyStatus {
+    /// Status as serialized (`active`, `retired`, `revoked`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Retired => "retired",
+            Self::Revoked => "revoked",
+        }
+    }
+
+    /// Parse a status as serialized.
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "active" => Some(Self::Active),
+            "retired" => Some(Self::Retired),
+            "revoked" => Some(Self::Revoked),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SigningKeyStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A signing key as recorded in a registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningKey {
+    /// Registry ID, written into the key's seals
+    pub key_id: String,
+    /// ML-DSA-65 public key
+    pub public_key: Vec<u8>,
+    /// Start of the validity period (Unix timestamp ms)
+    pub valid_from: u64,
+    /// End of the validity period (Unix timestamp ms, `None` = open-ended)
+    pub valid_until: Option<u64>,
+    /// Lifecycle status
+    pub status: SigningKeyStatus,
+}
+
+impl SigningKey {
+    /// An active key valid from `valid_from`, under its default ID
+    /// ([`key_id_for`]).
+    pub fn new(public_key: Vec<u8>, valid_from: u64) -> Self {
+        Self {
+            key_id: key_id_for(&public_key),
+            public_key,
+            valid_from,
+            valid_until: None,
+            status: SigningKeyStatus::Active,
+        }
+    }
+
+    /// Whether `timestamp` (Unix ms) falls within the validity period.
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        timestamp >= self.valid_from && self.valid_until.is_none_or(|until| timestamp <= until)
+    }
+}
+
+/// What a registry says about the key that signed a seal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyResolution {
+    /// The seal has no key ID: only its embedded key vouches for it
+    Embedded,
+    /// The registry does not know the key ID
+    Unknown,
+    /// Registered key, valid and not revoked at capture time
+    Trusted,
+    /// The registry holds another public key under the seal's key ID
+    KeyMismatch,
+    /// The key was revoked
+    Revoked,
+    /// The seal was captured outside the key's validity period
+    OutsideValidity,
+}
+
+impl KeyResolution {
+    /// Resolution as serialized (`trusted`, `key_mismatch`, ...).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Embedded => "embedded",
+            Self::Unknown => "unknown",
+            Self::Trusted => "trusted",
+            Self::KeyMismatch => "key_mismatch",
+            Self::Revoked => "revoked",
+            Self::OutsideValidity => "outside_validity",
+        }
+    }
+
+    /// Whether the registry contradicts the seal: its key is not the
+    /// registered one, revoked, or was not valid at capture time.
+    pub fn is_rejected(&self) -> bool {
+        matches!(
+            self,
+            Self::KeyMismatch | Self::Revoked | Self::OutsideValidity
+        )
+    }
+}
+
+impl VeritasSeal {
+    /// Check the seal's signing key against its registry entry, as found by
+    /// looking up [`VeritasSeal::key_id`] (`None` = not registered).
+    ///
+    /// Only meaningful once the signature has been verified.
+    pub fn resolve_signing_key(&self, registered: Option<&SigningKey>) -> KeyResolution {
+        let Some(key_id) = &self.key_id else {
+            return KeyResolution::Embedded;
+        };
+        let Some(key) = registered.filter(|key| &key.key_id == key_id) else {
+            return KeyResolution::Unknown;
+        };
+        if key.public_key != self.public_key {
+            KeyResolution::KeyMismatch
+        } else if key.status == SigningKeyStatus::Revoked {
+            KeyResolution::Revoked
+        } else if !key.is_valid_at(self.capture_timestamp_utc) {
+            KeyResolution::OutsideValidity
+        } else {
+            KeyResolution::Trusted
+        }
+    }
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, MediaType, SealBuilder};
+    use pqcrypto_traits::sign::PublicKey;
+
+    #[test]
+    fn test_key_id_for() {
+        let key_id = key_id_for(&[1; 32]);
+        assert_eq!(key_id.len(), 16);
+        assert_eq!(key_id, key_id_for(&[1; 32]));
+        assert_ne!(key_id, key_id_for(&[2; 32]));
+    }
+
+    #[test]
+    fn test_validity_period() {
+        let mut key = SigningKey::new(vec![1; 32], 1_000);
+        assert!(!key.is_valid_at(999));
+        assert!(key.is_valid_at(1_000));
+        assert!(key.is_valid_at(u64::MAX));
+
+        key.valid_until = Some(2_000);
+        assert!(key.is_valid_at(2_000));
+        assert!(!key.is_valid_at(2_001));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_signing_key() {
+        let (public_key, secret_key) = generate_keypair();
+        let registered = SigningKey::new(public_key.as_bytes().to_vec(), 0);
+        let seal = SealBuilder::new(b"Registered".to_vec(), MediaType::Image)
+            .with_key_id(registered.key_id.clone())
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            seal.resolve_signing_key(Some(&registered)),
+            KeyResolution::Trusted
+        );
+        assert_eq!(seal.resolve_signing_key(None), KeyResolution::Unknown);
+
+        // Retired keys keep vouching for the seals made while they were valid
+        let retired = SigningKey {
+            status: SigningKeyStatus::Retired,
+            valid_until: Some(seal.capture_timestamp_utc),
+            ..registered.clone()
+        };
+        assert_eq!(
+            seal.resolve_signing_key(Some(&retired)),
+            KeyResolution::Trusted
+        );
+        let expired = SigningKey {
+            valid_until: Some(seal.capture_timestamp_utc - 1),
+            ..retired
+        };
+        assert_eq!(
+            seal.resolve_signing_key(Some(&expired)),
+            KeyResolution::OutsideValidity
+        );
+
+        let revoked = SigningKey {
+            status: SigningKeyStatus::Revoked,
+            ..registered.clone()
+        };
+        assert_eq!(
+            seal.resolve_signing_key(Some(&revoked)),
+            KeyResolution::Revoked
+        );
+
+        // Another key claiming the seal's key ID
+        let (other_key, _) = generate_keypair();
+        let impostor = SigningKey {
+            public_key: other_key.as_bytes().to_vec(),
+            ..registered
+        };
+        let resolution = seal.resolve_signing_key(Some(&impostor));
+        assert_eq!(resolution, KeyResolution::KeyMismatch);
+        assert!(resolution.is_rejected());
+
+        let unregistered = SealBuilder::new(b"One-off".to_vec(), MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        assert_eq!(
+            unregistered.resolve_signing_key(Some(&impostor)),
+            KeyResolution::Embedded
+        );
+    }
+}
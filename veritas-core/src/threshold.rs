@@ -0,0 +1,449 @@
+//! Threshold (k-of-n) sealing.
+//!
+//! High-stakes evidence can require several independent devices or keys to
+//! co-sign a seal. The seal commits to a [`ThresholdPolicy`] (the `n` co-signer
+//! public keys and the threshold `k`) inside its signed payload; co-signers
+//! then add [`PartialSignature`]s over that same payload. The seal only
+//! verifies once at least `k` distinct co-signers have signed.
+//!
+//! Partial signatures can arrive in any order and from anywhere, so they are
+//! gathered with a [`ThresholdCollector`]:
+//!
+//! ```no_run
+//! # use veritas_core::threshold::{PartialSignature, ThresholdCollector};
+//! # fn example(seal: veritas_core::VeritasSeal, incoming: Vec<PartialSignature>)
+//! # -> veritas_core::Result<()> {
+//! let mut collector = ThresholdCollector::new(seal)?;
+//! // Send collector.signing_message() to the co-signers...
+//! for partial in incoming {
+//!     if collector.add(partial)?.is_met() {
+//!         break;
+//!     }
+//! }
+//! let seal = collector.finish()?;
+//! assert!(seal.verify()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, VeritasError};
+use crate::seal::{VeritasSeal, MLDSA65_PUBLIC_KEY_BYTES};
+
+/// Domain separation tag prepended to the payload co-signers sign.
+const THRESHOLD_DOMAIN: &[u8] = b"veritas-threshold-v1";
+
+/// Maximum number of co-signers in a policy.
+///
+/// Every co-signer key is stored twice (policy field and signed message), so
+/// this keeps a fully signed threshold seal under [`crate::MAX_SEAL_SIZE`].
+pub const MAX_THRESHOLD_SIGNERS: usize = 5;
+
+/// The `k`-of-`n` co-signing requirement committed to by a seal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdPolicy {
+    /// Number of valid co-signatures required (`k`)
+    pub threshold: u8,
+    /// ML-DSA-65 public keys of the allowed co-signers (`n`)
+    pub signers: Vec<Vec<u8>>,
+}
+
+impl ThresholdPolicy {
+    /// Create a policy requiring `threshold` of the given co-signers.
+    pub fn new(threshold: u8, signers: &[mldsa65::PublicKey]) -> Result<Self> {
+        let policy = Self {
+            threshold,
+            signers: signers.iter().map(|pk| pk.as_bytes().to_vec()).collect(),
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Check `1 <= k <= n <= MAX_THRESHOLD_SIGNERS` and that keys are well formed
+    /// and distinct.
+    pub fn validate(&self) -> Result<()> {
+        let n = self.signers.len();
+        if n == 0 || n > MAX_THRESHOLD_SIGNERS {
+            return Err(invalid(format!(
+                "threshold policy needs 1 to {MAX_THRESHOLD_SIGNERS} signers, got {n}"
+            )));
+        }
+        if self.threshold == 0 || usize::from(self.threshold) > n {
+            return Err(invalid(format!(
+                "threshold must be between 1 and {n}, got {}",
+                self.threshold
+            )));
+        }
+        for (i, key) in self.signers.iter().enumerate() {
+            if key.len() != MLDSA65_PUBLIC_KEY_BYTES {
+                return Err(invalid(format!(
+                    "threshold signer {i} has invalid key size {}",
+                    key.len()
+                )));
+            }
+            if self.signers[..i].contains(key) {
+                return Err(invalid(format!("threshold signer {i} is listed twice")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Index of a co-signer key in the policy.
+    pub fn signer_index(&self, public_key: &[u8]) -> Option<u8> {
+        self.signers
+            .iter()
+            .position(|key| key.as_slice() == public_key)
+            .and_then(|i| u8::try_from(i).ok())
+    }
+}
+
+/// One co-signer's signature over a threshold seal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    /// Position of the co-signer in [`ThresholdPolicy::signers`]
+    pub signer_index: u8,
+    /// Detached ML-DSA-65 signature over the threshold signing message
+    pub signature: Vec<u8>,
+}
+
+impl PartialSignature {
+    /// Sign a threshold signing message (see [`VeritasSeal::threshold_signing_message`]).
+    pub fn sign(message: &[u8], signer_index: u8, secret_key: &mldsa65::SecretKey) -> Self {
+        let signature = mldsa65::detached_sign(message, secret_key);
+        Self {
+            signer_index,
+            signature: signature.as_bytes().to_vec(),
+        }
+    }
+
+    /// Verify against the policy signer this partial signature claims to be from.
+    fn verify(&self, policy: &ThresholdPolicy, message: &[u8]) -> bool {
+        let Some(key) = policy.signers.get(usize::from(self.signer_index)) else {
+            return false;
+        };
+        let Ok(public_key) = mldsa65::PublicKey::from_bytes(key) else {
+            return false;
+        };
+        let Ok(signature) = mldsa65::DetachedSignature::from_bytes(&self.signature) else {
+            return false;
+        };
+        mldsa65::verify_detached_signature(&signature, message, &public_key).is_ok()
+    }
+}
+
+/// Progress of a threshold seal towards its required co-signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThresholdStatus {
+    /// Distinct co-signers with a valid partial signature
+    pub valid: usize,
+    /// Co-signatures required (`k`)
+    pub required: usize,
+    /// Co-signers allowed by the policy (`n`)
+    pub signers: usize,
+}
+
+impl ThresholdStatus {
+    /// Returns true if enough co-signers have signed.
+    pub fn is_met(&self) -> bool {
+        self.valid >= self.required
+    }
+}
+
+impl VeritasSeal {
+    /// Message co-signers must sign for a threshold seal.
+    ///
+    /// Returns `Err` if the seal has no threshold policy.
+    pub fn threshold_signing_message(&self) -> Result<Vec<u8>> {
+        if self.threshold_policy.is_none() {
+            return Err(invalid("seal has no threshold policy"));
+        }
+        let signable = self.signable_bytes()?;
+        let mut message = Vec::with_capacity(THRESHOLD_DOMAIN.len() + signable.len());
+        message.extend_from_slice(THRESHOLD_DOMAIN);
+        message.extend_from_slice(&signable);
+        Ok(message)
+    }
+
+    /// Count valid co-signatures. Returns `None` for seals without a policy.
+    pub fn threshold_status(&self) -> Result<Option<ThresholdStatus>> {
+        let Some(policy) = &self.threshold_policy else {
+            return Ok(None);
+        };
+        let message = self.threshold_signing_message()?;
+        Ok(Some(count_valid(
+            policy,
+            &self.partial_signatures,
+            &message,
+        )))
+    }
+
+    /// Co-sign a threshold seal with one of the policy keys.
+    pub fn cosign(
+        &mut self,
+        secret_key: &mldsa65::SecretKey,
+        public_key: &mldsa65::PublicKey,
+    ) -> Result<ThresholdStatus> {
+        let policy = self
+            .threshold_policy
+            .as_ref()
+            .ok_or_else(|| invalid("seal has no threshold policy"))?;
+        let signer_index = policy
+            .signer_index(public_key.as_bytes())
+            .ok_or_else(|| invalid("key is not a co-signer of this seal"))?;
+
+        let message = self.threshold_signing_message()?;
+        let partial = PartialSignature::sign(&message, signer_index, secret_key);
+        self.add_partial_signature(partial)
+    }
+
+    /// Attach a partial signature collected from a co-signer.
+    ///
+    /// The signature must verify for the co-signer it names. A second
+    /// signature from the same co-signer replaces the first.
+    pub fn add_partial_signature(&mut self, partial: PartialSignature) -> Result<ThresholdStatus> {
+        let message = self.threshold_signing_message()?;
+        let policy = self
+            .threshold_policy
+            .as_ref()
+            .expect("checked by threshold_signing_message");
+
+        if !partial.verify(policy, &message) {
+            return Err(invalid(format!(
+                "partial signature from signer {} does not verify",
+                partial.signer_index
+            )));
+        }
+
+        self.partial_signatures
+            .retain(|existing| existing.signer_index != partial.signer_index);
+        self.partial_signatures.push(partial);
+        self.partial_signatures.sort_by_key(|p| p.signer_index);
+
+        Ok(count_valid(policy, &self.partial_signatures, &message))
+    }
+}
+
+/// Gathers partial signatures for a threshold seal as they arrive.
+///
+/// Computes the signing message once and rejects invalid or foreign
+/// signatures immediately, so co-signers can be contacted concurrently and
+/// their responses fed in as they come back.
+#[derive(Debug)]
+pub struct ThresholdCollector {
+    seal: VeritasSeal,
+    message: Vec<u8>,
+}
+
+impl ThresholdCollector {
+    /// Start collecting for a seal built with a threshold policy.
+    pub fn new(seal: VeritasSeal) -> Result<Self> {
+        let message = seal.threshold_signing_message()?;
+        Ok(Self { seal, message })
+    }
+
+    /// Message to send to co-signers.
+    pub fn signing_message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// The policy being collected for.
+    pub fn policy(&self) -> &ThresholdPolicy {
+        self.seal
+            .threshold_policy
+            .as_ref()
+            .expect("checked in ThresholdCollector::new")
+    }
+
+    /// Add a partial signature and return the updated status.
+    pub fn add(&mut self, partial: PartialSignature) -> Result<ThresholdStatus> {
+        self.seal.add_partial_signature(partial)
+    }
+
+    /// Current status.
+    pub fn status(&self) -> ThresholdStatus {
+        count_valid(self.policy(), &self.seal.partial_signatures, &self.message)
+    }
+
+    /// Return the seal once the threshold is met.
+    pub fn finish(self) -> Result<VeritasSeal> {
+        let status = self.status();
+        if !status.is_met() {
+            return Err(invalid(format!(
+                "threshold not met: {} of {} required co-signatures",
+                status.valid, status.required
+            )));
+        }
+        Ok(self.seal)
+    }
+}
+
+/// Count distinct co-signers with a valid partial signature.
+fn count_valid(
+    policy: &ThresholdPolicy,
+    partials: &[PartialSignature],
+    message: &[u8],
+) -> ThresholdStatus {
+    let mut seen = [false; MAX_THRESHOLD_SIGNERS];
+    let mut valid = 0;
+    for partial in partials {
+        let index = usize::from(partial.signer_index);
+        if index < seen.len() && !seen[index] && partial.verify(policy, message) {
+            seen[index] = true;
+            valid += 1;
+        }
+    }
+
+    ThresholdStatus {
+        valid,
+        required: usize::from(policy.threshold),
+        signers: policy.signers.len(),
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> VeritasError {
+    VeritasError::InvalidSeal(msg.into())
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use crate::qrng::MockQrng;
+    use crate::seal::{generate_keypair, generate_keypair_raw, MediaType, SealBuilder};
+    use crate::VerificationResult;
+
+    struct Cosigners {
+        keys: Vec<(mldsa65::PublicKey, mldsa65::SecretKey)>,
+    }
+
+    impl Cosigners {
+        fn new(n: usize) -> Self {
+            Self {
+                keys: (0..n).map(|_| generate_keypair_raw()).collect(),
+            }
+        }
+
+        fn public_keys(&self) -> Vec<mldsa65::PublicKey> {
+            self.keys.iter().map(|(pk, _)| *pk).collect()
+        }
+    }
+
+    async fn threshold_seal(policy: ThresholdPolicy) -> VeritasSeal {
+        let (public_key, secret_key) = generate_keypair();
+        SealBuilder::new(b"high stakes".to_vec(), MediaType::Video)
+            .with_threshold(policy)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .expect("Failed to create seal")
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        let cosigners = Cosigners::new(3);
+        let keys = cosigners.public_keys();
+
+        assert!(ThresholdPolicy::new(2, &keys).is_ok());
+        assert!(ThresholdPolicy::new(0, &keys).is_err());
+        assert!(ThresholdPolicy::new(4, &keys).is_err());
+        assert!(ThresholdPolicy::new(1, &[]).is_err());
+        assert!(ThresholdPolicy::new(1, &[keys[0], keys[0]]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_seal_valid_only_after_k_signatures() {
+        let cosigners = Cosigners::new(3);
+        let policy = ThresholdPolicy::new(2, &cosigners.public_keys()).unwrap();
+        let mut seal = threshold_seal(policy).await;
+
+        assert_eq!(
+            seal.verify_detailed().unwrap(),
+            VerificationResult::ThresholdNotMet
+        );
+
+        let (pk, sk) = &cosigners.keys[2];
+        let status = seal.cosign(sk, pk).unwrap();
+        assert_eq!(status.valid, 1);
+        assert!(!seal.verify().unwrap());
+
+        let (pk, sk) = &cosigners.keys[0];
+        assert!(seal.cosign(sk, pk).unwrap().is_met());
+        assert!(seal.verify().unwrap());
+
+        let restored = VeritasSeal::from_cbor(&seal.to_cbor().unwrap()).unwrap();
+        assert!(restored.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_collector_aggregates_out_of_order() {
+        let cosigners = Cosigners::new(3);
+        let policy = ThresholdPolicy::new(2, &cosigners.public_keys()).unwrap();
+        let seal = threshold_seal(policy).await;
+
+        let mut collector = ThresholdCollector::new(seal).unwrap();
+        let message = collector.signing_message().to_vec();
+
+        // Co-signers respond independently with detached partial signatures
+        let partials: Vec<_> = [2u8, 1]
+            .iter()
+            .map(|&i| PartialSignature::sign(&message, i, &cosigners.keys[usize::from(i)].1))
+            .collect();
+
+        assert!(!collector.add(partials[0].clone()).unwrap().is_met());
+        // Duplicate from the same co-signer does not count twice
+        assert_eq!(collector.add(partials[0].clone()).unwrap().valid, 1);
+        assert!(collector.add(partials[1].clone()).unwrap().is_met());
+
+        let seal = collector.finish().unwrap();
+        assert_eq!(seal.partial_signatures.len(), 2);
+        assert!(seal.verify().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_foreign_and_forged_partials_rejected() {
+        let cosigners = Cosigners::new(2);
+        let policy = ThresholdPolicy::new(1, &cosigners.public_keys()).unwrap();
+        let mut seal = threshold_seal(policy).await;
+        let message = seal.threshold_signing_message().unwrap();
+
+        // Key outside the policy
+        let (outsider_pk, outsider_sk) = generate_keypair_raw();
+        assert!(seal.cosign(&outsider_sk, &outsider_pk).is_err());
+
+        // Valid signature claiming the wrong signer index
+        let wrong_index = PartialSignature::sign(&message, 1, &cosigners.keys[0].1);
+        assert!(seal.add_partial_signature(wrong_index).is_err());
+
+        // Index beyond the policy
+        let out_of_range = PartialSignature::sign(&message, 7, &cosigners.keys[0].1);
+        assert!(seal.add_partial_signature(out_of_range).is_err());
+
+        assert!(seal.partial_signatures.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collector_finish_requires_threshold() {
+        let cosigners = Cosigners::new(2);
+        let policy = ThresholdPolicy::new(2, &cosigners.public_keys()).unwrap();
+        let collector = ThresholdCollector::new(threshold_seal(policy).await).unwrap();
+        assert!(collector.finish().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_policy_is_covered_by_primary_signature() {
+        let cosigners = Cosigners::new(2);
+        let policy = ThresholdPolicy::new(1, &cosigners.public_keys()).unwrap();
+        let mut seal = threshold_seal(policy).await;
+        let (pk, sk) = &cosigners.keys[0];
+        seal.cosign(sk, pk).unwrap();
+
+        // Lowering the requirement or swapping keys breaks the primary signature
+        let (other_pk, _) = generate_keypair_raw();
+        seal.threshold_policy.as_mut().unwrap().signers[1] = other_pk.as_bytes().to_vec();
+        assert_eq!(
+            seal.verify_detailed().unwrap(),
+            VerificationResult::PayloadMismatch
+        );
+    }
+}
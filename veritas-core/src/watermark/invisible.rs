@@ -0,0 +1,440 @@
+//! Invisible watermarks carrying a seal ID.
+//!
+//! A seal travels next to the media it covers; an invisible watermark travels
+//! inside it. The 16-byte payload (a seal UUID) and a 32-bit checksum are
+//! spread over the 8x8 pixel blocks of the image, one bit per block, repeated
+//! across the whole picture.
+//!
+//! # Algorithm
+//!
+//! Each block carries its bit in two mid-frequency coefficients of the DCT of
+//! its luma, by quantization index modulation: the coefficient is moved to the
+//! nearest multiple of the quantization step for a 0, or halfway between two
+//! multiples for a 1. The luma change is added equally to the red, green and
+//! blue channels, leaving the colors untouched. Extraction reads every block,
+//! takes a soft majority vote for each bit and checks the checksum.
+//!
+//! The mark survives re-encoding as JPEG at moderate quality (75 and up) and
+//! lossless conversions. It does not survive cropping, resizing or rotation,
+//! which move the block grid; the perceptual hash covers those cases.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use veritas_core::watermark::{embed_watermark_bytes, extract_watermark_bytes};
+//!
+//! let seal_id = [0x42; 16];
+//! let image_data = std::fs::read("image.jpg").unwrap();
+//! let marked = embed_watermark_bytes(&image_data, &seal_id).unwrap();
+//!
+//! let extracted = extract_watermark_bytes(&marked.data).unwrap();
+//! assert_eq!(extracted.payload, seal_id);
+//! ```
+
+use std::f32::consts::PI;
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use sha3::{Digest, Sha3_256};
+
+use crate::error::{Result, VeritasError};
+
+/// Watermark payload size in bytes (a seal UUID).
+pub const WATERMARK_PAYLOAD_SIZE: usize = 16;
+
+/// Checksum bytes appended to the payload, so unmarked images are not
+/// mistaken for marked ones.
+const CHECKSUM_SIZE: usize = 4;
+
+/// Bits embedded in the image: payload and checksum.
+const MESSAGE_BITS: usize = (WATERMARK_PAYLOAD_SIZE + CHECKSUM_SIZE) * 8;
+
+/// Side of the pixel blocks carrying one bit each.
+const BLOCK_SIZE: u32 = 8;
+
+/// Every bit is repeated in at least this many blocks.
+const MIN_REPETITIONS: usize = 4;
+
+/// Fewest 8x8 blocks an image needs to carry a watermark (a 208x208 image
+/// has enough).
+pub const MIN_WATERMARK_BLOCKS: usize = MESSAGE_BITS * MIN_REPETITIONS;
+
+/// Quantization step of the marked coefficients. Larger steps survive
+/// harder compression but become visible.
+const QUANTIZATION_STEP: f32 = 28.0;
+
+/// DCT coefficients (row, column) carrying the bit of each block.
+const COEFFICIENTS: [(usize, usize); 2] = [(1, 2), (2, 1)];
+
+/// Quality of re-encoded JPEG output.
+const JPEG_QUALITY: u8 = 95;
+
+/// A watermark read from an image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExtractedWatermark {
+    /// The embedded payload (a seal UUID)
+    pub payload: [u8; WATERMARK_PAYLOAD_SIZE],
+    /// Agreement of the repeated bits, from 0 (noise) to 1 (untouched)
+    pub confidence: f32,
+}
+
+/// Encoding of a watermarked image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatermarkFormat {
+    /// JPEG, for JPEG input
+    Jpeg,
+    /// PNG, for every other input format
+    Png,
+}
+
+impl WatermarkFormat {
+    /// MIME type of the encoding.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+        }
+    }
+
+    /// Usual file extension of the encoding.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+        }
+    }
+}
+
+/// An encoded watermarked image.
+#[derive(Debug, Clone)]
+pub struct WatermarkedImage {
+    /// Encoded image
+    pub data: Vec<u8>,
+    /// Encoding of `data`
+    pub format: WatermarkFormat,
+}
+
+/// Embed `payload` in a decoded image.
+///
+/// Fails if the image has fewer than [`MIN_WATERMARK_BLOCKS`] 8x8 blocks.
+pub fn embed_watermark(
+    image: &DynamicImage,
+    payload: &[u8; WATERMARK_PAYLOAD_SIZE],
+) -> Result<DynamicImage> {
+    let mut pixels = image.to_rgba8();
+    let (blocks_x, blocks_y) = block_grid(&pixels)?;
+    let bits = message_bits(payload);
+    let bases = COEFFICIENTS.map(|(row, column)| coefficient_basis(row, column));
+
+    for by in 0..blocks_y {
+        for bx in 0..blocks_x {
+            let bit = bits[(by * blocks_x + bx) as usize % MESSAGE_BITS];
+            let luma = block_luma(&pixels, bx, by);
+            let mut delta = [[0.0f32; 8]; 8];
+            for basis in &bases {
+                let coefficient = project(&luma, basis);
+                let shift = quantize(coefficient, bit) - coefficient;
+                for (delta_row, basis_row) in delta.iter_mut().zip(basis) {
+                    for (d, b) in delta_row.iter_mut().zip(basis_row) {
+                        *d += shift * b;
+                    }
+                }
+            }
+
+            for (y, delta_row) in delta.iter().enumerate() {
+                for (x, d) in delta_row.iter().enumerate() {
+                    let pixel = pixels
+                        .get_pixel_mut(bx * BLOCK_SIZE + x as u32, by * BLOCK_SIZE + y as u32);
+                    for channel in &mut pixel.0[..3] {
+                        *channel = (f32::from(*channel) + d).round().clamp(0.0, 255.0) as u8;
+                    }
+                }
+            }
+        }
+    }
+
+    let marked = DynamicImage::ImageRgba8(pixels);
+    Ok(if image.color().has_alpha() {
+        marked
+    } else {
+        DynamicImage::ImageRgb8(marked.to_rgb8())
+    })
+}
+
+/// Embed `payload` in an encoded image (JPEG, PNG, GIF or WebP).
+///
+/// JPEG input is re-encoded as JPEG at high quality; other formats are
+/// written as PNG, since their lossy or palette encodings would erase the
+/// mark.
+pub fn embed_watermark_bytes(
+    image_data: &[u8],
+    payload: &[u8; WATERMARK_PAYLOAD_SIZE],
+) -> Result<WatermarkedImage> {
+    let input_format = image::guess_format(image_data)
+        .map_err(|e| VeritasError::WatermarkError(format!("Unsupported image: {}", e)))?;
+    let image = image::load_from_memory(image_data)
+        .map_err(|e| VeritasError::WatermarkError(format!("Failed to decode image: {}", e)))?;
+    let marked = embed_watermark(&image, payload)?;
+
+    let mut data = Vec::new();
+    let format = if input_format == ImageFormat::Jpeg {
+        JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY)
+            .encode_image(&marked.to_rgb8())
+            .map(|()| WatermarkFormat::Jpeg)
+    } else {
+        marked
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .map(|()| WatermarkFormat::Png)
+    }
+    .map_err(|e| VeritasError::WatermarkError(format!("Failed to encode image: {}", e)))?;
+
+    Ok(WatermarkedImage { data, format })
+}
+
+/// Read the watermark of a decoded image, or `None` if it carries none (or
+/// it was destroyed).
+pub fn extract_watermark(image: &DynamicImage) -> Option<ExtractedWatermark> {
+    let pixels = image.to_rgba8();
+    let (blocks_x, blocks_y) = block_grid(&pixels).ok()?;
+    let bases = COEFFICIENTS.map(|(row, column)| coefficient_basis(row, column));
+
+    // Soft votes: +1 for a coefficient on the 0 lattice, -1 on the 1 lattice
+    let mut votes = [0.0f32; MESSAGE_BITS];
+    let mut counts = [0u32; MESSAGE_BITS];
+    for by in 0..blocks_y {
+        for bx in 0..blocks_x {
+            let index = (by * blocks_x + bx) as usize % MESSAGE_BITS;
+            let luma = block_luma(&pixels, bx, by);
+            for basis in &bases {
+                let coefficient = project(&luma, basis);
+                votes[index] += (2.0 * PI * coefficient / QUANTIZATION_STEP).cos();
+                counts[index] += 1;
+            }
+        }
+    }
+
+    let mut message = [0u8; WATERMARK_PAYLOAD_SIZE + CHECKSUM_SIZE];
+    for (i, vote) in votes.iter().enumerate() {
+        if *vote < 0.0 {
+            message[i / 8] |= 0x80 >> (i % 8);
+        }
+    }
+    let (payload, checksum) = message.split_at(WATERMARK_PAYLOAD_SIZE);
+    let payload: [u8; WATERMARK_PAYLOAD_SIZE] = payload.try_into().ok()?;
+    if checksum != payload_checksum(&payload) {
+        return None;
+    }
+
+    let confidence = votes
+        .iter()
+        .zip(&counts)
+        .map(|(vote, count)| vote.abs() / *count as f32)
+        .sum::<f32>()
+        / MESSAGE_BITS as f32;
+    Some(ExtractedWatermark {
+        payload,
+        confidence,
+    })
+}
+
+/// Read the watermark of an encoded image (JPEG, PNG, GIF or WebP), or
+/// `None` if it is not an image or carries no watermark.
+pub fn extract_watermark_bytes(image_data: &[u8]) -> Option<ExtractedWatermark> {
+    let image = image::load_from_memory(image_data).ok()?;
+    extract_watermark(&image)
+}
+
+/// Blocks per row and per column, checking there are enough of them.
+fn block_grid(pixels: &RgbaImage) -> Result<(u32, u32)> {
+    let (blocks_x, blocks_y) = (pixels.width() / BLOCK_SIZE, pixels.height() / BLOCK_SIZE);
+    if (blocks_x as usize) * (blocks_y as usize) < MIN_WATERMARK_BLOCKS {
+        return Err(VeritasError::WatermarkError(format!(
+            "Image too small for a watermark: {}x{} pixels has fewer than {} 8x8 blocks",
+            pixels.width(),
+            pixels.height(),
+            MIN_WATERMARK_BLOCKS
+        )));
+    }
+    Ok((blocks_x, blocks_y))
+}
+
+/// Payload and checksum bits, most significant bit first.
+fn message_bits(payload: &[u8; WATERMARK_PAYLOAD_SIZE]) -> [bool; MESSAGE_BITS] {
+    let mut bits = [false; MESSAGE_BITS];
+    let checksum = payload_checksum(payload);
+    for (i, byte) in payload.iter().chain(&checksum).enumerate() {
+        for bit in 0..8 {
+            bits[i * 8 + bit] = byte & (0x80 >> bit) != 0;
+        }
+    }
+    bits
+}
+
+fn payload_checksum(payload: &[u8; WATERMARK_PAYLOAD_SIZE]) -> [u8; CHECKSUM_SIZE] {
+    let digest = Sha3_256::digest(payload);
+    let mut checksum = [0u8; CHECKSUM_SIZE];
+    checksum.copy_from_slice(&digest[..CHECKSUM_SIZE]);
+    checksum
+}
+
+/// Luma (BT.601) of the block at `bx`, `by`.
+fn block_luma(pixels: &RgbaImage, bx: u32, by: u32) -> [[f32; 8]; 8] {
+    let mut luma = [[0.0f32; 8]; 8];
+    for (y, row) in luma.iter_mut().enumerate() {
+        for (x, value) in row.iter_mut().enumerate() {
+            let [r, g, b, _] = pixels
+                .get_pixel(bx * BLOCK_SIZE + x as u32, by * BLOCK_SIZE + y as u32)
+                .0;
+            *value = 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+        }
+    }
+    luma
+}
+
+/// Orthonormal 8x8 DCT-II basis image of the coefficient at `row`, `column`.
+fn coefficient_basis(row: usize, column: usize) -> [[f32; 8]; 8] {
+    let dct = |k: usize, n: usize| {
+        let scale = if k == 0 { (1.0f32 / 8.0).sqrt() } else { 0.5 };
+        scale * ((2 * n + 1) as f32 * k as f32 * PI / 16.0).cos()
+    };
+    let mut basis = [[0.0f32; 8]; 8];
+    for (y, basis_row) in basis.iter_mut().enumerate() {
+        for (x, value) in basis_row.iter_mut().enumerate() {
+            *value = dct(row, y) * dct(column, x);
+        }
+    }
+    basis
+}
+
+/// DCT coefficient of `block` for `basis`.
+fn project(block: &[[f32; 8]; 8], basis: &[[f32; 8]; 8]) -> f32 {
+    block
+        .iter()
+        .flatten()
+        .zip(basis.iter().flatten())
+        .map(|(value, b)| value * b)
+        .sum()
+}
+
+/// Nearest point of the lattice encoding `bit`.
+fn quantize(coefficient: f32, bit: bool) -> f32 {
+    let offset = if bit { QUANTIZATION_STEP / 2.0 } else { 0.0 };
+    ((coefficient - offset) / QUANTIZATION_STEP).round() * QUANTIZATION_STEP + offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    const SEAL_ID: [u8; WATERMARK_PAYLOAD_SIZE] = [
+        0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4, 0xa7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00,
+        0x00,
+    ];
+
+    /// Mid-tone image with gradients and texture, like a photo.
+    fn photo(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let texture = ((x * 7 + y * 13) % 23) as u8;
+            Rgb([
+                60 + (x % 128) as u8 + texture,
+                70 + (y % 128) as u8,
+                90 + ((x + y) % 100) as u8 + texture,
+            ])
+        }))
+    }
+
+    #[test]
+    fn test_basis_is_orthonormal() {
+        let a = coefficient_basis(1, 2);
+        let b = coefficient_basis(2, 1);
+        assert!((project(&a, &a) - 1.0).abs() < 1e-4);
+        assert!(project(&a, &b).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_quantize_lattices() {
+        assert_eq!(quantize(30.0, false), 28.0);
+        assert_eq!(quantize(30.0, true), 42.0);
+        assert_eq!(quantize(-5.0, true), -14.0);
+    }
+
+    #[test]
+    fn test_embed_and_extract() {
+        let image = photo(256, 256);
+        let marked = embed_watermark(&image, &SEAL_ID).unwrap();
+        assert_eq!(marked.color(), image.color());
+
+        let extracted = extract_watermark(&marked).expect("watermark present");
+        assert_eq!(extracted.payload, SEAL_ID);
+        assert!(extracted.confidence > 0.9);
+
+        assert!(extract_watermark(&image).is_none());
+    }
+
+    #[test]
+    fn test_watermark_is_invisible() {
+        let image = photo(256, 256);
+        let marked = embed_watermark(&image, &SEAL_ID).unwrap();
+        let (a, b) = (image.to_rgb8(), marked.to_rgb8());
+        let max_change = a
+            .as_raw()
+            .iter()
+            .zip(b.as_raw())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap();
+        assert!(max_change <= 12, "max pixel change {max_change}");
+    }
+
+    #[test]
+    fn test_watermark_survives_jpeg() {
+        let image = photo(320, 240);
+        let marked = embed_watermark(&image, &SEAL_ID).unwrap();
+
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, 75)
+            .encode_image(&marked.to_rgb8())
+            .unwrap();
+
+        let extracted = extract_watermark_bytes(&jpeg).expect("watermark survives JPEG");
+        assert_eq!(extracted.payload, SEAL_ID);
+    }
+
+    #[test]
+    fn test_embed_bytes_keeps_jpeg() {
+        let mut png = Vec::new();
+        photo(256, 256)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let marked = embed_watermark_bytes(&png, &SEAL_ID).unwrap();
+        assert_eq!(marked.format, WatermarkFormat::Png);
+
+        let marked = embed_watermark_bytes(&marked.data, &[7; WATERMARK_PAYLOAD_SIZE]).unwrap();
+        assert_eq!(
+            extract_watermark_bytes(&marked.data).unwrap().payload,
+            [7; WATERMARK_PAYLOAD_SIZE]
+        );
+
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, 90)
+            .encode_image(&photo(256, 256).to_rgb8())
+            .unwrap();
+        let marked = embed_watermark_bytes(&jpeg, &SEAL_ID).unwrap();
+        assert_eq!(marked.format, WatermarkFormat::Jpeg);
+        assert_eq!(marked.format.mime_type(), "image/jpeg");
+        assert_eq!(
+            extract_watermark_bytes(&marked.data).unwrap().payload,
+            SEAL_ID
+        );
+    }
+
+    #[test]
+    fn test_small_image_rejected() {
+        let result = embed_watermark(&photo(64, 64), &SEAL_ID);
+        assert!(matches!(result, Err(VeritasError::WatermarkError(_))));
+        assert!(extract_watermark(&photo(64, 64)).is_none());
+        assert!(extract_watermark_bytes(b"not an image").is_none());
+    }
+}
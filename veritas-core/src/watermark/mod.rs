@@ -7,12 +7,15 @@
 //!
 //! - **Perceptual hashing**: Creates fingerprints that remain similar for visually
 //!   similar images, enabling verification even after re-encoding.
+//! - **Invisible watermarks**: Embed a seal ID in the pixel data itself, so a
+//!   copy stripped of its seal and metadata still points back to the seal.
 //!
 //! # Future Extensions
 //!
-//! - **Steganography**: Invisible watermarks embedded in pixel data (planned)
 //! - **Robust hashing**: Additional algorithms for specific use cases
 
+pub mod invisible;
 pub mod perceptual;
 
+pub use invisible::*;
 pub use perceptual::*;
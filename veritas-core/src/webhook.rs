@@ -0,0 +1,211 @@
+//! Signatures of webhook deliveries.
+//!
+//! The server signs every webhook body it posts with the subscription's
+//! secret: `X-Veritas-Timestamp` carries the Unix time of the delivery and
+//! `X-Veritas-Signature` is `v1=` followed by the hex HMAC-SHA3-256 of
+//! `"{timestamp}.{body}"`. Because the timestamp is signed, a receiver that
+//! rejects deliveries older than a few minutes ([`DEFAULT_TOLERANCE_SECS`])
+//! cannot be fed a replayed request. The signature header may hold several
+//! comma-separated `v1=` values (e.g. while a secret is being rotated); one
+//! matching value is enough.
+//!
+//! # Example
+//!
+//! ```
+//! use veritas_core::webhook::{sign_webhook, verify_webhook, DEFAULT_TOLERANCE_SECS};
+//!
+//! let body = br#"{"event":"seal_tampered"}"#;
+//! let timestamp = chrono::Utc::now().timestamp();
+//! let signature = sign_webhook(b"whsec", timestamp, body);
+//!
+//! // On the receiving side, with the headers of the request
+//! let timestamp = timestamp.to_string();
+//! verify_webhook(b"whsec", &signature, &timestamp, body, DEFAULT_TOLERANCE_SECS).unwrap();
+//! ```
+
+use hmac::{Hmac, Mac};
+use sha3::Sha3_256;
+use thiserror::Error;
+
+/// Header carrying the body signature.
+pub const SIGNATURE_HEADER: &str = "x-veritas-signature";
+
+/// Header carrying the Unix time (seconds) at which the delivery was signed.
+pub const TIMESTAMP_HEADER: &str = "x-veritas-timestamp";
+
+/// Version prefix of signatures.
+pub const SIGNATURE_VERSION: &str = "v1";
+
+/// Suggested maximum age of a delivery, in seconds (5 minutes).
+pub const DEFAULT_TOLERANCE_SECS: u64 = 5 * 60;
+
+type HmacSha3 = Hmac<Sha3_256>;
+
+/// Errors from verifying a webhook delivery
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WebhookError {
+    /// The timestamp header is not a Unix time
+    #[error("invalid webhook timestamp '{0}'")]
+    InvalidTimestamp(String),
+
+    /// The delivery was signed too long ago (or too far in the future)
+    #[error("webhook timestamp is {age_secs}s away from now (tolerance: {tolerance_secs}s)")]
+    TimestampOutOfTolerance { age_secs: u64, tolerance_secs: u64 },
+
+    /// The signature header holds no `v1=` hex value
+    #[error("malformed webhook signature header")]
+    MalformedSignature,
+
+    /// No signature matches the secret, timestamp and body
+    #[error("webhook signature mismatch")]
+    SignatureMismatch,
+}
+
+/// Signature header value of `body` delivered at `timestamp`:
+/// `v1=<hex HMAC-SHA3-256(secret, "{timestamp}.{body}")>`.
+pub fn sign_webhook(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let mac = mac(secret, timestamp, body).finalize().into_bytes();
+    format!("{}={}", SIGNATURE_VERSION, hex::encode(mac))
+}
+
+/// Check a delivery's signature and that its timestamp is within
+/// `tolerance_secs` of now.
+///
+/// `signature` and `timestamp` are the values of the [`SIGNATURE_HEADER`]
+/// and [`TIMESTAMP_HEADER`] headers, `body` the raw request body (before any
+/// JSON parsing). The comparison runs in constant time.
+pub fn verify_webhook(
+    secret: &[u8],
+    signature: &str,
+    timestamp: &str,
+    body: &[u8],
+    tolerance_secs: u64,
+) -> Result<(), WebhookError> {
+    verify_webhook_at(
+        secret,
+        signature,
+        timestamp,
+        body,
+        tolerance_secs,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+fn verify_webhook_at(
+    secret: &[u8],
+    signature: &str,
+    timestamp: &str,
+    body: &[u8],
+    tolerance_secs: u64,
+    now: i64,
+) -> Result<(), WebhookError> {
+    let signed_at: i64 = timestamp
+        .trim()
+        .parse()
+        .map_err(|_| WebhookError::InvalidTimestamp(timestamp.to_string()))?;
+    let age_secs = now.abs_diff(signed_at);
+    if age_secs > tolerance_secs {
+        return Err(WebhookError::TimestampOutOfTolerance {
+            age_secs,
+            tolerance_secs,
+        });
+    }
+
+    let candidates: Vec<Vec<u8>> = signature
+        .split(',')
+        .filter_map(|part| part.trim().split_once('='))
+        .filter(|(version, _)| *version == SIGNATURE_VERSION)
+        .filter_map(|(_, value)| hex::decode(value).ok())
+        .collect();
+    if candidates.is_empty() {
+        return Err(WebhookError::MalformedSignature);
+    }
+
+    let matches = candidates
+        .iter()
+        .any(|candidate| mac(secret, signed_at, body).verify_slice(candidate).is_ok());
+    if matches {
+        Ok(())
+    } else {
+        Err(WebhookError::SignatureMismatch)
+    }
+}
+
+fn mac(secret: &[u8], timestamp: i64, body: &[u8]) -> HmacSha3 {
+    let mut mac = HmacSha3::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_767_225_600;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let body = br#"{"event":"seal_tampered"}"#;
+        let signature = sign_webhook(b"secret", NOW, body);
+        assert!(signature.starts_with("v1="));
+        assert_eq!(signature.len(), "v1=".len() + 64);
+
+        let timestamp = NOW.to_string();
+        let verify = |secret: &[u8], signature: &str, body: &[u8], now: i64| {
+            verify_webhook_at(secret, signature, &timestamp, body, 300, now)
+        };
+        assert_eq!(verify(b"secret", &signature, body, NOW), Ok(()));
+        assert_eq!(verify(b"secret", &signature, body, NOW + 300), Ok(()));
+
+        // Rotation: any listed signature may match
+        let rotated = format!("{}, {}", sign_webhook(b"old", NOW, body), signature);
+        assert_eq!(verify(b"secret", &rotated, body, NOW), Ok(()));
+
+        assert_eq!(
+            verify(b"other", &signature, body, NOW),
+            Err(WebhookError::SignatureMismatch)
+        );
+        assert_eq!(
+            verify(b"secret", &signature, b"{}", NOW),
+            Err(WebhookError::SignatureMismatch)
+        );
+        assert_eq!(
+            verify(b"secret", "sha3-256=00", body, NOW),
+            Err(WebhookError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn test_replayed_deliveries_are_rejected() {
+        let body = b"{}";
+        let signature = sign_webhook(b"secret", NOW, body);
+
+        assert_eq!(
+            verify_webhook_at(
+                b"secret",
+                &signature,
+                &NOW.to_string(),
+                body,
+                300,
+                NOW + 301
+            ),
+            Err(WebhookError::TimestampOutOfTolerance {
+                age_secs: 301,
+                tolerance_secs: 300
+            })
+        );
+
+        // Moving the timestamp forward breaks the signature
+        let replayed = (NOW + 600).to_string();
+        assert_eq!(
+            verify_webhook_at(b"secret", &signature, &replayed, body, 300, NOW + 600),
+            Err(WebhookError::SignatureMismatch)
+        );
+        assert!(matches!(
+            verify_webhook_at(b"secret", &signature, "yesterday", body, 300, NOW),
+            Err(WebhookError::InvalidTimestamp(_))
+        ));
+    }
+}
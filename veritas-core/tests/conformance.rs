@@ -0,0 +1,212 @@
+//! Conformance test vectors.
+//!
+//! Runs the interoperability vectors of `conformance/` (at the repository
+//! root, see its README) against veritas-core. Other implementations of the
+//! seal format run the same vectors, so every expected result here is part of
+//! the format: a failure means either a regression or a breaking change that
+//! needs a new vector format version.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use veritas_core::did::{did_key_id, did_key_public_key};
+use veritas_core::{did_key, key_id_for, ContentVerificationResult, DidDocument, VeritasSeal};
+
+/// Vector format version this harness implements.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Deserialize)]
+struct Vectors {
+    format_version: u32,
+    verified_at: u64,
+    keys: Vec<KeyVector>,
+    seals: Vec<SealVector>,
+    #[cfg_attr(not(feature = "c2pa"), allow(dead_code))]
+    c2pa: Vec<C2paVector>,
+}
+
+#[derive(Deserialize)]
+struct KeyVector {
+    id: String,
+    public_key: String,
+    key_id: String,
+    did_key: String,
+    did_key_id: String,
+    did_web_key_id: String,
+}
+
+#[derive(Deserialize)]
+struct SealVector {
+    id: String,
+    seal: String,
+    content: String,
+    expected: Expected,
+}
+
+#[derive(Deserialize)]
+struct Expected {
+    parse: bool,
+    #[serde(default)]
+    version: u8,
+    #[serde(default)]
+    key_id: Option<String>,
+    #[serde(default)]
+    issuer_did: Option<String>,
+    #[serde(default)]
+    signature: String,
+    #[serde(default)]
+    signature_at: Vec<SignatureAt>,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    tampered_segments: Option<Vec<u32>>,
+}
+
+#[derive(Deserialize)]
+struct SignatureAt {
+    timestamp_ms: u64,
+    signature: String,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(not(feature = "c2pa"), allow(dead_code))]
+struct C2paVector {
+    id: String,
+    manifest_store: String,
+    seal: Option<String>,
+}
+
+fn conformance_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../conformance")
+}
+
+fn read(path: &str) -> Vec<u8> {
+    let path = conformance_dir().join(path);
+    std::fs::read(&path).unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()))
+}
+
+fn vectors() -> Vectors {
+    serde_json::from_slice(&read("vectors.json")).expect("vectors.json is malformed")
+}
+
+/// Seal files are CBOR, or JSON when named `*.json`.
+fn load_seal(path: &str) -> veritas_core::Result<VeritasSeal> {
+    let bytes = read(path);
+    if path.ends_with(".json") {
+        VeritasSeal::from_json(&bytes)
+    } else {
+        VeritasSeal::from_cbor(&bytes)
+    }
+}
+
+#[test]
+fn test_vector_format_is_supported() {
+    let vectors = vectors();
+    assert_eq!(vectors.format_version, FORMAT_VERSION);
+    assert!(!vectors.keys.is_empty());
+    assert!(!vectors.seals.is_empty());
+}
+
+#[test]
+fn test_seal_vectors() {
+    let vectors = vectors();
+    for vector in &vectors.seals {
+        let id = &vector.id;
+        let expected = &vector.expected;
+        let seal = match load_seal(&vector.seal) {
+            Ok(seal) => seal,
+            Err(_) if !expected.parse => continue,
+            Err(e) => panic!("{id}: failed to parse: {e}"),
+        };
+        assert!(expected.parse, "{id}: parsed a seal that must be rejected");
+
+        assert_eq!(seal.version, expected.version, "{id}");
+        assert_eq!(seal.key_id, expected.key_id, "{id}");
+        assert_eq!(seal.issuer_did(), expected.issuer_did.as_deref(), "{id}");
+
+        let signature = seal.verify_detailed_at(vectors.verified_at).unwrap();
+        assert_eq!(signature.code().as_str(), expected.signature, "{id}");
+        for check in &expected.signature_at {
+            let signature = seal.verify_detailed_at(check.timestamp_ms).unwrap();
+            assert_eq!(
+                signature.code().as_str(),
+                check.signature,
+                "{id} at {}",
+                check.timestamp_ms
+            );
+        }
+
+        let content = hex::decode(&vector.content).expect("content is hex");
+        let result = seal.verify_content(&content).unwrap();
+        assert_eq!(result.code().as_str(), expected.content, "{id}");
+        if let Some(indices) = &expected.tampered_segments {
+            let tampered = match result {
+                ContentVerificationResult::ContentModified {
+                    tampered_segments: Some(segments),
+                    ..
+                } => segments.iter().map(|s| s.index).collect::<Vec<_>>(),
+                other => panic!("{id}: expected tampered segments, got {other:?}"),
+            };
+            assert_eq!(&tampered, indices, "{id}");
+        }
+    }
+}
+
+#[test]
+fn test_key_vectors() {
+    for key in vectors().keys {
+        let id = &key.id;
+        let public_key = hex::decode(&key.public_key).expect("public key is hex");
+
+        assert_eq!(key_id_for(&public_key), key.key_id, "{id}");
+        assert_eq!(did_key(&public_key), key.did_key, "{id}");
+        assert_eq!(
+            did_key_id(&key.did_key, &public_key),
+            key.did_key_id,
+            "{id}"
+        );
+        let (issuer, _) = key.did_web_key_id.split_once('#').expect("DID URL");
+        assert_eq!(did_key_id(issuer, &public_key), key.did_web_key_id, "{id}");
+
+        // did:key documents are derived from the DID alone
+        assert_eq!(
+            did_key_public_key(&key.did_key).unwrap(),
+            public_key,
+            "{id}"
+        );
+        let document = DidDocument::for_did_key(&key.did_key).unwrap();
+        let method = document
+            .assertion_key(&key.did_key_id)
+            .expect("assertion key");
+        assert_eq!(method.public_key().unwrap(), public_key, "{id}");
+    }
+}
+
+#[cfg(feature = "c2pa")]
+#[test]
+fn test_c2pa_vectors() {
+    use veritas_core::c2pa::{quantum_seal_from_manifest_json, C2paError, QuantumSealAssertion};
+
+    for vector in vectors().c2pa {
+        let id = &vector.id;
+        let manifest_store = String::from_utf8(read(&vector.manifest_store)).unwrap();
+        let assertion = quantum_seal_from_manifest_json(&manifest_store);
+        let Some(seal) = &vector.seal else {
+            assert!(
+                matches!(assertion, Err(C2paError::NoVeritasSealFound)),
+                "{id}: found a quantum seal assertion"
+            );
+            continue;
+        };
+
+        // The assertion is exactly the one we embed for the seal
+        let seal = load_seal(seal).unwrap();
+        assert!(seal.verify().unwrap(), "{id}");
+        let assertion = assertion.unwrap_or_else(|e| panic!("{id}: {e}"));
+        assert_eq!(
+            serde_json::to_value(&assertion).unwrap(),
+            serde_json::to_value(QuantumSealAssertion::from(&seal)).unwrap(),
+            "{id}"
+        );
+    }
+}
@@ -0,0 +1,491 @@
+//! Compatibility tests for historical seal formats.
+//!
+//! Each fixture in `tests/fixtures/` is a real seal of an older (or the
+//! current) format version, generated by `scripts/generate-seal-fixtures.py`.
+//! Fixtures must never be regenerated: they stand in for evidence sealed by
+//! earlier releases, which has to keep loading and verifying forever.
+
+use veritas_core::{
+    key_id_for, liveness_code, AiVerdict, CertificateStatus, ContentVerificationResult,
+    CounterSignerRole, KeyResolution, MediaType, QrngConditioning, QrngPolicy, QrngSource,
+    SealField, SigningKey, SigningKeyStatus, TrustedProviderKeys, VerificationResult, VeritasSeal,
+    CURRENT_SEAL_VERSION,
+};
+
+/// Content sealed by every fixture.
+const FIXTURE_CONTENT: &[u8] = b"Veritas Q seal format fixture";
+
+const SEAL_V1_LEGACY: &[u8] = include_bytes!("fixtures/seal_v1_legacy.cbor");
+const SEAL_V1: &[u8] = include_bytes!("fixtures/seal_v1.cbor");
+const SEAL_V2: &[u8] = include_bytes!("fixtures/seal_v2.cbor");
+const SEAL_V3: &[u8] = include_bytes!("fixtures/seal_v3.cbor");
+const SEAL_V4: &[u8] = include_bytes!("fixtures/seal_v4.cbor");
+const SEAL_V5_REDACTED: &[u8] = include_bytes!("fixtures/seal_v5_redacted.cbor");
+const SEAL_V6_SEGMENTED: &[u8] = include_bytes!("fixtures/seal_v6_segmented.cbor");
+const SEAL_V7_SEGMENT_DIGESTS: &[u8] = include_bytes!("fixtures/seal_v7_segment_digests.cbor");
+const SEAL_V8_DOCUMENT: &[u8] = include_bytes!("fixtures/seal_v8_document.cbor");
+const SEAL_V9_CUSTOM_MEDIA: &[u8] = include_bytes!("fixtures/seal_v9_custom_media.cbor");
+const SEAL_V10_POOLED_ENTROPY: &[u8] = include_bytes!("fixtures/seal_v10_pooled_entropy.cbor");
+const SEAL_V11_VALIDITY: &[u8] = include_bytes!("fixtures/seal_v11_validity.cbor");
+const SEAL_V12_REGIONS: &[u8] = include_bytes!("fixtures/seal_v12_regions.cbor");
+const SEAL_V13_AI_DETECTION: &[u8] = include_bytes!("fixtures/seal_v13_ai_detection.cbor");
+const SEAL_V14_CAPTURE_NONCE: &[u8] = include_bytes!("fixtures/seal_v14_capture_nonce.cbor");
+const SEAL_V15_LIVENESS: &[u8] = include_bytes!("fixtures/seal_v15_liveness.cbor");
+const SEAL_V16_KEY_ID: &[u8] = include_bytes!("fixtures/seal_v16_key_id.cbor");
+const SEAL_V17_RAW_ENTROPY_HASH: &[u8] = include_bytes!("fixtures/seal_v17_raw_entropy_hash.cbor");
+
+/// (name, bytes, expected version) for every historical fixture.
+const FIXTURES: &[(&str, &[u8], u8)] = &[
+    ("v1 legacy", SEAL_V1_LEGACY, 1),
+    ("v1", SEAL_V1, 1),
+    ("v2", SEAL_V2, 2),
+    ("v3", SEAL_V3, 3),
+    ("v4", SEAL_V4, 4),
+    ("v5 redacted", SEAL_V5_REDACTED, 5),
+    ("v6 segmented", SEAL_V6_SEGMENTED, 6),
+    ("v7 segment digests", SEAL_V7_SEGMENT_DIGESTS, 7),
+    ("v8 document", SEAL_V8_DOCUMENT, 8),
+    ("v9 custom media", SEAL_V9_CUSTOM_MEDIA, 9),
+    ("v10 pooled entropy", SEAL_V10_POOLED_ENTROPY, 10),
+    ("v11 validity", SEAL_V11_VALIDITY, 11),
+    ("v12 regions", SEAL_V12_REGIONS, 12),
+    ("v13 AI detection", SEAL_V13_AI_DETECTION, 13),
+    ("v14 capture nonce", SEAL_V14_CAPTURE_NONCE, 14),
+    ("v15 liveness", SEAL_V15_LIVENESS, 15),
+    ("v16 key ID", SEAL_V16_KEY_ID, 16),
+    ("v17 raw entropy hash", SEAL_V17_RAW_ENTROPY_HASH, 17),
+];
+
+#[test]
+fn test_fixtures_cover_every_version() {
+    for version in 1..=CURRENT_SEAL_VERSION {
+        assert!(
+            FIXTURES.iter().any(|(_, _, v)| *v == version),
+            "missing compatibility fixture for seal version {version}"
+        );
+    }
+}
+
+#[test]
+fn test_fixtures_load_and_verify() {
+    for (name, bytes, version) in FIXTURES {
+        let seal = VeritasSeal::from_cbor(bytes)
+            .unwrap_or_else(|e| panic!("{name} fixture failed to load: {e}"));
+
+        assert_eq!(seal.version, *version, "{name}");
+        let expected_media_type = match version {
+            8 => MediaType::Document,
+            9 => MediaType::Custom("model/gltf-binary".into()),
+            _ => MediaType::Image,
+        };
+        assert_eq!(seal.media_type, expected_media_type, "{name}");
+        assert!(seal.verify().unwrap(), "{name} fixture signature invalid");
+        assert_eq!(
+            seal.verify_content(FIXTURE_CONTENT).unwrap(),
+            ContentVerificationResult::Authentic,
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_fixtures_migrate_to_latest() {
+    for (name, bytes, _) in FIXTURES {
+        let migrated = VeritasSeal::from_cbor(bytes)
+            .unwrap()
+            .migrate_to_latest()
+            .unwrap_or_else(|e| panic!("{name} fixture failed to migrate: {e}"));
+
+        assert!(migrated.is_latest_version(), "{name}");
+        assert!(
+            migrated.verify().unwrap(),
+            "{name} signature broken by migration"
+        );
+
+        // A migrated seal survives a round trip through the current format
+        let reloaded = VeritasSeal::from_cbor(&migrated.to_cbor().unwrap()).unwrap();
+        assert_eq!(reloaded.version, CURRENT_SEAL_VERSION, "{name}");
+        assert!(reloaded.verify().unwrap(), "{name}");
+    }
+}
+
+#[test]
+fn test_v1_legacy_fixture_has_no_version_field() {
+    let seal = VeritasSeal::from_cbor(SEAL_V1_LEGACY).unwrap();
+    assert_eq!(seal.version, 1);
+    assert_eq!(seal.qrng_source, QrngSource::LfdCloud);
+    assert!(seal.qrng_conditioning.is_none());
+    assert!(seal.entropy_certificate.is_none());
+}
+
+#[test]
+fn test_v2_fixture_fields() {
+    let seal = VeritasSeal::from_cbor(SEAL_V2).unwrap();
+    assert_eq!(seal.qrng_conditioning, Some(QrngConditioning::Sha3OsMix));
+
+    let certificate = seal
+        .entropy_certificate
+        .as_ref()
+        .expect("v2 fixture carries a certificate");
+    assert_eq!(certificate.source, QrngSource::LfdCloud);
+
+    // Validly signed, but the v2 seal cannot tie it to its conditioned entropy
+    let trusted = TrustedProviderKeys::new()
+        .with_key(QrngSource::LfdCloud, certificate.signer_public_key.clone());
+    assert_eq!(
+        certificate.verify(&trusted).unwrap(),
+        CertificateStatus::Trusted
+    );
+    assert_eq!(
+        seal.entropy_certificate_status(&trusted).unwrap(),
+        Some(CertificateStatus::Unbound)
+    );
+}
+
+#[test]
+fn test_v3_fixture_countersignatures() {
+    let seal = VeritasSeal::from_cbor(SEAL_V3).unwrap();
+    let roles: Vec<_> = seal.countersignatures.iter().map(|c| &c.role).collect();
+    assert_eq!(
+        roles,
+        [
+            &CounterSignerRole::Notary,
+            &CounterSignerRole::Custom("archivist".into())
+        ]
+    );
+    assert_eq!(seal.verify_countersignatures().unwrap(), vec![true, true]);
+}
+
+#[test]
+fn test_v4_fixture_threshold() {
+    let seal = VeritasSeal::from_cbor(SEAL_V4).unwrap();
+    let status = seal
+        .threshold_status()
+        .unwrap()
+        .expect("v4 fixture is a threshold seal");
+    assert_eq!((status.valid, status.required, status.signers), (2, 2, 3));
+    assert!(status.is_met());
+}
+
+#[test]
+fn test_v5_redacted_fixture() {
+    let seal = VeritasSeal::from_cbor(SEAL_V5_REDACTED).unwrap();
+    assert!(seal.capture_location.is_none());
+    assert_eq!(
+        seal.disclosure.as_ref().unwrap().redacted_fields(),
+        vec![SealField::CaptureLocation]
+    );
+
+    let proof = seal.disclosure_proof(SealField::CaptureTimestamp).unwrap();
+    assert!(proof.verify().unwrap());
+    assert_eq!(proof.value::<u64>().unwrap(), seal.capture_timestamp_utc);
+
+    // Fields added after v5 were never committed
+    assert!(seal.disclosure_proof(SealField::QrngPolicy).is_err());
+}
+
+#[test]
+fn test_v6_segmented_fixture() {
+    let seal = VeritasSeal::from_cbor(SEAL_V6_SEGMENTED).unwrap();
+    let manifest = seal.content_hash.segments.as_ref().unwrap();
+    assert_eq!(manifest.segment_size, 8);
+    assert_eq!(manifest.segment_count, 4);
+    assert_eq!(manifest.time_range_ms(1), Some(250..500));
+
+    let proof = seal.clip_proof(FIXTURE_CONTENT, 1..3).unwrap();
+    let clip = seal.clip_bytes(FIXTURE_CONTENT, 1..3).unwrap();
+    assert_eq!(clip, &FIXTURE_CONTENT[8..24]);
+    assert!(seal.verify_clip(clip, &proof).unwrap().is_authentic());
+}
+
+#[test]
+fn test_v7_fixture_localizes_tampering() {
+    let seal = VeritasSeal::from_cbor(SEAL_V7_SEGMENT_DIGESTS).unwrap();
+    assert_eq!(
+        seal.content_hash.segments.as_ref().unwrap().digests.len(),
+        4
+    );
+
+    let mut tampered = FIXTURE_CONTENT.to_vec();
+    tampered[10] ^= 1;
+    let ContentVerificationResult::ContentModified {
+        tampered_segments: Some(segments),
+        ..
+    } = seal.verify_content(&tampered).unwrap()
+    else {
+        panic!("expected localized tampering");
+    };
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].index, 1);
+    assert_eq!(segments[0].byte_range, 8..16);
+    assert_eq!(segments[0].time_range_ms, Some(250..500));
+
+    // v6 seals predate the digests and only report the mismatch
+    let v6 = VeritasSeal::from_cbor(SEAL_V6_SEGMENTED).unwrap();
+    assert!(matches!(
+        v6.verify_content(&tampered).unwrap(),
+        ContentVerificationResult::ContentModified {
+            tampered_segments: None,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_v8_fixture_has_canonical_hash() {
+    let seal = VeritasSeal::from_cbor(SEAL_V8_DOCUMENT).unwrap();
+    assert_eq!(seal.media_type, MediaType::Document);
+    assert!(seal.content_hash.canonical_hash.is_some());
+
+    // The fixture content is not a PDF, so only the exact bytes verify
+    assert!(!seal.matches_canonical_form(FIXTURE_CONTENT));
+    assert!(!seal
+        .verify_content(b"Veritas Q seal format fixturE")
+        .unwrap()
+        .is_authentic());
+
+    // Older schemas reject the document fields
+    let mut downgraded = seal.clone();
+    downgraded.version = 7;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v9_fixture_custom_media_type() {
+    let seal = VeritasSeal::from_cbor(SEAL_V9_CUSTOM_MEDIA).unwrap();
+    assert_eq!(
+        seal.media_type,
+        MediaType::Custom("model/gltf-binary".into())
+    );
+
+    assert_eq!(seal.media_type.default_mime(), "model/gltf-binary");
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 8;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v10_fixture_qrng_policy() {
+    let seal = VeritasSeal::from_cbor(SEAL_V10_POOLED_ENTROPY).unwrap();
+    assert_eq!(
+        seal.qrng_policy,
+        Some(QrngPolicy::Pooled { max_age_secs: 300 })
+    );
+
+    // Older fixtures did not record a policy
+    assert!(VeritasSeal::from_cbor(SEAL_V9_CUSTOM_MEDIA)
+        .unwrap()
+        .qrng_policy
+        .is_none());
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 9;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v11_fixture_validity_window() {
+    let seal = VeritasSeal::from_cbor(SEAL_V11_VALIDITY).unwrap();
+    assert_eq!(seal.not_before, Some(seal.capture_timestamp_utc));
+    let not_after = seal.not_after.expect("v11 fixture has a window");
+
+    assert_eq!(
+        seal.verify_detailed_at(seal.capture_timestamp_utc - 1)
+            .unwrap(),
+        VerificationResult::Expired
+    );
+    assert_eq!(
+        seal.verify_detailed_at(not_after + 1).unwrap(),
+        VerificationResult::Expired
+    );
+
+    // Older fixtures have no window
+    assert!(VeritasSeal::from_cbor(SEAL_V10_POOLED_ENTROPY)
+        .unwrap()
+        .not_after
+        .is_none());
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 10;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v12_fixture_region_hashes() {
+    let seal = VeritasSeal::from_cbor(SEAL_V12_REGIONS).unwrap();
+    let regions = seal
+        .content_hash
+        .region_hashes
+        .as_ref()
+        .expect("v12 fixture has region hashes");
+    assert_eq!(regions.grid, 4);
+    assert_eq!(regions.tiles.len(), 16);
+    assert_eq!(regions.tiles[0], 0x0123_4567_89AB_CDEF);
+
+    // Region hashes are signed with the rest of the content hash
+    let mut tampered = seal.clone();
+    if let Some(regions) = tampered.content_hash.region_hashes.as_mut() {
+        regions.tiles[0] ^= 1;
+    }
+    assert!(!tampered.verify().unwrap());
+
+    // The fixture content is not an image: no regions to compare
+    #[cfg(feature = "perceptual-hash")]
+    assert!(seal.region_comparison(FIXTURE_CONTENT).is_none());
+
+    assert!(VeritasSeal::from_cbor(SEAL_V11_VALIDITY)
+        .unwrap()
+        .content_hash
+        .region_hashes
+        .is_none());
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 11;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v13_fixture_ai_detections() {
+    let seal = VeritasSeal::from_cbor(SEAL_V13_AI_DETECTION).unwrap();
+    assert_eq!(seal.ai_detections.len(), 2);
+    let synthid = &seal.ai_detections[0].verdict;
+    assert_eq!(synthid.detector, "synthid");
+    assert_eq!(synthid.detector_version, "2.0");
+    assert_eq!(synthid.score_bps, 9_400);
+    assert_eq!(synthid.verdict, AiVerdict::AiGenerated);
+    assert_eq!(
+        seal.ai_detections[1].verdict.verdict,
+        AiVerdict::Inconclusive
+    );
+
+    assert!(seal.verify().unwrap());
+    assert_eq!(seal.verify_ai_detections().unwrap(), vec![true, true]);
+
+    // Verdicts are signed by the detector operator, not covered by the seal
+    let mut tampered = seal.clone();
+    tampered.ai_detections[0].verdict.verdict = AiVerdict::Human;
+    assert!(tampered.verify().unwrap());
+    assert_eq!(tampered.verify_ai_detections().unwrap(), vec![false, true]);
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 12;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v14_fixture_capture_nonce() {
+    let seal = VeritasSeal::from_cbor(SEAL_V14_CAPTURE_NONCE).unwrap();
+    let nonce = seal.capture_nonce.expect("v14 fixture has a capture nonce");
+    assert_eq!(nonce[..4], [0xa9, 0x04, 0x3b, 0x6c]);
+
+    // The nonce is signed: moving the media to another session breaks the seal
+    let mut tampered = seal.clone();
+    tampered.capture_nonce = Some([0; 32]);
+    assert!(!tampered.verify().unwrap());
+
+    assert!(VeritasSeal::from_cbor(SEAL_V13_AI_DETECTION)
+        .unwrap()
+        .capture_nonce
+        .is_none());
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 13;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v15_fixture_liveness() {
+    let seal = VeritasSeal::from_cbor(SEAL_V15_LIVENESS).unwrap();
+    let liveness = seal
+        .liveness
+        .clone()
+        .expect("v15 fixture has a liveness challenge");
+    assert_eq!(liveness.code, "009384");
+    assert_eq!(
+        liveness.code,
+        liveness_code(&seal.capture_nonce.expect("liveness needs a capture nonce"))
+    );
+    assert_eq!(liveness.response_time_ms(), 5_000);
+    assert_eq!(seal.verify_liveness(), Some(true));
+
+    // The answer is signed: claiming a quicker response breaks the seal
+    let mut tampered = seal.clone();
+    tampered.liveness.as_mut().unwrap().responded_at -= 4_000;
+    assert!(!tampered.verify().unwrap());
+
+    assert_eq!(
+        VeritasSeal::from_cbor(SEAL_V14_CAPTURE_NONCE)
+            .unwrap()
+            .verify_liveness(),
+        None
+    );
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 14;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v16_fixture_key_id() {
+    let seal = VeritasSeal::from_cbor(SEAL_V16_KEY_ID).unwrap();
+    let key_id = seal.key_id.clone().expect("v16 fixture has a key ID");
+    assert_eq!(key_id, key_id_for(&seal.public_key));
+
+    // Retired keys keep vouching for the seals made while they were valid
+    let registered = SigningKey {
+        status: SigningKeyStatus::Retired,
+        valid_until: Some(seal.capture_timestamp_utc + 1),
+        ..SigningKey::new(seal.public_key.clone(), seal.capture_timestamp_utc - 1)
+    };
+    assert_eq!(
+        seal.resolve_signing_key(Some(&registered)),
+        KeyResolution::Trusted
+    );
+    assert_eq!(
+        VeritasSeal::from_cbor(SEAL_V15_LIVENESS)
+            .unwrap()
+            .resolve_signing_key(Some(&registered)),
+        KeyResolution::Embedded
+    );
+
+    // The key ID is signed
+    let mut tampered = seal.clone();
+    tampered.key_id = Some("0123456789abcdef".to_string());
+    assert!(!tampered.verify().unwrap());
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 15;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
+
+#[test]
+fn test_v17_fixture_raw_entropy_hash() {
+    let seal = VeritasSeal::from_cbor(SEAL_V17_RAW_ENTROPY_HASH).unwrap();
+    let certificate = seal
+        .entropy_certificate
+        .as_ref()
+        .expect("v17 fixture carries a certificate");
+    assert_eq!(seal.raw_entropy_hash, Some(certificate.entropy_hash()));
+
+    let trusted = TrustedProviderKeys::new()
+        .with_key(QrngSource::LfdCloud, certificate.signer_public_key.clone());
+    assert_eq!(
+        seal.entropy_certificate_status(&trusted).unwrap(),
+        Some(CertificateStatus::Trusted)
+    );
+    assert_eq!(
+        seal.entropy_certificate_status(&TrustedProviderKeys::new())
+            .unwrap(),
+        Some(CertificateStatus::UntrustedSigner)
+    );
+
+    // The hash is signed
+    let mut tampered = seal.clone();
+    tampered.raw_entropy_hash = Some([0x42; 32]);
+    assert!(!tampered.verify().unwrap());
+
+    let mut downgraded = seal.clone();
+    downgraded.version = 16;
+    assert!(VeritasSeal::from_cbor(&downgraded.to_cbor().unwrap()).is_err());
+}
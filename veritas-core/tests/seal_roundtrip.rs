@@ -0,0 +1,227 @@
+//! Property-based round-trip tests for seal serialization.
+//!
+//! Seals are built from arbitrary combinations of the optional fields
+//! (location, device attestation, blockchain anchors, QRNG policy) for every
+//! media type, then pushed through CBOR and JSON. Decoding and re-encoding
+//! must give back the same bytes, and the signature must keep verifying:
+//! a serde attribute that drops or renames a field on one side breaks both.
+
+#![cfg(feature = "network")]
+
+use proptest::prelude::*;
+use veritas_core::anchor::ProofStep;
+use veritas_core::{
+    generate_keypair, BatchProof, BlockchainAnchor, ContentVerificationResult, DeviceAttestation,
+    MediaType, MockQrng, QrngPolicy, SealBuilder, VeritasSeal,
+};
+
+/// Signed seals are slow to build; fewer cases still cover every field mix.
+const CASES: u32 = 64;
+
+/// Optional fields of a generated seal
+#[derive(Debug, Clone)]
+struct SealFields {
+    content: Vec<u8>,
+    media_type: MediaType,
+    location: Option<String>,
+    attestation: Option<DeviceAttestation>,
+    anchor: Option<BlockchainAnchor>,
+    additional_anchors: Vec<BlockchainAnchor>,
+    qrng_policy: Option<QrngPolicy>,
+}
+
+fn media_type() -> impl Strategy<Value = MediaType> {
+    prop_oneof![
+        Just(MediaType::Image),
+        Just(MediaType::Video),
+        Just(MediaType::Audio),
+        Just(MediaType::Document),
+        Just(MediaType::Generic),
+        "(model|application|text)/[a-z0-9][a-z0-9.+-]{0,30}".prop_map(MediaType::Custom),
+    ]
+}
+
+fn attestation() -> impl Strategy<Value = DeviceAttestation> {
+    (
+        "[A-Za-z0-9-]{1,36}",
+        prop_oneof![
+            Just("ARM_TRUSTZONE".to_string()),
+            Just("APPLE_SECURE_ENCLAVE".to_string()),
+            Just("TPM_2_0".to_string()),
+        ],
+        prop::collection::vec(any::<u8>(), 0..128),
+    )
+        .prop_map(
+            |(device_id, tee_type, attestation_token)| DeviceAttestation {
+                device_id,
+                tee_type,
+                attestation_token,
+            },
+        )
+}
+
+fn anchor() -> impl Strategy<Value = BlockchainAnchor> {
+    (
+        prop_oneof![
+            Just("solana-mainnet"),
+            Just("solana-devnet"),
+            Just("bitcoin")
+        ],
+        "[1-9A-HJ-NP-Za-km-z]{32,88}",
+        any::<u64>(),
+        prop::option::of(prop::collection::vec(any::<u8>(), 1..256)),
+        prop::option::of(batch_proof()),
+    )
+        .prop_map(
+            |(chain, tx_id, block_height, proof, batch)| BlockchainAnchor {
+                chain: chain.to_string(),
+                tx_id,
+                block_height,
+                proof,
+                batch,
+            },
+        )
+}
+
+fn batch_proof() -> impl Strategy<Value = BatchProof> {
+    (
+        any::<[u8; 32]>(),
+        prop::collection::vec((any::<[u8; 32]>(), any::<bool>()), 0..20),
+    )
+        .prop_map(|(root, path)| BatchProof {
+            root,
+            path: path
+                .into_iter()
+                .map(|(sibling, sibling_is_left)| ProofStep {
+                    sibling,
+                    sibling_is_left,
+                })
+                .collect(),
+        })
+}
+
+fn qrng_policy() -> impl Strategy<Value = QrngPolicy> {
+    prop_oneof![
+        Just(QrngPolicy::FreshPerSeal),
+        (1..86_400u64).prop_map(|max_age_secs| QrngPolicy::Pooled { max_age_secs }),
+        (1..86_400u64).prop_map(|max_age_secs| QrngPolicy::Mixed { max_age_secs }),
+    ]
+}
+
+fn seal_fields() -> impl Strategy<Value = SealFields> {
+    (
+        prop::collection::vec(any::<u8>(), 1..512),
+        media_type(),
+        prop::option::of("[0-9b-hjkmnp-z]{1,12}"),
+        prop::option::of(attestation()),
+        prop::option::of(anchor()),
+        prop::collection::vec(anchor(), 0..3),
+        prop::option::of(qrng_policy()),
+    )
+        .prop_map(
+            |(
+                content,
+                media_type,
+                location,
+                attestation,
+                anchor,
+                additional_anchors,
+                qrng_policy,
+            )| {
+                SealFields {
+                    content,
+                    media_type,
+                    location,
+                    attestation,
+                    anchor,
+                    additional_anchors,
+                    qrng_policy,
+                }
+            },
+        )
+}
+
+/// Build and sign a seal with the given fields; the anchors are added after
+/// signing, as anchoring does.
+fn build_seal(fields: &SealFields) -> VeritasSeal {
+    let mut builder = SealBuilder::new(fields.content.clone(), fields.media_type.clone());
+    if let Some(location) = &fields.location {
+        builder = builder.with_location(location.clone());
+    }
+    if let Some(attestation) = &fields.attestation {
+        builder = builder.with_attestation(attestation.clone());
+    }
+    if let Some(policy) = fields.qrng_policy {
+        builder = builder.with_qrng_policy(policy);
+    }
+
+    let (public_key, secret_key) = generate_keypair();
+    let mut seal = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("Failed to start runtime")
+        .block_on(builder.build_secure(&MockQrng::default(), &secret_key, &public_key))
+        .expect("Failed to create seal");
+    seal.blockchain_anchor = fields.anchor.clone();
+    seal.additional_anchors = fields.additional_anchors.clone();
+    seal
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn cbor_roundtrip_is_lossless(fields in seal_fields()) {
+        let seal = build_seal(&fields);
+
+        let cbor = seal.to_cbor().unwrap();
+        let decoded = VeritasSeal::from_cbor(&cbor).unwrap();
+        prop_assert_eq!(decoded.to_cbor().unwrap(), cbor);
+        prop_assert_eq!(
+            decoded.to_json_canonical().unwrap(),
+            seal.to_json_canonical().unwrap()
+        );
+        prop_assert!(decoded.verify().unwrap());
+        prop_assert_eq!(
+            decoded.verify_content(&fields.content).unwrap(),
+            ContentVerificationResult::Authentic
+        );
+    }
+
+    #[test]
+    fn json_roundtrip_is_lossless(fields in seal_fields()) {
+        let seal = build_seal(&fields);
+        let canonical = seal.to_json_canonical().unwrap();
+
+        for json in [canonical.clone(), seal.to_json_pretty().unwrap()] {
+            let decoded = VeritasSeal::from_json(json.as_bytes()).unwrap();
+            prop_assert_eq!(decoded.to_json_canonical().unwrap(), canonical.clone());
+            prop_assert_eq!(decoded.to_cbor().unwrap(), seal.to_cbor().unwrap());
+            prop_assert!(decoded.verify().unwrap());
+        }
+    }
+
+    #[test]
+    fn verification_is_stable_across_formats(fields in seal_fields()) {
+        let seal = build_seal(&fields);
+        let expected = seal.verify_detailed().unwrap();
+        prop_assert!(expected.is_valid());
+
+        // CBOR -> JSON -> CBOR, and anchors never affect the signature
+        let via_json = VeritasSeal::from_json(
+            VeritasSeal::from_cbor(&seal.to_cbor().unwrap())
+                .unwrap()
+                .to_json_canonical()
+                .unwrap()
+                .as_bytes(),
+        )
+        .unwrap();
+        let mut unanchored = VeritasSeal::from_cbor(&via_json.to_cbor().unwrap()).unwrap();
+        unanchored.blockchain_anchor = None;
+        unanchored.additional_anchors.clear();
+
+        prop_assert_eq!(via_json.verify_detailed().unwrap(), expected.clone());
+        prop_assert_eq!(unanchored.verify_detailed().unwrap(), expected);
+        prop_assert_eq!(via_json.seal_hash(), seal.seal_hash());
+        prop_assert_eq!(unanchored.anchor_digest(), seal.anchor_digest());
+    }
+}
@@ -474,3 +474,56 @@ fn test_completely_different_images() {
         distance
     );
 }
+
+// ============================================================================
+// Graded Verification Tests
+// ============================================================================
+
+/// Encode an image as PNG bytes.
+fn encode_png(img: &DynamicImage) -> Vec<u8> {
+    let mut buffer = Cursor::new(Vec::new());
+    img.write_to(&mut buffer, image::ImageFormat::Png)
+        .expect("PNG encoding failed");
+    buffer.into_inner()
+}
+
+#[tokio::test]
+async fn test_graded_verification_of_recompressed_image() {
+    use veritas_core::{
+        generate_keypair, ContentGrade, MediaType, MockQrng, SealBuilder,
+        RECOMPRESSION_MAX_DISTANCE,
+    };
+
+    let original = DynamicImage::ImageRgb8(create_test_image(256, 256));
+    let sealed = encode_png(&original);
+    let (public_key, secret_key) = generate_keypair();
+    let seal = SealBuilder::new(sealed.clone(), MediaType::Image)
+        .build_secure(&MockQrng::default(), &secret_key, &public_key)
+        .await
+        .expect("Failed to create seal");
+
+    let (_, grade) = seal
+        .verify_content_graded(&sealed, RECOMPRESSION_MAX_DISTANCE)
+        .expect("Verification failed");
+    assert_eq!(grade, Some(ContentGrade::Authentic));
+
+    let recompressed = encode_png(&compress_jpeg(&original, 80));
+    let (result, grade) = seal
+        .verify_content_graded(&recompressed, RECOMPRESSION_MAX_DISTANCE)
+        .expect("Verification failed");
+    assert!(!result.is_authentic());
+    let Some(ContentGrade::LikelyRecompressed { distance }) = grade else {
+        panic!("Recompressed image should be graded likely recompressed (got: {grade:?})");
+    };
+    println!("Recompressed image - Hamming distance: {}", distance);
+
+    let other = encode_png(&DynamicImage::ImageRgb8(ImageBuffer::from_pixel(
+        256,
+        256,
+        Rgb([0, 0, 0]),
+    )));
+    let (_, grade) = seal
+        .verify_content_graded(&other, RECOMPRESSION_MAX_DISTANCE)
+        .expect("Verification failed");
+    assert_eq!(grade, Some(ContentGrade::Modified));
+}
@@ -0,0 +1,17 @@
+//! Regenerates `include/veritas.h` from the `extern "C"` API.
+
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+    match cbindgen::generate(&crate_dir) {
+        Ok(bindings) => {
+            bindings.write_to_file(Path::new(&crate_dir).join("include/veritas.h"));
+        }
+        // Keep building the library; the checked-in header stays as it was
+        Err(e) => println!("cargo:warning=Failed to generate veritas.h: {e}"),
+    }
+}
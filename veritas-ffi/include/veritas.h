@@ -0,0 +1,232 @@
+/* Veritas Q C API. Generated by cbindgen from veritas-ffi: do not edit. */
+
+#ifndef VERITAS_H
+#define VERITAS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Size of an ML-DSA-65 public key, in bytes
+ */
+#define VERITAS_PUBLIC_KEY_BYTES 1952
+
+/**
+ * Size of an ML-DSA-65 secret key, in bytes
+ */
+#define VERITAS_SECRET_KEY_BYTES 4032
+
+/**
+ * Size of a perceptual hash, in bytes
+ */
+#define VERITAS_PHASH_SIZE 8
+
+/**
+ * Entropy source for sealing.
+ *
+ * Passed to [`veritas_seal`] as a plain `int32_t`, so a value from a newer
+ * header or a corrupted one is rejected rather than undefined behaviour.
+ */
+enum VeritasQrng {
+  /**
+   * LfD quantum random number generator (network)
+   */
+  VERITAS_QRNG_LFD = 0,
+  /**
+   * Deterministic mock entropy: for tests only, never in production
+   */
+  VERITAS_QRNG_MOCK = 1,
+};
+typedef int32_t VeritasQrng;
+
+/**
+ * Status returned by every fallible function.
+ *
+ * Values are part of the ABI: never renumber them, only append.
+ */
+enum VeritasStatus {
+  /**
+   * Success
+   */
+  VERITAS_STATUS_OK = 0,
+  /**
+   * A required pointer argument was null
+   */
+  VERITAS_STATUS_NULL_POINTER = 1,
+  /**
+   * An argument was malformed (e.g. unknown media type, non-UTF-8 string)
+   */
+  VERITAS_STATUS_INVALID_ARGUMENT = 2,
+  /**
+   * The seal could not be parsed, or is not a supported seal
+   */
+  VERITAS_STATUS_INVALID_SEAL = 3,
+  /**
+   * A key has the wrong length or encoding
+   */
+  VERITAS_STATUS_INVALID_KEY = 4,
+  /**
+   * No quantum entropy could be obtained
+   */
+  VERITAS_STATUS_QRNG_UNAVAILABLE = 5,
+  /**
+   * Building or signing the seal failed
+   */
+  VERITAS_STATUS_SIGNING_FAILED = 6,
+  /**
+   * The data is not a decodable image
+   */
+  VERITAS_STATUS_PERCEPTUAL_HASH_FAILED = 7,
+  /**
+   * Any other failure
+   */
+  VERITAS_STATUS_INTERNAL = 8,
+  /**
+   * The library panicked; the call had no effect on outputs
+   */
+  VERITAS_STATUS_PANIC = 9,
+};
+typedef int32_t VeritasStatus;
+
+/**
+ * Outcome of verifying content against a seal.
+ */
+enum VeritasVerification {
+  /**
+   * Valid signature and the content is what was sealed
+   */
+  VERITAS_VERIFICATION_AUTHENTIC = 0,
+  /**
+   * Valid signature, but the content differs from what was sealed
+   */
+  VERITAS_VERIFICATION_CONTENT_MODIFIED = 1,
+  /**
+   * The seal's signature is invalid
+   */
+  VERITAS_VERIFICATION_SIGNATURE_INVALID = 2,
+};
+typedef int32_t VeritasVerification;
+
+/**
+ * Bytes allocated by the library; release with [`veritas_buffer_free`].
+ */
+typedef struct VeritasBuffer {
+  /**
+   * Start of the bytes (null for an empty buffer)
+   */
+  uint8_t *data;
+  /**
+   * Number of bytes
+   */
+  size_t len;
+} VeritasBuffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Library version, as a static NUL-terminated string.
+ */
+const char *veritas_version(void);
+
+/**
+ * Message describing the last failed call on this thread, or null if the
+ * last call succeeded.
+ *
+ * The string is valid until the next call into the library on this thread.
+ */
+const char *veritas_last_error_message(void);
+
+/**
+ * Release a buffer returned by the library. Null buffers are ignored.
+ *
+ * # Safety
+ *
+ * `buffer` must come from this library and not have been freed already.
+ */
+void veritas_buffer_free(struct VeritasBuffer buffer);
+
+/**
+ * Generate an ML-DSA-65 signing keypair.
+ *
+ * # Safety
+ *
+ * `out_public_key` must point to `VERITAS_PUBLIC_KEY_BYTES` writable bytes
+ * and `out_secret_key` to `VERITAS_SECRET_KEY_BYTES`. The caller owns the
+ * secret key and should wipe it once done.
+ */
+VeritasStatus veritas_generate_keypair(uint8_t *out_public_key, uint8_t *out_secret_key);
+
+/**
+ * Seal `content`, returning the seal as CBOR in `out_seal`.
+ *
+ * `media_type` is `image`, `video`, `audio`, `document`, `generic` or a
+ * MIME type; null means `generic`. `qrng` selects the entropy source, a
+ * [`VeritasQrng`] value; others are [`VeritasStatus::InvalidArgument`].
+ *
+ * # Safety
+ *
+ * Input pointers must be valid for reads of their lengths (or null with a
+ * zero length), `media_type` null or a NUL-terminated string and
+ * `out_seal` valid for writes. The seal must be released with
+ * [`veritas_buffer_free`].
+ */
+VeritasStatus veritas_seal(const uint8_t *content,
+                           size_t content_len,
+                           const char *media_type,
+                           const uint8_t *secret_key,
+                           size_t secret_key_len,
+                           const uint8_t *public_key,
+                           size_t public_key_len,
+                           int32_t qrng,
+                           struct VeritasBuffer *out_seal);
+
+/**
+ * Verify `content` against a seal (CBOR or JSON).
+ *
+ * A seal that cannot be parsed is an error ([`VeritasStatus::InvalidSeal`]);
+ * a parsed seal's verdict is written to `out_result`.
+ *
+ * # Safety
+ *
+ * Input pointers must be valid for reads of their lengths (or null with a
+ * zero length) and `out_result` valid for writes.
+ */
+VeritasStatus veritas_verify(const uint8_t *content,
+                             size_t content_len,
+                             const uint8_t *seal,
+                             size_t seal_len,
+                             VeritasVerification *out_result);
+
+/**
+ * Compute the perceptual hash of an image (JPEG, PNG, GIF or WebP).
+ *
+ * # Safety
+ *
+ * `image` must be valid for reads of `image_len` bytes and `out_hash` for
+ * writes of `VERITAS_PHASH_SIZE` bytes.
+ */
+VeritasStatus veritas_phash(const uint8_t *image, size_t image_len, uint8_t *out_hash);
+
+/**
+ * Number of differing bits between two perceptual hashes (0 to 64).
+ *
+ * Visually similar images are within a few bits of each other.
+ *
+ * # Safety
+ *
+ * `hash_a` and `hash_b` must be valid for reads of `VERITAS_PHASH_SIZE`
+ * bytes and `out_distance` valid for writes.
+ */
+VeritasStatus veritas_phash_distance(const uint8_t *hash_a,
+                                     const uint8_t *hash_b,
+                                     uint32_t *out_distance);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* VERITAS_H */
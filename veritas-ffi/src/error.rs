@@ -0,0 +1,120 @@
+//! Status codes and the per-thread last error message.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+
+use veritas_core::VeritasError;
+
+/// Status returned by every fallible function.
+///
+/// Values are part of the ABI: never renumber them, only append.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VeritasStatus {
+    /// Success
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// An argument was malformed (e.g. unknown media type, non-UTF-8 string)
+    InvalidArgument = 2,
+    /// The seal could not be parsed, or is not a supported seal
+    InvalidSeal = 3,
+    /// A key has the wrong length or encoding
+    InvalidKey = 4,
+    /// No quantum entropy could be obtained
+    QrngUnavailable = 5,
+    /// Building or signing the seal failed
+    SigningFailed = 6,
+    /// The data is not a decodable image
+    PerceptualHashFailed = 7,
+    /// Any other failure
+    Internal = 8,
+    /// The library panicked; the call had no effect on outputs
+    Panic = 9,
+}
+
+/// Failure of an FFI call, reported through [`VeritasStatus`] and
+/// [`veritas_last_error_message`](crate::veritas_last_error_message).
+#[derive(Debug)]
+pub(crate) struct FfiError {
+    pub status: VeritasStatus,
+    pub message: String,
+}
+
+impl FfiError {
+    pub fn new(status: VeritasStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn null_pointer(argument: &str) -> Self {
+        Self::new(
+            VeritasStatus::NullPointer,
+            format!("`{argument}` must not be null"),
+        )
+    }
+}
+
+impl From<VeritasError> for FfiError {
+    fn from(e: VeritasError) -> Self {
+        let status = match &e {
+            VeritasError::QrngError(_) | VeritasError::HttpError(_) => {
+                VeritasStatus::QrngUnavailable
+            }
+            VeritasError::SignatureError(_) => VeritasStatus::SigningFailed,
+            VeritasError::VerificationFailed(_)
+            | VeritasError::SerializationError(_)
+            | VeritasError::InvalidSeal(_)
+            | VeritasError::EntropyTimestampMismatch { .. }
+            | VeritasError::SealTooLarge { .. }
+            | VeritasError::UnsupportedSealVersion(..)
+            | VeritasError::InvalidTimestamp { .. } => VeritasStatus::InvalidSeal,
+            VeritasError::InvalidDocument(_)
+            | VeritasError::InvalidDid(_)
+            | VeritasError::WatermarkError(_) => VeritasStatus::InvalidArgument,
+            VeritasError::PerceptualHashError(_) => VeritasStatus::PerceptualHashFailed,
+            VeritasError::AnchorError(_) => VeritasStatus::Internal,
+        };
+        Self::new(status, e.to_string())
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    // Interior NULs would truncate the C string
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Message of the last failed call on this thread, or null.
+pub(crate) fn last_error_ptr() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Run an FFI call body: clears the last error, records the new one on
+/// failure and turns panics into [`VeritasStatus::Panic`] instead of
+/// unwinding into C.
+pub(crate) fn guard(body: impl FnOnce() -> Result<(), FfiError>) -> VeritasStatus {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => VeritasStatus::Ok,
+        Ok(Err(e)) => {
+            set_last_error(&e.message);
+            e.status
+        }
+        Err(_) => {
+            set_last_error("veritas-ffi panicked");
+            VeritasStatus::Panic
+        }
+    }
+}
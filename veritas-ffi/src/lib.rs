@@ -0,0 +1,556 @@
+//! C bindings for Veritas Q.
+//!
+//! Exposes sealing, seal verification and perceptual hashing through an
+//! `extern "C"` API, for C, C++, Swift, Go and other languages. The header
+//! `include/veritas.h` is generated by cbindgen on every build.
+//!
+//! ```c
+//! uint8_t public_key[VERITAS_PUBLIC_KEY_BYTES];
+//! uint8_t secret_key[VERITAS_SECRET_KEY_BYTES];
+//! veritas_generate_keypair(public_key, secret_key);
+//!
+//! VeritasBuffer seal;
+//! if (veritas_seal(photo, photo_len, "image/jpeg", secret_key, sizeof secret_key,
+//!                  public_key, sizeof public_key, VERITAS_QRNG_LFD, &seal) != VERITAS_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", veritas_last_error_message());
+//!     return 1;
+//! }
+//! VeritasVerification result;
+//! veritas_verify(photo, photo_len, seal.data, seal.len, &result);
+//! veritas_buffer_free(seal);
+//! ```
+//!
+//! # Errors
+//!
+//! Fallible functions return a [`VeritasStatus`]; its values are stable
+//! across releases. On failure, [`veritas_last_error_message`] describes the
+//! error. Outputs are only written on success.
+//!
+//! # Memory ownership
+//!
+//! - Input buffers stay owned by the caller and are only read during the
+//!   call. A null pointer is accepted for an empty (`len == 0`) buffer.
+//! - Fixed-size outputs (keys, perceptual hashes) are written into arrays
+//!   the caller allocates, of the sizes given by the `VERITAS_*` constants.
+//! - Variable-size outputs are returned as a [`VeritasBuffer`] owned by the
+//!   library. Release each exactly once with [`veritas_buffer_free`], never
+//!   with `free()`.
+//! - Strings returned by [`veritas_version`] are static. The string from
+//!   [`veritas_last_error_message`] belongs to the calling thread and stays
+//!   valid until its next call into the library.
+//!
+//! Functions are thread-safe. They block the calling thread (QRNG requests
+//! go over the network) and must not be called from inside a Rust async
+//! runtime.
+
+mod error;
+
+use std::ffi::{c_char, CStr};
+
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::PublicKey as _;
+use veritas_core::qrng::{BlockingEntropySource, BlockingLfdQrng};
+use veritas_core::{
+    compute_phash, generate_keypair, hamming_distance, ContentVerificationResult, MediaType,
+    MockQrng, SealBuilder, VeritasSeal, ZeroizingSecretKey, MLDSA65_PUBLIC_KEY_BYTES,
+    MLDSA65_SECRET_KEY_BYTES, PERCEPTUAL_HASH_SIZE,
+};
+
+pub use error::VeritasStatus;
+use error::{guard, FfiError};
+
+/// Size of an ML-DSA-65 public key, in bytes
+pub const VERITAS_PUBLIC_KEY_BYTES: usize = 1952;
+
+/// Size of an ML-DSA-65 secret key, in bytes
+pub const VERITAS_SECRET_KEY_BYTES: usize = 4032;
+
+/// Size of a perceptual hash, in bytes
+pub const VERITAS_PHASH_SIZE: usize = 8;
+
+// cbindgen needs literals; keep them in step with the core
+const _: () = assert!(VERITAS_PUBLIC_KEY_BYTES == MLDSA65_PUBLIC_KEY_BYTES);
+const _: () = assert!(VERITAS_SECRET_KEY_BYTES == MLDSA65_SECRET_KEY_BYTES);
+const _: () = assert!(VERITAS_PHASH_SIZE == PERCEPTUAL_HASH_SIZE);
+
+/// Outcome of verifying content against a seal.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VeritasVerification {
+    /// Valid signature and the content is what was sealed
+    Authentic = 0,
+    /// Valid signature, but the content differs from what was sealed
+    ContentModified = 1,
+    /// The seal's signature is invalid
+    SignatureInvalid = 2,
+}
+
+/// Entropy source for sealing.
+///
+/// Passed to [`veritas_seal`] as a plain `int32_t`, so a value from a newer
+/// header or a corrupted one is rejected rather than undefined behaviour.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VeritasQrng {
+    /// LfD quantum random number generator (network)
+    Lfd = 0,
+    /// Deterministic mock entropy: for tests only, never in production
+    Mock = 1,
+}
+
+impl TryFrom<i32> for VeritasQrng {
+    /// The unknown value
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, i32> {
+        match value {
+            0 => Ok(Self::Lfd),
+            1 => Ok(Self::Mock),
+            other => Err(other),
+        }
+    }
+}
+
+/// Bytes allocated by the library; release with [`veritas_buffer_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct VeritasBuffer {
+    /// Start of the bytes (null for an empty buffer)
+    pub data: *mut u8,
+    /// Number of bytes
+    pub len: usize,
+}
+
+impl VeritasBuffer {
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        let data = Box::into_raw(bytes.into_boxed_slice()).cast::<u8>();
+        Self { data, len }
+    }
+}
+
+/// Library version, as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn veritas_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Message describing the last failed call on this thread, or null if the
+/// last call succeeded.
+///
+/// The string is valid until the next call into the library on this thread.
+#[no_mangle]
+pub extern "C" fn veritas_last_error_message() -> *const c_char {
+    error::last_error_ptr()
+}
+
+/// Release a buffer returned by the library. Null buffers are ignored.
+///
+/// # Safety
+///
+/// `buffer` must come from this library and not have been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn veritas_buffer_free(buffer: VeritasBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+}
+
+/// Generate an ML-DSA-65 signing keypair.
+///
+/// # Safety
+///
+/// `out_public_key` must point to `VERITAS_PUBLIC_KEY_BYTES` writable bytes
+/// and `out_secret_key` to `VERITAS_SECRET_KEY_BYTES`. The caller owns the
+/// secret key and should wipe it once done.
+#[no_mangle]
+pub unsafe extern "C" fn veritas_generate_keypair(
+    out_public_key: *mut u8,
+    out_secret_key: *mut u8,
+) -> VeritasStatus {
+    guard(|| {
+        if out_public_key.is_null() {
+            return Err(FfiError::null_pointer("out_public_key"));
+        }
+        if out_secret_key.is_null() {
+            return Err(FfiError::null_pointer("out_secret_key"));
+        }
+        let (public_key, secret_key) = generate_keypair();
+        write_bytes(public_key.as_bytes(), out_public_key);
+        write_bytes(secret_key.as_bytes(), out_secret_key);
+        Ok(())
+    })
+}
+
+/// Seal `content`, returning the seal as CBOR in `out_seal`.
+///
+/// `media_type` is `image`, `video`, `audio`, `document`, `generic` or a
+/// MIME type; null means `generic`. `qrng` selects the entropy source, a
+/// [`VeritasQrng`] value; others are [`VeritasStatus::InvalidArgument`].
+///
+/// # Safety
+///
+/// Input pointers must be valid for reads of their lengths (or null with a
+/// zero length), `media_type` null or a NUL-terminated string and
+/// `out_seal` valid for writes. The seal must be released with
+/// [`veritas_buffer_free`].
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn veritas_seal(
+    content: *const u8,
+    content_len: usize,
+    media_type: *const c_char,
+    secret_key: *const u8,
+    secret_key_len: usize,
+    public_key: *const u8,
+    public_key_len: usize,
+    qrng: i32,
+    out_seal: *mut VeritasBuffer,
+) -> VeritasStatus {
+    guard(|| {
+        if out_seal.is_null() {
+            return Err(FfiError::null_pointer("out_seal"));
+        }
+        let qrng = VeritasQrng::try_from(qrng).map_err(|value| {
+            FfiError::new(
+                VeritasStatus::InvalidArgument,
+                format!("Unknown QRNG source: {value}"),
+            )
+        })?;
+        let content = input(content, content_len, "content")?;
+        let media_type = if media_type.is_null() {
+            MediaType::Generic
+        } else {
+            let name = CStr::from_ptr(media_type).to_str().map_err(|_| {
+                FfiError::new(
+                    VeritasStatus::InvalidArgument,
+                    "media_type is not valid UTF-8",
+                )
+            })?;
+            MediaType::parse(name).ok_or_else(|| {
+                FfiError::new(
+                    VeritasStatus::InvalidArgument,
+                    format!("Unknown media type: {name}"),
+                )
+            })?
+        };
+        let secret_key =
+            ZeroizingSecretKey::from_bytes(input(secret_key, secret_key_len, "secret_key")?)
+                .map_err(|e| FfiError::new(VeritasStatus::InvalidKey, e.to_string()))?;
+        let public_key =
+            mldsa65::PublicKey::from_bytes(input(public_key, public_key_len, "public_key")?)
+                .map_err(|e| {
+                    FfiError::new(
+                        VeritasStatus::InvalidKey,
+                        format!("Invalid public key: {e}"),
+                    )
+                })?;
+
+        let qrng: Box<dyn BlockingEntropySource> = match qrng {
+            VeritasQrng::Lfd => Box::new(BlockingLfdQrng::new()?),
+            VeritasQrng::Mock => Box::new(MockQrng::default()),
+        };
+        let seal = SealBuilder::new(content.to_vec(), media_type).build_blocking(
+            qrng.as_ref(),
+            &secret_key,
+            &public_key,
+        )?;
+        out_seal.write(VeritasBuffer::from_vec(seal.to_cbor()?));
+        Ok(())
+    })
+}
+
+/// Verify `content` against a seal (CBOR or JSON).
+///
+/// A seal that cannot be parsed is an error ([`VeritasStatus::InvalidSeal`]);
+/// a parsed seal's verdict is written to `out_result`.
+///
+/// # Safety
+///
+/// Input pointers must be valid for reads of their lengths (or null with a
+/// zero length) and `out_result` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn veritas_verify(
+    content: *const u8,
+    content_len: usize,
+    seal: *const u8,
+    seal_len: usize,
+    out_result: *mut VeritasVerification,
+) -> VeritasStatus {
+    guard(|| {
+        if out_result.is_null() {
+            return Err(FfiError::null_pointer("out_result"));
+        }
+        let content = input(content, content_len, "content")?;
+        let seal = input(seal, seal_len, "seal")?;
+        let seal = VeritasSeal::from_cbor(seal).or_else(|_| VeritasSeal::from_json(seal))?;
+        let result = match seal.verify_content(content)? {
+            ContentVerificationResult::Authentic => VeritasVerification::Authentic,
+            ContentVerificationResult::ContentModified { .. } => {
+                VeritasVerification::ContentModified
+            }
+            ContentVerificationResult::SignatureFailed(_) => VeritasVerification::SignatureInvalid,
+        };
+        out_result.write(result);
+        Ok(())
+    })
+}
+
+/// Compute the perceptual hash of an image (JPEG, PNG, GIF or WebP).
+///
+/// # Safety
+///
+/// `image` must be valid for reads of `image_len` bytes and `out_hash` for
+/// writes of `VERITAS_PHASH_SIZE` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn veritas_phash(
+    image: *const u8,
+    image_len: usize,
+    out_hash: *mut u8,
+) -> VeritasStatus {
+    guard(|| {
+        if out_hash.is_null() {
+            return Err(FfiError::null_pointer("out_hash"));
+        }
+        let hash = compute_phash(input(image, image_len, "image")?).ok_or_else(|| {
+            FfiError::new(VeritasStatus::PerceptualHashFailed, "Not a decodable image")
+        })?;
+        write_bytes(&hash, out_hash);
+        Ok(())
+    })
+}
+
+/// Number of differing bits between two perceptual hashes (0 to 64).
+///
+/// Visually similar images are within a few bits of each other.
+///
+/// # Safety
+///
+/// `hash_a` and `hash_b` must be valid for reads of `VERITAS_PHASH_SIZE`
+/// bytes and `out_distance` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn veritas_phash_distance(
+    hash_a: *const u8,
+    hash_b: *const u8,
+    out_distance: *mut u32,
+) -> VeritasStatus {
+    guard(|| {
+        if out_distance.is_null() {
+            return Err(FfiError::null_pointer("out_distance"));
+        }
+        let hash_a = input(hash_a, VERITAS_PHASH_SIZE, "hash_a")?;
+        let hash_b = input(hash_b, VERITAS_PHASH_SIZE, "hash_b")?;
+        let distance = hamming_distance(hash_a, hash_b)
+            .ok_or_else(|| FfiError::new(VeritasStatus::Internal, "Empty perceptual hash"))?;
+        out_distance.write(distance);
+        Ok(())
+    })
+}
+
+/// Borrow a caller's input buffer; null is only accepted when empty.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reads of `len` bytes for `'a`.
+unsafe fn input<'a>(data: *const u8, len: usize, argument: &str) -> Result<&'a [u8], FfiError> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(FfiError::null_pointer(argument))
+    } else {
+        Ok(std::slice::from_raw_parts(data, len))
+    }
+}
+
+/// Copy `bytes` to a caller's output array.
+///
+/// # Safety
+///
+/// `out` must be valid for writes of `bytes.len()` bytes.
+unsafe fn write_bytes(bytes: &[u8], out: *mut u8) {
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypair() -> (Vec<u8>, Vec<u8>) {
+        let mut public_key = vec![0u8; VERITAS_PUBLIC_KEY_BYTES];
+        let mut secret_key = vec![0u8; VERITAS_SECRET_KEY_BYTES];
+        let status =
+            unsafe { veritas_generate_keypair(public_key.as_mut_ptr(), secret_key.as_mut_ptr()) };
+        assert_eq!(status, VeritasStatus::Ok);
+        (public_key, secret_key)
+    }
+
+    fn seal(content: &[u8], media_type: &CStr) -> VeritasBuffer {
+        let (public_key, secret_key) = keypair();
+        let mut seal = VeritasBuffer {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+        let status = unsafe {
+            veritas_seal(
+                content.as_ptr(),
+                content.len(),
+                media_type.as_ptr(),
+                secret_key.as_ptr(),
+                secret_key.len(),
+                public_key.as_ptr(),
+                public_key.len(),
+                VeritasQrng::Mock as i32,
+                &mut seal,
+            )
+        };
+        assert_eq!(status, VeritasStatus::Ok);
+        seal
+    }
+
+    fn verify(content: &[u8], seal: &[u8]) -> (VeritasStatus, VeritasVerification) {
+        let mut result = VeritasVerification::SignatureInvalid;
+        let status = unsafe {
+            veritas_verify(
+                content.as_ptr(),
+                content.len(),
+                seal.as_ptr(),
+                seal.len(),
+                &mut result,
+            )
+        };
+        (status, result)
+    }
+
+    fn last_error() -> Option<String> {
+        let message = veritas_last_error_message();
+        (!message.is_null()).then(|| {
+            unsafe { CStr::from_ptr(message) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(veritas_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_seal_and_verify() {
+        let buffer = seal(b"pixels", c"image/jpeg");
+        let bytes = unsafe { std::slice::from_raw_parts(buffer.data, buffer.len) };
+        let parsed = VeritasSeal::from_cbor(bytes).unwrap();
+        assert_eq!(parsed.media_type, MediaType::Image);
+
+        assert_eq!(
+            verify(b"pixels", bytes),
+            (VeritasStatus::Ok, VeritasVerification::Authentic)
+        );
+        assert!(last_error().is_none());
+        assert_eq!(
+            verify(b"edited pixels", bytes),
+            (VeritasStatus::Ok, VeritasVerification::ContentModified)
+        );
+
+        // JSON seals are accepted too
+        let json = parsed.to_json_pretty().unwrap();
+        assert_eq!(
+            verify(b"pixels", json.as_bytes()).1,
+            VeritasVerification::Authentic
+        );
+        unsafe { veritas_buffer_free(buffer) };
+    }
+
+    #[test]
+    fn test_errors_set_status_and_message() {
+        assert_eq!(
+            verify(b"pixels", b"not a seal").0,
+            VeritasStatus::InvalidSeal
+        );
+        assert!(last_error().is_some());
+
+        let (public_key, _) = keypair();
+        let mut out = VeritasBuffer {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+        let status = unsafe {
+            veritas_seal(
+                std::ptr::null(),
+                0,
+                c"hologram".as_ptr(),
+                public_key.as_ptr(),
+                public_key.len(),
+                public_key.as_ptr(),
+                public_key.len(),
+                VeritasQrng::Mock as i32,
+                &mut out,
+            )
+        };
+        assert_eq!(status, VeritasStatus::InvalidArgument);
+        assert!(last_error().unwrap().contains("hologram"));
+        assert!(out.data.is_null());
+
+        let status = unsafe {
+            veritas_seal(
+                b"pixels".as_ptr(),
+                6,
+                std::ptr::null(),
+                public_key.as_ptr(),
+                public_key.len(),
+                public_key.as_ptr(),
+                public_key.len(),
+                7,
+                &mut out,
+            )
+        };
+        assert_eq!(status, VeritasStatus::InvalidArgument);
+        assert!(last_error().unwrap().contains("QRNG source: 7"));
+        assert!(out.data.is_null());
+
+        let status = unsafe {
+            veritas_verify(
+                std::ptr::null(),
+                4,
+                std::ptr::null(),
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, VeritasStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_qrng_from_i32() {
+        assert_eq!(VeritasQrng::try_from(0), Ok(VeritasQrng::Lfd));
+        assert_eq!(VeritasQrng::try_from(1), Ok(VeritasQrng::Mock));
+        assert_eq!(VeritasQrng::try_from(-1), Err(-1));
+        assert_eq!(VeritasQrng::try_from(2), Err(2));
+    }
+
+    #[test]
+    fn test_perceptual_hash() {
+        let image = image::RgbImage::from_fn(64, 64, |x, y| {
+            image::Rgb([(x * 4) as u8, (y * 4) as u8, 128])
+        });
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgb8(image)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+        let png = png.into_inner();
+
+        let mut hash = [0u8; VERITAS_PHASH_SIZE];
+        let status = unsafe { veritas_phash(png.as_ptr(), png.len(), hash.as_mut_ptr()) };
+        assert_eq!(status, VeritasStatus::Ok);
+        let mut distance = u32::MAX;
+        let status = unsafe { veritas_phash_distance(hash.as_ptr(), hash.as_ptr(), &mut distance) };
+        assert_eq!((status, distance), (VeritasStatus::Ok, 0));
+
+        let status = unsafe { veritas_phash(b"text".as_ptr(), 4, hash.as_mut_ptr()) };
+        assert_eq!(status, VeritasStatus::PerceptualHashFailed);
+    }
+}
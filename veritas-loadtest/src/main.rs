@@ -0,0 +1,178 @@
+//! Veritas load test - drives seal/verify mixes against a running server.
+//!
+//! Seals a pool of files for verify requests to check, then sends
+//! `--requests` requests from `--concurrency` workers following `--mix`, and
+//! reports p50/p95/p99 latencies and error rates per operation. With
+//! `--max-p95-ms` or `--max-error-rate` it exits with status 1 when a limit is
+//! exceeded, to validate performance-affecting changes (entropy pool,
+//! verification workers...) locally or in CI.
+
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+
+mod report;
+mod workload;
+
+use report::{Report, Sample};
+use workload::{Mix, Operation, Workload};
+
+#[derive(Parser)]
+#[command(name = "veritas-loadtest")]
+#[command(version, about = "Load test a Veritas Q server with seal/verify mixes", long_about = None)]
+struct Cli {
+    /// Server base URL
+    #[arg(
+        long,
+        env = "VERITAS_SERVER_URL",
+        default_value = "http://127.0.0.1:3000"
+    )]
+    server: String,
+
+    /// Requests to send, pool preparation excluded
+    #[arg(short = 'n', long, default_value_t = 200)]
+    requests: usize,
+
+    /// Requests in flight at once
+    #[arg(short, long, default_value_t = 8)]
+    concurrency: usize,
+
+    /// Operation weights, e.g. "seal=1,verify=3"
+    #[arg(long, default_value = "seal=1,verify=1")]
+    mix: Mix,
+
+    /// Size of each sealed file in bytes
+    #[arg(long, default_value_t = 64 * 1024)]
+    file_size: usize,
+
+    /// Files sealed before the run for verify requests to check
+    #[arg(long, default_value_t = 16)]
+    verify_pool: usize,
+
+    /// Seal with mock entropy (the server needs ALLOW_MOCK_QRNG=true)
+    #[arg(long)]
+    mock: bool,
+
+    /// API key sent in the X-API-Key header
+    #[arg(long, env = "VERITAS_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Timeout of each request in seconds
+    #[arg(long, default_value_t = 30)]
+    timeout_secs: u64,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Fail when an operation's p95 latency exceeds this many milliseconds
+    #[arg(long)]
+    max_p95_ms: Option<f64>,
+
+    /// Fail when an operation's error rate exceeds this share (0.0 to 1.0)
+    #[arg(long)]
+    max_error_rate: Option<f64>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("veritas-loadtest: {:#}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Run the load test, returning whether every threshold was met.
+async fn run(cli: Cli) -> Result<bool> {
+    if cli.concurrency == 0 {
+        bail!("--concurrency must be at least 1");
+    }
+    if cli.file_size == 0 {
+        bail!("--file-size must be at least 1 byte");
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(cli.timeout_secs))
+        .build()
+        .context("failed to build the HTTP client")?;
+    let workload = Arc::new(Workload::new(
+        client,
+        &cli.server,
+        cli.api_key.clone(),
+        cli.file_size,
+        cli.mock,
+    ));
+
+    workload
+        .health()
+        .await
+        .map_err(|e| anyhow!("{} is not healthy: {}", cli.server, e))?;
+
+    let pool = if cli.mix.weight(Operation::Verify) > 0 {
+        workload
+            .prepare(cli.verify_pool.max(1))
+            .await
+            .map_err(|e| anyhow!("failed to seal the verify pool: {}", e))?
+    } else {
+        Vec::new()
+    };
+    let pool = Arc::new(pool);
+
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+    let workers: Vec<_> = (0..cli.concurrency)
+        .map(|_| {
+            let workload = Arc::clone(&workload);
+            let pool = Arc::clone(&pool);
+            let next = Arc::clone(&next);
+            let mix = cli.mix.clone();
+            let requests = cli.requests;
+            tokio::spawn(async move {
+                let mut samples = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    if index >= requests {
+                        break samples;
+                    }
+                    let operation = mix.operation(index);
+                    let start = Instant::now();
+                    let outcome = match operation {
+                        Operation::Seal => workload.seal().await.map(drop),
+                        Operation::Verify => workload.verify(&pool[index % pool.len()]).await,
+                    };
+                    samples.push(Sample {
+                        operation,
+                        latency: start.elapsed(),
+                        error: outcome.err(),
+                    });
+                }
+            })
+        })
+        .collect();
+
+    let mut samples = Vec::with_capacity(cli.requests);
+    for worker in workers {
+        samples.extend(worker.await.context("load test worker panicked")?);
+    }
+    let report = Report::new(&samples, started.elapsed());
+
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", report);
+    }
+
+    let failures = report.check(cli.max_p95_ms, cli.max_error_rate);
+    for failure in &failures {
+        eprintln!("FAILED: {}", failure);
+    }
+    Ok(failures.is_empty())
+}
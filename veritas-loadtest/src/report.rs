@@ -0,0 +1,240 @@
+//! Latency percentiles, error rates and thresholds of a load test run.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::workload::Operation;
+
+/// Outcome of one request.
+pub struct Sample {
+    /// Operation exercised
+    pub operation: Operation,
+    /// Time from sending the request to reading the whole response
+    pub latency: Duration,
+    /// Error kind (`HTTP 429`, `timeout`, ...), `None` on success
+    pub error: Option<String>,
+}
+
+/// Figures of one operation.
+#[derive(Debug, Serialize)]
+pub struct OperationReport {
+    /// Operation measured
+    pub operation: Operation,
+    /// Requests sent
+    pub requests: usize,
+    /// Requests that failed
+    pub errors: usize,
+    /// Share of requests that failed (0.0 to 1.0)
+    pub error_rate: f64,
+    /// Latency percentiles of successful requests, in milliseconds (`None`
+    /// when none succeeded)
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    /// Failed requests per error kind
+    pub errors_by_kind: BTreeMap<String, usize>,
+}
+
+/// Figures of a whole run.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// Wall-clock duration of the run, preparation excluded
+    pub elapsed_secs: f64,
+    /// Requests completed per second
+    pub throughput_rps: f64,
+    /// Operations that received requests, in [`Operation::ALL`] order
+    pub operations: Vec<OperationReport>,
+}
+
+impl Report {
+    /// Summarize the samples of a run that took `elapsed`.
+    pub fn new(samples: &[Sample], elapsed: Duration) -> Self {
+        let operations = Operation::ALL
+            .into_iter()
+            .filter_map(|operation| {
+                let samples: Vec<&Sample> = samples
+                    .iter()
+                    .filter(|sample| sample.operation == operation)
+                    .collect();
+                (!samples.is_empty()).then(|| OperationReport::new(operation, &samples))
+            })
+            .collect();
+        let elapsed_secs = elapsed.as_secs_f64();
+        Self {
+            elapsed_secs,
+            throughput_rps: if elapsed_secs > 0.0 {
+                samples.len() as f64 / elapsed_secs
+            } else {
+                0.0
+            },
+            operations,
+        }
+    }
+
+    /// Thresholds exceeded by the run, one message per operation and limit.
+    pub fn check(&self, max_p95_ms: Option<f64>, max_error_rate: Option<f64>) -> Vec<String> {
+        let mut failures = Vec::new();
+        for operation in &self.operations {
+            if let Some(max) = max_error_rate {
+                if operation.error_rate > max {
+                    failures.push(format!(
+                        "{} error rate {:.2}% exceeds {:.2}%",
+                        operation.operation,
+                        operation.error_rate * 100.0,
+                        max * 100.0
+                    ));
+                }
+            }
+            if let (Some(max), Some(p95)) = (max_p95_ms, operation.p95_ms) {
+                if p95 > max {
+                    failures.push(format!(
+                        "{} p95 latency {:.1} ms exceeds {:.1} ms",
+                        operation.operation, p95, max
+                    ));
+                }
+            }
+        }
+        failures
+    }
+}
+
+impl OperationReport {
+    fn new(operation: Operation, samples: &[&Sample]) -> Self {
+        let mut latencies: Vec<f64> = samples
+            .iter()
+            .filter(|sample| sample.error.is_none())
+            .map(|sample| sample.latency.as_secs_f64() * 1000.0)
+            .collect();
+        latencies.sort_by(f64::total_cmp);
+
+        let mut errors_by_kind = BTreeMap::new();
+        for error in samples.iter().filter_map(|sample| sample.error.as_ref()) {
+            *errors_by_kind.entry(error.clone()).or_insert(0) += 1;
+        }
+        let errors = samples.len() - latencies.len();
+
+        Self {
+            operation,
+            requests: samples.len(),
+            errors,
+            error_rate: errors as f64 / samples.len() as f64,
+            p50_ms: percentile(&latencies, 50.0),
+            p95_ms: percentile(&latencies, 95.0),
+            p99_ms: percentile(&latencies, 99.0),
+            max_ms: latencies.last().copied(),
+            errors_by_kind,
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[f64], percent: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+fn millis(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |ms| format!("{:.1}", ms))
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<8} {:>9} {:>7} {:>8} {:>9} {:>9} {:>9} {:>9}",
+            "op", "requests", "errors", "err %", "p50 ms", "p95 ms", "p99 ms", "max ms"
+        )?;
+        for operation in &self.operations {
+            writeln!(
+                f,
+                "{:<8} {:>9} {:>7} {:>8.2} {:>9} {:>9} {:>9} {:>9}",
+                operation.operation.name(),
+                operation.requests,
+                operation.errors,
+                operation.error_rate * 100.0,
+                millis(operation.p50_ms),
+                millis(operation.p95_ms),
+                millis(operation.p99_ms),
+                millis(operation.max_ms),
+            )?;
+            for (kind, count) in &operation.errors_by_kind {
+                writeln!(f, "         {} x {}", count, kind)?;
+            }
+        }
+        writeln!(
+            f,
+            "{:.1} s, {:.1} requests/s",
+            self.elapsed_secs, self.throughput_rps
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(operation: Operation, millis: u64, error: Option<&str>) -> Sample {
+        Sample {
+            operation,
+            latency: Duration::from_millis(millis),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let values: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&values, 50.0), Some(50.0));
+        assert_eq!(percentile(&values, 95.0), Some(95.0));
+        assert_eq!(percentile(&values, 99.0), Some(99.0));
+        assert_eq!(percentile(&[7.0], 95.0), Some(7.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_report_counts_errors_per_operation() {
+        let samples = vec![
+            sample(Operation::Seal, 40, None),
+            sample(Operation::Seal, 20, None),
+            sample(Operation::Seal, 5, Some("HTTP 429")),
+            sample(Operation::Seal, 6, Some("HTTP 429")),
+        ];
+        let report = Report::new(&samples, Duration::from_secs(2));
+
+        assert_eq!(report.operations.len(), 1);
+        let seal = &report.operations[0];
+        assert_eq!(seal.operation, Operation::Seal);
+        assert_eq!((seal.requests, seal.errors), (4, 2));
+        assert_eq!(seal.error_rate, 0.5);
+        // Failed requests do not count towards latency
+        assert_eq!(seal.p50_ms, Some(20.0));
+        assert_eq!(seal.max_ms, Some(40.0));
+        assert_eq!(seal.errors_by_kind.get("HTTP 429"), Some(&2));
+        assert_eq!(report.throughput_rps, 2.0);
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let samples = vec![
+            sample(Operation::Seal, 300, None),
+            sample(Operation::Verify, 10, None),
+            sample(Operation::Verify, 10, Some("timeout")),
+        ];
+        let report = Report::new(&samples, Duration::from_secs(1));
+
+        assert!(report.check(None, None).is_empty());
+        assert!(report.check(Some(500.0), Some(0.5)).is_empty());
+
+        let failures = report.check(Some(100.0), Some(0.1));
+        assert_eq!(failures.len(), 2);
+        assert!(failures[0].starts_with("seal p95 latency"));
+        assert!(failures[1].starts_with("verify error rate"));
+    }
+}
@@ -0,0 +1,287 @@
+//! Requests sent by the load test and how they are mixed.
+
+use std::fmt;
+use std::str::FromStr;
+
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Header carrying API keys (see `veritas-server/src/auth/mod.rs`).
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Operation exercised by a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// `POST /api/v1/seal` of a fresh generic file
+    Seal,
+    /// `POST /api/v1/verify` of a file sealed before the run
+    Verify,
+}
+
+impl Operation {
+    /// Every operation, in report order.
+    pub const ALL: [Operation; 2] = [Operation::Seal, Operation::Verify];
+
+    /// Name used in `--mix` and reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::Seal => "seal",
+            Operation::Verify => "verify",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Relative weights of the operations, parsed from `seal=1,verify=3`.
+///
+/// Operations left out get no requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mix {
+    seal: u32,
+    verify: u32,
+}
+
+impl Mix {
+    /// Weight of an operation.
+    pub fn weight(&self, operation: Operation) -> u32 {
+        match operation {
+            Operation::Seal => self.seal,
+            Operation::Verify => self.verify,
+        }
+    }
+
+    /// Operation of the `index`-th request.
+    ///
+    /// Weighted round robin rather than random draws, so every run with the
+    /// same mix sends the same sequence.
+    pub fn operation(&self, index: usize) -> Operation {
+        let slot = index % (self.seal + self.verify) as usize;
+        if slot < self.seal as usize {
+            Operation::Seal
+        } else {
+            Operation::Verify
+        }
+    }
+}
+
+impl FromStr for Mix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mix = Mix { seal: 0, verify: 0 };
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (name, weight) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected operation=weight, got '{}'", entry))?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid weight for {}: '{}'", name, weight))?;
+            match name.trim() {
+                "seal" => mix.seal = weight,
+                "verify" => mix.verify = weight,
+                other => {
+                    return Err(format!(
+                        "unknown operation '{}' (expected seal or verify)",
+                        other
+                    ))
+                }
+            }
+        }
+        if mix.seal.checked_add(mix.verify).unwrap_or(0) == 0 {
+            return Err("the mix needs at least one operation with a weight above 0".into());
+        }
+        Ok(mix)
+    }
+}
+
+/// A file sealed before the run, verified by verify requests.
+pub struct Sealed {
+    content: Vec<u8>,
+    seal_data: String,
+}
+
+#[derive(Deserialize)]
+struct SealResponse {
+    seal_data: String,
+}
+
+/// Builds and sends the load test's requests.
+pub struct Workload {
+    client: Client,
+    server: String,
+    api_key: Option<String>,
+    file_size: usize,
+    mock: bool,
+}
+
+impl Workload {
+    /// Target `server` (base URL) with files of `file_size` bytes.
+    pub fn new(
+        client: Client,
+        server: &str,
+        api_key: Option<String>,
+        file_size: usize,
+        mock: bool,
+    ) -> Self {
+        Self {
+            client,
+            server: server.trim_end_matches('/').to_string(),
+            api_key,
+            file_size,
+            mock,
+        }
+    }
+
+    /// Check the server answers `GET /health`.
+    pub async fn health(&self) -> Result<(), String> {
+        self.send(self.client.get(format!("{}/health", self.server)))
+            .await
+            .map(drop)
+    }
+
+    /// Seal `count` files for verify requests to check.
+    pub async fn prepare(&self, count: usize) -> Result<Vec<Sealed>, String> {
+        let mut sealed = Vec::with_capacity(count);
+        for _ in 0..count {
+            sealed.push(self.seal().await?);
+        }
+        Ok(sealed)
+    }
+
+    /// Seal a fresh file.
+    ///
+    /// Every file is distinct, so the server cannot answer from a cache.
+    pub async fn seal(&self) -> Result<Sealed, String> {
+        let content = self.content();
+        let form = Form::new()
+            .part(
+                "file",
+                Part::bytes(content.clone()).file_name("loadtest.bin"),
+            )
+            .text("media_type", "generic")
+            .text("mock", self.mock.to_string());
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/api/v1/seal", self.server))
+                    .multipart(form),
+            )
+            .await?;
+        let body: SealResponse = response
+            .json()
+            .await
+            .map_err(|_| "invalid seal response".to_string())?;
+        Ok(Sealed {
+            content,
+            seal_data: body.seal_data,
+        })
+    }
+
+    /// Verify a file sealed before the run.
+    pub async fn verify(&self, sealed: &Sealed) -> Result<(), String> {
+        let form = Form::new()
+            .part(
+                "file",
+                Part::bytes(sealed.content.clone()).file_name("loadtest.bin"),
+            )
+            .text("seal_data", sealed.seal_data.clone());
+        let response = self
+            .send(
+                self.client
+                    .post(format!("{}/api/v1/verify", self.server))
+                    .multipart(form),
+            )
+            .await?;
+        response.bytes().await.map(drop).map_err(|e| describe(&e))
+    }
+
+    /// Send a request, turning transport failures and non-2xx statuses into
+    /// short error kinds for the report.
+    async fn send(&self, request: RequestBuilder) -> Result<Response, String> {
+        let request = match &self.api_key {
+            Some(key) => request.header(API_KEY_HEADER, key),
+            None => request,
+        };
+        let response = request.send().await.map_err(|e| describe(&e))?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status().as_u16()));
+        }
+        Ok(response)
+    }
+
+    fn content(&self) -> Vec<u8> {
+        let tag = Uuid::new_v4();
+        tag.as_bytes()
+            .iter()
+            .copied()
+            .cycle()
+            .take(self.file_size)
+            .collect()
+    }
+}
+
+fn describe(error: &reqwest::Error) -> String {
+    if error.is_timeout() {
+        "timeout".into()
+    } else if error.is_connect() {
+        "connection failed".into()
+    } else {
+        "request failed".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix_parsing() {
+        let mix: Mix = "seal=1, verify=3".parse().unwrap();
+        assert_eq!(mix.weight(Operation::Seal), 1);
+        assert_eq!(mix.weight(Operation::Verify), 3);
+
+        let seal_only: Mix = "seal=2".parse().unwrap();
+        assert_eq!(seal_only.weight(Operation::Verify), 0);
+
+        assert!("seal=0,verify=0".parse::<Mix>().is_err());
+        assert!("".parse::<Mix>().is_err());
+        assert!("seal".parse::<Mix>().is_err());
+        assert!("seal=-1".parse::<Mix>().is_err());
+        assert!("upload=1".parse::<Mix>().is_err());
+    }
+
+    #[test]
+    fn test_mix_sequence_follows_weights() {
+        let mix: Mix = "seal=1,verify=3".parse().unwrap();
+        let sequence: Vec<Operation> = (0..8).map(|index| mix.operation(index)).collect();
+        assert_eq!(
+            sequence,
+            [
+                Operation::Seal,
+                Operation::Verify,
+                Operation::Verify,
+                Operation::Verify,
+                Operation::Seal,
+                Operation::Verify,
+                Operation::Verify,
+                Operation::Verify,
+            ]
+        );
+
+        let verify_only: Mix = "verify=1".parse().unwrap();
+        assert!((0..4).all(|index| verify_only.operation(index) == Operation::Verify));
+    }
+}
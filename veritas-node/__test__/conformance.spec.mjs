@@ -0,0 +1,34 @@
+// Runs the seal vectors of conformance/ (see conformance/README.md).
+// Run with `npm test` after `npm run build`.
+import assert from "node:assert/strict";
+import { readFileSync } from "node:fs";
+import { createRequire } from "node:module";
+import { test } from "node:test";
+
+const veritas = createRequire(import.meta.url)("../index.js");
+
+const root = new URL("../../conformance/", import.meta.url);
+const vectors = JSON.parse(readFileSync(new URL("vectors.json", root)));
+
+test("vector format is supported", () => {
+  assert.equal(vectors.format_version, 1);
+});
+
+for (const vector of vectors.seals) {
+  test(`seal vector ${vector.id}`, () => {
+    const { expected } = vector;
+    const seal = readFileSync(new URL(vector.seal, root));
+    const content = Buffer.from(vector.content, "hex");
+    if (!expected.parse) {
+      assert.throws(() => veritas.verify(content, seal), /Failed to parse seal/);
+      return;
+    }
+
+    const result = veritas.verify(content, seal);
+    assert.equal(result.code, expected.content);
+    assert.equal(result.authentic, expected.content === "content_authentic");
+    if (expected.tampered_segments) {
+      assert.deepEqual(result.tamperedSegments, expected.tampered_segments);
+    }
+  });
+}
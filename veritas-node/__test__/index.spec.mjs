@@ -0,0 +1,53 @@
+// Run with `npm test` after `npm run build`.
+import assert from "node:assert/strict";
+import { mkdtempSync, readFileSync, writeFileSync } from "node:fs";
+import { createRequire } from "node:module";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { test } from "node:test";
+
+const veritas = createRequire(import.meta.url)("../index.js");
+
+// Content sealed by every core fixture (see veritas-core/tests/seal_compat.rs)
+const content = Buffer.from("Veritas Q seal format fixture");
+const seal = readFileSync(
+  new URL("../../veritas-core/tests/fixtures/seal_v1.cbor", import.meta.url),
+);
+
+test("verify reports authentic and modified content", () => {
+  const authentic = veritas.verify(content, seal);
+  assert.equal(authentic.authentic, true);
+  assert.equal(authentic.status, "authentic");
+  assert.equal(authentic.contentHash, authentic.expectedHash);
+
+  const modified = veritas.verify(Buffer.from("edited"), seal);
+  assert.equal(modified.authentic, false);
+  assert.equal(modified.status, "content_modified");
+  assert.notEqual(modified.contentHash, modified.expectedHash);
+
+  assert.throws(
+    () => veritas.verify(content, Buffer.from("not a seal")),
+    /Failed to parse seal/,
+  );
+});
+
+test("verifyFile verifies off the main thread", async () => {
+  const path = join(mkdtempSync(join(tmpdir(), "veritas-node-")), "content.bin");
+  writeFileSync(path, content);
+  assert.equal((await veritas.verifyFile(path, seal)).status, "authentic");
+  await assert.rejects(veritas.verifyFile(`${path}.missing`, seal), /Failed to read/);
+});
+
+test("perceptual hashes of the same image match", () => {
+  const icon = readFileSync(
+    new URL("../../www/public/icons/icon-72x72.png", import.meta.url),
+  );
+  const hash = veritas.phash(icon);
+  assert.equal(hash.length, 8);
+  assert.equal(veritas.phashDistance(hash, veritas.phash(icon)), 0);
+  assert.throws(() => veritas.phash(Buffer.from("text")), /Not a decodable image/);
+});
+
+test("extractC2paSealFromBuffer fails without a manifest", () => {
+  assert.throws(() => veritas.extractC2paSealFromBuffer(content, "image/jpeg"));
+});
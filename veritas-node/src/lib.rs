@@ -0,0 +1,204 @@
+//! Node.js native addon for Veritas Q.
+//!
+//! Verifies seals, extracts Veritas seals from C2PA manifests and computes
+//! perceptual hashes natively, for Node services where Wasm verification of
+//! large media is too slow. `Buffer` arguments are borrowed from JavaScript
+//! without copying.
+//!
+//! Build with the napi CLI (`npm run build` in this directory), which also
+//! generates `index.js` and `index.d.ts`.
+
+use std::io::Cursor;
+use std::path::Path;
+
+use napi::bindgen_prelude::{AsyncTask, Buffer};
+use napi::{Env, Error, Result, Status, Task};
+use napi_derive::napi;
+use veritas_core::c2pa::{
+    extract_quantum_seal, extract_quantum_seal_from_stream, C2paError, C2paResult,
+    QuantumSealAssertion,
+};
+use veritas_core::{
+    compute_phash, hamming_distance, ContentVerificationResult, MediaType, VeritasSeal,
+};
+
+/// Result of verifying content against a seal.
+#[napi(object)]
+pub struct VerifyResult {
+    /// Valid signature and the content is what was sealed
+    pub authentic: bool,
+    /// `authentic`, `content_modified` or `signature_invalid`
+    pub status: String,
+    /// Stable result code (`content_authentic`, `segments_modified`,
+    /// `invalid_signature`, ...)
+    pub code: String,
+    /// Capture time (RFC 3339)
+    pub captured_at: String,
+    /// Media type (custom types as their MIME type)
+    pub media_type: String,
+    /// QRNG source of the seal's entropy
+    pub qrng_source: String,
+    /// SHA3-256 of the verified content (hex)
+    pub content_hash: String,
+    /// SHA3-256 recorded in the seal (hex)
+    pub expected_hash: String,
+    /// Indices of altered segments (segmented seals only)
+    pub tampered_segments: Option<Vec<u32>>,
+}
+
+/// Library version.
+#[napi]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+/// Verify `content` against a seal (CBOR or JSON).
+///
+/// Runs on the calling thread; prefer `verifyFile` for large media.
+#[napi]
+pub fn verify(content: Buffer, seal: Buffer) -> Result<VerifyResult> {
+    verify_seal(&parse_seal(&seal)?, &content)
+}
+
+/// Reads and verifies a file on the libuv thread pool.
+pub struct VerifyFile {
+    path: String,
+    seal: Vec<u8>,
+}
+
+impl Task for VerifyFile {
+    type Output = VerifyResult;
+    type JsValue = VerifyResult;
+
+    fn compute(&mut self) -> Result<VerifyResult> {
+        let seal = parse_seal(&self.seal)?;
+        let content = std::fs::read(&self.path).map_err(|e| {
+            Error::new(
+                Status::GenericFailure,
+                format!("Failed to read {}: {e}", self.path),
+            )
+        })?;
+        verify_seal(&seal, &content)
+    }
+
+    fn resolve(&mut self, _env: Env, output: VerifyResult) -> Result<VerifyResult> {
+        Ok(output)
+    }
+}
+
+/// Verify the file at `path` against a seal (CBOR or JSON), off the main
+/// thread.
+#[napi(ts_return_type = "Promise<VerifyResult>")]
+pub fn verify_file(path: String, seal: Buffer) -> AsyncTask<VerifyFile> {
+    AsyncTask::new(VerifyFile {
+        path,
+        seal: seal.to_vec(),
+    })
+}
+
+/// Veritas seal assertion of the C2PA manifest embedded in the file at
+/// `path`, or `null` if the manifest has none.
+///
+/// Throws when the file has no C2PA manifest or cannot be read.
+#[napi(ts_return_type = "Record<string, any> | null")]
+pub fn extract_c2pa_seal(path: String) -> Result<Option<serde_json::Value>> {
+    assertion_to_js(extract_quantum_seal(Path::new(&path)))
+}
+
+/// Like `extractC2paSeal`, for media already in memory. `mimeType` is the
+/// media format, e.g. `image/jpeg` or `video/mp4`.
+#[napi(ts_return_type = "Record<string, any> | null")]
+pub fn extract_c2pa_seal_from_buffer(
+    content: Buffer,
+    mime_type: String,
+) -> Result<Option<serde_json::Value>> {
+    assertion_to_js(extract_quantum_seal_from_stream(
+        &mime_type,
+        Cursor::new(content.as_ref()),
+    ))
+}
+
+/// Perceptual hash (8 bytes) of an image (JPEG, PNG, GIF or WebP).
+#[napi]
+pub fn phash(image: Buffer) -> Result<Buffer> {
+    compute_phash(&image)
+        .map(Buffer::from)
+        .ok_or_else(|| Error::new(Status::InvalidArg, "Not a decodable image"))
+}
+
+/// Number of differing bits between two perceptual hashes; visually similar
+/// images are within a few bits of each other.
+#[napi]
+pub fn phash_distance(a: Buffer, b: Buffer) -> Result<u32> {
+    hamming_distance(&a, &b)
+        .ok_or_else(|| Error::new(Status::InvalidArg, "Perceptual hashes must not be empty"))
+}
+
+/// Parse a seal in CBOR or JSON.
+fn parse_seal(bytes: &[u8]) -> Result<VeritasSeal> {
+    VeritasSeal::from_cbor(bytes)
+        .or_else(|_| VeritasSeal::from_json(bytes))
+        .map_err(|e| Error::new(Status::InvalidArg, format!("Failed to parse seal: {e}")))
+}
+
+fn verify_seal(seal: &VeritasSeal, content: &[u8]) -> Result<VerifyResult> {
+    let result = seal
+        .verify_content(content)
+        .map_err(|e| Error::from_reason(format!("Verification error: {e}")))?;
+
+    let (status, actual_hash, tampered_segments) = match &result {
+        ContentVerificationResult::Authentic => ("authentic", seal.content_hash.crypto_hash, None),
+        ContentVerificationResult::ContentModified {
+            actual_hash,
+            tampered_segments,
+            ..
+        } => (
+            "content_modified",
+            *actual_hash,
+            tampered_segments
+                .as_ref()
+                .map(|segments| segments.iter().map(|s| s.index).collect()),
+        ),
+        ContentVerificationResult::SignatureFailed(_) => {
+            ("signature_invalid", seal.content_hash.crypto_hash, None)
+        }
+    };
+
+    Ok(VerifyResult {
+        authentic: result.is_authentic(),
+        status: status.to_string(),
+        code: result.code().as_str().to_string(),
+        captured_at: format_timestamp(seal.capture_timestamp_utc),
+        media_type: match &seal.media_type {
+            MediaType::Custom(mime) => mime.clone(),
+            other => format!("{other:?}"),
+        },
+        qrng_source: format!("{:?}", seal.qrng_source),
+        content_hash: hex::encode(actual_hash),
+        expected_hash: hex::encode(seal.content_hash.crypto_hash),
+        tampered_segments,
+    })
+}
+
+/// The assertion as a plain object; `None` when the manifest has none.
+fn assertion_to_js(
+    assertion: C2paResult<QuantumSealAssertion>,
+) -> Result<Option<serde_json::Value>> {
+    match assertion {
+        Ok(assertion) => serde_json::to_value(assertion)
+            .map(Some)
+            .map_err(|e| Error::from_reason(e.to_string())),
+        Err(C2paError::NoVeritasSealFound) => Ok(None),
+        Err(e) => Err(Error::from_reason(e.to_string())),
+    }
+}
+
+fn format_timestamp(timestamp_ms: u64) -> String {
+    use chrono::{TimeZone, Utc};
+    let secs = (timestamp_ms / 1000) as i64;
+    let nsecs = ((timestamp_ms % 1000) * 1_000_000) as u32;
+    match Utc.timestamp_opt(secs, nsecs) {
+        chrono::LocalResult::Single(dt) => dt.to_rfc3339(),
+        _ => format!("{timestamp_ms}ms"),
+    }
+}
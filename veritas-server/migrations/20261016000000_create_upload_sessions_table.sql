@@ -0,0 +1,32 @@
+-- Resumable upload sessions for Veritas Q
+-- Tracks tus-style chunked uploads; the bytes themselves are kept on disk (UPLOAD_DIR)
+
+CREATE TABLE IF NOT EXISTS upload_sessions (
+    -- Upload ID handed to the client (UUID v4, acts as a capability)
+    id UUID PRIMARY KEY,
+
+    -- User who created the upload (NULL for anonymous uploads)
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Declared total size and bytes received so far
+    upload_length BIGINT NOT NULL CHECK (upload_length > 0),
+    upload_offset BIGINT NOT NULL DEFAULT 0
+        CHECK (upload_offset >= 0 AND upload_offset <= upload_length),
+
+    -- File metadata declared at creation
+    media_type TEXT,                      -- "image", "video", ..., or a MIME type
+    content_type TEXT,                    -- Content-Type of the file
+    file_name TEXT,
+
+    -- Timestamps
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL       -- Pushed back on every chunk; abandoned uploads are purged
+);
+
+-- Index for the abandoned upload sweep
+CREATE INDEX IF NOT EXISTS idx_upload_sessions_expires_at ON upload_sessions(expires_at);
+
+COMMENT ON TABLE upload_sessions IS 'Resumable upload sessions (tus-style), sealed when complete';
+COMMENT ON COLUMN upload_sessions.upload_offset IS 'Bytes received so far; chunks must start at this offset';
+COMMENT ON COLUMN upload_sessions.expires_at IS 'Sessions past this time are deleted along with their data';
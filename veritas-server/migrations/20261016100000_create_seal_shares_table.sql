@@ -0,0 +1,29 @@
+-- Seal share links for Veritas Q
+-- Unguessable tokens giving public, read-only access to one seal's details
+
+CREATE TABLE IF NOT EXISTS seal_shares (
+    -- Share ID (UUID v4), used to list and revoke the share
+    id UUID PRIMARY KEY,
+
+    -- Shared seal and the user who shared it
+    seal_id UUID NOT NULL REFERENCES seals(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- SHA3-256 of the token; the token itself is only shown once, at creation
+    token_hash BYTEA NOT NULL UNIQUE,
+
+    -- Timestamps
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ,               -- NULL = never expires
+    revoked_at TIMESTAMPTZ,               -- Set when the owner revokes the share
+    last_accessed_at TIMESTAMPTZ,
+
+    access_count BIGINT NOT NULL DEFAULT 0
+);
+
+-- Index for listing the shares of a seal
+CREATE INDEX IF NOT EXISTS idx_seal_shares_seal_id ON seal_shares(seal_id);
+
+COMMENT ON TABLE seal_shares IS 'Share links granting unauthenticated access to a seal''s verification details';
+COMMENT ON COLUMN seal_shares.token_hash IS 'SHA3-256 of the share token; the token is never stored';
+COMMENT ON COLUMN seal_shares.revoked_at IS 'Revoked shares stay listed but no longer grant access';
@@ -0,0 +1,36 @@
+-- Admin tooling for Veritas Q
+-- Account roles and suspension, seal revocation, and a log of failed seal attempts
+
+-- Account role: 'user' or 'admin' (admins can use /api/v1/admin)
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS role TEXT NOT NULL DEFAULT 'user' CHECK (role IN ('user', 'admin')),
+    ADD COLUMN IF NOT EXISTS suspended_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN users.role IS 'Account role: user or admin (grant with UPDATE users SET role = ''admin'')';
+COMMENT ON COLUMN users.suspended_at IS 'Suspension timestamp; suspended accounts cannot authenticate (NULL = active)';
+
+-- Seal revocation by an administrator (the seal stays verifiable but is flagged)
+ALTER TABLE seals
+    ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS revocation_reason TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_seals_revoked_at ON seals(revoked_at) WHERE revoked_at IS NOT NULL;
+
+COMMENT ON COLUMN seals.revoked_at IS 'When an administrator revoked the seal (NULL = not revoked)';
+
+-- Failed seal attempts, for platform failure rates
+CREATE TABLE IF NOT EXISTS seal_failures (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- User who attempted the seal (NULL for anonymous requests)
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    -- API error code of the failure (e.g. QRNG_UNAVAILABLE)
+    error_code TEXT NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_seal_failures_created_at ON seal_failures(created_at);
+
+COMMENT ON TABLE seal_failures IS 'Seal attempts that failed after validation (QRNG or signing errors)';
@@ -0,0 +1,26 @@
+-- Audit log for Veritas Q
+-- Records who verified or opened which seal, for usage statistics and alerts
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- Event name: "seal_verified", "seal_resolved", "share_opened"
+    event TEXT NOT NULL,
+
+    -- Seal the event is about (NULL for events not tied to a seal)
+    seal_id UUID REFERENCES seals(id) ON DELETE CASCADE,
+
+    -- Authenticated user behind the request (NULL for anonymous requests)
+    actor_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    -- Event outcome, e.g. "authentic", "tampered", "invalid_signature"
+    outcome TEXT,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for per-seal history and per-owner counts (joined through seals)
+CREATE INDEX IF NOT EXISTS idx_audit_log_seal_created ON audit_log(seal_id, created_at DESC) WHERE seal_id IS NOT NULL;
+
+COMMENT ON TABLE audit_log IS 'Append-only record of verifications and other accesses to seals';
+COMMENT ON COLUMN audit_log.actor_user_id IS 'User behind the request (NULL = anonymous third party)';
@@ -0,0 +1,19 @@
+-- Near-duplicate clusters for Veritas Q
+-- Groups manifests whose perceptual hashes are close (re-uploads, edited variants)
+
+-- Cluster of the manifest, assigned by the background clustering job
+-- (NULL = not clustered yet, or no perceptual hash)
+ALTER TABLE manifests
+    ADD COLUMN IF NOT EXISTS cluster_id UUID;
+
+-- Index for listing the members of a cluster
+CREATE INDEX IF NOT EXISTS idx_manifests_cluster_id
+ON manifests (cluster_id)
+WHERE cluster_id IS NOT NULL;
+
+-- Index for finding manifests the job has not clustered yet
+CREATE INDEX IF NOT EXISTS idx_manifests_unclustered
+ON manifests (created_at)
+WHERE cluster_id IS NULL AND perceptual_hash IS NOT NULL;
+
+COMMENT ON COLUMN manifests.cluster_id IS 'Near-duplicate cluster (single linkage on perceptual hash distance), NULL until clustered';
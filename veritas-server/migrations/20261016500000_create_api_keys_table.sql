@@ -0,0 +1,32 @@
+-- API keys for Veritas Q
+-- Long-lived credentials for machine clients (e.g. fact-checking crawlers), limited to scopes
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    -- Key ID (UUID v4), used to list and revoke the key
+    id UUID PRIMARY KEY,
+
+    -- Account the key acts for
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Label chosen by the owner, and the first characters of the key to recognize it
+    name TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,
+
+    -- SHA3-256 of the key; the key itself is only shown once, at creation
+    key_hash BYTEA NOT NULL UNIQUE,
+
+    -- Granted scopes (e.g. 'resolve:batch')
+    scopes TEXT[] NOT NULL,
+
+    -- Timestamps
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ                -- Set when the owner revokes the key
+);
+
+-- Index for listing a user's keys
+CREATE INDEX IF NOT EXISTS idx_api_keys_user_id ON api_keys(user_id);
+
+COMMENT ON TABLE api_keys IS 'API keys authenticating machine clients with the X-API-Key header';
+COMMENT ON COLUMN api_keys.key_hash IS 'SHA3-256 of the API key; the key is never stored';
+COMMENT ON COLUMN api_keys.scopes IS 'Endpoints the key may call (resolve:batch)';
@@ -0,0 +1,25 @@
+-- Batched blockchain anchors for Veritas Q
+-- The server anchors the Merkle root of many seals in one transaction; each seal's
+-- anchor (root transaction plus its inclusion proof) is kept here until fetched
+
+CREATE TABLE IF NOT EXISTS anchor_proofs (
+    -- Anchor digest of the seal (SHA3-256, see VeritasSeal::anchor_digest)
+    digest BYTEA PRIMARY KEY,
+
+    -- Transaction anchoring the batch root
+    chain TEXT NOT NULL,
+    tx_id TEXT NOT NULL,
+    batch_root BYTEA NOT NULL,
+
+    -- Full BlockchainAnchor of the seal, including the Merkle path to the root
+    anchor JSONB NOT NULL,
+
+    -- Timestamps
+    anchored_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for finding the seals of one batch
+CREATE INDEX IF NOT EXISTS idx_anchor_proofs_batch_root ON anchor_proofs(batch_root);
+
+COMMENT ON TABLE anchor_proofs IS 'Per-seal inclusion proofs of batched blockchain anchors';
+COMMENT ON COLUMN anchor_proofs.anchor IS 'BlockchainAnchor to add to the seal, with its batch proof';
@@ -0,0 +1,7 @@
+-- Audit log events for Veritas Q
+-- Mock entropy use is audited without a seal (anonymous seals, streams, C2PA embeds)
+-- and counted platform-wide by event for the admin statistics
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_event_created ON audit_log(event, created_at DESC);
+
+COMMENT ON COLUMN audit_log.event IS 'Event name: seal_verified, seal_resolved, share_opened, mock_entropy_used';
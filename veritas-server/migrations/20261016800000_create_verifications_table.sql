@@ -0,0 +1,30 @@
+-- Verification counter for Veritas Q
+-- One row per verification of a stored seal by someone other than its owner,
+-- with a coarse origin only (no IP address, no user), plus a running count on
+-- the seal for listings and public lookups
+
+CREATE TABLE IF NOT EXISTS verifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+
+    -- Verified seal
+    seal_id UUID NOT NULL REFERENCES seals(id) ON DELETE CASCADE,
+
+    -- Verification outcome: "authentic", "tampered", "invalid_signature"
+    outcome TEXT NOT NULL,
+
+    -- Host of the site the request came from (Origin or Referer header), NULL for direct calls
+    site TEXT,
+
+    -- ISO 3166-1 alpha-2 country reported by the CDN, NULL when unknown
+    country TEXT,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for per-seal trends
+CREATE INDEX IF NOT EXISTS idx_verifications_seal_created ON verifications(seal_id, created_at DESC);
+
+ALTER TABLE seals ADD COLUMN IF NOT EXISTS verification_count BIGINT NOT NULL DEFAULT 0;
+
+COMMENT ON TABLE verifications IS 'Third-party verifications of stored seals, with a coarse origin';
+COMMENT ON COLUMN seals.verification_count IS 'Number of rows in verifications for the seal';
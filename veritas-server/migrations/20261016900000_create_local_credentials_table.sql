@@ -0,0 +1,20 @@
+-- Local credentials for Veritas Q
+-- Email/password logins of accounts managed by the server itself
+-- (AUTH_MODE=local), instead of Clerk. The account is a regular users row
+-- whose clerk_user_id is "local:<uuid>", the subject of the tokens we issue.
+
+CREATE TABLE IF NOT EXISTS local_credentials (
+    -- Account the credentials log into
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Login email, lowercased
+    email TEXT NOT NULL UNIQUE,
+
+    -- Argon2id hash in PHC string format
+    password_hash TEXT NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE local_credentials IS 'Email/password credentials of self-hosted accounts (AUTH_MODE=local)';
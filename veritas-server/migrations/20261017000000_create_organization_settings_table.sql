@@ -0,0 +1,24 @@
+-- Organization settings for Veritas Q
+-- White-label branding of the public verification endpoints: an
+-- organization's display name and logo are returned with the verification of
+-- its seals, or of any seal verified from its custom verification domain.
+
+CREATE TABLE IF NOT EXISTS organization_settings (
+    -- Organization (seals.organization_id)
+    organization_id UUID PRIMARY KEY,
+
+    -- Name shown on verification pages
+    display_name TEXT NOT NULL,
+
+    -- HTTPS URL of the logo
+    logo_url TEXT,
+
+    -- Host name serving the organization's verification pages, lowercased
+    verification_domain TEXT UNIQUE,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE organization_settings IS 'Branding of verification pages per organization (managed by administrators)';
+COMMENT ON COLUMN organization_settings.verification_domain IS 'Custom verification domain, matched against the Origin/Host of public verification requests';
@@ -0,0 +1,51 @@
+-- Seal tags and collections for Veritas Q
+-- Lets users organize their seals with free-form tags and named collections
+-- (e.g. "Ukraine field trip 2025"), both usable as seal list filters
+
+CREATE TABLE IF NOT EXISTS seal_tags (
+    -- Tagged seal; its owner owns the tag
+    seal_id UUID NOT NULL REFERENCES seals(id) ON DELETE CASCADE,
+
+    -- Tag, trimmed and lowercased
+    tag TEXT NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (seal_id, tag)
+);
+
+-- Index for filtering seals by tag
+CREATE INDEX IF NOT EXISTS idx_seal_tags_tag ON seal_tags(tag);
+
+CREATE TABLE IF NOT EXISTS collections (
+    -- Collection ID (UUID v4)
+    id UUID PRIMARY KEY,
+
+    -- Owner of the collection
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Name chosen by the owner, unique per owner
+    name TEXT NOT NULL,
+    description TEXT,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    UNIQUE (user_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS collection_seals (
+    collection_id UUID NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    seal_id UUID NOT NULL REFERENCES seals(id) ON DELETE CASCADE,
+
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (collection_id, seal_id)
+);
+
+-- Index for removing a seal from its collections and filtering by collection
+CREATE INDEX IF NOT EXISTS idx_collection_seals_seal_id ON collection_seals(seal_id);
+
+COMMENT ON TABLE seal_tags IS 'User-defined tags of seals';
+COMMENT ON TABLE collections IS 'Named groups of a user''s seals';
+COMMENT ON TABLE collection_seals IS 'Seals of each collection; a seal may belong to several collections';
@@ -0,0 +1,29 @@
+-- Offline-first sync for Veritas Q
+-- Mobile apps seal offline under client-generated IDs, push the seals when
+-- connectivity returns, and pull the seals changed on the server since their
+-- last sync (revocations, media deletion, seals made on other devices)
+
+-- When the seal last changed on the server; the pull cursor orders on it
+ALTER TABLE seals ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ;
+UPDATE seals SET updated_at = created_at WHERE updated_at IS NULL;
+ALTER TABLE seals ALTER COLUMN updated_at SET DEFAULT NOW();
+ALTER TABLE seals ALTER COLUMN updated_at SET NOT NULL;
+
+-- Changes clients see; verification counts change too often to sync
+CREATE TRIGGER seals_updated_at
+    BEFORE UPDATE ON seals
+    FOR EACH ROW
+    WHEN (
+        OLD.revoked_at IS DISTINCT FROM NEW.revoked_at
+        OR OLD.revocation_reason IS DISTINCT FROM NEW.revocation_reason
+        OR OLD.media_deleted_at IS DISTINCT FROM NEW.media_deleted_at
+        OR OLD.user_id IS DISTINCT FROM NEW.user_id
+        OR OLD.metadata IS DISTINCT FROM NEW.metadata
+        OR OLD.trust_tier IS DISTINCT FROM NEW.trust_tier
+    )
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Index for pulling a user's changed seals in cursor order
+CREATE INDEX IF NOT EXISTS idx_seals_user_updated ON seals(user_id, updated_at, id) WHERE user_id IS NOT NULL;
+
+COMMENT ON COLUMN seals.updated_at IS 'Last change clients sync (revocation, media deletion, metadata, trust tier)';
@@ -0,0 +1,68 @@
+-- Pre-aggregated statistics for Veritas Q
+-- Admin and usage statistics read these materialized views instead of counting the
+-- whole seals table. Each view covers complete UTC days only, up to its
+-- covered_until column (the start of the UTC day it was refreshed on); queries add
+-- what was created since from the base tables. The server refreshes them
+-- periodically with REFRESH MATERIALIZED VIEW CONCURRENTLY.
+
+-- When a batched anchor was queued, for anchor confirmation latency
+ALTER TABLE anchor_proofs ADD COLUMN IF NOT EXISTS queued_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN anchor_proofs.queued_at IS 'When the seal was queued for batched anchoring (NULL for anchors stored before it was recorded)';
+
+-- Seals per day, QRNG source and trust tier
+CREATE MATERIALIZED VIEW IF NOT EXISTS seal_stats_daily AS
+SELECT (created_at AT TIME ZONE 'UTC')::DATE AS day,
+       qrng_source,
+       trust_tier,
+       COUNT(*) AS seals,
+       COUNT(file_size) AS sized_seals,
+       COALESCE(SUM(file_size), 0)::BIGINT AS file_bytes,
+       date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS covered_until
+FROM seals
+WHERE created_at < date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+GROUP BY 1, 2, 3;
+
+-- Unique index required by REFRESH ... CONCURRENTLY
+CREATE UNIQUE INDEX IF NOT EXISTS idx_seal_stats_daily_key
+    ON seal_stats_daily(day, qrng_source, trust_tier);
+
+COMMENT ON MATERIALIZED VIEW seal_stats_daily IS 'Seals per UTC day, QRNG source and trust tier (complete days before covered_until)';
+
+-- Seals and sealed bytes per user and day, for account usage
+CREATE MATERIALIZED VIEW IF NOT EXISTS user_seal_stats_daily AS
+SELECT user_id,
+       (created_at AT TIME ZONE 'UTC')::DATE AS day,
+       COUNT(*) AS seals,
+       COALESCE(SUM(file_size), 0)::BIGINT AS sealed_bytes,
+       COALESCE(SUM(file_size) FILTER (WHERE media_deleted_at IS NULL), 0)::BIGINT AS media_bytes,
+       date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS covered_until
+FROM seals
+WHERE user_id IS NOT NULL
+AND created_at < date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+GROUP BY 1, 2;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_seal_stats_daily_key
+    ON user_seal_stats_daily(user_id, day);
+
+COMMENT ON MATERIALIZED VIEW user_seal_stats_daily IS 'Seals and sealed bytes per user and UTC day (complete days before covered_until)';
+
+-- Batched anchors per day and chain, with the delay between queueing and anchoring
+CREATE MATERIALIZED VIEW IF NOT EXISTS anchor_stats_daily AS
+SELECT (anchored_at AT TIME ZONE 'UTC')::DATE AS day,
+       chain,
+       COUNT(*) AS anchors,
+       COUNT(queued_at) AS timed_anchors,
+       COALESCE(SUM(EXTRACT(EPOCH FROM anchored_at - queued_at)), 0)::FLOAT8 AS latency_secs,
+       MAX(EXTRACT(EPOCH FROM anchored_at - queued_at))::FLOAT8 AS max_latency_secs,
+       date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS covered_until
+FROM anchor_proofs
+WHERE anchored_at < date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+GROUP BY 1, 2;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_anchor_stats_daily_key ON anchor_stats_daily(day, chain);
+
+COMMENT ON MATERIALIZED VIEW anchor_stats_daily IS 'Batched anchors per UTC day and chain with queueing-to-anchoring latency (complete days before covered_until)';
+
+-- Live counts of anchors since the last refresh
+CREATE INDEX IF NOT EXISTS idx_anchor_proofs_anchored_at ON anchor_proofs(anchored_at);
@@ -0,0 +1,30 @@
+-- Signing-key registry for Veritas Q
+-- Long-lived seal signing keys (SIGNING_KEY_FILE) are published here so verifiers can
+-- check the key ID written into each seal. Rotating keys retires the previous one with
+-- an overlapping validity period; retired keys keep vouching for their seals
+
+CREATE TABLE IF NOT EXISTS signing_keys (
+    -- Key ID written into seals (first 8 bytes of SHA3-256 of the public key, hex)
+    key_id TEXT PRIMARY KEY,
+
+    -- ML-DSA-65 public key
+    public_key BYTEA NOT NULL,
+
+    -- Validity period (valid_until NULL = open-ended)
+    valid_from TIMESTAMPTZ NOT NULL,
+    valid_until TIMESTAMPTZ,
+
+    -- Lifecycle: active (signing), retired (replaced), revoked (vouches for nothing)
+    status TEXT NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'retired', 'revoked')),
+
+    -- Timestamps
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ
+);
+
+-- Index for finding the keys still signing
+CREATE INDEX IF NOT EXISTS idx_signing_keys_active ON signing_keys(status) WHERE status = 'active';
+
+COMMENT ON TABLE signing_keys IS 'Registry of seal signing keys and their validity periods';
+COMMENT ON COLUMN signing_keys.valid_until IS 'End of validity; set to the rotation time plus the overlap when retired';
@@ -0,0 +1,123 @@
+-- Tenant isolation for Veritas Q
+-- Users belong to at most one organization; their seals are stamped with it
+-- (seals.organization_id). Repository queries filter every account's data
+-- on the tenant (user and organization) of the request. The row-level
+-- security policies below enforce the same rules in the database, for
+-- transactions that name their tenant with
+--   set_config('veritas.tenant_user_id', <user>, true)
+--   set_config('veritas.tenant_organization_id', <organization or ''>, true)
+--   set_config('veritas.tenant_all_organizations', <'on' or ''>, true)
+-- the last one covering the user's seals of every organization (personal
+-- data export and usage). They fail closed: a transaction naming no tenant
+-- sees no rows, unless it declares itself a system path (public
+-- verification, administration, background tasks, migrations) with
+--   set_config('veritas.tenant_bypass', 'on', true)
+-- The policies are only enforced once veritas_tenant_rls(true) has run
+-- (TENANT_RLS=true at startup).
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS organization_id UUID;
+
+-- Index for listing an organization's members
+CREATE INDEX IF NOT EXISTS idx_users_organization_id ON users(organization_id) WHERE organization_id IS NOT NULL;
+
+COMMENT ON COLUMN users.organization_id IS 'Organization (tenant) of the account; stamped on the seals it creates';
+
+-- Tenant named by the current transaction (NULL = none)
+CREATE OR REPLACE FUNCTION veritas_tenant_user() RETURNS UUID
+LANGUAGE SQL STABLE AS $$
+    SELECT NULLIF(current_setting('veritas.tenant_user_id', true), '')::UUID
+$$;
+
+CREATE OR REPLACE FUNCTION veritas_tenant_organization() RETURNS UUID
+LANGUAGE SQL STABLE AS $$
+    SELECT NULLIF(current_setting('veritas.tenant_organization_id', true), '')::UUID
+$$;
+
+CREATE OR REPLACE FUNCTION veritas_tenant_all_organizations() RETURNS BOOLEAN
+LANGUAGE SQL STABLE AS $$
+    SELECT COALESCE(current_setting('veritas.tenant_all_organizations', true), '') = 'on'
+$$;
+
+-- Whether the current transaction is a system path, seeing every row
+CREATE OR REPLACE FUNCTION veritas_tenant_bypass() RETURNS BOOLEAN
+LANGUAGE SQL STABLE AS $$
+    SELECT COALESCE(current_setting('veritas.tenant_bypass', true), '') = 'on'
+$$;
+
+-- Whether a row owned by `owner` in `organization` is visible: to system
+-- paths, and to the tenant owning it if made in its organization or outside
+-- any (or in any, for the personal scope). False when no tenant is named.
+CREATE OR REPLACE FUNCTION veritas_tenant_visible(owner UUID, organization UUID) RETURNS BOOLEAN
+LANGUAGE SQL STABLE AS $$
+    SELECT veritas_tenant_bypass()
+        OR COALESCE(owner = veritas_tenant_user()
+            AND (organization IS NULL OR organization = veritas_tenant_organization()
+                 OR veritas_tenant_all_organizations()), false)
+$$;
+
+DROP POLICY IF EXISTS tenant_isolation ON seals;
+CREATE POLICY tenant_isolation ON seals
+    USING (veritas_tenant_visible(user_id, organization_id));
+
+-- Rows belonging to a seal follow it (the seals policy applies to the subquery)
+DROP POLICY IF EXISTS tenant_isolation ON seal_tags;
+CREATE POLICY tenant_isolation ON seal_tags
+    USING (veritas_tenant_bypass()
+        OR EXISTS (SELECT 1 FROM seals s WHERE s.id = seal_id));
+
+DROP POLICY IF EXISTS tenant_isolation ON verifications;
+CREATE POLICY tenant_isolation ON verifications
+    USING (veritas_tenant_bypass()
+        OR EXISTS (SELECT 1 FROM seals s WHERE s.id = seal_id));
+
+DROP POLICY IF EXISTS tenant_isolation ON collection_seals;
+CREATE POLICY tenant_isolation ON collection_seals
+    USING (veritas_tenant_bypass()
+        OR (EXISTS (SELECT 1 FROM collections c WHERE c.id = collection_id)
+            AND EXISTS (SELECT 1 FROM seals s WHERE s.id = seal_id)));
+
+-- Rows belonging to an account follow their owner
+DROP POLICY IF EXISTS tenant_isolation ON collections;
+CREATE POLICY tenant_isolation ON collections
+    USING (veritas_tenant_visible(user_id, NULL));
+
+DROP POLICY IF EXISTS tenant_isolation ON seal_shares;
+CREATE POLICY tenant_isolation ON seal_shares
+    USING (veritas_tenant_visible(user_id, NULL));
+
+DROP POLICY IF EXISTS tenant_isolation ON api_keys;
+CREATE POLICY tenant_isolation ON api_keys
+    USING (veritas_tenant_visible(user_id, NULL));
+
+-- Enforce (true) or lift (false) the tenant policies, on the table owner too.
+-- Tables already in that state are skipped, to avoid taking their locks.
+CREATE OR REPLACE FUNCTION veritas_tenant_rls(enabled BOOLEAN) RETURNS VOID
+LANGUAGE plpgsql AS $$
+DECLARE
+    tbl TEXT;
+BEGIN
+    FOREACH tbl IN ARRAY ARRAY[
+        'seals', 'seal_tags', 'verifications', 'collection_seals',
+        'collections', 'seal_shares', 'api_keys'
+    ] LOOP
+        IF EXISTS (
+            SELECT 1 FROM pg_class
+            WHERE oid = tbl::regclass
+              AND relrowsecurity = enabled
+              AND relforcerowsecurity = enabled
+        ) THEN
+            CONTINUE;
+        END IF;
+
+        IF enabled THEN
+            EXECUTE format('ALTER TABLE %I ENABLE ROW LEVEL SECURITY', tbl);
+            EXECUTE format('ALTER TABLE %I FORCE ROW LEVEL SECURITY', tbl);
+        ELSE
+            EXECUTE format('ALTER TABLE %I NO FORCE ROW LEVEL SECURITY', tbl);
+            EXECUTE format('ALTER TABLE %I DISABLE ROW LEVEL SECURITY', tbl);
+        END IF;
+    END LOOP;
+END
+$$;
+
+COMMENT ON FUNCTION veritas_tenant_rls(BOOLEAN) IS 'Enforce or lift the tenant_isolation row-level security policies (TENANT_RLS)';
@@ -0,0 +1,32 @@
+-- Data retention policies for Veritas Q
+-- Organizations may have their seals' media marked deleted, and their seals
+-- deleted, a number of days after sealing. The retention janitor task
+-- enforces the policies and writes an audit entry for every seal it affects.
+
+CREATE TABLE IF NOT EXISTS retention_policies (
+    -- Organization (seals.organization_id)
+    organization_id UUID PRIMARY KEY,
+
+    -- Days after sealing before the media is deleted (NULL = kept)
+    media_retention_days INTEGER CHECK (media_retention_days > 0),
+
+    -- Days after sealing before the seal is deleted (NULL = kept)
+    seal_retention_days INTEGER CHECK (seal_retention_days > 0),
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CHECK (media_retention_days IS NOT NULL OR seal_retention_days IS NOT NULL)
+);
+
+-- Index for finding an organization's oldest seals
+CREATE INDEX IF NOT EXISTS idx_seals_organization_created ON seals(organization_id, created_at) WHERE organization_id IS NOT NULL;
+
+-- Context of audit entries outliving their seal (retention deletions)
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS details JSONB;
+
+COMMENT ON TABLE retention_policies IS 'Retention of seals and their media per organization (managed by administrators)';
+COMMENT ON COLUMN retention_policies.media_retention_days IS 'Days after sealing before media_deleted_at is set';
+COMMENT ON COLUMN retention_policies.seal_retention_days IS 'Days after sealing before the seal is deleted';
+COMMENT ON COLUMN audit_log.details IS 'Event context, e.g. seal_id and organization_id of media_expired and seal_expired entries';
+COMMENT ON COLUMN audit_log.event IS 'Event name: seal_verified, seal_resolved, share_opened, mock_entropy_used, media_expired, seal_expired';
@@ -0,0 +1,27 @@
+-- Legal hold for Veritas Q
+-- Seals kept as evidence under litigation: administrators place them under
+-- legal hold, which exempts the seal from retention deletion and its media
+-- from retention and GDPR erasure until the hold is released. Placing and
+-- releasing holds is audited (legal_hold_placed, legal_hold_released).
+
+ALTER TABLE seals ADD COLUMN IF NOT EXISTS legal_hold BOOLEAN NOT NULL DEFAULT false;
+
+-- Clients see holds in sync pulls
+DROP TRIGGER IF EXISTS seals_updated_at ON seals;
+CREATE TRIGGER seals_updated_at
+    BEFORE UPDATE ON seals
+    FOR EACH ROW
+    WHEN (
+        OLD.revoked_at IS DISTINCT FROM NEW.revoked_at
+        OR OLD.revocation_reason IS DISTINCT FROM NEW.revocation_reason
+        OR OLD.media_deleted_at IS DISTINCT FROM NEW.media_deleted_at
+        OR OLD.user_id IS DISTINCT FROM NEW.user_id
+        OR OLD.metadata IS DISTINCT FROM NEW.metadata
+        OR OLD.trust_tier IS DISTINCT FROM NEW.trust_tier
+        OR OLD.legal_hold IS DISTINCT FROM NEW.legal_hold
+    )
+    EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON COLUMN seals.legal_hold IS 'Evidence under litigation: exempt from retention deletion and media erasure';
+COMMENT ON COLUMN seals.updated_at IS 'Last change clients sync (revocation, media deletion, metadata, trust tier, legal hold)';
+COMMENT ON COLUMN audit_log.event IS 'Event name: seal_verified, seal_resolved, share_opened, mock_entropy_used, media_expired, seal_expired, legal_hold_placed, legal_hold_released';
@@ -0,0 +1,247 @@
+//! Batched anchoring of new seals
+//!
+//! Anchoring every seal in its own transaction costs one fee per seal. When
+//! `ANCHOR_BATCH_INTERVAL_SECS` is set, `/seal` instead queues the anchor
+//! digest of each new seal with the [`AnchorBatcher`], which periodically
+//! builds an [`AnchorBatch`] from the queue and anchors only its Merkle root
+//! on Bitcoin (OpenTimestamps). Each seal's anchor, with its inclusion proof,
+//! is stored in PostgreSQL when configured (in memory otherwise) and served by
+//! `GET /api/v1/anchors/{digest}`, for the owner to add to the seal.
+//!
+//! The queue itself lives in memory: digests not anchored yet are lost on
+//! restart.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use veritas_core::{AnchorBatch, AnchorProvider, BlockchainAnchor};
+
+use crate::db::AnchorProofRepository;
+use crate::error::ApiError;
+
+/// Most digests waiting for the next batch; further seals are not anchored.
+pub const MAX_PENDING_DIGESTS: usize = 100_000;
+
+/// Where a seal stands in batched anchoring
+#[derive(Debug, Clone)]
+pub enum BatchedAnchor {
+    /// Waiting for the next batch
+    Queued,
+    /// Anchored; the anchor to add to the seal
+    Anchored(BlockchainAnchor),
+}
+
+/// Anchor storage backend
+enum ProofBackend {
+    /// PostgreSQL storage (production)
+    Postgres(Arc<AnchorProofRepository>),
+    /// In-memory storage (development fallback)
+    Memory(DashMap<[u8; 32], BlockchainAnchor>),
+}
+
+/// Queues anchor digests and anchors them in batches.
+pub struct AnchorBatcher {
+    provider: Arc<dyn AnchorProvider>,
+    /// Queued digests and when they were first queued
+    pending: Mutex<HashMap<[u8; 32], DateTime<Utc>>>,
+    proofs: ProofBackend,
+}
+
+impl AnchorBatcher {
+    /// Create a batcher keeping anchors in memory.
+    pub fn in_memory(provider: Arc<dyn AnchorProvider>) -> Self {
+        Self {
+            provider,
+            pending: Mutex::new(HashMap::new()),
+            proofs: ProofBackend::Memory(DashMap::new()),
+        }
+    }
+
+    /// Create a batcher keeping anchors in PostgreSQL.
+    pub fn with_repository(
+        provider: Arc<dyn AnchorProvider>,
+        repo: Arc<AnchorProofRepository>,
+    ) -> Self {
+        Self {
+            provider,
+            pending: Mutex::new(HashMap::new()),
+            proofs: ProofBackend::Postgres(repo),
+        }
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<[u8; 32], DateTime<Utc>>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queue a seal's anchor digest for the next batch.
+    ///
+    /// Returns false when the queue is full.
+    pub fn submit(&self, digest: [u8; 32]) -> bool {
+        let mut pending = self.pending();
+        if pending.len() >= MAX_PENDING_DIGESTS {
+            tracing::warn!("Anchor batch queue full, seal not anchored");
+            return false;
+        }
+        pending.entry(digest).or_insert_with(Utc::now);
+        true
+    }
+
+    /// Anchor the queued digests in one transaction.
+    ///
+    /// Returns the number of seals anchored. Digests stay queued until their
+    /// anchors are stored, so a failed flush is retried with the next batch.
+    pub async fn flush(&self) -> Result<usize, ApiError> {
+        let queued: Vec<([u8; 32], DateTime<Utc>)> = self
+            .pending()
+            .iter()
+            .map(|(digest, queued_at)| (*digest, *queued_at))
+            .collect();
+        let digests: Vec<[u8; 32]> = queued.iter().map(|(digest, _)| *digest).collect();
+        let Some(batch) = AnchorBatch::new(&digests) else {
+            return Ok(0);
+        };
+
+        let root_anchor = self.provider.anchor(&batch.root()).await.map_err(|e| {
+            tracing::warn!(error = %e, "Failed to anchor batch root");
+            ApiError::service_unavailable("Anchoring is not available")
+        })?;
+        let anchors: Vec<([u8; 32], BlockchainAnchor, DateTime<Utc>)> = queued
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (digest, queued_at))| {
+                Some((*digest, batch.anchor_for(index, &root_anchor)?, *queued_at))
+            })
+            .collect();
+
+        match &self.proofs {
+            ProofBackend::Postgres(repo) => repo.insert_batch(&anchors).await.map_err(|e| {
+                tracing::error!(error = %e, "Failed to store batched anchors");
+                ApiError::internal("A database error occurred")
+            })?,
+            ProofBackend::Memory(map) => {
+                for (digest, anchor, _) in &anchors {
+                    map.insert(*digest, anchor.clone());
+                }
+            }
+        }
+
+        let mut pending = self.pending();
+        for digest in &digests {
+            pending.remove(digest);
+        }
+        Ok(anchors.len())
+    }
+
+    /// Batched anchoring state of an anchor digest (`None` if unknown).
+    pub async fn status(&self, digest: &[u8; 32]) -> Result<Option<BatchedAnchor>, ApiError> {
+        let anchor = match &self.proofs {
+            ProofBackend::Postgres(repo) => repo.find(digest).await.map_err(|e| {
+                tracing::error!(error = %e, "Failed to load batched anchor");
+                ApiError::internal("A database error occurred")
+            })?,
+            ProofBackend::Memory(map) => map.get(digest).map(|entry| entry.value().clone()),
+        };
+        Ok(match anchor {
+            Some(anchor) => Some(BatchedAnchor::Anchored(anchor)),
+            None if self.pending().contains_key(digest) => Some(BatchedAnchor::Queued),
+            None => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use veritas_core::{AnchorStatus, AnchorVerifier, VeritasSeal};
+
+    /// Records the digests it anchors, or fails
+    struct FakeProvider {
+        fail: bool,
+        anchored: Mutex<Vec<[u8; 32]>>,
+    }
+
+    #[async_trait]
+    impl AnchorVerifier for FakeProvider {
+        fn chain(&self) -> &str {
+            "bitcoin"
+        }
+
+        async fn verify(
+            &self,
+            _seal: &VeritasSeal,
+            _anchor: &BlockchainAnchor,
+        ) -> veritas_core::Result<AnchorStatus> {
+            Ok(AnchorStatus::Pending)
+        }
+    }
+
+    #[async_trait]
+    impl AnchorProvider for FakeProvider {
+        async fn anchor(&self, digest: &[u8; 32]) -> veritas_core::Result<BlockchainAnchor> {
+            if self.fail {
+                return Err(veritas_core::VeritasError::AnchorError("offline".into()));
+            }
+            self.anchored.lock().unwrap().push(*digest);
+            Ok(BlockchainAnchor {
+                chain: "bitcoin".into(),
+                tx_id: "https://calendar.example".into(),
+                block_height: 0,
+                proof: Some(vec![0x08]),
+                batch: None,
+            })
+        }
+    }
+
+    fn provider(fail: bool) -> Arc<FakeProvider> {
+        Arc::new(FakeProvider {
+            fail,
+            anchored: Mutex::new(Vec::new()),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_flush_anchors_one_root_per_batch() {
+        let provider = provider(false);
+        let batcher = AnchorBatcher::in_memory(provider.clone());
+        assert_eq!(batcher.flush().await.unwrap(), 0);
+
+        let digests = [[1u8; 32], [2u8; 32], [3u8; 32]];
+        for digest in digests {
+            assert!(batcher.submit(digest));
+        }
+        assert!(matches!(
+            batcher.status(&digests[0]).await.unwrap(),
+            Some(BatchedAnchor::Queued)
+        ));
+        assert!(batcher.status(&[9u8; 32]).await.unwrap().is_none());
+
+        assert_eq!(batcher.flush().await.unwrap(), 3);
+        let roots = provider.anchored.lock().unwrap().clone();
+        assert_eq!(roots.len(), 1);
+        for digest in &digests {
+            let Some(BatchedAnchor::Anchored(anchor)) = batcher.status(digest).await.unwrap()
+            else {
+                panic!("digest not anchored");
+            };
+            assert_eq!(anchor.tx_id, "https://calendar.example");
+            assert_eq!(anchor.batch.unwrap().root, roots[0]);
+        }
+
+        // The queue is empty again
+        assert_eq!(batcher.flush().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_keeps_digests_queued() {
+        let batcher = AnchorBatcher::in_memory(provider(true));
+        assert!(batcher.submit([7u8; 32]));
+        assert!(batcher.flush().await.is_err());
+        assert!(matches!(
+            batcher.status(&[7u8; 32]).await.unwrap(),
+            Some(BatchedAnchor::Queued)
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+//! Blockchain anchor checks for `/verify`
+//!
+//! A seal's anchors claim it was published on Solana or Bitcoin. `/verify`
+//! checks each claim on its chain with [`AnchorChecker`]: the transaction
+//! must exist, commit to the seal's digest, and sit under at least
+//! `ANCHOR_MIN_CONFIRMATIONS` blocks. Chains are queried through their public
+//! endpoints, and all checks of one request share `ANCHOR_CHECK_TIMEOUT_MS`;
+//! anchors not checked in time are reported as such, without failing the
+//! verification.
+
+use std::time::Duration;
+
+use veritas_core::{
+    verify_anchors, AnchorStatus, AnchorVerifier, BlockchainAnchor, OpenTimestamps, SolanaVerifier,
+    VeritasSeal,
+};
+
+/// Default confirmations an anchor needs to count as confirmed.
+pub const DEFAULT_ANCHOR_MIN_CONFIRMATIONS: u64 = 1;
+
+/// Default time allowed to the anchor checks of one request.
+pub const DEFAULT_ANCHOR_CHECK_TIMEOUT_MS: u64 = 5000;
+
+/// Checks seal anchors on Solana and Bitcoin
+pub struct AnchorChecker {
+    verifiers: Vec<Box<dyn AnchorVerifier>>,
+    min_confirmations: u64,
+    timeout: Duration,
+}
+
+impl AnchorChecker {
+    /// Check anchors through the public Solana clusters and OpenTimestamps
+    /// calendars. A zero `timeout` disables the checks.
+    pub fn new(min_confirmations: u64, timeout: Duration) -> Self {
+        let mut verifiers: Vec<Box<dyn AnchorVerifier>> = Vec::new();
+        if !timeout.is_zero() {
+            match SolanaVerifier::public_clusters() {
+                Ok(solana) => verifiers.extend(
+                    solana
+                        .into_iter()
+                        .map(|v| Box::new(v) as Box<dyn AnchorVerifier>),
+                ),
+                Err(e) => tracing::warn!(error = %e, "Solana anchor checks disabled"),
+            }
+            match OpenTimestamps::new() {
+                Ok(bitcoin) => verifiers.push(Box::new(bitcoin)),
+                Err(e) => tracing::warn!(error = %e, "Bitcoin anchor checks disabled"),
+            }
+        }
+        Self {
+            verifiers,
+            min_confirmations,
+            timeout,
+        }
+    }
+
+    /// Status of each anchor of `seal`, `None` for those that could not be
+    /// checked (checks disabled, chain unreachable or out of time).
+    pub async fn check<'a>(
+        &self,
+        seal: &'a VeritasSeal,
+    ) -> Vec<(&'a BlockchainAnchor, Option<AnchorStatus>)> {
+        if seal.anchors().next().is_none() {
+            return Vec::new();
+        }
+        if self.verifiers.is_empty() {
+            return seal.anchors().map(|anchor| (anchor, None)).collect();
+        }
+
+        let verifiers: Vec<&dyn AnchorVerifier> =
+            self.verifiers.iter().map(AsRef::as_ref).collect();
+        match tokio::time::timeout(
+            self.timeout,
+            verify_anchors(seal, &verifiers, self.min_confirmations),
+        )
+        .await
+        {
+            Ok(results) => results
+                .into_iter()
+                .map(|(anchor, status)| {
+                    let status = status
+                        .inspect_err(|e| {
+                            tracing::warn!(chain = %anchor.chain, error = %e, "Anchor check failed")
+                        })
+                        .ok();
+                    (anchor, status)
+                })
+                .collect(),
+            Err(_) => {
+                tracing::warn!(timeout = ?self.timeout, "Anchor checks timed out");
+                seal.anchors().map(|anchor| (anchor, None)).collect()
+            }
+        }
+    }
+}
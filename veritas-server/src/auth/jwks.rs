@@ -0,0 +1,633 @@
+//! Trusted token issuers and their JSON Web Key Sets
+//!
+//! Tokens are accepted from Clerk and from any additional issuer listed in
+//! `JWT_ISSUERS_FILE` (e.g. an internal IdP), each with its own `iss` and
+//! `aud` requirements. Every issuer has its own key set cache. Key sets live
+//! as long as the endpoint's `Cache-Control` max-age says (1 hour by
+//! default), are refreshed by the `jwks_refresh` task before they expire,
+//! and are revalidated with `If-None-Match` when the endpoint sent an `ETag`.
+//! In self-hosted mode the server's own `local` issuer is trusted too, with
+//! its public key instead of a key set.
+
+use std::time::{Duration, Instant};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::{jwk, Algorithm, DecodingKey};
+use reqwest::header::{HeaderMap, CACHE_CONTROL, ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use super::local::LocalAuth;
+use crate::error::ApiError;
+
+/// Key set lifetime when the endpoint sends no max-age (1 hour)
+const JWKS_CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// Shortest key set lifetime, whatever the endpoint asks
+const MIN_JWKS_TTL: Duration = Duration::from_secs(60);
+
+/// Longest key set lifetime, whatever the endpoint asks
+const MAX_JWKS_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// Share of its lifetime after which a key set is refreshed in the background
+const REFRESH_AHEAD_RATIO: f64 = 0.8;
+
+/// How often the `jwks_refresh` task looks for key sets due for a refresh.
+pub const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Name of the Clerk issuer, whose subjects are used as user IDs unchanged
+pub const CLERK_ISSUER_NAME: &str = "clerk";
+
+/// Name of the server's own issuer (`AUTH_MODE=local`)
+pub const LOCAL_ISSUER_NAME: &str = "local";
+
+/// Cached JWKS keys with their freshness
+struct CachedJwks {
+    keys: Vec<jwk::Jwk>,
+    fetched_at: Instant,
+    ttl: Duration,
+    etag: Option<String>,
+}
+
+impl CachedJwks {
+    fn is_fresh(&self) -> bool {
+        self.fetched_at.elapsed() < self.ttl
+    }
+
+    fn refresh_due(&self) -> bool {
+        self.fetched_at.elapsed() >= self.ttl.mul_f64(REFRESH_AHEAD_RATIO)
+    }
+}
+
+/// JWKS cache that fetches and caches an issuer's JSON Web Key Set
+pub struct JwksCache {
+    keys: RwLock<Option<CachedJwks>>,
+    jwks_url: String,
+    http_client: reqwest::Client,
+}
+
+/// JWKS response structure
+#[derive(Deserialize)]
+struct JwksResponse {
+    keys: Vec<jwk::Jwk>,
+}
+
+impl JwksCache {
+    /// Create a new JWKS cache for the given JWKS URL
+    pub fn new(jwks_url: String) -> Self {
+        Self {
+            keys: RwLock::new(None),
+            jwks_url,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Get cached JWKS keys, fetching them if expired or not yet cached
+    pub(super) async fn get_keys(&self) -> Result<Vec<jwk::Jwk>, ApiError> {
+        // Try read lock first (fast path)
+        {
+            let cache = self.keys.read().await;
+            if let Some(cached) = cache.as_ref().filter(|cached| cached.is_fresh()) {
+                return Ok(cached.keys.clone());
+            }
+        }
+
+        // Cache miss or expired — acquire write lock and fetch
+        let mut cache = self.keys.write().await;
+
+        // Double-check after acquiring write lock (another task may have refreshed)
+        if let Some(cached) = cache.as_ref().filter(|cached| cached.is_fresh()) {
+            return Ok(cached.keys.clone());
+        }
+
+        self.fetch(&mut cache).await
+    }
+
+    /// Refresh the key set if it is missing or close to expiry, returning
+    /// whether it was fetched.
+    pub async fn refresh_if_due(&self) -> Result<bool, ApiError> {
+        let cache = self.keys.read().await;
+        if cache.as_ref().is_some_and(|cached| !cached.refresh_due()) {
+            return Ok(false);
+        }
+        drop(cache);
+
+        let mut cache = self.keys.write().await;
+        if cache.as_ref().is_some_and(|cached| !cached.refresh_due()) {
+            return Ok(false);
+        }
+        self.fetch(&mut cache).await?;
+        Ok(true)
+    }
+
+    /// Number of keys in the key set, fetching it if expired or not yet cached
+    pub async fn key_count(&self) -> Result<usize, ApiError> {
+        Ok(self.get_keys().await?.len())
+    }
+
+    /// Find a JWK by key ID
+    pub(super) async fn find_key(&self, kid: &str) -> Result<jwk::Jwk, ApiError> {
+        let keys = self.get_keys().await?;
+        keys.into_iter()
+            .find(|k| k.common.key_id.as_deref() == Some(kid))
+            .ok_or_else(|| {
+                ApiError::auth_error(
+                    "AUTH_UNKNOWN_KEY",
+                    format!("No matching key found for kid '{}'", kid),
+                )
+            })
+    }
+
+    /// Fetch the key set into `cache`, revalidating the cached one when the
+    /// endpoint gave it an ETag.
+    async fn fetch(&self, cache: &mut Option<CachedJwks>) -> Result<Vec<jwk::Jwk>, ApiError> {
+        let unavailable = || ApiError::internal("Authentication service temporarily unavailable");
+
+        let mut request = self.http_client.get(&self.jwks_url);
+        if let Some(etag) = cache.as_ref().and_then(|cached| cached.etag.as_deref()) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await.map_err(|e| {
+            tracing::error!(jwks_url = %self.jwks_url, error = %e, "Failed to fetch JWKS");
+            unavailable()
+        })?;
+        let ttl = cache_ttl(response.headers());
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cache.as_mut() {
+                cached.fetched_at = Instant::now();
+                cached.ttl = ttl;
+                tracing::debug!(jwks_url = %self.jwks_url, "JWKS unchanged");
+                return Ok(cached.keys.clone());
+            }
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(
+                jwks_url = %self.jwks_url,
+                status = %status,
+                "JWKS endpoint returned error"
+            );
+            return Err(unavailable());
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let jwks: JwksResponse = response.json().await.map_err(|e| {
+            tracing::error!(jwks_url = %self.jwks_url, error = %e, "Failed to parse JWKS response");
+            unavailable()
+        })?;
+
+        let keys = jwks.keys;
+        tracing::info!(
+            jwks_url = %self.jwks_url,
+            key_count = keys.len(),
+            ttl_secs = ttl.as_secs(),
+            "Refreshed JWKS cache"
+        );
+
+        *cache = Some(CachedJwks {
+            keys: keys.clone(),
+            fetched_at: Instant::now(),
+            ttl,
+            etag,
+        });
+
+        Ok(keys)
+    }
+
+    /// Cache pre-loaded with `keys` (no HTTP fetching while fresh)
+    #[cfg(test)]
+    pub(super) fn with_keys(jwks_url: &str, keys: Vec<jwk::Jwk>) -> Self {
+        Self {
+            keys: RwLock::new(Some(CachedJwks {
+                keys,
+                fetched_at: Instant::now(),
+                ttl: JWKS_CACHE_TTL,
+                etag: None,
+            })),
+            jwks_url: jwks_url.to_string(),
+            http_client: reqwest::Client::new(),
+        }
+    }
+}
+
+/// Key set lifetime from a JWKS response's `Cache-Control` header.
+///
+/// `max-age` is clamped to between one minute and one day; `no-cache` and
+/// `no-store` get the one minute minimum.
+fn cache_ttl(headers: &HeaderMap) -> Duration {
+    let Some(cache_control) = headers
+        .get(CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return JWKS_CACHE_TTL;
+    };
+
+    let mut ttl = JWKS_CACHE_TTL;
+    for directive in cache_control.split(',').map(str::trim) {
+        let directive = directive.to_ascii_lowercase();
+        if directive == "no-cache" || directive == "no-store" {
+            return MIN_JWKS_TTL;
+        }
+        if let Some(secs) = directive
+            .strip_prefix("max-age=")
+            .and_then(|secs| secs.parse().ok())
+        {
+            ttl = Duration::from_secs(secs);
+        }
+    }
+    ttl.clamp(MIN_JWKS_TTL, MAX_JWKS_TTL)
+}
+
+/// A trusted token issuer
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JwtIssuerConfig {
+    /// Short name; users of issuers other than Clerk are identified as
+    /// `<name>:<sub>`, so subjects of different issuers never collide
+    pub name: String,
+    /// JSON Web Key Set of the issuer
+    pub jwks_url: String,
+    /// Required `iss` claim, which also routes tokens to this issuer
+    /// (mandatory except for Clerk, which accepts any `iss` when unset)
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// Accepted `aud` values (empty: the audience is not checked)
+    #[serde(default)]
+    pub audiences: Vec<String>,
+}
+
+impl JwtIssuerConfig {
+    /// The Clerk issuer, from `CLERK_JWKS_URL`, `CLERK_ISSUER` and `CLERK_AUDIENCE`
+    pub fn clerk(jwks_url: String, issuer: Option<String>, audiences: Vec<String>) -> Self {
+        Self {
+            name: CLERK_ISSUER_NAME.to_string(),
+            jwks_url,
+            issuer,
+            audiences,
+        }
+    }
+
+    /// Load the issuers trusted besides Clerk from `JWT_ISSUERS_FILE` if set.
+    pub fn additional_from_env() -> Result<Vec<Self>, String> {
+        match std::env::var("JWT_ISSUERS_FILE") {
+            Ok(path) => {
+                let json = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("failed to read {}: {}", path, e))?;
+                Self::parse_additional(&json).map_err(|e| format!("invalid {}: {}", path, e))
+            }
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Parse the additional issuers of `JWT_ISSUERS_FILE` (a JSON array).
+    pub fn parse_additional(json: &str) -> Result<Vec<Self>, String> {
+        let issuers: Vec<Self> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        for (i, issuer) in issuers.iter().enumerate() {
+            if issuer.name.is_empty() || issuer.name.contains(':') {
+                return Err(format!("invalid issuer name '{}'", issuer.name));
+            }
+            if issuer.name == CLERK_ISSUER_NAME {
+                return Err(format!(
+                    "issuer name '{}' is reserved (configure Clerk with CLERK_* variables)",
+                    CLERK_ISSUER_NAME
+                ));
+            }
+            if issuer.name == LOCAL_ISSUER_NAME {
+                return Err(format!(
+                    "issuer name '{}' is reserved (enabled by AUTH_MODE=local)",
+                    LOCAL_ISSUER_NAME
+                ));
+            }
+            if issuers[..i].iter().any(|other| other.name == issuer.name) {
+                return Err(format!("duplicate issuer name '{}'", issuer.name));
+            }
+            if issuer.jwks_url.is_empty() {
+                return Err(format!("issuer '{}' has no jwks_url", issuer.name));
+            }
+            if issuer.issuer.as_deref().is_none_or(str::is_empty) {
+                return Err(format!("issuer '{}' has no issuer (iss)", issuer.name));
+            }
+        }
+        Ok(issuers)
+    }
+
+    /// User ID of a token subject.
+    pub(super) fn user_id(&self, subject: &str) -> String {
+        if self.name == CLERK_ISSUER_NAME {
+            subject.to_string()
+        } else {
+            format!("{}:{}", self.name, subject)
+        }
+    }
+}
+
+/// Verification keys of an issuer
+pub(super) enum IssuerKeys {
+    /// Fetched from the issuer's JWKS endpoint (RS256)
+    Jwks(JwksCache),
+    /// The server's own public key
+    Local {
+        kid: String,
+        key: DecodingKey,
+        algorithm: Algorithm,
+    },
+}
+
+impl IssuerKeys {
+    /// Key and algorithm verifying tokens signed with key `kid`
+    pub(super) async fn find_key(&self, kid: &str) -> Result<(DecodingKey, Algorithm), ApiError> {
+        match self {
+            Self::Jwks(cache) => {
+                let jwk = cache.find_key(kid).await?;
+                let key = DecodingKey::from_jwk(&jwk).map_err(|e| {
+                    tracing::error!(
+                        error = %e,
+                        kid = %kid,
+                        "Failed to convert JWK to decoding key"
+                    );
+                    ApiError::auth_error("AUTH_INVALID_TOKEN", "Failed to process signing key")
+                })?;
+                Ok((key, Algorithm::RS256))
+            }
+            Self::Local {
+                kid: local_kid,
+                key,
+                algorithm,
+            } if local_kid == kid => Ok((key.clone(), *algorithm)),
+            Self::Local { .. } => Err(ApiError::auth_error(
+                "AUTH_UNKNOWN_KEY",
+                format!("No matching key found for kid '{}'", kid),
+            )),
+        }
+    }
+}
+
+/// An issuer and its keys
+pub(super) struct TrustedIssuer {
+    pub(super) config: JwtIssuerConfig,
+    pub(super) keys: IssuerKeys,
+}
+
+/// The issuers whose tokens are accepted
+pub struct JwtIssuers {
+    issuers: Vec<TrustedIssuer>,
+}
+
+/// The only claim read before the signature is checked
+#[derive(Deserialize)]
+struct UnverifiedIssuer {
+    iss: Option<String>,
+}
+
+impl JwtIssuers {
+    /// Trust the given issuers, each with an empty key set cache
+    pub fn new(configs: Vec<JwtIssuerConfig>) -> Self {
+        Self {
+            issuers: configs
+                .into_iter()
+                .map(|config| TrustedIssuer {
+                    keys: IssuerKeys::Jwks(JwksCache::new(config.jwks_url.clone())),
+                    config,
+                })
+                .collect(),
+        }
+    }
+
+    /// Also trust the tokens issued by this server.
+    pub fn trust_local(&mut self, auth: &LocalAuth) {
+        let (kid, key, algorithm) = auth.key();
+        self.issuers.push(TrustedIssuer {
+            config: auth.issuer_config(),
+            keys: IssuerKeys::Local {
+                kid: kid.to_string(),
+                key: key.clone(),
+                algorithm,
+            },
+        });
+    }
+
+    /// Names of the trusted issuers
+    pub fn names(&self) -> Vec<&str> {
+        self.issuers
+            .iter()
+            .map(|issuer| issuer.config.name.as_str())
+            .collect()
+    }
+
+    /// Issuer that must have signed `token`: the one whose `issuer` is the
+    /// token's `iss` claim, else Clerk when it accepts any `iss`.
+    pub(super) fn for_token(&self, token: &str) -> Result<&TrustedIssuer, ApiError> {
+        let claimed = token
+            .split('.')
+            .nth(1)
+            .and_then(|payload| URL_SAFE_NO_PAD.decode(payload).ok())
+            .and_then(|payload| serde_json::from_slice::<UnverifiedIssuer>(&payload).ok())
+            .and_then(|claims| claims.iss);
+
+        claimed
+            .as_deref()
+            .and_then(|iss| {
+                self.issuers
+                    .iter()
+                    .find(|issuer| issuer.config.issuer.as_deref() == Some(iss))
+            })
+            .or_else(|| {
+                self.issuers
+                    .iter()
+                    .find(|issuer| issuer.config.issuer.is_none())
+            })
+            .ok_or_else(|| {
+                ApiError::auth_error(
+                    "AUTH_UNKNOWN_ISSUER",
+                    format!(
+                        "Token issuer '{}' is not trusted",
+                        claimed.as_deref().unwrap_or_default()
+                    ),
+                )
+            })
+    }
+
+    /// Refresh the key sets that are missing or close to expiry, returning
+    /// how many were fetched. Every issuer is tried even if one fails.
+    pub async fn refresh_due(&self) -> Result<usize, String> {
+        let mut refreshed = 0;
+        let mut errors = Vec::new();
+        for issuer in &self.issuers {
+            let IssuerKeys::Jwks(cache) = &issuer.keys else {
+                continue;
+            };
+            match cache.refresh_if_due().await {
+                Ok(fetched) => refreshed += usize::from(fetched),
+                Err(e) => errors.push(format!("{}: {}", issuer.config.name, e)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(refreshed)
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Number of keys of each issuer, fetching key sets that expired.
+    pub async fn key_counts(&self) -> Result<Vec<(&str, usize)>, String> {
+        let mut counts = Vec::with_capacity(self.issuers.len());
+        for issuer in &self.issuers {
+            let count = match &issuer.keys {
+                IssuerKeys::Jwks(cache) => cache
+                    .key_count()
+                    .await
+                    .map_err(|e| format!("{}: {}", issuer.config.name, e))?,
+                IssuerKeys::Local { .. } => 1,
+            };
+            counts.push((issuer.config.name.as_str(), count));
+        }
+        Ok(counts)
+    }
+
+    /// Issuers over pre-loaded key set caches
+    #[cfg(test)]
+    pub(super) fn with_caches(issuers: Vec<(JwtIssuerConfig, JwksCache)>) -> Self {
+        Self {
+            issuers: issuers
+                .into_iter()
+                .map(|(config, cache)| TrustedIssuer {
+                    config,
+                    keys: IssuerKeys::Jwks(cache),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use axum::http::{header, HeaderValue};
+    use axum::routing::get;
+    use axum::Router;
+
+    fn headers(cache_control: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_CONTROL, HeaderValue::from_str(cache_control).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_cache_ttl_from_cache_control() {
+        assert_eq!(cache_ttl(&HeaderMap::new()), JWKS_CACHE_TTL);
+        assert_eq!(
+            cache_ttl(&headers("public, max-age=600, must-revalidate")),
+            Duration::from_secs(600)
+        );
+        assert_eq!(cache_ttl(&headers("max-age=5")), MIN_JWKS_TTL);
+        assert_eq!(cache_ttl(&headers("max-age=31536000")), MAX_JWKS_TTL);
+        assert_eq!(cache_ttl(&headers("No-Store")), MIN_JWKS_TTL);
+        assert_eq!(cache_ttl(&headers("private")), JWKS_CACHE_TTL);
+    }
+
+    #[test]
+    fn test_parse_additional_issuers() {
+        let issuers = JwtIssuerConfig::parse_additional(
+            r#"[{"name": "corp", "jwks_url": "https://idp.corp/jwks", "issuer": "https://idp.corp", "audiences": ["veritas"]}]"#,
+        )
+        .unwrap();
+        assert_eq!(issuers.len(), 1);
+        assert_eq!(issuers[0].issuer.as_deref(), Some("https://idp.corp"));
+        assert_eq!(issuers[0].user_id("alice"), "corp:alice");
+
+        for (json, error) in [
+            (
+                r#"[{"name": "corp", "jwks_url": "https://idp.corp/jwks"}]"#,
+                "no issuer",
+            ),
+            (
+                r#"[{"name": "clerk", "jwks_url": "https://a/jwks", "issuer": "https://a"}]"#,
+                "reserved",
+            ),
+            (
+                r#"[{"name": "a", "jwks_url": "https://a/jwks", "issuer": "https://a"},
+                    {"name": "a", "jwks_url": "https://b/jwks", "issuer": "https://b"}]"#,
+                "duplicate",
+            ),
+            (
+                r#"[{"name": "a", "jwks_url": "https://a/jwks", "issuer": "https://a", "aud": "x"}]"#,
+                "unknown field",
+            ),
+        ] {
+            let err = JwtIssuerConfig::parse_additional(json).unwrap_err();
+            assert!(err.contains(error), "{}: {}", error, err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_revalidates_with_etag() {
+        let full_fetches = Arc::new(AtomicUsize::new(0));
+        let revalidations = Arc::new(AtomicUsize::new(0));
+        let (full, revalidated) = (Arc::clone(&full_fetches), Arc::clone(&revalidations));
+        let app = Router::new().route(
+            "/jwks",
+            get(move |headers: axum::http::HeaderMap| {
+                let (full, revalidated) = (Arc::clone(&full), Arc::clone(&revalidated));
+                async move {
+                    if headers
+                        .get(header::IF_NONE_MATCH)
+                        .is_some_and(|v| v == "\"v1\"")
+                    {
+                        revalidated.fetch_add(1, Ordering::SeqCst);
+                        return (
+                            axum::http::StatusCode::NOT_MODIFIED,
+                            [
+                                (header::ETAG, "\"v1\""),
+                                (header::CACHE_CONTROL, "max-age=120"),
+                            ],
+                            String::new(),
+                        );
+                    }
+                    full.fetch_add(1, Ordering::SeqCst);
+                    (
+                        axum::http::StatusCode::OK,
+                        [
+                            (header::ETAG, "\"v1\""),
+                            (header::CACHE_CONTROL, "max-age=600"),
+                        ],
+                        r#"{"keys": []}"#.to_string(),
+                    )
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let cache = JwksCache::new(format!("http://{}/jwks", addr));
+        assert!(cache.refresh_if_due().await.unwrap());
+        assert!(!cache.refresh_if_due().await.unwrap());
+        assert_eq!(cache.key_count().await.unwrap(), 0);
+        assert_eq!(full_fetches.load(Ordering::SeqCst), 1);
+
+        // Once due: revalidated, not downloaded again
+        if let Some(cached) = cache.keys.write().await.as_mut() {
+            assert_eq!(cached.ttl, Duration::from_secs(600));
+            cached.ttl = Duration::ZERO;
+        }
+        assert!(cache.refresh_if_due().await.unwrap());
+        assert_eq!(full_fetches.load(Ordering::SeqCst), 1);
+        assert_eq!(revalidations.load(Ordering::SeqCst), 1);
+
+        let cache = cache.keys.read().await;
+        let cached = cache.as_ref().unwrap();
+        assert_eq!(cached.ttl, Duration::from_secs(120));
+        assert!(cached.is_fresh() && !cached.refresh_due());
+    }
+}
@@ -0,0 +1,352 @@
+//! Self-hosted authentication (`AUTH_MODE=local`)
+//!
+//! For deployments that cannot use Clerk: accounts register and log in with
+//! an email and password (Argon2id hashes in `local_credentials`), and the
+//! server signs its own access tokens (RS256 or EdDSA) with the key pair of
+//! `AUTH_SIGNING_KEY_FILE` / `AUTH_PUBLIC_KEY_FILE`. The `local` issuer is
+//! trusted like any other (see [`super::JwtIssuers`]), so the extractors work
+//! unchanged; local accounts are identified as `local:<uuid>`.
+
+use std::fmt;
+use std::time::Duration;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use chrono::Utc;
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use uuid::Uuid;
+
+use super::jwks::{JwtIssuerConfig, LOCAL_ISSUER_NAME};
+use crate::error::ApiError;
+
+/// Default lifetime of issued access tokens (12 hours)
+pub const DEFAULT_TOKEN_TTL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Default `iss` and `aud` of issued access tokens
+pub const DEFAULT_TOKEN_ISSUER: &str = "veritas-q";
+
+/// Shortest accepted password, in characters
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Longest accepted password, in characters (bounds hashing cost)
+pub const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// Where users authenticate
+#[derive(Debug, Clone, Default)]
+pub enum AuthMode {
+    /// Clerk sessions (default)
+    #[default]
+    Clerk,
+    /// Accounts managed by this server
+    Local(LocalAuthConfig),
+}
+
+impl AuthMode {
+    /// Read `AUTH_MODE` ("clerk" or "local"); local mode also reads the
+    /// `AUTH_*` settings of [`LocalAuthConfig::from_env`].
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("AUTH_MODE") {
+            Err(_) => Ok(Self::Clerk),
+            Ok(mode) if mode.is_empty() || mode.eq_ignore_ascii_case("clerk") => Ok(Self::Clerk),
+            Ok(mode) if mode.eq_ignore_ascii_case("local") => {
+                LocalAuthConfig::from_env().map(Self::Local)
+            }
+            Ok(mode) => Err(format!(
+                "unknown AUTH_MODE '{}' (expected clerk or local)",
+                mode
+            )),
+        }
+    }
+}
+
+/// Settings of self-hosted authentication
+#[derive(Clone)]
+pub struct LocalAuthConfig {
+    /// Token signature algorithm: RS256 (default) or EdDSA (Ed25519)
+    pub algorithm: Algorithm,
+    /// PEM private key signing access tokens
+    pub signing_key_pem: Vec<u8>,
+    /// PEM public key of the signing key
+    pub public_key_pem: Vec<u8>,
+    /// `iss` and `aud` of access tokens (default: veritas-q)
+    pub issuer: String,
+    /// Lifetime of access tokens (default: 12 hours)
+    pub token_ttl: Duration,
+}
+
+impl fmt::Debug for LocalAuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalAuthConfig")
+            .field("algorithm", &self.algorithm)
+            .field("issuer", &self.issuer)
+            .field("token_ttl", &self.token_ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LocalAuthConfig {
+    /// Load the settings:
+    ///
+    /// - `AUTH_SIGNING_KEY_FILE`, `AUTH_PUBLIC_KEY_FILE`: PEM key pair (required)
+    /// - `AUTH_JWT_ALGORITHM`: "RS256" or "EdDSA"
+    /// - `AUTH_TOKEN_ISSUER`: `iss`/`aud` of the tokens
+    /// - `AUTH_TOKEN_TTL_SECS`: token lifetime
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let read_key = |name: &str| {
+            let path =
+                var(name).ok_or_else(|| format!("{} is required with AUTH_MODE=local", name))?;
+            std::fs::read(&path).map_err(|e| format!("failed to read {}: {}", path, e))
+        };
+
+        let algorithm = match var("AUTH_JWT_ALGORITHM").as_deref() {
+            None | Some("RS256") => Algorithm::RS256,
+            Some("EdDSA") => Algorithm::EdDSA,
+            Some(other) => {
+                return Err(format!(
+                    "unsupported AUTH_JWT_ALGORITHM '{}' (expected RS256 or EdDSA)",
+                    other
+                ))
+            }
+        };
+        let token_ttl = match var("AUTH_TOKEN_TTL_SECS") {
+            Some(secs) => secs
+                .parse()
+                .ok()
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs)
+                .ok_or_else(|| format!("invalid AUTH_TOKEN_TTL_SECS '{}'", secs))?,
+            None => DEFAULT_TOKEN_TTL,
+        };
+
+        Ok(Self {
+            algorithm,
+            signing_key_pem: read_key("AUTH_SIGNING_KEY_FILE")?,
+            public_key_pem: read_key("AUTH_PUBLIC_KEY_FILE")?,
+            issuer: var("AUTH_TOKEN_ISSUER").unwrap_or_else(|| DEFAULT_TOKEN_ISSUER.to_string()),
+            token_ttl,
+        })
+    }
+}
+
+/// Claims of the access tokens we issue
+#[derive(Serialize, Deserialize)]
+struct LocalClaims {
+    sub: String,
+    iss: String,
+    aud: String,
+    iat: i64,
+    exp: i64,
+}
+
+/// An access token and its lifetime in seconds
+pub struct IssuedToken {
+    pub token: String,
+    pub expires_in: u64,
+}
+
+/// Signs access tokens and hashes passwords of self-hosted accounts.
+pub struct LocalAuth {
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    kid: String,
+    issuer: String,
+    token_ttl: Duration,
+    /// Hash checked when the email is unknown, so that login takes as long
+    /// whether or not the account exists
+    dummy_hash: String,
+}
+
+impl LocalAuth {
+    /// Load the key pair and check that it signs verifiable tokens.
+    pub fn new(config: &LocalAuthConfig) -> Result<Self, String> {
+        let keys = match config.algorithm {
+            Algorithm::EdDSA => EncodingKey::from_ed_pem(&config.signing_key_pem)
+                .and_then(|enc| Ok((enc, DecodingKey::from_ed_pem(&config.public_key_pem)?))),
+            _ => EncodingKey::from_rsa_pem(&config.signing_key_pem)
+                .and_then(|enc| Ok((enc, DecodingKey::from_rsa_pem(&config.public_key_pem)?))),
+        };
+        let (encoding_key, decoding_key) =
+            keys.map_err(|e| format!("invalid {:?} key pair: {}", config.algorithm, e))?;
+
+        // Key ID: start of the public key's SHA3-256
+        let digest = Sha3_256::digest(&config.public_key_pem);
+        let auth = Self {
+            algorithm: config.algorithm,
+            encoding_key,
+            decoding_key,
+            kid: hex::encode(&digest[..8]),
+            issuer: config.issuer.clone(),
+            token_ttl: config.token_ttl,
+            dummy_hash: hash(b"")?,
+        };
+
+        let probe = auth.issue_token("probe").map_err(|e| e.to_string())?;
+        let mut validation = Validation::new(auth.algorithm);
+        validation.set_audience(&[&auth.issuer]);
+        decode::<LocalClaims>(&probe.token, &auth.decoding_key, &validation)
+            .map_err(|e| format!("signing and public keys do not match: {}", e))?;
+        Ok(auth)
+    }
+
+    /// The `local` issuer, to trust alongside the others
+    pub fn issuer_config(&self) -> JwtIssuerConfig {
+        JwtIssuerConfig {
+            name: LOCAL_ISSUER_NAME.to_string(),
+            // Keys are not fetched: see `key()`
+            jwks_url: String::new(),
+            issuer: Some(self.issuer.clone()),
+            audiences: vec![self.issuer.clone()],
+        }
+    }
+
+    /// Key ID, verification key and algorithm of our tokens
+    pub(super) fn key(&self) -> (&str, &DecodingKey, Algorithm) {
+        (&self.kid, &self.decoding_key, self.algorithm)
+    }
+
+    /// Subject of a new account, and its `users.clerk_user_id`
+    pub fn new_subject(&self) -> (String, String) {
+        let subject = Uuid::new_v4().to_string();
+        let user_id = self.issuer_config().user_id(&subject);
+        (subject, user_id)
+    }
+
+    /// Token subject of a self-hosted account, from its `users.clerk_user_id`
+    pub fn subject_of<'a>(&self, user_id: &'a str) -> Option<&'a str> {
+        user_id
+            .strip_prefix(LOCAL_ISSUER_NAME)
+            .and_then(|rest| rest.strip_prefix(':'))
+    }
+
+    /// Sign an access token for `subject` (`users.clerk_user_id` without
+    /// the `local:` prefix)
+    pub fn issue_token(&self, subject: &str) -> Result<IssuedToken, ApiError> {
+        let now = Utc::now().timestamp();
+        let claims = LocalClaims {
+            sub: subject.to_string(),
+            iss: self.issuer.clone(),
+            aud: self.issuer.clone(),
+            iat: now,
+            exp: now + self.token_ttl.as_secs() as i64,
+        };
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.kid.clone());
+
+        let token = encode(&header, &claims, &self.encoding_key).map_err(|e| {
+            tracing::error!(error = %e, "Failed to sign access token");
+            ApiError::internal("Failed to issue access token")
+        })?;
+        Ok(IssuedToken {
+            token,
+            expires_in: self.token_ttl.as_secs(),
+        })
+    }
+
+    /// Argon2id hash (PHC string) of a password, computed off the async runtime.
+    pub async fn hash_password(&self, password: String) -> Result<String, ApiError> {
+        tokio::task::spawn_blocking(move || hash(password.as_bytes()))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()))
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to hash password");
+                ApiError::internal("Failed to hash password")
+            })
+    }
+
+    /// Check a password against a stored hash, or against a dummy hash when
+    /// the account does not exist (`None`); false in that case.
+    pub async fn verify_password(&self, hash: Option<String>, password: String) -> bool {
+        let exists = hash.is_some();
+        let hash = hash.unwrap_or_else(|| self.dummy_hash.clone());
+        let matches = tokio::task::spawn_blocking(move || {
+            PasswordHash::new(&hash).is_ok_and(|parsed| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &parsed)
+                    .is_ok()
+            })
+        })
+        .await
+        .unwrap_or(false);
+        exists && matches
+    }
+}
+
+/// Argon2id (default parameters) with a 16-byte salt from the OS RNG
+fn hash(password: &[u8]) -> Result<String, String> {
+    let mut salt = [0u8; 16];
+    getrandom::fill(&mut salt).map_err(|e| e.to_string())?;
+    let salt = SaltString::encode_b64(&salt).map_err(|e| e.to_string())?;
+    Argon2::default()
+        .hash_password(password, &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Check the length of a new password.
+pub fn check_password(password: &str) -> Result<(), ApiError> {
+    let length = password.chars().count();
+    if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&length) {
+        return Err(ApiError::bad_request(format!(
+            "Password must be between {} and {} characters",
+            MIN_PASSWORD_LENGTH, MAX_PASSWORD_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> LocalAuthConfig {
+        LocalAuthConfig {
+            algorithm: Algorithm::RS256,
+            signing_key_pem: include_bytes!("../../tests/fixtures/test_rsa_private.pem").to_vec(),
+            public_key_pem: include_bytes!("../../tests/fixtures/test_rsa_public.pem").to_vec(),
+            issuer: DEFAULT_TOKEN_ISSUER.to_string(),
+            token_ttl: DEFAULT_TOKEN_TTL,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_password_hashing() {
+        let auth = LocalAuth::new(&test_config()).unwrap();
+        let hash = auth
+            .hash_password("correct horse".to_string())
+            .await
+            .unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+
+        assert!(
+            auth.verify_password(Some(hash.clone()), "correct horse".to_string())
+                .await
+        );
+        assert!(!auth.verify_password(Some(hash), "wrong".to_string()).await);
+        assert!(!auth.verify_password(None, String::new()).await);
+        assert!(
+            !auth
+                .verify_password(Some("not a hash".to_string()), String::new())
+                .await
+        );
+    }
+
+    #[test]
+    fn test_invalid_key_pair_is_rejected() {
+        let config = LocalAuthConfig {
+            public_key_pem: b"-----BEGIN PUBLIC KEY-----\n-----END PUBLIC KEY-----\n".to_vec(),
+            ..test_config()
+        };
+        assert!(LocalAuth::new(&config).is_err());
+    }
+
+    #[test]
+    fn test_check_password() {
+        assert!(check_password("short").is_err());
+        assert!(check_password("long enough").is_ok());
+        assert!(check_password(&"x".repeat(MAX_PASSWORD_LENGTH + 1)).is_err());
+    }
+}
@@ -1,136 +1,79 @@
 //! JWT authentication module
 //!
-//! Provides `AuthenticatedUser` and `JwtClaims` extractors for Axum handlers.
-//! JWT tokens are validated against Clerk's JWKS endpoint with a 1-hour cache TTL.
+//! Provides `AuthenticatedUser`, `AdminUser` and `JwtClaims` extractors for Axum handlers.
+//! JWT tokens are validated against the JWKS of a trusted issuer (Clerk, plus any
+//! issuer from `JWT_ISSUERS_FILE`), see [`jwks`], or against the server's own
+//! key when it issues tokens itself (`AUTH_MODE=local`), see [`local`].
+//! Opaque tokens are checked by OAuth2 introspection when configured, see
+//! [`introspection`]. Client-credentials tokens of the services listed in
+//! `SERVICE_CLIENTS` authenticate a service (`Caller::Service`), never a user.
+//! Machine clients authenticate with an API key instead (`ApiKeyAuth`).
 
-use std::time::{Duration, Instant};
+mod introspection;
+mod jwks;
+mod local;
+
+pub use introspection::{IntrospectionConfig, TokenIntrospector, INTROSPECTION_SWEEP_INTERVAL};
+
+pub use jwks::{JwksCache, JwtIssuerConfig, JwtIssuers, JWKS_REFRESH_INTERVAL};
+pub use local::{check_password, AuthMode, IssuedToken, LocalAuth, LocalAuthConfig};
 
 use axum::extract::FromRequestParts;
 use axum::http::request::Parts;
-use jsonwebtoken::{decode, decode_header, jwk, Algorithm, DecodingKey, Validation};
+use axum::http::HeaderName;
+use jsonwebtoken::{decode, decode_header, Validation};
 use serde::Deserialize;
-use tokio::sync::RwLock;
 
-use crate::db::User;
+use crate::db::{ApiKey, ApiKeyScope, Tenant, User};
 use crate::error::ApiError;
 use crate::handlers::AppState;
+use crate::logging;
 
-/// JWKS cache TTL (1 hour)
-const JWKS_CACHE_TTL: Duration = Duration::from_secs(3600);
+/// Header carrying an API key
+pub const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
 
 /// JWT claims from Clerk tokens
 #[derive(Debug, Deserialize)]
 struct ClerkClaims {
-    /// Subject (Clerk user ID)
+    /// Subject (Clerk user ID, or the client of a client-credentials token)
     sub: String,
     /// Expiration time (validated by jsonwebtoken)
     #[allow(dead_code)]
     exp: u64,
+    /// Client the token was issued to (RFC 9068)
+    #[serde(default)]
+    client_id: Option<String>,
+    /// Authorized party, the client for issuers without `client_id`
+    #[serde(default)]
+    azp: Option<String>,
 }
 
-/// Cached JWKS keys with timestamp
-struct CachedJwks {
-    keys: Vec<jwk::Jwk>,
-    fetched_at: Instant,
-}
-
-/// JWKS cache that fetches and caches Clerk's JSON Web Key Set
-pub struct JwksCache {
-    keys: RwLock<Option<CachedJwks>>,
-    jwks_url: String,
-    http_client: reqwest::Client,
-}
-
-/// JWKS response structure from Clerk
-#[derive(Deserialize)]
-struct JwksResponse {
-    keys: Vec<jwk::Jwk>,
+/// Subject of a verified token
+#[derive(Debug, Clone)]
+struct VerifiedToken {
+    /// User ID (Clerk user ID, `<issuer name>:<sub>` for other issuers)
+    sub: String,
+    /// Issued to a client for itself (client-credentials grant): the
+    /// subject is the token's client, not a person
+    client_credentials: bool,
 }
 
-impl JwksCache {
-    /// Create a new JWKS cache for the given Clerk JWKS URL
-    pub fn new(jwks_url: String) -> Self {
-        Self {
-            keys: RwLock::new(None),
-            jwks_url,
-            http_client: reqwest::Client::new(),
-        }
-    }
-
-    /// Get cached JWKS keys, fetching from Clerk if expired or not yet cached
-    async fn get_keys(&self) -> Result<Vec<jwk::Jwk>, ApiError> {
-        // Try read lock first (fast path)
-        {
-            let cache = self.keys.read().await;
-            if let Some(ref cached) = *cache {
-                if cached.fetched_at.elapsed() < JWKS_CACHE_TTL {
-                    return Ok(cached.keys.clone());
-                }
-            }
-        }
-
-        // Cache miss or expired — acquire write lock and fetch
-        let mut cache = self.keys.write().await;
-
-        // Double-check after acquiring write lock (another task may have refreshed)
-        if let Some(ref cached) = *cache {
-            if cached.fetched_at.elapsed() < JWKS_CACHE_TTL {
-                return Ok(cached.keys.clone());
+impl VerifiedToken {
+    /// The service authenticated by a client-credentials token of one of
+    /// the `SERVICE_CLIENTS`
+    fn service(&self, state: &AppState) -> Option<ServiceAuth> {
+        (self.client_credentials && state.service_clients.contains(&self.sub)).then(|| {
+            ServiceAuth {
+                service_id: self.sub.clone(),
             }
-        }
-
-        let response = self
-            .http_client
-            .get(&self.jwks_url)
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!(error = %e, "Failed to fetch JWKS from Clerk");
-                ApiError::internal("Authentication service temporarily unavailable")
-            })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            tracing::error!(status = %status, "Clerk JWKS endpoint returned error");
-            return Err(ApiError::internal(
-                "Authentication service temporarily unavailable",
-            ));
-        }
-
-        let jwks: JwksResponse = response.json().await.map_err(|e| {
-            tracing::error!(error = %e, "Failed to parse JWKS response");
-            ApiError::internal("Authentication service temporarily unavailable")
-        })?;
-
-        let keys = jwks.keys;
-        tracing::info!(key_count = keys.len(), "Refreshed JWKS cache from Clerk");
-
-        *cache = Some(CachedJwks {
-            keys: keys.clone(),
-            fetched_at: Instant::now(),
-        });
-
-        Ok(keys)
-    }
-
-    /// Find a JWK by key ID
-    async fn find_key(&self, kid: &str) -> Result<jwk::Jwk, ApiError> {
-        let keys = self.get_keys().await?;
-        keys.into_iter()
-            .find(|k| k.common.key_id.as_deref() == Some(kid))
-            .ok_or_else(|| {
-                ApiError::auth_error(
-                    "AUTH_UNKNOWN_KEY",
-                    format!("No matching key found for kid '{}'", kid),
-                )
-            })
+        })
     }
 }
 
-/// Validate a JWT token and extract Clerk claims.
+/// Validate a JWT token against its issuer and extract its claims.
 ///
 /// This is the core validation logic, separated for testability.
-async fn validate_jwt(token: &str, jwks_cache: &JwksCache) -> Result<ClerkClaims, ApiError> {
+async fn validate_jwt(token: &str, issuers: &JwtIssuers) -> Result<VerifiedToken, ApiError> {
     // Decode header to get kid
     let header = decode_header(token).map_err(|e| {
         ApiError::auth_error("AUTH_INVALID_TOKEN", format!("Invalid JWT header: {}", e))
@@ -140,20 +83,22 @@ async fn validate_jwt(token: &str, jwks_cache: &JwksCache) -> Result<ClerkClaims
         ApiError::auth_error("AUTH_INVALID_TOKEN", "JWT header missing 'kid' field")
     })?;
 
-    // Find matching JWK
-    let jwk = jwks_cache.find_key(&kid).await?;
-
-    // Convert JWK to DecodingKey
-    let decoding_key = DecodingKey::from_jwk(&jwk).map_err(|e| {
-        tracing::error!(error = %e, kid = %kid, "Failed to convert JWK to decoding key");
-        ApiError::auth_error("AUTH_INVALID_TOKEN", "Failed to process signing key")
-    })?;
+    // Find the issuer and its matching key
+    let issuer = issuers.for_token(token)?;
+    let (decoding_key, algorithm) = issuer.keys.find_key(&kid).await?;
 
-    // Validate JWT with RS256
-    let mut validation = Validation::new(Algorithm::RS256);
+    // Validate JWT with the issuer's algorithm (RS256 for JWKS issuers)
+    let mut validation = Validation::new(algorithm);
     validation.validate_exp = true;
-    // Clerk tokens don't always have aud, so disable audience validation
-    validation.validate_aud = false;
+    if let Some(iss) = &issuer.config.issuer {
+        validation.set_issuer(&[iss]);
+    }
+    if issuer.config.audiences.is_empty() {
+        // Clerk tokens don't always have aud, so audience validation is opt-in
+        validation.validate_aud = false;
+    } else {
+        validation.set_audience(issuer.config.audiences.as_slice());
+    }
 
     let token_data =
         decode::<ClerkClaims>(token, &decoding_key, &validation).map_err(|e| match e.kind() {
@@ -163,13 +108,48 @@ async fn validate_jwt(token: &str, jwks_cache: &JwksCache) -> Result<ClerkClaims
             jsonwebtoken::errors::ErrorKind::InvalidSignature => {
                 ApiError::auth_error("AUTH_INVALID_TOKEN", "Invalid JWT signature")
             }
+            jsonwebtoken::errors::ErrorKind::InvalidIssuer => {
+                ApiError::auth_error("AUTH_INVALID_TOKEN", "Invalid JWT issuer")
+            }
+            jsonwebtoken::errors::ErrorKind::InvalidAudience => {
+                ApiError::auth_error("AUTH_INVALID_TOKEN", "Invalid JWT audience")
+            }
             _ => ApiError::auth_error(
                 "AUTH_INVALID_TOKEN",
                 format!("JWT validation failed: {}", e),
             ),
         })?;
 
-    Ok(token_data.claims)
+    let claims = token_data.claims;
+    let client_credentials = [&claims.client_id, &claims.azp]
+        .into_iter()
+        .any(|client| client.as_deref() == Some(claims.sub.as_str()));
+    Ok(VerifiedToken {
+        sub: issuer.config.user_id(&claims.sub),
+        client_credentials,
+    })
+}
+
+/// Verify a bearer token: JWTs against their issuer, other tokens by
+/// introspection when it is configured.
+async fn verify_token(token: &str, state: &AppState) -> Result<VerifiedToken, ApiError> {
+    let is_jwt = decode_header(token).is_ok();
+    match (&state.jwt_issuers, &state.introspector) {
+        (Some(issuers), _) if is_jwt => validate_jwt(token, issuers).await,
+        (_, Some(introspector)) => introspector.introspect(token).await,
+        (Some(issuers), None) => validate_jwt(token, issuers).await,
+        (None, None) => Err(ApiError::internal(
+            "JWT authentication not configured (missing CLERK_JWKS_URL)",
+        )),
+    }
+}
+
+/// Service tokens authenticate machines, which cannot act as a user
+fn reject_service(token: &VerifiedToken, state: &AppState) -> Result<(), ApiError> {
+    if token.service(state).is_some() {
+        return Err(ApiError::forbidden("Service tokens cannot act as a user"));
+    }
+    Ok(())
 }
 
 /// Extract the Bearer token from the Authorization header
@@ -201,15 +181,25 @@ fn extract_bearer_token(parts: &Parts) -> Result<&str, ApiError> {
 /// Use this for handlers that require a known, database-backed user.
 /// The extractor:
 /// 1. Reads `Authorization: Bearer <token>` header
-/// 2. Validates the JWT against Clerk's JWKS
+/// 2. Validates the JWT against its issuer's JWKS (Clerk or an issuer from `JWT_ISSUERS_FILE`),
+///    or introspects an opaque token
 /// 3. Looks up the user in the database by `clerk_user_id` (JWT `sub` claim)
+/// 4. Records the user's [`Tenant`] in the request extensions and log span
 ///
-/// Returns 401 with structured error codes on any failure.
+/// Returns 401 with structured error codes on any failure, and 403 for
+/// suspended accounts and service tokens.
 pub struct AuthenticatedUser {
     pub user: User,
     pub clerk_user_id: String,
 }
 
+impl AuthenticatedUser {
+    /// Tenant the user acts for, which scopes every query on their data
+    pub fn tenant(&self) -> Tenant {
+        Tenant::of(&self.user)
+    }
+}
+
 impl FromRequestParts<AppState> for AuthenticatedUser {
     type Rejection = ApiError;
 
@@ -218,12 +208,8 @@ impl FromRequestParts<AppState> for AuthenticatedUser {
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
         let token = extract_bearer_token(parts)?;
-
-        let jwks_cache = state.jwks_cache.as_ref().ok_or_else(|| {
-            ApiError::internal("JWT authentication not configured (missing CLERK_JWKS_URL)")
-        })?;
-
-        let claims = validate_jwt(token, jwks_cache).await?;
+        let claims = verify_token(token, state).await?;
+        reject_service(&claims, state)?;
 
         // Look up user in database
         let user_repo = state
@@ -244,6 +230,8 @@ impl FromRequestParts<AppState> for AuthenticatedUser {
                     "Valid token but user not found in database",
                 )
             })?;
+        reject_suspended(&user)?;
+        record_tenant(parts, &user);
 
         Ok(AuthenticatedUser {
             clerk_user_id: claims.sub,
@@ -252,16 +240,26 @@ impl FromRequestParts<AppState> for AuthenticatedUser {
     }
 }
 
-/// Optional authentication extractor.
-///
-/// Returns `Some(AuthenticatedUser)` if a valid Bearer token is present and the user
-/// exists in the database; returns `None` if no Authorization header is provided.
-/// Returns an error only for malformed/expired tokens (not for missing tokens).
+/// Service calling with a client-credentials token
+pub struct ServiceAuth {
+    /// Service client, as listed in `SERVICE_CLIENTS` (`<issuer name>:<client>`)
+    pub service_id: String,
+}
+
+/// Caller of an endpoint open to anonymous requests.
 ///
-/// Use this for endpoints where authentication is optional (e.g., anonymous seal creation).
-pub struct OptionalAuth(pub Option<AuthenticatedUser>);
+/// Like [`OptionalAuth`], but tells services (client-credentials tokens of
+/// `SERVICE_CLIENTS`) apart from anonymous requests.
+pub enum Caller {
+    /// No token, or a token of a user unknown to the database
+    Anonymous,
+    /// A known user
+    User(AuthenticatedUser),
+    /// A backend service acting for itself
+    Service(ServiceAuth),
+}
 
-impl FromRequestParts<AppState> for OptionalAuth {
+impl FromRequestParts<AppState> for Caller {
     type Rejection = ApiError;
 
     async fn from_request_parts(
@@ -279,22 +277,24 @@ impl FromRequestParts<AppState> for OptionalAuth {
                 })?;
                 match value.strip_prefix("Bearer ") {
                     Some(t) => t,
-                    None => return Ok(OptionalAuth(None)),
+                    None => return Ok(Caller::Anonymous),
                 }
             }
-            None => return Ok(OptionalAuth(None)),
+            None => return Ok(Caller::Anonymous),
         };
 
-        let jwks_cache = match state.jwks_cache.as_ref() {
-            Some(cache) => cache,
-            None => return Ok(OptionalAuth(None)),
-        };
+        if state.jwt_issuers.is_none() && state.introspector.is_none() {
+            return Ok(Caller::Anonymous);
+        }
 
-        let claims = validate_jwt(token, jwks_cache).await?;
+        let claims = verify_token(token, state).await?;
+        if let Some(service) = claims.service(state) {
+            return Ok(Caller::Service(service));
+        }
 
         let user_repo = match state.user_repo.as_ref() {
             Some(repo) => repo,
-            None => return Ok(OptionalAuth(None)),
+            None => return Ok(Caller::Anonymous),
         };
 
         let user = user_repo.find_by_clerk_id(&claims.sub).await.map_err(|e| {
@@ -303,15 +303,139 @@ impl FromRequestParts<AppState> for OptionalAuth {
         })?;
 
         match user {
-            Some(user) => Ok(OptionalAuth(Some(AuthenticatedUser {
-                clerk_user_id: claims.sub,
-                user,
-            }))),
-            None => Ok(OptionalAuth(None)),
+            Some(user) => {
+                reject_suspended(&user)?;
+                record_tenant(parts, &user);
+                Ok(Caller::User(AuthenticatedUser {
+                    clerk_user_id: claims.sub,
+                    user,
+                }))
+            }
+            None => Ok(Caller::Anonymous),
+        }
+    }
+}
+
+/// Optional authentication extractor.
+///
+/// Returns `Some(AuthenticatedUser)` if a valid Bearer token is present and the user
+/// exists in the database; returns `None` if no Authorization header is provided,
+/// and for service tokens.
+/// Returns an error only for malformed/expired tokens (not for missing tokens).
+///
+/// Use this for endpoints where authentication is optional (e.g., anonymous seal creation).
+pub struct OptionalAuth(pub Option<AuthenticatedUser>);
+
+impl FromRequestParts<AppState> for OptionalAuth {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        match Caller::from_request_parts(parts, state).await? {
+            Caller::User(user) => Ok(OptionalAuth(Some(user))),
+            Caller::Anonymous | Caller::Service(_) => Ok(OptionalAuth(None)),
+        }
+    }
+}
+
+/// Administrator extractor: an [`AuthenticatedUser`] with the admin role.
+///
+/// Returns 403 for authenticated users without the role.
+pub struct AdminUser(pub AuthenticatedUser);
+
+impl FromRequestParts<AppState> for AdminUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let auth = AuthenticatedUser::from_request_parts(parts, state).await?;
+        if !auth.user.is_admin() {
+            return Err(ApiError::forbidden("Administrator role required"));
+        }
+        Ok(AdminUser(auth))
+    }
+}
+
+/// API key extractor for machine clients.
+///
+/// Reads the `X-API-Key` header and looks up the unrevoked key by hash; keys of
+/// suspended accounts are rejected. Returns 401 on any failure. Handlers check
+/// the scope they need with [`ApiKeyAuth::require_scope`].
+pub struct ApiKeyAuth {
+    pub key: ApiKey,
+}
+
+impl ApiKeyAuth {
+    /// Fail with 403 unless the key was granted `scope`
+    pub fn require_scope(&self, scope: ApiKeyScope) -> Result<(), ApiError> {
+        if !self.key.has_scope(scope) {
+            return Err(ApiError::forbidden(format!(
+                "API key lacks the '{}' scope",
+                scope.as_str()
+            )));
         }
+        Ok(())
     }
 }
 
+impl FromRequestParts<AppState> for ApiKeyAuth {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let secret = parts
+            .headers
+            .get(&API_KEY_HEADER)
+            .ok_or_else(|| {
+                ApiError::auth_error("AUTH_MISSING_API_KEY", "Missing X-API-Key header")
+            })?
+            .to_str()
+            .map_err(|_| {
+                ApiError::auth_error("AUTH_INVALID_API_KEY", "Invalid X-API-Key header encoding")
+            })?;
+
+        let api_key_repo = state
+            .api_key_repo
+            .as_ref()
+            .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+
+        let key = api_key_repo
+            .authenticate(&ApiKey::hash_secret(secret))
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to look up API key");
+                ApiError::internal("A database error occurred")
+            })?
+            .ok_or_else(|| {
+                ApiError::auth_error("AUTH_INVALID_API_KEY", "Unknown or revoked API key")
+            })?;
+        logging::record_user(key.user_id);
+
+        Ok(ApiKeyAuth { key })
+    }
+}
+
+/// Add the tenant of an authenticated user to the request context
+fn record_tenant(parts: &mut Parts, user: &User) {
+    let tenant = Tenant::of(user);
+    logging::record_tenant(&tenant);
+    parts.extensions.insert(tenant);
+}
+
+/// Suspended accounts keep their data but cannot act on it.
+fn reject_suspended(user: &User) -> Result<(), ApiError> {
+    if user.is_suspended() {
+        return Err(ApiError::forbidden("Account suspended"));
+    }
+    Ok(())
+}
+
 /// JWT claims extractor that validates the token without database lookup.
 ///
 /// Use this for handlers where the user may not yet exist in the database
@@ -328,12 +452,8 @@ impl FromRequestParts<AppState> for JwtClaims {
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
         let token = extract_bearer_token(parts)?;
-
-        let jwks_cache = state.jwks_cache.as_ref().ok_or_else(|| {
-            ApiError::internal("JWT authentication not configured (missing CLERK_JWKS_URL)")
-        })?;
-
-        let claims = validate_jwt(token, jwks_cache).await?;
+        let claims = verify_token(token, state).await?;
+        reject_service(&claims, state)?;
 
         Ok(JwtClaims {
             clerk_user_id: claims.sub,
@@ -346,7 +466,7 @@ mod tests {
     use super::*;
     use base64::engine::general_purpose::{STANDARD as BASE64_STD, URL_SAFE_NO_PAD};
     use base64::Engine;
-    use jsonwebtoken::{encode, EncodingKey, Header};
+    use jsonwebtoken::{encode, jwk, Algorithm, EncodingKey, Header};
     use serde::Serialize;
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -385,6 +505,18 @@ mod tests {
         encode(&header, &claims, &encoding_key).unwrap()
     }
 
+    fn create_token_with_claims(
+        claims: &serde_json::Value,
+        kid: &str,
+        private_key_pem: &[u8],
+    ) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid.to_string());
+
+        let encoding_key = EncodingKey::from_rsa_pem(private_key_pem).unwrap();
+        encode(&header, claims, &encoding_key).unwrap()
+    }
+
     /// Parse an ASN.1 length field, returning (length, new_offset)
     fn parse_asn1_length(data: &[u8], offset: usize) -> (usize, usize) {
         let b = data[offset];
@@ -472,14 +604,15 @@ mod tests {
 
         let jwk_value: jwk::Jwk = serde_json::from_value(jwk_json).unwrap();
 
-        JwksCache {
-            keys: RwLock::new(Some(CachedJwks {
-                keys: vec![jwk_value],
-                fetched_at: Instant::now(),
-            })),
-            jwks_url: "http://test.invalid/.well-known/jwks.json".to_string(),
-            http_client: reqwest::Client::new(),
-        }
+        JwksCache::with_keys("http://test.invalid/.well-known/jwks.json", vec![jwk_value])
+    }
+
+    /// Trust Clerk (any `iss`, no `aud`) with a mock key set
+    fn create_mock_issuers(public_key_pem: &[u8], kid: &str) -> JwtIssuers {
+        JwtIssuers::with_caches(vec![(
+            JwtIssuerConfig::clerk(String::new(), None, Vec::new()),
+            create_mock_jwks_cache(public_key_pem, kid),
+        )])
     }
 
     #[tokio::test]
@@ -489,9 +622,9 @@ mod tests {
         let exp = now_epoch() + 3600;
 
         let token = create_test_token("user_clerk123", exp, kid, &private_key);
-        let cache = create_mock_jwks_cache(&public_key, kid);
+        let issuers = create_mock_issuers(&public_key, kid);
 
-        let claims = validate_jwt(&token, &cache).await.unwrap();
+        let claims = validate_jwt(&token, &issuers).await.unwrap();
         assert_eq!(claims.sub, "user_clerk123");
     }
 
@@ -502,9 +635,9 @@ mod tests {
         let exp = now_epoch() - 3600;
 
         let token = create_test_token("user_clerk123", exp, kid, &private_key);
-        let cache = create_mock_jwks_cache(&public_key, kid);
+        let issuers = create_mock_issuers(&public_key, kid);
 
-        let err = validate_jwt(&token, &cache).await.unwrap_err();
+        let err = validate_jwt(&token, &issuers).await.unwrap_err();
         match err {
             ApiError::AuthError { code, .. } => assert_eq!(code, "AUTH_TOKEN_EXPIRED"),
             other => panic!(
@@ -520,9 +653,9 @@ mod tests {
         let exp = now_epoch() + 3600;
 
         let token = create_test_token("user_clerk123", exp, "unknown-key", &private_key);
-        let cache = create_mock_jwks_cache(&public_key, "test-key-1");
+        let issuers = create_mock_issuers(&public_key, "test-key-1");
 
-        let err = validate_jwt(&token, &cache).await.unwrap_err();
+        let err = validate_jwt(&token, &issuers).await.unwrap_err();
         match err {
             ApiError::AuthError { code, .. } => assert_eq!(code, "AUTH_UNKNOWN_KEY"),
             other => panic!("Expected AuthError with AUTH_UNKNOWN_KEY, got: {:?}", other),
@@ -532,9 +665,9 @@ mod tests {
     #[tokio::test]
     async fn test_invalid_token() {
         let (_, public_key) = load_test_keys();
-        let cache = create_mock_jwks_cache(&public_key, "test-key-1");
+        let issuers = create_mock_issuers(&public_key, "test-key-1");
 
-        let err = validate_jwt("not-a-valid-jwt", &cache).await.unwrap_err();
+        let err = validate_jwt("not-a-valid-jwt", &issuers).await.unwrap_err();
         match err {
             ApiError::AuthError { code, .. } => assert_eq!(code, "AUTH_INVALID_TOKEN"),
             other => panic!(
@@ -604,4 +737,178 @@ mod tests {
         assert_eq!(keys.len(), 1);
         assert_eq!(keys[0].common.key_id.as_deref(), Some("test-key-1"));
     }
+
+    #[tokio::test]
+    async fn test_multiple_issuers() {
+        let (private_key, public_key) = load_test_keys();
+        let kid = "test-key-1";
+        let exp = now_epoch() + 3600;
+        let corp = JwtIssuerConfig {
+            name: "corp".to_string(),
+            jwks_url: String::new(),
+            issuer: Some("https://idp.corp".to_string()),
+            audiences: vec!["veritas".to_string()],
+        };
+        let issuers = JwtIssuers::with_caches(vec![
+            (
+                JwtIssuerConfig::clerk(String::new(), None, Vec::new()),
+                create_mock_jwks_cache(&public_key, kid),
+            ),
+            (corp.clone(), create_mock_jwks_cache(&public_key, kid)),
+        ]);
+
+        // Routed by `iss`, subject namespaced with the issuer name
+        let token = create_token_with_claims(
+            &serde_json::json!({
+                "sub": "alice",
+                "exp": exp,
+                "iss": "https://idp.corp",
+                "aud": "veritas"
+            }),
+            kid,
+            &private_key,
+        );
+        let claims = validate_jwt(&token, &issuers).await.unwrap();
+        assert_eq!(claims.sub, "corp:alice");
+
+        // Audience checked per issuer
+        let token = create_token_with_claims(
+            &serde_json::json!({
+                "sub": "alice",
+                "exp": exp,
+                "iss": "https://idp.corp",
+                "aud": "other"
+            }),
+            kid,
+            &private_key,
+        );
+        let err = validate_jwt(&token, &issuers).await.unwrap_err();
+        match err {
+            ApiError::AuthError { code, message } => {
+                assert_eq!(code, "AUTH_INVALID_TOKEN");
+                assert_eq!(message, "Invalid JWT audience");
+            }
+            other => panic!("Expected AuthError, got: {:?}", other),
+        }
+
+        // Other tokens go to Clerk, which accepts any `iss` when CLERK_ISSUER is unset
+        let token = create_token_with_claims(
+            &serde_json::json!({
+                "sub": "user_clerk123",
+                "exp": exp,
+                "iss": "https://clerk.example"
+            }),
+            kid,
+            &private_key,
+        );
+        let claims = validate_jwt(&token, &issuers).await.unwrap();
+        assert_eq!(claims.sub, "user_clerk123");
+
+        // With CLERK_ISSUER set, tokens of unknown issuers are rejected
+        let issuers = JwtIssuers::with_caches(vec![
+            (
+                JwtIssuerConfig::clerk(
+                    String::new(),
+                    Some("https://clerk.veritas-q.com".to_string()),
+                    Vec::new(),
+                ),
+                create_mock_jwks_cache(&public_key, kid),
+            ),
+            (corp, create_mock_jwks_cache(&public_key, kid)),
+        ]);
+        let err = validate_jwt(&token, &issuers).await.unwrap_err();
+        match err {
+            ApiError::AuthError { code, .. } => assert_eq!(code, "AUTH_UNKNOWN_ISSUER"),
+            other => panic!(
+                "Expected AuthError with AUTH_UNKNOWN_ISSUER, got: {:?}",
+                other
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_tokens() {
+        let (private_key, public_key) = load_test_keys();
+        let local = LocalAuth::new(&LocalAuthConfig {
+            algorithm: Algorithm::RS256,
+            signing_key_pem: private_key.clone(),
+            public_key_pem: public_key.clone(),
+            issuer: "veritas-q".to_string(),
+            token_ttl: std::time::Duration::from_secs(600),
+        })
+        .unwrap();
+        let mut issuers = create_mock_issuers(&public_key, "test-key-1");
+        issuers.trust_local(&local);
+        assert_eq!(issuers.names(), ["clerk", "local"]);
+
+        // Tokens we issue are routed to the `local` issuer
+        let (subject, user_id) = local.new_subject();
+        assert_eq!(user_id, format!("local:{}", subject));
+        assert_eq!(local.subject_of(&user_id), Some(subject.as_str()));
+        assert_eq!(local.subject_of("user_clerk123"), None);
+        let issued = local.issue_token(&subject).unwrap();
+        assert_eq!(issued.expires_in, 600);
+        let claims = validate_jwt(&issued.token, &issuers).await.unwrap();
+        assert_eq!(claims.sub, user_id);
+
+        // Clerk tokens still validate
+        let token = create_test_token(
+            "user_clerk123",
+            now_epoch() + 3600,
+            "test-key-1",
+            &private_key,
+        );
+        let claims = validate_jwt(&token, &issuers).await.unwrap();
+        assert_eq!(claims.sub, "user_clerk123");
+
+        // Our `iss` with another key is rejected
+        let token = create_token_with_claims(
+            &serde_json::json!({
+                "sub": subject,
+                "exp": now_epoch() + 3600,
+                "iss": "veritas-q",
+                "aud": "veritas-q"
+            }),
+            "test-key-1",
+            &private_key,
+        );
+        let err = validate_jwt(&token, &issuers).await.unwrap_err();
+        match err {
+            ApiError::AuthError { code, .. } => assert_eq!(code, "AUTH_UNKNOWN_KEY"),
+            other => panic!("Expected AuthError with AUTH_UNKNOWN_KEY, got: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_tokens() {
+        let (private_key, public_key) = load_test_keys();
+        let kid = "test-key-1";
+        let exp = now_epoch() + 3600;
+        let issuers = create_mock_issuers(&public_key, kid);
+
+        // Subject is the token's own client: a client-credentials token
+        for client_claim in ["client_id", "azp"] {
+            let token = create_token_with_claims(
+                &serde_json::json!({ "sub": "cms", "exp": exp, client_claim: "cms" }),
+                kid,
+                &private_key,
+            );
+            let verified = validate_jwt(&token, &issuers).await.unwrap();
+            assert_eq!(verified.sub, "cms");
+            assert!(verified.client_credentials, "{}", client_claim);
+        }
+
+        // A user of a client
+        let token = create_token_with_claims(
+            &serde_json::json!({
+                "sub": "user_clerk123",
+                "exp": exp,
+                "azp": "https://veritas-q.com"
+            }),
+            kid,
+            &private_key,
+        );
+        let verified = validate_jwt(&token, &issuers).await.unwrap();
+        assert!(!verified.client_credentials);
+    }
 }
@@ -0,0 +1,75 @@
+//! Near-duplicate clustering
+//!
+//! A background job groups manifests whose perceptual hashes are within a
+//! Hamming distance threshold of each other (single linkage), so that
+//! re-uploads and edited variants of the same image end up in one cluster.
+//! Each pass only looks at manifests that are not clustered yet: a new
+//! manifest joins the cluster of its nearest neighbours, and clusters it
+//! bridges are merged.
+
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::manifest_store::{ManifestStoreError, PostgresManifestStore};
+
+/// Default maximum perceptual hash distance between two variants.
+pub const DEFAULT_CLUSTER_THRESHOLD: u32 = 8;
+
+/// Default time between two clustering passes.
+pub const DEFAULT_CLUSTER_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Manifests fetched per batch.
+const BATCH_SIZE: i64 = 500;
+
+/// Cluster every manifest that is not clustered yet.
+///
+/// Returns the number of manifests clustered.
+pub async fn cluster_pending(
+    store: &PostgresManifestStore,
+    threshold: u32,
+) -> Result<usize, ManifestStoreError> {
+    let mut clustered = 0;
+    loop {
+        let batch = store.unclustered(BATCH_SIZE).await?;
+        for (id, phash) in &batch {
+            let neighbors = store.neighbor_clusters(phash, threshold).await?;
+            let (cluster_id, merged) = plan_cluster(*id, neighbors);
+            store.assign_cluster(*id, cluster_id, &merged).await?;
+        }
+        clustered += batch.len();
+        if (batch.len() as i64) < BATCH_SIZE {
+            return Ok(clustered);
+        }
+    }
+}
+
+/// Cluster joined by a new manifest, and the clusters merged into it.
+///
+/// The manifest starts its own cluster when it has no neighbours; otherwise
+/// it joins the smallest neighbouring cluster ID, so that concurrent passes
+/// agree, and the other neighbouring clusters are merged into that one.
+fn plan_cluster(id: Uuid, mut neighbors: Vec<Uuid>) -> (Uuid, Vec<Uuid>) {
+    neighbors.sort();
+    neighbors.dedup();
+    if neighbors.is_empty() {
+        return (id, Vec::new());
+    }
+    let cluster_id = neighbors.remove(0);
+    (cluster_id, neighbors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_cluster() {
+        let id = Uuid::new_v4();
+        assert_eq!(plan_cluster(id, Vec::new()), (id, Vec::new()));
+
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        assert_eq!(plan_cluster(id, vec![b]), (b, Vec::new()));
+        assert_eq!(plan_cluster(id, vec![b, a, b]), (a, vec![b]));
+    }
+}
@@ -0,0 +1,114 @@
+//! Conditional GET on seal resources
+//!
+//! Dashboards poll seal details, exports, share links and batched anchors
+//! while waiting for an anchor to confirm. Those responses carry an `ETag`
+//! derived from the data they are built from; a client sending it back in
+//! `If-None-Match` gets `304 Not Modified` without a body until something
+//! changes.
+
+use axum::http::header::{ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use sha3::{Digest, Sha3_256};
+
+/// Entity tag of a representation: a quoted, truncated SHA3-256 of its JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag(String);
+
+impl ETag {
+    /// Tag of the data a response is built from
+    pub fn of<T: Serialize + ?Sized>(value: &T) -> Self {
+        // Serializing our own response types does not fail
+        let json = serde_json::to_vec(value).unwrap_or_default();
+        let digest = Sha3_256::digest(&json);
+        Self(format!("\"{}\"", hex::encode(&digest[..16])))
+    }
+
+    /// Tag as sent in the `ETag` header, quotes included
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether `If-None-Match` lists this tag (weak comparison) or is `*`
+    pub fn matches(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get_all(IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == self.0)
+    }
+}
+
+/// A response that is only sent if the client's copy is stale.
+pub enum Conditional<T> {
+    /// The client's copy is current: 304 without body
+    NotModified(ETag),
+    /// 200 with the body
+    Modified(ETag, T),
+}
+
+impl<T> Conditional<T> {
+    /// `body` tagged with `etag`, or 304 if the request's `If-None-Match`
+    /// already has that tag
+    pub fn new(headers: &HeaderMap, etag: ETag, body: T) -> Self {
+        if etag.matches(headers) {
+            Self::NotModified(etag)
+        } else {
+            Self::Modified(etag, body)
+        }
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for Conditional<T> {
+    fn into_response(self) -> Response {
+        let (etag, mut response) = match self {
+            Conditional::NotModified(etag) => (etag, StatusCode::NOT_MODIFIED.into_response()),
+            Conditional::Modified(etag, body) => (etag, body.into_response()),
+        };
+        if let Ok(value) = HeaderValue::from_str(etag.as_str()) {
+            response.headers_mut().insert(ETAG, value);
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn if_none_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_etag_matching() {
+        let etag = ETag::of(&serde_json::json!({ "status": "queued" }));
+        assert_eq!(etag, ETag::of(&serde_json::json!({ "status": "queued" })));
+        assert_ne!(etag, ETag::of(&serde_json::json!({ "status": "anchored" })));
+        assert_eq!(etag.as_str().len(), 34);
+
+        assert!(!etag.matches(&HeaderMap::new()));
+        assert!(etag.matches(&if_none_match(etag.as_str())));
+        assert!(etag.matches(&if_none_match(&format!("\"other\", W/{}", etag.as_str()))));
+        assert!(etag.matches(&if_none_match("*")));
+        assert!(!etag.matches(&if_none_match("\"other\"")));
+    }
+
+    #[test]
+    fn test_conditional_response() {
+        let etag = ETag::of("body");
+        let response = Conditional::new(&HeaderMap::new(), etag.clone(), "body").into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ETAG], etag.as_str());
+
+        let headers = if_none_match(etag.as_str());
+        let response = Conditional::new(&headers, etag.clone(), "body").into_response();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], etag.as_str());
+    }
+}
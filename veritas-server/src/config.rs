@@ -5,6 +5,60 @@
 use base64::engine::general_purpose::STANDARD_NO_PAD as BASE64;
 use base64::Engine;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPoolOptions;
+use veritas_core::{is_valid_did, MockQrngHandling};
+
+use crate::anchor_checks::{DEFAULT_ANCHOR_CHECK_TIMEOUT_MS, DEFAULT_ANCHOR_MIN_CONFIRMATIONS};
+use crate::auth::{AuthMode, IntrospectionConfig, JwtIssuerConfig};
+use crate::clustering::{DEFAULT_CLUSTER_INTERVAL, DEFAULT_CLUSTER_THRESHOLD};
+use crate::maintenance::DEFAULT_MAINTENANCE_RETRY_AFTER_SECS;
+use crate::notifications::NotificationConfig;
+use crate::qrng::QrngSelection;
+use crate::readiness::{DEFAULT_READY_CACHE_SECS, DEFAULT_READY_CHECK_TIMEOUT_MS};
+use crate::signing_keys::DEFAULT_SIGNING_KEY_OVERLAP_SECS;
+use crate::trust::TrustPolicy;
+use crate::validation::UploadPolicy;
+use crate::verification_cache::{
+    DEFAULT_VERIFICATION_CACHE_SIZE, DEFAULT_VERIFICATION_CACHE_TTL_SECS,
+};
+use crate::verification_pool::default_verification_workers;
+
+/// Default maximum number of items per `/resolve/batch` request.
+pub const DEFAULT_BATCH_RESOLVE_MAX_ITEMS: usize = 100;
+
+/// Default number of `/resolve/batch` items each API key may submit per minute.
+pub const DEFAULT_BATCH_RESOLVE_ITEMS_PER_MIN: u64 = 1000;
+
+/// Default request body limit in MB of media upload routes (`/seal`, `/verify`...).
+pub const DEFAULT_UPLOAD_BODY_LIMIT_MB: usize = 500;
+
+/// Default request timeout in seconds of media upload routes.
+pub const DEFAULT_UPLOAD_TIMEOUT_SECS: u64 = 120;
+
+/// Default request body limit in KB of hash lookup routes (`/resolve`, `/resolve/batch`).
+pub const DEFAULT_LOOKUP_BODY_LIMIT_KB: usize = 256;
+
+/// Default request timeout in seconds of hash lookup routes.
+pub const DEFAULT_LOOKUP_TIMEOUT_SECS: u64 = 10;
+
+/// Default seconds to wait for a free database connection (sqlx default).
+pub const DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS: u64 = 30;
+
+/// Default seconds before an idle database connection is closed (sqlx default).
+pub const DEFAULT_DATABASE_IDLE_TIMEOUT_SECS: u64 = 10 * 60;
+
+/// Default seconds before a database connection is recycled (sqlx default).
+pub const DEFAULT_DATABASE_MAX_LIFETIME_SECS: u64 = 30 * 60;
+
+/// Default base URL of public seal share links.
+pub const DEFAULT_SHARE_BASE_URL: &str = "https://veritas-q.com/shared";
+
+/// Default DID of the server, issuing its seals and credentials.
+pub const DEFAULT_ISSUER_DID: &str = "did:web:veritas-q.com";
 
 /// Server configuration loaded from environment variables
 #[derive(Debug, Clone)]
@@ -15,12 +69,54 @@ pub struct Config {
     pub host: [u8; 4],
     /// Allowed CORS origins, comma-separated (default: allow all in dev)
     pub allowed_origins: Option<Vec<String>>,
-    /// Request body limit in MB (default: 50)
+    /// Request body limit in MB of routes without a specific limit (default: 50)
     pub body_limit_mb: usize,
+    /// Request body limit in MB of media upload routes: `/seal`, `/verify`, C2PA,
+    /// seal import, resumable upload chunks and stream chunks (default: 500)
+    pub upload_body_limit_mb: usize,
+    /// Request body limit in KB of `/resolve` and `/resolve/batch` (default: 256)
+    pub lookup_body_limit_kb: usize,
     /// Maximum file size per upload in MB (default: 25)
     pub max_file_size_mb: usize,
-    /// Request timeout in seconds (default: 30)
+    /// Upload limits: allowed types, per-media-type sizes, image dimensions
+    pub upload_policy: UploadPolicy,
+    /// Directory holding resumable upload data (default: `<tmp>/veritas-uploads`)
+    pub upload_dir: PathBuf,
+    /// Idle time in seconds before a resumable upload is purged (default: 86400)
+    pub upload_ttl_secs: u64,
+    /// Directory holding personal data export archives (default: `<tmp>/veritas-exports`)
+    pub export_dir: PathBuf,
+    /// Time in seconds a data export stays downloadable (default: 86400)
+    pub export_ttl_secs: u64,
+    /// Base URL of public seal share links (default: https://veritas-q.com/shared)
+    pub share_base_url: String,
+    /// DID of the server: issues Verifiable Credential and JWS exports, names the
+    /// seal signing keys and is served as a did:web document at
+    /// /.well-known/did.json, invalid DIDs ignored
+    /// (default: did:web:veritas-q.com)
+    pub issuer_did: String,
+    /// Removal date of the unversioned legacy routes (/seal, /verify, ...),
+    /// announced in their Sunset header (RFC 3339, default: not scheduled)
+    pub legacy_routes_sunset: Option<DateTime<Utc>>,
+    /// Seals each account may create per calendar month (default: unlimited)
+    pub monthly_seal_quota: Option<u64>,
+    /// Alerts to seal owners (default: disabled)
+    pub notifications: NotificationConfig,
+    /// Maximum perceptual hash distance between two variants of an image (default: 8)
+    pub duplicate_threshold: u32,
+    /// Seconds between two near-duplicate clustering passes (default: 300)
+    pub cluster_interval_secs: u64,
+    /// Maximum number of items per `/resolve/batch` request (default: 100)
+    pub batch_resolve_max_items: usize,
+    /// `/resolve/batch` items each API key may submit per minute (default: 1000)
+    pub batch_resolve_items_per_min: u64,
+    /// Request timeout in seconds of routes without a specific timeout (default: 30)
     pub timeout_secs: u64,
+    /// Request timeout in seconds of media upload routes, resumable upload
+    /// finalization included (default: 120)
+    pub upload_timeout_secs: u64,
+    /// Request timeout in seconds of `/resolve` and `/resolve/batch` (default: 10)
+    pub lookup_timeout_secs: u64,
     /// Enable rate limiting (default: false for tests, true when loaded from env)
     pub rate_limit_enabled: bool,
     /// Rate limit: requests per second (default: 10)
@@ -29,12 +125,94 @@ pub struct Config {
     pub rate_limit_burst: u32,
     /// Clerk JWKS URL for JWT validation (enables JWT auth when set)
     pub clerk_jwks_url: Option<String>,
+    /// Required `iss` of Clerk tokens (default: not checked)
+    pub clerk_issuer: Option<String>,
+    /// Accepted `aud` values of Clerk tokens (default: not checked)
+    pub clerk_audiences: Vec<String>,
+    /// Issuers trusted besides Clerk, from JWT_ISSUERS_FILE (default: none)
+    pub jwt_issuers: Vec<JwtIssuerConfig>,
+    /// Who authenticates users: Clerk, or this server with email/password
+    /// accounts and its own tokens (AUTH_MODE=local) (default: clerk)
+    pub auth_mode: AuthMode,
+    /// Introspection of opaque tokens, from INTROSPECTION_* (default: disabled)
+    pub introspection: Option<IntrospectionConfig>,
+    /// Clients whose client-credentials tokens authenticate a service instead
+    /// of a user, as `<issuer name>:<client>` (Clerk clients unprefixed)
+    /// (default: none)
+    pub service_clients: Vec<String>,
+    /// Production deployment (VERITAS_ENV=production): mock QRNG is never
+    /// allowed, ALLOW_MOCK_QRNG=true refuses to start
+    pub production: bool,
     /// Allow mock QRNG usage (default: false, enable with ALLOW_MOCK_QRNG=true)
     pub allow_mock_qrng: bool,
+    /// QRNG providers clients may request, attempts per provider and entropy policy
+    /// (default: auto, idq, lfd, hardware; 2 attempts; fresh entropy per seal)
+    pub qrng_selection: QrngSelection,
+    /// Reject uploads whose declared media type contradicts their magic bytes
+    /// (default: false, enable with STRICT_MEDIA_TYPES=true)
+    pub strict_media_types: bool,
+    /// Start in read-only maintenance mode: sealing and writes get 503
+    /// (default: false, enable with READ_ONLY=true)
+    pub read_only: bool,
+    /// Retry-After of writes refused in read-only mode, in seconds (default: 300)
+    pub maintenance_retry_after_secs: u64,
+    /// How /verify treats seals made with the mock QRNG: accept, warn or reject
+    /// (default: warn, set with MOCK_QRNG_VERIFICATION)
+    pub mock_qrng_verification: MockQrngHandling,
+    /// Rules computing the trust tier of each seal
+    pub trust_policy: TrustPolicy,
     /// Database connection pool maximum connections (default: 20)
     pub database_max_connections: u32,
     /// Database connection pool minimum connections (default: 2)
     pub database_min_connections: u32,
+    /// Seconds to wait for a free database connection (default: 30)
+    pub database_acquire_timeout_secs: u64,
+    /// Seconds before an idle database connection is closed (default: 600, 0 = never)
+    pub database_idle_timeout_secs: u64,
+    /// Seconds before a database connection is recycled (default: 1800, 0 = never)
+    pub database_max_lifetime_secs: u64,
+    /// Read replica for listings, exports and resolution (default: none, all
+    /// queries use DATABASE_URL)
+    pub database_read_url: Option<String>,
+    /// Apply pending migrations at startup; when false, refuse to start while
+    /// the schema is behind (default: true)
+    pub run_migrations: bool,
+    /// Enforce tenant isolation with Postgres row-level security on top of
+    /// the repositories' own filters (default: false, enable with TENANT_RLS=true)
+    pub tenant_rls: bool,
+    /// Fetch one block of QRNG entropy at startup and refuse to start if that
+    /// fails (default: true)
+    pub startup_qrng_check: bool,
+    /// Solana JSON-RPC endpoint checked by `/ready` (default: not checked)
+    pub solana_rpc_url: Option<String>,
+    /// Time allowed to each `/ready` dependency check in milliseconds (default: 2000)
+    pub ready_check_timeout_ms: u64,
+    /// Seconds a `/ready` report is reused before checking again (default: 10)
+    pub ready_cache_secs: u64,
+    /// Verification outcomes and public seal records cached per instance
+    /// (default: 10000, 0 disables the cache)
+    pub verification_cache_size: usize,
+    /// Seconds a cached verification or seal record is served (default: 60)
+    pub verification_cache_ttl_secs: u64,
+    /// Concurrent `/verify` hashing and signature jobs, run off the async
+    /// runtime threads (default: number of CPU cores)
+    pub verification_workers: usize,
+    /// Confirmations a seal's blockchain anchor needs to be reported as
+    /// confirmed by `/verify` (default: 1)
+    pub anchor_min_confirmations: u64,
+    /// Time allowed to the anchor checks of one `/verify` request in
+    /// milliseconds (default: 5000, 0 disables the checks)
+    pub anchor_check_timeout_ms: u64,
+    /// Seconds between two batches of new seals anchored on Bitcoin
+    /// (default: unset, batched anchoring disabled)
+    pub anchor_batch_interval_secs: Option<u64>,
+    /// VQKP key pair file (`veritas seal --save-keypair`) signing every seal
+    /// under a registered key ID; its passphrase is read from
+    /// SIGNING_KEY_PASSPHRASE (default: unset, one-off key per seal)
+    pub signing_key_file: Option<PathBuf>,
+    /// Seconds a replaced signing key stays valid after a rotation, while
+    /// every replica switches keys (default: 604800, 7 days)
+    pub signing_key_overlap_secs: u64,
 }
 
 impl Default for Config {
@@ -44,15 +222,64 @@ impl Default for Config {
             host: [127, 0, 0, 1],
             allowed_origins: None, // None = allow all (dev mode)
             body_limit_mb: 50,
+            upload_body_limit_mb: DEFAULT_UPLOAD_BODY_LIMIT_MB,
+            lookup_body_limit_kb: DEFAULT_LOOKUP_BODY_LIMIT_KB,
             max_file_size_mb: 25,
+            upload_policy: UploadPolicy::default(),
+            upload_dir: default_upload_dir(),
+            upload_ttl_secs: 24 * 60 * 60,
+            export_dir: default_export_dir(),
+            export_ttl_secs: 24 * 60 * 60,
+            share_base_url: DEFAULT_SHARE_BASE_URL.to_string(),
+            issuer_did: DEFAULT_ISSUER_DID.to_string(),
+            legacy_routes_sunset: None,
+            monthly_seal_quota: None,
+            notifications: NotificationConfig::default(),
+            duplicate_threshold: DEFAULT_CLUSTER_THRESHOLD,
+            cluster_interval_secs: DEFAULT_CLUSTER_INTERVAL.as_secs(),
+            batch_resolve_max_items: DEFAULT_BATCH_RESOLVE_MAX_ITEMS,
+            batch_resolve_items_per_min: DEFAULT_BATCH_RESOLVE_ITEMS_PER_MIN,
             timeout_secs: 30,
+            upload_timeout_secs: DEFAULT_UPLOAD_TIMEOUT_SECS,
+            lookup_timeout_secs: DEFAULT_LOOKUP_TIMEOUT_SECS,
             rate_limit_enabled: false, // Disabled by default (for tests)
             rate_limit_per_sec: 10,
             rate_limit_burst: 20,
             clerk_jwks_url: None,
+            clerk_issuer: None,
+            clerk_audiences: Vec::new(),
+            jwt_issuers: Vec::new(),
+            auth_mode: AuthMode::Clerk,
+            introspection: None,
+            service_clients: Vec::new(),
+            production: false,
             allow_mock_qrng: true, // Enabled by default for tests; from_env() defaults to false
+            qrng_selection: QrngSelection::default(),
+            strict_media_types: false,
+            read_only: false,
+            maintenance_retry_after_secs: DEFAULT_MAINTENANCE_RETRY_AFTER_SECS,
+            mock_qrng_verification: MockQrngHandling::Warn,
+            trust_policy: TrustPolicy::default(),
             database_max_connections: 20,
             database_min_connections: 2,
+            database_acquire_timeout_secs: DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS,
+            database_idle_timeout_secs: DEFAULT_DATABASE_IDLE_TIMEOUT_SECS,
+            database_max_lifetime_secs: DEFAULT_DATABASE_MAX_LIFETIME_SECS,
+            database_read_url: None,
+            run_migrations: true,
+            tenant_rls: false,
+            startup_qrng_check: true,
+            solana_rpc_url: None,
+            ready_check_timeout_ms: DEFAULT_READY_CHECK_TIMEOUT_MS,
+            ready_cache_secs: DEFAULT_READY_CACHE_SECS,
+            verification_cache_size: DEFAULT_VERIFICATION_CACHE_SIZE,
+            verification_cache_ttl_secs: DEFAULT_VERIFICATION_CACHE_TTL_SECS,
+            verification_workers: default_verification_workers(),
+            anchor_min_confirmations: DEFAULT_ANCHOR_MIN_CONFIRMATIONS,
+            anchor_check_timeout_ms: DEFAULT_ANCHOR_CHECK_TIMEOUT_MS,
+            anchor_batch_interval_secs: None,
+            signing_key_file: None,
+            signing_key_overlap_secs: DEFAULT_SIGNING_KEY_OVERLAP_SECS,
         }
     }
 }
@@ -89,16 +316,108 @@ impl Config {
             .and_then(|v| v.parse().ok())
             .unwrap_or(50);
 
-        let max_file_size_mb = std::env::var("MAX_FILE_SIZE_MB")
+        let upload_body_limit_mb = std::env::var("UPLOAD_BODY_LIMIT_MB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_UPLOAD_BODY_LIMIT_MB);
+
+        let lookup_body_limit_kb = std::env::var("LOOKUP_BODY_LIMIT_KB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_LOOKUP_BODY_LIMIT_KB);
+
+        // Reads UPLOAD_POLICY_FILE and the MAX_*_SIZE_MB / image limit overrides
+        let upload_policy =
+            UploadPolicy::from_env().unwrap_or_else(|e| panic!("Invalid upload policy: {}", e));
+        let max_file_size_mb = upload_policy.max_file_size / (1024 * 1024);
+
+        let upload_dir = std::env::var("UPLOAD_DIR")
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(default_upload_dir);
+
+        let upload_ttl_secs = std::env::var("UPLOAD_SESSION_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(24 * 60 * 60);
+
+        let export_dir = std::env::var("EXPORT_DIR")
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(default_export_dir);
+
+        let export_ttl_secs = std::env::var("EXPORT_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&secs| secs > 0)
+            .unwrap_or(24 * 60 * 60);
+
+        let share_base_url = std::env::var("SHARE_BASE_URL")
+            .ok()
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_SHARE_BASE_URL.to_string());
+
+        let issuer_did = std::env::var("ISSUER_DID")
+            .ok()
+            .filter(|did| is_valid_did(did))
+            .unwrap_or_else(|| DEFAULT_ISSUER_DID.to_string());
+
+        let legacy_routes_sunset = std::env::var("LEGACY_ROUTES_SUNSET")
+            .ok()
+            .and_then(|date| DateTime::parse_from_rfc3339(&date).ok())
+            .map(|date| date.with_timezone(&Utc));
+
+        // Unset or 0 = unlimited
+        let monthly_seal_quota = std::env::var("MONTHLY_SEAL_QUOTA")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&quota| quota > 0);
+
+        // Reads NOTIFY_WEBHOOK_URL, NOTIFY_WEBHOOKS_FILE, SMTP_URL and the other
+        // NOTIFY_* variables
+        let notifications = NotificationConfig::from_env()
+            .unwrap_or_else(|e| panic!("Invalid notification settings: {}", e));
+
+        let duplicate_threshold = std::env::var("DUPLICATE_CLUSTER_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_CLUSTER_THRESHOLD);
+
+        let cluster_interval_secs = std::env::var("DUPLICATE_CLUSTER_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&secs| secs > 0)
+            .unwrap_or(DEFAULT_CLUSTER_INTERVAL.as_secs());
+
+        let batch_resolve_max_items = std::env::var("BATCH_RESOLVE_MAX_ITEMS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&max| max > 0)
+            .unwrap_or(DEFAULT_BATCH_RESOLVE_MAX_ITEMS);
+
+        let batch_resolve_items_per_min = std::env::var("BATCH_RESOLVE_ITEMS_PER_MIN")
             .ok()
             .and_then(|v| v.parse().ok())
-            .unwrap_or(25);
+            .filter(|&rate| rate > 0)
+            .unwrap_or(DEFAULT_BATCH_RESOLVE_ITEMS_PER_MIN);
 
         let timeout_secs = std::env::var("REQUEST_TIMEOUT_SECS")
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(30);
 
+        let upload_timeout_secs = std::env::var("UPLOAD_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_UPLOAD_TIMEOUT_SECS);
+
+        let lookup_timeout_secs = std::env::var("LOOKUP_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_LOOKUP_TIMEOUT_SECS);
+
         let rate_limit_per_sec = std::env::var("RATE_LIMIT_PER_SEC")
             .ok()
             .and_then(|v| v.parse().ok())
@@ -124,9 +443,81 @@ impl Config {
             derive_jwks_url_from_publishable_key(&pk)
         });
 
+        let clerk_issuer = std::env::var("CLERK_ISSUER")
+            .ok()
+            .filter(|iss| !iss.trim().is_empty());
+
+        let clerk_audiences = std::env::var("CLERK_AUDIENCE")
+            .map(|audiences| {
+                audiences
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Reads JWT_ISSUERS_FILE
+        let jwt_issuers = JwtIssuerConfig::additional_from_env()
+            .unwrap_or_else(|e| panic!("Invalid JWT issuers: {}", e));
+
+        // Reads AUTH_MODE and, in local mode, the AUTH_* key settings
+        let auth_mode = AuthMode::from_env().unwrap_or_else(|e| panic!("Invalid auth mode: {}", e));
+
+        // Reads INTROSPECTION_URL and the other INTROSPECTION_* settings
+        let introspection = IntrospectionConfig::from_env()
+            .unwrap_or_else(|e| panic!("Invalid token introspection: {}", e));
+
+        let service_clients = std::env::var("SERVICE_CLIENTS")
+            .map(|clients| {
+                clients
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let production = std::env::var("VERITAS_ENV")
+            .map(|v| v.eq_ignore_ascii_case("production"))
+            .unwrap_or(false);
+
         let allow_mock_qrng = std::env::var("ALLOW_MOCK_QRNG")
             .map(|v| v.to_lowercase() == "true")
             .unwrap_or(false);
+        let allow_mock_qrng = mock_qrng_allowed(production, allow_mock_qrng)
+            .unwrap_or_else(|e| panic!("Invalid configuration: {}", e));
+
+        // Reads QRNG_ALLOWED_PROVIDERS, QRNG_MAX_ATTEMPTS and the QRNG_POLICY/QRNG_POOL_* settings
+        let qrng_selection = QrngSelection::from_env()
+            .unwrap_or_else(|e| panic!("Invalid QRNG provider selection: {}", e));
+
+        let strict_media_types = std::env::var("STRICT_MEDIA_TYPES")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        let read_only = std::env::var("READ_ONLY")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        let maintenance_retry_after_secs = std::env::var("MAINTENANCE_RETRY_AFTER_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAINTENANCE_RETRY_AFTER_SECS);
+
+        let mock_qrng_verification = match std::env::var("MOCK_QRNG_VERIFICATION") {
+            Ok(value) => MockQrngHandling::parse(&value).unwrap_or_else(|| {
+                panic!(
+                    "Invalid MOCK_QRNG_VERIFICATION: {} (expected accept, warn or reject)",
+                    value
+                )
+            }),
+            Err(_) => MockQrngHandling::Warn,
+        };
+
+        // Reads TRUST_POLICY_FILE and the TRUST_* overrides
+        let trust_policy =
+            TrustPolicy::from_env().unwrap_or_else(|e| panic!("Invalid trust policy: {}", e));
 
         let database_max_connections = std::env::var("DATABASE_MAX_CONNECTIONS")
             .ok()
@@ -138,20 +529,157 @@ impl Config {
             .and_then(|v| v.parse().ok())
             .unwrap_or(2);
 
+        let database_acquire_timeout_secs = std::env::var("DATABASE_ACQUIRE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&secs| secs > 0)
+            .unwrap_or(DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS);
+
+        let database_idle_timeout_secs = std::env::var("DATABASE_IDLE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_DATABASE_IDLE_TIMEOUT_SECS);
+
+        let database_max_lifetime_secs = std::env::var("DATABASE_MAX_LIFETIME_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_DATABASE_MAX_LIFETIME_SECS);
+
+        let database_read_url = std::env::var("DATABASE_READ_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
+
+        let run_migrations = std::env::var("RUN_MIGRATIONS")
+            .map(|v| v.to_lowercase() != "false")
+            .unwrap_or(true);
+
+        let tenant_rls = std::env::var("TENANT_RLS")
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false);
+
+        let startup_qrng_check = std::env::var("STARTUP_QRNG_CHECK")
+            .map(|v| v.to_lowercase() != "false")
+            .unwrap_or(true);
+
+        let solana_rpc_url = std::env::var("SOLANA_RPC_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
+
+        let ready_check_timeout_ms = std::env::var("READY_CHECK_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&ms| ms > 0)
+            .unwrap_or(DEFAULT_READY_CHECK_TIMEOUT_MS);
+
+        let ready_cache_secs = std::env::var("READY_CACHE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_READY_CACHE_SECS);
+
+        let verification_cache_size = std::env::var("VERIFICATION_CACHE_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_VERIFICATION_CACHE_SIZE);
+
+        let verification_cache_ttl_secs = std::env::var("VERIFICATION_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_VERIFICATION_CACHE_TTL_SECS);
+
+        let verification_workers = std::env::var("VERIFICATION_WORKERS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&workers| workers > 0)
+            .unwrap_or_else(default_verification_workers);
+
+        let anchor_min_confirmations = std::env::var("ANCHOR_MIN_CONFIRMATIONS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_ANCHOR_MIN_CONFIRMATIONS);
+
+        let anchor_check_timeout_ms = std::env::var("ANCHOR_CHECK_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_ANCHOR_CHECK_TIMEOUT_MS);
+
+        let anchor_batch_interval_secs = std::env::var("ANCHOR_BATCH_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&secs| secs > 0);
+
+        let signing_key_file = std::env::var("SIGNING_KEY_FILE")
+            .ok()
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from);
+
+        let signing_key_overlap_secs = std::env::var("SIGNING_KEY_OVERLAP_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SIGNING_KEY_OVERLAP_SECS);
+
         Self {
             port,
             host,
             allowed_origins,
             body_limit_mb,
+            upload_body_limit_mb,
+            lookup_body_limit_kb,
             max_file_size_mb,
+            upload_policy,
+            upload_dir,
+            upload_ttl_secs,
+            export_dir,
+            export_ttl_secs,
+            share_base_url,
+            issuer_did,
+            legacy_routes_sunset,
+            monthly_seal_quota,
+            notifications,
+            duplicate_threshold,
+            cluster_interval_secs,
+            batch_resolve_max_items,
+            batch_resolve_items_per_min,
             timeout_secs,
+            upload_timeout_secs,
+            lookup_timeout_secs,
             rate_limit_enabled,
             rate_limit_per_sec,
             rate_limit_burst,
             clerk_jwks_url,
+            clerk_issuer,
+            clerk_audiences,
+            jwt_issuers,
+            auth_mode,
+            introspection,
+            service_clients,
+            production,
             allow_mock_qrng,
+            qrng_selection,
+            strict_media_types,
+            read_only,
+            maintenance_retry_after_secs,
+            mock_qrng_verification,
+            trust_policy,
             database_max_connections,
             database_min_connections,
+            database_acquire_timeout_secs,
+            database_idle_timeout_secs,
+            database_max_lifetime_secs,
+            database_read_url,
+            run_migrations,
+            tenant_rls,
+            startup_qrng_check,
+            solana_rpc_url,
+            ready_check_timeout_ms,
+            ready_cache_secs,
+            verification_cache_size,
+            verification_cache_ttl_secs,
+            verification_workers,
+            anchor_min_confirmations,
+            anchor_check_timeout_ms,
+            anchor_batch_interval_secs,
+            signing_key_file,
+            signing_key_overlap_secs,
         }
     }
 
@@ -159,6 +687,48 @@ impl Config {
     pub fn socket_addr(&self) -> SocketAddr {
         SocketAddr::from((self.host, self.port))
     }
+
+    /// Issuers whose tokens are accepted: Clerk first (when configured and
+    /// not in local auth mode), then those of JWT_ISSUERS_FILE. The server's
+    /// own issuer is added when local auth starts.
+    pub fn trusted_jwt_issuers(&self) -> Vec<JwtIssuerConfig> {
+        let clerk_url = match self.auth_mode {
+            AuthMode::Clerk => self.clerk_jwks_url.as_ref(),
+            AuthMode::Local(_) => None,
+        };
+        let clerk = clerk_url.map(|url| {
+            JwtIssuerConfig::clerk(
+                url.clone(),
+                self.clerk_issuer.clone(),
+                self.clerk_audiences.clone(),
+            )
+        });
+        clerk
+            .into_iter()
+            .chain(self.jwt_issuers.iter().cloned())
+            .collect()
+    }
+
+    /// Connection pool settings, shared by the primary and the read replica
+    pub fn database_pool_options(&self) -> PgPoolOptions {
+        let non_zero = |secs: u64| Some(Duration::from_secs(secs)).filter(|d| !d.is_zero());
+        PgPoolOptions::new()
+            .max_connections(self.database_max_connections)
+            .min_connections(self.database_min_connections)
+            .acquire_timeout(Duration::from_secs(self.database_acquire_timeout_secs))
+            .idle_timeout(non_zero(self.database_idle_timeout_secs))
+            .max_lifetime(non_zero(self.database_max_lifetime_secs))
+    }
+}
+
+/// Default directory for resumable upload data.
+fn default_upload_dir() -> PathBuf {
+    std::env::temp_dir().join("veritas-uploads")
+}
+
+/// Default directory for personal data export archives.
+fn default_export_dir() -> PathBuf {
+    std::env::temp_dir().join("veritas-exports")
 }
 
 /// Derive the Clerk JWKS URL from a Clerk publishable key.
@@ -194,9 +764,18 @@ fn derive_jwks_url_from_publishable_key(pk: &str) -> Option<String> {
     Some(url)
 }
 
+/// Whether mock QRNG may be used, refusing to enable it in production
+fn mock_qrng_allowed(production: bool, requested: bool) -> Result<bool, String> {
+    if production && requested {
+        return Err("ALLOW_MOCK_QRNG=true is not allowed with VERITAS_ENV=production".to_string());
+    }
+    Ok(requested)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::auth::LocalAuthConfig;
 
     #[test]
     fn test_derive_jwks_url_from_test_key() {
@@ -237,6 +816,80 @@ mod tests {
         let config = Config::default();
         assert_eq!(config.port, 3000);
         assert!(config.clerk_jwks_url.is_none());
+        assert!(!config.production);
         assert!(config.allow_mock_qrng);
+        assert!(!config.strict_media_types);
+        assert!(!config.read_only);
+        assert_eq!(config.mock_qrng_verification, MockQrngHandling::Warn);
+        assert_eq!(
+            config.upload_policy.max_file_size,
+            config.max_file_size_mb * 1024 * 1024
+        );
+        assert!(config.database_read_url.is_none());
+        assert!(!config.tenant_rls);
+        assert_eq!(config.database_acquire_timeout_secs, 30);
+    }
+
+    #[test]
+    fn test_trusted_jwt_issuers() {
+        assert!(Config::default().trusted_jwt_issuers().is_empty());
+
+        let corp = JwtIssuerConfig {
+            name: "corp".to_string(),
+            jwks_url: "https://idp.corp/jwks".to_string(),
+            issuer: Some("https://idp.corp".to_string()),
+            audiences: Vec::new(),
+        };
+        let config = Config {
+            clerk_jwks_url: Some("https://clerk.example/.well-known/jwks.json".to_string()),
+            clerk_audiences: vec!["veritas".to_string()],
+            jwt_issuers: vec![corp.clone()],
+            ..Config::default()
+        };
+        let issuers = config.trusted_jwt_issuers();
+        assert_eq!(issuers.len(), 2);
+        assert_eq!(issuers[0].name, "clerk");
+        assert_eq!(issuers[0].issuer, None);
+        assert_eq!(issuers[0].audiences, ["veritas"]);
+        assert_eq!(issuers[1], corp);
+
+        // Clerk is not trusted in local auth mode
+        let config = Config {
+            auth_mode: AuthMode::Local(LocalAuthConfig {
+                algorithm: jsonwebtoken::Algorithm::RS256,
+                signing_key_pem: Vec::new(),
+                public_key_pem: Vec::new(),
+                issuer: "veritas-q".to_string(),
+                token_ttl: Duration::from_secs(600),
+            }),
+            ..config
+        };
+        assert_eq!(config.trusted_jwt_issuers(), [corp]);
+    }
+
+    #[test]
+    fn test_database_pool_options() {
+        let config = Config {
+            database_max_connections: 8,
+            database_idle_timeout_secs: 0,
+            ..Config::default()
+        };
+        let options = config.database_pool_options();
+        assert_eq!(options.get_max_connections(), 8);
+        assert_eq!(options.get_acquire_timeout(), Duration::from_secs(30));
+        assert_eq!(options.get_idle_timeout(), None);
+        assert_eq!(
+            options.get_max_lifetime(),
+            Some(Duration::from_secs(30 * 60))
+        );
+    }
+
+    #[test]
+    fn test_mock_qrng_blocked_in_production() {
+        assert_eq!(mock_qrng_allowed(false, true), Ok(true));
+        assert_eq!(mock_qrng_allowed(true, false), Ok(false));
+        assert!(mock_qrng_allowed(true, true)
+            .unwrap_err()
+            .contains("VERITAS_ENV=production"));
     }
 }
@@ -0,0 +1,60 @@
+//! Batched anchor repository
+//!
+//! Stores the anchor of each seal anchored in a batch (see
+//! [`crate::anchor_batcher`]), keyed by the seal's anchor digest.
+
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::PgPool;
+use veritas_core::BlockchainAnchor;
+
+/// Repository for batched anchor operations
+#[derive(Clone)]
+pub struct AnchorProofRepository {
+    pool: PgPool,
+}
+
+impl AnchorProofRepository {
+    /// Create a new batched anchor repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store the anchors of one batch, keyed by anchor digest, with when
+    /// each seal was queued
+    pub async fn insert_batch(
+        &self,
+        anchors: &[([u8; 32], BlockchainAnchor, DateTime<Utc>)],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for (digest, anchor, queued_at) in anchors {
+            let batch_root = anchor.batch.as_ref().map(|batch| batch.root.to_vec());
+            sqlx::query(
+                r#"
+                INSERT INTO anchor_proofs (digest, chain, tx_id, batch_root, anchor, queued_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (digest) DO NOTHING
+                "#,
+            )
+            .bind(digest.as_slice())
+            .bind(&anchor.chain)
+            .bind(&anchor.tx_id)
+            .bind(batch_root.unwrap_or_default())
+            .bind(Json(anchor))
+            .bind(queued_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Find the anchor of a seal by its anchor digest
+    pub async fn find(&self, digest: &[u8; 32]) -> Result<Option<BlockchainAnchor>, sqlx::Error> {
+        let row: Option<(Json<BlockchainAnchor>,)> =
+            sqlx::query_as("SELECT anchor FROM anchor_proofs WHERE digest = $1")
+                .bind(digest.as_slice())
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(Json(anchor),)| anchor))
+    }
+}
@@ -0,0 +1,278 @@
+//! API key entity and repository
+//!
+//! API keys authenticate machine clients (crawlers, newsroom integrations)
+//! without a Clerk session. Each key acts for the account that created it and
+//! is limited to a set of scopes. Only a hash of each key is stored. Listing
+//! and revoking are restricted to the user of a [`Tenant`].
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Tenant, TenantConnection};
+
+/// Prefix of every API key, to recognize leaked keys
+pub const API_KEY_PREFIX: &str = "vq_";
+
+/// Characters of the key stored in clear to recognize it in listings
+const DISPLAY_PREFIX_LEN: usize = API_KEY_PREFIX.len() + 8;
+
+/// Operations an API key may perform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ApiKeyScope {
+    /// Bulk soft-binding resolution (`POST /resolve/batch`)
+    #[serde(rename = "resolve:batch")]
+    ResolveBatch,
+}
+
+impl ApiKeyScope {
+    /// Value stored in the `api_keys.scopes` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ResolveBatch => "resolve:batch",
+        }
+    }
+}
+
+/// API key entity from database
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub key_hash: Vec<u8>,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Generate a new key: `vq_` followed by 32 bytes from the OS RNG,
+    /// URL-safe base64.
+    pub fn generate_secret() -> Result<String, getrandom::Error> {
+        let mut bytes = [0u8; 32];
+        getrandom::fill(&mut bytes)?;
+        Ok(format!(
+            "{}{}",
+            API_KEY_PREFIX,
+            URL_SAFE_NO_PAD.encode(bytes)
+        ))
+    }
+
+    /// SHA3-256 of a key, as stored in the database
+    pub fn hash_secret(secret: &str) -> Vec<u8> {
+        Sha3_256::digest(secret.as_bytes()).to_vec()
+    }
+
+    /// Leading characters of a key, stored in clear
+    pub fn display_prefix(secret: &str) -> String {
+        secret.chars().take(DISPLAY_PREFIX_LEN).collect()
+    }
+
+    /// Returns true if the key was granted `scope`
+    pub fn has_scope(&self, scope: ApiKeyScope) -> bool {
+        self.scopes.iter().any(|s| s == scope.as_str())
+    }
+}
+
+/// API key as listed to its owner (without the key)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKeyRecord {
+    /// Key ID, used to revoke it
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+
+    /// Label chosen by the owner
+    #[schema(example = "Newsroom crawler")]
+    pub name: String,
+
+    /// First characters of the key
+    #[schema(example = "vq_hX0r3m2b")]
+    pub key_prefix: String,
+
+    /// Granted scopes
+    #[schema(example = json!(["resolve:batch"]))]
+    pub scopes: Vec<String>,
+
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Last time the key authenticated a request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+
+    /// Revocation timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKey> for ApiKeyRecord {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            id: key.id,
+            name: key.name,
+            key_prefix: key.key_prefix,
+            scopes: key.scopes,
+            created_at: key.created_at,
+            last_used_at: key.last_used_at,
+            revoked_at: key.revoked_at,
+        }
+    }
+}
+
+/// Repository for API key operations
+#[derive(Clone)]
+pub struct ApiKeyRepository {
+    pool: PgPool,
+    tenant_rls: bool,
+}
+
+impl ApiKeyRepository {
+    /// Create a new API key repository
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Run tenant queries under row-level security, and the others as
+    /// system paths bypassing it (`TENANT_RLS`)
+    pub fn with_tenant_rls(mut self, tenant_rls: bool) -> Self {
+        self.tenant_rls = tenant_rls;
+        self
+    }
+
+    /// Insert a new key
+    pub async fn create(&self, key: &ApiKey) -> Result<(), sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        sqlx::query(
+            r#"
+            INSERT INTO api_keys (id, user_id, name, key_prefix, key_hash, scopes, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(key.id)
+        .bind(key.user_id)
+        .bind(&key.name)
+        .bind(&key.key_prefix)
+        .bind(&key.key_hash)
+        .bind(&key.scopes)
+        .bind(key.created_at)
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+        Ok(())
+    }
+
+    /// Find an unrevoked key of an active account by hash and mark it used
+    pub async fn authenticate(&self, key_hash: &[u8]) -> Result<Option<ApiKey>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let key = sqlx::query_as::<_, ApiKey>(
+            r#"
+            UPDATE api_keys k
+            SET last_used_at = NOW()
+            FROM users u
+            WHERE k.key_hash = $1
+              AND k.revoked_at IS NULL
+              AND u.id = k.user_id
+              AND u.suspended_at IS NULL
+            RETURNING k.id, k.user_id, k.name, k.key_prefix, k.key_hash, k.scopes,
+                      k.created_at, k.last_used_at, k.revoked_at
+            "#,
+        )
+        .bind(key_hash)
+        .fetch_optional(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(key)
+    }
+
+    /// List a tenant's keys, newest first
+    pub async fn list_for_user(&self, tenant: &Tenant) -> Result<Vec<ApiKey>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, ApiKey>(
+            r#"
+            SELECT id, user_id, name, key_prefix, key_hash, scopes,
+                   created_at, last_used_at, revoked_at
+            FROM api_keys
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(tenant.user_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Revoke a key, restricted to a tenant
+    ///
+    /// Returns false if no such key exists. Revoking twice keeps the first
+    /// revocation time.
+    pub async fn revoke(&self, id: Uuid, tenant: &Tenant) -> Result<bool, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(tenant.user_id)
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_secret() {
+        let secret = ApiKey::generate_secret().unwrap();
+        assert!(secret.starts_with(API_KEY_PREFIX));
+        assert_eq!(secret.len(), API_KEY_PREFIX.len() + 43);
+        assert_ne!(secret, ApiKey::generate_secret().unwrap());
+
+        assert_eq!(ApiKey::hash_secret(&secret).len(), 32);
+        assert_eq!(ApiKey::display_prefix(&secret), secret[..11]);
+    }
+
+    #[test]
+    fn test_api_key_scopes() {
+        let key = ApiKey {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "crawler".to_string(),
+            key_prefix: "vq_abcdefgh".to_string(),
+            key_hash: vec![0; 32],
+            scopes: vec![ApiKeyScope::ResolveBatch.as_str().to_string()],
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        assert!(key.has_scope(ApiKeyScope::ResolveBatch));
+        assert!(!ApiKey {
+            scopes: vec![],
+            ..key
+        }
+        .has_scope(ApiKeyScope::ResolveBatch));
+
+        assert_eq!(
+            serde_json::to_string(&ApiKeyScope::ResolveBatch).unwrap(),
+            "\"resolve:batch\""
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Audit log entity and repository
+//!
+//! Records verifications of and accesses to seals, so owners can see how
+//! their seals are used by third parties, every use of mock entropy, so
+//! operators can show which seals are not quantum-safe, and what retention
+//! policies deleted.
+
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Tenant, TenantConnection};
+
+/// Kind of audited event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEvent {
+    /// A seal was verified against content (`/verify`)
+    SealVerified,
+    /// An image was resolved to a seal by perceptual hash (`/resolve`)
+    SealResolved,
+    /// A seal was opened through a share link
+    ShareOpened,
+    /// A seal was made with mock QRNG entropy (`/seal`, uploads, streams, C2PA)
+    MockEntropyUsed,
+    /// A seal's media was deleted by its organization's retention policy
+    MediaExpired,
+    /// A seal was deleted by its organization's retention policy
+    SealExpired,
+    /// An administrator placed a seal under legal hold
+    LegalHoldPlaced,
+    /// An administrator released a seal's legal hold
+    LegalHoldReleased,
+}
+
+impl AuditEvent {
+    /// Value stored in the `audit_log.event` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEvent::SealVerified => "seal_verified",
+            AuditEvent::SealResolved => "seal_resolved",
+            AuditEvent::ShareOpened => "share_opened",
+            AuditEvent::MockEntropyUsed => "mock_entropy_used",
+            AuditEvent::MediaExpired => "media_expired",
+            AuditEvent::SealExpired => "seal_expired",
+            AuditEvent::LegalHoldPlaced => "legal_hold_placed",
+            AuditEvent::LegalHoldReleased => "legal_hold_released",
+        }
+    }
+}
+
+/// Outcome of an audited verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationOutcome {
+    /// Content matches the seal
+    Authentic,
+    /// Content differs from the sealed content
+    Tampered,
+    /// Seal signature does not verify
+    InvalidSignature,
+}
+
+impl VerificationOutcome {
+    /// Value stored in the `audit_log.outcome` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationOutcome::Authentic => "authentic",
+            VerificationOutcome::Tampered => "tampered",
+            VerificationOutcome::InvalidSignature => "invalid_signature",
+        }
+    }
+}
+
+/// Activity on a user's seals by anyone but the user
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct ThirdPartyActivity {
+    /// Verifications of the user's seals
+    pub verifications: i64,
+    /// Verifications in the last 30 days
+    pub verifications_last_30_days: i64,
+    /// Verifications that found the content tampered
+    pub tampered: i64,
+    /// Images resolved to the user's seals by perceptual hash
+    pub resolve_matches: i64,
+    /// Views of the user's seals through share links
+    pub share_views: i64,
+}
+
+/// Repository for audit log operations
+#[derive(Clone)]
+pub struct AuditLogRepository {
+    pool: PgPool,
+    tenant_rls: bool,
+}
+
+impl AuditLogRepository {
+    /// Create a new audit log repository
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Read the user's seals under row-level security (`TENANT_RLS`)
+    pub fn with_tenant_rls(mut self, tenant_rls: bool) -> Self {
+        self.tenant_rls = tenant_rls;
+        self
+    }
+
+    /// Append an entry
+    pub async fn record(
+        &self,
+        event: AuditEvent,
+        seal_id: Option<Uuid>,
+        actor_user_id: Option<Uuid>,
+        outcome: Option<VerificationOutcome>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (event, seal_id, actor_user_id, outcome)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(event.as_str())
+        .bind(seal_id)
+        .bind(actor_user_id)
+        .bind(outcome.map(|outcome| outcome.as_str()))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Count verifications, resolve matches and share views of a user's
+    /// seals by others
+    /// (anonymous requests included, the user's own requests excluded)
+    pub async fn third_party_activity(
+        &self,
+        owner_id: Uuid,
+    ) -> Result<ThirdPartyActivity, sqlx::Error> {
+        let tenant = Tenant::personal(owner_id);
+        let mut conn = TenantConnection::acquire(&self.pool, &tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, ThirdPartyActivity>(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE a.event = 'seal_verified') AS verifications,
+                   COUNT(*) FILTER (WHERE a.event = 'seal_verified'
+                                    AND a.created_at > NOW() - INTERVAL '30 days')
+                       AS verifications_last_30_days,
+                   COUNT(*) FILTER (WHERE a.event = 'seal_verified' AND a.outcome = 'tampered')
+                       AS tampered,
+                   COUNT(*) FILTER (WHERE a.event = 'seal_resolved') AS resolve_matches,
+                   COUNT(*) FILTER (WHERE a.event = 'share_opened') AS share_views
+            FROM audit_log a
+            JOIN seals s ON s.id = a.seal_id
+            WHERE s.user_id = $1 AND a.actor_user_id IS DISTINCT FROM $1
+            "#,
+        )
+        .bind(owner_id)
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    /// Count events of a kind over the last `days` days
+    pub async fn count_recent(&self, event: AuditEvent, days: i32) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM audit_log
+            WHERE event = $1 AND created_at > NOW() - make_interval(days => $2)
+            "#,
+        )
+        .bind(event.as_str())
+        .bind(days)
+        .fetch_one(&self.pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_values_match_queries() {
+        assert_eq!(AuditEvent::SealVerified.as_str(), "seal_verified");
+        assert_eq!(AuditEvent::SealResolved.as_str(), "seal_resolved");
+        assert_eq!(AuditEvent::ShareOpened.as_str(), "share_opened");
+        assert_eq!(AuditEvent::MockEntropyUsed.as_str(), "mock_entropy_used");
+        assert_eq!(AuditEvent::MediaExpired.as_str(), "media_expired");
+        assert_eq!(AuditEvent::SealExpired.as_str(), "seal_expired");
+        assert_eq!(AuditEvent::LegalHoldPlaced.as_str(), "legal_hold_placed");
+        assert_eq!(
+            AuditEvent::LegalHoldReleased.as_str(),
+            "legal_hold_released"
+        );
+        assert_eq!(VerificationOutcome::Tampered.as_str(), "tampered");
+    }
+}
@@ -0,0 +1,304 @@
+//! Collection entity and repository
+//!
+//! Named groups of a user's seals (e.g. one per assignment). A seal may
+//! belong to several collections; deleting a collection keeps its seals.
+//! Collections belong to the user of a [`Tenant`], and only take seals of
+//! that tenant.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{is_unique_violation, Tenant, TenantConnection};
+
+/// Collection from database, with its number of seals
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow, ToSchema)]
+pub struct Collection {
+    /// Collection ID
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+
+    /// Name, unique among the user's collections
+    #[schema(example = "Ukraine field trip 2025")]
+    pub name: String,
+
+    /// Free-form description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Seals in the collection
+    #[schema(example = 42)]
+    pub seal_count: i64,
+
+    /// Creation timestamp
+    #[schema(value_type = String, example = "2026-01-08T10:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    /// Last update timestamp
+    #[schema(value_type = String, example = "2026-01-08T10:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A seal of a collection, as stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+pub struct CollectionSeal {
+    pub collection_id: Uuid,
+    pub seal_id: Uuid,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Repository for collections
+#[derive(Clone)]
+pub struct CollectionRepository {
+    pool: PgPool,
+    tenant_rls: bool,
+}
+
+impl CollectionRepository {
+    /// Create a new collection repository
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Run tenant queries under row-level security, and the others as
+    /// system paths bypassing it (`TENANT_RLS`)
+    pub fn with_tenant_rls(mut self, tenant_rls: bool) -> Self {
+        self.tenant_rls = tenant_rls;
+        self
+    }
+
+    /// Create a collection
+    ///
+    /// Returns `None` if the user already has a collection with that name.
+    pub async fn create(
+        &self,
+        tenant: &Tenant,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<Option<Collection>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let collection = sqlx::query_as::<_, Collection>(
+            r#"
+            INSERT INTO collections (id, user_id, name, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, name, description, 0::BIGINT AS seal_count, created_at, updated_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(tenant.user_id)
+        .bind(name)
+        .bind(description)
+        .fetch_one(&mut *conn)
+        .await;
+
+        match collection {
+            Ok(collection) => {
+                conn.finish().await?;
+                Ok(Some(collection))
+            }
+            Err(e) if is_unique_violation(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// A tenant's collections, alphabetically
+    pub async fn list_for_user(&self, tenant: &Tenant) -> Result<Vec<Collection>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, Collection>(
+            r#"
+            SELECT c.id, c.name, c.description,
+                   (SELECT COUNT(*) FROM collection_seals cs WHERE cs.collection_id = c.id)
+                       AS seal_count,
+                   c.created_at, c.updated_at
+            FROM collections c
+            WHERE c.user_id = $1
+            ORDER BY c.name
+            "#,
+        )
+        .bind(tenant.user_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Find a collection by ID, restricted to a tenant
+    pub async fn find_for_user(
+        &self,
+        id: Uuid,
+        tenant: &Tenant,
+    ) -> Result<Option<Collection>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, Collection>(
+            r#"
+            SELECT c.id, c.name, c.description,
+                   (SELECT COUNT(*) FROM collection_seals cs WHERE cs.collection_id = c.id)
+                       AS seal_count,
+                   c.created_at, c.updated_at
+            FROM collections c
+            WHERE c.id = $1 AND c.user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(tenant.user_id)
+        .fetch_optional(&mut *conn)
+        .await
+    }
+
+    /// Rename a collection and replace its description, restricted to a
+    /// tenant
+    ///
+    /// Returns `None` if no such collection exists or the user already has
+    /// another collection with that name.
+    pub async fn update(
+        &self,
+        id: Uuid,
+        tenant: &Tenant,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<Option<Collection>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let collection = sqlx::query_as::<_, Collection>(
+            r#"
+            UPDATE collections c
+            SET name = $3, description = $4, updated_at = NOW()
+            WHERE c.id = $1 AND c.user_id = $2
+            RETURNING c.id, c.name, c.description,
+                      (SELECT COUNT(*) FROM collection_seals cs WHERE cs.collection_id = c.id)
+                          AS seal_count,
+                      c.created_at, c.updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(tenant.user_id)
+        .bind(name)
+        .bind(description)
+        .fetch_optional(&mut *conn)
+        .await;
+
+        match collection {
+            Ok(collection) => {
+                conn.finish().await?;
+                Ok(collection)
+            }
+            Err(e) if is_unique_violation(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Delete a collection (not its seals), restricted to a tenant
+    ///
+    /// Returns false if no such collection exists.
+    pub async fn delete(&self, id: Uuid, tenant: &Tenant) -> Result<bool, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let result = sqlx::query("DELETE FROM collections WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(tenant.user_id)
+            .execute(&mut *conn)
+            .await?;
+        conn.finish().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Add a tenant's seal to one of its collections; adding it twice keeps
+    /// the first time
+    ///
+    /// Returns false if the collection or the seal is not the tenant's.
+    pub async fn add_seal(
+        &self,
+        id: Uuid,
+        seal_id: Uuid,
+        tenant: &Tenant,
+    ) -> Result<bool, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let owned: Option<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+            SELECT c.id, s.id
+            FROM collections c, seals s
+            WHERE c.id = $1 AND c.user_id = $3
+              AND s.id = $2 AND s.user_id = $3
+              AND (s.organization_id IS NULL OR s.organization_id = $4)
+            "#,
+        )
+        .bind(id)
+        .bind(seal_id)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+        if owned.is_none() {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO collection_seals (collection_id, seal_id)
+            VALUES ($1, $2)
+            ON CONFLICT (collection_id, seal_id) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(seal_id)
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+        Ok(true)
+    }
+
+    /// Remove a seal from a collection, restricted to a tenant
+    ///
+    /// Returns false if the seal was not in the collection.
+    pub async fn remove_seal(
+        &self,
+        id: Uuid,
+        seal_id: Uuid,
+        tenant: &Tenant,
+    ) -> Result<bool, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let result = sqlx::query(
+            r#"
+            DELETE FROM collection_seals cs
+            USING collections c
+            WHERE c.id = cs.collection_id
+              AND cs.collection_id = $1 AND cs.seal_id = $2 AND c.user_id = $3
+            "#,
+        )
+        .bind(id)
+        .bind(seal_id)
+        .bind(tenant.user_id)
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Seals of all of a tenant's collections (personal data exports, with a
+    /// [`Tenant::personal`] scope)
+    pub async fn seals_for_user(
+        &self,
+        tenant: &Tenant,
+    ) -> Result<Vec<CollectionSeal>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, CollectionSeal>(
+            r#"
+            SELECT cs.collection_id, cs.seal_id, cs.added_at
+            FROM collection_seals cs
+            JOIN collections c ON c.id = cs.collection_id
+            JOIN seals s ON s.id = cs.seal_id
+            WHERE c.user_id = $1
+              AND (s.organization_id IS NULL OR s.organization_id = $2 OR $3)
+            ORDER BY cs.collection_id, cs.added_at
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .bind(tenant.all_organizations)
+        .fetch_all(&mut *conn)
+        .await
+    }
+}
@@ -0,0 +1,114 @@
+//! Local credential repository
+//!
+//! Email/password logins of self-hosted accounts (`AUTH_MODE=local`). Each
+//! credential belongs to a regular user whose `clerk_user_id` is the subject
+//! of the tokens the server issues (`local:<uuid>`). Only Argon2id hashes of
+//! passwords are stored.
+
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use super::{is_unique_violation, User};
+
+/// An account and the password hash it logs in with
+#[derive(Debug, Clone, FromRow)]
+pub struct LocalCredential {
+    #[sqlx(flatten)]
+    pub user: User,
+    pub password_hash: String,
+}
+
+/// Repository for local credential operations
+#[derive(Clone)]
+pub struct LocalCredentialRepository {
+    pool: PgPool,
+}
+
+impl LocalCredentialRepository {
+    /// Create a new local credential repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create an account and its credentials in one transaction
+    ///
+    /// `email` must already be normalized (lowercased). Returns None if the
+    /// email is already registered.
+    pub async fn register(
+        &self,
+        clerk_user_id: &str,
+        email: &str,
+        name: Option<&str>,
+        password_hash: &str,
+    ) -> Result<Option<User>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            INSERT INTO users (clerk_user_id, email, name)
+            VALUES ($1, $2, $3)
+            RETURNING id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                      organization_id, created_at, updated_at, deleted_at
+            "#,
+        )
+        .bind(clerk_user_id)
+        .bind(email)
+        .bind(name)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO local_credentials (user_id, email, password_hash)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(user.id)
+        .bind(email)
+        .bind(password_hash)
+        .execute(&mut *tx)
+        .await;
+
+        match inserted {
+            Ok(_) => {
+                tx.commit().await?;
+                Ok(Some(user))
+            }
+            // Dropping the transaction rolls back the user
+            Err(e) if is_unique_violation(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Find the credentials of a (possibly suspended) undeleted account by email
+    pub async fn find_for_login(
+        &self,
+        email: &str,
+    ) -> Result<Option<LocalCredential>, sqlx::Error> {
+        sqlx::query_as::<_, LocalCredential>(
+            r#"
+            SELECT u.id, u.clerk_user_id, u.email, u.name, u.avatar_url, u.tier, u.role,
+                   u.suspended_at, u.organization_id, u.created_at, u.updated_at, u.deleted_at,
+                   c.password_hash
+            FROM local_credentials c
+            JOIN users u ON u.id = c.user_id
+            WHERE c.email = $1 AND u.deleted_at IS NULL
+            "#,
+        )
+        .bind(email)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Delete a user's credentials (account deletion), freeing the email
+    ///
+    /// Returns false if the user had none (e.g. a Clerk account).
+    pub async fn delete_for_user(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM local_credentials WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -2,11 +2,70 @@
 //!
 //! Contains entities, repositories, and database utilities.
 
+use sqlx::PgPool;
+
+pub mod anchor_proof;
+pub mod api_key;
+pub mod audit;
+pub mod collection;
+pub mod credential;
+pub mod organization;
+pub mod retention;
 pub mod seal;
+pub mod share;
+pub mod signing_key;
+pub mod tag;
+pub mod tenant;
+pub mod upload;
 pub mod user;
+pub mod verification;
 
+pub use anchor_proof::AnchorProofRepository;
+pub use api_key::{ApiKey, ApiKeyRecord, ApiKeyRepository, ApiKeyScope, API_KEY_PREFIX};
+pub use audit::{AuditEvent, AuditLogRepository, ThirdPartyActivity, VerificationOutcome};
+pub use collection::{Collection, CollectionRepository, CollectionSeal};
+pub use credential::{LocalCredential, LocalCredentialRepository};
+pub use organization::{Branding, OrganizationSettings, OrganizationSettingsRepository};
+pub use retention::{RetentionPolicy, RetentionPolicyRepository, RETENTION_INTERVAL};
 pub use seal::{
-    CreateSeal, DeviceInfo, Seal, SealListParams, SealListResponse, SealLocation, SealMetadata,
-    SealRecord, SealRepository,
+    AnchorLatencyStats, CreateSeal, DailySealStats, DeviceInfo, LegalHoldChange, LocationBucket,
+    MediaErasure, QrngSourceStats, Seal, SealListParams, SealListResponse, SealLocation,
+    SealMetadata, SealRecord, SealRepository, SealTotals, SealUpsert, SealUsage, SyncedSeal,
+    TrustTierStats, STATS_REFRESH_INTERVAL,
+};
+pub use share::{SealShare, SealShareRecord, SealShareRepository};
+pub use signing_key::SigningKeyRepository;
+pub use tag::{SealTag, SealTagRepository, TagCount};
+pub use tenant::{Tenant, TenantConnection};
+pub use upload::{UploadSession, UploadSessionRepository};
+pub use user::{
+    CreateUser, TrustTier, UpdateUser, User, UserListParams, UserRepository, UserResponse,
+    UserRole, UserStats,
+};
+pub use verification::{
+    DailyVerifications, OriginCount, VerificationOrigin, VerificationRepository,
 };
-pub use user::{CreateUser, TrustTier, UpdateUser, User, UserRepository, UserResponse};
+
+/// Database connection pools: the primary, and an optional read replica for
+/// read-heavy endpoints (seal listings and exports, resolution)
+#[derive(Clone)]
+pub struct DatabasePools {
+    /// Primary database, for writes and reads that must see them
+    pub primary: PgPool,
+    /// Read replica (DATABASE_READ_URL), if configured
+    pub replica: Option<PgPool>,
+}
+
+impl DatabasePools {
+    /// Pool for read-heavy queries: the replica, or the primary without one
+    pub fn read(&self) -> &PgPool {
+        self.replica.as_ref().unwrap_or(&self.primary)
+    }
+}
+
+/// Whether a query failed on a unique constraint
+pub(crate) fn is_unique_violation(error: &sqlx::Error) -> bool {
+    error
+        .as_database_error()
+        .is_some_and(|e| e.is_unique_violation())
+}
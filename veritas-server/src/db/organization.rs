@@ -0,0 +1,170 @@
+//! Organization settings entity and repository
+//!
+//! Branding of the public verification endpoints per organization: display
+//! name, logo and custom verification domain, managed by administrators.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::is_unique_violation;
+
+/// Organization settings from database
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow, ToSchema)]
+pub struct OrganizationSettings {
+    /// Organization ID (`organization_id` of its seals)
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub organization_id: Uuid,
+
+    /// Name shown on verification pages
+    #[schema(example = "Agence France Presse")]
+    pub display_name: String,
+
+    /// HTTPS URL of the logo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "https://cdn.example.com/logo.svg")]
+    pub logo_url: Option<String>,
+
+    /// Host name serving the organization's verification pages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "verify.example.com")]
+    pub verification_domain: Option<String>,
+
+    /// Creation timestamp
+    #[schema(value_type = String, example = "2026-01-08T10:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    /// Last update timestamp
+    #[schema(value_type = String, example = "2026-01-08T10:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Branding returned by public verification endpoints
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Branding {
+    /// Name to show on the verification page
+    #[schema(example = "Agence France Presse")]
+    pub display_name: String,
+
+    /// HTTPS URL of the logo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "https://cdn.example.com/logo.svg")]
+    pub logo_url: Option<String>,
+
+    /// Custom verification domain of the organization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "verify.example.com")]
+    pub verification_domain: Option<String>,
+}
+
+impl From<OrganizationSettings> for Branding {
+    fn from(settings: OrganizationSettings) -> Self {
+        Self {
+            display_name: settings.display_name,
+            logo_url: settings.logo_url,
+            verification_domain: settings.verification_domain,
+        }
+    }
+}
+
+/// Repository for organization settings
+#[derive(Clone)]
+pub struct OrganizationSettingsRepository {
+    pool: PgPool,
+}
+
+impl OrganizationSettingsRepository {
+    /// Create a new organization settings repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Settings of an organization
+    pub async fn get(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<Option<OrganizationSettings>, sqlx::Error> {
+        sqlx::query_as::<_, OrganizationSettings>(
+            r#"
+            SELECT organization_id, display_name, logo_url, verification_domain,
+                   created_at, updated_at
+            FROM organization_settings
+            WHERE organization_id = $1
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Settings of the organization whose verification domain is `domain`
+    /// (lowercase host name)
+    pub async fn find_by_domain(
+        &self,
+        domain: &str,
+    ) -> Result<Option<OrganizationSettings>, sqlx::Error> {
+        sqlx::query_as::<_, OrganizationSettings>(
+            r#"
+            SELECT organization_id, display_name, logo_url, verification_domain,
+                   created_at, updated_at
+            FROM organization_settings
+            WHERE verification_domain = $1
+            "#,
+        )
+        .bind(domain)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Create or replace an organization's settings
+    ///
+    /// Returns `None` if another organization already uses the domain.
+    pub async fn upsert(
+        &self,
+        organization_id: Uuid,
+        display_name: &str,
+        logo_url: Option<&str>,
+        verification_domain: Option<&str>,
+    ) -> Result<Option<OrganizationSettings>, sqlx::Error> {
+        let settings = sqlx::query_as::<_, OrganizationSettings>(
+            r#"
+            INSERT INTO organization_settings
+                (organization_id, display_name, logo_url, verification_domain)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (organization_id) DO UPDATE
+            SET display_name = EXCLUDED.display_name,
+                logo_url = EXCLUDED.logo_url,
+                verification_domain = EXCLUDED.verification_domain,
+                updated_at = NOW()
+            RETURNING organization_id, display_name, logo_url, verification_domain,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(organization_id)
+        .bind(display_name)
+        .bind(logo_url)
+        .bind(verification_domain)
+        .fetch_one(&self.pool)
+        .await;
+
+        match settings {
+            Ok(settings) => Ok(Some(settings)),
+            Err(e) if is_unique_violation(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Delete an organization's settings
+    ///
+    /// Returns false if it had none.
+    pub async fn delete(&self, organization_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM organization_settings WHERE organization_id = $1")
+            .bind(organization_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,244 @@
+//! Retention policy entity and repository
+//!
+//! Per organization, how many days after sealing its seals' media is deleted
+//! and its seals themselves are, managed by administrators. The retention
+//! janitor task calls [`RetentionPolicyRepository::enforce`] every
+//! [`RETENTION_INTERVAL`]; each seal it affects gets a `media_expired` or
+//! `seal_expired` audit entry, with the seal in `details` so the entry
+//! outlives the seal. Seals under legal hold are skipped, media included,
+//! until the hold is released.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{AuditEvent, TenantConnection};
+
+/// How often retention policies are enforced
+pub const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Seals expired per statement, so a new policy on a large organization
+/// does not hold locks on all its seals at once
+pub const RETENTION_BATCH_SIZE: i64 = 500;
+
+/// Retention policy of an organization
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow, ToSchema)]
+pub struct RetentionPolicy {
+    /// Organization ID (`organization_id` of its seals)
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub organization_id: Uuid,
+
+    /// Days after sealing before the media is deleted (absent = kept)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 90)]
+    pub media_retention_days: Option<i32>,
+
+    /// Days after sealing before the seal is deleted (absent = kept)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 2557)]
+    pub seal_retention_days: Option<i32>,
+
+    /// Creation timestamp
+    #[schema(value_type = String, example = "2026-01-08T10:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    /// Last update timestamp
+    #[schema(value_type = String, example = "2026-01-08T10:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Repository for retention policies
+#[derive(Clone)]
+pub struct RetentionPolicyRepository {
+    pool: PgPool,
+    tenant_rls: bool,
+}
+
+impl RetentionPolicyRepository {
+    /// Create a new retention policy repository
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Expire seals as a system path bypassing row-level security
+    /// (`TENANT_RLS`)
+    pub fn with_tenant_rls(mut self, tenant_rls: bool) -> Self {
+        self.tenant_rls = tenant_rls;
+        self
+    }
+
+    /// Retention policy of an organization
+    pub async fn get(&self, organization_id: Uuid) -> Result<Option<RetentionPolicy>, sqlx::Error> {
+        sqlx::query_as::<_, RetentionPolicy>(
+            r#"
+            SELECT organization_id, media_retention_days, seal_retention_days,
+                   created_at, updated_at
+            FROM retention_policies
+            WHERE organization_id = $1
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Create or replace an organization's retention policy
+    pub async fn upsert(
+        &self,
+        organization_id: Uuid,
+        media_retention_days: Option<i32>,
+        seal_retention_days: Option<i32>,
+    ) -> Result<RetentionPolicy, sqlx::Error> {
+        sqlx::query_as::<_, RetentionPolicy>(
+            r#"
+            INSERT INTO retention_policies
+                (organization_id, media_retention_days, seal_retention_days)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (organization_id) DO UPDATE
+            SET media_retention_days = EXCLUDED.media_retention_days,
+                seal_retention_days = EXCLUDED.seal_retention_days,
+                updated_at = NOW()
+            RETURNING organization_id, media_retention_days, seal_retention_days,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(organization_id)
+        .bind(media_retention_days)
+        .bind(seal_retention_days)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Delete an organization's retention policy, keeping its data for good
+    ///
+    /// Returns false if it had none.
+    pub async fn delete(&self, organization_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM retention_policies WHERE organization_id = $1")
+            .bind(organization_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete every seal and media past its organization's retention
+    ///
+    /// Seals go first, so media about to be deleted with its seal is not
+    /// audited twice. Returns how many seals were affected.
+    pub async fn enforce(&self) -> Result<usize, sqlx::Error> {
+        let mut processed = 0;
+        loop {
+            let expired = self.expire_seals(RETENTION_BATCH_SIZE).await?;
+            processed += expired;
+            if expired < RETENTION_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+        loop {
+            let expired = self.expire_media(RETENTION_BATCH_SIZE).await?;
+            processed += expired;
+            if expired < RETENTION_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+        Ok(processed as usize)
+    }
+
+    /// Delete up to `limit` seals past their organization's seal retention,
+    /// with their manifests, auditing each one
+    ///
+    /// Tags, shares, verification counts and collection entries go with the
+    /// seal. Returns how many seals were deleted.
+    pub async fn expire_seals(&self, limit: i64) -> Result<u64, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let result = sqlx::query(
+            r#"
+            WITH expired AS (
+                DELETE FROM seals
+                WHERE id IN (
+                    SELECT s.id
+                    FROM seals s
+                    JOIN retention_policies p ON p.organization_id = s.organization_id
+                    WHERE p.seal_retention_days IS NOT NULL
+                      AND NOT s.legal_hold
+                      AND s.created_at < NOW() - make_interval(days => p.seal_retention_days)
+                    LIMIT $1
+                    FOR UPDATE OF s SKIP LOCKED
+                )
+                RETURNING id, organization_id, user_id, created_at
+            ),
+            expired_manifests AS (
+                DELETE FROM manifests m
+                USING expired e
+                WHERE m.seal_id = e.id::TEXT
+            )
+            INSERT INTO audit_log (event, details)
+            SELECT $2, jsonb_build_object(
+                'seal_id', id,
+                'organization_id', organization_id,
+                'user_id', user_id,
+                'sealed_at', created_at
+            )
+            FROM expired
+            "#,
+        )
+        .bind(limit)
+        .bind(AuditEvent::SealExpired.as_str())
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Mark deleted the media of up to `limit` seals past their
+    /// organization's media retention, auditing each one
+    ///
+    /// The seal and its hashes are kept, so the media can still be verified
+    /// against it. Returns how many seals lost their media.
+    pub async fn expire_media(&self, limit: i64) -> Result<u64, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let result = sqlx::query(
+            r#"
+            WITH expired AS (
+                UPDATE seals
+                SET media_deleted_at = NOW()
+                WHERE id IN (
+                    SELECT s.id
+                    FROM seals s
+                    JOIN retention_policies p ON p.organization_id = s.organization_id
+                    WHERE p.media_retention_days IS NOT NULL
+                      AND s.media_deleted_at IS NULL
+                      AND NOT s.legal_hold
+                      AND s.created_at < NOW() - make_interval(days => p.media_retention_days)
+                    LIMIT $1
+                    FOR UPDATE OF s SKIP LOCKED
+                )
+                RETURNING id, organization_id, user_id, created_at
+            )
+            INSERT INTO audit_log (event, details)
+            SELECT $2, jsonb_build_object(
+                'seal_id', id,
+                'organization_id', organization_id,
+                'user_id', user_id,
+                'sealed_at', created_at
+            )
+            FROM expired
+            "#,
+        )
+        .bind(limit)
+        .bind(AuditEvent::MediaExpired.as_str())
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,14 +1,23 @@
 //! Seal entity and repository
 //!
 //! Handles quantum-authenticated seal records linked to users.
+//!
+//! Platform and usage statistics read pre-aggregated materialized views
+//! (`seal_stats_daily`, `user_seal_stats_daily`, `anchor_stats_daily`) that
+//! cover complete UTC days up to their `covered_until` column, and count only
+//! what was created since from the base tables. [`SealRepository::refresh_stats`]
+//! brings them up to date every [`STATS_REFRESH_INTERVAL`].
+
+use std::time::Duration;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use super::TrustTier;
+use super::{AuditEvent, Tenant, TenantConnection, TrustTier};
+use crate::geohash::GeohashGrid;
 
 /// Seal entity from database
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -32,6 +41,10 @@ pub struct Seal {
     pub captured_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub media_deleted_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revocation_reason: Option<String>,
+    pub verification_count: i64,
+    pub legal_hold: bool,
 }
 
 /// DTO for creating a new seal
@@ -84,6 +97,10 @@ pub struct SealMetadata {
     /// Whether device attestation was included
     #[schema(example = true)]
     pub has_device_attestation: bool,
+
+    /// Why the seal received its trust tier (see `TrustPolicy`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trust_rationale: Vec<String>,
 }
 
 /// GPS location data
@@ -151,6 +168,23 @@ pub struct SealRecord {
 
     /// Whether media has been deleted (GDPR)
     pub media_deleted: bool,
+
+    /// When an administrator revoked the seal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2026-01-09T10:00:00Z")]
+    pub revoked_at: Option<DateTime<Utc>>,
+
+    /// Why the seal was revoked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation_reason: Option<String>,
+
+    /// Verifications of the seal by anyone but its owner
+    #[schema(example = 42)]
+    pub verification_count: i64,
+
+    /// Whether the seal is kept as evidence: exempt from retention deletion
+    /// and media erasure
+    pub legal_hold: bool,
 }
 
 impl From<Seal> for SealRecord {
@@ -167,6 +201,10 @@ impl From<Seal> for SealRecord {
             captured_at: seal.captured_at,
             created_at: seal.created_at,
             media_deleted: seal.media_deleted_at.is_some(),
+            revoked_at: seal.revoked_at,
+            revocation_reason: seal.revocation_reason,
+            verification_count: seal.verification_count,
+            legal_hold: seal.legal_hold,
         }
     }
 }
@@ -189,6 +227,15 @@ pub struct SealListParams {
     /// Filter by seals with GPS location
     #[serde(default)]
     pub has_location: Option<bool>,
+
+    /// Filter by seals carrying this tag (lowercase)
+    #[serde(default)]
+    pub tag: Option<String>,
+
+    /// Filter by seals in this collection
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub collection_id: Option<Uuid>,
 }
 
 fn default_page() -> i64 {
@@ -209,21 +256,190 @@ pub struct SealListResponse {
     pub has_more: bool,
 }
 
+/// Outcome of [`SealRepository::upsert_with_id`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SealUpsert {
+    /// The seal was stored under the requested ID
+    Created,
+    /// The same seal of the same user is already stored under that ID
+    Unchanged,
+    /// Another seal, or another user's, is stored under that ID
+    Conflict,
+}
+
+/// A seal with the time it last changed (sync pulls)
+#[derive(Debug, Clone, FromRow)]
+pub struct SyncedSeal {
+    #[sqlx(flatten)]
+    pub seal: Seal,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A seal with its legal hold before the change (legal hold updates)
+#[derive(Debug, Clone, FromRow)]
+pub struct LegalHoldChange {
+    #[sqlx(flatten)]
+    pub seal: Seal,
+    pub was_held: bool,
+}
+
+/// Outcome of a request to erase a seal's media
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaErasure {
+    /// The media is now marked deleted
+    Erased,
+    /// The media was already deleted
+    AlreadyErased,
+    /// The seal is under legal hold, its media is kept
+    LegalHold,
+    /// No such seal for the tenant
+    NotFound,
+}
+
+/// A user's located seals in one cell of a [`GeohashGrid`]
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct LocationBucket {
+    /// Column of the cell
+    pub x: i64,
+    /// Row of the cell
+    pub y: i64,
+    /// Seals captured in the cell
+    pub seals: i64,
+    /// Mean latitude of the seals
+    pub lat: f64,
+    /// Mean longitude of the seals
+    pub lng: f64,
+}
+
+/// Seals created and failed attempts on one day (UTC)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct DailySealStats {
+    /// Day (UTC)
+    #[schema(value_type = String, example = "2026-01-08")]
+    pub day: NaiveDate,
+    /// Seals created
+    pub seals: i64,
+    /// Failed seal attempts
+    pub failures: i64,
+}
+
+/// Seals created with one QRNG source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct QrngSourceStats {
+    /// QRNG source name
+    #[schema(example = "lfd")]
+    pub source: String,
+    /// Seals created
+    pub seals: i64,
+}
+
+/// Seals created with one trust tier
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct TrustTierStats {
+    /// Trust tier of the seals
+    #[sqlx(try_from = "i16")]
+    pub tier: TrustTier,
+    /// Seals created
+    pub seals: i64,
+}
+
+/// Batched anchors on one chain and how long seals waited for them
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow, ToSchema)]
+pub struct AnchorLatencyStats {
+    /// Chain identifier
+    #[schema(example = "bitcoin")]
+    pub chain: String,
+    /// Seals anchored
+    pub anchors: i64,
+    /// Mean delay between queueing a seal and anchoring it, in seconds
+    #[schema(example = 612.5)]
+    pub average_latency_secs: Option<f64>,
+    /// Longest delay between queueing a seal and anchoring it, in seconds
+    pub max_latency_secs: Option<f64>,
+}
+
+/// Platform-wide seal counts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct SealTotals {
+    /// All seals
+    pub total: i64,
+    /// Revoked seals
+    pub revoked: i64,
+    /// Seals created in the last 24 hours
+    pub last_24h: i64,
+    /// Failed seal attempts in the last 24 hours
+    pub failures_last_24h: i64,
+}
+
+/// A user's seal counts and sizes (for the account dashboard)
+#[derive(Debug, Clone, Default, PartialEq, Eq, FromRow)]
+pub struct SealUsage {
+    pub total: i64,
+    pub last_24h: i64,
+    pub last_7_days: i64,
+    pub last_30_days: i64,
+    /// Seals created since the start of the calendar month (quota period)
+    pub this_month: i64,
+    /// Total size of all sealed files
+    pub sealed_bytes: i64,
+    /// Total size of sealed files whose media has not been deleted
+    pub media_bytes: i64,
+}
+
+/// How often the statistics views are refreshed
+pub const STATS_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Materialized views refreshed by [`SealRepository::refresh_stats`]
+const STATS_VIEWS: [&str; 3] = [
+    "seal_stats_daily",
+    "user_seal_stats_daily",
+    "anchor_stats_daily",
+];
+
 /// Repository for seal database operations
+///
+/// Seal listings, detail/export lookups and owner resolution read from
+/// `read_pool` (a replica when configured); everything else uses the primary.
+/// Queries for a [`Tenant`] only see its seals; the others (public lookups,
+/// administration, background tasks) run as system paths.
 #[derive(Clone)]
 pub struct SealRepository {
     pool: PgPool,
+    read_pool: PgPool,
+    tenant_rls: bool,
 }
 
 impl SealRepository {
     /// Create a new seal repository
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            read_pool: pool.clone(),
+            pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Create a seal repository writing to `pool` and serving read-heavy
+    /// queries from `read_pool`
+    pub fn with_read_pool(pool: PgPool, read_pool: PgPool) -> Self {
+        Self {
+            pool,
+            read_pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Run tenant queries under row-level security, and the others as
+    /// system paths bypassing it (`TENANT_RLS`)
+    pub fn with_tenant_rls(mut self, tenant_rls: bool) -> Self {
+        self.tenant_rls = tenant_rls;
+        self
     }
 
     /// Create a new seal
     pub async fn create(&self, input: CreateSeal) -> Result<SealCreated, sqlx::Error> {
-        sqlx::query_as::<_, SealCreated>(
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let created = sqlx::query_as::<_, SealCreated>(
             r#"
             INSERT INTO seals (
                 user_id, organization_id, content_hash, perceptual_hash,
@@ -250,46 +466,147 @@ impl SealRepository {
         .bind(i16::from(input.trust_tier))
         .bind(input.c2pa_manifest_embedded)
         .bind(input.captured_at)
-        .fetch_one(&self.pool)
-        .await
+        .fetch_one(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(created)
+    }
+
+    /// Create a seal under an ID chosen by the client (offline sync)
+    ///
+    /// Idempotent: pushing the same seal again under the same ID changes
+    /// nothing and returns [`SealUpsert::Unchanged`].
+    pub async fn upsert_with_id(
+        &self,
+        id: Uuid,
+        input: CreateSeal,
+    ) -> Result<SealUpsert, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO seals (
+                id, user_id, organization_id, content_hash, perceptual_hash,
+                qrng_entropy, qrng_source, signature, public_key,
+                media_type, file_size, mime_type, metadata,
+                trust_tier, c2pa_manifest_embedded, captured_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(input.user_id)
+        .bind(input.organization_id)
+        .bind(&input.content_hash)
+        .bind(&input.perceptual_hash)
+        .bind(&input.qrng_entropy)
+        .bind(&input.qrng_source)
+        .bind(&input.signature)
+        .bind(&input.public_key)
+        .bind(&input.media_type)
+        .bind(input.file_size)
+        .bind(&input.mime_type)
+        .bind(&input.metadata)
+        .bind(i16::from(input.trust_tier))
+        .bind(input.c2pa_manifest_embedded)
+        .bind(input.captured_at)
+        .execute(&mut *conn)
+        .await?;
+        if result.rows_affected() > 0 {
+            conn.finish().await?;
+            return Ok(SealUpsert::Created);
+        }
+
+        let same: Option<bool> = sqlx::query_scalar(
+            r#"
+            SELECT user_id IS NOT DISTINCT FROM $2 AND signature = $3
+            FROM seals
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(input.user_id)
+        .bind(&input.signature)
+        .fetch_optional(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(match same {
+            Some(true) => SealUpsert::Unchanged,
+            _ => SealUpsert::Conflict,
+        })
     }
 
     /// Find seal by ID
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Seal>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
         sqlx::query_as::<_, Seal>(
             r#"
             SELECT id, user_id, organization_id, content_hash, perceptual_hash,
                    qrng_entropy, qrng_source, signature, public_key,
                    media_type, file_size, mime_type, metadata,
-                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at
+                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                   revoked_at, revocation_reason, verification_count, legal_hold
             FROM seals
             WHERE id = $1
             "#,
         )
         .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await
     }
 
-    /// Find seal by ID, restricted to a specific user
+    /// Find seal by ID, restricted to a tenant
     pub async fn find_by_id_for_user(
         &self,
         id: Uuid,
-        user_id: Uuid,
+        tenant: &Tenant,
     ) -> Result<Option<Seal>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.read_pool, tenant, self.tenant_rls).await?;
         sqlx::query_as::<_, Seal>(
             r#"
             SELECT id, user_id, organization_id, content_hash, perceptual_hash,
                    qrng_entropy, qrng_source, signature, public_key,
                    media_type, file_size, mime_type, metadata,
-                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at
+                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                   revoked_at, revocation_reason, verification_count, legal_hold
             FROM seals
-            WHERE id = $1 AND user_id = $2
+            WHERE id = $1 AND user_id = $2 AND (organization_id IS NULL OR organization_id = $3)
             "#,
         )
         .bind(id)
-        .bind(user_id)
-        .fetch_optional(&self.pool)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_optional(&mut *conn)
+        .await
+    }
+
+    /// Find seals by ID, restricted to a tenant (oldest first); IDs of
+    /// missing or other tenants' seals are skipped
+    pub async fn find_many_for_user(
+        &self,
+        ids: &[Uuid],
+        tenant: &Tenant,
+    ) -> Result<Vec<Seal>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.read_pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, Seal>(
+            r#"
+            SELECT id, user_id, organization_id, content_hash, perceptual_hash,
+                   qrng_entropy, qrng_source, signature, public_key,
+                   media_type, file_size, mime_type, metadata,
+                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                   revoked_at, revocation_reason, verification_count, legal_hold
+            FROM seals
+            WHERE id = ANY($1) AND user_id = $2
+              AND (organization_id IS NULL OR organization_id = $3)
+            ORDER BY created_at
+            "#,
+        )
+        .bind(ids)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_all(&mut *conn)
         .await
     }
 
@@ -298,33 +615,38 @@ impl SealRepository {
         &self,
         content_hash: &str,
     ) -> Result<Option<Seal>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
         sqlx::query_as::<_, Seal>(
             r#"
             SELECT id, user_id, organization_id, content_hash, perceptual_hash,
                    qrng_entropy, qrng_source, signature, public_key,
                    media_type, file_size, mime_type, metadata,
-                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at
+                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                   revoked_at, revocation_reason, verification_count, legal_hold
             FROM seals
             WHERE content_hash = $1
             "#,
         )
         .bind(content_hash)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await
     }
 
-    /// List seals for a user with pagination
+    /// List a tenant's seals with pagination
     pub async fn list_for_user(
         &self,
-        user_id: Uuid,
+        tenant: &Tenant,
         params: &SealListParams,
     ) -> Result<SealListResponse, sqlx::Error> {
         let limit = params.limit.min(100);
         let offset = (params.page - 1).max(0) * limit;
 
         // Build WHERE clause based on filters
-        let mut where_conditions = vec!["user_id = $1".to_string()];
-        let mut bind_idx = 2;
+        let mut where_conditions = vec![
+            "user_id = $1".to_string(),
+            "(organization_id IS NULL OR organization_id = $2)".to_string(),
+        ];
+        let mut bind_idx = 3;
 
         if params.media_type.is_some() {
             where_conditions.push(format!("media_type = ${}", bind_idx));
@@ -339,6 +661,23 @@ impl SealRepository {
             }
         }
 
+        if params.tag.is_some() {
+            where_conditions.push(format!(
+                "EXISTS (SELECT 1 FROM seal_tags t WHERE t.seal_id = seals.id AND t.tag = ${})",
+                bind_idx
+            ));
+            bind_idx += 1;
+        }
+
+        if params.collection_id.is_some() {
+            where_conditions.push(format!(
+                "EXISTS (SELECT 1 FROM collection_seals c \
+                 WHERE c.seal_id = seals.id AND c.collection_id = ${})",
+                bind_idx
+            ));
+            bind_idx += 1;
+        }
+
         let where_clause = where_conditions.join(" AND ");
 
         // Build queries
@@ -347,7 +686,8 @@ impl SealRepository {
             SELECT id, user_id, organization_id, content_hash, perceptual_hash,
                    qrng_entropy, qrng_source, signature, public_key,
                    media_type, file_size, mime_type, metadata,
-                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at
+                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                   revoked_at, revocation_reason, verification_count, legal_hold
             FROM seals
             WHERE {}
             ORDER BY created_at DESC
@@ -367,18 +707,33 @@ impl SealRepository {
         );
 
         // Execute queries with bindings
-        let mut seals_query = sqlx::query_as::<_, Seal>(&select_query).bind(user_id);
-        let mut count_query = sqlx::query_as::<_, (i64,)>(&count_query).bind(user_id);
+        let mut seals_query = sqlx::query_as::<_, Seal>(&select_query)
+            .bind(tenant.user_id)
+            .bind(tenant.organization_id);
+        let mut count_query = sqlx::query_as::<_, (i64,)>(&count_query)
+            .bind(tenant.user_id)
+            .bind(tenant.organization_id);
 
         if let Some(ref media_type) = params.media_type {
             seals_query = seals_query.bind(media_type);
             count_query = count_query.bind(media_type);
         }
 
+        if let Some(ref tag) = params.tag {
+            seals_query = seals_query.bind(tag);
+            count_query = count_query.bind(tag);
+        }
+
+        if let Some(collection_id) = params.collection_id {
+            seals_query = seals_query.bind(collection_id);
+            count_query = count_query.bind(collection_id);
+        }
+
         seals_query = seals_query.bind(limit).bind(offset);
 
-        let seals = seals_query.fetch_all(&self.pool).await?;
-        let total = count_query.fetch_one(&self.pool).await?.0;
+        let mut conn = TenantConnection::acquire(&self.read_pool, tenant, self.tenant_rls).await?;
+        let seals = seals_query.fetch_all(&mut *conn).await?;
+        let total = count_query.fetch_one(&mut *conn).await?.0;
 
         let records: Vec<SealRecord> = seals.into_iter().map(SealRecord::from).collect();
         let has_more = offset + (records.len() as i64) < total;
@@ -392,22 +747,135 @@ impl SealRepository {
         })
     }
 
-    /// Count seals for a user (for usage tracking)
+    /// All seals of a tenant, oldest first (personal data exports, with a
+    /// [`Tenant::personal`] scope)
+    pub async fn all_for_user(&self, tenant: &Tenant) -> Result<Vec<Seal>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.read_pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, Seal>(
+            r#"
+            SELECT id, user_id, organization_id, content_hash, perceptual_hash,
+                   qrng_entropy, qrng_source, signature, public_key,
+                   media_type, file_size, mime_type, metadata,
+                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                   revoked_at, revocation_reason, verification_count, legal_hold
+            FROM seals
+            WHERE user_id = $1 AND (organization_id IS NULL OR organization_id = $2 OR $3)
+            ORDER BY created_at
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .bind(tenant.all_organizations)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// A tenant's seals changed after `since` (an `updated_at` and seal ID,
+    /// exclusive), in change order, up to `limit` (sync pulls)
+    ///
+    /// Changes of the last `settle_secs` seconds are left for the next pull:
+    /// a transaction that started earlier may still commit an older
+    /// `updated_at`, which a cursor past it would skip.
+    pub async fn changed_since(
+        &self,
+        tenant: &Tenant,
+        since: Option<(DateTime<Utc>, Uuid)>,
+        settle_secs: i64,
+        limit: i64,
+    ) -> Result<Vec<SyncedSeal>, sqlx::Error> {
+        let (since_at, since_id) = since.unzip();
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, SyncedSeal>(
+            r#"
+            SELECT id, user_id, organization_id, content_hash, perceptual_hash,
+                   qrng_entropy, qrng_source, signature, public_key,
+                   media_type, file_size, mime_type, metadata,
+                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                   revoked_at, revocation_reason, verification_count, legal_hold, updated_at
+            FROM seals
+            WHERE user_id = $1 AND (organization_id IS NULL OR organization_id = $6)
+              AND ($2::TIMESTAMPTZ IS NULL OR (updated_at, id) > ($2, $3))
+              AND updated_at < NOW() - make_interval(secs => $4)
+            ORDER BY updated_at, id
+            LIMIT $5
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(since_at)
+        .bind(since_id)
+        .bind(settle_secs as f64)
+        .bind(limit)
+        .bind(tenant.organization_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Count a tenant's seals with a GPS location per cell of `grid`, largest
+    /// buckets first (seal map); the grouping runs in the database, so only
+    /// one row per cell is transferred
+    pub async fn location_buckets(
+        &self,
+        tenant: &Tenant,
+        grid: &GeohashGrid,
+        media_type: Option<&str>,
+    ) -> Result<Vec<LocationBucket>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.read_pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, LocationBucket>(
+            r#"
+            WITH located AS (
+                SELECT (metadata->'location'->>'lat')::FLOAT8 AS lat,
+                       (metadata->'location'->>'lng')::FLOAT8 AS lng
+                FROM seals
+                WHERE user_id = $1 AND (organization_id IS NULL OR organization_id = $7)
+                AND ($2::TEXT IS NULL OR media_type = $2)
+                AND jsonb_typeof(metadata->'location'->'lat') = 'number'
+                AND jsonb_typeof(metadata->'location'->'lng') = 'number'
+            )
+            SELECT LEAST(FLOOR((lng + 180) / $3), $5)::BIGINT AS x,
+                   LEAST(FLOOR((lat + 90) / $4), $6)::BIGINT AS y,
+                   COUNT(*) AS seals,
+                   AVG(lat) AS lat,
+                   AVG(lng) AS lng
+            FROM located
+            WHERE lat BETWEEN -90 AND 90 AND lng BETWEEN -180 AND 180
+            GROUP BY 1, 2
+            ORDER BY seals DESC, x, y
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(media_type)
+        .bind(grid.cell_width())
+        .bind(grid.cell_height())
+        .bind((grid.columns() - 1) as f64)
+        .bind((grid.rows() - 1) as f64)
+        .bind(tenant.organization_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Count the seals of an account (for usage tracking)
+    ///
+    /// Quotas are per account: seals made in any organization count.
     pub async fn count_for_user(&self, user_id: Uuid) -> Result<i64, sqlx::Error> {
+        let tenant = Tenant::personal(user_id);
+        let mut conn = TenantConnection::acquire(&self.pool, &tenant, self.tenant_rls).await?;
         let result: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(*) FROM seals WHERE user_id = $1
             "#,
         )
         .bind(user_id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(result.0)
     }
 
-    /// Count seals created this month for a user (for plan limits)
+    /// Count seals created this month by an account (for plan limits, which
+    /// are per account)
     pub async fn count_for_user_this_month(&self, user_id: Uuid) -> Result<i64, sqlx::Error> {
+        let tenant = Tenant::personal(user_id);
+        let mut conn = TenantConnection::acquire(&self.pool, &tenant, self.tenant_rls).await?;
         let result: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(*) FROM seals
@@ -416,27 +884,546 @@ impl SealRepository {
             "#,
         )
         .bind(user_id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(result.0)
     }
 
-    /// Mark media as deleted (GDPR compliance)
-    pub async fn delete_media(&self, id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query(
+    /// Seal counts by period and sealed file sizes of an account (all
+    /// organizations, like quotas)
+    ///
+    /// Totals come from `user_seal_stats_daily` plus the seals created since
+    /// it was refreshed, so media deleted in between is still counted in
+    /// `media_bytes` until the next refresh. Period counts only read the
+    /// last month of the user's seals.
+    pub async fn usage_for_user(&self, user_id: Uuid) -> Result<SealUsage, sqlx::Error> {
+        let tenant = Tenant::personal(user_id);
+        let mut conn = TenantConnection::acquire(&self.pool, &tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, SealUsage>(
+            r#"
+            WITH coverage AS (
+                SELECT COALESCE(MAX(covered_until), '-infinity') AS covered_until
+                FROM user_seal_stats_daily
+            ),
+            totals AS (
+                SELECT SUM(seals) AS seals,
+                       SUM(sealed_bytes) AS sealed_bytes,
+                       SUM(media_bytes) AS media_bytes
+                FROM (
+                    SELECT seals, sealed_bytes, media_bytes
+                    FROM user_seal_stats_daily
+                    WHERE user_id = $1
+                    UNION ALL
+                    SELECT COUNT(*),
+                           COALESCE(SUM(file_size), 0),
+                           COALESCE(SUM(file_size) FILTER (WHERE media_deleted_at IS NULL), 0)
+                    FROM seals, coverage
+                    WHERE user_id = $1 AND created_at >= coverage.covered_until
+                ) counts
+            ),
+            periods AS (
+                SELECT COUNT(*) FILTER (WHERE created_at > NOW() - INTERVAL '1 day') AS last_24h,
+                       COUNT(*) FILTER (WHERE created_at > NOW() - INTERVAL '7 days')
+                           AS last_7_days,
+                       COUNT(*) FILTER (WHERE created_at > NOW() - INTERVAL '30 days')
+                           AS last_30_days,
+                       COUNT(*) FILTER (WHERE created_at >= date_trunc('month', CURRENT_TIMESTAMP))
+                           AS this_month
+                FROM seals
+                WHERE user_id = $1
+                AND created_at >= LEAST(
+                    NOW() - INTERVAL '30 days',
+                    date_trunc('month', CURRENT_TIMESTAMP)
+                )
+            )
+            SELECT COALESCE(totals.seals, 0)::BIGINT AS total,
+                   periods.last_24h,
+                   periods.last_7_days,
+                   periods.last_30_days,
+                   periods.this_month,
+                   COALESCE(totals.sealed_bytes, 0)::BIGINT AS sealed_bytes,
+                   COALESCE(totals.media_bytes, 0)::BIGINT AS media_bytes
+            FROM totals, periods
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    /// Find the ID and owner of the stored seal with this content hash and
+    /// signature
+    pub async fn find_owner_by_signature(
+        &self,
+        content_hash: &str,
+        signature: &[u8],
+    ) -> Result<Option<(Uuid, Option<Uuid>)>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        sqlx::query_as(
+            r#"
+            SELECT id, user_id FROM seals
+            WHERE content_hash = $1 AND signature = $2
+            LIMIT 1
+            "#,
+        )
+        .bind(content_hash)
+        .bind(signature)
+        .fetch_optional(&mut *conn)
+        .await
+    }
+
+    /// Find the owners of the given seals (IDs not found are left out)
+    pub async fn find_owners(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Option<Uuid>)>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.read_pool, self.tenant_rls).await?;
+        sqlx::query_as("SELECT id, user_id FROM seals WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(&mut *conn)
+            .await
+    }
+
+    /// Mark media of a tenant's seal as deleted (GDPR compliance)
+    ///
+    /// Media of seals under legal hold is kept: callers must tell the user
+    /// why their erasure request was not carried out.
+    pub async fn delete_media(
+        &self,
+        id: Uuid,
+        tenant: &Tenant,
+    ) -> Result<MediaErasure, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let outcome: Option<(bool, bool, bool)> = sqlx::query_as(
+            r#"
+            WITH target AS (
+                SELECT id, legal_hold, media_deleted_at IS NOT NULL AS already_erased
+                FROM seals
+                WHERE id = $1 AND user_id = $2
+                  AND (organization_id IS NULL OR organization_id = $3)
+                FOR UPDATE
+            ),
+            erased AS (
+                UPDATE seals s
+                SET media_deleted_at = NOW()
+                FROM target t
+                WHERE s.id = t.id AND NOT t.legal_hold AND NOT t.already_erased
+                RETURNING s.id
+            )
+            SELECT legal_hold, already_erased, EXISTS (SELECT 1 FROM erased) FROM target
+            "#,
+        )
+        .bind(id)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(match outcome {
+            None => MediaErasure::NotFound,
+            Some((_, _, true)) => MediaErasure::Erased,
+            Some((true, false, false)) => MediaErasure::LegalHold,
+            Some(_) => MediaErasure::AlreadyErased,
+        })
+    }
+
+    /// Place a seal under legal hold or release it (admin operation)
+    ///
+    /// A change is audited in the same statement (`legal_hold_placed` or
+    /// `legal_hold_released`, by `admin_id`, with the reason), the seal kept
+    /// in `details` rather than `seal_id` so the entry outlives it. Returns the
+    /// seal with whether it was held before, `None` if it does not exist.
+    pub async fn set_legal_hold(
+        &self,
+        id: Uuid,
+        legal_hold: bool,
+        admin_id: Uuid,
+        reason: Option<&str>,
+    ) -> Result<Option<LegalHoldChange>, sqlx::Error> {
+        let event = if legal_hold {
+            AuditEvent::LegalHoldPlaced
+        } else {
+            AuditEvent::LegalHoldReleased
+        };
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let change = sqlx::query_as::<_, LegalHoldChange>(
+            r#"
+            WITH previous AS (
+                SELECT id, legal_hold FROM seals WHERE id = $1 FOR UPDATE
+            ),
+            updated AS (
+                UPDATE seals s
+                SET legal_hold = $2
+                FROM previous p
+                WHERE s.id = p.id
+                RETURNING s.id, s.user_id, s.organization_id, s.content_hash, s.perceptual_hash,
+                          s.qrng_entropy, s.qrng_source, s.signature, s.public_key,
+                          s.media_type, s.file_size, s.mime_type, s.metadata,
+                          s.trust_tier, s.c2pa_manifest_embedded, s.captured_at, s.created_at,
+                          s.media_deleted_at, s.revoked_at, s.revocation_reason,
+                          s.verification_count, s.legal_hold, p.legal_hold AS was_held
+            ),
+            audited AS (
+                INSERT INTO audit_log (event, actor_user_id, details)
+                SELECT $3, $4, jsonb_strip_nulls(jsonb_build_object(
+                    'seal_id', id,
+                    'organization_id', organization_id,
+                    'user_id', user_id,
+                    'reason', $5::TEXT
+                ))
+                FROM updated
+                WHERE was_held <> $2
+            )
+            SELECT * FROM updated
+            "#,
+        )
+        .bind(id)
+        .bind(legal_hold)
+        .bind(event.as_str())
+        .bind(admin_id)
+        .bind(reason)
+        .fetch_optional(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(change)
+    }
+
+    /// Revoke a seal (admin operation)
+    ///
+    /// Revoking an already revoked seal keeps the original timestamp but
+    /// updates the reason.
+    pub async fn revoke(&self, id: Uuid, reason: &str) -> Result<Option<Seal>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let seal = sqlx::query_as::<_, Seal>(
             r#"
             UPDATE seals
-            SET media_deleted_at = NOW()
-            WHERE id = $1 AND user_id = $2 AND media_deleted_at IS NULL
+            SET revoked_at = COALESCE(revoked_at, NOW()), revocation_reason = $2
+            WHERE id = $1
+            RETURNING id, user_id, organization_id, content_hash, perceptual_hash,
+                      qrng_entropy, qrng_source, signature, public_key,
+                      media_type, file_size, mime_type, metadata,
+                      trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                      revoked_at, revocation_reason, verification_count, legal_hold
             "#,
         )
         .bind(id)
-        .bind(user_id)
-        .execute(&self.pool)
+        .bind(reason)
+        .fetch_optional(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(seal)
+    }
+
+    /// Lift the revocation of a seal (admin operation)
+    pub async fn reinstate(&self, id: Uuid) -> Result<Option<Seal>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let seal = sqlx::query_as::<_, Seal>(
+            r#"
+            UPDATE seals
+            SET revoked_at = NULL, revocation_reason = NULL
+            WHERE id = $1
+            RETURNING id, user_id, organization_id, content_hash, perceptual_hash,
+                      qrng_entropy, qrng_source, signature, public_key,
+                      media_type, file_size, mime_type, metadata,
+                      trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                      revoked_at, revocation_reason, verification_count, legal_hold
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(seal)
+    }
+
+    /// List revoked seals, most recently revoked first (admin operation)
+    pub async fn list_revoked(
+        &self,
+        page: i64,
+        limit: i64,
+    ) -> Result<SealListResponse, sqlx::Error> {
+        let limit = limit.clamp(1, 100);
+        let offset = (page - 1).max(0) * limit;
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+
+        let seals = sqlx::query_as::<_, Seal>(
+            r#"
+            SELECT id, user_id, organization_id, content_hash, perceptual_hash,
+                   qrng_entropy, qrng_source, signature, public_key,
+                   media_type, file_size, mime_type, metadata,
+                   trust_tier, c2pa_manifest_embedded, captured_at, created_at, media_deleted_at,
+                   revoked_at, revocation_reason, verification_count, legal_hold
+            FROM seals
+            WHERE revoked_at IS NOT NULL
+            ORDER BY revoked_at DESC
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&mut *conn)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM seals WHERE revoked_at IS NOT NULL")
+                .fetch_one(&mut *conn)
+                .await?;
+
+        let records: Vec<SealRecord> = seals.into_iter().map(SealRecord::from).collect();
+        let has_more = offset + (records.len() as i64) < total.0;
+
+        Ok(SealListResponse {
+            seals: records,
+            page,
+            limit,
+            total: total.0,
+            has_more,
+        })
+    }
+
+    /// Record a failed seal attempt (for failure rates)
+    pub async fn record_failure(
+        &self,
+        user_id: Option<Uuid>,
+        error_code: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO seal_failures (user_id, error_code) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(error_code)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Platform-wide seal counts (admin operation)
+    pub async fn totals(&self) -> Result<SealTotals, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        sqlx::query_as::<_, SealTotals>(
+            r#"
+            WITH coverage AS (
+                SELECT COALESCE(MAX(covered_until), '-infinity') AS covered_until
+                FROM seal_stats_daily
+            )
+            SELECT ((SELECT COALESCE(SUM(seals), 0) FROM seal_stats_daily)
+                    + (SELECT COUNT(*) FROM seals, coverage
+                       WHERE created_at >= coverage.covered_until))::BIGINT AS total,
+                   (SELECT COUNT(*) FROM seals WHERE revoked_at IS NOT NULL) AS revoked,
+                   (SELECT COUNT(*) FROM seals
+                    WHERE created_at > NOW() - INTERVAL '1 day') AS last_24h,
+                   (SELECT COUNT(*) FROM seal_failures
+                    WHERE created_at > NOW() - INTERVAL '1 day') AS failures_last_24h
+            "#,
+        )
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    /// Seals and failed attempts per day over the last `days` days, oldest
+    /// first, including days without activity (admin operation)
+    pub async fn daily_stats(&self, days: i32) -> Result<Vec<DailySealStats>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        sqlx::query_as::<_, DailySealStats>(
+            r#"
+            WITH days AS (
+                SELECT generate_series(
+                    (NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1),
+                    (NOW() AT TIME ZONE 'UTC')::DATE,
+                    INTERVAL '1 day'
+                )::DATE AS day
+            ),
+            coverage AS (
+                SELECT COALESCE(MAX(covered_until), '-infinity') AS covered_until
+                FROM seal_stats_daily
+            ),
+            seal_counts AS (
+                SELECT day, SUM(seals)::BIGINT AS seals
+                FROM (
+                    SELECT day, seals
+                    FROM seal_stats_daily
+                    WHERE day >= (NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1)
+                    UNION ALL
+                    SELECT (created_at AT TIME ZONE 'UTC')::DATE, COUNT(*)
+                    FROM seals, coverage
+                    WHERE created_at >= coverage.covered_until
+                    AND created_at >= NOW() - make_interval(days => $1)
+                    GROUP BY 1
+                ) counts
+                GROUP BY day
+            ),
+            failure_counts AS (
+                SELECT (created_at AT TIME ZONE 'UTC')::DATE AS day, COUNT(*) AS failures
+                FROM seal_failures
+                WHERE created_at >= NOW() - make_interval(days => $1)
+                GROUP BY 1
+            )
+            SELECT days.day,
+                   COALESCE(seal_counts.seals, 0) AS seals,
+                   COALESCE(failure_counts.failures, 0) AS failures
+            FROM days
+            LEFT JOIN seal_counts ON seal_counts.day = days.day
+            LEFT JOIN failure_counts ON failure_counts.day = days.day
+            ORDER BY days.day
+            "#,
+        )
+        .bind(days)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Seals per QRNG source over the last `days` UTC days (today
+    /// included), most used first (admin operation)
+    pub async fn qrng_source_stats(&self, days: i32) -> Result<Vec<QrngSourceStats>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        sqlx::query_as::<_, QrngSourceStats>(
+            r#"
+            WITH coverage AS (
+                SELECT COALESCE(MAX(covered_until), '-infinity') AS covered_until
+                FROM seal_stats_daily
+            )
+            SELECT source, SUM(seals)::BIGINT AS seals
+            FROM (
+                SELECT qrng_source AS source, seals
+                FROM seal_stats_daily
+                WHERE day >= (NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1)
+                UNION ALL
+                SELECT qrng_source, COUNT(*)
+                FROM seals, coverage
+                WHERE created_at >= coverage.covered_until
+                AND created_at >= ((NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1))::TIMESTAMP
+                    AT TIME ZONE 'UTC'
+                GROUP BY qrng_source
+            ) counts
+            GROUP BY source
+            ORDER BY seals DESC, source
+            "#,
+        )
+        .bind(days)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Seals per trust tier over the last `days` UTC days (today included),
+    /// lowest tier first (admin operation)
+    pub async fn trust_tier_stats(&self, days: i32) -> Result<Vec<TrustTierStats>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        sqlx::query_as::<_, TrustTierStats>(
+            r#"
+            WITH coverage AS (
+                SELECT COALESCE(MAX(covered_until), '-infinity') AS covered_until
+                FROM seal_stats_daily
+            )
+            SELECT tier, SUM(seals)::BIGINT AS seals
+            FROM (
+                SELECT trust_tier AS tier, seals
+                FROM seal_stats_daily
+                WHERE day >= (NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1)
+                UNION ALL
+                SELECT trust_tier, COUNT(*)
+                FROM seals, coverage
+                WHERE created_at >= coverage.covered_until
+                AND created_at >= ((NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1))::TIMESTAMP
+                    AT TIME ZONE 'UTC'
+                GROUP BY trust_tier
+            ) counts
+            GROUP BY tier
+            ORDER BY tier
+            "#,
+        )
+        .bind(days)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Mean size of the files sealed over the last `days` UTC days (today
+    /// included), `None` without any sized seal (admin operation)
+    pub async fn average_file_size(&self, days: i32) -> Result<Option<f64>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        sqlx::query_scalar(
+            r#"
+            WITH coverage AS (
+                SELECT COALESCE(MAX(covered_until), '-infinity') AS covered_until
+                FROM seal_stats_daily
+            )
+            SELECT (SUM(file_bytes) / NULLIF(SUM(sized_seals), 0))::FLOAT8
+            FROM (
+                SELECT file_bytes, sized_seals
+                FROM seal_stats_daily
+                WHERE day >= (NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1)
+                UNION ALL
+                SELECT COALESCE(SUM(file_size), 0), COUNT(file_size)
+                FROM seals, coverage
+                WHERE created_at >= coverage.covered_until
+                AND created_at >= ((NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1))::TIMESTAMP
+                    AT TIME ZONE 'UTC'
+            ) sizes
+            "#,
+        )
+        .bind(days)
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    /// Batched anchors per chain over the last `days` UTC days (today
+    /// included), with how long seals waited in the queue, most used chain
+    /// first (admin operation)
+    pub async fn anchor_latency_stats(
+        &self,
+        days: i32,
+    ) -> Result<Vec<AnchorLatencyStats>, sqlx::Error> {
+        sqlx::query_as::<_, AnchorLatencyStats>(
+            r#"
+            WITH coverage AS (
+                SELECT COALESCE(MAX(covered_until), '-infinity') AS covered_until
+                FROM anchor_stats_daily
+            )
+            SELECT chain,
+                   SUM(anchors)::BIGINT AS anchors,
+                   (SUM(latency_secs) / NULLIF(SUM(timed_anchors), 0))::FLOAT8
+                       AS average_latency_secs,
+                   MAX(max_latency_secs) AS max_latency_secs
+            FROM (
+                SELECT chain, anchors, timed_anchors, latency_secs, max_latency_secs
+                FROM anchor_stats_daily
+                WHERE day >= (NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1)
+                UNION ALL
+                SELECT chain,
+                       COUNT(*),
+                       COUNT(queued_at),
+                       COALESCE(SUM(EXTRACT(EPOCH FROM anchored_at - queued_at)), 0)::FLOAT8,
+                       MAX(EXTRACT(EPOCH FROM anchored_at - queued_at))::FLOAT8
+                FROM anchor_proofs, coverage
+                WHERE anchored_at >= coverage.covered_until
+                AND anchored_at >= ((NOW() AT TIME ZONE 'UTC')::DATE - ($1 - 1))::TIMESTAMP
+                    AT TIME ZONE 'UTC'
+                GROUP BY chain
+            ) latencies
+            GROUP BY chain
+            ORDER BY anchors DESC, chain
+            "#,
+        )
+        .bind(days)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Refresh the statistics views, returning how many were refreshed
+    ///
+    /// Refreshes run concurrently with reads, which keep seeing the previous
+    /// contents until each view is replaced.
+    pub async fn refresh_stats(&self) -> Result<usize, sqlx::Error> {
+        for view in STATS_VIEWS {
+            let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+            sqlx::query(&format!("REFRESH MATERIALIZED VIEW CONCURRENTLY {view}"))
+                .execute(&mut *conn)
+                .await?;
+            conn.finish().await?;
+        }
+        Ok(STATS_VIEWS.len())
     }
 }
 
@@ -465,6 +1452,10 @@ mod tests {
             captured_at: Utc::now(),
             created_at: Utc::now(),
             media_deleted_at: None,
+            revoked_at: None,
+            revocation_reason: None,
+            verification_count: 3,
+            legal_hold: true,
         };
 
         let record = SealRecord::from(seal.clone());
@@ -472,6 +1463,8 @@ mod tests {
         assert_eq!(record.content_hash, seal.content_hash);
         assert_eq!(record.perceptual_hash, Some("0102030405060708".to_string()));
         assert!(!record.media_deleted);
+        assert_eq!(record.verification_count, 3);
+        assert!(record.legal_hold);
     }
 
     #[test]
@@ -489,11 +1482,20 @@ mod tests {
             }),
             capture_source: "camera".to_string(),
             has_device_attestation: true,
+            trust_rationale: vec!["account is a verified reporter".to_string()],
         };
 
         let json = serde_json::to_string(&metadata).unwrap();
         assert!(json.contains("timestamp"));
         assert!(json.contains("location"));
         assert!(json.contains("lat"));
+        assert!(json.contains("trust_rationale"));
+
+        // Metadata stored before trust rationales still deserializes
+        let legacy: SealMetadata = serde_json::from_str(
+            r#"{"timestamp":"2026-01-08T10:00:00Z","capture_source":"camera","has_device_attestation":false}"#,
+        )
+        .unwrap();
+        assert!(legacy.trust_rationale.is_empty());
     }
 }
@@ -0,0 +1,267 @@
+//! Seal share entity and repository
+//!
+//! Handles share links granting public access to a single seal. Shares
+//! belong to the user of a [`Tenant`], and are only made for its seals.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Tenant, TenantConnection};
+
+/// Seal share entity from database
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct SealShare {
+    pub id: Uuid,
+    pub seal_id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_accessed_at: Option<DateTime<Utc>>,
+    pub access_count: i64,
+}
+
+impl SealShare {
+    /// Returns true if the share still grants access at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+/// Share as listed to its owner (without the token)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SealShareRecord {
+    /// Share ID, used to revoke it
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+
+    /// Shared seal ID
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub seal_id: Uuid,
+
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Expiry timestamp (absent = never expires)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Revocation timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+
+    /// Last time the share was opened
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_accessed_at: Option<DateTime<Utc>>,
+
+    /// Number of times the share was opened
+    pub access_count: i64,
+
+    /// Whether the share still grants access
+    pub active: bool,
+}
+
+impl From<SealShare> for SealShareRecord {
+    fn from(share: SealShare) -> Self {
+        Self {
+            active: share.is_active(Utc::now()),
+            id: share.id,
+            seal_id: share.seal_id,
+            created_at: share.created_at,
+            expires_at: share.expires_at,
+            revoked_at: share.revoked_at,
+            last_accessed_at: share.last_accessed_at,
+            access_count: share.access_count,
+        }
+    }
+}
+
+/// Repository for seal share operations
+#[derive(Clone)]
+pub struct SealShareRepository {
+    pool: PgPool,
+    tenant_rls: bool,
+}
+
+impl SealShareRepository {
+    /// Create a new seal share repository
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Run tenant queries under row-level security, and the others as
+    /// system paths bypassing it (`TENANT_RLS`)
+    pub fn with_tenant_rls(mut self, tenant_rls: bool) -> Self {
+        self.tenant_rls = tenant_rls;
+        self
+    }
+
+    /// Insert a new share of a tenant's seal, owned by the tenant's user
+    ///
+    /// Returns false if the seal is not the tenant's.
+    pub async fn create(&self, share: &SealShare, tenant: &Tenant) -> Result<bool, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO seal_shares (id, seal_id, user_id, token_hash, created_at, expires_at)
+            SELECT $1, s.id, s.user_id, $4, $5, $6
+            FROM seals s
+            WHERE s.id = $2 AND s.user_id = $3
+              AND (s.organization_id IS NULL OR s.organization_id = $7)
+            "#,
+        )
+        .bind(share.id)
+        .bind(share.seal_id)
+        .bind(tenant.user_id)
+        .bind(&share.token_hash)
+        .bind(share.created_at)
+        .bind(share.expires_at)
+        .bind(tenant.organization_id)
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Find an unrevoked, unexpired share by token hash and count the access
+    pub async fn open_by_token_hash(
+        &self,
+        token_hash: &[u8],
+    ) -> Result<Option<SealShare>, sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        let share = sqlx::query_as::<_, SealShare>(
+            r#"
+            UPDATE seal_shares
+            SET access_count = access_count + 1, last_accessed_at = NOW()
+            WHERE token_hash = $1
+              AND revoked_at IS NULL
+              AND (expires_at IS NULL OR expires_at > NOW())
+            RETURNING id, seal_id, user_id, token_hash, created_at, expires_at,
+                      revoked_at, last_accessed_at, access_count
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(share)
+    }
+
+    /// List the shares of a seal, newest first, restricted to a tenant
+    pub async fn list_for_seal(
+        &self,
+        seal_id: Uuid,
+        tenant: &Tenant,
+    ) -> Result<Vec<SealShare>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, SealShare>(
+            r#"
+            SELECT id, seal_id, user_id, token_hash, created_at, expires_at,
+                   revoked_at, last_accessed_at, access_count
+            FROM seal_shares
+            WHERE seal_id = $1 AND user_id = $2
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(seal_id)
+        .bind(tenant.user_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// List all shares a tenant created of its seals, newest first (of any
+    /// organization with a [`Tenant::personal`] scope)
+    pub async fn list_for_user(&self, tenant: &Tenant) -> Result<Vec<SealShare>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, SealShare>(
+            r#"
+            SELECT sh.id, sh.seal_id, sh.user_id, sh.token_hash, sh.created_at, sh.expires_at,
+                   sh.revoked_at, sh.last_accessed_at, sh.access_count
+            FROM seal_shares sh
+            JOIN seals s ON s.id = sh.seal_id
+            WHERE sh.user_id = $1
+              AND (s.organization_id IS NULL OR s.organization_id = $2 OR $3)
+            ORDER BY sh.created_at DESC
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .bind(tenant.all_organizations)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Revoke a share, restricted to a tenant
+    ///
+    /// Returns false if no such share exists. Revoking twice keeps the first
+    /// revocation time.
+    pub async fn revoke(
+        &self,
+        id: Uuid,
+        seal_id: Uuid,
+        tenant: &Tenant,
+    ) -> Result<bool, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let result = sqlx::query(
+            r#"
+            UPDATE seal_shares
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1 AND seal_id = $2 AND user_id = $3
+            "#,
+        )
+        .bind(id)
+        .bind(seal_id)
+        .bind(tenant.user_id)
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn share() -> SealShare {
+        SealShare {
+            id: Uuid::new_v4(),
+            seal_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            token_hash: vec![0; 32],
+            created_at: Utc::now(),
+            expires_at: None,
+            revoked_at: None,
+            last_accessed_at: None,
+            access_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_share_is_active() {
+        let now = Utc::now();
+        assert!(share().is_active(now));
+
+        let expired = SealShare {
+            expires_at: Some(now - chrono::Duration::seconds(1)),
+            ..share()
+        };
+        assert!(!expired.is_active(now));
+
+        let revoked = SealShare {
+            revoked_at: Some(now),
+            ..share()
+        };
+        assert!(!SealShareRecord::from(revoked).active);
+    }
+}
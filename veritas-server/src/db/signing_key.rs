@@ -0,0 +1,148 @@
+//! Signing-key registry repository
+//!
+//! Stores the server's seal signing keys (see [`crate::signing_keys`]) with
+//! their validity periods and status.
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use veritas_core::{SigningKey, SigningKeyStatus};
+
+/// Signing key as stored in the database
+#[derive(Debug, Clone, FromRow)]
+struct SigningKeyRow {
+    key_id: String,
+    public_key: Vec<u8>,
+    valid_from: DateTime<Utc>,
+    valid_until: Option<DateTime<Utc>>,
+    status: String,
+}
+
+impl From<SigningKeyRow> for SigningKey {
+    fn from(row: SigningKeyRow) -> Self {
+        Self {
+            key_id: row.key_id,
+            public_key: row.public_key,
+            valid_from: row.valid_from.timestamp_millis().max(0) as u64,
+            valid_until: row
+                .valid_until
+                .map(|until| until.timestamp_millis().max(0) as u64),
+            // The column is constrained to the three statuses
+            status: SigningKeyStatus::parse(&row.status).unwrap_or(SigningKeyStatus::Revoked),
+        }
+    }
+}
+
+/// Repository for signing-key registry operations
+#[derive(Clone)]
+pub struct SigningKeyRepository {
+    pool: PgPool,
+}
+
+impl SigningKeyRepository {
+    /// Create a new signing-key repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record `key` as the active signing key, retiring the other active
+    /// keys with a validity ending at `retire_until`
+    ///
+    /// A key already registered keeps its entry; a retired one is made active
+    /// again, a revoked one stays revoked. Returns the key as registered.
+    pub async fn register(
+        &self,
+        key: &SigningKey,
+        retire_until: DateTime<Utc>,
+    ) -> Result<SigningKey, sqlx::Error> {
+        let valid_from = DateTime::from_timestamp_millis(key.valid_from as i64).unwrap_or_default();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO signing_keys (key_id, public_key, valid_from, status)
+            VALUES ($1, $2, $3, 'active')
+            ON CONFLICT (key_id) DO UPDATE SET status = 'active', valid_until = NULL
+            WHERE signing_keys.status = 'retired'
+            "#,
+        )
+        .bind(&key.key_id)
+        .bind(&key.public_key)
+        .bind(valid_from)
+        .execute(&mut *tx)
+        .await?;
+
+        let registered: SigningKeyRow = sqlx::query_as(
+            r#"
+            SELECT key_id, public_key, valid_from, valid_until, status
+            FROM signing_keys
+            WHERE key_id = $1
+            "#,
+        )
+        .bind(&key.key_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if registered.status == SigningKeyStatus::Active.as_str() {
+            // LEAST ignores NULL: open-ended keys end at retire_until
+            sqlx::query(
+                r#"
+                UPDATE signing_keys
+                SET status = 'retired', valid_until = LEAST(valid_until, $2)
+                WHERE status = 'active' AND key_id <> $1
+                "#,
+            )
+            .bind(&key.key_id)
+            .bind(retire_until)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(registered.into())
+    }
+
+    /// Find a signing key by ID
+    pub async fn find(&self, key_id: &str) -> Result<Option<SigningKey>, sqlx::Error> {
+        let row: Option<SigningKeyRow> = sqlx::query_as(
+            r#"
+            SELECT key_id, public_key, valid_from, valid_until, status
+            FROM signing_keys
+            WHERE key_id = $1
+            "#,
+        )
+        .bind(key_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(SigningKey::from))
+    }
+
+    /// List every signing key, newest first
+    pub async fn list(&self) -> Result<Vec<SigningKey>, sqlx::Error> {
+        let rows: Vec<SigningKeyRow> = sqlx::query_as(
+            r#"
+            SELECT key_id, public_key, valid_from, valid_until, status
+            FROM signing_keys
+            ORDER BY valid_from DESC, key_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(SigningKey::from).collect())
+    }
+
+    /// Revoke a signing key, returning it (`None` if unknown)
+    pub async fn revoke(&self, key_id: &str) -> Result<Option<SigningKey>, sqlx::Error> {
+        let row: Option<SigningKeyRow> = sqlx::query_as(
+            r#"
+            UPDATE signing_keys
+            SET status = 'revoked', revoked_at = COALESCE(revoked_at, NOW())
+            WHERE key_id = $1
+            RETURNING key_id, public_key, valid_from, valid_until, status
+            "#,
+        )
+        .bind(key_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(SigningKey::from))
+    }
+}
@@ -0,0 +1,235 @@
+//! Seal tag entity and repository
+//!
+//! Free-form tags users put on their own seals. A tag has no row of its own:
+//! it exists while at least one of the user's seals carries it. Every query
+//! is restricted to the seals of a [`Tenant`].
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Tenant, TenantConnection};
+
+/// A tag and how many of the user's seals carry it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct TagCount {
+    /// Tag (lowercase)
+    #[schema(example = "ukraine")]
+    pub tag: String,
+    /// Seals carrying the tag
+    #[schema(example = 128)]
+    pub seals: i64,
+}
+
+/// A tag of a seal, as stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+pub struct SealTag {
+    pub seal_id: Uuid,
+    pub tag: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Repository for seal tags
+#[derive(Clone)]
+pub struct SealTagRepository {
+    pool: PgPool,
+    tenant_rls: bool,
+}
+
+impl SealTagRepository {
+    /// Create a new seal tag repository
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Run tenant queries under row-level security, and the others as
+    /// system paths bypassing it (`TENANT_RLS`)
+    pub fn with_tenant_rls(mut self, tenant_rls: bool) -> Self {
+        self.tenant_rls = tenant_rls;
+        self
+    }
+
+    /// Tags of a tenant's seal, alphabetically
+    pub async fn tags_of(
+        &self,
+        seal_id: Uuid,
+        tenant: &Tenant,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_scalar(
+            r#"
+            SELECT t.tag
+            FROM seal_tags t
+            JOIN seals s ON s.id = t.seal_id
+            WHERE t.seal_id = $1
+              AND s.user_id = $2 AND (s.organization_id IS NULL OR s.organization_id = $3)
+            ORDER BY t.tag
+            "#,
+        )
+        .bind(seal_id)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Replace the tags of a tenant's seal; tags it keeps keep their
+    /// creation time
+    pub async fn set_tags(
+        &self,
+        seal_id: Uuid,
+        tenant: &Tenant,
+        tags: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = tenant.begin(&self.pool, self.tenant_rls).await?;
+        let owned: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM seals
+            WHERE id = $1 AND user_id = $2 AND (organization_id IS NULL OR organization_id = $3)
+            FOR UPDATE
+            "#,
+        )
+        .bind(seal_id)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if owned.is_none() {
+            return Ok(());
+        }
+
+        sqlx::query("DELETE FROM seal_tags WHERE seal_id = $1 AND tag <> ALL($2)")
+            .bind(seal_id)
+            .bind(tags)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO seal_tags (seal_id, tag)
+            SELECT $1, tag FROM UNNEST($2::TEXT[]) AS tag
+            ON CONFLICT (seal_id, tag) DO NOTHING
+            "#,
+        )
+        .bind(seal_id)
+        .bind(tags)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    /// Tags of a tenant's seals with their number of seals, most used first
+    pub async fn list_for_user(&self, tenant: &Tenant) -> Result<Vec<TagCount>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, TagCount>(
+            r#"
+            SELECT t.tag, COUNT(*) AS seals
+            FROM seal_tags t
+            JOIN seals s ON s.id = t.seal_id
+            WHERE s.user_id = $1 AND (s.organization_id IS NULL OR s.organization_id = $2)
+            GROUP BY t.tag
+            ORDER BY seals DESC, t.tag
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Rename a tag on all of a tenant's seals, merging it into `new_tag` on
+    /// seals carrying both
+    ///
+    /// Returns the number of seals that carried the tag.
+    pub async fn rename(
+        &self,
+        tenant: &Tenant,
+        tag: &str,
+        new_tag: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = tenant.begin(&self.pool, self.tenant_rls).await?;
+        sqlx::query(
+            r#"
+            INSERT INTO seal_tags (seal_id, tag, created_at)
+            SELECT t.seal_id, $3, t.created_at
+            FROM seal_tags t
+            JOIN seals s ON s.id = t.seal_id
+            WHERE s.user_id = $1 AND (s.organization_id IS NULL OR s.organization_id = $4)
+              AND t.tag = $2
+            ON CONFLICT (seal_id, tag) DO NOTHING
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(tag)
+        .bind(new_tag)
+        .bind(tenant.organization_id)
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query(
+            r#"
+            DELETE FROM seal_tags t
+            USING seals s
+            WHERE s.id = t.seal_id
+              AND s.user_id = $1 AND (s.organization_id IS NULL OR s.organization_id = $3)
+              AND t.tag = $2
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(tag)
+        .bind(tenant.organization_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Remove a tag from all of a tenant's seals
+    ///
+    /// Returns the number of seals that carried it.
+    pub async fn delete(&self, tenant: &Tenant, tag: &str) -> Result<u64, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        let result = sqlx::query(
+            r#"
+            DELETE FROM seal_tags t
+            USING seals s
+            WHERE s.id = t.seal_id
+              AND s.user_id = $1 AND (s.organization_id IS NULL OR s.organization_id = $3)
+              AND t.tag = $2
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(tag)
+        .bind(tenant.organization_id)
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// All tags of a tenant's seals (personal data exports, with a
+    /// [`Tenant::personal`] scope)
+    pub async fn all_for_user(&self, tenant: &Tenant) -> Result<Vec<SealTag>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, SealTag>(
+            r#"
+            SELECT t.seal_id, t.tag, t.created_at
+            FROM seal_tags t
+            JOIN seals s ON s.id = t.seal_id
+            WHERE s.user_id = $1
+              AND (s.organization_id IS NULL OR s.organization_id = $2 OR $3)
+            ORDER BY t.seal_id, t.tag
+            "#,
+        )
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .bind(tenant.all_organizations)
+        .fetch_all(&mut *conn)
+        .await
+    }
+}
@@ -0,0 +1,234 @@
+//! Tenant scoping
+//!
+//! Every repository method reading or changing an account's data takes the
+//! [`Tenant`] of the request: the user, and the organization they act in.
+//! A tenant sees the seals it owns that were made in its organization or
+//! outside any, so a user moved to another organization leaves the seals of
+//! the previous one behind. Tags, verifications and collection entries
+//! follow their seal; collections, shares and API keys their owner. The
+//! account's own data (personal data export, usage and quotas) is read with
+//! a [`Tenant::personal`] scope instead, covering every organization.
+//!
+//! With `TENANT_RLS=true` the same rules are enforced by Postgres row-level
+//! security, with the policies of the `add_tenant_isolation` migration:
+//! tenant-scoped queries run in a transaction naming the tenant
+//! (`veritas.tenant_user_id`, `veritas.tenant_organization_id`,
+//! `veritas.tenant_all_organizations`), so a query missing its filter still
+//! cannot read another tenant's rows. The policies fail closed: a connection
+//! naming no tenant sees no rows, so system paths (public verification,
+//! administration, background tasks, migrations) declare themselves with
+//! `veritas.tenant_bypass`, through [`TenantConnection::system`] and
+//! [`run_migrations`].
+
+use std::ops::{Deref, DerefMut};
+
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::pool::PoolConnection;
+use sqlx::{Connection, PgConnection, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use super::User;
+
+/// Account and organization a request acts for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tenant {
+    /// Acting user
+    pub user_id: Uuid,
+    /// Organization of the user, if any
+    pub organization_id: Option<Uuid>,
+    /// Whether the user's seals made in any organization are covered, not
+    /// only those of `organization_id` (see [`Tenant::personal`])
+    pub all_organizations: bool,
+}
+
+impl Tenant {
+    /// Tenant of a user acting in an organization (or outside any)
+    pub fn new(user_id: Uuid, organization_id: Option<Uuid>) -> Self {
+        Self {
+            user_id,
+            organization_id,
+            all_organizations: false,
+        }
+    }
+
+    /// Tenant of a user acting in their current organization
+    pub fn of(user: &User) -> Self {
+        Self::new(user.id, user.organization_id)
+    }
+
+    /// Tenant covering all of a user's own data, whatever organization it
+    /// was made in: personal data exports, usage and quotas
+    ///
+    /// Honoured by the repository methods reading the account's data as a
+    /// whole; the others still filter on the current organization.
+    pub fn personal(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            organization_id: None,
+            all_organizations: true,
+        }
+    }
+
+    /// Start a transaction for this tenant, naming it to the row-level
+    /// security policies when `rls` is set
+    pub async fn begin(
+        &self,
+        pool: &PgPool,
+        rls: bool,
+    ) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        if rls {
+            // Settings local to the transaction, reset when it ends
+            sqlx::query(
+                r#"
+                SELECT set_config('veritas.tenant_user_id', $1, true),
+                       set_config('veritas.tenant_organization_id', $2, true),
+                       set_config('veritas.tenant_all_organizations', $3, true)
+                "#,
+            )
+            .bind(self.user_id.to_string())
+            .bind(
+                self.organization_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+            )
+            .bind(if self.all_organizations { "on" } else { "" })
+            .execute(&mut *tx)
+            .await?;
+        }
+        Ok(tx)
+    }
+}
+
+/// Database connection for the queries of one tenant, or of a system path
+///
+/// A pooled connection, or with row-level security a transaction naming the
+/// tenant (or the bypass). Writes must call [`TenantConnection::finish`] to
+/// be committed; reads may simply drop it.
+pub enum TenantConnection {
+    /// Connection not restricted by row-level security
+    Shared(PoolConnection<Postgres>),
+    /// Transaction naming its tenant or the bypass to row-level security
+    Scoped(Transaction<'static, Postgres>),
+}
+
+impl TenantConnection {
+    /// Connection from `pool` for `tenant`, naming it to the row-level
+    /// security policies when `rls` is set
+    pub async fn acquire(pool: &PgPool, tenant: &Tenant, rls: bool) -> Result<Self, sqlx::Error> {
+        if !rls {
+            return Ok(Self::Shared(pool.acquire().await?));
+        }
+
+        Ok(Self::Scoped(tenant.begin(pool, true).await?))
+    }
+
+    /// Connection from `pool` for a system path, seeing every tenant's rows:
+    /// with `rls` set, a transaction declaring `veritas.tenant_bypass`
+    pub async fn system(pool: &PgPool, rls: bool) -> Result<Self, sqlx::Error> {
+        if !rls {
+            return Ok(Self::Shared(pool.acquire().await?));
+        }
+
+        let mut tx = pool.begin().await?;
+        sqlx::query("SELECT set_config('veritas.tenant_bypass', 'on', true)")
+            .execute(&mut *tx)
+            .await?;
+        Ok(Self::Scoped(tx))
+    }
+
+    /// Commit the tenant's changes
+    pub async fn finish(self) -> Result<(), sqlx::Error> {
+        match self {
+            Self::Shared(_) => Ok(()),
+            Self::Scoped(tx) => tx.commit().await,
+        }
+    }
+}
+
+impl Deref for TenantConnection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            Self::Shared(conn) => conn,
+            Self::Scoped(tx) => tx,
+        }
+    }
+}
+
+impl DerefMut for TenantConnection {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            Self::Shared(conn) => conn,
+            Self::Scoped(tx) => tx,
+        }
+    }
+}
+
+/// Apply the pending migrations of `migrator`, on a connection declaring
+/// `veritas.tenant_bypass` so data migrations see every tenant's rows
+///
+/// The connection is closed afterwards rather than returned to the pool.
+pub async fn run_migrations(migrator: &Migrator, pool: &PgPool) -> Result<(), MigrateError> {
+    let mut conn = pool.acquire().await?.detach();
+    sqlx::query("SET veritas.tenant_bypass = 'on'")
+        .execute(&mut conn)
+        .await?;
+    let result = migrator.run(&mut conn).await;
+    let closed = conn.close().await;
+    result?;
+    Ok(closed?)
+}
+
+/// Turn the tenant row-level security policies on or off (`TENANT_RLS`)
+///
+/// Tables already in the requested state are left alone, so instances
+/// starting together do not wait on each other's table locks.
+pub async fn set_row_level_security(pool: &PgPool, enabled: bool) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT veritas_tenant_rls($1)")
+        .bind(enabled)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{TrustTier, UserRole};
+    use chrono::Utc;
+
+    #[test]
+    fn test_tenant_of_user() {
+        let organization_id = Uuid::new_v4();
+        let user = User {
+            id: Uuid::new_v4(),
+            clerk_user_id: "clerk_123".to_string(),
+            email: "reporter@example.com".to_string(),
+            name: None,
+            avatar_url: None,
+            tier: TrustTier::Tier1,
+            role: UserRole::User,
+            organization_id: Some(organization_id),
+            suspended_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+
+        let tenant = Tenant::of(&user);
+        assert_eq!(tenant.user_id, user.id);
+        assert_eq!(tenant.organization_id, Some(organization_id));
+        assert!(!tenant.all_organizations);
+    }
+
+    #[test]
+    fn test_personal_tenant_covers_all_organizations() {
+        let user_id = Uuid::new_v4();
+        let tenant = Tenant::personal(user_id);
+        assert_eq!(tenant.user_id, user_id);
+        assert_eq!(tenant.organization_id, None);
+        assert!(tenant.all_organizations);
+    }
+}
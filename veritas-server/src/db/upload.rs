@@ -0,0 +1,130 @@
+//! Upload session entity and repository
+//!
+//! Handles the state of resumable uploads (see [`crate::uploads`]).
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Upload session entity from database
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct UploadSession {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub upload_length: i64,
+    pub upload_offset: i64,
+    pub media_type: Option<String>,
+    pub content_type: Option<String>,
+    pub file_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl UploadSession {
+    /// Returns true once every declared byte has been received
+    pub fn is_complete(&self) -> bool {
+        self.upload_offset == self.upload_length
+    }
+}
+
+/// Repository for upload session operations
+#[derive(Clone)]
+pub struct UploadSessionRepository {
+    pool: PgPool,
+}
+
+impl UploadSessionRepository {
+    /// Create a new upload session repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Insert a new upload session
+    pub async fn create(&self, session: &UploadSession) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO upload_sessions (
+                id, user_id, upload_length, upload_offset, media_type,
+                content_type, file_name, created_at, updated_at, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(session.id)
+        .bind(session.user_id)
+        .bind(session.upload_length)
+        .bind(session.upload_offset)
+        .bind(&session.media_type)
+        .bind(&session.content_type)
+        .bind(&session.file_name)
+        .bind(session.created_at)
+        .bind(session.updated_at)
+        .bind(session.expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Find an unexpired upload session by ID
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<UploadSession>, sqlx::Error> {
+        sqlx::query_as::<_, UploadSession>(
+            r#"
+            SELECT id, user_id, upload_length, upload_offset, media_type,
+                   content_type, file_name, created_at, updated_at, expires_at
+            FROM upload_sessions
+            WHERE id = $1 AND expires_at > NOW()
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Move the offset forward, only if it still equals `expected_offset`
+    ///
+    /// Returns false if another request advanced the upload first.
+    pub async fn advance(
+        &self,
+        id: Uuid,
+        expected_offset: i64,
+        new_offset: i64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE upload_sessions
+            SET upload_offset = $3, updated_at = NOW(), expires_at = $4
+            WHERE id = $1 AND upload_offset = $2
+            "#,
+        )
+        .bind(id)
+        .bind(expected_offset)
+        .bind(new_offset)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete an upload session
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM upload_sessions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete expired upload sessions, returning their IDs
+    pub async fn delete_expired(&self) -> Result<Vec<Uuid>, sqlx::Error> {
+        let rows: Vec<(Uuid,)> =
+            sqlx::query_as("DELETE FROM upload_sessions WHERE expires_at <= NOW() RETURNING id")
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+}
@@ -8,8 +8,10 @@ use sqlx::{FromRow, PgPool};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-/// Trust tier for users
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+/// Trust tier of a user account or seal
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum TrustTier {
     /// Tier 1: In-app capture only (default for all users)
@@ -17,7 +19,7 @@ pub enum TrustTier {
     Tier1 = 1,
     /// Tier 2: Verified reporter (can import from gallery)
     Tier2 = 2,
-    /// Tier 3: Hardware attestation (future)
+    /// Tier 3: Verified hardware attestation
     Tier3 = 3,
 }
 
@@ -37,6 +39,36 @@ impl From<TrustTier> for i16 {
     }
 }
 
+/// Account role
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    /// Regular account
+    #[default]
+    User,
+    /// Administrator with access to `/api/v1/admin`
+    Admin,
+}
+
+impl UserRole {
+    /// Value stored in the `users.role` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::User => "user",
+            UserRole::Admin => "admin",
+        }
+    }
+}
+
+impl From<String> for UserRole {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "admin" => UserRole::Admin,
+            _ => UserRole::User,
+        }
+    }
+}
+
 /// User entity from database
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
@@ -47,11 +79,28 @@ pub struct User {
     pub avatar_url: Option<String>,
     #[sqlx(try_from = "i16")]
     pub tier: TrustTier,
+    #[sqlx(try_from = "String")]
+    pub role: UserRole,
+    /// Organization (tenant) the account belongs to, if any
+    pub organization_id: Option<Uuid>,
+    pub suspended_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
+impl User {
+    /// Returns true if the account has administrator rights
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+
+    /// Returns true if the account is suspended
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some()
+    }
+}
+
 /// DTO for creating a new user
 #[derive(Debug, Clone, Deserialize)]
 pub struct CreateUser {
@@ -104,6 +153,34 @@ impl From<User> for UserResponse {
     }
 }
 
+/// Account counts for the admin dashboard (deleted accounts excluded)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct UserStats {
+    /// Active and suspended accounts
+    pub total: i64,
+    /// Suspended accounts
+    pub suspended: i64,
+    /// Administrator accounts
+    pub admins: i64,
+    /// Accounts per trust tier
+    pub tier1: i64,
+    pub tier2: i64,
+    pub tier3: i64,
+}
+
+/// Filters for listing users (admin operation)
+#[derive(Debug, Clone, Default)]
+pub struct UserListParams {
+    /// Page number (1-indexed)
+    pub page: i64,
+    /// Items per page (max 100)
+    pub limit: i64,
+    /// Only suspended (true) or only active (false) accounts
+    pub suspended: Option<bool>,
+    /// Case-insensitive email substring
+    pub email: Option<String>,
+}
+
 /// Repository for user database operations
 #[derive(Clone)]
 pub struct UserRepository {
@@ -120,7 +197,8 @@ impl UserRepository {
     pub async fn find_by_clerk_id(&self, clerk_user_id: &str) -> Result<Option<User>, sqlx::Error> {
         sqlx::query_as::<_, User>(
             r#"
-            SELECT id, clerk_user_id, email, name, avatar_url, tier, created_at, updated_at, deleted_at
+            SELECT id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                   organization_id, created_at, updated_at, deleted_at
             FROM users
             WHERE clerk_user_id = $1 AND deleted_at IS NULL
             "#,
@@ -134,7 +212,8 @@ impl UserRepository {
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, sqlx::Error> {
         sqlx::query_as::<_, User>(
             r#"
-            SELECT id, clerk_user_id, email, name, avatar_url, tier, created_at, updated_at, deleted_at
+            SELECT id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                   organization_id, created_at, updated_at, deleted_at
             FROM users
             WHERE id = $1 AND deleted_at IS NULL
             "#,
@@ -148,7 +227,8 @@ impl UserRepository {
     pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, sqlx::Error> {
         sqlx::query_as::<_, User>(
             r#"
-            SELECT id, clerk_user_id, email, name, avatar_url, tier, created_at, updated_at, deleted_at
+            SELECT id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                   organization_id, created_at, updated_at, deleted_at
             FROM users
             WHERE email = $1 AND deleted_at IS NULL
             "#,
@@ -170,7 +250,8 @@ impl UserRepository {
                 name = EXCLUDED.name,
                 avatar_url = EXCLUDED.avatar_url,
                 updated_at = NOW()
-            RETURNING id, clerk_user_id, email, name, avatar_url, tier, created_at, updated_at, deleted_at
+            RETURNING id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                      organization_id, created_at, updated_at, deleted_at
             "#,
         )
         .bind(&input.clerk_user_id)
@@ -192,7 +273,8 @@ impl UserRepository {
                 avatar_url = COALESCE($4, avatar_url),
                 updated_at = NOW()
             WHERE id = $1 AND deleted_at IS NULL
-            RETURNING id, clerk_user_id, email, name, avatar_url, tier, created_at, updated_at, deleted_at
+            RETURNING id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                      organization_id, created_at, updated_at, deleted_at
             "#,
         )
         .bind(id)
@@ -235,7 +317,8 @@ impl UserRepository {
             UPDATE users
             SET tier = $2, updated_at = NOW()
             WHERE id = $1 AND deleted_at IS NULL
-            RETURNING id, clerk_user_id, email, name, avatar_url, tier, created_at, updated_at, deleted_at
+            RETURNING id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                      organization_id, created_at, updated_at, deleted_at
             "#,
         )
         .bind(id)
@@ -243,6 +326,120 @@ impl UserRepository {
         .fetch_optional(&self.pool)
         .await
     }
+
+    /// Suspend or reinstate a user (admin operation)
+    ///
+    /// Suspending an already suspended user keeps the original timestamp.
+    pub async fn set_suspended(
+        &self,
+        id: Uuid,
+        suspended: bool,
+    ) -> Result<Option<User>, sqlx::Error> {
+        sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET suspended_at = CASE WHEN $2 THEN COALESCE(suspended_at, NOW()) ELSE NULL END,
+                updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                      organization_id, created_at, updated_at, deleted_at
+            "#,
+        )
+        .bind(id)
+        .bind(suspended)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Move a user to an organization, or out of any with `None` (admin
+    /// operation)
+    ///
+    /// Seals already made stay with the organization they were made in.
+    pub async fn set_organization(
+        &self,
+        id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<Option<User>, sqlx::Error> {
+        sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET organization_id = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                      organization_id, created_at, updated_at, deleted_at
+            "#,
+        )
+        .bind(id)
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// List users, newest first, with the total matching count (admin operation)
+    pub async fn list(&self, params: &UserListParams) -> Result<(Vec<User>, i64), sqlx::Error> {
+        let limit = params.limit.clamp(1, 100);
+        let offset = (params.page - 1).max(0) * limit;
+        let email_pattern = params.email.as_ref().map(|email| {
+            let escaped = email
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{}%", escaped)
+        });
+
+        // NULL parameters disable their filter
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            SELECT id, clerk_user_id, email, name, avatar_url, tier, role, suspended_at,
+                   organization_id, created_at, updated_at, deleted_at
+            FROM users
+            WHERE deleted_at IS NULL
+              AND ($1::BOOLEAN IS NULL OR (suspended_at IS NOT NULL) = $1)
+              AND ($2::TEXT IS NULL OR email ILIKE $2)
+            ORDER BY created_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(params.suspended)
+        .bind(&email_pattern)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM users
+            WHERE deleted_at IS NULL
+              AND ($1::BOOLEAN IS NULL OR (suspended_at IS NOT NULL) = $1)
+              AND ($2::TEXT IS NULL OR email ILIKE $2)
+            "#,
+        )
+        .bind(params.suspended)
+        .bind(&email_pattern)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok((users, total.0))
+    }
+
+    /// Count accounts by status, role and tier (admin operation)
+    pub async fn stats(&self) -> Result<UserStats, sqlx::Error> {
+        sqlx::query_as::<_, UserStats>(
+            r#"
+            SELECT COUNT(*) AS total,
+                   COUNT(*) FILTER (WHERE suspended_at IS NOT NULL) AS suspended,
+                   COUNT(*) FILTER (WHERE role = 'admin') AS admins,
+                   COUNT(*) FILTER (WHERE tier = 1) AS tier1,
+                   COUNT(*) FILTER (WHERE tier = 2) AS tier2,
+                   COUNT(*) FILTER (WHERE tier = 3) AS tier3
+            FROM users
+            WHERE deleted_at IS NULL
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -261,6 +458,14 @@ mod tests {
         assert_eq!(i16::from(TrustTier::Tier3), 3);
     }
 
+    #[test]
+    fn test_user_role_conversion() {
+        assert_eq!(UserRole::from("admin".to_string()), UserRole::Admin);
+        assert_eq!(UserRole::from("user".to_string()), UserRole::User);
+        assert_eq!(UserRole::from("unknown".to_string()), UserRole::User);
+        assert_eq!(UserRole::Admin.as_str(), "admin");
+    }
+
     #[test]
     fn test_user_response_from_user() {
         let user = User {
@@ -270,6 +475,9 @@ mod tests {
             name: Some("Test User".to_string()),
             avatar_url: None,
             tier: TrustTier::Tier1,
+            role: UserRole::User,
+            organization_id: None,
+            suspended_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -0,0 +1,216 @@
+//! Verification counter entity and repository
+//!
+//! Counts verifications of stored seals by anyone but their owner, with a
+//! coarse origin (site and country, never the IP address or the user), so
+//! owners can see how far their content travels. Only the owning
+//! [`Tenant`] reads a seal's counters.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{Tenant, TenantConnection, VerificationOutcome};
+
+/// Coarse origin of a verification request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerificationOrigin {
+    /// Host of the site the request came from (`Origin` or `Referer` header)
+    pub site: Option<String>,
+    /// ISO 3166-1 alpha-2 country reported by the CDN
+    pub country: Option<String>,
+}
+
+/// Verifications of a seal on one day (UTC)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct DailyVerifications {
+    /// Day (UTC)
+    #[schema(value_type = String, example = "2026-01-08")]
+    pub day: NaiveDate,
+    /// Verifications
+    pub verifications: i64,
+    /// Verifications that found the content tampered
+    pub tampered: i64,
+}
+
+/// Verifications of a seal from one site or country
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct OriginCount {
+    /// Site host or country code
+    #[schema(example = "news.example.com")]
+    pub origin: String,
+    /// Verifications
+    pub verifications: i64,
+}
+
+/// Repository for verification counter operations
+#[derive(Clone)]
+pub struct VerificationRepository {
+    pool: PgPool,
+    tenant_rls: bool,
+}
+
+impl VerificationRepository {
+    /// Create a new verification repository
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            tenant_rls: false,
+        }
+    }
+
+    /// Run tenant queries under row-level security, and the others as
+    /// system paths bypassing it (`TENANT_RLS`)
+    pub fn with_tenant_rls(mut self, tenant_rls: bool) -> Self {
+        self.tenant_rls = tenant_rls;
+        self
+    }
+
+    /// Record a verification and bump the seal's `verification_count`
+    pub async fn record(
+        &self,
+        seal_id: Uuid,
+        outcome: VerificationOutcome,
+        origin: &VerificationOrigin,
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = TenantConnection::system(&self.pool, self.tenant_rls).await?;
+        sqlx::query(
+            r#"
+            WITH inserted AS (
+                INSERT INTO verifications (seal_id, outcome, site, country)
+                VALUES ($1, $2, $3, $4)
+                RETURNING seal_id
+            )
+            UPDATE seals
+            SET verification_count = verification_count + 1
+            WHERE id = (SELECT seal_id FROM inserted)
+            "#,
+        )
+        .bind(seal_id)
+        .bind(outcome.as_str())
+        .bind(origin.site.as_deref())
+        .bind(origin.country.as_deref())
+        .execute(&mut *conn)
+        .await?;
+        conn.finish().await?;
+        Ok(())
+    }
+
+    /// Verifications of a tenant's seal per day over the last `days` days,
+    /// oldest first, including days without verifications
+    pub async fn daily(
+        &self,
+        seal_id: Uuid,
+        tenant: &Tenant,
+        days: i32,
+    ) -> Result<Vec<DailyVerifications>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, DailyVerifications>(
+            r#"
+            WITH days AS (
+                SELECT generate_series(
+                    (NOW() AT TIME ZONE 'UTC')::DATE - ($2 - 1),
+                    (NOW() AT TIME ZONE 'UTC')::DATE,
+                    INTERVAL '1 day'
+                )::DATE AS day
+            ),
+            counts AS (
+                SELECT (created_at AT TIME ZONE 'UTC')::DATE AS day,
+                       COUNT(*) AS verifications,
+                       COUNT(*) FILTER (WHERE outcome = 'tampered') AS tampered
+                FROM verifications
+                WHERE seal_id = $1 AND created_at >= NOW() - make_interval(days => $2)
+                  AND EXISTS (
+                      SELECT 1 FROM seals s
+                      WHERE s.id = $1 AND s.user_id = $3
+                        AND (s.organization_id IS NULL OR s.organization_id = $4)
+                  )
+                GROUP BY 1
+            )
+            SELECT days.day,
+                   COALESCE(counts.verifications, 0) AS verifications,
+                   COALESCE(counts.tampered, 0) AS tampered
+            FROM days
+            LEFT JOIN counts ON counts.day = days.day
+            ORDER BY days.day
+            "#,
+        )
+        .bind(seal_id)
+        .bind(days)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Sites a tenant's seal was verified from over the last `days` days,
+    /// most verifications first
+    pub async fn top_sites(
+        &self,
+        seal_id: Uuid,
+        tenant: &Tenant,
+        days: i32,
+        limit: i64,
+    ) -> Result<Vec<OriginCount>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, OriginCount>(
+            r#"
+            SELECT site AS origin, COUNT(*) AS verifications
+            FROM verifications
+            WHERE seal_id = $1 AND site IS NOT NULL
+              AND created_at >= NOW() - make_interval(days => $2)
+              AND EXISTS (
+                  SELECT 1 FROM seals s
+                  WHERE s.id = $1 AND s.user_id = $4
+                    AND (s.organization_id IS NULL OR s.organization_id = $5)
+              )
+            GROUP BY site
+            ORDER BY verifications DESC, origin
+            LIMIT $3
+            "#,
+        )
+        .bind(seal_id)
+        .bind(days)
+        .bind(limit)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Countries a tenant's seal was verified from over the last `days` days,
+    /// most verifications first
+    pub async fn top_countries(
+        &self,
+        seal_id: Uuid,
+        tenant: &Tenant,
+        days: i32,
+        limit: i64,
+    ) -> Result<Vec<OriginCount>, sqlx::Error> {
+        let mut conn = TenantConnection::acquire(&self.pool, tenant, self.tenant_rls).await?;
+        sqlx::query_as::<_, OriginCount>(
+            r#"
+            SELECT country AS origin, COUNT(*) AS verifications
+            FROM verifications
+            WHERE seal_id = $1 AND country IS NOT NULL
+              AND created_at >= NOW() - make_interval(days => $2)
+              AND EXISTS (
+                  SELECT 1 FROM seals s
+                  WHERE s.id = $1 AND s.user_id = $4
+                    AND (s.organization_id IS NULL OR s.organization_id = $5)
+              )
+            GROUP BY country
+            ORDER BY verifications DESC, origin
+            LIMIT $3
+            "#,
+        )
+        .bind(seal_id)
+        .bind(days)
+        .bind(limit)
+        .bind(tenant.user_id)
+        .bind(tenant.organization_id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+}
@@ -7,7 +7,20 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use thiserror::Error;
+use utoipa::ToSchema;
+
+/// Body of every error response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Message safe to show to the client
+    #[schema(example = "Seal not found")]
+    pub error: String,
+    /// Stable machine-readable code
+    #[schema(example = "NOT_FOUND")]
+    pub code: String,
+}
 
 /// API error type with structured variants for different error categories
 #[derive(Debug, Error)]
@@ -20,10 +33,38 @@ pub enum ApiError {
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    /// Forbidden - authenticated but not allowed (suspended account, missing role)
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     /// Not found - requested resource does not exist
     #[error("Not found: {0}")]
     NotFound(String),
 
+    /// Conflict - request conflicts with the current state of the resource
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// Payload too large - upload exceeds a size limit of the upload policy
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    /// Unsupported media type - upload Content-Type not allowed by the upload policy
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    /// Image too large - image dimensions exceed the upload policy's decoding limits
+    #[error("Image too large: {0}")]
+    ImageTooLarge(String),
+
+    /// Quota exceeded - the account used up its monthly seal quota
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    /// Rate limited - the client spent its request budget for now
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
     /// Request timeout - operation took too long
     #[error("Request timeout: {0}")]
     Timeout(String),
@@ -36,6 +77,10 @@ pub enum ApiError {
     #[error("Service unavailable: {0}")]
     ServiceUnavailable(String),
 
+    /// Read-only - the server is in maintenance mode and refuses writes
+    #[error("Read-only: {0}")]
+    ReadOnly(String),
+
     /// Authentication error with specific error code
     #[error("{message}")]
     AuthError { message: String, code: String },
@@ -56,11 +101,46 @@ impl ApiError {
         Self::Unauthorized(message.into())
     }
 
+    /// Create a forbidden error
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::Forbidden(message.into())
+    }
+
     /// Create a not found error
     pub fn not_found(message: impl Into<String>) -> Self {
         Self::NotFound(message.into())
     }
 
+    /// Create a conflict error
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict(message.into())
+    }
+
+    /// Create a payload too large error
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::PayloadTooLarge(message.into())
+    }
+
+    /// Create an unsupported media type error
+    pub fn unsupported_media_type(message: impl Into<String>) -> Self {
+        Self::UnsupportedMediaType(message.into())
+    }
+
+    /// Create an image too large error
+    pub fn image_too_large(message: impl Into<String>) -> Self {
+        Self::ImageTooLarge(message.into())
+    }
+
+    /// Create a quota exceeded error
+    pub fn quota_exceeded(message: impl Into<String>) -> Self {
+        Self::QuotaExceeded(message.into())
+    }
+
+    /// Create a rate limited error
+    pub fn rate_limited(message: impl Into<String>) -> Self {
+        Self::RateLimited(message.into())
+    }
+
     /// Create an internal server error
     pub fn internal(message: impl Into<String>) -> Self {
         Self::Internal(message.into())
@@ -76,6 +156,11 @@ impl ApiError {
         Self::ServiceUnavailable(message.into())
     }
 
+    /// Create a read-only maintenance error
+    pub fn read_only(message: impl Into<String>) -> Self {
+        Self::ReadOnly(message.into())
+    }
+
     /// Create an authentication error with a specific error code
     pub fn auth_error(code: impl Into<String>, message: impl Into<String>) -> Self {
         Self::AuthError {
@@ -89,14 +174,21 @@ impl ApiError {
         match self {
             Self::BadRequest(_) => StatusCode::BAD_REQUEST,
             Self::Unauthorized(_) | Self::AuthError { .. } => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::ImageTooLarge(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::QuotaExceeded(_) | Self::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
             Self::Timeout(_) => StatusCode::REQUEST_TIMEOUT,
             Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::ServiceUnavailable(_) | Self::ReadOnly(_) => StatusCode::SERVICE_UNAVAILABLE,
             Self::Veritas(ref e) => match e {
                 // External service failures → 503
                 veritas_core::VeritasError::QrngError(_)
-                | veritas_core::VeritasError::HttpError(_) => StatusCode::SERVICE_UNAVAILABLE,
+                | veritas_core::VeritasError::HttpError(_)
+                | veritas_core::VeritasError::AnchorError(_) => StatusCode::SERVICE_UNAVAILABLE,
 
                 // Verification failures → 422 Unprocessable Entity
                 veritas_core::VeritasError::VerificationFailed(_)
@@ -108,7 +200,10 @@ impl ApiError {
                 veritas_core::VeritasError::InvalidSeal(_)
                 | veritas_core::VeritasError::UnsupportedSealVersion(_, _)
                 | veritas_core::VeritasError::SealTooLarge { .. }
-                | veritas_core::VeritasError::InvalidTimestamp { .. } => StatusCode::BAD_REQUEST,
+                | veritas_core::VeritasError::InvalidTimestamp { .. }
+                | veritas_core::VeritasError::InvalidDocument(_)
+                | veritas_core::VeritasError::InvalidDid(_)
+                | veritas_core::VeritasError::WatermarkError(_) => StatusCode::BAD_REQUEST,
 
                 // Internal processing failures → 500
                 veritas_core::VeritasError::SignatureError(_)
@@ -121,15 +216,23 @@ impl ApiError {
     }
 
     /// Get the error code for programmatic error handling
-    fn error_code(&self) -> &'static str {
+    pub(crate) fn error_code(&self) -> &'static str {
         match self {
             Self::BadRequest(_) => "INVALID_INPUT",
             Self::Unauthorized(_) => "UNAUTHORIZED",
             Self::AuthError { .. } => "AUTH_ERROR",
+            Self::Forbidden(_) => "FORBIDDEN",
             Self::NotFound(_) => "NOT_FOUND",
+            Self::Conflict(_) => "CONFLICT",
+            Self::PayloadTooLarge(_) => "FILE_TOO_LARGE",
+            Self::UnsupportedMediaType(_) => "UNSUPPORTED_MEDIA_TYPE",
+            Self::ImageTooLarge(_) => "IMAGE_TOO_LARGE",
+            Self::QuotaExceeded(_) => "QUOTA_EXCEEDED",
+            Self::RateLimited(_) => "RATE_LIMITED",
             Self::Timeout(_) => "TIMEOUT",
             Self::Internal(_) => "INTERNAL_ERROR",
             Self::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
+            Self::ReadOnly(_) => "READ_ONLY",
             Self::Veritas(ref e) => match e {
                 veritas_core::VeritasError::QrngError(_) => "QRNG_UNAVAILABLE",
                 veritas_core::VeritasError::HttpError(_) => "UPSTREAM_ERROR",
@@ -143,15 +246,19 @@ impl ApiError {
                 }
                 veritas_core::VeritasError::SealTooLarge { .. } => "SEAL_TOO_LARGE",
                 veritas_core::VeritasError::InvalidTimestamp { .. } => "INVALID_TIMESTAMP",
+                veritas_core::VeritasError::InvalidDocument(_) => "INVALID_DOCUMENT",
+                veritas_core::VeritasError::AnchorError(_) => "ANCHOR_ERROR",
+                veritas_core::VeritasError::InvalidDid(_) => "INVALID_DID",
                 veritas_core::VeritasError::SignatureError(_) => "SIGNATURE_ERROR",
                 veritas_core::VeritasError::SerializationError(_) => "SERIALIZATION_ERROR",
                 veritas_core::VeritasError::PerceptualHashError(_) => "PERCEPTUAL_HASH_ERROR",
+                veritas_core::VeritasError::WatermarkError(_) => "WATERMARK_ERROR",
             },
         }
     }
 
     /// Get sanitized error message for client response
-    fn client_message(&self) -> String {
+    pub(crate) fn client_message(&self) -> String {
         match self {
             // For Veritas errors, sanitize internal details
             Self::Veritas(ref e) => match e {
@@ -173,6 +280,11 @@ impl ApiError {
                 veritas_core::VeritasError::InvalidTimestamp { .. } => {
                     "Invalid timestamp".to_string()
                 }
+                veritas_core::VeritasError::InvalidDocument(_) => "Invalid document".to_string(),
+                veritas_core::VeritasError::AnchorError(_) => {
+                    "Anchor verification failed".to_string()
+                }
+                veritas_core::VeritasError::InvalidDid(_) => "Invalid DID".to_string(),
                 veritas_core::VeritasError::SignatureError(_) => {
                     "Signature operation failed".to_string()
                 }
@@ -182,6 +294,9 @@ impl ApiError {
                 veritas_core::VeritasError::PerceptualHashError(_) => {
                     "Perceptual hash computation failed".to_string()
                 }
+                veritas_core::VeritasError::WatermarkError(_) => {
+                    "Watermark embedding failed".to_string()
+                }
             },
             // For other errors, use the Display message
             _ => self.to_string(),
@@ -194,10 +309,18 @@ impl ApiError {
             Self::BadRequest(_) => "bad_request",
             Self::Unauthorized(_) => "unauthorized",
             Self::AuthError { .. } => "auth_error",
+            Self::Forbidden(_) => "forbidden",
             Self::NotFound(_) => "not_found",
+            Self::Conflict(_) => "conflict",
+            Self::PayloadTooLarge(_) | Self::UnsupportedMediaType(_) | Self::ImageTooLarge(_) => {
+                "upload_rejected"
+            }
+            Self::QuotaExceeded(_) => "quota_exceeded",
+            Self::RateLimited(_) => "rate_limited",
             Self::Timeout(_) => "timeout",
             Self::Internal(_) => "internal",
             Self::ServiceUnavailable(_) => "service_unavailable",
+            Self::ReadOnly(_) => "read_only",
             Self::Veritas(_) => "veritas",
         }
     }
@@ -213,7 +336,14 @@ impl IntoResponse for ApiError {
 
         // Log based on severity, always including internal details
         match &self {
-            Self::BadRequest(_) | Self::NotFound(_) => {
+            Self::BadRequest(_)
+            | Self::NotFound(_)
+            | Self::Conflict(_)
+            | Self::PayloadTooLarge(_)
+            | Self::UnsupportedMediaType(_)
+            | Self::ImageTooLarge(_)
+            | Self::QuotaExceeded(_)
+            | Self::RateLimited(_) => {
                 tracing::warn!(
                     status = %status,
                     category = category,
@@ -222,7 +352,7 @@ impl IntoResponse for ApiError {
                     "Client error"
                 );
             }
-            Self::Unauthorized(_) | Self::AuthError { .. } => {
+            Self::Unauthorized(_) | Self::AuthError { .. } | Self::Forbidden(_) => {
                 tracing::warn!(
                     status = %status,
                     category = category,
@@ -231,7 +361,7 @@ impl IntoResponse for ApiError {
                     "Authentication error"
                 );
             }
-            Self::ServiceUnavailable(_) => {
+            Self::ServiceUnavailable(_) | Self::ReadOnly(_) => {
                 tracing::warn!(
                     status = %status,
                     category = category,
@@ -263,10 +393,10 @@ impl IntoResponse for ApiError {
         }
 
         // All error responses include a `code` field for programmatic error handling
-        let body = serde_json::json!({
-            "error": client_message,
-            "code": code,
-        });
+        let body = ErrorResponse {
+            error: client_message,
+            code: code.to_string(),
+        };
 
         (status, Json(body)).into_response()
     }
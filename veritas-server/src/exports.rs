@@ -0,0 +1,442 @@
+//! Personal data and seal exports
+//!
+//! Backs `/api/v1/users/me/export` (GDPR data portability, a tar archive)
+//! and `/api/v1/seals/export-batch` (selected seals, a zip archive). An
+//! export is built in the background into an archive under the export
+//! directory; once ready it is served through a download link signed with a
+//! per-process secret, so the link works without a session token (e.g. from
+//! an email or a download manager). Export state lives in memory: archives
+//! and links do not survive a restart, and exports older than the TTL are
+//! purged with their archive.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use sha3::{Digest, Sha3_256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+/// Default time an export stays downloadable (24 hours).
+pub const DEFAULT_EXPORT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How often expired exports are swept.
+pub const EXPORT_SWEEP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Progress of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStatus {
+    /// The archive is being built
+    Pending,
+    /// The archive can be downloaded
+    Ready,
+    /// Building the archive failed
+    Failed,
+}
+
+/// What an export contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    /// Everything stored about the user, as a tar archive
+    UserData,
+    /// Selected seals with their reports, as a zip archive
+    Seals,
+}
+
+impl ExportKind {
+    /// File extension of the archive
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::UserData => "tar",
+            Self::Seals => "zip",
+        }
+    }
+
+    /// Media type of the archive
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::UserData => "application/x-tar",
+            Self::Seals => "application/zip",
+        }
+    }
+}
+
+/// One export of a user's data or seals
+#[derive(Debug, Clone)]
+pub struct DataExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: ExportKind,
+    pub status: ExportStatus,
+    /// Archive size in bytes, once ready
+    pub size_bytes: Option<u64>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Registry of data exports, shared across requests.
+pub struct ExportStore {
+    exports: DashMap<Uuid, DataExport>,
+    dir: PathBuf,
+    ttl: Duration,
+    secret: [u8; 32],
+}
+
+impl ExportStore {
+    /// Create a store writing archives under `dir`, with a random link
+    /// signing secret.
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        let mut secret = [0u8; 32];
+        secret[..16].copy_from_slice(Uuid::new_v4().as_bytes());
+        secret[16..].copy_from_slice(Uuid::new_v4().as_bytes());
+        Self {
+            exports: DashMap::new(),
+            dir,
+            ttl,
+            secret,
+        }
+    }
+
+    /// File holding the archive of an export.
+    pub fn archive_path(&self, export: &DataExport) -> PathBuf {
+        self.dir
+            .join(format!("{}.{}", export.id, export.kind.extension()))
+    }
+
+    /// Return the user's pending or ready data export, or register a new
+    /// pending one. The flag is true when the export is new and must be built.
+    pub fn start(&self, user_id: Uuid) -> (DataExport, bool) {
+        let now = Utc::now();
+        let existing = self
+            .exports
+            .iter()
+            .filter(|entry| {
+                entry.user_id == user_id
+                    && entry.kind == ExportKind::UserData
+                    && entry.status != ExportStatus::Failed
+                    && entry.expires_at > now
+            })
+            .max_by_key(|entry| entry.created_at)
+            .map(|entry| entry.value().clone());
+        if let Some(export) = existing {
+            return (export, false);
+        }
+
+        (self.register(user_id, ExportKind::UserData), true)
+    }
+
+    /// Register a new pending seal export, or return `None` while another
+    /// seal export of the user is being built.
+    pub fn start_seals(&self, user_id: Uuid) -> Option<DataExport> {
+        let now = Utc::now();
+        let running = self.exports.iter().any(|entry| {
+            entry.user_id == user_id
+                && entry.kind == ExportKind::Seals
+                && entry.status == ExportStatus::Pending
+                && entry.expires_at > now
+        });
+        (!running).then(|| self.register(user_id, ExportKind::Seals))
+    }
+
+    fn register(&self, user_id: Uuid, kind: ExportKind) -> DataExport {
+        let now = Utc::now();
+        let export = DataExport {
+            id: Uuid::new_v4(),
+            user_id,
+            kind,
+            status: ExportStatus::Pending,
+            size_bytes: None,
+            created_at: now,
+            expires_at: now + self.ttl,
+        };
+        self.exports.insert(export.id, export.clone());
+        export
+    }
+
+    /// Look up an unexpired export.
+    pub fn get(&self, id: Uuid) -> Option<DataExport> {
+        self.exports
+            .get(&id)
+            .map(|entry| entry.value().clone())
+            .filter(|export| export.expires_at > Utc::now())
+    }
+
+    /// Forget a failed data export of the user and return it, so the failure
+    /// is reported once and the next request starts over.
+    pub fn take_failed(&self, user_id: Uuid) -> Option<DataExport> {
+        let id = self
+            .exports
+            .iter()
+            .find(|entry| {
+                entry.user_id == user_id
+                    && entry.kind == ExportKind::UserData
+                    && entry.status == ExportStatus::Failed
+            })
+            .map(|entry| *entry.key())?;
+        self.exports.remove(&id).map(|(_, export)| export)
+    }
+
+    /// Write the archive of a pending export and mark it ready.
+    ///
+    /// `files` are archive paths and contents. On error the export is marked
+    /// failed and any partial archive deleted.
+    pub async fn complete(&self, id: Uuid, files: Vec<(String, Vec<u8>)>) -> Result<(), ApiError> {
+        let Some(export) = self.exports.get(&id).map(|entry| entry.value().clone()) else {
+            return Err(ApiError::not_found("Export not found"));
+        };
+        let path = self.archive_path(&export);
+        let result = match tokio::fs::create_dir_all(&self.dir).await {
+            Ok(()) => {
+                let archive = path.clone();
+                tokio::task::spawn_blocking(move || match export.kind {
+                    ExportKind::UserData => write_tar(&archive, &files),
+                    ExportKind::Seals => write_zip(&archive, &files),
+                })
+                .await
+                .unwrap_or_else(|e| Err(std::io::Error::other(e)))
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(size) => {
+                if let Some(mut export) = self.exports.get_mut(&id) {
+                    export.status = ExportStatus::Ready;
+                    export.size_bytes = Some(size);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                self.fail(id);
+                let _ = tokio::fs::remove_file(&path).await;
+                Err(io_error("write export archive", e))
+            }
+        }
+    }
+
+    /// Mark an export failed.
+    pub fn fail(&self, id: Uuid) {
+        if let Some(mut export) = self.exports.get_mut(&id) {
+            export.status = ExportStatus::Failed;
+        }
+    }
+
+    /// Signature of a download link for an export, valid until `expires`
+    /// (Unix seconds): hex SHA3-256(secret || export id || expires).
+    pub fn sign(&self, id: Uuid, expires: i64) -> String {
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.secret);
+        hasher.update(id.as_bytes());
+        hasher.update(expires.to_be_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Check a download link signature (constant time) and its expiry.
+    pub fn verify_link(&self, id: Uuid, expires: i64, signature: &str) -> bool {
+        let expected = self.sign(id, expires);
+        let matches = expected.len() == signature.len()
+            && expected
+                .bytes()
+                .zip(signature.bytes())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0;
+        matches && expires > Utc::now().timestamp()
+    }
+
+    /// Signed download path of an export, valid until the export expires.
+    pub fn download_path(&self, export: &DataExport) -> String {
+        let expires = export.expires_at.timestamp();
+        format!(
+            "/api/v1/exports/{}/download?expires={}&signature={}",
+            export.id,
+            expires,
+            self.sign(export.id, expires)
+        )
+    }
+
+    /// Delete expired exports and their archives, returning how many were purged.
+    pub async fn purge_expired(&self) -> usize {
+        let now = Utc::now();
+        let mut expired = Vec::new();
+        self.exports.retain(|_, export| {
+            let keep = export.expires_at > now;
+            if !keep {
+                expired.push(export.clone());
+            }
+            keep
+        });
+
+        for export in &expired {
+            if let Err(e) = tokio::fs::remove_file(self.archive_path(export)).await {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!(
+                        export_id = %export.id,
+                        error = %e,
+                        "Failed to delete export archive"
+                    );
+                }
+            }
+        }
+        expired.len()
+    }
+}
+
+/// Write `files` to a tar archive at `path`, returning its size.
+fn write_tar(path: &Path, files: &[(String, Vec<u8>)]) -> std::io::Result<u64> {
+    let mtime = Utc::now().timestamp().max(0) as u64;
+    let mut builder = tar::Builder::new(std::fs::File::create(path)?);
+    for (name, data) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        builder.append_data(&mut header, name, data.as_slice())?;
+    }
+    let file = builder.into_inner()?;
+    file.sync_all()?;
+    Ok(file.metadata()?.len())
+}
+
+/// Write `files` to a deflated zip archive at `path`, returning its size.
+fn write_zip(path: &Path, files: &[(String, Vec<u8>)]) -> std::io::Result<u64> {
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    for (name, data) in files {
+        zip.start_file(name.as_str(), options)
+            .map_err(std::io::Error::other)?;
+        zip.write_all(data)?;
+    }
+    let file = zip.finish().map_err(std::io::Error::other)?;
+    file.sync_all()?;
+    Ok(file.metadata()?.len())
+}
+
+fn io_error(action: &str, error: std::io::Error) -> ApiError {
+    tracing::error!(error = %error, "Failed to {}", action);
+    ApiError::internal("Export storage error")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(ttl: Duration) -> (tempfile::TempDir, ExportStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ExportStore::new(dir.path().to_path_buf(), ttl);
+        (dir, store)
+    }
+
+    #[tokio::test]
+    async fn test_export_lifecycle() {
+        let (_dir, store) = store(DEFAULT_EXPORT_TTL);
+        let user_id = Uuid::new_v4();
+
+        let (export, created) = store.start(user_id);
+        assert!(created);
+        assert_eq!(export.status, ExportStatus::Pending);
+        let (again, created) = store.start(user_id);
+        assert!(!created);
+        assert_eq!(again.id, export.id);
+
+        let files = vec![("user.json".to_string(), b"{}".to_vec())];
+        store.complete(export.id, files).await.unwrap();
+        let ready = store.get(export.id).unwrap();
+        assert_eq!(ready.status, ExportStatus::Ready);
+        let size = std::fs::metadata(store.archive_path(&ready)).unwrap().len();
+        assert_eq!(ready.size_bytes, Some(size));
+
+        let mut archive =
+            tar::Archive::new(std::fs::File::open(store.archive_path(&ready)).unwrap());
+        let names: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .collect();
+        assert_eq!(names, ["user.json"]);
+    }
+
+    #[tokio::test]
+    async fn test_seal_export_is_a_zip() {
+        let (_dir, store) = store(DEFAULT_EXPORT_TTL);
+        let user_id = Uuid::new_v4();
+
+        let export = store.start_seals(user_id).unwrap();
+        assert_eq!(export.kind, ExportKind::Seals);
+        // One seal export at a time, independent of data exports
+        assert!(store.start_seals(user_id).is_none());
+        assert!(store.start(user_id).1);
+
+        let files = vec![
+            ("manifest.json".to_string(), b"{}".to_vec()),
+            ("seals/a.veritas".to_string(), vec![0xa5; 64]),
+        ];
+        store.complete(export.id, files).await.unwrap();
+        let ready = store.get(export.id).unwrap();
+        assert_eq!(ready.status, ExportStatus::Ready);
+        assert!(store
+            .archive_path(&ready)
+            .ends_with(format!("{}.zip", export.id)));
+
+        let archive =
+            zip::ZipArchive::new(std::fs::File::open(store.archive_path(&ready)).unwrap()).unwrap();
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        assert_eq!(names, ["manifest.json", "seals/a.veritas"]);
+
+        // Finished: the next seal export can start
+        assert!(store.start_seals(user_id).is_some());
+    }
+
+    #[test]
+    fn test_failed_export_is_reported_once() {
+        let (_dir, store) = store(DEFAULT_EXPORT_TTL);
+        let user_id = Uuid::new_v4();
+        let (export, _) = store.start(user_id);
+        store.fail(export.id);
+
+        let (retry, created) = store.start(user_id);
+        assert!(created);
+        assert_ne!(retry.id, export.id);
+        assert_eq!(store.take_failed(user_id).unwrap().id, export.id);
+        assert!(store.take_failed(user_id).is_none());
+    }
+
+    #[test]
+    fn test_download_link_signature() {
+        let (_dir, store) = store(DEFAULT_EXPORT_TTL);
+        let id = Uuid::new_v4();
+        let expires = Utc::now().timestamp() + 60;
+        let signature = store.sign(id, expires);
+
+        assert!(store.verify_link(id, expires, &signature));
+        assert!(!store.verify_link(id, expires + 1, &signature));
+        assert!(!store.verify_link(Uuid::new_v4(), expires, &signature));
+        assert!(!store.verify_link(id, expires, &signature[1..]));
+
+        // Links from another process (another secret) are rejected
+        let (_other_dir, other) = self::store(DEFAULT_EXPORT_TTL);
+        assert!(!other.verify_link(id, expires, &signature));
+
+        // Expired links are rejected even when correctly signed
+        let past = Utc::now().timestamp() - 1;
+        assert!(!store.verify_link(id, past, &store.sign(id, past)));
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired_removes_archive() {
+        let (_dir, store) = store(Duration::ZERO);
+        let (export, _) = store.start(Uuid::new_v4());
+        store.complete(export.id, Vec::new()).await.unwrap();
+
+        assert!(store.get(export.id).is_none());
+        assert_eq!(store.purge_expired().await, 1);
+        assert!(!store.archive_path(&export).exists());
+    }
+}
@@ -0,0 +1,165 @@
+//! Geohash buckets for the seal map
+//!
+//! A geohash of `precision` characters encodes `5 * precision` bits that
+//! alternate between longitude and latitude, so its cells form a regular
+//! grid of the lng/lat plane. The database groups located seals by grid cell
+//! (`floor((lng + 180) / width)`, `floor((lat + 90) / height)`) and this
+//! module names the cells, so bucketing never needs a geo extension.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Smallest supported precision (cells of about 5000 km)
+pub const MIN_PRECISION: u8 = 1;
+
+/// Largest supported precision (cells of about 40 m)
+pub const MAX_PRECISION: u8 = 8;
+
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// The grid of geohash cells of one precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeohashGrid {
+    precision: u8,
+}
+
+/// Extent of a cell, in degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+pub struct CellBounds {
+    #[schema(example = 48.8232421875)]
+    pub south: f64,
+    #[schema(example = 2.3291015625)]
+    pub west: f64,
+    #[schema(example = 48.8671875)]
+    pub north: f64,
+    #[schema(example = 2.373046875)]
+    pub east: f64,
+}
+
+impl GeohashGrid {
+    /// Grid of `precision`-character geohashes, or `None` outside
+    /// [`MIN_PRECISION`]..=[`MAX_PRECISION`]
+    pub fn new(precision: u8) -> Option<Self> {
+        (MIN_PRECISION..=MAX_PRECISION)
+            .contains(&precision)
+            .then_some(Self { precision })
+    }
+
+    /// Characters of the geohashes
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    fn lng_bits(&self) -> u32 {
+        (5 * u32::from(self.precision)).div_ceil(2)
+    }
+
+    fn lat_bits(&self) -> u32 {
+        5 * u32::from(self.precision) / 2
+    }
+
+    /// Width of a cell, in degrees of longitude
+    pub fn cell_width(&self) -> f64 {
+        360.0 / self.columns() as f64
+    }
+
+    /// Height of a cell, in degrees of latitude
+    pub fn cell_height(&self) -> f64 {
+        180.0 / self.rows() as f64
+    }
+
+    /// Number of columns of the grid
+    pub fn columns(&self) -> i64 {
+        1 << self.lng_bits()
+    }
+
+    /// Number of rows of the grid
+    pub fn rows(&self) -> i64 {
+        1 << self.lat_bits()
+    }
+
+    /// Column and row of the cell containing a point
+    pub fn cell_of(&self, lat: f64, lng: f64) -> (i64, i64) {
+        (
+            ((lng + 180.0) / self.cell_width()).floor() as i64,
+            ((lat + 90.0) / self.cell_height()).floor() as i64,
+        )
+    }
+
+    /// Geohash of the cell at column `x` and row `y`.
+    ///
+    /// Indices past the grid (points on the east edge or the north pole)
+    /// are clamped to its last cell.
+    pub fn geohash(&self, x: i64, y: i64) -> String {
+        let (lng_bits, lat_bits) = (self.lng_bits(), self.lat_bits());
+        let x = x.clamp(0, self.columns() - 1) as u64;
+        let y = y.clamp(0, self.rows() - 1) as u64;
+
+        let mut hash = String::with_capacity(usize::from(self.precision));
+        let mut value = 0;
+        for i in 0..5 * u32::from(self.precision) {
+            // Even bits are longitude, odd bits latitude, most significant first
+            let bit = if i % 2 == 0 {
+                (x >> (lng_bits - 1 - i / 2)) & 1
+            } else {
+                (y >> (lat_bits - 1 - i / 2)) & 1
+            };
+            value = (value << 1) | bit as usize;
+            if i % 5 == 4 {
+                hash.push(BASE32[value] as char);
+                value = 0;
+            }
+        }
+        hash
+    }
+
+    /// Extent of the cell at column `x` and row `y`
+    pub fn bounds(&self, x: i64, y: i64) -> CellBounds {
+        let (width, height) = (self.cell_width(), self.cell_height());
+        let x = x.clamp(0, self.columns() - 1) as f64;
+        let y = y.clamp(0, self.rows() - 1) as f64;
+        CellBounds {
+            south: y * height - 90.0,
+            west: x * width - 180.0,
+            north: (y + 1.0) * height - 90.0,
+            east: (x + 1.0) * width - 180.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(lat: f64, lng: f64, precision: u8) -> String {
+        let grid = GeohashGrid::new(precision).unwrap();
+        let (x, y) = grid.cell_of(lat, lng);
+        grid.geohash(x, y)
+    }
+
+    #[test]
+    fn test_known_geohashes() {
+        assert_eq!(encode(48.8566, 2.3522, 6), "u09tvw");
+        assert_eq!(encode(40.6892, -74.0445, 7), "dr5r7p4");
+        assert_eq!(encode(-33.8568, 151.2153, 5), "r3gx2");
+        assert_eq!(encode(-90.0, -180.0, 2), "00");
+        // The north-east corner belongs to the last cell
+        assert_eq!(encode(90.0, 180.0, 3), "zzz");
+    }
+
+    #[test]
+    fn test_grid_cells() {
+        assert!(GeohashGrid::new(0).is_none());
+        assert!(GeohashGrid::new(MAX_PRECISION + 1).is_none());
+
+        let grid = GeohashGrid::new(5).unwrap();
+        assert!((grid.cell_width() - 0.043_945_312_5).abs() < 1e-12);
+        assert!((grid.cell_height() - 0.043_945_312_5).abs() < 1e-12);
+
+        let (x, y) = grid.cell_of(48.8566, 2.3522);
+        let bounds = grid.bounds(x, y);
+        assert!(bounds.south <= 48.8566 && 48.8566 < bounds.north);
+        assert!(bounds.west <= 2.3522 && 2.3522 < bounds.east);
+        assert!((bounds.east - bounds.west - grid.cell_width()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,989 @@
+//! Admin handlers
+//!
+//! The `/api/v1/admin` namespace, restricted to accounts with the admin role:
+//! platform statistics, user management (suspension, trust tier,
+//! organization), seal revocation and legal hold, signing-key revocation and
+//! read-only maintenance mode. Every change is logged with the acting
+//! administrator.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::auth::AdminUser;
+use crate::db::{
+    AnchorLatencyStats, AuditEvent, AuditLogRepository, DailySealStats, QrngSourceStats,
+    RetentionPolicy, Seal, SealListResponse, SealRecord, SealRepository, SealTotals, TrustTier,
+    TrustTierStats, User, UserListParams, UserRepository, UserResponse, UserRole, UserStats,
+};
+use crate::error::ApiError;
+use crate::handlers::signing_keys::SigningKeyRecord;
+use crate::handlers::AppState;
+use crate::janitor::TaskStats;
+use crate::maintenance::MaintenanceStatus;
+
+/// Default statistics window in days
+pub const DEFAULT_STATS_DAYS: i32 = 30;
+
+/// Longest statistics window in days
+pub const MAX_STATS_DAYS: i32 = 365;
+
+/// Query parameters for platform statistics
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AdminStatsQuery {
+    /// Number of days covered by the per-day and per-source figures
+    #[param(default = 30, minimum = 1, maximum = 365)]
+    pub days: Option<i32>,
+}
+
+/// Platform statistics
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminStatsResponse {
+    /// Number of days covered by `seals_per_day`, `qrng_sources`,
+    /// `trust_tiers`, `average_file_size_bytes`, `anchor_latency`,
+    /// `failure_rate` and `mock_entropy_seals`
+    pub days: i32,
+    /// Account counts
+    pub users: UserStats,
+    /// Platform-wide seal counts
+    pub seals: SealTotals,
+    /// Seals and failed attempts per day, oldest first
+    pub seals_per_day: Vec<DailySealStats>,
+    /// Seals per QRNG source, most used first
+    pub qrng_sources: Vec<QrngSourceStats>,
+    /// Seals per trust tier, lowest tier first
+    pub trust_tiers: Vec<TrustTierStats>,
+    /// Mean size of the sealed files, when any was recorded
+    #[schema(example = 2_483_200.0)]
+    pub average_file_size_bytes: Option<f64>,
+    /// Batched anchors per chain and how long seals waited for them
+    pub anchor_latency: Vec<AnchorLatencyStats>,
+    /// Share of seal attempts that failed over the window (0.0 to 1.0)
+    #[schema(example = 0.012)]
+    pub failure_rate: f64,
+    /// Seals made with mock entropy over the window, anonymous seals, streams
+    /// and C2PA embeds included (audit log)
+    #[schema(example = 0)]
+    pub mock_entropy_seals: i64,
+}
+
+/// Request to turn read-only maintenance mode on or off
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMaintenanceRequest {
+    /// Refuse sealing and writes (true) or accept them again (false)
+    pub read_only: bool,
+    /// Why the server is read-only, included in refused responses
+    #[schema(example = "Database upgrade")]
+    pub reason: Option<String>,
+    /// `Retry-After` of refused writes, in seconds (default: unchanged)
+    #[schema(example = 600)]
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Query parameters for listing users
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AdminListUsersQuery {
+    /// Page number (1-indexed)
+    #[param(default = 1, minimum = 1)]
+    pub page: Option<i64>,
+
+    /// Items per page (max 100)
+    #[param(default = 20, minimum = 1, maximum = 100)]
+    pub limit: Option<i64>,
+
+    /// Only suspended (true) or only active (false) accounts
+    pub suspended: Option<bool>,
+
+    /// Case-insensitive email substring
+    pub email: Option<String>,
+}
+
+/// User as seen by administrators
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserRecord {
+    /// Public profile
+    #[serde(flatten)]
+    pub user: UserResponse,
+    /// Account role
+    pub role: UserRole,
+    /// Suspension timestamp (absent = active)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2026-01-08T10:00:00Z")]
+    pub suspended_at: Option<DateTime<Utc>>,
+    /// Organization (tenant) of the account (absent = none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "0192d4e8-0a6f-7c3e-9b1a-3f5e2d8c4b70")]
+    pub organization_id: Option<Uuid>,
+}
+
+impl From<User> for AdminUserRecord {
+    fn from(user: User) -> Self {
+        Self {
+            role: user.role,
+            suspended_at: user.suspended_at,
+            organization_id: user.organization_id,
+            user: UserResponse::from(user),
+        }
+    }
+}
+
+/// Paginated user list
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUserListResponse {
+    pub users: Vec<AdminUserRecord>,
+    pub page: i64,
+    pub limit: i64,
+    pub total: i64,
+    pub has_more: bool,
+}
+
+/// Request to change a user's trust tier
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTierRequest {
+    /// New account trust tier
+    pub tier: TrustTier,
+}
+
+/// Request to move a user to another organization
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateOrganizationRequest {
+    /// Organization of the account, or null to take it out of any
+    #[schema(value_type = Option<String>, example = "0192d4e8-0a6f-7c3e-9b1a-3f5e2d8c4b70")]
+    pub organization_id: Option<Uuid>,
+}
+
+/// Query parameters for listing revoked seals
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AdminListSealsQuery {
+    /// Page number (1-indexed)
+    #[param(default = 1, minimum = 1)]
+    pub page: Option<i64>,
+
+    /// Items per page (max 100)
+    #[param(default = 20, minimum = 1, maximum = 100)]
+    pub limit: Option<i64>,
+}
+
+/// Request to revoke a seal
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RevokeSealRequest {
+    /// Why the seal is revoked (shown with the seal)
+    #[schema(example = "Content reported as staged")]
+    pub reason: String,
+}
+
+/// Request to place a seal under legal hold or release it
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateLegalHoldRequest {
+    /// Keep the seal and its media as evidence (true) or release them (false)
+    pub legal_hold: bool,
+    /// Why, recorded in the audit log (required to place a hold)
+    #[schema(example = "Evidence in case 2026-CV-0142")]
+    pub reason: Option<String>,
+}
+
+/// Seal after a legal hold change
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LegalHoldResponse {
+    /// Seal record
+    #[serde(flatten)]
+    pub seal: SealRecord,
+    /// What the hold cannot preserve, or what releasing it lets happen
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[schema(example = json!(["The media was deleted before the hold: only the seal is kept"]))]
+    pub warnings: Vec<String>,
+}
+
+/// Get platform statistics
+///
+/// Seals per day, QRNG source and trust tier breakdowns, average file size,
+/// anchoring latency, failure rate, and account and seal totals. Figures come
+/// from statistics views refreshed every 15 minutes, topped up with what was
+/// created since.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/stats",
+    tag = "Admin",
+    params(AdminStatsQuery),
+    responses(
+        (status = 200, description = "Platform statistics", body = AdminStatsResponse),
+        (status = 400, description = "Invalid window"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn admin_stats_handler(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<AdminStatsQuery>,
+) -> Result<Json<AdminStatsResponse>, ApiError> {
+    let days = query.days.unwrap_or(DEFAULT_STATS_DAYS);
+    if !(1..=MAX_STATS_DAYS).contains(&days) {
+        return Err(ApiError::bad_request(format!(
+            "days must be between 1 and {}",
+            MAX_STATS_DAYS
+        )));
+    }
+
+    let user_repo = user_repo(&state)?;
+    let seal_repo = seal_repo(&state)?;
+    let audit_repo = audit_repo(&state)?;
+
+    let (
+        users,
+        seals,
+        seals_per_day,
+        qrng_sources,
+        trust_tiers,
+        average_file_size_bytes,
+        anchor_latency,
+        mock_entropy_seals,
+    ) = tokio::try_join!(
+        user_repo.stats(),
+        seal_repo.totals(),
+        seal_repo.daily_stats(days),
+        seal_repo.qrng_source_stats(days),
+        seal_repo.trust_tier_stats(days),
+        seal_repo.average_file_size(days),
+        seal_repo.anchor_latency_stats(days),
+        audit_repo.count_recent(AuditEvent::MockEntropyUsed, days),
+    )
+    .map_err(|e| db_error("load admin stats", e))?;
+
+    Ok(Json(AdminStatsResponse {
+        days,
+        failure_rate: failure_rate(&seals_per_day),
+        users,
+        seals,
+        seals_per_day,
+        qrng_sources,
+        trust_tiers,
+        average_file_size_bytes,
+        anchor_latency,
+        mock_entropy_seals,
+    }))
+}
+
+/// Scheduled maintenance tasks
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminTasksResponse {
+    /// Tasks in registration order
+    pub tasks: Vec<TaskStats>,
+}
+
+/// Get scheduled task statistics
+///
+/// Runs, failures, items processed and last run of each periodic
+/// maintenance task (upload and export sweeps, clustering, anchoring...).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/tasks",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Scheduled task statistics", body = AdminTasksResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn admin_tasks_handler(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+) -> Json<AdminTasksResponse> {
+    Json(AdminTasksResponse {
+        tasks: state.janitor.stats(),
+    })
+}
+
+/// Get the maintenance mode
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/maintenance",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Maintenance mode of this instance", body = MaintenanceStatus),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn get_maintenance_handler(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+) -> Json<MaintenanceStatus> {
+    Json(state.maintenance.status())
+}
+
+/// Turn read-only maintenance mode on or off
+///
+/// While read-only, verification and lookups keep working; sealing and every
+/// other write get `503 READ_ONLY` with `Retry-After`, and `/ready` reports
+/// the mode. Applies to the instance receiving the request only: set
+/// `READ_ONLY=true` to put a whole deployment in maintenance.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/maintenance",
+    tag = "Admin",
+    request_body = UpdateMaintenanceRequest,
+    responses(
+        (status = 200, description = "Maintenance mode updated", body = MaintenanceStatus),
+        (status = 400, description = "Invalid Retry-After"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn update_maintenance_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Json(request): Json<UpdateMaintenanceRequest>,
+) -> Result<Json<MaintenanceStatus>, ApiError> {
+    if request.retry_after_secs == Some(0) {
+        return Err(ApiError::bad_request("retry_after_secs must be positive"));
+    }
+    let reason = request
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+
+    let status = state
+        .maintenance
+        .set(request.read_only, reason, request.retry_after_secs);
+
+    tracing::warn!(
+        admin_id = %admin.user.id,
+        read_only = status.read_only,
+        reason = status.reason.as_deref().unwrap_or_default(),
+        "Admin changed maintenance mode"
+    );
+    Ok(Json(status))
+}
+
+/// List users
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/users",
+    tag = "Admin",
+    params(AdminListUsersQuery),
+    responses(
+        (status = 200, description = "Users, newest first", body = AdminUserListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn admin_list_users_handler(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<AdminListUsersQuery>,
+) -> Result<Json<AdminUserListResponse>, ApiError> {
+    let params = UserListParams {
+        page: query.page.unwrap_or(1),
+        limit: query.limit.unwrap_or(20).clamp(1, 100),
+        suspended: query.suspended,
+        email: query.email.filter(|email| !email.is_empty()),
+    };
+
+    let (users, total) = user_repo(&state)?
+        .list(&params)
+        .await
+        .map_err(|e| db_error("list users", e))?;
+
+    let offset = (params.page - 1).max(0) * params.limit;
+    Ok(Json(AdminUserListResponse {
+        has_more: offset + (users.len() as i64) < total,
+        users: users.into_iter().map(AdminUserRecord::from).collect(),
+        page: params.page,
+        limit: params.limit,
+        total,
+    }))
+}
+
+/// Suspend a user
+///
+/// Suspended accounts are refused by every authenticated endpoint (403);
+/// their seals stay verifiable.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/{user_id}/suspend",
+    tag = "Admin",
+    params(
+        ("user_id" = String, Path, description = "User ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "User suspended", body = AdminUserRecord),
+        (status = 400, description = "Administrators cannot suspend themselves"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "User not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn suspend_user_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<AdminUserRecord>, ApiError> {
+    if user_id == admin.user.id {
+        return Err(ApiError::bad_request(
+            "Administrators cannot suspend themselves",
+        ));
+    }
+    set_suspended(&state, &admin.user, user_id, true).await
+}
+
+/// Lift a user's suspension
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/{user_id}/unsuspend",
+    tag = "Admin",
+    params(
+        ("user_id" = String, Path, description = "User ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "User reinstated", body = AdminUserRecord),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "User not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn unsuspend_user_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<AdminUserRecord>, ApiError> {
+    set_suspended(&state, &admin.user, user_id, false).await
+}
+
+/// Change a user's trust tier
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/users/{user_id}/tier",
+    tag = "Admin",
+    params(
+        ("user_id" = String, Path, description = "User ID (UUID)")
+    ),
+    request_body = UpdateTierRequest,
+    responses(
+        (status = 200, description = "Tier updated", body = AdminUserRecord),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "User not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn update_user_tier_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<UpdateTierRequest>,
+) -> Result<Json<AdminUserRecord>, ApiError> {
+    let user = user_repo(&state)?
+        .update_tier(user_id, request.tier)
+        .await
+        .map_err(|e| db_error("update user tier", e))?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+
+    tracing::info!(
+        admin_id = %admin.user.id,
+        user_id = %user_id,
+        tier = ?request.tier,
+        "Admin changed user tier"
+    );
+    Ok(Json(AdminUserRecord::from(user)))
+}
+
+/// Move a user to another organization
+///
+/// The user's next seals are made in the new organization. Seals already
+/// made stay with the organization they were made in, and are no longer
+/// listed to the user once they leave it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/users/{user_id}/organization",
+    tag = "Admin",
+    params(
+        ("user_id" = String, Path, description = "User ID (UUID)")
+    ),
+    request_body = UpdateOrganizationRequest,
+    responses(
+        (status = 200, description = "Organization updated", body = AdminUserRecord),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "User not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn update_user_organization_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<UpdateOrganizationRequest>,
+) -> Result<Json<AdminUserRecord>, ApiError> {
+    let user = user_repo(&state)?
+        .set_organization(user_id, request.organization_id)
+        .await
+        .map_err(|e| db_error("update user organization", e))?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+
+    tracing::info!(
+        admin_id = %admin.user.id,
+        user_id = %user_id,
+        organization_id = ?request.organization_id,
+        "Admin changed user organization"
+    );
+    Ok(Json(AdminUserRecord::from(user)))
+}
+
+/// List revoked seals
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/seals/revoked",
+    tag = "Admin",
+    params(AdminListSealsQuery),
+    responses(
+        (status = 200, description = "Revoked seals, most recent first", body = SealListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn list_revoked_seals_handler(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Query(query): Query<AdminListSealsQuery>,
+) -> Result<Json<SealListResponse>, ApiError> {
+    let response = seal_repo(&state)?
+        .list_revoked(query.page.unwrap_or(1), query.limit.unwrap_or(20))
+        .await
+        .map_err(|e| db_error("list revoked seals", e))?;
+    Ok(Json(response))
+}
+
+/// Revoke a seal
+///
+/// The seal stays verifiable but is flagged as revoked, with the reason,
+/// wherever it is shown. Revoking again updates the reason.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/seals/{seal_id}/revoke",
+    tag = "Admin",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    request_body = RevokeSealRequest,
+    responses(
+        (status = 200, description = "Seal revoked", body = SealRecord),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn revoke_seal_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(seal_id): Path<Uuid>,
+    Json(request): Json<RevokeSealRequest>,
+) -> Result<Json<SealRecord>, ApiError> {
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::bad_request("A revocation reason is required"));
+    }
+
+    let seal = seal_repo(&state)?
+        .revoke(seal_id, reason)
+        .await
+        .map_err(|e| db_error("revoke seal", e))?
+        .ok_or_else(|| ApiError::not_found("Seal not found"))?;
+    state.verification_cache.invalidate_seal(seal_id);
+
+    tracing::info!(
+        admin_id = %admin.user.id,
+        seal_id = %seal_id,
+        reason,
+        "Admin revoked seal"
+    );
+    Ok(Json(SealRecord::from(seal)))
+}
+
+/// Lift a seal's revocation
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/seals/{seal_id}/reinstate",
+    tag = "Admin",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Seal reinstated", body = SealRecord),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn reinstate_seal_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(seal_id): Path<Uuid>,
+) -> Result<Json<SealRecord>, ApiError> {
+    let seal = seal_repo(&state)?
+        .reinstate(seal_id)
+        .await
+        .map_err(|e| db_error("reinstate seal", e))?
+        .ok_or_else(|| ApiError::not_found("Seal not found"))?;
+    state.verification_cache.invalidate_seal(seal_id);
+
+    tracing::info!(admin_id = %admin.user.id, seal_id = %seal_id, "Admin reinstated seal");
+    Ok(Json(SealRecord::from(seal)))
+}
+
+/// Place a seal under legal hold or release it
+///
+/// A seal under legal hold is kept as evidence: its organization's retention
+/// policy neither deletes it nor its media, and media erasure requests are
+/// refused. Placing a hold requires a reason; both placing and releasing are
+/// audited. The response warns when the media is already gone, or when the
+/// released seal is past its retention and goes at the next retention run.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/seals/{seal_id}/legal-hold",
+    tag = "Admin",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    request_body = UpdateLegalHoldRequest,
+    responses(
+        (status = 200, description = "Legal hold updated", body = LegalHoldResponse),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn update_legal_hold_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(seal_id): Path<Uuid>,
+    Json(request): Json<UpdateLegalHoldRequest>,
+) -> Result<Json<LegalHoldResponse>, ApiError> {
+    let reason = request
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    if request.legal_hold && reason.is_none() {
+        return Err(ApiError::bad_request("A legal hold reason is required"));
+    }
+
+    let change = seal_repo(&state)?
+        .set_legal_hold(seal_id, request.legal_hold, admin.user.id, reason)
+        .await
+        .map_err(|e| db_error("update legal hold", e))?
+        .ok_or_else(|| ApiError::not_found("Seal not found"))?;
+    state.verification_cache.invalidate_seal(seal_id);
+
+    let policy = match (&state.retention_repo, change.seal.organization_id) {
+        (Some(repo), Some(organization_id)) if !request.legal_hold => {
+            repo.get(organization_id).await.unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to look up retention policy");
+                None
+            })
+        }
+        _ => None,
+    };
+    let warnings = legal_hold_warnings(&change.seal, policy.as_ref(), Utc::now());
+
+    if change.was_held != request.legal_hold {
+        tracing::info!(
+            admin_id = %admin.user.id,
+            seal_id = %seal_id,
+            legal_hold = request.legal_hold,
+            reason,
+            "Admin changed seal legal hold"
+        );
+    }
+    Ok(Json(LegalHoldResponse {
+        seal: SealRecord::from(change.seal),
+        warnings,
+    }))
+}
+
+/// Revoke a seal signing key
+///
+/// Seals signed with the key no longer verify as authentic
+/// (`untrusted_signing_key`), whenever they were made: use it when the key is
+/// compromised, not to rotate keys. Revoking the key this server signs with
+/// stops `/seal` until a new `SIGNING_KEY_FILE` is configured.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/signing-keys/{key_id}/revoke",
+    tag = "Admin",
+    params(
+        ("key_id" = String, Path, description = "Key ID")
+    ),
+    responses(
+        (status = 200, description = "Signing key revoked", body = SigningKeyRecord),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "Signing key not found")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn revoke_signing_key_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(key_id): Path<String>,
+) -> Result<Json<SigningKeyRecord>, ApiError> {
+    let key = state
+        .signing_keys
+        .revoke(&key_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Signing key not found"))?;
+
+    tracing::warn!(admin_id = %admin.user.id, key_id = %key_id, "Admin revoked signing key");
+    Ok(Json(SigningKeyRecord::from(key)))
+}
+
+async fn set_suspended(
+    state: &AppState,
+    admin: &User,
+    user_id: Uuid,
+    suspended: bool,
+) -> Result<Json<AdminUserRecord>, ApiError> {
+    let user = user_repo(state)?
+        .set_suspended(user_id, suspended)
+        .await
+        .map_err(|e| db_error("update user suspension", e))?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+
+    tracing::info!(
+        admin_id = %admin.id,
+        user_id = %user_id,
+        suspended,
+        "Admin changed user suspension"
+    );
+    Ok(Json(AdminUserRecord::from(user)))
+}
+
+/// Share of attempts that failed: failures / (seals + failures).
+fn failure_rate(days: &[DailySealStats]) -> f64 {
+    let (seals, failures) = days.iter().fold((0, 0), |(seals, failures), day| {
+        (seals + day.seals, failures + day.failures)
+    });
+    if seals + failures == 0 {
+        return 0.0;
+    }
+    failures as f64 / (seals + failures) as f64
+}
+
+/// Warnings of a legal hold change: media the hold cannot bring back, or
+/// data the organization's retention `policy` deletes once released
+fn legal_hold_warnings(
+    seal: &Seal,
+    policy: Option<&RetentionPolicy>,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if seal.legal_hold {
+        if seal.media_deleted_at.is_some() {
+            warnings
+                .push("The media was deleted before the hold: only the seal is kept".to_string());
+        }
+        return warnings;
+    }
+
+    let expired = |days: Option<i32>| {
+        days.filter(|days| seal.created_at + chrono::Duration::days(i64::from(*days)) < now)
+    };
+    let Some(policy) = policy else {
+        return warnings;
+    };
+    if let Some(days) = expired(policy.seal_retention_days) {
+        warnings.push(format!(
+            "The seal is past its organization's {} days retention and will be deleted at \
+             the next retention run",
+            days
+        ));
+    } else if let Some(days) =
+        expired(policy.media_retention_days).filter(|_| seal.media_deleted_at.is_none())
+    {
+        warnings.push(format!(
+            "The media is past its organization's {} days retention and will be deleted at \
+             the next retention run",
+            days
+        ));
+    }
+    warnings
+}
+
+fn user_repo(state: &AppState) -> Result<&UserRepository, ApiError> {
+    state
+        .user_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+fn seal_repo(state: &AppState) -> Result<&SealRepository, ApiError> {
+    state
+        .seal_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+fn audit_repo(state: &AppState) -> Result<&AuditLogRepository, ApiError> {
+    state
+        .audit_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+fn db_error(action: &str, error: sqlx::Error) -> ApiError {
+    tracing::error!(error = %error, "Failed to {}", action);
+    ApiError::internal("A database error occurred")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(seals: i64, failures: i64) -> DailySealStats {
+        DailySealStats {
+            day: chrono::NaiveDate::from_ymd_opt(2026, 1, 8).unwrap(),
+            seals,
+            failures,
+        }
+    }
+
+    #[test]
+    fn test_failure_rate() {
+        assert_eq!(failure_rate(&[]), 0.0);
+        assert_eq!(failure_rate(&[day(0, 0)]), 0.0);
+        assert_eq!(failure_rate(&[day(3, 1), day(5, 1)]), 0.2);
+    }
+
+    #[test]
+    fn test_admin_user_record_serialization() {
+        let user = User {
+            id: Uuid::new_v4(),
+            clerk_user_id: "clerk_123".to_string(),
+            email: "reporter@example.com".to_string(),
+            name: None,
+            avatar_url: None,
+            tier: TrustTier::Tier2,
+            role: UserRole::User,
+            organization_id: None,
+            suspended_at: Some(Utc::now()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+
+        let json = serde_json::to_value(AdminUserRecord::from(user)).unwrap();
+        assert_eq!(json["email"], "reporter@example.com");
+        assert_eq!(json["tier"], "tier2");
+        assert_eq!(json["role"], "user");
+        assert!(json["suspended_at"].is_string());
+        assert!(json.get("clerk_user_id").is_none());
+    }
+
+    #[test]
+    fn test_legal_hold_warnings() {
+        let now = Utc::now();
+        let seal = |legal_hold: bool, age_days: i64, media_deleted: bool| Seal {
+            id: Uuid::new_v4(),
+            user_id: Some(Uuid::new_v4()),
+            organization_id: Some(Uuid::new_v4()),
+            content_hash: "ab".repeat(32),
+            perceptual_hash: None,
+            qrng_entropy: vec![0; 32],
+            qrng_source: "lfd".to_string(),
+            signature: vec![],
+            public_key: vec![],
+            media_type: "image".to_string(),
+            file_size: None,
+            mime_type: None,
+            metadata: serde_json::json!({}),
+            trust_tier: TrustTier::Tier1,
+            c2pa_manifest_embedded: false,
+            captured_at: now,
+            created_at: now - chrono::Duration::days(age_days),
+            media_deleted_at: media_deleted.then_some(now),
+            revoked_at: None,
+            revocation_reason: None,
+            verification_count: 0,
+            legal_hold,
+        };
+        let policy = RetentionPolicy {
+            organization_id: Uuid::new_v4(),
+            media_retention_days: Some(90),
+            seal_retention_days: Some(2557),
+            created_at: now,
+            updated_at: now,
+        };
+
+        // Placing a hold
+        assert!(legal_hold_warnings(&seal(true, 10, false), None, now).is_empty());
+        let warnings = legal_hold_warnings(&seal(true, 100, true), None, now);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("only the seal is kept"));
+
+        // Releasing it
+        assert!(legal_hold_warnings(&seal(false, 100, false), None, now).is_empty());
+        assert!(legal_hold_warnings(&seal(false, 10, false), Some(&policy), now).is_empty());
+        assert!(legal_hold_warnings(&seal(false, 100, true), Some(&policy), now).is_empty());
+        let warnings = legal_hold_warnings(&seal(false, 100, false), Some(&policy), now);
+        assert!(warnings[0].contains("media is past"));
+        let warnings = legal_hold_warnings(&seal(false, 3000, false), Some(&policy), now);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("seal is past"));
+    }
+}
@@ -0,0 +1,95 @@
+//! Batched anchor handler
+//!
+//! Serves the anchors of seals anchored in batches (see
+//! [`crate::anchor_batcher`]). Clients look a seal up by the `anchor_digest`
+//! returned by `/seal`, and add the anchor to the seal once its batch is
+//! anchored.
+
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+use veritas_core::BlockchainAnchor;
+
+use crate::anchor_batcher::BatchedAnchor;
+use crate::conditional::{Conditional, ETag};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Batched anchoring state of a seal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnchorStatusResponse {
+    /// Anchor digest of the seal (hex)
+    #[schema(example = "9f2c...e41a")]
+    pub anchor_digest: String,
+    /// `queued` (waiting for the next batch) or `anchored`
+    #[schema(example = "anchored")]
+    pub status: String,
+    /// Merkle root anchored on chain (hex, once anchored)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_root: Option<String>,
+    /// Anchor to add to the seal, with its inclusion proof (once anchored)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub anchor: Option<BlockchainAnchor>,
+}
+
+/// Get the batched anchor of a seal
+///
+/// Looks a seal up by its anchor digest. Until the next batch is anchored the
+/// seal is `queued`; afterwards the response carries the anchor to add to the
+/// seal, whose inclusion proof verifiers check offline. Pollers should send
+/// the `ETag` back in `If-None-Match`: the reply is 304 until the state changes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/anchors/{digest}",
+    tag = "Sealing",
+    params(
+        ("digest" = String, Path, description = "Anchor digest of the seal (hex, 32 bytes)")
+    ),
+    responses(
+        (status = 200, description = "Anchoring state of the seal", body = AnchorStatusResponse),
+        (status = 304, description = "Not modified (If-None-Match matches the ETag)"),
+        (status = 400, description = "Invalid anchor digest"),
+        (status = 404, description = "Seal not queued for batched anchoring"),
+        (status = 503, description = "Batched anchoring not enabled")
+    )
+)]
+pub async fn anchor_status_handler(
+    State(state): State<AppState>,
+    Path(digest): Path<String>,
+    headers: HeaderMap,
+) -> Result<Conditional<Json<AnchorStatusResponse>>, ApiError> {
+    let batcher = state
+        .anchor_batcher
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Batched anchoring not enabled"))?;
+    let key: [u8; 32] = hex::decode(&digest)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ApiError::bad_request("Anchor digest must be 32 bytes of hex"))?;
+
+    let response = match batcher.status(&key).await? {
+        Some(BatchedAnchor::Queued) => AnchorStatusResponse {
+            anchor_digest: hex::encode(key),
+            status: "queued".to_string(),
+            batch_root: None,
+            anchor: None,
+        },
+        Some(BatchedAnchor::Anchored(anchor)) => AnchorStatusResponse {
+            anchor_digest: hex::encode(key),
+            status: "anchored".to_string(),
+            batch_root: anchor.batch.as_ref().map(|batch| hex::encode(batch.root)),
+            anchor: Some(anchor),
+        },
+        None => return Err(ApiError::not_found("Seal not queued for anchoring")),
+    };
+    Ok(Conditional::new(
+        &headers,
+        ETag::of(&response),
+        Json(response),
+    ))
+}
@@ -0,0 +1,231 @@
+//! API key handlers
+//!
+//! Lets a signed-in user create scoped API keys for machine clients, list
+//! them and revoke them. A key is only returned once, at creation; only its
+//! hash and first characters are stored.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::db::{ApiKey, ApiKeyRecord, ApiKeyRepository, ApiKeyScope};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Longest allowed key name
+const MAX_API_KEY_NAME_LEN: usize = 100;
+
+/// Request to create an API key
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Label to recognize the key
+    #[schema(example = "Newsroom crawler")]
+    pub name: String,
+    /// Scopes granted to the key (at least one)
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+/// Newly created API key, including the key itself
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateApiKeyResponse {
+    /// The key's details
+    #[serde(flatten)]
+    pub key: ApiKeyRecord,
+    /// API key to send in the `X-API-Key` header; only returned here
+    #[schema(example = "vq_hX0r3m2b4c5d6e7f8g9h0i1j2k3l4m5n6o7p8q9r0s1")]
+    pub secret: String,
+}
+
+/// API keys of the current user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListApiKeysResponse {
+    /// Keys, newest first (revoked ones included)
+    pub keys: Vec<ApiKeyRecord>,
+}
+
+/// Create an API key
+///
+/// The key acts for the current user, limited to the requested scopes.
+#[utoipa::path(
+    post,
+    path = "/api/v1/api-keys",
+    tag = "API Keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "API key created", body = CreateApiKeyResponse),
+        (status = 400, description = "Invalid name or no scope"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn create_api_key_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreateApiKeyResponse>), ApiError> {
+    let name = validate_api_key_name(&request.name)?;
+    let scopes = validate_scopes(request.scopes)?;
+    let api_key_repo = api_key_repo(&state)?;
+
+    let secret = ApiKey::generate_secret().map_err(|e| {
+        tracing::error!(error = %e, "Failed to generate API key");
+        ApiError::internal("Failed to generate API key")
+    })?;
+    let key = ApiKey {
+        id: Uuid::new_v4(),
+        user_id: auth.user.id,
+        name,
+        key_prefix: ApiKey::display_prefix(&secret),
+        key_hash: ApiKey::hash_secret(&secret),
+        scopes,
+        created_at: Utc::now(),
+        last_used_at: None,
+        revoked_at: None,
+    };
+    api_key_repo.create(&key).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to create API key");
+        ApiError::internal("A database error occurred")
+    })?;
+
+    tracing::info!(user_id = %auth.user.id, key_id = %key.id, "API key created");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateApiKeyResponse {
+            key: ApiKeyRecord::from(key),
+            secret,
+        }),
+    ))
+}
+
+/// List the current user's API keys
+#[utoipa::path(
+    get,
+    path = "/api/v1/api-keys",
+    tag = "API Keys",
+    responses(
+        (status = 200, description = "API keys of the user", body = ListApiKeysResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn list_api_keys_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<Json<ListApiKeysResponse>, ApiError> {
+    let keys = api_key_repo(&state)?
+        .list_for_user(&auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to list API keys");
+            ApiError::internal("A database error occurred")
+        })?;
+
+    Ok(Json(ListApiKeysResponse {
+        keys: keys.into_iter().map(ApiKeyRecord::from).collect(),
+    }))
+}
+
+/// Revoke an API key
+///
+/// The key stops working immediately; it stays listed as revoked.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/api-keys/{key_id}",
+    tag = "API Keys",
+    params(
+        ("key_id" = String, Path, description = "API key ID (UUID)")
+    ),
+    responses(
+        (status = 204, description = "API key revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "API key not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn revoke_api_key_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(key_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let revoked = api_key_repo(&state)?
+        .revoke(key_id, &auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to revoke API key");
+            ApiError::internal("A database error occurred")
+        })?;
+    if !revoked {
+        return Err(ApiError::not_found("API key not found"));
+    }
+
+    tracing::info!(user_id = %auth.user.id, key_id = %key_id, "API key revoked");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn api_key_repo(state: &AppState) -> Result<&ApiKeyRepository, ApiError> {
+    state
+        .api_key_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+fn validate_api_key_name(name: &str) -> Result<String, ApiError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_API_KEY_NAME_LEN {
+        return Err(ApiError::bad_request(format!(
+            "name must be 1 to {} characters",
+            MAX_API_KEY_NAME_LEN
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Deduplicated scope names, or 400 if none was requested.
+fn validate_scopes(scopes: Vec<ApiKeyScope>) -> Result<Vec<String>, ApiError> {
+    let mut names: Vec<String> = Vec::new();
+    for scope in scopes {
+        let name = scope.as_str().to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    if names.is_empty() {
+        return Err(ApiError::bad_request("At least one scope is required"));
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_api_key_request() {
+        assert_eq!(validate_api_key_name("  crawler ").unwrap(), "crawler");
+        assert!(validate_api_key_name(" ").is_err());
+        assert!(validate_api_key_name(&"x".repeat(MAX_API_KEY_NAME_LEN + 1)).is_err());
+
+        assert!(validate_scopes(Vec::new()).is_err());
+        assert_eq!(
+            validate_scopes(vec![ApiKeyScope::ResolveBatch, ApiKeyScope::ResolveBatch]).unwrap(),
+            vec!["resolve:batch"]
+        );
+    }
+}
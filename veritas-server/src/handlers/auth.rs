@@ -0,0 +1,214 @@
+//! Self-hosted account handlers (`AUTH_MODE=local`)
+//!
+//! Registration and login of accounts managed by the server itself instead
+//! of Clerk. Both return an access token signed by the server, sent as a
+//! Bearer token like a Clerk session token; the account then uses the same
+//! endpoints as Clerk users.
+
+use axum::{extract::State, http::StatusCode, Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::auth::{check_password, LocalAuth};
+use crate::db::{LocalCredentialRepository, User, UserResponse};
+use crate::error::ApiError;
+use crate::handlers::AppState;
+
+/// Longest accepted email address
+const MAX_EMAIL_LENGTH: usize = 254;
+
+/// Request to create a self-hosted account
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterRequest {
+    /// Login email (case-insensitive)
+    #[schema(example = "user@example.com")]
+    pub email: String,
+    /// Password (8 to 128 characters)
+    #[schema(example = "correct horse battery staple")]
+    pub password: String,
+    /// Display name
+    #[serde(default)]
+    #[schema(example = "John Doe")]
+    pub name: Option<String>,
+}
+
+/// Request to log into a self-hosted account
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    /// Login email (case-insensitive)
+    #[schema(example = "user@example.com")]
+    pub email: String,
+    /// Password
+    pub password: String,
+}
+
+/// Access token of a self-hosted account
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthTokenResponse {
+    /// Token to send as `Authorization: Bearer <token>`
+    pub access_token: String,
+    /// Always "Bearer"
+    #[schema(example = "Bearer")]
+    pub token_type: &'static str,
+    /// Seconds until the token expires
+    #[schema(example = 43200)]
+    pub expires_in: u64,
+    /// The account
+    pub user: UserResponse,
+}
+
+/// Create a self-hosted account
+///
+/// Registers an email and password and returns an access token for the new
+/// account. Only available when the server manages accounts itself
+/// (`AUTH_MODE=local`).
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/register",
+    tag = "Users",
+    request_body = RegisterRequest,
+    responses(
+        (status = 201, description = "Account created", body = AuthTokenResponse),
+        (status = 400, description = "Invalid email or password"),
+        (status = 409, description = "Email already registered"),
+        (status = 503, description = "Self-hosted authentication or database not available")
+    )
+)]
+pub async fn register_handler(
+    State(state): State<AppState>,
+    Json(request): Json<RegisterRequest>,
+) -> Result<(StatusCode, Json<AuthTokenResponse>), ApiError> {
+    let (local_auth, credential_repo) = local_accounts(&state)?;
+
+    let email = normalize_email(&request.email)?;
+    check_password(&request.password)?;
+    let name = request
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty());
+
+    let password_hash = local_auth.hash_password(request.password).await?;
+    let (subject, clerk_user_id) = local_auth.new_subject();
+    let user = credential_repo
+        .register(&clerk_user_id, &email, name, &password_hash)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to register account");
+            ApiError::internal("A database error occurred")
+        })?
+        .ok_or_else(|| ApiError::conflict("Email already registered"))?;
+
+    tracing::info!(user_id = %user.id, "Self-hosted account registered");
+    let response = token_response(local_auth, &subject, user)?;
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Log into a self-hosted account
+///
+/// Checks an email and password and returns a new access token. Only
+/// available when the server manages accounts itself (`AUTH_MODE=local`).
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    tag = "Users",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Logged in", body = AuthTokenResponse),
+        (status = 401, description = "Wrong email or password"),
+        (status = 403, description = "Account suspended"),
+        (status = 503, description = "Self-hosted authentication or database not available")
+    )
+)]
+pub async fn login_handler(
+    State(state): State<AppState>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<AuthTokenResponse>, ApiError> {
+    let (local_auth, credential_repo) = local_accounts(&state)?;
+
+    let email = request.email.trim().to_lowercase();
+    let credential = credential_repo.find_for_login(&email).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to look up credentials");
+        ApiError::internal("A database error occurred")
+    })?;
+
+    // Unknown emails are checked against a dummy hash, so they take as long
+    let (user, password_hash) = match credential {
+        Some(credential) => (Some(credential.user), Some(credential.password_hash)),
+        None => (None, None),
+    };
+    let valid = local_auth
+        .verify_password(password_hash, request.password)
+        .await;
+    let user = user.filter(|_| valid).ok_or_else(|| {
+        ApiError::auth_error("AUTH_INVALID_CREDENTIALS", "Invalid email or password")
+    })?;
+
+    if user.is_suspended() {
+        return Err(ApiError::forbidden("Account suspended"));
+    }
+
+    let subject = local_auth
+        .subject_of(&user.clerk_user_id)
+        .ok_or_else(|| ApiError::internal("Account is not a self-hosted account"))?
+        .to_string();
+    Ok(Json(token_response(local_auth, &subject, user)?))
+}
+
+/// Token signer and credential repository, when accounts are self-hosted
+fn local_accounts(
+    state: &AppState,
+) -> Result<(&Arc<LocalAuth>, &Arc<LocalCredentialRepository>), ApiError> {
+    let local_auth = state.local_auth.as_ref().ok_or_else(|| {
+        ApiError::service_unavailable("Self-hosted authentication not enabled (AUTH_MODE=local)")
+    })?;
+    let credential_repo = state
+        .credential_repo
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+    Ok((local_auth, credential_repo))
+}
+
+fn token_response(
+    local_auth: &LocalAuth,
+    subject: &str,
+    user: User,
+) -> Result<AuthTokenResponse, ApiError> {
+    let issued = local_auth.issue_token(subject)?;
+    Ok(AuthTokenResponse {
+        access_token: issued.token,
+        token_type: "Bearer",
+        expires_in: issued.expires_in,
+        user: UserResponse::from(user),
+    })
+}
+
+/// Trimmed, lowercased email, rejected if it cannot be an address
+fn normalize_email(email: &str) -> Result<String, ApiError> {
+    let email = email.trim().to_lowercase();
+    let valid = email.len() <= MAX_EMAIL_LENGTH
+        && email
+            .split_once('@')
+            .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'));
+    if !valid {
+        return Err(ApiError::bad_request("Invalid email address"));
+    }
+    Ok(email)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_email() {
+        assert_eq!(
+            normalize_email("  Alice@Example.COM ").unwrap(),
+            "alice@example.com"
+        );
+        for invalid in ["", "alice", "@example.com", "alice@localhost"] {
+            assert!(normalize_email(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! Bulk seal export handlers
+//!
+//! Exports many of a user's seals at once, chosen by ID or by the filters of
+//! the seal list, as a zip archive built in the background by the export
+//! store (see [`crate::exports`]): each seal's `.veritas` file (its CBOR,
+//! when the manifest store has it), a PDF verification report per seal, and
+//! a `manifest.json` listing the seals with their verification outcome and
+//! file hashes. The client polls the export until it gets a signed download
+//! link.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use veritas_core::VeritasSeal;
+
+use crate::auth::AuthenticatedUser;
+use crate::db::{Seal, SealListParams, SealRecord, SealRepository, Tenant};
+use crate::error::ApiError;
+use crate::exports::{ExportKind, ExportStatus};
+use crate::handlers::data_export::{
+    export_response, stored_seal_cbor, to_json, DataExportResponse,
+};
+use crate::handlers::seals::build_report;
+use crate::state::AppState;
+
+/// Most seals in one export
+pub const MAX_BATCH_EXPORT_SEALS: usize = 1000;
+
+/// Page size used to collect the seals matching a filter
+const FILTER_PAGE_SIZE: i64 = 100;
+
+/// Request to export several seals
+///
+/// Exactly one of `seal_ids` and `filter` must be given.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchExportRequest {
+    /// Seals to export
+    #[serde(default)]
+    #[schema(
+        value_type = Option<Vec<String>>,
+        example = json!(["550e8400-e29b-41d4-a716-446655440000"])
+    )]
+    pub seal_ids: Option<Vec<Uuid>>,
+    /// Export the seals matching these filters instead
+    #[serde(default)]
+    pub filter: Option<BatchExportFilter>,
+}
+
+/// Seal list filters selecting the seals of an export
+#[derive(Debug, Default, PartialEq, Deserialize, ToSchema)]
+pub struct BatchExportFilter {
+    /// Media type (image, video, audio, document)
+    #[serde(default)]
+    pub media_type: Option<String>,
+    /// Seals with (true) or without (false) GPS location
+    #[serde(default)]
+    pub has_location: Option<bool>,
+    /// Seals carrying this tag
+    #[serde(default)]
+    #[schema(example = "ukraine")]
+    pub tag: Option<String>,
+    /// Seals in this collection
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub collection_id: Option<Uuid>,
+}
+
+/// Seals selected by a batch export request
+#[derive(Debug, PartialEq)]
+enum Selection {
+    Ids(Vec<Uuid>),
+    Filter(BatchExportFilter),
+}
+
+/// Entry of `manifest.json`
+#[derive(Debug, Serialize)]
+struct ManifestSeal {
+    #[serde(flatten)]
+    record: SealRecord,
+    /// Path of the seal file in the archive, when the full seal is stored
+    veritas_file: Option<String>,
+    /// SHA3-256 of the seal file (hex)
+    veritas_sha3_256: Option<String>,
+    /// Path of the PDF verification report in the archive
+    report_file: String,
+    /// Verification outcome (`AUTHENTIC`, `SIGNATURE VALID`, `NOT VERIFIED`, ...)
+    outcome: String,
+    /// Explanation of the outcome
+    outcome_description: String,
+}
+
+/// `manifest.json` of a seal export
+#[derive(Debug, Serialize)]
+struct ExportManifest {
+    export_id: Uuid,
+    created_at: DateTime<Utc>,
+    /// Seals exported
+    seal_count: usize,
+    /// Seals whose full seal is not stored and have no `.veritas` file
+    missing_seal_files: usize,
+    seals: Vec<ManifestSeal>,
+}
+
+/// Export several seals
+///
+/// Starts building a zip archive of the selected seals: `seals/<id>.veritas`
+/// (the seal CBOR, when stored), `reports/<id>.pdf` (verification report)
+/// and `manifest.json`. Returns 202; poll
+/// `/api/v1/seals/export-batch/{export_id}` for the signed download link.
+/// Only one seal export per user runs at a time.
+#[utoipa::path(
+    post,
+    path = "/api/v1/seals/export-batch",
+    tag = "Seals",
+    request_body = BatchExportRequest,
+    responses(
+        (status = 202, description = "Archive being built", body = DataExportResponse),
+        (status = 400, description = "Invalid selection or too many seals"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 409, description = "A seal export is already being built"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn batch_export_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Json(request): Json<BatchExportRequest>,
+) -> Result<(StatusCode, Json<DataExportResponse>), ApiError> {
+    let selection = requested_selection(request)?;
+    let seal_repo = state
+        .seal_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+
+    let seals = selected_seals(seal_repo, &auth.tenant(), selection).await?;
+
+    let export = state
+        .exports
+        .start_seals(auth.user.id)
+        .ok_or_else(|| ApiError::conflict("A seal export is already being built"))?;
+    tracing::info!(
+        user_id = %auth.user.id,
+        export_id = %export.id,
+        seals = seals.len(),
+        "Seal export requested"
+    );
+    let response = export_response(&state, &export);
+    tokio::spawn(build_seal_export(
+        state,
+        export.id,
+        export.created_at,
+        seals,
+    ));
+
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
+
+/// Get a seal export
+///
+/// Returns 202 while the archive is being built and 200 with a signed
+/// download link once it is ready.
+#[utoipa::path(
+    get,
+    path = "/api/v1/seals/export-batch/{export_id}",
+    tag = "Seals",
+    params(
+        ("export_id" = String, Path, description = "Export ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Archive ready for download", body = DataExportResponse),
+        (status = 202, description = "Archive being built", body = DataExportResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Export not found or expired"),
+        (status = 500, description = "Building the archive failed; request a new export")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn batch_export_status_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(export_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<DataExportResponse>), ApiError> {
+    let export = state
+        .exports
+        .get(export_id)
+        .filter(|export| export.user_id == auth.user.id && export.kind == ExportKind::Seals)
+        .ok_or_else(|| ApiError::not_found("Export not found"))?;
+
+    let status = match export.status {
+        ExportStatus::Ready => StatusCode::OK,
+        ExportStatus::Pending => StatusCode::ACCEPTED,
+        ExportStatus::Failed => {
+            return Err(ApiError::internal("Seal export failed, request it again"));
+        }
+    };
+    Ok((status, Json(export_response(&state, &export))))
+}
+
+/// Seal IDs (deduplicated, in request order) or filter of a request.
+fn requested_selection(request: BatchExportRequest) -> Result<Selection, ApiError> {
+    match (request.seal_ids, request.filter) {
+        (Some(ids), None) => {
+            let mut unique = Vec::with_capacity(ids.len());
+            for id in ids {
+                if !unique.contains(&id) {
+                    unique.push(id);
+                }
+            }
+            if unique.is_empty() {
+                return Err(ApiError::bad_request("seal_ids is empty"));
+            }
+            if unique.len() > MAX_BATCH_EXPORT_SEALS {
+                return Err(too_many_seals(unique.len()));
+            }
+            Ok(Selection::Ids(unique))
+        }
+        (None, Some(filter)) => Ok(Selection::Filter(filter)),
+        _ => Err(ApiError::bad_request(
+            "Give either seal_ids or filter to select the seals",
+        )),
+    }
+}
+
+/// The tenant's selected seals, or 404 if a requested ID is not one of them.
+async fn selected_seals(
+    seal_repo: &SealRepository,
+    tenant: &Tenant,
+    selection: Selection,
+) -> Result<Vec<Seal>, ApiError> {
+    let db_error = |e: sqlx::Error| {
+        tracing::error!(error = %e, "Failed to select seals for export");
+        ApiError::internal("A database error occurred")
+    };
+
+    let ids = match selection {
+        Selection::Ids(ids) => ids,
+        Selection::Filter(filter) => {
+            let mut params = SealListParams {
+                page: 1,
+                limit: FILTER_PAGE_SIZE,
+                media_type: filter.media_type,
+                has_location: filter.has_location,
+                tag: filter.tag.map(|tag| tag.trim().to_lowercase()),
+                collection_id: filter.collection_id,
+            };
+            let mut ids = Vec::new();
+            loop {
+                let page = seal_repo
+                    .list_for_user(tenant, &params)
+                    .await
+                    .map_err(db_error)?;
+                if page.total > MAX_BATCH_EXPORT_SEALS as i64 {
+                    return Err(too_many_seals(page.total as usize));
+                }
+                ids.extend(page.seals.iter().map(|seal| seal.id));
+                if !page.has_more {
+                    break;
+                }
+                params.page += 1;
+            }
+            if ids.is_empty() {
+                return Err(ApiError::bad_request("No seal matches the filter"));
+            }
+            ids
+        }
+    };
+
+    let seals = seal_repo
+        .find_many_for_user(&ids, tenant)
+        .await
+        .map_err(db_error)?;
+    if let Some(missing) = ids
+        .iter()
+        .find(|id| !seals.iter().any(|seal| seal.id == **id))
+    {
+        return Err(ApiError::not_found(format!("Seal not found: {}", missing)));
+    }
+    Ok(seals)
+}
+
+fn too_many_seals(count: usize) -> ApiError {
+    ApiError::bad_request(format!(
+        "{} seals selected, at most {} per export",
+        count, MAX_BATCH_EXPORT_SEALS
+    ))
+}
+
+/// Load the seal files and write the archive of a seal export.
+async fn build_seal_export(
+    state: AppState,
+    export_id: Uuid,
+    created_at: DateTime<Utc>,
+    seals: Vec<Seal>,
+) {
+    let mut stored = Vec::with_capacity(seals.len());
+    for seal in seals {
+        let cbor = stored_seal_cbor(&state, &seal).await;
+        stored.push((seal, cbor));
+    }
+
+    // Signature checks and PDF rendering are CPU-bound
+    let files =
+        tokio::task::spawn_blocking(move || seal_export_files(export_id, created_at, stored))
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!(error = %e, "Seal export task panicked");
+                Err(ApiError::internal("Failed to build seal export"))
+            });
+    let files = match files {
+        Ok(files) => files,
+        Err(e) => {
+            tracing::warn!(export_id = %export_id, error = %e, "Failed to build seal export");
+            state.exports.fail(export_id);
+            return;
+        }
+    };
+
+    match state.exports.complete(export_id, files).await {
+        Ok(()) => tracing::info!(export_id = %export_id, "Seal export ready"),
+        Err(e) => tracing::warn!(export_id = %export_id, error = %e, "Failed to write export"),
+    }
+}
+
+/// Archive files (path, contents) of a seal export: seal files, reports and
+/// the manifest.
+fn seal_export_files(
+    export_id: Uuid,
+    created_at: DateTime<Utc>,
+    seals: Vec<(Seal, Option<Vec<u8>>)>,
+) -> Result<Vec<(String, Vec<u8>)>, ApiError> {
+    let mut files = Vec::with_capacity(2 * seals.len() + 1);
+    let mut manifest = ExportManifest {
+        export_id,
+        created_at,
+        seal_count: seals.len(),
+        missing_seal_files: 0,
+        seals: Vec::with_capacity(seals.len()),
+    };
+
+    for (seal, cbor) in seals {
+        let stored_seal = cbor
+            .as_deref()
+            .and_then(|cbor| VeritasSeal::from_cbor(cbor).ok());
+        let report = build_report(&seal, stored_seal.as_ref());
+        let report_file = format!("reports/{}.pdf", seal.id);
+        files.push((report_file.clone(), report.to_pdf()));
+
+        let (veritas_file, veritas_sha3_256) = match cbor {
+            Some(cbor) => {
+                let path = format!("seals/{}.veritas", seal.id);
+                let hash = hex::encode(Sha3_256::digest(&cbor));
+                files.push((path.clone(), cbor));
+                (Some(path), Some(hash))
+            }
+            None => {
+                manifest.missing_seal_files += 1;
+                (None, None)
+            }
+        };
+
+        manifest.seals.push(ManifestSeal {
+            record: SealRecord::from(seal),
+            veritas_file,
+            veritas_sha3_256,
+            report_file,
+            outcome: report.outcome.label().to_string(),
+            outcome_description: report.outcome.description(),
+        });
+    }
+
+    files.push(("manifest.json".to_string(), to_json(&manifest)?));
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(
+        seal_ids: Option<Vec<Uuid>>,
+        filter: Option<BatchExportFilter>,
+    ) -> BatchExportRequest {
+        BatchExportRequest { seal_ids, filter }
+    }
+
+    #[test]
+    fn test_requested_selection() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(
+            requested_selection(request(Some(vec![a, b, a]), None)).unwrap(),
+            Selection::Ids(vec![a, b])
+        );
+        assert_eq!(
+            requested_selection(request(None, Some(BatchExportFilter::default()))).unwrap(),
+            Selection::Filter(BatchExportFilter::default())
+        );
+
+        assert!(requested_selection(request(None, None)).is_err());
+        assert!(requested_selection(request(Some(vec![a]), Some(Default::default()))).is_err());
+        assert!(requested_selection(request(Some(Vec::new()), None)).is_err());
+        let too_many = (0..=MAX_BATCH_EXPORT_SEALS)
+            .map(|_| Uuid::new_v4())
+            .collect();
+        assert!(requested_selection(request(Some(too_many), None)).is_err());
+    }
+}
@@ -5,6 +5,7 @@
 
 use axum::{
     extract::{Multipart, State},
+    http::HeaderMap,
     Json,
 };
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
@@ -14,9 +15,11 @@ use utoipa::ToSchema;
 use veritas_core::c2pa::{
     extract_quantum_seal_from_stream, QuantumSealAssertion, VeritasManifestBuilder, VeritasSigner,
 };
+use veritas_core::qrng::HealthCheckedQrng;
 use veritas_core::{generate_keypair, LfdQrng, MediaType, MockQrng, SealBuilder, VeritasSeal};
 
 use crate::error::ApiError;
+use crate::handlers::seal::{mock_entropy_headers, record_mock_entropy};
 use crate::multipart::MultipartFields;
 use crate::state::AppState;
 use crate::validation::DEFAULT_MAX_FILE_SIZE;
@@ -101,13 +104,15 @@ impl From<&QuantumSealAssertion> for QuantumSealInfo {
 /// - **mock** (optional): "true" to use mock QRNG when creating new seal
 ///
 /// If no seal_data is provided, a new seal will be created for the file.
+/// A seal made with mock entropy is flagged by the `X-Veritas-Mock-Entropy`
+/// response header.
 ///
 /// **Note**: Requires C2PA signing credentials configured via environment:
 /// - `C2PA_SIGNING_KEY`: Path to ECDSA P-256 private key (PEM)
 /// - `C2PA_SIGNING_CERT`: Path to X.509 certificate chain (PEM)
 #[utoipa::path(
     post,
-    path = "/c2pa/embed",
+    path = "/api/v1/c2pa/embed",
     tag = "C2PA",
     request_body(
         content_type = "multipart/form-data",
@@ -122,7 +127,7 @@ impl From<&QuantumSealAssertion> for QuantumSealInfo {
 pub async fn c2pa_embed_handler(
     State(state): State<AppState>,
     mut multipart: Multipart,
-) -> Result<Json<C2paEmbedResponse>, ApiError> {
+) -> Result<(HeaderMap, Json<C2paEmbedResponse>), ApiError> {
     // Parse multipart form (no content type validation for C2PA)
     let fields = MultipartFields::parse(&mut multipart, false, DEFAULT_MAX_FILE_SIZE).await?;
 
@@ -157,7 +162,7 @@ pub async fn c2pa_embed_handler(
 
         // Create new seal
         let (public_key, secret_key) = generate_keypair();
-        let media_type = media_type_from_mime(&mime_type);
+        let media_type = MediaType::from_mime(&mime_type);
 
         let seal = if use_mock {
             let qrng = MockQrng::default();
@@ -165,7 +170,7 @@ pub async fn c2pa_embed_handler(
                 .build_secure(&qrng, &secret_key, &public_key)
                 .await?
         } else {
-            let qrng = LfdQrng::new().map_err(|e| {
+            let qrng = LfdQrng::new().map(HealthCheckedQrng::new).map_err(|e| {
                 tracing::error!("QRNG client creation failed: {}", e);
                 ApiError::service_unavailable("QRNG service unavailable")
             })?;
@@ -189,6 +194,7 @@ pub async fn c2pa_embed_handler(
     })?;
 
     // Build manifest and embed
+    let mock_entropy = seal.uses_mock_entropy();
     let builder = VeritasManifestBuilder::new(seal);
 
     // Create seekable buffer for input
@@ -208,11 +214,18 @@ pub async fn c2pa_embed_handler(
     // Get the actual output data
     let output_data = output.into_inner().clone();
 
-    Ok(Json(C2paEmbedResponse {
-        media_data: BASE64.encode(&output_data),
-        content_type: mime_type,
-        new_seal_created,
-    }))
+    if mock_entropy && new_seal_created {
+        record_mock_entropy(&state, "c2pa_embed", None, None).await;
+    }
+
+    Ok((
+        mock_entropy_headers(mock_entropy),
+        Json(C2paEmbedResponse {
+            media_data: BASE64.encode(&output_data),
+            content_type: mime_type,
+            new_seal_created,
+        }),
+    ))
 }
 
 /// Verify C2PA manifest and extract Veritas seal info
@@ -223,7 +236,7 @@ pub async fn c2pa_embed_handler(
 /// Returns validation status for both C2PA signature and embedded Veritas seal.
 #[utoipa::path(
     post,
-    path = "/c2pa/verify",
+    path = "/api/v1/c2pa/verify",
     tag = "C2PA",
     request_body(
         content_type = "multipart/form-data",
@@ -280,18 +293,8 @@ fn mime_from_filename(filename: &str) -> Option<String> {
         "mov" => "video/quicktime",
         "mp3" => "audio/mpeg",
         "wav" => "audio/wav",
+        "pdf" => "application/pdf",
         _ => return None,
     };
     Some(mime.to_string())
 }
-
-/// Convert MIME type to MediaType
-fn media_type_from_mime(mime: &str) -> MediaType {
-    if mime.starts_with("video/") {
-        MediaType::Video
-    } else if mime.starts_with("audio/") {
-        MediaType::Audio
-    } else {
-        MediaType::Image
-    }
-}
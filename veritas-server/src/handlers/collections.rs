@@ -0,0 +1,396 @@
+//! Collection handlers
+//!
+//! Lets users group their seals into named collections (e.g. "Ukraine field
+//! trip 2025"): create, rename and delete collections, and add or remove
+//! seals. Seal listings filter on a collection with `?collection_id=`.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::db::{Collection, CollectionRepository, Tenant};
+use crate::error::ApiError;
+use crate::handlers::share::require_own_seal;
+use crate::state::AppState;
+
+/// Longest collection name, in characters
+pub const MAX_COLLECTION_NAME_CHARS: usize = 100;
+
+/// Longest collection description, in characters
+pub const MAX_COLLECTION_DESCRIPTION_CHARS: usize = 2000;
+
+/// Request to create or update a collection
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CollectionRequest {
+    /// Name, unique among the user's collections
+    #[schema(example = "Ukraine field trip 2025")]
+    pub name: String,
+    /// Free-form description
+    #[serde(default)]
+    #[schema(example = "Kharkiv and Izium, March 2025")]
+    pub description: Option<String>,
+}
+
+/// Collections of the current user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListCollectionsResponse {
+    /// Collections, alphabetically
+    pub collections: Vec<Collection>,
+}
+
+/// Create a collection
+#[utoipa::path(
+    post,
+    path = "/api/v1/collections",
+    tag = "Seals",
+    request_body = CollectionRequest,
+    responses(
+        (status = 201, description = "Collection created", body = Collection),
+        (status = 400, description = "Invalid name or description"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "A collection with this name exists"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn create_collection_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Json(request): Json<CollectionRequest>,
+) -> Result<(StatusCode, Json<Collection>), ApiError> {
+    let (name, description) = validate_collection(&request)?;
+
+    let collection = collection_repo(&state)?
+        .create(&auth.tenant(), &name, description.as_deref())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to create collection");
+            ApiError::internal("A database error occurred")
+        })?
+        .ok_or_else(|| ApiError::conflict("A collection with this name exists"))?;
+
+    tracing::info!(
+        user_id = %auth.user.id,
+        collection_id = %collection.id,
+        "Collection created"
+    );
+    Ok((StatusCode::CREATED, Json(collection)))
+}
+
+/// List the current user's collections
+#[utoipa::path(
+    get,
+    path = "/api/v1/collections",
+    tag = "Seals",
+    responses(
+        (status = 200, description = "Collections of the user", body = ListCollectionsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn list_collections_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<Json<ListCollectionsResponse>, ApiError> {
+    let collections = collection_repo(&state)?
+        .list_for_user(&auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to list collections");
+            ApiError::internal("A database error occurred")
+        })?;
+
+    Ok(Json(ListCollectionsResponse { collections }))
+}
+
+/// Get a collection
+///
+/// List its seals with `GET /api/v1/seals?collection_id=`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/collections/{collection_id}",
+    tag = "Seals",
+    params(
+        ("collection_id" = String, Path, description = "Collection ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Collection", body = Collection),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Collection not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn get_collection_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(collection_id): Path<Uuid>,
+) -> Result<Json<Collection>, ApiError> {
+    let collection = require_own_collection(&state, collection_id, &auth.tenant()).await?;
+    Ok(Json(collection))
+}
+
+/// Update a collection
+///
+/// Replaces the name and description of the collection.
+#[utoipa::path(
+    put,
+    path = "/api/v1/collections/{collection_id}",
+    tag = "Seals",
+    params(
+        ("collection_id" = String, Path, description = "Collection ID (UUID)")
+    ),
+    request_body = CollectionRequest,
+    responses(
+        (status = 200, description = "Collection updated", body = Collection),
+        (status = 400, description = "Invalid name or description"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Collection not found"),
+        (status = 409, description = "Another collection has this name"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn update_collection_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(collection_id): Path<Uuid>,
+    Json(request): Json<CollectionRequest>,
+) -> Result<Json<Collection>, ApiError> {
+    let (name, description) = validate_collection(&request)?;
+    let collection_repo = collection_repo(&state)?;
+    require_own_collection(&state, collection_id, &auth.tenant()).await?;
+
+    collection_repo
+        .update(collection_id, &auth.tenant(), &name, description.as_deref())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to update collection");
+            ApiError::internal("A database error occurred")
+        })?
+        .map(Json)
+        .ok_or_else(|| ApiError::conflict("Another collection has this name"))
+}
+
+/// Delete a collection
+///
+/// The collection's seals are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/collections/{collection_id}",
+    tag = "Seals",
+    params(
+        ("collection_id" = String, Path, description = "Collection ID (UUID)")
+    ),
+    responses(
+        (status = 204, description = "Collection deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Collection not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn delete_collection_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(collection_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = collection_repo(&state)?
+        .delete(collection_id, &auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to delete collection");
+            ApiError::internal("A database error occurred")
+        })?;
+    if !deleted {
+        return Err(ApiError::not_found("Collection not found"));
+    }
+
+    tracing::info!(
+        user_id = %auth.user.id,
+        collection_id = %collection_id,
+        "Collection deleted"
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add a seal to a collection
+///
+/// Adding a seal already in the collection does nothing.
+#[utoipa::path(
+    put,
+    path = "/api/v1/collections/{collection_id}/seals/{seal_id}",
+    tag = "Seals",
+    params(
+        ("collection_id" = String, Path, description = "Collection ID (UUID)"),
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    responses(
+        (status = 204, description = "Seal in the collection"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Collection or seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn add_collection_seal_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path((collection_id, seal_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let collection_repo = collection_repo(&state)?;
+    let tenant = auth.tenant();
+    require_own_collection(&state, collection_id, &tenant).await?;
+    require_own_seal(&state, seal_id, &tenant).await?;
+
+    let added = collection_repo
+        .add_seal(collection_id, seal_id, &tenant)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to add seal to collection");
+            ApiError::internal("A database error occurred")
+        })?;
+    if !added {
+        return Err(ApiError::not_found("Seal not found"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Remove a seal from a collection
+///
+/// The seal itself is kept.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/collections/{collection_id}/seals/{seal_id}",
+    tag = "Seals",
+    params(
+        ("collection_id" = String, Path, description = "Collection ID (UUID)"),
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    responses(
+        (status = 204, description = "Seal removed from the collection"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not in the collection"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn remove_collection_seal_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path((collection_id, seal_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let removed = collection_repo(&state)?
+        .remove_seal(collection_id, seal_id, &auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to remove seal from collection");
+            ApiError::internal("A database error occurred")
+        })?;
+    if !removed {
+        return Err(ApiError::not_found("Seal not in the collection"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn collection_repo(state: &AppState) -> Result<&CollectionRepository, ApiError> {
+    state
+        .collection_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+/// The collection, or 404 unless it exists and belongs to `tenant`.
+async fn require_own_collection(
+    state: &AppState,
+    collection_id: Uuid,
+    tenant: &Tenant,
+) -> Result<Collection, ApiError> {
+    collection_repo(state)?
+        .find_for_user(collection_id, tenant)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to get collection");
+            ApiError::internal("A database error occurred")
+        })?
+        .ok_or_else(|| ApiError::not_found("Collection not found"))
+}
+
+/// Trimmed name and description (an empty description clears it).
+fn validate_collection(request: &CollectionRequest) -> Result<(String, Option<String>), ApiError> {
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_COLLECTION_NAME_CHARS {
+        return Err(ApiError::bad_request(format!(
+            "name must be 1 to {} characters",
+            MAX_COLLECTION_NAME_CHARS
+        )));
+    }
+
+    let description = request
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|description| !description.is_empty());
+    if description
+        .is_some_and(|description| description.chars().count() > MAX_COLLECTION_DESCRIPTION_CHARS)
+    {
+        return Err(ApiError::bad_request(format!(
+            "description must be at most {} characters",
+            MAX_COLLECTION_DESCRIPTION_CHARS
+        )));
+    }
+
+    Ok((name.to_string(), description.map(str::to_string)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(name: &str, description: Option<&str>) -> CollectionRequest {
+        CollectionRequest {
+            name: name.to_string(),
+            description: description.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_validate_collection() {
+        let (name, description) =
+            validate_collection(&request(" Ukraine field trip 2025 ", Some("  "))).unwrap();
+        assert_eq!(name, "Ukraine field trip 2025");
+        assert_eq!(description, None);
+
+        let (_, description) = validate_collection(&request("Trip", Some(" Kharkiv "))).unwrap();
+        assert_eq!(description.as_deref(), Some("Kharkiv"));
+
+        assert!(validate_collection(&request("  ", None)).is_err());
+        let long_name = "x".repeat(MAX_COLLECTION_NAME_CHARS + 1);
+        assert!(validate_collection(&request(&long_name, None)).is_err());
+        let long_description = "x".repeat(MAX_COLLECTION_DESCRIPTION_CHARS + 1);
+        assert!(validate_collection(&request("Trip", Some(&long_description))).is_err());
+    }
+}
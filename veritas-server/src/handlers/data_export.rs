@@ -0,0 +1,357 @@
+//! Personal data export handlers (GDPR data portability)
+//!
+//! Complements account deletion: a user can download everything stored
+//! about them as a tar archive of JSON files plus the CBOR of each seal.
+//! The archive is built in the background (see [`crate::exports`]); the
+//! client polls `/users/me/export` until it gets a signed download link.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use veritas_core::VeritasSeal;
+
+use crate::auth::AuthenticatedUser;
+use crate::db::{
+    ApiKeyRecord, Collection, Seal, SealRecord, SealShareRecord, SealTag, Tenant,
+    ThirdPartyActivity, User,
+};
+use crate::error::ApiError;
+use crate::exports::{DataExport, ExportKind, ExportStatus};
+use crate::state::AppState;
+
+/// State of a data or seal export
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DataExportResponse {
+    /// Export identifier
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub export_id: Uuid,
+    /// Progress of the export
+    pub status: ExportStatus,
+    /// When the export was requested
+    #[schema(value_type = String, example = "2026-01-08T10:00:00Z")]
+    pub created_at: DateTime<Utc>,
+    /// When the archive and its download link expire
+    #[schema(value_type = String, example = "2026-01-09T10:00:00Z")]
+    pub expires_at: DateTime<Utc>,
+    /// Archive size in bytes (once ready)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    /// Signed download path, usable without authentication (once ready)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(
+        example = "/api/v1/exports/550e8400-.../download?expires=1767952800&signature=9f2c..."
+    )]
+    pub download_url: Option<String>,
+}
+
+/// Seal entry of `seals.json`
+#[derive(Debug, Serialize)]
+struct ExportedSeal {
+    #[serde(flatten)]
+    record: SealRecord,
+    qrng_source: String,
+    mime_type: Option<String>,
+    /// ML-DSA signature (hex)
+    signature: String,
+    /// ML-DSA public key (hex)
+    public_key: String,
+    /// Path of the seal's CBOR in the archive, when stored
+    cbor_file: Option<String>,
+}
+
+/// Collection entry of `collections.json`
+#[derive(Debug, Serialize)]
+struct ExportedCollection {
+    #[serde(flatten)]
+    collection: Collection,
+    /// Seals of the collection, in the order they were added
+    seal_ids: Vec<Uuid>,
+}
+
+/// Export the current user's data
+///
+/// Starts building an archive of everything stored about the user: profile
+/// (`user.json`), seals (`seals.json` and `seals/<id>.cbor`), share links
+/// (`shares.json`), tags and collections of the seals (`tags.json`,
+/// `collections.json`), API keys without their secrets (`api_keys.json`) and
+/// third-party activity on the seals (`activity.json`). Returns 202 while
+/// the archive is being built and 200 with a signed download link once it
+/// is ready; call again to poll. Only one export per user runs at a time.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/export",
+    tag = "Users",
+    responses(
+        (status = 200, description = "Archive ready for download", body = DataExportResponse),
+        (status = 202, description = "Archive being built", body = DataExportResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "The previous export failed; call again to retry"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn export_user_data_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<(StatusCode, Json<DataExportResponse>), ApiError> {
+    if state.seal_repo.is_none() {
+        return Err(ApiError::service_unavailable("Database not configured"));
+    }
+
+    if state.exports.take_failed(auth.user.id).is_some() {
+        return Err(ApiError::internal("Data export failed, request it again"));
+    }
+
+    let (export, created) = state.exports.start(auth.user.id);
+    if created {
+        tracing::info!(
+            user_id = %auth.user.id,
+            export_id = %export.id,
+            "Data export requested (GDPR)"
+        );
+        tokio::spawn(build_export(state.clone(), export.id, auth.user));
+    }
+
+    let status = match export.status {
+        ExportStatus::Ready => StatusCode::OK,
+        _ => StatusCode::ACCEPTED,
+    };
+    Ok((status, Json(export_response(&state, &export))))
+}
+
+/// Query parameters of a signed download link
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportDownloadQuery {
+    /// Link expiry (Unix seconds)
+    pub expires: i64,
+    /// Link signature (hex)
+    pub signature: String,
+}
+
+/// Download a data or seal export
+///
+/// Serves the archive of a ready export. Requires no authentication: the
+/// link returned by `/users/me/export` or `/seals/export-batch` is signed and
+/// expires with the export.
+#[utoipa::path(
+    get,
+    path = "/api/v1/exports/{export_id}/download",
+    tag = "Users",
+    params(
+        ("export_id" = String, Path, description = "Export ID (UUID)"),
+        ExportDownloadQuery
+    ),
+    responses(
+        (status = 200, description = "Export archive (application/x-tar, or application/zip for seal exports)"),
+        (status = 403, description = "Invalid or expired download link"),
+        (status = 404, description = "Export not found or not ready")
+    )
+)]
+pub async fn download_export_handler(
+    State(state): State<AppState>,
+    Path(export_id): Path<Uuid>,
+    Query(query): Query<ExportDownloadQuery>,
+) -> Result<Response, ApiError> {
+    if !state
+        .exports
+        .verify_link(export_id, query.expires, &query.signature)
+    {
+        return Err(ApiError::forbidden("Invalid or expired download link"));
+    }
+
+    let export = state
+        .exports
+        .get(export_id)
+        .filter(|export| export.status == ExportStatus::Ready)
+        .ok_or_else(|| ApiError::not_found("Export not found"))?;
+
+    let archive = tokio::fs::read(state.exports.archive_path(&export))
+        .await
+        .map_err(|e| {
+            tracing::error!(export_id = %export.id, error = %e, "Failed to read export archive");
+            ApiError::not_found("Export not found")
+        })?;
+
+    let name = match export.kind {
+        ExportKind::UserData => "veritas-export",
+        ExportKind::Seals => "veritas-seals",
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, export.kind.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}-{}.{}\"",
+                    name,
+                    export.created_at.format("%Y-%m-%d"),
+                    export.kind.extension()
+                ),
+            ),
+        ],
+        archive,
+    )
+        .into_response())
+}
+
+pub(crate) fn export_response(state: &AppState, export: &DataExport) -> DataExportResponse {
+    let ready = export.status == ExportStatus::Ready;
+    DataExportResponse {
+        export_id: export.id,
+        status: export.status,
+        created_at: export.created_at,
+        expires_at: export.expires_at,
+        size_bytes: export.size_bytes,
+        download_url: ready.then(|| state.exports.download_path(export)),
+    }
+}
+
+/// Collect the user's data and write the archive of an export.
+async fn build_export(state: AppState, export_id: Uuid, user: User) {
+    let files = match collect_user_data(&state, &user).await {
+        Ok(files) => files,
+        Err(e) => {
+            tracing::warn!(export_id = %export_id, error = %e, "Failed to collect export data");
+            state.exports.fail(export_id);
+            return;
+        }
+    };
+
+    match state.exports.complete(export_id, files).await {
+        Ok(()) => tracing::info!(user_id = %user.id, export_id = %export_id, "Data export ready"),
+        Err(e) => tracing::warn!(export_id = %export_id, error = %e, "Failed to write export"),
+    }
+}
+
+/// Archive files (path, contents) holding everything stored about `user`.
+async fn collect_user_data(
+    state: &AppState,
+    user: &User,
+) -> Result<Vec<(String, Vec<u8>)>, ApiError> {
+    let seal_repo = state
+        .seal_repo
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+    let db_error = |e: sqlx::Error| {
+        tracing::error!(error = %e, "Failed to load export data");
+        ApiError::internal("A database error occurred")
+    };
+
+    // Everything the account owns, including seals left in organizations
+    // the user has since moved out of
+    let tenant = Tenant::personal(user.id);
+    let seals = seal_repo.all_for_user(&tenant).await.map_err(db_error)?;
+    let shares: Vec<SealShareRecord> = match &state.share_repo {
+        Some(repo) => repo
+            .list_for_user(&tenant)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(SealShareRecord::from)
+            .collect(),
+        None => Vec::new(),
+    };
+    let api_keys: Vec<ApiKeyRecord> = match &state.api_key_repo {
+        Some(repo) => repo
+            .list_for_user(&tenant)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(ApiKeyRecord::from)
+            .collect(),
+        None => Vec::new(),
+    };
+    let tags: Vec<SealTag> = match &state.tag_repo {
+        Some(repo) => repo.all_for_user(&tenant).await.map_err(db_error)?,
+        None => Vec::new(),
+    };
+    let collections: Vec<ExportedCollection> = match &state.collection_repo {
+        Some(repo) => {
+            let members = repo.seals_for_user(&tenant).await.map_err(db_error)?;
+            repo.list_for_user(&tenant)
+                .await
+                .map_err(db_error)?
+                .into_iter()
+                .map(|collection| ExportedCollection {
+                    seal_ids: members
+                        .iter()
+                        .filter(|member| member.collection_id == collection.id)
+                        .map(|member| member.seal_id)
+                        .collect(),
+                    collection,
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    let activity = match &state.audit_repo {
+        Some(repo) => repo.third_party_activity(user.id).await.map_err(db_error)?,
+        None => ThirdPartyActivity::default(),
+    };
+
+    let mut files = Vec::with_capacity(seals.len() + 7);
+    let mut exported_seals = Vec::with_capacity(seals.len());
+    for seal in seals {
+        let cbor_file = match stored_seal_cbor(state, &seal).await {
+            Some(cbor) => {
+                let path = format!("seals/{}.cbor", seal.id);
+                files.push((path.clone(), cbor));
+                Some(path)
+            }
+            None => None,
+        };
+        exported_seals.push(ExportedSeal {
+            qrng_source: seal.qrng_source.clone(),
+            mime_type: seal.mime_type.clone(),
+            signature: hex::encode(&seal.signature),
+            public_key: hex::encode(&seal.public_key),
+            cbor_file,
+            record: SealRecord::from(seal),
+        });
+    }
+
+    files.push(("user.json".to_string(), to_json(user)?));
+    files.push(("seals.json".to_string(), to_json(&exported_seals)?));
+    files.push(("shares.json".to_string(), to_json(&shares)?));
+    files.push(("tags.json".to_string(), to_json(&tags)?));
+    files.push(("collections.json".to_string(), to_json(&collections)?));
+    files.push(("api_keys.json".to_string(), to_json(&api_keys)?));
+    files.push(("activity.json".to_string(), to_json(&activity)?));
+    Ok(files)
+}
+
+/// CBOR of a seal from the manifest store, if stored.
+///
+/// The store is keyed by content hash, which other users may have sealed
+/// too, so the stored seal is only used when its signature is the user's.
+pub(crate) async fn stored_seal_cbor(state: &AppState, seal: &Seal) -> Option<Vec<u8>> {
+    let store = state.manifest_store.as_ref()?;
+    match store.get_by_image_hash(&seal.content_hash).await {
+        Ok(record) => record
+            .filter(|record| {
+                VeritasSeal::from_cbor(&record.seal_cbor)
+                    .is_ok_and(|stored| stored.signature == seal.signature)
+            })
+            .map(|record| record.seal_cbor),
+        Err(e) => {
+            tracing::warn!(seal_id = %seal.id, error = %e, "Failed to load seal for export");
+            None
+        }
+    }
+}
+
+pub(crate) fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, ApiError> {
+    serde_json::to_vec_pretty(value).map_err(|e| {
+        tracing::error!(error = %e, "Failed to serialize export data");
+        ApiError::internal("Failed to serialize export data")
+    })
+}
@@ -0,0 +1,166 @@
+//! Near-duplicate handler
+//!
+//! Lists the visual variants of a seal: other sealed images whose perceptual
+//! hash is close to the seal's, such as re-uploads, crops or manipulated
+//! copies. Results come from the background clustering job; seals it has not
+//! reached yet fall back to a direct similarity search.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::error::ApiError;
+use crate::handlers::share::require_own_seal;
+use crate::manifest_store::{ManifestStoreError, SimilarityMatch};
+use crate::state::AppState;
+
+/// Default number of variants returned
+const DEFAULT_DUPLICATES_LIMIT: usize = 20;
+
+/// Maximum number of variants returned
+const MAX_DUPLICATES_LIMIT: usize = 100;
+
+/// Query parameters for listing a seal's variants
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DuplicatesQuery {
+    /// Maximum number of variants returned (max 100)
+    #[param(default = 20, minimum = 1, maximum = 100)]
+    pub limit: Option<usize>,
+}
+
+/// A visually similar seal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateSeal {
+    /// Seal ID of the variant
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub seal_id: String,
+    /// Perceptual hash distance from the seal (0 = visually identical)
+    #[schema(example = 3)]
+    pub hamming_distance: u32,
+    /// Media type of the variant
+    #[schema(example = "image")]
+    pub media_type: String,
+    /// When the variant was sealed
+    pub created_at: DateTime<Utc>,
+}
+
+/// Visual variants of a seal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicatesResponse {
+    /// The seal
+    pub seal_id: Uuid,
+    /// Total number of known variants, which may exceed `duplicates`
+    #[schema(example = 4)]
+    pub variants: i64,
+    /// Whether the variants come from the seal's cluster (false = direct
+    /// similarity search, before the seal is clustered)
+    pub clustered: bool,
+    /// Variants, closest first
+    pub duplicates: Vec<DuplicateSeal>,
+}
+
+impl From<SimilarityMatch> for DuplicateSeal {
+    fn from(m: SimilarityMatch) -> Self {
+        Self {
+            seal_id: m.record.seal_id,
+            hamming_distance: m.hamming_distance,
+            media_type: m.record.media_type,
+            created_at: m.record.created_at,
+        }
+    }
+}
+
+/// List a seal's visual variants
+///
+/// Returns other sealed images that look like this seal's image (possible
+/// re-uploads or manipulated variants). Seals without a perceptual hash have
+/// no variants.
+#[utoipa::path(
+    get,
+    path = "/api/v1/seals/{seal_id}/duplicates",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)"),
+        DuplicatesQuery
+    ),
+    responses(
+        (status = 200, description = "Visual variants of the seal", body = DuplicatesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn seal_duplicates_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(seal_id): Path<Uuid>,
+    Query(query): Query<DuplicatesQuery>,
+) -> Result<Json<DuplicatesResponse>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_DUPLICATES_LIMIT)
+        .clamp(1, MAX_DUPLICATES_LIMIT);
+
+    let store = state
+        .manifest_store
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Manifest store not configured"))?;
+    require_own_seal(&state, seal_id, &auth.tenant()).await?;
+
+    let key = seal_id.to_string();
+    let store_error = |e: ManifestStoreError| {
+        tracing::error!(error = %e, "Failed to find seal variants");
+        ApiError::internal("A database error occurred")
+    };
+
+    let mut response = DuplicatesResponse {
+        seal_id,
+        variants: 0,
+        clustered: false,
+        duplicates: Vec::new(),
+    };
+
+    let Some(phash) = store
+        .get_by_seal_id(&key)
+        .await
+        .map_err(store_error)?
+        .and_then(|manifest| manifest.perceptual_hash)
+    else {
+        return Ok(Json(response));
+    };
+
+    if let Some((members, total)) = store
+        .cluster_members(&key, limit)
+        .await
+        .map_err(store_error)?
+    {
+        response.variants = total;
+        response.clustered = true;
+        response.duplicates = members.into_iter().map(DuplicateSeal::from).collect();
+        return Ok(Json(response));
+    }
+
+    // Not clustered yet: search directly, the seal itself included
+    let duplicates: Vec<DuplicateSeal> = store
+        .find_similar(&phash, state.duplicate_threshold, limit + 1)
+        .await
+        .map_err(store_error)?
+        .into_iter()
+        .filter(|m| m.record.seal_id != key)
+        .take(limit)
+        .map(DuplicateSeal::from)
+        .collect();
+    response.variants = duplicates.len() as i64;
+    response.duplicates = duplicates;
+
+    Ok(Json(response))
+}
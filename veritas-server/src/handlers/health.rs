@@ -4,11 +4,15 @@
 
 use std::sync::OnceLock;
 
-use axum::Json;
+use axum::{extract::State, http::StatusCode, Json};
 use serde::Serialize;
 use utoipa::ToSchema;
 use veritas_core::qrng::{QrngProviderConfig, QrngProviderFactory};
 
+use crate::maintenance::MaintenanceStatus;
+use crate::readiness::{DependencyHealth, ReadinessStatus};
+use crate::state::AppState;
+
 /// Cached QRNG availability status (computed once at first health check)
 static QRNG_AVAILABLE: OnceLock<bool> = OnceLock::new();
 
@@ -66,26 +70,62 @@ pub struct ReadyResponse {
     /// Whether the service is ready to accept traffic
     #[schema(example = true)]
     pub ready: bool,
+    /// Overall status: "ready", "degraded" or "unavailable"
+    pub status: ReadinessStatus,
     /// Optional message explaining status
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<&'static str>,
+    /// Status and latency of each dependency
+    pub dependencies: Vec<DependencyHealth>,
+    /// Read-only maintenance mode, when on: reads are served, writes get 503
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintenance: Option<MaintenanceStatus>,
 }
 
 /// Kubernetes readiness probe
 ///
-/// Returns 200 if the service is ready to accept traffic.
-/// Unlike /health, this is a simple yes/no check.
+/// Checks the database, manifest store, QRNG provider, Clerk JWKS and Solana
+/// RPC, each with a timeout, and reports their individual status and latency.
+/// Returns 503 when a critical dependency (the database) is down; other
+/// failures only mark the service as degraded. Reports are cached for
+/// `READY_CACHE_SECS` seconds. In read-only maintenance mode the instance
+/// stays ready, since it still serves reads, and reports the mode in
+/// `maintenance`.
 #[utoipa::path(
     get,
     path = "/ready",
     tag = "Health",
     responses(
-        (status = 200, description = "Service is ready", body = ReadyResponse)
+        (status = 200, description = "Service is ready, possibly degraded", body = ReadyResponse),
+        (status = 503, description = "A critical dependency is down", body = ReadyResponse)
     )
 )]
-pub async fn ready() -> Json<ReadyResponse> {
-    Json(ReadyResponse {
-        ready: true,
-        message: None,
-    })
+pub async fn ready(State(state): State<AppState>) -> (StatusCode, Json<ReadyResponse>) {
+    let report = state.readiness.report().await;
+
+    let maintenance = Some(state.maintenance.status()).filter(|status| status.read_only);
+
+    let (status_code, message) = match report.status {
+        ReadinessStatus::Ready if maintenance.is_some() => (
+            StatusCode::OK,
+            Some("Read-only maintenance, writes are paused"),
+        ),
+        ReadinessStatus::Ready => (StatusCode::OK, None),
+        ReadinessStatus::Degraded => (StatusCode::OK, Some("Some dependencies are down")),
+        ReadinessStatus::Unavailable => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Some("A critical dependency is down"),
+        ),
+    };
+
+    (
+        status_code,
+        Json(ReadyResponse {
+            ready: report.status != ReadinessStatus::Unavailable,
+            status: report.status,
+            message,
+            dependencies: report.dependencies,
+            maintenance,
+        }),
+    )
 }
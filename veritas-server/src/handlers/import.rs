@@ -0,0 +1,456 @@
+//! Seal import handler
+//!
+//! Seals created offline (by the CLI or a capture app without network)
+//! never reach the server. `POST /api/v1/seals/import` stores such a seal
+//! under the authenticated user once it checks out: the CBOR must decode to
+//! a supported seal version with a valid signature and, when the media is
+//! sent along, the media must match the sealed content hash. Imported seals
+//! then appear in the seal list, verify as stored seals and are queued for
+//! anchoring like seals made on the server.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Multipart, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use veritas_core::{compute_phash, ContentHash, MediaType, VeritasSeal};
+
+use crate::auth::AuthenticatedUser;
+use crate::db::{CreateSeal, SealLocation, SealMetadata, SealRepository, Tenant};
+use crate::error::ApiError;
+use crate::handlers::seal::{
+    check_seal_quota, mock_entropy_headers, qrng_source_name, record_mock_entropy, trust_tier_name,
+};
+use crate::manifest_store::ManifestInput;
+use crate::multipart::{FileField, MultipartFields};
+use crate::state::AppState;
+use crate::trust::TrustEvidence;
+
+/// Response for an imported seal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportSealResponse {
+    /// ID of the stored seal
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub seal_id: String,
+    /// Sealed content hash (SHA3-256, hex-encoded)
+    #[schema(example = "a1b2c3d4e5f67890...")]
+    pub content_hash: String,
+    /// Perceptual hash (hex-encoded), from the seal or computed from the
+    /// imported image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "a1b2c3d4e5f67890")]
+    pub perceptual_hash: Option<String>,
+    /// Media type of the seal
+    #[schema(example = "image")]
+    pub media_type: String,
+    /// Capture time recorded in the seal (RFC 3339)
+    #[schema(example = "2026-01-08T10:00:00+00:00")]
+    pub captured_at: String,
+    /// Trust tier of the stored seal
+    #[schema(example = "tier1")]
+    pub trust_tier: String,
+    /// QRNG source the seal was created with
+    #[schema(example = "lfd")]
+    pub qrng_source: String,
+    /// Whether the seal was made with mock entropy (not quantum-safe)
+    #[schema(example = false)]
+    pub mock_entropy: bool,
+    /// Whether the media was sent and matches the seal
+    #[schema(example = true)]
+    pub media_verified: bool,
+    /// Whether the user had already imported or created this seal, in which
+    /// case the stored seal is returned
+    #[schema(example = false)]
+    pub existing: bool,
+}
+
+/// Import a seal created offline
+///
+/// Accepts multipart/form-data with:
+/// - **seal_data** (required): Base64-encoded CBOR seal (the contents of a
+///   `.veritas` file)
+/// - **file** (optional): the sealed media; it must match the seal's
+///   content hash, and images get a perceptual hash (used by `/resolve`)
+///   when the seal has none
+///
+/// The seal must be a supported version with a valid ML-DSA-65 signature at
+/// its capture time. It is stored under the authenticated user with
+/// `capture_source: "import"` and counts towards the monthly seal quota.
+/// Importing a seal the user already has returns it with 200; a seal stored
+/// by another account is refused.
+#[utoipa::path(
+    post,
+    path = "/api/v1/seals/import",
+    tag = "Seals",
+    request_body(
+        content_type = "multipart/form-data",
+        description = "Seal (seal_data) and optional media (file)"
+    ),
+    responses(
+        (status = 200, description = "Seal already stored for the user", body = ImportSealResponse),
+        (status = 201, description = "Seal imported", body = ImportSealResponse),
+        (status = 400, description = "Missing or invalid seal, invalid signature, media not matching the seal, or mock entropy not allowed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Seal stored by another account"),
+        (status = 413, description = "File exceeds the upload size limit for its media type"),
+        (status = 429, description = "Monthly seal quota exceeded"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn import_seal_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, HeaderMap, Json<ImportSealResponse>), ApiError> {
+    let seal_repo = state
+        .seal_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+    let user_id = auth.user.id;
+
+    let fields = MultipartFields::parse_with_policy(&mut multipart, &state.upload_policy).await?;
+    let seal_b64 = fields
+        .get_text("seal_data")
+        .ok_or_else(|| ApiError::bad_request("No seal_data provided."))?;
+    let (seal_cbor, seal) = decode_offline_seal(&state, seal_b64).await?;
+
+    let media_phash = match fields.get_file() {
+        Some(file) => Some(check_media(&state, &seal, file).await?),
+        None => None,
+    };
+    let media_verified = media_phash.is_some();
+    let perceptual_hash = seal
+        .content_hash
+        .perceptual_hash
+        .clone()
+        .or(media_phash.flatten());
+
+    let content_hash = hex::encode(seal.content_hash.crypto_hash);
+    let existing = seal_repo
+        .find_owner_by_signature(&content_hash, &seal.signature)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to look up imported seal");
+            ApiError::internal("A database error occurred")
+        })?;
+    match existing {
+        Some((seal_id, Some(owner_id))) if owner_id == user_id => {
+            return existing_seal(seal_repo, &seal, seal_id, &auth.tenant(), media_verified).await;
+        }
+        Some(_) => return Err(ApiError::conflict("Seal stored by another account")),
+        None => {}
+    }
+
+    check_seal_quota(&state, &auth.tenant()).await?;
+
+    let mut record = offline_seal_record(
+        &state,
+        &auth,
+        &seal,
+        perceptual_hash.clone(),
+        None,
+        "import",
+    );
+    if let Some(file) = fields.get_file() {
+        record.file_size = Some(file.len() as i32);
+        record.mime_type = file
+            .content_type
+            .clone()
+            .or_else(|| file.detected_mime.map(str::to_string));
+    }
+    let trust_tier = record.trust_tier;
+    let captured_at = record.captured_at;
+    let stored = seal_repo.create(record).await.map_err(|e| {
+        tracing::error!(user_id = %user_id, error = %e, "Failed to store imported seal");
+        ApiError::internal("A database error occurred")
+    })?;
+
+    let mock_entropy = seal.uses_mock_entropy();
+    finish_offline_seal(
+        &state,
+        stored.id,
+        user_id,
+        &seal,
+        seal_cbor,
+        perceptual_hash.clone(),
+        "import",
+    )
+    .await;
+    tracing::info!(
+        seal_id = %stored.id,
+        user_id = %user_id,
+        media_verified,
+        "Seal imported"
+    );
+
+    let response = ImportSealResponse {
+        seal_id: stored.id.to_string(),
+        content_hash,
+        perceptual_hash: perceptual_hash.map(hex::encode),
+        media_type: seal.media_type.to_string(),
+        captured_at: captured_at.to_rfc3339(),
+        trust_tier: trust_tier_name(trust_tier).to_string(),
+        qrng_source: qrng_source_name(&seal).to_string(),
+        mock_entropy,
+        media_verified,
+        existing: false,
+    };
+    Ok((
+        StatusCode::CREATED,
+        mock_entropy_headers(mock_entropy),
+        Json(response),
+    ))
+}
+
+/// Decode a base64 seal made offline (the contents of a `.veritas` file)
+///
+/// Returns its CBOR and the seal once it decodes to a supported version,
+/// its signature verifies, and it does not use mock entropy unless the
+/// server allows it.
+pub(crate) async fn decode_offline_seal(
+    state: &AppState,
+    seal_b64: &str,
+) -> Result<(Vec<u8>, Arc<VeritasSeal>), ApiError> {
+    let seal_cbor = BASE64
+        .decode(seal_b64.trim())
+        .map_err(|e| ApiError::bad_request(format!("Invalid base64 in seal_data: {}", e)))?;
+    let seal = Arc::new(
+        VeritasSeal::from_cbor(&seal_cbor)
+            .map_err(|e| ApiError::bad_request(format!("Invalid seal format: {}", e)))?,
+    );
+
+    if seal.uses_mock_entropy() && !state.allow_mock_qrng {
+        return Err(ApiError::bad_request(
+            "Seals made with mock entropy are not accepted by this server",
+        ));
+    }
+    check_signature(state, &seal).await?;
+    Ok((seal_cbor, seal))
+}
+
+/// Record storing a seal made offline under the authenticated user, with the
+/// seal's capture time and the trust tier of the account
+pub(crate) fn offline_seal_record(
+    state: &AppState,
+    auth: &AuthenticatedUser,
+    seal: &VeritasSeal,
+    perceptual_hash: Option<Vec<u8>>,
+    location: Option<SealLocation>,
+    capture_source: &str,
+) -> CreateSeal {
+    let trust = state.trust_policy.assess(&TrustEvidence {
+        account_tier: Some(auth.user.tier),
+        attestation: None,
+    });
+    let captured_at = capture_time(seal);
+    let metadata = SealMetadata {
+        timestamp: captured_at.to_rfc3339(),
+        location,
+        device: None,
+        capture_source: capture_source.to_string(),
+        has_device_attestation: seal.device_attestation.is_some(),
+        trust_rationale: trust.rationale,
+    };
+
+    CreateSeal {
+        user_id: Some(auth.user.id),
+        organization_id: auth.user.organization_id,
+        content_hash: hex::encode(seal.content_hash.crypto_hash),
+        perceptual_hash,
+        qrng_entropy: seal.qrng_entropy.to_vec(),
+        qrng_source: qrng_source_name(seal).to_string(),
+        signature: seal.signature.clone(),
+        public_key: seal.public_key.clone(),
+        media_type: seal.media_type.to_string(),
+        file_size: None,
+        mime_type: None,
+        metadata: serde_json::to_value(&metadata).unwrap_or_default(),
+        trust_tier: trust.tier,
+        c2pa_manifest_embedded: false,
+        captured_at,
+    }
+}
+
+/// Finish storing a seal made offline (non-fatal): keep the full seal in the
+/// manifest store, queue it for anchoring and audit mock entropy.
+pub(crate) async fn finish_offline_seal(
+    state: &AppState,
+    seal_id: Uuid,
+    user_id: Uuid,
+    seal: &VeritasSeal,
+    seal_cbor: Vec<u8>,
+    perceptual_hash: Option<Vec<u8>>,
+    endpoint: &str,
+) {
+    // The manifest store lets /verify and /resolve find the full seal
+    if let Some(store) = &state.manifest_store {
+        let input = ManifestInput {
+            seal_id: seal_id.to_string(),
+            perceptual_hash,
+            image_hash: hex::encode(seal.content_hash.crypto_hash),
+            seal_cbor,
+            media_type: seal.media_type.to_string(),
+        };
+        if let Err(e) = store.store(&input).await {
+            tracing::warn!(seal_id = %seal_id, error = %e, "Failed to store offline seal manifest");
+        }
+    }
+
+    // Offline seals could not be anchored when they were made
+    if seal.anchors().next().is_none() {
+        if let Some(batcher) = &state.anchor_batcher {
+            batcher.submit(seal.anchor_digest());
+        }
+    }
+
+    if seal.uses_mock_entropy() {
+        record_mock_entropy(state, endpoint, Some(seal_id), Some(user_id)).await;
+    }
+}
+
+/// Refuse a seal whose signature does not verify at its capture time, so
+/// seals whose validity window has since closed can still be imported.
+async fn check_signature(state: &AppState, seal: &Arc<VeritasSeal>) -> Result<(), ApiError> {
+    let result = {
+        let seal = Arc::clone(seal);
+        state
+            .verification_pool
+            .run(move || seal.verify_detailed_at(seal.capture_timestamp_utc))
+            .await?
+    }
+    .map_err(|e| {
+        tracing::error!(error = %e, "Import verification error");
+        ApiError::internal("Verification processing failed")
+    })?;
+
+    if !result.is_valid() {
+        return Err(ApiError::bad_request(format!(
+            "Invalid seal: {}",
+            result.description()
+        )));
+    }
+    Ok(())
+}
+
+/// Check that the media is the sealed content; returns its perceptual hash
+/// for images.
+async fn check_media(
+    state: &AppState,
+    seal: &Arc<VeritasSeal>,
+    file: &FileField,
+) -> Result<Option<Vec<u8>>, ApiError> {
+    state.upload_policy.check_upload(file, &seal.media_type)?;
+
+    // Large uploads spooled to disk are hashed from the temp file, except
+    // images and documents whose perceptual/canonical hashes need the bytes
+    let (matches, perceptual_hash) = match &file.spooled {
+        Some(spooled) if !matches!(seal.media_type, MediaType::Image | MediaType::Document) => {
+            let content_hash = spooled.content_hash().await?;
+            (
+                content_hash.crypto_hash == seal.content_hash.crypto_hash,
+                None,
+            )
+        }
+        _ => {
+            let content = file.contents().await?;
+            let seal = Arc::clone(seal);
+            state
+                .verification_pool
+                .run(move || {
+                    let matches = ContentHash::from_bytes(&content).crypto_hash
+                        == seal.content_hash.crypto_hash
+                        || seal.matches_canonical_form(&content);
+                    let perceptual_hash = match seal.media_type {
+                        MediaType::Image => compute_phash(&content),
+                        _ => None,
+                    };
+                    (matches, perceptual_hash)
+                })
+                .await?
+        }
+    };
+
+    if !matches {
+        return Err(ApiError::bad_request("The media does not match the seal"));
+    }
+    Ok(perceptual_hash)
+}
+
+/// Response for a seal the user already has
+async fn existing_seal(
+    seal_repo: &SealRepository,
+    seal: &VeritasSeal,
+    seal_id: Uuid,
+    tenant: &Tenant,
+    media_verified: bool,
+) -> Result<(StatusCode, HeaderMap, Json<ImportSealResponse>), ApiError> {
+    let stored = seal_repo
+        .find_by_id_for_user(seal_id, tenant)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to get imported seal");
+            ApiError::internal("A database error occurred")
+        })?
+        .ok_or_else(|| ApiError::not_found("Seal not found"))?;
+
+    let mock_entropy = seal.uses_mock_entropy();
+    let response = ImportSealResponse {
+        seal_id: stored.id.to_string(),
+        content_hash: stored.content_hash,
+        perceptual_hash: stored.perceptual_hash.map(hex::encode),
+        media_type: stored.media_type,
+        captured_at: stored.captured_at.to_rfc3339(),
+        trust_tier: trust_tier_name(stored.trust_tier).to_string(),
+        qrng_source: stored.qrng_source,
+        mock_entropy,
+        media_verified,
+        existing: true,
+    };
+    Ok((
+        StatusCode::OK,
+        mock_entropy_headers(mock_entropy),
+        Json(response),
+    ))
+}
+
+/// Capture time recorded in the seal, or now if it is out of range
+fn capture_time(seal: &VeritasSeal) -> DateTime<Utc> {
+    i64::try_from(seal.capture_timestamp_utc)
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use veritas_core::{generate_keypair, MockQrng, SealBuilder};
+
+    #[tokio::test]
+    async fn test_capture_time() {
+        let (public_key, secret_key) = generate_keypair();
+        let mut seal = SealBuilder::new(b"offline".to_vec(), MediaType::Generic)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+
+        seal.capture_timestamp_utc = 1_767_866_400_000;
+        assert_eq!(
+            capture_time(&seal).to_rfc3339(),
+            "2026-01-08T10:00:00+00:00"
+        );
+
+        seal.capture_timestamp_utc = u64::MAX;
+        assert!(capture_time(&seal) <= Utc::now());
+    }
+}
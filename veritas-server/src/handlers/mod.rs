@@ -2,27 +2,119 @@
 //!
 //! This module contains all the request handlers for the API endpoints.
 
+pub mod admin;
+pub mod anchors;
+pub mod api_keys;
+pub mod auth;
+pub mod batch_export;
 #[cfg(feature = "c2pa")]
 pub mod c2pa;
+pub mod collections;
+pub mod data_export;
+pub mod duplicates;
 pub mod health;
+pub mod import;
+pub mod organizations;
 pub mod resolve;
 pub mod seal;
 pub mod seals;
+pub mod share;
+pub mod signing_keys;
+pub mod stream;
+pub mod sync;
+pub mod tags;
+pub mod upload;
 pub mod user;
+pub mod verifications;
 pub mod verify;
 
 pub use crate::state::AppState;
+pub use admin::{
+    admin_list_users_handler, admin_stats_handler, admin_tasks_handler, get_maintenance_handler,
+    list_revoked_seals_handler, reinstate_seal_handler, revoke_seal_handler,
+    revoke_signing_key_handler, suspend_user_handler, unsuspend_user_handler,
+    update_legal_hold_handler, update_maintenance_handler, update_user_organization_handler,
+    update_user_tier_handler, AdminListSealsQuery, AdminListUsersQuery, AdminStatsQuery,
+    AdminStatsResponse, AdminTasksResponse, AdminUserListResponse, AdminUserRecord,
+    LegalHoldResponse, RevokeSealRequest, UpdateLegalHoldRequest, UpdateMaintenanceRequest,
+    UpdateOrganizationRequest, UpdateTierRequest,
+};
+pub use anchors::{anchor_status_handler, AnchorStatusResponse};
+pub use api_keys::{
+    create_api_key_handler, list_api_keys_handler, revoke_api_key_handler, CreateApiKeyRequest,
+    CreateApiKeyResponse, ListApiKeysResponse,
+};
+pub use auth::{login_handler, register_handler, AuthTokenResponse, LoginRequest, RegisterRequest};
+pub use batch_export::{
+    batch_export_handler, batch_export_status_handler, BatchExportFilter, BatchExportRequest,
+};
 #[cfg(feature = "c2pa")]
 pub use c2pa::{c2pa_embed_handler, c2pa_verify_handler, C2paEmbedResponse, C2paVerifyResponse};
+pub use collections::{
+    add_collection_seal_handler, create_collection_handler, delete_collection_handler,
+    get_collection_handler, list_collections_handler, remove_collection_seal_handler,
+    update_collection_handler, CollectionRequest, ListCollectionsResponse,
+};
+pub use data_export::{
+    download_export_handler, export_user_data_handler, DataExportResponse, ExportDownloadQuery,
+};
+pub use duplicates::{seal_duplicates_handler, DuplicateSeal, DuplicatesQuery, DuplicatesResponse};
 pub use health::{health, ready, HealthResponse, ReadyResponse};
-pub use resolve::{resolve_handler, ResolveMatch, ResolveRequest, ResolveResponse};
-pub use seal::{seal_handler, SealResponse};
+pub use import::{import_seal_handler, ImportSealResponse};
+pub use organizations::{
+    branding_handler, delete_organization_settings_handler, delete_retention_policy_handler,
+    get_organization_settings_handler, get_retention_policy_handler,
+    update_organization_settings_handler, update_retention_policy_handler, BrandingQuery,
+    UpdateOrganizationSettingsRequest, UpdateRetentionPolicyRequest,
+};
+pub use resolve::{
+    resolve_batch_handler, resolve_handler, BatchResolveItem, BatchResolveRequest,
+    BatchResolveResponse, BatchResolveResult, ResolveMatch, ResolveRequest, ResolveResponse,
+};
+pub use seal::{
+    seal_handler, SealResponse, ANCHOR_DIGEST_HEADER, MOCK_ENTROPY_HEADER, PERCEPTUAL_HASH_HEADER,
+    QRNG_SOURCE_HEADER, SEAL_ID_HEADER, TRUST_TIER_HEADER,
+};
 pub use seals::{
-    export_seal_handler, get_user_seal_handler, list_user_seals_handler, C2paExportResponse,
-    ExportFormat, ExportResponse, ExportSealQuery, JsonExportResponse, SealDetailResponse,
+    export_seal_handler, get_user_seal_handler, list_user_seals_handler, seal_map_handler,
+    seal_qr_handler, seal_report_handler, C2paExportResponse, ExportFormat, ExportResponse,
+    ExportSealQuery, JsonExportResponse, QrImageFormat, QrPayload, ReportFormat,
+    SealDetailResponse, SealMapCluster, SealMapQuery, SealMapResponse, SealQrQuery,
+    SealReportQuery,
+};
+pub use share::{
+    create_share_handler, list_shares_handler, revoke_share_handler, shared_seal_handler,
+    CreateShareRequest, CreateShareResponse, ListSharesResponse, SharedSealResponse,
+};
+pub use signing_keys::{
+    did_document_handler, list_signing_keys_handler, SigningKeyListResponse, SigningKeyRecord,
+};
+pub use stream::{
+    finish_stream_handler, start_stream_handler, stream_chunk_handler, CheckpointResponse,
+    FinishStreamResponse, StartStreamRequest, StartStreamResponse, StreamChunkResponse,
+};
+pub use sync::{
+    sync_pull_handler, sync_push_handler, SyncPullQuery, SyncPullResponse, SyncPushRequest,
+    SyncPushResponse, SyncPushResult, SyncPushSeal, SyncPushStatus, SyncSealRecord,
+};
+pub use tags::{
+    delete_tag_handler, get_seal_tags_handler, list_tags_handler, rename_tag_handler,
+    set_seal_tags_handler, ListTagsResponse, RenameTagRequest, SealTagsResponse,
+    SetSealTagsRequest,
+};
+pub use upload::{
+    create_upload_handler, delete_upload_handler, finalize_upload_handler, upload_chunk_handler,
+    upload_status_handler, CreateUploadRequest, FinalizeUploadRequest, UploadResponse,
 };
 pub use user::{
-    delete_user_handler, get_current_user_handler, sync_user_handler, CurrentUserResponse,
-    DeleteUserResponse, SyncUserRequest, SyncUserResponse,
+    delete_user_handler, get_current_user_handler, get_current_user_stats_handler,
+    sync_user_handler, CurrentUserResponse, DeleteUserResponse, QuotaUsage, SealCounts,
+    StorageUsage, SyncUserRequest, SyncUserResponse, UserStatsResponse,
+};
+pub use verifications::{
+    seal_verifications_handler, VerificationTrendsQuery, VerificationTrendsResponse,
+};
+pub use verify::{
+    verify_handler, AnchorInfo, ChangedRegionInfo, LivenessInfo, PolicyInfo, PolicyViolationInfo,
+    SigningKeyInfo, TamperedSegmentInfo, VerifyResponse,
 };
-pub use verify::{verify_handler, VerifyResponse};
@@ -0,0 +1,592 @@
+//! Organization branding and retention handlers
+//!
+//! Administrators set each organization's display name, logo and custom
+//! verification domain under `/api/v1/admin/organizations`. The public
+//! verification endpoints (`/verify`, `/api/v1/shared/{token}`) return that
+//! branding for seals of the organization, or for requests made from its
+//! verification domain (`Origin`, else `Host`), so enterprise customers can
+//! serve white-labelled verification pages from this backend.
+//!
+//! Administrators also set how long an organization's media and seals are
+//! kept; the retention janitor task deletes them afterwards (see
+//! [`crate::db::retention`]).
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::auth::AdminUser;
+use crate::db::{
+    Branding, OrganizationSettings, OrganizationSettingsRepository, RetentionPolicy,
+    RetentionPolicyRepository,
+};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Longest organization display name, in characters
+pub const MAX_DISPLAY_NAME_CHARS: usize = 100;
+
+/// Longest logo URL, in bytes
+pub const MAX_LOGO_URL_LEN: usize = 2048;
+
+/// Longest retention period, in days (100 years)
+pub const MAX_RETENTION_DAYS: i32 = 36_500;
+
+/// Request to set an organization's branding
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateOrganizationSettingsRequest {
+    /// Name shown on verification pages
+    #[schema(example = "Agence France Presse")]
+    pub display_name: String,
+    /// HTTPS URL of the logo
+    #[serde(default)]
+    #[schema(example = "https://cdn.example.com/logo.svg")]
+    pub logo_url: Option<String>,
+    /// Host name serving the organization's verification pages (unique)
+    #[serde(default)]
+    #[schema(example = "verify.example.com")]
+    pub verification_domain: Option<String>,
+}
+
+/// Request to set an organization's retention policy
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRetentionPolicyRequest {
+    /// Days after sealing before the media is deleted (null = kept)
+    #[serde(default)]
+    #[schema(example = 90)]
+    pub media_retention_days: Option<i32>,
+    /// Days after sealing before the seal is deleted (null = kept)
+    #[serde(default)]
+    #[schema(example = 2557)]
+    pub seal_retention_days: Option<i32>,
+}
+
+/// Query parameters for looking up branding
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BrandingQuery {
+    /// Verification domain (default: the request's `Origin`, else `Host`)
+    pub domain: Option<String>,
+}
+
+/// Get an organization's branding settings
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/organizations/{organization_id}/settings",
+    tag = "Admin",
+    params(
+        ("organization_id" = String, Path, description = "Organization ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Organization settings", body = OrganizationSettings),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "Organization has no settings"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn get_organization_settings_handler(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Path(organization_id): Path<Uuid>,
+) -> Result<Json<OrganizationSettings>, ApiError> {
+    organization_repo(&state)?
+        .get(organization_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to get organization settings");
+            ApiError::internal("A database error occurred")
+        })?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Organization has no settings"))
+}
+
+/// Set an organization's branding settings
+///
+/// Creates or replaces the display name, logo URL (HTTPS) and custom
+/// verification domain of the organization.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/organizations/{organization_id}/settings",
+    tag = "Admin",
+    params(
+        ("organization_id" = String, Path, description = "Organization ID (UUID)")
+    ),
+    request_body = UpdateOrganizationSettingsRequest,
+    responses(
+        (status = 200, description = "Organization settings saved", body = OrganizationSettings),
+        (status = 400, description = "Invalid display name, logo URL or domain"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 409, description = "Verification domain used by another organization"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn update_organization_settings_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(organization_id): Path<Uuid>,
+    Json(request): Json<UpdateOrganizationSettingsRequest>,
+) -> Result<Json<OrganizationSettings>, ApiError> {
+    let display_name = validate_display_name(&request.display_name)?;
+    let logo_url = request
+        .logo_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(validate_logo_url)
+        .transpose()?;
+    let verification_domain = request
+        .verification_domain
+        .as_deref()
+        .map(str::trim)
+        .filter(|domain| !domain.is_empty())
+        .map(|domain| {
+            normalize_domain(domain).ok_or_else(|| {
+                ApiError::bad_request(format!("Invalid verification domain: {}", domain))
+            })
+        })
+        .transpose()?;
+
+    let settings = organization_repo(&state)?
+        .upsert(
+            organization_id,
+            display_name,
+            logo_url,
+            verification_domain.as_deref(),
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to save organization settings");
+            ApiError::internal("A database error occurred")
+        })?
+        .ok_or_else(|| ApiError::conflict("Verification domain is used by another organization"))?;
+
+    tracing::info!(
+        admin_id = %admin.user.id,
+        organization_id = %organization_id,
+        "Admin updated organization settings"
+    );
+    Ok(Json(settings))
+}
+
+/// Delete an organization's branding settings
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/organizations/{organization_id}/settings",
+    tag = "Admin",
+    params(
+        ("organization_id" = String, Path, description = "Organization ID (UUID)")
+    ),
+    responses(
+        (status = 204, description = "Organization settings deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "Organization has no settings"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn delete_organization_settings_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(organization_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = organization_repo(&state)?
+        .delete(organization_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to delete organization settings");
+            ApiError::internal("A database error occurred")
+        })?;
+    if !deleted {
+        return Err(ApiError::not_found("Organization has no settings"));
+    }
+
+    tracing::info!(
+        admin_id = %admin.user.id,
+        organization_id = %organization_id,
+        "Admin deleted organization settings"
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get an organization's retention policy
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/organizations/{organization_id}/retention",
+    tag = "Admin",
+    params(
+        ("organization_id" = String, Path, description = "Organization ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Retention policy", body = RetentionPolicy),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "Organization has no retention policy"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn get_retention_policy_handler(
+    State(state): State<AppState>,
+    AdminUser(_admin): AdminUser,
+    Path(organization_id): Path<Uuid>,
+) -> Result<Json<RetentionPolicy>, ApiError> {
+    retention_repo(&state)?
+        .get(organization_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to get retention policy");
+            ApiError::internal("A database error occurred")
+        })?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Organization has no retention policy"))
+}
+
+/// Set an organization's retention policy
+///
+/// Creates or replaces how many days after sealing the organization's media
+/// is deleted (the seal stays verifiable) and its seals are deleted. At
+/// least one must be set. Data already past the new periods is deleted at
+/// the next retention run, within the hour, each deletion being audited.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/organizations/{organization_id}/retention",
+    tag = "Admin",
+    params(
+        ("organization_id" = String, Path, description = "Organization ID (UUID)")
+    ),
+    request_body = UpdateRetentionPolicyRequest,
+    responses(
+        (status = 200, description = "Retention policy saved", body = RetentionPolicy),
+        (status = 400, description = "Invalid retention periods"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn update_retention_policy_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(organization_id): Path<Uuid>,
+    Json(request): Json<UpdateRetentionPolicyRequest>,
+) -> Result<Json<RetentionPolicy>, ApiError> {
+    validate_retention(&request)?;
+
+    let policy = retention_repo(&state)?
+        .upsert(
+            organization_id,
+            request.media_retention_days,
+            request.seal_retention_days,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to save retention policy");
+            ApiError::internal("A database error occurred")
+        })?;
+
+    tracing::info!(
+        admin_id = %admin.user.id,
+        organization_id = %organization_id,
+        media_retention_days = ?policy.media_retention_days,
+        seal_retention_days = ?policy.seal_retention_days,
+        "Admin updated retention policy"
+    );
+    Ok(Json(policy))
+}
+
+/// Delete an organization's retention policy
+///
+/// The organization's media and seals are then kept indefinitely.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/organizations/{organization_id}/retention",
+    tag = "Admin",
+    params(
+        ("organization_id" = String, Path, description = "Organization ID (UUID)")
+    ),
+    responses(
+        (status = 204, description = "Retention policy deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Administrator role required"),
+        (status = 404, description = "Organization has no retention policy"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn delete_retention_policy_handler(
+    State(state): State<AppState>,
+    AdminUser(admin): AdminUser,
+    Path(organization_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = retention_repo(&state)?
+        .delete(organization_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to delete retention policy");
+            ApiError::internal("A database error occurred")
+        })?;
+    if !deleted {
+        return Err(ApiError::not_found("Organization has no retention policy"));
+    }
+
+    tracing::info!(
+        admin_id = %admin.user.id,
+        organization_id = %organization_id,
+        "Admin deleted retention policy"
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get the branding of a verification domain
+///
+/// Public: lets a white-labelled verification page show the organization's
+/// name and logo before anything is verified.
+#[utoipa::path(
+    get,
+    path = "/api/v1/branding",
+    tag = "Verification",
+    params(BrandingQuery),
+    responses(
+        (status = 200, description = "Branding of the domain", body = Branding),
+        (status = 404, description = "No organization uses the domain"),
+        (status = 503, description = "Database not available")
+    )
+)]
+pub async fn branding_handler(
+    State(state): State<AppState>,
+    Query(query): Query<BrandingQuery>,
+    headers: HeaderMap,
+) -> Result<Json<Branding>, ApiError> {
+    let repo = organization_repo(&state)?;
+    let domain = match query.domain.as_deref() {
+        Some(domain) => normalize_domain(domain),
+        None => request_domain(&headers),
+    }
+    .ok_or_else(|| ApiError::not_found("No branding for this domain"))?;
+
+    repo.find_by_domain(&domain)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to look up branding");
+            ApiError::internal("A database error occurred")
+        })?
+        .map(|settings| Json(Branding::from(settings)))
+        .ok_or_else(|| ApiError::not_found("No branding for this domain"))
+}
+
+/// Branding of a verification response: the settings of the seal's
+/// organization, else of the organization whose verification domain the
+/// request comes from (non-fatal; `None` without a database)
+pub(crate) async fn response_branding(
+    state: &AppState,
+    organization_id: Option<Uuid>,
+    headers: &HeaderMap,
+) -> Option<Branding> {
+    let repo = state.organization_repo.as_ref()?;
+    let settings = match organization_id {
+        Some(organization_id) => repo.get(organization_id).await,
+        None => Ok(None),
+    };
+    let settings = match settings {
+        Ok(None) => match request_domain(headers) {
+            Some(domain) => repo.find_by_domain(&domain).await,
+            None => Ok(None),
+        },
+        settings => settings,
+    };
+    settings
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to look up branding");
+            None
+        })
+        .map(Branding::from)
+}
+
+/// Host a request was made from: the `Origin` host (the verification page),
+/// else the `Host` header (the API served on the custom domain)
+fn request_domain(headers: &HeaderMap) -> Option<String> {
+    let header_text = |name: header::HeaderName| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    header_text(header::ORIGIN)
+        .and_then(|origin| url::Url::parse(&origin).ok())
+        .and_then(|url| url.host_str().and_then(normalize_domain))
+        .or_else(|| {
+            header_text(header::HOST).and_then(|host| {
+                // Strip the port
+                let host = host
+                    .rsplit_once(':')
+                    .map_or(host.as_str(), |(host, _)| host);
+                normalize_domain(host)
+            })
+        })
+}
+
+/// Lowercase host name with at least two labels of letters, digits and
+/// inner hyphens, or `None`
+fn normalize_domain(domain: &str) -> Option<String> {
+    let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+    let valid_label = |label: &str| {
+        (1..=63).contains(&label.len())
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    };
+    (domain.len() <= 253 && domain.contains('.') && domain.split('.').all(valid_label))
+        .then_some(domain)
+}
+
+fn validate_display_name(display_name: &str) -> Result<&str, ApiError> {
+    let display_name = display_name.trim();
+    if display_name.is_empty() || display_name.chars().count() > MAX_DISPLAY_NAME_CHARS {
+        return Err(ApiError::bad_request(format!(
+            "display_name must be 1 to {} characters",
+            MAX_DISPLAY_NAME_CHARS
+        )));
+    }
+    Ok(display_name)
+}
+
+fn validate_logo_url(logo_url: &str) -> Result<&str, ApiError> {
+    let valid = logo_url.len() <= MAX_LOGO_URL_LEN
+        && url::Url::parse(logo_url)
+            .is_ok_and(|url| url.scheme() == "https" && url.host_str().is_some());
+    if !valid {
+        return Err(ApiError::bad_request("logo_url must be an HTTPS URL"));
+    }
+    Ok(logo_url)
+}
+
+fn validate_retention(request: &UpdateRetentionPolicyRequest) -> Result<(), ApiError> {
+    if request.media_retention_days.is_none() && request.seal_retention_days.is_none() {
+        return Err(ApiError::bad_request(
+            "Set media_retention_days or seal_retention_days (delete the policy to keep data)",
+        ));
+    }
+    for (field, days) in [
+        ("media_retention_days", request.media_retention_days),
+        ("seal_retention_days", request.seal_retention_days),
+    ] {
+        if days.is_some_and(|days| !(1..=MAX_RETENTION_DAYS).contains(&days)) {
+            return Err(ApiError::bad_request(format!(
+                "{} must be 1 to {} days",
+                field, MAX_RETENTION_DAYS
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn organization_repo(state: &AppState) -> Result<&OrganizationSettingsRepository, ApiError> {
+    state
+        .organization_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+fn retention_repo(state: &AppState) -> Result<&RetentionPolicyRepository, ApiError> {
+    state
+        .retention_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_normalize_domain() {
+        assert_eq!(
+            normalize_domain(" Verify.Example.COM. "),
+            Some("verify.example.com".to_string())
+        );
+        assert_eq!(
+            normalize_domain("xn--vrifier-bya.fr"),
+            Some("xn--vrifier-bya.fr".to_string())
+        );
+        assert_eq!(normalize_domain("localhost"), None);
+        assert_eq!(normalize_domain("-bad.example.com"), None);
+        assert_eq!(normalize_domain("verify.example.com/path"), None);
+        assert_eq!(normalize_domain("a..b"), None);
+    }
+
+    #[test]
+    fn test_request_domain() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(request_domain(&headers), None);
+
+        headers.insert(
+            header::HOST,
+            HeaderValue::from_static("API.example.com:8443"),
+        );
+        assert_eq!(
+            request_domain(&headers),
+            Some("api.example.com".to_string())
+        );
+
+        // The page's origin wins over the API host
+        headers.insert(
+            header::ORIGIN,
+            HeaderValue::from_static("https://verify.example.org"),
+        );
+        assert_eq!(
+            request_domain(&headers),
+            Some("verify.example.org".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        assert_eq!(validate_display_name("  AFP ").unwrap(), "AFP");
+        assert!(validate_display_name(" ").is_err());
+        assert!(validate_display_name(&"x".repeat(MAX_DISPLAY_NAME_CHARS + 1)).is_err());
+
+        assert!(validate_logo_url("https://cdn.example.com/logo.svg").is_ok());
+        assert!(validate_logo_url("http://cdn.example.com/logo.svg").is_err());
+        assert!(validate_logo_url("javascript:alert(1)").is_err());
+    }
+
+    #[test]
+    fn test_validate_retention() {
+        let request = |media, seal| UpdateRetentionPolicyRequest {
+            media_retention_days: media,
+            seal_retention_days: seal,
+        };
+        assert!(validate_retention(&request(Some(90), Some(2557))).is_ok());
+        assert!(validate_retention(&request(Some(90), None)).is_ok());
+        assert!(validate_retention(&request(None, Some(MAX_RETENTION_DAYS))).is_ok());
+
+        assert!(validate_retention(&request(None, None)).is_err());
+        assert!(validate_retention(&request(Some(0), None)).is_err());
+        assert!(validate_retention(&request(Some(90), Some(-1))).is_err());
+        assert!(validate_retention(&request(None, Some(MAX_RETENTION_DAYS + 1))).is_err());
+    }
+}
@@ -1,15 +1,21 @@
-//! Soft binding resolution handler
+//! Soft binding resolution handlers
 //!
-//! Handles POST /resolve requests to find seals by perceptual hash similarity.
+//! Handles POST /resolve requests to find seals by perceptual hash similarity,
+//! and POST /resolve/batch for API clients screening many images at once.
 
 use axum::{extract::State, Json};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use uuid::Uuid;
 use veritas_core::compute_phash;
 
+use crate::auth::{ApiKeyAuth, OptionalAuth};
+use crate::db::{ApiKeyScope, AuditEvent};
 use crate::error::ApiError;
 use crate::handlers::AppState;
+use crate::manifest_store::SimilarityMatch;
+use crate::notifications::{Notification, NotificationEvent};
 
 /// Default similarity threshold (Hamming distance)
 const DEFAULT_THRESHOLD: u32 = 10;
@@ -17,6 +23,9 @@ const DEFAULT_THRESHOLD: u32 = 10;
 /// Default maximum results
 const DEFAULT_LIMIT: usize = 5;
 
+/// Maximum results per query
+const MAX_LIMIT: usize = 100;
+
 /// Request for resolving a seal by perceptual hash similarity.
 #[derive(Deserialize, ToSchema)]
 pub struct ResolveRequest {
@@ -92,6 +101,100 @@ pub struct ResolveMatch {
     pub seal_data: Option<String>,
 }
 
+impl ResolveMatch {
+    fn new(m: SimilarityMatch, include_seal_data: bool) -> Self {
+        Self {
+            seal_id: m.record.seal_id,
+            image_hash: m.record.image_hash,
+            hamming_distance: m.hamming_distance,
+            media_type: m.record.media_type,
+            created_at: m.record.created_at.to_rfc3339(),
+            seal_data: include_seal_data.then(|| BASE64.encode(&m.record.seal_cbor)),
+        }
+    }
+}
+
+/// One image to resolve in a batch.
+#[derive(Deserialize, ToSchema)]
+pub struct BatchResolveItem {
+    /// Client reference echoed in the result (e.g. the image URL).
+    #[serde(default)]
+    #[schema(example = "https://example.com/feed/42.jpg")]
+    pub id: Option<String>,
+
+    /// Base64-encoded image data to compute perceptual hash from.
+    /// Either `image_data` or `perceptual_hash` must be provided.
+    #[serde(default)]
+    pub image_data: Option<String>,
+
+    /// Hex-encoded perceptual hash (8 bytes = 16 hex chars).
+    #[serde(default)]
+    #[schema(example = "a1b2c3d4e5f67890")]
+    pub perceptual_hash: Option<String>,
+}
+
+/// Request for resolving many images at once.
+#[derive(Deserialize, ToSchema)]
+pub struct BatchResolveRequest {
+    /// Images to resolve (at most `BATCH_RESOLVE_MAX_ITEMS`, default 100).
+    pub items: Vec<BatchResolveItem>,
+
+    /// Maximum Hamming distance for similarity matching (default: 10).
+    #[serde(default)]
+    #[schema(example = 10)]
+    pub threshold: Option<u32>,
+
+    /// Maximum number of matches per item (default: 5).
+    #[serde(default)]
+    #[schema(example = 5)]
+    pub limit: Option<usize>,
+
+    /// Whether to include the full seal CBOR in matches (default: false).
+    #[serde(default)]
+    pub include_seal_data: Option<bool>,
+}
+
+/// Resolution result of one batch item.
+#[derive(Serialize, ToSchema)]
+pub struct BatchResolveResult {
+    /// Position of the item in the request.
+    #[schema(example = 0)]
+    pub index: usize,
+
+    /// Client reference of the item, if provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Whether any matches were found.
+    pub found: bool,
+
+    /// The matching seals, sorted by similarity (closest first).
+    pub matches: Vec<ResolveMatch>,
+
+    /// Why the item could not be resolved (invalid hash or image).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for a batch resolution.
+#[derive(Serialize, ToSchema)]
+pub struct BatchResolveResponse {
+    /// Number of items in the request.
+    #[schema(example = 3)]
+    pub count: usize,
+
+    /// Number of items with at least one match.
+    #[schema(example = 1)]
+    pub matched: usize,
+
+    /// Number of items that could not be resolved.
+    #[schema(example = 0)]
+    pub failed: usize,
+
+    /// One result per item, in request order.
+    pub results: Vec<BatchResolveResult>,
+}
+
 /// Resolve a seal by perceptual hash similarity.
 ///
 /// This endpoint enables "soft binding" resolution: finding the original
@@ -103,9 +206,11 @@ pub struct ResolveMatch {
 /// - `perceptual_hash`: Pre-computed hex-encoded perceptual hash
 ///
 /// Returns matching seals sorted by similarity (Hamming distance).
+/// Matches are recorded in the audit log, and the owners of the matched seals
+/// are alerted unless they made the request themselves.
 #[utoipa::path(
     post,
-    path = "/resolve",
+    path = "/api/v1/resolve",
     tag = "Resolution",
     request_body = ResolveRequest,
     responses(
@@ -116,6 +221,7 @@ pub struct ResolveMatch {
 )]
 pub async fn resolve_handler(
     State(state): State<AppState>,
+    OptionalAuth(auth): OptionalAuth,
     Json(request): Json<ResolveRequest>,
 ) -> Result<Json<ResolveResponse>, ApiError> {
     // Ensure manifest store is available
@@ -124,36 +230,14 @@ pub async fn resolve_handler(
         .as_ref()
         .ok_or_else(|| ApiError::service_unavailable("Manifest store not configured"))?;
 
-    // Get perceptual hash from request
-    let phash_bytes = if let Some(ref image_data) = request.image_data {
-        // Decode base64 image and compute perceptual hash
-        let image_bytes = BASE64
-            .decode(image_data)
-            .map_err(|e| ApiError::bad_request(format!("Invalid base64 image_data: {}", e)))?;
-
-        compute_phash(&image_bytes).ok_or_else(|| {
-            ApiError::bad_request("Failed to compute perceptual hash from image data")
-        })?
-    } else if let Some(ref phash_hex) = request.perceptual_hash {
-        // Decode hex perceptual hash
-        hex::decode(phash_hex)
-            .map_err(|e| ApiError::bad_request(format!("Invalid hex perceptual_hash: {}", e)))?
-    } else {
-        return Err(ApiError::bad_request(
-            "Either 'image_data' or 'perceptual_hash' must be provided",
-        ));
-    };
-
-    // Validate hash length (accept both legacy 5-byte and standard 8-byte hashes)
-    if phash_bytes.is_empty() || phash_bytes.len() > 8 {
-        return Err(ApiError::bad_request(format!(
-            "Perceptual hash must be 1-8 bytes, got {}",
-            phash_bytes.len()
-        )));
-    }
+    let phash_bytes = decode_phash(
+        request.image_data.as_deref(),
+        request.perceptual_hash.as_deref(),
+    )
+    .map_err(ApiError::bad_request)?;
 
     let threshold = request.threshold.unwrap_or(DEFAULT_THRESHOLD);
-    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).min(100); // Cap at 100
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
     let include_seal_data = request.include_seal_data.unwrap_or(false);
 
     // Search for similar manifests
@@ -168,20 +252,11 @@ pub async fn resolve_handler(
     // Convert to response format
     let response_matches: Vec<ResolveMatch> = matches
         .into_iter()
-        .map(|m| ResolveMatch {
-            seal_id: m.record.seal_id,
-            image_hash: m.record.image_hash,
-            hamming_distance: m.hamming_distance,
-            media_type: m.record.media_type,
-            created_at: m.record.created_at.to_rfc3339(),
-            seal_data: if include_seal_data {
-                Some(BASE64.encode(&m.record.seal_cbor))
-            } else {
-                None
-            },
-        })
+        .map(|m| ResolveMatch::new(m, include_seal_data))
         .collect();
 
+    record_matches(&state, auth.map(|a| a.user.id), &response_matches).await;
+
     let count = response_matches.len();
 
     Ok(Json(ResolveResponse {
@@ -190,3 +265,214 @@ pub async fn resolve_handler(
         matches: response_matches,
     }))
 }
+
+/// Resolve many images by perceptual hash similarity.
+///
+/// Bulk variant of `/resolve` for API clients such as fact-checking crawlers:
+/// each item is resolved independently, and an invalid item only fails its
+/// own result. Requires an API key with the `resolve:batch` scope; each key
+/// may submit `BATCH_RESOLVE_ITEMS_PER_MIN` items per minute. Matches are
+/// audited and alerted on as for `/resolve`, on behalf of the key's owner.
+#[utoipa::path(
+    post,
+    path = "/api/v1/resolve/batch",
+    tag = "Resolution",
+    request_body = BatchResolveRequest,
+    responses(
+        (status = 200, description = "Per-item resolution results", body = BatchResolveResponse),
+        (status = 400, description = "No items, or more than the maximum"),
+        (status = 401, description = "Missing or invalid API key"),
+        (status = 403, description = "API key lacks the resolve:batch scope"),
+        (status = 429, description = "API key's item budget exhausted for this minute"),
+        (status = 503, description = "Manifest store not available")
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn resolve_batch_handler(
+    State(state): State<AppState>,
+    auth: ApiKeyAuth,
+    Json(request): Json<BatchResolveRequest>,
+) -> Result<Json<BatchResolveResponse>, ApiError> {
+    auth.require_scope(ApiKeyScope::ResolveBatch)?;
+
+    let store = state
+        .manifest_store
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Manifest store not configured"))?;
+
+    let count = request.items.len();
+    if count == 0 {
+        return Err(ApiError::bad_request("At least one item is required"));
+    }
+    if count > state.batch_resolve_max_items {
+        return Err(ApiError::bad_request(format!(
+            "At most {} items per batch, got {}",
+            state.batch_resolve_max_items, count
+        )));
+    }
+
+    let limiter = &state.batch_resolve_limiter;
+    limiter
+        .try_acquire(auth.key.id, count as u64)
+        .map_err(|retry_after| {
+            ApiError::rate_limited(format!(
+                "Batch budget of {} items per minute exhausted, retry in {}s",
+                limiter.limit(),
+                retry_after.as_secs().max(1)
+            ))
+        })?;
+
+    let threshold = request.threshold.unwrap_or(DEFAULT_THRESHOLD);
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let include_seal_data = request.include_seal_data.unwrap_or(false);
+
+    let mut results = Vec::with_capacity(count);
+    for (index, item) in request.items.into_iter().enumerate() {
+        let mut result = BatchResolveResult {
+            index,
+            id: item.id,
+            found: false,
+            matches: Vec::new(),
+            error: None,
+        };
+
+        match decode_phash(item.image_data.as_deref(), item.perceptual_hash.as_deref()) {
+            Ok(phash_bytes) => {
+                let matches = store
+                    .find_similar(&phash_bytes, threshold, limit)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!(error = %e, "Failed to search for similar manifests");
+                        ApiError::internal("A database error occurred")
+                    })?;
+                result.matches = matches
+                    .into_iter()
+                    .map(|m| ResolveMatch::new(m, include_seal_data))
+                    .collect();
+                result.found = !result.matches.is_empty();
+                record_matches(&state, Some(auth.key.user_id), &result.matches).await;
+            }
+            Err(e) => result.error = Some(e),
+        }
+
+        results.push(result);
+    }
+
+    let matched = results.iter().filter(|r| r.found).count();
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    tracing::info!(
+        key_id = %auth.key.id,
+        count,
+        matched,
+        failed,
+        "Batch resolution"
+    );
+
+    Ok(Json(BatchResolveResponse {
+        count,
+        matched,
+        failed,
+        results,
+    }))
+}
+
+/// Perceptual hash of a request: computed from `image_data` (base64), or
+/// decoded from `perceptual_hash` (hex).
+fn decode_phash(
+    image_data: Option<&str>,
+    perceptual_hash: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    let phash_bytes = if let Some(image_data) = image_data {
+        // Decode base64 image and compute perceptual hash
+        let image_bytes = BASE64
+            .decode(image_data)
+            .map_err(|e| format!("Invalid base64 image_data: {}", e))?;
+
+        compute_phash(&image_bytes)
+            .ok_or_else(|| "Failed to compute perceptual hash from image data".to_string())?
+    } else if let Some(phash_hex) = perceptual_hash {
+        // Decode hex perceptual hash
+        hex::decode(phash_hex).map_err(|e| format!("Invalid hex perceptual_hash: {}", e))?
+    } else {
+        return Err("Either 'image_data' or 'perceptual_hash' must be provided".to_string());
+    };
+
+    // Validate hash length (accept both legacy 5-byte and standard 8-byte hashes)
+    if phash_bytes.is_empty() || phash_bytes.len() > 8 {
+        return Err(format!(
+            "Perceptual hash must be 1-8 bytes, got {}",
+            phash_bytes.len()
+        ));
+    }
+
+    Ok(phash_bytes)
+}
+
+/// Record resolved seals in the audit log and alert their owners (non-fatal).
+async fn record_matches(state: &AppState, actor_user_id: Option<Uuid>, matches: &[ResolveMatch]) {
+    let Some(seal_repo) = &state.seal_repo else {
+        return;
+    };
+    let matched: Vec<(Uuid, u32)> = matches
+        .iter()
+        .filter_map(|m| Some((m.seal_id.parse().ok()?, m.hamming_distance)))
+        .collect();
+    if matched.is_empty() {
+        return;
+    }
+
+    let ids: Vec<Uuid> = matched.iter().map(|(id, _)| *id).collect();
+    let owners = match seal_repo.find_owners(&ids).await {
+        Ok(owners) => owners,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to look up resolved seals");
+            return;
+        }
+    };
+
+    for (seal_id, owner_id) in owners {
+        if let Some(audit_repo) = &state.audit_repo {
+            if let Err(e) = audit_repo
+                .record(AuditEvent::SealResolved, Some(seal_id), actor_user_id, None)
+                .await
+            {
+                tracing::warn!(error = %e, "Failed to record seal resolution");
+            }
+        }
+
+        let (Some(notifier), Some(owner_id)) = (&state.notifier, owner_id) else {
+            continue;
+        };
+        if actor_user_id == Some(owner_id) {
+            continue;
+        }
+        let hamming_distance = matched
+            .iter()
+            .find(|(id, _)| *id == seal_id)
+            .map_or(0, |(_, distance)| *distance);
+        notifier.notify(Notification::new(
+            NotificationEvent::SealResolved { hamming_distance },
+            seal_id,
+            owner_id,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_phash() {
+        assert_eq!(
+            decode_phash(None, Some("a1b2c3d4e5f67890")).unwrap(),
+            vec![0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0xf6, 0x78, 0x90]
+        );
+        assert!(decode_phash(None, Some("zz")).is_err());
+        assert!(decode_phash(None, Some("a1b2c3d4e5f6789012")).is_err());
+        assert!(decode_phash(Some("not base64!"), None).is_err());
+        assert!(decode_phash(None, None).is_err());
+    }
+}
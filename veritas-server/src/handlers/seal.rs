@@ -6,7 +6,8 @@ use std::io::Cursor;
 
 use axum::{
     extract::{Multipart, State},
-    http::StatusCode,
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
@@ -16,18 +17,22 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 use veritas_core::{
     c2pa::{VeritasManifestBuilder, VeritasSigner},
-    generate_keypair,
-    qrng::{QrngProviderConfig, QrngProviderFactory},
-    MediaType, MockQrng, SealBuilder, VeritasSeal,
+    embed_watermark_bytes, generate_keypair, ContentHash, LivenessChallenge, MediaType,
+    SealBuilder, VeritasSeal,
 };
 
-use crate::auth::OptionalAuth;
-use crate::db::{CreateSeal, SealLocation, SealMetadata, TrustTier};
+use crate::auth::{AuthenticatedUser, Caller};
+use crate::db::{AuditEvent, CreateSeal, SealLocation, SealMetadata, Tenant, TrustTier};
 use crate::error::ApiError;
+use crate::logging;
 use crate::manifest_store::ManifestInput;
-use crate::multipart::MultipartFields;
+use crate::multipart::{FileField, MultipartFields};
+use crate::negotiation::{wants_cbor, Cbor, Negotiated};
+use crate::qrng::{QrngFetch, QrngProvider, QrngSelection};
+use crate::signing_keys::ServerSigner;
 use crate::state::AppState;
-use crate::validation::DEFAULT_MAX_FILE_SIZE;
+use crate::trust::{AttestationEvidence, TrustAssessment, TrustEvidence};
+use crate::validation::UploadPolicy;
 use crate::webauthn::DeviceAttestation;
 
 /// Response for successful seal creation
@@ -57,6 +62,10 @@ pub struct SealResponse {
     /// Size of the C2PA manifest in bytes (when embed_c2pa=true)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub manifest_size: Option<usize>,
+    /// Base64-encoded copy of the image carrying the seal ID as an invisible
+    /// watermark (when watermark=true); JPEG stays JPEG, other formats become PNG
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watermarked_image: Option<String>,
     /// User ID who created the seal (if authenticated)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
@@ -67,6 +76,22 @@ pub struct SealResponse {
     /// QRNG source used for entropy
     #[schema(example = "lfd")]
     pub qrng_source: String,
+    /// Whether the seal was made with mock entropy (testing only, not
+    /// quantum-safe); also flagged by the `X-Veritas-Mock-Entropy` header
+    #[schema(example = false)]
+    pub mock_entropy: bool,
+    /// How the entropy was fetched: provider latency, retries and failover
+    /// (absent for an existing seal)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qrng_fetch: Option<QrngFetch>,
+    /// Whether this is an existing seal for identical content (`dedupe=true`)
+    /// rather than a newly created one
+    #[schema(example = false)]
+    pub existing: bool,
+    /// Digest published when anchoring the seal (hex); with batched
+    /// anchoring, look the anchor up at `/api/v1/anchors/{anchor_digest}`
+    #[schema(example = "9f2c...e41a")]
+    pub anchor_digest: String,
 }
 
 /// Location data included with seal request
@@ -84,14 +109,78 @@ pub struct LocationInput {
     pub altitude: Option<f64>,
 }
 
+/// Answer to the liveness challenge of a capture session
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct LivenessInput {
+    /// Code shown in frame, from `POST /webauthn/capture/session`
+    #[schema(example = "009384")]
+    pub code: String,
+    /// When the capture showed it (Unix timestamp ms, server clock)
+    #[schema(example = 1704067204000_u64)]
+    pub responded_at: u64,
+}
+
 /// Maximum age for device attestation to be considered fresh (5 minutes)
 const MAX_ATTESTATION_AGE_SECS: u64 = 300;
 
+/// Set to `true` on responses carrying a seal made with mock entropy
+pub const MOCK_ENTROPY_HEADER: HeaderName = HeaderName::from_static("x-veritas-mock-entropy");
+
+/// Seal ID of a CBOR seal response
+pub const SEAL_ID_HEADER: HeaderName = HeaderName::from_static("x-veritas-seal-id");
+
+/// Trust tier of a CBOR seal response
+pub const TRUST_TIER_HEADER: HeaderName = HeaderName::from_static("x-veritas-trust-tier");
+
+/// QRNG source of a CBOR seal response
+pub const QRNG_SOURCE_HEADER: HeaderName = HeaderName::from_static("x-veritas-qrng-source");
+
+/// Anchor digest of a CBOR seal response
+pub const ANCHOR_DIGEST_HEADER: HeaderName = HeaderName::from_static("x-veritas-anchor-digest");
+
+/// Perceptual hash of a CBOR seal response (images only)
+pub const PERCEPTUAL_HASH_HEADER: HeaderName = HeaderName::from_static("x-veritas-perceptual-hash");
+
+/// Headers flagging a seal made with mock entropy (empty otherwise)
+pub(crate) fn mock_entropy_headers(mock_entropy: bool) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if mock_entropy {
+        headers.insert(MOCK_ENTROPY_HEADER, HeaderValue::from_static("true"));
+    }
+    headers
+}
+
+/// Audit a seal made with mock entropy (non-fatal)
+///
+/// `seal_id` is the stored seal record, if the seal was stored.
+pub(crate) async fn record_mock_entropy(
+    state: &AppState,
+    endpoint: &str,
+    seal_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+) {
+    tracing::warn!(
+        endpoint,
+        seal_id = ?seal_id,
+        user_id = ?user_id,
+        "Seal made with mock entropy - not quantum-safe"
+    );
+    if let Some(audit_repo) = &state.audit_repo {
+        if let Err(e) = audit_repo
+            .record(AuditEvent::MockEntropyUsed, seal_id, user_id, None)
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to record mock entropy use");
+        }
+    }
+}
+
 /// Parameters for persisting a seal to the database
 struct PersistSealParams<'a> {
     seal_id: Uuid,
-    user_id: Option<Uuid>,
-    user_trust_tier: TrustTier,
+    /// Owner of the seal (anonymous seals are not stored)
+    tenant: Option<Tenant>,
+    trust: &'a TrustAssessment,
     seal: &'a VeritasSeal,
     seal_cbor: &'a [u8],
     media_type: MediaType,
@@ -103,66 +192,91 @@ struct PersistSealParams<'a> {
     qrng_source_name: &'a str,
 }
 
-/// Create a seal with the appropriate QRNG provider
+/// Create a seal with the requested QRNG provider
 ///
 /// Handles QRNG selection, keypair generation, seal building, and CBOR serialization.
 ///
 /// # Arguments
-/// * `content` - The media content to seal
-/// * `media_type` - The type of media (Image, Video, Audio)
-/// * `use_mock` - Whether to use mock QRNG instead of real quantum source
+/// * `builder` - Seal builder over the content (or its precomputed hash)
+/// * `provider` - QRNG provider requested by the client
+/// * `selection` - Server configuration: allowed providers, attempts per provider and entropy policy
 /// * `allow_mock_qrng` - Server configuration: whether mock QRNG is allowed
+/// * `signer` - Registered signing key (None = one-off key pair for this seal)
 ///
 /// # Returns
-/// Tuple of (seal, CBOR-encoded seal bytes)
+/// Tuple of (seal, CBOR-encoded seal bytes, entropy fetch metadata)
 async fn create_seal_with_provider(
-    content: Vec<u8>,
-    media_type: MediaType,
-    use_mock: bool,
+    builder: SealBuilder,
+    provider: QrngProvider,
+    selection: &QrngSelection,
     allow_mock_qrng: bool,
-) -> Result<(VeritasSeal, Vec<u8>), ApiError> {
-    // Generate keypair for this seal (in production, use persistent keys from TEE)
-    // Uses ZeroizingSecretKey for secure memory handling
-    let (public_key, secret_key) = generate_keypair();
-
+    signer: Option<&ServerSigner>,
+) -> Result<(VeritasSeal, Vec<u8>, QrngFetch), ApiError> {
     // Check mock QRNG permission
-    if use_mock && !allow_mock_qrng {
+    if provider == QrngProvider::Mock && !allow_mock_qrng {
         return Err(ApiError::bad_request(
             "Mock QRNG is not allowed in this environment. Set ALLOW_MOCK_QRNG=true to enable.",
         ));
     }
+    selection.check(provider)?;
 
-    // Create seal with appropriate QRNG source
-    let seal = if use_mock {
-        let qrng = MockQrng::default();
-        SealBuilder::new(content, media_type)
-            .build_secure(&qrng, &secret_key, &public_key)
-            .await?
-    } else {
-        let provider = QrngProviderFactory::create(QrngProviderConfig::Auto).map_err(|e| {
-            tracing::error!("QRNG provider creation failed: {}", e);
-            ApiError::service_unavailable("QRNG service unavailable")
-        })?;
-        SealBuilder::new(content, media_type)
-            .build_secure(&*provider, &secret_key, &public_key)
-            .await
-            .map_err(|e| {
-                tracing::error!("QRNG entropy fetch failed: {}", e);
-                ApiError::service_unavailable("QRNG service unavailable")
-            })?
+    // Sign with the registered key, writing its ID into the seal, or with a
+    // keypair generated for this seal only
+    // Uses ZeroizingSecretKey for secure memory handling
+    let one_off;
+    let (public_key, secret_key, builder) = match signer {
+        Some(signer) => (
+            &signer.keypair().public_key,
+            &signer.keypair().secret_key,
+            builder.with_key_id(signer.key_id()),
+        ),
+        None => {
+            one_off = generate_keypair();
+            (&one_off.0, &one_off.1, builder)
+        }
     };
 
+    // Fetch entropy first (with retries and failover), then seal with it,
+    // recording the entropy policy the builder checks it against
+    let (entropy, fetch) = selection.fetch(provider).await?;
+    let seal = builder
+        .with_qrng_policy(selection.policy())
+        .build_secure(&entropy, secret_key, public_key)
+        .await?;
+
     // Serialize seal to CBOR
     let seal_cbor = seal.to_cbor()?;
 
-    Ok((seal, seal_cbor))
+    Ok((seal, seal_cbor, fetch))
+}
+
+/// QRNG provider requested by the `mock` and `qrng_provider` fields
+///
+/// `qrng_provider` defaults to "auto", or "mock" with `mock=true`.
+pub(crate) fn requested_qrng_provider(
+    use_mock: bool,
+    qrng_provider: Option<&str>,
+) -> Result<QrngProvider, ApiError> {
+    let provider = match qrng_provider {
+        Some(name) => QrngProvider::parse(name)
+            .ok_or_else(|| ApiError::bad_request(format!("Unknown QRNG provider: {}", name)))?,
+        None if use_mock => QrngProvider::Mock,
+        None => QrngProvider::Auto,
+    };
+    if use_mock && provider != QrngProvider::Mock {
+        return Err(ApiError::bad_request(
+            "mock=true conflicts with qrng_provider; use one or the other",
+        ));
+    }
+    Ok(provider)
 }
 
 /// Persist seal and manifest to database
 ///
 /// Stores seal metadata and perceptual hash in the seal repository and manifest store.
-/// Errors are logged but not propagated (non-fatal).
-async fn persist_seal(state: &AppState, params: PersistSealParams<'_>) {
+/// Errors are logged but not propagated (non-fatal). Returns the ID of the
+/// stored seal record, if one was stored.
+async fn persist_seal(state: &AppState, params: PersistSealParams<'_>) -> Option<Uuid> {
     let perceptual_hash = params.seal.content_hash.perceptual_hash.clone();
 
     // Store manifest in database if manifest store is configured
@@ -172,7 +286,7 @@ async fn persist_seal(state: &AppState, params: PersistSealParams<'_>) {
             perceptual_hash: perceptual_hash.clone(),
             image_hash: hex::encode(params.seal.content_hash.crypto_hash),
             seal_cbor: params.seal_cbor.to_vec(),
-            media_type: format!("{:?}", params.media_type).to_lowercase(),
+            media_type: params.media_type.to_string(),
         };
 
         if let Err(e) = store.store(&input).await {
@@ -188,7 +302,7 @@ async fn persist_seal(state: &AppState, params: PersistSealParams<'_>) {
     }
 
     // Store seal in database if authenticated and seal_repo is available
-    if let (Some(uid), Some(ref seal_repo)) = (params.user_id, &state.seal_repo) {
+    if let (Some(tenant), Some(ref seal_repo)) = (params.tenant, &state.seal_repo) {
         // Build metadata JSON
         let metadata = SealMetadata {
             timestamp: Utc::now().to_rfc3339(),
@@ -200,22 +314,23 @@ async fn persist_seal(state: &AppState, params: PersistSealParams<'_>) {
             device: None, // Could be populated from User-Agent header
             capture_source: "camera".to_string(),
             has_device_attestation: params.has_device_attestation,
+            trust_rationale: params.trust.rationale.clone(),
         };
 
         let create_seal = CreateSeal {
-            user_id: Some(uid),
-            organization_id: None,
+            user_id: Some(tenant.user_id),
+            organization_id: tenant.organization_id,
             content_hash: hex::encode(params.seal.content_hash.crypto_hash),
             perceptual_hash: perceptual_hash.clone(),
             qrng_entropy: params.seal.qrng_entropy.to_vec(),
             qrng_source: params.qrng_source_name.to_string(),
             signature: params.seal.signature.clone(),
             public_key: params.seal.public_key.clone(),
-            media_type: format!("{:?}", params.media_type).to_lowercase(),
+            media_type: params.media_type.to_string(),
             file_size: params.file_size.map(|s| s as i32),
             mime_type: params.content_type_hint.clone(),
             metadata: serde_json::to_value(&metadata).unwrap_or_default(),
-            trust_tier: params.user_trust_tier,
+            trust_tier: params.trust.tier,
             c2pa_manifest_embedded: params.embed_c2pa,
             captured_at: Utc::now(),
         };
@@ -224,21 +339,115 @@ async fn persist_seal(state: &AppState, params: PersistSealParams<'_>) {
             Ok(stored_seal) => {
                 tracing::info!(
                     seal_id = %stored_seal.id,
-                    user_id = %uid,
+                    user_id = %tenant.user_id,
                     "Seal stored in database with user association"
                 );
+                return Some(stored_seal.id);
             }
             Err(e) => {
                 // Log error but don't fail - the cryptographic seal succeeded
                 tracing::error!(
                     seal_id = %params.seal_id,
-                    user_id = %uid,
+                    user_id = %tenant.user_id,
                     error = %e,
                     "Failed to store seal in database"
                 );
             }
         }
     }
+    None
+}
+
+/// Look up an existing seal for byte-identical content
+///
+/// Matches the SHA3-256 content hash against the manifest store. Returns
+/// `None` when no manifest store is configured or the lookup fails, so
+/// deduplication never blocks sealing.
+async fn find_existing_seal(
+    state: &AppState,
+    file: &FileField,
+) -> Result<Option<SealResponse>, ApiError> {
+    let Some(ref store) = state.manifest_store else {
+        tracing::debug!("Manifest store not configured, skipping seal deduplication");
+        return Ok(None);
+    };
+
+    let content_hash = match &file.spooled {
+        Some(spooled) => spooled.content_hash().await?,
+        None => ContentHash::from_bytes(&file.data),
+    };
+    let image_hash = content_hash.crypto_hash_hex();
+
+    let record = match store.get_by_image_hash(&image_hash).await {
+        Ok(Some(record)) => record,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            tracing::warn!(error = %e, "Seal deduplication lookup failed");
+            return Ok(None);
+        }
+    };
+
+    let seal = VeritasSeal::from_cbor(&record.seal_cbor).map_err(|e| {
+        tracing::error!(seal_id = %record.seal_id, error = %e, "Stored seal is corrupt");
+        ApiError::internal("Failed to decode existing seal")
+    })?;
+
+    // The trust tier lives on the seal record, when it was stored for a user
+    let trust_tier = match &state.seal_repo {
+        Some(seal_repo) => match seal_repo.find_by_content_hash(&image_hash).await {
+            Ok(stored) => stored.map(|s| s.trust_tier).unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load trust tier of existing seal");
+                TrustTier::default()
+            }
+        },
+        None => TrustTier::default(),
+    };
+
+    tracing::info!(
+        seal_id = %record.seal_id,
+        content_hash = %image_hash,
+        "Returning existing seal for identical content"
+    );
+
+    Ok(Some(SealResponse {
+        seal_id: record.seal_id,
+        seal_data: BASE64.encode(&record.seal_cbor),
+        timestamp: seal.capture_timestamp_utc,
+        has_device_attestation: seal.device_attestation.is_some(),
+        perceptual_hash: record.perceptual_hash.as_ref().map(hex::encode),
+        sealed_image: None,
+        manifest_size: None,
+        watermarked_image: None,
+        // Never reveal who sealed the content first
+        user_id: None,
+        trust_tier: trust_tier_name(trust_tier).to_string(),
+        qrng_source: qrng_source_name(&seal).to_string(),
+        mock_entropy: seal.uses_mock_entropy(),
+        qrng_fetch: None,
+        existing: true,
+        anchor_digest: hex::encode(seal.anchor_digest()),
+    }))
+}
+
+/// API name of a trust tier
+pub(crate) fn trust_tier_name(tier: TrustTier) -> &'static str {
+    match tier {
+        TrustTier::Tier1 => "tier1",
+        TrustTier::Tier2 => "tier2",
+        TrustTier::Tier3 => "tier3",
+    }
+}
+
+/// API name of the QRNG source a seal was created with
+pub(crate) fn qrng_source_name(seal: &VeritasSeal) -> &'static str {
+    match seal.qrng_source {
+        veritas_core::QrngSource::LfdCloud => "lfd",
+        veritas_core::QrngSource::AnuCloud => "anu",
+        veritas_core::QrngSource::IdQuantiqueCloud => "idq",
+        veritas_core::QrngSource::Mock => "mock",
+        veritas_core::QrngSource::DeviceHardware { .. } => "hardware",
+    }
 }
 
 /// Embed C2PA manifest in content if applicable
@@ -253,11 +462,9 @@ fn embed_c2pa_if_applicable(
     seal_id: Uuid,
 ) -> (Option<String>, Option<usize>) {
     // Determine MIME type for C2PA embedding
-    let mime_type = content_type.as_deref().unwrap_or(match media_type {
-        MediaType::Image => "image/jpeg",
-        MediaType::Video => "video/mp4",
-        MediaType::Audio => "audio/mpeg",
-    });
+    let mime_type = content_type
+        .as_deref()
+        .unwrap_or_else(|| media_type.default_mime());
 
     match VeritasSigner::from_env() {
         Ok(signer) => {
@@ -296,19 +503,113 @@ fn embed_c2pa_if_applicable(
     }
 }
 
+/// Watermark an image with its seal ID
+///
+/// Returns the base64-encoded watermarked image, or None if the image cannot
+/// carry a watermark (too small, undecodable).
+async fn watermark_if_applicable(content: Vec<u8>, seal_id: Uuid) -> Option<String> {
+    // DCT embedding of every 8x8 block is CPU-bound
+    let result =
+        tokio::task::spawn_blocking(move || embed_watermark_bytes(&content, seal_id.as_bytes()))
+            .await;
+    match result {
+        Ok(Ok(marked)) => {
+            tracing::debug!(seal_id = %seal_id, format = ?marked.format, "Watermark embedded");
+            Some(BASE64.encode(&marked.data))
+        }
+        Ok(Err(e)) => {
+            tracing::warn!(seal_id = %seal_id, error = %e, "Failed to embed watermark");
+            None
+        }
+        Err(e) => {
+            tracing::error!(seal_id = %seal_id, error = %e, "Watermark task panicked");
+            None
+        }
+    }
+}
+
+/// Pick the media type to seal from the client's declaration and the
+/// format detected from the file's magic bytes.
+///
+/// Without a declaration the detected format wins, falling back to image.
+/// A declaration or Content-Type contradicting the detected format is
+/// rejected in strict mode, and only logged otherwise.
+fn resolve_media_type(
+    declared: Option<MediaType>,
+    file: &FileField,
+    strict: bool,
+) -> Result<MediaType, ApiError> {
+    let detected = file.detected_mime.map(MediaType::from_mime);
+    let header = file
+        .content_type
+        .as_deref()
+        .map(MediaType::from_mime)
+        .filter(|media_type| *media_type != MediaType::Generic);
+
+    for claim in [declared.as_ref(), header.as_ref()].into_iter().flatten() {
+        let consistent = match (claim, &detected) {
+            // Generic and custom types make no claim about the format
+            (MediaType::Generic | MediaType::Custom(_), _) => true,
+            // MP4 and WebM containers also carry audio-only recordings
+            (MediaType::Audio, Some(MediaType::Video)) => true,
+            (claim, detected) => Some(claim) == detected.as_ref(),
+        };
+        if consistent {
+            continue;
+        }
+
+        let detected_name = file.detected_mime.unwrap_or("an unrecognized format");
+        if strict {
+            return Err(ApiError::bad_request(format!(
+                "Declared media type '{}' does not match the uploaded file ({})",
+                claim, detected_name
+            )));
+        }
+        tracing::warn!(
+            declared = %claim,
+            detected = detected_name,
+            "Declared media type does not match the uploaded file"
+        );
+    }
+
+    Ok(declared.or(detected).unwrap_or(MediaType::Image))
+}
+
 /// Create a quantum-authenticated seal for uploaded content
 ///
 /// Accepts multipart/form-data with:
-/// - **file** (required): The media file to seal (max 25MB)
-/// - **media_type** (optional): "image", "video", "audio", or "generic" (default: "image")
+/// - **file** (required): The media file to seal (max 25MB by default, see the upload policy)
+/// - **media_type** (optional): "image", "video", "audio", "document", "generic", or a MIME type
+///   (default: detected from the file's magic bytes, else "image"). With `STRICT_MEDIA_TYPES=true`,
+///   a media_type or Content-Type contradicting the detected format is rejected.
 /// - **mock** (optional): "true" to use mock QRNG instead of ANU (for testing only)
+/// - **qrng_provider** (optional): entropy source to use: "auto" (default), "idq", "lfd",
+///   "anu", "hardware" or "mock". Must be on the server allowlist (`QRNG_ALLOWED_PROVIDERS`);
+///   an explicit provider never fails over to another one
 /// - **device_attestation** (optional): JSON-encoded WebAuthn device attestation
+/// - **capture_nonce** (optional): hex nonce from `POST /webauthn/capture/session`; requires a
+///   device_attestation for the same credential, is single use and is signed into the seal
+/// - **liveness** (optional): JSON-encoded answer to the capture session's liveness challenge
+///   {code, responded_at}; requires a capture_nonce and is signed into the seal
 /// - **embed_c2pa** (optional): "true" (default) to embed C2PA manifest in response, "false" to skip
+/// - **watermark** (optional): "true" to also return the image with the seal ID embedded as an
+///   invisible watermark (`watermarked_image`, images only); off by default
 /// - **location** (optional): JSON-encoded GPS location {lat, lng, altitude?}
+/// - **dedupe** (optional): "true" to return the existing seal (200, `existing: true`) when
+///   byte-identical content was already sealed, instead of creating a new one
+/// - **not_before** / **not_after** (optional): RFC 3339 validity window; the seal does not
+///   verify outside it (e.g. time-boxed credentials)
+///
+/// With `Accept: application/cbor`, the response body is the raw CBOR seal (what `seal_data`
+/// holds in base64) and its metadata comes in `X-Veritas-Seal-Id`, `X-Veritas-Trust-Tier`,
+/// `X-Veritas-Qrng-Source`, `X-Veritas-Anchor-Digest` and `X-Veritas-Perceptual-Hash`
+/// headers; `embed_c2pa` and `watermark` are then ignored, as there is no room for images.
 ///
 /// Authentication (optional):
 /// - Pass `Authorization: Bearer <token>` header to link seal to authenticated user
 /// - If authenticated, seal is stored in database with user association
+/// - Backend services (client-credentials tokens of `SERVICE_CLIENTS`, JWT or
+///   opaque) seal for themselves: the seal is not linked to any user
 ///
 /// The seal contains:
 /// - QRNG entropy (256 bits from quantum random number generator)
@@ -318,58 +619,229 @@ fn embed_c2pa_if_applicable(
 /// - Device attestation (if provided and fresh)
 #[utoipa::path(
     post,
-    path = "/seal",
+    path = "/api/v1/seal",
     tag = "Sealing",
     request_body(
         content_type = "multipart/form-data",
         description = "Media file to seal with optional parameters"
     ),
     responses(
-        (status = 201, description = "Seal created successfully", body = SealResponse),
-        (status = 400, description = "Invalid request (missing file, unsupported format, stale attestation)"),
-        (status = 413, description = "File too large (max 25MB)"),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Existing seal returned for identical content (dedupe=true)", body = SealResponse),
+        (status = 201, description = "Seal created successfully (raw CBOR seal with Accept: application/cbor)", body = SealResponse),
+        (status = 400, description = "Invalid request (missing file, unsupported format, stale attestation, invalid capture nonce or liveness answer, QRNG provider not allowed, invalid validity window)"),
+        (status = 413, description = "File exceeds the upload size limit for its media type"),
+        (status = 415, description = "Content-Type not allowed by the upload policy"),
+        (status = 422, description = "Image dimensions exceed the upload policy limits"),
+        (status = 429, description = "Monthly seal quota exceeded"),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "Requested QRNG provider not configured or unavailable")
     )
 )]
 pub async fn seal_handler(
     State(state): State<AppState>,
-    OptionalAuth(auth): OptionalAuth,
+    caller: Caller,
+    headers: HeaderMap,
     mut multipart: Multipart,
-) -> Result<(StatusCode, Json<SealResponse>), ApiError> {
-    // Parse multipart form
-    let fields = MultipartFields::parse(&mut multipart, true, DEFAULT_MAX_FILE_SIZE).await?;
+) -> Result<Negotiated<Response>, ApiError> {
+    let auth = match caller {
+        Caller::User(user) => Some(user),
+        Caller::Service(service) => {
+            tracing::info!(service_id = %service.service_id, "Service seal request");
+            None
+        }
+        Caller::Anonymous => None,
+    };
 
-    // Extract required and optional fields
+    // Parse multipart form
+    let fields = MultipartFields::parse_with_policy(&mut multipart, &state.upload_policy).await?;
     let file = fields.require_file()?;
-    let content = file.data.clone();
-    let content_type_hint = file.content_type.clone();
-    let file_size = file.data.len();
-
-    let media_type = fields
-        .get_text("media_type")
-        .map(|s| match s.to_lowercase().as_str() {
-            "video" => MediaType::Video,
-            "audio" => MediaType::Audio,
-            _ => MediaType::Image,
-        })
-        .unwrap_or(MediaType::Image);
-
-    let use_mock = fields.get_bool("mock");
-    let embed_c2pa = fields.get_text("embed_c2pa") != Some("false");
-    let device_attestation: Option<DeviceAttestation> = fields.get_json("device_attestation")?;
-    let location: Option<LocationInput> = fields.get_json("location")?;
+    let cbor = wants_cbor(&headers);
+
+    let options = SealOptions {
+        declared_media_type: fields.get_text("media_type").and_then(MediaType::parse),
+        qrng_provider: requested_qrng_provider(
+            fields.get_bool("mock"),
+            fields.get_text("qrng_provider"),
+        )?,
+        embed_c2pa: !cbor && fields.get_text("embed_c2pa") != Some("false"),
+        watermark: !cbor && fields.get_bool("watermark"),
+        device_attestation: fields.get_json("device_attestation")?,
+        capture_nonce: parse_capture_nonce(fields.get_text("capture_nonce"))?,
+        liveness: fields.get_json("liveness")?,
+        location: fields.get_json("location")?,
+        dedupe: fields.get_bool("dedupe"),
+        not_before: parse_time_field(fields.get_text("not_before"), "not_before")?,
+        not_after: parse_time_field(fields.get_text("not_after"), "not_after")?,
+    };
+
+    let response = seal_file(&state, &state.upload_policy, auth, file, options).await?;
+    if cbor {
+        return Ok(Negotiated(response.into_cbor()?.into_response()));
+    }
+    Ok(Negotiated(
+        (response.status(), response.headers(), Json(response)).into_response(),
+    ))
+}
+
+/// Parse an optional hex-encoded capture nonce
+pub(crate) fn parse_capture_nonce(value: Option<&str>) -> Result<Option<[u8; 32]>, ApiError> {
+    let Some(value) = value.filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .map(Some)
+        .ok_or_else(|| ApiError::bad_request("Invalid capture_nonce: expected 64 hex characters"))
+}
+
+/// Parse an optional RFC 3339 form field as Unix milliseconds
+fn parse_time_field(value: Option<&str>, name: &str) -> Result<Option<u64>, ApiError> {
+    let Some(value) = value.filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let time = chrono::DateTime::parse_from_rfc3339(value).map_err(|_| {
+        ApiError::bad_request(format!("Invalid {}: expected an RFC 3339 time", name))
+    })?;
+    unix_millis(time).map(Some)
+}
+
+/// A validity window bound as Unix milliseconds
+pub(crate) fn unix_millis<Tz: chrono::TimeZone>(
+    time: chrono::DateTime<Tz>,
+) -> Result<u64, ApiError> {
+    u64::try_from(time.timestamp_millis()).map_err(|_| {
+        ApiError::bad_request("Invalid validity window: times before 1970 are not supported")
+    })
+}
+
+/// Check a requested validity window before any entropy is fetched
+fn check_validity_window(not_before: Option<u64>, not_after: Option<u64>) -> Result<(), ApiError> {
+    if let (Some(not_before), Some(not_after)) = (not_before, not_after) {
+        if not_after < not_before {
+            return Err(ApiError::bad_request(
+                "Invalid validity window: not_after is before not_before",
+            ));
+        }
+    }
+    if not_after.is_some_and(|not_after| not_after < Utc::now().timestamp_millis() as u64) {
+        return Err(ApiError::bad_request(
+            "Invalid validity window: not_after is in the past",
+        ));
+    }
+    Ok(())
+}
+
+impl SealResponse {
+    /// 201 for a new seal, 200 when an existing seal was returned
+    pub(crate) fn status(&self) -> StatusCode {
+        if self.existing {
+            StatusCode::OK
+        } else {
+            StatusCode::CREATED
+        }
+    }
+
+    /// Flags a seal made with mock entropy (see [`MOCK_ENTROPY_HEADER`])
+    pub(crate) fn headers(&self) -> HeaderMap {
+        mock_entropy_headers(self.mock_entropy)
+    }
+
+    /// The raw CBOR seal, with the metadata clients need as headers
+    pub(crate) fn into_cbor(self) -> Result<(StatusCode, HeaderMap, Cbor), ApiError> {
+        let seal_cbor = BASE64.decode(&self.seal_data).map_err(|e| {
+            tracing::error!(seal_id = %self.seal_id, error = %e, "Invalid seal_data encoding");
+            ApiError::internal("Failed to encode seal")
+        })?;
+        let mut headers = self.headers();
+        for (name, value) in [
+            (SEAL_ID_HEADER, Some(&self.seal_id)),
+            (TRUST_TIER_HEADER, Some(&self.trust_tier)),
+            (QRNG_SOURCE_HEADER, Some(&self.qrng_source)),
+            (ANCHOR_DIGEST_HEADER, Some(&self.anchor_digest)),
+            (PERCEPTUAL_HASH_HEADER, self.perceptual_hash.as_ref()),
+        ] {
+            if let Some(value) = value.and_then(|value| HeaderValue::from_str(value).ok()) {
+                headers.insert(name, value);
+            }
+        }
+        Ok((self.status(), headers, Cbor(seal_cbor)))
+    }
+}
+
+/// Sealing options shared by the seal form and resumable uploads
+pub(crate) struct SealOptions {
+    /// Media type declared by the client, checked against the detected format
+    pub declared_media_type: Option<MediaType>,
+    /// QRNG provider requested by the client
+    pub qrng_provider: QrngProvider,
+    /// Embed a C2PA manifest in the response when possible
+    pub embed_c2pa: bool,
+    /// Return a copy of the image watermarked with the seal ID
+    pub watermark: bool,
+    /// WebAuthn device attestation to include in the seal
+    pub device_attestation: Option<DeviceAttestation>,
+    /// Trusted capture session nonce, bound to the attestation's credential
+    pub capture_nonce: Option<[u8; 32]>,
+    /// Answer to the capture session's liveness challenge
+    pub liveness: Option<LivenessInput>,
+    /// GPS location recorded with the seal
+    pub location: Option<LocationInput>,
+    /// Return the existing seal for byte-identical content instead of resealing
+    pub dedupe: bool,
+    /// Validity window (Unix ms); the seal does not verify outside it
+    pub not_before: Option<u64>,
+    pub not_after: Option<u64>,
+}
+
+/// Seal an uploaded file and persist the result
+///
+/// Applies `policy`, resolves the media type, seals (from a streamed hash
+/// for spooled uploads where possible) and stores the seal record.
+pub(crate) async fn seal_file(
+    state: &AppState,
+    policy: &UploadPolicy,
+    auth: Option<AuthenticatedUser>,
+    file: &FileField,
+    options: SealOptions,
+) -> Result<SealResponse, ApiError> {
+    let SealOptions {
+        declared_media_type,
+        qrng_provider,
+        embed_c2pa,
+        watermark,
+        device_attestation,
+        capture_nonce,
+        liveness,
+        location,
+        dedupe,
+        not_before,
+        not_after,
+    } = options;
+    let file_size = file.len();
+    check_validity_window(not_before, not_after)?;
+
+    // Prefer the sniffed MIME type over a missing or generic Content-Type
+    let content_type_hint = match file.content_type.as_deref() {
+        None | Some("application/octet-stream") => file.detected_mime.map(str::to_string),
+        Some(content_type) => Some(content_type.to_string()),
+    };
+
+    let media_type = resolve_media_type(declared_media_type, file, state.strict_media_types)?;
+    policy.check_upload(file, &media_type)?;
 
     // Extract user info from JWT auth (optional — anonymous seals are allowed)
-    let (user_id, user_trust_tier) = match &auth {
+    let tenant = auth.as_ref().map(AuthenticatedUser::tenant);
+    let (user_id, account_tier) = match &auth {
         Some(auth_user) => {
             tracing::info!(
                 clerk_user_id = %auth_user.clerk_user_id,
                 user_id = %auth_user.user.id,
                 "Authenticated seal request"
             );
-            (Some(auth_user.user.id), auth_user.user.tier)
+            (Some(auth_user.user.id), Some(auth_user.user.tier))
         }
-        None => (None, TrustTier::default()),
+        None => (None, None),
     };
 
     // Validate device attestation freshness
@@ -388,40 +860,150 @@ pub async fn seal_handler(
         );
     }
 
+    // A capture nonce is redeemed once, by the credential it was issued to
+    let liveness = match capture_nonce {
+        Some(nonce) => {
+            let Some(ref attestation) = device_attestation else {
+                return Err(ApiError::bad_request(
+                    "capture_nonce requires a device_attestation",
+                ));
+            };
+            let issued = state
+                .webauthn
+                .storage
+                .take_capture_nonce(&hex::encode(nonce))
+                .filter(|issued| issued.credential_id == attestation.credential_id)
+                .ok_or_else(|| {
+                    ApiError::bad_request("Capture nonce is unknown, expired or already used")
+                })?;
+            match liveness {
+                Some(answer) => {
+                    let challenge = LivenessChallenge {
+                        code: answer.code,
+                        issued_at: issued.issued_at,
+                        responded_at: answer.responded_at,
+                    };
+                    challenge
+                        .check(Some(&nonce), Utc::now().timestamp_millis() as u64)
+                        .map_err(|e| {
+                            ApiError::bad_request(format!("Invalid liveness answer: {}", e))
+                        })?;
+                    Some(challenge)
+                }
+                None => None,
+            }
+        }
+        None if liveness.is_some() => {
+            return Err(ApiError::bad_request("liveness requires a capture_nonce"));
+        }
+        None => None,
+    };
+
     if let Some(ref loc) = location {
         tracing::debug!(location = ?loc, "Location data included");
     }
 
-    // Create seal with QRNG provider
-    let (seal, seal_cbor) =
-        create_seal_with_provider(content.clone(), media_type, use_mock, state.allow_mock_qrng)
-            .await?;
+    // Compute the trust tier from the evidence behind this seal
+    let attestation = match &device_attestation {
+        Some(attestation) => {
+            Some(AttestationEvidence::check(&state.webauthn.storage, attestation).await)
+        }
+        None => None,
+    };
+    let trust = state.trust_policy.assess(&TrustEvidence {
+        account_tier,
+        attestation,
+    });
+    tracing::debug!(tier = ?trust.tier, rationale = ?trust.rationale, "Trust tier computed");
+
+    if dedupe {
+        if let Some(existing) = find_existing_seal(state, file).await? {
+            return Ok(existing);
+        }
+    }
+
+    if let Some(tenant) = &tenant {
+        check_seal_quota(state, tenant).await?;
+    }
+
+    // Large uploads spooled to disk are hashed from the temp file, except
+    // images and documents whose perceptual/canonical hashes need the bytes
+    let (mut builder, content) = match &file.spooled {
+        Some(spooled) if !matches!(media_type, MediaType::Image | MediaType::Document) => {
+            let content_hash = spooled.content_hash().await?;
+            (
+                SealBuilder::from_content_hash(content_hash, media_type.clone()),
+                None,
+            )
+        }
+        _ => {
+            let content = file.contents().await?;
+            (
+                SealBuilder::new(content.clone(), media_type.clone()),
+                Some(content),
+            )
+        }
+    };
+
+    if not_before.is_some() || not_after.is_some() {
+        builder = builder.with_validity(not_before, not_after);
+    }
+    if let Some(nonce) = capture_nonce {
+        builder = builder.with_capture_nonce(nonce);
+    }
+    if let Some(liveness) = liveness {
+        builder = builder.with_liveness(liveness);
+    }
+
+    // Create seal with QRNG provider and the server's signing key, logging
+    // failures for the admin stats
+    let sealed = match state.signing_keys.signer().await {
+        Ok(signer) => {
+            create_seal_with_provider(
+                builder,
+                qrng_provider,
+                &state.qrng_selection,
+                state.allow_mock_qrng,
+                signer,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    let (seal, seal_cbor, qrng_fetch) = match sealed {
+        Ok(sealed) => sealed,
+        Err(e) => {
+            record_seal_failure(state, user_id, &e).await;
+            return Err(e);
+        }
+    };
 
     // Generate seal ID and encode
     let seal_id = Uuid::new_v4();
+    logging::record_seal(seal_id);
     let seal_data = BASE64.encode(&seal_cbor);
     let has_device_attestation = device_attestation.is_some();
     let perceptual_hash_hex = seal.content_hash.perceptual_hash.as_ref().map(hex::encode);
 
     // Determine QRNG source name
-    let qrng_source_name = match seal.qrng_source {
-        veritas_core::QrngSource::LfdCloud => "lfd",
-        veritas_core::QrngSource::AnuCloud => "anu",
-        veritas_core::QrngSource::IdQuantiqueCloud => "idq",
-        veritas_core::QrngSource::Mock => "mock",
-        veritas_core::QrngSource::DeviceHardware { .. } => "hardware",
-    };
+    let qrng_source_name = qrng_source_name(&seal);
+
+    // Queue the seal for the next anchored batch
+    let anchor_digest = seal.anchor_digest();
+    if let Some(batcher) = &state.anchor_batcher {
+        batcher.submit(anchor_digest);
+    }
 
     // Persist seal and manifest to database (non-fatal)
-    persist_seal(
-        &state,
+    let stored_seal_id = persist_seal(
+        state,
         PersistSealParams {
             seal_id,
-            user_id,
-            user_trust_tier,
+            tenant,
+            trust: &trust,
             seal: &seal,
             seal_cbor: &seal_cbor,
-            media_type,
+            media_type: media_type.clone(),
             content_type_hint: content_type_hint.clone(),
             file_size: Some(file_size),
             location,
@@ -432,61 +1014,123 @@ pub async fn seal_handler(
     )
     .await;
 
+    let mock_entropy = seal.uses_mock_entropy();
+    if mock_entropy {
+        record_mock_entropy(state, "seal", stored_seal_id, user_id).await;
+    }
+
+    // Watermark images with their seal ID if requested
+    let watermarked_image = match &content {
+        Some(content) if watermark && media_type == MediaType::Image => {
+            watermark_if_applicable(content.clone(), seal_id).await
+        }
+        _ => None,
+    };
+
     // Embed C2PA manifest if requested
-    let (sealed_image, manifest_size) = if embed_c2pa {
-        embed_c2pa_if_applicable(
+    let (sealed_image, manifest_size) = match content {
+        Some(content) if embed_c2pa => embed_c2pa_if_applicable(
             content,
             seal.clone(),
             media_type,
             content_type_hint,
             seal_id,
-        )
-    } else {
-        (None, None)
+        ),
+        None if embed_c2pa => {
+            // Returning the embedded file would buffer the whole upload again
+            tracing::debug!(
+                seal_id = %seal_id,
+                file_size,
+                "Skipping C2PA embedding for spooled upload"
+            );
+            (None, None)
+        }
+        _ => (None, None),
     };
 
-    // Format trust tier for response
-    let trust_tier_str = match user_trust_tier {
-        TrustTier::Tier1 => "tier1",
-        TrustTier::Tier2 => "tier2",
-        TrustTier::Tier3 => "tier3",
+    Ok(SealResponse {
+        seal_id: seal_id.to_string(),
+        seal_data,
+        timestamp: seal.capture_timestamp_utc,
+        has_device_attestation,
+        perceptual_hash: perceptual_hash_hex,
+        sealed_image,
+        manifest_size,
+        watermarked_image,
+        user_id: user_id.map(|u| u.to_string()),
+        trust_tier: trust_tier_name(trust.tier).to_string(),
+        qrng_source: qrng_source_name.to_string(),
+        mock_entropy,
+        qrng_fetch: Some(qrng_fetch),
+        existing: false,
+        anchor_digest: hex::encode(anchor_digest),
+    })
+}
+
+/// Refuse the seal once the account used up its monthly quota.
+///
+/// Fails open if the count cannot be read: persistence is non-fatal too.
+pub(crate) async fn check_seal_quota(state: &AppState, tenant: &Tenant) -> Result<(), ApiError> {
+    let (Some(quota), Some(seal_repo)) = (state.monthly_seal_quota, &state.seal_repo) else {
+        return Ok(());
     };
+    match seal_repo.count_for_user_this_month(tenant.user_id).await {
+        Ok(used) if used as u64 >= quota => Err(ApiError::quota_exceeded(format!(
+            "Monthly seal quota of {} reached",
+            quota
+        ))),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to check seal quota");
+            Ok(())
+        }
+    }
+}
 
-    Ok((
-        StatusCode::CREATED,
-        Json(SealResponse {
-            seal_id: seal_id.to_string(),
-            seal_data,
-            timestamp: seal.capture_timestamp_utc,
-            has_device_attestation,
-            perceptual_hash: perceptual_hash_hex,
-            sealed_image,
-            manifest_size,
-            user_id: user_id.map(|u| u.to_string()),
-            trust_tier: trust_tier_str.to_string(),
-            qrng_source: qrng_source_name.to_string(),
-        }),
-    ))
+/// Record a failed seal attempt (non-fatal).
+///
+/// Client errors, such as a refused mock QRNG, are not platform failures.
+async fn record_seal_failure(state: &AppState, user_id: Option<Uuid>, error: &ApiError) {
+    let Some(seal_repo) = &state.seal_repo else {
+        return;
+    };
+    if !error.status_code().is_server_error() {
+        return;
+    }
+    if let Err(e) = seal_repo.record_failure(user_id, error.error_code()).await {
+        tracing::warn!(error = %e, "Failed to record seal failure");
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use veritas_core::{KeypairFile, QrngPolicy};
 
     #[tokio::test]
     async fn test_create_seal_with_mock_provider() {
         let content = b"test image content".to_vec();
         let media_type = MediaType::Image;
 
-        let result = create_seal_with_provider(content.clone(), media_type, true, true).await;
+        let builder = SealBuilder::new(content, media_type.clone());
+        let result = create_seal_with_provider(
+            builder,
+            QrngProvider::Mock,
+            &QrngSelection::default(),
+            true,
+            None,
+        )
+        .await;
 
         assert!(result.is_ok());
-        let (seal, seal_cbor) = result.unwrap();
+        let (seal, seal_cbor, fetch) = result.unwrap();
 
         // Verify seal properties
         assert_eq!(seal.qrng_source, veritas_core::QrngSource::Mock);
         assert_eq!(seal.media_type, media_type);
         assert!(!seal_cbor.is_empty());
+        assert_eq!(fetch.provider, "mock");
+        assert!(!fetch.failover);
 
         // Verify seal can be deserialized
         let deserialized = VeritasSeal::from_cbor(&seal_cbor);
@@ -498,21 +1142,111 @@ mod tests {
         let content = b"test image content".to_vec();
         let media_type = MediaType::Image;
 
-        let result = create_seal_with_provider(content, media_type, true, false).await;
+        let builder = SealBuilder::new(content, media_type);
+        let result = create_seal_with_provider(
+            builder,
+            QrngProvider::Mock,
+            &QrngSelection::default(),
+            false,
+            None,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(err, ApiError::BadRequest(_)));
     }
 
+    #[tokio::test]
+    async fn test_create_seal_provider_not_allowed() {
+        let builder = SealBuilder::new(b"test image content".to_vec(), MediaType::Image);
+        let selection = QrngSelection::new(vec![QrngProvider::IdQuantique], 1);
+        let result =
+            create_seal_with_provider(builder, QrngProvider::Lfd, &selection, true, None).await;
+
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_seal_records_qrng_policy() {
+        let policy = QrngPolicy::Pooled { max_age_secs: 60 };
+        let selection = QrngSelection::default().with_policy(policy, 2);
+
+        for pooled in [false, true] {
+            let builder = SealBuilder::new(b"test image content".to_vec(), MediaType::Image);
+            let (seal, _, fetch) =
+                create_seal_with_provider(builder, QrngProvider::Mock, &selection, true, None)
+                    .await
+                    .unwrap();
+
+            assert_eq!(fetch.pooled, pooled);
+            assert_eq!(seal.qrng_policy, Some(policy));
+            assert!(seal.verify().unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_seal_with_server_signer() {
+        let (public_key, secret_key) = generate_keypair();
+        let signer = ServerSigner::new(
+            KeypairFile::new(public_key, secret_key).unwrap(),
+            "did:web:veritas-q.com",
+        );
+
+        let mut public_keys = Vec::new();
+        for _ in 0..2 {
+            let builder = SealBuilder::new(b"test image content".to_vec(), MediaType::Image);
+            let (seal, _, _) = create_seal_with_provider(
+                builder,
+                QrngProvider::Mock,
+                &QrngSelection::default(),
+                true,
+                Some(&signer),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(seal.key_id.as_deref(), Some(signer.key_id()));
+            assert!(seal.verify().unwrap());
+            public_keys.push(seal.public_key);
+        }
+        // Every seal is signed with the same registered key
+        assert_eq!(public_keys[0], public_keys[1]);
+    }
+
+    #[test]
+    fn test_requested_qrng_provider() {
+        assert_eq!(
+            requested_qrng_provider(false, None).unwrap(),
+            QrngProvider::Auto
+        );
+        assert_eq!(
+            requested_qrng_provider(true, None).unwrap(),
+            QrngProvider::Mock
+        );
+        assert_eq!(
+            requested_qrng_provider(false, Some("idq")).unwrap(),
+            QrngProvider::IdQuantique
+        );
+        assert!(requested_qrng_provider(false, Some("random.org")).is_err());
+        assert!(requested_qrng_provider(true, Some("lfd")).is_err());
+    }
+
     #[tokio::test]
     async fn test_embed_c2pa_without_credentials() {
         // Create a real seal using mock QRNG
         let content = b"test image content".to_vec();
         let media_type = MediaType::Image;
-        let (seal, _) = create_seal_with_provider(content.clone(), media_type, true, true)
-            .await
-            .unwrap();
+        let builder = SealBuilder::new(content.clone(), media_type.clone());
+        let (seal, _, _) = create_seal_with_provider(
+            builder,
+            QrngProvider::Mock,
+            &QrngSelection::default(),
+            true,
+            None,
+        )
+        .await
+        .unwrap();
 
         let seal_id = Uuid::new_v4();
 
@@ -529,4 +1263,57 @@ mod tests {
         assert!(sealed_image.is_none());
         assert!(manifest_size.is_none());
     }
+
+    fn upload(content_type: Option<&str>, data: &[u8]) -> FileField {
+        FileField {
+            data: data.to_vec(),
+            spooled: None,
+            content_type: content_type.map(str::to_string),
+            file_name: None,
+            detected_mime: crate::multipart::detect_mime(data),
+        }
+    }
+
+    #[test]
+    fn test_resolve_media_type_uses_detected_format() {
+        let png = upload(None, b"\x89PNG\r\n\x1A\n\0\0\0\rIHDR");
+        assert_eq!(
+            resolve_media_type(None, &png, true).unwrap(),
+            MediaType::Image
+        );
+
+        let wav = upload(Some("application/octet-stream"), b"RIFF\0\0\0\0WAVEfmt ");
+        assert_eq!(
+            resolve_media_type(None, &wav, true).unwrap(),
+            MediaType::Audio
+        );
+
+        // Unrecognized content keeps the historical default
+        let text = upload(None, b"plain text");
+        assert_eq!(
+            resolve_media_type(None, &text, true).unwrap(),
+            MediaType::Image
+        );
+    }
+
+    #[test]
+    fn test_resolve_media_type_mismatch() {
+        let jpeg = upload(Some("image/jpeg"), b"\xFF\xD8\xFF\xE0\0\x10JFIF");
+
+        assert!(resolve_media_type(Some(MediaType::Video), &jpeg, true).is_err());
+        // Lenient mode keeps the declaration
+        assert_eq!(
+            resolve_media_type(Some(MediaType::Video), &jpeg, false).unwrap(),
+            MediaType::Video
+        );
+        // Generic declarations never conflict
+        assert_eq!(
+            resolve_media_type(Some(MediaType::Generic), &jpeg, true).unwrap(),
+            MediaType::Generic
+        );
+
+        // A lying Content-Type header is a mismatch too
+        let mislabeled = upload(Some("video/mp4"), b"\xFF\xD8\xFF\xE0\0\x10JFIF");
+        assert!(resolve_media_type(None, &mislabeled, true).is_err());
+    }
 }
@@ -1,19 +1,30 @@
 //! User seals handlers
 //!
-//! Handles listing, retrieving, and exporting seals for authenticated users.
+//! Handles listing, retrieving, exporting, and reporting on seals for
+//! authenticated users, and QR code labels linking to their verification.
 
 use axum::{
     extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::{Html, IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use veritas_core::jws::JWT_MEDIA_TYPE;
+use veritas_core::qr::{self, SealQrCode, DEFAULT_PNG_SCALE, MAX_PNG_SCALE};
+use veritas_core::report::{ReportOutcome, VerificationReport, DEFAULT_VERIFY_BASE_URL};
+use veritas_core::{SealClaims, VerifiableCredential, VeritasSeal};
 
 use crate::auth::AuthenticatedUser;
-use crate::db::{SealListParams, SealListResponse, SealRecord, TrustTier};
+use crate::conditional::{Conditional, ETag};
+use crate::db::{Seal, SealListParams, SealListResponse, SealMetadata, SealRecord, TrustTier};
 use crate::error::ApiError;
+use crate::geohash::{CellBounds, GeohashGrid, MAX_PRECISION, MIN_PRECISION};
 use crate::handlers::AppState;
+use crate::jws::JwsSigner;
+use crate::negotiation::{stored_seal_cbor, wants_cbor, Cbor, Negotiated, APPLICATION_CBOR};
 
 /// Query parameters for listing seals
 #[derive(Debug, Deserialize, IntoParams)]
@@ -26,11 +37,18 @@ pub struct ListSealsQuery {
     #[param(default = 20, minimum = 1, maximum = 100)]
     pub limit: Option<i64>,
 
-    /// Filter by media type (image, video, audio)
+    /// Filter by media type (image, video, audio, document)
     pub media_type: Option<String>,
 
     /// Filter by seals with GPS location
     pub has_location: Option<bool>,
+
+    /// Filter by seals carrying this tag (case-insensitive)
+    pub tag: Option<String>,
+
+    /// Filter by seals in this collection (UUID)
+    #[param(value_type = Option<String>)]
+    pub collection_id: Option<Uuid>,
 }
 
 impl From<ListSealsQuery> for SealListParams {
@@ -40,10 +58,59 @@ impl From<ListSealsQuery> for SealListParams {
             limit: query.limit.unwrap_or(20),
             media_type: query.media_type,
             has_location: query.has_location,
+            tag: query.tag.map(|tag| tag.trim().to_lowercase()),
+            collection_id: query.collection_id,
         }
     }
 }
 
+/// Query parameters of the seal map
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SealMapQuery {
+    /// Geohash length of the clusters: 3 gives cells of about 150 km, 5 of
+    /// about 5 km, 7 of about 150 m
+    #[param(default = 5, minimum = 1, maximum = 8)]
+    pub precision: Option<u8>,
+
+    /// Filter by media type (image, video, audio, document)
+    pub media_type: Option<String>,
+}
+
+/// Default geohash length of the seal map clusters
+const DEFAULT_MAP_PRECISION: u8 = 5;
+
+/// Capture locations of a user's seals, clustered by geohash
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SealMapResponse {
+    /// Geohash length of the clusters
+    #[schema(example = 5)]
+    pub precision: u8,
+    /// Seals with a GPS location
+    #[schema(example = 42)]
+    pub total: i64,
+    /// Clusters, largest first
+    pub clusters: Vec<SealMapCluster>,
+}
+
+/// Seals captured in one geohash cell
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SealMapCluster {
+    /// Geohash of the cell
+    #[schema(example = "u09tv")]
+    pub geohash: String,
+    /// Seals captured in the cell
+    #[schema(example = 12)]
+    pub seals: i64,
+    /// Mean latitude of the seals (where to draw the marker)
+    #[schema(example = 48.8566)]
+    pub lat: f64,
+    /// Mean longitude of the seals
+    #[schema(example = 2.3522)]
+    pub lng: f64,
+    /// Extent of the cell
+    pub bounds: CellBounds,
+}
+
 /// Response for seal detail
 #[derive(Debug, Serialize, ToSchema)]
 pub struct SealDetailResponse {
@@ -59,6 +126,18 @@ pub struct SealDetailResponse {
     pub qrng_source: String,
 }
 
+impl From<Seal> for SealDetailResponse {
+    fn from(seal: Seal) -> Self {
+        Self {
+            signature: hex::encode(&seal.signature),
+            public_key: hex::encode(&seal.public_key),
+            qrng_entropy: hex::encode(&seal.qrng_entropy),
+            qrng_source: seal.qrng_source.clone(),
+            seal: SealRecord::from(seal),
+        }
+    }
+}
+
 /// List seals for authenticated user
 ///
 /// Returns a paginated list of seals created by the authenticated user,
@@ -89,7 +168,7 @@ pub async fn list_user_seals_handler(
 
     let params = SealListParams::from(query);
     let response = seal_repo
-        .list_for_user(auth.user.id, &params)
+        .list_for_user(&auth.tenant(), &params)
         .await
         .map_err(|e| {
             tracing::error!(error = %e, "Failed to list seals");
@@ -99,9 +178,71 @@ pub async fn list_user_seals_handler(
     Ok(Json(response))
 }
 
+/// Map of the authenticated user's seals
+///
+/// Counts the seals with a GPS location per geohash cell, so a map of where
+/// evidence was captured does not need every seal record. Seals without a
+/// location are left out.
+#[utoipa::path(
+    get,
+    path = "/api/v1/seals/map",
+    tag = "Seals",
+    params(SealMapQuery),
+    responses(
+        (status = 200, description = "Seal locations clustered by geohash", body = SealMapResponse),
+        (status = 400, description = "Precision out of range"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn seal_map_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Query(query): Query<SealMapQuery>,
+) -> Result<Json<SealMapResponse>, ApiError> {
+    let precision = query.precision.unwrap_or(DEFAULT_MAP_PRECISION);
+    let grid = GeohashGrid::new(precision).ok_or_else(|| {
+        ApiError::bad_request(format!(
+            "precision must be between {} and {}",
+            MIN_PRECISION, MAX_PRECISION
+        ))
+    })?;
+    let seal_repo = state
+        .seal_repo
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+
+    let buckets = seal_repo
+        .location_buckets(&auth.tenant(), &grid, query.media_type.as_deref())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to build seal map");
+            ApiError::internal("A database error occurred")
+        })?;
+
+    Ok(Json(SealMapResponse {
+        precision,
+        total: buckets.iter().map(|bucket| bucket.seals).sum(),
+        clusters: buckets
+            .into_iter()
+            .map(|bucket| SealMapCluster {
+                geohash: grid.geohash(bucket.x, bucket.y),
+                seals: bucket.seals,
+                lat: bucket.lat,
+                lng: bucket.lng,
+                bounds: grid.bounds(bucket.x, bucket.y),
+            })
+            .collect(),
+    }))
+}
+
 /// Get seal detail for authenticated user
 ///
-/// Returns detailed information about a specific seal owned by the user.
+/// Returns detailed information about a specific seal owned by the user,
+/// with an `ETag` for conditional polling (`If-None-Match`).
 #[utoipa::path(
     get,
     path = "/api/v1/seals/{seal_id}",
@@ -111,6 +252,7 @@ pub async fn list_user_seals_handler(
     ),
     responses(
         (status = 200, description = "Seal details", body = SealDetailResponse),
+        (status = 304, description = "Not modified (If-None-Match matches the ETag)"),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Seal not found"),
         (status = 503, description = "Database not available")
@@ -123,7 +265,8 @@ pub async fn get_user_seal_handler(
     State(state): State<AppState>,
     auth: AuthenticatedUser,
     Path(seal_id): Path<Uuid>,
-) -> Result<Json<SealDetailResponse>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Conditional<Json<SealDetailResponse>>, ApiError> {
     let seal_repo = state
         .seal_repo
         .as_ref()
@@ -131,7 +274,7 @@ pub async fn get_user_seal_handler(
 
     // Get seal (restricted to user's seals)
     let seal = seal_repo
-        .find_by_id_for_user(seal_id, auth.user.id)
+        .find_by_id_for_user(seal_id, &auth.tenant())
         .await
         .map_err(|e| {
             tracing::error!(error = %e, "Failed to get seal");
@@ -139,13 +282,8 @@ pub async fn get_user_seal_handler(
         })?
         .ok_or_else(|| ApiError::not_found("Seal not found"))?;
 
-    Ok(Json(SealDetailResponse {
-        seal: SealRecord::from(seal.clone()),
-        signature: hex::encode(&seal.signature),
-        public_key: hex::encode(&seal.public_key),
-        qrng_entropy: hex::encode(&seal.qrng_entropy),
-        qrng_source: seal.qrng_source,
-    }))
+    let detail = SealDetailResponse::from(seal);
+    Ok(Conditional::new(&headers, ETag::of(&detail), Json(detail)))
 }
 
 /// Export format options
@@ -157,6 +295,10 @@ pub enum ExportFormat {
     Json,
     /// C2PA JUMBF manifest format
     C2pa,
+    /// W3C Verifiable Credential (VC Data Model 2.0)
+    Vc,
+    /// Compact JWS of the seal claims (`application/jwt`, ES256)
+    Jws,
 }
 
 impl std::fmt::Display for ExportFormat {
@@ -164,6 +306,8 @@ impl std::fmt::Display for ExportFormat {
         match self {
             ExportFormat::Json => write!(f, "json"),
             ExportFormat::C2pa => write!(f, "c2pa"),
+            ExportFormat::Vc => write!(f, "vc"),
+            ExportFormat::Jws => write!(f, "jws"),
         }
     }
 }
@@ -171,7 +315,7 @@ impl std::fmt::Display for ExportFormat {
 /// Query parameters for seal export
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct ExportSealQuery {
-    /// Export format (json, c2pa)
+    /// Export format (json, c2pa, vc, jws)
     #[param(default = "json")]
     pub format: Option<ExportFormat>,
 }
@@ -322,11 +466,20 @@ pub enum ExportResponse {
     Json(JsonExportResponse),
     /// C2PA export
     C2pa(C2paExportResponse),
+    /// W3C Verifiable Credential wrapping the seal, issued by
+    /// ISSUER_DID (see `veritas_core::credential`)
+    Vc(serde_json::Value),
 }
 
 /// Export seal in specified format
 ///
-/// Returns seal data in JSON or C2PA format for interoperability.
+/// Returns seal data in JSON or C2PA format, as a W3C Verifiable Credential,
+/// or as a compact JWS for JOSE tooling, for interoperability, with an `ETag`
+/// that only changes with the seal (not the export time). Clients preferring
+/// `Accept: application/cbor` get the raw CBOR seal instead, whatever the
+/// format. The credential and the JWS embed the stored seal, so they need the
+/// manifest store; the JWS is signed ES256 with the C2PA signing key
+/// (`x5c` header) and carries the ML-DSA-65 signature as a claim.
 #[utoipa::path(
     get,
     path = "/api/v1/seals/{seal_id}/export",
@@ -336,10 +489,11 @@ pub enum ExportResponse {
         ExportSealQuery
     ),
     responses(
-        (status = 200, description = "Export data", body = ExportResponse),
+        (status = 200, description = "Export data (raw CBOR seal with Accept: application/cbor, application/jwt with format=jws)", body = ExportResponse),
+        (status = 304, description = "Not modified (If-None-Match matches the ETag)"),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Seal not found"),
-        (status = 503, description = "Database not available")
+        (status = 503, description = "Database, manifest store or JWS signing key not available")
     ),
     security(
         ("clerk_token" = [])
@@ -350,7 +504,8 @@ pub async fn export_seal_handler(
     auth: AuthenticatedUser,
     Path(seal_id): Path<Uuid>,
     Query(query): Query<ExportSealQuery>,
-) -> Result<Json<ExportResponse>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Negotiated<Response>, ApiError> {
     let seal_repo = state
         .seal_repo
         .as_ref()
@@ -358,7 +513,7 @@ pub async fn export_seal_handler(
 
     // Get seal (restricted to user's seals)
     let seal = seal_repo
-        .find_by_id_for_user(seal_id, auth.user.id)
+        .find_by_id_for_user(seal_id, &auth.tenant())
         .await
         .map_err(|e| {
             tracing::error!(error = %e, "Failed to get seal for export");
@@ -366,7 +521,31 @@ pub async fn export_seal_handler(
         })?
         .ok_or_else(|| ApiError::not_found("Seal not found"))?;
 
+    if wants_cbor(&headers) {
+        let etag = ETag::of(&(APPLICATION_CBOR, seal.id));
+        if etag.matches(&headers) {
+            return Ok(Negotiated(
+                Conditional::<()>::NotModified(etag).into_response(),
+            ));
+        }
+        let cbor = stored_seal_cbor(&state, seal.id).await?;
+        return Ok(Negotiated(
+            Conditional::Modified(etag, Cbor(cbor)).into_response(),
+        ));
+    }
+
     let format = query.format.unwrap_or_default();
+    let etag = ETag::of(&(
+        format.to_string(),
+        env!("CARGO_PKG_VERSION"),
+        &*state.issuer_did,
+        &seal,
+    ));
+    if etag.matches(&headers) {
+        return Ok(Negotiated(
+            Conditional::<()>::NotModified(etag).into_response(),
+        ));
+    }
     let now = chrono::Utc::now();
 
     match format {
@@ -395,7 +574,9 @@ pub async fn export_seal_handler(
                     hash_algorithm: "SHA3-256".to_string(),
                 },
             };
-            Ok(Json(ExportResponse::Json(response)))
+            Ok(Negotiated(
+                Conditional::Modified(etag, Json(ExportResponse::Json(response))).into_response(),
+            ))
         }
         ExportFormat::C2pa => {
             use base64::{engine::general_purpose::STANDARD, Engine};
@@ -471,7 +652,384 @@ pub async fn export_seal_handler(
                     usage_note: "This manifest can be embedded into media files using C2PA tools. The veritas.quantum_seal assertion contains the post-quantum signature.".to_string(),
                 },
             };
-            Ok(Json(ExportResponse::C2pa(response)))
+            Ok(Negotiated(
+                Conditional::Modified(etag, Json(ExportResponse::C2pa(response))).into_response(),
+            ))
+        }
+        ExportFormat::Vc => {
+            let cbor = stored_seal_cbor(&state, seal.id).await?;
+            let credential = VeritasSeal::from_cbor(&cbor)
+                .and_then(|stored| VerifiableCredential::from_seal(&stored, &state.issuer_did))
+                .and_then(|credential| {
+                    serde_json::to_value(credential)
+                        .map_err(|e| veritas_core::VeritasError::SerializationError(e.to_string()))
+                })
+                .map_err(|e| {
+                    tracing::error!(seal_id = %seal.id, error = %e, "Failed to export credential");
+                    ApiError::internal("Failed to export seal as a credential")
+                })?;
+            Ok(Negotiated(
+                Conditional::Modified(etag, Json(ExportResponse::Vc(credential))).into_response(),
+            ))
+        }
+        ExportFormat::Jws => {
+            let signer = JwsSigner::from_env().map_err(|e| {
+                tracing::error!(error = %e, "JWS signing key not configured");
+                ApiError::service_unavailable(
+                    "JWS signing key not configured. Set C2PA_SIGNING_KEY and C2PA_SIGNING_CERT",
+                )
+            })?;
+            let cbor = stored_seal_cbor(&state, seal.id).await?;
+            let claims = VeritasSeal::from_cbor(&cbor)
+                .and_then(|stored| SealClaims::from_seal(&stored, &state.issuer_did))
+                .map_err(|e| {
+                    tracing::error!(seal_id = %seal.id, error = %e, "Failed to export JWS");
+                    ApiError::internal("Failed to export seal as a JWS")
+                })?;
+            let token = signer.sign(&claims).map_err(|e| {
+                tracing::error!(seal_id = %seal.id, error = %e, "Failed to sign JWS");
+                ApiError::internal("Failed to export seal as a JWS")
+            })?;
+            Ok(Negotiated(
+                Conditional::Modified(etag, ([(header::CONTENT_TYPE, JWT_MEDIA_TYPE)], token))
+                    .into_response(),
+            ))
+        }
+    }
+}
+
+/// Report format options
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// Printable PDF certificate
+    #[default]
+    Pdf,
+    /// Standalone HTML page
+    Html,
+}
+
+/// Query parameters for the verification report
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SealReportQuery {
+    /// Report format (pdf, html)
+    #[param(default = "pdf")]
+    pub format: Option<ReportFormat>,
+}
+
+/// Get a human-readable verification report for a seal
+///
+/// Renders a verification certificate with the seal details, the outcome of
+/// re-verifying the stored seal signature, the trust tier and its rationale,
+/// the blockchain anchor link, and a QR code to the online verify page.
+#[utoipa::path(
+    get,
+    path = "/api/v1/seals/{seal_id}/report",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)"),
+        SealReportQuery
+    ),
+    responses(
+        (status = 200, description = "Verification report (application/pdf or text/html)"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn seal_report_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(seal_id): Path<Uuid>,
+    Query(query): Query<SealReportQuery>,
+) -> Result<Response, ApiError> {
+    let seal_repo = state
+        .seal_repo
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+
+    // Get seal (restricted to user's seals)
+    let seal = seal_repo
+        .find_by_id_for_user(seal_id, &auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to get seal for report");
+            ApiError::internal("A database error occurred")
+        })?
+        .ok_or_else(|| ApiError::not_found("Seal not found"))?;
+
+    // The full seal lives in the manifest store; verify its signature when available
+    let stored_seal = match &state.manifest_store {
+        Some(store) => match store.get_by_image_hash(&seal.content_hash).await {
+            Ok(record) => record.and_then(|r| VeritasSeal::from_cbor(&r.seal_cbor).ok()),
+            Err(e) => {
+                tracing::warn!(seal_id = %seal.id, error = %e, "Failed to load seal for report");
+                None
+            }
+        },
+        None => None,
+    };
+
+    let report = build_report(&seal, stored_seal.as_ref());
+    let response = match query.format.unwrap_or_default() {
+        ReportFormat::Pdf => (
+            [
+                (header::CONTENT_TYPE, "application/pdf".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("inline; filename=\"veritas-report-{}.pdf\"", seal.id),
+                ),
+            ],
+            report.to_pdf(),
+        )
+            .into_response(),
+        ReportFormat::Html => Html(report.to_html()).into_response(),
+    };
+    Ok(response)
+}
+
+/// QR code image format options
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QrImageFormat {
+    /// PNG image
+    #[default]
+    Png,
+    /// SVG vector image
+    Svg,
+}
+
+/// What a QR code label encodes
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QrPayload {
+    /// Link to the online verify page
+    #[default]
+    Url,
+    /// Compact seal digest (`vq1:<content hash>:<signature tag>`)
+    Digest,
+}
+
+/// Query parameters for the QR code label
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SealQrQuery {
+    /// Image format (png, svg)
+    #[param(default = "png")]
+    pub format: Option<QrImageFormat>,
+
+    /// Encoded content (url, digest)
+    #[param(default = "url")]
+    pub payload: Option<QrPayload>,
+
+    /// PNG pixels per module
+    #[param(default = 8, minimum = 1, maximum = 32)]
+    pub scale: Option<u32>,
+}
+
+/// Get a QR code label for a seal
+///
+/// Encodes the seal's verify page URL, or its compact digest, as a QR code
+/// for printing on physical evidence labels.
+#[utoipa::path(
+    get,
+    path = "/api/v1/seals/{seal_id}/qr",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)"),
+        SealQrQuery
+    ),
+    responses(
+        (status = 200, description = "QR code image (image/png or image/svg+xml)"),
+        (status = 400, description = "Invalid scale"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn seal_qr_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(seal_id): Path<Uuid>,
+    Query(query): Query<SealQrQuery>,
+) -> Result<Response, ApiError> {
+    let scale = query.scale.unwrap_or(DEFAULT_PNG_SCALE);
+    if !(1..=MAX_PNG_SCALE).contains(&scale) {
+        return Err(ApiError::bad_request(format!(
+            "scale must be between 1 and {}",
+            MAX_PNG_SCALE
+        )));
+    }
+
+    let seal_repo = state
+        .seal_repo
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+
+    // Get seal (restricted to user's seals)
+    let seal = seal_repo
+        .find_by_id_for_user(seal_id, &auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to get seal for QR code");
+            ApiError::internal("A database error occurred")
+        })?
+        .ok_or_else(|| ApiError::not_found("Seal not found"))?;
+
+    let code = SealQrCode::encode(&qr_payload(&seal, query.payload.unwrap_or_default()))?;
+    let response = match query.format.unwrap_or_default() {
+        QrImageFormat::Png => {
+            ([(header::CONTENT_TYPE, "image/png")], code.to_png(scale)).into_response()
+        }
+        QrImageFormat::Svg => {
+            ([(header::CONTENT_TYPE, "image/svg+xml")], code.to_svg()).into_response()
         }
+    };
+    Ok(response)
+}
+
+/// Text encoded in a seal's QR code label.
+fn qr_payload(seal: &Seal, payload: QrPayload) -> String {
+    match payload {
+        QrPayload::Url => qr::verify_url(DEFAULT_VERIFY_BASE_URL, &seal.id.to_string()),
+        QrPayload::Digest => qr::seal_digest_from_parts(&seal.content_hash, &seal.signature),
+    }
+}
+
+/// Build the report for a stored seal record.
+///
+/// Without the full seal (no manifest store, or not stored there) the
+/// signature cannot be checked and the report says so.
+pub(crate) fn build_report(seal: &Seal, stored_seal: Option<&VeritasSeal>) -> VerificationReport {
+    let mut report = match stored_seal {
+        Some(stored_seal) => VerificationReport::from_seal(stored_seal),
+        None => VerificationReport {
+            seal_id: None,
+            file_name: None,
+            outcome: ReportOutcome::Unverified(
+                "The full seal is not available on this server, so its signature was not checked."
+                    .to_string(),
+            ),
+            media_type: seal.media_type.clone(),
+            sealed_at: seal.captured_at,
+            content_hash: seal.content_hash.clone(),
+            perceptual_hash: seal.perceptual_hash.as_ref().map(hex::encode),
+            qrng_source: seal.qrng_source.clone(),
+            location: None,
+            has_device_attestation: false,
+            trust_tier: None,
+            trust_rationale: Vec::new(),
+            anchor: None,
+            verify_url: None,
+            regions: None,
+            generated_at: chrono::Utc::now(),
+        },
+    };
+
+    let tier = TrustTierExport::from(seal.trust_tier);
+    report.seal_id = Some(seal.id.to_string());
+    report.trust_tier = Some(format!("Tier {} - {}", tier.level, tier.label));
+    report.verify_url = Some(qr::verify_url(
+        DEFAULT_VERIFY_BASE_URL,
+        &seal.id.to_string(),
+    ));
+
+    if let Ok(metadata) = serde_json::from_value::<SealMetadata>(seal.metadata.clone()) {
+        report.has_device_attestation |= metadata.has_device_attestation;
+        report.trust_rationale = metadata.trust_rationale;
+        if let Some(location) = metadata.location {
+            report.location = Some(format!("{:.5}, {:.5}", location.lat, location.lng));
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seal_row() -> Seal {
+        Seal {
+            id: Uuid::new_v4(),
+            user_id: Some(Uuid::new_v4()),
+            organization_id: None,
+            content_hash: "ab".repeat(32),
+            perceptual_hash: None,
+            qrng_entropy: vec![0; 32],
+            qrng_source: "lfd".to_string(),
+            signature: vec![],
+            public_key: vec![],
+            media_type: "image".to_string(),
+            file_size: Some(1024),
+            mime_type: Some("image/jpeg".to_string()),
+            metadata: serde_json::json!({
+                "timestamp": "2026-01-08T10:00:00Z",
+                "location": {"lat": 48.8566, "lng": 2.3522},
+                "capture_source": "camera",
+                "has_device_attestation": true,
+                "trust_rationale": ["device attestation verified (packed format)"]
+            }),
+            trust_tier: TrustTier::Tier3,
+            c2pa_manifest_embedded: false,
+            captured_at: chrono::Utc::now(),
+            created_at: chrono::Utc::now(),
+            media_deleted_at: None,
+            revoked_at: None,
+            revocation_reason: None,
+            verification_count: 0,
+            legal_hold: false,
+        }
+    }
+
+    #[test]
+    fn test_qr_payload() {
+        let seal = seal_row();
+
+        assert_eq!(
+            qr_payload(&seal, QrPayload::Url),
+            format!("https://veritas-q.com/verify/{}", seal.id)
+        );
+        let digest = qr_payload(&seal, QrPayload::Digest);
+        assert!(digest.starts_with(&format!("vq1:{}:", seal.content_hash)));
+    }
+
+    #[test]
+    fn test_export_format_query() {
+        let query: ExportSealQuery = serde_json::from_str(r#"{"format": "vc"}"#).unwrap();
+        assert!(matches!(query.format, Some(ExportFormat::Vc)));
+        assert_eq!(ExportFormat::Vc.to_string(), "vc");
+        let query: ExportSealQuery = serde_json::from_str(r#"{"format": "jws"}"#).unwrap();
+        assert!(matches!(query.format, Some(ExportFormat::Jws)));
+        assert_eq!(ExportFormat::Jws.to_string(), "jws");
+        assert!(serde_json::from_str::<ExportSealQuery>(r#"{"format": "jsonld"}"#).is_err());
+    }
+
+    #[test]
+    fn test_build_report_without_stored_seal() {
+        let seal = seal_row();
+        let report = build_report(&seal, None);
+
+        assert!(matches!(report.outcome, ReportOutcome::Unverified(_)));
+        assert_eq!(report.seal_id, Some(seal.id.to_string()));
+        assert_eq!(
+            report.trust_tier.as_deref(),
+            Some("Tier 3 - Hardware Secure")
+        );
+        assert_eq!(
+            report.trust_rationale,
+            vec!["device attestation verified (packed format)"]
+        );
+        assert_eq!(report.location.as_deref(), Some("48.85660, 2.35220"));
+        assert!(report.has_device_attestation);
+        assert_eq!(
+            report.verify_url,
+            Some(format!("https://veritas-q.com/verify/{}", seal.id))
+        );
     }
 }
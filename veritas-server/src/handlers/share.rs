@@ -0,0 +1,403 @@
+//! Seal share handlers
+//!
+//! Lets a seal's owner create unguessable share links giving anyone public,
+//! read-only access to that seal's verification details, optionally until an
+//! expiry date, and revoke them. Only a hash of each token is stored, so a
+//! lost link cannot be recovered, only revoked and replaced.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::conditional::{Conditional, ETag};
+use crate::db::{AuditEvent, Branding, SealShare, SealShareRecord, SealShareRepository, Tenant};
+use crate::error::ApiError;
+use crate::handlers::organizations::response_branding;
+use crate::handlers::seals::SealDetailResponse;
+use crate::negotiation::{stored_seal_cbor, wants_cbor, Cbor, Negotiated, APPLICATION_CBOR};
+use crate::state::AppState;
+
+/// Shortest allowed share lifetime (1 minute)
+pub const MIN_SHARE_TTL_SECS: u64 = 60;
+
+/// Longest allowed share lifetime (1 year)
+pub const MAX_SHARE_TTL_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// Request to share a seal
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CreateShareRequest {
+    /// Lifetime of the share in seconds (default: never expires)
+    #[serde(default)]
+    #[schema(example = 604800_u64)]
+    pub expires_in_secs: Option<u64>,
+}
+
+/// Newly created share, including its token
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateShareResponse {
+    /// The share
+    pub share: SealShareRecord,
+    /// Share token; only returned here, store it or the link
+    #[schema(example = "hX0r3m2b4c5d6e7f8g9h0i1j2k3l4m5n6o7p8q9r0s1")]
+    pub token: String,
+    /// Public link to the shared seal
+    #[schema(example = "https://veritas-q.com/shared/hX0r3m2b4c5d6e7f8g9h0i1j2k3l4m5n6o7p8q9r0s1")]
+    pub url: String,
+}
+
+/// Shares of a seal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListSharesResponse {
+    /// Shares, newest first (revoked and expired ones included)
+    pub shares: Vec<SealShareRecord>,
+}
+
+/// Seal details opened through a share link
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SharedSealResponse {
+    /// Seal details
+    #[serde(flatten)]
+    pub detail: SealDetailResponse,
+    /// When the share link stops working (absent = never)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub share_expires_at: Option<DateTime<Utc>>,
+    /// Branding of the seal's organization, else of the organization whose
+    /// verification domain the request comes from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branding: Option<Branding>,
+}
+
+/// Share a seal
+///
+/// Creates a share link giving unauthenticated, read-only access to the
+/// seal's verification details. The token is only returned once.
+#[utoipa::path(
+    post,
+    path = "/api/v1/seals/{seal_id}/share",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    request_body = CreateShareRequest,
+    responses(
+        (status = 201, description = "Share created", body = CreateShareResponse),
+        (status = 400, description = "Invalid expiry"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn create_share_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(seal_id): Path<Uuid>,
+    request: Option<Json<CreateShareRequest>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let expires_in = request
+        .expires_in_secs
+        .map(validate_share_ttl)
+        .transpose()?;
+
+    let share_repo = share_repo(&state)?;
+    let tenant = auth.tenant();
+    require_own_seal(&state, seal_id, &tenant).await?;
+
+    let token = generate_share_token();
+    let now = Utc::now();
+    let share = SealShare {
+        id: Uuid::new_v4(),
+        seal_id,
+        user_id: auth.user.id,
+        token_hash: hash_share_token(&token),
+        created_at: now,
+        expires_at: expires_in.map(|ttl| now + ttl),
+        revoked_at: None,
+        last_accessed_at: None,
+        access_count: 0,
+    };
+    let created = share_repo.create(&share, &tenant).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to create seal share");
+        ApiError::internal("A database error occurred")
+    })?;
+    if !created {
+        return Err(ApiError::not_found("Seal not found"));
+    }
+
+    tracing::info!(seal_id = %seal_id, share_id = %share.id, "Seal shared");
+
+    let url = format!("{}/{}", state.share_base_url.trim_end_matches('/'), token);
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateShareResponse {
+            share: SealShareRecord::from(share),
+            token,
+            url,
+        }),
+    ))
+}
+
+/// List the shares of a seal
+#[utoipa::path(
+    get,
+    path = "/api/v1/seals/{seal_id}/shares",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Shares of the seal", body = ListSharesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn list_shares_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(seal_id): Path<Uuid>,
+) -> Result<Json<ListSharesResponse>, ApiError> {
+    let share_repo = share_repo(&state)?;
+    let tenant = auth.tenant();
+    require_own_seal(&state, seal_id, &tenant).await?;
+
+    let shares = share_repo
+        .list_for_seal(seal_id, &tenant)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to list seal shares");
+            ApiError::internal("A database error occurred")
+        })?;
+
+    Ok(Json(ListSharesResponse {
+        shares: shares.into_iter().map(SealShareRecord::from).collect(),
+    }))
+}
+
+/// Revoke a share
+///
+/// The link stops working immediately; the share stays listed as revoked.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/seals/{seal_id}/shares/{share_id}",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)"),
+        ("share_id" = String, Path, description = "Share ID (UUID)")
+    ),
+    responses(
+        (status = 204, description = "Share revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Share not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn revoke_share_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path((seal_id, share_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let revoked = share_repo(&state)?
+        .revoke(share_id, seal_id, &auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to revoke seal share");
+            ApiError::internal("A database error occurred")
+        })?;
+    if !revoked {
+        return Err(ApiError::not_found("Share not found"));
+    }
+
+    tracing::info!(seal_id = %seal_id, share_id = %share_id, "Seal share revoked");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Open a shared seal
+///
+/// Public endpoint behind share links: returns the seal's verification
+/// details without authentication. Unknown, expired and revoked tokens all
+/// return 404. Responses carry an `ETag` for conditional polling. Clients
+/// preferring `Accept: application/cbor` get the raw CBOR seal instead.
+#[utoipa::path(
+    get,
+    path = "/api/v1/shared/{token}",
+    tag = "Seals",
+    params(
+        ("token" = String, Path, description = "Share token")
+    ),
+    responses(
+        (status = 200, description = "Shared seal details (raw CBOR seal with Accept: application/cbor)", body = SharedSealResponse),
+        (status = 304, description = "Not modified (If-None-Match matches the ETag)"),
+        (status = 404, description = "Share not found, expired or revoked"),
+        (status = 503, description = "Database or manifest store not available")
+    )
+)]
+pub async fn shared_seal_handler(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Result<Negotiated<Response>, ApiError> {
+    let share_repo = share_repo(&state)?;
+    let seal_repo = state
+        .seal_repo
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+
+    let share = share_repo
+        .open_by_token_hash(&hash_share_token(&token))
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to open seal share");
+            ApiError::internal("A database error occurred")
+        })?
+        .ok_or_else(|| ApiError::not_found("Share not found"))?;
+
+    // The share itself is checked on every request; the seal record may be cached
+    let seal = match state.verification_cache.seal(share.seal_id) {
+        Some(seal) => seal,
+        None => {
+            let seal = seal_repo
+                .find_by_id(share.seal_id)
+                .await
+                .map_err(|e| {
+                    tracing::error!(error = %e, "Failed to get shared seal");
+                    ApiError::internal("A database error occurred")
+                })?
+                .ok_or_else(|| ApiError::not_found("Share not found"))?;
+            state.verification_cache.insert_seal(seal.clone());
+            seal
+        }
+    };
+
+    // Share links are opened anonymously
+    if let Some(audit_repo) = &state.audit_repo {
+        if let Err(e) = audit_repo
+            .record(AuditEvent::ShareOpened, Some(seal.id), None, None)
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to record share view");
+        }
+    }
+
+    if wants_cbor(&headers) {
+        let etag = ETag::of(&(APPLICATION_CBOR, seal.id));
+        if etag.matches(&headers) {
+            return Ok(Negotiated(
+                Conditional::<()>::NotModified(etag).into_response(),
+            ));
+        }
+        let cbor = stored_seal_cbor(&state, seal.id).await?;
+        return Ok(Negotiated(
+            Conditional::Modified(etag, Cbor(cbor)).into_response(),
+        ));
+    }
+
+    let branding = response_branding(&state, seal.organization_id, &headers).await;
+    let response = SharedSealResponse {
+        detail: SealDetailResponse::from(seal),
+        share_expires_at: share.expires_at,
+        branding,
+    };
+    Ok(Negotiated(
+        Conditional::new(&headers, ETag::of(&response), Json(response)).into_response(),
+    ))
+}
+
+fn share_repo(state: &AppState) -> Result<&SealShareRepository, ApiError> {
+    state
+        .share_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+/// Fail with 404 unless `seal_id` exists and belongs to `user_id`.
+pub(crate) async fn require_own_seal(
+    state: &AppState,
+    seal_id: Uuid,
+    tenant: &Tenant,
+) -> Result<(), ApiError> {
+    let seal_repo = state
+        .seal_repo
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+
+    seal_repo
+        .find_by_id_for_user(seal_id, tenant)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to get seal");
+            ApiError::internal("A database error occurred")
+        })?
+        .map(|_| ())
+        .ok_or_else(|| ApiError::not_found("Seal not found"))
+}
+
+fn validate_share_ttl(secs: u64) -> Result<chrono::Duration, ApiError> {
+    if !(MIN_SHARE_TTL_SECS..=MAX_SHARE_TTL_SECS).contains(&secs) {
+        return Err(ApiError::bad_request(format!(
+            "expires_in_secs must be between {} and {}",
+            MIN_SHARE_TTL_SECS, MAX_SHARE_TTL_SECS
+        )));
+    }
+    Ok(chrono::Duration::seconds(secs as i64))
+}
+
+/// Generate a share token: 32 bytes from two random UUIDs (244 random bits),
+/// URL-safe base64.
+fn generate_share_token() -> String {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(Uuid::new_v4().as_bytes());
+    bytes[16..].copy_from_slice(Uuid::new_v4().as_bytes());
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// SHA3-256 of a share token, as stored in the database.
+fn hash_share_token(token: &str) -> Vec<u8> {
+    Sha3_256::digest(token.as_bytes()).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_tokens_are_unique_and_hashed() {
+        let token = generate_share_token();
+        assert_eq!(token.len(), 43);
+        assert_ne!(token, generate_share_token());
+
+        let hash = hash_share_token(&token);
+        assert_eq!(hash.len(), 32);
+        assert_eq!(hash, hash_share_token(&token));
+        assert_ne!(hash, hash_share_token(&generate_share_token()));
+    }
+
+    #[test]
+    fn test_validate_share_ttl() {
+        assert!(validate_share_ttl(MIN_SHARE_TTL_SECS - 1).is_err());
+        assert!(validate_share_ttl(MAX_SHARE_TTL_SECS + 1).is_err());
+        assert_eq!(
+            validate_share_ttl(3600).unwrap(),
+            chrono::Duration::hours(1)
+        );
+    }
+}
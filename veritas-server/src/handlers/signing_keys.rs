@@ -0,0 +1,124 @@
+//! Signing-key registry handler
+//!
+//! Publishes the keys that sign this server's seals (see
+//! [`crate::signing_keys`]), so that verifiers checking seals offline can
+//! resolve the `key_id` of a seal the way `/verify` does: as a list, and as
+//! the did:web document of the server's `ISSUER_DID`.
+
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use veritas_core::{DidDocument, SigningKey, SigningKeyStatus};
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// A registered seal signing key
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SigningKeyRecord {
+    /// Key ID written into the key's seals
+    #[schema(example = "3f2a9c0e5b7d1a46")]
+    pub key_id: String,
+    /// ML-DSA-65 public key (hex)
+    pub public_key: String,
+    /// Start of the validity period
+    pub valid_from: DateTime<Utc>,
+    /// End of the validity period (absent = open-ended)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+    /// `active` (signing new seals), `retired` (replaced, still vouching for
+    /// the seals made within its validity) or `revoked`
+    #[schema(value_type = String, example = "active")]
+    pub status: SigningKeyStatus,
+}
+
+impl From<SigningKey> for SigningKeyRecord {
+    fn from(key: SigningKey) -> Self {
+        Self {
+            key_id: key.key_id,
+            public_key: hex::encode(&key.public_key),
+            valid_from: DateTime::from_timestamp_millis(key.valid_from as i64).unwrap_or_default(),
+            valid_until: key
+                .valid_until
+                .and_then(|until| DateTime::from_timestamp_millis(until as i64)),
+            status: key.status,
+        }
+    }
+}
+
+/// Registered signing keys
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SigningKeyListResponse {
+    /// Keys, newest first
+    pub keys: Vec<SigningKeyRecord>,
+}
+
+/// List the seal signing keys
+///
+/// Every key that signed seals on this server (`SIGNING_KEY_FILE`), with its
+/// validity period and status. A seal's `key_id` is trusted when the key
+/// registered under it has the seal's public key, is not revoked and was
+/// valid at capture time. Empty when seals are signed with one-off keys.
+#[utoipa::path(
+    get,
+    path = "/api/v1/signing-keys",
+    tag = "Verification",
+    responses(
+        (status = 200, description = "Registered signing keys", body = SigningKeyListResponse)
+    )
+)]
+pub async fn list_signing_keys_handler(
+    State(state): State<AppState>,
+) -> Result<Json<SigningKeyListResponse>, ApiError> {
+    let keys = state.signing_keys.list().await?;
+    Ok(Json(SigningKeyListResponse {
+        keys: keys.into_iter().map(SigningKeyRecord::from).collect(),
+    }))
+}
+
+/// DID document of the issuer
+///
+/// The did:web document of `ISSUER_DID`, listing the signing keys that are
+/// not revoked as ML-DSA-65 Multikeys under their key IDs. Seals signed by
+/// this server carry such a key ID (`<ISSUER_DID>#<key ID>`), so anyone can
+/// resolve their issuer with a standard DID resolver.
+#[utoipa::path(
+    get,
+    path = "/.well-known/did.json",
+    tag = "Verification",
+    responses(
+        (status = 200, description = "W3C DID document (DID Core 1.0)")
+    )
+)]
+pub async fn did_document_handler(
+    State(state): State<AppState>,
+) -> Result<Json<DidDocument>, ApiError> {
+    let keys = state.signing_keys.list().await?;
+    DidDocument::new(&state.issuer_did, &keys)
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to build DID document");
+            ApiError::internal("Failed to build DID document")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_key_record_serialization() {
+        let key = SigningKey {
+            valid_until: Some(1_767_225_600_000),
+            status: SigningKeyStatus::Retired,
+            ..SigningKey::new(vec![0xab; 4], 1_735_689_600_000)
+        };
+        let json = serde_json::to_value(SigningKeyRecord::from(key)).unwrap();
+
+        assert_eq!(json["public_key"], "abababab");
+        assert_eq!(json["valid_from"], "2025-01-01T00:00:00Z");
+        assert_eq!(json["valid_until"], "2026-01-01T00:00:00Z");
+        assert_eq!(json["status"], "retired");
+    }
+}
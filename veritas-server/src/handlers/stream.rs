@@ -0,0 +1,362 @@
+//! Live stream sealing handlers
+//!
+//! Seal a live broadcast while it is being produced: open a stream, upload
+//! the media in chunks as it is encoded, and receive signed checkpoints
+//! along the way. Closing the stream returns a segmented seal over the whole
+//! recording, signed by the same key as the checkpoints.
+
+use std::time::Instant;
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use veritas_core::segments::DEFAULT_SEGMENT_SIZE;
+use veritas_core::{
+    generate_keypair,
+    qrng::{QrngProviderConfig, QrngProviderFactory},
+    MediaType, MockQrng, QrngSource, SealSession, SegmentLayout, SessionCheckpoint,
+};
+
+use crate::error::ApiError;
+use crate::handlers::seal::{mock_entropy_headers, record_mock_entropy};
+use crate::state::AppState;
+use crate::streams::StreamEntry;
+
+/// Smallest accepted segment size (4 KiB)
+const MIN_SEGMENT_SIZE: u32 = 4 * 1024;
+
+/// Largest accepted segment size (16 MiB)
+const MAX_SEGMENT_SIZE: u32 = 16 * 1024 * 1024;
+
+/// Default number of segments between checkpoints
+const DEFAULT_CHECKPOINT_EVERY: u32 = 10;
+
+/// Request to open a stream
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct StartStreamRequest {
+    /// "video" (default) or "audio"
+    #[serde(default)]
+    #[schema(example = "video")]
+    pub media_type: Option<String>,
+    /// Segment size in bytes (default 1 MiB, 4 KiB to 16 MiB)
+    #[serde(default)]
+    #[schema(example = 1048576)]
+    pub segment_size: Option<u32>,
+    /// Nominal playback duration of one segment, for time-based clip lookups
+    #[serde(default)]
+    #[schema(example = 2000)]
+    pub segment_duration_ms: Option<u32>,
+    /// Segments between signed checkpoints (default 10, 0 disables)
+    #[serde(default)]
+    #[schema(example = 10)]
+    pub checkpoint_every: Option<u32>,
+    /// Use mock QRNG for the final seal (testing only)
+    #[serde(default)]
+    pub mock: bool,
+}
+
+/// Response for a newly opened stream
+#[derive(Serialize, ToSchema)]
+pub struct StartStreamResponse {
+    /// Identifier to use in chunk and finish requests
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub stream_id: String,
+    /// Session identifier embedded in every checkpoint (hex)
+    #[schema(example = "9f86d081884c7d659a2feaa0c55ad015")]
+    pub session_id: String,
+    /// Segment size in bytes
+    #[schema(example = 1048576)]
+    pub segment_size: u32,
+    /// Segments between signed checkpoints
+    #[schema(example = 10)]
+    pub checkpoint_every: u32,
+    /// Base64-encoded ML-DSA-65 public key signing checkpoints and the final seal
+    pub public_key: String,
+}
+
+/// A signed checkpoint over the segments received so far
+#[derive(Serialize, ToSchema)]
+pub struct CheckpointResponse {
+    /// Checkpoint sequence number within the stream
+    #[schema(example = 0)]
+    pub sequence: u32,
+    /// Number of segments covered
+    #[schema(example = 10)]
+    pub segment_count: u32,
+    /// Merkle root over the covered segments (hex)
+    pub root: String,
+    /// Signing time in milliseconds since Unix epoch
+    #[schema(example = 1704067200000_u64)]
+    pub timestamp: u64,
+    /// Base64-encoded CBOR checkpoint, verifiable on its own
+    pub checkpoint_data: String,
+}
+
+impl CheckpointResponse {
+    fn from_checkpoint(checkpoint: &SessionCheckpoint) -> Result<Self, ApiError> {
+        Ok(Self {
+            sequence: checkpoint.sequence,
+            segment_count: checkpoint.segment_count,
+            root: hex::encode(checkpoint.root),
+            timestamp: checkpoint.timestamp,
+            checkpoint_data: BASE64.encode(checkpoint.to_cbor()?),
+        })
+    }
+}
+
+/// Response for an uploaded chunk
+#[derive(Serialize, ToSchema)]
+pub struct StreamChunkResponse {
+    /// Total bytes received on this stream
+    #[schema(example = 5242880_u64)]
+    pub bytes_received: u64,
+    /// Complete segments received on this stream
+    #[schema(example = 5)]
+    pub segments_completed: u32,
+    /// Checkpoints that became due with this chunk
+    pub checkpoints: Vec<CheckpointResponse>,
+}
+
+/// Response for a closed stream
+#[derive(Serialize, ToSchema)]
+pub struct FinishStreamResponse {
+    /// Unique identifier for this seal
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub seal_id: String,
+    /// Base64-encoded CBOR seal data
+    #[schema(example = "omZzZWFsX2...")]
+    pub seal_data: String,
+    /// Capture timestamp in milliseconds since Unix epoch
+    #[schema(example = 1704067200000_u64)]
+    pub timestamp: u64,
+    /// Number of segments in the seal
+    #[schema(example = 42)]
+    pub segment_count: u32,
+    /// Total bytes sealed
+    #[schema(example = 44040192_u64)]
+    pub bytes_received: u64,
+    /// QRNG source used for entropy
+    #[schema(example = "lfd")]
+    pub qrng_source: String,
+}
+
+/// Open a live stream for incremental sealing
+///
+/// The stream is held in memory (segment hashes only) until it is finished
+/// or left idle for 10 minutes.
+#[utoipa::path(
+    post,
+    path = "/api/v1/streams",
+    tag = "Sealing",
+    request_body = StartStreamRequest,
+    responses(
+        (status = 201, description = "Stream opened", body = StartStreamResponse),
+        (status = 400, description = "Invalid parameters or mock QRNG not allowed"),
+        (status = 503, description = "Too many open streams")
+    )
+)]
+pub async fn start_stream_handler(
+    State(state): State<AppState>,
+    Json(request): Json<StartStreamRequest>,
+) -> Result<(StatusCode, Json<StartStreamResponse>), ApiError> {
+    if request.mock && !state.allow_mock_qrng {
+        return Err(ApiError::bad_request(
+            "Mock QRNG is not allowed in this environment. Set ALLOW_MOCK_QRNG=true to enable.",
+        ));
+    }
+
+    let media_type = match request.media_type.as_deref().map(str::to_lowercase) {
+        None => MediaType::Video,
+        Some(value) => match value.as_str() {
+            "video" => MediaType::Video,
+            "audio" => MediaType::Audio,
+            other => {
+                return Err(ApiError::bad_request(format!(
+                    "Unsupported stream media type '{}' (expected video or audio)",
+                    other
+                )))
+            }
+        },
+    };
+
+    let segment_size = request.segment_size.unwrap_or(DEFAULT_SEGMENT_SIZE);
+    if !(MIN_SEGMENT_SIZE..=MAX_SEGMENT_SIZE).contains(&segment_size) {
+        return Err(ApiError::bad_request(format!(
+            "segment_size must be between {} and {} bytes",
+            MIN_SEGMENT_SIZE, MAX_SEGMENT_SIZE
+        )));
+    }
+
+    let mut layout = SegmentLayout::new(segment_size);
+    if let Some(duration_ms) = request.segment_duration_ms {
+        layout = layout.with_duration_ms(duration_ms);
+    }
+    let checkpoint_every = request.checkpoint_every.unwrap_or(DEFAULT_CHECKPOINT_EVERY);
+
+    // One key per stream: it signs every checkpoint and the final seal
+    let (public_key, secret_key) = generate_keypair();
+    let session = SealSession::new(media_type, layout, public_key, secret_key)?
+        .with_checkpoint_every(checkpoint_every);
+    let session_id = hex::encode(session.session_id());
+
+    let stream_id = state
+        .streams
+        .insert(StreamEntry::new(session, request.mock))
+        .ok_or_else(|| ApiError::service_unavailable("Too many open streams, try again later"))?;
+
+    tracing::info!(stream_id = %stream_id, segment_size, "Stream opened");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(StartStreamResponse {
+            stream_id: stream_id.to_string(),
+            session_id,
+            segment_size,
+            checkpoint_every,
+            public_key: BASE64.encode(public_key.as_bytes()),
+        }),
+    ))
+}
+
+/// Append a chunk of media to an open stream
+///
+/// The raw request body is appended in order. Any checkpoints completed by
+/// the chunk are returned so the broadcaster can publish them right away.
+#[utoipa::path(
+    post,
+    path = "/api/v1/streams/{stream_id}/chunks",
+    tag = "Sealing",
+    params(("stream_id" = String, Path, description = "Stream ID (UUID)")),
+    request_body(content = Vec<u8>, content_type = "application/octet-stream"),
+    responses(
+        (status = 200, description = "Chunk appended", body = StreamChunkResponse),
+        (status = 400, description = "Empty chunk"),
+        (status = 404, description = "Stream not found or expired")
+    )
+)]
+pub async fn stream_chunk_handler(
+    State(state): State<AppState>,
+    Path(stream_id): Path<Uuid>,
+    body: Bytes,
+) -> Result<Json<StreamChunkResponse>, ApiError> {
+    if body.is_empty() {
+        return Err(ApiError::bad_request("Chunk is empty"));
+    }
+
+    let entry = state
+        .streams
+        .get(&stream_id)
+        .ok_or_else(|| ApiError::not_found("Stream not found or expired"))?;
+    let mut entry = entry.lock().await;
+
+    let checkpoints = entry.session.append(&body)?;
+    entry.last_activity = Instant::now();
+
+    let checkpoints = checkpoints
+        .iter()
+        .map(CheckpointResponse::from_checkpoint)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Json(StreamChunkResponse {
+        bytes_received: entry.session.bytes_received(),
+        segments_completed: entry.session.segments_completed(),
+        checkpoints,
+    }))
+}
+
+/// Close a stream and seal everything received
+///
+/// Returns a segmented seal over the full recording. If entropy cannot be
+/// fetched the stream stays open, so the request can be retried.
+#[utoipa::path(
+    post,
+    path = "/api/v1/streams/{stream_id}/finish",
+    tag = "Sealing",
+    params(("stream_id" = String, Path, description = "Stream ID (UUID)")),
+    responses(
+        (status = 201, description = "Seal created", body = FinishStreamResponse),
+        (status = 400, description = "Nothing was streamed"),
+        (status = 404, description = "Stream not found or expired"),
+        (status = 503, description = "QRNG service unavailable")
+    )
+)]
+pub async fn finish_stream_handler(
+    State(state): State<AppState>,
+    Path(stream_id): Path<Uuid>,
+) -> Result<(StatusCode, HeaderMap, Json<FinishStreamResponse>), ApiError> {
+    let entry = state
+        .streams
+        .get(&stream_id)
+        .ok_or_else(|| ApiError::not_found("Stream not found or expired"))?;
+    let mut entry = entry.lock().await;
+    entry.last_activity = Instant::now();
+
+    if entry.session.bytes_received() == 0 {
+        return Err(ApiError::bad_request("Nothing was streamed yet"));
+    }
+
+    let seal = if entry.use_mock {
+        entry.session.finish(&MockQrng::default()).await?
+    } else {
+        let provider = QrngProviderFactory::create(QrngProviderConfig::Auto).map_err(|e| {
+            tracing::error!("QRNG provider creation failed: {}", e);
+            ApiError::service_unavailable("QRNG service unavailable")
+        })?;
+        entry.session.finish(&*provider).await.map_err(|e| {
+            tracing::error!("QRNG entropy fetch failed: {}", e);
+            ApiError::service_unavailable("QRNG service unavailable")
+        })?
+    };
+    let seal_cbor = seal.to_cbor()?;
+    let bytes_received = entry.session.bytes_received();
+    drop(entry);
+
+    state.streams.remove(&stream_id);
+
+    let segment_count = seal
+        .content_hash
+        .segments
+        .as_ref()
+        .map_or(0, |segments| segments.segment_count);
+    let qrng_source = match seal.qrng_source {
+        QrngSource::LfdCloud => "lfd",
+        QrngSource::AnuCloud => "anu",
+        QrngSource::IdQuantiqueCloud => "idq",
+        QrngSource::Mock => "mock",
+        QrngSource::DeviceHardware { .. } => "hardware",
+    };
+
+    let seal_id = Uuid::new_v4();
+    tracing::info!(
+        stream_id = %stream_id,
+        seal_id = %seal_id,
+        segment_count,
+        bytes_received,
+        "Stream sealed"
+    );
+
+    let mock_entropy = seal.uses_mock_entropy();
+    if mock_entropy {
+        record_mock_entropy(&state, "stream", None, None).await;
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        mock_entropy_headers(mock_entropy),
+        Json(FinishStreamResponse {
+            seal_id: seal_id.to_string(),
+            seal_data: BASE64.encode(&seal_cbor),
+            timestamp: seal.capture_timestamp_utc,
+            segment_count,
+            bytes_received,
+            qrng_source: qrng_source.to_string(),
+        }),
+    ))
+}
@@ -0,0 +1,433 @@
+//! Offline sync handlers
+//!
+//! The mobile app seals fully offline, under seal IDs it generates itself,
+//! and reconciles with the server when connectivity returns:
+//!
+//! 1. **Push** (`POST /api/v1/sync/push`) the seals made offline. Pushes are
+//!    idempotent, so a push cut off by the network is simply sent again.
+//! 2. **Pull** (`GET /api/v1/sync/pull?cursor=`) the seals that changed on
+//!    the server since the last pull: seals made on other devices or on the
+//!    web, revocations, media deletions. Keep the returned cursor for the
+//!    next pull; pull again while `has_more` is true.
+//!
+//! Conflict rules, reported per pushed seal:
+//! - `created`: stored under the client's ID
+//! - `unchanged`: already stored under that ID (a retried push)
+//! - `duplicate`: the same seal is stored under another ID (e.g. imported
+//!   earlier); the client adopts the returned server ID
+//! - `conflict`: the ID is taken by another seal (push again under a new
+//!   ID) or the seal belongs to another account (drop it)
+//! - `rejected`: the seal is invalid or over quota; see `error`
+//!
+//! The server owns everything but the seal itself: revocations, media
+//! deletion and trust tiers always come from the pull.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::db::{SealLocation, SealRecord, SealRepository, SealUpsert};
+use crate::error::ApiError;
+use crate::handlers::import::{decode_offline_seal, finish_offline_seal, offline_seal_record};
+use crate::handlers::seal::{check_seal_quota, LocationInput};
+use crate::state::AppState;
+
+/// Most seals in one push
+pub const MAX_SYNC_PUSH_SEALS: usize = 100;
+
+/// Default and largest number of seals in one pull
+const DEFAULT_PULL_LIMIT: i64 = 100;
+const MAX_PULL_LIMIT: i64 = 500;
+
+/// Changes younger than this are left for the next pull, so that a slow
+/// transaction committing an older change time is not skipped by the cursor
+const SYNC_SETTLE_SECS: i64 = 2;
+
+/// Seals made offline
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SyncPushRequest {
+    /// Seals to store, at most 100
+    pub seals: Vec<SyncPushSeal>,
+}
+
+/// A seal made offline
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SyncPushSeal {
+    /// Seal ID generated by the client (UUID), kept by the server
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    /// Base64-encoded CBOR seal
+    #[schema(example = "omZzZWFsX2...")]
+    pub seal_data: String,
+    /// GPS location of the capture
+    #[serde(default)]
+    pub location: Option<LocationInput>,
+    /// How the media was captured: `camera` (default) or `gallery`
+    #[serde(default)]
+    #[schema(example = "camera")]
+    pub capture_source: Option<String>,
+    /// Size of the sealed media in bytes
+    #[serde(default)]
+    #[schema(example = 1024000)]
+    pub file_size: Option<u32>,
+    /// Content-Type of the sealed media
+    #[serde(default)]
+    #[schema(example = "image/jpeg")]
+    pub mime_type: Option<String>,
+}
+
+/// What happened to a pushed seal (see the conflict rules)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPushStatus {
+    /// Stored under the client's ID
+    Created,
+    /// Already stored under the client's ID
+    Unchanged,
+    /// Already stored under another ID, returned in `seal_id`
+    Duplicate,
+    /// ID taken by another seal, or seal of another account
+    Conflict,
+    /// Invalid seal or quota exceeded
+    Rejected,
+}
+
+/// Result of one pushed seal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncPushResult {
+    /// Client ID of the seal
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: Uuid,
+    /// What happened to the seal
+    pub status: SyncPushStatus,
+    /// ID of the seal on the server (the client ID unless `duplicate`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    pub seal_id: Option<Uuid>,
+    /// Why the seal was not stored (`conflict`, `rejected`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Results of a push, in request order
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncPushResponse {
+    pub results: Vec<SyncPushResult>,
+}
+
+/// Query parameters of a pull
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SyncPullQuery {
+    /// Cursor returned by the previous pull (none for the first pull)
+    pub cursor: Option<String>,
+
+    /// Most seals to return
+    #[param(default = 100, minimum = 1, maximum = 500)]
+    pub limit: Option<i64>,
+}
+
+/// A seal as synced, with the time it last changed
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncSealRecord {
+    #[serde(flatten)]
+    pub seal: SealRecord,
+    /// When the seal last changed on the server
+    #[schema(value_type = String, example = "2026-01-08T10:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Seals changed since the cursor
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncPullResponse {
+    /// Changed seals, oldest change first
+    pub seals: Vec<SyncSealRecord>,
+    /// Cursor for the next pull (the request's cursor if nothing changed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "1767866400000000_550e8400-e29b-41d4-a716-446655440000")]
+    pub cursor: Option<String>,
+    /// Whether more changes are waiting: pull again right away
+    pub has_more: bool,
+}
+
+/// Push seals made offline
+///
+/// Stores each seal under its client ID after the checks of
+/// `/api/v1/seals/import` (supported version, valid signature, mock entropy
+/// only if allowed). Pushing the same seals again is safe. Each seal gets a
+/// status: `created`, `unchanged`, `duplicate` (stored under the returned
+/// `seal_id`), `conflict` or `rejected`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/sync/push",
+    tag = "Seals",
+    request_body = SyncPushRequest,
+    responses(
+        (status = 200, description = "Result of each seal", body = SyncPushResponse),
+        (status = 400, description = "No seals, or more than 100"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn sync_push_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Json(request): Json<SyncPushRequest>,
+) -> Result<Json<SyncPushResponse>, ApiError> {
+    let seal_repo = seal_repo(&state)?;
+    let count = request.seals.len();
+    if count == 0 {
+        return Err(ApiError::bad_request("At least one seal is required"));
+    }
+    if count > MAX_SYNC_PUSH_SEALS {
+        return Err(ApiError::bad_request(format!(
+            "At most {} seals per push, got {}",
+            MAX_SYNC_PUSH_SEALS, count
+        )));
+    }
+
+    let mut results = Vec::with_capacity(count);
+    for item in request.seals {
+        let id = item.id;
+        // Invalid seals are reported per seal; server failures fail the
+        // push, which the client retries as a whole
+        let result = match push_seal(&state, seal_repo, &auth, item).await {
+            Ok(result) => result,
+            Err(e) if e.status_code().is_client_error() => SyncPushResult {
+                id,
+                status: SyncPushStatus::Rejected,
+                seal_id: None,
+                error: Some(e.client_message()),
+            },
+            Err(e) => return Err(e),
+        };
+        results.push(result);
+    }
+
+    let created = results
+        .iter()
+        .filter(|result| result.status == SyncPushStatus::Created)
+        .count();
+    tracing::info!(user_id = %auth.user.id, count, created, "Sync push");
+    Ok(Json(SyncPushResponse { results }))
+}
+
+/// Pull seals changed on the server
+///
+/// Returns the current user's seals changed since `cursor`, oldest change
+/// first: new seals (from any device) and seals revoked, reinstated or whose
+/// media was deleted. Without a cursor, all of the user's seals.
+#[utoipa::path(
+    get,
+    path = "/api/v1/sync/pull",
+    tag = "Seals",
+    params(SyncPullQuery),
+    responses(
+        (status = 200, description = "Seals changed since the cursor", body = SyncPullResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn sync_pull_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Query(query): Query<SyncPullQuery>,
+) -> Result<Json<SyncPullResponse>, ApiError> {
+    let seal_repo = seal_repo(&state)?;
+    let since = query.cursor.as_deref().map(decode_cursor).transpose()?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PULL_LIMIT)
+        .clamp(1, MAX_PULL_LIMIT);
+
+    let mut seals = seal_repo
+        .changed_since(&auth.tenant(), since, SYNC_SETTLE_SECS, limit + 1)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to pull changed seals");
+            ApiError::internal("A database error occurred")
+        })?;
+    let has_more = seals.len() as i64 > limit;
+    seals.truncate(limit as usize);
+
+    let cursor = seals
+        .last()
+        .map(|last| encode_cursor(last.updated_at, last.seal.id))
+        .or(query.cursor);
+    let seals = seals
+        .into_iter()
+        .map(|synced| SyncSealRecord {
+            seal: SealRecord::from(synced.seal),
+            updated_at: synced.updated_at,
+        })
+        .collect();
+
+    Ok(Json(SyncPullResponse {
+        seals,
+        cursor,
+        has_more,
+    }))
+}
+
+/// Store one pushed seal, applying the conflict rules.
+async fn push_seal(
+    state: &AppState,
+    seal_repo: &SealRepository,
+    auth: &AuthenticatedUser,
+    item: SyncPushSeal,
+) -> Result<SyncPushResult, ApiError> {
+    let user_id = auth.user.id;
+    let capture_source = capture_source(item.capture_source.as_deref())?;
+    let (seal_cbor, seal) = decode_offline_seal(state, &item.seal_data).await?;
+    let result = |status, seal_id, error: Option<&str>| SyncPushResult {
+        id: item.id,
+        status,
+        seal_id,
+        error: error.map(str::to_string),
+    };
+
+    let db_error = |e: sqlx::Error| {
+        tracing::error!(error = %e, "Failed to store pushed seal");
+        ApiError::internal("A database error occurred")
+    };
+    let content_hash = hex::encode(seal.content_hash.crypto_hash);
+    let existing = seal_repo
+        .find_owner_by_signature(&content_hash, &seal.signature)
+        .await
+        .map_err(db_error)?;
+    match existing {
+        Some((seal_id, Some(owner_id))) if owner_id == user_id => {
+            let status = if seal_id == item.id {
+                SyncPushStatus::Unchanged
+            } else {
+                SyncPushStatus::Duplicate
+            };
+            return Ok(result(status, Some(seal_id), None));
+        }
+        Some(_) => {
+            return Ok(result(
+                SyncPushStatus::Conflict,
+                None,
+                Some("Seal stored by another account"),
+            ));
+        }
+        None => {}
+    }
+
+    check_seal_quota(state, &auth.tenant()).await?;
+
+    let location = item.location.map(|location| SealLocation {
+        lat: location.lat,
+        lng: location.lng,
+        altitude: location.altitude,
+    });
+    let perceptual_hash = seal.content_hash.perceptual_hash.clone();
+    let mut record = offline_seal_record(
+        state,
+        auth,
+        &seal,
+        perceptual_hash.clone(),
+        location,
+        capture_source,
+    );
+    record.file_size = item.file_size.and_then(|size| i32::try_from(size).ok());
+    record.mime_type = item.mime_type;
+
+    match seal_repo
+        .upsert_with_id(item.id, record)
+        .await
+        .map_err(db_error)?
+    {
+        SealUpsert::Created => {
+            finish_offline_seal(
+                state,
+                item.id,
+                user_id,
+                &seal,
+                seal_cbor,
+                perceptual_hash,
+                "sync",
+            )
+            .await;
+            Ok(result(SyncPushStatus::Created, Some(item.id), None))
+        }
+        SealUpsert::Unchanged => Ok(result(SyncPushStatus::Unchanged, Some(item.id), None)),
+        SealUpsert::Conflict => Ok(result(
+            SyncPushStatus::Conflict,
+            None,
+            Some("ID used by another seal, push the seal again under a new ID"),
+        )),
+    }
+}
+
+fn seal_repo(state: &AppState) -> Result<&SealRepository, ApiError> {
+    state
+        .seal_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+/// `capture_source` of a pushed seal: `camera` (default) or `gallery`
+fn capture_source(capture_source: Option<&str>) -> Result<&'static str, ApiError> {
+    match capture_source {
+        None | Some("camera") => Ok("camera"),
+        Some("gallery") => Ok("gallery"),
+        Some(other) => Err(ApiError::bad_request(format!(
+            "Invalid capture_source '{}': expected camera or gallery",
+            other
+        ))),
+    }
+}
+
+/// Pull cursor: change time (Unix microseconds) and ID of the last seal
+fn encode_cursor(updated_at: DateTime<Utc>, id: Uuid) -> String {
+    format!("{}_{}", updated_at.timestamp_micros(), id)
+}
+
+fn decode_cursor(cursor: &str) -> Result<(DateTime<Utc>, Uuid), ApiError> {
+    cursor
+        .split_once('_')
+        .and_then(|(micros, id)| {
+            let updated_at = DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+            Some((updated_at, id.parse().ok()?))
+        })
+        .ok_or_else(|| ApiError::bad_request("Invalid cursor"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let updated_at = DateTime::from_timestamp_micros(1_767_866_400_123_456).unwrap();
+        let id = Uuid::new_v4();
+
+        let cursor = encode_cursor(updated_at, id);
+        assert_eq!(decode_cursor(&cursor).unwrap(), (updated_at, id));
+
+        assert!(decode_cursor("").is_err());
+        assert!(decode_cursor("1767866400123456").is_err());
+        assert!(decode_cursor("yesterday_550e8400-e29b-41d4-a716-446655440000").is_err());
+        assert!(decode_cursor("1767866400123456_not-a-uuid").is_err());
+    }
+
+    #[test]
+    fn test_capture_source() {
+        assert_eq!(capture_source(None).unwrap(), "camera");
+        assert_eq!(capture_source(Some("gallery")).unwrap(), "gallery");
+        assert!(capture_source(Some("screenshot")).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+//! Seal tag handlers
+//!
+//! Lets users tag their seals, list their tags with how many seals carry
+//! each, and rename or delete a tag across all their seals. Tags are trimmed
+//! and lowercased, so `Ukraine` and `ukraine ` are the same tag; seal
+//! listings filter on them with `?tag=`.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::db::{SealTagRepository, TagCount};
+use crate::error::ApiError;
+use crate::handlers::share::require_own_seal;
+use crate::state::AppState;
+
+/// Longest tag, in characters
+pub const MAX_TAG_CHARS: usize = 50;
+
+/// Most tags on one seal
+pub const MAX_TAGS_PER_SEAL: usize = 20;
+
+/// Request to replace the tags of a seal
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetSealTagsRequest {
+    /// New tags (an empty list removes them all)
+    #[schema(example = json!(["ukraine", "kharkiv", "frontline"]))]
+    pub tags: Vec<String>,
+}
+
+/// Tags of a seal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SealTagsResponse {
+    /// Tags, alphabetically
+    #[schema(example = json!(["frontline", "kharkiv", "ukraine"]))]
+    pub tags: Vec<String>,
+}
+
+/// Tags of the current user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListTagsResponse {
+    /// Tags, most used first
+    pub tags: Vec<TagCount>,
+}
+
+/// Request to rename a tag
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RenameTagRequest {
+    /// New tag; seals carrying both tags keep one
+    #[schema(example = "ukraine-2025")]
+    pub tag: String,
+}
+
+/// List the current user's tags
+#[utoipa::path(
+    get,
+    path = "/api/v1/tags",
+    tag = "Seals",
+    responses(
+        (status = 200, description = "Tags with their number of seals", body = ListTagsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn list_tags_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<Json<ListTagsResponse>, ApiError> {
+    let tags = tag_repo(&state)?
+        .list_for_user(&auth.tenant())
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to list tags");
+            ApiError::internal("A database error occurred")
+        })?;
+
+    Ok(Json(ListTagsResponse { tags }))
+}
+
+/// Rename a tag
+///
+/// Renames the tag on all of the current user's seals.
+#[utoipa::path(
+    put,
+    path = "/api/v1/tags/{tag}",
+    tag = "Seals",
+    params(
+        ("tag" = String, Path, description = "Tag to rename")
+    ),
+    request_body = RenameTagRequest,
+    responses(
+        (status = 204, description = "Tag renamed"),
+        (status = 400, description = "Invalid tag"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No seal carries the tag"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn rename_tag_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(tag): Path<String>,
+    Json(request): Json<RenameTagRequest>,
+) -> Result<StatusCode, ApiError> {
+    let tag = normalize_tag(&tag)?;
+    let new_tag = normalize_tag(&request.tag)?;
+
+    let seals = tag_repo(&state)?
+        .rename(&auth.tenant(), &tag, &new_tag)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to rename tag");
+            ApiError::internal("A database error occurred")
+        })?;
+    if seals == 0 {
+        return Err(ApiError::not_found("Tag not found"));
+    }
+
+    tracing::info!(user_id = %auth.user.id, seals, "Tag renamed");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Delete a tag
+///
+/// Removes the tag from all of the current user's seals; the seals stay.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tags/{tag}",
+    tag = "Seals",
+    params(
+        ("tag" = String, Path, description = "Tag to delete")
+    ),
+    responses(
+        (status = 204, description = "Tag deleted"),
+        (status = 400, description = "Invalid tag"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No seal carries the tag"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn delete_tag_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(tag): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let tag = normalize_tag(&tag)?;
+
+    let seals = tag_repo(&state)?
+        .delete(&auth.tenant(), &tag)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to delete tag");
+            ApiError::internal("A database error occurred")
+        })?;
+    if seals == 0 {
+        return Err(ApiError::not_found("Tag not found"));
+    }
+
+    tracing::info!(user_id = %auth.user.id, seals, "Tag deleted");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get the tags of a seal
+#[utoipa::path(
+    get,
+    path = "/api/v1/seals/{seal_id}/tags",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Tags of the seal", body = SealTagsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn get_seal_tags_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(seal_id): Path<Uuid>,
+) -> Result<Json<SealTagsResponse>, ApiError> {
+    let tag_repo = tag_repo(&state)?;
+    let tenant = auth.tenant();
+    require_own_seal(&state, seal_id, &tenant).await?;
+
+    let tags = tag_repo.tags_of(seal_id, &tenant).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to get seal tags");
+        ApiError::internal("A database error occurred")
+    })?;
+
+    Ok(Json(SealTagsResponse { tags }))
+}
+
+/// Replace the tags of a seal
+#[utoipa::path(
+    put,
+    path = "/api/v1/seals/{seal_id}/tags",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)")
+    ),
+    request_body = SetSealTagsRequest,
+    responses(
+        (status = 200, description = "Tags of the seal", body = SealTagsResponse),
+        (status = 400, description = "Invalid tag or too many tags"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn set_seal_tags_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(seal_id): Path<Uuid>,
+    Json(request): Json<SetSealTagsRequest>,
+) -> Result<Json<SealTagsResponse>, ApiError> {
+    let tags = normalize_tags(&request.tags)?;
+    let tag_repo = tag_repo(&state)?;
+    let tenant = auth.tenant();
+    require_own_seal(&state, seal_id, &tenant).await?;
+
+    tag_repo
+        .set_tags(seal_id, &tenant, &tags)
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to set seal tags");
+            ApiError::internal("A database error occurred")
+        })?;
+
+    Ok(Json(SealTagsResponse { tags }))
+}
+
+fn tag_repo(state: &AppState) -> Result<&SealTagRepository, ApiError> {
+    state
+        .tag_repo
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))
+}
+
+/// Tag as stored: trimmed and lowercased, 1 to [`MAX_TAG_CHARS`]
+/// characters without control characters.
+fn normalize_tag(tag: &str) -> Result<String, ApiError> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() || tag.chars().count() > MAX_TAG_CHARS || tag.chars().any(char::is_control) {
+        return Err(ApiError::bad_request(format!(
+            "Tags must be 1 to {} characters",
+            MAX_TAG_CHARS
+        )));
+    }
+    Ok(tag)
+}
+
+/// Normalized, deduplicated and sorted tags, or 400 past
+/// [`MAX_TAGS_PER_SEAL`].
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>, ApiError> {
+    let mut normalized = tags
+        .iter()
+        .map(|tag| normalize_tag(tag))
+        .collect::<Result<Vec<_>, _>>()?;
+    normalized.sort();
+    normalized.dedup();
+    if normalized.len() > MAX_TAGS_PER_SEAL {
+        return Err(ApiError::bad_request(format!(
+            "A seal can have at most {} tags",
+            MAX_TAGS_PER_SEAL
+        )));
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tags() {
+        assert_eq!(normalize_tag("  Ukraine ").unwrap(), "ukraine");
+        assert_eq!(normalize_tag("Élection 2025").unwrap(), "élection 2025");
+        assert!(normalize_tag(" ").is_err());
+        assert!(normalize_tag("a\nb").is_err());
+        assert!(normalize_tag(&"x".repeat(MAX_TAG_CHARS + 1)).is_err());
+
+        let tags = ["Kharkiv", "ukraine", "UKRAINE "].map(String::from);
+        assert_eq!(normalize_tags(&tags).unwrap(), vec!["kharkiv", "ukraine"]);
+        assert!(normalize_tags(&[]).unwrap().is_empty());
+
+        let too_many: Vec<String> = (0..=MAX_TAGS_PER_SEAL).map(|i| i.to_string()).collect();
+        assert!(normalize_tags(&too_many).is_err());
+    }
+}
@@ -0,0 +1,412 @@
+//! Resumable upload handlers
+//!
+//! A tus-style protocol for large files on unreliable networks: create an
+//! upload with its total size, send the bytes in any number of PATCH requests
+//! (each starting at the offset the server acknowledged last), then finalize
+//! to seal the assembled file. After a dropped connection, a HEAD request
+//! tells the client where to resume.
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncReadExt;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use veritas_core::MediaType;
+
+use crate::auth::{AuthenticatedUser, OptionalAuth};
+use crate::db::UploadSession;
+use crate::error::ApiError;
+use crate::handlers::seal::{
+    parse_capture_nonce, requested_qrng_provider, seal_file, unix_millis, LivenessInput,
+    LocationInput, SealOptions, SealResponse,
+};
+use crate::multipart::{detect_mime, FileField, SpooledFile, SPOOL_HEAD_SIZE};
+use crate::state::AppState;
+use crate::uploads::NewUpload;
+use crate::webauthn::DeviceAttestation;
+
+/// Offset of the next byte the server expects
+pub const UPLOAD_OFFSET: HeaderName = HeaderName::from_static("upload-offset");
+
+/// Total size of the upload
+pub const UPLOAD_LENGTH: HeaderName = HeaderName::from_static("upload-length");
+
+/// Content-Type required for PATCH chunks
+pub const OFFSET_OCTET_STREAM: &str = "application/offset+octet-stream";
+
+/// Request to create a resumable upload
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateUploadRequest {
+    /// Total size of the file in bytes
+    #[schema(example = 209715200_u64)]
+    pub upload_length: u64,
+    /// "image", "video", "audio", "document", "generic", or a MIME type
+    /// (default: detected from the file's magic bytes when finalized)
+    #[serde(default)]
+    #[schema(example = "video")]
+    pub media_type: Option<String>,
+    /// Content-Type of the file
+    #[serde(default)]
+    #[schema(example = "video/mp4")]
+    pub content_type: Option<String>,
+    /// Original file name
+    #[serde(default)]
+    #[schema(example = "interview.mp4")]
+    pub file_name: Option<String>,
+}
+
+/// State of a resumable upload
+#[derive(Serialize, ToSchema)]
+pub struct UploadResponse {
+    /// Identifier to use in chunk and finalize requests
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub upload_id: String,
+    /// Bytes received so far; the next chunk must start here
+    #[schema(example = 0)]
+    pub upload_offset: u64,
+    /// Total size of the file in bytes
+    #[schema(example = 209715200_u64)]
+    pub upload_length: u64,
+    /// When the upload is purged if no further chunk arrives (RFC 3339)
+    #[schema(example = "2026-01-09T10:00:00Z")]
+    pub expires_at: String,
+}
+
+impl From<&UploadSession> for UploadResponse {
+    fn from(session: &UploadSession) -> Self {
+        Self {
+            upload_id: session.id.to_string(),
+            upload_offset: session.upload_offset as u64,
+            upload_length: session.upload_length as u64,
+            expires_at: session.expires_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Sealing options applied when an upload is finalized
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct FinalizeUploadRequest {
+    /// Use mock QRNG instead of a real quantum source (testing only)
+    #[serde(default)]
+    pub mock: bool,
+    /// QRNG provider to use ("auto" by default), from the server allowlist
+    #[serde(default)]
+    #[schema(example = "idq")]
+    pub qrng_provider: Option<String>,
+    /// Embed a C2PA manifest in the response when possible (default: true)
+    #[serde(default)]
+    pub embed_c2pa: Option<bool>,
+    /// Return a copy of the image watermarked with the seal ID (default: false)
+    #[serde(default)]
+    pub watermark: bool,
+    /// GPS location recorded with the seal
+    #[serde(default)]
+    pub location: Option<LocationInput>,
+    /// WebAuthn device attestation (must be fresh at finalization)
+    #[serde(default)]
+    pub device_attestation: Option<DeviceAttestation>,
+    /// Hex nonce from a trusted capture session, issued to the attestation's credential
+    #[serde(default)]
+    pub capture_nonce: Option<String>,
+    /// Answer to the capture session's liveness challenge (requires `capture_nonce`)
+    #[serde(default)]
+    pub liveness: Option<LivenessInput>,
+    /// Return the existing seal for byte-identical content instead of resealing
+    #[serde(default)]
+    pub dedupe: bool,
+    /// Start of the seal's validity window
+    #[serde(default)]
+    pub not_before: Option<DateTime<Utc>>,
+    /// End of the seal's validity window; the seal does not verify afterwards
+    #[serde(default)]
+    pub not_after: Option<DateTime<Utc>>,
+}
+
+/// Load an unexpired upload, hiding uploads owned by another user.
+async fn load_upload(
+    state: &AppState,
+    upload_id: Uuid,
+    auth: Option<&AuthenticatedUser>,
+) -> Result<UploadSession, ApiError> {
+    let session = state
+        .uploads
+        .get(upload_id)
+        .await?
+        .filter(|session| {
+            session.user_id.is_none() || session.user_id == auth.map(|auth| auth.user.id)
+        })
+        .ok_or_else(|| ApiError::not_found("Upload not found or expired"))?;
+    Ok(session)
+}
+
+fn offset_headers(session: &UploadSession) -> [(HeaderName, HeaderValue); 3] {
+    [
+        (UPLOAD_OFFSET, HeaderValue::from(session.upload_offset)),
+        (UPLOAD_LENGTH, HeaderValue::from(session.upload_length)),
+        (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+    ]
+}
+
+/// Create a resumable upload
+///
+/// Declares the file's total size (and optionally its type) up front, so
+/// size and Content-Type limits are enforced before any byte is sent.
+/// Resumable uploads may be up to `MAX_RESUMABLE_UPLOAD_MB` (default 500 MB).
+#[utoipa::path(
+    post,
+    path = "/api/v1/uploads",
+    tag = "Sealing",
+    request_body = CreateUploadRequest,
+    responses(
+        (status = 201, description = "Upload created", body = UploadResponse),
+        (status = 400, description = "Invalid upload length or media type"),
+        (status = 413, description = "Upload exceeds the size limit for its media type"),
+        (status = 415, description = "Content-Type not allowed by the upload policy")
+    )
+)]
+pub async fn create_upload_handler(
+    State(state): State<AppState>,
+    OptionalAuth(auth): OptionalAuth,
+    Json(request): Json<CreateUploadRequest>,
+) -> Result<Response, ApiError> {
+    if request.upload_length == 0 {
+        return Err(ApiError::bad_request("upload_length must be positive"));
+    }
+
+    let policy = state.upload_policy.resumable();
+    policy.check_content_type(request.content_type.as_deref())?;
+
+    let max_size = match request.media_type.as_deref() {
+        Some(value) => {
+            let media_type = MediaType::parse(value).ok_or_else(|| {
+                ApiError::bad_request(format!("Unsupported media type '{}'", value))
+            })?;
+            policy.max_size_for(&media_type)
+        }
+        None => policy.max_file_size,
+    };
+    if request.upload_length > max_size as u64 {
+        return Err(ApiError::payload_too_large(format!(
+            "Upload of {} bytes exceeds the limit of {} bytes",
+            request.upload_length, max_size
+        )));
+    }
+
+    let session = state
+        .uploads
+        .create(NewUpload {
+            user_id: auth.as_ref().map(|auth| auth.user.id),
+            upload_length: request.upload_length,
+            media_type: request.media_type,
+            content_type: request.content_type,
+            file_name: request.file_name,
+        })
+        .await?;
+
+    tracing::info!(
+        upload_id = %session.id,
+        upload_length = request.upload_length,
+        "Resumable upload created"
+    );
+
+    let location = format!("/api/v1/uploads/{}", session.id);
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, location)],
+        Json(UploadResponse::from(&session)),
+    )
+        .into_response())
+}
+
+/// Get the offset to resume an upload from
+///
+/// Returns the `Upload-Offset` and `Upload-Length` headers with an empty body.
+#[utoipa::path(
+    head,
+    path = "/api/v1/uploads/{upload_id}",
+    tag = "Sealing",
+    params(("upload_id" = String, Path, description = "Upload ID (UUID)")),
+    responses(
+        (status = 200, description = "Upload state in the Upload-Offset and Upload-Length headers"),
+        (status = 404, description = "Upload not found or expired")
+    )
+)]
+pub async fn upload_status_handler(
+    State(state): State<AppState>,
+    OptionalAuth(auth): OptionalAuth,
+    Path(upload_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let session = load_upload(&state, upload_id, auth.as_ref()).await?;
+    Ok((StatusCode::OK, offset_headers(&session)).into_response())
+}
+
+/// Append a chunk to an upload
+///
+/// The `Upload-Offset` header must equal the offset the server reports; the
+/// body (`application/offset+octet-stream`) is written from there.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/uploads/{upload_id}",
+    tag = "Sealing",
+    params(
+        ("upload_id" = String, Path, description = "Upload ID (UUID)"),
+        ("Upload-Offset" = u64, Header, description = "Offset of the first byte in the body")
+    ),
+    request_body(content = Vec<u8>, content_type = "application/offset+octet-stream"),
+    responses(
+        (status = 204, description = "Chunk stored; new offset in the Upload-Offset header"),
+        (status = 400, description = "Missing Upload-Offset header or empty chunk"),
+        (status = 404, description = "Upload not found or expired"),
+        (status = 409, description = "Upload-Offset does not match the server's offset"),
+        (status = 413, description = "Chunk exceeds the declared upload length"),
+        (status = 415, description = "Content-Type is not application/offset+octet-stream")
+    )
+)]
+pub async fn upload_chunk_handler(
+    State(state): State<AppState>,
+    OptionalAuth(auth): OptionalAuth,
+    Path(upload_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    if content_type != Some(OFFSET_OCTET_STREAM) {
+        return Err(ApiError::unsupported_media_type(format!(
+            "Chunks must be sent as {}",
+            OFFSET_OCTET_STREAM
+        )));
+    }
+    let offset: i64 = headers
+        .get(&UPLOAD_OFFSET)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| ApiError::bad_request("Missing or invalid Upload-Offset header"))?;
+    if body.is_empty() {
+        return Err(ApiError::bad_request("Chunk is empty"));
+    }
+
+    let _guard = state.uploads.lock(upload_id).await;
+    let session = load_upload(&state, upload_id, auth.as_ref()).await?;
+    if offset != session.upload_offset {
+        return Err(ApiError::conflict(format!(
+            "Upload-Offset {} does not match the current offset {}",
+            offset, session.upload_offset
+        )));
+    }
+
+    let session = state.uploads.append(&session, &body).await?;
+    Ok((StatusCode::NO_CONTENT, offset_headers(&session)).into_response())
+}
+
+/// Seal a completed upload
+///
+/// Seals the assembled file exactly like `POST /seal` (with the resumable
+/// size limit) and deletes the upload. If sealing fails, the upload is kept
+/// so the request can be retried.
+#[utoipa::path(
+    post,
+    path = "/api/v1/uploads/{upload_id}/finalize",
+    tag = "Sealing",
+    params(("upload_id" = String, Path, description = "Upload ID (UUID)")),
+    request_body = FinalizeUploadRequest,
+    responses(
+        (status = 200, description = "Existing seal returned for identical content (dedupe)", body = SealResponse),
+        (status = 201, description = "Seal created", body = SealResponse),
+        (status = 400, description = "Invalid media type or stale attestation"),
+        (status = 404, description = "Upload not found or expired"),
+        (status = 409, description = "Upload is incomplete"),
+        (status = 413, description = "File exceeds the upload size limit for its media type"),
+        (status = 429, description = "Monthly seal quota exceeded"),
+        (status = 503, description = "QRNG service unavailable")
+    )
+)]
+pub async fn finalize_upload_handler(
+    State(state): State<AppState>,
+    OptionalAuth(auth): OptionalAuth,
+    Path(upload_id): Path<Uuid>,
+    Json(request): Json<FinalizeUploadRequest>,
+) -> Result<(StatusCode, HeaderMap, Json<SealResponse>), ApiError> {
+    let _guard = state.uploads.lock(upload_id).await;
+    let session = load_upload(&state, upload_id, auth.as_ref()).await?;
+    if !session.is_complete() {
+        return Err(ApiError::conflict(format!(
+            "Upload is incomplete: {} of {} bytes received",
+            session.upload_offset, session.upload_length
+        )));
+    }
+
+    // Keep only the head in memory for format detection; the file is hashed from disk
+    let path = state.uploads.data_path(&upload_id);
+    let mut head = Vec::new();
+    tokio::fs::File::open(&path)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to open upload: {}", e)))?
+        .take(SPOOL_HEAD_SIZE as u64)
+        .read_to_end(&mut head)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to read upload: {}", e)))?;
+
+    let file = FileField {
+        detected_mime: detect_mime(&head),
+        data: head,
+        spooled: Some(SpooledFile::from_path(path, session.upload_length as usize)),
+        content_type: session.content_type.clone(),
+        file_name: session.file_name.clone(),
+    };
+    let options = SealOptions {
+        declared_media_type: session.media_type.as_deref().and_then(MediaType::parse),
+        qrng_provider: requested_qrng_provider(request.mock, request.qrng_provider.as_deref())?,
+        embed_c2pa: request.embed_c2pa.unwrap_or(true),
+        watermark: request.watermark,
+        device_attestation: request.device_attestation,
+        capture_nonce: parse_capture_nonce(request.capture_nonce.as_deref())?,
+        liveness: request.liveness,
+        location: request.location,
+        dedupe: request.dedupe,
+        not_before: request.not_before.map(unix_millis).transpose()?,
+        not_after: request.not_after.map(unix_millis).transpose()?,
+    };
+
+    let policy = state.upload_policy.resumable();
+    let response = seal_file(&state, &policy, auth, &file, options).await?;
+
+    state.uploads.remove(upload_id).await?;
+    tracing::info!(
+        upload_id = %upload_id,
+        seal_id = %response.seal_id,
+        "Resumable upload sealed"
+    );
+
+    Ok((response.status(), response.headers(), Json(response)))
+}
+
+/// Abandon an upload and delete its data
+#[utoipa::path(
+    delete,
+    path = "/api/v1/uploads/{upload_id}",
+    tag = "Sealing",
+    params(("upload_id" = String, Path, description = "Upload ID (UUID)")),
+    responses(
+        (status = 204, description = "Upload deleted"),
+        (status = 404, description = "Upload not found or expired")
+    )
+)]
+pub async fn delete_upload_handler(
+    State(state): State<AppState>,
+    OptionalAuth(auth): OptionalAuth,
+    Path(upload_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let _guard = state.uploads.lock(upload_id).await;
+    load_upload(&state, upload_id, auth.as_ref()).await?;
+    state.uploads.remove(upload_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
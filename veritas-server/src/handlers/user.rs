@@ -3,11 +3,12 @@
 //! Handles user profile synchronization from Clerk authentication.
 
 use axum::{extract::State, Json};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::auth::{AuthenticatedUser, JwtClaims};
-use crate::db::{CreateUser, UserResponse};
+use crate::db::{CreateUser, SealUsage, ThirdPartyActivity, UserResponse};
 use crate::error::ApiError;
 use crate::handlers::AppState;
 
@@ -195,6 +196,17 @@ pub async fn delete_user_handler(
             "User account soft deleted (GDPR)"
         );
 
+        // Self-hosted accounts: drop the password hash and free the email
+        if let Some(credential_repo) = &state.credential_repo {
+            if let Err(e) = credential_repo.delete_for_user(auth.user.id).await {
+                tracing::warn!(
+                    user_id = %auth.user.id,
+                    error = %e,
+                    "Failed to delete local credentials"
+                );
+            }
+        }
+
         Ok(Json(DeleteUserResponse {
             success: true,
             message: "Account deleted successfully. Cryptographic seals are preserved for verification integrity.".to_string(),
@@ -203,3 +215,169 @@ pub async fn delete_user_handler(
         Err(ApiError::internal("Failed to delete user"))
     }
 }
+
+/// Seals created by the user, by period
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SealCounts {
+    pub total: i64,
+    pub last_24h: i64,
+    pub last_7_days: i64,
+    pub last_30_days: i64,
+    /// Since the start of the calendar month (UTC)
+    pub this_month: i64,
+}
+
+/// Size of the files the user sealed
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StorageUsage {
+    /// All sealed files, in bytes
+    #[schema(example = 52428800_i64)]
+    pub sealed_bytes: i64,
+    /// Sealed files whose media has not been deleted, in bytes
+    #[schema(example = 41943040_i64)]
+    pub media_bytes: i64,
+}
+
+/// Monthly seal quota consumption
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuotaUsage {
+    /// Seals allowed per calendar month (absent = unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// Seals created this month
+    pub used: i64,
+    /// Seals left this month (absent = unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u64>,
+    /// When the quota resets (start of next month, UTC)
+    #[schema(value_type = String, example = "2026-02-01T00:00:00Z")]
+    pub resets_at: DateTime<Utc>,
+}
+
+/// Usage statistics of the current user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserStatsResponse {
+    /// Seals created, by period
+    pub seals: SealCounts,
+    /// Size of the sealed files
+    pub storage: StorageUsage,
+    /// Verifications and share views of the user's seals by others
+    pub third_party: ThirdPartyActivity,
+    /// Monthly seal quota consumption
+    pub quota: QuotaUsage,
+}
+
+/// Get current user usage statistics
+///
+/// Seal counts by period, size of the sealed files, verifications of the
+/// user's seals by third parties (from the audit log) and monthly quota
+/// consumption, for the account dashboard.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/stats",
+    tag = "Users",
+    responses(
+        (status = 200, description = "Usage statistics", body = UserStatsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn get_current_user_stats_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<Json<UserStatsResponse>, ApiError> {
+    let seal_repo = state
+        .seal_repo
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Database not configured"))?;
+
+    let usage = seal_repo.usage_for_user(auth.user.id).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to load seal usage");
+        ApiError::internal("A database error occurred")
+    })?;
+
+    let third_party = match &state.audit_repo {
+        Some(audit_repo) => audit_repo
+            .third_party_activity(auth.user.id)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "Failed to load third-party activity");
+                ApiError::internal("A database error occurred")
+            })?,
+        None => ThirdPartyActivity::default(),
+    };
+
+    Ok(Json(build_user_stats(
+        usage,
+        third_party,
+        state.monthly_seal_quota,
+        Utc::now(),
+    )))
+}
+
+fn build_user_stats(
+    usage: SealUsage,
+    third_party: ThirdPartyActivity,
+    quota: Option<u64>,
+    now: DateTime<Utc>,
+) -> UserStatsResponse {
+    let (year, month) = match now.month() {
+        12 => (now.year() + 1, 1),
+        month => (now.year(), month + 1),
+    };
+    let resets_at = Utc
+        .with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now);
+
+    UserStatsResponse {
+        seals: SealCounts {
+            total: usage.total,
+            last_24h: usage.last_24h,
+            last_7_days: usage.last_7_days,
+            last_30_days: usage.last_30_days,
+            this_month: usage.this_month,
+        },
+        storage: StorageUsage {
+            sealed_bytes: usage.sealed_bytes,
+            media_bytes: usage.media_bytes,
+        },
+        third_party,
+        quota: QuotaUsage {
+            limit: quota,
+            used: usage.this_month,
+            remaining: quota.map(|quota| quota.saturating_sub(usage.this_month.max(0) as u64)),
+            resets_at,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_user_stats_quota() {
+        let usage = SealUsage {
+            this_month: 12,
+            ..SealUsage::default()
+        };
+        let now = Utc.with_ymd_and_hms(2026, 12, 15, 10, 0, 0).unwrap();
+
+        let stats = build_user_stats(usage.clone(), ThirdPartyActivity::default(), Some(10), now);
+        assert_eq!(stats.quota.used, 12);
+        assert_eq!(stats.quota.remaining, Some(0));
+        assert_eq!(
+            stats.quota.resets_at,
+            Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()
+        );
+
+        let unlimited = build_user_stats(usage, ThirdPartyActivity::default(), None, now);
+        let json = serde_json::to_value(&unlimited).unwrap();
+        assert!(json["quota"].get("limit").is_none());
+        assert!(json["quota"].get("remaining").is_none());
+    }
+}
@@ -0,0 +1,124 @@
+//! Verification trends handler
+//!
+//! Shows owners how often their seal was verified by others, per day, and
+//! from which sites and countries: evidence of how far the content travels.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::db::{DailyVerifications, OriginCount};
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Default trend window in days
+const DEFAULT_TREND_DAYS: i32 = 30;
+
+/// Longest trend window in days
+const MAX_TREND_DAYS: i32 = 365;
+
+/// Sites and countries listed per seal
+const TOP_ORIGINS_LIMIT: i64 = 10;
+
+/// Query parameters for a seal's verification trends
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct VerificationTrendsQuery {
+    /// Number of days covered by the trends
+    #[param(default = 30, minimum = 1, maximum = 365)]
+    pub days: Option<i32>,
+}
+
+/// Verifications of a seal by anyone but its owner
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationTrendsResponse {
+    /// The seal
+    #[schema(value_type = String, example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub seal_id: Uuid,
+    /// Verifications since the seal was created
+    #[schema(example = 42)]
+    pub verification_count: i64,
+    /// Number of days covered by `per_day`, `sites` and `countries`
+    pub days: i32,
+    /// Verifications per day, oldest first
+    pub per_day: Vec<DailyVerifications>,
+    /// Sites the seal was verified from, most verifications first (top 10)
+    pub sites: Vec<OriginCount>,
+    /// Countries the seal was verified from, most verifications first (top 10)
+    pub countries: Vec<OriginCount>,
+}
+
+/// Get a seal's verification trends
+///
+/// Verifications by the owner are not counted. Sites come from the
+/// `Origin`/`Referer` header of `/verify` requests and countries from the
+/// CDN; neither is known for direct API calls.
+#[utoipa::path(
+    get,
+    path = "/api/v1/seals/{seal_id}/verifications",
+    tag = "Seals",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID (UUID)"),
+        VerificationTrendsQuery
+    ),
+    responses(
+        (status = 200, description = "Verification trends of the seal", body = VerificationTrendsResponse),
+        (status = 400, description = "Invalid window"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Seal not found"),
+        (status = 503, description = "Database not available")
+    ),
+    security(
+        ("clerk_token" = [])
+    )
+)]
+pub async fn seal_verifications_handler(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(seal_id): Path<Uuid>,
+    Query(query): Query<VerificationTrendsQuery>,
+) -> Result<Json<VerificationTrendsResponse>, ApiError> {
+    let days = query.days.unwrap_or(DEFAULT_TREND_DAYS);
+    if !(1..=MAX_TREND_DAYS).contains(&days) {
+        return Err(ApiError::bad_request(format!(
+            "days must be between 1 and {}",
+            MAX_TREND_DAYS
+        )));
+    }
+
+    let (Some(seal_repo), Some(verification_repo)) = (&state.seal_repo, &state.verification_repo)
+    else {
+        return Err(ApiError::service_unavailable("Database not configured"));
+    };
+    let db_error = |e: sqlx::Error| {
+        tracing::error!(error = %e, "Failed to load verification trends");
+        ApiError::internal("A database error occurred")
+    };
+
+    let tenant = auth.tenant();
+    let seal = seal_repo
+        .find_by_id_for_user(seal_id, &tenant)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| ApiError::not_found("Seal not found"))?;
+
+    let (per_day, sites, countries) = tokio::try_join!(
+        verification_repo.daily(seal_id, &tenant, days),
+        verification_repo.top_sites(seal_id, &tenant, days, TOP_ORIGINS_LIMIT),
+        verification_repo.top_countries(seal_id, &tenant, days, TOP_ORIGINS_LIMIT),
+    )
+    .map_err(db_error)?;
+
+    Ok(Json(VerificationTrendsResponse {
+        seal_id,
+        verification_count: seal.verification_count,
+        days,
+        per_day,
+        sites,
+        countries,
+    }))
+}
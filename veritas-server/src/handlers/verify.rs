@@ -2,15 +2,33 @@
 //!
 //! Handles POST /verify requests to verify seals against content.
 
-use axum::{extract::Multipart, Json};
+use std::sync::Arc;
+
+use axum::{
+    extract::{Multipart, State},
+    http::{header, HeaderMap},
+    Json,
+};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serde::Serialize;
 use utoipa::ToSchema;
-use veritas_core::{ContentVerificationResult, VeritasSeal};
+use uuid::Uuid;
+use veritas_core::{
+    AnchorStatus, BlockchainAnchor, ContentGrade, ContentVerificationResult, KeyResolution, Locale,
+    MessageCode, MockQrngHandling, PolicyViolation, SigningKeyStatus, TamperedSegment,
+    VerificationPolicy, VeritasSeal, RECOMPRESSION_MAX_DISTANCE, REGION_CHANGE_THRESHOLD,
+};
 
+use crate::auth::OptionalAuth;
+use crate::db::{AuditEvent, Branding, VerificationOrigin, VerificationOutcome};
 use crate::error::ApiError;
+use crate::handlers::organizations::response_branding;
+use crate::handlers::AppState;
+use crate::logging;
 use crate::multipart::MultipartFields;
+use crate::notifications::{Notification, NotificationEvent};
 use crate::validation::DEFAULT_MAX_FILE_SIZE;
+use crate::verification_cache::{CachedVerification, VerificationKey};
 
 /// Response for verification
 #[derive(Serialize, ToSchema)]
@@ -18,27 +36,290 @@ pub struct VerifyResponse {
     /// Whether the content is authentic (unmodified since sealing)
     #[schema(example = true)]
     pub authentic: bool,
-    /// Human-readable details about the verification result
+    /// Stable code of the result (`content_authentic`, `content_modified`,
+    /// `invalid_signature`, `mock_entropy`, ...), for clients showing their
+    /// own messages
+    #[schema(value_type = String, example = "content_authentic")]
+    pub code: MessageCode,
+    /// Human-readable details about the verification result, in the requested
+    /// language
     #[schema(
         example = "Seal valid. Media type: Image, QRNG source: Anu, Captured: 2024-01-01T00:00:00Z"
     )]
     pub details: String,
+    /// Graded content outcome: `authentic`, `likely_recompressed` (the hash
+    /// differs but the image looks the same, e.g. recompressed or resized) or
+    /// `modified`; absent when the signature fails
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "authentic")]
+    pub outcome: Option<String>,
+    /// Perceptual hash distance to the sealed image in bits, for
+    /// `likely_recompressed` content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 4)]
+    pub perceptual_distance: Option<u32>,
+    /// Segments that differ from the sealed recording (segmented seals only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tampered_segments: Option<Vec<TamperedSegmentInfo>>,
+    /// Regions of a `modified` image that differ from the sealed one (image
+    /// seals with region hashes only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_regions: Vec<ChangedRegionInfo>,
+    /// Blockchain anchors of an authentic seal, checked on their chains
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub anchors: Vec<AnchorInfo>,
+    /// Start of the seal's validity window (RFC 3339), if it has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<String>,
+    /// End of the seal's validity window (RFC 3339), if it has one; the seal
+    /// no longer verifies afterwards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "2026-12-31T00:00:00+00:00")]
+    pub not_after: Option<String>,
+    /// Liveness challenge answered by the capture, if the seal recorded one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liveness: Option<LivenessInfo>,
+    /// Signing key of a seal carrying a key ID, as found in this server's
+    /// key registry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<SigningKeyInfo>,
+    /// Why the server rejected the seal although the content is authentic
+    /// (`mock_entropy` with `MOCK_QRNG_VERIFICATION=reject`,
+    /// `untrusted_signing_key` when the key registry contradicts the seal)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<PolicyViolationInfo>,
+    /// Weaknesses of the seal, e.g. `mock_entropy` (made with the mock QRNG,
+    /// not quantum-safe)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<PolicyViolationInfo>,
+    /// Verdict of the verification policy, when one was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<PolicyInfo>,
+    /// Branding of the organization whose verification domain the request
+    /// comes from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branding: Option<Branding>,
+}
+
+/// Verdict of a verification policy
+#[derive(Serialize, ToSchema)]
+pub struct PolicyInfo {
+    /// Preset name (`strict`, `standard`, `lenient`), or `custom`
+    #[schema(example = "standard")]
+    pub name: String,
+    /// Whether the content is authentic and the seal meets the policy
+    pub accepted: bool,
+    /// Policy requirements the seal does not meet
+    pub violations: Vec<PolicyViolationInfo>,
+    /// Weaknesses the policy tolerates
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<PolicyViolationInfo>,
+}
+
+/// The seal's signing key in the key registry
+#[derive(Serialize, ToSchema)]
+pub struct SigningKeyInfo {
+    /// Key ID written into the seal
+    #[schema(example = "3f2a9c0e5b7d1a46")]
+    pub key_id: String,
+    /// `trusted`, `unknown` (not registered here), `key_mismatch` (another
+    /// key is registered under this ID), `revoked` or `outside_validity`
+    /// (sealed outside the key's validity period)
+    #[schema(value_type = String, example = "trusted")]
+    pub resolution: KeyResolution,
+    /// Status of the registered key: `active`, `retired` or `revoked`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "active")]
+    pub status: Option<SigningKeyStatus>,
+}
+
+/// A requirement the seal does not meet, or a weakness of the seal
+#[derive(Serialize, ToSchema)]
+pub struct PolicyViolationInfo {
+    /// `missing_device_attestation`, `mock_entropy`, `anchor_not_confirmed`
+    /// or `untrusted_signing_key`
+    #[schema(value_type = String, example = "mock_entropy")]
+    pub code: PolicyViolation,
+    /// Human-readable description, in the requested language
+    pub message: String,
+}
+
+impl PolicyViolationInfo {
+    fn new(violation: PolicyViolation, locale: Locale) -> Self {
+        Self {
+            code: violation,
+            message: violation.description_in(locale).to_string(),
+        }
+    }
+}
+
+/// A blockchain anchor of the seal and its status on chain
+#[derive(Serialize, ToSchema)]
+pub struct AnchorInfo {
+    /// Chain identifier
+    #[schema(example = "solana-mainnet")]
+    pub chain: String,
+    /// Transaction ID (calendar URL for Bitcoin anchors)
+    pub tx_id: String,
+    /// `confirmed`, `shallow` (fewer confirmations than required), `pending`,
+    /// `mismatch` (does not commit to this seal), `unsupported` or
+    /// `unchecked` (chain unreachable)
+    #[schema(example = "confirmed")]
+    pub status: String,
+    /// Block (slot on Solana) committing to the seal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 284_031_337_u64)]
+    pub block_height: Option<u64>,
+    /// Blocks on top of it, counting its own
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 32_u64)]
+    pub confirmations: Option<u64>,
+    /// Why the anchor does not match the seal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl AnchorInfo {
+    fn new(anchor: &BlockchainAnchor, status: Option<AnchorStatus>) -> Self {
+        let depth = status.as_ref().and_then(AnchorStatus::depth);
+        Self {
+            chain: anchor.chain.clone(),
+            tx_id: anchor.tx_id.clone(),
+            status: status
+                .as_ref()
+                .map_or("unchecked", AnchorStatus::as_str)
+                .to_string(),
+            block_height: depth.map(|(height, _)| height),
+            confirmations: depth.map(|(_, confirmations)| confirmations),
+            reason: match status {
+                Some(AnchorStatus::Mismatch(reason)) => Some(reason),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A segment of a segmented recording that failed verification
+#[derive(Serialize, ToSchema)]
+pub struct TamperedSegmentInfo {
+    /// Segment index
+    #[schema(example = 3)]
+    pub index: u32,
+    /// First byte of the segment
+    #[schema(example = 3145728_u64)]
+    pub byte_start: u64,
+    /// End of the segment (exclusive)
+    #[schema(example = 4194304_u64)]
+    pub byte_end: u64,
+    /// Playback start of the segment in ms (when segment durations are known)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 6000_u64)]
+    pub time_start_ms: Option<u64>,
+    /// Playback end of the segment in ms (when segment durations are known)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 8000_u64)]
+    pub time_end_ms: Option<u64>,
+}
+
+impl From<&TamperedSegment> for TamperedSegmentInfo {
+    fn from(segment: &TamperedSegment) -> Self {
+        Self {
+            index: segment.index,
+            byte_start: segment.byte_range.start,
+            byte_end: segment.byte_range.end,
+            time_start_ms: segment.time_range_ms.as_ref().map(|t| t.start),
+            time_end_ms: segment.time_range_ms.as_ref().map(|t| t.end),
+        }
+    }
+}
+
+/// A region of a modified image that differs from the sealed one
+#[derive(Serialize, ToSchema)]
+pub struct ChangedRegionInfo {
+    /// Tile row, from the top
+    #[schema(example = 1)]
+    pub row: u8,
+    /// Tile column, from the left
+    #[schema(example = 2)]
+    pub column: u8,
+    /// Number of tiles per row and per column
+    #[schema(example = 4)]
+    pub grid: u8,
+    /// Perceptual distance to the sealed tile, in bits (0-64)
+    #[schema(example = 27)]
+    pub distance: u32,
+}
+
+/// Liveness challenge recorded in a seal (see `veritas_core::liveness`)
+#[derive(Serialize, ToSchema)]
+pub struct LivenessInfo {
+    /// Code the capture showed in frame
+    #[schema(example = "009384")]
+    pub code: String,
+    /// When the challenge was issued (RFC 3339)
+    pub issued_at: Option<String>,
+    /// When the capture answered it (RFC 3339)
+    pub responded_at: Option<String>,
+    /// Delay between the challenge and the answer, in milliseconds
+    #[schema(example = 4200)]
+    pub response_time_ms: u64,
+    /// Whether the code matches the seal's capture nonce and the answer came
+    /// in time, before sealing
+    pub valid: bool,
+}
+
+impl LivenessInfo {
+    fn from_seal(seal: &VeritasSeal) -> Option<Self> {
+        let liveness = seal.liveness.as_ref()?;
+        Some(Self {
+            code: liveness.code.clone(),
+            issued_at: format_timestamp(liveness.issued_at),
+            responded_at: format_timestamp(liveness.responded_at),
+            response_time_ms: liveness.response_time_ms(),
+            valid: seal.verify_liveness() == Some(true),
+        })
+    }
 }
 
 /// Verify a seal against content
 ///
 /// Accepts multipart/form-data with:
 /// - **file** (required): The media file to verify
-/// - **seal_data** (required): Base64-encoded CBOR seal from the /seal endpoint
+/// - **seal_data** (required): Base64-encoded CBOR seal from the /seal endpoint, or the raw
+///   CBOR seal in a part with `Content-Type: application/cbor`
+/// - **policy** (optional): verification policy the seal must also meet, as a preset
+///   (`strict`, `standard`, `lenient`) or a JSON object such as
+///   `{"require_device_attestation": true, "mock_qrng": "reject", "require_confirmed_anchor": false}`;
+///   the verdict is returned in `policy`
+/// - **lang** (optional): language of the messages (`en`, `fr`); defaults to the
+///   `Accept-Language` header, then English
 ///
 /// Returns whether the content is authentic (unchanged since sealing) or has been tampered with.
+/// When the hash differs, images whose perceptual hash is within a few bits of the sealed one
+/// are graded `likely_recompressed` (re-encoded or resized, not edited) rather than `modified`.
+/// For segmented recordings, the altered segments are listed with their byte and time ranges.
+/// For modified images sealed with region hashes, `changed_regions` locates the edited tiles.
+/// Seals from a trusted capture session report their liveness challenge in `liveness`.
+/// Blockchain anchors of an authentic seal are checked on their chains: the transaction
+/// must commit to the seal and be buried under `ANCHOR_MIN_CONFIRMATIONS` blocks.
+/// Seals made with the mock QRNG are reported with a `mock_entropy` warning, or rejected
+/// (`authentic: false` with a `rejection`) under `MOCK_QRNG_VERIFICATION=reject`.
+/// The key ID of seals signed with a registered key is resolved against the key registry
+/// (`signing_key`); seals whose key is revoked, registered under another public key or
+/// was not valid at capture time are rejected (`untrusted_signing_key`).
+/// Verifications of seals stored on this server are recorded in the audit log, and
+/// the owner is alerted when someone else verifies their seal against tampered content.
+/// Verifications by anyone but the owner also count towards the seal's
+/// `verification_count`, with the requesting site and country (never the IP address).
+/// Outcomes are cached briefly, so repeat requests for the same seal and file are cheap,
+/// and verification runs on a bounded worker pool off the async runtime.
 /// Verification checks:
 /// - Post-quantum signature validity (ML-DSA-65)
 /// - Content hash match (SHA3-256)
 /// - Seal structure integrity
 #[utoipa::path(
     post,
-    path = "/verify",
+    path = "/api/v1/verify",
     tag = "Verification",
     request_body(
         content_type = "multipart/form-data",
@@ -46,57 +327,460 @@ pub struct VerifyResponse {
     ),
     responses(
         (status = 200, description = "Verification completed", body = VerifyResponse),
-        (status = 400, description = "Invalid request (missing file, invalid seal format, etc.)"),
+        (status = 400, description = "Invalid request (missing file, invalid seal format, unknown policy or language, etc.)"),
         (status = 500, description = "Internal server error")
     )
 )]
-pub async fn verify_handler(mut multipart: Multipart) -> Result<Json<VerifyResponse>, ApiError> {
+pub async fn verify_handler(
+    State(state): State<AppState>,
+    OptionalAuth(auth): OptionalAuth,
+    headers: HeaderMap,
+    mut multipart: Multipart,
+) -> Result<Json<VerifyResponse>, ApiError> {
     // Parse multipart form
-    let fields = MultipartFields::parse(&mut multipart, true, DEFAULT_MAX_FILE_SIZE).await?;
+    let mut fields = MultipartFields::parse(&mut multipart, true, DEFAULT_MAX_FILE_SIZE).await?;
 
     // Extract required fields
-    let file = fields.require_file()?;
-    let content = &file.data;
+    let content = Arc::new(fields.take_file()?.data);
 
-    let seal_b64 = fields
-        .get_text("seal_data")
-        .ok_or_else(|| ApiError::bad_request("No seal_data provided."))?;
-
-    // Decode seal from base64
-    let seal_cbor = BASE64
-        .decode(seal_b64)
-        .map_err(|e| ApiError::bad_request(format!("Invalid base64 in seal_data: {}", e)))?;
+    // Raw CBOR seal, or base64 text
+    let seal_cbor = match fields.take_cbor("seal_data") {
+        Some(seal_cbor) => seal_cbor,
+        None => {
+            let seal_b64 = fields
+                .get_text("seal_data")
+                .ok_or_else(|| ApiError::bad_request("No seal_data provided."))?;
+            BASE64
+                .decode(seal_b64)
+                .map_err(|e| ApiError::bad_request(format!("Invalid base64 in seal_data: {}", e)))?
+        }
+    };
+    let policy = requested_policy(fields.get_text("policy"))?;
+    let locale = requested_locale(fields.get_text("lang"), &headers)?;
 
     // Deserialize seal from CBOR
-    let seal = VeritasSeal::from_cbor(&seal_cbor)
-        .map_err(|e| ApiError::bad_request(format!("Invalid seal format: {}", e)))?;
-
-    // Verify signature and content in one call
-    let result = seal.verify_content(content).map_err(|e| {
-        tracing::error!(error = %e, "Verification error");
-        ApiError::internal("Verification processing failed")
-    })?;
-
-    let (authentic, details) = match result {
-        ContentVerificationResult::Authentic => (
-            true,
-            format!(
-                "Seal valid. Media type: {:?}, QRNG source: {:?}, Captured: {}",
-                seal.media_type,
-                seal.qrng_source,
-                chrono::DateTime::from_timestamp_millis(seal.capture_timestamp_utc as i64)
-                    .map(|dt| dt.to_rfc3339())
-                    .unwrap_or_else(|| "unknown".to_string())
-            ),
+    let seal = Arc::new(
+        VeritasSeal::from_cbor(&seal_cbor)
+            .map_err(|e| ApiError::bad_request(format!("Invalid seal format: {}", e)))?,
+    );
+
+    // Hashing and signature checks run on the verification pool, off the
+    // async runtime threads
+    let key = {
+        let content = Arc::clone(&content);
+        state
+            .verification_pool
+            .run(move || VerificationKey::new(&seal_cbor, &content))
+            .await?
+    };
+
+    // Repeat verifications of the same seal and content are served from the cache
+    let (result, grade, regions, stored_seal) = match state.verification_cache.verification(&key) {
+        Some(cached) => (
+            cached.result,
+            cached.grade,
+            cached.regions,
+            cached.stored_seal,
         ),
-        ContentVerificationResult::ContentModified { .. } => (
+        None => {
+            // Verify signature and content in one call, grading modified
+            // images by their perceptual distance and locating their edits
+            let (result, grade, regions) = {
+                let seal = Arc::clone(&seal);
+                state
+                    .verification_pool
+                    .run(move || {
+                        seal.verify_content_graded(&content, RECOMPRESSION_MAX_DISTANCE)
+                            .map(|(result, grade)| {
+                                let regions = match grade {
+                                    Some(ContentGrade::Modified) => {
+                                        seal.region_comparison(&content)
+                                    }
+                                    _ => None,
+                                };
+                                (result, grade, regions)
+                            })
+                    })
+                    .await?
+            }
+            .map_err(|e| {
+                tracing::error!(error = %e, "Verification error");
+                ApiError::internal("Verification processing failed")
+            })?;
+
+            let stored_seal = match find_stored_seal(&state, &seal).await {
+                Ok(stored_seal) => {
+                    // The result of a seal with a validity window changes over time
+                    if seal.not_before.is_none() && seal.not_after.is_none() {
+                        state.verification_cache.insert_verification(
+                            key,
+                            CachedVerification {
+                                result: result.clone(),
+                                grade,
+                                regions: regions.clone(),
+                                stored_seal,
+                            },
+                        );
+                    }
+                    stored_seal
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to look up verified seal");
+                    None
+                }
+            };
+            (result, grade, regions, stored_seal)
+        }
+    };
+
+    let outcome = match &result {
+        ContentVerificationResult::Authentic => VerificationOutcome::Authentic,
+        ContentVerificationResult::ContentModified { .. } => VerificationOutcome::Tampered,
+        ContentVerificationResult::SignatureFailed(_) => VerificationOutcome::InvalidSignature,
+    };
+    if let Some((seal_id, owner_id)) = stored_seal {
+        logging::record_seal(seal_id);
+        let actor_user_id = auth.map(|a| a.user.id);
+        record_verification(&state, actor_user_id, seal_id, owner_id, outcome).await;
+        if owner_id.is_none_or(|owner_id| actor_user_id != Some(owner_id)) {
+            count_verification(&state, seal_id, outcome, &verification_origin(&headers)).await;
+        }
+    }
+
+    let anchor_statuses = if matches!(result, ContentVerificationResult::Authentic) {
+        state.anchor_checker.check(&seal).await
+    } else {
+        Vec::new()
+    };
+    let policy = policy.map(|(name, policy)| {
+        let checked: Vec<AnchorStatus> = anchor_statuses
+            .iter()
+            .filter_map(|(_, status)| status.clone())
+            .collect();
+        let violations = policy.evaluate(&seal, &checked);
+        PolicyInfo {
+            name,
+            accepted: result.is_authentic() && violations.is_empty(),
+            violations: violations
+                .into_iter()
+                .map(|violation| PolicyViolationInfo::new(violation, locale))
+                .collect(),
+            warnings: policy
+                .warnings(&seal)
+                .into_iter()
+                .map(|warning| PolicyViolationInfo::new(warning, locale))
+                .collect(),
+        }
+    });
+
+    // Mock entropy is deterministic, so mock seals are flagged unless the
+    // server is configured to accept them
+    let mock_entropy = || PolicyViolationInfo::new(PolicyViolation::MockEntropy, locale);
+    let (rejection, warnings): (Option<PolicyViolationInfo>, Vec<PolicyViolationInfo>) =
+        if seal.uses_mock_entropy() {
+            match state.mock_qrng_verification {
+                MockQrngHandling::Accept => (None, Vec::new()),
+                MockQrngHandling::Warn => (None, vec![mock_entropy()]),
+                MockQrngHandling::Reject => (Some(mock_entropy()), Vec::new()),
+            }
+        } else {
+            (None, Vec::new())
+        };
+    // Seals carrying a key ID are checked against the key registry
+    let signing_key = match &seal.key_id {
+        Some(key_id) if !matches!(result, ContentVerificationResult::SignatureFailed(_)) => {
+            let registered = state.signing_keys.find(key_id).await?;
+            Some(SigningKeyInfo {
+                key_id: key_id.clone(),
+                resolution: seal.resolve_signing_key(registered.as_ref()),
+                status: registered.map(|key| key.status),
+            })
+        }
+        _ => None,
+    };
+    let rejection = match &signing_key {
+        Some(key) if key.resolution.is_rejected() => Some(PolicyViolationInfo::new(
+            PolicyViolation::UntrustedSigningKey,
+            locale,
+        )),
+        _ => rejection,
+    };
+    let anchors = anchor_statuses
+        .into_iter()
+        .map(|(anchor, status)| AnchorInfo::new(anchor, status))
+        .collect();
+
+    let recompressed = match grade {
+        Some(ContentGrade::LikelyRecompressed { distance }) => Some(distance),
+        _ => None,
+    };
+    let changed_regions = regions
+        .map(|regions| {
+            regions
+                .changed_regions(REGION_CHANGE_THRESHOLD)
+                .into_iter()
+                .map(|region| ChangedRegionInfo {
+                    row: region.row,
+                    column: region.column,
+                    grid: regions.grid,
+                    distance: region.distance,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let code = match &rejection {
+        Some(rejection) if result.is_authentic() => rejection.code.code(),
+        _ if recompressed.is_some() => MessageCode::LikelyRecompressed,
+        _ => result.code(),
+    };
+    let (authentic, details, tampered_segments) = match (result, &rejection) {
+        (ContentVerificationResult::Authentic, Some(rejection)) => {
+            (false, rejected_details(rejection.code, locale), None)
+        }
+        (ContentVerificationResult::Authentic, None) => {
+            (true, seal_valid_details(&seal, locale), None)
+        }
+        (
+            ContentVerificationResult::ContentModified {
+                tampered_segments, ..
+            },
+            _,
+        ) => (
             false,
-            "Content hash mismatch - file has been modified since sealing".into(),
+            match recompressed {
+                Some(_) => MessageCode::LikelyRecompressed.message(locale).into(),
+                None => content_modified_details(locale).into(),
+            },
+            tampered_segments.map(|segments| segments.iter().map(Into::into).collect()),
         ),
-        ContentVerificationResult::SignatureFailed(sig_result) => {
-            (false, sig_result.description().into())
+        (ContentVerificationResult::SignatureFailed(sig_result), _) => {
+            (false, sig_result.description_in(locale).into(), None)
+        }
+    };
+
+    Ok(Json(VerifyResponse {
+        authentic,
+        code,
+        details,
+        outcome: grade.map(|grade| grade.as_str().to_string()),
+        perceptual_distance: recompressed,
+        tampered_segments,
+        changed_regions,
+        anchors,
+        not_before: seal.not_before.and_then(format_timestamp),
+        not_after: seal.not_after.and_then(format_timestamp),
+        liveness: LivenessInfo::from_seal(&seal),
+        signing_key,
+        rejection,
+        warnings,
+        policy,
+        branding: response_branding(&state, None, &headers).await,
+    }))
+}
+
+/// Verification policy requested by the `policy` field: a preset name or a
+/// JSON object, with the name reported back
+fn requested_policy(
+    policy: Option<&str>,
+) -> Result<Option<(String, VerificationPolicy)>, ApiError> {
+    let Some(policy) = policy.map(str::trim).filter(|policy| !policy.is_empty()) else {
+        return Ok(None);
+    };
+    if policy.starts_with('{') {
+        let policy = serde_json::from_str(policy)
+            .map_err(|e| ApiError::bad_request(format!("Invalid policy JSON: {}", e)))?;
+        return Ok(Some(("custom".to_string(), policy)));
+    }
+    VerificationPolicy::preset(policy)
+        .map(|preset| Some((policy.to_ascii_lowercase(), preset)))
+        .ok_or_else(|| {
+            ApiError::bad_request(format!(
+                "Unknown verification policy: {} (expected {} or a JSON object)",
+                policy,
+                VerificationPolicy::PRESETS.join(", ")
+            ))
+        })
+}
+
+/// Language of the messages: the `lang` field, else the preferred supported
+/// language of `Accept-Language`, else English
+fn requested_locale(lang: Option<&str>, headers: &HeaderMap) -> Result<Locale, ApiError> {
+    if let Some(lang) = lang.map(str::trim).filter(|lang| !lang.is_empty()) {
+        return Locale::parse(lang).ok_or_else(|| {
+            let supported: Vec<&str> = Locale::ALL.iter().map(Locale::as_str).collect();
+            ApiError::bad_request(format!(
+                "Unsupported language: {} (expected {})",
+                lang,
+                supported.join(", ")
+            ))
+        });
+    }
+    Ok(headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Locale::from_accept_language)
+        .unwrap_or_default())
+}
+
+/// Details of an authentic seal
+fn seal_valid_details(seal: &VeritasSeal, locale: Locale) -> String {
+    let captured = format_timestamp(seal.capture_timestamp_utc);
+    match locale {
+        Locale::En => format!(
+            "Seal valid. Media type: {:?}, QRNG source: {:?}, Captured: {}",
+            seal.media_type,
+            seal.qrng_source,
+            captured.unwrap_or_else(|| "unknown".to_string())
+        ),
+        Locale::Fr => format!(
+            "Sceau valide. Type de média : {:?}, source QRNG : {:?}, capturé le : {}",
+            seal.media_type,
+            seal.qrng_source,
+            captured.unwrap_or_else(|| "inconnu".to_string())
+        ),
+    }
+}
+
+/// Details of an authentic seal rejected by the server
+fn rejected_details(violation: PolicyViolation, locale: Locale) -> String {
+    let reason = violation.description_in(locale);
+    match locale {
+        Locale::En => format!("Seal rejected: {}", reason),
+        Locale::Fr => format!("Sceau rejeté : {}", reason),
+    }
+}
+
+/// Details of content that differs from the sealed content
+fn content_modified_details(locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => "Content hash mismatch - file has been modified since sealing",
+        Locale::Fr => {
+            "Empreinte du contenu différente - le fichier a été modifié depuis le scellement"
         }
+    }
+}
+
+/// Unix milliseconds as RFC 3339
+fn format_timestamp(timestamp_ms: u64) -> Option<String> {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms as i64).map(|dt| dt.to_rfc3339())
+}
+
+/// Find the stored seal matching a verified seal: its ID and owner.
+///
+/// Seals this server did not issue, or any seal without a database, give `None`.
+async fn find_stored_seal(
+    state: &AppState,
+    seal: &VeritasSeal,
+) -> Result<Option<(Uuid, Option<Uuid>)>, sqlx::Error> {
+    let Some(seal_repo) = &state.seal_repo else {
+        return Ok(None);
     };
+    seal_repo
+        .find_owner_by_signature(&seal.content_hash.crypto_hash_hex(), &seal.signature)
+        .await
+}
+
+/// Count a verification of a stored seal by someone other than its owner
+/// (non-fatal).
+async fn count_verification(
+    state: &AppState,
+    seal_id: Uuid,
+    outcome: VerificationOutcome,
+    origin: &VerificationOrigin,
+) {
+    if let Some(verification_repo) = &state.verification_repo {
+        if let Err(e) = verification_repo.record(seal_id, outcome, origin).await {
+            tracing::warn!(error = %e, "Failed to count seal verification");
+        }
+    }
+}
+
+/// Coarse origin of a request: the host of the site it came from
+/// (`Origin`, else `Referer`) and the country set by Cloudflare (`CF-IPCountry`)
+fn verification_origin(headers: &HeaderMap) -> VerificationOrigin {
+    let header_text = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let site = header_text(header::ORIGIN.as_str())
+        .or_else(|| header_text(header::REFERER.as_str()))
+        .and_then(|value| url::Url::parse(value).ok())
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase));
+    // Cloudflare reports XX when the country is unknown and T1 for Tor
+    let country = header_text("cf-ipcountry")
+        .filter(|code| {
+            code.len() == 2
+                && code.bytes().all(|b| b.is_ascii_alphabetic())
+                && !code.eq_ignore_ascii_case("XX")
+        })
+        .map(str::to_ascii_uppercase);
+    VerificationOrigin { site, country }
+}
+
+/// Record the verification of a stored seal in the audit log and alert its
+/// owner when someone else found the content tampered (non-fatal).
+async fn record_verification(
+    state: &AppState,
+    actor_user_id: Option<Uuid>,
+    seal_id: Uuid,
+    owner_id: Option<Uuid>,
+    outcome: VerificationOutcome,
+) {
+    if let Some(audit_repo) = &state.audit_repo {
+        if let Err(e) = audit_repo
+            .record(
+                AuditEvent::SealVerified,
+                Some(seal_id),
+                actor_user_id,
+                Some(outcome),
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to record seal verification");
+        }
+    }
+
+    if let (Some(notifier), Some(owner_id)) = (&state.notifier, owner_id) {
+        if outcome == VerificationOutcome::Tampered && actor_user_id != Some(owner_id) {
+            notifier.notify(Notification::new(
+                NotificationEvent::SealTampered,
+                seal_id,
+                owner_id,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_verification_origin() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(verification_origin(&headers), VerificationOrigin::default());
+
+        headers.insert(
+            header::REFERER,
+            HeaderValue::from_static("https://News.Example.com:8443/articles/1?ref=x"),
+        );
+        headers.insert("cf-ipcountry", HeaderValue::from_static("fr"));
+        let origin = verification_origin(&headers);
+        assert_eq!(origin.site.as_deref(), Some("news.example.com"));
+        assert_eq!(origin.country.as_deref(), Some("FR"));
+
+        // Origin wins over Referer; unknown and Tor countries are dropped
+        headers.insert(
+            header::ORIGIN,
+            HeaderValue::from_static("https://blog.example.org"),
+        );
+        headers.insert("cf-ipcountry", HeaderValue::from_static("T1"));
+        let origin = verification_origin(&headers);
+        assert_eq!(origin.site.as_deref(), Some("blog.example.org"));
+        assert_eq!(origin.country, None);
 
-    Ok(Json(VerifyResponse { authentic, details }))
+        headers.insert(header::ORIGIN, HeaderValue::from_static("null"));
+        headers.insert("cf-ipcountry", HeaderValue::from_static("XX"));
+        let origin = verification_origin(&headers);
+        assert_eq!(origin.site, None);
+        assert_eq!(origin.country, None);
+    }
 }
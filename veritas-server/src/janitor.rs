@@ -0,0 +1,336 @@
+//! Scheduled maintenance tasks
+//!
+//! Periodic housekeeping (purging abandoned uploads and expired exports,
+//! dropping idle streams and stale WebAuthn challenges, refreshing signing
+//! keys, clustering manifests, flushing anchor batches, enforcing retention
+//! policies) is registered with the [`Janitor`] held in `AppState` instead of
+//! being spawned ad hoc. Each task runs on its
+//! own interval with ±10% jitter, so replicas started together do not hit
+//! the database in lockstep, and keeps run statistics for
+//! `/api/v1/admin/tasks`. Tasks writing to the database are registered with
+//! [`Janitor::register_write`] and skip their runs while the server is in
+//! read-only maintenance mode. On shutdown, tasks finish their current run
+//! and stop; they also stop when the janitor is dropped.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::maintenance::MaintenanceMode;
+
+/// Share of a task's interval by which each wait is randomly shortened or
+/// lengthened.
+pub const JITTER_RATIO: f64 = 0.1;
+
+/// How long shutdown waits for running tasks to finish.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Run statistics of a scheduled task
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TaskStats {
+    /// Task name
+    #[schema(example = "upload_sweep")]
+    pub name: &'static str,
+    /// Nominal interval between runs, in seconds
+    #[schema(example = 600)]
+    pub interval_secs: u64,
+    /// Completed runs
+    pub runs: u64,
+    /// Runs that returned an error
+    pub failures: u64,
+    /// Items processed (purged, clustered, anchored...) over all runs
+    pub processed: u64,
+    /// When the last run finished
+    #[schema(value_type = Option<String>, example = "2026-01-08T10:00:00Z")]
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Duration of the last run in milliseconds
+    pub last_duration_ms: Option<u64>,
+    /// Error of the last run, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl TaskStats {
+    fn new(name: &'static str, interval: Duration) -> Self {
+        Self {
+            name,
+            interval_secs: interval.as_secs(),
+            runs: 0,
+            failures: 0,
+            processed: 0,
+            last_run_at: None,
+            last_duration_ms: None,
+            last_error: None,
+        }
+    }
+
+    fn record(&mut self, result: &Result<usize, String>, duration: Duration) {
+        self.runs += 1;
+        self.last_run_at = Some(Utc::now());
+        self.last_duration_ms = Some(duration.as_millis() as u64);
+        match result {
+            Ok(processed) => {
+                self.processed += *processed as u64;
+                self.last_error = None;
+            }
+            Err(e) => {
+                self.failures += 1;
+                self.last_error = Some(e.clone());
+            }
+        }
+    }
+}
+
+/// A registered task
+struct ScheduledTask {
+    stats: Arc<Mutex<TaskStats>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Registry and scheduler of periodic maintenance tasks.
+pub struct Janitor {
+    shutdown: watch::Sender<bool>,
+    tasks: Mutex<Vec<ScheduledTask>>,
+}
+
+impl Janitor {
+    /// Create a janitor without tasks.
+    pub fn new() -> Self {
+        Self {
+            shutdown: watch::Sender::new(false),
+            tasks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Run `task` every `interval` (with jitter) until shutdown.
+    ///
+    /// The task returns how many items it processed, or an error message;
+    /// errors are logged and counted, and the task runs again at its next
+    /// tick. The first run happens within the first tenth of the interval.
+    /// Outside a Tokio runtime the task is registered but never runs.
+    pub fn register<F, Fut>(&self, name: &'static str, interval: Duration, mut task: F)
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<usize, String>> + Send + 'static,
+    {
+        let stats = Arc::new(Mutex::new(TaskStats::new(name, interval)));
+        let handle = tokio::runtime::Handle::try_current().ok().map(|runtime| {
+            let stats = Arc::clone(&stats);
+            let mut shutdown = self.shutdown.subscribe();
+            runtime.spawn(async move {
+                let mut delay = interval.mul_f64(JITTER_RATIO * random_unit());
+                loop {
+                    tokio::select! {
+                        biased;
+                        // Err: the janitor was dropped
+                        _ = shutdown.changed() => break,
+                        _ = tokio::time::sleep(delay) => {}
+                    }
+
+                    let started = Instant::now();
+                    let result = task().await;
+                    let duration = started.elapsed();
+                    match &result {
+                        Ok(0) => {}
+                        Ok(processed) => {
+                            tracing::info!(task = name, processed, "Scheduled task completed")
+                        }
+                        Err(e) => tracing::warn!(task = name, error = %e, "Scheduled task failed"),
+                    }
+                    if let Ok(mut stats) = stats.lock() {
+                        stats.record(&result, duration);
+                    }
+                    delay = jittered(interval);
+                }
+            })
+        });
+
+        if let Ok(mut tasks) = self.tasks.lock() {
+            tasks.push(ScheduledTask { stats, handle });
+        }
+    }
+
+    /// Run `task` like [`Janitor::register`], except while `maintenance` is
+    /// read-only: the task writes to the database, so those runs are
+    /// skipped and process nothing.
+    pub fn register_write<F, Fut>(
+        &self,
+        name: &'static str,
+        interval: Duration,
+        maintenance: &Arc<MaintenanceMode>,
+        mut task: F,
+    ) where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<usize, String>> + Send + 'static,
+    {
+        let maintenance = Arc::clone(maintenance);
+        self.register(name, interval, move || {
+            let run = (!maintenance.is_read_only()).then(&mut task);
+            async move {
+                match run {
+                    Some(run) => run.await,
+                    None => {
+                        tracing::debug!(task = name, "Read-only mode, scheduled task skipped");
+                        Ok(0)
+                    }
+                }
+            }
+        });
+    }
+
+    /// Run statistics of every registered task, in registration order.
+    pub fn stats(&self) -> Vec<TaskStats> {
+        let Ok(tasks) = self.tasks.lock() else {
+            return Vec::new();
+        };
+        tasks
+            .iter()
+            .filter_map(|task| task.stats.lock().ok().map(|stats| stats.clone()))
+            .collect()
+    }
+
+    /// Stop all tasks, waiting up to `timeout` for running ones to finish.
+    pub async fn shutdown(&self, timeout: Duration) {
+        self.shutdown.send_replace(true);
+        let handles: Vec<JoinHandle<()>> = match self.tasks.lock() {
+            Ok(mut tasks) => tasks
+                .iter_mut()
+                .filter_map(|task| task.handle.take())
+                .collect(),
+            Err(_) => return,
+        };
+        if handles.is_empty() {
+            return;
+        }
+
+        let count = handles.len();
+        let join_all = async {
+            for handle in handles {
+                let _ = handle.await;
+            }
+        };
+        match tokio::time::timeout(timeout, join_all).await {
+            Ok(()) => tracing::info!(tasks = count, "Scheduled tasks stopped"),
+            Err(_) => tracing::warn!(
+                timeout_secs = timeout.as_secs(),
+                "Scheduled tasks still running at shutdown"
+            ),
+        }
+    }
+}
+
+impl Default for Janitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `interval` randomly shortened or lengthened by up to [`JITTER_RATIO`].
+fn jittered(interval: Duration) -> Duration {
+    let spread = interval.mul_f64(JITTER_RATIO);
+    (interval - spread) + spread.mul_f64(2.0 * random_unit())
+}
+
+/// Uniform random number in [0, 1).
+fn random_unit() -> f64 {
+    // The low 53 bits of a v4 UUID are random
+    let bits = Uuid::new_v4().as_u128() as u64 & ((1 << 53) - 1);
+    bits as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_jitter_stays_within_ratio() {
+        let interval = Duration::from_secs(600);
+        for _ in 0..1000 {
+            let delay = jittered(interval);
+            assert!((Duration::from_secs(540)..=Duration::from_secs(660)).contains(&delay));
+        }
+        assert_eq!(jittered(Duration::ZERO), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_tasks_run_record_stats_and_stop() {
+        let janitor = Janitor::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        janitor.register("counter", Duration::from_millis(10), move || {
+            let counter = Arc::clone(&counter);
+            async move {
+                match counter.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err("first run fails".to_string()),
+                    _ => Ok(2),
+                }
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        janitor.shutdown(SHUTDOWN_TIMEOUT).await;
+        let stopped_at = runs.load(Ordering::SeqCst);
+        assert!(stopped_at >= 2);
+
+        let stats = janitor.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].name, "counter");
+        assert_eq!(stats[0].runs, stopped_at as u64);
+        assert_eq!(stats[0].failures, 1);
+        assert_eq!(stats[0].processed, 2 * (stopped_at as u64 - 1));
+        assert!(stats[0].last_error.is_none());
+
+        // No run after shutdown
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), stopped_at);
+    }
+
+    #[tokio::test]
+    async fn test_write_tasks_pause_in_read_only_mode() {
+        let janitor = Janitor::new();
+        let maintenance = Arc::new(MaintenanceMode::new(true, 300));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        janitor.register_write(
+            "writer",
+            Duration::from_millis(10),
+            &maintenance,
+            move || {
+                let counter = Arc::clone(&counter);
+                async move { Ok(counter.fetch_add(1, Ordering::SeqCst)) }
+            },
+        );
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        assert!(janitor.stats()[0].runs > 0);
+
+        maintenance.set(false, None, None);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(runs.load(Ordering::SeqCst) > 0);
+        janitor.shutdown(SHUTDOWN_TIMEOUT).await;
+    }
+
+    #[tokio::test]
+    async fn test_tasks_stop_when_janitor_dropped() {
+        let janitor = Janitor::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        janitor.register("counter", Duration::from_millis(10), move || {
+            let counter = Arc::clone(&counter);
+            async move { Ok(counter.fetch_add(1, Ordering::SeqCst)) }
+        });
+        drop(janitor);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+    }
+}
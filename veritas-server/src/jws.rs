@@ -0,0 +1,110 @@
+//! JWS export of seals
+//!
+//! `GET /api/v1/seals/{seal_id}/export?format=jws` returns the seal as a
+//! compact JWS (see [`veritas_core::jws`]) for partners that only consume
+//! JOSE. Tokens are signed ES256 with the C2PA signing key
+//! (`C2PA_SIGNING_KEY`, PKCS#8 PEM) and carry its certificate chain
+//! (`C2PA_SIGNING_CERT`) in the `x5c` header, so verifiers can check them
+//! against the same trust anchors as our C2PA manifests.
+
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use veritas_core::SealClaims;
+
+/// Signs seal claims into compact JWS
+pub struct JwsSigner {
+    encoding_key: EncodingKey,
+    /// Certificate chain, leaf first (base64 DER)
+    x5c: Vec<String>,
+}
+
+impl JwsSigner {
+    /// Load the C2PA signing key and certificate chain:
+    ///
+    /// - `C2PA_SIGNING_KEY`: path to the ECDSA P-256 private key (PKCS#8 PEM)
+    /// - `C2PA_SIGNING_CERT`: path to the certificate chain (PEM, leaf first)
+    pub fn from_env() -> Result<Self, String> {
+        let read = |name: &str| {
+            let path = std::env::var(name).map_err(|_| format!("{} is not set", name))?;
+            std::fs::read(&path).map_err(|e| format!("failed to read {}: {}", path, e))
+        };
+        Self::from_pem(&read("C2PA_SIGNING_KEY")?, &read("C2PA_SIGNING_CERT")?)
+    }
+
+    /// Signer for a PEM ECDSA P-256 key and its PEM certificate chain.
+    pub fn from_pem(key_pem: &[u8], cert_chain_pem: &[u8]) -> Result<Self, String> {
+        let encoding_key = EncodingKey::from_ec_pem(key_pem)
+            .map_err(|e| format!("invalid ES256 signing key: {}", e))?;
+        let x5c = pem_certificates(&String::from_utf8_lossy(cert_chain_pem));
+        if x5c.is_empty() {
+            return Err("certificate chain is empty".to_string());
+        }
+        Ok(Self { encoding_key, x5c })
+    }
+
+    /// Compact JWS of `claims`
+    pub fn sign(&self, claims: &SealClaims) -> Result<String, jsonwebtoken::errors::Error> {
+        let header = Header {
+            x5c: Some(self.x5c.clone()),
+            ..Header::new(Algorithm::ES256)
+        };
+        encode(&header, claims, &self.encoding_key)
+    }
+}
+
+/// Base64 DER of the certificates of a PEM chain, in order
+fn pem_certificates(pem: &str) -> Vec<String> {
+    pem.split("-----BEGIN CERTIFICATE-----")
+        .skip(1)
+        .filter_map(|block| block.split_once("-----END CERTIFICATE-----"))
+        .map(|(body, _)| body.split_whitespace().collect())
+        .collect()
+}
+
+#[cfg(all(test, feature = "c2pa"))]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+    use openssl::pkey::PKey;
+    use veritas_core::c2pa::generate_test_certificate;
+    use veritas_core::{generate_keypair, MediaType, MockQrng, SealBuilder};
+
+    #[tokio::test]
+    async fn test_signed_claims_verify_with_jwt_tooling() {
+        let (key_pem, chain_pem) = generate_test_certificate().unwrap();
+        let signer = JwsSigner::from_pem(&key_pem, &chain_pem).unwrap();
+        let (public_key, secret_key) = generate_keypair();
+        let seal = SealBuilder::new(b"JOSE".to_vec(), MediaType::Image)
+            .build_secure(&MockQrng::default(), &secret_key, &public_key)
+            .await
+            .unwrap();
+        let claims = SealClaims::from_seal(&seal, "did:web:veritas-q.com").unwrap();
+
+        let token = signer.sign(&claims).unwrap();
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.alg, Algorithm::ES256);
+        assert_eq!(header.x5c.unwrap().len(), 2);
+
+        let public_pem = PKey::private_key_from_pem(&key_pem)
+            .unwrap()
+            .public_key_to_pem()
+            .unwrap();
+        let mut validation = Validation::new(Algorithm::ES256);
+        validation.required_spec_claims.clear();
+        let verified = decode::<SealClaims>(
+            &token,
+            &DecodingKey::from_ec_pem(&public_pem).unwrap(),
+            &validation,
+        )
+        .unwrap();
+        assert_eq!(verified.claims, claims);
+        assert!(verified.claims.seal().unwrap().verify().unwrap());
+    }
+
+    #[test]
+    fn test_pem_certificates() {
+        let pem = "-----BEGIN CERTIFICATE-----\nQUJD\nREVG\n-----END CERTIFICATE-----\n\
+                   -----BEGIN CERTIFICATE-----\nR0hJ\n-----END CERTIFICATE-----\n";
+        assert_eq!(pem_certificates(pem), ["QUJDREVG", "R0hJ"]);
+        assert!(JwsSigner::from_pem(b"not a key", pem.as_bytes()).is_err());
+    }
+}
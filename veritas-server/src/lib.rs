@@ -3,20 +3,47 @@
 //! This library exposes the server components for use in integration tests.
 //! The main binary uses these same components.
 
+pub mod anchor_batcher;
+pub mod anchor_checks;
 pub mod auth;
+pub mod clustering;
+pub mod conditional;
 pub mod config;
 pub mod db;
 pub mod error;
+pub mod exports;
+pub mod geohash;
 pub mod handlers;
+pub mod janitor;
+pub mod jws;
+pub mod logging;
+pub mod maintenance;
 pub mod manifest_store;
 pub mod multipart;
+pub mod negotiation;
+pub mod notifications;
 pub mod openapi;
+pub mod qrng;
+pub mod rate_limit;
+pub mod readiness;
 pub mod routes;
+pub mod signing_keys;
+pub mod startup;
 pub mod state;
+pub mod streams;
+pub mod trust;
+pub mod uploads;
 pub mod validation;
+pub mod verification_cache;
+pub mod verification_pool;
+pub mod versioning;
 pub mod webauthn;
 
-pub use auth::{AuthenticatedUser, JwksCache, JwtClaims, OptionalAuth};
+pub use auth::{
+    ApiKeyAuth, AuthMode, AuthenticatedUser, Caller, IntrospectionConfig, JwksCache, JwtClaims,
+    JwtIssuerConfig, JwtIssuers, LocalAuth, LocalAuthConfig, OptionalAuth, ServiceAuth,
+    TokenIntrospector,
+};
 pub use config::Config;
 pub use db::{
     CreateSeal, CreateUser, DeviceInfo, Seal, SealListParams, SealListResponse, SealLocation,
@@ -28,5 +55,8 @@ pub use manifest_store::{
     ManifestInput, ManifestRecord, ManifestStoreError, PostgresManifestStore, SimilarityMatch,
 };
 pub use openapi::ApiDoc;
-pub use routes::{create_router, create_router_with_config, create_router_with_config_sync};
+pub use routes::{
+    create_router, create_router_with_config, create_router_with_config_sync,
+    create_router_with_janitor, create_router_with_pools,
+};
 pub use webauthn::{DeviceAttestation, StorageError, WebAuthnConfig, WebAuthnStorage};
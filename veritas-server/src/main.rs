@@ -1,13 +1,18 @@
 //! Veritas Server - REST API for quantum-authenticated media sealing
 //!
 //! Exposes veritas-core functionality via HTTP endpoints:
-//! - POST /seal - Create a seal for uploaded content
-//! - POST /verify - Verify a seal against content
+//! - POST /api/v1/seal - Create a seal for uploaded content
+//! - POST /api/v1/verify - Verify a seal against content
 //! - GET /health - Health check
+//!
+//! `veritas-server openapi` prints the OpenAPI spec and exits.
 
-use veritas_server::{create_router_with_config, Config};
+use veritas_server::janitor::SHUTDOWN_TIMEOUT;
+use veritas_server::logging::{self, LogFormat};
+use veritas_server::startup::self_check;
+use veritas_server::{create_router_with_janitor, ApiDoc, Config};
 
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use utoipa::OpenApi;
 
 /// Graceful shutdown signal handler
 ///
@@ -40,14 +45,29 @@ async fn shutdown_signal() {
 
 #[tokio::main]
 async fn main() {
-    // Initialize tracing subscriber with env filter (RUST_LOG)
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "veritas_server=info,tower_http=info".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    // Spec for client generation (clients/typescript), no config needed
+    if std::env::args().nth(1).as_deref() == Some("openapi") {
+        match ApiDoc::openapi().to_pretty_json() {
+            Ok(spec) => println!("{}", spec),
+            Err(e) => {
+                eprintln!(
+                    "veritas-server: failed to serialize the OpenAPI spec: {}",
+                    e
+                );
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Initialize logging: RUST_LOG filter, LOG_FORMAT output, secrets redacted
+    match LogFormat::from_env() {
+        Ok(format) => logging::init(format),
+        Err(e) => {
+            eprintln!("veritas-server: {}", e);
+            std::process::exit(1);
+        }
+    }
 
     // Load configuration from environment
     let config = Config::from_env();
@@ -58,17 +78,33 @@ async fn main() {
     println!("║   Quantum-Authenticated Media Sealing      ║");
     println!("╚════════════════════════════════════════════╝");
 
-    let app = create_router_with_config(&config).await;
+    // Fail fast on unusable configuration rather than on the first request
+    let pools = match self_check(&config).await {
+        Ok(pools) => pools,
+        Err(e) => {
+            tracing::error!("Startup check failed: {}", e);
+            eprintln!("veritas-server: refusing to start: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let (app, janitor) = create_router_with_janitor(&config, pools);
 
     tracing::info!("Listening on http://{}", addr);
-    tracing::info!("Endpoints: POST /seal, POST /verify, GET /health, GET /ready");
+    tracing::info!("Endpoints: POST /api/v1/seal, POST /api/v1/verify, GET /health, GET /ready");
     tracing::info!("OpenAPI: GET /docs (Swagger UI), GET /api-docs/openapi.json");
     tracing::info!(
         timeout_secs = config.timeout_secs,
         body_limit_mb = config.body_limit_mb,
+        upload_timeout_secs = config.upload_timeout_secs,
+        upload_body_limit_mb = config.upload_body_limit_mb,
+        lookup_timeout_secs = config.lookup_timeout_secs,
+        lookup_body_limit_kb = config.lookup_body_limit_kb,
         rate_limit_enabled = config.rate_limit_enabled,
         rate_limit_per_sec = config.rate_limit_per_sec,
         rate_limit_burst = config.rate_limit_burst,
+        production = config.production,
+        allow_mock_qrng = config.allow_mock_qrng,
         "Server configuration"
     );
 
@@ -81,6 +117,9 @@ async fn main() {
     .await
     .unwrap();
 
+    // Let maintenance tasks finish their current run
+    janitor.shutdown(SHUTDOWN_TIMEOUT).await;
+
     tracing::info!("Server shutdown complete");
 }
 
@@ -0,0 +1,195 @@
+//! Read-only maintenance mode
+//!
+//! During database maintenance windows the server can be switched to
+//! read-only: verification and lookups keep working, while sealing and every
+//! other write answer `503 READ_ONLY` with a `Retry-After` header. The mode
+//! is set at startup with `READ_ONLY=true` or at runtime by administrators
+//! (`PUT /api/v1/admin/maintenance`), and reported by `/ready`. Runtime
+//! changes apply to the instance that received them only.
+//!
+//! Writes are recognized by method: `GET`, `HEAD` and `OPTIONS` always pass,
+//! other methods only on [`READ_ONLY_ROUTES`]. Best-effort records made while
+//! serving reads (audit entries, verification counters) fail on their own if
+//! the database refuses them, without failing the request.
+
+use std::sync::{Arc, RwLock};
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, Method};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::error::ApiError;
+use crate::versioning::API_V1;
+
+/// Default `Retry-After` of writes refused in read-only mode, in seconds.
+pub const DEFAULT_MAINTENANCE_RETRY_AFTER_SECS: u64 = 300;
+
+/// Routes taking other methods than `GET` that only read, allowed in
+/// read-only mode (paths under `/api/v1`, legacy aliases included)
+pub const READ_ONLY_ROUTES: &[&str] = &[
+    "/verify",
+    "/resolve",
+    "/resolve/batch",
+    "/c2pa/verify",
+    "/auth/login",
+    "/admin/maintenance",
+];
+
+/// Current maintenance state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct MaintenanceStatus {
+    /// Whether sealing and writes are refused
+    #[schema(example = true)]
+    pub read_only: bool,
+    /// Reason given when read-only mode was turned on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Database upgrade")]
+    pub reason: Option<String>,
+    /// When read-only mode was turned on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, example = "2026-10-16T22:00:00Z")]
+    pub since: Option<DateTime<Utc>>,
+    /// `Retry-After` of refused writes, in seconds
+    #[schema(example = 300)]
+    pub retry_after_secs: u64,
+}
+
+/// Read-only switch shared by all requests
+#[derive(Debug)]
+pub struct MaintenanceMode {
+    status: RwLock<MaintenanceStatus>,
+}
+
+impl MaintenanceMode {
+    /// Start in read-only mode or not, refused writes being retried after
+    /// `retry_after_secs`
+    pub fn new(read_only: bool, retry_after_secs: u64) -> Self {
+        Self {
+            status: RwLock::new(MaintenanceStatus {
+                read_only,
+                reason: None,
+                since: read_only.then(Utc::now),
+                retry_after_secs,
+            }),
+        }
+    }
+
+    /// Current state
+    pub fn status(&self) -> MaintenanceStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    /// Whether writes are refused
+    pub fn is_read_only(&self) -> bool {
+        self.status.read().unwrap().read_only
+    }
+
+    /// Turn read-only mode on or off, keeping the current `Retry-After` when
+    /// `retry_after_secs` is `None`
+    pub fn set(
+        &self,
+        read_only: bool,
+        reason: Option<String>,
+        retry_after_secs: Option<u64>,
+    ) -> MaintenanceStatus {
+        let mut status = self.status.write().unwrap();
+        if read_only != status.read_only {
+            status.since = read_only.then(Utc::now);
+        }
+        status.read_only = read_only;
+        status.reason = reason.filter(|_| read_only);
+        if let Some(secs) = retry_after_secs {
+            status.retry_after_secs = secs;
+        }
+        status.clone()
+    }
+}
+
+impl Default for MaintenanceMode {
+    fn default() -> Self {
+        Self::new(false, DEFAULT_MAINTENANCE_RETRY_AFTER_SECS)
+    }
+}
+
+/// Whether a request may run in read-only mode
+pub fn allowed_in_read_only(method: &Method, path: &str) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+    let path = path.strip_prefix(API_V1).unwrap_or(path);
+    READ_ONLY_ROUTES.contains(&path.trim_end_matches('/'))
+}
+
+/// Middleware refusing writes while the server is read-only
+pub async fn reject_writes(
+    State(maintenance): State<Arc<MaintenanceMode>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !maintenance.is_read_only() || allowed_in_read_only(request.method(), request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let status = maintenance.status();
+    let mut response = ApiError::read_only(match &status.reason {
+        Some(reason) => format!("Read-only maintenance, writes are paused: {}", reason),
+        None => "Read-only maintenance, writes are paused".to_string(),
+    })
+    .into_response();
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(status.retry_after_secs),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_in_read_only() {
+        assert!(allowed_in_read_only(&Method::GET, "/api/v1/seals"));
+        assert!(allowed_in_read_only(&Method::HEAD, "/api/v1/uploads/abc"));
+        assert!(allowed_in_read_only(&Method::POST, "/api/v1/verify"));
+        assert!(allowed_in_read_only(&Method::POST, "/verify"));
+        assert!(allowed_in_read_only(&Method::POST, "/api/v1/resolve/batch"));
+        assert!(allowed_in_read_only(
+            &Method::PUT,
+            "/api/v1/admin/maintenance"
+        ));
+
+        assert!(!allowed_in_read_only(&Method::POST, "/api/v1/seal"));
+        assert!(!allowed_in_read_only(&Method::POST, "/seal"));
+        assert!(!allowed_in_read_only(&Method::POST, "/api/v1/c2pa/embed"));
+        assert!(!allowed_in_read_only(&Method::DELETE, "/api/v1/seals/abc"));
+        assert!(!allowed_in_read_only(&Method::POST, "/api/v1/verifyx"));
+    }
+
+    #[test]
+    fn test_set_maintenance_mode() {
+        let maintenance = MaintenanceMode::default();
+        assert!(!maintenance.is_read_only());
+        assert_eq!(maintenance.status().since, None);
+
+        let status = maintenance.set(true, Some("Database upgrade".into()), Some(60));
+        assert!(maintenance.is_read_only());
+        assert_eq!(status.reason.as_deref(), Some("Database upgrade"));
+        assert_eq!(status.retry_after_secs, 60);
+        let since = status.since.expect("read-only since");
+
+        // Updating the reason keeps the start of the window
+        let status = maintenance.set(true, Some("Still upgrading".into()), None);
+        assert_eq!(status.since, Some(since));
+        assert_eq!(status.retry_after_secs, 60);
+
+        let status = maintenance.set(false, Some("ignored".into()), None);
+        assert!(!status.read_only);
+        assert_eq!(status.reason, None);
+        assert_eq!(status.since, None);
+    }
+}
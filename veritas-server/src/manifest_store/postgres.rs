@@ -8,10 +8,13 @@ use super::{ManifestInput, ManifestRecord, ManifestStoreError, SimilarityMatch};
 
 /// PostgreSQL-backed manifest store.
 ///
-/// Provides persistence and similarity search for Veritas seals.
+/// Provides persistence and similarity search for Veritas seals. Lookups
+/// serving `/resolve` and the duplicates listing read from `read_pool` (a
+/// replica when configured); writes and the clustering job use the primary.
 #[derive(Clone)]
 pub struct PostgresManifestStore {
     pool: PgPool,
+    read_pool: PgPool,
 }
 
 /// Row type for database queries.
@@ -45,7 +48,16 @@ impl PostgresManifestStore {
     ///
     /// The caller is responsible for running migrations on the pool before use.
     pub fn from_pool(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            read_pool: pool.clone(),
+            pool,
+        }
+    }
+
+    /// Create a manifest store writing to `pool` and serving lookups from
+    /// `read_pool`.
+    pub fn from_pools(pool: PgPool, read_pool: PgPool) -> Self {
+        Self { pool, read_pool }
     }
 
     /// Store a new manifest record.
@@ -89,7 +101,7 @@ impl PostgresManifestStore {
             "#,
         )
         .bind(seal_id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&self.read_pool)
         .await?;
 
         Ok(row.map(Into::into))
@@ -176,7 +188,7 @@ impl PostgresManifestStore {
         .bind(phash_len)
         .bind(threshold as i32)
         .bind(limit as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&self.read_pool)
         .await?;
 
         let matches: Vec<SimilarityMatch> = rows
@@ -201,6 +213,139 @@ impl PostgresManifestStore {
         Ok(matches)
     }
 
+    /// Manifests with a perceptual hash that are not in a cluster yet, oldest
+    /// first.
+    pub async fn unclustered(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, Vec<u8>)>, ManifestStoreError> {
+        let rows: Vec<(Uuid, Vec<u8>)> = sqlx::query_as(
+            r#"
+            SELECT id, perceptual_hash
+            FROM manifests
+            WHERE cluster_id IS NULL AND perceptual_hash IS NOT NULL
+            ORDER BY created_at
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Clusters containing a manifest within `threshold` of `phash`.
+    pub async fn neighbor_clusters(
+        &self,
+        phash: &[u8],
+        threshold: u32,
+    ) -> Result<Vec<Uuid>, ManifestStoreError> {
+        let clusters: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT cluster_id
+            FROM manifests
+            WHERE cluster_id IS NOT NULL
+              AND length(perceptual_hash) = $2
+              AND bit_count(perceptual_hash::bit varying # $1::bit varying) <= $3
+            "#,
+        )
+        .bind(phash)
+        .bind(phash.len() as i32)
+        .bind(threshold as i32)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(clusters)
+    }
+
+    /// Put a manifest in `cluster_id`, moving the members of the `merged`
+    /// clusters into it as well.
+    pub async fn assign_cluster(
+        &self,
+        id: Uuid,
+        cluster_id: Uuid,
+        merged: &[Uuid],
+    ) -> Result<(), ManifestStoreError> {
+        let mut tx = self.pool.begin().await?;
+
+        if !merged.is_empty() {
+            sqlx::query("UPDATE manifests SET cluster_id = $1 WHERE cluster_id = ANY($2)")
+                .bind(cluster_id)
+                .bind(merged)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query("UPDATE manifests SET cluster_id = $1 WHERE id = $2")
+            .bind(cluster_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Other members of a seal's cluster, closest first, with the cluster
+    /// size excluding the seal.
+    ///
+    /// Returns `None` if the seal's manifest is not clustered yet.
+    pub async fn cluster_members(
+        &self,
+        seal_id: &str,
+        limit: usize,
+    ) -> Result<Option<(Vec<SimilarityMatch>, i64)>, ManifestStoreError> {
+        let cluster_id: Option<Option<Uuid>> =
+            sqlx::query_scalar("SELECT cluster_id FROM manifests WHERE seal_id = $1")
+                .bind(seal_id)
+                .fetch_optional(&self.read_pool)
+                .await?;
+        let Some(Some(cluster_id)) = cluster_id else {
+            return Ok(None);
+        };
+
+        #[derive(FromRow)]
+        struct MemberRow {
+            #[sqlx(flatten)]
+            manifest: ManifestRow,
+            hamming_distance: i32,
+            total: i64,
+        }
+
+        let rows: Vec<MemberRow> = sqlx::query_as(
+            r#"
+            SELECT m.id, m.seal_id, m.perceptual_hash, m.image_hash, m.seal_cbor, m.media_type,
+                   m.created_at,
+                   bit_count(m.perceptual_hash::bit varying # t.perceptual_hash::bit varying)
+                       AS hamming_distance,
+                   COUNT(*) OVER () AS total
+            FROM manifests m
+            JOIN manifests t ON t.seal_id = $1
+            WHERE m.cluster_id = $2
+              AND m.seal_id <> $1
+              AND length(m.perceptual_hash) = length(t.perceptual_hash)
+            ORDER BY hamming_distance ASC, m.created_at ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(seal_id)
+        .bind(cluster_id)
+        .bind(limit as i64)
+        .fetch_all(&self.read_pool)
+        .await?;
+
+        let total = rows.first().map_or(0, |row| row.total);
+        let members = rows
+            .into_iter()
+            .map(|row| SimilarityMatch {
+                record: row.manifest.into(),
+                hamming_distance: row.hamming_distance as u32,
+            })
+            .collect();
+
+        Ok(Some((members, total)))
+    }
+
     /// Delete a manifest by seal_id.
     pub async fn delete(&self, seal_id: &str) -> Result<bool, ManifestStoreError> {
         let result = sqlx::query("DELETE FROM manifests WHERE seal_id = $1")
@@ -211,6 +356,15 @@ impl PostgresManifestStore {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Check that the manifests table is reachable.
+    pub async fn ping(&self) -> Result<(), ManifestStoreError> {
+        sqlx::query("SELECT 1 FROM manifests LIMIT 1")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
     /// Count total manifests in the store.
     pub async fn count(&self) -> Result<i64, ManifestStoreError> {
         let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM manifests")
@@ -4,22 +4,177 @@
 //! reducing code duplication across handlers.
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use axum::extract::Multipart;
 use serde::de::DeserializeOwned;
+use tempfile::TempPath;
+use tokio::io::AsyncWriteExt;
+use veritas_core::ContentHash;
 
 use crate::error::ApiError;
-use crate::validation::{validate_content_type, validate_file_size};
+use crate::negotiation::APPLICATION_CBOR;
+use crate::validation::{validate_file_size, UploadPolicy};
+
+/// Leading bytes of a spooled upload kept in memory for format detection
+/// and image header checks
+pub const SPOOL_HEAD_SIZE: usize = 256 * 1024;
 
 /// Represents a file uploaded via multipart form
 #[derive(Debug, Clone)]
 pub struct FileField {
-    /// File data bytes
+    /// File data bytes (only the first [`SPOOL_HEAD_SIZE`] bytes when `spooled` is set)
     pub data: Vec<u8>,
+    /// Temp file holding the full upload, when it exceeded the spool threshold
+    pub spooled: Option<SpooledFile>,
     /// Content-Type from the multipart field (if provided)
     pub content_type: Option<String>,
     /// Original filename from the multipart field (if provided)
     pub file_name: Option<String>,
+    /// MIME type detected from the file's magic bytes (if recognized)
+    pub detected_mime: Option<&'static str>,
+}
+
+impl FileField {
+    /// Size of the upload in bytes
+    pub fn len(&self) -> usize {
+        self.spooled
+            .as_ref()
+            .map_or(self.data.len(), |spooled| spooled.len)
+    }
+
+    /// Returns true if the upload is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Full upload contents, read back from disk if the upload was spooled
+    pub async fn contents(&self) -> Result<Vec<u8>, ApiError> {
+        match &self.spooled {
+            Some(spooled) => tokio::fs::read(spooled.path()).await.map_err(|e| {
+                tracing::error!(error = %e, "Failed to read spooled upload");
+                ApiError::internal("Failed to read uploaded file")
+            }),
+            None => Ok(self.data.clone()),
+        }
+    }
+}
+
+/// An upload stored on disk
+///
+/// Spooled multipart uploads are temp files deleted when the last clone is
+/// dropped; files wrapped with [`SpooledFile::from_path`] are left in place.
+#[derive(Debug, Clone)]
+pub struct SpooledFile {
+    path: PathBuf,
+    len: usize,
+    _temp: Option<Arc<TempPath>>,
+}
+
+impl SpooledFile {
+    /// Wrap an existing file of `len` bytes, which the caller keeps ownership of
+    pub fn from_path(path: PathBuf, len: usize) -> Self {
+        Self {
+            path,
+            len,
+            _temp: None,
+        }
+    }
+
+    /// Location of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Hash the spooled file without loading it into memory
+    pub async fn content_hash(&self) -> Result<ContentHash, ApiError> {
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || {
+            std::fs::File::open(path)
+                .map(std::io::BufReader::new)
+                .and_then(ContentHash::from_reader)
+        })
+        .await
+        .map_err(|e| ApiError::internal(format!("Hashing task failed: {}", e)))?
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to hash spooled upload");
+            ApiError::internal("Failed to read uploaded file")
+        })
+    }
+}
+
+/// Write buffered upload data to a new temp file.
+async fn spool(data: &[u8]) -> Result<(tokio::fs::File, TempPath), ApiError> {
+    let (file, path) = tempfile::NamedTempFile::new()
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to create upload spool file");
+            ApiError::internal("Failed to store uploaded file")
+        })?
+        .into_parts();
+    let mut file = tokio::fs::File::from_std(file);
+    write_spool(&mut file, data).await?;
+    Ok((file, path))
+}
+
+async fn write_spool(file: &mut tokio::fs::File, data: &[u8]) -> Result<(), ApiError> {
+    file.write_all(data).await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to write upload spool file");
+        ApiError::internal("Failed to store uploaded file")
+    })
+}
+
+/// Detect a file's MIME type from its leading magic bytes.
+///
+/// Covers the image, video, audio and document formats the seal endpoints
+/// handle. Returns `None` for anything else, including plain text.
+pub fn detect_mime(data: &[u8]) -> Option<&'static str> {
+    let at = |offset: usize, magic: &[u8]| data.get(offset..offset + magic.len()) == Some(magic);
+
+    // ISO base media (MP4, MOV, HEIC, AVIF): size, then `ftyp` and a major brand
+    if at(4, b"ftyp") {
+        return match data.get(8..12)? {
+            b"heic" | b"heix" | b"mif1" | b"msf1" => Some("image/heic"),
+            b"avif" | b"avis" => Some("image/avif"),
+            b"qt  " => Some("video/quicktime"),
+            b"M4A " | b"M4B " => Some("audio/mp4"),
+            _ => Some("video/mp4"),
+        };
+    }
+    if at(0, b"RIFF") {
+        return match data.get(8..12)? {
+            b"WEBP" => Some("image/webp"),
+            b"WAVE" => Some("audio/wav"),
+            b"AVI " => Some("video/x-msvideo"),
+            _ => None,
+        };
+    }
+
+    let mime = if at(0, b"\xFF\xD8\xFF") {
+        "image/jpeg"
+    } else if at(0, b"\x89PNG\r\n\x1A\n") {
+        "image/png"
+    } else if at(0, b"GIF87a") || at(0, b"GIF89a") {
+        "image/gif"
+    } else if at(0, b"II*\0") || at(0, b"MM\0*") {
+        "image/tiff"
+    } else if at(0, b"BM") && data.len() >= 26 {
+        "image/bmp"
+    } else if at(0, b"\x1A\x45\xDF\xA3") {
+        "video/webm"
+    } else if at(0, b"ID3") || (data.len() >= 2 && data[0] == 0xFF && data[1] & 0xE6 == 0xE2) {
+        // ID3 tag, or an MPEG audio frame header (sync bits, layer III)
+        "audio/mpeg"
+    } else if at(0, b"fLaC") {
+        "audio/flac"
+    } else if at(0, b"OggS") {
+        "audio/ogg"
+    } else if veritas_core::document::is_pdf(data) {
+        "application/pdf"
+    } else {
+        return None;
+    };
+    Some(mime)
 }
 
 /// Parsed multipart form fields
@@ -32,6 +187,8 @@ pub struct MultipartFields {
     file: Option<FileField>,
     /// Text fields indexed by name
     text_fields: HashMap<String, String>,
+    /// Raw `application/cbor` fields indexed by name
+    cbor_fields: HashMap<String, Vec<u8>>,
 }
 
 impl MultipartFields {
@@ -57,11 +214,43 @@ impl MultipartFields {
         multipart: &mut Multipart,
         validate_content_type_flag: bool,
         max_file_size: usize,
+    ) -> Result<Self, ApiError> {
+        let policy = validate_content_type_flag.then(UploadPolicy::default);
+        Self::read(multipart, policy.as_ref(), max_file_size, None).await
+    }
+
+    /// Parse all fields, checking the file's Content-Type and size against
+    /// an upload policy
+    ///
+    /// Files larger than the policy's spool threshold are written to a temp
+    /// file instead of being buffered in memory (see [`FileField::spooled`]).
+    ///
+    /// Limits that depend on the media type are checked separately with
+    /// [`UploadPolicy::check_upload`], once the handler has resolved it.
+    pub async fn parse_with_policy(
+        multipart: &mut Multipart,
+        policy: &UploadPolicy,
+    ) -> Result<Self, ApiError> {
+        Self::read(
+            multipart,
+            Some(policy),
+            policy.max_file_size,
+            Some(policy.spool_threshold),
+        )
+        .await
+    }
+
+    async fn read(
+        multipart: &mut Multipart,
+        type_policy: Option<&UploadPolicy>,
+        max_file_size: usize,
+        spool_threshold: Option<usize>,
     ) -> Result<Self, ApiError> {
         let mut file: Option<FileField> = None;
         let mut text_fields = HashMap::new();
+        let mut cbor_fields = HashMap::new();
 
-        while let Some(field) = multipart
+        while let Some(mut field) = multipart
             .next_field()
             .await
             .map_err(|e| ApiError::bad_request(format!("Failed to parse multipart: {}", e)))?
@@ -74,25 +263,64 @@ impl MultipartFields {
                 let file_name = field.file_name().map(|s| s.to_string());
 
                 // Validate Content-Type if requested
-                if validate_content_type_flag {
-                    validate_content_type(content_type.as_deref())?;
+                if let Some(policy) = type_policy {
+                    policy.check_content_type(content_type.as_deref())?;
                 }
 
-                // Read file data
-                let data = field
-                    .bytes()
+                // Read file data chunk by chunk, validating the size as it
+                // grows and moving to a temp file past the spool threshold
+                let mut data = Vec::new();
+                let mut spooled: Option<(tokio::fs::File, TempPath)> = None;
+                let mut len = 0;
+                while let Some(chunk) = field
+                    .chunk()
                     .await
                     .map_err(|e| ApiError::bad_request(format!("Failed to read file: {}", e)))?
-                    .to_vec();
+                {
+                    len += chunk.len();
+                    validate_file_size(len, max_file_size)?;
 
-                // Validate file size
-                validate_file_size(data.len(), max_file_size)?;
+                    match spooled.as_mut() {
+                        Some((spool_file, _)) => write_spool(spool_file, &chunk).await?,
+                        None => {
+                            data.extend_from_slice(&chunk);
+                            if spool_threshold.is_some_and(|threshold| data.len() > threshold) {
+                                spooled = Some(spool(&data).await?);
+                                data.truncate(SPOOL_HEAD_SIZE);
+                            }
+                        }
+                    }
+                }
+
+                let spooled = match spooled {
+                    Some((mut spool_file, path)) => {
+                        spool_file.flush().await.map_err(|e| {
+                            tracing::error!(error = %e, "Failed to flush upload spool file");
+                            ApiError::internal("Failed to store uploaded file")
+                        })?;
+                        Some(SpooledFile {
+                            path: path.to_path_buf(),
+                            len,
+                            _temp: Some(Arc::new(path)),
+                        })
+                    }
+                    None => None,
+                };
 
+                let detected_mime = detect_mime(&data);
                 file = Some(FileField {
                     data,
+                    spooled,
                     content_type,
                     file_name,
+                    detected_mime,
                 });
+            } else if field.content_type() == Some(APPLICATION_CBOR) {
+                // Raw CBOR field (e.g. a seal), not valid UTF-8
+                let value = field.bytes().await.map_err(|e| {
+                    ApiError::bad_request(format!("Failed to read field '{}': {}", name, e))
+                })?;
+                cbor_fields.insert(name, value.to_vec());
             } else {
                 // Text field
                 let value = field.text().await.map_err(|e| {
@@ -102,7 +330,11 @@ impl MultipartFields {
             }
         }
 
-        Ok(Self { file, text_fields })
+        Ok(Self {
+            file,
+            text_fields,
+            cbor_fields,
+        })
     }
 
     /// Get the file field (required)
@@ -114,6 +346,15 @@ impl MultipartFields {
         })
     }
 
+    /// Take ownership of the file field (required)
+    ///
+    /// Returns an error if no file was uploaded.
+    pub fn take_file(&mut self) -> Result<FileField, ApiError> {
+        self.file.take().ok_or_else(|| {
+            ApiError::bad_request("No file provided. Use 'file' field in multipart form.")
+        })
+    }
+
     /// Get the file field (optional)
     pub fn get_file(&self) -> Option<&FileField> {
         self.file.as_ref()
@@ -126,6 +367,13 @@ impl MultipartFields {
         self.text_fields.get(name).map(|s| s.as_str())
     }
 
+    /// Take ownership of a field sent as `application/cbor`
+    ///
+    /// Returns `None` if the field is missing or was sent as text.
+    pub fn take_cbor(&mut self, name: &str) -> Option<Vec<u8>> {
+        self.cbor_fields.remove(name)
+    }
+
     /// Get a text field parsed as a boolean
     ///
     /// Returns `true` if the field value is "true" (case-insensitive), `false` otherwise.
@@ -174,6 +422,7 @@ mod tests {
         let fields = MultipartFields {
             file: None,
             text_fields,
+            cbor_fields: HashMap::new(),
         };
 
         assert_eq!(fields.get_bool("flag1"), true);
@@ -191,6 +440,7 @@ mod tests {
         let fields = MultipartFields {
             file: None,
             text_fields,
+            cbor_fields: HashMap::new(),
         };
 
         assert_eq!(fields.get_text("name"), Some("test"));
@@ -202,8 +452,42 @@ mod tests {
         let fields = MultipartFields {
             file: None,
             text_fields: HashMap::new(),
+            cbor_fields: HashMap::new(),
         };
 
         assert!(fields.require_file().is_err());
     }
+
+    #[test]
+    fn test_detect_mime() {
+        assert_eq!(
+            detect_mime(b"\xFF\xD8\xFF\xE0\0\x10JFIF"),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            detect_mime(b"\x89PNG\r\n\x1A\n\0\0\0\rIHDR"),
+            Some("image/png")
+        );
+        assert_eq!(detect_mime(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(detect_mime(b"RIFF\0\0\0\0WAVEfmt "), Some("audio/wav"));
+        assert_eq!(
+            detect_mime(b"\0\0\0\x18ftypisom\0\0\0\0"),
+            Some("video/mp4")
+        );
+        assert_eq!(
+            detect_mime(b"\0\0\0\x18ftypheic\0\0\0\0"),
+            Some("image/heic")
+        );
+        assert_eq!(detect_mime(b"ID3\x04\0\0"), Some("audio/mpeg"));
+        assert_eq!(detect_mime(b"%PDF-1.7\n"), Some("application/pdf"));
+    }
+
+    #[test]
+    fn test_detect_mime_unknown() {
+        assert_eq!(detect_mime(b""), None);
+        assert_eq!(detect_mime(b"plain text content"), None);
+        assert_eq!(detect_mime(b"RIFF\0\0\0\0XXXX"), None);
+        // Too short to carry a brand
+        assert_eq!(detect_mime(b"\0\0\0\x18ftyp"), None);
+    }
 }
@@ -0,0 +1,122 @@
+//! Content negotiation of seal responses
+//!
+//! Seals are CBOR documents; JSON responses wrap them in base64 or expand
+//! them into verbose fields. Clients preferring `application/cbor` in their
+//! `Accept` header get the raw seal bytes instead, with `Vary: Accept` so
+//! caches keep both representations apart.
+
+use axum::http::header::{ACCEPT, CONTENT_TYPE, VARY};
+use axum::http::{HeaderMap, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::state::AppState;
+
+/// Media type of raw CBOR seals
+pub const APPLICATION_CBOR: &str = "application/cbor";
+
+/// Whether `Accept` prefers CBOR: `application/cbor` is listed with a
+/// quality at least that of JSON (`application/json` or `application/*`)
+///
+/// Wildcards never select CBOR, so clients that do not ask for it keep
+/// getting JSON.
+pub fn wants_cbor(headers: &HeaderMap) -> bool {
+    let mut cbor: f32 = 0.0;
+    let mut json: f32 = 0.0;
+    for range in headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+    {
+        let mut params = range.split(';').map(str::trim);
+        let media_type = params.next().unwrap_or_default().to_ascii_lowercase();
+        let quality = params
+            .filter_map(|param| param.strip_prefix("q="))
+            .find_map(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0);
+        match media_type.as_str() {
+            APPLICATION_CBOR => cbor = cbor.max(quality),
+            "application/json" | "application/*" => json = json.max(quality),
+            _ => {}
+        }
+    }
+    cbor > 0.0 && cbor >= json
+}
+
+/// Raw CBOR response body
+pub struct Cbor(pub Vec<u8>);
+
+impl IntoResponse for Cbor {
+    fn into_response(self) -> Response {
+        ([(CONTENT_TYPE, APPLICATION_CBOR)], self.0).into_response()
+    }
+}
+
+/// A response whose representation depends on the request's `Accept` header
+pub struct Negotiated<T>(pub T);
+
+impl<T: IntoResponse> IntoResponse for Negotiated<T> {
+    fn into_response(self) -> Response {
+        let mut response = self.0.into_response();
+        response
+            .headers_mut()
+            .append(VARY, HeaderValue::from_static("accept"));
+        response
+    }
+}
+
+/// Raw CBOR of a stored seal, from the manifest store
+pub async fn stored_seal_cbor(state: &AppState, seal_id: Uuid) -> Result<Vec<u8>, ApiError> {
+    let store = state
+        .manifest_store
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Manifest store not configured"))?;
+    store
+        .get_by_seal_id(&seal_id.to_string())
+        .await
+        .map_err(|e| {
+            tracing::error!(seal_id = %seal_id, error = %e, "Failed to load seal CBOR");
+            ApiError::internal("A database error occurred")
+        })?
+        .map(|record| record.seal_cbor)
+        .ok_or_else(|| ApiError::not_found("Seal data not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_wants_cbor() {
+        assert!(!wants_cbor(&HeaderMap::new()));
+        assert!(!wants_cbor(&accept("*/*")));
+        assert!(!wants_cbor(&accept("application/json")));
+        assert!(wants_cbor(&accept("application/cbor")));
+        assert!(wants_cbor(&accept("Application/CBOR")));
+        assert!(wants_cbor(&accept("application/cbor, */*;q=0.8")));
+        assert!(wants_cbor(&accept(
+            "application/json;q=0.5, application/cbor"
+        )));
+        assert!(!wants_cbor(&accept(
+            "application/json, application/cbor;q=0.9"
+        )));
+        assert!(!wants_cbor(&accept("application/cbor;q=0")));
+        // Ties go to CBOR, which the client named explicitly
+        assert!(wants_cbor(&accept("application/*, application/cbor")));
+    }
+
+    #[test]
+    fn test_negotiated_response_headers() {
+        let response = Negotiated(Cbor(vec![0xa0])).into_response();
+        assert_eq!(response.headers()[CONTENT_TYPE], APPLICATION_CBOR);
+        assert_eq!(response.headers()[VARY], "accept");
+    }
+}
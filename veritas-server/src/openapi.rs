@@ -1,9 +1,19 @@
 //! OpenAPI documentation configuration
 //!
 //! Generates OpenAPI 3.0 specification for the Veritas Q Truth API.
+//!
+//! Every route of `routes.rs` is listed in `paths`, with the schemas it
+//! uses. Error responses share the [`ErrorResponse`] envelope and the
+//! `clerk_token` / `api_key` security schemes are declared by modifiers.
+//! `veritas-server openapi` prints the spec; CI generates the TypeScript
+//! client of `clients/typescript` from it.
 
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{Content, Ref, RefOr};
 use utoipa::OpenApi;
 
+use crate::error::ErrorResponse;
+
 /// Veritas Q Truth API - OpenAPI Documentation
 #[derive(OpenApi)]
 #[openapi(
@@ -51,10 +61,13 @@ Veritas Q provides **unforgeable digital seals** for media content using:
     ),
     tags(
         (name = "Sealing", description = "Create quantum-authenticated seals for media content"),
-        (name = "Seals", description = "List, retrieve, and export user's seals with C2PA interoperability"),
+        (name = "Seals", description = "List, retrieve, export, share, tag, collect, report on, and print QR labels for user's seals with C2PA interoperability"),
         (name = "Resolution", description = "Resolve seals by perceptual hash similarity (soft binding)"),
+        (name = "Users", description = "Account sync, profile, usage statistics, data export and deletion; self-hosted registration and login"),
+        (name = "API Keys", description = "Scoped API keys authenticating machine clients with the X-API-Key header"),
         (name = "Verification", description = "Verify seals against content to detect tampering"),
         (name = "WebAuthn", description = "Device attestation via WebAuthn/FIDO2 for hardware-backed authentication"),
+        (name = "Admin", description = "Platform statistics, user management, seal revocation and legal hold, signing-key revocation, organization branding and retention (admin role required)"),
         (name = "C2PA", description = "C2PA manifest operations for Content Authenticity Initiative compatibility"),
         (name = "Health", description = "Service health and readiness endpoints")
     ),
@@ -62,38 +75,229 @@ Veritas Q provides **unforgeable digital seals** for media content using:
         crate::handlers::health::health,
         crate::handlers::health::ready,
         crate::handlers::seal::seal_handler,
+        crate::handlers::anchors::anchor_status_handler,
         crate::handlers::resolve::resolve_handler,
+        crate::handlers::resolve::resolve_batch_handler,
         crate::handlers::verify::verify_handler,
+        crate::handlers::signing_keys::list_signing_keys_handler,
+        crate::handlers::signing_keys::did_document_handler,
+        crate::handlers::auth::register_handler,
+        crate::handlers::auth::login_handler,
+        crate::handlers::user::sync_user_handler,
+        crate::handlers::user::get_current_user_handler,
+        crate::handlers::user::delete_user_handler,
+        crate::handlers::user::get_current_user_stats_handler,
+        crate::handlers::data_export::export_user_data_handler,
+        crate::handlers::data_export::download_export_handler,
         crate::handlers::seals::list_user_seals_handler,
+        crate::handlers::seals::seal_map_handler,
         crate::handlers::seals::get_user_seal_handler,
         crate::handlers::seals::export_seal_handler,
+        crate::handlers::import::import_seal_handler,
+        crate::handlers::batch_export::batch_export_handler,
+        crate::handlers::batch_export::batch_export_status_handler,
+        crate::handlers::sync::sync_push_handler,
+        crate::handlers::sync::sync_pull_handler,
+        crate::handlers::seals::seal_report_handler,
+        crate::handlers::seals::seal_qr_handler,
+        crate::handlers::duplicates::seal_duplicates_handler,
+        crate::handlers::verifications::seal_verifications_handler,
+        crate::handlers::api_keys::create_api_key_handler,
+        crate::handlers::api_keys::list_api_keys_handler,
+        crate::handlers::api_keys::revoke_api_key_handler,
+        crate::handlers::share::create_share_handler,
+        crate::handlers::share::list_shares_handler,
+        crate::handlers::share::revoke_share_handler,
+        crate::handlers::share::shared_seal_handler,
+        crate::handlers::tags::get_seal_tags_handler,
+        crate::handlers::tags::set_seal_tags_handler,
+        crate::handlers::tags::list_tags_handler,
+        crate::handlers::tags::rename_tag_handler,
+        crate::handlers::tags::delete_tag_handler,
+        crate::handlers::collections::create_collection_handler,
+        crate::handlers::collections::list_collections_handler,
+        crate::handlers::collections::get_collection_handler,
+        crate::handlers::collections::update_collection_handler,
+        crate::handlers::collections::delete_collection_handler,
+        crate::handlers::collections::add_collection_seal_handler,
+        crate::handlers::collections::remove_collection_seal_handler,
+        crate::handlers::stream::start_stream_handler,
+        crate::handlers::stream::stream_chunk_handler,
+        crate::handlers::stream::finish_stream_handler,
+        crate::handlers::upload::create_upload_handler,
+        crate::handlers::upload::upload_status_handler,
+        crate::handlers::upload::upload_chunk_handler,
+        crate::handlers::upload::finalize_upload_handler,
+        crate::handlers::upload::delete_upload_handler,
+        crate::handlers::admin::admin_stats_handler,
+        crate::handlers::admin::admin_tasks_handler,
+        crate::handlers::admin::get_maintenance_handler,
+        crate::handlers::admin::update_maintenance_handler,
+        crate::handlers::admin::admin_list_users_handler,
+        crate::handlers::admin::suspend_user_handler,
+        crate::handlers::admin::unsuspend_user_handler,
+        crate::handlers::admin::update_user_tier_handler,
+        crate::handlers::admin::update_user_organization_handler,
+        crate::handlers::admin::list_revoked_seals_handler,
+        crate::handlers::admin::revoke_seal_handler,
+        crate::handlers::admin::reinstate_seal_handler,
+        crate::handlers::admin::update_legal_hold_handler,
+        crate::handlers::admin::revoke_signing_key_handler,
+        crate::handlers::organizations::get_organization_settings_handler,
+        crate::handlers::organizations::update_organization_settings_handler,
+        crate::handlers::organizations::delete_organization_settings_handler,
+        crate::handlers::organizations::get_retention_policy_handler,
+        crate::handlers::organizations::update_retention_policy_handler,
+        crate::handlers::organizations::delete_retention_policy_handler,
+        crate::handlers::organizations::branding_handler,
         crate::webauthn::handlers::start_registration,
         crate::webauthn::handlers::finish_registration,
         crate::webauthn::handlers::start_authentication,
         crate::webauthn::handlers::finish_authentication,
+        crate::webauthn::handlers::start_capture_session,
     ),
     components(
         schemas(
+            crate::error::ErrorResponse,
             crate::handlers::HealthResponse,
             crate::handlers::ReadyResponse,
+            crate::readiness::DependencyHealth,
+            crate::readiness::DependencyStatus,
+            crate::readiness::ReadinessStatus,
             crate::handlers::SealResponse,
+            crate::qrng::QrngFetch,
+            crate::handlers::AnchorStatusResponse,
             crate::handlers::ResolveRequest,
             crate::handlers::ResolveResponse,
             crate::handlers::ResolveMatch,
+            crate::handlers::BatchResolveItem,
+            crate::handlers::BatchResolveRequest,
+            crate::handlers::BatchResolveResult,
+            crate::handlers::BatchResolveResponse,
             crate::handlers::VerifyResponse,
+            crate::handlers::TamperedSegmentInfo,
+            crate::handlers::ChangedRegionInfo,
+            crate::handlers::LivenessInfo,
+            crate::handlers::AnchorInfo,
+            crate::handlers::PolicyInfo,
+            crate::handlers::PolicyViolationInfo,
+            crate::handlers::SigningKeyInfo,
+            crate::handlers::SigningKeyRecord,
+            crate::handlers::SigningKeyListResponse,
+            // Users
+            crate::handlers::RegisterRequest,
+            crate::handlers::LoginRequest,
+            crate::handlers::AuthTokenResponse,
+            crate::handlers::SyncUserRequest,
+            crate::handlers::SyncUserResponse,
+            crate::handlers::CurrentUserResponse,
+            crate::handlers::DeleteUserResponse,
+            crate::handlers::UserStatsResponse,
+            crate::handlers::SealCounts,
+            crate::handlers::StorageUsage,
+            crate::handlers::QuotaUsage,
+            crate::db::ThirdPartyActivity,
+            crate::handlers::DataExportResponse,
+            crate::exports::ExportStatus,
             // Seal list and detail
             crate::db::SealRecord,
             crate::db::SealListResponse,
             crate::db::SealMetadata,
             crate::handlers::SealDetailResponse,
+            crate::handlers::ImportSealResponse,
+            crate::handlers::BatchExportRequest,
+            crate::handlers::BatchExportFilter,
+            // Offline sync
+            crate::handlers::SyncPushRequest,
+            crate::handlers::SyncPushSeal,
+            crate::handlers::SyncPushResponse,
+            crate::handlers::SyncPushResult,
+            crate::handlers::SyncPushStatus,
+            crate::handlers::SyncPullResponse,
+            crate::handlers::SyncSealRecord,
             crate::db::TrustTier,
+            // Seal map
+            crate::handlers::SealMapResponse,
+            crate::handlers::SealMapCluster,
+            crate::geohash::CellBounds,
             // Export
             crate::handlers::ExportResponse,
             crate::handlers::JsonExportResponse,
             crate::handlers::C2paExportResponse,
+            // Share links
+            crate::handlers::CreateShareRequest,
+            crate::handlers::CreateShareResponse,
+            crate::handlers::ListSharesResponse,
+            crate::handlers::SharedSealResponse,
+            crate::handlers::DuplicatesResponse,
+            crate::handlers::DuplicateSeal,
+            crate::handlers::VerificationTrendsResponse,
+            crate::db::DailyVerifications,
+            crate::db::OriginCount,
+            crate::db::SealShareRecord,
+            // Tags and collections
+            crate::handlers::SetSealTagsRequest,
+            crate::handlers::SealTagsResponse,
+            crate::handlers::ListTagsResponse,
+            crate::handlers::RenameTagRequest,
+            crate::db::TagCount,
+            crate::handlers::CollectionRequest,
+            crate::handlers::ListCollectionsResponse,
+            crate::db::Collection,
+            // API keys
+            crate::handlers::CreateApiKeyRequest,
+            crate::handlers::CreateApiKeyResponse,
+            crate::handlers::ListApiKeysResponse,
+            crate::db::ApiKeyRecord,
+            crate::db::ApiKeyScope,
+            // Live streams
+            crate::handlers::StartStreamRequest,
+            crate::handlers::StartStreamResponse,
+            crate::handlers::StreamChunkResponse,
+            crate::handlers::CheckpointResponse,
+            crate::handlers::FinishStreamResponse,
+            // Resumable uploads
+            crate::handlers::CreateUploadRequest,
+            crate::handlers::UploadResponse,
+            crate::handlers::FinalizeUploadRequest,
+            crate::handlers::seal::LocationInput,
+            crate::handlers::seal::LivenessInput,
+            // Admin
+            crate::handlers::AdminStatsResponse,
+            crate::handlers::AdminTasksResponse,
+            crate::janitor::TaskStats,
+            crate::handlers::UpdateMaintenanceRequest,
+            crate::maintenance::MaintenanceStatus,
+            crate::handlers::AdminUserListResponse,
+            crate::handlers::AdminUserRecord,
+            crate::handlers::UpdateTierRequest,
+            crate::handlers::UpdateOrganizationRequest,
+            crate::handlers::RevokeSealRequest,
+            crate::handlers::UpdateLegalHoldRequest,
+            crate::handlers::LegalHoldResponse,
+            crate::db::UserResponse,
+            crate::db::UserRole,
+            crate::db::UserStats,
+            crate::db::SealTotals,
+            crate::db::DailySealStats,
+            crate::db::QrngSourceStats,
+            crate::db::TrustTierStats,
+            crate::db::AnchorLatencyStats,
+            // Organization branding
+            crate::db::OrganizationSettings,
+            crate::db::Branding,
+            crate::handlers::UpdateOrganizationSettingsRequest,
+            crate::db::RetentionPolicy,
+            crate::handlers::UpdateRetentionPolicyRequest,
             // WebAuthn
             crate::webauthn::StartRegistrationRequest,
+            crate::webauthn::StartRegistrationResponse,
+            crate::webauthn::FinishRegistrationRequest,
             crate::webauthn::StartAuthenticationRequest,
+            crate::webauthn::StartAuthenticationResponse,
+            crate::webauthn::FinishAuthenticationRequest,
+            crate::webauthn::StartCaptureSessionRequest,
+            crate::webauthn::CaptureSessionResponse,
             crate::webauthn::DeviceAttestationResponse,
             crate::webauthn::DeviceAttestation,
             crate::webauthn::DeviceModel,
@@ -101,10 +305,77 @@ Veritas Q provides **unforgeable digital seals** for media content using:
             crate::webauthn::AttestationFormat,
         )
     ),
-    modifiers(&C2paModifier)
+    modifiers(&C2paModifier, &SecuritySchemes, &ErrorEnvelope)
 )]
 pub struct ApiDoc;
 
+/// Declares the security schemes handlers refer to
+struct SecuritySchemes;
+
+impl utoipa::Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "clerk_token",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some(
+                        "Clerk session token, token of a JWT_ISSUERS_FILE issuer or of a \
+                         self-hosted account, or an introspected opaque token",
+                    ))
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                crate::auth::API_KEY_HEADER.as_str(),
+                "Scoped API key (vq_...) of a machine client",
+            ))),
+        );
+    }
+}
+
+/// Gives every 4xx/5xx response without a body the [`ErrorResponse`] body
+/// `ApiError` sends
+struct ErrorEnvelope;
+
+impl utoipa::Modify for ErrorEnvelope {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let error_body = || {
+            let schema: RefOr<utoipa::openapi::schema::Schema> =
+                Ref::from_schema_name(<ErrorResponse as utoipa::ToSchema>::name()).into();
+            Content::new(Some(schema))
+        };
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.head,
+                &mut item.patch,
+            ];
+            for operation in operations.into_iter().flatten() {
+                for (status, response) in operation.responses.responses.iter_mut() {
+                    if !(status.starts_with('4') || status.starts_with('5')) {
+                        continue;
+                    }
+                    if let RefOr::T(response) = response {
+                        if response.content.is_empty() {
+                            response
+                                .content
+                                .insert("application/json".to_string(), error_body());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Modifier to add C2PA paths when the feature is enabled
 struct C2paModifier;
 
@@ -0,0 +1,492 @@
+//! Per-request QRNG provider selection
+//!
+//! Clients may ask for a specific entropy source with the `qrng_provider`
+//! seal field; the server only honors providers on its allowlist. Entropy is
+//! fetched here rather than inside the seal builder, so each attempt can be
+//! retried (and, for `auto`, fail over to the next configured provider) and
+//! the seal response can report which source answered and how.
+//!
+//! `QRNG_POLICY` decides whether each seal needs a fresh draw or may use a
+//! block drawn ahead into a per-provider [`EntropyPool`]. The policy is
+//! recorded in every seal, and the seal builder refuses pooled entropy that
+//! does not satisfy it.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde::Serialize;
+use utoipa::ToSchema;
+use veritas_core::qrng::{
+    validate_entropy, AttestedEntropy, DeviceQrngConfig, EntropyPool, IdQuantiqueConfig,
+    QrngProviderConfig, QrngProviderFactory,
+};
+use veritas_core::{QrngPolicy, QrngSource, QuantumEntropySource};
+
+use crate::error::ApiError;
+
+/// Default number of attempts per provider (the first one included).
+pub const DEFAULT_QRNG_MAX_ATTEMPTS: u32 = 2;
+
+/// Default maximum age of pooled entropy in seconds
+pub const DEFAULT_QRNG_POOL_MAX_AGE_SECS: u64 = 300;
+
+/// Default number of blocks drawn ahead per provider
+pub const DEFAULT_QRNG_POOL_SIZE: usize = 16;
+
+/// Providers clients may request by default (ANU is deprecated).
+pub const DEFAULT_ALLOWED_PROVIDERS: &[QrngProvider] = &[
+    QrngProvider::Auto,
+    QrngProvider::IdQuantique,
+    QrngProvider::Lfd,
+    QrngProvider::Device,
+];
+
+/// QRNG provider requested for a seal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QrngProvider {
+    /// Best configured provider, failing over to the next one
+    Auto,
+    /// ID Quantique cloud API (needs QRNG_API_KEY)
+    IdQuantique,
+    /// LfD QRNG API (Germany)
+    Lfd,
+    /// ANU QRNG API (deprecated)
+    Anu,
+    /// Local hardware device (needs QRNG_DEVICE_PATH)
+    Device,
+    /// Deterministic mock, governed by ALLOW_MOCK_QRNG rather than the allowlist
+    Mock,
+}
+
+impl QrngProvider {
+    /// Parse a provider name as used in `qrng_source` ("idq", "lfd", ...).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "idq" | "idquantique" => Some(Self::IdQuantique),
+            "lfd" => Some(Self::Lfd),
+            "anu" => Some(Self::Anu),
+            "hardware" | "device" => Some(Self::Device),
+            "mock" => Some(Self::Mock),
+            _ => None,
+        }
+    }
+
+    /// Provider name, matching the `qrng_source` of the resulting seal
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::IdQuantique => "idq",
+            Self::Lfd => "lfd",
+            Self::Anu => "anu",
+            Self::Device => "hardware",
+            Self::Mock => "mock",
+        }
+    }
+
+    /// Provider configuration, or None if the provider is not set up here.
+    fn config(&self) -> Option<QrngProviderConfig> {
+        match self {
+            Self::Auto => None,
+            Self::IdQuantique => IdQuantiqueConfig::from_env()
+                .ok()
+                .map(QrngProviderConfig::IdQuantique),
+            Self::Lfd => Some(QrngProviderConfig::Lfd(Default::default())),
+            Self::Anu => Some(QrngProviderConfig::Anu(Default::default())),
+            Self::Device => DeviceQrngConfig::from_env()
+                .ok()
+                .map(QrngProviderConfig::Device),
+            Self::Mock => Some(QrngProviderConfig::Mock {
+                seed: 0xDEADBEEF_CAFEBABE,
+            }),
+        }
+    }
+}
+
+/// How entropy was obtained for a seal
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct QrngFetch {
+    /// Provider requested by the client ("auto" when unspecified)
+    #[schema(example = "auto")]
+    pub requested: String,
+    /// Provider that supplied the entropy
+    #[schema(example = "idq")]
+    pub provider: String,
+    /// Time spent fetching entropy in milliseconds, retries and failover included
+    #[schema(example = 182)]
+    pub latency_ms: u64,
+    /// Attempts made beyond the first one
+    #[schema(example = 0)]
+    pub retries: u32,
+    /// Whether a provider failed and the next one supplied the entropy
+    #[schema(example = false)]
+    pub failover: bool,
+    /// Whether the entropy was drawn ahead into the pool (QRNG_POLICY)
+    #[schema(example = false)]
+    pub pooled: bool,
+}
+
+/// Entropy fetched ahead of sealing, replayed to the seal builder
+pub struct PrefetchedEntropy {
+    entropy: AttestedEntropy,
+    source: QrngSource,
+}
+
+#[async_trait]
+impl QuantumEntropySource for PrefetchedEntropy {
+    async fn get_entropy(&self) -> veritas_core::Result<[u8; 32]> {
+        Ok(self.entropy.entropy)
+    }
+
+    async fn get_attested_entropy(&self) -> veritas_core::Result<AttestedEntropy> {
+        Ok(self.entropy.clone())
+    }
+
+    fn source_id(&self) -> QrngSource {
+        self.source.clone()
+    }
+}
+
+/// Which QRNG providers clients may request, how hard to try each one, and
+/// whether entropy may be drawn ahead
+#[derive(Clone)]
+pub struct QrngSelection {
+    allowed: Vec<QrngProvider>,
+    max_attempts: u32,
+    policy: QrngPolicy,
+    pool_size: usize,
+    /// Provider instances, created on first use and kept so that their
+    /// continuous health tests see every block they return
+    providers: Arc<DashMap<QrngProvider, Arc<dyn QuantumEntropySource>>>,
+    /// Entropy pools per provider, created on first use (unused with
+    /// `QrngPolicy::FreshPerSeal`)
+    pools: Arc<DashMap<QrngProvider, Arc<EntropyPool>>>,
+}
+
+impl std::fmt::Debug for QrngSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QrngSelection")
+            .field("allowed", &self.allowed)
+            .field("max_attempts", &self.max_attempts)
+            .field("policy", &self.policy)
+            .field("pool_size", &self.pool_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for QrngSelection {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_ALLOWED_PROVIDERS.to_vec(),
+            DEFAULT_QRNG_MAX_ATTEMPTS,
+        )
+    }
+}
+
+impl QrngSelection {
+    /// Allow `allowed` providers, trying each up to `max_attempts` times,
+    /// with a fresh draw per seal
+    pub fn new(allowed: Vec<QrngProvider>, max_attempts: u32) -> Self {
+        Self {
+            allowed,
+            max_attempts: max_attempts.max(1),
+            policy: QrngPolicy::FreshPerSeal,
+            pool_size: DEFAULT_QRNG_POOL_SIZE,
+            providers: Arc::new(DashMap::new()),
+            pools: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Apply an entropy reuse policy, pooling up to `pool_size` blocks per provider
+    pub fn with_policy(mut self, policy: QrngPolicy, pool_size: usize) -> Self {
+        self.policy = policy;
+        self.pool_size = pool_size.max(1);
+        self.pools = Arc::new(DashMap::new());
+        self
+    }
+
+    /// Entropy reuse policy recorded in every seal
+    pub fn policy(&self) -> QrngPolicy {
+        self.policy
+    }
+
+    /// Load QRNG_ALLOWED_PROVIDERS (comma-separated names), QRNG_MAX_ATTEMPTS,
+    /// QRNG_POLICY (`fresh`, `pooled` or `mixed`), QRNG_POOL_MAX_AGE_SECS and
+    /// QRNG_POOL_SIZE
+    pub fn from_env() -> Result<Self, String> {
+        let allowed = match std::env::var("QRNG_ALLOWED_PROVIDERS") {
+            Ok(names) if !names.trim().is_empty() => names
+                .split(',')
+                .map(|name| {
+                    QrngProvider::parse(name)
+                        .ok_or_else(|| format!("Unknown QRNG provider: {}", name.trim()))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => DEFAULT_ALLOWED_PROVIDERS.to_vec(),
+        };
+
+        let max_attempts = std::env::var("QRNG_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_QRNG_MAX_ATTEMPTS);
+
+        let max_age_secs = std::env::var("QRNG_POOL_MAX_AGE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_QRNG_POOL_MAX_AGE_SECS);
+        let policy = match std::env::var("QRNG_POLICY") {
+            Ok(name) if !name.trim().is_empty() => parse_policy(&name, max_age_secs)?,
+            _ => QrngPolicy::FreshPerSeal,
+        };
+
+        let pool_size = std::env::var("QRNG_POOL_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_QRNG_POOL_SIZE);
+
+        Ok(Self::new(allowed, max_attempts).with_policy(policy, pool_size))
+    }
+
+    /// Refuse a provider that is not on the allowlist (mock is checked by
+    /// the caller against ALLOW_MOCK_QRNG).
+    pub fn check(&self, provider: QrngProvider) -> Result<(), ApiError> {
+        if provider == QrngProvider::Mock || self.allowed.contains(&provider) {
+            return Ok(());
+        }
+        let allowed: Vec<&str> = self.allowed.iter().map(QrngProvider::name).collect();
+        Err(ApiError::bad_request(format!(
+            "QRNG provider '{}' is not allowed (allowed: {})",
+            provider.name(),
+            allowed.join(", ")
+        )))
+    }
+
+    /// Fetch entropy from `requested`, retrying each candidate provider.
+    pub async fn fetch(
+        &self,
+        requested: QrngProvider,
+    ) -> Result<(PrefetchedEntropy, QrngFetch), ApiError> {
+        let started = Instant::now();
+        let mut attempts = 0;
+
+        for (index, (provider, config)) in candidates(requested)?.into_iter().enumerate() {
+            let source = match self.source(provider, config) {
+                Ok(source) => source,
+                Err(e) => {
+                    tracing::warn!(provider = provider.name(), error = %e, "QRNG provider creation failed");
+                    continue;
+                }
+            };
+
+            for _ in 0..self.max_attempts {
+                attempts += 1;
+                match fetch_once(&source).await {
+                    Ok(entropy) => {
+                        let fetch = QrngFetch {
+                            requested: requested.name().to_string(),
+                            provider: provider.name().to_string(),
+                            latency_ms: started.elapsed().as_millis() as u64,
+                            retries: attempts - 1,
+                            failover: index > 0,
+                            pooled: entropy.drawn_at.is_some(),
+                        };
+                        let entropy = PrefetchedEntropy {
+                            entropy,
+                            source: source.source_id(),
+                        };
+                        return Ok((entropy, fetch));
+                    }
+                    Err(e) => {
+                        tracing::warn!(provider = provider.name(), attempt = attempts, error = %e, "QRNG entropy fetch failed");
+                    }
+                }
+            }
+        }
+
+        tracing::error!(
+            requested = requested.name(),
+            attempts,
+            "No QRNG provider supplied entropy"
+        );
+        Err(ApiError::service_unavailable("QRNG service unavailable"))
+    }
+
+    /// Entropy source for `provider`: the provider itself, or its pool when
+    /// the policy allows drawing ahead
+    fn source(
+        &self,
+        provider: QrngProvider,
+        config: QrngProviderConfig,
+    ) -> veritas_core::Result<Arc<dyn QuantumEntropySource>> {
+        if self.policy == QrngPolicy::FreshPerSeal {
+            return self.provider(provider, config);
+        }
+        if let Some(pool) = self.pools.get(&provider) {
+            return Ok(pool.clone());
+        }
+
+        let pool = Arc::new(EntropyPool::new(
+            self.provider(provider, config)?,
+            self.policy,
+            self.pool_size,
+        ));
+        Ok(self.pools.entry(provider).or_insert(pool).clone())
+    }
+
+    /// Shared instance of `provider`, so a source that failed its health
+    /// tests keeps refusing entropy instead of being rebuilt for the next seal
+    fn provider(
+        &self,
+        provider: QrngProvider,
+        config: QrngProviderConfig,
+    ) -> veritas_core::Result<Arc<dyn QuantumEntropySource>> {
+        if let Some(source) = self.providers.get(&provider) {
+            return Ok(source.clone());
+        }
+
+        let source = QrngProviderFactory::create(config)?;
+        Ok(self.providers.entry(provider).or_insert(source).clone())
+    }
+}
+
+/// Parse a QRNG_POLICY name, with `max_age_secs` for pooled entropy
+fn parse_policy(name: &str, max_age_secs: u64) -> Result<QrngPolicy, String> {
+    match name.trim().to_lowercase().as_str() {
+        "fresh" => Ok(QrngPolicy::FreshPerSeal),
+        "pooled" => Ok(QrngPolicy::Pooled { max_age_secs }),
+        "mixed" => Ok(QrngPolicy::Mixed { max_age_secs }),
+        other => Err(format!(
+            "Unknown QRNG policy: {} (expected fresh, pooled or mixed)",
+            other
+        )),
+    }
+}
+
+/// Providers to try for `requested`, in order.
+///
+/// `auto` follows the server's priority (hardware device, ID Quantique, LfD)
+/// over the configured providers; an explicit provider has no fallback.
+fn candidates(
+    requested: QrngProvider,
+) -> Result<Vec<(QrngProvider, QrngProviderConfig)>, ApiError> {
+    if requested != QrngProvider::Auto {
+        return match requested.config() {
+            Some(config) => Ok(vec![(requested, config)]),
+            None => Err(ApiError::service_unavailable(format!(
+                "QRNG provider '{}' is not configured on this server",
+                requested.name()
+            ))),
+        };
+    }
+
+    Ok([
+        QrngProvider::Device,
+        QrngProvider::IdQuantique,
+        QrngProvider::Lfd,
+    ]
+    .into_iter()
+    .filter_map(|provider| provider.config().map(|config| (provider, config)))
+    .collect())
+}
+
+/// One attempt, rejecting degenerate entropy like the seal builder would.
+async fn fetch_once(
+    source: &Arc<dyn QuantumEntropySource>,
+) -> veritas_core::Result<AttestedEntropy> {
+    let entropy = source.get_attested_entropy().await?;
+    validate_entropy(&entropy.entropy)?;
+    Ok(entropy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_names_round_trip() {
+        for provider in [
+            QrngProvider::Auto,
+            QrngProvider::IdQuantique,
+            QrngProvider::Lfd,
+            QrngProvider::Anu,
+            QrngProvider::Device,
+            QrngProvider::Mock,
+        ] {
+            assert_eq!(QrngProvider::parse(provider.name()), Some(provider));
+        }
+        assert_eq!(
+            QrngProvider::parse(" IDQ "),
+            Some(QrngProvider::IdQuantique)
+        );
+        assert_eq!(QrngProvider::parse("random.org"), None);
+    }
+
+    #[test]
+    fn test_selection_allowlist() {
+        let selection = QrngSelection::new(vec![QrngProvider::IdQuantique], 0);
+        assert_eq!(selection.max_attempts, 1);
+        assert!(selection.check(QrngProvider::IdQuantique).is_ok());
+        assert!(selection.check(QrngProvider::Mock).is_ok());
+
+        let err = selection.check(QrngProvider::Lfd).unwrap_err();
+        assert_eq!(err.status_code(), axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_mock_reports_metadata() {
+        let (entropy, fetch) = QrngSelection::default()
+            .fetch(QrngProvider::Mock)
+            .await
+            .unwrap();
+
+        assert_eq!(entropy.source_id(), QrngSource::Mock);
+        assert_eq!(fetch.requested, "mock");
+        assert_eq!(fetch.provider, "mock");
+        assert_eq!(fetch.retries, 0);
+        assert!(!fetch.failover);
+        assert!(!fetch.pooled);
+    }
+
+    #[tokio::test]
+    async fn test_fresh_policy_keeps_provider_instance() {
+        let selection = QrngSelection::default();
+        selection.fetch(QrngProvider::Mock).await.unwrap();
+        selection.fetch(QrngProvider::Mock).await.unwrap();
+
+        assert_eq!(selection.providers.len(), 1);
+        assert!(selection.pools.is_empty());
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        assert_eq!(
+            parse_policy("fresh", 300).unwrap(),
+            QrngPolicy::FreshPerSeal
+        );
+        assert_eq!(
+            parse_policy(" Pooled ", 60).unwrap(),
+            QrngPolicy::Pooled { max_age_secs: 60 }
+        );
+        assert_eq!(
+            parse_policy("mixed", 300).unwrap(),
+            QrngPolicy::Mixed { max_age_secs: 300 }
+        );
+        assert!(parse_policy("reuse", 300).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pooled_policy_reuses_provider_pool() {
+        let selection =
+            QrngSelection::default().with_policy(QrngPolicy::Pooled { max_age_secs: 60 }, 4);
+
+        let (_, first) = selection.fetch(QrngProvider::Mock).await.unwrap();
+        assert!(!first.pooled);
+
+        let (entropy, second) = selection.fetch(QrngProvider::Mock).await.unwrap();
+        assert!(second.pooled);
+        assert!(entropy.entropy.drawn_at.is_some());
+        assert_eq!(selection.pools.len(), 1);
+    }
+}
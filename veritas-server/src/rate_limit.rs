@@ -0,0 +1,87 @@
+//! Per-key rate limiting
+//!
+//! The IP-based governor layer counts requests; bulk endpoints also need to
+//! count the work inside each request. [`KeyedRateLimiter`] gives every key
+//! (e.g. an API key ID) a budget of units per fixed window.
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use uuid::Uuid;
+
+/// Fixed-window budget of units per key
+pub struct KeyedRateLimiter {
+    limit: u64,
+    window: Duration,
+    windows: DashMap<Uuid, Window>,
+}
+
+/// Units used by a key in its current window
+struct Window {
+    started: Instant,
+    used: u64,
+}
+
+impl KeyedRateLimiter {
+    /// Allow `limit` units per key every `window`
+    pub fn new(limit: u64, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: DashMap::new(),
+        }
+    }
+
+    /// Units allowed per window
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Spend `cost` units of `key`'s budget.
+    ///
+    /// Nothing is spent when the budget is short; the error is the time until
+    /// the window resets.
+    pub fn try_acquire(&self, key: Uuid, cost: u64) -> Result<(), Duration> {
+        self.try_acquire_at(key, cost, Instant::now())
+    }
+
+    fn try_acquire_at(&self, key: Uuid, cost: u64, now: Instant) -> Result<(), Duration> {
+        let mut window = self.windows.entry(key).or_insert(Window {
+            started: now,
+            used: 0,
+        });
+        if now.duration_since(window.started) >= self.window {
+            window.started = now;
+            window.used = 0;
+        }
+        if window.used + cost > self.limit {
+            return Err(self.window - now.duration_since(window.started));
+        }
+        window.used += cost;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_resets_each_window() {
+        let limiter = KeyedRateLimiter::new(10, Duration::from_secs(60));
+        let (key, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire_at(key, 6, start).is_ok());
+        assert!(limiter.try_acquire_at(key, 4, start).is_ok());
+        assert_eq!(
+            limiter.try_acquire_at(key, 1, start + Duration::from_secs(15)),
+            Err(Duration::from_secs(45))
+        );
+        assert!(limiter.try_acquire_at(other, 10, start).is_ok());
+
+        let later = start + Duration::from_secs(60);
+        assert!(limiter.try_acquire_at(key, 10, later).is_ok());
+        assert!(limiter.try_acquire_at(key, 11, later).is_err());
+    }
+}
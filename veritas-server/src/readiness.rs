@@ -0,0 +1,410 @@
+//! Dependency checks behind `/ready`
+//!
+//! Every dependency is probed concurrently, each under a timeout, and
+//! reported with its status and latency. Only the database (with its read
+//! replica and the manifest store it backs) is critical: when it is down the
+//! probe fails with 503 so Kubernetes stops routing traffic. The other
+//! dependencies only take part of
+//! the API down (sealing without a QRNG, sign-in without Clerk keys,
+//! anchoring without Solana), so they mark the server `degraded` instead.
+//!
+//! Reports are cached for a few seconds so that frequent probes do not draw
+//! entropy from the QRNG provider or hit Clerk on every call.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+use utoipa::ToSchema;
+use veritas_core::qrng::{validate_entropy, QrngProviderConfig, QrngProviderFactory};
+use veritas_core::QuantumEntropySource;
+
+use crate::auth::JwtIssuers;
+use crate::db::DatabasePools;
+use crate::manifest_store::PostgresManifestStore;
+
+/// Default time allowed to each dependency check in milliseconds.
+pub const DEFAULT_READY_CHECK_TIMEOUT_MS: u64 = 2000;
+
+/// Default time a readiness report is reused in seconds.
+pub const DEFAULT_READY_CACHE_SECS: u64 = 10;
+
+/// Health of one dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyStatus {
+    /// Reachable and answering
+    Up,
+    /// Failing or too slow
+    Down,
+    /// Not configured on this server
+    Disabled,
+}
+
+/// Overall readiness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessStatus {
+    /// Every configured dependency is up
+    Ready,
+    /// A non-critical dependency is down; the server still accepts traffic
+    Degraded,
+    /// A critical dependency is down
+    Unavailable,
+}
+
+/// Result of checking one dependency
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DependencyHealth {
+    /// Dependency name: database, database_replica, manifest_store, qrng,
+    /// clerk_jwks or solana_rpc
+    #[schema(example = "database")]
+    pub name: &'static str,
+    /// Dependency status
+    pub status: DependencyStatus,
+    /// Whether the server is unavailable while this dependency is down
+    #[schema(example = true)]
+    pub critical: bool,
+    /// Time taken by the check in milliseconds (absent when disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 3)]
+    pub latency_ms: Option<u64>,
+    /// Failure reason, or details about a healthy dependency
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "4 connections, 3 idle")]
+    pub detail: Option<String>,
+}
+
+impl DependencyHealth {
+    fn disabled(name: &'static str, critical: bool) -> Self {
+        Self {
+            name,
+            status: DependencyStatus::Disabled,
+            critical,
+            latency_ms: None,
+            detail: None,
+        }
+    }
+}
+
+/// Statuses of all dependencies
+#[derive(Debug, Clone)]
+pub struct ReadinessReport {
+    /// Overall status
+    pub status: ReadinessStatus,
+    /// One entry per dependency, in a fixed order
+    pub dependencies: Vec<DependencyHealth>,
+}
+
+impl ReadinessReport {
+    /// Combine dependency checks into an overall status
+    pub fn new(dependencies: Vec<DependencyHealth>) -> Self {
+        let down = |critical: bool| {
+            dependencies
+                .iter()
+                .any(|d| d.status == DependencyStatus::Down && d.critical == critical)
+        };
+        let status = if down(true) {
+            ReadinessStatus::Unavailable
+        } else if down(false) {
+            ReadinessStatus::Degraded
+        } else {
+            ReadinessStatus::Ready
+        };
+        Self {
+            status,
+            dependencies,
+        }
+    }
+}
+
+/// Dependencies to check and how
+pub struct ReadinessProbe {
+    db_pools: Option<DatabasePools>,
+    manifest_store: Option<Arc<PostgresManifestStore>>,
+    jwt_issuers: Option<Arc<JwtIssuers>>,
+    solana_rpc_url: Option<String>,
+    http_client: reqwest::Client,
+    timeout: Duration,
+    cache_ttl: Duration,
+    last: Mutex<Option<(Instant, ReadinessReport)>>,
+}
+
+impl ReadinessProbe {
+    /// Check the given dependencies, `None` meaning not configured
+    pub fn new(
+        db_pools: Option<DatabasePools>,
+        manifest_store: Option<Arc<PostgresManifestStore>>,
+        jwt_issuers: Option<Arc<JwtIssuers>>,
+        solana_rpc_url: Option<String>,
+    ) -> Self {
+        Self {
+            db_pools,
+            manifest_store,
+            jwt_issuers,
+            solana_rpc_url,
+            http_client: reqwest::Client::new(),
+            timeout: Duration::from_millis(DEFAULT_READY_CHECK_TIMEOUT_MS),
+            cache_ttl: Duration::from_secs(DEFAULT_READY_CACHE_SECS),
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Set the per-check timeout and how long a report is reused
+    pub fn with_timing(mut self, timeout: Duration, cache_ttl: Duration) -> Self {
+        self.timeout = timeout;
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Check every dependency, or return the last report if still fresh.
+    ///
+    /// Concurrent callers wait for the same round of checks.
+    pub async fn report(&self) -> ReadinessReport {
+        let mut last = self.last.lock().await;
+        if let Some((checked_at, report)) = last.as_ref() {
+            if checked_at.elapsed() < self.cache_ttl {
+                return report.clone();
+            }
+        }
+
+        let (database, replica, manifest_store, qrng, clerk_jwks, solana_rpc) = tokio::join!(
+            self.check_database(),
+            self.check_replica(),
+            self.check_manifest_store(),
+            self.check_qrng(),
+            self.check_jwks(),
+            self.check_solana(),
+        );
+        let report = ReadinessReport::new(vec![
+            database,
+            replica,
+            manifest_store,
+            qrng,
+            clerk_jwks,
+            solana_rpc,
+        ]);
+
+        if report.status != ReadinessStatus::Ready {
+            for dependency in &report.dependencies {
+                if dependency.status == DependencyStatus::Down {
+                    tracing::warn!(
+                        dependency = dependency.name,
+                        critical = dependency.critical,
+                        detail = dependency.detail.as_deref().unwrap_or_default(),
+                        "Readiness check failed"
+                    );
+                }
+            }
+        }
+
+        *last = Some((Instant::now(), report.clone()));
+        report
+    }
+
+    async fn check_database(&self) -> DependencyHealth {
+        match &self.db_pools {
+            Some(pools) => self.check_pool("database", &pools.primary).await,
+            None => DependencyHealth::disabled("database", true),
+        }
+    }
+
+    /// Listings, exports and resolution fail while the replica is down
+    async fn check_replica(&self) -> DependencyHealth {
+        match self.db_pools.as_ref().and_then(|p| p.replica.as_ref()) {
+            Some(replica) => self.check_pool("database_replica", replica).await,
+            None => DependencyHealth::disabled("database_replica", true),
+        }
+    }
+
+    async fn check_pool(&self, name: &'static str, pool: &PgPool) -> DependencyHealth {
+        self.timed(name, true, async {
+            sqlx::query("SELECT 1")
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(Some(format!(
+                "{} connections, {} idle",
+                pool.size(),
+                pool.num_idle()
+            )))
+        })
+        .await
+    }
+
+    async fn check_manifest_store(&self) -> DependencyHealth {
+        let Some(store) = &self.manifest_store else {
+            return DependencyHealth::disabled("manifest_store", true);
+        };
+        self.timed("manifest_store", true, async {
+            store.ping().await.map_err(|e| e.to_string())?;
+            Ok(None)
+        })
+        .await
+    }
+
+    /// Draw one block from the provider sealing would pick by default
+    async fn check_qrng(&self) -> DependencyHealth {
+        self.timed("qrng", false, async {
+            let source =
+                QrngProviderFactory::create(QrngProviderConfig::Auto).map_err(|e| e.to_string())?;
+            let entropy = source.get_entropy().await.map_err(|e| e.to_string())?;
+            validate_entropy(&entropy).map_err(|e| e.to_string())?;
+            Ok(Some(source.source_id().to_string()))
+        })
+        .await
+    }
+
+    async fn check_jwks(&self) -> DependencyHealth {
+        let Some(jwt_issuers) = &self.jwt_issuers else {
+            return DependencyHealth::disabled("clerk_jwks", false);
+        };
+        self.timed("clerk_jwks", false, async {
+            let counts = jwt_issuers.key_counts().await?;
+            let details: Vec<String> = counts
+                .iter()
+                .map(|(issuer, keys)| format!("{}: {} keys", issuer, keys))
+                .collect();
+            Ok(Some(details.join(", ")))
+        })
+        .await
+    }
+
+    /// Solana JSON-RPC `getHealth`, which answers "ok" when the node is in sync
+    async fn check_solana(&self) -> DependencyHealth {
+        let Some(url) = &self.solana_rpc_url else {
+            return DependencyHealth::disabled("solana_rpc", false);
+        };
+        self.timed("solana_rpc", false, async {
+            let response: serde_json::Value = self
+                .http_client
+                .post(url)
+                .json(&serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "getHealth"}))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| e.to_string())?
+                .json()
+                .await
+                .map_err(|e| e.to_string())?;
+            match response.get("result").and_then(|r| r.as_str()) {
+                Some("ok") => Ok(None),
+                _ => Err(response
+                    .get("error")
+                    .and_then(|e| e.get("message"))
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("unexpected getHealth response")
+                    .to_string()),
+            }
+        })
+        .await
+    }
+
+    /// Run a check under the timeout, measuring its latency
+    async fn timed(
+        &self,
+        name: &'static str,
+        critical: bool,
+        check: impl Future<Output = Result<Option<String>, String>>,
+    ) -> DependencyHealth {
+        let started = Instant::now();
+        let result = tokio::time::timeout(self.timeout, check)
+            .await
+            .unwrap_or_else(|_| Err(format!("timed out after {}ms", self.timeout.as_millis())));
+        let (status, detail) = match result {
+            Ok(detail) => (DependencyStatus::Up, detail),
+            Err(e) => (DependencyStatus::Down, Some(e)),
+        };
+        DependencyHealth {
+            name,
+            status,
+            critical,
+            latency_ms: Some(started.elapsed().as_millis() as u64),
+            detail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(
+        name: &'static str,
+        status: DependencyStatus,
+        critical: bool,
+    ) -> DependencyHealth {
+        DependencyHealth {
+            name,
+            status,
+            critical,
+            latency_ms: Some(1),
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_overall_status() {
+        let report = ReadinessReport::new(vec![
+            dependency("database", DependencyStatus::Up, true),
+            dependency("solana_rpc", DependencyStatus::Disabled, false),
+        ]);
+        assert_eq!(report.status, ReadinessStatus::Ready);
+
+        let report = ReadinessReport::new(vec![
+            dependency("database", DependencyStatus::Up, true),
+            dependency("qrng", DependencyStatus::Down, false),
+        ]);
+        assert_eq!(report.status, ReadinessStatus::Degraded);
+
+        let report = ReadinessReport::new(vec![
+            dependency("database", DependencyStatus::Down, true),
+            dependency("qrng", DependencyStatus::Down, false),
+        ]);
+        assert_eq!(report.status, ReadinessStatus::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_dependencies_are_disabled() {
+        let probe = ReadinessProbe::new(None, None, None, None)
+            .with_timing(Duration::from_millis(50), Duration::from_secs(60));
+        let report = probe.report().await;
+
+        let names: Vec<_> = report.dependencies.iter().map(|d| d.name).collect();
+        assert_eq!(
+            names,
+            [
+                "database",
+                "database_replica",
+                "manifest_store",
+                "qrng",
+                "clerk_jwks",
+                "solana_rpc"
+            ]
+        );
+        for dependency in &report.dependencies {
+            if dependency.name != "qrng" {
+                assert_eq!(dependency.status, DependencyStatus::Disabled);
+            }
+        }
+        assert_ne!(report.status, ReadinessStatus::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_check_is_down() {
+        let probe = ReadinessProbe::new(None, None, None, None)
+            .with_timing(Duration::from_millis(10), Duration::from_secs(60));
+        let health = probe
+            .timed("slow", false, async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(None)
+            })
+            .await;
+
+        assert_eq!(health.status, DependencyStatus::Down);
+        assert_eq!(health.detail.as_deref(), Some("timed out after 10ms"));
+    }
+}
@@ -5,38 +5,97 @@
 use std::{sync::Arc, time::Duration};
 
 use axum::{
+    extract::DefaultBodyLimit,
     http::{header, Method, StatusCode},
-    routing::{get, post},
+    middleware::{from_fn, from_fn_with_state},
+    routing::{delete, get, head, post, put},
     Router,
 };
-use sqlx::postgres::PgPoolOptions;
 use tower_governor::{
     governor::GovernorConfigBuilder, key_extractor::SmartIpKeyExtractor, GovernorLayer,
 };
+use tower_http::compression::predicate::{DefaultPredicate, NotForContentType, Predicate};
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::sensitive_headers::{
+    SetSensitiveRequestHeadersLayer, SetSensitiveResponseHeadersLayer,
+};
 use tower_http::timeout::TimeoutLayer;
-use tower_http::trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer};
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
+use veritas_core::{AnchorProvider, OpenTimestamps};
 
-use crate::auth::JwksCache;
+use crate::anchor_batcher::AnchorBatcher;
+use crate::anchor_checks::AnchorChecker;
+use crate::auth::{
+    AuthMode, JwtIssuers, LocalAuth, TokenIntrospector, API_KEY_HEADER,
+    INTROSPECTION_SWEEP_INTERVAL, JWKS_REFRESH_INTERVAL,
+};
+use crate::clustering;
 use crate::config::Config;
-use crate::db::{SealRepository, UserRepository};
-#[cfg(feature = "c2pa")]
-use crate::handlers::{c2pa_embed_handler, c2pa_verify_handler};
+use crate::db::tenant::{run_migrations, set_row_level_security};
+use crate::db::{
+    AnchorProofRepository, ApiKeyRepository, AuditLogRepository, CollectionRepository,
+    DatabasePools, LocalCredentialRepository, OrganizationSettingsRepository,
+    RetentionPolicyRepository, SealRepository, SealShareRepository, SealTagRepository,
+    SigningKeyRepository, UploadSessionRepository, UserRepository, VerificationRepository,
+    RETENTION_INTERVAL, STATS_REFRESH_INTERVAL,
+};
+use crate::exports::{ExportStore, EXPORT_SWEEP_INTERVAL};
+use crate::handlers::upload::{UPLOAD_LENGTH, UPLOAD_OFFSET};
 use crate::handlers::{
-    delete_user_handler, export_seal_handler, get_current_user_handler, get_user_seal_handler,
-    health, list_user_seals_handler, ready, resolve_handler, seal_handler, sync_user_handler,
-    verify_handler,
+    add_collection_seal_handler, admin_list_users_handler, admin_stats_handler,
+    admin_tasks_handler, anchor_status_handler, batch_export_handler, batch_export_status_handler,
+    branding_handler, create_api_key_handler, create_collection_handler, create_share_handler,
+    create_upload_handler, delete_collection_handler, delete_organization_settings_handler,
+    delete_retention_policy_handler, delete_tag_handler, delete_upload_handler,
+    delete_user_handler, did_document_handler, download_export_handler, export_seal_handler,
+    export_user_data_handler, finalize_upload_handler, finish_stream_handler,
+    get_collection_handler, get_current_user_handler, get_current_user_stats_handler,
+    get_maintenance_handler, get_organization_settings_handler, get_retention_policy_handler,
+    get_seal_tags_handler, get_user_seal_handler, health, import_seal_handler,
+    list_api_keys_handler, list_collections_handler, list_revoked_seals_handler,
+    list_shares_handler, list_signing_keys_handler, list_tags_handler, list_user_seals_handler,
+    login_handler, ready, register_handler, reinstate_seal_handler, remove_collection_seal_handler,
+    rename_tag_handler, resolve_batch_handler, resolve_handler, revoke_api_key_handler,
+    revoke_seal_handler, revoke_share_handler, revoke_signing_key_handler, seal_duplicates_handler,
+    seal_handler, seal_map_handler, seal_qr_handler, seal_report_handler,
+    seal_verifications_handler, set_seal_tags_handler, shared_seal_handler, start_stream_handler,
+    stream_chunk_handler, suspend_user_handler, sync_pull_handler, sync_push_handler,
+    sync_user_handler, unsuspend_user_handler, update_collection_handler,
+    update_legal_hold_handler, update_maintenance_handler, update_organization_settings_handler,
+    update_retention_policy_handler, update_user_organization_handler, update_user_tier_handler,
+    upload_chunk_handler, upload_status_handler, verify_handler, ANCHOR_DIGEST_HEADER,
+    MOCK_ENTROPY_HEADER, PERCEPTUAL_HASH_HEADER, QRNG_SOURCE_HEADER, SEAL_ID_HEADER,
+    TRUST_TIER_HEADER,
 };
+#[cfg(feature = "c2pa")]
+use crate::handlers::{c2pa_embed_handler, c2pa_verify_handler};
+use crate::janitor::Janitor;
+use crate::logging::{RequestSpan, REQUEST_ID_HEADER, SENSITIVE_HEADERS};
+use crate::maintenance::{reject_writes, MaintenanceMode};
 use crate::manifest_store::PostgresManifestStore;
+use crate::notifications::Notifier;
 use crate::openapi::ApiDoc;
+use crate::rate_limit::KeyedRateLimiter;
+use crate::readiness::ReadinessProbe;
+use crate::signing_keys::{ServerSigner, SigningKeyRegistry};
+use crate::startup::MIGRATOR;
 use crate::state::AppState;
+use crate::streams::{StreamRegistry, STREAM_IDLE_TIMEOUT, STREAM_SWEEP_INTERVAL};
+use crate::uploads::{UploadStore, UPLOAD_SWEEP_INTERVAL};
+use crate::verification_cache::VerificationCache;
+use crate::verification_pool::VerificationPool;
+use crate::versioning::{
+    deprecated, negotiate_version, v1_with_legacy_aliases, Deprecation, API_V1, API_VERSION_HEADER,
+    DEPRECATION_HEADER, SUNSET_HEADER,
+};
 use crate::webauthn::{
-    finish_authentication, finish_registration, start_authentication, start_registration,
-    WebAuthnState, WebAuthnStorage,
+    finish_authentication, finish_registration, start_authentication, start_capture_session,
+    start_registration, storage::CHALLENGE_CLEANUP_INTERVAL, WebAuthnState, WebAuthnStorage,
 };
 
 /// Create the application router with default config (for testing)
@@ -47,22 +106,98 @@ pub fn create_router() -> Router {
 
 /// Create the application router with in-memory WebAuthn storage (sync version for tests)
 pub fn create_router_with_config_sync(config: &Config) -> Router {
-    create_router_internal(config, WebAuthnStorage::in_memory(), None, None, None, None)
+    create_router_internal(
+        config,
+        WebAuthnStorage::in_memory(),
+        Repositories::default(),
+        None,
+        None,
+    )
+    .0
+}
+
+/// PostgreSQL-backed components, all absent without a database
+#[derive(Default)]
+struct Repositories {
+    db_pools: Option<DatabasePools>,
+    manifest_store: Option<Arc<PostgresManifestStore>>,
+    user_repo: Option<Arc<UserRepository>>,
+    seal_repo: Option<Arc<SealRepository>>,
+    share_repo: Option<Arc<SealShareRepository>>,
+    upload_repo: Option<Arc<UploadSessionRepository>>,
+    audit_repo: Option<Arc<AuditLogRepository>>,
+    verification_repo: Option<Arc<VerificationRepository>>,
+    api_key_repo: Option<Arc<ApiKeyRepository>>,
+    credential_repo: Option<Arc<LocalCredentialRepository>>,
+    organization_repo: Option<Arc<OrganizationSettingsRepository>>,
+    retention_repo: Option<Arc<RetentionPolicyRepository>>,
+    tag_repo: Option<Arc<SealTagRepository>>,
+    collection_repo: Option<Arc<CollectionRepository>>,
+    anchor_proof_repo: Option<Arc<AnchorProofRepository>>,
+    signing_key_repo: Option<Arc<SigningKeyRepository>>,
+}
+
+impl Repositories {
+    /// Initialize all components using the shared pools; read-heavy queries
+    /// go to the read replica when there is one, tenant queries run under
+    /// row-level security with `tenant_rls` (and system queries bypass it)
+    fn from_pools(pools: &DatabasePools, tenant_rls: bool) -> Self {
+        tracing::info!("Manifest store and repositories initialized with shared pool");
+        let pool = &pools.primary;
+        let read_pool = pools.read();
+        Self {
+            db_pools: Some(pools.clone()),
+            manifest_store: Some(Arc::new(PostgresManifestStore::from_pools(
+                pool.clone(),
+                read_pool.clone(),
+            ))),
+            user_repo: Some(Arc::new(UserRepository::new(pool.clone()))),
+            seal_repo: Some(Arc::new(
+                SealRepository::with_read_pool(pool.clone(), read_pool.clone())
+                    .with_tenant_rls(tenant_rls),
+            )),
+            share_repo: Some(Arc::new(
+                SealShareRepository::new(pool.clone()).with_tenant_rls(tenant_rls),
+            )),
+            upload_repo: Some(Arc::new(UploadSessionRepository::new(pool.clone()))),
+            audit_repo: Some(Arc::new(
+                AuditLogRepository::new(pool.clone()).with_tenant_rls(tenant_rls),
+            )),
+            verification_repo: Some(Arc::new(
+                VerificationRepository::new(pool.clone()).with_tenant_rls(tenant_rls),
+            )),
+            api_key_repo: Some(Arc::new(
+                ApiKeyRepository::new(pool.clone()).with_tenant_rls(tenant_rls),
+            )),
+            credential_repo: Some(Arc::new(LocalCredentialRepository::new(pool.clone()))),
+            organization_repo: Some(Arc::new(OrganizationSettingsRepository::new(pool.clone()))),
+            retention_repo: Some(Arc::new(
+                RetentionPolicyRepository::new(pool.clone()).with_tenant_rls(tenant_rls),
+            )),
+            tag_repo: Some(Arc::new(
+                SealTagRepository::new(pool.clone()).with_tenant_rls(tenant_rls),
+            )),
+            collection_repo: Some(Arc::new(
+                CollectionRepository::new(pool.clone()).with_tenant_rls(tenant_rls),
+            )),
+            anchor_proof_repo: Some(Arc::new(AnchorProofRepository::new(pool.clone()))),
+            signing_key_repo: Some(Arc::new(SigningKeyRepository::new(pool.clone()))),
+        }
+    }
 }
 
 /// Create the application router with custom configuration (async version)
 /// Uses PostgreSQL storage if DATABASE_URL is set.
+///
+/// Database errors are logged and the server runs without the database; the
+/// binary uses [`crate::startup::self_check`] and [`create_router_with_pools`]
+/// instead, to refuse to start.
 pub async fn create_router_with_config(config: &Config) -> Router {
     // Initialize stores if DATABASE_URL is set
-    let (storage, manifest_store, user_repo, seal_repo) = match std::env::var("DATABASE_URL") {
+    let pool = match std::env::var("DATABASE_URL") {
         Ok(url) => {
             // Create shared pool with configured connection limits
-            let pool = match PgPoolOptions::new()
-                .max_connections(config.database_max_connections)
-                .min_connections(config.database_min_connections)
-                .connect(&url)
-                .await
-            {
+            match config.database_pool_options().connect(&url).await {
                 Ok(pool) => {
                     tracing::info!(
                         "Database pool connected (min: {}, max: {})",
@@ -71,9 +206,12 @@ pub async fn create_router_with_config(config: &Config) -> Router {
                     );
 
                     // Run migrations
-                    if let Err(e) = sqlx::migrate!("./migrations").run(&pool).await {
+                    if let Err(e) = run_migrations(&MIGRATOR, &pool).await {
                         tracing::error!("Failed to run migrations: {}", e);
                         None
+                    } else if let Err(e) = set_row_level_security(&pool, config.tenant_rls).await {
+                        tracing::error!("Failed to apply TENANT_RLS: {}", e);
+                        None
                     } else {
                         tracing::info!("Database migrations applied");
                         Some(pool)
@@ -83,62 +221,108 @@ pub async fn create_router_with_config(config: &Config) -> Router {
                     tracing::warn!("Failed to connect to database: {}", e);
                     None
                 }
-            };
+            }
+        }
+        Err(_) => {
+            tracing::info!("DATABASE_URL not set, database features disabled");
+            None
+        }
+    };
 
-            // Initialize all components using shared pool
-            let storage = pool
-                .as_ref()
-                .map(|p| WebAuthnStorage::from_pool(p.clone()))
-                .unwrap_or_else(WebAuthnStorage::in_memory);
+    // Read replica for listings, exports and resolution, if configured
+    let pools = match (pool, &config.database_read_url) {
+        (Some(primary), Some(read_url)) => {
+            let replica = match config.database_pool_options().connect(read_url).await {
+                Ok(replica) => {
+                    tracing::info!("Read replica connected (DATABASE_READ_URL)");
+                    Some(replica)
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to connect to read replica, using primary: {}", e);
+                    None
+                }
+            };
+            Some(DatabasePools { primary, replica })
+        }
+        (pool, _) => pool.map(|primary| DatabasePools {
+            primary,
+            replica: None,
+        }),
+    };
 
-            let manifest_store = pool.as_ref().map(|p| {
-                tracing::info!("Manifest store initialized with shared pool");
-                Arc::new(PostgresManifestStore::from_pool(p.clone()))
-            });
+    create_router_with_pools(config, pools)
+}
 
-            let user_repo = pool.as_ref().map(|p| {
-                tracing::info!("User repository initialized with shared pool");
-                Arc::new(UserRepository::new(p.clone()))
-            });
+/// Create the application router over already migrated database pools
+/// (None = database features disabled).
+pub fn create_router_with_pools(config: &Config, pools: Option<DatabasePools>) -> Router {
+    create_router_with_janitor(config, pools).0
+}
 
-            let seal_repo = pool.map(|p| {
-                tracing::info!("Seal repository initialized with shared pool");
-                Arc::new(SealRepository::new(p))
-            });
+/// Like [`create_router_with_pools`], also returning the [`Janitor`] running
+/// the periodic maintenance tasks, to stop them on graceful shutdown.
+pub fn create_router_with_janitor(
+    config: &Config,
+    pools: Option<DatabasePools>,
+) -> (Router, Arc<Janitor>) {
+    let (storage, repositories) = match pools {
+        Some(pools) => (
+            WebAuthnStorage::from_pool(pools.primary.clone()),
+            Repositories::from_pools(&pools, config.tenant_rls),
+        ),
+        None => (WebAuthnStorage::in_memory(), Repositories::default()),
+    };
 
-            (storage, manifest_store, user_repo, seal_repo)
-        }
-        Err(_) => {
-            tracing::info!("DATABASE_URL not set, database features disabled");
-            (WebAuthnStorage::in_memory(), None, None, None)
-        }
+    // Self-hosted accounts (AUTH_MODE=local), signed with our own key pair
+    let local_auth = match &config.auth_mode {
+        AuthMode::Clerk => None,
+        AuthMode::Local(local) => Some(Arc::new(
+            LocalAuth::new(local).unwrap_or_else(|e| panic!("Invalid local auth: {}", e)),
+        )),
     };
 
-    // Initialize JWKS cache for JWT validation if Clerk JWKS URL is configured
-    let jwks_cache = config.clerk_jwks_url.as_ref().map(|url| {
-        tracing::info!("JWT authentication enabled (CLERK_JWKS_URL set)");
-        Arc::new(JwksCache::new(url.clone()))
+    // Trusted JWT issuers (Clerk and JWT_ISSUERS_FILE), each with a JWKS
+    // cache, plus our own in local mode
+    let issuers = config.trusted_jwt_issuers();
+    let jwt_issuers = (!issuers.is_empty() || local_auth.is_some()).then(|| {
+        let mut issuers = JwtIssuers::new(issuers);
+        if let Some(local) = &local_auth {
+            issuers.trust_local(local);
+        }
+        tracing::info!(issuers = ?issuers.names(), "JWT authentication enabled");
+        Arc::new(issuers)
     });
 
-    create_router_internal(
-        config,
-        storage,
-        manifest_store,
-        user_repo,
-        seal_repo,
-        jwks_cache,
-    )
+    create_router_internal(config, storage, repositories, jwt_issuers, local_auth)
 }
 
 /// Internal router creation with provided storage
 fn create_router_internal(
     config: &Config,
     webauthn_storage: WebAuthnStorage,
-    manifest_store: Option<Arc<PostgresManifestStore>>,
-    user_repo: Option<Arc<UserRepository>>,
-    seal_repo: Option<Arc<SealRepository>>,
-    jwks_cache: Option<Arc<JwksCache>>,
-) -> Router {
+    repositories: Repositories,
+    jwt_issuers: Option<Arc<JwtIssuers>>,
+    local_auth: Option<Arc<LocalAuth>>,
+) -> (Router, Arc<Janitor>) {
+    let Repositories {
+        db_pools,
+        manifest_store,
+        user_repo,
+        seal_repo,
+        share_repo,
+        upload_repo,
+        audit_repo,
+        verification_repo,
+        api_key_repo,
+        credential_repo,
+        organization_repo,
+        retention_repo,
+        tag_repo,
+        collection_repo,
+        anchor_proof_repo,
+        signing_key_repo,
+    } = repositories;
+
     // Configure CORS based on allowed_origins
     let cors = match &config.allowed_origins {
         Some(origins) if !origins.is_empty() => {
@@ -146,12 +330,41 @@ fn create_router_internal(
             tracing::info!("CORS: Restricting to {} origin(s)", origins.len());
             CorsLayer::new()
                 .allow_origin(origins)
-                .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+                .allow_methods([
+                    Method::GET,
+                    Method::HEAD,
+                    Method::POST,
+                    Method::PUT,
+                    Method::PATCH,
+                    Method::DELETE,
+                    Method::OPTIONS,
+                ])
                 .allow_headers([
                     header::CONTENT_TYPE,
                     header::ACCEPT,
                     header::AUTHORIZATION,
                     header::ORIGIN,
+                    header::IF_NONE_MATCH,
+                    UPLOAD_OFFSET,
+                    API_KEY_HEADER,
+                    API_VERSION_HEADER,
+                ])
+                .expose_headers([
+                    header::LOCATION,
+                    header::ETAG,
+                    header::LINK,
+                    header::RETRY_AFTER,
+                    API_VERSION_HEADER,
+                    DEPRECATION_HEADER,
+                    SUNSET_HEADER,
+                    UPLOAD_OFFSET,
+                    UPLOAD_LENGTH,
+                    MOCK_ENTROPY_HEADER,
+                    SEAL_ID_HEADER,
+                    TRUST_TIER_HEADER,
+                    QRNG_SOURCE_HEADER,
+                    ANCHOR_DIGEST_HEADER,
+                    PERCEPTUAL_HASH_HEADER,
                 ])
                 .allow_credentials(true)
         }
@@ -164,21 +377,9 @@ fn create_router_internal(
         }
     };
 
-    // Request body limit
-    let body_limit = RequestBodyLimitLayer::new(config.body_limit_mb * 1024 * 1024);
-
-    // Request timeout
-    let timeout = TimeoutLayer::with_status_code(
-        StatusCode::REQUEST_TIMEOUT,
-        Duration::from_secs(config.timeout_secs),
-    );
-
-    // Request ID header name
-    let x_request_id = axum::http::HeaderName::from_static("x-request-id");
-
-    // Trace layer with request ID in spans
+    // Trace layer: one span per request with its request ID (see logging.rs)
     let trace_layer = TraceLayer::new_for_http()
-        .make_span_with(DefaultMakeSpan::new().include_headers(true))
+        .make_span_with(RequestSpan)
         .on_response(DefaultOnResponse::new().include_headers(true));
 
     // Initialize WebAuthn state with provided storage
@@ -196,62 +397,495 @@ fn create_router_internal(
         .route("/register/finish", post(finish_registration))
         .route("/authenticate/start", post(start_authentication))
         .route("/authenticate/finish", post(finish_authentication))
-        .with_state(webauthn_state);
+        .route("/capture/session", post(start_capture_session))
+        .with_state(Arc::clone(&webauthn_state));
+
+    // Read-only switch for database maintenance windows
+    let maintenance = Arc::new(MaintenanceMode::new(
+        config.read_only,
+        config.maintenance_retry_after_secs,
+    ));
+    if config.read_only {
+        tracing::warn!("Starting in read-only maintenance mode: sealing and writes are refused");
+    }
+
+    // Periodic maintenance, stopped on shutdown or when the state is dropped;
+    // tasks writing to the database pause in read-only mode
+    let janitor = Arc::new(Janitor::new());
+    let webauthn = Arc::clone(&webauthn_state);
+    janitor.register(
+        "webauthn_challenges",
+        CHALLENGE_CLEANUP_INTERVAL,
+        move || {
+            let removed = webauthn.storage.cleanup_expired();
+            async move { Ok(removed) }
+        },
+    );
+
+    // Signing keys of trusted issuers, refreshed before they expire
+    if let Some(issuers) = &jwt_issuers {
+        let issuers = Arc::clone(issuers);
+        janitor.register("jwks_refresh", JWKS_REFRESH_INTERVAL, move || {
+            let issuers = Arc::clone(&issuers);
+            async move { issuers.refresh_due().await }
+        });
+    }
+
+    // Opaque tokens, checked by introspection when configured
+    let introspector = config.introspection.clone().map(|introspection| {
+        tracing::info!(url = %introspection.url, "Token introspection enabled");
+        Arc::new(TokenIntrospector::new(introspection))
+    });
+    if let Some(introspector) = &introspector {
+        let introspector = Arc::clone(introspector);
+        janitor.register(
+            "introspection_cache",
+            INTROSPECTION_SWEEP_INTERVAL,
+            move || {
+                let purged = introspector.purge_expired();
+                async move { Ok(purged) }
+            },
+        );
+    }
+    if !config.service_clients.is_empty() {
+        tracing::info!(services = ?config.service_clients, "Service clients trusted");
+    }
+
+    // Live streams: idle ones are dropped
+    let streams = Arc::new(StreamRegistry::new());
+    let registry = Arc::clone(&streams);
+    janitor.register("stream_idle", STREAM_SWEEP_INTERVAL, move || {
+        let dropped = registry.purge_idle(STREAM_IDLE_TIMEOUT);
+        async move { Ok(dropped) }
+    });
+
+    // Resumable uploads: session state in PostgreSQL when available, data on disk
+    let upload_ttl = Duration::from_secs(config.upload_ttl_secs);
+    let upload_store = Arc::new(match upload_repo {
+        Some(repo) => UploadStore::with_repository(repo, config.upload_dir.clone(), upload_ttl),
+        None => UploadStore::in_memory(config.upload_dir.clone(), upload_ttl),
+    });
+    let store = Arc::clone(&upload_store);
+    janitor.register_write(
+        "upload_sweep",
+        UPLOAD_SWEEP_INTERVAL,
+        &maintenance,
+        move || {
+            let store = Arc::clone(&store);
+            async move { store.purge_expired().await.map_err(|e| e.to_string()) }
+        },
+    );
+
+    // Personal data exports: archives on disk, served through signed links
+    let export_store = Arc::new(ExportStore::new(
+        config.export_dir.clone(),
+        Duration::from_secs(config.export_ttl_secs),
+    ));
+    let store = Arc::clone(&export_store);
+    janitor.register("export_sweep", EXPORT_SWEEP_INTERVAL, move || {
+        let store = Arc::clone(&store);
+        async move { Ok(store.purge_expired().await) }
+    });
+
+    // Group near-duplicate manifests in the background
+    if let Some(store) = &manifest_store {
+        let store = Arc::clone(store);
+        let threshold = config.duplicate_threshold;
+        janitor.register_write(
+            "manifest_clustering",
+            Duration::from_secs(config.cluster_interval_secs),
+            &maintenance,
+            move || {
+                let store = Arc::clone(&store);
+                async move {
+                    clustering::cluster_pending(&store, threshold)
+                        .await
+                        .map_err(|e| e.to_string())
+                }
+            },
+        );
+    }
+
+    // Statistics views behind the admin and usage stats
+    if let Some(repo) = &seal_repo {
+        let repo = Arc::clone(repo);
+        janitor.register_write(
+            "stats_refresh",
+            STATS_REFRESH_INTERVAL,
+            &maintenance,
+            move || {
+                let repo = Arc::clone(&repo);
+                async move { repo.refresh_stats().await.map_err(|e| e.to_string()) }
+            },
+        );
+    }
+
+    // Organizations' retention policies: expired seals and media, audited
+    if let Some(repo) = &retention_repo {
+        let repo = Arc::clone(repo);
+        janitor.register_write("retention", RETENTION_INTERVAL, &maintenance, move || {
+            let repo = Arc::clone(&repo);
+            async move { repo.enforce().await.map_err(|e| e.to_string()) }
+        });
+    }
+
+    // Batched anchoring of new seals, when an interval is configured
+    let anchor_batcher = config.anchor_batch_interval_secs.and_then(|secs| {
+        let provider: Arc<dyn AnchorProvider> = match OpenTimestamps::new() {
+            Ok(provider) => Arc::new(provider),
+            Err(e) => {
+                tracing::warn!(error = %e, "Batched anchoring disabled");
+                return None;
+            }
+        };
+        let batcher = Arc::new(match anchor_proof_repo {
+            Some(repo) => AnchorBatcher::with_repository(provider, repo),
+            None => AnchorBatcher::in_memory(provider),
+        });
+        let queue = Arc::clone(&batcher);
+        janitor.register_write(
+            "anchor_batch",
+            Duration::from_secs(secs),
+            &maintenance,
+            move || {
+                let queue = Arc::clone(&queue);
+                async move { queue.flush().await.map_err(|e| e.to_string()) }
+            },
+        );
+        tracing::info!(interval_secs = secs, "Batched anchoring enabled");
+        Some(batcher)
+    });
+
+    // Seal signing key (SIGNING_KEY_FILE), registered on first use
+    let signer = config.signing_key_file.as_deref().map(|path| {
+        let signer = ServerSigner::load(path, &config.issuer_did)
+            .unwrap_or_else(|e| panic!("Invalid signing key {}: {}", path.display(), e));
+        tracing::info!(
+            key_id = signer.key_id(),
+            "Seals signed with a registered key"
+        );
+        signer
+    });
+    let overlap = Duration::from_secs(config.signing_key_overlap_secs);
+    let signing_keys = Arc::new(match signing_key_repo {
+        Some(repo) => SigningKeyRegistry::with_repository(repo, signer, overlap),
+        None => SigningKeyRegistry::in_memory(signer, overlap),
+    });
+
+    // Alerts to seal owners, when a webhook or SMTP server is configured
+    let notifier = config.notifications.is_enabled().then(|| {
+        tracing::info!("Seal owner notifications enabled");
+        Arc::new(Notifier::new(
+            config.notifications.clone(),
+            user_repo.clone(),
+        ))
+    });
+
+    // Dependency checks for /ready
+    let readiness = Arc::new(
+        ReadinessProbe::new(
+            db_pools,
+            manifest_store.clone(),
+            jwt_issuers.clone(),
+            config.solana_rpc_url.clone(),
+        )
+        .with_timing(
+            Duration::from_millis(config.ready_check_timeout_ms),
+            Duration::from_secs(config.ready_cache_secs),
+        ),
+    );
 
     // Create app state for shared resources
     let app_state = AppState {
         manifest_store,
         user_repo,
         seal_repo,
-        jwks_cache,
+        share_repo,
+        tag_repo,
+        collection_repo,
+        share_base_url: Arc::from(config.share_base_url.as_str()),
+        issuer_did: Arc::from(config.issuer_did.as_str()),
+        audit_repo,
+        verification_repo,
+        monthly_seal_quota: config.monthly_seal_quota,
+        api_key_repo,
+        batch_resolve_max_items: config.batch_resolve_max_items,
+        batch_resolve_limiter: Arc::new(KeyedRateLimiter::new(
+            config.batch_resolve_items_per_min,
+            Duration::from_secs(60),
+        )),
+        duplicate_threshold: config.duplicate_threshold,
+        notifier,
+        jwt_issuers,
+        introspector,
+        service_clients: Arc::from(config.service_clients.clone()),
+        local_auth,
+        credential_repo,
+        organization_repo,
+        retention_repo,
         allow_mock_qrng: config.allow_mock_qrng,
+        qrng_selection: Arc::new(config.qrng_selection.clone()),
+        strict_media_types: config.strict_media_types,
+        mock_qrng_verification: config.mock_qrng_verification,
+        upload_policy: Arc::new(config.upload_policy.clone()),
+        trust_policy: Arc::new(config.trust_policy.clone()),
+        webauthn: webauthn_state,
+        streams,
+        uploads: upload_store,
+        exports: export_store,
+        readiness,
+        maintenance: Arc::clone(&maintenance),
+        verification_cache: Arc::new(VerificationCache::new(
+            config.verification_cache_size,
+            Duration::from_secs(config.verification_cache_ttl_secs),
+        )),
+        verification_pool: Arc::new(VerificationPool::new(config.verification_workers)),
+        anchor_checker: Arc::new(AnchorChecker::new(
+            config.anchor_min_confirmations,
+            Duration::from_millis(config.anchor_check_timeout_ms),
+        )),
+        anchor_batcher,
+        signing_keys,
+        janitor: Arc::clone(&janitor),
     };
 
-    // Routes that require app state (seal, resolve, users, seals, c2pa)
-    let mut stateful_router = Router::new()
-        .route("/seal", post(seal_handler))
-        .route("/resolve", post(resolve_handler))
+    // Unversioned routes of the first releases stay as deprecated aliases of
+    // their /api/v1 paths
+    let legacy_routes = Deprecation::legacy_routes(config.legacy_routes_sunset);
+
+    // Media upload routes: large bodies, slow sealing and verification
+    let mut media_routes = vec![
+        ("/seal", post(seal_handler)),
+        ("/verify", post(verify_handler)),
+    ];
+    // Add C2PA routes if feature enabled (needs AppState for mock QRNG gating)
+    #[cfg(feature = "c2pa")]
+    media_routes.extend([
+        ("/c2pa/embed", post(c2pa_embed_handler)),
+        ("/c2pa/verify", post(c2pa_verify_handler)),
+    ]);
+    let upload_router = v1_with_legacy_aliases(media_routes, legacy_routes)
+        .route("/api/v1/seals/import", post(import_seal_handler))
+        .route(
+            "/api/v1/streams/{stream_id}/chunks",
+            post(stream_chunk_handler),
+        )
+        .route(
+            "/api/v1/uploads/{upload_id}",
+            head(upload_status_handler)
+                .patch(upload_chunk_handler)
+                .delete(delete_upload_handler),
+        )
+        .route(
+            "/api/v1/uploads/{upload_id}/finalize",
+            post(finalize_upload_handler),
+        );
+
+    // Hash lookups: small JSON bodies, quick answers
+    let lookup_router = v1_with_legacy_aliases(
+        vec![
+            ("/resolve", post(resolve_handler)),
+            ("/resolve/batch", post(resolve_batch_handler)),
+        ],
+        legacy_routes,
+    );
+
+    // Other routes that require app state (users, seals, admin...)
+    let stateful_router = Router::new()
+        .route("/ready", get(ready))
+        // Self-hosted accounts (AUTH_MODE=local)
+        .route("/api/v1/auth/register", post(register_handler))
+        .route("/api/v1/auth/login", post(login_handler))
         // User routes (v1 API)
         .route("/api/v1/users/sync", post(sync_user_handler))
         .route(
             "/api/v1/users/me",
             get(get_current_user_handler).delete(delete_user_handler),
         )
+        .route(
+            "/api/v1/users/me/stats",
+            get(get_current_user_stats_handler),
+        )
+        .route("/api/v1/users/me/export", get(export_user_data_handler))
+        .route(
+            "/api/v1/exports/{export_id}/download",
+            get(download_export_handler),
+        )
+        // API keys for machine clients (v1 API)
+        .route(
+            "/api/v1/api-keys",
+            get(list_api_keys_handler).post(create_api_key_handler),
+        )
+        .route("/api/v1/api-keys/{key_id}", delete(revoke_api_key_handler))
+        // Offline sync for mobile capture (v1 API)
+        .route("/api/v1/sync/push", post(sync_push_handler))
+        .route("/api/v1/sync/pull", get(sync_pull_handler))
         // Seals routes (v1 API) - user's seal history
         .route("/api/v1/seals", get(list_user_seals_handler))
+        .route("/api/v1/seals/map", get(seal_map_handler))
+        .route("/api/v1/seals/export-batch", post(batch_export_handler))
+        .route(
+            "/api/v1/seals/export-batch/{export_id}",
+            get(batch_export_status_handler),
+        )
         .route("/api/v1/seals/{seal_id}", get(get_user_seal_handler))
-        .route("/api/v1/seals/{seal_id}/export", get(export_seal_handler));
-
-    // Add C2PA routes if feature enabled (needs AppState for mock QRNG gating)
-    #[cfg(feature = "c2pa")]
-    {
-        stateful_router = stateful_router
-            .route("/c2pa/embed", post(c2pa_embed_handler))
-            .route("/c2pa/verify", post(c2pa_verify_handler));
-    }
-
-    let stateful_router = stateful_router.with_state(app_state);
+        .route("/api/v1/seals/{seal_id}/export", get(export_seal_handler))
+        .route("/api/v1/seals/{seal_id}/report", get(seal_report_handler))
+        .route("/api/v1/seals/{seal_id}/qr", get(seal_qr_handler))
+        .route(
+            "/api/v1/seals/{seal_id}/duplicates",
+            get(seal_duplicates_handler),
+        )
+        .route(
+            "/api/v1/seals/{seal_id}/verifications",
+            get(seal_verifications_handler),
+        )
+        .route("/api/v1/seals/{seal_id}/share", post(create_share_handler))
+        .route("/api/v1/seals/{seal_id}/shares", get(list_shares_handler))
+        .route(
+            "/api/v1/seals/{seal_id}/shares/{share_id}",
+            delete(revoke_share_handler),
+        )
+        .route(
+            "/api/v1/seals/{seal_id}/tags",
+            get(get_seal_tags_handler).put(set_seal_tags_handler),
+        )
+        // Tags and collections (v1 API)
+        .route("/api/v1/tags", get(list_tags_handler))
+        .route(
+            "/api/v1/tags/{tag}",
+            put(rename_tag_handler).delete(delete_tag_handler),
+        )
+        .route(
+            "/api/v1/collections",
+            get(list_collections_handler).post(create_collection_handler),
+        )
+        .route(
+            "/api/v1/collections/{collection_id}",
+            get(get_collection_handler)
+                .put(update_collection_handler)
+                .delete(delete_collection_handler),
+        )
+        .route(
+            "/api/v1/collections/{collection_id}/seals/{seal_id}",
+            put(add_collection_seal_handler).delete(remove_collection_seal_handler),
+        )
+        // Public share links (v1 API)
+        .route("/api/v1/shared/{token}", get(shared_seal_handler))
+        // White-label branding of verification pages (v1 API)
+        .route("/api/v1/branding", get(branding_handler))
+        // Batched anchors of new seals (v1 API)
+        .route("/api/v1/anchors/{digest}", get(anchor_status_handler))
+        // Seal signing keys (v1 API)
+        .route("/api/v1/signing-keys", get(list_signing_keys_handler))
+        .route("/.well-known/did.json", get(did_document_handler))
+        // Live stream sealing (v1 API)
+        .route("/api/v1/streams", post(start_stream_handler))
+        .route(
+            "/api/v1/streams/{stream_id}/finish",
+            post(finish_stream_handler),
+        )
+        // Resumable uploads (v1 API)
+        .route("/api/v1/uploads", post(create_upload_handler))
+        // Admin routes (v1 API) - admin role required
+        .route("/api/v1/admin/stats", get(admin_stats_handler))
+        .route("/api/v1/admin/tasks", get(admin_tasks_handler))
+        .route(
+            "/api/v1/admin/maintenance",
+            get(get_maintenance_handler).put(update_maintenance_handler),
+        )
+        .route("/api/v1/admin/users", get(admin_list_users_handler))
+        .route(
+            "/api/v1/admin/users/{user_id}/suspend",
+            post(suspend_user_handler),
+        )
+        .route(
+            "/api/v1/admin/users/{user_id}/unsuspend",
+            post(unsuspend_user_handler),
+        )
+        .route(
+            "/api/v1/admin/users/{user_id}/tier",
+            put(update_user_tier_handler),
+        )
+        .route(
+            "/api/v1/admin/users/{user_id}/organization",
+            put(update_user_organization_handler),
+        )
+        .route(
+            "/api/v1/admin/seals/revoked",
+            get(list_revoked_seals_handler),
+        )
+        .route(
+            "/api/v1/admin/seals/{seal_id}/revoke",
+            post(revoke_seal_handler),
+        )
+        .route(
+            "/api/v1/admin/seals/{seal_id}/reinstate",
+            post(reinstate_seal_handler),
+        )
+        .route(
+            "/api/v1/admin/seals/{seal_id}/legal-hold",
+            put(update_legal_hold_handler),
+        )
+        .route(
+            "/api/v1/admin/signing-keys/{key_id}/revoke",
+            post(revoke_signing_key_handler),
+        )
+        .route(
+            "/api/v1/admin/organizations/{organization_id}/settings",
+            get(get_organization_settings_handler)
+                .put(update_organization_settings_handler)
+                .delete(delete_organization_settings_handler),
+        )
+        .route(
+            "/api/v1/admin/organizations/{organization_id}/retention",
+            get(get_retention_policy_handler)
+                .put(update_retention_policy_handler)
+                .delete(delete_retention_policy_handler),
+        );
 
-    // Base router with common layers
+    // Base router: every route gets the body limit and timeout of its group
     let router = Router::new()
-        .merge(stateful_router)
-        .route("/verify", post(verify_handler))
+        .merge(stateful_router.with_state(app_state.clone()))
         .route("/health", get(health))
-        .route("/ready", get(ready))
-        .nest("/webauthn", webauthn_router);
-
-    let router = router
+        .nest(&format!("{API_V1}/webauthn"), webauthn_router.clone())
+        .nest(
+            "/webauthn",
+            webauthn_router.route_layer(from_fn_with_state(legacy_routes, deprecated)),
+        )
         // OpenAPI documentation endpoints
         .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .layer(route_limits(
+            config.body_limit_mb * 1024 * 1024,
+            config.timeout_secs,
+        ))
+        .merge(
+            upload_router
+                .layer(route_limits(
+                    config.upload_body_limit_mb * 1024 * 1024,
+                    config.upload_timeout_secs,
+                ))
+                .with_state(app_state.clone()),
+        )
+        .merge(
+            lookup_router
+                .layer(route_limits(
+                    config.lookup_body_limit_kb * 1024,
+                    config.lookup_timeout_secs,
+                ))
+                .with_state(app_state),
+        );
+
+    let router = router
+        .layer(CompressionLayer::new().compress_when(compress_when()))
+        .layer(from_fn_with_state(maintenance, reject_writes))
+        .layer(from_fn(negotiate_version))
         .layer(cors)
-        .layer(body_limit)
-        .layer(timeout)
-        // Request ID layers - set ID on incoming request, propagate to response
-        .layer(PropagateRequestIdLayer::new(x_request_id.clone()))
-        .layer(SetRequestIdLayer::new(x_request_id, MakeRequestUuid));
+        // Credentials in response headers are never logged
+        .layer(SetSensitiveResponseHeadersLayer::new(SENSITIVE_HEADERS));
 
     // Conditionally apply rate limiting (disabled in tests, enabled in production)
-    if config.rate_limit_enabled {
+    let router = if config.rate_limit_enabled {
         // Use SmartIpKeyExtractor to support proxies (X-Forwarded-For, X-Real-Ip)
         // and fall back to direct connection IP
         let governor_conf = GovernorConfigBuilder::default()
@@ -267,11 +901,49 @@ fn create_router_internal(
             config.rate_limit_burst
         );
 
-        router
-            .layer(GovernorLayer::new(Arc::new(governor_conf)))
-            .layer(trace_layer)
+        router.layer(GovernorLayer::new(Arc::new(governor_conf)))
     } else {
         tracing::warn!("Rate limiting: DISABLED");
-        router.layer(trace_layer)
-    }
+        router
+    };
+
+    // Outermost: request IDs and sensitive request headers are set before the
+    // trace layer opens the request span
+    let router = router
+        .layer(trace_layer)
+        .layer(PropagateRequestIdLayer::new(REQUEST_ID_HEADER))
+        .layer(SetRequestIdLayer::new(REQUEST_ID_HEADER, MakeRequestUuid))
+        .layer(SetSensitiveRequestHeadersLayer::new(SENSITIVE_HEADERS));
+    (router, janitor)
+}
+
+/// Responses worth compressing (gzip or brotli, as the client accepts)
+///
+/// On top of the defaults (bodies of 32+ bytes, no raster images, gRPC or
+/// event streams), media returned by C2PA embedding, PDF reports and zip
+/// exports are left alone: they are compressed already.
+fn compress_when() -> impl Predicate {
+    DefaultPredicate::new()
+        .and(NotForContentType::const_new("video/"))
+        .and(NotForContentType::const_new("audio/"))
+        .and(NotForContentType::const_new("application/pdf"))
+        .and(NotForContentType::const_new("application/zip"))
+}
+
+/// Body limit (in bytes) and timeout layers of a group of routes
+///
+/// axum's default 2 MB limit of body extractors is lifted so that the
+/// group's limit is the only one that applies.
+fn route_limits(
+    body_limit: usize,
+    timeout_secs: u64,
+) -> (TimeoutLayer, RequestBodyLimitLayer, DefaultBodyLimit) {
+    (
+        TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            Duration::from_secs(timeout_secs),
+        ),
+        RequestBodyLimitLayer::new(body_limit),
+        DefaultBodyLimit::disable(),
+    )
 }
@@ -0,0 +1,320 @@
+//! Seal signing keys and their registry
+//!
+//! Without `SIGNING_KEY_FILE`, `/seal` signs every seal with a one-off key
+//! pair and the seal carries no key ID. With it, seals are signed with the
+//! configured key pair and carry its key ID (seal version 16), a DID URL
+//! under the server's `ISSUER_DID` (`did:web:veritas-q.com#<key ID>`). The
+//! key is recorded in the registry (PostgreSQL when configured, in memory
+//! otherwise) on first use: a new key retires the previous active one, which
+//! stays valid for `SIGNING_KEY_OVERLAP_SECS` while every replica switches
+//! over. `/verify` resolves each seal's key ID against the registry,
+//! `GET /api/v1/signing-keys` publishes it, and `/.well-known/did.json`
+//! publishes it as a did:web document for verifiers that do not use our API.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use dashmap::DashMap;
+use pqcrypto_traits::sign::PublicKey;
+use veritas_core::did::did_key_id;
+use veritas_core::{KeypairFile, KeypairFileError, SigningKey, SigningKeyStatus, Zeroizing};
+
+use crate::db::SigningKeyRepository;
+use crate::error::ApiError;
+
+/// Default time a replaced signing key stays valid after a rotation (7 days)
+pub const DEFAULT_SIGNING_KEY_OVERLAP_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Longest overlap honored (100 years)
+const MAX_OVERLAP_SECS: u64 = 100 * 365 * 24 * 60 * 60;
+
+/// Environment variable holding the passphrase of an encrypted SIGNING_KEY_FILE
+pub const SIGNING_KEY_PASSPHRASE_ENV: &str = "SIGNING_KEY_PASSPHRASE";
+
+/// Long-lived key pair signing the server's seals
+pub struct ServerSigner {
+    key_id: String,
+    keypair: KeypairFile,
+}
+
+impl ServerSigner {
+    /// Sign with `keypair`, under its DID URL as a key of `issuer_did`
+    pub fn new(keypair: KeypairFile, issuer_did: &str) -> Self {
+        Self {
+            key_id: did_key_id(issuer_did, keypair.public_key.as_bytes()),
+            keypair,
+        }
+    }
+
+    /// Load a VQKP key pair file (`veritas seal --save-keypair`), decrypted with
+    /// SIGNING_KEY_PASSPHRASE when set
+    pub fn load(path: &Path, issuer_did: &str) -> Result<Self, KeypairFileError> {
+        let passphrase = std::env::var(SIGNING_KEY_PASSPHRASE_ENV)
+            .ok()
+            .filter(|passphrase| !passphrase.is_empty())
+            .map(Zeroizing::new);
+        KeypairFile::load(path, passphrase.as_deref().map(String::as_str))
+            .map(|keypair| Self::new(keypair, issuer_did))
+    }
+
+    /// Key ID written into the seals
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Key pair signing the seals
+    pub fn keypair(&self) -> &KeypairFile {
+        &self.keypair
+    }
+}
+
+/// Registry storage backend
+enum KeyBackend {
+    /// PostgreSQL storage (production)
+    Postgres(Arc<SigningKeyRepository>),
+    /// In-memory storage (development fallback)
+    Memory(DashMap<String, SigningKey>),
+}
+
+/// Registered signing keys, and the key signing this server's seals
+pub struct SigningKeyRegistry {
+    keys: KeyBackend,
+    signer: Option<ServerSigner>,
+    overlap: Duration,
+    /// Whether `signer` is registered and active
+    registered: AtomicBool,
+}
+
+impl SigningKeyRegistry {
+    /// Create a registry keeping keys in memory.
+    pub fn in_memory(signer: Option<ServerSigner>, overlap: Duration) -> Self {
+        Self::new(KeyBackend::Memory(DashMap::new()), signer, overlap)
+    }
+
+    /// Create a registry keeping keys in PostgreSQL.
+    pub fn with_repository(
+        repo: Arc<SigningKeyRepository>,
+        signer: Option<ServerSigner>,
+        overlap: Duration,
+    ) -> Self {
+        Self::new(KeyBackend::Postgres(repo), signer, overlap)
+    }
+
+    fn new(keys: KeyBackend, signer: Option<ServerSigner>, overlap: Duration) -> Self {
+        Self {
+            keys,
+            signer,
+            overlap,
+            registered: AtomicBool::new(false),
+        }
+    }
+
+    /// Key to sign new seals with (`None` = one-off key per seal).
+    ///
+    /// Registers the key on first use. Fails while it is revoked, so no seal
+    /// is signed with a withdrawn key.
+    pub async fn signer(&self) -> Result<Option<&ServerSigner>, ApiError> {
+        let Some(signer) = &self.signer else {
+            return Ok(None);
+        };
+        if !self.registered.load(Ordering::Acquire) {
+            let key = self.register(signer).await?;
+            if key.status == SigningKeyStatus::Revoked {
+                tracing::error!(
+                    key_id = %key.key_id,
+                    "Seal signing key is revoked, configure a new SIGNING_KEY_FILE"
+                );
+                return Err(ApiError::service_unavailable("Seal signing key revoked"));
+            }
+            self.registered.store(true, Ordering::Release);
+            tracing::info!(key_id = %key.key_id, "Seal signing key registered");
+        }
+        Ok(Some(signer))
+    }
+
+    /// Record `signer` as the active key, retiring the others after the
+    /// overlap period. Returns the key as registered.
+    async fn register(&self, signer: &ServerSigner) -> Result<SigningKey, ApiError> {
+        let now = Utc::now();
+        let key = SigningKey {
+            key_id: signer.key_id.clone(),
+            ..SigningKey::new(
+                signer.keypair.public_key.as_bytes().to_vec(),
+                now.timestamp_millis() as u64,
+            )
+        };
+        // Capped so absurd overlaps cannot overflow the timestamp
+        let overlap = self.overlap.as_secs().min(MAX_OVERLAP_SECS) as i64;
+        let retire_until = now + chrono::Duration::seconds(overlap);
+
+        match &self.keys {
+            KeyBackend::Postgres(repo) => repo
+                .register(&key, retire_until)
+                .await
+                .map_err(|e| db_error("register signing key", e)),
+            KeyBackend::Memory(map) => {
+                let registered = {
+                    let mut entry = map.entry(key.key_id.clone()).or_insert(key);
+                    if entry.status == SigningKeyStatus::Retired {
+                        entry.status = SigningKeyStatus::Active;
+                        entry.valid_until = None;
+                    }
+                    entry.value().clone()
+                };
+                if registered.status == SigningKeyStatus::Active {
+                    let retire_until = retire_until.timestamp_millis() as u64;
+                    for mut other in map.iter_mut() {
+                        if other.key_id != registered.key_id
+                            && other.status == SigningKeyStatus::Active
+                        {
+                            other.status = SigningKeyStatus::Retired;
+                            other.valid_until = Some(
+                                other
+                                    .valid_until
+                                    .map_or(retire_until, |until| until.min(retire_until)),
+                            );
+                        }
+                    }
+                }
+                Ok(registered)
+            }
+        }
+    }
+
+    /// Find a registered key by ID
+    pub async fn find(&self, key_id: &str) -> Result<Option<SigningKey>, ApiError> {
+        match &self.keys {
+            KeyBackend::Postgres(repo) => repo
+                .find(key_id)
+                .await
+                .map_err(|e| db_error("find signing key", e)),
+            KeyBackend::Memory(map) => Ok(map.get(key_id).map(|entry| entry.value().clone())),
+        }
+    }
+
+    /// Every registered key, newest first
+    pub async fn list(&self) -> Result<Vec<SigningKey>, ApiError> {
+        match &self.keys {
+            KeyBackend::Postgres(repo) => repo
+                .list()
+                .await
+                .map_err(|e| db_error("list signing keys", e)),
+            KeyBackend::Memory(map) => {
+                let mut keys: Vec<SigningKey> =
+                    map.iter().map(|entry| entry.value().clone()).collect();
+                keys.sort_by(|a, b| {
+                    b.valid_from
+                        .cmp(&a.valid_from)
+                        .then_with(|| a.key_id.cmp(&b.key_id))
+                });
+                Ok(keys)
+            }
+        }
+    }
+
+    /// Revoke a key, returning it (`None` if unknown).
+    ///
+    /// Its seals no longer verify as authentic. Revoking this server's own
+    /// key stops `/seal` until a new key is configured.
+    pub async fn revoke(&self, key_id: &str) -> Result<Option<SigningKey>, ApiError> {
+        let revoked = match &self.keys {
+            KeyBackend::Postgres(repo) => repo
+                .revoke(key_id)
+                .await
+                .map_err(|e| db_error("revoke signing key", e))?,
+            KeyBackend::Memory(map) => map.get_mut(key_id).map(|mut entry| {
+                entry.status = SigningKeyStatus::Revoked;
+                entry.value().clone()
+            }),
+        };
+        if revoked.is_some() && self.signer.as_ref().is_some_and(|s| s.key_id == key_id) {
+            self.registered.store(false, Ordering::Release);
+        }
+        Ok(revoked)
+    }
+}
+
+fn db_error(action: &str, error: sqlx::Error) -> ApiError {
+    tracing::error!(error = %error, "Failed to {}", action);
+    ApiError::internal("A database error occurred")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use veritas_core::generate_keypair;
+
+    use crate::config::DEFAULT_ISSUER_DID;
+
+    fn signer() -> ServerSigner {
+        let (public_key, secret_key) = generate_keypair();
+        ServerSigner::new(
+            KeypairFile::new(public_key, secret_key).unwrap(),
+            DEFAULT_ISSUER_DID,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_without_signer_seals_use_one_off_keys() {
+        let registry = SigningKeyRegistry::in_memory(None, Duration::from_secs(60));
+        assert!(registry.signer().await.unwrap().is_none());
+        assert!(registry.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rotation_retires_previous_key_after_overlap() {
+        let overlap = Duration::from_secs(60);
+        let registry = SigningKeyRegistry::in_memory(Some(signer()), overlap);
+        let old_id = registry
+            .signer()
+            .await
+            .unwrap()
+            .unwrap()
+            .key_id()
+            .to_string();
+        let old = registry.find(&old_id).await.unwrap().unwrap();
+        assert_eq!(old.status, SigningKeyStatus::Active);
+        assert_eq!(old.valid_until, None);
+
+        let new_signer = signer();
+        let before = Utc::now().timestamp_millis() as u64;
+        let new = registry.register(&new_signer).await.unwrap();
+        assert_eq!(new.status, SigningKeyStatus::Active);
+
+        let old = registry.find(&old_id).await.unwrap().unwrap();
+        assert_eq!(old.status, SigningKeyStatus::Retired);
+        let until = old.valid_until.unwrap();
+        assert!(until >= before + overlap.as_millis() as u64);
+        assert_eq!(registry.list().await.unwrap().len(), 2);
+
+        // Registering a retired key again makes it active again
+        let again = registry.register(registry.signer.as_ref().unwrap()).await;
+        assert_eq!(again.unwrap().status, SigningKeyStatus::Active);
+        let new = registry.find(new_signer.key_id()).await.unwrap().unwrap();
+        assert_eq!(new.status, SigningKeyStatus::Retired);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_signer_stops_sealing() {
+        let registry = SigningKeyRegistry::in_memory(Some(signer()), Duration::from_secs(60));
+        let key_id = registry
+            .signer()
+            .await
+            .unwrap()
+            .unwrap()
+            .key_id()
+            .to_string();
+
+        let revoked = registry.revoke(&key_id).await.unwrap().unwrap();
+        assert_eq!(revoked.status, SigningKeyStatus::Revoked);
+        assert!(registry.signer().await.is_err());
+        // Never reactivated by registering again
+        let key = registry.find(&key_id).await.unwrap().unwrap();
+        assert_eq!(key.status, SigningKeyStatus::Revoked);
+
+        assert!(registry.revoke("unknown").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,248 @@
+//! Startup self-check
+//!
+//! `main` runs these checks before binding the listener, so that a
+//! misconfigured deployment exits with a clear diagnostic instead of failing
+//! its first requests:
+//!
+//! - `DATABASE_URL`, when set, must accept a connection, and so must the
+//!   read replica (`DATABASE_READ_URL`) when configured.
+//! - Pending migrations are applied (`RUN_MIGRATIONS=true`, the default), or
+//!   the server refuses to start while the schema is behind.
+//! - Tenant row-level security is turned on or off as `TENANT_RLS` says,
+//!   which requires the database role to own the tables.
+//! - C2PA signing credentials, when configured, must load and parse.
+//! - The seal signing key (`SIGNING_KEY_FILE`), when configured, must load
+//!   and decrypt, and its key ID under `ISSUER_DID` must fit in a seal.
+//! - The QRNG must supply one block of entropy (skip with
+//!   `STARTUP_QRNG_CHECK=false`, e.g. for offline development).
+
+use std::collections::HashSet;
+
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use sqlx::postgres::PgPool;
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::db::tenant::{run_migrations, set_row_level_security};
+use crate::db::DatabasePools;
+use crate::qrng::QrngProvider;
+use crate::signing_keys::ServerSigner;
+use veritas_core::MAX_KEY_ID_LEN;
+
+/// Migrations embedded in the binary
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Reasons to refuse to start
+#[derive(Debug, Error)]
+pub enum StartupError {
+    /// DATABASE_URL is set but the database cannot be reached
+    #[error("cannot connect to DATABASE_URL: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// DATABASE_READ_URL is set but the replica cannot be reached
+    #[error("cannot connect to DATABASE_READ_URL: {0}")]
+    ReadReplica(sqlx::Error),
+
+    /// Migrations could not be applied or listed
+    #[error("database migrations failed: {0}")]
+    Migrations(#[from] MigrateError),
+
+    /// The schema is behind and RUN_MIGRATIONS=false
+    #[error(
+        "database schema is behind: {} pending migration(s) ({}); apply them or set RUN_MIGRATIONS=true",
+        .0.len(),
+        .0.join(", ")
+    )]
+    PendingMigrations(Vec<String>),
+
+    /// The row-level security policies could not be turned on or off
+    #[error("cannot apply TENANT_RLS row-level security: {0}")]
+    TenantRls(sqlx::Error),
+
+    /// C2PA_SIGNING_KEY / C2PA_SIGNING_CERT are set but unusable
+    #[error("invalid C2PA signing credentials (C2PA_SIGNING_KEY, C2PA_SIGNING_CERT): {0}")]
+    C2paSigner(String),
+
+    /// SIGNING_KEY_FILE is set but cannot be loaded
+    #[error("invalid seal signing key (SIGNING_KEY_FILE, SIGNING_KEY_PASSPHRASE): {0}")]
+    SigningKey(String),
+
+    /// No QRNG provider supplied entropy
+    #[error("QRNG self-test failed: {0} (set STARTUP_QRNG_CHECK=false to skip)")]
+    Qrng(String),
+}
+
+/// Run every startup check, returning the database pools when DATABASE_URL is set
+pub async fn self_check(config: &Config) -> Result<Option<DatabasePools>, StartupError> {
+    let pools = match std::env::var("DATABASE_URL") {
+        Ok(url) => {
+            let primary = connect_database(config, &url).await?;
+            let replica = match &config.database_read_url {
+                Some(read_url) => Some(
+                    config
+                        .database_pool_options()
+                        .connect(read_url)
+                        .await
+                        .map_err(StartupError::ReadReplica)?,
+                ),
+                None => None,
+            };
+            if replica.is_some() {
+                tracing::info!("Read replica connected (DATABASE_READ_URL)");
+            }
+            Some(DatabasePools { primary, replica })
+        }
+        Err(_) => {
+            tracing::info!("DATABASE_URL not set, database features disabled");
+            None
+        }
+    };
+
+    check_c2pa_signer()?;
+    check_signing_key(config)?;
+
+    if config.startup_qrng_check {
+        check_qrng(config).await?;
+    } else {
+        tracing::warn!("Startup QRNG check skipped (STARTUP_QRNG_CHECK=false)");
+    }
+
+    Ok(pools)
+}
+
+/// Connect to the database and bring its schema up to date
+async fn connect_database(config: &Config, url: &str) -> Result<PgPool, StartupError> {
+    let pool = config.database_pool_options().connect(url).await?;
+    tracing::info!(
+        "Database pool connected (min: {}, max: {})",
+        config.database_min_connections,
+        config.database_max_connections
+    );
+
+    if config.run_migrations {
+        run_migrations(&MIGRATOR, &pool).await?;
+        tracing::info!("Database migrations applied");
+    } else {
+        let pending = pending_migrations(&pool).await?;
+        if !pending.is_empty() {
+            return Err(StartupError::PendingMigrations(pending));
+        }
+        tracing::info!("Database schema is up to date");
+    }
+
+    set_row_level_security(&pool, config.tenant_rls)
+        .await
+        .map_err(StartupError::TenantRls)?;
+    if config.tenant_rls {
+        tracing::info!("Tenant row-level security enforced (TENANT_RLS)");
+    }
+
+    Ok(pool)
+}
+
+/// Embedded migrations not yet applied to the database, as "version_description"
+async fn pending_migrations(pool: &PgPool) -> Result<Vec<String>, StartupError> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let applied: HashSet<i64> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|m| m.version)
+        .collect();
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version))
+        .map(|m| format!("{}_{}", m.version, m.description))
+        .collect())
+}
+
+/// Load the C2PA signer when credentials are configured
+#[cfg(feature = "c2pa")]
+fn check_c2pa_signer() -> Result<(), StartupError> {
+    if std::env::var_os("C2PA_SIGNING_KEY").is_none()
+        && std::env::var_os("C2PA_SIGNING_CERT").is_none()
+    {
+        tracing::info!("C2PA signing credentials not set, manifest embedding disabled");
+        return Ok(());
+    }
+
+    veritas_core::c2pa::VeritasSigner::from_env()
+        .map_err(|e| StartupError::C2paSigner(e.to_string()))?;
+    tracing::info!("C2PA signing credentials loaded");
+    Ok(())
+}
+
+#[cfg(not(feature = "c2pa"))]
+fn check_c2pa_signer() -> Result<(), StartupError> {
+    Ok(())
+}
+
+/// Load the seal signing key when SIGNING_KEY_FILE is set
+fn check_signing_key(config: &Config) -> Result<(), StartupError> {
+    let Some(path) = &config.signing_key_file else {
+        return Ok(());
+    };
+    let signer = ServerSigner::load(path, &config.issuer_did)
+        .map_err(|e| StartupError::SigningKey(format!("{}: {}", path.display(), e)))?;
+    if signer.key_id().len() > MAX_KEY_ID_LEN {
+        return Err(StartupError::SigningKey(format!(
+            "key ID {} is longer than {} bytes, shorten ISSUER_DID",
+            signer.key_id(),
+            MAX_KEY_ID_LEN
+        )));
+    }
+    tracing::info!(key_id = signer.key_id(), "Seal signing key loaded");
+    Ok(())
+}
+
+/// Fetch one block of entropy the way an `auto` seal would
+async fn check_qrng(config: &Config) -> Result<(), StartupError> {
+    let (_, fetch) = config
+        .qrng_selection
+        .fetch(QrngProvider::Auto)
+        .await
+        .map_err(|e| StartupError::Qrng(e.to_string()))?;
+    tracing::info!(
+        provider = %fetch.provider,
+        latency_ms = fetch.latency_ms,
+        retries = fetch.retries,
+        "QRNG self-test passed"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_migrations_message() {
+        let err = StartupError::PendingMigrations(vec![
+            "20250101000000_create_users".to_string(),
+            "20250102000000_create_seals".to_string(),
+        ]);
+        assert_eq!(
+            err.to_string(),
+            "database schema is behind: 2 pending migration(s) \
+             (20250101000000_create_users, 20250102000000_create_seals); \
+             apply them or set RUN_MIGRATIONS=true"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_self_check_without_database_or_qrng() {
+        let config = Config {
+            startup_qrng_check: false,
+            ..Config::default()
+        };
+        // DATABASE_URL and the C2PA variables are not set in tests
+        if std::env::var_os("DATABASE_URL").is_none()
+            && std::env::var_os("C2PA_SIGNING_KEY").is_none()
+            && std::env::var_os("C2PA_SIGNING_CERT").is_none()
+        {
+            assert!(self_check(&config).await.unwrap().is_none());
+        }
+    }
+}
@@ -4,9 +4,32 @@
 
 use std::sync::Arc;
 
-use crate::auth::JwksCache;
-use crate::db::{SealRepository, UserRepository};
+use veritas_core::MockQrngHandling;
+
+use crate::anchor_batcher::AnchorBatcher;
+use crate::anchor_checks::AnchorChecker;
+use crate::auth::{JwtIssuers, LocalAuth, TokenIntrospector};
+use crate::db::{
+    ApiKeyRepository, AuditLogRepository, CollectionRepository, LocalCredentialRepository,
+    OrganizationSettingsRepository, RetentionPolicyRepository, SealRepository, SealShareRepository,
+    SealTagRepository, UserRepository, VerificationRepository,
+};
+use crate::exports::ExportStore;
+use crate::janitor::Janitor;
+use crate::maintenance::MaintenanceMode;
 use crate::manifest_store::PostgresManifestStore;
+use crate::notifications::Notifier;
+use crate::qrng::QrngSelection;
+use crate::rate_limit::KeyedRateLimiter;
+use crate::readiness::ReadinessProbe;
+use crate::signing_keys::SigningKeyRegistry;
+use crate::streams::StreamRegistry;
+use crate::trust::TrustPolicy;
+use crate::uploads::UploadStore;
+use crate::validation::UploadPolicy;
+use crate::verification_cache::VerificationCache;
+use crate::verification_pool::VerificationPool;
+use crate::webauthn::WebAuthnState;
 
 /// Application state containing shared resources.
 #[derive(Clone)]
@@ -17,8 +40,81 @@ pub struct AppState {
     pub user_repo: Option<Arc<UserRepository>>,
     /// Seal repository for authenticated seal storage
     pub seal_repo: Option<Arc<SealRepository>>,
-    /// JWKS cache for Clerk JWT validation
-    pub jwks_cache: Option<Arc<JwksCache>>,
+    /// Seal share repository for public share links
+    pub share_repo: Option<Arc<SealShareRepository>>,
+    /// User-defined tags of seals
+    pub tag_repo: Option<Arc<SealTagRepository>>,
+    /// Named collections of seals
+    pub collection_repo: Option<Arc<CollectionRepository>>,
+    /// Base URL of share links (the token is appended)
+    pub share_base_url: Arc<str>,
+    /// DID of the server, issuing its seals and credentials
+    pub issuer_did: Arc<str>,
+    /// Audit log of seal verifications and share link views
+    pub audit_repo: Option<Arc<AuditLogRepository>>,
+    /// Verification counter and trends of stored seals
+    pub verification_repo: Option<Arc<VerificationRepository>>,
+    /// Seals each account may create per calendar month (None = unlimited)
+    pub monthly_seal_quota: Option<u64>,
+    /// API keys of machine clients
+    pub api_key_repo: Option<Arc<ApiKeyRepository>>,
+    /// Maximum number of items per `/resolve/batch` request
+    pub batch_resolve_max_items: usize,
+    /// Per-API-key budget of `/resolve/batch` items
+    pub batch_resolve_limiter: Arc<KeyedRateLimiter>,
+    /// Maximum perceptual hash distance between two variants of an image
+    pub duplicate_threshold: u32,
+    /// Alerts to seal owners (None = no delivery channel configured)
+    pub notifier: Option<Arc<Notifier>>,
+    /// Trusted JWT issuers and their key sets (None = JWT auth disabled)
+    pub jwt_issuers: Option<Arc<JwtIssuers>>,
+    /// Introspection of opaque tokens (None = only JWTs are accepted)
+    pub introspector: Option<Arc<TokenIntrospector>>,
+    /// Clients whose client-credentials tokens authenticate a service
+    /// (`<issuer name>:<client>`, Clerk clients unprefixed)
+    pub service_clients: Arc<[String]>,
+    /// Self-hosted accounts and token signing (None = AUTH_MODE is not local)
+    pub local_auth: Option<Arc<LocalAuth>>,
+    /// Email/password credentials of self-hosted accounts
+    pub credential_repo: Option<Arc<LocalCredentialRepository>>,
+    /// Branding of verification responses per organization
+    pub organization_repo: Option<Arc<OrganizationSettingsRepository>>,
+    /// Retention of media and seals per organization
+    pub retention_repo: Option<Arc<RetentionPolicyRepository>>,
     /// Whether mock QRNG is allowed (for testing environments only)
     pub allow_mock_qrng: bool,
+    /// QRNG providers clients may request for a seal
+    pub qrng_selection: Arc<QrngSelection>,
+    /// Whether uploads must match their declared media type
+    pub strict_media_types: bool,
+    /// How /verify treats seals made with the mock QRNG
+    pub mock_qrng_verification: MockQrngHandling,
+    /// Limits applied to uploaded files
+    pub upload_policy: Arc<UploadPolicy>,
+    /// Rules computing the trust tier of each seal
+    pub trust_policy: Arc<TrustPolicy>,
+    /// WebAuthn credentials, used to verify device attestations
+    pub webauthn: Arc<WebAuthnState>,
+    /// Open live stream sealing sessions
+    pub streams: Arc<StreamRegistry>,
+    /// Resumable upload sessions
+    pub uploads: Arc<UploadStore>,
+    /// Personal data exports being built or awaiting download
+    pub exports: Arc<ExportStore>,
+    /// Dependency checks reported by `/ready`
+    pub readiness: Arc<ReadinessProbe>,
+    /// Read-only maintenance mode, refusing sealing and writes
+    pub maintenance: Arc<MaintenanceMode>,
+    /// Recent verification outcomes and public seal records
+    pub verification_cache: Arc<VerificationCache>,
+    /// Blocking threads running content hashing and signature checks
+    pub verification_pool: Arc<VerificationPool>,
+    /// Checks of seal anchors on their blockchains
+    pub anchor_checker: Arc<AnchorChecker>,
+    /// Batched anchoring of new seals (None = disabled)
+    pub anchor_batcher: Option<Arc<AnchorBatcher>>,
+    /// Seal signing keys: the registry and this server's key
+    pub signing_keys: Arc<SigningKeyRegistry>,
+    /// Periodic maintenance tasks and their run statistics
+    pub janitor: Arc<Janitor>,
 }
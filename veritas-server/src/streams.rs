@@ -0,0 +1,148 @@
+//! Live stream sealing sessions
+//!
+//! Holds the in-progress [`SealSession`]s behind the `/api/v1/streams`
+//! endpoints. Only segment hashes are kept per stream, never the media
+//! itself, so a session costs a few kilobytes regardless of stream length.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+use veritas_core::SealSession;
+
+/// Maximum number of concurrently open streams.
+pub const MAX_ACTIVE_STREAMS: usize = 64;
+
+/// Streams without activity for this long are dropped.
+pub const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// How often idle streams are swept.
+pub const STREAM_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// An open stream and its sealing state.
+pub struct StreamEntry {
+    /// Incremental seal over the chunks received so far
+    pub session: SealSession,
+    /// Whether the final seal uses mock entropy
+    pub use_mock: bool,
+    /// Last time a chunk was received
+    pub last_activity: Instant,
+}
+
+impl StreamEntry {
+    /// Wrap a new session.
+    pub fn new(session: SealSession, use_mock: bool) -> Self {
+        Self {
+            session,
+            use_mock,
+            last_activity: Instant::now(),
+        }
+    }
+}
+
+/// Registry of open streams, shared across requests.
+#[derive(Default)]
+pub struct StreamRegistry {
+    streams: DashMap<Uuid, Arc<Mutex<StreamEntry>>>,
+}
+
+impl StreamRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a stream, returning its ID, or `None` when the registry is
+    /// full even after dropping idle streams.
+    pub fn insert(&self, entry: StreamEntry) -> Option<Uuid> {
+        if self.streams.len() >= MAX_ACTIVE_STREAMS {
+            self.purge_idle(STREAM_IDLE_TIMEOUT);
+        }
+        if self.streams.len() >= MAX_ACTIVE_STREAMS {
+            return None;
+        }
+
+        let id = Uuid::new_v4();
+        self.streams.insert(id, Arc::new(Mutex::new(entry)));
+        Some(id)
+    }
+
+    /// Look up an open stream.
+    pub fn get(&self, id: &Uuid) -> Option<Arc<Mutex<StreamEntry>>> {
+        self.streams.get(id).map(|entry| Arc::clone(entry.value()))
+    }
+
+    /// Close a stream.
+    pub fn remove(&self, id: &Uuid) {
+        self.streams.remove(id);
+    }
+
+    /// Drop streams idle for longer than `max_idle`, returning how many were dropped.
+    ///
+    /// Streams currently in use are kept.
+    pub fn purge_idle(&self, max_idle: Duration) -> usize {
+        let before = self.streams.len();
+        self.streams.retain(|_, entry| match entry.try_lock() {
+            Ok(entry) => entry.last_activity.elapsed() < max_idle,
+            Err(_) => true,
+        });
+        before - self.streams.len()
+    }
+
+    /// Number of open streams.
+    pub fn len(&self) -> usize {
+        self.streams.len()
+    }
+
+    /// Returns true if no stream is open.
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use veritas_core::{generate_keypair, MediaType, SegmentLayout};
+
+    fn entry() -> StreamEntry {
+        let (public_key, secret_key) = generate_keypair();
+        let session = SealSession::new(
+            MediaType::Video,
+            SegmentLayout::new(1024),
+            public_key,
+            secret_key,
+        )
+        .unwrap();
+        StreamEntry::new(session, true)
+    }
+
+    #[tokio::test]
+    async fn test_insert_get_remove() {
+        let registry = StreamRegistry::new();
+        let id = registry.insert(entry()).unwrap();
+
+        assert_eq!(registry.len(), 1);
+        assert!(registry.get(&id).is_some());
+
+        registry.remove(&id);
+        assert!(registry.get(&id).is_none());
+        assert!(registry.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_purge_keeps_streams_in_use() {
+        let registry = StreamRegistry::new();
+        let idle = registry.insert(entry()).unwrap();
+        let busy = registry.insert(entry()).unwrap();
+
+        let busy_entry = registry.get(&busy).unwrap();
+        let _guard = busy_entry.lock().await;
+
+        assert_eq!(registry.purge_idle(Duration::ZERO), 1);
+        assert!(registry.get(&idle).is_none());
+        assert!(registry.get(&busy).is_some());
+    }
+}
@@ -0,0 +1,264 @@
+//! Trust tier computation
+//!
+//! Computes the [`TrustTier`] of each seal from the evidence available when
+//! it is created, instead of copying the user's account tier:
+//!
+//! - a verified WebAuthn device attestation grants Tier 3
+//! - an account flagged as a verified reporter grants Tier 2
+//! - anything else, including anonymous seals, gets Tier 1
+//!
+//! The tiers and the conditions for accepting an attestation are set by a
+//! [`TrustPolicy`], loaded from an optional JSON file (`TRUST_POLICY_FILE`)
+//! and environment overrides. Each decision comes with a rationale that is
+//! stored in the seal metadata.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::TrustTier;
+use crate::webauthn::{AttestationFormat, DeviceAttestation, WebAuthnStorage};
+
+/// Rules mapping seal evidence to a trust tier.
+///
+/// Every field is optional in the JSON policy file; missing fields keep their
+/// defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrustPolicy {
+    /// Tier granted by a verified device attestation
+    pub attested_tier: TrustTier,
+    /// Tier granted to accounts verified as reporters (account tier 2 or above)
+    pub reporter_tier: TrustTier,
+    /// Tier of seals matching no other rule, including anonymous seals
+    pub default_tier: TrustTier,
+    /// Only accept attestations from credentials registered with this server
+    pub require_registered_credential: bool,
+    /// Accept self-attested credentials (attestation format "none")
+    pub allow_self_attestation: bool,
+    /// Only accept attestations on authenticated requests
+    pub require_account_for_attestation: bool,
+}
+
+impl Default for TrustPolicy {
+    fn default() -> Self {
+        Self {
+            attested_tier: TrustTier::Tier3,
+            reporter_tier: TrustTier::Tier2,
+            default_tier: TrustTier::Tier1,
+            require_registered_credential: true,
+            allow_self_attestation: false,
+            require_account_for_attestation: false,
+        }
+    }
+}
+
+/// Facts about a seal request that the trust policy is evaluated against.
+#[derive(Debug, Clone, Default)]
+pub struct TrustEvidence {
+    /// Tier of the authenticated account, `None` for anonymous requests
+    pub account_tier: Option<TrustTier>,
+    /// Device attestation included in the seal, if any
+    pub attestation: Option<AttestationEvidence>,
+}
+
+/// What the server could establish about a device attestation.
+#[derive(Debug, Clone)]
+pub struct AttestationEvidence {
+    /// Attestation statement format
+    pub format: AttestationFormat,
+    /// Whether the credential is registered here with the same authenticator
+    pub registered: bool,
+}
+
+impl AttestationEvidence {
+    /// Check an attestation against the credentials registered with this server.
+    ///
+    /// Storage errors are logged and treated as an unregistered credential.
+    pub async fn check(storage: &WebAuthnStorage, attestation: &DeviceAttestation) -> Self {
+        let registered = match storage.get_credential(&attestation.credential_id).await {
+            Ok(Some(stored)) => stored.device_attestation.aaguid == attestation.aaguid,
+            Ok(None) => false,
+            Err(e) => {
+                tracing::warn!(
+                    credential_id = %attestation.credential_id,
+                    error = %e,
+                    "Failed to look up attestation credential"
+                );
+                false
+            }
+        };
+
+        Self {
+            format: attestation.attestation_format,
+            registered,
+        }
+    }
+}
+
+/// Tier computed for a seal and the reasons behind it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrustAssessment {
+    /// Resulting trust tier
+    pub tier: TrustTier,
+    /// Human-readable reasons, one per rule considered
+    pub rationale: Vec<String>,
+}
+
+impl TrustPolicy {
+    /// Load the policy from `TRUST_POLICY_FILE` (JSON) if set, then apply
+    /// environment overrides:
+    ///
+    /// - `TRUST_REQUIRE_REGISTERED_CREDENTIAL`: "true" or "false"
+    /// - `TRUST_ALLOW_SELF_ATTESTATION`: "true" or "false"
+    /// - `TRUST_REQUIRE_ACCOUNT_FOR_ATTESTATION`: "true" or "false"
+    pub fn from_env() -> Result<Self, String> {
+        let mut policy = match std::env::var("TRUST_POLICY_FILE") {
+            Ok(path) => {
+                let json = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("failed to read {}: {}", path, e))?;
+                serde_json::from_str(&json).map_err(|e| format!("invalid {}: {}", path, e))?
+            }
+            Err(_) => Self::default(),
+        };
+
+        for (name, flag) in [
+            (
+                "TRUST_REQUIRE_REGISTERED_CREDENTIAL",
+                &mut policy.require_registered_credential,
+            ),
+            (
+                "TRUST_ALLOW_SELF_ATTESTATION",
+                &mut policy.allow_self_attestation,
+            ),
+            (
+                "TRUST_REQUIRE_ACCOUNT_FOR_ATTESTATION",
+                &mut policy.require_account_for_attestation,
+            ),
+        ] {
+            if let Ok(value) = std::env::var(name) {
+                *flag = value.eq_ignore_ascii_case("true");
+            }
+        }
+
+        Ok(policy)
+    }
+
+    /// Compute the trust tier for a seal.
+    ///
+    /// The result is the highest tier among the rules the evidence satisfies.
+    pub fn assess(&self, evidence: &TrustEvidence) -> TrustAssessment {
+        let mut tier = self.default_tier;
+        let mut rationale = Vec::new();
+
+        if let Some(attestation) = &evidence.attestation {
+            let format = attestation.format.as_str();
+            if attestation.format == AttestationFormat::None && !self.allow_self_attestation {
+                rationale.push("device attestation ignored: self-attested credential".to_string());
+            } else if !attestation.registered && self.require_registered_credential {
+                rationale.push(format!(
+                    "device attestation ignored: {} credential not registered with this server",
+                    format
+                ));
+            } else if evidence.account_tier.is_none() && self.require_account_for_attestation {
+                rationale.push("device attestation ignored: anonymous request".to_string());
+            } else {
+                tier = tier.max(self.attested_tier);
+                rationale.push(format!("device attestation verified ({} format)", format));
+            }
+        }
+
+        match evidence.account_tier {
+            Some(account_tier) if account_tier >= TrustTier::Tier2 => {
+                tier = tier.max(self.reporter_tier);
+                rationale.push("account is a verified reporter".to_string());
+            }
+            Some(_) => rationale.push("account is not a verified reporter".to_string()),
+            None => rationale.push("anonymous seal".to_string()),
+        }
+
+        TrustAssessment { tier, rationale }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attestation(format: AttestationFormat, registered: bool) -> Option<AttestationEvidence> {
+        Some(AttestationEvidence { format, registered })
+    }
+
+    #[test]
+    fn test_default_policy_tiers() {
+        let policy = TrustPolicy::default();
+
+        let anonymous = policy.assess(&TrustEvidence::default());
+        assert_eq!(anonymous.tier, TrustTier::Tier1);
+        assert_eq!(anonymous.rationale, vec!["anonymous seal"]);
+
+        let user = policy.assess(&TrustEvidence {
+            account_tier: Some(TrustTier::Tier1),
+            attestation: None,
+        });
+        assert_eq!(user.tier, TrustTier::Tier1);
+
+        let reporter = policy.assess(&TrustEvidence {
+            account_tier: Some(TrustTier::Tier2),
+            attestation: None,
+        });
+        assert_eq!(reporter.tier, TrustTier::Tier2);
+
+        let attested = policy.assess(&TrustEvidence {
+            account_tier: None,
+            attestation: attestation(AttestationFormat::Packed, true),
+        });
+        assert_eq!(attested.tier, TrustTier::Tier3);
+        assert_eq!(
+            attested.rationale,
+            vec![
+                "device attestation verified (packed format)",
+                "anonymous seal"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unverified_attestations_are_ignored() {
+        let policy = TrustPolicy::default();
+
+        let unregistered = policy.assess(&TrustEvidence {
+            account_tier: Some(TrustTier::Tier2),
+            attestation: attestation(AttestationFormat::Apple, false),
+        });
+        assert_eq!(unregistered.tier, TrustTier::Tier2);
+        assert!(unregistered.rationale[0].contains("not registered"));
+
+        let self_attested = policy.assess(&TrustEvidence {
+            account_tier: None,
+            attestation: attestation(AttestationFormat::None, true),
+        });
+        assert_eq!(self_attested.tier, TrustTier::Tier1);
+        assert!(self_attested.rationale[0].contains("self-attested"));
+    }
+
+    #[test]
+    fn test_policy_rules_are_configurable() {
+        let policy: TrustPolicy = serde_json::from_str(
+            r#"{"allow_self_attestation": true, "require_account_for_attestation": true, "attested_tier": "tier2"}"#,
+        )
+        .unwrap();
+
+        let anonymous = policy.assess(&TrustEvidence {
+            account_tier: None,
+            attestation: attestation(AttestationFormat::None, true),
+        });
+        assert_eq!(anonymous.tier, TrustTier::Tier1);
+
+        let user = policy.assess(&TrustEvidence {
+            account_tier: Some(TrustTier::Tier1),
+            attestation: attestation(AttestationFormat::None, true),
+        });
+        assert_eq!(user.tier, TrustTier::Tier2);
+
+        assert!(serde_json::from_str::<TrustPolicy>(r#"{"unknown": 1}"#).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+//! Resumable upload sessions
+//!
+//! Backs the tus-style `/api/v1/uploads` endpoints. Session state lives in
+//! PostgreSQL when configured (in memory otherwise, lost on restart) and the
+//! received bytes in one file per upload under the upload directory, so a
+//! client on a flaky network can resume from the last acknowledged offset.
+//! Sessions left untouched for the TTL are purged along with their data.
+
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use dashmap::DashMap;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use uuid::Uuid;
+
+use crate::db::{UploadSession, UploadSessionRepository};
+use crate::error::ApiError;
+
+/// Default time an upload may sit idle before it is purged (24 hours).
+pub const DEFAULT_UPLOAD_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How often abandoned uploads are swept.
+pub const UPLOAD_SWEEP_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Metadata declared when an upload is created.
+#[derive(Debug, Clone, Default)]
+pub struct NewUpload {
+    /// Authenticated user creating the upload
+    pub user_id: Option<Uuid>,
+    /// Total size in bytes
+    pub upload_length: u64,
+    /// Declared media type ("video", ..., or a MIME type)
+    pub media_type: Option<String>,
+    /// Content-Type of the file
+    pub content_type: Option<String>,
+    /// Original file name
+    pub file_name: Option<String>,
+}
+
+/// Session storage backend
+enum SessionBackend {
+    /// PostgreSQL storage (production)
+    Postgres(Arc<UploadSessionRepository>),
+    /// In-memory storage (development fallback)
+    Memory(DashMap<Uuid, UploadSession>),
+}
+
+/// Registry of resumable uploads, shared across requests.
+pub struct UploadStore {
+    sessions: SessionBackend,
+    dir: PathBuf,
+    ttl: Duration,
+    locks: DashMap<Uuid, Arc<Mutex<()>>>,
+}
+
+impl UploadStore {
+    /// Create a store keeping session state in memory.
+    pub fn in_memory(dir: PathBuf, ttl: Duration) -> Self {
+        Self {
+            sessions: SessionBackend::Memory(DashMap::new()),
+            dir,
+            ttl,
+            locks: DashMap::new(),
+        }
+    }
+
+    /// Create a store keeping session state in PostgreSQL.
+    pub fn with_repository(
+        repo: Arc<UploadSessionRepository>,
+        dir: PathBuf,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            sessions: SessionBackend::Postgres(repo),
+            dir,
+            ttl,
+            locks: DashMap::new(),
+        }
+    }
+
+    /// File holding the bytes received for an upload.
+    pub fn data_path(&self, id: &Uuid) -> PathBuf {
+        self.dir.join(format!("{}.part", id))
+    }
+
+    /// Register a new upload and create its (empty) data file.
+    pub async fn create(&self, upload: NewUpload) -> Result<UploadSession, ApiError> {
+        let upload_length = i64::try_from(upload.upload_length)
+            .map_err(|_| ApiError::bad_request("upload_length is too large"))?;
+        let now = Utc::now();
+        let session = UploadSession {
+            id: Uuid::new_v4(),
+            user_id: upload.user_id,
+            upload_length,
+            upload_offset: 0,
+            media_type: upload.media_type,
+            content_type: upload.content_type,
+            file_name: upload.file_name,
+            created_at: now,
+            updated_at: now,
+            expires_at: now + self.ttl,
+        };
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| io_error("create upload directory", e))?;
+        tokio::fs::File::create(self.data_path(&session.id))
+            .await
+            .map_err(|e| io_error("create upload file", e))?;
+
+        match &self.sessions {
+            SessionBackend::Postgres(repo) => {
+                if let Err(e) = repo.create(&session).await {
+                    let _ = tokio::fs::remove_file(self.data_path(&session.id)).await;
+                    return Err(db_error("create upload session", e));
+                }
+            }
+            SessionBackend::Memory(map) => {
+                map.insert(session.id, session.clone());
+            }
+        }
+
+        Ok(session)
+    }
+
+    /// Look up an unexpired upload.
+    pub async fn get(&self, id: Uuid) -> Result<Option<UploadSession>, ApiError> {
+        match &self.sessions {
+            SessionBackend::Postgres(repo) => repo
+                .find_by_id(id)
+                .await
+                .map_err(|e| db_error("load upload session", e)),
+            SessionBackend::Memory(map) => Ok(map
+                .get(&id)
+                .map(|entry| entry.value().clone())
+                .filter(|session| session.expires_at > Utc::now())),
+        }
+    }
+
+    /// Serialize writes to one upload within this process.
+    pub async fn lock(&self, id: Uuid) -> OwnedMutexGuard<()> {
+        let lock = Arc::clone(self.locks.entry(id).or_default().value());
+        lock.lock_owned().await
+    }
+
+    /// Write `chunk` at the session's current offset and advance it.
+    ///
+    /// The caller must hold [`UploadStore::lock`] for the session. Bytes past
+    /// the acknowledged offset (left by an interrupted chunk) are discarded
+    /// first, so a retried chunk always lands in the right place.
+    pub async fn append(
+        &self,
+        session: &UploadSession,
+        chunk: &[u8],
+    ) -> Result<UploadSession, ApiError> {
+        let offset = session.upload_offset;
+        let new_offset = offset + chunk.len() as i64;
+        if new_offset > session.upload_length {
+            return Err(ApiError::payload_too_large(format!(
+                "Chunk exceeds the declared upload length of {} bytes",
+                session.upload_length
+            )));
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(self.data_path(&session.id))
+            .await
+            .map_err(|e| io_error("open upload file", e))?;
+        file.set_len(offset as u64)
+            .await
+            .map_err(|e| io_error("truncate upload file", e))?;
+        file.seek(SeekFrom::Start(offset as u64))
+            .await
+            .map_err(|e| io_error("seek upload file", e))?;
+        file.write_all(chunk)
+            .await
+            .map_err(|e| io_error("write upload file", e))?;
+        file.sync_data()
+            .await
+            .map_err(|e| io_error("sync upload file", e))?;
+
+        let now = Utc::now();
+        let expires_at = now + self.ttl;
+        let advanced = match &self.sessions {
+            SessionBackend::Postgres(repo) => repo
+                .advance(session.id, offset, new_offset, expires_at)
+                .await
+                .map_err(|e| db_error("advance upload session", e))?,
+            SessionBackend::Memory(map) => match map.get_mut(&session.id) {
+                Some(mut entry) if entry.upload_offset == offset => {
+                    entry.upload_offset = new_offset;
+                    entry.updated_at = now;
+                    entry.expires_at = expires_at;
+                    true
+                }
+                _ => false,
+            },
+        };
+        if !advanced {
+            return Err(ApiError::conflict("Upload offset changed concurrently"));
+        }
+
+        Ok(UploadSession {
+            upload_offset: new_offset,
+            updated_at: now,
+            expires_at,
+            ..session.clone()
+        })
+    }
+
+    /// Delete an upload and its data.
+    pub async fn remove(&self, id: Uuid) -> Result<(), ApiError> {
+        match &self.sessions {
+            SessionBackend::Postgres(repo) => {
+                repo.delete(id)
+                    .await
+                    .map_err(|e| db_error("delete upload session", e))?;
+            }
+            SessionBackend::Memory(map) => {
+                map.remove(&id);
+            }
+        }
+        self.remove_data(id).await;
+        Ok(())
+    }
+
+    /// Delete expired uploads and their data, returning how many were purged.
+    pub async fn purge_expired(&self) -> Result<usize, ApiError> {
+        let expired = match &self.sessions {
+            SessionBackend::Postgres(repo) => repo
+                .delete_expired()
+                .await
+                .map_err(|e| db_error("purge upload sessions", e))?,
+            SessionBackend::Memory(map) => {
+                let now = Utc::now();
+                let mut expired = Vec::new();
+                map.retain(|id, session| {
+                    let keep = session.expires_at > now;
+                    if !keep {
+                        expired.push(*id);
+                    }
+                    keep
+                });
+                expired
+            }
+        };
+
+        for id in &expired {
+            self.remove_data(*id).await;
+        }
+        Ok(expired.len())
+    }
+
+    async fn remove_data(&self, id: Uuid) {
+        self.locks.remove(&id);
+        if let Err(e) = tokio::fs::remove_file(self.data_path(&id)).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(upload_id = %id, error = %e, "Failed to delete upload data");
+            }
+        }
+    }
+}
+
+fn io_error(action: &str, error: std::io::Error) -> ApiError {
+    tracing::error!(error = %error, "Failed to {}", action);
+    ApiError::internal("Upload storage error")
+}
+
+fn db_error(action: &str, error: sqlx::Error) -> ApiError {
+    tracing::error!(error = %error, "Failed to {}", action);
+    ApiError::internal("Upload storage error")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(ttl: Duration) -> (tempfile::TempDir, UploadStore) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = UploadStore::in_memory(dir.path().to_path_buf(), ttl);
+        (dir, store)
+    }
+
+    fn upload(length: u64) -> NewUpload {
+        NewUpload {
+            upload_length: length,
+            ..NewUpload::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_resumes_at_offset() {
+        let (_dir, store) = store(DEFAULT_UPLOAD_TTL);
+        let session = store.create(upload(6)).await.unwrap();
+
+        let session = store.append(&session, b"abc").await.unwrap();
+        assert_eq!(session.upload_offset, 3);
+        assert!(!session.is_complete());
+
+        // Stray bytes from an interrupted chunk are discarded on resume
+        tokio::fs::write(store.data_path(&session.id), b"abcXY")
+            .await
+            .unwrap();
+        let session = store.append(&session, b"def").await.unwrap();
+        assert!(session.is_complete());
+        assert_eq!(
+            tokio::fs::read(store.data_path(&session.id)).await.unwrap(),
+            b"abcdef"
+        );
+
+        let stored = store.get(session.id).await.unwrap().unwrap();
+        assert_eq!(stored.upload_offset, 6);
+    }
+
+    #[tokio::test]
+    async fn test_append_rejects_overflow_and_stale_offset() {
+        let (_dir, store) = store(DEFAULT_UPLOAD_TTL);
+        let session = store.create(upload(4)).await.unwrap();
+
+        let overflow = store.append(&session, b"too long").await;
+        assert!(matches!(overflow, Err(ApiError::PayloadTooLarge(_))));
+
+        store.append(&session, b"ab").await.unwrap();
+        let stale = store.append(&session, b"ab").await;
+        assert!(matches!(stale, Err(ApiError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired_removes_data() {
+        let (_dir, store) = store(Duration::ZERO);
+        let session = store.create(upload(4)).await.unwrap();
+
+        assert!(store.get(session.id).await.unwrap().is_none());
+        assert_eq!(store.purge_expired().await.unwrap(), 1);
+        assert!(!store.data_path(&session.id).exists());
+    }
+}
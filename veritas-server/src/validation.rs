@@ -1,43 +1,280 @@
 //! Upload validation module
 //!
-//! Provides validation utilities for multipart file uploads.
+//! Defines the [`UploadPolicy`] applied to multipart file uploads: accepted
+//! Content-Types, size limits per media type, and image dimension limits that
+//! keep decompression bombs away from the image decoder. The policy is loaded
+//! from an optional JSON file (`UPLOAD_POLICY_FILE`) and environment overrides.
 
-use crate::error::ApiError;
+use std::io::Cursor;
+
+use serde::Deserialize;
+use veritas_core::MediaType;
 
-/// Allowed MIME type categories for media uploads
-const ALLOWED_MIME_PREFIXES: &[&str] = &["image/", "video/", "audio/", "application/octet-stream"];
+use crate::error::ApiError;
+use crate::multipart::FileField;
 
 /// Default max file size in bytes (25 MB)
 pub const DEFAULT_MAX_FILE_SIZE: usize = 25 * 1024 * 1024;
 
-/// Validates the Content-Type of an uploaded file
+/// Default size above which uploads are spooled to a temp file (4 MB)
+pub const DEFAULT_SPOOL_THRESHOLD: usize = 4 * 1024 * 1024;
+
+/// Default max size of a resumable upload in bytes (500 MB)
+pub const DEFAULT_MAX_RESUMABLE_SIZE: usize = 500 * 1024 * 1024;
+
+/// Default maximum image width or height in pixels
+pub const DEFAULT_MAX_IMAGE_DIMENSION: u32 = 16_384;
+
+/// Default maximum image area in pixels (100 megapixels, ~400 MB decoded as RGBA)
+pub const DEFAULT_MAX_IMAGE_PIXELS: u64 = 100_000_000;
+
+const MB: usize = 1024 * 1024;
+
+/// Limits applied to uploaded files.
+///
+/// Every field is optional in the JSON policy file; missing fields keep their
+/// defaults. Sizes are in bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UploadPolicy {
+    /// Accepted Content-Types; `type/*` accepts a whole category
+    pub allowed_mime_types: Vec<String>,
+    /// Maximum size of any upload
+    pub max_file_size: usize,
+    /// Maximum size of images, if tighter than `max_file_size`
+    pub max_image_size: Option<usize>,
+    /// Maximum size of videos, if tighter than `max_file_size`
+    pub max_video_size: Option<usize>,
+    /// Maximum size of audio files, if tighter than `max_file_size`
+    pub max_audio_size: Option<usize>,
+    /// Maximum size of documents, if tighter than `max_file_size`
+    pub max_document_size: Option<usize>,
+    /// Maximum image width or height in pixels
+    pub max_image_dimension: u32,
+    /// Maximum image area in pixels, bounding the memory needed to decode it
+    pub max_image_pixels: u64,
+    /// Uploads larger than this are spooled to a temp file instead of memory
+    pub spool_threshold: usize,
+    /// Maximum size of a resumable upload, which replaces `max_file_size` for
+    /// uploads sent in chunks
+    pub max_resumable_size: usize,
+}
+
+impl Default for UploadPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_mime_types: [
+                "image/*",
+                "video/*",
+                "audio/*",
+                "application/pdf",
+                "application/octet-stream",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            max_image_size: None,
+            max_video_size: None,
+            max_audio_size: None,
+            max_document_size: None,
+            max_image_dimension: DEFAULT_MAX_IMAGE_DIMENSION,
+            max_image_pixels: DEFAULT_MAX_IMAGE_PIXELS,
+            spool_threshold: DEFAULT_SPOOL_THRESHOLD,
+            max_resumable_size: DEFAULT_MAX_RESUMABLE_SIZE,
+        }
+    }
+}
+
+impl UploadPolicy {
+    /// Load the policy from `UPLOAD_POLICY_FILE` (JSON) if set, then apply
+    /// environment overrides:
+    ///
+    /// - `UPLOAD_ALLOWED_MIME_TYPES`: comma-separated Content-Types
+    /// - `MAX_FILE_SIZE_MB`, `MAX_IMAGE_SIZE_MB`, `MAX_VIDEO_SIZE_MB`,
+    ///   `MAX_AUDIO_SIZE_MB`, `MAX_DOCUMENT_SIZE_MB`
+    /// - `MAX_IMAGE_DIMENSION`, `MAX_IMAGE_PIXELS`
+    /// - `UPLOAD_SPOOL_THRESHOLD_MB`, `MAX_RESUMABLE_UPLOAD_MB`
+    pub fn from_env() -> Result<Self, String> {
+        let mut policy = match std::env::var("UPLOAD_POLICY_FILE") {
+            Ok(path) => {
+                let json = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("failed to read {}: {}", path, e))?;
+                serde_json::from_str(&json).map_err(|e| format!("invalid {}: {}", path, e))?
+            }
+            Err(_) => Self::default(),
+        };
+
+        if let Ok(types) = std::env::var("UPLOAD_ALLOWED_MIME_TYPES") {
+            policy.allowed_mime_types = types
+                .split(',')
+                .map(|t| t.trim().to_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect();
+        }
+
+        let env_mb = |name: &str| -> Option<usize> {
+            std::env::var(name)
+                .ok()?
+                .parse::<usize>()
+                .ok()?
+                .checked_mul(MB)
+        };
+        if let Some(size) = env_mb("MAX_FILE_SIZE_MB") {
+            policy.max_file_size = size;
+        }
+        if let Some(size) = env_mb("UPLOAD_SPOOL_THRESHOLD_MB") {
+            policy.spool_threshold = size;
+        }
+        if let Some(size) = env_mb("MAX_RESUMABLE_UPLOAD_MB") {
+            policy.max_resumable_size = size;
+        }
+        for (name, limit) in [
+            ("MAX_IMAGE_SIZE_MB", &mut policy.max_image_size),
+            ("MAX_VIDEO_SIZE_MB", &mut policy.max_video_size),
+            ("MAX_AUDIO_SIZE_MB", &mut policy.max_audio_size),
+            ("MAX_DOCUMENT_SIZE_MB", &mut policy.max_document_size),
+        ] {
+            if let Some(size) = env_mb(name) {
+                *limit = Some(size);
+            }
+        }
+
+        if let Some(dimension) = std::env::var("MAX_IMAGE_DIMENSION")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            policy.max_image_dimension = dimension;
+        }
+        if let Some(pixels) = std::env::var("MAX_IMAGE_PIXELS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            policy.max_image_pixels = pixels;
+        }
+
+        Ok(policy)
+    }
+
+    /// Check a file's Content-Type against the allowed types.
+    ///
+    /// A missing Content-Type is treated as binary data and accepted.
+    pub fn check_content_type(&self, content_type: Option<&str>) -> Result<(), ApiError> {
+        let Some(ct) = content_type else {
+            return Ok(());
+        };
+        let essence = ct
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        let allowed =
+            self.allowed_mime_types
+                .iter()
+                .any(|pattern| match pattern.strip_suffix("/*") {
+                    Some(category) => essence
+                        .split_once('/')
+                        .is_some_and(|(kind, _)| kind.eq_ignore_ascii_case(category)),
+                    None => essence.eq_ignore_ascii_case(pattern),
+                });
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(ApiError::unsupported_media_type(format!(
+                "Unsupported Content-Type: '{}'. Allowed types: {}",
+                ct,
+                self.allowed_mime_types.join(", ")
+            )))
+        }
+    }
+
+    /// The policy applied to resumable uploads: identical, except that the
+    /// overall size limit is `max_resumable_size`.
+    pub fn resumable(&self) -> Self {
+        Self {
+            max_file_size: self.max_resumable_size,
+            ..self.clone()
+        }
+    }
+
+    /// Size limit for uploads of the given media type.
+    pub fn max_size_for(&self, media_type: &MediaType) -> usize {
+        let specific = match media_type {
+            MediaType::Image => self.max_image_size,
+            MediaType::Video => self.max_video_size,
+            MediaType::Audio => self.max_audio_size,
+            MediaType::Document => self.max_document_size,
+            MediaType::Generic | MediaType::Custom(_) => None,
+        };
+        specific.map_or(self.max_file_size, |limit| limit.min(self.max_file_size))
+    }
+
+    /// Check an upload against the limits for its media type, reading image
+    /// headers to enforce the dimension limits before anything decodes it.
+    pub fn check_upload(&self, file: &FileField, media_type: &MediaType) -> Result<(), ApiError> {
+        let max_size = self.max_size_for(media_type);
+        if file.len() > max_size {
+            return Err(ApiError::payload_too_large(format!(
+                "{} upload of {} bytes exceeds the limit of {} bytes",
+                media_type,
+                file.len(),
+                max_size
+            )));
+        }
+
+        let is_image = *media_type == MediaType::Image
+            || file
+                .detected_mime
+                .is_some_and(|mime| mime.starts_with("image/"));
+        if is_image {
+            self.check_image_dimensions(&file.data)?;
+        }
+        Ok(())
+    }
+
+    /// Reject images whose header declares more pixels than the policy allows.
+    ///
+    /// Formats the decoder cannot read are accepted: they are never decoded.
+    pub fn check_image_dimensions(&self, data: &[u8]) -> Result<(), ApiError> {
+        let dimensions = image::ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .ok()
+            .and_then(|reader| reader.into_dimensions().ok());
+        let Some((width, height)) = dimensions else {
+            return Ok(());
+        };
+
+        if width > self.max_image_dimension || height > self.max_image_dimension {
+            return Err(ApiError::image_too_large(format!(
+                "Image is {}x{} pixels; the maximum width or height is {}",
+                width, height, self.max_image_dimension
+            )));
+        }
+        let pixels = u64::from(width) * u64::from(height);
+        if pixels > self.max_image_pixels {
+            return Err(ApiError::image_too_large(format!(
+                "Image has {} pixels; the maximum is {}",
+                pixels, self.max_image_pixels
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Validates the Content-Type of an uploaded file against the default policy
 ///
 /// Accepts:
 /// - image/* (image/jpeg, image/png, image/webp, etc.)
 /// - video/* (video/mp4, video/webm, etc.)
 /// - audio/* (audio/mpeg, audio/wav, etc.)
+/// - application/pdf (documents)
 /// - application/octet-stream (binary data)
 ///
 /// Returns an error if the Content-Type is not supported.
 pub fn validate_content_type(content_type: Option<&str>) -> Result<(), ApiError> {
-    match content_type {
-        Some(ct) => {
-            let ct_lower = ct.to_lowercase();
-            if ALLOWED_MIME_PREFIXES
-                .iter()
-                .any(|prefix| ct_lower.starts_with(prefix))
-            {
-                Ok(())
-            } else {
-                Err(ApiError::bad_request(format!(
-                    "Unsupported Content-Type: '{}'. Allowed types: image/*, video/*, audio/*, application/octet-stream",
-                    ct
-                )))
-            }
-        }
-        // Allow missing Content-Type (treat as binary)
-        None => Ok(()),
-    }
+    UploadPolicy::default().check_content_type(content_type)
 }
 
 /// Validates the size of an uploaded file
@@ -45,9 +282,9 @@ pub fn validate_content_type(content_type: Option<&str>) -> Result<(), ApiError>
 /// Returns an error if the file exceeds the maximum size.
 pub fn validate_file_size(size: usize, max_size: usize) -> Result<(), ApiError> {
     if size > max_size {
-        let max_mb = max_size / (1024 * 1024);
-        let actual_mb = size / (1024 * 1024);
-        Err(ApiError::bad_request(format!(
+        let max_mb = max_size / MB;
+        let actual_mb = size / MB;
+        Err(ApiError::payload_too_large(format!(
             "File too large: {} MB exceeds maximum of {} MB",
             actual_mb, max_mb
         )))
@@ -83,6 +320,7 @@ mod tests {
     #[test]
     fn test_validate_content_type_binary() {
         assert!(validate_content_type(Some("application/octet-stream")).is_ok());
+        assert!(validate_content_type(Some("application/pdf")).is_ok());
     }
 
     #[test]
@@ -111,4 +349,99 @@ mod tests {
         assert!(validate_file_size(max + 1, max).is_err());
         assert!(validate_file_size(20 * 1024 * 1024, max).is_err());
     }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        image::RgbImage::new(width, height)
+            .write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    fn file(data: Vec<u8>) -> FileField {
+        FileField {
+            detected_mime: crate::multipart::detect_mime(&data),
+            data,
+            spooled: None,
+            content_type: None,
+            file_name: None,
+        }
+    }
+
+    #[test]
+    fn test_policy_allowed_mime_patterns() {
+        let policy = UploadPolicy {
+            allowed_mime_types: vec!["image/*".into(), "application/pdf".into()],
+            ..UploadPolicy::default()
+        };
+        assert!(policy.check_content_type(Some("image/png")).is_ok());
+        assert!(policy
+            .check_content_type(Some("application/pdf; charset=binary"))
+            .is_ok());
+        assert!(policy.check_content_type(Some("video/mp4")).is_err());
+        assert!(policy.check_content_type(Some("imagefoo/png")).is_err());
+    }
+
+    #[test]
+    fn test_policy_per_media_type_size() {
+        let policy = UploadPolicy {
+            max_file_size: 100,
+            max_image_size: Some(10),
+            max_video_size: Some(1000),
+            ..UploadPolicy::default()
+        };
+        assert_eq!(policy.max_size_for(&MediaType::Image), 10);
+        assert_eq!(policy.max_size_for(&MediaType::Video), 100); // capped by max_file_size
+        assert_eq!(policy.max_size_for(&MediaType::Generic), 100);
+
+        let upload = file(vec![0u8; 50]);
+        assert!(policy.check_upload(&upload, &MediaType::Audio).is_ok());
+        let err = policy.check_upload(&upload, &MediaType::Image).unwrap_err();
+        assert!(matches!(err, ApiError::PayloadTooLarge(_)));
+    }
+
+    #[test]
+    fn test_policy_image_dimensions() {
+        let policy = UploadPolicy {
+            max_image_dimension: 32,
+            max_image_pixels: 500,
+            ..UploadPolicy::default()
+        };
+        assert!(policy
+            .check_upload(&file(png(16, 16)), &MediaType::Image)
+            .is_ok());
+
+        let too_wide = policy.check_upload(&file(png(64, 1)), &MediaType::Image);
+        assert!(matches!(too_wide, Err(ApiError::ImageTooLarge(_))));
+
+        let too_many_pixels = policy.check_upload(&file(png(30, 30)), &MediaType::Image);
+        assert!(matches!(too_many_pixels, Err(ApiError::ImageTooLarge(_))));
+
+        // Detected images are checked even when declared as generic content
+        let generic = policy.check_upload(&file(png(64, 1)), &MediaType::Generic);
+        assert!(matches!(generic, Err(ApiError::ImageTooLarge(_))));
+    }
+
+    #[test]
+    fn test_policy_skips_unreadable_images() {
+        let policy = UploadPolicy {
+            max_image_dimension: 1,
+            ..UploadPolicy::default()
+        };
+        assert!(policy
+            .check_upload(&file(vec![0xFF, 0xD8, 0xFF, 0x00]), &MediaType::Image)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_policy_from_json() {
+        let policy: UploadPolicy =
+            serde_json::from_str(r#"{"max_image_size": 1024, "max_image_dimension": 4096}"#)
+                .unwrap();
+        assert_eq!(policy.max_image_size, Some(1024));
+        assert_eq!(policy.max_image_dimension, 4096);
+        assert_eq!(policy.max_file_size, DEFAULT_MAX_FILE_SIZE);
+
+        assert!(serde_json::from_str::<UploadPolicy>(r#"{"max_size": 1}"#).is_err());
+    }
 }
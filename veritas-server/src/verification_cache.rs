@@ -0,0 +1,289 @@
+//! Cache of hot verifications
+//!
+//! Widely shared media gets verified over and over against the same seal.
+//! [`VerificationCache`] keeps, for a short time, the outcome of `/verify` for
+//! each (seal, content) pair together with the stored seal it matched, and the
+//! seal records served by public share links. Repeat requests then skip the
+//! ML-DSA check and the database lookups.
+//!
+//! The cache is per instance and bounded: least recently used entries are
+//! evicted beyond `VERIFICATION_CACHE_SIZE`, and entries expire after
+//! `VERIFICATION_CACHE_TTL_SECS`. Revoking or reinstating a seal drops its
+//! entries on the instance handling the request; other instances pick up
+//! the change when their entries expire.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sha3::{Digest, Sha3_256};
+use uuid::Uuid;
+use veritas_core::{ct_eq, ContentGrade, ContentVerificationResult, RegionComparison};
+
+use crate::db::Seal;
+
+/// Default number of entries kept per cache (verifications, seal records).
+pub const DEFAULT_VERIFICATION_CACHE_SIZE: usize = 10_000;
+
+/// Default seconds an entry is served before being checked again.
+pub const DEFAULT_VERIFICATION_CACHE_TTL_SECS: u64 = 60;
+
+/// A seal and the content verified against it, by SHA3-256 digest
+#[derive(Debug, Clone, Copy, Eq)]
+pub struct VerificationKey {
+    seal_digest: [u8; 32],
+    content_digest: [u8; 32],
+}
+
+impl VerificationKey {
+    /// Key for verifying `content` against the seal encoded as `seal_cbor`
+    pub fn new(seal_cbor: &[u8], content: &[u8]) -> Self {
+        Self {
+            seal_digest: Sha3_256::digest(seal_cbor).into(),
+            content_digest: Sha3_256::digest(content).into(),
+        }
+    }
+}
+
+impl PartialEq for VerificationKey {
+    fn eq(&self, other: &Self) -> bool {
+        // Digests of submitted content: compare without leaking a prefix match
+        ct_eq(&self.seal_digest, &other.seal_digest)
+            & ct_eq(&self.content_digest, &other.content_digest)
+    }
+}
+
+impl Hash for VerificationKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.seal_digest.hash(state);
+        self.content_digest.hash(state);
+    }
+}
+
+/// Outcome of a verification, as cached
+#[derive(Debug, Clone)]
+pub struct CachedVerification {
+    /// Result of the signature and content checks
+    pub result: ContentVerificationResult,
+    /// Graded content outcome (None when the signature failed)
+    pub grade: Option<ContentGrade>,
+    /// Per-region distances of a modified image to the sealed one
+    pub regions: Option<RegionComparison>,
+    /// Seal ID and owner of the matching stored seal (None = not issued here)
+    pub stored_seal: Option<(Uuid, Option<Uuid>)>,
+}
+
+/// Recent verification outcomes and public seal records
+pub struct VerificationCache {
+    verifications: Mutex<Lru<VerificationKey, CachedVerification>>,
+    seals: Mutex<Lru<Uuid, Seal>>,
+}
+
+impl VerificationCache {
+    /// Keep up to `capacity` entries of each kind for `ttl` (0 disables the cache)
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            verifications: Mutex::new(Lru::new(capacity, ttl)),
+            seals: Mutex::new(Lru::new(capacity, ttl)),
+        }
+    }
+
+    /// Cached outcome of verifying `key`
+    pub fn verification(&self, key: &VerificationKey) -> Option<CachedVerification> {
+        lock(&self.verifications).get(key, Instant::now())
+    }
+
+    /// Remember the outcome of verifying `key`
+    pub fn insert_verification(&self, key: VerificationKey, verification: CachedVerification) {
+        lock(&self.verifications).insert(key, verification, Instant::now());
+    }
+
+    /// Cached record of a stored seal
+    pub fn seal(&self, seal_id: Uuid) -> Option<Seal> {
+        lock(&self.seals).get(&seal_id, Instant::now())
+    }
+
+    /// Remember the record of a stored seal
+    pub fn insert_seal(&self, seal: Seal) {
+        lock(&self.seals).insert(seal.id, seal, Instant::now());
+    }
+
+    /// Drop everything cached about a seal, after its status changed
+    pub fn invalidate_seal(&self, seal_id: Uuid) {
+        lock(&self.seals).remove(&seal_id);
+        lock(&self.verifications)
+            .retain(|_, cached| cached.stored_seal.map(|(id, _)| id) != Some(seal_id));
+    }
+}
+
+impl Default for VerificationCache {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_VERIFICATION_CACHE_SIZE,
+            Duration::from_secs(DEFAULT_VERIFICATION_CACHE_TTL_SECS),
+        )
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Bounded map evicting the least recently used entry, with expiry
+struct Lru<K, V> {
+    capacity: usize,
+    ttl: Duration,
+    /// Incremented on every use; orders entries by recency
+    clock: u64,
+    entries: HashMap<K, LruEntry<V>>,
+    /// Last use of each entry, oldest first
+    recency: BTreeMap<u64, K>,
+}
+
+struct LruEntry<V> {
+    value: V,
+    inserted: Instant,
+    last_used: u64,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Lru<K, V> {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            clock: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: &K, now: Instant) -> Option<V> {
+        let entry = self.entries.get_mut(key)?;
+        if now.duration_since(entry.inserted) >= self.ttl {
+            self.remove(key);
+            return None;
+        }
+
+        self.clock += 1;
+        self.recency.remove(&entry.last_used);
+        self.recency.insert(self.clock, key.clone());
+        entry.last_used = self.clock;
+        Some(entry.value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V, now: Instant) {
+        if self.capacity == 0 || self.ttl.is_zero() {
+            return;
+        }
+
+        self.remove(&key);
+        while self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+
+        self.clock += 1;
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(
+            key,
+            LruEntry {
+                value,
+                inserted: now,
+                last_used: self.clock,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+        }
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(&K, &V) -> bool) {
+        let recency = &mut self.recency;
+        self.entries.retain(|key, entry| {
+            let kept = keep(key, &entry.value);
+            if !kept {
+                recency.remove(&entry.last_used);
+            }
+            kept
+        });
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let mut lru = Lru::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        lru.insert("a", 1, now);
+        lru.insert("b", 2, now);
+        assert_eq!(lru.get(&"a", now), Some(1));
+        lru.insert("c", 3, now);
+
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get(&"b", now), None);
+        assert_eq!(lru.get(&"a", now), Some(1));
+        assert_eq!(lru.get(&"c", now), Some(3));
+    }
+
+    #[test]
+    fn test_lru_entries_expire() {
+        let mut lru = Lru::new(8, Duration::from_secs(60));
+        let now = Instant::now();
+
+        lru.insert("a", 1, now);
+        assert_eq!(lru.get(&"a", now + Duration::from_secs(59)), Some(1));
+        assert_eq!(lru.get(&"a", now + Duration::from_secs(60)), None);
+        assert_eq!(lru.len(), 0);
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        let mut lru = Lru::new(0, Duration::from_secs(60));
+        lru.insert("a", 1, Instant::now());
+        assert_eq!(lru.len(), 0);
+    }
+
+    #[test]
+    fn test_invalidate_seal_drops_its_verifications() {
+        let cache = VerificationCache::default();
+        let (seal_id, other_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let cached = |stored_seal| CachedVerification {
+            result: ContentVerificationResult::Authentic,
+            grade: Some(ContentGrade::Authentic),
+            regions: None,
+            stored_seal,
+        };
+
+        let key = VerificationKey::new(b"seal", b"content");
+        let tampered = VerificationKey::new(b"seal", b"tampered content");
+        let other = VerificationKey::new(b"other seal", b"content");
+        let foreign = VerificationKey::new(b"foreign seal", b"content");
+        assert_ne!(key, tampered);
+
+        cache.insert_verification(key, cached(Some((seal_id, None))));
+        cache.insert_verification(tampered, cached(Some((seal_id, None))));
+        cache.insert_verification(other, cached(Some((other_id, None))));
+        cache.insert_verification(foreign, cached(None));
+
+        cache.invalidate_seal(seal_id);
+        assert!(cache.verification(&key).is_none());
+        assert!(cache.verification(&tampered).is_none());
+        assert!(cache.verification(&other).is_some());
+        assert!(cache.verification(&foreign).is_some());
+    }
+}
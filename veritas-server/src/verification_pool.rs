@@ -0,0 +1,116 @@
+//! Worker pool for CPU-bound verification
+//!
+//! Hashing uploads and checking ML-DSA signatures take milliseconds of CPU
+//! each. Run on the async runtime threads, a burst of `/verify` requests
+//! would stall every other request on those threads. [`VerificationPool`]
+//! runs that work on Tokio's blocking threads instead, at most `workers`
+//! jobs at a time (`VERIFICATION_WORKERS`, default: one per core); further
+//! jobs wait for a free worker.
+
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+
+use crate::error::ApiError;
+
+/// Default number of concurrent verification jobs: one per available core.
+pub fn default_verification_workers() -> usize {
+    std::thread::available_parallelism().map_or(4, |n| n.get())
+}
+
+/// Bounded pool of blocking verification jobs
+pub struct VerificationPool {
+    workers: usize,
+    permits: Arc<Semaphore>,
+}
+
+impl VerificationPool {
+    /// Run up to `workers` jobs at a time (at least 1)
+    pub fn new(workers: usize) -> Self {
+        let workers = workers.max(1);
+        Self {
+            workers,
+            permits: Arc::new(Semaphore::new(workers)),
+        }
+    }
+
+    /// Maximum number of concurrent jobs
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Run `job` on a blocking thread once a worker is free.
+    ///
+    /// The worker stays busy until the job finishes, even if the request that
+    /// submitted it is dropped.
+    pub async fn run<T, F>(&self, job: F) -> Result<T, ApiError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|_| ApiError::internal("Verification pool closed"))?;
+
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            job()
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!(error = %e, "Verification task failed");
+            ApiError::internal("Verification processing failed")
+        })
+    }
+}
+
+impl Default for VerificationPool {
+    fn default() -> Self {
+        Self::new(default_verification_workers())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pool_limits_concurrent_jobs() {
+        let pool = Arc::new(VerificationPool::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let jobs: Vec<_> = (0..8)
+            .map(|i| {
+                let (pool, running, peak) = (pool.clone(), running.clone(), peak.clone());
+                tokio::spawn(async move {
+                    pool.run(move || {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(20));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        i * 2
+                    })
+                    .await
+                })
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for job in jobs {
+            results.push(job.await.unwrap().unwrap());
+        }
+        assert_eq!(results, (0..8).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_pool_has_at_least_one_worker() {
+        assert_eq!(VerificationPool::new(0).workers(), 1);
+        assert!(VerificationPool::default().workers() >= 1);
+    }
+}
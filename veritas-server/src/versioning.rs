@@ -0,0 +1,225 @@
+//! API versioning and deprecation
+//!
+//! The public API lives under `/api/v{N}`. Every response names the API
+//! version that served it in `API-Version`; clients may send the header too,
+//! to pin the version they were written against, and get `400` once the
+//! server no longer serves it.
+//!
+//! Routes being phased out carry a [`Deprecation`]: their responses get a
+//! `Deprecation` header (RFC 9745), a `Sunset` header (RFC 8594) once their
+//! removal is scheduled, and a `Link` to the route replacing them. The
+//! unversioned routes of the first releases (`/seal`, `/verify`, `/resolve`,
+//! `/c2pa/*`, `/webauthn/*`) are kept as deprecated aliases of their
+//! `/api/v1` paths, see [`v1_with_legacy_aliases`].
+
+use axum::extract::{OriginalUri, Request, State};
+use axum::http::header::LINK;
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::{from_fn_with_state, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::MethodRouter;
+use axum::Router;
+use chrono::{DateTime, Utc};
+
+use crate::error::ApiError;
+
+/// Header naming the API version of a request or response
+pub const API_VERSION_HEADER: HeaderName = HeaderName::from_static("api-version");
+
+/// Header marking deprecated routes (RFC 9745)
+pub const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+
+/// Header announcing when a route goes away (RFC 8594)
+pub const SUNSET_HEADER: HeaderName = HeaderName::from_static("sunset");
+
+/// API version of the routes this server serves
+pub const CURRENT_API_VERSION: u32 = 1;
+
+/// API versions clients may pin
+pub const SUPPORTED_API_VERSIONS: &[u32] = &[1];
+
+/// Path prefix of version 1 of the API
+pub const API_V1: &str = "/api/v1";
+
+/// When the unversioned routes were deprecated (2026-10-16T00:00:00Z)
+pub const LEGACY_ROUTES_DEPRECATED_AT: i64 = 1_792_108_800;
+
+/// Deprecation of a group of routes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecation {
+    /// When the routes were deprecated (Unix seconds)
+    pub deprecated_at: i64,
+    /// When the routes are removed, if scheduled
+    pub sunset: Option<DateTime<Utc>>,
+    /// Prefix of the replacing routes: `/api/v1` points `/seal` to
+    /// `/api/v1/seal`
+    pub successor_prefix: Option<&'static str>,
+}
+
+impl Deprecation {
+    /// Deprecation of the unversioned routes, removed at `sunset` if set
+    pub fn legacy_routes(sunset: Option<DateTime<Utc>>) -> Self {
+        Self {
+            deprecated_at: LEGACY_ROUTES_DEPRECATED_AT,
+            sunset,
+            successor_prefix: Some(API_V1),
+        }
+    }
+
+    /// Add the deprecation headers to the response of a request for `path`
+    fn apply(&self, path: &str, response: &mut Response) {
+        let headers = response.headers_mut();
+        if let Ok(value) = HeaderValue::from_str(&format!("@{}", self.deprecated_at)) {
+            headers.insert(DEPRECATION_HEADER, value);
+        }
+        if let Some(sunset) = self.sunset {
+            let date = sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+            if let Ok(value) = HeaderValue::from_str(&date) {
+                headers.insert(SUNSET_HEADER, value);
+            }
+        }
+        if let Some(prefix) = self.successor_prefix {
+            let link = format!("<{prefix}{path}>; rel=\"successor-version\"");
+            if let Ok(value) = HeaderValue::from_str(&link) {
+                headers.append(LINK, value);
+            }
+        }
+    }
+}
+
+/// Middleware adding the headers of a [`Deprecation`] to responses
+pub async fn deprecated(
+    State(deprecation): State<Deprecation>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // Nested routers see their path without the nesting prefix
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| request.uri().path(), |uri| uri.path())
+        .to_string();
+    tracing::debug!(path = %path, "Deprecated route called");
+    let mut response = next.run(request).await;
+    deprecation.apply(&path, &mut response);
+    response
+}
+
+/// Middleware checking the `API-Version` a client pins, and naming the
+/// version that served each response
+pub async fn negotiate_version(request: Request, next: Next) -> Response {
+    let pinned = request
+        .headers()
+        .get(&API_VERSION_HEADER)
+        .map(|value| value.to_str().unwrap_or_default().trim().to_string());
+    let mut response = match pinned {
+        Some(pinned) if !is_supported_version(&pinned) => ApiError::bad_request(format!(
+            "Unsupported API version '{}', this server serves version {}",
+            pinned, CURRENT_API_VERSION
+        ))
+        .into_response(),
+        _ => next.run(request).await,
+    };
+    response
+        .headers_mut()
+        .insert(API_VERSION_HEADER, HeaderValue::from(CURRENT_API_VERSION));
+    response
+}
+
+/// Whether a pinned version (`1` or `v1`) is served
+fn is_supported_version(version: &str) -> bool {
+    version
+        .strip_prefix(['v', 'V'])
+        .unwrap_or(version)
+        .parse::<u32>()
+        .is_ok_and(|version| SUPPORTED_API_VERSIONS.contains(&version))
+}
+
+/// Router serving `routes` under `/api/v1`, and at their unversioned paths
+/// as aliases carrying the `deprecation` headers
+pub fn v1_with_legacy_aliases<S>(
+    routes: Vec<(&'static str, MethodRouter<S>)>,
+    deprecation: Deprecation,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    routes
+        .into_iter()
+        .fold(Router::new(), |router, (path, method_router)| {
+            router
+                .route(&format!("{API_V1}{path}"), method_router.clone())
+                .route(
+                    path,
+                    method_router.route_layer(from_fn_with_state(deprecation, deprecated)),
+                )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request as HttpRequest, StatusCode};
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    fn app(sunset: Option<DateTime<Utc>>) -> Router {
+        v1_with_legacy_aliases(
+            vec![("/ping", get(|| async { "pong" }))],
+            Deprecation::legacy_routes(sunset),
+        )
+        .layer(axum::middleware::from_fn(negotiate_version))
+    }
+
+    async fn call(app: Router, path: &str, version: Option<&str>) -> Response {
+        let mut request = HttpRequest::builder().uri(path);
+        if let Some(version) = version {
+            request = request.header(API_VERSION_HEADER, version);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_versioned_route_is_not_deprecated() {
+        let response = call(app(None), "/api/v1/ping", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[&API_VERSION_HEADER], "1");
+        assert!(response.headers().get(&DEPRECATION_HEADER).is_none());
+        assert!(response.headers().get(LINK).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_alias_carries_deprecation_headers() {
+        let response = call(app(None), "/ping", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[&DEPRECATION_HEADER], "@1792108800");
+        assert!(response.headers().get(&SUNSET_HEADER).is_none());
+        assert_eq!(
+            response.headers()[LINK],
+            "</api/v1/ping>; rel=\"successor-version\""
+        );
+
+        let sunset = "2027-04-01T00:00:00Z".parse().unwrap();
+        let response = call(app(Some(sunset)), "/ping", None).await;
+        assert_eq!(
+            response.headers()[&SUNSET_HEADER],
+            "Thu, 01 Apr 2027 00:00:00 GMT"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pinned_api_version() {
+        for version in ["1", "v1"] {
+            let response = call(app(None), "/api/v1/ping", Some(version)).await;
+            assert_eq!(response.status(), StatusCode::OK, "{version}");
+        }
+        for version in ["2", "latest"] {
+            let response = call(app(None), "/api/v1/ping", Some(version)).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{version}");
+            assert_eq!(response.headers()[&API_VERSION_HEADER], "1");
+        }
+    }
+}
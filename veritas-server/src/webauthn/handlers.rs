@@ -7,11 +7,14 @@ use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::mds::lookup_device_model;
-use super::storage::{StoredCredential, WebAuthnStorage};
+use super::storage::{
+    IssuedCaptureNonce, StoredCredential, WebAuthnStorage, CAPTURE_NONCE_EXPIRY_SECS,
+};
 use super::types::{
-    AttestationFormat, AuthenticatorType, DeviceAttestation, DeviceAttestationResponse,
-    FinishAuthenticationRequest, FinishRegistrationRequest, StartAuthenticationRequest,
-    StartAuthenticationResponse, StartRegistrationRequest, StartRegistrationResponse,
+    AttestationFormat, AuthenticatorType, CaptureSessionResponse, DeviceAttestation,
+    DeviceAttestationResponse, FinishAuthenticationRequest, FinishRegistrationRequest,
+    StartAuthenticationRequest, StartAuthenticationResponse, StartCaptureSessionRequest,
+    StartRegistrationRequest, StartRegistrationResponse,
 };
 use super::WebAuthnConfig;
 use crate::error::ApiError;
@@ -53,11 +56,11 @@ impl WebAuthnState {
 /// Returns a challenge that must be signed by the authenticator.
 #[utoipa::path(
     post,
-    path = "/webauthn/register/start",
+    path = "/api/v1/webauthn/register/start",
     tag = "WebAuthn",
     request_body = StartRegistrationRequest,
     responses(
-        (status = 200, description = "Registration challenge created (JSON with challenge_id and public_key options)"),
+        (status = 200, description = "Registration challenge created", body = StartRegistrationResponse),
         (status = 500, description = "Failed to generate challenge")
     )
 )]
@@ -101,9 +104,9 @@ pub async fn start_registration(
 /// Request body contains the WebAuthn `RegisterPublicKeyCredential` from the browser.
 #[utoipa::path(
     post,
-    path = "/webauthn/register/finish",
+    path = "/api/v1/webauthn/register/finish",
     tag = "WebAuthn",
-    request_body(content_type = "application/json", description = "WebAuthn registration response from browser"),
+    request_body(content = FinishRegistrationRequest, description = "WebAuthn registration response from browser"),
     responses(
         (status = 200, description = "Registration completed", body = DeviceAttestationResponse),
         (status = 400, description = "Invalid challenge or response"),
@@ -177,11 +180,11 @@ pub async fn finish_registration(
 /// Returns a challenge that must be signed by the authenticator.
 #[utoipa::path(
     post,
-    path = "/webauthn/authenticate/start",
+    path = "/api/v1/webauthn/authenticate/start",
     tag = "WebAuthn",
     request_body = StartAuthenticationRequest,
     responses(
-        (status = 200, description = "Authentication challenge created (JSON with challenge_id and public_key options)"),
+        (status = 200, description = "Authentication challenge created", body = StartAuthenticationResponse),
         (status = 404, description = "Credential not found"),
         (status = 500, description = "Failed to generate challenge")
     )
@@ -234,9 +237,9 @@ pub async fn start_authentication(
 /// Request body contains the WebAuthn `PublicKeyCredential` assertion from the browser.
 #[utoipa::path(
     post,
-    path = "/webauthn/authenticate/finish",
+    path = "/api/v1/webauthn/authenticate/finish",
     tag = "WebAuthn",
-    request_body(content_type = "application/json", description = "WebAuthn authentication assertion from browser"),
+    request_body(content = FinishAuthenticationRequest, description = "WebAuthn authentication assertion from browser"),
     responses(
         (status = 200, description = "Authentication completed", body = DeviceAttestationResponse),
         (status = 400, description = "Invalid challenge or response"),
@@ -297,6 +300,68 @@ pub async fn finish_authentication(
     Ok(Json(DeviceAttestationResponse { device_attestation }))
 }
 
+/// POST /webauthn/capture/session
+///
+/// Start a trusted capture session: issue a single-use nonce bound to the
+/// device credential. The app requests it right before capture and sends it
+/// as `capture_nonce` with the seal request, together with the device
+/// attestation of the same credential. The nonce is signed into the seal and
+/// expires after two minutes, so old media cannot be replayed through the
+/// trusted capture path.
+///
+/// The response also carries the session's liveness challenge: a code the
+/// app shows in frame (or writes into the frame metadata) and answers with
+/// `liveness` at seal time (see `veritas_core::liveness`).
+#[utoipa::path(
+    post,
+    path = "/api/v1/webauthn/capture/session",
+    tag = "WebAuthn",
+    request_body = StartCaptureSessionRequest,
+    responses(
+        (status = 200, description = "Capture nonce issued", body = CaptureSessionResponse),
+        (status = 400, description = "Credential not found"),
+        (status = 500, description = "Storage error")
+    )
+)]
+pub async fn start_capture_session(
+    State(state): State<Arc<WebAuthnState>>,
+    Json(req): Json<StartCaptureSessionRequest>,
+) -> Result<Json<CaptureSessionResponse>, ApiError> {
+    let known = state
+        .storage
+        .has_credential(&req.credential_id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Storage error: {:?}", e)))?;
+    if !known {
+        return Err(ApiError::bad_request("Credential not found"));
+    }
+
+    // 32 bytes from two random UUIDs (244 random bits)
+    let mut nonce = [0u8; 32];
+    nonce[..16].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    nonce[16..].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    let capture_nonce = hex::encode(nonce);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let issued_at = now.as_millis() as u64;
+    state.storage.store_capture_nonce(
+        capture_nonce.clone(),
+        IssuedCaptureNonce {
+            credential_id: req.credential_id.clone(),
+            issued_at,
+        },
+    );
+
+    tracing::info!(credential_id = %req.credential_id, "Capture session started");
+
+    Ok(Json(CaptureSessionResponse {
+        capture_nonce,
+        expires_at: now.as_secs() + CAPTURE_NONCE_EXPIRY_SECS,
+        liveness_code: veritas_core::liveness_code(&nonce),
+        issued_at_ms: issued_at,
+    }))
+}
+
 /// Base64url encode bytes
 fn base64_url_encode(bytes: &[u8]) -> String {
     use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
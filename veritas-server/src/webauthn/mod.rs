@@ -19,12 +19,13 @@ mod types;
 
 pub use config::WebAuthnConfig;
 pub use handlers::{
-    finish_authentication, finish_registration, start_authentication, start_registration,
-    WebAuthnState,
+    finish_authentication, finish_registration, start_authentication, start_capture_session,
+    start_registration, WebAuthnState,
 };
 pub use storage::{StorageError, StoredCredential, WebAuthnStorage};
 pub use types::{
-    AttestationFormat, AuthenticatorType, DeviceAttestation, DeviceAttestationResponse,
-    DeviceModel, FinishAuthenticationRequest, FinishRegistrationRequest,
-    StartAuthenticationRequest, StartRegistrationRequest,
+    AttestationFormat, AuthenticatorType, CaptureSessionResponse, DeviceAttestation,
+    DeviceAttestationResponse, DeviceModel, FinishAuthenticationRequest, FinishRegistrationRequest,
+    StartAuthenticationRequest, StartAuthenticationResponse, StartCaptureSessionRequest,
+    StartRegistrationRequest, StartRegistrationResponse,
 };
@@ -10,6 +10,9 @@ use webauthn_rs::prelude::*;
 /// Maximum age for challenge states (5 minutes)
 const CHALLENGE_EXPIRY_SECS: u64 = 300;
 
+/// Maximum age for capture nonces (2 minutes): capture follows the handshake
+pub const CAPTURE_NONCE_EXPIRY_SECS: u64 = 120;
+
 /// Registration state entry with expiration
 pub struct RegistrationStateEntry {
     pub state: PasskeyRegistration,
@@ -24,6 +27,21 @@ pub struct AuthStateEntry {
     pub credential_id: String,
 }
 
+/// Capture session nonce entry with expiration
+pub struct CaptureNonceEntry {
+    pub nonce: IssuedCaptureNonce,
+    pub expires_at: Instant,
+}
+
+/// What a capture nonce was issued for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssuedCaptureNonce {
+    /// Credential the nonce was issued to
+    pub credential_id: String,
+    /// When the nonce and its liveness challenge were issued (Unix timestamp ms)
+    pub issued_at: u64,
+}
+
 /// In-memory storage for temporary challenge states
 #[derive(Default)]
 pub struct ChallengeStore {
@@ -31,6 +49,8 @@ pub struct ChallengeStore {
     registration_states: DashMap<String, RegistrationStateEntry>,
     /// Pending authentication challenges (challenge_id -> state)
     authentication_states: DashMap<String, AuthStateEntry>,
+    /// Unused capture session nonces (hex nonce -> credential)
+    capture_nonces: DashMap<String, CaptureNonceEntry>,
 }
 
 impl ChallengeStore {
@@ -99,13 +119,48 @@ impl ChallengeStore {
         }
     }
 
-    /// Remove expired challenge states (called periodically)
-    pub fn cleanup_expired(&self) {
+    /// Store a capture session nonce issued to a credential
+    pub fn store_capture_nonce(&self, nonce: String, issued: IssuedCaptureNonce) {
+        self.capture_nonces.insert(
+            nonce,
+            CaptureNonceEntry {
+                nonce: issued,
+                expires_at: Instant::now() + Duration::from_secs(CAPTURE_NONCE_EXPIRY_SECS),
+            },
+        );
+    }
+
+    /// Retrieve and remove a capture session nonce, returning what it was
+    /// issued for
+    pub fn take_capture_nonce(&self, nonce: &str) -> Option<IssuedCaptureNonce> {
+        let (_, entry) = self.capture_nonces.remove(nonce)?;
+        if entry.expires_at > Instant::now() {
+            Some(entry.nonce)
+        } else {
+            None // Expired
+        }
+    }
+
+    /// Remove expired challenge states (called periodically), returning how
+    /// many were removed
+    pub fn cleanup_expired(&self) -> usize {
         let now = Instant::now();
+        let before = self.pending_count();
         self.registration_states
             .retain(|_, entry| entry.expires_at > now);
         self.authentication_states
             .retain(|_, entry| entry.expires_at > now);
+        self.capture_nonces
+            .retain(|_, entry| entry.expires_at > now);
+        // Saturating: challenges may be added concurrently
+        before.saturating_sub(self.pending_count())
+    }
+
+    /// Number of pending challenges and capture nonces
+    fn pending_count(&self) -> usize {
+        self.registration_states.len()
+            + self.authentication_states.len()
+            + self.capture_nonces.len()
     }
 
     /// Get number of pending registration challenges
@@ -117,6 +172,11 @@ impl ChallengeStore {
     pub fn authentication_count(&self) -> usize {
         self.authentication_states.len()
     }
+
+    /// Get number of unused capture nonces
+    pub fn capture_nonce_count(&self) -> usize {
+        self.capture_nonces.len()
+    }
 }
 
 impl std::fmt::Debug for ChallengeStore {
@@ -124,6 +184,7 @@ impl std::fmt::Debug for ChallengeStore {
         f.debug_struct("ChallengeStore")
             .field("registration_states", &self.registration_states.len())
             .field("authentication_states", &self.authentication_states.len())
+            .field("capture_nonces", &self.capture_nonces.len())
             .finish()
     }
 }
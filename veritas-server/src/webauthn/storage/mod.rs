@@ -12,14 +12,19 @@
 mod memory;
 mod postgres;
 
-pub use memory::ChallengeStore;
+pub use memory::{ChallengeStore, IssuedCaptureNonce, CAPTURE_NONCE_EXPIRY_SECS};
 pub use postgres::PostgresCredentialStore;
 
+use std::time::Duration;
+
 use dashmap::DashMap;
 use webauthn_rs::prelude::*;
 
 use super::types::DeviceAttestation;
 
+/// How often expired challenges are removed.
+pub const CHALLENGE_CLEANUP_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 /// Storage errors
 #[derive(Debug, thiserror::Error)]
 pub enum StorageError {
@@ -160,6 +165,17 @@ impl WebAuthnStorage {
         self.challenges.take_authentication_state(challenge_id)
     }
 
+    /// Store a capture session nonce issued to a credential
+    pub fn store_capture_nonce(&self, nonce: String, issued: IssuedCaptureNonce) {
+        self.challenges.store_capture_nonce(nonce, issued);
+    }
+
+    /// Retrieve and remove a capture session nonce, returning what it was
+    /// issued for (single use)
+    pub fn take_capture_nonce(&self, nonce: &str) -> Option<IssuedCaptureNonce> {
+        self.challenges.take_capture_nonce(nonce)
+    }
+
     // ==================== Credential Methods ====================
 
     /// Store a registered credential
@@ -237,9 +253,9 @@ impl WebAuthnStorage {
 
     // ==================== Maintenance ====================
 
-    /// Remove expired challenge states
-    pub fn cleanup_expired(&self) {
-        self.challenges.cleanup_expired();
+    /// Remove expired challenge states, returning how many were removed
+    pub fn cleanup_expired(&self) -> usize {
+        self.challenges.cleanup_expired()
     }
 
     /// Get storage statistics
@@ -252,6 +268,7 @@ impl WebAuthnStorage {
         StorageStats {
             registration_states: self.challenges.registration_count(),
             authentication_states: self.challenges.authentication_count(),
+            capture_nonces: self.challenges.capture_nonce_count(),
             credentials: credentials_count,
             persistent: self.is_persistent(),
         }
@@ -263,6 +280,7 @@ impl WebAuthnStorage {
 pub struct StorageStats {
     pub registration_states: usize,
     pub authentication_states: usize,
+    pub capture_nonces: usize,
     pub credentials: usize,
     pub persistent: bool,
 }
@@ -289,4 +307,18 @@ mod tests {
         let storage = WebAuthnStorage::in_memory();
         assert!(!storage.is_persistent());
     }
+
+    #[test]
+    fn test_capture_nonce_single_use() {
+        let storage = WebAuthnStorage::in_memory();
+        let issued = IssuedCaptureNonce {
+            credential_id: "credential-1".into(),
+            issued_at: 1_704_067_200_000,
+        };
+        storage.store_capture_nonce("ab".repeat(32), issued.clone());
+
+        assert_eq!(storage.take_capture_nonce(&"ab".repeat(32)), Some(issued));
+        assert!(storage.take_capture_nonce(&"ab".repeat(32)).is_none());
+        assert!(storage.take_capture_nonce("unknown").is_none());
+    }
 }
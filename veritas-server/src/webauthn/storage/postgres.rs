@@ -5,6 +5,8 @@
 use sqlx::PgPool;
 use webauthn_rs::prelude::Passkey;
 
+use crate::db::tenant::run_migrations;
+use crate::startup::MIGRATOR;
 use crate::webauthn::types::DeviceAttestation;
 
 use super::{StorageError, StoredCredential};
@@ -32,8 +34,7 @@ impl PostgresCredentialStore {
 
     /// Run database migrations
     pub async fn migrate(&self) -> Result<(), StorageError> {
-        sqlx::migrate!("./migrations")
-            .run(&self.pool)
+        run_migrations(&MIGRATOR, &self.pool)
             .await
             .map_err(|e| StorageError::Migration(e.to_string()))?;
 
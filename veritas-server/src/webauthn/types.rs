@@ -15,20 +15,22 @@ pub struct StartRegistrationRequest {
 }
 
 /// Response containing the registration challenge
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct StartRegistrationResponse {
     /// Challenge ID to track this registration
     pub challenge_id: String,
     /// WebAuthn credential creation options (to be passed to navigator.credentials.create)
+    #[schema(value_type = Object)]
     pub public_key: CreationChallengeResponse,
 }
 
 /// Request to complete device registration
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct FinishRegistrationRequest {
     /// Challenge ID from start_registration
     pub challenge_id: String,
     /// WebAuthn credential response from navigator.credentials.create
+    #[schema(value_type = Object)]
     pub response: RegisterPublicKeyCredential,
 }
 
@@ -41,23 +43,53 @@ pub struct StartAuthenticationRequest {
 }
 
 /// Response containing the authentication challenge
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct StartAuthenticationResponse {
     /// Challenge ID to track this authentication
     pub challenge_id: String,
     /// WebAuthn request options (to be passed to navigator.credentials.get)
+    #[schema(value_type = Object)]
     pub public_key: RequestChallengeResponse,
 }
 
 /// Request to complete device authentication
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct FinishAuthenticationRequest {
     /// Challenge ID from start_authentication
     pub challenge_id: String,
     /// WebAuthn assertion response from navigator.credentials.get
+    #[schema(value_type = Object)]
     pub response: PublicKeyCredential,
 }
 
+/// Request to open a trusted capture session
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartCaptureSessionRequest {
+    /// Credential ID of the registered capturing device
+    #[schema(example = "abc123...")]
+    pub credential_id: String,
+}
+
+/// Single-use nonce for the next capture of a device
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CaptureSessionResponse {
+    /// Nonce to send as `capture_nonce` with the seal request (hex, 32 bytes);
+    /// it is signed into the seal
+    #[schema(example = "9f2c...e41a")]
+    pub capture_nonce: String,
+    /// Unix timestamp after which the nonce is no longer accepted
+    #[schema(example = 1704067320)]
+    pub expires_at: u64,
+    /// Liveness challenge: code to show in frame or write into the frame
+    /// metadata, answered with `liveness` in the seal request
+    #[schema(example = "009384")]
+    pub liveness_code: String,
+    /// When the challenge was issued (Unix timestamp ms); the app reports
+    /// its answer time as this plus its own elapsed time
+    #[schema(example = 1704067200000_u64)]
+    pub issued_at_ms: u64,
+}
+
 /// Response containing device attestation
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DeviceAttestationResponse {
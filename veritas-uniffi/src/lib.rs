@@ -0,0 +1,307 @@
+//! Kotlin and Swift bindings for Veritas Q.
+//!
+//! The interface is defined in `src/veritas.udl` and exported with UniFFI,
+//! for the Android and iOS capture apps. It wraps the
+//! [`Veritas`](veritas_core::Veritas) facade: a [`VeritasClient`] seals with
+//! a chosen [`QrngProvider`] and a keypair that is ephemeral, given, or kept
+//! in a keypair file (keystore).
+//!
+//! Generate the sources with the bundled `uniffi-bindgen`, from the built
+//! library:
+//!
+//! ```text
+//! cargo build --release -p veritas-uniffi
+//! cargo run -p veritas-uniffi --features bindgen-cli --bin uniffi-bindgen -- \
+//!     generate --library target/release/libveritas_uniffi.so --language kotlin --out-dir out
+//! ```
+//!
+//! Calls are blocking: seals need a QRNG request over the network.
+
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::PublicKey as _;
+use veritas_core::client::ClientError;
+use veritas_core::qrng::{AnuQrngConfig, LfdQrngConfig, QrngProviderConfig, QrngProviderFactory};
+use veritas_core::{
+    ContentVerificationResult, KeypairFile, MediaType, Veritas, VeritasSeal, ZeroizingSecretKey,
+};
+
+uniffi::include_scaffolding!("veritas");
+
+/// Errors from every fallible call.
+///
+/// Only the variant and message cross to Kotlin (`VeritasException`) and
+/// Swift.
+#[derive(Debug, thiserror::Error)]
+pub enum VeritasError {
+    #[error("IO error: {0}")]
+    Io(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("Invalid seal: {0}")]
+    InvalidSeal(String),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("Keystore error: {0}")]
+    Keystore(String),
+    #[error("QRNG unavailable: {0}")]
+    QrngUnavailable(String),
+    #[error("Sealing failed: {0}")]
+    SealingFailed(String),
+}
+
+impl From<veritas_core::VeritasError> for VeritasError {
+    fn from(e: veritas_core::VeritasError) -> Self {
+        use veritas_core::VeritasError as Core;
+
+        let message = e.to_string();
+        match e {
+            Core::QrngError(_) | Core::HttpError(_) => Self::QrngUnavailable(message),
+            Core::SignatureError(_) | Core::AnchorError(_) | Core::PerceptualHashError(_) => {
+                Self::SealingFailed(message)
+            }
+            Core::InvalidDocument(_) | Core::InvalidDid(_) | Core::WatermarkError(_) => {
+                Self::InvalidArgument(message)
+            }
+            Core::VerificationFailed(_)
+            | Core::SerializationError(_)
+            | Core::InvalidSeal(_)
+            | Core::EntropyTimestampMismatch { .. }
+            | Core::SealTooLarge { .. }
+            | Core::UnsupportedSealVersion(..)
+            | Core::InvalidTimestamp { .. } => Self::InvalidSeal(message),
+        }
+    }
+}
+
+impl From<ClientError> for VeritasError {
+    fn from(e: ClientError) -> Self {
+        match e {
+            ClientError::Io(e) => Self::Io(e.to_string()),
+            ClientError::Veritas(e) => e.into(),
+            ClientError::Keystore(e) => Self::Keystore(e.to_string()),
+        }
+    }
+}
+
+type VeritasResult<T> = Result<T, VeritasError>;
+
+/// Entropy source for sealing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrngProvider {
+    Auto,
+    Lfd,
+    Anu,
+    Mock,
+}
+
+impl QrngProvider {
+    fn config(self) -> QrngProviderConfig {
+        match self {
+            Self::Auto => QrngProviderConfig::Auto,
+            Self::Lfd => QrngProviderConfig::Lfd(LfdQrngConfig::default()),
+            Self::Anu => QrngProviderConfig::Anu(AnuQrngConfig::default()),
+            Self::Mock => QrngProviderConfig::Mock { seed: 42 },
+        }
+    }
+}
+
+/// Outcome of verifying content against a seal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationOutcome {
+    Authentic,
+    ContentModified,
+    SignatureInvalid,
+}
+
+impl From<ContentVerificationResult> for VerificationOutcome {
+    fn from(result: ContentVerificationResult) -> Self {
+        match result {
+            ContentVerificationResult::Authentic => Self::Authentic,
+            ContentVerificationResult::ContentModified { .. } => Self::ContentModified,
+            ContentVerificationResult::SignatureFailed(_) => Self::SignatureInvalid,
+        }
+    }
+}
+
+/// ML-DSA-65 keypair, encoded as raw key bytes.
+pub struct Keypair {
+    pub public_key: Vec<u8>,
+    pub secret_key: Vec<u8>,
+}
+
+fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+fn generate_keypair() -> Keypair {
+    let (public_key, secret_key) = veritas_core::generate_keypair();
+    Keypair {
+        public_key: public_key.as_bytes().to_vec(),
+        secret_key: secret_key.as_bytes().to_vec(),
+    }
+}
+
+fn verify(content: Vec<u8>, seal: Vec<u8>) -> VeritasResult<VerificationOutcome> {
+    Ok(parse_seal(&seal)?.verify_content(&content)?.into())
+}
+
+/// Parse a seal in CBOR or JSON.
+fn parse_seal(bytes: &[u8]) -> VeritasResult<VeritasSeal> {
+    Ok(VeritasSeal::from_cbor(bytes).or_else(|_| VeritasSeal::from_json(bytes))?)
+}
+
+/// Seals and verifies content with one entropy source and signing keypair.
+pub struct VeritasClient {
+    inner: Veritas,
+    /// Drives the async core from blocking calls
+    runtime: tokio::runtime::Runtime,
+}
+
+impl VeritasClient {
+    /// Client signing with a new, ephemeral keypair.
+    pub fn new(qrng: QrngProvider) -> VeritasResult<Self> {
+        Self::build(Veritas::new()?, qrng)
+    }
+
+    /// Client signing with the given keypair.
+    pub fn with_keypair(keypair: Keypair, qrng: QrngProvider) -> VeritasResult<Self> {
+        let public_key = mldsa65::PublicKey::from_bytes(&keypair.public_key)
+            .map_err(|e| VeritasError::InvalidKey(format!("public key: {e}")))?;
+        let secret_key = ZeroizingSecretKey::from_bytes(&keypair.secret_key)
+            .map_err(|e| VeritasError::InvalidKey(e.to_string()))?;
+        // Checks that the keys belong together
+        let keypair = KeypairFile::new(public_key, secret_key)
+            .map_err(|e| VeritasError::InvalidKey(e.to_string()))?;
+        Self::build(
+            Veritas::with_keypair(keypair.public_key, keypair.secret_key)?,
+            qrng,
+        )
+    }
+
+    /// Client signing with the keypair file at `path`, created on first use.
+    pub fn with_keystore(
+        path: String,
+        passphrase: Option<String>,
+        qrng: QrngProvider,
+    ) -> VeritasResult<Self> {
+        Self::build(Veritas::with_keystore(path, passphrase.as_deref())?, qrng)
+    }
+
+    fn build(inner: Veritas, qrng: QrngProvider) -> VeritasResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| VeritasError::Io(format!("Failed to start runtime: {e}")))?;
+        let qrng = QrngProviderFactory::create(qrng.config())?;
+        Ok(Self {
+            inner: inner.with_qrng(qrng),
+            runtime,
+        })
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.inner.public_key().as_bytes().to_vec()
+    }
+
+    pub fn seal(&self, content: Vec<u8>, media_type: Option<String>) -> VeritasResult<Vec<u8>> {
+        let media_type = match media_type {
+            Some(name) => MediaType::parse(&name).ok_or_else(|| {
+                VeritasError::InvalidArgument(format!("Unknown media type: {name}"))
+            })?,
+            None => MediaType::Generic,
+        };
+        let seal = self
+            .runtime
+            .block_on(self.inner.seal_bytes(content, media_type))?;
+        Ok(seal.to_cbor()?)
+    }
+
+    pub fn seal_file(&self, path: String) -> VeritasResult<Vec<u8>> {
+        let seal = self.runtime.block_on(self.inner.seal_file(path))?;
+        Ok(seal.to_cbor()?)
+    }
+
+    pub fn verify(&self, content: Vec<u8>, seal: Vec<u8>) -> VeritasResult<VerificationOutcome> {
+        let seal = parse_seal(&seal)?;
+        Ok(self.inner.verify_bytes(&content, &seal)?.into())
+    }
+
+    pub fn verify_file(&self, path: String, seal: Vec<u8>) -> VeritasResult<VerificationOutcome> {
+        let seal = parse_seal(&seal)?;
+        Ok(self.inner.verify_file(path, &seal)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_verify() {
+        let client = VeritasClient::new(QrngProvider::Mock).unwrap();
+        let seal = client
+            .seal(b"pixels".to_vec(), Some("image/jpeg".into()))
+            .unwrap();
+        assert_eq!(
+            VeritasSeal::from_cbor(&seal).unwrap().public_key,
+            client.public_key()
+        );
+        assert_eq!(
+            client.verify(b"pixels".to_vec(), seal.clone()).unwrap(),
+            VerificationOutcome::Authentic
+        );
+        assert_eq!(
+            verify(b"edited".to_vec(), seal).unwrap(),
+            VerificationOutcome::ContentModified
+        );
+        assert!(matches!(
+            verify(b"pixels".to_vec(), b"not a seal".to_vec()),
+            Err(VeritasError::InvalidSeal(_))
+        ));
+        assert!(matches!(
+            client.seal(Vec::new(), Some("hologram".into())),
+            Err(VeritasError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_keypair_round_trip() {
+        let keypair = generate_keypair();
+        let public_key = keypair.public_key.clone();
+        let client = VeritasClient::with_keypair(keypair, QrngProvider::Mock).unwrap();
+        assert_eq!(client.public_key(), public_key);
+
+        // Keys of different keypairs are refused
+        let other = generate_keypair();
+        assert!(matches!(
+            VeritasClient::with_keypair(
+                Keypair {
+                    public_key,
+                    secret_key: other.secret_key,
+                },
+                QrngProvider::Mock,
+            ),
+            Err(VeritasError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_keystore_keeps_keypair() {
+        let path = std::env::temp_dir().join(format!("veritas-uniffi-{}.key", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        let _ = std::fs::remove_file(&path);
+
+        let first =
+            VeritasClient::with_keystore(path.clone(), Some("secret".into()), QrngProvider::Mock)
+                .unwrap();
+        let second =
+            VeritasClient::with_keystore(path.clone(), Some("secret".into()), QrngProvider::Mock)
+                .unwrap();
+        assert_eq!(first.public_key(), second.public_key());
+        assert!(matches!(
+            VeritasClient::with_keystore(path.clone(), None, QrngProvider::Mock),
+            Err(VeritasError::Keystore(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -2,11 +2,27 @@
 //!
 //! This module provides client-side verification of Veritas Seals
 //! directly in the browser without sending files to a server.
+//!
+//! Batches (`verify_batch_wasm`, `compute_phash_batch_wasm`) run on a Web
+//! Worker pool when built with the `parallel` feature and `initThreadPool`
+//! has been awaited; otherwise they run in order on the calling thread.
+//!
+//! Verification functions take an optional locale (`en`, `fr`, or a tag such
+//! as `navigator.language`); messages are in English when it is omitted or
+//! not supported. Results also carry a stable `code` for pages that show
+//! their own messages.
 
+use js_sys::Uint8Array;
 use serde::{Deserialize, Serialize};
-use veritas_core::{ContentVerificationResult as CoreVerificationResult, VeritasSeal};
+use veritas_core::{
+    ContentHash, ContentHasher, ContentVerificationResult as CoreVerificationResult, Locale,
+    MediaType, MessageCode, VeritasSeal,
+};
 use wasm_bindgen::prelude::*;
 
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
 /// Initialize panic hook for better error messages in browser console.
 #[wasm_bindgen(start)]
 pub fn init() {
@@ -31,8 +47,44 @@ pub struct VerificationResult {
     pub qrng_source: String,
     /// Media type
     pub media_type: String,
+    /// Stable code of the result (`content_authentic`, `content_modified`,
+    /// `invalid_signature`, ...), absent if the seal could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<MessageCode>,
+    /// Description of the result in the requested locale
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
     /// Error message if verification failed
     pub error: Option<String>,
+    /// Indices of altered segments (segmented seals only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tampered_segments: Option<Vec<u32>>,
+    /// Blockchain anchors claimed by the seal
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub anchors: Vec<AnchorResult>,
+}
+
+/// A blockchain anchor claimed by the seal.
+///
+/// The browser build has no network access, so anchors are listed as the
+/// seal records them with status `unchecked`; the server's `/verify` and
+/// `veritas verify --anchors` check them on their chains. The inclusion proof
+/// of a batched anchor is checked here, offline: status `mismatch` when the
+/// seal is not in the anchored batch.
+#[derive(Serialize, Deserialize)]
+pub struct AnchorResult {
+    /// Chain identifier (e.g. `solana-mainnet`, `bitcoin`)
+    pub chain: String,
+    /// Transaction ID (calendar URL for Bitcoin anchors)
+    pub tx_id: String,
+    /// Block height recorded in the seal, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_height: Option<u64>,
+    /// Merkle root anchored on chain (hex), for batched anchors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_root: Option<String>,
+    /// `unchecked`, or `mismatch` when the batch proof fails
+    pub status: String,
 }
 
 /// Verify a file against its Veritas seal.
@@ -40,67 +92,229 @@ pub struct VerificationResult {
 /// # Arguments
 /// * `file_bytes` - The original file content as bytes
 /// * `seal_bytes` - The seal file content (CBOR or JSON format)
+/// * `locale` - Language of the messages (`en`, `fr`), English if omitted
 ///
 /// # Returns
 /// A JSON string containing the verification result
 #[wasm_bindgen]
-pub fn verify_file_wasm(file_bytes: &[u8], seal_bytes: &[u8]) -> String {
-    match verify_internal(file_bytes, seal_bytes) {
-        Ok(result) => serde_json::to_string(&result).unwrap_or_else(|e| {
-            format!(r#"{{"valid":false,"error":"Serialization error: {}"}}"#, e)
-        }),
-        Err(e) => {
-            let result = VerificationResult {
-                valid: false,
-                content_matches: false,
-                timestamp: String::new(),
-                content_hash: String::new(),
-                expected_hash: String::new(),
-                qrng_source: String::new(),
-                media_type: String::new(),
-                error: Some(e),
-            };
-            serde_json::to_string(&result)
-                .unwrap_or_else(|_| r#"{"valid":false,"error":"Unknown error"}"#.to_string())
-        }
+pub fn verify_file_wasm(file_bytes: &[u8], seal_bytes: &[u8], locale: Option<String>) -> String {
+    let locale = parse_locale(locale.as_deref());
+    result_to_json(verify_internal(file_bytes, seal_bytes, locale))
+}
+
+/// Incremental verifier for files too large to hold in memory.
+///
+/// Feed the file in order with `update` (e.g. the chunks of
+/// `File.stream()`), then call `finalize` with the seal. Only the running
+/// hash is kept, so memory use does not grow with the file.
+///
+/// Streaming cannot recognise a re-saved PDF by its canonical form nor list
+/// tampered segments; use `verify_file_wasm` for those.
+#[wasm_bindgen]
+pub struct VerifierHandle {
+    hasher: ContentHasher,
+}
+
+/// Start a streaming verification.
+#[wasm_bindgen]
+pub fn create_verifier() -> VerifierHandle {
+    VerifierHandle {
+        hasher: ContentHasher::new(),
+    }
+}
+
+#[wasm_bindgen]
+impl VerifierHandle {
+    /// Hash the next chunk of the file.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// Number of bytes fed so far.
+    #[wasm_bindgen(getter)]
+    pub fn bytes_hashed(&self) -> f64 {
+        self.hasher.bytes_hashed() as f64
+    }
+
+    /// Verify the fed content against the seal (CBOR or JSON).
+    ///
+    /// Returns the same JSON as `verify_file_wasm`. The handle is consumed.
+    pub fn finalize(self, seal_bytes: &[u8], locale: Option<String>) -> String {
+        let locale = parse_locale(locale.as_deref());
+        result_to_json(verify_hash_internal(
+            self.hasher.finalize(),
+            seal_bytes,
+            locale,
+        ))
+    }
+}
+
+/// Verify many files against their seals (`files[i]` with `seals[i]`).
+///
+/// Returns a JSON array with one `verify_file_wasm` result per pair.
+#[wasm_bindgen]
+pub fn verify_batch_wasm(
+    files: Vec<Uint8Array>,
+    seals: Vec<Uint8Array>,
+    locale: Option<String>,
+) -> Result<String, JsError> {
+    let locale = parse_locale(locale.as_deref());
+    if files.len() != seals.len() {
+        return Err(JsError::new(&format!(
+            "{} files but {} seals",
+            files.len(),
+            seals.len()
+        )));
     }
+    // Copied out of JS memory so workers can read them
+    let pairs: Vec<(Vec<u8>, Vec<u8>)> = files
+        .iter()
+        .zip(&seals)
+        .map(|(file, seal)| (file.to_vec(), seal.to_vec()))
+        .collect();
+    let results = batch_map(&pairs, |(file, seal)| {
+        verify_internal(file, seal, locale).unwrap_or_else(error_result)
+    });
+    serde_json::to_string(&results).map_err(|e| JsError::new(&e.to_string()))
 }
 
-fn verify_internal(file_bytes: &[u8], seal_bytes: &[u8]) -> Result<VerificationResult, String> {
+/// Perceptual hash (hex) of an image, or `undefined` if it cannot be decoded.
+#[cfg(feature = "perceptual-hash")]
+#[wasm_bindgen]
+pub fn compute_phash_wasm(image_bytes: &[u8]) -> Option<String> {
+    veritas_core::compute_phash(image_bytes).map(hex::encode)
+}
+
+/// Perceptual hashes of many images, as a JSON array of hex strings (`null`
+/// for images that cannot be decoded).
+#[cfg(feature = "perceptual-hash")]
+#[wasm_bindgen]
+pub fn compute_phash_batch_wasm(images: Vec<Uint8Array>) -> Result<String, JsError> {
+    let images: Vec<Vec<u8>> = images.iter().map(Uint8Array::to_vec).collect();
+    let hashes = batch_map(&images, |image| compute_phash_wasm(image));
+    serde_json::to_string(&hashes).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Apply `f` to every item, on the worker pool with the `parallel` feature.
+fn batch_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.iter().map(f).collect()
+    }
+}
+
+/// Serialize a verification result, or an error as an invalid result.
+fn result_to_json(result: Result<VerificationResult, String>) -> String {
+    serde_json::to_string(&result.unwrap_or_else(error_result))
+        .unwrap_or_else(|e| format!(r#"{{"valid":false,"error":"Serialization error: {}"}}"#, e))
+}
+
+/// Invalid result carrying an error message.
+fn error_result(error: String) -> VerificationResult {
+    VerificationResult {
+        valid: false,
+        content_matches: false,
+        timestamp: String::new(),
+        content_hash: String::new(),
+        expected_hash: String::new(),
+        qrng_source: String::new(),
+        media_type: String::new(),
+        code: None,
+        message: None,
+        error: Some(error),
+        tampered_segments: None,
+        anchors: Vec::new(),
+    }
+}
+
+/// Requested locale, English if absent or unsupported
+fn parse_locale(locale: Option<&str>) -> Locale {
+    locale.and_then(Locale::parse).unwrap_or_default()
+}
+
+fn parse_seal(seal_bytes: &[u8]) -> Result<VeritasSeal, String> {
     // Try to parse seal (CBOR first, then JSON)
-    let seal: VeritasSeal = VeritasSeal::from_cbor(seal_bytes)
-        .or_else(|_| serde_json::from_slice(seal_bytes).map_err(|e| e.to_string()))
-        .map_err(|e| format!("Failed to parse seal: {}", e))?;
+    VeritasSeal::from_cbor(seal_bytes)
+        .or_else(|_| VeritasSeal::from_json(seal_bytes))
+        .map_err(|e| format!("Failed to parse seal: {}", e))
+}
+
+fn verify_internal(
+    file_bytes: &[u8],
+    seal_bytes: &[u8],
+    locale: Locale,
+) -> Result<VerificationResult, String> {
+    let seal = parse_seal(seal_bytes)?;
 
     // Verify signature and content in one call
     let result = seal
         .verify_content(file_bytes)
         .map_err(|e| format!("Verification error: {}", e))?;
+    Ok(verification_result(&seal, &result, locale))
+}
+
+fn verify_hash_internal(
+    content_hash: ContentHash,
+    seal_bytes: &[u8],
+    locale: Locale,
+) -> Result<VerificationResult, String> {
+    let seal = parse_seal(seal_bytes)?;
+    let result = seal
+        .verify_content_hash(&content_hash.crypto_hash)
+        .map_err(|e| format!("Verification error: {}", e))?;
+    Ok(verification_result(&seal, &result, locale))
+}
 
+fn verification_result(
+    seal: &VeritasSeal,
+    result: &CoreVerificationResult,
+    locale: Locale,
+) -> VerificationResult {
     // Format timestamp
     let timestamp = format_timestamp(seal.capture_timestamp_utc);
 
     // Format QRNG source
     let qrng_source = format!("{:?}", seal.qrng_source);
 
-    // Format media type
-    let media_type = format!("{:?}", seal.media_type);
+    // Format media type (custom types as their MIME type)
+    let media_type = match &seal.media_type {
+        MediaType::Custom(mime) => mime.clone(),
+        other => format!("{:?}", other),
+    };
 
     // Map core result to wasm result
-    let (valid, content_matches, actual_hash, error) = match &result {
-        CoreVerificationResult::Authentic => (true, true, seal.content_hash.crypto_hash, None),
-        CoreVerificationResult::ContentModified { actual_hash, .. } => {
-            (false, false, *actual_hash, None)
+    let (valid, content_matches, actual_hash, error, tampered_segments) = match result {
+        CoreVerificationResult::Authentic => {
+            (true, true, seal.content_hash.crypto_hash, None, None)
         }
+        CoreVerificationResult::ContentModified {
+            actual_hash,
+            tampered_segments,
+            ..
+        } => (
+            false,
+            false,
+            *actual_hash,
+            None,
+            tampered_segments
+                .as_ref()
+                .map(|segments| segments.iter().map(|s| s.index).collect()),
+        ),
         CoreVerificationResult::SignatureFailed(sig_result) => (
             false,
             true, // Content check skipped when signature fails
             seal.content_hash.crypto_hash,
-            Some(sig_result.description().to_string()),
+            Some(sig_result.description_in(locale).to_string()),
+            None,
         ),
     };
 
-    Ok(VerificationResult {
+    VerificationResult {
         valid,
         content_matches,
         timestamp,
@@ -108,8 +322,25 @@ fn verify_internal(file_bytes: &[u8], seal_bytes: &[u8]) -> Result<VerificationR
         expected_hash: hex::encode(seal.content_hash.crypto_hash),
         qrng_source,
         media_type,
+        code: Some(result.code()),
+        message: Some(result.description_in(locale)),
         error,
-    })
+        tampered_segments,
+        anchors: seal
+            .anchors()
+            .map(|anchor| AnchorResult {
+                chain: anchor.chain.clone(),
+                tx_id: anchor.tx_id.clone(),
+                block_height: Some(anchor.block_height).filter(|&height| height > 0),
+                batch_root: anchor.batch.as_ref().map(|batch| hex::encode(batch.root)),
+                status: match anchor.committed_digest(seal) {
+                    Some(_) => "unchecked",
+                    None => "mismatch",
+                }
+                .to_string(),
+            })
+            .collect(),
+    }
 }
 
 fn format_timestamp(timestamp_ms: u64) -> String {